@@ -0,0 +1,265 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::client::{ApiError, HttpClient};
+use crate::health::HealthCheck;
+
+/// Client for the Bazarr API (`/api`).
+///
+/// Bazarr keeps its Sonarr and Radarr connections, subtitle providers, and
+/// language profiles in one settings document, read with
+/// `GET /api/system/settings` and changed by posting form fields named
+/// `settings-{section}-{key}` back to the same path. Fields not posted keep
+/// their value, except `languages-profiles`: profiles left out of it are
+/// deleted.
+#[derive(Debug, Clone)]
+pub struct BazarrClient {
+    http: HttpClient,
+}
+
+/// The parts of Bazarr's settings the operator manages.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BazarrSettings {
+    #[serde(default)]
+    pub general: BazarrGeneralSettings,
+    #[serde(default)]
+    pub sonarr: BazarrConnection,
+    #[serde(default)]
+    pub radarr: BazarrConnection,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BazarrGeneralSettings {
+    #[serde(default)]
+    pub use_sonarr: bool,
+    #[serde(default)]
+    pub use_radarr: bool,
+    #[serde(default)]
+    pub enabled_providers: Vec<String>,
+    #[serde(default)]
+    pub serie_default_enabled: bool,
+    #[serde(default, deserialize_with = "profile_id")]
+    pub serie_default_profile: Option<i64>,
+    #[serde(default)]
+    pub movie_default_enabled: bool,
+    #[serde(default, deserialize_with = "profile_id")]
+    pub movie_default_profile: Option<i64>,
+}
+
+/// Bazarr's connection to Sonarr or Radarr.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct BazarrConnection {
+    #[serde(default)]
+    pub ip: String,
+    #[serde(default)]
+    pub port: u16,
+    #[serde(default)]
+    pub base_url: String,
+    #[serde(default)]
+    pub ssl: bool,
+    #[serde(default)]
+    pub apikey: String,
+}
+
+/// A Bazarr language profile. Fields the operator doesn't manage, such as
+/// `mustContain`, are carried in `extra` so posting a profile back keeps
+/// them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BazarrLanguageProfile {
+    pub profile_id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub cutoff: Option<i64>,
+    #[serde(default)]
+    pub items: Vec<BazarrProfileItem>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A language of a profile. Bazarr stores the flags as the strings
+/// `"True"` and `"False"`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BazarrProfileItem {
+    pub id: i64,
+    pub language: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Bazarr stores an unset default profile as an empty string, and a set one
+/// as a number or a numeric string depending on the version.
+fn profile_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+    Ok(match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::Number(n) => n.as_i64(),
+        serde_json::Value::String(s) => s.parse().ok(),
+        _ => None,
+    })
+}
+
+impl BazarrClient {
+    /// Create a new Bazarr client.
+    ///
+    /// `base_url` should be the root URL (e.g. `http://bazarr:6767`).
+    /// `api_key` is sent as the `X-Api-Key` header.
+    pub fn new(base_url: &str, api_key: &str) -> Result<Self, ApiError> {
+        let url = format!("{}/api/", base_url.trim_end_matches('/'));
+        Ok(Self {
+            http: HttpClient::new(&url, Some(api_key))?,
+        })
+    }
+
+    /// Read the settings document.
+    pub async fn settings(&self) -> Result<BazarrSettings, ApiError> {
+        crate::faults::api("bazarr", "settings").await?;
+        self.http.get("system/settings").await
+    }
+
+    /// List the language profiles.
+    pub async fn language_profiles(&self) -> Result<Vec<BazarrLanguageProfile>, ApiError> {
+        crate::faults::api("bazarr", "language_profiles").await?;
+        self.http.get("system/languages/profiles").await
+    }
+
+    /// Post settings form fields. A key may repeat for list settings such
+    /// as `settings-general-enabled_providers`.
+    pub async fn update_settings(&self, form: &[(String, String)]) -> Result<(), ApiError> {
+        crate::faults::api("bazarr", "update_settings").await?;
+        let url = self.http.base_url().join("system/settings")?;
+        let body = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(form)
+            .finish();
+        let resp = self
+            .http
+            .inner()
+            .post(url)
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .body(body)
+            .send()
+            .await?;
+        if resp.status().is_success() {
+            Ok(())
+        } else {
+            let status = resp.status().as_u16();
+            let body = resp.text().await.unwrap_or_default();
+            Err(ApiError::ApiResponse { status, body })
+        }
+    }
+}
+
+impl HealthCheck for BazarrClient {
+    async fn is_healthy(&self) -> Result<bool, ApiError> {
+        crate::faults::api("bazarr", "is_healthy").await?;
+        let url = self.http.base_url().join("system/status")?;
+        let resp = self.http.inner().get(url).send().await?;
+        Ok(resp.status().is_success())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::{body_string, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn settings_parse_default_profiles() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/system/settings"))
+            .and(header("x-api-key", "k"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "general": {
+                    "use_sonarr": true,
+                    "enabled_providers": ["podnapisi"],
+                    "serie_default_enabled": true,
+                    "serie_default_profile": "2",
+                    "movie_default_profile": ""
+                },
+                "sonarr": {"ip": "sonarr.media.svc", "port": 8989, "apikey": "s"},
+                "notifications": {}
+            })))
+            .mount(&server)
+            .await;
+        let settings = BazarrClient::new(&server.uri(), "k")
+            .unwrap()
+            .settings()
+            .await
+            .unwrap();
+        assert!(settings.general.use_sonarr);
+        assert!(!settings.general.use_radarr);
+        assert_eq!(settings.general.enabled_providers, ["podnapisi"]);
+        assert_eq!(settings.general.serie_default_profile, Some(2));
+        assert_eq!(settings.general.movie_default_profile, None);
+        assert_eq!(settings.sonarr.port, 8989);
+        assert_eq!(settings.radarr, BazarrConnection::default());
+    }
+
+    #[tokio::test]
+    async fn language_profiles_keep_unmanaged_fields() {
+        let profile = serde_json::json!({
+            "profileId": 1,
+            "name": "English",
+            "cutoff": null,
+            "items": [{"id": 1, "language": "en", "hi": "False", "forced": "False"}],
+            "mustContain": ["x"],
+            "originalFormat": false
+        });
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/system/languages/profiles"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([profile])))
+            .mount(&server)
+            .await;
+        let profiles = BazarrClient::new(&server.uri(), "k")
+            .unwrap()
+            .language_profiles()
+            .await
+            .unwrap();
+        assert_eq!(profiles[0].items[0].language, "en");
+        assert_eq!(serde_json::to_value(&profiles[0]).unwrap(), profile);
+    }
+
+    #[tokio::test]
+    async fn update_settings_posts_a_form() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/system/settings"))
+            .and(header("content-type", "application/x-www-form-urlencoded"))
+            .and(body_string(
+                "settings-general-enabled_providers=podnapisi\
+                 &settings-general-enabled_providers=opensubtitlescom\
+                 &settings-sonarr-base_url=%2F",
+            ))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let form = [
+            ("settings-general-enabled_providers", "podnapisi"),
+            ("settings-general-enabled_providers", "opensubtitlescom"),
+            ("settings-sonarr-base_url", "/"),
+        ]
+        .map(|(k, v)| (k.to_string(), v.to_string()));
+        BazarrClient::new(&server.uri(), "k")
+            .unwrap()
+            .update_settings(&form)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn is_healthy_checks_status() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/system/status"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+        let client = BazarrClient::new(&server.uri(), "wrong").unwrap();
+        assert!(!client.is_healthy().await.unwrap());
+    }
+}
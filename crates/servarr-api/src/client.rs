@@ -60,6 +60,18 @@ impl HttpClient {
         Self::handle_response(resp).await
     }
 
+    /// GET `{base_url}/{path}` and return the raw response body.
+    pub async fn get_bytes(&self, path: &str) -> Result<Vec<u8>, ApiError> {
+        let url = self.base_url.join(path)?;
+        let resp = self.inner.get(url).send().await?;
+        if !resp.status().is_success() {
+            let status = resp.status().as_u16();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(ApiError::ApiResponse { status, body });
+        }
+        Ok(resp.bytes().await?.to_vec())
+    }
+
     /// POST `{base_url}/{path}` with a JSON body and deserialize the response.
     pub async fn post<T: DeserializeOwned, B: Serialize>(
         &self,
@@ -0,0 +1,244 @@
+//! Fault injection for integration tests of error handling.
+//!
+//! With the `fault-injection` feature, every client call and every item of a
+//! controller's sync pass first checks the rules in the `SERVARR_FAULTS`
+//! environment variable, so a test can make an app return 500s, answer
+//! slowly, or have part of a sync fail without scripting a mock server for
+//! each case. Without the feature the hooks compile to nothing.
+//!
+//! Rules are comma-separated, each `<scope>:<target>=<fault>[*<count>]`:
+//!
+//! - `api:<client>[.<method>]` matches calls on a client (`sonarr`,
+//!   `prowlarr`, `transmission`, ...), optionally one method of it.
+//! - `sync:<task>[.<item>]` matches items of a sync pass (`prowlarr-apps`,
+//!   `overseerr-servers`, `download-clients`, `root-folders`, `indexers`,
+//!   `media-server-users`), optionally one item by name.
+//! - `*` matches any client or task.
+//! - The fault is `error` (status 500), `error:<status>`, or
+//!   `delay:<millis>`. Sync items only honor `error`.
+//! - `*<count>` limits the rule to its first `count` matches, to simulate a
+//!   transient failure.
+//!
+//! For example `api:sonarr=error:503*2,api:prowlarr.list_indexers=delay:3000,
+//! sync:download-clients.media-sabnzbd=error`.
+
+use std::time::Duration;
+
+use crate::client::ApiError;
+
+/// Environment variable holding the rules.
+pub const ENV: &str = "SERVARR_FAULTS";
+
+/// What a matching rule does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fault {
+    /// Fail with this HTTP status.
+    Error(u16),
+    /// Wait this long before making the call.
+    Delay(Duration),
+}
+
+/// One rule of `SERVARR_FAULTS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    /// `api` or `sync`.
+    pub scope: String,
+    /// Client or task, or `*`.
+    pub target: String,
+    /// Method or item, or `None` for all of them.
+    pub item: Option<String>,
+    pub fault: Fault,
+    /// How many matches the rule applies to, or `None` for all.
+    pub count: Option<u32>,
+}
+
+impl Rule {
+    /// Whether the rule covers `item` of `target` in `scope`.
+    pub fn matches(&self, scope: &str, target: &str, item: &str) -> bool {
+        self.scope == scope
+            && (self.target == "*" || self.target == target)
+            && self.item.as_deref().is_none_or(|i| i == item)
+    }
+}
+
+/// Parse a `SERVARR_FAULTS` value.
+pub fn parse(spec: &str) -> Result<Vec<Rule>, String> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .map(|rule| {
+            let (selector, fault) = rule
+                .split_once('=')
+                .ok_or_else(|| format!("fault rule '{rule}' has no '='"))?;
+            let (scope, target) = selector
+                .split_once(':')
+                .filter(|(scope, _)| matches!(*scope, "api" | "sync"))
+                .ok_or_else(|| format!("fault rule '{rule}' must start with api: or sync:"))?;
+            let (target, item) = match target.split_once('.') {
+                Some((target, item)) => (target, Some(item.to_string())),
+                None => (target, None),
+            };
+            let (fault, count) = match fault.split_once('*') {
+                Some((fault, count)) => (
+                    fault,
+                    Some(
+                        count
+                            .parse()
+                            .map_err(|_| format!("fault rule '{rule}' has a bad count"))?,
+                    ),
+                ),
+                None => (fault, None),
+            };
+            let fault = match fault.split_once(':') {
+                None if fault == "error" => Fault::Error(500),
+                Some(("error", status)) => Fault::Error(
+                    status
+                        .parse()
+                        .map_err(|_| format!("fault rule '{rule}' has a bad status"))?,
+                ),
+                Some(("delay", millis)) => Fault::Delay(Duration::from_millis(
+                    millis
+                        .parse()
+                        .map_err(|_| format!("fault rule '{rule}' has a bad delay"))?,
+                )),
+                _ => return Err(format!("fault rule '{rule}' has an unknown fault")),
+            };
+            Ok(Rule {
+                scope: scope.to_string(),
+                target: target.to_string(),
+                item,
+                fault,
+                count,
+            })
+        })
+        .collect()
+}
+
+/// The error an injected `Error` fault returns.
+pub fn error(status: u16) -> ApiError {
+    ApiError::ApiResponse {
+        status,
+        body: "injected fault".to_string(),
+    }
+}
+
+#[cfg(feature = "fault-injection")]
+mod active {
+    use std::collections::HashMap;
+    use std::sync::{LazyLock, Mutex};
+
+    use super::{ENV, Fault, Rule, parse};
+
+    /// Matches so far per rule, for rules with a count.
+    static HITS: LazyLock<Mutex<HashMap<String, u32>>> = LazyLock::new(Default::default);
+
+    /// The faults that apply to this call, read from the environment each
+    /// time so a test can change them between cases.
+    pub fn lookup(scope: &str, target: &str, item: &str) -> Vec<Fault> {
+        let Ok(spec) = std::env::var(ENV) else {
+            return Vec::new();
+        };
+        let rules = match parse(&spec) {
+            Ok(rules) => rules,
+            Err(e) => {
+                tracing::warn!(error = %e, "ignoring {ENV}");
+                return Vec::new();
+            }
+        };
+        let mut hits = HITS.lock().unwrap_or_else(|e| e.into_inner());
+        rules
+            .into_iter()
+            .filter(|rule| rule.matches(scope, target, item))
+            .filter(|rule: &Rule| match rule.count {
+                Some(count) => {
+                    let key = format!("{spec}#{rule:?}");
+                    let hit = hits.entry(key).or_default();
+                    *hit += 1;
+                    *hit <= count
+                }
+                None => true,
+            })
+            .map(|rule| rule.fault)
+            .collect()
+    }
+}
+
+/// Apply the faults for a call of `client`'s `method`: wait out delays,
+/// then fail if an error fault matches.
+#[cfg(feature = "fault-injection")]
+pub async fn api(client: &str, method: &str) -> Result<(), ApiError> {
+    for fault in active::lookup("api", client, method) {
+        match fault {
+            Fault::Delay(delay) => tokio::time::sleep(delay).await,
+            Fault::Error(status) => {
+                tracing::info!(client, method, status, "injecting API fault");
+                return Err(error(status));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Apply the faults for a call of `client`'s `method`: wait out delays,
+/// then fail if an error fault matches.
+#[cfg(not(feature = "fault-injection"))]
+#[inline(always)]
+pub async fn api(_client: &str, _method: &str) -> Result<(), ApiError> {
+    Ok(())
+}
+
+/// The error to report for `item` of sync `task`, if a rule fails it.
+#[cfg(feature = "fault-injection")]
+pub fn sync(task: &str, item: &str) -> Option<ApiError> {
+    active::lookup("sync", task, item)
+        .into_iter()
+        .find_map(|fault| match fault {
+            Fault::Error(status) => {
+                tracing::info!(task, item, status, "injecting sync fault");
+                Some(error(status))
+            }
+            Fault::Delay(_) => None,
+        })
+}
+
+/// The error to report for `item` of sync `task`, if a rule fails it.
+#[cfg(not(feature = "fault-injection"))]
+#[inline(always)]
+pub fn sync(_task: &str, _item: &str) -> Option<ApiError> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rules() {
+        let rules = parse(
+            "api:sonarr=error:503*2, api:prowlarr.list_indexers=delay:3000,sync:*.media-sabnzbd=error",
+        )
+        .unwrap();
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[0].fault, Fault::Error(503));
+        assert_eq!(rules[0].count, Some(2));
+        assert_eq!(rules[1].item.as_deref(), Some("list_indexers"));
+        assert_eq!(rules[1].fault, Fault::Delay(Duration::from_secs(3)));
+        assert_eq!(rules[2].fault, Fault::Error(500));
+
+        assert!(rules[0].matches("api", "sonarr", "health"));
+        assert!(!rules[0].matches("api", "radarr", "health"));
+        assert!(!rules[1].matches("api", "prowlarr", "list_tags"));
+        assert!(rules[2].matches("sync", "download-clients", "media-sabnzbd"));
+        assert!(!rules[2].matches("api", "sabnzbd", "media-sabnzbd"));
+    }
+
+    #[test]
+    fn rejects_malformed_rules() {
+        assert!(parse("").unwrap().is_empty());
+        assert!(parse("sonarr=error").is_err());
+        assert!(parse("api:sonarr").is_err());
+        assert!(parse("api:sonarr=explode").is_err());
+        assert!(parse("api:sonarr=error:abc").is_err());
+        assert!(parse("api:sonarr=error*x").is_err());
+    }
+}
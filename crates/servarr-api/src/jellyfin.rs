@@ -18,6 +18,43 @@ pub struct JellyfinClient {
 pub struct JellyfinUser {
     pub id: String,
     pub name: String,
+    /// The user's policy object, kept whole so it can be sent back with
+    /// only the managed keys changed.
+    #[serde(default)]
+    pub policy: serde_json::Value,
+}
+
+/// A Jellyfin library (virtual folder).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct JellyfinLibrary {
+    pub name: String,
+    /// ID used in a user policy's `EnabledFolders`.
+    pub item_id: String,
+}
+
+/// A Jellyfin API key (`GET /Auth/Keys`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct JellyfinApiKey {
+    pub access_token: String,
+    #[serde(default)]
+    pub app_name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ApiKeyList {
+    #[serde(default)]
+    items: Vec<JellyfinApiKey>,
+}
+
+/// Jellyfin create-user request body.
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct CreateUserRequest<'a> {
+    name: &'a str,
+    password: &'a str,
 }
 
 /// Jellyfin auth response.
@@ -115,6 +152,7 @@ impl JellyfinClient {
     ///
     /// Calls `GET /Startup/Configuration`; a 200 response means the wizard is pending.
     pub async fn startup_pending(&self) -> Result<bool, ApiError> {
+        crate::faults::api("jellyfin", "startup_pending").await?;
         let url = self.http.base_url().join("/Startup/Configuration")?;
         let resp = self
             .http
@@ -153,6 +191,7 @@ impl JellyfinClient {
 
     /// Set the initial admin user via the startup wizard (`POST /Startup/User`).
     pub async fn startup_set_user(&self, username: &str, password: &str) -> Result<(), ApiError> {
+        crate::faults::api("jellyfin", "startup_set_user").await?;
         let url = self.http.base_url().join("/Startup/User")?;
         let body = StartupUserRequest {
             name: username,
@@ -250,6 +289,7 @@ impl JellyfinClient {
 
     /// Authenticate as a user and return the access token.
     pub async fn authenticate(&self, username: &str, password: &str) -> Result<String, ApiError> {
+        crate::faults::api("jellyfin", "authenticate").await?;
         let url = self.http.base_url().join("/Users/AuthenticateByName")?;
         let body = AuthenticateRequest {
             username,
@@ -276,6 +316,7 @@ impl JellyfinClient {
 
     /// List all users (requires an authenticated token).
     pub async fn list_users(&self, token: &str) -> Result<Vec<JellyfinUser>, ApiError> {
+        crate::faults::api("jellyfin", "list_users").await?;
         let url = self.http.base_url().join("/Users")?;
         let resp = self
             .http
@@ -306,6 +347,7 @@ impl JellyfinClient {
         user_id: &str,
         new_password: &str,
     ) -> Result<(), ApiError> {
+        crate::faults::api("jellyfin", "set_password").await?;
         let url = self
             .http
             .base_url()
@@ -336,6 +378,134 @@ impl JellyfinClient {
         }
     }
 
+    /// Send `req` with `token` in the authorization header, failing on a
+    /// non-2xx response.
+    async fn send_authed(
+        &self,
+        req: reqwest::RequestBuilder,
+        token: &str,
+    ) -> Result<reqwest::Response, ApiError> {
+        let resp = req
+            .header(
+                "X-Emby-Authorization",
+                format!("{JELLYFIN_AUTH_HEADER}, Token=\"{token}\""),
+            )
+            .send()
+            .await
+            .map_err(ApiError::Request)?;
+        if resp.status().is_success() {
+            Ok(resp)
+        } else {
+            let status = resp.status().as_u16();
+            let body = resp.text().await.unwrap_or_default();
+            Err(ApiError::ApiResponse { status, body })
+        }
+    }
+
+    /// List the libraries (`GET /Library/VirtualFolders`).
+    pub async fn list_libraries(&self, token: &str) -> Result<Vec<JellyfinLibrary>, ApiError> {
+        crate::faults::api("jellyfin", "list_libraries").await?;
+        let url = self.http.base_url().join("/Library/VirtualFolders")?;
+        let resp = self.send_authed(self.http.inner().get(url), token).await?;
+        resp.json().await.map_err(ApiError::Request)
+    }
+
+    /// Create a user (`POST /Users/New`).
+    pub async fn create_user(
+        &self,
+        token: &str,
+        name: &str,
+        password: &str,
+    ) -> Result<JellyfinUser, ApiError> {
+        crate::faults::api("jellyfin", "create_user").await?;
+        let url = self.http.base_url().join("/Users/New")?;
+        let body = CreateUserRequest { name, password };
+        let resp = self
+            .send_authed(self.http.inner().post(url).json(&body), token)
+            .await?;
+        resp.json().await.map_err(ApiError::Request)
+    }
+
+    /// Delete a user (`DELETE /Users/{userId}`).
+    pub async fn delete_user(&self, token: &str, user_id: &str) -> Result<(), ApiError> {
+        crate::faults::api("jellyfin", "delete_user").await?;
+        let url = self.http.base_url().join(&format!("/Users/{user_id}"))?;
+        self.send_authed(self.http.inner().delete(url), token)
+            .await
+            .map(|_| ())
+    }
+
+    /// Replace a user's policy (`POST /Users/{userId}/Policy`).
+    pub async fn set_user_policy(
+        &self,
+        token: &str,
+        user_id: &str,
+        policy: &serde_json::Value,
+    ) -> Result<(), ApiError> {
+        crate::faults::api("jellyfin", "set_user_policy").await?;
+        let url = self
+            .http
+            .base_url()
+            .join(&format!("/Users/{user_id}/Policy"))?;
+        self.send_authed(self.http.inner().post(url).json(policy), token)
+            .await
+            .map(|_| ())
+    }
+
+    /// Set another user's password as an administrator, without their
+    /// current one (`POST /Users/{userId}/Password`).
+    pub async fn set_user_password(
+        &self,
+        token: &str,
+        user_id: &str,
+        password: &str,
+    ) -> Result<(), ApiError> {
+        crate::faults::api("jellyfin", "set_user_password").await?;
+        let url = self
+            .http
+            .base_url()
+            .join(&format!("/Users/{user_id}/Password"))?;
+        let body = SetPasswordRequest {
+            current_pw: "",
+            new_pw: password,
+            reset_password: false,
+        };
+        self.send_authed(self.http.inner().post(url).json(&body), token)
+            .await
+            .map(|_| ())
+    }
+
+    /// List the API keys (`GET /Auth/Keys`).
+    pub async fn api_keys(&self, token: &str) -> Result<Vec<JellyfinApiKey>, ApiError> {
+        crate::faults::api("jellyfin", "api_keys").await?;
+        let url = self.http.base_url().join("/Auth/Keys")?;
+        let resp = self.send_authed(self.http.inner().get(url), token).await?;
+        let list: ApiKeyList = resp.json().await.map_err(ApiError::Request)?;
+        Ok(list.items)
+    }
+
+    /// The API key named `app`, creating it (`POST /Auth/Keys?app=...`)
+    /// when there is none. Jellyfin doesn't return the key it creates, so
+    /// the list is read again afterwards.
+    pub async fn ensure_api_key(&self, token: &str, app: &str) -> Result<String, ApiError> {
+        let find = |keys: Vec<JellyfinApiKey>| {
+            keys.into_iter()
+                .find(|k| k.app_name == app)
+                .map(|k| k.access_token)
+        };
+        if let Some(key) = find(self.api_keys(token).await?) {
+            return Ok(key);
+        }
+        crate::faults::api("jellyfin", "create_api_key").await?;
+        let mut url = self.http.base_url().join("/Auth/Keys")?;
+        url.query_pairs_mut().append_pair("app", app);
+        self.send_authed(self.http.inner().post(url), token).await?;
+        find(self.api_keys(token).await?).ok_or_else(|| ApiError::ApiResponse {
+            status: 404,
+            body: format!("API key '{app}' was not created"),
+        })
+    }
+
     /// Configure the Jellyfin admin account.
     ///
     /// If the startup wizard is pending, runs the full wizard sequence.
@@ -344,6 +514,7 @@ impl JellyfinClient {
     /// Each wizard step is wrapped with its name so failures are identifiable
     /// in operator logs (e.g. `startup_configure: API returned 500: …`).
     pub async fn configure_admin(&self, username: &str, password: &str) -> Result<(), ApiError> {
+        crate::faults::api("jellyfin", "configure_admin").await?;
         if self.startup_pending().await? {
             // Step 1: set locale/language (required before user creation in ≥ 10.9).
             self.startup_configure()
@@ -385,6 +556,7 @@ impl JellyfinClient {
 
 impl HealthCheck for JellyfinClient {
     async fn is_healthy(&self) -> Result<bool, ApiError> {
+        crate::faults::api("jellyfin", "is_healthy").await?;
         let url = self.http.base_url().join("/health")?;
         let resp = self.http.inner().get(url).send().await?;
         Ok(resp.status().is_success())
@@ -393,7 +565,7 @@ impl HealthCheck for JellyfinClient {
 
 #[cfg(test)]
 mod tests {
-    use wiremock::matchers::{header_exists, method, path};
+    use wiremock::matchers::{header_exists, method, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     use super::*;
@@ -677,4 +849,39 @@ mod tests {
             other => panic!("expected 404, got: {other}"),
         }
     }
+
+    #[tokio::test]
+    async fn ensure_api_key_creates_a_missing_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/Auth/Keys"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"Items": []})),
+            )
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/Auth/Keys"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Items": [
+                    {"AccessToken": "other", "AppName": "kodi"},
+                    {"AccessToken": "k3y", "AppName": "jellyseerr"},
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/Auth/Keys"))
+            .and(query_param("app", "jellyseerr"))
+            .and(header_exists("X-Emby-Authorization"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let c = client(&server);
+        assert_eq!(c.ensure_api_key("tok", "jellyseerr").await.unwrap(), "k3y");
+        // The key now exists, so it is returned without creating another.
+        assert_eq!(c.ensure_api_key("tok", "jellyseerr").await.unwrap(), "k3y");
+    }
 }
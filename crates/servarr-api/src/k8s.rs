@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use k8s_openapi::api::core::v1::Secret;
 use kube::Client;
 use kube::api::Api;
@@ -42,3 +44,27 @@ pub async fn read_secret_key(
         key: key.to_string(),
     })
 }
+
+/// Read every key of a Kubernetes Secret as a decoded UTF-8 string.
+pub async fn read_secret_data(
+    client: &Client,
+    namespace: &str,
+    secret_name: &str,
+) -> Result<BTreeMap<String, String>, SecretError> {
+    let api = Api::<Secret>::namespaced(client.clone(), namespace);
+    let secret = api.get(secret_name).await?;
+
+    let data = secret.data.ok_or_else(|| SecretError::NoData {
+        name: secret_name.to_string(),
+    })?;
+
+    data.into_iter()
+        .map(|(key, bytes)| match String::from_utf8(bytes.0) {
+            Ok(value) => Ok((key, value)),
+            Err(_) => Err(SecretError::InvalidUtf8 {
+                name: secret_name.to_string(),
+                key,
+            }),
+        })
+        .collect()
+}
@@ -1,23 +1,30 @@
+pub mod bazarr;
 mod client;
+pub mod faults;
 pub mod health;
 pub mod jellyfin;
 pub mod k8s;
 pub mod overseerr;
 pub mod plex;
 pub mod prowlarr;
+pub mod qbittorrent;
+pub mod releases;
 pub mod sabnzbd;
 pub mod servarr_v3;
 pub mod tautulli;
 pub mod transmission;
 
+pub use bazarr::BazarrClient;
 pub use client::{ApiError, HttpClient};
 pub use health::HealthCheck;
 pub use jellyfin::JellyfinClient;
-pub use k8s::{SecretError, read_secret_key};
+pub use k8s::{SecretError, read_secret_data, read_secret_key};
 pub use overseerr::OverseerrClient;
-pub use plex::PlexClient;
+pub use plex::{PlexClient, PlexTvClient};
 pub use prowlarr::ProwlarrClient;
+pub use qbittorrent::QbittorrentClient;
+pub use releases::ReleaseClient;
 pub use sabnzbd::SabnzbdClient;
-pub use servarr_v3::{AppKind, ServarrClient};
+pub use servarr_v3::{AppKind, QueueItem, ServarrClient, Tag};
 pub use tautulli::TautulliClient;
 pub use transmission::TransmissionClient;
@@ -1,11 +1,13 @@
 use serde::Serialize;
 
 use crate::client::ApiError;
+use crate::health::HealthCheck;
 
 /// Client for the Overseerr settings API.
 ///
 /// Wraps the `overseerr` crate to manage Sonarr/Radarr server registrations
-/// in Overseerr for media request routing.
+/// in Overseerr for media request routing. Jellyseerr is an Overseerr fork
+/// with the same API, so this client serves both.
 pub struct OverseerrClient {
     config: overseerr::apis::configuration::Configuration,
 }
@@ -24,6 +26,15 @@ fn map_err<E: std::fmt::Debug>(e: overseerr::apis::Error<E>) -> ApiError {
     }
 }
 
+/// The SDK's reqwest is a different version from ours, so its errors are
+/// carried as text.
+fn request_err(e: impl std::fmt::Display) -> ApiError {
+    ApiError::ApiResponse {
+        status: 0,
+        body: e.to_string(),
+    }
+}
+
 impl OverseerrClient {
     /// Create a new Overseerr API client.
     ///
@@ -41,6 +52,7 @@ impl OverseerrClient {
 
     /// List all Sonarr server registrations.
     pub async fn list_sonarr(&self) -> Result<Vec<overseerr::models::SonarrSettings>, ApiError> {
+        crate::faults::api("overseerr", "list_sonarr").await?;
         overseerr::apis::settings_api::list_sonarr(&self.config)
             .await
             .map_err(map_err)
@@ -51,6 +63,7 @@ impl OverseerrClient {
         &self,
         settings: overseerr::models::SonarrSettings,
     ) -> Result<overseerr::models::SonarrSettings, ApiError> {
+        crate::faults::api("overseerr", "create_sonarr").await?;
         overseerr::apis::settings_api::create_sonarr(&self.config, settings)
             .await
             .map_err(map_err)
@@ -62,6 +75,7 @@ impl OverseerrClient {
         id: i32,
         settings: overseerr::models::SonarrSettings,
     ) -> Result<overseerr::models::SonarrSettings, ApiError> {
+        crate::faults::api("overseerr", "update_sonarr").await?;
         overseerr::apis::settings_api::update_sonarr(&self.config, id, settings)
             .await
             .map_err(map_err)
@@ -69,6 +83,7 @@ impl OverseerrClient {
 
     /// Remove a Sonarr server registration.
     pub async fn delete_sonarr(&self, id: i32) -> Result<(), ApiError> {
+        crate::faults::api("overseerr", "delete_sonarr").await?;
         overseerr::apis::settings_api::delete_sonarr(&self.config, id)
             .await
             .map_err(map_err)
@@ -77,6 +92,7 @@ impl OverseerrClient {
 
     /// List all Radarr server registrations.
     pub async fn list_radarr(&self) -> Result<Vec<overseerr::models::RadarrSettings>, ApiError> {
+        crate::faults::api("overseerr", "list_radarr").await?;
         overseerr::apis::settings_api::list_radarr(&self.config)
             .await
             .map_err(map_err)
@@ -87,6 +103,7 @@ impl OverseerrClient {
         &self,
         settings: overseerr::models::RadarrSettings,
     ) -> Result<overseerr::models::RadarrSettings, ApiError> {
+        crate::faults::api("overseerr", "create_radarr").await?;
         overseerr::apis::settings_api::create_radarr(&self.config, settings)
             .await
             .map_err(map_err)
@@ -98,6 +115,7 @@ impl OverseerrClient {
         id: i32,
         settings: overseerr::models::RadarrSettings,
     ) -> Result<overseerr::models::RadarrSettings, ApiError> {
+        crate::faults::api("overseerr", "update_radarr").await?;
         overseerr::apis::settings_api::update_radarr(&self.config, id, settings)
             .await
             .map_err(map_err)
@@ -105,16 +123,74 @@ impl OverseerrClient {
 
     /// Remove a Radarr server registration.
     pub async fn delete_radarr(&self, id: i32) -> Result<(), ApiError> {
+        crate::faults::api("overseerr", "delete_radarr").await?;
         overseerr::apis::settings_api::delete_radarr(&self.config, id)
             .await
             .map_err(map_err)
             .map(|_| ())
     }
 
+    /// The Jellyfin connection settings (`GET /api/v1/settings/jellyfin`).
+    /// Jellyseerr only; Overseerr has no such endpoint.
+    pub async fn jellyfin_settings(&self) -> Result<serde_json::Value, ApiError> {
+        crate::faults::api("overseerr", "jellyfin_settings").await?;
+        let url = format!("{}/api/v1/settings/jellyfin", self.config.base_path);
+        let resp = self
+            .config
+            .client
+            .get(&url)
+            .header("X-Api-Key", self.api_key())
+            .send()
+            .await
+            .map_err(request_err)?;
+        if resp.status().is_success() {
+            resp.json().await.map_err(request_err)
+        } else {
+            let status = resp.status().as_u16();
+            let body = resp.text().await.unwrap_or_default();
+            Err(ApiError::ApiResponse { status, body })
+        }
+    }
+
+    /// Save the Jellyfin connection settings
+    /// (`POST /api/v1/settings/jellyfin`). Jellyseerr only.
+    pub async fn update_jellyfin_settings(
+        &self,
+        settings: &serde_json::Value,
+    ) -> Result<(), ApiError> {
+        crate::faults::api("overseerr", "update_jellyfin_settings").await?;
+        let url = format!("{}/api/v1/settings/jellyfin", self.config.base_path);
+        let resp = self
+            .config
+            .client
+            .post(&url)
+            .header("X-Api-Key", self.api_key())
+            .json(settings)
+            .send()
+            .await
+            .map_err(request_err)?;
+        if resp.status().is_success() {
+            Ok(())
+        } else {
+            let status = resp.status().as_u16();
+            let body = resp.text().await.unwrap_or_default();
+            Err(ApiError::ApiResponse { status, body })
+        }
+    }
+
+    fn api_key(&self) -> &str {
+        self.config
+            .api_key
+            .as_ref()
+            .map(|k| k.key.as_str())
+            .unwrap_or_default()
+    }
+
     /// Configure local authentication via `PUT /api/v1/auth/local`.
     ///
     /// Sets the admin username and password for Overseerr's local auth provider.
     pub async fn setup_local_auth(&self, username: &str, password: &str) -> Result<(), ApiError> {
+        crate::faults::api("overseerr", "setup_local_auth").await?;
         let url = format!("{}/api/v1/auth/local", self.config.base_path);
         let body = LocalAuthRequest { username, password };
         let resp = self
@@ -138,6 +214,22 @@ impl OverseerrClient {
     }
 }
 
+impl HealthCheck for OverseerrClient {
+    /// `GET /api/v1/status`, which needs no authentication.
+    async fn is_healthy(&self) -> Result<bool, ApiError> {
+        crate::faults::api("overseerr", "is_healthy").await?;
+        let url = format!("{}/api/v1/status", self.config.base_path);
+        let resp = self
+            .config
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(request_err)?;
+        Ok(resp.status().is_success())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -211,4 +303,40 @@ mod tests {
             other => panic!("expected ApiResponse, got: {other}"),
         }
     }
+
+    #[tokio::test]
+    async fn is_healthy_reads_the_status_endpoint() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/status"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"version": "2.7.3"})),
+            )
+            .mount(&server)
+            .await;
+        let client = OverseerrClient::new(&server.uri(), "test-key");
+        assert!(client.is_healthy().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn update_jellyfin_settings_sends_the_api_key() {
+        use wiremock::matchers::{body_json, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let settings = serde_json::json!({"ip": "jellyfin.media.svc", "port": 8096});
+        Mock::given(method("POST"))
+            .and(path("/api/v1/settings/jellyfin"))
+            .and(header("X-Api-Key", "test-key"))
+            .and(body_json(&settings))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&settings))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = OverseerrClient::new(&server.uri(), "test-key");
+        client.update_jellyfin_settings(&settings).await.unwrap();
+    }
 }
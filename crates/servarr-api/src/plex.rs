@@ -1,3 +1,8 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::json;
+
 use crate::client::{ApiError, HttpClient};
 use crate::health::HealthCheck;
 
@@ -10,18 +15,298 @@ pub struct PlexClient {
     http: HttpClient,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct IdentityResponse {
+    media_container: Identity,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Identity {
+    machine_identifier: String,
+}
+
 impl PlexClient {
     pub fn new(base_url: &str) -> Result<Self, ApiError> {
         Ok(Self {
             http: HttpClient::new(base_url, None)?,
         })
     }
+
+    /// The server's machine identifier, which plex.tv knows it by
+    /// (`GET /identity`).
+    pub async fn machine_identifier(&self) -> Result<String, ApiError> {
+        crate::faults::api("plex", "machine_identifier").await?;
+        let url = self.http.base_url().join("/identity")?;
+        let resp = self
+            .http
+            .inner()
+            .get(url)
+            .header("Accept", "application/json")
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status().as_u16();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(ApiError::ApiResponse { status, body });
+        }
+        let identity: IdentityResponse = resp.json().await?;
+        Ok(identity.media_container.machine_identifier)
+    }
 }
 
 impl HealthCheck for PlexClient {
     async fn is_healthy(&self) -> Result<bool, ApiError> {
+        crate::faults::api("plex", "is_healthy").await?;
         let url = self.http.base_url().join("/identity")?;
         let resp = self.http.inner().get(url).send().await?;
         Ok(resp.status().is_success())
     }
 }
+
+/// Base URL of plex.tv, where Plex accounts and library shares live.
+pub const PLEX_TV_URL: &str = "https://plex.tv";
+
+/// Client for the plex.tv API of a server's owner, used to share the
+/// server's libraries with other Plex accounts.
+///
+/// These endpoints answer in XML only, so the few attributes needed are
+/// read with a minimal parser rather than a full XML library.
+#[derive(Debug, Clone)]
+pub struct PlexTvClient {
+    http: HttpClient,
+    token: String,
+}
+
+/// A library of a server as plex.tv numbers it; these IDs differ from the
+/// server's own section keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlexSection {
+    pub id: u64,
+    pub title: String,
+}
+
+/// A server shared with another Plex account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlexShare {
+    pub id: u64,
+    pub username: String,
+    pub email: String,
+    /// The plex.tv IDs of the sections shared.
+    pub section_ids: Vec<u64>,
+}
+
+impl PlexShare {
+    /// Whether the share is with `account`, a plex.tv username or email.
+    pub fn is_for(&self, account: &str) -> bool {
+        self.username.eq_ignore_ascii_case(account) || self.email.eq_ignore_ascii_case(account)
+    }
+}
+
+impl PlexTvClient {
+    pub fn new(base_url: &str, token: &str) -> Result<Self, ApiError> {
+        Ok(Self {
+            http: HttpClient::new(base_url, None)?,
+            token: token.to_string(),
+        })
+    }
+
+    async fn send(&self, req: reqwest::RequestBuilder) -> Result<String, ApiError> {
+        let resp = req
+            .header("X-Plex-Token", &self.token)
+            .header("X-Plex-Client-Identifier", "servarr-operator")
+            .send()
+            .await?;
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        if status.is_success() {
+            Ok(body)
+        } else {
+            Err(ApiError::ApiResponse {
+                status: status.as_u16(),
+                body,
+            })
+        }
+    }
+
+    /// The server's libraries (`GET /api/servers/{machineId}`).
+    pub async fn sections(&self, machine_id: &str) -> Result<Vec<PlexSection>, ApiError> {
+        crate::faults::api("plex", "sections").await?;
+        let url = self
+            .http
+            .base_url()
+            .join(&format!("/api/servers/{machine_id}"))?;
+        let body = self.send(self.http.inner().get(url)).await?;
+        Ok(xml_elements(&body, "Section")
+            .into_iter()
+            .filter_map(|section| {
+                let attrs = xml_attrs(section);
+                Some(PlexSection {
+                    id: attrs.get("id")?.parse().ok()?,
+                    title: attrs.get("title").cloned().unwrap_or_default(),
+                })
+            })
+            .collect())
+    }
+
+    /// The accounts the server is shared with
+    /// (`GET /api/servers/{machineId}/shared_servers`).
+    pub async fn list_shares(&self, machine_id: &str) -> Result<Vec<PlexShare>, ApiError> {
+        crate::faults::api("plex", "list_shares").await?;
+        let url = self
+            .http
+            .base_url()
+            .join(&format!("/api/servers/{machine_id}/shared_servers"))?;
+        let body = self.send(self.http.inner().get(url)).await?;
+        Ok(xml_elements(&body, "SharedServer")
+            .into_iter()
+            .filter_map(|share| {
+                let attrs = xml_attrs(share);
+                Some(PlexShare {
+                    id: attrs.get("id")?.parse().ok()?,
+                    username: attrs.get("username").cloned().unwrap_or_default(),
+                    email: attrs.get("email").cloned().unwrap_or_default(),
+                    section_ids: xml_elements(share, "Section")
+                        .into_iter()
+                        .map(xml_attrs)
+                        .filter(|s| s.get("shared").is_some_and(|v| v == "1"))
+                        .filter_map(|s| s.get("id")?.parse().ok())
+                        .collect(),
+                })
+            })
+            .collect())
+    }
+
+    /// Invite `account` (a plex.tv username or email) to the server with
+    /// access to `section_ids` (`POST /api/servers/{machineId}/shared_servers`).
+    pub async fn share(
+        &self,
+        machine_id: &str,
+        account: &str,
+        section_ids: &[u64],
+    ) -> Result<(), ApiError> {
+        crate::faults::api("plex", "share").await?;
+        let url = self
+            .http
+            .base_url()
+            .join(&format!("/api/servers/{machine_id}/shared_servers"))?;
+        let body = json!({
+            "server_id": machine_id,
+            "shared_server": {
+                "library_section_ids": section_ids,
+                "invited_email": account,
+            },
+            "sharing_settings": {},
+        });
+        self.send(self.http.inner().post(url).json(&body))
+            .await
+            .map(|_| ())
+    }
+
+    /// Change the sections of a share
+    /// (`PUT /api/servers/{machineId}/shared_servers/{id}`).
+    pub async fn update_share(
+        &self,
+        machine_id: &str,
+        share_id: u64,
+        section_ids: &[u64],
+    ) -> Result<(), ApiError> {
+        crate::faults::api("plex", "update_share").await?;
+        let url = self.http.base_url().join(&format!(
+            "/api/servers/{machine_id}/shared_servers/{share_id}"
+        ))?;
+        let body = json!({
+            "server_id": machine_id,
+            "shared_server": { "library_section_ids": section_ids },
+        });
+        self.send(self.http.inner().put(url).json(&body))
+            .await
+            .map(|_| ())
+    }
+
+    /// Stop sharing the server with an account
+    /// (`DELETE /api/servers/{machineId}/shared_servers/{id}`).
+    pub async fn remove_share(&self, machine_id: &str, share_id: u64) -> Result<(), ApiError> {
+        crate::faults::api("plex", "remove_share").await?;
+        let url = self.http.base_url().join(&format!(
+            "/api/servers/{machine_id}/shared_servers/{share_id}"
+        ))?;
+        self.send(self.http.inner().delete(url)).await.map(|_| ())
+    }
+}
+
+/// The text after each `<{tag}` in `xml`, up to the next one, so an
+/// element's children are included in its slice.
+fn xml_elements<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{tag}");
+    let starts: Vec<usize> = xml
+        .match_indices(&open)
+        .map(|(i, _)| i + open.len())
+        .filter(|&i| xml[i..].starts_with(|c: char| c.is_whitespace() || c == '/' || c == '>'))
+        .collect();
+    starts
+        .iter()
+        .enumerate()
+        .map(|(n, &start)| {
+            let end = starts
+                .get(n + 1)
+                .map_or(xml.len(), |next| next - open.len());
+            &xml[start..end]
+        })
+        .collect()
+}
+
+/// The attributes of the opening tag `element` starts with.
+fn xml_attrs(element: &str) -> BTreeMap<String, String> {
+    let mut rest = &element[..element.find('>').unwrap_or(element.len())];
+    let mut attrs = BTreeMap::new();
+    while let Some(eq) = rest.find("=\"") {
+        let name = rest[..eq].split_whitespace().last().unwrap_or_default();
+        let value = &rest[eq + 2..];
+        let Some(len) = value.find('"') else {
+            break;
+        };
+        attrs.insert(name.to_string(), xml_unescape(&value[..len]));
+        rest = &value[len + 1..];
+    }
+    attrs
+}
+
+fn xml_unescape(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_shared_servers() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<MediaContainer friendlyName="myPlex" machineIdentifier="abc">
+  <SharedServer id="11" username="kid" email="kid@example.com" userID="5">
+    <Section id="101" key="1" title="Movies" type="movie" shared="1"/>
+    <Section id="102" key="2" title="Kids &amp; Family" type="show" shared="0"/>
+  </SharedServer>
+  <SharedServer id="12" username="gran" email="gran@example.com" userID="6"/>
+</MediaContainer>"#;
+        let shares = xml_elements(xml, "SharedServer");
+        assert_eq!(shares.len(), 2);
+        let first = xml_attrs(shares[0]);
+        assert_eq!(first["username"], "kid");
+        let sections: Vec<_> = xml_elements(shares[0], "Section")
+            .into_iter()
+            .map(xml_attrs)
+            .collect();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[1]["title"], "Kids & Family");
+        assert!(xml_elements(shares[1], "Section").is_empty());
+        assert!(xml_elements("<SectionList/>", "Section").is_empty());
+    }
+}
@@ -46,11 +46,58 @@ pub struct ProwlarrAppField {
     pub value: serde_json::Value,
 }
 
+/// A Prowlarr app profile, which indexers use to choose RSS and search
+/// behavior.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProwlarrAppProfile {
+    #[serde(default)]
+    pub id: i64,
+    pub name: String,
+    pub enable_rss: bool,
+    pub enable_automatic_search: bool,
+    pub enable_interactive_search: bool,
+    pub minimum_seeders: i32,
+}
+
+/// An indexer in Prowlarr, or an entry of its indexer schema.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProwlarrIndexer {
+    #[serde(default)]
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub implementation: String,
+    #[serde(default)]
+    pub config_contract: String,
+    #[serde(default)]
+    pub definition_name: String,
+    #[serde(default)]
+    pub enable: bool,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub app_profile_id: i64,
+    #[serde(default)]
+    pub fields: Vec<ProwlarrAppField>,
+    #[serde(default)]
+    pub tags: Vec<i64>,
+}
+
+/// A Prowlarr tag.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProwlarrTag {
+    #[serde(default)]
+    pub id: i64,
+    pub label: String,
+}
+
 // --- Conversion helpers between our types and SDK types ---
 
-fn sdk_to_app(r: prowlarr::models::ApplicationResource) -> ProwlarrApp {
-    let fields = r
-        .fields
+fn sdk_to_fields(fields: Option<Option<Vec<prowlarr::models::Field>>>) -> Vec<ProwlarrAppField> {
+    fields
         .and_then(|outer| outer)
         .unwrap_or_default()
         .into_iter()
@@ -62,17 +109,33 @@ fn sdk_to_app(r: prowlarr::models::ApplicationResource) -> ProwlarrApp {
             let value = f.value.and_then(|v| v).unwrap_or(serde_json::Value::Null);
             Some(ProwlarrAppField { name, value })
         })
-        .collect();
+        .collect()
+}
 
-    let sync_level = r.sync_level.map(|s| s.to_string()).unwrap_or_default();
+fn fields_to_sdk(fields: &[ProwlarrAppField]) -> Vec<prowlarr::models::Field> {
+    fields
+        .iter()
+        .map(|f| {
+            let mut field = prowlarr::models::Field::new();
+            field.name = Some(Some(f.name.clone()));
+            field.value = Some(Some(f.value.clone()));
+            field
+        })
+        .collect()
+}
 
-    let tags = r
-        .tags
-        .and_then(|outer| outer)
+fn sdk_to_tags(tags: Option<Option<Vec<i32>>>) -> Vec<i64> {
+    tags.and_then(|outer| outer)
         .unwrap_or_default()
         .into_iter()
         .map(|t| t as i64)
-        .collect();
+        .collect()
+}
+
+fn sdk_to_app(r: prowlarr::models::ApplicationResource) -> ProwlarrApp {
+    let fields = sdk_to_fields(r.fields);
+    let sync_level = r.sync_level.map(|s| s.to_string()).unwrap_or_default();
+    let tags = sdk_to_tags(r.tags);
 
     ProwlarrApp {
         id: r.id.unwrap_or(0) as i64,
@@ -86,16 +149,7 @@ fn sdk_to_app(r: prowlarr::models::ApplicationResource) -> ProwlarrApp {
 }
 
 fn app_to_sdk(app: &ProwlarrApp) -> prowlarr::models::ApplicationResource {
-    let fields: Vec<prowlarr::models::Field> = app
-        .fields
-        .iter()
-        .map(|f| {
-            let mut field = prowlarr::models::Field::new();
-            field.name = Some(Some(f.name.clone()));
-            field.value = Some(Some(f.value.clone()));
-            field
-        })
-        .collect();
+    let fields = fields_to_sdk(&app.fields);
 
     let sync_level = match app.sync_level.as_str() {
         "disabled" => Some(prowlarr::models::ApplicationSyncLevel::Disabled),
@@ -121,6 +175,66 @@ fn app_to_sdk(app: &ProwlarrApp) -> prowlarr::models::ApplicationResource {
     resource
 }
 
+fn sdk_to_profile(r: prowlarr::models::AppProfileResource) -> ProwlarrAppProfile {
+    ProwlarrAppProfile {
+        id: r.id.unwrap_or(0) as i64,
+        name: r.name.and_then(|n| n).unwrap_or_default(),
+        enable_rss: r.enable_rss.unwrap_or(false),
+        enable_automatic_search: r.enable_automatic_search.unwrap_or(false),
+        enable_interactive_search: r.enable_interactive_search.unwrap_or(false),
+        minimum_seeders: r.minimum_seeders.unwrap_or(0),
+    }
+}
+
+fn profile_to_sdk(profile: &ProwlarrAppProfile) -> prowlarr::models::AppProfileResource {
+    let mut resource = prowlarr::models::AppProfileResource::new();
+    resource.id = (profile.id != 0).then_some(profile.id as i32);
+    resource.name = Some(Some(profile.name.clone()));
+    resource.enable_rss = Some(profile.enable_rss);
+    resource.enable_automatic_search = Some(profile.enable_automatic_search);
+    resource.enable_interactive_search = Some(profile.enable_interactive_search);
+    resource.minimum_seeders = Some(profile.minimum_seeders);
+    resource
+}
+
+fn sdk_to_indexer(r: prowlarr::models::IndexerResource) -> ProwlarrIndexer {
+    ProwlarrIndexer {
+        id: r.id.unwrap_or(0) as i64,
+        name: r.name.and_then(|n| n).unwrap_or_default(),
+        implementation: r.implementation.and_then(|i| i).unwrap_or_default(),
+        config_contract: r.config_contract.and_then(|c| c).unwrap_or_default(),
+        definition_name: r.definition_name.and_then(|d| d).unwrap_or_default(),
+        enable: r.enable.unwrap_or(false),
+        priority: r.priority.unwrap_or(0),
+        app_profile_id: r.app_profile_id.unwrap_or(0) as i64,
+        fields: sdk_to_fields(r.fields),
+        tags: sdk_to_tags(r.tags),
+    }
+}
+
+fn indexer_to_sdk(indexer: &ProwlarrIndexer) -> prowlarr::models::IndexerResource {
+    let mut resource = prowlarr::models::IndexerResource::new();
+    resource.id = (indexer.id != 0).then_some(indexer.id as i32);
+    resource.name = Some(Some(indexer.name.clone()));
+    resource.implementation = Some(Some(indexer.implementation.clone()));
+    resource.config_contract = Some(Some(indexer.config_contract.clone()));
+    resource.definition_name =
+        (!indexer.definition_name.is_empty()).then(|| Some(indexer.definition_name.clone()));
+    resource.enable = Some(indexer.enable);
+    resource.priority = Some(indexer.priority);
+    resource.app_profile_id = Some(indexer.app_profile_id as i32);
+    resource.fields = Some(Some(fields_to_sdk(&indexer.fields)));
+    resource.tags = Some(Some(indexer.tags.iter().map(|&t| t as i32).collect()));
+    resource
+}
+
+fn sdk_to_tag(r: prowlarr::models::TagResource) -> ProwlarrTag {
+    ProwlarrTag {
+        id: r.id.unwrap_or(0) as i64,
+        label: r.label.and_then(|l| l).unwrap_or_default(),
+    }
+}
+
 impl ProwlarrClient {
     /// Create a new Prowlarr API client.
     ///
@@ -138,6 +252,7 @@ impl ProwlarrClient {
 
     /// GET `/api/v1/applications` — list all registered applications.
     pub async fn list_applications(&self) -> Result<Vec<ProwlarrApp>, ApiError> {
+        crate::faults::api("prowlarr", "list_applications").await?;
         prowlarr::apis::application_api::list_applications(&self.config)
             .await
             .map(|v| v.into_iter().map(sdk_to_app).collect())
@@ -146,6 +261,7 @@ impl ProwlarrClient {
 
     /// POST `/api/v1/applications` — add a new application.
     pub async fn add_application(&self, app: &ProwlarrApp) -> Result<ProwlarrApp, ApiError> {
+        crate::faults::api("prowlarr", "add_application").await?;
         let resource = app_to_sdk(app);
         prowlarr::apis::application_api::create_applications(&self.config, None, Some(resource))
             .await
@@ -159,6 +275,7 @@ impl ProwlarrClient {
         id: i64,
         app: &ProwlarrApp,
     ) -> Result<ProwlarrApp, ApiError> {
+        crate::faults::api("prowlarr", "update_application").await?;
         let resource = app_to_sdk(app);
         prowlarr::apis::application_api::update_applications(
             &self.config,
@@ -173,10 +290,134 @@ impl ProwlarrClient {
 
     /// DELETE `/api/v1/applications/{id}` — remove an application.
     pub async fn delete_application(&self, id: i64) -> Result<(), ApiError> {
+        crate::faults::api("prowlarr", "delete_application").await?;
         prowlarr::apis::application_api::delete_applications(&self.config, id as i32)
             .await
             .map_err(map_sdk_err)
     }
+
+    /// GET `/api/v1/appprofile` — list all app profiles.
+    pub async fn list_app_profiles(&self) -> Result<Vec<ProwlarrAppProfile>, ApiError> {
+        crate::faults::api("prowlarr", "list_app_profiles").await?;
+        prowlarr::apis::app_profile_api::list_app_profile(&self.config)
+            .await
+            .map(|v| v.into_iter().map(sdk_to_profile).collect())
+            .map_err(map_sdk_err)
+    }
+
+    /// POST `/api/v1/appprofile` — add an app profile.
+    pub async fn add_app_profile(
+        &self,
+        profile: &ProwlarrAppProfile,
+    ) -> Result<ProwlarrAppProfile, ApiError> {
+        crate::faults::api("prowlarr", "add_app_profile").await?;
+        prowlarr::apis::app_profile_api::create_app_profile(
+            &self.config,
+            Some(profile_to_sdk(profile)),
+        )
+        .await
+        .map(sdk_to_profile)
+        .map_err(map_sdk_err)
+    }
+
+    /// PUT `/api/v1/appprofile/{id}` — update an app profile.
+    pub async fn update_app_profile(
+        &self,
+        id: i64,
+        profile: &ProwlarrAppProfile,
+    ) -> Result<ProwlarrAppProfile, ApiError> {
+        crate::faults::api("prowlarr", "update_app_profile").await?;
+        prowlarr::apis::app_profile_api::update_app_profile(
+            &self.config,
+            &id.to_string(),
+            Some(profile_to_sdk(profile)),
+        )
+        .await
+        .map(sdk_to_profile)
+        .map_err(map_sdk_err)
+    }
+
+    /// GET `/api/v1/indexer` — list all indexers.
+    pub async fn list_indexers(&self) -> Result<Vec<ProwlarrIndexer>, ApiError> {
+        crate::faults::api("prowlarr", "list_indexers").await?;
+        prowlarr::apis::indexer_api::list_indexer(&self.config)
+            .await
+            .map(|v| v.into_iter().map(sdk_to_indexer).collect())
+            .map_err(map_sdk_err)
+    }
+
+    /// GET `/api/v1/indexer/schema` — list the indexers Prowlarr can add,
+    /// with their default field values.
+    pub async fn indexer_schema(&self) -> Result<Vec<ProwlarrIndexer>, ApiError> {
+        crate::faults::api("prowlarr", "indexer_schema").await?;
+        prowlarr::apis::indexer_api::list_indexer_schema(&self.config)
+            .await
+            .map(|v| v.into_iter().map(sdk_to_indexer).collect())
+            .map_err(map_sdk_err)
+    }
+
+    /// POST `/api/v1/indexer` — add an indexer. Prowlarr tests it first and
+    /// rejects it if the test fails.
+    pub async fn add_indexer(
+        &self,
+        indexer: &ProwlarrIndexer,
+    ) -> Result<ProwlarrIndexer, ApiError> {
+        crate::faults::api("prowlarr", "add_indexer").await?;
+        prowlarr::apis::indexer_api::create_indexer(
+            &self.config,
+            None,
+            Some(indexer_to_sdk(indexer)),
+        )
+        .await
+        .map(sdk_to_indexer)
+        .map_err(map_sdk_err)
+    }
+
+    /// PUT `/api/v1/indexer/{id}` — update an indexer.
+    pub async fn update_indexer(
+        &self,
+        id: i64,
+        indexer: &ProwlarrIndexer,
+    ) -> Result<ProwlarrIndexer, ApiError> {
+        crate::faults::api("prowlarr", "update_indexer").await?;
+        prowlarr::apis::indexer_api::update_indexer(
+            &self.config,
+            &id.to_string(),
+            None,
+            Some(indexer_to_sdk(indexer)),
+        )
+        .await
+        .map(sdk_to_indexer)
+        .map_err(map_sdk_err)
+    }
+
+    /// DELETE `/api/v1/indexer/{id}` — remove an indexer.
+    pub async fn delete_indexer(&self, id: i64) -> Result<(), ApiError> {
+        crate::faults::api("prowlarr", "delete_indexer").await?;
+        prowlarr::apis::indexer_api::delete_indexer(&self.config, id as i32)
+            .await
+            .map_err(map_sdk_err)
+    }
+
+    /// GET `/api/v1/tag` — list all tags.
+    pub async fn list_tags(&self) -> Result<Vec<ProwlarrTag>, ApiError> {
+        crate::faults::api("prowlarr", "list_tags").await?;
+        prowlarr::apis::tag_api::list_tag(&self.config)
+            .await
+            .map(|v| v.into_iter().map(sdk_to_tag).collect())
+            .map_err(map_sdk_err)
+    }
+
+    /// POST `/api/v1/tag` — create a tag.
+    pub async fn create_tag(&self, label: &str) -> Result<ProwlarrTag, ApiError> {
+        crate::faults::api("prowlarr", "create_tag").await?;
+        let mut resource = prowlarr::models::TagResource::new();
+        resource.label = Some(Some(label.to_string()));
+        prowlarr::apis::tag_api::create_tag(&self.config, Some(resource))
+            .await
+            .map(sdk_to_tag)
+            .map_err(map_sdk_err)
+    }
 }
 
 #[cfg(test)]
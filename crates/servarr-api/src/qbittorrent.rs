@@ -0,0 +1,71 @@
+use reqwest::StatusCode;
+
+use crate::client::{ApiError, HttpClient};
+use crate::health::HealthCheck;
+
+/// Client for the qBittorrent Web API (`/api/v2`).
+///
+/// qBittorrent authenticates with a session cookie from `/api/v2/auth/login`
+/// unless the caller's subnet is whitelisted, so the health check accepts a
+/// `403 Forbidden` as proof that the web UI is up.
+#[derive(Debug, Clone)]
+pub struct QbittorrentClient {
+    http: HttpClient,
+}
+
+impl QbittorrentClient {
+    /// Create a new qBittorrent client.
+    ///
+    /// `base_url` should be the root URL (e.g. `http://qbittorrent:8080`).
+    pub fn new(base_url: &str) -> Result<Self, ApiError> {
+        let url = format!("{}/api/v2/", base_url.trim_end_matches('/'));
+        Ok(Self {
+            http: HttpClient::new(&url, None)?,
+        })
+    }
+}
+
+impl HealthCheck for QbittorrentClient {
+    async fn is_healthy(&self) -> Result<bool, ApiError> {
+        crate::faults::api("qbittorrent", "is_healthy").await?;
+        let url = self.http.base_url().join("app/version")?;
+        let resp = self.http.inner().get(url).send().await?;
+        Ok(resp.status().is_success() || resp.status() == StatusCode::FORBIDDEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    async fn health_with(status: u16) -> bool {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/app/version"))
+            .respond_with(ResponseTemplate::new(status).set_body_string("v5.1.2"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        QbittorrentClient::new(&format!("{}/", server.uri()))
+            .expect("client")
+            .is_healthy()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn is_healthy_accepts_ok_and_forbidden() {
+        assert!(health_with(200).await);
+        // Up, but the operator has no session cookie
+        assert!(health_with(403).await);
+        assert!(!health_with(502).await);
+    }
+
+    #[test]
+    fn new_rejects_invalid_url() {
+        assert!(QbittorrentClient::new("not a url").is_err());
+    }
+}
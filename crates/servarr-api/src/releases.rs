@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::Deserialize;
+use url::Url;
+
+use crate::client::ApiError;
+
+/// GitHub's public REST API.
+pub const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// Client for the sources of upstream release versions: GitHub's "latest
+/// release" endpoint and plain JSON version feeds.
+#[derive(Debug, Clone)]
+pub struct ReleaseClient {
+    inner: reqwest::Client,
+    github_api: Url,
+}
+
+#[derive(Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+}
+
+impl ReleaseClient {
+    /// Create a client that queries GitHub at `github_api_url` (normally
+    /// [`GITHUB_API_URL`]).
+    pub fn new(github_api_url: &str) -> Result<Self, ApiError> {
+        let github_api = Url::parse(&format!("{}/", github_api_url.trim_end_matches('/')))?;
+        // GitHub rejects requests without a User-Agent.
+        let inner = reqwest::Client::builder()
+            .user_agent(concat!("servarr-operator/", env!("CARGO_PKG_VERSION")))
+            .timeout(Duration::from_secs(30))
+            .connect_timeout(Duration::from_secs(10))
+            .build()?;
+        Ok(Self { inner, github_api })
+    }
+
+    /// Tag of the latest published (non-prerelease, non-draft) release of
+    /// the GitHub repository `repo` (`owner/name`).
+    ///
+    /// Calls `GET /repos/{repo}/releases/latest`.
+    pub async fn latest_github_release(&self, repo: &str) -> Result<String, ApiError> {
+        crate::faults::api("releases", "latest_github_release").await?;
+        let url = self
+            .github_api
+            .join(&format!("repos/{repo}/releases/latest"))?;
+        let release: GithubRelease = self.get_json(url).await?;
+        Ok(release.tag_name)
+    }
+
+    /// A JSON version feed: an object mapping app names to their latest
+    /// version, e.g. `{"sonarr": "4.0.16.2944"}`.
+    pub async fn version_feed(&self, url: &str) -> Result<BTreeMap<String, String>, ApiError> {
+        crate::faults::api("releases", "version_feed").await?;
+        self.get_json(Url::parse(url)?).await
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: Url) -> Result<T, ApiError> {
+        let resp = self
+            .inner
+            .get(url)
+            .header("Accept", "application/json")
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status().as_u16();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(ApiError::ApiResponse { status, body });
+        }
+        Ok(resp.json().await?)
+    }
+}
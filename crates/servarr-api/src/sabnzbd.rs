@@ -64,6 +64,7 @@ impl SabnzbdClient {
 
     /// GET `/api?mode=version&apikey=<key>&output=json`
     pub async fn version(&self) -> Result<String, ApiError> {
+        crate::faults::api("sabnzbd", "version").await?;
         let resp: VersionResponse = self
             .http
             .get(&format!(
@@ -76,6 +77,7 @@ impl SabnzbdClient {
 
     /// GET `/api?mode=queue&apikey=<key>&output=json`
     pub async fn queue_status(&self) -> Result<QueueStatus, ApiError> {
+        crate::faults::api("sabnzbd", "queue_status").await?;
         let resp: QueueResponse = self
             .http
             .get(&format!("?mode=queue&apikey={}&output=json", self.api_key))
@@ -85,6 +87,7 @@ impl SabnzbdClient {
 
     /// GET `/api?mode=server_stats&apikey=<key>&output=json`
     pub async fn server_stats(&self) -> Result<ServerStatsResponse, ApiError> {
+        crate::faults::api("sabnzbd", "server_stats").await?;
         self.http
             .get(&format!(
                 "?mode=server_stats&apikey={}&output=json",
@@ -102,6 +105,7 @@ impl SabnzbdClient {
         keyword: &str,
         value: &str,
     ) -> Result<(), ApiError> {
+        crate::faults::api("sabnzbd", "set_config").await?;
         let path = format!(
             "?mode=set_config&section={section}&keyword={keyword}&value={value}&apikey={}&output=json",
             self.api_key
@@ -112,6 +116,7 @@ impl SabnzbdClient {
 
     /// Set the admin username and password via the `set_config` API.
     pub async fn set_credentials(&self, username: &str, password: &str) -> Result<(), ApiError> {
+        crate::faults::api("sabnzbd", "set_credentials").await?;
         self.set_config("misc", "username", username).await?;
         self.set_config("misc", "password", password).await?;
         Ok(())
@@ -120,6 +125,7 @@ impl SabnzbdClient {
 
 impl HealthCheck for SabnzbdClient {
     async fn is_healthy(&self) -> Result<bool, ApiError> {
+        crate::faults::api("sabnzbd", "is_healthy").await?;
         let version = self.version().await?;
         Ok(!version.is_empty())
     }
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::client::{ApiError, HttpClient};
 use crate::health::HealthCheck;
@@ -6,20 +6,48 @@ use crate::health::HealthCheck;
 /// Which Servarr v3 application this client targets.
 ///
 /// Used to dispatch SDK calls to the correct crate
-/// (sonarr, radarr, lidarr, or prowlarr).
+/// (sonarr, radarr, lidarr, or prowlarr). Readarr and Whisparr have no SDK
+/// crate and are called over plain HTTP.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AppKind {
     Sonarr,
     Radarr,
     Lidarr,
+    Readarr,
+    Whisparr,
     Prowlarr,
 }
 
-/// Client for the Servarr v3 REST API shared by Sonarr, Radarr, Lidarr, and Prowlarr.
+impl AppKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sonarr => "sonarr",
+            Self::Radarr => "radarr",
+            Self::Lidarr => "lidarr",
+            Self::Readarr => "readarr",
+            Self::Whisparr => "whisparr",
+            Self::Prowlarr => "prowlarr",
+        }
+    }
+
+    /// Version segment of the app's API path: `v1` for Lidarr, Readarr,
+    /// and Prowlarr, `v3` for Sonarr, Radarr, and Whisparr.
+    pub fn api_version(&self) -> &'static str {
+        match self {
+            Self::Lidarr | Self::Readarr | Self::Prowlarr => "v1",
+            Self::Sonarr | Self::Radarr | Self::Whisparr => "v3",
+        }
+    }
+}
+
+/// Client for the Servarr REST API shared by Sonarr, Radarr, Lidarr,
+/// Readarr, Whisparr, and Prowlarr. Endpoint docs below name the `/api/v3`
+/// path; Lidarr, Readarr, and Prowlarr serve the same endpoints under
+/// `/api/v1`.
 ///
 /// Internally dispatches to the devopsarr SDK crate matching [`AppKind`].
-/// The `create_backup` endpoint is not present in the SDK, so it falls
-/// back to a direct HTTP call.
+/// Endpoints the SDKs don't expose, such as `create_backup`, and every
+/// Readarr and Whisparr call fall back to direct HTTP calls.
 #[derive(Debug, Clone)]
 pub struct ServarrClient {
     kind: AppKind,
@@ -27,7 +55,8 @@ pub struct ServarrClient {
     radarr_config: radarr::apis::configuration::Configuration,
     lidarr_config: lidarr::apis::configuration::Configuration,
     prowlarr_config: prowlarr::apis::configuration::Configuration,
-    /// Kept for `create_backup` which has no SDK endpoint.
+    /// Used for endpoints without an SDK call, and for every Readarr and
+    /// Whisparr call.
     http: HttpClient,
 }
 
@@ -108,6 +137,73 @@ pub struct Backup {
     pub time: String,
 }
 
+/// A tag (`GET /api/v3/tag`), which other resources refer to by `id`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tag {
+    #[serde(default)]
+    pub id: i64,
+    pub label: String,
+}
+
+/// An item in the download queue (`GET /api/v3/queue`). Most string fields
+/// are null for items the app can't match, hence the `Option`s.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueItem {
+    pub id: i64,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Download client status, e.g. `downloading`, `completed`, `warning`.
+    #[serde(default)]
+    pub status: Option<String>,
+    /// `ok`, `warning`, or `error`.
+    #[serde(default)]
+    pub tracked_download_status: Option<String>,
+    /// e.g. `downloading`, `importPending`, `importBlocked`, `importFailed`,
+    /// `failedPending`.
+    #[serde(default)]
+    pub tracked_download_state: Option<String>,
+    #[serde(default)]
+    pub status_messages: Option<Vec<QueueStatusMessage>>,
+    #[serde(default)]
+    pub error_message: Option<String>,
+    /// When the item was grabbed (RFC 3339).
+    #[serde(default)]
+    pub added: Option<String>,
+    #[serde(default)]
+    pub download_client: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueStatusMessage {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub messages: Option<Vec<String>>,
+}
+
+impl QueueItem {
+    /// The error message and every status message, for matching.
+    pub fn messages(&self) -> impl Iterator<Item = &str> {
+        self.error_message.as_deref().into_iter().chain(
+            self.status_messages.iter().flatten().flat_map(|m| {
+                m.title
+                    .as_deref()
+                    .into_iter()
+                    .chain(m.messages.iter().flatten().map(String::as_str))
+            }),
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct QueuePage {
+    #[serde(default)]
+    records: Vec<QueueItem>,
+}
+
 // --- Helper: unwrap Option<Option<String>> from SDK types ---
 
 fn oo_str(v: &Option<Option<String>>) -> String {
@@ -274,7 +370,7 @@ impl ServarrClient {
     /// `app_kind` selects which SDK crate to use for API calls.
     pub fn new(base_url: &str, api_key: &str, app_kind: AppKind) -> Result<Self, ApiError> {
         let base = base_url.trim_end_matches('/').to_string();
-        let http_url = format!("{base}/api/v3/");
+        let http_url = format!("{base}/api/{}/", app_kind.api_version());
 
         let mut sonarr_config = sonarr::apis::configuration::Configuration::new();
         sonarr_config.base_path = base.clone();
@@ -316,6 +412,7 @@ impl ServarrClient {
 
     /// GET `/api/v3/system/status`
     pub async fn system_status(&self) -> Result<SystemStatus, ApiError> {
+        crate::faults::api(self.kind.as_str(), "system_status").await?;
         match self.kind {
             AppKind::Sonarr => sonarr::apis::system_api::get_system_status(&self.sonarr_config)
                 .await
@@ -329,6 +426,7 @@ impl ServarrClient {
                 .await
                 .map(system_status_from_lidarr)
                 .map_err(map_sdk_err),
+            AppKind::Readarr | AppKind::Whisparr => self.http.get("system/status").await,
             AppKind::Prowlarr => {
                 prowlarr::apis::system_api::get_system_status(&self.prowlarr_config)
                     .await
@@ -340,6 +438,7 @@ impl ServarrClient {
 
     /// GET `/api/v3/health`
     pub async fn health(&self) -> Result<Vec<HealthCheckResult>, ApiError> {
+        crate::faults::api(self.kind.as_str(), "health").await?;
         match self.kind {
             AppKind::Sonarr => sonarr::apis::health_api::list_health(&self.sonarr_config)
                 .await
@@ -353,6 +452,7 @@ impl ServarrClient {
                 .await
                 .map(|v| health_from_oo!(lidarr, v))
                 .map_err(map_sdk_err),
+            AppKind::Readarr | AppKind::Whisparr => self.http.get("health").await,
             AppKind::Prowlarr => prowlarr::apis::health_api::list_health(&self.prowlarr_config)
                 .await
                 .map(|v| health_from_oo!(prowlarr, v))
@@ -362,6 +462,7 @@ impl ServarrClient {
 
     /// GET `/api/v3/rootfolder`
     pub async fn root_folder(&self) -> Result<Vec<RootFolder>, ApiError> {
+        crate::faults::api(self.kind.as_str(), "root_folder").await?;
         match self.kind {
             AppKind::Sonarr => sonarr::apis::root_folder_api::list_root_folder(&self.sonarr_config)
                 .await
@@ -375,13 +476,31 @@ impl ServarrClient {
                 .await
                 .map(|v| root_folder_from!(lidarr, v))
                 .map_err(map_sdk_err),
+            AppKind::Readarr | AppKind::Whisparr => self.http.get("rootfolder").await,
             // Prowlarr does not have a root folder API
             AppKind::Prowlarr => Ok(Vec::new()),
         }
     }
 
+    /// POST `/api/v3/rootfolder` — add a root folder. The app rejects a
+    /// path that doesn't exist in its container.
+    pub async fn create_root_folder(&self, path: &str) -> Result<RootFolder, ApiError> {
+        crate::faults::api(self.kind.as_str(), "create_root_folder").await?;
+        self.http
+            .post("rootfolder", &serde_json::json!({ "path": path }))
+            .await
+    }
+
+    /// DELETE `/api/v3/rootfolder/{id}` — remove a root folder. Media under
+    /// it stays in the library.
+    pub async fn delete_root_folder(&self, id: i64) -> Result<(), ApiError> {
+        crate::faults::api(self.kind.as_str(), "delete_root_folder").await?;
+        self.http.delete(&format!("rootfolder/{id}")).await
+    }
+
     /// GET `/api/v3/update` — returns available updates.
     pub async fn updates(&self) -> Result<Vec<UpdateInfo>, ApiError> {
+        crate::faults::api(self.kind.as_str(), "updates").await?;
         match self.kind {
             AppKind::Sonarr => sonarr::apis::update_api::list_update(&self.sonarr_config)
                 .await
@@ -395,6 +514,7 @@ impl ServarrClient {
                 .await
                 .map(|v| update_from!(lidarr, v))
                 .map_err(map_sdk_err),
+            AppKind::Readarr | AppKind::Whisparr => self.http.get("update").await,
             AppKind::Prowlarr => prowlarr::apis::update_api::list_update(&self.prowlarr_config)
                 .await
                 .map(|v| update_from!(prowlarr, v))
@@ -404,6 +524,7 @@ impl ServarrClient {
 
     /// GET `/api/v3/system/backup` — list all backups.
     pub async fn list_backups(&self) -> Result<Vec<Backup>, ApiError> {
+        crate::faults::api(self.kind.as_str(), "list_backups").await?;
         match self.kind {
             AppKind::Sonarr => sonarr::apis::backup_api::list_system_backup(&self.sonarr_config)
                 .await
@@ -417,6 +538,7 @@ impl ServarrClient {
                 .await
                 .map(|v| backup_from!(lidarr, v))
                 .map_err(map_sdk_err),
+            AppKind::Readarr | AppKind::Whisparr => self.http.get("system/backup").await,
             AppKind::Prowlarr => {
                 prowlarr::apis::backup_api::list_system_backup(&self.prowlarr_config)
                     .await
@@ -431,6 +553,7 @@ impl ServarrClient {
     /// The SDK crates do not expose a create-backup endpoint, so this
     /// falls back to a direct HTTP POST.
     pub async fn create_backup(&self) -> Result<Backup, ApiError> {
+        crate::faults::api(self.kind.as_str(), "create_backup").await?;
         self.http
             .post("system/backup", &serde_json::json!({}))
             .await
@@ -438,6 +561,7 @@ impl ServarrClient {
 
     /// POST `/api/v3/system/backup/restore/{id}` — restore from a backup.
     pub async fn restore_backup(&self, id: i64) -> Result<(), ApiError> {
+        crate::faults::api(self.kind.as_str(), "restore_backup").await?;
         let id32 = id as i32;
         match self.kind {
             AppKind::Sonarr => sonarr::apis::backup_api::create_system_backup_restore_by_id(
@@ -458,6 +582,21 @@ impl ServarrClient {
             )
             .await
             .map_err(map_sdk_err),
+            // The restore endpoint answers with an empty body.
+            AppKind::Readarr | AppKind::Whisparr => {
+                let url = self
+                    .http
+                    .base_url()
+                    .join(&format!("system/backup/restore/{id}"))?;
+                let resp = self.http.inner().post(url).send().await?;
+                if resp.status().is_success() {
+                    Ok(())
+                } else {
+                    let status = resp.status().as_u16();
+                    let body = resp.text().await.unwrap_or_default();
+                    Err(ApiError::ApiResponse { status, body })
+                }
+            }
             AppKind::Prowlarr => prowlarr::apis::backup_api::create_system_backup_restore_by_id(
                 &self.prowlarr_config,
                 id32,
@@ -469,6 +608,7 @@ impl ServarrClient {
 
     /// DELETE `/api/v3/system/backup/{id}` — delete a backup.
     pub async fn delete_backup(&self, id: i64) -> Result<(), ApiError> {
+        crate::faults::api(self.kind.as_str(), "delete_backup").await?;
         let id32 = id as i32;
         match self.kind {
             AppKind::Sonarr => {
@@ -486,6 +626,9 @@ impl ServarrClient {
                     .await
                     .map_err(map_sdk_err)
             }
+            AppKind::Readarr | AppKind::Whisparr => {
+                self.http.delete(&format!("system/backup/{id}")).await
+            }
             AppKind::Prowlarr => {
                 prowlarr::apis::backup_api::delete_system_backup(&self.prowlarr_config, id32)
                     .await
@@ -494,6 +637,241 @@ impl ServarrClient {
         }
     }
 
+    /// Download a backup archive listed by [`list_backups`](Self::list_backups).
+    ///
+    /// `backup.path` (e.g. `/backup/scheduled/sonarr_backup.zip`) is relative
+    /// to the app root rather than the `api/v3/` base, and the app accepts
+    /// the API key header for it.
+    pub async fn download_backup(&self, backup: &Backup) -> Result<Vec<u8>, ApiError> {
+        crate::faults::api(self.kind.as_str(), "download_backup").await?;
+        let path = format!("../../{}", backup.path.trim_start_matches('/'));
+        self.http.get_bytes(&path).await
+    }
+
+    /// GET `/api/v3/queue` — the first 1000 queue items. Prowlarr has no
+    /// queue and returns an empty list.
+    pub async fn queue(&self) -> Result<Vec<QueueItem>, ApiError> {
+        crate::faults::api(self.kind.as_str(), "queue").await?;
+        if self.kind == AppKind::Prowlarr {
+            return Ok(Vec::new());
+        }
+        let page: QueuePage = self.http.get("queue?page=1&pageSize=1000").await?;
+        Ok(page.records)
+    }
+
+    /// DELETE `/api/v3/queue/{id}` — remove a queue item. With `blocklist`
+    /// the release is never grabbed again; unless `skip_redownload`, the app
+    /// then searches for a replacement.
+    pub async fn remove_queue_item(
+        &self,
+        id: i64,
+        remove_from_client: bool,
+        blocklist: bool,
+        skip_redownload: bool,
+    ) -> Result<(), ApiError> {
+        crate::faults::api(self.kind.as_str(), "remove_queue_item").await?;
+        self.http
+            .delete(&format!(
+                "queue/{id}?removeFromClient={remove_from_client}&blocklist={blocklist}&skipRedownload={skip_redownload}"
+            ))
+            .await
+    }
+
+    /// GET `/api/v3/qualityprofile` — every quality profile, as the app
+    /// returns them. Profiles are kept as raw JSON so fields the operator
+    /// doesn't manage (format scores, Radarr's language) round-trip intact.
+    pub async fn quality_profiles(&self) -> Result<Vec<serde_json::Value>, ApiError> {
+        crate::faults::api(self.kind.as_str(), "quality_profiles").await?;
+        self.http.get("qualityprofile").await
+    }
+
+    /// GET `/api/v3/qualityprofile/schema` — a blank profile listing every
+    /// quality and quality group the app knows, none of them allowed.
+    pub async fn quality_profile_schema(&self) -> Result<serde_json::Value, ApiError> {
+        crate::faults::api(self.kind.as_str(), "quality_profile_schema").await?;
+        self.http.get("qualityprofile/schema").await
+    }
+
+    /// POST `/api/v3/qualityprofile` — create a profile.
+    pub async fn create_quality_profile(
+        &self,
+        profile: &serde_json::Value,
+    ) -> Result<serde_json::Value, ApiError> {
+        crate::faults::api(self.kind.as_str(), "create_quality_profile").await?;
+        self.http.post("qualityprofile", profile).await
+    }
+
+    /// PUT `/api/v3/qualityprofile/{id}` — replace a profile.
+    pub async fn update_quality_profile(
+        &self,
+        id: i64,
+        profile: &serde_json::Value,
+    ) -> Result<serde_json::Value, ApiError> {
+        crate::faults::api(self.kind.as_str(), "update_quality_profile").await?;
+        self.http
+            .put(&format!("qualityprofile/{id}"), profile)
+            .await
+    }
+
+    /// GET `/api/v3/customformat` — every custom format, as raw JSON.
+    pub async fn custom_formats(&self) -> Result<Vec<serde_json::Value>, ApiError> {
+        crate::faults::api(self.kind.as_str(), "custom_formats").await?;
+        self.http.get("customformat").await
+    }
+
+    /// POST `/api/v3/customformat` — create a custom format.
+    pub async fn create_custom_format(
+        &self,
+        format: &serde_json::Value,
+    ) -> Result<serde_json::Value, ApiError> {
+        crate::faults::api(self.kind.as_str(), "create_custom_format").await?;
+        self.http.post("customformat", format).await
+    }
+
+    /// PUT `/api/v3/customformat/{id}` — replace a custom format.
+    pub async fn update_custom_format(
+        &self,
+        id: i64,
+        format: &serde_json::Value,
+    ) -> Result<serde_json::Value, ApiError> {
+        crate::faults::api(self.kind.as_str(), "update_custom_format").await?;
+        self.http.put(&format!("customformat/{id}"), format).await
+    }
+
+    /// GET `/api/v3/qualitydefinition` — the size limits of every quality.
+    pub async fn quality_definitions(&self) -> Result<Vec<serde_json::Value>, ApiError> {
+        crate::faults::api(self.kind.as_str(), "quality_definitions").await?;
+        self.http.get("qualitydefinition").await
+    }
+
+    /// PUT `/api/v3/qualitydefinition/update` — replace several quality
+    /// definitions at once.
+    pub async fn update_quality_definitions(
+        &self,
+        definitions: &[serde_json::Value],
+    ) -> Result<Vec<serde_json::Value>, ApiError> {
+        crate::faults::api(self.kind.as_str(), "update_quality_definitions").await?;
+        self.http
+            .put("qualitydefinition/update", &definitions)
+            .await
+    }
+
+    /// GET `/api/v3/downloadclient` — every download client, as raw JSON.
+    pub async fn download_clients(&self) -> Result<Vec<serde_json::Value>, ApiError> {
+        crate::faults::api(self.kind.as_str(), "download_clients").await?;
+        self.http.get("downloadclient").await
+    }
+
+    /// GET `/api/v3/downloadclient/schema` — a blank download client of
+    /// each implementation the app supports, with its default fields.
+    pub async fn download_client_schema(&self) -> Result<Vec<serde_json::Value>, ApiError> {
+        crate::faults::api(self.kind.as_str(), "download_client_schema").await?;
+        self.http.get("downloadclient/schema").await
+    }
+
+    /// POST `/api/v3/downloadclient` — add a download client. The app tests
+    /// the connection first and rejects the client if it can't reach it.
+    pub async fn create_download_client(
+        &self,
+        download_client: &serde_json::Value,
+    ) -> Result<serde_json::Value, ApiError> {
+        crate::faults::api(self.kind.as_str(), "create_download_client").await?;
+        self.http.post("downloadclient", download_client).await
+    }
+
+    /// PUT `/api/v3/downloadclient/{id}` — replace a download client.
+    pub async fn update_download_client(
+        &self,
+        id: i64,
+        download_client: &serde_json::Value,
+    ) -> Result<serde_json::Value, ApiError> {
+        crate::faults::api(self.kind.as_str(), "update_download_client").await?;
+        self.http
+            .put(&format!("downloadclient/{id}"), download_client)
+            .await
+    }
+
+    /// DELETE `/api/v3/downloadclient/{id}`
+    pub async fn delete_download_client(&self, id: i64) -> Result<(), ApiError> {
+        crate::faults::api(self.kind.as_str(), "delete_download_client").await?;
+        self.http.delete(&format!("downloadclient/{id}")).await
+    }
+
+    /// GET `/api/v3/tag` — every tag.
+    pub async fn tags(&self) -> Result<Vec<Tag>, ApiError> {
+        crate::faults::api(self.kind.as_str(), "tags").await?;
+        self.http.get("tag").await
+    }
+
+    /// POST `/api/v3/tag` — create a tag. The apps lowercase the label.
+    pub async fn create_tag(&self, label: &str) -> Result<Tag, ApiError> {
+        crate::faults::api(self.kind.as_str(), "create_tag").await?;
+        self.http
+            .post(
+                "tag",
+                &Tag {
+                    id: 0,
+                    label: label.to_string(),
+                },
+            )
+            .await
+    }
+
+    /// GET `/api/v3/config/ui` — the app's UI preferences (theme, calendar,
+    /// date formats, language), as raw JSON.
+    pub async fn ui_config(&self) -> Result<serde_json::Value, ApiError> {
+        crate::faults::api(self.kind.as_str(), "ui_config").await?;
+        self.http.get("config/ui").await
+    }
+
+    /// PUT `/api/v3/config/ui/{id}` — replace the UI preferences.
+    pub async fn update_ui_config(
+        &self,
+        id: i64,
+        config: &serde_json::Value,
+    ) -> Result<serde_json::Value, ApiError> {
+        crate::faults::api(self.kind.as_str(), "update_ui_config").await?;
+        self.http.put(&format!("config/ui/{id}"), config).await
+    }
+
+    /// GET `/api/v3/config/mediamanagement` — Sonarr/Radarr file handling
+    /// settings (hardlinks, recycle bin, free space), as raw JSON.
+    pub async fn media_management_config(&self) -> Result<serde_json::Value, ApiError> {
+        crate::faults::api(self.kind.as_str(), "media_management_config").await?;
+        self.http.get("config/mediamanagement").await
+    }
+
+    /// PUT `/api/v3/config/mediamanagement/{id}` — replace the media
+    /// management settings.
+    pub async fn update_media_management_config(
+        &self,
+        id: i64,
+        config: &serde_json::Value,
+    ) -> Result<serde_json::Value, ApiError> {
+        crate::faults::api(self.kind.as_str(), "update_media_management_config").await?;
+        self.http
+            .put(&format!("config/mediamanagement/{id}"), config)
+            .await
+    }
+
+    /// GET `/api/v3/config/indexer` — search settings shared by all
+    /// indexers (retention, RSS interval, Radarr's availability delay), as
+    /// raw JSON.
+    pub async fn indexer_config(&self) -> Result<serde_json::Value, ApiError> {
+        crate::faults::api(self.kind.as_str(), "indexer_config").await?;
+        self.http.get("config/indexer").await
+    }
+
+    /// PUT `/api/v3/config/indexer/{id}` — replace the indexer settings.
+    pub async fn update_indexer_config(
+        &self,
+        id: i64,
+        config: &serde_json::Value,
+    ) -> Result<serde_json::Value, ApiError> {
+        crate::faults::api(self.kind.as_str(), "update_indexer_config").await?;
+        self.http.put(&format!("config/indexer/{id}"), config).await
+    }
+
     /// Configure Forms authentication credentials via `PUT /api/v3/config/host`.
     ///
     /// Fetches the current host configuration, sets `authenticationMethod` to
@@ -508,12 +886,48 @@ impl ServarrClient {
     /// already enabled and the client has no valid API key.  The caller should
     /// treat that as "credentials already configured" rather than a fatal error.
     pub async fn configure_admin(&self, username: &str, password: &str) -> Result<(), ApiError> {
+        crate::faults::api(self.kind.as_str(), "configure_admin").await?;
+        self.configure_auth("forms", None, Some((username, password)))
+            .await
+    }
+
+    /// Replace the app's API key via `PUT /api/v3/config/host`. The app
+    /// writes it to `config.xml`; an `{APP}__AUTH__APIKEY` env var still
+    /// wins after the next restart.
+    pub async fn set_api_key(&self, api_key: &str) -> Result<(), ApiError> {
+        crate::faults::api(self.kind.as_str(), "set_api_key").await?;
         let mut config: serde_json::Value = self.http.get("config/host").await?;
         let id = config.get("id").and_then(|v| v.as_i64()).unwrap_or(1);
-        config["authenticationMethod"] = serde_json::json!("forms");
-        config["username"] = serde_json::json!(username);
-        config["password"] = serde_json::json!(password);
-        config["passwordConfirmation"] = serde_json::json!(password);
+        config["apiKey"] = serde_json::json!(api_key);
+        let _: serde_json::Value = self.http.put(&format!("config/host/{id}"), &config).await?;
+        Ok(())
+    }
+
+    /// Set the web UI authentication via `PUT /api/v3/config/host`.
+    ///
+    /// `method` and `required` take the API's values (`forms`, `basic`,
+    /// `external`; `enabled`, `disabledForLocalAddresses`); `required` is
+    /// left as it is when `None`. `credentials` sets the login's username
+    /// and password. The same 401 caveat as
+    /// [`configure_admin`](Self::configure_admin) applies.
+    pub async fn configure_auth(
+        &self,
+        method: &str,
+        required: Option<&str>,
+        credentials: Option<(&str, &str)>,
+    ) -> Result<(), ApiError> {
+        crate::faults::api(self.kind.as_str(), "configure_auth").await?;
+        let mut config: serde_json::Value = self.http.get("config/host").await?;
+        let id = config.get("id").and_then(|v| v.as_i64()).unwrap_or(1);
+        config["authenticationMethod"] = serde_json::json!(method);
+        if let Some(required) = required {
+            config["authenticationRequired"] = serde_json::json!(required);
+        }
+        if let Some((username, password)) = credentials {
+            config["username"] = serde_json::json!(username);
+            config["password"] = serde_json::json!(password);
+            config["passwordConfirmation"] = serde_json::json!(password);
+        }
         let _: serde_json::Value = self.http.put(&format!("config/host/{id}"), &config).await?;
         Ok(())
     }
@@ -521,6 +935,7 @@ impl ServarrClient {
 
 impl HealthCheck for ServarrClient {
     async fn is_healthy(&self) -> Result<bool, ApiError> {
+        crate::faults::api(self.kind.as_str(), "is_healthy").await?;
         let status = self.system_status().await?;
         Ok(!status.version.is_empty())
     }
@@ -588,6 +1003,15 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn api_version_per_kind() {
+        assert_eq!(AppKind::Sonarr.api_version(), "v3");
+        assert_eq!(AppKind::Whisparr.api_version(), "v3");
+        assert_eq!(AppKind::Lidarr.api_version(), "v1");
+        assert_eq!(AppKind::Readarr.api_version(), "v1");
+        assert_eq!(AppKind::Prowlarr.api_version(), "v1");
+    }
+
     #[test]
     fn new_with_invalid_url_returns_error() {
         let result = ServarrClient::new("not a url", "key", AppKind::Sonarr);
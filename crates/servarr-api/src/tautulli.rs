@@ -25,6 +25,7 @@ impl TautulliClient {
     ///
     /// Calls `GET /api/v2?cmd=set_credentials&username=...&password=...`.
     pub async fn set_credentials(&self, username: &str, password: &str) -> Result<(), ApiError> {
+        crate::faults::api("tautulli", "set_credentials").await?;
         let mut url = self.http.base_url().clone();
         url.query_pairs_mut()
             .append_pair("cmd", "set_credentials")
@@ -51,6 +52,7 @@ impl TautulliClient {
 
 impl HealthCheck for TautulliClient {
     async fn is_healthy(&self) -> Result<bool, ApiError> {
+        crate::faults::api("tautulli", "is_healthy").await?;
         let mut url = self.http.base_url().clone();
         url.query_pairs_mut()
             .append_pair("cmd", "status")
@@ -110,11 +110,13 @@ impl TransmissionClient {
 
     /// Fetch session info via `session-get`.
     pub async fn session_get(&self) -> Result<SessionInfo, ApiError> {
+        crate::faults::api("transmission", "session_get").await?;
         self.rpc_call("session-get", None).await
     }
 
     /// Fetch transfer statistics via `session-stats`.
     pub async fn session_stats(&self) -> Result<SessionStats, ApiError> {
+        crate::faults::api("transmission", "session_stats").await?;
         self.rpc_call("session-stats", None).await
     }
 
@@ -125,6 +127,7 @@ impl TransmissionClient {
     /// or when the client reconnects. Create a new `TransmissionClient` with
     /// the updated credentials for subsequent calls.
     pub async fn session_set_auth(&self, username: &str, password: &str) -> Result<(), ApiError> {
+        crate::faults::api("transmission", "session_set_auth").await?;
         let args = serde_json::json!({
             "rpc-authentication-required": true,
             "rpc-username": username,
@@ -181,6 +184,7 @@ impl TransmissionClient {
 
 impl HealthCheck for TransmissionClient {
     async fn is_healthy(&self) -> Result<bool, ApiError> {
+        crate::faults::api("transmission", "is_healthy").await?;
         let info = self.session_get().await?;
         Ok(!info.version.is_empty())
     }
@@ -1,9 +1,10 @@
 use servarr_api::HealthCheck;
+use servarr_api::servarr_v3::Backup;
 use servarr_api::{
-    ApiError, AppKind, HttpClient, JellyfinClient, OverseerrClient, PlexClient, ProwlarrClient,
-    SabnzbdClient, SecretError, ServarrClient, TransmissionClient,
+    ApiError, AppKind, HttpClient, JellyfinClient, OverseerrClient, PlexClient, PlexTvClient,
+    ProwlarrClient, ReleaseClient, SabnzbdClient, SecretError, ServarrClient, TransmissionClient,
 };
-use wiremock::matchers::{method, path, path_regex};
+use wiremock::matchers::{body_partial_json, header, method, path, path_regex, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 // ---------------------------------------------------------------------------
@@ -471,6 +472,53 @@ mod plex_client {
     }
 }
 
+mod plex_tv_client {
+    use super::*;
+
+    #[tokio::test]
+    async fn lists_and_updates_shares() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/identity"))
+            .and(header("Accept", "application/json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "MediaContainer": {"size": 0, "machineIdentifier": "abc123", "version": "1.40"}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/servers/abc123/shared_servers"))
+            .and(header("X-Plex-Token", "owner-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<MediaContainer><SharedServer id="9" username="kid" email="kid@example.com"><Section id="101" title="Movies" shared="1"/><Section id="102" title="TV" shared="0"/></SharedServer></MediaContainer>"#,
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/servers/abc123/shared_servers/9"))
+            .and(body_partial_json(serde_json::json!({
+                "shared_server": {"library_section_ids": [101, 102]}
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let plex = PlexClient::new(&server.uri()).unwrap();
+        let machine_id = plex.machine_identifier().await.unwrap();
+        assert_eq!(machine_id, "abc123");
+        let tv = PlexTvClient::new(&server.uri(), "owner-token").unwrap();
+        let shares = tv.list_shares(&machine_id).await.unwrap();
+        assert_eq!(shares.len(), 1);
+        assert!(shares[0].is_for("KID@example.com"));
+        assert_eq!(shares[0].section_ids, vec![101]);
+        tv.update_share(&machine_id, shares[0].id, &[101, 102])
+            .await
+            .unwrap();
+    }
+}
+
 // ---------------------------------------------------------------------------
 // JellyfinClient tests
 // ---------------------------------------------------------------------------
@@ -517,6 +565,52 @@ mod jellyfin_client {
         let client = JellyfinClient::new(&server.uri()).unwrap();
         assert!(!client.is_healthy().await.unwrap());
     }
+
+    #[tokio::test]
+    async fn create_user_and_limit_libraries() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/Library/VirtualFolders"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"Name": "Movies", "ItemId": "f137a2dd21bbc1b99aa5c0f6bf02a805", "Locations": []}
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/Users/New"))
+            .and(body_partial_json(serde_json::json!({"Name": "kid"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Id": "u1",
+                "Name": "kid",
+                "Policy": {"EnableAllFolders": true, "EnabledFolders": []}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/Users/u1/Policy"))
+            .and(body_partial_json(
+                serde_json::json!({"EnableAllFolders": false}),
+            ))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = JellyfinClient::new(&server.uri()).unwrap();
+        let libraries = client.list_libraries("token").await.unwrap();
+        assert_eq!(libraries[0].name, "Movies");
+        let user = client.create_user("token", "kid", "pw").await.unwrap();
+        assert_eq!(user.policy["EnableAllFolders"], serde_json::json!(true));
+        let mut policy = user.policy.clone();
+        policy["EnableAllFolders"] = false.into();
+        policy["EnabledFolders"] = serde_json::json!([libraries[0].item_id]);
+        client
+            .set_user_policy("token", &user.id, &policy)
+            .await
+            .unwrap();
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -642,6 +736,29 @@ mod servarr_client {
         assert_eq!(backups[1].name, "backup_2.zip");
     }
 
+    #[tokio::test]
+    async fn download_backup_fetches_archive_relative_to_app_root() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/backup/scheduled/backup_1.zip"))
+            .and(header("X-Api-Key", "test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"PK\x03\x04".to_vec()))
+            .mount(&server)
+            .await;
+
+        let client = ServarrClient::new(&server.uri(), "test-key", AppKind::Sonarr).unwrap();
+        let backup = Backup {
+            id: 1,
+            name: "backup_1.zip".into(),
+            path: "/backup/scheduled/backup_1.zip".into(),
+            size: 4,
+            time: "2024-01-10T08:00:00Z".into(),
+        };
+        let bytes = client.download_backup(&backup).await.unwrap();
+        assert_eq!(bytes, b"PK\x03\x04");
+    }
+
     #[tokio::test]
     async fn is_healthy_returns_true_when_version_present() {
         let server = MockServer::start().await;
@@ -739,6 +856,395 @@ mod servarr_client {
         let result = client.delete_backup(7).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn queue_returns_records() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/queue"))
+            .and(query_param("pageSize", "1000"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "page": 1,
+                "pageSize": 1000,
+                "totalRecords": 1,
+                "records": [{
+                    "id": 12,
+                    "title": "Show.S01E01.1080p.WEB",
+                    "status": "completed",
+                    "trackedDownloadStatus": "warning",
+                    "trackedDownloadState": "importPending",
+                    "statusMessages": [{
+                        "title": "Show.S01E01.1080p.WEB",
+                        "messages": ["No files found are eligible for import"]
+                    }],
+                    "added": "2025-01-01T00:00:00Z",
+                    "downloadClient": "Transmission"
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = ServarrClient::new(&server.uri(), "test-key", AppKind::Sonarr).unwrap();
+        let queue = client.queue().await.unwrap();
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].id, 12);
+        assert_eq!(
+            queue[0].tracked_download_state.as_deref(),
+            Some("importPending")
+        );
+        assert!(
+            queue[0]
+                .messages()
+                .any(|m| m == "No files found are eligible for import")
+        );
+    }
+
+    #[tokio::test]
+    async fn remove_queue_item_sends_options() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/api/v3/queue/12"))
+            .and(query_param("removeFromClient", "true"))
+            .and(query_param("blocklist", "true"))
+            .and(query_param("skipRedownload", "false"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ServarrClient::new(&server.uri(), "test-key", AppKind::Radarr).unwrap();
+        let result = client.remove_queue_item(12, true, true, false).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn quality_profile_endpoints() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/qualityprofile"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "id": 1, "name": "Any", "cutoff": 1, "items": [] }
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/qualityprofile/schema"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "name": "", "items": [] })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/qualityprofile"))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(serde_json::json!({ "id": 2, "name": "HD" })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v3/qualityprofile/1"))
+            .respond_with(
+                ResponseTemplate::new(202)
+                    .set_body_json(serde_json::json!({ "id": 1, "name": "Any" })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ServarrClient::new(&server.uri(), "test-key", AppKind::Sonarr).unwrap();
+        let profiles = client.quality_profiles().await.unwrap();
+        assert_eq!(profiles[0]["name"], "Any");
+        let schema = client.quality_profile_schema().await.unwrap();
+        assert!(schema["items"].is_array());
+        let created = client
+            .create_quality_profile(&serde_json::json!({ "name": "HD" }))
+            .await
+            .unwrap();
+        assert_eq!(created["id"], 2);
+        client
+            .update_quality_profile(1, &profiles[0])
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn custom_format_and_quality_definition_endpoints() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/customformat"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "id": 4, "name": "x265 (HD)", "specifications": [] }
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/customformat"))
+            .and(body_partial_json(serde_json::json!({ "name": "3D" })))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(serde_json::json!({ "id": 5, "name": "3D" })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v3/customformat/4"))
+            .respond_with(
+                ResponseTemplate::new(202)
+                    .set_body_json(serde_json::json!({ "id": 4, "name": "x265 (HD)" })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/qualitydefinition"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "id": 1, "quality": { "id": 4, "name": "HDTV-720p" }, "minSize": 0, "maxSize": 100 }
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v3/qualitydefinition/update"))
+            .and(body_partial_json(serde_json::json!([{ "id": 1 }])))
+            .respond_with(ResponseTemplate::new(202).set_body_json(serde_json::json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ServarrClient::new(&server.uri(), "test-key", AppKind::Radarr).unwrap();
+        let formats = client.custom_formats().await.unwrap();
+        assert_eq!(formats[0]["name"], "x265 (HD)");
+        let created = client
+            .create_custom_format(&serde_json::json!({ "name": "3D" }))
+            .await
+            .unwrap();
+        assert_eq!(created["id"], 5);
+        client.update_custom_format(4, &formats[0]).await.unwrap();
+        let definitions = client.quality_definitions().await.unwrap();
+        assert_eq!(definitions[0]["quality"]["name"], "HDTV-720p");
+        client
+            .update_quality_definitions(&definitions)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn root_folder_create_and_delete() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v3/rootfolder"))
+            .and(body_partial_json(serde_json::json!({ "path": "/tv" })))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(serde_json::json!({ "id": 3, "path": "/tv/" })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/api/v3/rootfolder/2"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ServarrClient::new(&server.uri(), "test-key", AppKind::Sonarr).unwrap();
+        let folder = client.create_root_folder("/tv").await.unwrap();
+        assert_eq!(folder.id, 3);
+        client.delete_root_folder(2).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn download_client_endpoints() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/downloadclient"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "id": 1, "name": "transmission", "implementation": "Transmission" }
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/downloadclient/schema"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "implementation": "Sabnzbd", "fields": [] }
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/downloadclient"))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(serde_json::json!({ "id": 2, "name": "sabnzbd" })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v3/downloadclient/1"))
+            .respond_with(ResponseTemplate::new(202).set_body_json(serde_json::json!({ "id": 1 })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/api/v3/downloadclient/1"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ServarrClient::new(&server.uri(), "test-key", AppKind::Sonarr).unwrap();
+        let existing = client.download_clients().await.unwrap();
+        assert_eq!(existing[0]["implementation"], "Transmission");
+        let schema = client.download_client_schema().await.unwrap();
+        let created = client.create_download_client(&schema[0]).await.unwrap();
+        assert_eq!(created["id"], 2);
+        client
+            .update_download_client(1, &existing[0])
+            .await
+            .unwrap();
+        client.delete_download_client(1).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn tag_endpoints_follow_the_api_version() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v1/tag"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "id": 1, "label": "4k" }
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/tag"))
+            .and(body_partial_json(serde_json::json!({ "label": "anime" })))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(serde_json::json!({ "id": 2, "label": "anime" })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ServarrClient::new(&server.uri(), "test-key", AppKind::Lidarr).unwrap();
+        let tags = client.tags().await.unwrap();
+        assert_eq!(tags[0].label, "4k");
+        let created = client.create_tag("anime").await.unwrap();
+        assert_eq!(created.id, 2);
+    }
+
+    #[tokio::test]
+    async fn ui_config_endpoints() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/config/ui"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "id": 1, "theme": "auto", "firstDayOfWeek": 0 }),
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v3/config/ui/1"))
+            .and(body_partial_json(serde_json::json!({ "theme": "dark" })))
+            .respond_with(
+                ResponseTemplate::new(202)
+                    .set_body_json(serde_json::json!({ "id": 1, "theme": "dark" })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ServarrClient::new(&server.uri(), "test-key", AppKind::Radarr).unwrap();
+        let mut config = client.ui_config().await.unwrap();
+        assert_eq!(config["firstDayOfWeek"], 0);
+        config["theme"] = "dark".into();
+        client.update_ui_config(1, &config).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn media_management_config_endpoints() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/config/mediamanagement"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 1,
+                "copyUsingHardlinks": false,
+                "recycleBin": "",
+                "fileDate": "none"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v3/config/mediamanagement/1"))
+            .and(body_partial_json(serde_json::json!({
+                "copyUsingHardlinks": true,
+                "fileDate": "none"
+            })))
+            .respond_with(
+                ResponseTemplate::new(202)
+                    .set_body_json(serde_json::json!({ "id": 1, "copyUsingHardlinks": true })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ServarrClient::new(&server.uri(), "test-key", AppKind::Sonarr).unwrap();
+        let mut config = client.media_management_config().await.unwrap();
+        assert_eq!(config["recycleBin"], "");
+        config["copyUsingHardlinks"] = true.into();
+        client
+            .update_media_management_config(1, &config)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn indexer_config_endpoints() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/config/indexer"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 1,
+                "retention": 0,
+                "rssSyncInterval": 60,
+                "availabilityDelay": 0
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v3/config/indexer/1"))
+            .and(body_partial_json(serde_json::json!({
+                "availabilityDelay": 7,
+                "rssSyncInterval": 60
+            })))
+            .respond_with(
+                ResponseTemplate::new(202)
+                    .set_body_json(serde_json::json!({ "id": 1, "availabilityDelay": 7 })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ServarrClient::new(&server.uri(), "test-key", AppKind::Radarr).unwrap();
+        let mut config = client.indexer_config().await.unwrap();
+        assert_eq!(config["rssSyncInterval"], 60);
+        config["availabilityDelay"] = 7.into();
+        client.update_indexer_config(1, &config).await.unwrap();
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -1086,6 +1592,60 @@ mod servarr_client_multi_kind {
         assert!(client.delete_backup(10).await.is_ok());
     }
 
+    // -- Readarr and Whisparr (plain HTTP, no SDK) ----------------------------
+
+    #[tokio::test]
+    async fn readarr_uses_api_v1() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/system/status"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "appName": "Readarr",
+                "version": "0.4.18.2805"
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = ServarrClient::new(&server.uri(), "test-api-key", AppKind::Readarr).unwrap();
+        let status = client.system_status().await.unwrap();
+        assert_eq!(status.app_name, "Readarr");
+        assert!(client.is_healthy().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn readarr_backups() {
+        let server = MockServer::start().await;
+        mount_standard_mocks(&server, "Readarr").await;
+
+        let client = ServarrClient::new(&server.uri(), "test-api-key", AppKind::Readarr).unwrap();
+        let backups = client.list_backups().await.unwrap();
+        assert_eq!(backups[0].id, 10);
+        assert!(client.restore_backup(10).await.is_ok());
+        assert!(client.delete_backup(10).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn whisparr_uses_api_v3() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/health"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                    "source": "IndexerStatusCheck",
+                    "type": "warning",
+                    "message": "Indexers unavailable"
+                }])),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ServarrClient::new(&server.uri(), "test-api-key", AppKind::Whisparr).unwrap();
+        let checks = client.health().await.unwrap();
+        assert_eq!(checks[0].check_type, "warning");
+    }
+
     // -- Sonarr root_folder (complete coverage for all 4 kinds) ---------------
 
     #[tokio::test]
@@ -1590,4 +2150,208 @@ mod prowlarr_client {
         let result = client.delete_application(3).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn app_profiles_list_and_update() {
+        let server = MockServer::start().await;
+        let profile = serde_json::json!({
+            "id": 1,
+            "name": "Standard",
+            "enableRss": true,
+            "enableAutomaticSearch": true,
+            "enableInteractiveSearch": true,
+            "minimumSeeders": 1
+        });
+
+        Mock::given(method("GET"))
+            .and(path("/api/v1/appprofile"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([profile])))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/appprofile/1"))
+            .and(body_partial_json(serde_json::json!({"enableRss": false})))
+            .respond_with(ResponseTemplate::new(202).set_body_json(serde_json::json!({
+                "id": 1,
+                "name": "Standard",
+                "enableRss": false,
+                "enableAutomaticSearch": true,
+                "enableInteractiveSearch": true,
+                "minimumSeeders": 1
+            })))
+            .mount(&server)
+            .await;
+
+        let client = ProwlarrClient::new(&server.uri(), "test-key").unwrap();
+        let mut profiles = client.list_app_profiles().await.unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].name, "Standard");
+        assert!(profiles[0].enable_rss);
+
+        profiles[0].enable_rss = false;
+        let updated = client.update_app_profile(1, &profiles[0]).await.unwrap();
+        assert!(!updated.enable_rss);
+    }
+
+    #[tokio::test]
+    async fn indexers_list_and_add() {
+        let server = MockServer::start().await;
+        let indexer = serde_json::json!({
+            "id": 4,
+            "name": "1337x",
+            "implementation": "Cardigann",
+            "configContract": "CardigannSettings",
+            "definitionName": "1337x",
+            "enable": true,
+            "priority": 25,
+            "appProfileId": 1,
+            "fields": [{"name": "definitionFile", "value": "1337x"}],
+            "tags": [2]
+        });
+
+        Mock::given(method("GET"))
+            .and(path("/api/v1/indexer"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([indexer])))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/indexer"))
+            .and(body_partial_json(serde_json::json!({
+                "name": "1337x",
+                "definitionName": "1337x",
+                "tags": [2]
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(indexer.clone()))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/tag"))
+            .and(body_partial_json(serde_json::json!({"label": "public"})))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(serde_json::json!({"id": 2, "label": "public"})),
+            )
+            .mount(&server)
+            .await;
+
+        let client = ProwlarrClient::new(&server.uri(), "test-key").unwrap();
+        let indexers = client.list_indexers().await.unwrap();
+        assert_eq!(indexers.len(), 1);
+        assert_eq!(indexers[0].definition_name, "1337x");
+        assert_eq!(indexers[0].fields[0].name, "definitionFile");
+        assert_eq!(indexers[0].tags, vec![2]);
+
+        let tag = client.create_tag("public").await.unwrap();
+        assert_eq!(tag.id, 2);
+
+        let mut desired = indexers[0].clone();
+        desired.id = 0;
+        let created = client.add_indexer(&desired).await.unwrap();
+        assert_eq!(created.id, 4);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ReleaseClient tests
+// ---------------------------------------------------------------------------
+
+mod release_client {
+    use super::*;
+
+    #[tokio::test]
+    async fn latest_github_release_returns_tag() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/Sonarr/Sonarr/releases/latest"))
+            .and(header("accept", "application/json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "tag_name": "v4.0.16.2944",
+                "name": "4.0.16.2944",
+                "prerelease": false
+            })))
+            .mount(&server)
+            .await;
+
+        let client = ReleaseClient::new(&server.uri()).unwrap();
+        let tag = client.latest_github_release("Sonarr/Sonarr").await.unwrap();
+        assert_eq!(tag, "v4.0.16.2944");
+    }
+
+    #[tokio::test]
+    async fn latest_github_release_surfaces_rate_limit() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(403).set_body_string("rate limit exceeded"))
+            .mount(&server)
+            .await;
+
+        let client = ReleaseClient::new(&server.uri()).unwrap();
+        let err = client
+            .latest_github_release("Radarr/Radarr")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ApiError::ApiResponse { status: 403, .. }),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn version_feed_returns_map() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/versions.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sonarr": "4.0.16.2944",
+                "plex": "1.41.5"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = ReleaseClient::new("https://api.github.com").unwrap();
+        let feed = client
+            .version_feed(&format!("{}/versions.json", server.uri()))
+            .await
+            .unwrap();
+        assert_eq!(feed["plex"], "1.41.5");
+        assert_eq!(feed.len(), 2);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Fault injection tests
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "fault-injection")]
+mod fault_injection {
+    use super::*;
+
+    #[test]
+    fn injected_error_is_returned_until_its_count_runs_out() {
+        temp_env::with_var(
+            servarr_api::faults::ENV,
+            Some("api:prowlarr.list_tags=error:503*1"),
+            || {
+                tokio::runtime::Runtime::new().unwrap().block_on(async {
+                    let server = MockServer::start().await;
+                    Mock::given(method("GET"))
+                        .and(path("/api/v1/tag"))
+                        .respond_with(
+                            ResponseTemplate::new(200).set_body_json(serde_json::json!([])),
+                        )
+                        .expect(1)
+                        .mount(&server)
+                        .await;
+
+                    let client = ProwlarrClient::new(&server.uri(), "test-key").unwrap();
+                    let err = client.list_tags().await.unwrap_err();
+                    assert!(
+                        matches!(err, ApiError::ApiResponse { status: 503, .. }),
+                        "expected an injected 503, got: {err}"
+                    );
+                    assert!(client.list_tags().await.unwrap().is_empty());
+                });
+            },
+        );
+    }
 }
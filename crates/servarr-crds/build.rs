@@ -13,6 +13,20 @@ struct AppImageConfig {
     downloads: bool,
     probe_path: Option<String>,
     probe_type: Option<String>,
+    #[serde(default = "default_architectures")]
+    architectures: Vec<String>,
+    #[serde(default)]
+    arch_images: BTreeMap<String, ArchImage>,
+}
+
+#[derive(Deserialize)]
+struct ArchImage {
+    repository: String,
+    tag: String,
+}
+
+fn default_architectures() -> Vec<String> {
+    vec!["amd64".into(), "arm64".into()]
 }
 
 fn main() {
@@ -45,6 +59,11 @@ fn main() {
     code.push_str("    pub downloads: bool,\n");
     code.push_str("    pub probe_path: &'static str,\n");
     code.push_str("    pub probe_type: &'static str,\n");
+    code.push_str("    pub architectures: &'static [&'static str],\n");
+    code.push_str(
+        "    /// `(arch, repository, tag)` for architectures needing a different image.\n",
+    );
+    code.push_str("    pub arch_images: &'static [(&'static str, &'static str, &'static str)],\n");
     code.push_str("}\n\n");
     code.push_str("pub(crate) fn image_defaults(app: &str) -> Option<AppImageDefaults> {\n");
     code.push_str("    match app {\n");
@@ -52,6 +71,11 @@ fn main() {
     for (name, cfg) in &apps {
         let probe_path = cfg.probe_path.as_deref().unwrap_or("/");
         let probe_type = cfg.probe_type.as_deref().unwrap_or("http");
+        let arch_images: Vec<_> = cfg
+            .arch_images
+            .iter()
+            .map(|(arch, img)| (arch, &img.repository, &img.tag))
+            .collect();
         code.push_str(&format!(
             "        {name:?} => Some(AppImageDefaults {{\n\
              \x20           repository: {:?},\n\
@@ -61,8 +85,18 @@ fn main() {
              \x20           downloads: {},\n\
              \x20           probe_path: {:?},\n\
              \x20           probe_type: {:?},\n\
+             \x20           architectures: &{:?},\n\
+             \x20           arch_images: &{:?},\n\
              \x20       }}),\n",
-            cfg.repository, cfg.tag, cfg.port, cfg.security, cfg.downloads, probe_path, probe_type,
+            cfg.repository,
+            cfg.tag,
+            cfg.port,
+            cfg.security,
+            cfg.downloads,
+            probe_path,
+            probe_type,
+            cfg.architectures,
+            arch_images,
         ));
     }
 
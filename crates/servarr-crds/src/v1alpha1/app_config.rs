@@ -1,14 +1,253 @@
+use std::collections::BTreeMap;
+
 use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
 use serde::{Deserialize, Serialize};
 
+use super::types::{CredentialsSecretRef, default_true, is_under};
+use super::{AppType, PersistenceSpec};
+
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum AppConfig {
     Transmission(TransmissionConfig),
+    Qbittorrent(QbittorrentConfig),
     Sabnzbd(SabnzbdConfig),
     Prowlarr(ProwlarrConfig),
     SshBastion(SshBastionConfig),
     Overseerr(Box<OverseerrConfig>),
+    /// Jellyseerr is a fork of Overseerr and takes the same settings.
+    Jellyseerr(Box<OverseerrConfig>),
+    Bazarr(BazarrConfig),
+    TdarrNode(TdarrNodeConfig),
+    Sonarr(ArrConfig),
+    Radarr(ArrConfig),
+    /// Lidarr only takes `tags`.
+    Lidarr(ArrConfig),
+    Jellyfin(MediaServerConfig),
+    Plex(MediaServerConfig),
+}
+
+impl AppConfig {
+    /// The BitTorrent peer port of a torrent client, which gets its own
+    /// container, Service, and NetworkPolicy ports.
+    pub fn peer_port(&self) -> Option<&PeerPortConfig> {
+        match self {
+            Self::Transmission(tc) => tc.peer_port.as_ref(),
+            Self::Qbittorrent(qc) => qc.peer_port.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+// --- Sonarr / Radarr ---
+
+/// Settings the operator keeps in sync inside Sonarr or Radarr through
+/// their v3 API. Lidarr takes only `tags`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ArrConfig {
+    /// Custom formats to create, or update when a format of the same name
+    /// exists. Applied before `qualityProfiles`, so profiles can score
+    /// them; formats not listed here are left alone.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_formats: Vec<CustomFormat>,
+    /// Quality profiles to create, or update when a profile of the same
+    /// name exists. Re-applied on every reconcile; profiles not listed
+    /// here are left alone.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quality_profiles: Vec<QualityProfile>,
+    /// Root folders the app should have.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root_folders: Option<RootFoldersSpec>,
+    /// File handling settings on the app's Media Management page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_management: Option<MediaManagementSpec>,
+    /// How the app monitors the library once items are in it. A `split4k`
+    /// stack entry's 4K instance gets the same settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monitoring: Option<MonitoringSpec>,
+    /// Tags to create, by label. Release profiles, delay profiles, and
+    /// download clients choose series, movies, or artists by tag, so these
+    /// exist before anything refers to them. Tags not listed here are left
+    /// alone.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// Sonarr/Radarr media management settings, applied through
+/// `/api/v3/config/mediamanagement`. Only the fields that are set are
+/// managed; the rest keep whatever the app has.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaManagementSpec {
+    /// Hardlink imported files instead of copying them when the download
+    /// and the library are on the same filesystem.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub use_hardlinks: Option<bool>,
+    /// Delete series or movie folders left empty by a rescan.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delete_empty_folders: Option<bool>,
+    /// Free space in MB an import must leave on the library's disk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum_free_space_mb: Option<u32>,
+    /// Absolute path deleted files are moved to instead of being removed;
+    /// empty to delete them outright. Must be on a mounted volume.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recycle_bin: Option<String>,
+}
+
+impl MediaManagementSpec {
+    /// Fields of the app's `config/mediamanagement` resource this spec
+    /// sets, with the values the API expects.
+    pub fn api_fields(&self) -> Vec<(&'static str, serde_json::Value)> {
+        let mut fields = Vec::new();
+        if let Some(hardlinks) = self.use_hardlinks {
+            fields.push(("copyUsingHardlinks", hardlinks.into()));
+        }
+        if let Some(delete) = self.delete_empty_folders {
+            fields.push(("deleteEmptyFolders", delete.into()));
+        }
+        if let Some(mb) = self.minimum_free_space_mb {
+            fields.push(("minimumFreeSpaceWhenImporting", mb.into()));
+        }
+        if let Some(ref path) = self.recycle_bin {
+            fields.push(("recycleBin", path.as_str().into()));
+        }
+        fields
+    }
+}
+
+/// Sonarr/Radarr monitoring defaults. Only the fields that are set are
+/// managed. The monitor and season folder choices in the add-series and
+/// add-movie dialogs are kept by the browser, not the app, so they can't be
+/// set here.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitoringSpec {
+    /// Unmonitor an episode or movie when its file is deleted outside the
+    /// app, so it isn't grabbed again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unmonitor_deleted: Option<bool>,
+    /// Create each series or movie folder when it is added, before
+    /// anything is imported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub create_empty_folders: Option<bool>,
+    /// Radarr only: days before (negative) or after a movie's minimum
+    /// availability date to start searching for it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub availability_delay_days: Option<i32>,
+}
+
+impl MonitoringSpec {
+    /// Fields of the app's `config/mediamanagement` resource this spec
+    /// sets, under the names `app` uses.
+    pub fn media_management_fields(&self, app: &AppType) -> Vec<(&'static str, serde_json::Value)> {
+        let (unmonitor, empty_folders) = match app {
+            AppType::Radarr => (
+                "autoUnmonitorPreviouslyDownloadedMovies",
+                "createEmptyMovieFolders",
+            ),
+            _ => (
+                "autoUnmonitorPreviouslyDownloadedEpisodes",
+                "createEmptySeriesFolders",
+            ),
+        };
+        let mut fields = Vec::new();
+        if let Some(unmonitor_deleted) = self.unmonitor_deleted {
+            fields.push((unmonitor, unmonitor_deleted.into()));
+        }
+        if let Some(create) = self.create_empty_folders {
+            fields.push((empty_folders, create.into()));
+        }
+        fields
+    }
+
+    /// Fields of the app's `config/indexer` resource this spec sets. Only
+    /// Radarr has any.
+    pub fn indexer_fields(&self, app: &AppType) -> Vec<(&'static str, serde_json::Value)> {
+        match (app, self.availability_delay_days) {
+            (AppType::Radarr, Some(days)) => vec![("availabilityDelay", days.into())],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Root folders kept in sync inside Sonarr or Radarr.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RootFoldersSpec {
+    /// Absolute paths inside the container, e.g. `/tv`. Each must be on a
+    /// mounted volume for the app to accept it.
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Remove root folders the app has that aren't listed in `paths`. The
+    /// series or movies under them stay in the library.
+    #[serde(default)]
+    pub auto_remove: bool,
+}
+
+/// A Sonarr/Radarr quality profile.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QualityProfile {
+    /// Profile name, unique within the app.
+    pub name: String,
+    /// Allowed qualities or quality groups by the name the app shows, e.g.
+    /// `Bluray-1080p` or `WEB 1080p`, most preferred first. Naming a group
+    /// allows every quality in it.
+    pub qualities: Vec<String>,
+    /// Quality at which upgrades stop (default: the first of `qualities`).
+    #[serde(default)]
+    pub cutoff: Option<String>,
+    /// Upgrade releases until the cutoff is reached (default: true).
+    #[serde(default = "default_true")]
+    pub upgrade_allowed: bool,
+    /// Minimum custom format score a release needs (default: 0).
+    #[serde(default)]
+    pub min_format_score: i32,
+    /// Custom format score at which upgrades stop (default: 0).
+    #[serde(default)]
+    pub cutoff_format_score: i32,
+    /// Scores by custom format name. Formats not listed keep the score the
+    /// app has, which is 0 for a new profile.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub format_scores: BTreeMap<String, i32>,
+}
+
+/// A Sonarr/Radarr custom format.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomFormat {
+    /// Format name, unique within the app.
+    pub name: String,
+    /// Make the format available to the `{Custom Formats}` renaming token.
+    #[serde(default)]
+    pub include_custom_format_when_renaming: bool,
+    /// Conditions a release is matched against.
+    pub specifications: Vec<CustomFormatSpecification>,
+}
+
+/// One condition of a custom format, in the shape of the app's custom
+/// format JSON export.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomFormatSpecification {
+    pub name: String,
+    /// Condition type, e.g. `ReleaseTitleSpecification`,
+    /// `ResolutionSpecification`, or `SourceSpecification`.
+    pub implementation: String,
+    /// Match releases that fail the condition instead.
+    #[serde(default)]
+    pub negate: bool,
+    /// The release must match this condition for the format to apply.
+    /// Otherwise any one matching condition is enough.
+    #[serde(default)]
+    pub required: bool,
+    /// Condition settings by field name, e.g. `value`, or `min` and `max`
+    /// for `SizeSpecification`.
+    #[serde(default)]
+    #[schemars(schema_with = "json_object_schema")]
+    pub fields: BTreeMap<String, serde_json::Value>,
 }
 
 // --- Prowlarr ---
@@ -33,6 +272,93 @@ pub struct ProwlarrConfig {
     /// Custom indexer definitions to place in /config/Definitions/Custom.
     #[serde(default)]
     pub custom_definitions: Vec<IndexerDefinition>,
+    /// Indexers to create, or update when an indexer of the same name
+    /// exists. Re-applied on every reconcile. Requires `apiKeySecret`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub indexers: Vec<ProwlarrIndexer>,
+    /// Remove indexers Prowlarr has that aren't listed in `indexers`.
+    #[serde(default)]
+    pub auto_remove: bool,
+}
+
+/// An indexer kept in sync inside Prowlarr.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProwlarrIndexer {
+    /// Indexer name, unique within Prowlarr.
+    pub name: String,
+    /// Prowlarr implementation, e.g. `Cardigann`, `Newznab`, or `Torznab`.
+    pub implementation: String,
+    /// Definition to use for implementations with several, e.g. `1337x`
+    /// for `Cardigann`. Picks the matching entry of Prowlarr's indexer
+    /// schema, whose defaults fill in the fields not set here.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition_name: Option<String>,
+    /// Indexer settings by field name, e.g. `baseUrl` or `apiPath`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(schema_with = "json_object_schema")]
+    pub fields: BTreeMap<String, serde_json::Value>,
+    /// Secret in the app's namespace whose keys are set as fields, for
+    /// passwords, cookies, and API keys that shouldn't be in the spec.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields_secret: Option<String>,
+    /// Tag labels, created in Prowlarr when missing.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Whether the indexer is enabled (default: true).
+    #[serde(default = "default_true")]
+    pub enable: bool,
+    /// Indexer priority, 1 (highest) to 50 (default: Prowlarr's, 25).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i32>,
+    /// Name of the app profile to use (default: Prowlarr's first).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_profile: Option<String>,
+}
+
+// --- Jellyfin / Plex ---
+
+/// Accounts the operator keeps in sync on a media server, so they survive
+/// the server being rebuilt or restored from a backup.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaServerConfig {
+    /// Users to create, or update when one of the same name exists.
+    /// Re-applied on every reconcile. Jellyfin signs in with
+    /// `adminCredentials`; Plex needs `plexTokenSecret`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub users: Vec<MediaServerUser>,
+    /// Remove accounts not listed in `users`: Jellyfin users (never
+    /// administrators) or Plex library shares.
+    #[serde(default)]
+    pub auto_remove: bool,
+    /// Plex only: Secret with a `token` key holding the server owner's
+    /// plex.tv token. Plex accounts live on plex.tv, so users are managed
+    /// by sharing the server's libraries with them there.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plex_token_secret: Option<String>,
+}
+
+/// A media server account and the libraries it may see.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaServerUser {
+    /// Account name, unique on the server (case-insensitive).
+    pub username: String,
+    /// Jellyfin only: Secret with a `password` key. The password is reset
+    /// to it whenever signing in with it fails.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_secret: Option<String>,
+    /// Identity the user signs in with elsewhere, in place of a password.
+    /// For Plex, the plex.tv username or email to share with (default:
+    /// `username`). For Jellyfin, the account gets a random password, so
+    /// it can only sign in through an SSO plugin that links it by
+    /// `username`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sso_subject: Option<String>,
+    /// Library names the user may access; empty for all of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub libraries: Vec<String>,
 }
 
 // --- SABnzbd ---
@@ -49,6 +375,11 @@ pub struct SabnzbdConfig {
     /// a post-processing script.
     #[serde(default)]
     pub tar_unpack: bool,
+    /// A Secret managed outside the operator holding SABnzbd's API key
+    /// under `apiKeyKey`. Replaces `apiKeySecret`; the operator never
+    /// creates it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials_secret_ref: Option<CredentialsSecretRef>,
 }
 
 // --- Transmission ---
@@ -63,6 +394,247 @@ pub struct TransmissionConfig {
     pub peer_port: Option<PeerPortConfig>,
     #[serde(default)]
     pub auth: Option<TransmissionAuth>,
+    /// A Secret managed outside the operator holding the RPC login under
+    /// `usernameKey` and `passwordKey`. An alternative to `auth`, whose
+    /// Secret must use the keys `USER` and `PASS`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials_secret_ref: Option<CredentialsSecretRef>,
+    /// Download, incomplete, watch, and per-category directories. Rendered
+    /// into settings.json and created by the settings init container.
+    #[serde(default)]
+    pub directories: Option<TransmissionDirectories>,
+}
+
+impl TransmissionConfig {
+    /// The Secret and the username and password keys of the RPC login,
+    /// from `credentialsSecretRef` or `auth`.
+    pub fn rpc_login(&self) -> Option<(&str, &str, &str)> {
+        match (&self.credentials_secret_ref, &self.auth) {
+            (Some(creds), _) => Some((&creds.name, &creds.username_key, &creds.password_key)),
+            (None, Some(auth)) => Some((&auth.secret_name, "USER", "PASS")),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Where Transmission keeps its files. Every path must be absolute and lie
+/// under one of the app's writable volume mounts, so that the *arr apps see
+/// downloads at the same path Transmission reports them.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TransmissionDirectories {
+    /// Completed downloads (`download-dir`).
+    #[serde(default)]
+    pub download: Option<String>,
+    /// Downloads in progress (`incomplete-dir`). Setting it also sets
+    /// `incomplete-dir-enabled`.
+    #[serde(default)]
+    pub incomplete: Option<String>,
+    /// Folder watched for `.torrent` files (`watch-dir`, default `/watch`).
+    #[serde(default)]
+    pub watch: Option<String>,
+    /// Add torrents dropped into the watch folder (`watch-dir-enabled`,
+    /// default: true).
+    #[serde(default = "default_true")]
+    pub watch_enabled: bool,
+    /// Download directory per category, keyed by the category (Transmission
+    /// label) the *arr apps tag their torrents with, e.g. `tv-sonarr`.
+    #[serde(default)]
+    pub categories: BTreeMap<String, String>,
+}
+
+impl Default for TransmissionDirectories {
+    fn default() -> Self {
+        Self {
+            download: None,
+            incomplete: None,
+            watch: None,
+            watch_enabled: true,
+            categories: BTreeMap::new(),
+        }
+    }
+}
+
+/// Mount path of the emptyDir the operator provides for the watch folder.
+pub const TRANSMISSION_WATCH_MOUNT: &str = "/watch";
+
+impl TransmissionDirectories {
+    /// The settings.json keys these directories manage.
+    pub const SETTINGS_KEYS: &[&str] = &[
+        "download-dir",
+        "incomplete-dir",
+        "incomplete-dir-enabled",
+        "watch-dir",
+        "watch-dir-enabled",
+    ];
+
+    /// settings.json entries for the configured directories.
+    pub fn settings(&self) -> serde_json::Map<String, serde_json::Value> {
+        let mut settings = serde_json::Map::new();
+        if let Some(ref dir) = self.download {
+            settings.insert("download-dir".into(), dir.clone().into());
+        }
+        if let Some(ref dir) = self.incomplete {
+            settings.insert("incomplete-dir".into(), dir.clone().into());
+            settings.insert("incomplete-dir-enabled".into(), true.into());
+        }
+        let watch = self.watch.as_deref().unwrap_or(TRANSMISSION_WATCH_MOUNT);
+        settings.insert("watch-dir".into(), watch.into());
+        settings.insert("watch-dir-enabled".into(), self.watch_enabled.into());
+        settings
+    }
+
+    /// Every configured directory, labelled with the field it came from.
+    pub fn paths(&self) -> Vec<(String, &str)> {
+        let mut paths = Vec::new();
+        if let Some(ref dir) = self.download {
+            paths.push(("download".to_string(), dir.as_str()));
+        }
+        if let Some(ref dir) = self.incomplete {
+            paths.push(("incomplete".to_string(), dir.as_str()));
+        }
+        if let Some(ref dir) = self.watch {
+            paths.push(("watch".to_string(), dir.as_str()));
+        }
+        for (category, dir) in &self.categories {
+            paths.push((format!("categories.{category}"), dir.as_str()));
+        }
+        paths
+    }
+
+    /// Check that every directory is an absolute path under a writable mount
+    /// of `persistence` (or the watch emptyDir, for `watch`). Returns one
+    /// message per problem.
+    pub fn validate(&self, persistence: &PersistenceSpec) -> Vec<String> {
+        let mut errors = Vec::new();
+        for category in self.categories.keys() {
+            if category.is_empty() || category.contains(char::is_whitespace) {
+                errors.push(format!(
+                    "appConfig.transmission.directories.categories: '{category}' must be non-empty without whitespace"
+                ));
+            }
+        }
+        for (field, path) in self.paths() {
+            let field = format!("appConfig.transmission.directories.{field}");
+            if !path.starts_with('/') || path.split('/').any(|c| c == "." || c == "..") {
+                errors.push(format!(
+                    "{field}: '{path}' must be an absolute path without '.' or '..'"
+                ));
+                continue;
+            }
+            if field.ends_with(".watch") && is_under(path, TRANSMISSION_WATCH_MOUNT) {
+                continue;
+            }
+            match persistence.mount_for(path) {
+                None => errors.push(format!(
+                    "{field}: '{path}' is not under any volume or NFS mount"
+                )),
+                Some((mount, true)) => {
+                    errors.push(format!("{field}: '{path}' is on read-only mount '{mount}'"))
+                }
+                Some(_) => {}
+            }
+        }
+        errors
+    }
+}
+
+// --- qBittorrent ---
+
+/// Settings the operator writes into qBittorrent's config before each
+/// start. The web UI listens on the Service's container port.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QbittorrentConfig {
+    /// Incoming BitTorrent connection port (`Session\Port`).
+    /// `randomOnStart` is not supported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer_port: Option<PeerPortConfig>,
+    /// Save path per category, keyed by the category the *arr apps tag
+    /// their torrents with, e.g. `tv-sonarr`. Written to `categories.json`,
+    /// replacing categories added in the web UI, and created by the
+    /// settings init container. Paths must be absolute and lie under one of
+    /// the app's writable volume mounts.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub categories: BTreeMap<String, String>,
+    /// Global transfer limits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed_limits: Option<QbittorrentSpeedLimits>,
+}
+
+/// qBittorrent transfer limits in KiB/s; 0 means unlimited. Unset limits
+/// keep whatever qBittorrent has.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QbittorrentSpeedLimits {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload: Option<u32>,
+    /// Download limit while the alternative speed limits are on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alt_download: Option<u32>,
+    /// Upload limit while the alternative speed limits are on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alt_upload: Option<u32>,
+}
+
+impl QbittorrentSpeedLimits {
+    /// `qBittorrent.conf` entries for the limits that are set, as
+    /// `section.key` pairs. qBittorrent stores them in KiB/s.
+    pub fn settings(&self) -> Vec<(&'static str, u32)> {
+        [
+            ("BitTorrent.Session\\GlobalDLSpeedLimit", self.download),
+            ("BitTorrent.Session\\GlobalUPSpeedLimit", self.upload),
+            (
+                "BitTorrent.Session\\AlternativeGlobalDLSpeedLimit",
+                self.alt_download,
+            ),
+            (
+                "BitTorrent.Session\\AlternativeGlobalUPSpeedLimit",
+                self.alt_upload,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(key, limit)| Some((key, limit?)))
+        .collect()
+    }
+}
+
+impl QbittorrentConfig {
+    /// Check the categories: names without whitespace, and save paths that
+    /// are absolute and under a writable mount of `persistence`. Returns one
+    /// message per problem.
+    pub fn validate(&self, persistence: &PersistenceSpec) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.peer_port.as_ref().is_some_and(|p| p.random_on_start) {
+            errors.push("appConfig.qbittorrent.peerPort.randomOnStart is not supported".into());
+        }
+        for (category, path) in &self.categories {
+            let field = format!("appConfig.qbittorrent.categories.{category}");
+            if category.is_empty() || category.contains(char::is_whitespace) {
+                errors.push(format!(
+                    "appConfig.qbittorrent.categories: '{category}' must be non-empty without whitespace"
+                ));
+            }
+            if !path.starts_with('/') || path.split('/').any(|c| c == "." || c == "..") {
+                errors.push(format!(
+                    "{field}: '{path}' must be an absolute path without '.' or '..'"
+                ));
+                continue;
+            }
+            match persistence.mount_for(path) {
+                None => errors.push(format!(
+                    "{field}: '{path}' is not under any volume or NFS mount"
+                )),
+                Some((mount, true)) => {
+                    errors.push(format!("{field}: '{path}' is on read-only mount '{mount}'"))
+                }
+                Some(_) => {}
+            }
+        }
+        errors
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
@@ -156,6 +728,32 @@ pub struct SshUser {
     /// SSH public keys (one per line).
     #[serde(default)]
     pub public_keys: String,
+    /// Chroot this user to one of the app's persistence volumes (only
+    /// applies when mode is sftp).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sftp_chroot: Option<SftpChroot>,
+}
+
+/// A per-user SFTP chroot built from a persistence volume.
+///
+/// The user is locked into `/sftp/<user>`, where the volume (or `subPath`
+/// within it) appears as `/<volume>`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SftpChroot {
+    /// Name of an entry in `persistence.volumes` or `persistence.nfsMounts`.
+    pub volume: String,
+    /// Directory within the volume to expose (default: the volume root).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub_path: Option<String>,
+    /// Mount the directory read-only (default: false).
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+impl SftpChroot {
+    /// Root of all per-user chroot directories in the bastion container.
+    pub const ROOT: &str = "/sftp";
 }
 
 /// SSH access mode.
@@ -239,3 +837,127 @@ pub struct OverseerrServerDefaults4k {
     #[serde(default)]
     pub enable_season_folders: Option<bool>,
 }
+
+// --- Bazarr ---
+
+/// Subtitle settings the operator keeps in sync inside Bazarr through its
+/// settings API. Requires `apiKeySecret`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BazarrConfig {
+    /// Subtitle providers to enable, by Bazarr's provider ID, e.g.
+    /// `opensubtitlescom` or `podnapisi`. Replaces the enabled providers
+    /// when set; providers that need an account must still be logged in
+    /// to from the web UI.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub providers: Vec<String>,
+    /// Language profiles to create, or update when a profile of the same
+    /// name exists. Profiles not listed here are left alone. Their
+    /// languages are enabled in Bazarr.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub language_profiles: Vec<BazarrLanguageProfile>,
+    /// Profile assigned to new series and movies. Must be one of
+    /// `languageProfiles`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+}
+
+/// A Bazarr language profile.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BazarrLanguageProfile {
+    /// Profile name, unique within Bazarr.
+    pub name: String,
+    /// Subtitle languages as ISO 639-1 codes, e.g. `en`, most preferred
+    /// first.
+    pub languages: Vec<String>,
+}
+
+impl BazarrConfig {
+    /// Every language of the profiles, in order of first use.
+    pub fn languages(&self) -> Vec<&str> {
+        let mut languages: Vec<&str> = Vec::new();
+        for profile in &self.language_profiles {
+            for language in &profile.languages {
+                if !languages.contains(&language.as_str()) {
+                    languages.push(language);
+                }
+            }
+        }
+        languages
+    }
+
+    /// Check profile names are unique and non-empty, languages are ISO
+    /// 639-1 codes, and the default profile is one of the profiles.
+    /// Returns one message per problem.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mut names = std::collections::BTreeSet::new();
+        for profile in &self.language_profiles {
+            if profile.name.trim().is_empty() {
+                errors.push("appConfig.bazarr.languageProfiles: name must not be empty".into());
+            } else if !names.insert(profile.name.as_str()) {
+                errors.push(format!(
+                    "appConfig.bazarr.languageProfiles: duplicate profile '{}'",
+                    profile.name
+                ));
+            }
+            if profile.languages.is_empty() {
+                errors.push(format!(
+                    "appConfig.bazarr.languageProfiles.{}: languages must not be empty",
+                    profile.name
+                ));
+            }
+            for language in &profile.languages {
+                if language.len() != 2 || !language.chars().all(|c| c.is_ascii_lowercase()) {
+                    errors.push(format!(
+                        "appConfig.bazarr.languageProfiles.{}: '{language}' is not an ISO 639-1 code",
+                        profile.name
+                    ));
+                }
+            }
+        }
+        if let Some(ref default) = self.default_profile
+            && !names.contains(default.as_str())
+        {
+            errors.push(format!(
+                "appConfig.bazarr.defaultProfile: '{default}' is not in languageProfiles"
+            ));
+        }
+        if self.providers.iter().any(|p| p.trim().is_empty()) {
+            errors.push("appConfig.bazarr.providers: provider IDs must not be empty".into());
+        }
+        errors
+    }
+}
+
+// --- Tdarr ---
+
+/// Port the Tdarr server listens on for its nodes, next to the web UI.
+pub const TDARR_SERVER_PORT: i32 = 8266;
+
+/// Transcoding workers for a Tdarr server.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TdarrNodeConfig {
+    /// Name of the Tdarr ServarrApp in the same namespace the nodes register
+    /// with. Defaults to `tdarr`, or to the stack's Tdarr app for a
+    /// MediaStack child.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<String>,
+    /// Number of nodes, each in its own Deployment so every node has its own
+    /// name in Tdarr: the first is named after the app, the others
+    /// `{app}-1`, `{app}-2`, and so on. Defaults to 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_count: Option<i32>,
+}
+
+impl TdarrNodeConfig {
+    pub fn node_count(&self) -> i32 {
+        self.node_count.unwrap_or(1)
+    }
+
+    pub fn server(&self) -> &str {
+        self.server.as_deref().unwrap_or("tdarr")
+    }
+}
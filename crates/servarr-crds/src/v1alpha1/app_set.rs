@@ -0,0 +1,239 @@
+use std::collections::{BTreeMap, HashSet};
+
+use kube::CustomResource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::spec::{ServarrAppSpec, is_valid_instance};
+use super::status::Condition;
+
+/// Label on every ServarrApp generated by a ServarrAppSet, valued with the
+/// set's name.
+pub const APP_SET_LABEL: &str = "servarr.dev/app-set";
+
+/// Label carrying the name of the set instance a ServarrApp was generated
+/// from.
+pub const APP_SET_INSTANCE_LABEL: &str = "servarr.dev/app-set-instance";
+
+// ---------------------------------------------------------------------------
+// ServarrAppSet CRD
+// ---------------------------------------------------------------------------
+
+/// Stamps out one ServarrApp per entry in `instances` from a shared template,
+/// e.g. a Sonarr per audio language or an Overseerr per family member.
+/// Generated apps are named `{set}-{instance}`, owned by the set, and
+/// deleted when their instance is removed from the list.
+#[derive(CustomResource, Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[kube(
+    group = "servarr.dev",
+    version = "v1alpha1",
+    kind = "ServarrAppSet",
+    namespaced,
+    status = "ServarrAppSetStatus",
+    shortname = "sas",
+    printcolumn = r#"{"name":"Desired","type":"integer","jsonPath":".status.replicas"}"#,
+    printcolumn = r#"{"name":"Ready","type":"integer","jsonPath":".status.readyReplicas"}"#
+)]
+#[serde(rename_all = "camelCase")]
+pub struct ServarrAppSetSpec {
+    /// The ServarrApp every instance is generated from.
+    pub template: ServarrAppTemplate,
+
+    /// One entry per generated ServarrApp.
+    #[serde(default)]
+    pub instances: Vec<AppSetInstance>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ServarrAppTemplate {
+    /// Extra labels for every generated ServarrApp.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+
+    /// ServarrApp spec shared by all instances. String values may reference
+    /// instance parameters as `$(name)`; `$(instance)` is the instance name.
+    /// `instance` defaults to the instance name so the generated apps don't
+    /// collide with each other.
+    pub spec: ServarrAppSpec,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AppSetInstance {
+    /// Instance name, a lowercase DNS label. The generated ServarrApp is
+    /// named `{set}-{name}`.
+    pub name: String,
+
+    /// Values substituted for `$(key)` references in the template.
+    #[serde(default)]
+    pub parameters: BTreeMap<String, String>,
+}
+
+/// A ServarrApp to be generated for one instance of a set.
+#[derive(Clone, Debug)]
+pub struct RenderedApp {
+    pub name: String,
+    pub instance: String,
+    pub labels: BTreeMap<String, String>,
+    pub spec: ServarrAppSpec,
+}
+
+impl ServarrAppSetSpec {
+    /// Check that instance names are distinct and usable in resource names.
+    /// Returns one message per problem.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mut seen = HashSet::new();
+        for (i, instance) in self.instances.iter().enumerate() {
+            if !is_valid_instance(&instance.name) {
+                errors.push(format!(
+                    "instances[{i}]: name '{}' must be a lowercase DNS label (a-z, 0-9, '-')",
+                    instance.name
+                ));
+            }
+            if !seen.insert(instance.name.as_str()) {
+                errors.push(format!(
+                    "instances[{i}]: duplicate name '{}'",
+                    instance.name
+                ));
+            }
+        }
+        errors
+    }
+
+    /// Render the ServarrApp for every instance of the set `set_name`.
+    pub fn render(&self, set_name: &str) -> Result<Vec<RenderedApp>, String> {
+        let errors = self.validate();
+        if !errors.is_empty() {
+            return Err(errors.join("; "));
+        }
+        let template = serde_json::to_value(&self.template.spec).map_err(|e| e.to_string())?;
+
+        self.instances
+            .iter()
+            .map(|instance| {
+                let mut params = instance.parameters.clone();
+                params.insert("instance".into(), instance.name.clone());
+
+                let mut value = template.clone();
+                substitute(&mut value, &params)
+                    .map_err(|e| format!("instance '{}': {e}", instance.name))?;
+                let mut spec: ServarrAppSpec = serde_json::from_value(value)
+                    .map_err(|e| format!("instance '{}': {e}", instance.name))?;
+                if spec.instance.is_none() {
+                    spec.instance = Some(instance.name.clone());
+                }
+
+                let mut labels = self.template.labels.clone();
+                labels.insert(APP_SET_LABEL.into(), set_name.into());
+                labels.insert(APP_SET_INSTANCE_LABEL.into(), instance.name.clone());
+
+                Ok(RenderedApp {
+                    name: format!("{set_name}-{}", instance.name),
+                    instance: instance.name.clone(),
+                    labels,
+                    spec,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Replace `$(key)` in every string of `value` with `params[key]`. A
+/// reference to a key that isn't in `params` is an error, so a typo doesn't
+/// end up in a hostname or path.
+fn substitute(
+    value: &mut serde_json::Value,
+    params: &BTreeMap<String, String>,
+) -> Result<(), String> {
+    match value {
+        serde_json::Value::String(s) => {
+            let mut out = String::with_capacity(s.len());
+            let mut rest = s.as_str();
+            while let Some(start) = rest.find("$(") {
+                let Some(len) = rest[start + 2..].find(')') else {
+                    break;
+                };
+                let key = &rest[start + 2..start + 2 + len];
+                let param = params
+                    .get(key)
+                    .ok_or_else(|| format!("unknown parameter '$({key})'"))?;
+                out.push_str(&rest[..start]);
+                out.push_str(param);
+                rest = &rest[start + 3 + len..];
+            }
+            out.push_str(rest);
+            *s = out;
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                substitute(item, params)?;
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for item in map.values_mut() {
+                substitute(item, params)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// ServarrAppSet Status
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ServarrAppSetStatus {
+    /// Number of instances in the spec.
+    #[serde(default)]
+    pub replicas: i32,
+    #[serde(default)]
+    pub ready_replicas: i32,
+    /// Label selector matching the generated ServarrApps, for
+    /// `kubectl get servarrapps -l`.
+    #[serde(default)]
+    pub selector: String,
+    #[serde(default)]
+    pub instances: Vec<AppSetInstanceStatus>,
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    #[serde(default)]
+    pub observed_generation: i64,
+    /// What a reconcile would change, one entry per generated ServarrApp.
+    /// Only set while the operator runs with `--dry-run`; cleared by the
+    /// next real reconcile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub would_change: Option<Vec<String>>,
+}
+
+impl ServarrAppSetStatus {
+    pub fn set_condition(&mut self, cond: Condition) {
+        if let Some(existing) = self
+            .conditions
+            .iter_mut()
+            .find(|c| c.condition_type == cond.condition_type)
+        {
+            *existing = cond;
+        } else {
+            self.conditions.push(cond);
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AppSetInstanceStatus {
+    pub name: String,
+    /// Name of the generated ServarrApp.
+    pub app: String,
+    #[serde(default)]
+    pub ready: bool,
+    /// Why the instance isn't applied, e.g. a ServarrApp of the same name
+    /// that the set doesn't own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
@@ -0,0 +1,169 @@
+use std::collections::HashSet;
+
+use kube::CustomResource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::spec::is_valid_instance;
+use super::status::Condition;
+
+/// Longest usable ServarrCredential name: the projected volume is named
+/// `credential-{name}` and volume names are DNS labels.
+pub const MAX_CREDENTIAL_NAME_LEN: usize = 52;
+
+// ---------------------------------------------------------------------------
+// ServarrCredential CRD
+// ---------------------------------------------------------------------------
+
+/// A set of credentials (an indexer login, a Usenet provider, a VPN account)
+/// kept in one Secret and shared by every ServarrApp in the namespace that
+/// lists it under `spec.credentials`. Each consuming app gets its own copy
+/// of the Secret, so rotating the source Secret rolls every consumer.
+#[derive(CustomResource, Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[kube(
+    group = "servarr.dev",
+    version = "v1alpha1",
+    kind = "ServarrCredential",
+    namespaced,
+    status = "ServarrCredentialStatus",
+    shortname = "scred",
+    printcolumn = r#"{"name":"Type","type":"string","jsonPath":".spec.type"}"#,
+    printcolumn = r#"{"name":"Secret","type":"string","jsonPath":".spec.secretName"}"#,
+    printcolumn = r#"{"name":"Consumers","type":"integer","jsonPath":".status.consumerCount"}"#
+)]
+#[serde(rename_all = "camelCase")]
+pub struct ServarrCredentialSpec {
+    /// What the credentials are for. Informational; it does not change how
+    /// they are projected.
+    #[serde(rename = "type", default)]
+    pub credential_type: CredentialType,
+
+    /// Name of the Secret in the same namespace holding the credentials.
+    /// The operator reads but never modifies it.
+    pub secret_name: String,
+
+    /// Keys of the Secret to share. Empty shares every key.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keys: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub enum CredentialType {
+    Indexer,
+    DownloadClient,
+    Vpn,
+    #[default]
+    Generic,
+}
+
+impl ServarrCredentialSpec {
+    /// Whether `key` of the source Secret is shared.
+    pub fn shares(&self, key: &str) -> bool {
+        self.keys.is_empty() || self.keys.iter().any(|k| k == key)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// References from ServarrApps
+// ---------------------------------------------------------------------------
+
+/// A ServarrCredential used by an app. Its keys are copied into a Secret
+/// named `{app}-credential-{name}` owned by the app, and exposed to the
+/// container as env vars, files, or both.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialRef {
+    /// Name of the ServarrCredential in the app's namespace.
+    pub name: String,
+
+    /// Env vars set from keys of the credential.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env: Vec<CredentialEnvVar>,
+
+    /// Directory the credential's keys are mounted into, one file per key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mount_path: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialEnvVar {
+    /// Env var name, e.g. `OPENVPN_USER`.
+    pub name: String,
+    /// Key of the credential whose value the env var gets.
+    pub key: String,
+}
+
+impl CredentialRef {
+    /// Check the reference on its own. Returns one message per problem.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let field = format!("credentials[{}]", self.name);
+        if !is_valid_instance(&self.name) || self.name.len() > MAX_CREDENTIAL_NAME_LEN {
+            errors.push(format!(
+                "{field}: name must be a lowercase DNS label of at most {MAX_CREDENTIAL_NAME_LEN} characters"
+            ));
+        }
+        if self.env.is_empty() && self.mount_path.is_none() {
+            errors.push(format!("{field}: set env, mountPath, or both"));
+        }
+        if let Some(ref path) = self.mount_path
+            && (!path.starts_with('/') || path == "/")
+        {
+            errors.push(format!(
+                "{field}: mountPath '{path}' must be an absolute path below /"
+            ));
+        }
+        let mut names = HashSet::new();
+        for var in &self.env {
+            if var.name.is_empty() || var.key.is_empty() {
+                errors.push(format!("{field}: env entries need a name and a key"));
+            } else if !names.insert(var.name.as_str()) {
+                errors.push(format!("{field}: duplicate env var '{}'", var.name));
+            }
+        }
+        errors
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ServarrCredential Status
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ServarrCredentialStatus {
+    /// ServarrApps in the namespace that reference this credential, sorted.
+    /// These are the apps rolled when the source Secret changes.
+    #[serde(default)]
+    pub consumers: Vec<String>,
+    #[serde(default)]
+    pub consumer_count: i32,
+    /// SHA-256 of the shared keys and values, so a rotation shows up as a
+    /// change here without exposing the values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_checksum: Option<String>,
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    #[serde(default)]
+    pub observed_generation: i64,
+}
+
+impl ServarrCredentialStatus {
+    /// Set a condition, keeping the existing transition time when its status
+    /// is unchanged so repeated reconciles leave the object untouched.
+    pub fn set_condition(&mut self, mut cond: Condition) {
+        if let Some(existing) = self
+            .conditions
+            .iter_mut()
+            .find(|c| c.condition_type == cond.condition_type)
+        {
+            if existing.status == cond.status {
+                cond.last_transition_time = existing.last_transition_time.clone();
+            }
+            *existing = cond;
+        } else {
+            self.conditions.push(cond);
+        }
+    }
+}
@@ -37,6 +37,51 @@ impl AppDefaults {
 
         defaults.image = image(img.repository, img.tag);
 
+        // Jellyseerr keeps its settings and database under /app/config
+        if matches!(app, super::AppType::Jellyseerr) {
+            defaults.persistence.volumes = vec![pvc("config", "/app/config", "1Gi")];
+        }
+
+        // Tdarr keeps its database in /app/server and its settings in
+        // /app/configs, and takes node connections on a second port
+        if matches!(app, super::AppType::Tdarr) {
+            defaults.persistence.volumes = vec![
+                pvc("server", "/app/server", "5Gi"),
+                pvc("config", "/app/configs", "1Gi"),
+            ];
+            defaults
+                .service
+                .ports
+                .push(sport("server", super::TDARR_SERVER_PORT));
+            defaults.env.extend([
+                env("serverIP", "0.0.0.0"),
+                env("serverPort", &super::TDARR_SERVER_PORT.to_string()),
+                env("webUIPort", &img.port.to_string()),
+                env("internalNode", "false"),
+                env("inContainer", "true"),
+            ]);
+        }
+
+        // Tdarr nodes keep no state: they are configured through env, their
+        // transcode cache is an emptyDir, and they serve nothing to probe
+        if matches!(app, super::AppType::TdarrNode) {
+            defaults.persistence.volumes = Vec::new();
+            defaults.service = single_port_service("node", img.port);
+            // `[T]` keeps the probe from matching its own command line
+            defaults.probes = exec_probes(
+                &[
+                    "/bin/sh",
+                    "-c",
+                    "grep -qs '[T]darr_Node' /proc/[0-9]*/cmdline",
+                ],
+                30,
+                10,
+            );
+            defaults
+                .env
+                .extend([env("inContainer", "true"), env("ffmpegVersion", "6")]);
+        }
+
         // App-specific config
         if matches!(app, super::AppType::Transmission) {
             defaults.app_config =
@@ -46,6 +91,35 @@ impl AppDefaults {
         defaults
     }
 
+    /// The default image for `app` on nodes of architecture `arch` (a
+    /// `kubernetes.io/arch` value such as `amd64`, `arm64`, or `arm`).
+    pub fn image_for_arch(
+        app: &super::AppType,
+        arch: &str,
+    ) -> Result<ImageSpec, UnsupportedArchitecture> {
+        let app_name = app.to_string();
+        let img = image_defaults(&app_name)
+            .unwrap_or_else(|| panic!("no image defaults for app: {app_name}"));
+        if let Some((_, repo, tag)) = img.arch_images.iter().find(|(a, _, _)| *a == arch) {
+            return Ok(image(repo, tag));
+        }
+        if img.architectures.contains(&arch) {
+            return Ok(image(img.repository, img.tag));
+        }
+        let mut supported: Vec<String> = img
+            .architectures
+            .iter()
+            .chain(img.arch_images.iter().map(|(a, _, _)| a))
+            .map(|a| a.to_string())
+            .collect();
+        supported.sort();
+        Err(UnsupportedArchitecture {
+            image: format!("{}:{}", img.repository, img.tag),
+            arch: arch.to_string(),
+            supported,
+        })
+    }
+
     fn linuxserver_base(port: i32, downloads: bool, probe_path: &str) -> Self {
         let mut volumes = vec![pvc("config", "/config", "1Gi")];
         if downloads {
@@ -58,6 +132,7 @@ impl AppDefaults {
             persistence: PersistenceSpec {
                 volumes,
                 nfs_mounts: vec![],
+                verify_mounts: false,
             },
             probes: http_probes(probe_path, 30, 10),
             resources: std_resources("1", "512Mi", "100m", "128Mi"),
@@ -76,6 +151,7 @@ impl AppDefaults {
             persistence: PersistenceSpec {
                 volumes: vec![pvc("config", "/config", "1Gi")],
                 nfs_mounts: vec![],
+                verify_mounts: false,
             },
             probes: http_probes(probe_path, 30, 10),
             resources: std_resources("1", "512Mi", "100m", "128Mi"),
@@ -111,6 +187,7 @@ impl AppDefaults {
             persistence: PersistenceSpec {
                 volumes: vec![pvc("host-keys", "/etc/ssh/keys", "10Mi")],
                 nfs_mounts: vec![],
+                verify_mounts: false,
             },
             probes: tcp_probes(30, 10),
             resources: std_resources("500m", "256Mi", "100m", "128Mi"),
@@ -122,6 +199,29 @@ impl AppDefaults {
     }
 }
 
+/// No default image is published for the architecture an app is pinned to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsupportedArchitecture {
+    pub image: String,
+    pub arch: String,
+    pub supported: Vec<String>,
+}
+
+impl std::fmt::Display for UnsupportedArchitecture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "default image {} is not published for {} (available: {}); set spec.image to a {} image",
+            self.image,
+            self.arch,
+            self.supported.join(", "),
+            self.arch
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedArchitecture {}
+
 fn image(repo: &str, tag: &str) -> ImageSpec {
     ImageSpec {
         repository: repo.into(),
@@ -138,6 +238,7 @@ fn pvc(name: &str, mount: &str, size: &str) -> PvcVolume {
         access_mode: "ReadWriteOnce".into(),
         size: size.into(),
         storage_class: String::new(),
+        ..Default::default()
     }
 }
 
@@ -155,6 +256,7 @@ fn single_port_service(name: &str, port: i32) -> ServiceSpec {
     ServiceSpec {
         service_type: "ClusterIP".into(),
         ports: vec![sport(name, port)],
+        ..Default::default()
     }
 }
 
@@ -179,6 +281,30 @@ fn tcp_probes(liveness_delay: i32, readiness_delay: i32) -> ProbeSpec {
     }
 }
 
+fn exec_probes(command: &[&str], liveness_delay: i32, readiness_delay: i32) -> ProbeSpec {
+    let command: Vec<String> = command.iter().map(|s| s.to_string()).collect();
+    ProbeSpec {
+        liveness: ProbeConfig {
+            probe_type: ProbeType::Exec,
+            command: command.clone(),
+            initial_delay_seconds: liveness_delay,
+            period_seconds: 10,
+            timeout_seconds: 1,
+            failure_threshold: 3,
+            ..Default::default()
+        },
+        readiness: ProbeConfig {
+            probe_type: ProbeType::Exec,
+            command,
+            initial_delay_seconds: readiness_delay,
+            period_seconds: 5,
+            timeout_seconds: 1,
+            failure_threshold: 3,
+            ..Default::default()
+        },
+    }
+}
+
 fn http_probes(path: &str, liveness_delay: i32, readiness_delay: i32) -> ProbeSpec {
     ProbeSpec {
         liveness: ProbeConfig {
@@ -221,8 +347,12 @@ fn std_resources(
 }
 
 fn tz_env() -> EnvVar {
+    env("TZ", "UTC")
+}
+
+fn env(name: &str, value: &str) -> EnvVar {
     EnvVar {
-        name: "TZ".into(),
-        value: "UTC".into(),
+        name: name.into(),
+        value: value.into(),
     }
 }
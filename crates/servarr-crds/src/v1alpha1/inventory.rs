@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+
+use kube::CustomResource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::spec::AppType;
+use super::status::Condition;
+
+// ---------------------------------------------------------------------------
+// ServarrInventory CRD
+// ---------------------------------------------------------------------------
+
+/// A periodic report of the version every managed app runs next to the
+/// latest upstream release, for planning upgrades across many apps. The
+/// report is kept in the status and exported as the
+/// `servarr_app_version_outdated` metric.
+#[derive(CustomResource, Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[kube(
+    group = "servarr.dev",
+    version = "v1alpha1",
+    kind = "ServarrInventory",
+    namespaced,
+    status = "ServarrInventoryStatus",
+    shortname = "sinv",
+    printcolumn = r#"{"name":"Apps","type":"integer","jsonPath":".status.total"}"#,
+    printcolumn = r#"{"name":"Outdated","type":"integer","jsonPath":".status.outdated"}"#,
+    printcolumn = r#"{"name":"Collected","type":"date","jsonPath":".status.lastCollected"}"#
+)]
+#[serde(rename_all = "camelCase")]
+pub struct ServarrInventorySpec {
+    /// Minutes between collections.
+    #[serde(default = "default_inventory_interval")]
+    pub interval_minutes: u32,
+
+    /// Namespaces whose ServarrApps are listed. Empty lists every namespace
+    /// the operator watches.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub namespaces: Vec<String>,
+
+    /// Where the latest upstream versions come from.
+    #[serde(default)]
+    pub feed: VersionFeed,
+}
+
+fn default_inventory_interval() -> u32 {
+    360
+}
+
+/// Sources of upstream versions. An app's latest version is taken from
+/// `url` when the feed lists it, otherwise from the latest GitHub release
+/// of its repository.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionFeed {
+    /// URL of a JSON object mapping app types to their latest version, e.g.
+    /// `{"sonarr": "4.0.16.2944", "plex": "1.41.5"}`. Use it for apps
+    /// without GitHub releases, or to pin the versions your fleet should run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// GitHub repositories (`owner/name`) by app type, replacing or adding
+    /// to the built-in list, e.g. `{"sonarr": "Sonarr/Sonarr"}`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub github_repos: BTreeMap<String, String>,
+
+    /// GitHub API base URL, for GitHub Enterprise or a caching proxy.
+    /// Defaults to `https://api.github.com`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_api_url: Option<String>,
+}
+
+impl ServarrInventorySpec {
+    /// Whether apps in `namespace` belong in the report.
+    pub fn includes_namespace(&self, namespace: &str) -> bool {
+        self.namespaces.is_empty() || self.namespaces.iter().any(|n| n == namespace)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ServarrInventory Status
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ServarrInventoryStatus {
+    /// When the report was last collected (RFC 3339).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_collected: Option<String>,
+    /// One entry per app, sorted by namespace and name.
+    #[serde(default)]
+    pub apps: Vec<AppVersion>,
+    #[serde(default)]
+    pub total: i32,
+    /// Apps running an older version than upstream's latest.
+    #[serde(default)]
+    pub outdated: i32,
+    /// Apps whose installed or latest version could not be determined.
+    #[serde(default)]
+    pub unknown: i32,
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    #[serde(default)]
+    pub observed_generation: i64,
+}
+
+/// One app's row in the inventory report.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AppVersion {
+    pub namespace: String,
+    pub name: String,
+    pub app: AppType,
+    /// Image the app's Deployment runs.
+    pub image: String,
+    /// Version parsed from the image tag; unset for tags like `latest`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_version: Option<String>,
+    /// Unset when either version is unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outdated: Option<bool>,
+}
+
+impl ServarrInventoryStatus {
+    /// Set a condition, keeping the existing transition time when its status
+    /// is unchanged.
+    pub fn set_condition(&mut self, mut cond: Condition) {
+        if let Some(existing) = self
+            .conditions
+            .iter_mut()
+            .find(|c| c.condition_type == cond.condition_type)
+        {
+            if existing.status == cond.status {
+                cond.last_transition_time = existing.last_transition_time.clone();
+            }
+            *existing = cond;
+        } else {
+            self.conditions.push(cond);
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::BTreeMap;
+
+use kube::CustomResource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// ---------------------------------------------------------------------------
+// MaintenanceWindow CRD
+// ---------------------------------------------------------------------------
+
+/// A time range during which matching ServarrApps in the same namespace are
+/// suspended or update-frozen, e.g. while the NAS behind their volumes is
+/// being upgraded.
+#[derive(CustomResource, Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[kube(
+    group = "servarr.dev",
+    version = "v1alpha1",
+    kind = "MaintenanceWindow",
+    namespaced,
+    shortname = "mw",
+    printcolumn = r#"{"name":"Mode","type":"string","jsonPath":".spec.mode"}"#,
+    printcolumn = r#"{"name":"Start","type":"string","jsonPath":".spec.start"}"#,
+    printcolumn = r#"{"name":"End","type":"string","jsonPath":".spec.end"}"#
+)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceWindowSpec {
+    /// Labels a ServarrApp must carry to be covered by this window. Empty
+    /// matches every app in the namespace; `servarr.dev/stack: <name>`
+    /// matches every app of a MediaStack.
+    #[serde(default)]
+    pub selector: BTreeMap<String, String>,
+
+    /// Window start as an RFC 3339 timestamp (e.g. `2025-06-01T02:00:00Z`).
+    pub start: String,
+
+    /// Window end as an RFC 3339 timestamp.
+    pub end: String,
+
+    /// What happens to matching apps while the window is active.
+    #[serde(default)]
+    pub mode: MaintenanceMode,
+
+    /// How many minutes before `start` matching apps with backups enabled
+    /// take a fresh backup. Set to 0 to skip the pre-window backup.
+    #[serde(default = "default_backup_lead_minutes")]
+    pub backup_lead_minutes: u32,
+}
+
+fn default_backup_lead_minutes() -> u32 {
+    30
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub enum MaintenanceMode {
+    /// Scale the app's Deployment to zero replicas.
+    #[default]
+    Suspend,
+    /// Keep the app running but stop applying Deployment changes, so image
+    /// bumps and config rollouts wait until the window closes.
+    Freeze,
+}
+
+impl MaintenanceWindowSpec {
+    /// Whether an app with the given labels is covered by this window.
+    pub fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        self.selector
+            .iter()
+            .all(|(k, v)| labels.get(k).is_some_and(|l| l == v))
+    }
+}
@@ -1,11 +1,15 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
+use k8s_openapi::api::core::v1::Container;
 use kube::CustomResource;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use super::app_config::AppConfig;
-use super::spec::{AppType, ServarrAppSpec, nullable_app_config_schema};
+use super::app_config::{AppConfig, TdarrNodeConfig};
+use super::credential::CredentialRef;
+use super::spec::{
+    AppType, ServarrAppSpec, container_list_schema, is_valid_instance, nullable_app_config_schema,
+};
 use super::status::Condition;
 use super::types::*;
 
@@ -35,6 +39,12 @@ pub struct MediaStackSpec {
     #[serde(default)]
     pub nfs: Option<NfsServerSpec>,
 
+    /// Shared Postgres instance. When set (and `enabled`), the operator deploys
+    /// one Postgres server for the stack and wires every compatible app to its
+    /// own database and role. Omit to keep the apps on SQLite.
+    #[serde(default)]
+    pub postgres: Option<PostgresSpec>,
+
     /// Shared defaults applied to every app in the stack. Per-app fields
     /// override these values.
     #[serde(default)]
@@ -42,6 +52,98 @@ pub struct MediaStackSpec {
 
     /// The list of apps to deploy as part of this stack.
     pub apps: Vec<StackApp>,
+
+    /// How many child apps of a tier are applied at once. Defaults to 4;
+    /// `1` applies them one at a time, and `0` is treated as `1`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apply_parallelism: Option<u32>,
+
+    /// Spread the NFS server and the stack's apps across nodes or zones.
+    /// Omit to leave their placement to the scheduler.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spread_policy: Option<SpreadPolicy>,
+}
+
+impl MediaStackSpec {
+    /// Children applied at once when `applyParallelism` is unset.
+    pub const DEFAULT_APPLY_PARALLELISM: usize = 4;
+
+    /// The effective `applyParallelism`, never below 1.
+    pub fn apply_parallelism(&self) -> usize {
+        self.apply_parallelism
+            .map_or(Self::DEFAULT_APPLY_PARALLELISM, |n| n.max(1) as usize)
+    }
+
+    /// Check that every app has a distinct `(app, instance)` pair, counting
+    /// the `4k` instance `split4k` adds and the canary's, and that instance
+    /// values are usable in resource names. Returns one message per problem.
+    pub fn validate_instances(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        for (i, app) in self.apps.iter().enumerate() {
+            if let Some(inst) = &app.instance
+                && !is_valid_instance(inst)
+            {
+                errors.push(format!(
+                    "apps[{i}]: instance '{inst}' must be a lowercase DNS label (a-z, 0-9, '-')"
+                ));
+            }
+            let mut instances = vec![app.instance.clone()];
+            if app.split4k == Some(true) {
+                instances.push(Some("4k".into()));
+            }
+            if app.canary.is_some() {
+                instances.push(Some(app.canary_instance()));
+            }
+            for inst in instances {
+                if !seen.insert((app.app.as_str(), inst.clone())) {
+                    errors.push(format!(
+                        "apps[{i}]: duplicate app={} instance={}",
+                        app.app,
+                        inst.map_or("(default)".into(), |i| format!("'{i}'"))
+                    ));
+                }
+            }
+        }
+        errors
+    }
+
+    /// The namespaces the enabled apps are deployed into, including
+    /// `stack_namespace`.
+    pub fn namespaces(&self, stack_namespace: &str) -> BTreeSet<String> {
+        let mut namespaces = BTreeSet::from([stack_namespace.to_string()]);
+        for app in self.apps.iter().filter(|a| a.enabled) {
+            namespaces.insert(app.namespace_or(stack_namespace).to_string());
+        }
+        namespaces
+    }
+
+    /// Check that apps placed outside `stack_namespace` name a valid
+    /// namespace and don't rely on the shared Postgres, whose Secret only
+    /// exists in the stack's namespace.
+    pub fn validate_namespaces(&self, stack_namespace: &str) -> Vec<String> {
+        let postgres = self.postgres.as_ref().is_some_and(|p| p.enabled);
+        let mut errors = Vec::new();
+        for (i, app) in self.apps.iter().enumerate() {
+            let Some(ns) = app.namespace.as_deref() else {
+                continue;
+            };
+            if ns == stack_namespace {
+                continue;
+            }
+            if !is_valid_instance(ns) {
+                errors.push(format!(
+                    "apps[{i}]: namespace '{ns}' must be a lowercase DNS label (a-z, 0-9, '-')"
+                ));
+            }
+            if postgres && app.database.is_none() && app.app.postgres_env_prefix().is_some() {
+                errors.push(format!(
+                    "apps[{i}]: namespace '{ns}' cannot use the stack's shared Postgres, whose Secret is in '{stack_namespace}'; set database or leave the app in the stack's namespace"
+                ));
+            }
+        }
+        errors
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -77,6 +179,94 @@ pub struct StackDefaults {
     pub pod_annotations: Option<BTreeMap<String, String>>,
     #[serde(default)]
     pub admin_credentials: Option<AdminCredentialsSpec>,
+    /// Web UI authentication for the stack's Sonarr, Radarr, Lidarr, and
+    /// Prowlarr apps; other apps don't receive it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<AuthSpec>,
+    /// Web UI preferences for the stack's Sonarr, Radarr, Lidarr, and
+    /// Prowlarr apps. An app's own `uiSettings` replaces these.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ui_settings: Option<UiSettingsSpec>,
+    /// Register the stack's Transmission and SABnzbd apps as download
+    /// clients in its Sonarr, Radarr, and Lidarr apps. An app's own
+    /// `downloadClientSync` replaces this.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_client_sync: Option<DownloadClientSyncSpec>,
+    /// API key Secrets for apps that don't set `apiKeySecret`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_keys: Option<StackApiKeys>,
+    #[serde(default)]
+    pub drift_ignore_fields: Vec<String>,
+
+    /// Base DNS domain for the stack's routes (e.g. `media.example.com`).
+    /// Apps whose resolved gateway is enabled but lists no hosts get
+    /// `{app}.{domain}`, or `{app}-{instance}.{domain}` for named instances.
+    #[serde(default)]
+    pub domain: Option<String>,
+}
+
+/// Stack-wide API key Secret naming. Apps without `apiKeySecret` that
+/// enable a feature calling their API (`apiHealthCheck`, `backup`,
+/// `queueRemediation`, `trashSync`, `uiSettings`, or a sync) get a Secret named from
+/// `secretName`, which the operator creates with a random key.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StackApiKeys {
+    /// Secret name template. `{name}` is replaced by the child ServarrApp's
+    /// name.
+    #[serde(default = "default_api_key_secret_name")]
+    pub secret_name: String,
+    /// Also give every Sonarr, Radarr, Lidarr, and Prowlarr in the stack a
+    /// Secret, whether or not it enables such a feature.
+    #[serde(default)]
+    pub all_servarr_apps: bool,
+}
+
+impl Default for StackApiKeys {
+    fn default() -> Self {
+        Self {
+            secret_name: default_api_key_secret_name(),
+            all_servarr_apps: false,
+        }
+    }
+}
+
+fn default_api_key_secret_name() -> String {
+    "{name}-api-key".into()
+}
+
+impl StackApiKeys {
+    /// The Secret name for the child ServarrApp `child`.
+    pub fn secret_name_for(&self, child: &str) -> String {
+        self.secret_name.replace("{name}", child)
+    }
+
+    /// Set `spec.api_key_secret` for the child `child` when it has no API
+    /// key Secret and either calls its own API or is a Servarr v3 app under
+    /// `allServarrApps`.
+    fn apply(&self, spec: &mut ServarrAppSpec, child: &str) {
+        if spec.api_key_ref().is_none()
+            && (uses_api_key(spec) || (self.all_servarr_apps && spec.app.is_servarr_v3()))
+        {
+            spec.api_key_secret = Some(self.secret_name_for(child));
+        }
+    }
+}
+
+/// Whether `spec` enables a feature that calls the app's API with its key.
+fn uses_api_key(spec: &ServarrAppSpec) -> bool {
+    spec.api_health_check.as_ref().is_some_and(|h| h.enabled)
+        || spec.backup.as_ref().is_some_and(|b| b.enabled)
+        || spec.queue_remediation.as_ref().is_some_and(|q| q.enabled)
+        || spec.trash_sync.as_ref().is_some_and(|t| t.enabled)
+        || spec.ui_settings.is_some()
+        || spec.prowlarr_sync.as_ref().is_some_and(|p| p.enabled)
+        || spec.overseerr_sync.as_ref().is_some_and(|o| o.enabled)
+        || spec.bazarr_sync.as_ref().is_some_and(|b| b.enabled)
+        || spec
+            .download_client_sync
+            .as_ref()
+            .is_some_and(|d| d.enabled)
 }
 
 // ---------------------------------------------------------------------------
@@ -97,6 +287,11 @@ pub struct StackApp {
     #[serde(default = "default_enabled")]
     pub enabled: bool,
 
+    /// Namespace to deploy this app's ServarrApp into. Defaults to the
+    /// stack's namespace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+
     // -- Override fields (all optional, fall back to StackDefaults) --
     #[serde(default)]
     pub image: Option<ImageSpec>,
@@ -110,14 +305,26 @@ pub struct StackApp {
     pub service: Option<ServiceSpec>,
     #[serde(default)]
     pub gateway: Option<GatewaySpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingress: Option<IngressSpec>,
     #[serde(default)]
     pub resources: Option<ResourceRequirements>,
     #[serde(default)]
     pub persistence: Option<PersistenceSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workload_type: Option<WorkloadType>,
     #[serde(default)]
     pub env: Vec<EnvVar>,
     #[serde(default)]
     pub probes: Option<ProbeSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifecycle: Option<LifecycleSpec>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(schema_with = "container_list_schema")]
+    pub extra_containers: Vec<Container>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(schema_with = "container_list_schema")]
+    pub extra_init_containers: Vec<Container>,
     #[serde(default)]
     pub scheduling: Option<NodeScheduling>,
     #[serde(default)]
@@ -127,24 +334,62 @@ pub struct StackApp {
     #[serde(default)]
     #[schemars(schema_with = "nullable_app_config_schema")]
     pub app_config: Option<AppConfig>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub config_file: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rclone_mounts: Vec<RcloneMount>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub credentials: Vec<CredentialRef>,
     #[serde(default)]
     pub api_key_secret: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_secret_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_rotation: Option<ApiKeyRotationSpec>,
     #[serde(default)]
     pub api_health_check: Option<ApiHealthCheckSpec>,
     #[serde(default)]
     pub backup: Option<BackupSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_remediation: Option<QueueRemediationSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trash_sync: Option<TrashSyncSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_policy: Option<UpdatePolicySpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maintenance_window: Option<AppMaintenanceWindow>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requeue: Option<RequeueSpec>,
     #[serde(default)]
     pub image_pull_secrets: Option<Vec<String>>,
     #[serde(default)]
     pub pod_annotations: Option<BTreeMap<String, String>>,
     #[serde(default)]
     pub gpu: Option<GpuSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub autoscaling: Option<AutoscalingSpec>,
     #[serde(default)]
     pub prowlarr_sync: Option<ProwlarrSyncSpec>,
     #[serde(default)]
     pub overseerr_sync: Option<OverseerrSyncSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bazarr_sync: Option<BazarrSyncSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_client_sync: Option<DownloadClientSyncSpec>,
     #[serde(default)]
     pub admin_credentials: Option<AdminCredentialsSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<AuthSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ui_settings: Option<UiSettingsSpec>,
+    #[serde(default)]
+    pub database: Option<DatabaseSpec>,
+    #[serde(default)]
+    pub drift_ignore_fields: Vec<String>,
+    #[serde(default)]
+    pub patches: Vec<ResourcePatch>,
+    #[serde(default)]
+    pub unsupported_overrides: Option<UnsupportedOverrides>,
 
     /// When true, creates both a standard and a 4K instance of this app.
     /// Only valid for Sonarr and Radarr.
@@ -154,6 +399,28 @@ pub struct StackApp {
     /// Override fields applied only to the 4K instance when split4k is true.
     #[serde(default)]
     pub split4k_overrides: Option<Split4kOverrides>,
+
+    /// Runs a second instance, `{child}-canary`, with a newer image and
+    /// sends it a share of the app's HTTPRoute traffic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canary: Option<CanarySpec>,
+}
+
+/// A canary instance of a StackApp, for trying a new image against real
+/// indexers and clients before rolling it out to the whole stack.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CanarySpec {
+    /// Image the canary runs.
+    pub image: ImageSpec,
+    /// Percentage of the app's HTTPRoute traffic sent to the canary
+    /// (default: 10).
+    #[serde(default = "default_canary_weight")]
+    pub weight: i32,
+}
+
+fn default_canary_weight() -> i32 {
+    10
 }
 
 /// Override fields applied only to the 4K instance when `split4k` is true.
@@ -191,6 +458,30 @@ impl StackApp {
         }
     }
 
+    /// The namespace this app's children are deployed into.
+    pub fn namespace_or<'a>(&'a self, stack_namespace: &'a str) -> &'a str {
+        self.namespace.as_deref().unwrap_or(stack_namespace)
+    }
+
+    /// Hostname for this app under a stack `domain`: `"{app}.{domain}"` or
+    /// `"{app}-{instance}.{domain}"`.
+    pub fn hostname(&self, domain: &str) -> String {
+        let domain = domain.trim_matches('.');
+        match &self.instance {
+            Some(inst) => format!("{}-{inst}.{domain}", self.app.as_str()),
+            None => format!("{}.{domain}", self.app.as_str()),
+        }
+    }
+
+    /// Instance of the canary: `"canary"` or `"{instance}-canary"`, which
+    /// makes its child name `"{child}-canary"`.
+    pub fn canary_instance(&self) -> String {
+        match &self.instance {
+            Some(inst) => format!("{inst}-canary"),
+            None => "canary".into(),
+        }
+    }
+
     /// Returns `true` if `split4k` is valid for this app type.
     /// Only Sonarr and Radarr support the split 4K pattern.
     pub fn split4k_valid(&self) -> bool {
@@ -205,6 +496,10 @@ impl StackApp {
     /// When `nfs` is provided, NFS media mounts are auto-injected into each spec
     /// based on app type. 4K instances receive the 4K-specific path variants.
     ///
+    /// When `canary` is set, also produces a `{child}-canary` instance running
+    /// the canary image, without a gateway or Ingress of its own: the base
+    /// instance's HTTPRoute sends it the canary's weight of the traffic.
+    ///
     /// Returns `Err` if `split4k` is set on an unsupported app type.
     pub fn expand(
         &self,
@@ -221,9 +516,42 @@ impl StackApp {
         }
 
         let base_name = self.child_name(stack_name);
+        let api_keys = defaults.and_then(|d| d.api_keys.as_ref());
         let mut base_spec = self.to_servarr_spec(defaults);
+        if let Some(keys) = api_keys {
+            keys.apply(&mut base_spec, &base_name);
+        }
         inject_nfs_mounts(&mut base_spec, nfs, false, stack_name, stack_namespace);
+        inject_tdarr_server(&mut base_spec, stack_name);
+
+        let canary = self.canary.as_ref().map(|canary| {
+            let mut canary_app = self.clone();
+            canary_app.instance = Some(self.canary_instance());
+            canary_app.image = Some(canary.image.clone());
+            canary_app.split4k = None;
+            canary_app.canary = None;
+            let canary_name = canary_app.child_name(stack_name);
+
+            let mut canary_spec = canary_app.to_servarr_spec(defaults);
+            canary_spec.gateway = None;
+            canary_spec.ingress = None;
+            if let Some(keys) = api_keys {
+                keys.apply(&mut canary_spec, &canary_name);
+            }
+            inject_nfs_mounts(&mut canary_spec, nfs, false, stack_name, stack_namespace);
+            inject_tdarr_server(&mut canary_spec, stack_name);
+
+            if let Some(gw) = base_spec.gateway.as_mut().filter(|gw| gw.enabled) {
+                gw.backends.push(GatewayBackend {
+                    name: canary_name.clone(),
+                    port: None,
+                    weight: canary.weight,
+                });
+            }
+            (canary_name, canary_spec)
+        });
         let mut result = vec![(base_name, base_spec)];
+        result.extend(canary);
 
         if self.split4k == Some(true) {
             // Build the 4K instance by cloning and applying overrides
@@ -259,6 +587,9 @@ impl StackApp {
             }
 
             let mut four_k_spec = four_k_app.to_servarr_spec(defaults);
+            if let Some(keys) = api_keys {
+                keys.apply(&mut four_k_spec, &four_k_name);
+            }
             inject_nfs_mounts(&mut four_k_spec, nfs, true, stack_name, stack_namespace);
             result.push((four_k_name, four_k_spec));
         }
@@ -278,10 +609,27 @@ impl StackApp {
         // with dedup by name.
         let persistence = merge_persistence(d.persistence.as_ref(), self.persistence.as_ref());
 
+        // Merge drift ignore paths: stack defaults plus per-app additions.
+        let mut drift_ignore_fields = d.drift_ignore_fields.clone();
+        for f in &self.drift_ignore_fields {
+            if !drift_ignore_fields.contains(f) {
+                drift_ignore_fields.push(f.clone());
+            }
+        }
+
         // Merge pod_annotations: stack defaults, per-app overrides matching keys.
         let pod_annotations =
             merge_annotations(d.pod_annotations.as_ref(), self.pod_annotations.as_ref());
 
+        // Fill in hosts from the stack domain when the gateway leaves them out.
+        let mut gateway = self.gateway.clone().or(d.gateway);
+        if let (Some(gw), Some(domain)) = (gateway.as_mut(), d.domain.as_deref())
+            && gw.enabled
+            && gw.hosts.is_empty()
+        {
+            gw.hosts.push(self.hostname(domain));
+        }
+
         ServarrAppSpec {
             app: self.app.clone(),
             instance: self.instance.clone(),
@@ -290,11 +638,16 @@ impl StackApp {
             gid: self.gid.or(d.gid),
             security: self.security.clone().or(d.security),
             service: self.service.clone(),
-            gateway: self.gateway.clone().or(d.gateway),
+            gateway,
+            ingress: self.ingress.clone(),
             resources: self.resources.clone().or(d.resources),
             persistence,
+            workload_type: self.workload_type,
             env,
             probes: self.probes.clone(),
+            lifecycle: self.lifecycle.clone(),
+            extra_containers: self.extra_containers.clone(),
+            extra_init_containers: self.extra_init_containers.clone(),
             scheduling: self.scheduling.clone().or(d.scheduling),
             network_policy: self.network_policy.or(d.network_policy),
             network_policy_config: self
@@ -302,15 +655,43 @@ impl StackApp {
                 .clone()
                 .or(d.network_policy_config),
             app_config: self.app_config.clone(),
+            config_file: self.config_file.clone(),
+            rclone_mounts: self.rclone_mounts.clone(),
+            credentials: self.credentials.clone(),
             api_key_secret: self.api_key_secret.clone(),
+            api_key_secret_key: self.api_key_secret_key.clone(),
+            api_key_rotation: self.api_key_rotation.clone(),
             api_health_check: self.api_health_check.clone(),
             backup: self.backup.clone(),
+            queue_remediation: self.queue_remediation.clone(),
+            trash_sync: self.trash_sync.clone(),
+            update_policy: self.update_policy.clone(),
+            requeue: self.requeue.clone(),
+            maintenance_window: self.maintenance_window.clone(),
+            suspend: false,
             image_pull_secrets: self.image_pull_secrets.clone().or(d.image_pull_secrets),
             pod_annotations,
             gpu: self.gpu.clone(),
+            autoscaling: self.autoscaling.clone(),
             prowlarr_sync: self.prowlarr_sync.clone(),
             overseerr_sync: self.overseerr_sync.clone(),
+            bazarr_sync: self.bazarr_sync.clone(),
+            download_client_sync: self.download_client_sync.clone().or(d
+                .download_client_sync
+                .filter(|_| self.app.downloads_media())),
             admin_credentials: self.admin_credentials.clone().or(d.admin_credentials),
+            auth: self
+                .auth
+                .clone()
+                .or(d.auth.filter(|_| self.app.auth_env_prefix().is_some())),
+            ui_settings: self
+                .ui_settings
+                .clone()
+                .or(d.ui_settings.filter(|_| self.app.is_servarr_v3())),
+            database: self.database.clone(),
+            drift_ignore_fields,
+            patches: self.patches.clone(),
+            unsupported_overrides: self.unsupported_overrides.clone(),
         }
     }
 }
@@ -322,7 +703,8 @@ impl StackApp {
 ///
 /// `is_4k` routes Sonarr and Radarr to their 4K path variants so the
 /// standard and 4K instances see the same container path (`/tv`, `/movies`)
-/// while pointing to separate directories on the NFS server.
+/// while pointing to separate directories on the NFS server. Named exports
+/// are mounted into the app types that use them.
 fn inject_nfs_mounts(
     spec: &mut ServarrAppSpec,
     nfs: Option<&NfsServerSpec>,
@@ -342,12 +724,12 @@ fn inject_nfs_mounts(
     let make = |name: &str, server_path: &str, mount_path: &str| NfsMount {
         name: name.to_string(),
         server: server.clone(),
-        path: nfs.nfs_path(server_path),
+        path: nfs.nfs_path(&nfs.media_path(server_path)),
         mount_path: mount_path.to_string(),
         read_only: false,
     };
 
-    let mounts: Vec<NfsMount> = match &spec.app {
+    let mut mounts: Vec<NfsMount> = match &spec.app {
         AppType::Sonarr => {
             let nfs_path = if is_4k { &nfs.tv_4k_path } else { &nfs.tv_path };
             vec![make("tv", nfs_path, &nfs.tv_path.clone())]
@@ -361,7 +743,7 @@ fn inject_nfs_mounts(
             vec![make("movies", nfs_path, &nfs.movies_path.clone())]
         }
         AppType::Lidarr => vec![make("music", &nfs.music_path, &nfs.music_path.clone())],
-        AppType::Sabnzbd | AppType::Transmission => vec![
+        AppType::Sabnzbd | AppType::Transmission | AppType::Qbittorrent => vec![
             make("movies", &nfs.movies_path, &nfs.movies_path.clone()),
             make("tv", &nfs.tv_path, &nfs.tv_path.clone()),
             make("music", &nfs.music_path, &nfs.music_path.clone()),
@@ -372,7 +754,9 @@ fn inject_nfs_mounts(
             ),
             make("tv-4k", &nfs.tv_4k_path, &nfs.tv_4k_path.clone()),
         ],
-        AppType::Plex | AppType::Jellyfin => vec![
+        // Tdarr nodes replace files in place, so they mount the media
+        // read-write like the media servers' libraries.
+        AppType::Plex | AppType::Jellyfin | AppType::Tdarr | AppType::TdarrNode => vec![
             make("movies", &nfs.movies_path, &nfs.movies_path.clone()),
             make("tv", &nfs.tv_path, &nfs.tv_path.clone()),
             make("music", &nfs.music_path, &nfs.music_path.clone()),
@@ -387,14 +771,40 @@ fn inject_nfs_mounts(
             make("movies", &nfs.movies_path, &nfs.movies_path.clone()),
             make("tv", &nfs.tv_path, &nfs.tv_path.clone()),
         ],
+        // Subtitles are written next to the media, at the paths Sonarr and
+        // Radarr report.
+        AppType::Bazarr => vec![
+            make("movies", &nfs.movies_path, &nfs.movies_path.clone()),
+            make("tv", &nfs.tv_path, &nfs.tv_path.clone()),
+            make(
+                "movies-4k",
+                &nfs.movies_4k_path,
+                &nfs.movies_4k_path.clone(),
+            ),
+            make("tv-4k", &nfs.tv_4k_path, &nfs.tv_4k_path.clone()),
+        ],
         AppType::SshBastion => vec![
             make("movies", &nfs.movies_path, &nfs.movies_path.clone()),
             make("tv", &nfs.tv_path, &nfs.tv_path.clone()),
             make("music", &nfs.music_path, &nfs.music_path.clone()),
         ],
-        _ => return,
+        _ => Vec::new(),
     };
 
+    // Named exports, other than the one holding the media paths
+    mounts.extend(
+        nfs.exports
+            .iter()
+            .filter(|e| e.name != "media" && e.mounted_by(&spec.app))
+            .map(|e| NfsMount {
+                name: e.name.clone(),
+                server: server.clone(),
+                path: nfs.nfs_path(&e.path()),
+                mount_path: e.mount_path(),
+                read_only: e.read_only,
+            }),
+    );
+
     if mounts.is_empty() {
         return;
     }
@@ -404,6 +814,7 @@ fn inject_nfs_mounts(
     let injected = PersistenceSpec {
         volumes: Vec::new(),
         nfs_mounts: mounts,
+        verify_mounts: false,
     };
     spec.persistence = Some(match spec.persistence.take() {
         None => injected,
@@ -411,6 +822,94 @@ fn inject_nfs_mounts(
     });
 }
 
+/// Point a Tdarr node child without an explicit `server` at the stack's
+/// Tdarr app.
+fn inject_tdarr_server(spec: &mut ServarrAppSpec, stack_name: &str) {
+    if spec.app != AppType::TdarrNode {
+        return;
+    }
+    if let AppConfig::TdarrNode(config) = spec
+        .app_config
+        .get_or_insert_with(|| AppConfig::TdarrNode(TdarrNodeConfig::default()))
+    {
+        config
+            .server
+            .get_or_insert_with(|| format!("{stack_name}-{}", AppType::Tdarr.as_str()));
+    }
+}
+
+/// Wire an expanded child app to the stack's shared Postgres instance.
+///
+/// Only apps that support Postgres and have no explicit `database` are
+/// touched. Each child gets its own role (derived from the child name) and
+/// `{role}_main` / `{role}_log` databases; the password lives in the stack's
+/// credentials Secret under [`PostgresSpec::password_key`].
+pub fn inject_postgres(
+    spec: &mut ServarrAppSpec,
+    child_name: &str,
+    postgres: Option<&PostgresSpec>,
+    stack_name: &str,
+    stack_namespace: &str,
+) {
+    let Some(pg) = postgres else { return };
+    if !pg.enabled || spec.database.is_some() || spec.app.postgres_env_prefix().is_none() {
+        return;
+    }
+
+    let role = PostgresSpec::role_name(child_name);
+    spec.database = Some(DatabaseSpec {
+        host: PostgresSpec::host(stack_name, stack_namespace),
+        port: 5432,
+        user: role.clone(),
+        password_secret: PostgresSpec::resource_name(stack_name),
+        password_key: PostgresSpec::password_key(&role),
+        main_database: format!("{role}_main"),
+        log_database: Some(format!("{role}_log")),
+    });
+}
+
+/// Add the stack's spread constraint to an expanded child app.
+///
+/// A child whose own scheduling already spreads over the same topology key
+/// keeps its constraint.
+pub fn inject_spread(spec: &mut ServarrAppSpec, spread: Option<&SpreadPolicy>, stack_name: &str) {
+    let Some(spread) = spread else { return };
+    let constraint = spread.constraint(stack_name);
+    let constraints = &mut spec
+        .scheduling
+        .get_or_insert_with(Default::default)
+        .topology_spread_constraints;
+    if constraints
+        .iter()
+        .any(|c| c["topologyKey"] == constraint["topologyKey"])
+    {
+        return;
+    }
+    constraints.push(constraint);
+}
+
+/// Let an expanded child in `namespace` reach, and be reached from, the
+/// stack's other `namespaces` through its NetworkPolicy.
+pub fn inject_namespace_peers(
+    spec: &mut ServarrAppSpec,
+    namespace: &str,
+    namespaces: &BTreeSet<String>,
+) {
+    let peers: Vec<&String> = namespaces.iter().filter(|ns| *ns != namespace).collect();
+    if peers.is_empty() {
+        return;
+    }
+    let allowed = &mut spec
+        .network_policy_config
+        .get_or_insert_with(Default::default)
+        .allowed_namespaces;
+    for ns in peers {
+        if !allowed.contains(ns) {
+            allowed.push(ns.to_string());
+        }
+    }
+}
+
 /// Merge env vars: stack defaults first, per-app overrides same-name entries.
 fn merge_env(defaults: &[EnvVar], overrides: &[EnvVar]) -> Vec<EnvVar> {
     use indexmap::IndexMap;
@@ -474,6 +973,9 @@ pub struct MediaStackStatus {
     pub total_apps: i32,
     #[serde(default)]
     pub ready_apps: i32,
+    /// Children applied in this reconcile, out of `totalApps`.
+    #[serde(default)]
+    pub applied_apps: i32,
     #[serde(default)]
     pub app_statuses: Vec<StackAppStatus>,
     #[serde(default)]
@@ -484,6 +986,17 @@ pub struct MediaStackStatus {
     /// Reset when the tier advances. Used to enforce the tier rollout timeout.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tier_blocked_since: Option<String>,
+    /// Progress of each teardown phase while the stack is being deleted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub teardown: Vec<TeardownPhaseStatus>,
+    /// Results of the in-cluster NFS server's backups, when configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nfs_backup: Option<NfsBackupStatus>,
+    /// What a reconcile would change, one entry per object. Only set while
+    /// the operator runs with `--dry-run`; cleared by the next real
+    /// reconcile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub would_change: Option<Vec<String>>,
 }
 
 impl MediaStackStatus {
@@ -500,6 +1013,30 @@ impl MediaStackStatus {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NfsBackupStatus {
+    /// Name of the newest VolumeSnapshot of the NFS server's PVC.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_snapshot: Option<String>,
+    /// RFC 3339 timestamp of the newest snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_snapshot_time: Option<String>,
+    /// Snapshots currently retained, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub snapshots: Vec<String>,
+    /// RFC 3339 timestamp of the last successful rsync Job.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_sync_time: Option<String>,
+    /// Outcome of the most recent rsync Job: `Running`, `Succeeded`, or
+    /// `Failed: <reason>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_sync_result: Option<String>,
+    /// Why a backup could not be scheduled, e.g. an invalid cron expression.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
 pub enum StackPhase {
     #[default]
@@ -507,6 +1044,7 @@ pub enum StackPhase {
     RollingOut,
     Ready,
     Degraded,
+    Terminating,
 }
 
 impl std::fmt::Display for StackPhase {
@@ -516,10 +1054,90 @@ impl std::fmt::Display for StackPhase {
             Self::RollingOut => write!(f, "RollingOut"),
             Self::Ready => write!(f, "Ready"),
             Self::Degraded => write!(f, "Degraded"),
+            Self::Terminating => write!(f, "Terminating"),
+        }
+    }
+}
+
+/// Teardown order for a deleted MediaStack: each phase's children are
+/// deleted only once every earlier phase is gone, so apps that clean up
+/// registrations in other apps (Sonarr in Prowlarr, say) still find them
+/// running, and nothing loses its storage while still mounted.
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, JsonSchema,
+)]
+pub enum TeardownPhase {
+    /// Overseerr, Jellyseerr, Tautulli, Maintainerr, Bazarr, Tdarr.
+    RequestApps,
+    /// Sonarr, Radarr, Lidarr, Readarr, Whisparr.
+    MediaManagers,
+    /// Prowlarr, Jackett.
+    Indexers,
+    /// SABnzbd, Transmission, qBittorrent.
+    DownloadClients,
+    /// Plex, Jellyfin, SSH bastion.
+    MediaServers,
+    /// In-cluster NFS server and shared Postgres.
+    Storage,
+}
+
+impl TeardownPhase {
+    pub const ALL: [Self; 6] = [
+        Self::RequestApps,
+        Self::MediaManagers,
+        Self::Indexers,
+        Self::DownloadClients,
+        Self::MediaServers,
+        Self::Storage,
+    ];
+
+    /// The phase in which a child app of type `app` is deleted.
+    pub fn for_app(app: &AppType) -> Self {
+        match app {
+            AppType::Overseerr
+            | AppType::Jellyseerr
+            | AppType::Tautulli
+            | AppType::Maintainerr
+            | AppType::Bazarr
+            | AppType::Tdarr
+            | AppType::TdarrNode => Self::RequestApps,
+            AppType::Sonarr
+            | AppType::Radarr
+            | AppType::Lidarr
+            | AppType::Readarr
+            | AppType::Whisparr => Self::MediaManagers,
+            AppType::Prowlarr | AppType::Jackett => Self::Indexers,
+            AppType::Sabnzbd | AppType::Transmission | AppType::Qbittorrent => {
+                Self::DownloadClients
+            }
+            AppType::Plex | AppType::Jellyfin | AppType::SshBastion => Self::MediaServers,
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+pub enum TeardownState {
+    Pending,
+    InProgress,
+    Complete,
+    /// The phase did not finish within the teardown timeout and later phases
+    /// were started anyway.
+    TimedOut,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TeardownPhaseStatus {
+    pub phase: TeardownPhase,
+    pub state: TeardownState,
+    /// Resources of this phase still present.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remaining: Vec<String>,
+    /// RFC 3339 timestamp of when the phase started deleting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct StackAppStatus {
@@ -534,4 +1152,18 @@ pub struct StackAppStatus {
     pub ready: bool,
     #[serde(default)]
     pub enabled: bool,
+    /// Whether the child was applied in the last reconcile. False while its
+    /// tier waits on an earlier one, or when applying it failed.
+    #[serde(default)]
+    pub applied: bool,
+    /// Namespace of the child when it differs from the stack's.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// Reason of the child's `Ready` condition while it is not ready, e.g.
+    /// `AppUnhealthy` or `RolloutInProgress`, or `ApplyFailed` when it could
+    /// not be applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
 }
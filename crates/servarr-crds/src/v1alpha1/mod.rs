@@ -1,13 +1,23 @@
 mod app_config;
+mod app_set;
+mod credential;
 mod defaults;
+mod inventory;
+mod maintenance_window;
 mod media_stack;
+mod policy;
 mod spec;
 mod status;
 mod types;
 
 pub use app_config::*;
+pub use app_set::*;
+pub use credential::*;
 pub use defaults::*;
+pub use inventory::*;
+pub use maintenance_window::*;
 pub use media_stack::*;
+pub use policy::*;
 pub use spec::*;
 pub use status::*;
 pub use types::*;
@@ -0,0 +1,89 @@
+use kube::CustomResource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::spec::AppType;
+
+// ---------------------------------------------------------------------------
+// ServarrPolicy CRD
+// ---------------------------------------------------------------------------
+
+/// Guardrails for the ServarrApps and MediaStacks in one namespace, enforced
+/// by the admission webhook. Lets cluster admins hand a namespace to a
+/// tenant while limiting what they can deploy. When a namespace has several
+/// policies, an object must satisfy all of them.
+#[derive(CustomResource, Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[kube(
+    group = "servarr.dev",
+    version = "v1alpha1",
+    kind = "ServarrPolicy",
+    namespaced,
+    shortname = "spol",
+    printcolumn = r#"{"name":"Apps","type":"string","jsonPath":".spec.allowedApps"}"#,
+    printcolumn = r#"{"name":"Age","type":"date","jsonPath":".metadata.creationTimestamp"}"#
+)]
+#[serde(rename_all = "camelCase")]
+pub struct ServarrPolicySpec {
+    /// App types that may be deployed. Empty allows every type.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_apps: Vec<AppType>,
+
+    /// NetworkPolicy settings every app must use.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_policy: Option<NetworkPolicyRequirements>,
+
+    /// Container resource limits every app must have.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<ResourceRequirementsPolicy>,
+
+    /// NFS servers (hostnames or IPs, as written in `nfsMounts[].server`)
+    /// that apps may not mount from.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denied_nfs_servers: Vec<String>,
+
+    /// Reject `patches` and `unsupportedOverrides` that add `hostPath`
+    /// volumes, the only way an app can reach the node's filesystem.
+    #[serde(default)]
+    pub deny_host_path: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkPolicyRequirements {
+    /// Apps may not turn their NetworkPolicy off with `networkPolicy: false`.
+    #[serde(default)]
+    pub required: bool,
+
+    /// Apps may not set `networkPolicyConfig.allowInternetEgress`.
+    #[serde(default)]
+    pub deny_internet_egress: bool,
+
+    /// CIDR blocks every app must list in
+    /// `networkPolicyConfig.deniedCidrBlocks`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denied_cidr_blocks: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceRequirementsPolicy {
+    /// Apps must end up with CPU and memory limits, whether set in
+    /// `resources` or inherited from the app type's defaults.
+    #[serde(default)]
+    pub require_limits: bool,
+
+    /// Highest CPU limit an app may set, e.g. `2` or `1500m`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cpu: Option<String>,
+
+    /// Highest memory limit an app may set, e.g. `4Gi`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_memory: Option<String>,
+}
+
+impl ServarrPolicySpec {
+    /// Whether `app` may be deployed under this policy.
+    pub fn allows_app(&self, app: &AppType) -> bool {
+        self.allowed_apps.is_empty() || self.allowed_apps.contains(app)
+    }
+}
@@ -1,6 +1,10 @@
+use std::collections::BTreeMap;
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use super::BackupDeletionPolicy;
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ServarrAppStatus {
@@ -14,6 +18,227 @@ pub struct ServarrAppStatus {
     pub conditions: Vec<Condition>,
     #[serde(default)]
     pub backup_status: Option<BackupStatus>,
+    /// Outcome of the last API key rotation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_rotation: Option<ApiKeyRotationStatus>,
+    /// Queue remediation counters and the most recent actions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_remediation: Option<QueueRemediationStatus>,
+    /// Outcome of the last TRaSH-guide sync.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_trash_sync: Option<TrashSyncStatus>,
+    /// Automatic image updates under `updatePolicy.mode: Auto`: the tag
+    /// in use and the upgrades applied so far.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_update: Option<ImageUpdateStatus>,
+    /// Outcome of the Prowlarr or Overseerr app sync. Only set on apps with
+    /// `prowlarrSync` or `overseerrSync` enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync_status: Option<SyncStatus>,
+    /// SHA-256 of the spec the operator last applied. Differs from the hash
+    /// of the current spec while the operator is still catching up.
+    #[serde(default)]
+    pub applied_spec_hash: Option<String>,
+    /// Value of the `servarr.dev/reconcile-now` annotation that the last
+    /// completed reconcile observed.
+    #[serde(default)]
+    pub reconcile_trigger: Option<String>,
+    /// How to reach the app, where its credentials are, and what to set up
+    /// next. Filled in once the app is ready.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Resources the operator generated for this app on the last reconcile.
+    #[serde(default)]
+    pub generated_resources: Option<GeneratedResources>,
+    /// PVC backing each volume whose data was migrated to another storage
+    /// class, keyed by volume name. Other volumes use `{app}-{volume}`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub volume_claims: BTreeMap<String, String>,
+    /// Storage migrations that are copying or have failed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub storage_migrations: Vec<StorageMigrationStatus>,
+    /// What a reconcile would change, one entry per child object. Only set
+    /// while the operator runs with `--dry-run`; cleared by the next real
+    /// reconcile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub would_change: Option<Vec<String>>,
+    /// Why the most recent reconcile failed. Cleared by the next reconcile
+    /// that succeeds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<ReconcileError>,
+    /// The most recent pod template drift the operator corrected. Kept
+    /// until drift is corrected again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_drift: Option<DriftReport>,
+    /// Disruptive actions waiting for `spec.maintenanceWindow` to open.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pending_actions: Vec<PendingAction>,
+    /// Hash of what each group of child objects was last applied from.
+    /// Groups whose hash still matches are skipped until the next full
+    /// resync.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_groups: Option<ResourceGroupHashes>,
+}
+
+/// Input hashes of the resource groups a ServarrApp reconcile applies, as
+/// reported in `status.resourceGroups`. A group without a hash is applied
+/// on the next pass.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceGroupHashes {
+    /// The Deployment or StatefulSet and its PVCs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workload: Option<String>,
+    /// Service, NetworkPolicy, Gateway API route, Ingress, and Certificate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub networking: Option<String>,
+    /// Generated ConfigMaps and Secrets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<String>,
+    /// Settings pushed through the app's own API: `uiSettings` and the
+    /// quality profiles, root folders, media management, indexers, and
+    /// users in `appConfig`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_sync: Option<String>,
+    /// When every group was last applied regardless of its hash (RFC 3339).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_sync_time: Option<String>,
+}
+
+/// A disruptive action held back by `spec.maintenanceWindow`, as reported
+/// in `status.pendingActions`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingAction {
+    /// `Restore`, `ImageUpdate`, or `DriftCorrection`.
+    pub action: String,
+    pub message: String,
+    /// When the action was first held back (RFC 3339).
+    pub since: String,
+}
+
+/// A corrected drift, as reported in `status.lastDrift`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftReport {
+    /// Kind of the drifted object, `Deployment` or `StatefulSet`.
+    pub kind: String,
+    /// Pod template paths that differed, at most ten. Values are left out,
+    /// as they can hold secrets.
+    pub paths: Vec<String>,
+    /// How many paths differed, including those not listed.
+    pub total_paths: u32,
+    /// When the drift was corrected (RFC 3339).
+    pub time: String,
+}
+
+/// A failed reconcile, as reported in `status.lastError`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconcileError {
+    pub category: ErrorCategory,
+    pub message: String,
+    /// When the reconcile failed (RFC 3339).
+    pub time: String,
+}
+
+/// What kind of problem failed a reconcile.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+pub enum ErrorCategory {
+    /// A Secret the app's pods reference, or one of its keys, is missing.
+    SecretMissing,
+    /// The spec can't be turned into valid objects, e.g. a broken patch or
+    /// a field the API server rejects.
+    InvalidSpec,
+    /// Another field manager or a concurrent update conflicted with the
+    /// operator's write.
+    Conflict,
+    /// An app's own HTTP API failed or was unreachable.
+    ExternalApi,
+    /// Any other Kubernetes API failure.
+    Kubernetes,
+    /// A bug or unexpected state in the operator.
+    Internal,
+}
+
+impl ErrorCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SecretMissing => "SecretMissing",
+            Self::InvalidSpec => "InvalidSpec",
+            Self::Conflict => "Conflict",
+            Self::ExternalApi => "ExternalApi",
+            Self::Kubernetes => "Kubernetes",
+            Self::Internal => "Internal",
+        }
+    }
+}
+
+/// A volume being copied into a PVC of a new storage class.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageMigrationStatus {
+    pub volume: String,
+    pub source_claim: String,
+    pub target_claim: String,
+    pub storage_class: String,
+    pub phase: StorageMigrationPhase,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+pub enum StorageMigrationPhase {
+    /// The copy Job is running; the app is scaled to zero.
+    Copying,
+    /// The copy Job failed. The app runs on the old PVC again; delete the
+    /// Job to retry.
+    Failed,
+}
+
+/// Names of the namespaced resources the operator created for an app, so
+/// tooling can find them without relying on the naming convention.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedResources {
+    #[serde(default)]
+    pub deployment: Option<String>,
+    /// Set instead of `deployment` for `workloadType: StatefulSet`.
+    #[serde(default)]
+    pub stateful_set: Option<String>,
+    /// Deployments of a Tdarr node app's extra nodes (`nodeCount`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub worker_deployments: Vec<String>,
+    #[serde(default)]
+    pub service: Option<String>,
+    #[serde(default)]
+    pub persistent_volume_claims: Vec<String>,
+    #[serde(default)]
+    pub network_policy: Option<String>,
+    #[serde(default)]
+    pub config_maps: Vec<String>,
+    /// Secrets the operator writes, such as the SSH bastion authorized keys.
+    #[serde(default)]
+    pub secrets: Vec<String>,
+    /// The `spec.apiKeySecret` Secret, created by the operator if it was
+    /// missing.
+    #[serde(default)]
+    pub api_key_secret: Option<String>,
+    #[serde(default)]
+    pub http_route: Option<String>,
+    #[serde(default)]
+    pub tcp_route: Option<String>,
+    /// Ingress for `spec.ingress`.
+    #[serde(default)]
+    pub ingress: Option<String>,
+    #[serde(default)]
+    pub certificate: Option<String>,
+    /// Secret cert-manager writes the issued certificate into.
+    #[serde(default)]
+    pub certificate_secret: Option<String>,
+    /// HorizontalPodAutoscaler for `spec.autoscaling`.
+    #[serde(default)]
+    pub horizontal_pod_autoscaler: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
@@ -23,6 +248,151 @@ pub struct BackupStatus {
     pub last_backup_result: Option<String>,
     #[serde(default)]
     pub backup_count: u32,
+    /// VolumeSnapshots kept for `backup.volumeSnapshot`, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub snapshots: Vec<String>,
+    /// Uploads to `backup.objectStorage.bucket`, and what deleting the app
+    /// will do to them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object_storage: Option<ObjectStorageBackupStatus>,
+}
+
+/// Backups uploaded to object storage.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectStorageBackupStatus {
+    /// `s3://bucket/prefix` the backups are uploaded to.
+    pub location: String,
+    /// What happens to the uploaded backups when the app is deleted.
+    #[serde(default)]
+    pub deletion_policy: BackupDeletionPolicy,
+    /// When the last upload finished.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_upload_time: Option<String>,
+    /// `success`, or why the last upload failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_upload_result: Option<String>,
+}
+
+/// Outcome of the last API key rotation.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyRotationStatus {
+    pub last_rotation_time: Option<String>,
+    /// Summary of the last rotation, or why it failed.
+    pub last_rotation_result: Option<String>,
+    /// The `servarr.dev/rotate-api-key` value last acted on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<String>,
+    /// Prowlarr and Overseerr registrations of the app still hold the old
+    /// key. They are updated once the app answers with the new one.
+    #[serde(default)]
+    pub dependents_pending: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueRemediationStatus {
+    pub last_check_time: Option<String>,
+    /// Summary of the last check, or why it failed.
+    pub last_check_result: Option<String>,
+    /// Queue items remediated since remediation was enabled, by reason.
+    /// Dry runs are not counted.
+    #[serde(default)]
+    pub remediated: BTreeMap<String, u64>,
+    /// The most recent actions, newest first.
+    #[serde(default)]
+    pub recent: Vec<RemediatedItem>,
+}
+
+impl QueueRemediationStatus {
+    /// How many entries `recent` keeps.
+    pub const MAX_RECENT: usize = 10;
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashSyncStatus {
+    /// When the templates were last applied. A sync that can't reach the
+    /// app leaves this unchanged and is retried on the next reconcile.
+    pub time: Option<String>,
+    /// Summary of the last sync, or why it failed.
+    pub result: Option<String>,
+    /// Whether the last sync applied every custom format and quality
+    /// definition.
+    #[serde(default)]
+    pub succeeded: bool,
+    /// Custom formats the last sync created or updated.
+    #[serde(default)]
+    pub custom_formats_changed: u32,
+    /// Quality definitions the last sync updated.
+    #[serde(default)]
+    pub quality_definitions_changed: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageUpdateStatus {
+    /// Tag the operator runs instead of the spec's or default one. Ignored
+    /// once that tag is as new, or when `mode` is no longer `Auto`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// When the registry was last checked (RFC 3339). A check that can't
+    /// reach the registry leaves this unchanged and is retried on the next
+    /// reconcile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_check: Option<String>,
+    /// Outcome of the last check.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    /// Upgrades applied, most recent first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<ImageUpgrade>,
+}
+
+impl ImageUpdateStatus {
+    /// How many entries `history` keeps.
+    pub const MAX_HISTORY: usize = 10;
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageUpgrade {
+    pub from: String,
+    pub to: String,
+    /// When the new tag was rolled out (RFC 3339).
+    pub time: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RemediatedItem {
+    pub title: String,
+    pub reason: String,
+    pub action: String,
+    pub time: String,
+    /// The action was only reported because of `dryRun`.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Result of the last Prowlarr or Overseerr sync pass.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStatus {
+    /// When the last pass completed, even if some apps failed to sync.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_sync_time: Option<String>,
+    /// Apps registered or updated on the last completed pass.
+    #[serde(default)]
+    pub apps_synced: u32,
+    /// Stale registrations removed on the last completed pass.
+    #[serde(default)]
+    pub apps_removed: u32,
+    /// Why the last pass failed, or which apps it could not sync. Cleared
+    /// by a pass without errors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
 }
 
 impl ServarrAppStatus {
@@ -44,6 +414,10 @@ impl ServarrAppStatus {
 #[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Condition {
+    /// Serialized as `type`, the key kstatus, ArgoCD, and `kubectl wait`
+    /// look for. `conditionType` is still accepted when reading status
+    /// written by older operator versions.
+    #[serde(rename = "type", alias = "conditionType")]
     pub condition_type: String,
     pub status: String,
     #[serde(default)]
@@ -60,13 +434,89 @@ pub mod condition_types {
     pub const DEPLOYMENT_READY: &str = "DeploymentReady";
     pub const SERVICE_READY: &str = "ServiceReady";
     pub const NETWORK_POLICY_READY: &str = "NetworkPolicyReady";
+    /// Whether the cluster's CNI enforces the app's NetworkPolicy: False on
+    /// CNIs that ignore policies, Unknown when it can't be detected.
+    pub const NETWORK_POLICY_ENFORCED: &str = "NetworkPolicyEnforced";
     pub const ROUTE_READY: &str = "RouteReady";
     pub const PVC_READY: &str = "PvcReady";
     pub const PROGRESSING: &str = "Progressing";
+    /// kstatus abnormal-true condition: set while a rollout is in flight.
+    pub const RECONCILING: &str = "Reconciling";
+    /// kstatus abnormal-true condition: set when a rollout cannot complete.
+    pub const STALLED: &str = "Stalled";
     pub const DEGRADED: &str = "Degraded";
     pub const APP_HEALTHY: &str = "AppHealthy";
     pub const UPDATE_AVAILABLE: &str = "UpdateAvailable";
     pub const ADMIN_CREDENTIALS_CONFIGURED: &str = "AdminCredentialsConfigured";
+    pub const MAINTENANCE: &str = "Maintenance";
+    /// Whether the app is scaled to zero by `spec.suspend`, the
+    /// `servarr.dev/suspend` annotation, or a Suspend maintenance window.
+    pub const SUSPENDED: &str = "Suspended";
+    /// True while `spec.unsupportedOverrides` is set, whether or not the
+    /// operator flag lets it take effect.
+    pub const UNSUPPORTED_OVERRIDES: &str = "UnsupportedOverrides";
+    /// False when the default image has no variant for the node
+    /// architecture the app is pinned to; the Deployment is not applied.
+    pub const IMAGE_ARCHITECTURE_SUPPORTED: &str = "ImageArchitectureSupported";
+    /// False while a volume is being copied to a new storage class or the
+    /// copy failed; True once every requested migration has completed.
+    pub const STORAGE_MIGRATION: &str = "StorageMigration";
+    /// Whether some node advertises the device plugin resource for each GPU
+    /// in `spec.gpu`. Only set on apps that request a GPU.
+    pub const GPU_AVAILABLE: &str = "GpuAvailable";
+    /// Whether every format in `appConfig.customFormats` matches the app;
+    /// the message lists those created or updated on the last pass.
+    pub const CUSTOM_FORMATS_SYNCED: &str = "CustomFormatsSynced";
+    /// Whether every profile in `appConfig.qualityProfiles` matches the
+    /// app; the message lists those created or updated on the last pass.
+    pub const QUALITY_PROFILES_SYNCED: &str = "QualityProfilesSynced";
+    /// Whether `spec.auth` has been applied to the app's web UI.
+    pub const AUTH_CONFIGURED: &str = "AuthConfigured";
+    /// Whether the app's UI preferences match `spec.uiSettings`.
+    pub const UI_SETTINGS_SYNCED: &str = "UiSettingsSynced";
+    /// Whether Sonarr/Radarr media management settings match
+    /// `appConfig.mediaManagement`.
+    pub const MEDIA_MANAGEMENT_SYNCED: &str = "MediaManagementSynced";
+    /// Whether every piece of post-start configuration the operator applies
+    /// through the app's API (credentials, root folders, download clients,
+    /// profiles, and so on) has been applied. False until the app is Ready.
+    pub const CONFIGURATION_COMPLETE: &str = "ConfigurationComplete";
+    /// Whether Sonarr/Radarr monitoring settings match
+    /// `appConfig.monitoring`.
+    pub const MONITORING_SYNCED: &str = "MonitoringSynced";
+    /// Whether every tag in `appConfig.tags` exists in the app; the message
+    /// lists those created on the last pass.
+    pub const TAGS_SYNCED: &str = "TagsSynced";
+    /// Whether the app's root folders match `appConfig.rootFolders`; the
+    /// message lists those added or removed on the last pass.
+    pub const ROOT_FOLDERS_SYNCED: &str = "RootFoldersSynced";
+    /// Whether the namespace's Transmission and SABnzbd apps are registered
+    /// as download clients per `downloadClientSync`.
+    pub const DOWNLOAD_CLIENTS_SYNCED: &str = "DownloadClientsSynced";
+    /// Whether Prowlarr's indexers match `appConfig.indexers`; the message
+    /// lists those added, updated, or removed on the last pass.
+    pub const INDEXERS_SYNCED: &str = "IndexersSynced";
+    /// Whether a media server's accounts match `appConfig.users`; the
+    /// message lists those added, updated, or removed on the last pass.
+    pub const USERS_SYNCED: &str = "UsersSynced";
+    /// Result of the last `backup.integrityCheck` run against the app's
+    /// SQLite database.
+    pub const CONFIG_DATABASE_HEALTHY: &str = "ConfigDatabaseHealthy";
+    /// Whether the HorizontalPodAutoscaler for `spec.autoscaling` is able to
+    /// scale the workload, from its `AbleToScale` and `ScalingActive`
+    /// conditions.
+    pub const AUTOSCALING: &str = "Autoscaling";
+    /// Result of the `persistence.verifyMounts` init container in the
+    /// app's newest pod; the message names the mounts that failed.
+    pub const MOUNTS_VERIFIED: &str = "MountsVerified";
+    /// Whether every PVC the app uses is Bound; the message lists those
+    /// that aren't.
+    pub const STORAGE_BOUND: &str = "StorageBound";
+    /// Whether the Gateways the app's HTTPRoute or TCPRoute attaches to
+    /// have accepted it.
+    pub const ROUTE_ACCEPTED: &str = "RouteAccepted";
+    /// The cert-manager Certificate's own Ready condition.
+    pub const CERTIFICATE_READY: &str = "CertificateReady";
 }
 
 impl Condition {
@@ -29,6 +29,43 @@ pub struct PvcVolume {
     pub size: String,
     #[serde(default)]
     pub storage_class: String,
+    /// Copy the data into a new PVC when `storageClass` no longer matches
+    /// the existing claim's. Without it a changed storage class on an
+    /// existing volume is ignored, since a PVC's class is immutable.
+    #[serde(default)]
+    pub migrate_storage: bool,
+    /// What happens to the old PVC once a storage migration has copied it.
+    #[serde(default)]
+    pub deletion_policy: VolumeDeletionPolicy,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub enum VolumeDeletionPolicy {
+    /// Keep the old PVC (and its data) after migrating.
+    #[default]
+    Retain,
+    /// Delete the old PVC after migrating.
+    Delete,
+}
+
+/// Kind of workload that runs the app's pod.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub enum WorkloadType {
+    /// A Deployment mounting PVCs the operator creates up front.
+    #[default]
+    Deployment,
+    /// A StatefulSet whose volumes come from volumeClaimTemplates, giving
+    /// the pod a stable identity and claims named `{volume}-{app}-0`.
+    StatefulSet,
+}
+
+impl WorkloadType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Deployment => "Deployment",
+            Self::StatefulSet => "StatefulSet",
+        }
+    }
 }
 
 fn default_access_mode() -> String {
@@ -57,6 +94,12 @@ pub struct PersistenceSpec {
     pub volumes: Vec<PvcVolume>,
     #[serde(default)]
     pub nfs_mounts: Vec<NfsMount>,
+    /// Check in an init container that every NFS mount can be read, and
+    /// written unless it is `readOnly`, before the app starts. A failed
+    /// check keeps the pod from starting and is reported in the
+    /// `MountsVerified` condition.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub verify_mounts: bool,
 }
 
 impl PersistenceSpec {
@@ -65,6 +108,7 @@ impl PersistenceSpec {
     /// - PVC volumes: `over.volumes` replaces entirely when non-empty; base
     ///   volumes are used when `over.volumes` is empty.
     /// - NFS mounts: additive, deduplicated by name (`over` wins on conflict).
+    /// - `verify_mounts`: set when either layer sets it.
     pub fn merge_with(&self, over: &PersistenceSpec) -> PersistenceSpec {
         let volumes = if over.volumes.is_empty() {
             self.volumes.clone()
@@ -83,7 +127,119 @@ impl PersistenceSpec {
         PersistenceSpec {
             volumes,
             nfs_mounts: nfs_map.into_values().collect(),
+            verify_mounts: self.verify_mounts || over.verify_mounts,
+        }
+    }
+
+    /// The deepest mount containing `path`, as `(mount_path, read_only)`.
+    pub fn mount_for(&self, path: &str) -> Option<(&str, bool)> {
+        let volumes = self.volumes.iter().map(|v| (v.mount_path.as_str(), false));
+        let nfs = self
+            .nfs_mounts
+            .iter()
+            .map(|m| (m.mount_path.as_str(), m.read_only));
+        volumes
+            .chain(nfs)
+            .filter(|(mount, _)| is_under(path, mount))
+            .max_by_key(|(mount, _)| mount.trim_end_matches('/').len())
+    }
+}
+
+/// A cloud storage remote mounted with `rclone mount` in a sidecar container
+/// and shared with the app through mount propagation.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RcloneMount {
+    /// Lowercase DNS label, unique within the app. Names the sidecar
+    /// (`rclone-<name>`) and its volumes.
+    pub name: String,
+    /// Remote and path to mount, as rclone writes it: `gdrive:media/movies`.
+    pub remote: String,
+    /// Where the remote appears in the app container.
+    pub mount_path: String,
+    /// Secret holding the rclone config that defines the remote.
+    pub config_secret: String,
+    /// Key of the config in `configSecret` (default: `rclone.conf`).
+    #[serde(default = "default_rclone_config_key")]
+    pub config_key: String,
+    /// Mount read-only (default: true). Media servers only need to read.
+    #[serde(default = "default_true")]
+    pub read_only: bool,
+    #[serde(default)]
+    pub vfs: RcloneVfsSpec,
+    /// rclone image (default: `rclone/rclone`).
+    #[serde(default)]
+    pub image: Option<ImageSpec>,
+    /// Extra flags for `rclone mount`, e.g. `--buffer-size=64M`.
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+    #[serde(default)]
+    pub resources: Option<ResourceRequirements>,
+}
+
+fn default_rclone_config_key() -> String {
+    "rclone.conf".into()
+}
+
+/// rclone VFS cache settings. Unset fields keep rclone's defaults.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RcloneVfsSpec {
+    /// `--vfs-cache-mode`: `off`, `minimal`, `writes`, or `full`
+    /// (default: `full`, which streaming media needs for seeking).
+    #[serde(default)]
+    pub cache_mode: Option<String>,
+    /// `--vfs-cache-max-size`, e.g. `50G`. The cache lives in an emptyDir, so
+    /// leave room for it on the node's ephemeral storage.
+    #[serde(default)]
+    pub cache_max_size: Option<String>,
+    /// `--vfs-cache-max-age`, e.g. `24h`.
+    #[serde(default)]
+    pub cache_max_age: Option<String>,
+    /// `--dir-cache-time`, e.g. `1h`.
+    #[serde(default)]
+    pub dir_cache_time: Option<String>,
+    /// `--vfs-read-ahead`, e.g. `256M`.
+    #[serde(default)]
+    pub read_ahead: Option<String>,
+}
+
+impl RcloneMount {
+    pub const CACHE_MODES: &[&str] = &["off", "minimal", "writes", "full"];
+
+    /// Check the mount on its own. Returns one message per problem.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let field = format!("rcloneMounts[{}]", self.name);
+        if !super::is_valid_instance(&self.name) {
+            errors.push(format!(
+                "{field}: name must be a lowercase DNS label (a-z, 0-9, '-')"
+            ));
+        }
+        if !self.remote.contains(':') {
+            errors.push(format!(
+                "{field}: remote '{}' must be 'remote:path'",
+                self.remote
+            ));
+        }
+        if !self.mount_path.starts_with('/') || self.mount_path == "/" {
+            errors.push(format!(
+                "{field}: mountPath '{}' must be an absolute path below /",
+                self.mount_path
+            ));
         }
+        if self.config_secret.is_empty() {
+            errors.push(format!("{field}: configSecret must be set"));
+        }
+        if let Some(ref mode) = self.vfs.cache_mode
+            && !Self::CACHE_MODES.contains(&mode.as_str())
+        {
+            errors.push(format!(
+                "{field}: vfs.cacheMode '{mode}' must be one of {}",
+                Self::CACHE_MODES.join(", ")
+            ));
+        }
+        errors
     }
 }
 
@@ -102,6 +258,24 @@ pub struct GatewaySpec {
     /// Certificate and uses a TCPRoute instead of an HTTPRoute.
     #[serde(default)]
     pub tls: Option<TlsSpec>,
+    /// Other Services in the app's namespace that receive a weighted share
+    /// of the HTTPRoute's traffic, e.g. a MediaStack canary. The app's own
+    /// Service gets what is left of 100.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub backends: Vec<GatewayBackend>,
+}
+
+/// A weighted HTTPRoute backend next to the app's own Service.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayBackend {
+    /// Name of the Service.
+    pub name: String,
+    /// Service port. Defaults to the app's first Service port.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<i32>,
+    /// Percentage of requests sent to this Service, from 0 to 100.
+    pub weight: i32,
 }
 
 /// TLS termination via cert-manager.
@@ -144,12 +318,70 @@ pub struct GatewayParentRef {
     pub section_name: String,
 }
 
+/// Exposure through a `networking.k8s.io/v1` Ingress, for clusters without
+/// Gateway API. Can be set alongside `gateway`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IngressSpec {
+    /// Hostname the Ingress routes to the app.
+    pub host: String,
+    /// Path prefix routed to the app (default: `/`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// IngressClass to use. If omitted, the cluster's default class applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingress_class_name: Option<String>,
+    /// Secret holding the TLS certificate for `host`. TLS is terminated at
+    /// the ingress controller when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_secret_name: Option<String>,
+    /// Annotations added to the Ingress, e.g. controller-specific settings.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub annotations: std::collections::BTreeMap<String, String>,
+    /// Namespace of the ingress controller. Its pods are allowed through the
+    /// app's NetworkPolicy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub controller_namespace: Option<String>,
+}
+
+impl IngressSpec {
+    /// The path prefix routed to the app.
+    pub fn path(&self) -> &str {
+        self.path.as_deref().unwrap_or("/")
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ServiceSpec {
     #[serde(default = "default_service_type")]
     pub service_type: String,
     pub ports: Vec<ServicePort>,
+    /// `Local` routes in-cluster traffic only to the app's pod on the
+    /// client's node, e.g. to keep media reads next to a node-local cache.
+    /// Clients on nodes without one get no response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub internal_traffic_policy: Option<TrafficPolicy>,
+    /// Annotate the Service with `service.kubernetes.io/topology-mode:
+    /// Auto`, so kube-proxy prefers endpoints in the client's zone.
+    #[serde(default)]
+    pub topology_aware_routing: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub enum TrafficPolicy {
+    #[default]
+    Cluster,
+    Local,
+}
+
+impl TrafficPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cluster => "Cluster",
+            Self::Local => "Local",
+        }
+    }
 }
 
 fn default_service_type() -> String {
@@ -265,6 +497,39 @@ pub struct ResourceList {
     pub memory: String,
 }
 
+/// Lifecycle hooks for the app container.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LifecycleSpec {
+    /// Command run in the container right after it starts (postStart). The
+    /// container is restarted if it fails.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_start: Vec<String>,
+    /// Command run before the container is stopped (preStop), e.g.
+    /// `["transmission-remote", "--exit"]`. Replaces the app type's default
+    /// hook.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_stop: Vec<String>,
+    /// Use the app type's graceful-shutdown preStop hook when `preStop` is
+    /// empty (default: true).
+    #[serde(default = "default_true")]
+    pub default_hooks: bool,
+    /// Seconds the pod has to stop, preStop hook included (default: 30).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub termination_grace_period_seconds: Option<i64>,
+}
+
+impl Default for LifecycleSpec {
+    fn default() -> Self {
+        Self {
+            post_start: Vec::new(),
+            pre_stop: Vec::new(),
+            default_hooks: true,
+            termination_grace_period_seconds: None,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProbeSpec {
@@ -347,9 +612,74 @@ pub struct NodeScheduling {
     #[serde(default)]
     #[schemars(schema_with = "json_object_schema")]
     pub affinity: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(schema_with = "json_object_array_schema")]
+    pub topology_spread_constraints: Vec<serde_json::Value>,
+}
+
+/// Failure domain a MediaStack's pods are spread across.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub enum SpreadTopology {
+    /// Spread across nodes (`kubernetes.io/hostname`).
+    #[default]
+    Node,
+    /// Spread across zones (`topology.kubernetes.io/zone`).
+    Zone,
+}
+
+impl SpreadTopology {
+    /// The node label that identifies the failure domain.
+    pub fn topology_key(&self) -> &'static str {
+        match self {
+            Self::Node => "kubernetes.io/hostname",
+            Self::Zone => "topology.kubernetes.io/zone",
+        }
+    }
+}
+
+/// How a MediaStack's pods are spread across failure domains, so that
+/// losing one node or zone degrades the stack instead of taking all of it
+/// down.
+///
+/// Every pod labelled `servarr.dev/stack: <stack>` counts towards the
+/// spread; the NFS server and the stack's apps are placed with a
+/// topologySpreadConstraint of `maxSkew: 1` over those pods.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpreadPolicy {
+    /// The failure domain to spread across. Defaults to `Node`.
+    #[serde(default)]
+    pub topology: SpreadTopology,
+    /// Leave a pod Pending rather than unbalance the spread. By default the
+    /// spread is a preference, so a stack on too few nodes still schedules.
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl SpreadPolicy {
+    /// The topologySpreadConstraint placing a pod of `stack_name`.
+    pub fn constraint(&self, stack_name: &str) -> serde_json::Value {
+        serde_json::json!({
+            "maxSkew": 1,
+            "topologyKey": self.topology.topology_key(),
+            "whenUnsatisfiable": if self.required { "DoNotSchedule" } else { "ScheduleAnyway" },
+            "labelSelector": {
+                "matchLabels": { "servarr.dev/stack": stack_name }
+            }
+        })
+    }
+}
+
+/// Whether `path` is `mount` or lies below it.
+pub(crate) fn is_under(path: &str, mount: &str) -> bool {
+    let mount = mount.trim_end_matches('/');
+    path == mount
+        || path
+            .strip_prefix(mount)
+            .is_some_and(|rest| rest.starts_with('/'))
 }
 
-fn default_true() -> bool {
+pub(crate) fn default_true() -> bool {
     true
 }
 
@@ -377,6 +707,10 @@ pub struct NetworkPolicyConfig {
     #[serde(default)]
     #[schemars(schema_with = "json_object_array_schema")]
     pub custom_egress_rules: Vec<serde_json::Value>,
+    /// Other namespaces whose pods may reach this app, and which this app
+    /// may reach, e.g. the namespaces of the rest of its MediaStack.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_namespaces: Vec<String>,
 }
 
 impl Default for NetworkPolicyConfig {
@@ -387,6 +721,7 @@ impl Default for NetworkPolicyConfig {
             allow_internet_egress: false,
             denied_cidr_blocks: Vec::new(),
             custom_egress_rules: Vec::new(),
+            allowed_namespaces: Vec::new(),
         }
     }
 }
@@ -403,6 +738,16 @@ pub struct ApiHealthCheckSpec {
     pub interval_seconds: Option<u32>,
 }
 
+/// Scheduled rotation of the app's API key. Sonarr, Radarr, Lidarr,
+/// Readarr, Whisparr, Prowlarr, and Bazarr only; needs an `apiKeySecret`
+/// the operator created.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyRotationSpec {
+    /// Cron expression for the rotation schedule (e.g. "0 4 1 * *").
+    pub schedule: String,
+}
+
 /// Backup configuration for the app.
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
 #[serde(rename_all = "camelCase")]
@@ -416,6 +761,93 @@ pub struct BackupSpec {
     /// Number of backups to retain.
     #[serde(default = "default_retention_count")]
     pub retention_count: u32,
+    /// S3-compatible storage that `servarr.dev/restore-from-object`
+    /// annotations read backup archives from and, with a `bucket`, that
+    /// scheduled backups are uploaded to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object_storage: Option<ObjectStorageSpec>,
+    /// Scheduled `PRAGMA integrity_check` of the app's SQLite database,
+    /// reported as the `ConfigDatabaseHealthy` condition. Independent of
+    /// `enabled`; ignored for apps on Postgres.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity_check: Option<IntegrityCheckSpec>,
+    /// Back up by taking CSI VolumeSnapshots of the config volume on
+    /// `schedule` instead of through the app's backup API, so apps without
+    /// one (Plex, Jellyfin, Transmission) can be backed up too.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume_snapshot: Option<VolumeSnapshotBackupSpec>,
+}
+
+/// CSI snapshots of the app's config PersistentVolumeClaim.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeSnapshotBackupSpec {
+    /// VolumeSnapshotClass to snapshot with (default: the cluster's
+    /// default class for the volume's CSI driver).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume_snapshot_class_name: Option<String>,
+}
+
+/// A CronJob that checks a consistent copy of the app's SQLite database.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityCheckSpec {
+    /// Five-field cron expression for the check CronJob (e.g. "0 5 * * 0").
+    pub schedule: String,
+    /// Image providing `sh` and `sqlite3` (default: `keinos/sqlite3`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<ImageSpec>,
+}
+
+/// An S3-compatible object store.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectStorageSpec {
+    /// Secret with `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` keys.
+    pub credentials_secret: String,
+    /// Endpoint of a non-AWS service, e.g. `https://s3.us-west-000.backblazeb2.com`
+    /// or a MinIO URL (default: AWS).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// Bucket region (default: the service's default).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// Bucket that the app's backups are uploaded to after each scheduled
+    /// backup. Without it the store is only read from, for restores.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bucket: Option<String>,
+    /// Key prefix the backups are uploaded under. Each app's backups go to
+    /// `{prefix}/{namespace}/{app}`, so apps sharing this block never share
+    /// a folder (default: `{namespace}/{app}` at the bucket root).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    /// What happens to the uploaded backups when the app is deleted.
+    #[serde(default)]
+    pub deletion_policy: BackupDeletionPolicy,
+}
+
+impl ObjectStorageSpec {
+    /// Key prefix the backups of app `name` in `namespace` are uploaded
+    /// under, without leading or trailing slashes. It always ends in
+    /// `{namespace}/{name}`, so deleting one app's backups never touches
+    /// another app's, or the rest of the bucket.
+    pub fn upload_prefix(&self, namespace: &str, name: &str) -> String {
+        match self.prefix.as_deref().map(|p| p.trim_matches('/')) {
+            Some(prefix) if !prefix.is_empty() => format!("{prefix}/{namespace}/{name}"),
+            _ => format!("{namespace}/{name}"),
+        }
+    }
+}
+
+/// What happens to a deleted app's backups in object storage.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub enum BackupDeletionPolicy {
+    /// Keep the uploaded backups.
+    #[default]
+    Retain,
+    /// Delete every object under the upload prefix before the app is
+    /// removed.
+    Delete,
 }
 
 fn default_retention_count() -> u32 {
@@ -428,6 +860,263 @@ impl Default for BackupSpec {
             enabled: false,
             schedule: String::new(),
             retention_count: default_retention_count(),
+            object_storage: None,
+            integrity_check: None,
+            volume_snapshot: None,
+        }
+    }
+}
+
+/// Automatic handling of downloads that are stuck in the Sonarr or Radarr
+/// queue: failed imports, releases in the wrong format, downloads without
+/// usable files, and stalled torrents.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueRemediationSpec {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Minutes between queue checks.
+    #[serde(default = "default_remediation_interval")]
+    pub interval_minutes: u32,
+    /// Record what would be done without touching the queue.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// What to do per kind of problem. A queue item is handled by the
+    /// first rule for its reason; reasons without a rule are left alone.
+    #[serde(default = "QueueRemediationSpec::default_rules")]
+    pub rules: Vec<RemediationRule>,
+}
+
+fn default_remediation_interval() -> u32 {
+    15
+}
+
+impl QueueRemediationSpec {
+    pub fn default_rules() -> Vec<RemediationRule> {
+        [
+            (RemediationReason::ImportFailed, 60),
+            (RemediationReason::WrongFormat, 60),
+            (RemediationReason::MissingFiles, 60),
+            (RemediationReason::Stalled, 360),
+        ]
+        .into_iter()
+        .map(|(reason, min_age_minutes)| RemediationRule {
+            reason,
+            min_age_minutes,
+            action: RemediationAction::default(),
+            remove_from_client: true,
+        })
+        .collect()
+    }
+
+    /// The rule handling `reason`, if any.
+    pub fn rule_for(&self, reason: RemediationReason) -> Option<&RemediationRule> {
+        self.rules.iter().find(|r| r.reason == reason)
+    }
+}
+
+impl Default for QueueRemediationSpec {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_minutes: default_remediation_interval(),
+            dry_run: false,
+            rules: Self::default_rules(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RemediationRule {
+    pub reason: RemediationReason,
+    /// Minutes since the release was grabbed before the rule acts, so the
+    /// app's own retries get a chance first.
+    #[serde(default = "default_remediation_min_age")]
+    pub min_age_minutes: u32,
+    #[serde(default)]
+    pub action: RemediationAction,
+    /// Also delete the download and its files from the download client.
+    #[serde(default = "default_true")]
+    pub remove_from_client: bool,
+}
+
+fn default_remediation_min_age() -> u32 {
+    60
+}
+
+/// Why a queue item is stuck.
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, JsonSchema,
+)]
+pub enum RemediationReason {
+    /// The import failed or is blocked for any reason not covered below.
+    ImportFailed,
+    /// The release can't be imported as is: not an upgrade, unparseable,
+    /// a sample, or an unsupported file type.
+    WrongFormat,
+    /// The download finished without any importable media files.
+    MissingFiles,
+    /// The download client reports the download as stalled.
+    Stalled,
+}
+
+impl RemediationReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ImportFailed => "ImportFailed",
+            Self::WrongFormat => "WrongFormat",
+            Self::MissingFiles => "MissingFiles",
+            Self::Stalled => "Stalled",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub enum RemediationAction {
+    /// Blocklist the release and search for another one.
+    #[default]
+    BlocklistAndSearch,
+    /// Blocklist the release without searching.
+    Blocklist,
+    /// Remove the item from the queue without blocklisting.
+    Remove,
+}
+
+/// Scheduled sync of TRaSH-guide custom formats and quality definitions
+/// into Sonarr or Radarr, the way Recyclarr does it.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashSyncSpec {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Cron expression or shorthand for sync runs (default: `@daily`).
+    #[serde(default = "default_trash_sync_schedule")]
+    pub schedule: String,
+    /// Templates built into the operator to apply, in order:
+    /// `quality-definitions` (TRaSH file sizes) and `unwanted` (formats
+    /// for releases most profiles should score down).
+    #[serde(default = "TrashSyncSpec::default_templates")]
+    pub templates: Vec<String>,
+    /// ConfigMap in the app's namespace whose `.json` keys hold more
+    /// templates, applied after `templates` in key order. A custom format
+    /// in both replaces the built-in one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_map: Option<String>,
+}
+
+fn default_trash_sync_schedule() -> String {
+    "@daily".into()
+}
+
+impl TrashSyncSpec {
+    pub fn default_templates() -> Vec<String> {
+        vec!["quality-definitions".into()]
+    }
+}
+
+impl Default for TrashSyncSpec {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            schedule: default_trash_sync_schedule(),
+            templates: Self::default_templates(),
+            config_map: None,
+        }
+    }
+}
+
+/// What the operator does about newer releases of an app.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub enum UpdateMode {
+    /// Don't look for updates.
+    Manual,
+    /// Report a newer release in the `UpdateAvailable` condition.
+    #[default]
+    Notify,
+    /// Move the image to the newest tag in its registry on `schedule`.
+    Auto,
+}
+
+/// Image update handling. Without it the operator notifies, as with
+/// `mode: Notify`.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePolicySpec {
+    #[serde(default)]
+    pub mode: UpdateMode,
+    /// Cron expression or shorthand for when `Auto` checks the registry
+    /// and rolls out a newer tag (default: `0 4 * * *`). This is the
+    /// upgrade window: the Deployment only changes at these times, and a
+    /// MaintenanceWindow that suspends or freezes the app postpones it.
+    #[serde(default = "default_update_schedule")]
+    pub schedule: String,
+    /// Let `Auto` move to a new major version. Off by default, as major
+    /// releases can migrate the database one way.
+    #[serde(default)]
+    pub allow_major: bool,
+}
+
+fn default_update_schedule() -> String {
+    "0 4 * * *".into()
+}
+
+impl Default for UpdatePolicySpec {
+    fn default() -> Self {
+        Self {
+            mode: UpdateMode::default(),
+            schedule: default_update_schedule(),
+            allow_major: false,
+        }
+    }
+}
+
+/// A recurring window for the operator's disruptive actions on one app:
+/// restores, automatic image updates, and drift corrections that restart
+/// the pod. Outside it they are listed in `status.pendingActions` and run
+/// once it opens. Changes to the spec itself are applied right away.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AppMaintenanceWindow {
+    /// Cron expression or shorthand for when the window opens.
+    pub schedule: String,
+    /// How long the window stays open (default: 60).
+    #[serde(default = "default_window_minutes")]
+    pub duration_minutes: u32,
+}
+
+fn default_window_minutes() -> u32 {
+    60
+}
+
+/// How soon a ServarrApp is reconciled again after a successful pass.
+/// Apps that are still rolling out or failing health checks come back
+/// after `unsettledSeconds`; settled apps after `settledSeconds`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RequeueSpec {
+    /// Seconds between reconciles while the app is not Ready, a rollout is
+    /// in progress, or the app's health check fails.
+    #[serde(default = "default_requeue_unsettled")]
+    pub unsettled_seconds: u32,
+    /// Seconds between reconciles once the app is Ready and healthy.
+    #[serde(default = "default_requeue_settled")]
+    pub settled_seconds: u32,
+}
+
+fn default_requeue_unsettled() -> u32 {
+    20
+}
+
+fn default_requeue_settled() -> u32 {
+    300
+}
+
+impl Default for RequeueSpec {
+    fn default() -> Self {
+        Self {
+            unsettled_seconds: default_requeue_unsettled(),
+            settled_seconds: default_requeue_settled(),
         }
     }
 }
@@ -435,7 +1124,8 @@ impl Default for BackupSpec {
 /// GPU device passthrough configuration.
 ///
 /// When set, the corresponding GPU device plugin resource is added
-/// to the container's resource limits and requests.
+/// to the container's resource limits and requests. If no node advertises
+/// the resource, the app runs without the GPU unless `required` is set.
 #[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GpuSpec {
@@ -448,6 +1138,43 @@ pub struct GpuSpec {
     /// AMD GPU count (adds `amd.com/gpu` resource limit+request).
     #[serde(default)]
     pub amd: Option<i32>,
+    /// Keep requesting the GPU when no node advertises it, leaving pods
+    /// Pending with a `GpuAvailable=False` condition. By default the app
+    /// falls back to CPU (software transcoding) until a device plugin
+    /// provides the resource.
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Horizontal pod autoscaling for apps that can run several replicas.
+///
+/// The operator owns a HorizontalPodAutoscaler (`autoscaling/v2`) for the
+/// app's workload and stops setting its replica count. With no target set,
+/// the autoscaler aims for 80% average CPU utilization.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoscalingSpec {
+    /// Fewest replicas to run.
+    #[serde(default = "default_min_replicas")]
+    pub min_replicas: i32,
+    /// Most replicas to run.
+    pub max_replicas: i32,
+    /// Target average CPU utilization, as a percentage of the CPU request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_cpu_utilization_percentage: Option<i32>,
+    /// Target average memory utilization, as a percentage of the memory
+    /// request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_memory_utilization_percentage: Option<i32>,
+}
+
+fn default_min_replicas() -> i32 {
+    1
+}
+
+impl AutoscalingSpec {
+    /// CPU target used when neither target is set.
+    pub const DEFAULT_CPU_UTILIZATION: i32 = 80;
 }
 
 /// Configuration for Prowlarr cross-app synchronization.
@@ -464,9 +1191,31 @@ pub struct ProwlarrSyncSpec {
     /// Namespace to discover apps in. Defaults to the Prowlarr CR's namespace.
     #[serde(default)]
     pub namespace_scope: Option<String>,
+    /// More namespaces to discover apps in. With `namespaces` or
+    /// `namespaceSelector` set, the Prowlarr CR's own namespace is only
+    /// searched when it is listed or selected.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub namespaces: Vec<String>,
+    /// Discover apps in every namespace with these labels too. Listing
+    /// namespaces needs the cluster-wide RBAC of `watchAllNamespaces`.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub namespace_selector: std::collections::BTreeMap<String, String>,
     /// Whether to remove apps from Prowlarr when their CRs are deleted.
     #[serde(default = "default_true")]
     pub auto_remove: bool,
+    /// How Prowlarr syncs indexers to the registered apps (default:
+    /// fullSync).
+    #[serde(default)]
+    pub sync_level: ProwlarrSyncLevel,
+    /// Indexer categories sent to each app type, e.g. only the Usenet TV
+    /// categories to Sonarr. Apps without a mapping keep Prowlarr's default
+    /// categories.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<ProwlarrCategoryMapping>,
+    /// Sync profiles (Prowlarr app profiles) to create or update by name.
+    /// Indexers refer to them to choose RSS and search behavior.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub app_profiles: Vec<ProwlarrAppProfile>,
 }
 
 impl Default for ProwlarrSyncSpec {
@@ -474,16 +1223,87 @@ impl Default for ProwlarrSyncSpec {
         Self {
             enabled: false,
             namespace_scope: None,
+            namespaces: Vec::new(),
+            namespace_selector: Default::default(),
             auto_remove: true,
+            sync_level: ProwlarrSyncLevel::default(),
+            categories: Vec::new(),
+            app_profiles: Vec::new(),
+        }
+    }
+}
+
+/// Prowlarr's application sync level.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ProwlarrSyncLevel {
+    /// Don't sync indexers to the app.
+    Disabled,
+    /// Add new indexers to the app, but never update or remove them.
+    AddOnly,
+    /// Keep the app's indexers fully in sync.
+    #[default]
+    FullSync,
+}
+
+impl ProwlarrSyncLevel {
+    /// The value Prowlarr's API uses.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Disabled => "disabled",
+            Self::AddOnly => "addOnly",
+            Self::FullSync => "fullSync",
         }
     }
 }
 
+/// Indexer categories Prowlarr syncs to the apps of one type.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProwlarrCategoryMapping {
+    /// Sonarr, Radarr, or Lidarr.
+    pub app: super::AppType,
+    /// Only apply to this instance of the app type (e.g. `4k`). A mapping
+    /// with an instance takes precedence over one without.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Newznab category IDs, e.g. `[5000, 5030, 5040]` for TV.
+    pub sync_categories: Vec<i32>,
+    /// Anime category IDs (Sonarr only).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub anime_sync_categories: Vec<i32>,
+}
+
+/// A Prowlarr app profile ("sync profile").
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProwlarrAppProfile {
+    /// Profile name, which identifies it in Prowlarr.
+    pub name: String,
+    /// Sync the indexer for RSS (default: true).
+    #[serde(default = "default_true")]
+    pub enable_rss: bool,
+    /// Use the indexer for automatic searches (default: true).
+    #[serde(default = "default_true")]
+    pub enable_automatic_search: bool,
+    /// Use the indexer for interactive searches (default: true).
+    #[serde(default = "default_true")]
+    pub enable_interactive_search: bool,
+    /// Minimum seeders for torrent indexers (default: 1).
+    #[serde(default = "default_minimum_seeders")]
+    pub minimum_seeders: i32,
+}
+
+fn default_minimum_seeders() -> i32 {
+    1
+}
+
 /// Configuration for Overseerr cross-app synchronization.
 ///
-/// When enabled on an Overseerr-type ServarrApp, the operator discovers
-/// Sonarr/Radarr instances in the target namespace and registers them as
-/// servers in Overseerr with correct `is4k`/`isDefault` flags.
+/// When enabled on an Overseerr or Jellyseerr ServarrApp, the operator
+/// discovers Sonarr/Radarr instances in the target namespace and registers
+/// them as servers with correct `is4k`/`isDefault` flags. Jellyseerr is also
+/// connected to the namespace's Jellyfin when that has adminCredentials.
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OverseerrSyncSpec {
@@ -508,6 +1328,81 @@ impl Default for OverseerrSyncSpec {
     }
 }
 
+/// Configuration for Bazarr cross-app synchronization.
+///
+/// When enabled on a Bazarr ServarrApp, the operator discovers the Sonarr
+/// and Radarr instances in the target namespace and connects Bazarr to
+/// them, then applies the providers and language profiles of
+/// `appConfig.bazarr`. Bazarr connects to one Sonarr and one Radarr, so
+/// `instance` picks which.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BazarrSyncSpec {
+    /// Whether Bazarr sync is enabled.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Namespace to discover apps in. Defaults to the Bazarr CR's namespace.
+    #[serde(default)]
+    pub namespace_scope: Option<String>,
+    /// Connect the Sonarr and Radarr of this instance, e.g. `4k` (default:
+    /// the ones without an instance).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Whether to turn off Bazarr's Sonarr or Radarr connection when the
+    /// app it points to is deleted.
+    #[serde(default = "default_true")]
+    pub auto_remove: bool,
+}
+
+impl Default for BazarrSyncSpec {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            namespace_scope: None,
+            instance: None,
+            auto_remove: true,
+        }
+    }
+}
+
+/// Download client wiring for a Sonarr, Radarr, or Lidarr ServarrApp.
+///
+/// When enabled, the operator registers every Transmission and SABnzbd
+/// ServarrApp in the app's namespace as a download client in the app,
+/// with credentials read from their Secrets. Apps created by a MediaStack
+/// only get the download clients of the same stack.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadClientSyncSpec {
+    /// Whether download client sync is enabled.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Download client category for this app's downloads, e.g. `tv`.
+    /// Defaults to the download client's own default.
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Whether to remove download clients the operator registered once
+    /// their ServarrApps are deleted.
+    #[serde(default = "default_true")]
+    pub auto_remove: bool,
+    /// Tags, by label, set on the download clients the operator registers,
+    /// created in the app when missing. A tagged download client is only
+    /// used for items with a matching tag.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl Default for DownloadClientSyncSpec {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            category: None,
+            auto_remove: true,
+            tags: Vec::new(),
+        }
+    }
+}
+
 /// Configuration for the in-cluster NFS server deployed by the MediaStack operator.
 ///
 /// By default (when this field is absent or `enabled` is true), the operator
@@ -580,6 +1475,158 @@ pub struct NfsServerSpec {
     /// to form the NFS server-side path (e.g. `/volume1` + `/movies` = `/volume1/movies`).
     #[serde(default = "default_external_path")]
     pub external_path: String,
+
+    /// Backups of the in-cluster NFS server's data. Ignored for external
+    /// servers.
+    #[serde(default)]
+    pub backup: Option<NfsBackupSpec>,
+
+    /// Named exports next to the media paths, e.g. `downloads` or
+    /// `backups`, each mounted into the apps that use it. An export named
+    /// `media` holds the media paths instead of the root of the share.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exports: Vec<NfsExport>,
+}
+
+/// A named export of the stack's NFS server.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NfsExport {
+    /// Lowercase DNS label naming the export. Also names the volume in the
+    /// apps that mount it.
+    pub name: String,
+
+    /// Path of the export on the server, relative to the share root (or to
+    /// `externalPath`). Defaults to `/{name}`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// Where apps mount the export. Defaults to `/{name}`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mount_path: Option<String>,
+
+    /// Size of a PVC of its own backing the export, which caps how much
+    /// the apps can write to it. Without it the export lives on the server's
+    /// main PVC. In-cluster servers only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+
+    /// How the server maps the UIDs of clients. Defaults to `NoRootSquash`.
+    #[serde(default)]
+    pub squash: NfsSquash,
+
+    /// UID that squashed users are mapped to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anon_uid: Option<u32>,
+
+    /// GID that squashed users are mapped to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anon_gid: Option<u32>,
+
+    /// Export read-only, and mount it read-only in the apps.
+    #[serde(default)]
+    pub read_only: bool,
+
+    /// App types that mount the export. Defaults by name: `downloads` goes
+    /// to the download clients and the apps that import from them, and
+    /// `backups` to the Servarr apps. Other exports are mounted nowhere
+    /// unless listed here.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub apps: Vec<super::AppType>,
+}
+
+/// UID mapping of an NFS export.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub enum NfsSquash {
+    /// Clients' root stays root.
+    #[default]
+    NoRootSquash,
+    /// Clients' root is mapped to the anonymous user.
+    RootSquash,
+    /// Every client user is mapped to the anonymous user.
+    AllSquash,
+}
+
+impl NfsSquash {
+    /// The option in an `/etc/exports` entry.
+    pub fn export_option(&self) -> &'static str {
+        match self {
+            Self::NoRootSquash => "no_root_squash",
+            Self::RootSquash => "root_squash",
+            Self::AllSquash => "all_squash",
+        }
+    }
+}
+
+impl NfsExport {
+    /// Names the auto-injected media mounts already use.
+    pub const RESERVED_NAMES: &[&str] = &["movies", "tv", "music", "movies-4k", "tv-4k"];
+
+    /// Path of the export relative to the share root.
+    pub fn path(&self) -> String {
+        self.path
+            .clone()
+            .unwrap_or_else(|| format!("/{}", self.name))
+    }
+
+    /// Where apps mount the export.
+    pub fn mount_path(&self) -> String {
+        self.mount_path
+            .clone()
+            .unwrap_or_else(|| format!("/{}", self.name))
+    }
+
+    /// Whether apps of type `app` mount the export.
+    pub fn mounted_by(&self, app: &super::AppType) -> bool {
+        use super::AppType;
+        if !self.apps.is_empty() {
+            return self.apps.contains(app);
+        }
+        match self.name.as_str() {
+            "downloads" => {
+                app.downloads_media()
+                    || matches!(
+                        app,
+                        AppType::Sabnzbd | AppType::Transmission | AppType::Qbittorrent
+                    )
+            }
+            "backups" => app.is_servarr_v3(),
+            _ => false,
+        }
+    }
+
+    /// Check the export on its own. Returns one message per problem.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let field = format!("nfs.exports[{}]", self.name);
+        if !super::is_valid_instance(&self.name) {
+            errors.push(format!(
+                "{field}: name must be a lowercase DNS label (a-z, 0-9, '-')"
+            ));
+        }
+        if Self::RESERVED_NAMES.contains(&self.name.as_str()) {
+            errors.push(format!(
+                "{field}: name is used by the media mounts; pick another"
+            ));
+        }
+        for (key, path) in [("path", &self.path), ("mountPath", &self.mount_path)] {
+            if let Some(path) = path
+                && (!path.starts_with('/') || path == "/")
+            {
+                errors.push(format!(
+                    "{field}: {key} '{path}' must be an absolute path below /"
+                ));
+            }
+        }
+        if self.squash == NfsSquash::NoRootSquash
+            && (self.anon_uid.is_some() || self.anon_gid.is_some())
+        {
+            errors.push(format!(
+                "{field}: anonUid and anonGid need squash RootSquash or AllSquash"
+            ));
+        }
+        errors
+    }
 }
 
 impl Default for NfsServerSpec {
@@ -596,6 +1643,8 @@ impl Default for NfsServerSpec {
             tv_4k_path: default_tv_4k_path(),
             external_server: None,
             external_path: default_external_path(),
+            backup: None,
+            exports: Vec::new(),
         }
     }
 }
@@ -623,6 +1672,20 @@ impl NfsServerSpec {
         }
     }
 
+    /// The `media` export, which holds the media paths when present.
+    pub fn media_export(&self) -> Option<&NfsExport> {
+        self.exports.iter().find(|e| e.name == "media")
+    }
+
+    /// Path of a media subpath relative to the share root: inside the
+    /// `media` export when there is one.
+    pub fn media_path(&self, media_subpath: &str) -> String {
+        match self.media_export() {
+            Some(export) => format!("{}{media_subpath}", export.path().trim_end_matches('/')),
+            None => media_subpath.to_string(),
+        }
+    }
+
     /// Compute the NFS server-side path for a given media subpath.
     ///
     /// For in-cluster servers `/nfsshare` is the NFSv4 root (`fsid=0`), so
@@ -672,6 +1735,68 @@ fn default_external_path() -> String {
     "/".to_string()
 }
 
+/// Backups of the in-cluster NFS server's PVC. Either or both methods can be
+/// enabled.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NfsBackupSpec {
+    /// Scheduled CSI VolumeSnapshots of the PVC.
+    #[serde(default)]
+    pub snapshot: Option<NfsSnapshotSpec>,
+
+    /// Scheduled rsync of the export to a remote host over SSH.
+    #[serde(default)]
+    pub rsync: Option<NfsRsyncSpec>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NfsSnapshotSpec {
+    /// Five-field cron expression, as for a CronJob (e.g. "0 4 * * *").
+    pub schedule: String,
+
+    /// VolumeSnapshotClass to use. If omitted, uses the cluster default.
+    #[serde(default)]
+    pub volume_snapshot_class: Option<String>,
+
+    /// Number of snapshots to keep; older ones are deleted.
+    #[serde(default = "default_retention_count")]
+    pub retention_count: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NfsRsyncSpec {
+    /// Five-field cron expression for the backup CronJob.
+    pub schedule: String,
+
+    /// rsync destination as `user@host:/path`.
+    pub destination: String,
+
+    /// SSH port on the destination host.
+    #[serde(default = "default_ssh_port")]
+    pub port: u16,
+
+    /// Name of a Secret with the SSH private key under `ssh-privatekey` and,
+    /// optionally, the destination's host key under `known_hosts`. Without
+    /// `known_hosts` the host key is trusted on first use.
+    pub ssh_secret: String,
+
+    /// Delete files on the destination that no longer exist on the NFS
+    /// server. Off by default so the remote copy also protects against
+    /// accidental deletes.
+    #[serde(default)]
+    pub delete: bool,
+
+    /// Image override for the rsync container.
+    #[serde(default)]
+    pub image: Option<ImageSpec>,
+}
+
+fn default_ssh_port() -> u16 {
+    22
+}
+
 /// Reference to a user-created Kubernetes Secret containing admin credentials.
 ///
 /// The operator reads but never creates or owns this secret. It must have
@@ -683,10 +1808,358 @@ pub struct AdminCredentialsSpec {
     pub secret_name: String,
 }
 
+/// Reference to a Secret created outside the operator, such as one synced
+/// by external-secrets, with the keys that hold each credential in it.
+///
+/// The operator reads but never creates or owns this secret.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialsSecretRef {
+    /// Name of the Kubernetes Secret.
+    pub name: String,
+    /// Key holding the username (default: `username`).
+    #[serde(default = "default_username_key")]
+    pub username_key: String,
+    /// Key holding the password (default: `password`).
+    #[serde(default = "default_password_key")]
+    pub password_key: String,
+    /// Key holding the API key (default: `api-key`).
+    #[serde(default = "default_api_key_key")]
+    pub api_key_key: String,
+}
+
+impl Default for CredentialsSecretRef {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            username_key: default_username_key(),
+            password_key: default_password_key(),
+            api_key_key: default_api_key_key(),
+        }
+    }
+}
+
+fn default_username_key() -> String {
+    "username".into()
+}
+
+/// Key of the API key in `apiKeySecret` and in the Secrets the operator
+/// creates for it.
+pub const DEFAULT_API_KEY_KEY: &str = "api-key";
+
+fn default_api_key_key() -> String {
+    DEFAULT_API_KEY_KEY.into()
+}
+
+/// Web UI authentication for Sonarr, Radarr, Lidarr, and Prowlarr.
+///
+/// The method is set through the apps' `{APP}__AUTH__METHOD` and
+/// `{APP}__AUTH__REQUIRED` env vars, so a new pod starts configured and
+/// never shows the authentication setup screen. The login from
+/// `secretName` is applied through `/api/v3/config/host`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthSpec {
+    /// How users sign in to the web UI (default: Forms).
+    #[serde(default)]
+    pub method: AuthMethod,
+    /// Secret with `username` and `password` keys. Required for Forms and
+    /// Basic; not used with External.
+    #[serde(default)]
+    pub secret_name: Option<String>,
+    /// Let requests from local network addresses in without signing in.
+    #[serde(default)]
+    pub disabled_for_local_addresses: bool,
+}
+
+/// Web UI authentication method of the *arr apps.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub enum AuthMethod {
+    /// A login page.
+    #[default]
+    Forms,
+    /// HTTP basic authentication.
+    Basic,
+    /// Authentication is left to a reverse proxy in front of the app, such
+    /// as an SSO proxy.
+    External,
+}
+
+impl AuthMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Forms => "Forms",
+            Self::Basic => "Basic",
+            Self::External => "External",
+        }
+    }
+
+    /// Whether the method signs users in with the username and password.
+    pub fn needs_credentials(&self) -> bool {
+        !matches!(self, Self::External)
+    }
+}
+
+impl AuthSpec {
+    /// Value for `{APP}__AUTH__REQUIRED`.
+    pub fn required(&self) -> &'static str {
+        if self.disabled_for_local_addresses {
+            "DisabledForLocalAddresses"
+        } else {
+            "Enabled"
+        }
+    }
+}
+
+/// Web UI preferences for Sonarr, Radarr, Lidarr, and Prowlarr, applied
+/// through `/api/v3/config/ui`. Only the fields that are set are managed;
+/// the rest keep whatever the app has.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UiSettingsSpec {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<UiTheme>,
+    /// Day the calendar week starts on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_day_of_week: Option<FirstDayOfWeek>,
+    /// The app's numeric id for the UI language (1 is English).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ui_language: Option<i32>,
+}
+
+impl UiSettingsSpec {
+    /// Fields of the app's `config/ui` resource this spec sets, with the
+    /// values the API expects.
+    pub fn api_fields(&self) -> Vec<(&'static str, serde_json::Value)> {
+        let mut fields = Vec::new();
+        if let Some(theme) = self.theme {
+            fields.push(("theme", theme.as_str().into()));
+        }
+        if let Some(day) = self.first_day_of_week {
+            fields.push(("firstDayOfWeek", (day as i32).into()));
+        }
+        if let Some(language) = self.ui_language {
+            fields.push(("uiLanguage", language.into()));
+        }
+        fields
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+pub enum UiTheme {
+    /// Follow the browser's light or dark preference.
+    Auto,
+    Dark,
+    Light,
+}
+
+impl UiTheme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Dark => "dark",
+            Self::Light => "light",
+        }
+    }
+}
+
+/// First day of the calendar week; the discriminant is the API's value.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+pub enum FirstDayOfWeek {
+    Sunday = 0,
+    Monday = 1,
+}
+
+/// PostgreSQL connection for apps that can use Postgres instead of SQLite.
+///
+/// Only Sonarr, Radarr, Lidarr, and Prowlarr support Postgres; for these the
+/// connection is injected as `APP__POSTGRES__*` env vars. The field is
+/// ignored for every other app type.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseSpec {
+    /// Hostname of the Postgres server.
+    pub host: String,
+
+    /// Postgres port. Defaults to 5432.
+    #[serde(default = "default_postgres_port")]
+    pub port: i32,
+
+    /// Role the app connects as.
+    pub user: String,
+
+    /// Name of a Kubernetes Secret holding the role's password.
+    pub password_secret: String,
+
+    /// Key within `passwordSecret` that holds the password. Defaults to "password".
+    #[serde(default = "default_password_key")]
+    pub password_key: String,
+
+    /// Database for the app's main data.
+    pub main_database: String,
+
+    /// Database for the app's log data. When omitted the app uses its
+    /// built-in default (`{app}-log`).
+    #[serde(default)]
+    pub log_database: Option<String>,
+}
+
+fn default_postgres_port() -> i32 {
+    5432
+}
+
+fn default_password_key() -> String {
+    "password".to_string()
+}
+
+/// Configuration for the shared Postgres instance deployed by the MediaStack operator.
+///
+/// When present and `enabled`, the operator deploys a single Postgres
+/// StatefulSet for the stack, generates a role and password per compatible
+/// app, and wires each of those apps to its own `{role}_main` / `{role}_log`
+/// databases. Apps that set `database` explicitly are left untouched.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PostgresSpec {
+    /// Deploy the shared Postgres instance. Defaults to true.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Size of the PVC backing Postgres. Defaults to "10Gi".
+    #[serde(default = "default_postgres_storage_size")]
+    pub storage_size: String,
+
+    /// Storage class for the Postgres PVC. If omitted, uses the cluster default.
+    #[serde(default)]
+    pub storage_class: Option<String>,
+
+    /// Image override for the Postgres container.
+    #[serde(default)]
+    pub image: Option<ImageSpec>,
+}
+
+impl Default for PostgresSpec {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            storage_size: default_postgres_storage_size(),
+            storage_class: None,
+            image: None,
+        }
+    }
+}
+
+impl PostgresSpec {
+    /// Key in the stack credentials Secret holding the superuser password.
+    pub const SUPERUSER_PASSWORD_KEY: &'static str = "superuser-password";
+
+    /// Name shared by the Postgres StatefulSet, Service, and credentials Secret.
+    pub fn resource_name(stack_name: &str) -> String {
+        format!("{stack_name}-postgres")
+    }
+
+    /// Cluster-local DNS name of the Postgres Service.
+    pub fn host(stack_name: &str, namespace: &str) -> String {
+        format!(
+            "{}.{namespace}.svc.cluster.local",
+            Self::resource_name(stack_name)
+        )
+    }
+
+    /// Postgres role for a stack child app.
+    ///
+    /// Postgres identifiers are case-folded and may not contain `-` unquoted,
+    /// so the child name is lower-cased with dashes replaced by underscores.
+    pub fn role_name(child_name: &str) -> String {
+        child_name.to_lowercase().replace('-', "_")
+    }
+
+    /// Key in the stack credentials Secret holding a role's password.
+    pub fn password_key(role: &str) -> String {
+        format!("app-{role}")
+    }
+}
+
+fn default_postgres_storage_size() -> String {
+    "10Gi".to_string()
+}
+
+/// A post-render patch applied to a resource the operator generates, after
+/// the builders run and before it is applied. The patched object stays
+/// operator-managed, so drift detection compares against the patched form.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourcePatch {
+    /// Which generated resource(s) to patch.
+    pub target: PatchTarget,
+
+    /// How `patch` is interpreted. Defaults to `StrategicMerge`.
+    #[serde(default, rename = "type")]
+    pub patch_type: PatchType,
+
+    /// The patch body as YAML or JSON: a partial object for
+    /// `StrategicMerge`, a list of RFC 6902 operations for `Json6902`.
+    pub patch: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchTarget {
+    /// Kind of the generated resource, e.g. `Deployment`, `Service`,
+    /// `NetworkPolicy`, `ConfigMap`, `PersistentVolumeClaim`, `HTTPRoute`.
+    pub kind: String,
+
+    /// Resource name. When omitted, every generated resource of `kind` is
+    /// patched.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl PatchTarget {
+    pub fn matches(&self, kind: &str, name: &str) -> bool {
+        self.kind == kind && self.name.as_deref().is_none_or(|n| n == name)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub enum PatchType {
+    /// Kubernetes-style merge: maps merge recursively, `null` deletes a key,
+    /// and lists of named items (containers, env, volumes, ports) merge by
+    /// `name`. A list item with `$patch: delete` removes the named item.
+    /// Other lists are replaced.
+    #[default]
+    StrategicMerge,
+    /// RFC 6902 JSON Patch operations (`add`, `remove`, `replace`, `move`,
+    /// `copy`, `test`).
+    Json6902,
+}
+
+/// Raw overrides for settings the schema does not model yet. Only honoured
+/// when the operator runs with `ALLOW_UNSUPPORTED_OVERRIDES=true`; otherwise
+/// the webhook rejects them and the controller ignores them.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsupportedOverrides {
+    /// A partial PodSpec deep-merged into the generated Deployment's pod
+    /// template, using the same merge rules as a `StrategicMerge` patch.
+    /// Top-level fields set here are excluded from drift detection.
+    #[serde(default)]
+    #[schemars(schema_with = "preserved_object_schema")]
+    pub pod_spec: Option<serde_json::Value>,
+}
+
 fn json_object_schema(_gen: &mut SchemaGenerator) -> Schema {
     json_schema!({ "type": "object" })
 }
 
+fn preserved_object_schema(_gen: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "object",
+        "nullable": true,
+        "x-kubernetes-preserve-unknown-fields": true
+    })
+}
+
 fn json_object_array_schema(_gen: &mut SchemaGenerator) -> Schema {
     json_schema!({
         "type": "array",
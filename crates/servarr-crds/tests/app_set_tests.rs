@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+
+use servarr_crds::*;
+
+fn instance(name: &str, params: &[(&str, &str)]) -> AppSetInstance {
+    AppSetInstance {
+        name: name.into(),
+        parameters: params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+    }
+}
+
+fn sonarr_set(instances: Vec<AppSetInstance>) -> ServarrAppSetSpec {
+    ServarrAppSetSpec {
+        template: ServarrAppTemplate {
+            labels: BTreeMap::from([("team".into(), "media".into())]),
+            spec: ServarrAppSpec {
+                app: AppType::Sonarr,
+                env: vec![EnvVar {
+                    name: "LANGUAGE".into(),
+                    value: "$(language)".into(),
+                }],
+                gateway: Some(GatewaySpec {
+                    enabled: true,
+                    hosts: vec!["sonarr-$(instance).example.com".into()],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        },
+        instances,
+    }
+}
+
+#[test]
+fn test_render_substitutes_parameters_per_instance() {
+    let spec = sonarr_set(vec![
+        instance("en", &[("language", "english")]),
+        instance("de", &[("language", "german")]),
+    ]);
+    let apps = spec.render("sonarr").unwrap();
+
+    assert_eq!(apps.len(), 2);
+    assert_eq!(apps[0].name, "sonarr-en");
+    assert_eq!(apps[0].spec.env[0].value, "english");
+    assert_eq!(
+        apps[0].spec.gateway.as_ref().unwrap().hosts,
+        vec!["sonarr-en.example.com"]
+    );
+    assert_eq!(apps[1].name, "sonarr-de");
+    assert_eq!(apps[1].spec.env[0].value, "german");
+}
+
+#[test]
+fn test_render_sets_instance_and_labels() {
+    let spec = sonarr_set(vec![instance("en", &[("language", "english")])]);
+    let app = &spec.render("sonarr").unwrap()[0];
+
+    assert_eq!(app.spec.instance.as_deref(), Some("en"));
+    assert_eq!(app.labels[APP_SET_LABEL], "sonarr");
+    assert_eq!(app.labels[APP_SET_INSTANCE_LABEL], "en");
+    assert_eq!(app.labels["team"], "media");
+}
+
+#[test]
+fn test_render_keeps_explicit_template_instance() {
+    let mut spec = sonarr_set(vec![instance("en", &[("language", "english")])]);
+    spec.template.spec.instance = Some("$(instance)-hd".into());
+    let app = &spec.render("sonarr").unwrap()[0];
+    assert_eq!(app.spec.instance.as_deref(), Some("en-hd"));
+}
+
+#[test]
+fn test_render_rejects_unknown_parameter() {
+    let spec = sonarr_set(vec![instance("en", &[("lang", "english")])]);
+    let err = spec.render("sonarr").unwrap_err();
+    assert!(err.contains("instance 'en'"), "{err}");
+    assert!(err.contains("$(language)"), "{err}");
+}
+
+#[test]
+fn test_validate_instance_names() {
+    let spec = sonarr_set(vec![
+        instance("en", &[]),
+        instance("en", &[]),
+        instance("Bad_Name", &[]),
+    ]);
+    let errors = spec.validate();
+    assert_eq!(errors.len(), 2, "{errors:?}");
+    assert!(errors[0].contains("duplicate name 'en'"));
+    assert!(errors[1].contains("'Bad_Name'"));
+    assert!(spec.render("sonarr").is_err());
+}
+
+#[test]
+fn test_app_set_spec_deserializes_from_yaml() {
+    let yaml = r#"
+template:
+  spec:
+    app: Overseerr
+instances:
+  - name: alice
+  - name: bob
+    parameters:
+      tz: Europe/Berlin
+"#;
+    let spec: ServarrAppSetSpec = serde_yaml::from_str(yaml).unwrap();
+    assert!(spec.template.labels.is_empty());
+    assert_eq!(spec.instances.len(), 2);
+    assert!(spec.instances[0].parameters.is_empty());
+    assert_eq!(spec.instances[1].parameters["tz"], "Europe/Berlin");
+}
@@ -31,6 +31,8 @@ fn test_crd_serde_roundtrip_transmission_with_config() {
             auth: Some(TransmissionAuth {
                 secret_name: "transmission-auth".into(),
             }),
+            credentials_secret_ref: None,
+            directories: None,
         })),
         ..Default::default()
     };
@@ -41,6 +43,93 @@ fn test_crd_serde_roundtrip_transmission_with_config() {
     assert!(deserialized.app_config.is_some());
 }
 
+#[test]
+fn test_qbittorrent_config_from_json() {
+    let spec: ServarrAppSpec = serde_json::from_value(serde_json::json!({
+        "app": "Qbittorrent",
+        "appConfig": {
+            "qbittorrent": {
+                "peerPort": {"port": 6881},
+                "categories": {"tv-sonarr": "/downloads/tv"},
+                "speedLimits": {"download": 10240, "altUpload": 512}
+            }
+        }
+    }))
+    .unwrap();
+    let Some(AppConfig::Qbittorrent(qc)) = spec.app_config else {
+        panic!("expected a qbittorrent appConfig");
+    };
+    assert_eq!(qc.peer_port.as_ref().map(|p| p.port), Some(6881));
+    assert_eq!(qc.categories["tv-sonarr"], "/downloads/tv");
+    assert_eq!(
+        qc.speed_limits.unwrap().settings(),
+        [
+            ("BitTorrent.Session\\GlobalDLSpeedLimit", 10240),
+            ("BitTorrent.Session\\AlternativeGlobalUPSpeedLimit", 512)
+        ]
+    );
+}
+
+#[test]
+fn test_radarr_custom_formats_from_json() {
+    let spec: ServarrAppSpec = serde_json::from_value(serde_json::json!({
+        "app": "Radarr",
+        "appConfig": {
+            "radarr": {
+                "customFormats": [{
+                    "name": "Big",
+                    "specifications": [{
+                        "name": "Size",
+                        "implementation": "SizeSpecification",
+                        "fields": {"min": 40, "max": 400}
+                    }]
+                }],
+                "qualityProfiles": [{
+                    "name": "HD",
+                    "qualities": ["Bluray-1080p"],
+                    "formatScores": {"Big": -500}
+                }]
+            }
+        }
+    }))
+    .unwrap();
+    let Some(AppConfig::Radarr(config)) = spec.app_config else {
+        panic!("expected a radarr appConfig");
+    };
+    let condition = &config.custom_formats[0].specifications[0];
+    assert!(!condition.negate && !condition.required);
+    assert_eq!(condition.fields["max"], 400);
+    assert_eq!(config.quality_profiles[0].format_scores["Big"], -500);
+}
+
+#[test]
+fn test_bazarr_config_from_json() {
+    let spec: ServarrAppSpec = serde_json::from_value(serde_json::json!({
+        "app": "Bazarr",
+        "apiKeySecret": "bazarr-api-key",
+        "bazarrSync": {"enabled": true, "instance": "4k"},
+        "appConfig": {
+            "bazarr": {
+                "providers": ["podnapisi"],
+                "languageProfiles": [
+                    {"name": "English", "languages": ["en"]},
+                    {"name": "Dutch", "languages": ["nl", "en"]}
+                ],
+                "defaultProfile": "Dutch"
+            }
+        }
+    }))
+    .unwrap();
+    let sync = spec.bazarr_sync.unwrap();
+    assert_eq!(sync.instance.as_deref(), Some("4k"));
+    assert!(sync.auto_remove);
+    let Some(AppConfig::Bazarr(config)) = spec.app_config else {
+        panic!("expected a bazarr appConfig");
+    };
+    assert_eq!(config.languages(), ["en", "nl"]);
+    assert!(config.validate().is_empty());
+}
+
 #[test]
 fn test_crd_serde_roundtrip_all_fields() {
     let spec = ServarrAppSpec {
@@ -64,6 +153,7 @@ fn test_crd_serde_roundtrip_all_fields() {
                 container_port: None,
                 host_port: None,
             }],
+            ..Default::default()
         }),
         gateway: Some(GatewaySpec {
             enabled: true,
@@ -75,6 +165,7 @@ fn test_crd_serde_roundtrip_all_fields() {
             }],
             hosts: vec!["radarr.example.com".into()],
             tls: None,
+            backends: vec![],
         }),
         resources: Some(ResourceRequirements {
             limits: ResourceList {
@@ -93,6 +184,8 @@ fn test_crd_serde_roundtrip_all_fields() {
                 access_mode: "ReadWriteOnce".into(),
                 size: "2Gi".into(),
                 storage_class: "fast".into(),
+                migrate_storage: true,
+                deletion_policy: VolumeDeletionPolicy::Delete,
             }],
             nfs_mounts: vec![NfsMount {
                 name: "media".into(),
@@ -101,6 +194,7 @@ fn test_crd_serde_roundtrip_all_fields() {
                 mount_path: "/media".into(),
                 read_only: false,
             }],
+            verify_mounts: false,
         }),
         env: vec![EnvVar {
             name: "TZ".into(),
@@ -110,11 +204,28 @@ fn test_crd_serde_roundtrip_all_fields() {
             liveness: ProbeConfig::default(),
             readiness: ProbeConfig::default(),
         }),
+        lifecycle: None,
+        extra_containers: vec![],
+        extra_init_containers: vec![],
         scheduling: None,
         network_policy: Some(true),
         network_policy_config: None,
         app_config: None,
+        config_file: Default::default(),
+        rclone_mounts: Default::default(),
+        credentials: Default::default(),
+        queue_remediation: None,
+        trash_sync: None,
+        update_policy: None,
+        maintenance_window: None,
+        requeue: None,
+        suspend: false,
+        workload_type: None,
+        auth: None,
+        ui_settings: None,
         api_key_secret: Some("radarr-api-key".into()),
+        api_key_secret_key: None,
+        api_key_rotation: None,
         api_health_check: None,
         backup: None,
         image_pull_secrets: Some(vec!["ghcr-secret".into()]),
@@ -123,9 +234,17 @@ fn test_crd_serde_roundtrip_all_fields() {
             "true".into(),
         )])),
         gpu: None,
+        autoscaling: None,
+        ingress: None,
         prowlarr_sync: None,
         overseerr_sync: None,
+        bazarr_sync: None,
+        download_client_sync: None,
         admin_credentials: None,
+        database: None,
+        drift_ignore_fields: Vec::new(),
+        patches: Vec::new(),
+        unsupported_overrides: None,
     };
 
     let json = serde_json::to_string_pretty(&spec).unwrap();
@@ -136,6 +255,8 @@ fn test_crd_serde_roundtrip_all_fields() {
     assert!(deserialized.persistence.is_some());
     let p = deserialized.persistence.unwrap();
     assert_eq!(p.volumes.len(), 1);
+    assert!(p.volumes[0].migrate_storage);
+    assert_eq!(p.volumes[0].deletion_policy, VolumeDeletionPolicy::Delete);
     assert_eq!(p.nfs_mounts.len(), 1);
 }
 
@@ -145,13 +266,19 @@ fn test_defaults_for_all_app_types() {
         AppType::Sonarr,
         AppType::Radarr,
         AppType::Lidarr,
+        AppType::Readarr,
+        AppType::Whisparr,
         AppType::Prowlarr,
         AppType::Sabnzbd,
         AppType::Transmission,
+        AppType::Qbittorrent,
         AppType::Tautulli,
         AppType::Overseerr,
+        AppType::Jellyseerr,
         AppType::Maintainerr,
         AppType::Jackett,
+        AppType::Bazarr,
+        AppType::Tdarr,
         AppType::Jellyfin,
         AppType::Plex,
     ];
@@ -183,8 +310,11 @@ fn test_linuxserver_apps_have_downloads_pvc() {
         AppType::Sonarr,
         AppType::Radarr,
         AppType::Lidarr,
+        AppType::Readarr,
+        AppType::Whisparr,
         AppType::Sabnzbd,
         AppType::Transmission,
+        AppType::Qbittorrent,
     ];
 
     for app_type in &with_downloads {
@@ -204,7 +334,9 @@ fn test_config_only_apps() {
         AppType::Prowlarr,
         AppType::Tautulli,
         AppType::Overseerr,
+        AppType::Jellyseerr,
         AppType::Jackett,
+        AppType::Bazarr,
         AppType::Maintainerr,
         AppType::Jellyfin,
         AppType::Plex,
@@ -230,6 +362,42 @@ fn test_maintainerr_is_nonroot() {
     ));
 }
 
+#[test]
+fn test_tdarr_defaults() {
+    let server = AppDefaults::for_app(&AppType::Tdarr);
+    let ports: Vec<(&str, i32)> = server
+        .service
+        .ports
+        .iter()
+        .map(|p| (p.name.as_str(), p.port))
+        .collect();
+    assert_eq!(ports, vec![("http", 8265), ("server", TDARR_SERVER_PORT)]);
+    let volumes: Vec<&str> = server
+        .persistence
+        .volumes
+        .iter()
+        .map(|v| v.mount_path.as_str())
+        .collect();
+    assert_eq!(volumes, vec!["/app/server", "/app/configs"]);
+    assert!(
+        server
+            .env
+            .iter()
+            .any(|e| e.name == "internalNode" && e.value == "false")
+    );
+
+    let node = AppDefaults::for_app(&AppType::TdarrNode);
+    assert!(node.persistence.volumes.is_empty());
+    assert!(matches!(node.probes.liveness.probe_type, ProbeType::Exec));
+    assert!(
+        node.probes
+            .liveness
+            .command
+            .join(" ")
+            .contains("[T]darr_Node")
+    );
+}
+
 #[test]
 fn test_transmission_has_app_config() {
     let defaults = AppDefaults::for_app(&AppType::Transmission);
@@ -244,11 +412,23 @@ fn test_app_type_display() {
     assert_eq!(AppType::Sonarr.to_string(), "sonarr");
     assert_eq!(AppType::Radarr.to_string(), "radarr");
     assert_eq!(AppType::Transmission.to_string(), "transmission");
+    assert_eq!(AppType::Qbittorrent.to_string(), "qbittorrent");
     assert_eq!(AppType::Maintainerr.to_string(), "maintainerr");
+    assert_eq!(AppType::Bazarr.to_string(), "bazarr");
+    assert_eq!(AppType::TdarrNode.to_string(), "tdarr-node");
     assert_eq!(AppType::Jellyfin.to_string(), "jellyfin");
     assert_eq!(AppType::Plex.to_string(), "plex");
 }
 
+#[test]
+fn test_app_type_from_str_roundtrips() {
+    for app in AppType::ALL {
+        assert_eq!(app.as_str().parse::<AppType>(), Ok(app.clone()));
+    }
+    assert_eq!("Sonarr".parse::<AppType>(), Ok(AppType::Sonarr));
+    assert!("sickrage".parse::<AppType>().is_err());
+}
+
 #[test]
 fn test_crd_generation() {
     use kube::CustomResourceExt;
@@ -427,6 +607,22 @@ fn test_status_serde() {
             last_transition_time: "2024-01-01T00:00:00Z".into(),
         }],
         backup_status: None,
+        applied_spec_hash: None,
+        reconcile_trigger: None,
+        message: None,
+        generated_resources: None,
+        volume_claims: Default::default(),
+        storage_migrations: vec![],
+        api_key_rotation: None,
+        queue_remediation: None,
+        last_trash_sync: None,
+        image_update: None,
+        sync_status: None,
+        would_change: None,
+        last_error: None,
+        last_drift: None,
+        resource_groups: None,
+        pending_actions: vec![],
     };
 
     let json = serde_json::to_string(&status).unwrap();
@@ -435,3 +631,223 @@ fn test_status_serde() {
     assert_eq!(deserialized.ready_replicas, 1);
     assert_eq!(deserialized.conditions.len(), 1);
 }
+
+#[test]
+fn test_maintenance_window_defaults_and_selector() {
+    let spec: MaintenanceWindowSpec = serde_json::from_value(serde_json::json!({
+        "selector": { "servarr.dev/stack": "media" },
+        "start": "2025-06-01T02:00:00Z",
+        "end": "2025-06-01T04:00:00Z"
+    }))
+    .unwrap();
+    assert_eq!(spec.mode, MaintenanceMode::Suspend);
+    assert_eq!(spec.backup_lead_minutes, 30);
+
+    let stack_app = std::collections::BTreeMap::from([
+        ("servarr.dev/stack".to_string(), "media".to_string()),
+        ("servarr.dev/tier".to_string(), "2".to_string()),
+    ]);
+    assert!(spec.matches(&stack_app));
+    assert!(!spec.matches(&std::collections::BTreeMap::new()));
+
+    let match_all = MaintenanceWindowSpec {
+        selector: Default::default(),
+        ..spec
+    };
+    assert!(match_all.matches(&std::collections::BTreeMap::new()));
+}
+
+#[test]
+fn test_rclone_mount_defaults() {
+    let yaml = r#"
+app: Plex
+rcloneMounts:
+  - name: gdrive
+    remote: "gdrive:media"
+    mountPath: /media
+    configSecret: rclone-config
+"#;
+    let spec: ServarrAppSpec = serde_yaml::from_str(yaml).unwrap();
+    let mount = &spec.rclone_mounts[0];
+    assert_eq!(mount.config_key, "rclone.conf");
+    assert!(mount.read_only);
+    assert!(mount.vfs.cache_mode.is_none());
+    assert!(mount.validate().is_empty());
+
+    // Omitted when empty, so existing objects serialize unchanged.
+    let json = serde_json::to_value(ServarrAppSpec::default()).unwrap();
+    assert!(json.get("rcloneMounts").is_none());
+}
+
+#[test]
+fn test_queue_remediation_defaults() {
+    let yaml = r#"
+app: Sonarr
+queueRemediation:
+  enabled: true
+"#;
+    let spec: ServarrAppSpec = serde_yaml::from_str(yaml).unwrap();
+    let qr = spec.queue_remediation.unwrap();
+    assert_eq!(qr.interval_minutes, 15);
+    assert!(!qr.dry_run);
+    assert_eq!(qr.rules.len(), 4);
+    let stalled = qr.rule_for(RemediationReason::Stalled).unwrap();
+    assert_eq!(stalled.min_age_minutes, 360);
+    assert_eq!(stalled.action, RemediationAction::BlocklistAndSearch);
+    assert!(stalled.remove_from_client);
+
+    // Explicit rules replace the defaults.
+    let yaml = r#"
+app: Radarr
+queueRemediation:
+  enabled: true
+  rules:
+    - reason: WrongFormat
+      action: Remove
+      removeFromClient: false
+"#;
+    let spec: ServarrAppSpec = serde_yaml::from_str(yaml).unwrap();
+    let qr = spec.queue_remediation.unwrap();
+    assert_eq!(qr.rules.len(), 1);
+    assert!(qr.rule_for(RemediationReason::ImportFailed).is_none());
+    let rule = qr.rule_for(RemediationReason::WrongFormat).unwrap();
+    assert_eq!(rule.min_age_minutes, 60);
+    assert_eq!(rule.action, RemediationAction::Remove);
+    assert!(!rule.remove_from_client);
+}
+
+#[test]
+fn test_trash_sync_defaults() {
+    let yaml = r#"
+app: Radarr
+trashSync:
+  enabled: true
+"#;
+    let spec: ServarrAppSpec = serde_yaml::from_str(yaml).unwrap();
+    let trash = spec.trash_sync.unwrap();
+    assert_eq!(trash.schedule, "@daily");
+    assert_eq!(trash.templates, vec!["quality-definitions".to_string()]);
+    assert!(trash.config_map.is_none());
+
+    let yaml = r#"
+app: Sonarr
+trashSync:
+  enabled: true
+  schedule: "0 4 * * 1"
+  templates: []
+  configMap: trash-guides
+"#;
+    let spec: ServarrAppSpec = serde_yaml::from_str(yaml).unwrap();
+    let trash = spec.trash_sync.unwrap();
+    assert!(trash.templates.is_empty());
+    assert_eq!(trash.config_map.as_deref(), Some("trash-guides"));
+}
+
+#[test]
+fn test_servarr_credential_parses() {
+    use kube::CustomResourceExt;
+
+    let yaml = r#"
+secretName: usenet-provider
+type: DownloadClient
+keys: [username, password]
+"#;
+    let spec: ServarrCredentialSpec = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(spec.credential_type, CredentialType::DownloadClient);
+    assert!(spec.shares("password"));
+    assert!(!spec.shares("host"));
+
+    let all: ServarrCredentialSpec = serde_yaml::from_str("secretName: vpn").unwrap();
+    assert_eq!(all.credential_type, CredentialType::Generic);
+    assert!(all.shares("anything"));
+
+    let crd = ServarrCredential::crd();
+    assert_eq!(
+        crd.metadata.name.as_deref(),
+        Some("servarrcredentials.servarr.dev")
+    );
+}
+
+#[test]
+fn test_servarr_policy_parses() {
+    use kube::CustomResourceExt;
+
+    let yaml = r#"
+allowedApps: [Sonarr, Radarr]
+networkPolicy:
+  required: true
+resources:
+  maxMemory: 2Gi
+deniedNfsServers: [nas.example.com]
+"#;
+    let spec: ServarrPolicySpec = serde_yaml::from_str(yaml).unwrap();
+    assert!(spec.allows_app(&AppType::Radarr));
+    assert!(!spec.allows_app(&AppType::Plex));
+    let np = spec.network_policy.unwrap();
+    assert!(np.required);
+    assert!(!np.deny_internet_egress);
+    let resources = spec.resources.unwrap();
+    assert!(!resources.require_limits);
+    assert_eq!(resources.max_memory.as_deref(), Some("2Gi"));
+    assert!(!spec.deny_host_path);
+
+    // An empty policy allows every app type.
+    let empty: ServarrPolicySpec = serde_yaml::from_str("{}").unwrap();
+    assert!(empty.allows_app(&AppType::Plex));
+
+    let crd = ServarrPolicy::crd();
+    assert_eq!(
+        crd.metadata.name.as_deref(),
+        Some("servarrpolicies.servarr.dev")
+    );
+}
+
+#[test]
+fn test_servarr_inventory_defaults() {
+    use kube::CustomResourceExt;
+
+    let spec: ServarrInventorySpec = serde_yaml::from_str("{}").unwrap();
+    assert_eq!(spec.interval_minutes, 360);
+    assert!(spec.includes_namespace("anything"));
+    assert!(spec.feed.url.is_none());
+
+    let yaml = r#"
+intervalMinutes: 60
+namespaces: [media]
+feed:
+  url: https://example.com/versions.json
+  githubRepos:
+    sonarr: Sonarr/Sonarr
+"#;
+    let spec: ServarrInventorySpec = serde_yaml::from_str(yaml).unwrap();
+    assert!(spec.includes_namespace("media"));
+    assert!(!spec.includes_namespace("other"));
+    assert_eq!(spec.feed.github_repos["sonarr"], "Sonarr/Sonarr");
+
+    let crd = ServarrInventory::crd();
+    assert_eq!(
+        crd.metadata.name.as_deref(),
+        Some("servarrinventories.servarr.dev")
+    );
+}
+
+#[test]
+fn test_credential_ref_requires_a_target() {
+    let spec: ServarrAppSpec = serde_yaml::from_str(
+        r#"
+app: Transmission
+credentials:
+  - name: vpn
+    env:
+      - name: OPENVPN_USER
+        key: username
+  - name: usenet
+"#,
+    )
+    .unwrap();
+    assert!(spec.credentials[0].validate().is_empty());
+    assert_eq!(spec.credentials[1].validate().len(), 1);
+
+    let json = serde_json::to_value(ServarrAppSpec::default()).unwrap();
+    assert!(json.get("credentials").is_none());
+}
@@ -162,6 +162,8 @@ fn http_apps_use_http_probes_not_tcp() {
         AppType::Sonarr,
         AppType::Radarr,
         AppType::Lidarr,
+        AppType::Readarr,
+        AppType::Whisparr,
         AppType::Prowlarr,
     ];
 
@@ -223,7 +225,7 @@ fn security_profile_custom_has_custom_type() {
 }
 
 // ---------------------------------------------------------------------------
-// ProwlarrSyncSpec::default and OverseerrSyncSpec::default
+// ProwlarrSyncSpec, OverseerrSyncSpec, and BazarrSyncSpec defaults
 // ---------------------------------------------------------------------------
 
 #[test]
@@ -241,3 +243,33 @@ fn overseerr_sync_spec_default_values() {
     assert!(spec.namespace_scope.is_none());
     assert!(spec.auto_remove);
 }
+
+#[test]
+fn bazarr_sync_spec_default_values() {
+    let spec = BazarrSyncSpec::default();
+    assert!(!spec.enabled);
+    assert!(spec.instance.is_none());
+    assert!(spec.auto_remove);
+}
+
+// ---------------------------------------------------------------------------
+// Architecture-aware images
+// ---------------------------------------------------------------------------
+
+#[test]
+fn image_for_arch_uses_default_on_published_arches() {
+    let defaults = AppDefaults::for_app(&AppType::Sonarr);
+    for arch in ["amd64", "arm64"] {
+        let img = AppDefaults::image_for_arch(&AppType::Sonarr, arch).unwrap();
+        assert_eq!(img.repository, defaults.image.repository);
+        assert_eq!(img.tag, defaults.image.tag);
+    }
+}
+
+#[test]
+fn image_for_arch_refuses_unpublished_arch() {
+    let err = AppDefaults::image_for_arch(&AppType::Plex, "arm").unwrap_err();
+    assert_eq!(err.arch, "arm");
+    assert_eq!(err.supported, vec!["amd64", "arm64"]);
+    assert!(err.to_string().contains("set spec.image"));
+}
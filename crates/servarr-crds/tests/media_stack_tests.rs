@@ -14,11 +14,16 @@ fn test_tier_assignment() {
     assert_eq!(AppType::Sonarr.tier(), 2);
     assert_eq!(AppType::Radarr.tier(), 2);
     assert_eq!(AppType::Lidarr.tier(), 2);
+    assert_eq!(AppType::Readarr.tier(), 2);
+    assert_eq!(AppType::Whisparr.tier(), 2);
     assert_eq!(AppType::Tautulli.tier(), 3);
     assert_eq!(AppType::Overseerr.tier(), 3);
     assert_eq!(AppType::Maintainerr.tier(), 3);
     assert_eq!(AppType::Prowlarr.tier(), 3);
     assert_eq!(AppType::Jackett.tier(), 3);
+    assert_eq!(AppType::Bazarr.tier(), 3);
+    assert_eq!(AppType::Tdarr.tier(), 3);
+    assert_eq!(AppType::TdarrNode.tier(), 3);
 }
 
 #[test]
@@ -54,17 +59,43 @@ fn test_child_name_without_instance() {
         network_policy: None,
         network_policy_config: None,
         app_config: None,
+        config_file: Default::default(),
+        rclone_mounts: Default::default(),
+        credentials: Default::default(),
+        queue_remediation: None,
+        trash_sync: None,
+        update_policy: None,
+        maintenance_window: None,
+        requeue: None,
+        workload_type: None,
+        auth: None,
+        ui_settings: None,
         api_key_secret: None,
+        api_key_secret_key: None,
+        api_key_rotation: None,
         api_health_check: None,
         backup: None,
         image_pull_secrets: None,
         pod_annotations: None,
         gpu: None,
+        autoscaling: None,
+        ingress: None,
         prowlarr_sync: None,
         overseerr_sync: None,
+        bazarr_sync: None,
+        download_client_sync: None,
         admin_credentials: None,
+        database: None,
+        drift_ignore_fields: Vec::new(),
+        patches: Vec::new(),
+        unsupported_overrides: None,
         split4k: None,
         split4k_overrides: None,
+        namespace: None,
+        canary: None,
+        lifecycle: None,
+        extra_containers: vec![],
+        extra_init_containers: vec![],
     };
     assert_eq!(app.child_name("media"), "media-sonarr");
 }
@@ -89,17 +120,43 @@ fn test_child_name_with_instance() {
         network_policy: None,
         network_policy_config: None,
         app_config: None,
+        config_file: Default::default(),
+        rclone_mounts: Default::default(),
+        credentials: Default::default(),
+        queue_remediation: None,
+        trash_sync: None,
+        update_policy: None,
+        maintenance_window: None,
+        requeue: None,
+        workload_type: None,
+        auth: None,
+        ui_settings: None,
         api_key_secret: None,
+        api_key_secret_key: None,
+        api_key_rotation: None,
         api_health_check: None,
         backup: None,
         image_pull_secrets: None,
         pod_annotations: None,
         gpu: None,
+        autoscaling: None,
+        ingress: None,
         prowlarr_sync: None,
         overseerr_sync: None,
+        bazarr_sync: None,
+        download_client_sync: None,
         admin_credentials: None,
+        database: None,
+        drift_ignore_fields: Vec::new(),
+        patches: Vec::new(),
+        unsupported_overrides: None,
         split4k: None,
         split4k_overrides: None,
+        namespace: None,
+        canary: None,
+        lifecycle: None,
+        extra_containers: vec![],
+        extra_init_containers: vec![],
     };
     assert_eq!(app.child_name("stack"), "stack-sonarr-4k");
 }
@@ -127,17 +184,43 @@ fn minimal_stack_app(app: AppType) -> StackApp {
         network_policy: None,
         network_policy_config: None,
         app_config: None,
+        config_file: Default::default(),
+        rclone_mounts: Default::default(),
+        credentials: Default::default(),
+        queue_remediation: None,
+        trash_sync: None,
+        update_policy: None,
+        maintenance_window: None,
+        requeue: None,
+        workload_type: None,
+        auth: None,
+        ui_settings: None,
         api_key_secret: None,
+        api_key_secret_key: None,
+        api_key_rotation: None,
         api_health_check: None,
         backup: None,
         image_pull_secrets: None,
         pod_annotations: None,
         gpu: None,
+        autoscaling: None,
+        ingress: None,
         prowlarr_sync: None,
         overseerr_sync: None,
+        bazarr_sync: None,
+        download_client_sync: None,
         admin_credentials: None,
+        database: None,
+        drift_ignore_fields: Vec::new(),
+        patches: Vec::new(),
+        unsupported_overrides: None,
         split4k: None,
         split4k_overrides: None,
+        namespace: None,
+        canary: None,
+        lifecycle: None,
+        extra_containers: vec![],
+        extra_init_containers: vec![],
     }
 }
 
@@ -192,6 +275,7 @@ fn test_merge_persistence_app_pvc_replaces_stack() {
                 ..Default::default()
             }],
             nfs_mounts: Vec::new(),
+            verify_mounts: false,
         }),
         ..Default::default()
     };
@@ -205,6 +289,7 @@ fn test_merge_persistence_app_pvc_replaces_stack() {
             ..Default::default()
         }],
         nfs_mounts: Vec::new(),
+        verify_mounts: false,
     });
 
     let spec = app.to_servarr_spec(Some(&defaults));
@@ -234,6 +319,7 @@ fn test_merge_persistence_nfs_additive_dedup() {
                     read_only: true,
                 },
             ],
+            verify_mounts: false,
         }),
         ..Default::default()
     };
@@ -248,6 +334,7 @@ fn test_merge_persistence_nfs_additive_dedup() {
             mount_path: "/media".into(),
             read_only: true,
         }],
+        verify_mounts: false,
     });
 
     let spec = app.to_servarr_spec(Some(&defaults));
@@ -310,6 +397,281 @@ fn test_to_servarr_spec_with_overrides() {
     assert_eq!(spec.gid, Some(568)); // stack default
 }
 
+#[test]
+fn test_drift_ignore_fields_merge_defaults_and_app() {
+    let defaults = StackDefaults {
+        drift_ignore_fields: vec!["metadata.annotations['sidecar.istio.io/status']".into()],
+        ..Default::default()
+    };
+    let mut app = minimal_stack_app(AppType::Sonarr);
+    app.drift_ignore_fields = vec![
+        "spec.containers[*].env".into(),
+        "metadata.annotations['sidecar.istio.io/status']".into(),
+    ];
+
+    let spec = app.to_servarr_spec(Some(&defaults));
+    assert_eq!(
+        spec.drift_ignore_fields,
+        vec![
+            "metadata.annotations['sidecar.istio.io/status']",
+            "spec.containers[*].env",
+        ]
+    );
+}
+
+#[test]
+fn test_stack_domain_generates_gateway_hosts() {
+    let defaults = StackDefaults {
+        domain: Some("media.example.com".into()),
+        gateway: Some(GatewaySpec {
+            enabled: true,
+            tls: Some(TlsSpec {
+                enabled: true,
+                cert_issuer: "letsencrypt".into(),
+                secret_name: None,
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let spec = minimal_stack_app(AppType::Sonarr).to_servarr_spec(Some(&defaults));
+    let gateway = spec.gateway.unwrap();
+    assert_eq!(gateway.hosts, vec!["sonarr.media.example.com"]);
+    assert_eq!(gateway.tls.unwrap().cert_issuer, "letsencrypt");
+
+    let mut anime = minimal_stack_app(AppType::Sonarr);
+    anime.instance = Some("anime".into());
+    let spec = anime.to_servarr_spec(Some(&defaults));
+    assert_eq!(
+        spec.gateway.unwrap().hosts,
+        vec!["sonarr-anime.media.example.com"]
+    );
+}
+
+#[test]
+fn test_stack_domain_respects_explicit_hosts_and_disabled_gateway() {
+    let defaults = StackDefaults {
+        domain: Some("media.example.com".into()),
+        gateway: Some(GatewaySpec {
+            enabled: true,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let mut custom = minimal_stack_app(AppType::Overseerr);
+    custom.gateway = Some(GatewaySpec {
+        enabled: true,
+        hosts: vec!["requests.example.com".into()],
+        ..Default::default()
+    });
+    assert_eq!(
+        custom
+            .to_servarr_spec(Some(&defaults))
+            .gateway
+            .unwrap()
+            .hosts,
+        vec!["requests.example.com"]
+    );
+
+    let mut internal = minimal_stack_app(AppType::Sabnzbd);
+    internal.gateway = Some(GatewaySpec::default());
+    assert!(
+        internal
+            .to_servarr_spec(Some(&defaults))
+            .gateway
+            .unwrap()
+            .hosts
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_stack_split4k_gets_own_hostname() {
+    let defaults = StackDefaults {
+        domain: Some("media.example.com".into()),
+        gateway: Some(GatewaySpec {
+            enabled: true,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let mut radarr = minimal_stack_app(AppType::Radarr);
+    radarr.split4k = Some(true);
+
+    let expanded = radarr
+        .expand("media", "default", Some(&defaults), None)
+        .unwrap();
+    let hosts: Vec<_> = expanded
+        .iter()
+        .map(|(_, spec)| spec.gateway.as_ref().unwrap().hosts[0].clone())
+        .collect();
+    assert_eq!(
+        hosts,
+        vec!["radarr.media.example.com", "radarr-4k.media.example.com"]
+    );
+}
+
+#[test]
+fn test_stack_canary_takes_weighted_gateway_traffic() {
+    let defaults = StackDefaults {
+        domain: Some("media.example.com".into()),
+        gateway: Some(GatewaySpec {
+            enabled: true,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let mut sonarr = minimal_stack_app(AppType::Sonarr);
+    sonarr.canary = Some(CanarySpec {
+        image: ImageSpec {
+            repository: "linuxserver/sonarr".into(),
+            tag: "5.0.0".into(),
+            ..Default::default()
+        },
+        weight: 25,
+    });
+
+    let expanded = sonarr
+        .expand("media", "default", Some(&defaults), None)
+        .unwrap();
+    assert_eq!(expanded.len(), 2);
+    let (base_name, base) = &expanded[0];
+    let (canary_name, canary) = &expanded[1];
+    assert_eq!(base_name, "media-sonarr");
+    assert_eq!(canary_name, "media-sonarr-canary");
+    assert_eq!(canary.instance.as_deref(), Some("canary"));
+    assert_eq!(canary.image.as_ref().unwrap().tag, "5.0.0");
+    assert!(canary.gateway.is_none());
+
+    let gw = base.gateway.as_ref().unwrap();
+    assert_eq!(gw.hosts, vec!["sonarr.media.example.com"]);
+    assert_eq!(gw.backends.len(), 1);
+    assert_eq!(gw.backends[0].name, "media-sonarr-canary");
+    assert_eq!(gw.backends[0].weight, 25);
+
+    let spec = MediaStackSpec {
+        apps: vec![sonarr.clone(), {
+            let mut dup = minimal_stack_app(AppType::Sonarr);
+            dup.instance = Some("canary".into());
+            dup
+        }],
+        ..Default::default()
+    };
+    let errors = spec.validate_instances();
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].contains("duplicate app=sonarr instance='canary'"));
+}
+
+#[test]
+fn test_stack_api_keys_name_secrets_for_apps_that_need_them() {
+    let mut defaults = StackDefaults {
+        api_keys: Some(StackApiKeys::default()),
+        ..Default::default()
+    };
+    let mut radarr = minimal_stack_app(AppType::Radarr);
+    radarr.split4k = Some(true);
+    radarr.api_health_check = Some(ApiHealthCheckSpec {
+        enabled: true,
+        ..Default::default()
+    });
+    let secrets: Vec<_> = radarr
+        .expand("media", "default", Some(&defaults), None)
+        .unwrap()
+        .into_iter()
+        .map(|(_, spec)| spec.api_key_secret)
+        .collect();
+    assert_eq!(
+        secrets,
+        vec![
+            Some("media-radarr-api-key".to_string()),
+            Some("media-radarr-4k-api-key".to_string())
+        ]
+    );
+
+    // An explicit name wins, and apps that don't call their API get none
+    // unless allServarrApps is set.
+    radarr.split4k = None;
+    radarr.api_key_secret = Some("radarr-key".into());
+    let spec = &radarr
+        .expand("media", "default", Some(&defaults), None)
+        .unwrap()[0]
+        .1;
+    assert_eq!(spec.api_key_secret.as_deref(), Some("radarr-key"));
+    let sonarr = minimal_stack_app(AppType::Sonarr);
+    let spec = &sonarr
+        .expand("media", "default", Some(&defaults), None)
+        .unwrap()[0]
+        .1;
+    assert_eq!(spec.api_key_secret, None);
+
+    defaults.api_keys = Some(StackApiKeys {
+        secret_name: "{name}-key".into(),
+        all_servarr_apps: true,
+    });
+    let spec = &sonarr
+        .expand("media", "default", Some(&defaults), None)
+        .unwrap()[0]
+        .1;
+    assert_eq!(spec.api_key_secret.as_deref(), Some("media-sonarr-key"));
+    let plex = minimal_stack_app(AppType::Plex);
+    let spec = &plex
+        .expand("media", "default", Some(&defaults), None)
+        .unwrap()[0]
+        .1;
+    assert_eq!(spec.api_key_secret, None);
+}
+
+#[test]
+fn test_validate_instances_allows_distinct_instances() {
+    let mut anime = minimal_stack_app(AppType::Sonarr);
+    anime.instance = Some("anime".into());
+    let mut radarr = minimal_stack_app(AppType::Radarr);
+    radarr.split4k = Some(true);
+    let spec = MediaStackSpec {
+        apps: vec![minimal_stack_app(AppType::Sonarr), anime, radarr],
+        ..Default::default()
+    };
+    assert!(spec.validate_instances().is_empty());
+}
+
+#[test]
+fn test_validate_instances_rejects_duplicates_and_bad_names() {
+    let mut split = minimal_stack_app(AppType::Sonarr);
+    split.split4k = Some(true);
+    let mut explicit_4k = minimal_stack_app(AppType::Sonarr);
+    explicit_4k.instance = Some("4k".into());
+    let mut bad = minimal_stack_app(AppType::Radarr);
+    bad.instance = Some("Anime_HD".into());
+    let spec = MediaStackSpec {
+        apps: vec![
+            split,
+            explicit_4k,
+            minimal_stack_app(AppType::Lidarr),
+            minimal_stack_app(AppType::Lidarr),
+            bad,
+        ],
+        ..Default::default()
+    };
+
+    let errors = spec.validate_instances();
+    assert_eq!(errors.len(), 3, "{errors:?}");
+    assert!(errors[0].starts_with("apps[1]: duplicate app=sonarr instance='4k'"));
+    assert!(errors[1].starts_with("apps[3]: duplicate app=lidarr instance=(default)"));
+    assert!(errors[2].contains("Anime_HD"));
+}
+
+#[test]
+fn test_is_valid_instance() {
+    assert!(is_valid_instance("4k"));
+    assert!(is_valid_instance("anime-1080p"));
+    assert!(!is_valid_instance(""));
+    assert!(!is_valid_instance("-anime"));
+    assert!(!is_valid_instance("Anime"));
+    assert!(!is_valid_instance("anime.hd"));
+}
+
 // ---------------------------------------------------------------------------
 // CRD serde roundtrip
 // ---------------------------------------------------------------------------
@@ -318,6 +680,12 @@ fn test_to_servarr_spec_with_overrides() {
 fn test_media_stack_serde_roundtrip() {
     let spec = MediaStackSpec {
         nfs: None,
+        postgres: None,
+        apply_parallelism: Some(2),
+        spread_policy: Some(SpreadPolicy {
+            topology: SpreadTopology::Zone,
+            required: true,
+        }),
         defaults: Some(StackDefaults {
             uid: Some(568),
             gid: Some(568),
@@ -346,17 +714,43 @@ fn test_media_stack_serde_roundtrip() {
                 network_policy: None,
                 network_policy_config: None,
                 app_config: None,
+                config_file: Default::default(),
+                rclone_mounts: Default::default(),
+                credentials: Default::default(),
+                queue_remediation: None,
+                trash_sync: None,
+                update_policy: None,
+                maintenance_window: None,
+                requeue: None,
+                workload_type: None,
+                auth: None,
+                ui_settings: None,
                 api_key_secret: None,
+                api_key_secret_key: None,
+                api_key_rotation: None,
                 api_health_check: None,
                 backup: None,
                 image_pull_secrets: None,
                 pod_annotations: None,
                 gpu: None,
+                autoscaling: None,
+                ingress: None,
                 prowlarr_sync: None,
                 overseerr_sync: None,
+                bazarr_sync: None,
+                download_client_sync: None,
                 admin_credentials: None,
+                database: None,
+                drift_ignore_fields: Vec::new(),
+                patches: Vec::new(),
+                unsupported_overrides: None,
                 split4k: None,
                 split4k_overrides: None,
+                namespace: None,
+                canary: None,
+                lifecycle: None,
+                extra_containers: vec![],
+                extra_init_containers: vec![],
             },
             StackApp {
                 app: AppType::Sonarr,
@@ -376,17 +770,43 @@ fn test_media_stack_serde_roundtrip() {
                 network_policy: None,
                 network_policy_config: None,
                 app_config: None,
+                config_file: Default::default(),
+                rclone_mounts: Default::default(),
+                credentials: Default::default(),
+                queue_remediation: None,
+                trash_sync: None,
+                update_policy: None,
+                maintenance_window: None,
+                requeue: None,
+                workload_type: None,
+                auth: None,
+                ui_settings: None,
                 api_key_secret: None,
+                api_key_secret_key: None,
+                api_key_rotation: None,
                 api_health_check: None,
                 backup: None,
                 image_pull_secrets: None,
                 pod_annotations: None,
                 gpu: None,
+                autoscaling: None,
+                ingress: None,
                 prowlarr_sync: None,
                 overseerr_sync: None,
+                bazarr_sync: None,
+                download_client_sync: None,
                 admin_credentials: None,
+                database: None,
+                drift_ignore_fields: Vec::new(),
+                patches: Vec::new(),
+                unsupported_overrides: None,
                 split4k: None,
                 split4k_overrides: None,
+                namespace: None,
+                canary: None,
+                lifecycle: None,
+                extra_containers: vec![],
+                extra_init_containers: vec![],
             },
         ],
     };
@@ -397,6 +817,24 @@ fn test_media_stack_serde_roundtrip() {
     assert_eq!(deserialized.apps[0].app, AppType::Jellyfin);
     assert_eq!(deserialized.apps[1].instance.as_deref(), Some("4k"));
     assert_eq!(deserialized.apps[1].uid, Some(1000));
+    assert_eq!(deserialized.apply_parallelism(), 2);
+    assert_eq!(
+        deserialized.spread_policy.unwrap().topology,
+        SpreadTopology::Zone
+    );
+}
+
+#[test]
+fn test_apply_parallelism_defaults_and_floor() {
+    let mut spec = MediaStackSpec::default();
+    assert_eq!(
+        spec.apply_parallelism(),
+        MediaStackSpec::DEFAULT_APPLY_PARALLELISM
+    );
+    spec.apply_parallelism = Some(0);
+    assert_eq!(spec.apply_parallelism(), 1);
+    spec.apply_parallelism = Some(12);
+    assert_eq!(spec.apply_parallelism(), 12);
 }
 
 // ---------------------------------------------------------------------------
@@ -496,6 +934,50 @@ fn test_stack_phase_display() {
     assert_eq!(StackPhase::RollingOut.to_string(), "RollingOut");
     assert_eq!(StackPhase::Ready.to_string(), "Ready");
     assert_eq!(StackPhase::Degraded.to_string(), "Degraded");
+    assert_eq!(StackPhase::Terminating.to_string(), "Terminating");
+}
+
+// ---------------------------------------------------------------------------
+// Teardown ordering
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_teardown_phase_for_app() {
+    assert_eq!(
+        TeardownPhase::for_app(&AppType::Overseerr),
+        TeardownPhase::RequestApps
+    );
+    assert_eq!(
+        TeardownPhase::for_app(&AppType::Bazarr),
+        TeardownPhase::RequestApps
+    );
+    assert_eq!(
+        TeardownPhase::for_app(&AppType::TdarrNode),
+        TeardownPhase::RequestApps
+    );
+    assert_eq!(
+        TeardownPhase::for_app(&AppType::Sonarr),
+        TeardownPhase::MediaManagers
+    );
+    assert_eq!(
+        TeardownPhase::for_app(&AppType::Readarr),
+        TeardownPhase::MediaManagers
+    );
+    assert_eq!(
+        TeardownPhase::for_app(&AppType::Prowlarr),
+        TeardownPhase::Indexers
+    );
+    assert_eq!(
+        TeardownPhase::for_app(&AppType::Transmission),
+        TeardownPhase::DownloadClients
+    );
+    assert_eq!(
+        TeardownPhase::for_app(&AppType::Plex),
+        TeardownPhase::MediaServers
+    );
+    // ALL is in teardown order
+    assert!(TeardownPhase::ALL.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(TeardownPhase::ALL.last(), Some(&TeardownPhase::Storage));
 }
 
 // ---------------------------------------------------------------------------
@@ -822,6 +1304,8 @@ fn test_nfs_server_spec_serde_roundtrip() {
         tv_4k_path: "/media/tv-4k".to_string(),
         external_server: None,
         external_path: "/".to_string(),
+        backup: None,
+        exports: vec![],
     };
     let json = serde_json::to_string(&nfs).unwrap();
     let decoded: NfsServerSpec = serde_json::from_str(&json).unwrap();
@@ -830,6 +1314,30 @@ fn test_nfs_server_spec_serde_roundtrip() {
     assert_eq!(decoded.movies_path, "/media/movies");
 }
 
+#[test]
+fn test_nfs_backup_spec_defaults() {
+    let nfs: NfsServerSpec = serde_yaml::from_str(
+        r#"
+backup:
+  snapshot:
+    schedule: "0 4 * * *"
+  rsync:
+    schedule: "0 5 * * 0"
+    destination: backup@nas.example.com:/backups/media
+    sshSecret: nfs-backup-ssh
+"#,
+    )
+    .unwrap();
+    let backup = nfs.backup.unwrap();
+    let snapshot = backup.snapshot.unwrap();
+    assert_eq!(snapshot.retention_count, 5);
+    assert!(snapshot.volume_snapshot_class.is_none());
+    let rsync = backup.rsync.unwrap();
+    assert_eq!(rsync.port, 22);
+    assert!(!rsync.delete);
+    assert_eq!(rsync.ssh_secret, "nfs-backup-ssh");
+}
+
 #[test]
 fn test_nfs_server_spec_serde_external() {
     let nfs = NfsServerSpec {
@@ -878,6 +1386,68 @@ fn test_nfs_inject_sonarr_gets_tv_mount() {
     assert_eq!(mounts[0].mount_path, "/tv");
 }
 
+#[test]
+fn test_nfs_inject_named_exports() {
+    let nfs = NfsServerSpec {
+        exports: vec![
+            NfsExport {
+                name: "media".into(),
+                ..Default::default()
+            },
+            NfsExport {
+                name: "downloads".into(),
+                size: Some("500Gi".into()),
+                ..Default::default()
+            },
+            NfsExport {
+                name: "backups".into(),
+                path: Some("/archive/servarr".into()),
+                read_only: true,
+                ..Default::default()
+            },
+        ],
+        ..nfs_external()
+    };
+    let mounts_of = |app: AppType| {
+        let result = minimal_stack_app(app)
+            .expand("mystack", "media", None, Some(&nfs))
+            .unwrap();
+        result[0].1.persistence.clone().unwrap().nfs_mounts
+    };
+
+    let mounts = mounts_of(AppType::Sonarr);
+    let paths: Vec<_> = mounts
+        .iter()
+        .map(|m| (m.name.as_str(), m.path.as_str(), m.mount_path.as_str()))
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            ("tv", "/volume1/media/tv", "/tv"),
+            ("downloads", "/volume1/downloads", "/downloads"),
+            ("backups", "/volume1/archive/servarr", "/backups"),
+        ]
+    );
+    assert!(mounts[2].read_only);
+
+    let names: Vec<_> = mounts_of(AppType::Sabnzbd)
+        .into_iter()
+        .map(|m| m.name)
+        .collect();
+    assert!(names.contains(&"downloads".to_string()));
+    assert!(!names.contains(&"backups".to_string()));
+
+    // Apps without media mounts still get the exports listed for them
+    let mut nfs = nfs.clone();
+    nfs.exports[2].apps = vec![AppType::Overseerr];
+    let result = minimal_stack_app(AppType::Overseerr)
+        .expand("mystack", "media", None, Some(&nfs))
+        .unwrap();
+    let mounts = result[0].1.persistence.clone().unwrap().nfs_mounts;
+    assert_eq!(mounts.len(), 1);
+    assert_eq!(mounts[0].name, "backups");
+}
+
 #[test]
 fn test_nfs_inject_radarr_gets_movies_mount() {
     let app = minimal_stack_app(AppType::Radarr);
@@ -930,6 +1500,40 @@ fn test_nfs_inject_sabnzbd_gets_all_mounts() {
     assert_eq!(mounts.len(), 5, "sabnzbd should get all five media mounts");
 }
 
+#[test]
+fn test_tdarr_node_gets_media_mounts_and_stack_server() {
+    let app = minimal_stack_app(AppType::TdarrNode);
+    let nfs = nfs_in_cluster();
+    let result = app.expand("mystack", "media", None, Some(&nfs)).unwrap();
+    let (_, spec) = &result[0];
+    let mounts = &spec.persistence.as_ref().unwrap().nfs_mounts;
+    assert_eq!(
+        mounts.len(),
+        5,
+        "tdarr nodes should get all five media mounts"
+    );
+    assert!(mounts.iter().all(|m| !m.read_only));
+    match spec.app_config {
+        Some(AppConfig::TdarrNode(ref c)) => assert_eq!(c.server(), "mystack-tdarr"),
+        ref other => panic!("expected TdarrNode config, got {other:?}"),
+    }
+
+    // An explicit server is kept
+    let mut app = minimal_stack_app(AppType::TdarrNode);
+    app.app_config = Some(AppConfig::TdarrNode(TdarrNodeConfig {
+        server: Some("remote-tdarr".into()),
+        node_count: Some(3),
+    }));
+    let result = app.expand("mystack", "media", None, None).unwrap();
+    match result[0].1.app_config {
+        Some(AppConfig::TdarrNode(ref c)) => {
+            assert_eq!(c.server(), "remote-tdarr");
+            assert_eq!(c.node_count(), 3);
+        }
+        ref other => panic!("expected TdarrNode config, got {other:?}"),
+    }
+}
+
 #[test]
 fn test_nfs_inject_user_mounts_preserved_by_name() {
     // A user-defined NFS mount with the same name takes precedence.
@@ -943,6 +1547,7 @@ fn test_nfs_inject_user_mounts_preserved_by_name() {
             mount_path: "/tv".to_string(),
             read_only: true,
         }],
+        verify_mounts: false,
     });
     let nfs = nfs_in_cluster();
     let result = app.expand("mystack", "media", None, Some(&nfs)).unwrap();
@@ -1050,6 +1655,7 @@ fn test_nfs_inject_split4k_user_override_via_split4k_overrides() {
                 mount_path: "/tv".to_string(),
                 read_only: false,
             }],
+            verify_mounts: false,
         }),
         ..Default::default()
     });
@@ -1117,6 +1723,58 @@ fn test_media_stack_spec_nfs_field_round_trips() {
     assert_eq!(nfs.storage_class.as_deref(), Some("nfs-fast"));
 }
 
+// ---------------------------------------------------------------------------
+// uiSettings propagation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_ui_settings_defaults_only_reach_servarr_apps() {
+    let defaults = StackDefaults {
+        ui_settings: Some(UiSettingsSpec {
+            theme: Some(UiTheme::Dark),
+            first_day_of_week: Some(FirstDayOfWeek::Monday),
+            ui_language: None,
+        }),
+        ..Default::default()
+    };
+    let spec = minimal_stack_app(AppType::Prowlarr).to_servarr_spec(Some(&defaults));
+    assert_eq!(spec.ui_settings, defaults.ui_settings);
+
+    let spec = minimal_stack_app(AppType::Jellyfin).to_servarr_spec(Some(&defaults));
+    assert!(spec.ui_settings.is_none());
+
+    let mut app = minimal_stack_app(AppType::Sonarr);
+    app.ui_settings = Some(UiSettingsSpec {
+        theme: Some(UiTheme::Light),
+        ..Default::default()
+    });
+    let spec = app.to_servarr_spec(Some(&defaults));
+    let ui = spec.ui_settings.unwrap();
+    assert_eq!(ui.theme, Some(UiTheme::Light));
+    assert_eq!(ui.first_day_of_week, None);
+}
+
+#[test]
+fn test_download_client_sync_defaults_only_reach_downloading_apps() {
+    let defaults = StackDefaults {
+        download_client_sync: Some(DownloadClientSyncSpec {
+            enabled: true,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let spec = minimal_stack_app(AppType::Lidarr).to_servarr_spec(Some(&defaults));
+    assert!(spec.download_client_sync.is_some_and(|s| s.enabled));
+
+    let spec = minimal_stack_app(AppType::Prowlarr).to_servarr_spec(Some(&defaults));
+    assert!(spec.download_client_sync.is_none());
+
+    let mut app = minimal_stack_app(AppType::Radarr);
+    app.download_client_sync = Some(DownloadClientSyncSpec::default());
+    let spec = app.to_servarr_spec(Some(&defaults));
+    assert!(!spec.download_client_sync.unwrap().enabled);
+}
+
 // ---------------------------------------------------------------------------
 // adminCredentials propagation
 // ---------------------------------------------------------------------------
@@ -1214,3 +1872,173 @@ fn test_admin_credentials_serde_roundtrip() {
     let deserialized: AdminCredentialsSpec = serde_json::from_str(&json).unwrap();
     assert_eq!(deserialized.secret_name, "my-admin-secret");
 }
+
+// ---------------------------------------------------------------------------
+// Shared Postgres wiring
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_postgres_inject_wires_supported_app() {
+    let app = minimal_stack_app(AppType::Sonarr);
+    let pg = PostgresSpec::default();
+    let (child, mut spec) = app.expand("media", "ns", None, None).unwrap().remove(0);
+    inject_postgres(&mut spec, &child, Some(&pg), "media", "ns");
+
+    let db = spec.database.expect("sonarr should be wired to postgres");
+    assert_eq!(db.host, "media-postgres.ns.svc.cluster.local");
+    assert_eq!(db.port, 5432);
+    assert_eq!(db.user, "media_sonarr");
+    assert_eq!(db.password_secret, "media-postgres");
+    assert_eq!(db.password_key, "app-media_sonarr");
+    assert_eq!(db.main_database, "media_sonarr_main");
+    assert_eq!(db.log_database.as_deref(), Some("media_sonarr_log"));
+}
+
+#[test]
+fn test_postgres_inject_skips_unsupported_app() {
+    let app = minimal_stack_app(AppType::Sabnzbd);
+    let pg = PostgresSpec::default();
+    let (child, mut spec) = app.expand("media", "ns", None, None).unwrap().remove(0);
+    inject_postgres(&mut spec, &child, Some(&pg), "media", "ns");
+    assert!(spec.database.is_none());
+}
+
+#[test]
+fn test_postgres_inject_disabled_or_absent_is_noop() {
+    let app = minimal_stack_app(AppType::Radarr);
+    let (child, mut spec) = app.expand("media", "ns", None, None).unwrap().remove(0);
+    inject_postgres(&mut spec, &child, None, "media", "ns");
+    assert!(spec.database.is_none());
+
+    let pg = PostgresSpec {
+        enabled: false,
+        ..Default::default()
+    };
+    inject_postgres(&mut spec, &child, Some(&pg), "media", "ns");
+    assert!(spec.database.is_none());
+}
+
+#[test]
+fn test_postgres_inject_keeps_explicit_database() {
+    let mut app = minimal_stack_app(AppType::Prowlarr);
+    app.database = Some(DatabaseSpec {
+        host: "db.example.com".into(),
+        port: 5433,
+        user: "prowlarr".into(),
+        password_secret: "external-db".into(),
+        password_key: "password".into(),
+        main_database: "prowlarr".into(),
+        log_database: None,
+    });
+    let pg = PostgresSpec::default();
+    let (child, mut spec) = app.expand("media", "ns", None, None).unwrap().remove(0);
+    inject_postgres(&mut spec, &child, Some(&pg), "media", "ns");
+    assert_eq!(spec.database.unwrap().host, "db.example.com");
+}
+
+#[test]
+fn test_postgres_inject_split4k_gets_distinct_role() {
+    let mut app = minimal_stack_app(AppType::Radarr);
+    app.split4k = Some(true);
+    let pg = PostgresSpec::default();
+    let users: Vec<String> = app
+        .expand("media", "ns", None, None)
+        .unwrap()
+        .into_iter()
+        .map(|(child, mut spec)| {
+            inject_postgres(&mut spec, &child, Some(&pg), "media", "ns");
+            spec.database.unwrap().user
+        })
+        .collect();
+    assert_eq!(users, vec!["media_radarr", "media_radarr_4k"]);
+}
+
+#[test]
+fn test_media_stack_spec_postgres_defaults() {
+    let json = r#"{"postgres": {}, "apps": [{"app": "Sonarr"}]}"#;
+    let spec: MediaStackSpec = serde_json::from_str(json).unwrap();
+    let pg = spec.postgres.unwrap();
+    assert!(pg.enabled);
+    assert_eq!(pg.storage_size, "10Gi");
+    assert!(pg.storage_class.is_none());
+}
+
+// ---------------------------------------------------------------------------
+// Spread policy
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_spread_policy_constraint() {
+    let spread: SpreadPolicy = serde_json::from_str("{}").unwrap();
+    let c = spread.constraint("media");
+    assert_eq!(c["topologyKey"], "kubernetes.io/hostname");
+    assert_eq!(c["whenUnsatisfiable"], "ScheduleAnyway");
+    assert_eq!(c["maxSkew"], 1);
+    assert_eq!(
+        c["labelSelector"]["matchLabels"]["servarr.dev/stack"],
+        "media"
+    );
+
+    let spread: SpreadPolicy =
+        serde_json::from_str(r#"{"topology": "Zone", "required": true}"#).unwrap();
+    let c = spread.constraint("media");
+    assert_eq!(c["topologyKey"], "topology.kubernetes.io/zone");
+    assert_eq!(c["whenUnsatisfiable"], "DoNotSchedule");
+}
+
+#[test]
+fn test_spread_inject_keeps_app_scheduling() {
+    let mut app = minimal_stack_app(AppType::Sabnzbd);
+    app.scheduling = Some(NodeScheduling {
+        node_selector: [("disk".to_string(), "ssd".to_string())].into(),
+        ..Default::default()
+    });
+    let (_, mut spec) = app.expand("media", "ns", None, None).unwrap().remove(0);
+    inject_spread(&mut spec, None, "media");
+    assert!(
+        spec.scheduling
+            .as_ref()
+            .unwrap()
+            .topology_spread_constraints
+            .is_empty()
+    );
+
+    inject_spread(&mut spec, Some(&SpreadPolicy::default()), "media");
+    let scheduling = spec.scheduling.unwrap();
+    assert_eq!(scheduling.node_selector["disk"], "ssd");
+    assert_eq!(scheduling.topology_spread_constraints.len(), 1);
+}
+
+#[test]
+fn test_spread_inject_defers_to_app_constraint_on_same_key() {
+    let own = serde_json::json!({
+        "maxSkew": 2,
+        "topologyKey": "kubernetes.io/hostname",
+        "whenUnsatisfiable": "DoNotSchedule",
+        "labelSelector": { "matchLabels": { "app.kubernetes.io/name": "plex" } }
+    });
+    let mut app = minimal_stack_app(AppType::Plex);
+    app.scheduling = Some(NodeScheduling {
+        topology_spread_constraints: vec![own.clone()],
+        ..Default::default()
+    });
+    let (_, mut spec) = app.expand("media", "ns", None, None).unwrap().remove(0);
+    inject_spread(&mut spec, Some(&SpreadPolicy::default()), "media");
+    assert_eq!(
+        spec.scheduling
+            .as_ref()
+            .unwrap()
+            .topology_spread_constraints,
+        vec![own]
+    );
+
+    let zone = SpreadPolicy {
+        topology: SpreadTopology::Zone,
+        required: false,
+    };
+    inject_spread(&mut spec, Some(&zone), "media");
+    assert_eq!(
+        spec.scheduling.unwrap().topology_spread_constraints.len(),
+        2
+    );
+}
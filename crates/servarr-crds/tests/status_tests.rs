@@ -1,4 +1,5 @@
 use servarr_crds::*;
+use std::collections::BTreeMap;
 
 // ---------------------------------------------------------------------------
 // Condition::ok()
@@ -209,7 +210,87 @@ fn servarr_app_status_serde_roundtrip_with_conditions_and_backup() {
             last_backup_time: Some("2025-06-01T03:00:00Z".into()),
             last_backup_result: Some("Success".into()),
             backup_count: 7,
+            snapshots: vec![],
+            object_storage: Some(ObjectStorageBackupStatus {
+                location: "s3://backups/media/sonarr".into(),
+                deletion_policy: BackupDeletionPolicy::Delete,
+                last_upload_time: Some("2025-06-01T03:01:00Z".into()),
+                last_upload_result: Some("success".into()),
+            }),
         }),
+        applied_spec_hash: Some("abc123".into()),
+        reconcile_trigger: Some("2025-06-01T12:00:00Z".into()),
+        message: Some("sonarr is ready at http://sonarr.media.svc:8989.".into()),
+        generated_resources: Some(GeneratedResources {
+            deployment: Some("sonarr".into()),
+            persistent_volume_claims: vec!["sonarr-config".into()],
+            certificate_secret: Some("sonarr-tls".into()),
+            ..Default::default()
+        }),
+        volume_claims: BTreeMap::from([("config".into(), "sonarr-config-fast".into())]),
+        storage_migrations: vec![StorageMigrationStatus {
+            volume: "config".into(),
+            source_claim: "sonarr-config-fast".into(),
+            target_claim: "sonarr-config-faster".into(),
+            storage_class: "faster".into(),
+            phase: StorageMigrationPhase::Copying,
+            message: None,
+        }],
+        api_key_rotation: None,
+        queue_remediation: Some(QueueRemediationStatus {
+            last_check_time: Some("2025-06-01T12:00:00Z".into()),
+            last_check_result: Some("1 item(s) remediated".into()),
+            remediated: BTreeMap::from([("Stalled".into(), 3)]),
+            recent: vec![RemediatedItem {
+                title: "Show.S01E01.1080p".into(),
+                reason: "Stalled".into(),
+                action: "BlocklistAndSearch".into(),
+                time: "2025-06-01T12:00:00Z".into(),
+                dry_run: false,
+            }],
+        }),
+        last_trash_sync: Some(TrashSyncStatus {
+            time: Some("2025-06-01T12:00:00Z".into()),
+            result: Some("created: x265 (HD)".into()),
+            succeeded: true,
+            custom_formats_changed: 1,
+            quality_definitions_changed: 14,
+        }),
+        image_update: Some(ImageUpdateStatus {
+            tag: Some("4.0.17".into()),
+            last_check: Some("2025-06-01T04:00:00Z".into()),
+            result: Some("Upgraded from 4.0.16 to 4.0.17".into()),
+            history: vec![ImageUpgrade {
+                from: "4.0.16".into(),
+                to: "4.0.17".into(),
+                time: "2025-06-01T04:00:00Z".into(),
+            }],
+        }),
+        sync_status: Some(SyncStatus {
+            last_sync_time: Some("2025-06-01T12:00:00Z".into()),
+            apps_synced: 3,
+            apps_removed: 1,
+            last_error: Some("add lidarr: connection refused".into()),
+        }),
+        would_change: Some(vec!["create Service/sonarr".into()]),
+        last_error: Some(ReconcileError {
+            category: ErrorCategory::SecretMissing,
+            message: "Secret sonarr-admin does not exist".into(),
+            time: "2025-06-01T12:00:00Z".into(),
+        }),
+        last_drift: Some(DriftReport {
+            kind: "Deployment".into(),
+            paths: vec!["spec.containers[0].image".into()],
+            total_paths: 1,
+            time: "2025-06-01T12:00:00Z".into(),
+        }),
+        resource_groups: Some(ResourceGroupHashes {
+            workload: Some("a1b2".into()),
+            external_sync: Some("c3d4".into()),
+            full_sync_time: Some("2025-06-01T12:00:00Z".into()),
+            ..Default::default()
+        }),
+        pending_actions: vec![],
     };
 
     let json = serde_json::to_string(&status).unwrap();
@@ -218,6 +299,20 @@ fn servarr_app_status_serde_roundtrip_with_conditions_and_backup() {
     assert!(deserialized.ready);
     assert_eq!(deserialized.ready_replicas, 2);
     assert_eq!(deserialized.observed_generation, 42);
+    let remediation = deserialized.queue_remediation.as_ref().unwrap();
+    assert_eq!(remediation.remediated["Stalled"], 3);
+    assert_eq!(remediation.recent[0].action, "BlocklistAndSearch");
+    assert_eq!(deserialized.sync_status, status.sync_status);
+    assert_eq!(deserialized.last_trash_sync, status.last_trash_sync);
+    assert_eq!(deserialized.image_update, status.image_update);
+    assert!(json.contains(r#""qualityDefinitionsChanged":14"#), "{json}");
+    assert_eq!(deserialized.last_error, status.last_error);
+    assert!(json.contains(r#""category":"SecretMissing""#), "{json}");
+    assert_eq!(deserialized.last_drift, status.last_drift);
+    assert!(json.contains(r#""totalPaths":1"#), "{json}");
+    assert_eq!(deserialized.resource_groups, status.resource_groups);
+    assert!(json.contains(r#""externalSync":"c3d4""#), "{json}");
+    assert!(json.contains(r#""syncStatus":{"lastSyncTime""#), "{json}");
     assert_eq!(deserialized.conditions.len(), 2);
 
     assert_eq!(deserialized.conditions[0].condition_type, "Ready");
@@ -232,6 +327,33 @@ fn servarr_app_status_serde_roundtrip_with_conditions_and_backup() {
     );
     assert_eq!(backup.last_backup_result.as_deref(), Some("Success"));
     assert_eq!(backup.backup_count, 7);
+    let upload = backup.object_storage.expect("objectStorage missing");
+    assert_eq!(upload.location, "s3://backups/media/sonarr");
+    assert_eq!(upload.deletion_policy, BackupDeletionPolicy::Delete);
+
+    assert_eq!(deserialized.applied_spec_hash.as_deref(), Some("abc123"));
+    assert_eq!(
+        deserialized.reconcile_trigger.as_deref(),
+        Some("2025-06-01T12:00:00Z")
+    );
+    assert_eq!(
+        deserialized.message.as_deref(),
+        Some("sonarr is ready at http://sonarr.media.svc:8989.")
+    );
+    let generated = deserialized.generated_resources.unwrap();
+    assert_eq!(generated.persistent_volume_claims, vec!["sonarr-config"]);
+    assert_eq!(generated.certificate_secret.as_deref(), Some("sonarr-tls"));
+
+    assert_eq!(deserialized.volume_claims["config"], "sonarr-config-fast");
+    assert_eq!(deserialized.storage_migrations.len(), 1);
+    assert_eq!(
+        deserialized.storage_migrations[0].phase,
+        StorageMigrationPhase::Copying
+    );
+    assert_eq!(
+        deserialized.would_change,
+        Some(vec!["create Service/sonarr".to_string()])
+    );
 }
 
 #[test]
@@ -247,13 +369,34 @@ fn servarr_app_status_serde_roundtrip_camel_case_keys() {
             "2025-06-01T00:00:00Z",
         )],
         backup_status: None,
+        applied_spec_hash: None,
+        reconcile_trigger: None,
+        message: None,
+        generated_resources: None,
+        volume_claims: BTreeMap::new(),
+        storage_migrations: vec![],
+        api_key_rotation: None,
+        queue_remediation: None,
+        last_trash_sync: None,
+        image_update: None,
+        sync_status: None,
+        would_change: None,
+        last_error: None,
+        last_drift: None,
+        resource_groups: None,
+        pending_actions: vec![],
     };
 
     let json = serde_json::to_string(&status).unwrap();
     // Verify camelCase serialization
+    assert!(!json.contains("volumeClaims"));
+    assert!(!json.contains("storageMigrations"));
+    assert!(!json.contains("wouldChange"));
+    assert!(!json.contains("resourceGroups"));
     assert!(json.contains("readyReplicas"));
     assert!(json.contains("observedGeneration"));
-    assert!(json.contains("conditionType"));
+    assert!(json.contains(r#""type":"Progressing""#));
+    assert!(!json.contains("conditionType"));
     assert!(json.contains("lastTransitionTime"));
     assert!(json.contains("backupStatus"));
 }
@@ -268,12 +411,21 @@ fn condition_types_constants_are_correct() {
     assert_eq!(condition_types::DEPLOYMENT_READY, "DeploymentReady");
     assert_eq!(condition_types::SERVICE_READY, "ServiceReady");
     assert_eq!(condition_types::NETWORK_POLICY_READY, "NetworkPolicyReady");
+    assert_eq!(
+        condition_types::NETWORK_POLICY_ENFORCED,
+        "NetworkPolicyEnforced"
+    );
     assert_eq!(condition_types::ROUTE_READY, "RouteReady");
     assert_eq!(condition_types::PVC_READY, "PvcReady");
     assert_eq!(condition_types::PROGRESSING, "Progressing");
+    assert_eq!(condition_types::RECONCILING, "Reconciling");
+    assert_eq!(condition_types::STALLED, "Stalled");
     assert_eq!(condition_types::DEGRADED, "Degraded");
     assert_eq!(condition_types::APP_HEALTHY, "AppHealthy");
     assert_eq!(condition_types::UPDATE_AVAILABLE, "UpdateAvailable");
+    assert_eq!(condition_types::STORAGE_BOUND, "StorageBound");
+    assert_eq!(condition_types::ROUTE_ACCEPTED, "RouteAccepted");
+    assert_eq!(condition_types::CERTIFICATE_READY, "CertificateReady");
 }
 
 // ---------------------------------------------------------------------------
@@ -323,3 +475,13 @@ fn default_servarr_app_status_is_not_ready() {
     assert!(status.conditions.is_empty());
     assert!(status.backup_status.is_none());
 }
+
+#[test]
+fn condition_reads_legacy_condition_type_key() {
+    let cond: Condition = serde_json::from_value(serde_json::json!({
+        "conditionType": "Ready",
+        "status": "True",
+    }))
+    .unwrap();
+    assert_eq!(cond.condition_type, "Ready");
+}
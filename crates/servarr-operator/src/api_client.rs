@@ -0,0 +1,424 @@
+//! Kubernetes API client used by the operator and CLI. Wraps kube's default
+//! HTTP stack so that a slow or overloaded API server fails requests instead
+//! of hanging reconciles:
+//!
+//! - every request attempt is bounded by a timeout until the response
+//!   headers arrive (watches stream their body afterwards, so they aren't cut
+//!   off);
+//! - 429 responses, and 5xx responses and timeouts of reads, are retried
+//!   with jittered exponential backoff, honouring `Retry-After`;
+//! - failures, retries, and recoveries are logged and counted in the
+//!   `servarr_operator_kube_api_*` metrics.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use http::{Method, Request, Response, StatusCode};
+use kube::client::{Body, ClientBuilder};
+use kube::{Client, Config};
+use tower::buffer::BufferLayer;
+use tower::retry::{Policy, RetryLayer};
+use tower::timeout::TimeoutLayer;
+use tower::timeout::error::Elapsed;
+use tower::{BoxError, Layer, Service, ServiceBuilder};
+use tracing::{info, warn};
+
+use crate::metrics::{
+    increment_kube_api_errors, increment_kube_api_reconnects, increment_kube_api_retries,
+};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const MIN_BACKOFF: Duration = Duration::from_millis(250);
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Timeout and retry settings for API server requests.
+#[derive(Clone, Debug)]
+pub struct ApiClientSettings {
+    /// Time allowed for each attempt to get a response.
+    pub timeout: Duration,
+    /// Retries after the first attempt.
+    pub max_retries: u32,
+}
+
+impl Default for ApiClientSettings {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            max_retries: DEFAULT_MAX_RETRIES,
+        }
+    }
+}
+
+impl ApiClientSettings {
+    /// Read `KUBE_API_TIMEOUT_SECONDS` (default 30) and `KUBE_API_MAX_RETRIES`
+    /// (default 3).
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let timeout = std::env::var("KUBE_API_TIMEOUT_SECONDS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .filter(|&secs| secs > 0)
+            .map_or(defaults.timeout, Duration::from_secs);
+        let max_retries = std::env::var("KUBE_API_MAX_RETRIES")
+            .ok()
+            .and_then(|v| v.trim().parse::<u32>().ok())
+            .unwrap_or(defaults.max_retries);
+        Self {
+            timeout,
+            max_retries,
+        }
+    }
+}
+
+/// Build a client for `config` with timeouts, retries, and API metrics.
+pub fn build(config: Config, settings: &ApiClientSettings) -> Result<Client, kube::Error> {
+    let layer = ServiceBuilder::new()
+        .layer(ApiMetricsLayer::default())
+        .layer(RetryLayer::new(RetryPolicy::new(settings.max_retries)))
+        .layer(BufferLayer::new(1024))
+        .layer(TimeoutLayer::new(settings.timeout))
+        .into_inner();
+    Ok(ClientBuilder::try_from(config)?.with_layer(&layer).build())
+}
+
+/// Throttled requests were rejected before being handled, so any method can
+/// be repeated. A 5xx to a write may come after the write was applied (a
+/// 504 in particular), so only reads are retried on those.
+fn is_retryable(method: &Method, status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || (method == Method::GET
+            && status.is_server_error()
+            && status != StatusCode::NOT_IMPLEMENTED)
+}
+
+/// Seconds from a `Retry-After` header, which the API server sends with 429s.
+fn retry_after(response_headers: &http::HeaderMap) -> Option<Duration> {
+    response_headers
+        .get(http::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Delay before retry number `attempt` (1-based): doubling from
+/// `MIN_BACKOFF`, capped at `MAX_BACKOFF`, with up to 50% jitter removed so
+/// that controllers throttled together don't retry in lockstep.
+fn backoff(attempt: u32) -> Duration {
+    let base = MIN_BACKOFF
+        .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+        .min(MAX_BACKOFF);
+    base.mul_f64(rand::random_range(0.5..=1.0))
+}
+
+#[derive(Clone)]
+struct RetryPolicy {
+    attempt: u32,
+    max_retries: u32,
+}
+
+impl RetryPolicy {
+    fn new(max_retries: u32) -> Self {
+        Self {
+            attempt: 0,
+            max_retries,
+        }
+    }
+}
+
+impl<Res> Policy<Request<Body>, Response<Res>, BoxError> for RetryPolicy {
+    type Future = tokio::time::Sleep;
+
+    fn retry(
+        &mut self,
+        req: &mut Request<Body>,
+        result: &mut Result<Response<Res>, BoxError>,
+    ) -> Option<Self::Future> {
+        if self.attempt >= self.max_retries {
+            return None;
+        }
+        let (reason, delay) = match result {
+            Ok(response) if is_retryable(req.method(), response.status()) => {
+                let reason = if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                    "throttled"
+                } else {
+                    "server_error"
+                };
+                let delay = retry_after(response.headers())
+                    .map_or_else(|| backoff(self.attempt + 1), |d| d.min(MAX_BACKOFF));
+                (reason, delay)
+            }
+            // Reads are safe to repeat; a timed-out write may have been applied.
+            Err(e) if e.is::<Elapsed>() && req.method() == Method::GET => {
+                ("timeout", backoff(self.attempt + 1))
+            }
+            _ => return None,
+        };
+        self.attempt += 1;
+        increment_kube_api_retries(reason);
+        warn!(
+            method = %req.method(),
+            path = %req.uri().path(),
+            %reason,
+            attempt = self.attempt,
+            delay_ms = delay.as_millis() as u64,
+            "retrying Kubernetes API request"
+        );
+        Some(tokio::time::sleep(delay))
+    }
+
+    fn clone_request(&mut self, req: &Request<Body>) -> Option<Request<Body>> {
+        // Streaming bodies can't be replayed; such requests are sent once.
+        let body = req.body().try_clone()?;
+        let mut clone = Request::builder()
+            .method(req.method().clone())
+            .uri(req.uri().clone())
+            .version(req.version())
+            .body(body)
+            .ok()?;
+        *clone.headers_mut() = req.headers().clone();
+        *clone.extensions_mut() = req.extensions().clone();
+        Some(clone)
+    }
+}
+
+/// Counts requests that still fail after retries and logs when the API
+/// server becomes reachable again after timeouts or connection errors.
+#[derive(Clone, Default)]
+struct ApiMetricsLayer {
+    unreachable: Arc<AtomicBool>,
+}
+
+impl<S> Layer<S> for ApiMetricsLayer {
+    type Service = ApiMetrics<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ApiMetrics {
+            inner,
+            unreachable: self.unreachable.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct ApiMetrics<S> {
+    inner: S,
+    unreachable: Arc<AtomicBool>,
+}
+
+impl<S, B> Service<Request<Body>> for ApiMetrics<S>
+where
+    S: Service<Request<Body>, Response = Response<B>, Error = BoxError>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<B>;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<Response<B>, BoxError>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let method = req.method().clone();
+        let path = req.uri().path().to_string();
+        let unreachable = self.unreachable.clone();
+        let fut = self.inner.call(req);
+        Box::pin(async move {
+            let result = fut.await;
+            match &result {
+                Ok(response) => {
+                    if unreachable.swap(false, Ordering::Relaxed) {
+                        increment_kube_api_reconnects();
+                        info!("Kubernetes API server reachable again");
+                    }
+                    let status = response.status();
+                    if status == StatusCode::TOO_MANY_REQUESTS {
+                        increment_kube_api_errors("throttled");
+                    } else if status.is_server_error() {
+                        increment_kube_api_errors("server_error");
+                    }
+                }
+                Err(e) => {
+                    let kind = if e.is::<Elapsed>() {
+                        "timeout"
+                    } else {
+                        "connection"
+                    };
+                    unreachable.store(true, Ordering::Relaxed);
+                    increment_kube_api_errors(kind);
+                    warn!(%method, %path, %kind, error = %e, "Kubernetes API request failed");
+                }
+            }
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::{KUBE_API_ERRORS_TOTAL, KUBE_API_RETRIES_TOTAL};
+    use k8s_openapi::api::core::v1::ConfigMap;
+    use kube::Api;
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn config(server_uri: &str) -> Config {
+        let mut config = Config::new(server_uri.parse().unwrap());
+        config.default_namespace = "test".into();
+        config
+    }
+
+    fn config_map() -> serde_json::Value {
+        json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "metadata": { "name": "cm", "namespace": "test" }
+        })
+    }
+
+    #[test]
+    fn retries_throttling_and_server_errors() {
+        let get = &Method::GET;
+        assert!(is_retryable(get, StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable(get, StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_retryable(get, StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!is_retryable(get, StatusCode::NOT_IMPLEMENTED));
+        assert!(!is_retryable(get, StatusCode::CONFLICT));
+        assert!(!is_retryable(get, StatusCode::NOT_FOUND));
+
+        for write in [Method::POST, Method::PUT, Method::PATCH, Method::DELETE] {
+            assert!(is_retryable(&write, StatusCode::TOO_MANY_REQUESTS));
+            assert!(!is_retryable(&write, StatusCode::GATEWAY_TIMEOUT));
+            assert!(!is_retryable(&write, StatusCode::INTERNAL_SERVER_ERROR));
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_cap() {
+        for attempt in 1..=10 {
+            let expected = MIN_BACKOFF
+                .saturating_mul(2u32.pow(attempt - 1))
+                .min(MAX_BACKOFF);
+            let delay = backoff(attempt);
+            assert!(delay <= expected && delay >= expected / 2, "{delay:?}");
+        }
+    }
+
+    #[test]
+    fn retry_after_reads_seconds() {
+        let mut headers = http::HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(http::header::RETRY_AFTER, "2".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn settings_from_env() {
+        temp_env::with_vars(
+            [
+                ("KUBE_API_TIMEOUT_SECONDS", Some("5")),
+                ("KUBE_API_MAX_RETRIES", Some("0")),
+            ],
+            || {
+                let settings = ApiClientSettings::from_env();
+                assert_eq!(settings.timeout, Duration::from_secs(5));
+                assert_eq!(settings.max_retries, 0);
+            },
+        );
+        temp_env::with_vars(
+            [
+                ("KUBE_API_TIMEOUT_SECONDS", Some("0")),
+                ("KUBE_API_MAX_RETRIES", None::<&str>),
+            ],
+            || {
+                let settings = ApiClientSettings::from_env();
+                assert_eq!(settings.timeout, DEFAULT_TIMEOUT);
+                assert_eq!(settings.max_retries, DEFAULT_MAX_RETRIES);
+            },
+        );
+    }
+
+    #[tokio::test]
+    async fn throttled_request_is_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/namespaces/test/configmaps/cm"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/namespaces/test/configmaps/cm"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(config_map()))
+            .mount(&server)
+            .await;
+
+        let before = KUBE_API_RETRIES_TOTAL
+            .with_label_values(&["throttled"])
+            .get();
+        let client = build(config(&server.uri()), &ApiClientSettings::default()).unwrap();
+        let cm = Api::<ConfigMap>::namespaced(client, "test")
+            .get("cm")
+            .await
+            .unwrap();
+        assert_eq!(cm.metadata.name.as_deref(), Some("cm"));
+        assert!(
+            KUBE_API_RETRIES_TOTAL
+                .with_label_values(&["throttled"])
+                .get()
+                > before
+        );
+    }
+
+    #[tokio::test]
+    async fn write_is_not_retried_on_gateway_timeout() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/namespaces/test/configmaps"))
+            .respond_with(ResponseTemplate::new(504))
+            .mount(&server)
+            .await;
+
+        let client = build(config(&server.uri()), &ApiClientSettings::default()).unwrap();
+        let cm: ConfigMap = serde_json::from_value(config_map()).unwrap();
+        let result = Api::<ConfigMap>::namespaced(client, "test")
+            .create(&Default::default(), &cm)
+            .await;
+        assert!(result.is_err());
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn slow_request_times_out() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/namespaces/test/configmaps/cm"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(config_map())
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .mount(&server)
+            .await;
+
+        let before = KUBE_API_ERRORS_TOTAL.with_label_values(&["timeout"]).get();
+        let settings = ApiClientSettings {
+            timeout: Duration::from_millis(100),
+            max_retries: 1,
+        };
+        let client = build(config(&server.uri()), &settings).unwrap();
+        let result = Api::<ConfigMap>::namespaced(client, "test").get("cm").await;
+        assert!(result.is_err());
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+        assert!(KUBE_API_ERRORS_TOTAL.with_label_values(&["timeout"]).get() > before);
+    }
+}
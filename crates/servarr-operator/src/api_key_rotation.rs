@@ -0,0 +1,530 @@
+//! API key rotation, on `spec.apiKeyRotation.schedule` or when the
+//! `servarr.dev/rotate-api-key` annotation gets a new value.
+//!
+//! A rotation sets the new key in the app first (through `config/host` for
+//! the *arr apps), then stores it in the operator-created `apiKeySecret`,
+//! putting the old key back in the app if that fails. The workload is then
+//! restarted, so the `{APP}__AUTH__APIKEY` env var, or Bazarr's config.yaml,
+//! carries the new key. Once the app answers with it, the app's Prowlarr and
+//! Overseerr registrations are updated, since those syncs never compare keys.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use k8s_openapi::ByteString;
+use k8s_openapi::api::core::v1::{ObjectReference, Secret};
+use kube::api::{Api, ListParams, Patch, PatchParams};
+use kube::runtime::events::{Event, EventType};
+use kube::{Client, ResourceExt};
+use servarr_api::ServarrClient;
+use servarr_crds::{ApiKeyRotationStatus, AppType, ServarrApp};
+use servarr_resources::common;
+use tracing::{info, warn};
+
+use crate::controller::{app_type_to_kind, chrono_now, generate_api_key, runs_overseerr_sync};
+use crate::events::ThrottledRecorder;
+use crate::workload;
+
+/// Annotation whose new values each trigger one rotation.
+pub const ROTATE_API_KEY_ANNOTATION: &str = "servarr.dev/rotate-api-key";
+
+/// Pod template annotation that restarts the app with the new key.
+const CHECKSUM_ANNOTATION: &str = "servarr.dev/api-key-checksum";
+
+/// Whether the operator can set a new API key for `app_type`.
+pub fn supported(app_type: &AppType) -> bool {
+    app_type.is_servarr_v3() || *app_type == AppType::Bazarr
+}
+
+/// Whether Prowlarr or Overseerr may hold registrations with the key.
+fn has_dependents(app_type: &AppType) -> bool {
+    app_type.is_servarr_v3() && *app_type != AppType::Prowlarr
+}
+
+/// The `servarr.dev/rotate-api-key` value if it has not been acted on yet.
+pub(crate) fn pending_trigger(app: &ServarrApp) -> Option<String> {
+    let trigger = app.annotations().get(ROTATE_API_KEY_ANNOTATION)?;
+    let handled = app
+        .status
+        .as_ref()
+        .and_then(|s| s.api_key_rotation.as_ref())
+        .and_then(|r| r.trigger.as_ref());
+    (handled != Some(trigger)).then(|| trigger.clone())
+}
+
+/// Whether `spec.apiKeyRotation.schedule` fired between `since` (the last
+/// rotation, or the app's creation) and `now`. `Err` carries an invalid
+/// schedule's message.
+pub(crate) fn schedule_due(
+    schedule: &str,
+    since: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Result<bool, String> {
+    let schedule = crate::schedule::parse(schedule)?;
+    Ok(since.is_some_and(|since| schedule.after(&since).take(1).any(|next| next <= now)))
+}
+
+fn last_rotation(app: &ServarrApp) -> Option<DateTime<Utc>> {
+    let rotated = app
+        .status
+        .as_ref()
+        .and_then(|s| s.api_key_rotation.as_ref())
+        .and_then(|r| r.last_rotation_time.as_deref())
+        .and_then(|t| t.parse().ok());
+    rotated.or_else(|| {
+        app.metadata
+            .creation_timestamp
+            .as_ref()
+            .and_then(|t| t.0.to_string().parse().ok())
+    })
+}
+
+/// SHA-256 of `key`, for the pod template annotation.
+fn checksum(key: &str) -> String {
+    use sha2::{Digest, Sha256};
+    format!("{:x}", Sha256::digest(key.as_bytes()))
+}
+
+/// Rotate the app's API key when due, or finish a rotation whose
+/// dependents are pending. Returns the status to record; between rotations
+/// that is the previous status.
+pub async fn maybe_rotate(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &ObjectReference,
+) -> Option<ApiKeyRotationStatus> {
+    let previous = app.status.as_ref().and_then(|s| s.api_key_rotation.clone());
+    if previous.as_ref().is_some_and(|p| p.dependents_pending) {
+        return Some(finish(client, app, ns, previous.unwrap_or_default()).await);
+    }
+
+    let trigger = pending_trigger(app);
+    let scheduled = match app.spec.api_key_rotation {
+        Some(ref spec) if trigger.is_none() => {
+            match schedule_due(&spec.schedule, last_rotation(app), Utc::now()) {
+                Ok(due) => due,
+                Err(e) => {
+                    warn!(error = %e, "invalid API key rotation schedule");
+                    return Some(ApiKeyRotationStatus {
+                        last_rotation_result: Some(e),
+                        ..previous.unwrap_or_default()
+                    });
+                }
+            }
+        }
+        _ => false,
+    };
+    if trigger.is_none() && !scheduled {
+        return previous;
+    }
+
+    let handled = trigger
+        .clone()
+        .or_else(|| previous.as_ref()?.trigger.clone());
+    let failed = |message: String| ApiKeyRotationStatus {
+        last_rotation_result: Some(format!("error: {message}")),
+        trigger: handled.clone(),
+        ..previous.clone().unwrap_or_default()
+    };
+    let status = match rotate(client, app, ns).await {
+        Ok(()) => {
+            let _ = recorder
+                .publish(
+                    &Event {
+                        type_: EventType::Normal,
+                        reason: "ApiKeyRotated".into(),
+                        note: Some(match trigger {
+                            Some(ref t) => format!("{ROTATE_API_KEY_ANNOTATION}={t}"),
+                            None => "Scheduled API key rotation".into(),
+                        }),
+                        action: "RotateApiKey".into(),
+                        secondary: None,
+                    },
+                    obj_ref,
+                )
+                .await;
+            let pending = has_dependents(&app.spec.app);
+            ApiKeyRotationStatus {
+                last_rotation_time: Some(chrono_now()),
+                last_rotation_result: Some(
+                    if pending {
+                        "rotated; updating registrations once the app restarts"
+                    } else {
+                        "success"
+                    }
+                    .into(),
+                ),
+                trigger: handled,
+                dependents_pending: pending,
+            }
+        }
+        Err(e) => {
+            warn!(app = %app.name_any(), error = %e, "API key rotation failed");
+            let _ = recorder
+                .publish(
+                    &Event {
+                        type_: EventType::Warning,
+                        reason: "ApiKeyRotationFailed".into(),
+                        note: Some(e.clone()),
+                        action: "RotateApiKey".into(),
+                        secondary: None,
+                    },
+                    obj_ref,
+                )
+                .await;
+            failed(e)
+        }
+    };
+    Some(status)
+}
+
+/// Set a new key in the app and its Secret, then restart the workload.
+async fn rotate(client: &Client, app: &ServarrApp, ns: &str) -> Result<(), String> {
+    if !supported(&app.spec.app) {
+        return Err(format!(
+            "{} does not support API key rotation",
+            app.spec.app
+        ));
+    }
+    let (secret_name, key) = match (app.spec.api_key_secret.as_deref(), app.spec.api_key_ref()) {
+        (Some(_), Some(r)) => r,
+        _ => return Err("API key rotation requires apiKeySecret".into()),
+    };
+
+    // Only a Secret the operator created is rotated; one synced from
+    // elsewhere would be overwritten by its source.
+    let secrets = Api::<Secret>::namespaced(client.clone(), ns);
+    let secret = secrets.get(secret_name).await.map_err(|e| e.to_string())?;
+    let owned = secret
+        .owner_references()
+        .iter()
+        .any(|o| Some(&o.uid) == app.metadata.uid.as_ref());
+    if !owned {
+        return Err(format!(
+            "Secret {secret_name} was not created by the operator; rotate the key at its source"
+        ));
+    }
+    let old_key = secret
+        .data
+        .as_ref()
+        .and_then(|d| d.get(key))
+        .and_then(|v| String::from_utf8(v.0.clone()).ok())
+        .ok_or_else(|| format!("Secret {secret_name} has no key {key}"))?;
+    let new_key = generate_api_key();
+    let name = app.name_any();
+
+    // The app first, so a failure leaves both on the old key.
+    let api = if app.spec.app.is_servarr_v3() {
+        let api = ServarrClient::new(
+            &common::internal_url(app),
+            &old_key,
+            app_type_to_kind(&app.spec.app),
+        )
+        .map_err(|e| e.to_string())?;
+        api.set_api_key(&new_key)
+            .await
+            .map_err(|e| format!("failed to set the key in the app: {e}"))?;
+        Some(api)
+    } else {
+        None
+    };
+
+    let patch = Secret {
+        data: Some(BTreeMap::from([(
+            key.to_string(),
+            ByteString(new_key.clone().into_bytes()),
+        )])),
+        ..Default::default()
+    };
+    if let Err(e) = secrets
+        .patch(secret_name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await
+    {
+        if let Some(api) = api
+            && let Err(revert) = api.set_api_key(&old_key).await
+        {
+            warn!(%name, error = %revert, "failed to restore the old API key in the app");
+        }
+        return Err(format!("failed to update Secret {secret_name}: {e}"));
+    }
+    info!(%name, secret = %secret_name, "rotated API key");
+
+    // Use a separate field manager so the main SSA apply does not strip it.
+    let pp = PatchParams::apply("servarr-operator/api-key-rotation").force();
+    let restart = serde_json::json!({
+        "apiVersion": "apps/v1",
+        "kind": workload::workload_type(app).as_str(),
+        "metadata": { "name": common::app_name(app) },
+        "spec": {
+            "template": {
+                "metadata": {
+                    "annotations": { CHECKSUM_ANNOTATION: checksum(&new_key) }
+                }
+            }
+        }
+    });
+    workload::patch(client, app, ns, &pp, &Patch::Apply(restart))
+        .await
+        .map_err(|e| format!("key rotated, but restarting the app failed: {e}"))
+}
+
+/// Once the app answers with the key in its Secret, give it to the Prowlarr
+/// and Overseerr instances that register the app.
+async fn finish(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    previous: ApiKeyRotationStatus,
+) -> ApiKeyRotationStatus {
+    let pending = |result: String| ApiKeyRotationStatus {
+        last_rotation_result: Some(result),
+        ..previous.clone()
+    };
+    let Some((secret, key)) = app
+        .spec
+        .api_key_ref()
+        .filter(|_| has_dependents(&app.spec.app))
+    else {
+        return ApiKeyRotationStatus {
+            dependents_pending: false,
+            ..previous.clone()
+        };
+    };
+    let api_key = match servarr_api::read_secret_key(client, ns, secret, key).await {
+        Ok(k) => k,
+        Err(e) => return pending(format!("error: {e}")),
+    };
+    let url = common::internal_url(app);
+    let answers = match ServarrClient::new(&url, &api_key, app_type_to_kind(&app.spec.app)) {
+        Ok(api) => api.system_status().await.is_ok(),
+        Err(_) => false,
+    };
+    if !answers {
+        return previous;
+    }
+
+    let errors = resync_dependents(client, app, ns, &url, &api_key).await;
+    if errors.is_empty() {
+        ApiKeyRotationStatus {
+            last_rotation_result: Some("success".into()),
+            dependents_pending: false,
+            ..previous
+        }
+    } else {
+        pending(format!("error: {}", errors.join("; ")))
+    }
+}
+
+/// Update the key in every Prowlarr application and Overseerr server
+/// registered for `url`. Returns the failures.
+async fn resync_dependents(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    url: &str,
+    api_key: &str,
+) -> Vec<String> {
+    let apps = match Api::<ServarrApp>::all(client.clone())
+        .list(&ListParams::default())
+        .await
+    {
+        Ok(list) => list.items,
+        // Namespace-scoped installs may not list cluster-wide.
+        Err(_) => match Api::<ServarrApp>::namespaced(client.clone(), ns)
+            .list(&ListParams::default())
+            .await
+        {
+            Ok(list) => list.items,
+            Err(e) => return vec![format!("failed to list ServarrApps: {e}")],
+        },
+    };
+
+    let mut errors = Vec::new();
+    for dependent in &apps {
+        let prowlarr = dependent.spec.app == AppType::Prowlarr
+            && dependent
+                .spec
+                .prowlarr_sync
+                .as_ref()
+                .is_some_and(|s| s.enabled);
+        let overseerr = runs_overseerr_sync(dependent)
+            && matches!(app.spec.app, AppType::Sonarr | AppType::Radarr);
+        if !prowlarr && !overseerr {
+            continue;
+        }
+        let dependent_ns = dependent.namespace().unwrap_or_else(|| ns.to_string());
+        let Some((secret, key)) = dependent.spec.api_key_ref() else {
+            continue;
+        };
+        let dependent_key =
+            match servarr_api::read_secret_key(client, &dependent_ns, secret, key).await {
+                Ok(k) => k,
+                Err(e) => {
+                    errors.push(format!("{}: {e}", dependent.name_any()));
+                    continue;
+                }
+            };
+        let dependent_url = common::internal_url(dependent);
+        let result = if prowlarr {
+            update_prowlarr(&dependent_url, &dependent_key, url, api_key).await
+        } else {
+            update_overseerr(&dependent_url, &dependent_key, app, url, api_key).await
+        };
+        match result {
+            Ok(0) => {}
+            Ok(_) => info!(
+                app = %app.name_any(),
+                dependent = %dependent.name_any(),
+                "updated the rotated API key in a registration"
+            ),
+            Err(e) => errors.push(format!("{}: {e}", dependent.name_any())),
+        }
+    }
+    errors
+}
+
+/// Set `api_key` on the Prowlarr applications whose base URL is `url`.
+async fn update_prowlarr(
+    prowlarr_url: &str,
+    prowlarr_key: &str,
+    url: &str,
+    api_key: &str,
+) -> Result<usize, String> {
+    let prowlarr =
+        servarr_api::ProwlarrClient::new(prowlarr_url, prowlarr_key).map_err(|e| e.to_string())?;
+    let applications = prowlarr
+        .list_applications()
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut updated = 0;
+    for mut application in applications {
+        let registers = application
+            .fields
+            .iter()
+            .any(|f| f.name == "baseUrl" && f.value.as_str() == Some(url));
+        if !registers {
+            continue;
+        }
+        for field in &mut application.fields {
+            if field.name == "apiKey" {
+                field.value = api_key.into();
+            }
+        }
+        prowlarr
+            .update_application(application.id, &application)
+            .await
+            .map_err(|e| e.to_string())?;
+        updated += 1;
+    }
+    Ok(updated)
+}
+
+/// Set `api_key` on the Overseerr Sonarr or Radarr servers at `url`'s host
+/// and port.
+async fn update_overseerr(
+    overseerr_url: &str,
+    overseerr_key: &str,
+    app: &ServarrApp,
+    url: &str,
+    api_key: &str,
+) -> Result<usize, String> {
+    let parsed = url::Url::parse(url).map_err(|e| e.to_string())?;
+    let host = parsed.host_str().unwrap_or_default();
+    let port = parsed.port().unwrap_or(80) as f64;
+    let overseerr = servarr_api::OverseerrClient::new(overseerr_url, overseerr_key);
+    let mut updated = 0;
+    if app.spec.app == AppType::Sonarr {
+        let servers = overseerr.list_sonarr().await.map_err(|e| e.to_string())?;
+        for mut server in servers {
+            if server.hostname != host || server.port != port {
+                continue;
+            }
+            server.api_key = api_key.to_string();
+            let id = server.id.unwrap_or(0.0) as i32;
+            overseerr
+                .update_sonarr(id, server)
+                .await
+                .map_err(|e| e.to_string())?;
+            updated += 1;
+        }
+    } else {
+        let servers = overseerr.list_radarr().await.map_err(|e| e.to_string())?;
+        for mut server in servers {
+            if server.hostname != host || server.port != port {
+                continue;
+            }
+            server.api_key = api_key.to_string();
+            let id = server.id.unwrap_or(0.0) as i32;
+            overseerr
+                .update_radarr(id, server)
+                .await
+                .map_err(|e| e.to_string())?;
+            updated += 1;
+        }
+    }
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kube::api::ObjectMeta;
+    use servarr_crds::{ServarrAppSpec, ServarrAppStatus};
+
+    fn app(annotation: Option<&str>, handled: Option<&str>) -> ServarrApp {
+        ServarrApp {
+            metadata: ObjectMeta {
+                name: Some("sonarr".into()),
+                annotations: annotation
+                    .map(|a| BTreeMap::from([(ROTATE_API_KEY_ANNOTATION.into(), a.into())])),
+                ..Default::default()
+            },
+            spec: ServarrAppSpec {
+                app: AppType::Sonarr,
+                ..Default::default()
+            },
+            status: Some(ServarrAppStatus {
+                api_key_rotation: Some(ApiKeyRotationStatus {
+                    trigger: handled.map(String::from),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn annotation_triggers_once_per_value() {
+        assert_eq!(pending_trigger(&app(None, None)), None);
+        assert_eq!(pending_trigger(&app(Some("1"), None)).as_deref(), Some("1"));
+        assert_eq!(pending_trigger(&app(Some("1"), Some("1"))), None);
+        assert_eq!(
+            pending_trigger(&app(Some("2"), Some("1"))).as_deref(),
+            Some("2")
+        );
+    }
+
+    #[test]
+    fn schedule_due_since_last_rotation() {
+        let at = |t: &str| t.parse::<DateTime<Utc>>().unwrap();
+        let monthly = "0 4 1 * *";
+        let since = Some(at("2024-01-01T05:00:00Z"));
+        assert!(!schedule_due(monthly, since, at("2024-01-20T00:00:00Z")).unwrap());
+        assert!(schedule_due(monthly, since, at("2024-02-01T04:00:00Z")).unwrap());
+        // Without a baseline the schedule waits rather than rotating now.
+        assert!(!schedule_due(monthly, None, at("2024-02-01T04:00:00Z")).unwrap());
+        assert!(schedule_due("not a schedule", since, at("2024-02-01T04:00:00Z")).is_err());
+    }
+
+    #[test]
+    fn supported_apps() {
+        assert!(supported(&AppType::Sonarr));
+        assert!(supported(&AppType::Bazarr));
+        assert!(!supported(&AppType::Sabnzbd));
+        assert!(has_dependents(&AppType::Radarr));
+        assert!(!has_dependents(&AppType::Prowlarr));
+        assert!(!has_dependents(&AppType::Bazarr));
+    }
+}
@@ -0,0 +1,373 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use anyhow::Result;
+use futures::StreamExt;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+use kube::api::{Api, ListParams, Patch, PatchParams};
+use kube::runtime::controller::{Action, Controller};
+use kube::runtime::watcher;
+use kube::{Client, CustomResourceExt, Resource, ResourceExt};
+use serde_json::Value;
+use servarr_crds::{
+    APP_SET_LABEL, AppSetInstanceStatus, Condition, RenderedApp, ServarrApp, ServarrAppSet,
+    ServarrAppSetStatus,
+};
+use thiserror::Error;
+use tokio::time::Duration;
+use tracing::{error, info, warn};
+
+use crate::context::Context;
+use crate::requeue::{self, object_key};
+
+const FIELD_MANAGER: &str = "servarr-operator-appset";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Kubernetes API error: {0}")]
+    Kube(#[source] kube::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[source] serde_json::Error),
+}
+
+pub fn print_crd() -> Result<()> {
+    let crd = ServarrAppSet::crd();
+    let yaml = serde_yaml::to_string(&crd)?;
+    println!("{yaml}");
+    Ok(())
+}
+
+pub async fn run(
+    client: kube::Client,
+    server_state: crate::server::ServerState,
+    dry_run: bool,
+) -> Result<()> {
+    let ctx = Arc::new(Context {
+        dry_run,
+        ..Context::new(client.clone())
+    });
+
+    let (sets, apps) = if let Some(ref ns) = ctx.watch_namespace {
+        (
+            Api::<ServarrAppSet>::namespaced(client.clone(), ns),
+            Api::<ServarrApp>::namespaced(client.clone(), ns),
+        )
+    } else {
+        (
+            Api::<ServarrAppSet>::all(client.clone()),
+            Api::<ServarrApp>::all(client.clone()),
+        )
+    };
+
+    info!("Starting app-set controller");
+    server_state.set_ready();
+
+    let controller = Controller::new(sets, watcher::Config::default());
+    tokio::spawn(crate::metrics::sample_cache(
+        "servarrappset",
+        controller.store(),
+        |set| {
+            crate::metrics::generation_pending(
+                set.metadata.generation,
+                set.status.as_ref().map(|s| s.observed_generation),
+            )
+        },
+    ));
+
+    controller
+        .owns(apps, watcher::Config::default())
+        .shutdown_on_signal()
+        .run(reconcile, error_policy, ctx.clone())
+        .for_each(|res| {
+            if let Ok((ref obj, _)) = res {
+                ctx.error_backoff
+                    .succeeded(&object_key(obj.namespace.as_deref(), &obj.name));
+            }
+            async move {
+                match res {
+                    Ok(o) => info!(?o, "app-set reconciled"),
+                    Err(e) => error!(%e, "app-set reconcile error"),
+                }
+            }
+        })
+        .await;
+
+    Ok(())
+}
+
+/// Whether `app` is controlled by the set with UID `set_uid`.
+fn owned_by(app: &ServarrApp, set_uid: &str) -> bool {
+    app.owner_references()
+        .iter()
+        .any(|o| o.controller == Some(true) && o.uid == set_uid)
+}
+
+pub async fn reconcile(set: Arc<ServarrAppSet>, ctx: Arc<Context>) -> Result<Action, Error> {
+    let _in_flight = crate::metrics::InFlight::start("servarrappset");
+    let client = &ctx.client;
+    let name = set.name_any();
+    let ns = set.namespace().unwrap_or_else(|| "default".into());
+    let pp = PatchParams::apply(FIELD_MANAGER).force();
+    let now = chrono_now();
+
+    info!(%name, %ns, "reconciling ServarrAppSet");
+
+    if ctx.dry_run {
+        return reconcile_dry_run(&set, client, &name, &ns).await;
+    }
+
+    let mut status = ServarrAppSetStatus {
+        replicas: set.spec.instances.len() as i32,
+        selector: format!("{APP_SET_LABEL}={name}"),
+        observed_generation: set.metadata.generation.unwrap_or(0),
+        ..Default::default()
+    };
+
+    let rendered = match set.spec.render(&name) {
+        Ok(rendered) => rendered,
+        Err(msg) => {
+            warn!(%name, error = %msg, "invalid ServarrAppSet");
+            status.set_condition(Condition::fail("Valid", "InvalidSpec", &msg, &now));
+            patch_status(client, &ns, &name, &status).await?;
+            return Ok(Action::requeue(Duration::from_secs(60)));
+        }
+    };
+    status.set_condition(Condition::ok("Valid", "Valid", "Spec is valid", &now));
+
+    let owner_ref = set.controller_owner_ref(&()).expect("set should have UID");
+    let set_uid = owner_ref.uid.clone();
+    let sa_api = Api::<ServarrApp>::namespaced(client.clone(), &ns);
+
+    for app in &rendered {
+        if let Some(existing) = sa_api.get_opt(&app.name).await.map_err(Error::Kube)?
+            && !owned_by(&existing, &set_uid)
+        {
+            warn!(%name, child = %app.name, "ServarrApp exists and is not owned by this set");
+            status.instances.push(AppSetInstanceStatus {
+                name: app.instance.clone(),
+                app: app.name.clone(),
+                ready: false,
+                message: Some(format!(
+                    "ServarrApp {} already exists and is not managed by this set",
+                    app.name
+                )),
+            });
+            continue;
+        }
+
+        let child_value = child_value(app, &ns, &owner_ref)?;
+        let applied = sa_api
+            .patch(&app.name, &pp, &Patch::Apply(child_value))
+            .await
+            .map_err(Error::Kube)?;
+        let ready = applied.status.as_ref().is_some_and(|s| s.ready);
+        if ready {
+            status.ready_replicas += 1;
+        }
+        status.instances.push(AppSetInstanceStatus {
+            name: app.instance.clone(),
+            app: app.name.clone(),
+            ready,
+            message: None,
+        });
+    }
+
+    // Delete apps whose instance was removed from the spec
+    let desired: HashSet<&str> = rendered.iter().map(|a| a.name.as_str()).collect();
+    let existing = sa_api
+        .list(&ListParams::default().labels(&status.selector))
+        .await
+        .map_err(Error::Kube)?;
+    for child in &existing {
+        let child_name = child.name_any();
+        if !desired.contains(child_name.as_str()) && owned_by(child, &set_uid) {
+            info!(%name, child = %child_name, "deleting ServarrApp of removed instance");
+            if let Err(e) = sa_api.delete(&child_name, &Default::default()).await {
+                warn!(%name, child = %child_name, error = %e, "failed to delete orphaned app");
+            }
+        }
+    }
+
+    let ready = status.ready_replicas == status.replicas;
+    let conflicts = status
+        .instances
+        .iter()
+        .filter(|i| i.message.is_some())
+        .count();
+    let message = format!("{}/{} apps ready", status.ready_replicas, status.replicas);
+    status.set_condition(if conflicts > 0 {
+        Condition::fail(
+            "Ready",
+            "NameConflict",
+            &format!("{message}; {conflicts} instance(s) blocked by existing ServarrApps"),
+            &now,
+        )
+    } else if ready {
+        Condition::ok("Ready", "AllAppsReady", &message, &now)
+    } else {
+        Condition::fail("Ready", "AppsNotReady", &message, &now)
+    });
+
+    patch_status(client, &ns, &name, &status).await?;
+
+    info!(
+        %name,
+        ready = status.ready_replicas,
+        total = status.replicas,
+        "ServarrAppSet reconciliation complete"
+    );
+
+    let requeue = if ready && conflicts == 0 {
+        requeue::settings().settled
+    } else {
+        Duration::from_secs(30)
+    };
+    Ok(Action::requeue(requeue))
+}
+
+/// The ServarrApp for `app` as a server-side apply body.
+fn child_value(app: &RenderedApp, ns: &str, owner_ref: &OwnerReference) -> Result<Value, Error> {
+    let mut child = ServarrApp::new(&app.name, app.spec.clone());
+    child.metadata.namespace = Some(ns.to_string());
+    child.metadata.labels = Some(app.labels.clone());
+    child.metadata.owner_references = Some(vec![owner_ref.clone()]);
+    let mut value = serde_json::to_value(&child).map_err(Error::Serialization)?;
+    // The serialized status is always empty; leave it to the app controller
+    if let Some(obj) = value.as_object_mut() {
+        obj.remove("status");
+    }
+    Ok(value)
+}
+
+/// `--dry-run` pass: record what a real reconcile would change in
+/// `status.wouldChange` and leave everything else untouched.
+async fn reconcile_dry_run(
+    set: &ServarrAppSet,
+    client: &Client,
+    name: &str,
+    ns: &str,
+) -> Result<Action, Error> {
+    if set.metadata.deletion_timestamp.is_some() {
+        return Ok(Action::await_change());
+    }
+    let rendered = match set.spec.render(name) {
+        Ok(rendered) => rendered,
+        Err(msg) => {
+            warn!(%name, error = %msg, "invalid ServarrAppSet");
+            return Ok(Action::requeue(Duration::from_secs(60)));
+        }
+    };
+
+    let owner_ref = set.controller_owner_ref(&()).expect("set should have UID");
+    let sa_api = Api::<ServarrApp>::namespaced(client.clone(), ns);
+    let mut objects = Vec::new();
+    for app in &rendered {
+        // Apps of the same name the set doesn't own are never touched
+        if let Some(existing) = sa_api.get_opt(&app.name).await.map_err(Error::Kube)?
+            && !owned_by(&existing, &owner_ref.uid)
+        {
+            continue;
+        }
+        objects.push(child_value(app, ns, &owner_ref)?);
+    }
+    let mut changes = crate::dry_run::plan(client, ns, &objects)
+        .await
+        .map_err(Error::Kube)?;
+
+    let desired: HashSet<&str> = rendered.iter().map(|a| a.name.as_str()).collect();
+    let existing = sa_api
+        .list(&ListParams::default().labels(&format!("{APP_SET_LABEL}={name}")))
+        .await
+        .map_err(Error::Kube)?;
+    for child in &existing {
+        let child_name = child.name_any();
+        if !desired.contains(child_name.as_str()) && owned_by(child, &owner_ref.uid) {
+            changes.push(format!("delete ServarrApp/{child_name}"));
+        }
+    }
+
+    let previous = set.status.as_ref().and_then(|s| s.would_change.as_ref());
+    if previous != Some(&changes) {
+        for change in &changes {
+            info!(%name, %ns, %change, "dry run: would change");
+        }
+        let mut status = set.status.clone().unwrap_or_default();
+        status.would_change = Some(changes);
+        patch_status(client, ns, name, &status).await?;
+    }
+    Ok(Action::requeue(Duration::from_secs(300)))
+}
+
+async fn patch_status(
+    client: &Client,
+    ns: &str,
+    name: &str,
+    status: &ServarrAppSetStatus,
+) -> Result<(), Error> {
+    let sets = Api::<ServarrAppSet>::namespaced(client.clone(), ns);
+    let status_patch = serde_json::json!({
+        "apiVersion": "servarr.dev/v1alpha1",
+        "kind": "ServarrAppSet",
+        "status": status,
+    });
+    sets.patch_status(
+        name,
+        &PatchParams::apply(FIELD_MANAGER).force(),
+        &Patch::Apply(status_patch),
+    )
+    .await
+    .map_err(Error::Kube)?;
+    Ok(())
+}
+
+pub fn error_policy(set: Arc<ServarrAppSet>, error: &Error, ctx: Arc<Context>) -> Action {
+    warn!(%error, "app-set reconciliation failed, requeuing");
+    Action::requeue(
+        ctx.error_backoff
+            .failed(&object_key(set.namespace().as_deref(), &set.name_any())),
+    )
+}
+
+fn chrono_now() -> String {
+    use chrono::{SecondsFormat, Utc};
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+    use servarr_crds::{AppType, ServarrAppSpec};
+
+    #[test]
+    fn print_crd_returns_ok() {
+        assert!(print_crd().is_ok());
+    }
+
+    #[test]
+    fn owned_by_requires_controller_reference_with_set_uid() {
+        let mut app = ServarrApp::new(
+            "family-alice",
+            ServarrAppSpec {
+                app: AppType::Overseerr,
+                ..Default::default()
+            },
+        );
+        assert!(!owned_by(&app, "set-uid"));
+
+        let reference = |uid: &str, controller| OwnerReference {
+            api_version: "servarr.dev/v1alpha1".into(),
+            kind: "ServarrAppSet".into(),
+            name: "family".into(),
+            uid: uid.into(),
+            controller: Some(controller),
+            ..Default::default()
+        };
+        app.metadata.owner_references = Some(vec![reference("set-uid", false)]);
+        assert!(!owned_by(&app, "set-uid"));
+        app.metadata.owner_references = Some(vec![reference("other-uid", true)]);
+        assert!(!owned_by(&app, "set-uid"));
+        app.metadata.owner_references = Some(vec![reference("set-uid", true)]);
+        assert!(owned_by(&app, "set-uid"));
+    }
+}
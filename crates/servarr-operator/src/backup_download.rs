@@ -0,0 +1,156 @@
+//! `servarr-operator backup download`: copy an app's backup archive off the
+//! cluster, e.g. before a risky upgrade.
+
+use std::path::Path;
+
+use anyhow::{Context as _, bail};
+use chrono::{DateTime, Utc};
+use kube::Client;
+use servarr_api::servarr_v3::Backup;
+use servarr_api::{AppKind, ServarrClient};
+use servarr_crds::AppType;
+
+use crate::port_forward::{self, Forward};
+
+/// Which backup to download.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Selection {
+    Latest,
+    Id(i64),
+}
+
+fn app_kind(app_type: &AppType) -> anyhow::Result<AppKind> {
+    Ok(match app_type {
+        AppType::Sonarr => AppKind::Sonarr,
+        AppType::Radarr => AppKind::Radarr,
+        AppType::Lidarr => AppKind::Lidarr,
+        AppType::Readarr => AppKind::Readarr,
+        AppType::Whisparr => AppKind::Whisparr,
+        AppType::Prowlarr => AppKind::Prowlarr,
+        other => bail!("{other} does not expose a backup API"),
+    })
+}
+
+fn backup_time(backup: &Backup) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&backup.time)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Pick the backup matching `selection` from `backups`.
+pub fn select(backups: &[Backup], selection: Selection) -> anyhow::Result<&Backup> {
+    match selection {
+        Selection::Latest => backups
+            .iter()
+            .max_by_key(|b| backup_time(b))
+            .context("the app has no backups"),
+        Selection::Id(id) => backups
+            .iter()
+            .find(|b| b.id == id)
+            .with_context(|| format!("no backup with id {id}")),
+    }
+}
+
+fn print_backups(backups: &[Backup]) {
+    println!("{:>6}  {:<25}  {:>10}  NAME", "ID", "TIME", "SIZE");
+    for b in backups {
+        println!("{:>6}  {:<25}  {:>10}  {}", b.id, b.time, b.size, b.name);
+    }
+}
+
+/// List the backups of ServarrApp `ns/name` through a temporary
+/// port-forward and, when `selection` is given, write that archive into
+/// `output_dir`. Without a selection the backups are printed so one can be
+/// picked by id.
+pub async fn run(
+    client: Client,
+    ns: &str,
+    name: &str,
+    selection: Option<Selection>,
+    output_dir: &Path,
+) -> anyhow::Result<()> {
+    let app = port_forward::get_app(&client, ns, name).await?;
+    let kind = app_kind(&app.spec.app)?;
+    let (secret, key) = app
+        .spec
+        .api_key_ref()
+        .with_context(|| format!("{ns}/{name} has no apiKeySecret"))?;
+    let api_key = servarr_api::read_secret_key(&client, ns, secret, key)
+        .await
+        .with_context(|| format!("failed to read API key from Secret {ns}/{secret}"))?;
+
+    let forward = Forward::start(&client, ns, &app, 0).await?;
+    let api = ServarrClient::new(
+        &format!("http://127.0.0.1:{}", forward.local_port),
+        &api_key,
+        kind,
+    )?;
+    let backups = api.list_backups().await?;
+
+    let Some(selection) = selection else {
+        print_backups(&backups);
+        return Ok(());
+    };
+    let backup = select(&backups, selection)?;
+    let archive = api
+        .download_backup(backup)
+        .await
+        .with_context(|| format!("failed to download {}", backup.name))?;
+
+    std::fs::create_dir_all(output_dir)?;
+    let file_name = Path::new(&backup.name)
+        .file_name()
+        .with_context(|| format!("invalid backup name '{}'", backup.name))?;
+    let dest = output_dir.join(file_name);
+    std::fs::write(&dest, &archive)
+        .with_context(|| format!("failed to write {}", dest.display()))?;
+    println!(
+        "{} ({} bytes) -> {}",
+        backup.name,
+        archive.len(),
+        dest.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backup(id: i64, time: &str) -> Backup {
+        Backup {
+            id,
+            name: format!("backup_{id}.zip"),
+            path: format!("/backup/scheduled/backup_{id}.zip"),
+            size: 0,
+            time: time.into(),
+        }
+    }
+
+    #[test]
+    fn select_latest_uses_timestamp_not_list_order() {
+        let backups = [
+            backup(1, "2024-01-10T08:00:00Z"),
+            backup(3, "2024-01-20T08:00:00Z"),
+            backup(2, "2024-01-15T08:00:00Z"),
+        ];
+        assert_eq!(select(&backups, Selection::Latest).unwrap().id, 3);
+    }
+
+    #[test]
+    fn select_by_id() {
+        let backups = [
+            backup(1, "2024-01-10T08:00:00Z"),
+            backup(2, "2024-01-15T08:00:00Z"),
+        ];
+        assert_eq!(select(&backups, Selection::Id(1)).unwrap().id, 1);
+        assert!(select(&backups, Selection::Id(9)).is_err());
+        assert!(select(&[], Selection::Latest).is_err());
+    }
+
+    #[test]
+    fn only_servarr_v3_apps_support_download() {
+        assert_eq!(app_kind(&AppType::Radarr).unwrap(), AppKind::Radarr);
+        assert!(app_kind(&AppType::Jellyfin).is_err());
+    }
+}
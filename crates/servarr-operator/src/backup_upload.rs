@@ -0,0 +1,339 @@
+//! Uploading a Servarr v3 app's backups to `backup.objectStorage.bucket`,
+//! and applying its `deletionPolicy` to them when the app is deleted.
+//!
+//! After each scheduled backup a Job copies the app's backup folder to the
+//! bucket. While a bucket is set the app carries [`FINALIZER`]; on deletion
+//! the uploaded backups are kept or, with `deletionPolicy: Delete`, removed
+//! by a prune Job before the finalizer is released. The location and policy
+//! are reported in `status.backupStatus.objectStorage` all along, so what
+//! deleting the app does to its backups is visible before it happens.
+
+use chrono::{DateTime, Utc};
+use k8s_openapi::api::batch::v1::Job;
+use k8s_openapi::api::core::v1::ObjectReference;
+use kube::api::{Api, DeleteParams, Patch, PatchParams};
+use kube::runtime::events::{Event, EventType};
+use kube::{Client, ResourceExt};
+use servarr_crds::{
+    BackupDeletionPolicy, BackupStatus, ObjectStorageBackupStatus, ObjectStorageSpec, ServarrApp,
+};
+use servarr_resources::object_storage;
+use tracing::{info, warn};
+
+use crate::controller::chrono_now;
+use crate::events::ThrottledRecorder;
+use crate::metrics::increment_backup_operations;
+use crate::storage_migration::{JobOutcome, job_outcome};
+
+/// Finalizer holding a deleted app until its `deletionPolicy` has been
+/// applied to the uploaded backups.
+pub const FINALIZER: &str = "servarr.dev/backup-cleanup";
+
+/// The object store, bucket, and key prefix the app's backups go to. Only
+/// Servarr v3 apps backed up through their API upload, and only when
+/// `backup.objectStorage.bucket` is set; uploading, the finalizer, and the
+/// deletion cleanup all go by this.
+fn target(app: &ServarrApp) -> Option<(&ObjectStorageSpec, &str, String)> {
+    if !app.spec.app.is_servarr_v3() {
+        return None;
+    }
+    let backup = app.spec.backup.as_ref()?;
+    if backup.volume_snapshot.is_some() {
+        return None;
+    }
+    let storage = backup.object_storage.as_ref()?;
+    let bucket = storage.bucket.as_deref().filter(|b| !b.is_empty())?;
+    Some((storage, bucket, object_storage::upload_prefix(app, storage)))
+}
+
+/// Whether a backup taken since the last upload is waiting to be uploaded.
+/// Failed backups have nothing new to upload, and a failed upload is only
+/// retried after the next backup.
+pub fn upload_due(status: &BackupStatus, last_upload: Option<&str>) -> bool {
+    let parse = |t: &str| t.parse::<DateTime<Utc>>().ok();
+    if status.last_backup_result.as_deref() != Some("success") {
+        return false;
+    }
+    let Some(backup) = status.last_backup_time.as_deref().and_then(parse) else {
+        return false;
+    };
+    last_upload
+        .and_then(parse)
+        .is_none_or(|upload| backup > upload)
+}
+
+async fn publish(
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &ObjectReference,
+    type_: EventType,
+    reason: &str,
+    note: String,
+) {
+    let _ = recorder
+        .publish(
+            &Event {
+                type_,
+                reason: reason.into(),
+                note: Some(note),
+                action: "Backup".into(),
+                secondary: None,
+            },
+            obj_ref,
+        )
+        .await;
+}
+
+/// Upload the app's backups after each successful scheduled backup and
+/// record the uploads in `status`. Returns the updated status and whether
+/// an upload is running, so the app is checked again soon.
+pub async fn reconcile(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    pp: &PatchParams,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &ObjectReference,
+    status: Option<BackupStatus>,
+) -> Result<(Option<BackupStatus>, bool), kube::Error> {
+    let Some(mut status) = status else {
+        return Ok((None, false));
+    };
+    let Some((storage, bucket, prefix)) = target(app) else {
+        status.object_storage = None;
+        return Ok((Some(status), false));
+    };
+
+    let location = format!("s3://{bucket}/{prefix}");
+    // A freshly taken backup starts from an empty status; carry the
+    // uploads over from the previous one.
+    let previous = app
+        .status
+        .as_ref()
+        .and_then(|s| s.backup_status.as_ref())
+        .and_then(|b| b.object_storage.clone());
+    let mut upload = status
+        .object_storage
+        .take()
+        .or(previous)
+        .filter(|o| o.location == location)
+        .unwrap_or_else(|| ObjectStorageBackupStatus {
+            location: location.clone(),
+            ..Default::default()
+        });
+    upload.deletion_policy = storage.deletion_policy;
+
+    let name = app.name_any();
+    let app_type = app.spec.app.as_str();
+    let job_api = Api::<Job>::namespaced(client.clone(), ns);
+    let job_name = object_storage::upload_job_name(app);
+    let running = match job_outcome(job_api.get_opt(&job_name).await?.as_ref()) {
+        JobOutcome::Missing if upload_due(&status, upload.last_upload_time.as_deref()) => {
+            info!(%name, %location, "uploading backups to object storage");
+            let job = object_storage::build_upload_job(app, storage, bucket);
+            job_api.patch(&job_name, pp, &Patch::Apply(&job)).await?;
+            publish(
+                recorder,
+                obj_ref,
+                EventType::Normal,
+                "BackupUploadStarted",
+                format!("Uploading backups to {location}"),
+            )
+            .await;
+            true
+        }
+        JobOutcome::Missing => false,
+        JobOutcome::Running => true,
+        JobOutcome::Succeeded => {
+            job_api
+                .delete(&job_name, &DeleteParams::background())
+                .await?;
+            info!(%name, %location, "backups uploaded");
+            increment_backup_operations(app_type, "upload", "success");
+            upload.last_upload_time = Some(chrono_now());
+            upload.last_upload_result = Some("success".into());
+            publish(
+                recorder,
+                obj_ref,
+                EventType::Normal,
+                "BackupUploaded",
+                format!("Backups uploaded to {location}"),
+            )
+            .await;
+            false
+        }
+        JobOutcome::Failed(message) => {
+            job_api
+                .delete(&job_name, &DeleteParams::background())
+                .await?;
+            warn!(%name, %location, %message, "backup upload failed");
+            increment_backup_operations(app_type, "upload", "error");
+            upload.last_upload_time = Some(chrono_now());
+            upload.last_upload_result = Some(format!("error: {message}"));
+            publish(
+                recorder,
+                obj_ref,
+                EventType::Warning,
+                "BackupUploadFailed",
+                format!("Uploading backups to {location} failed: {message}"),
+            )
+            .await;
+            false
+        }
+    };
+    status.object_storage = Some(upload);
+    Ok((Some(status), running))
+}
+
+/// Add [`FINALIZER`] while a bucket is set, and drop it once it isn't. The
+/// finalizers are re-read so ones added earlier in the reconcile are kept.
+pub async fn ensure_finalizer(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+) -> Result<(), kube::Error> {
+    let wanted = target(app).is_some();
+    if app.finalizers().iter().any(|f| f == FINALIZER) == wanted {
+        return Ok(());
+    }
+    let api = Api::<ServarrApp>::namespaced(client.clone(), ns);
+    let name = app.name_any();
+    let mut finalizers = api
+        .get(&name)
+        .await?
+        .metadata
+        .finalizers
+        .unwrap_or_default();
+    finalizers.retain(|f| f != FINALIZER);
+    if wanted {
+        finalizers.push(FINALIZER.to_string());
+    }
+    let patch = serde_json::json!({ "metadata": { "finalizers": finalizers } });
+    api.patch(&name, &PatchParams::default(), &Patch::Merge(patch))
+        .await?;
+    Ok(())
+}
+
+/// Apply `deletionPolicy` to a deleted app's uploaded backups: keep them,
+/// or delete everything under the upload prefix. Returns whether the prune
+/// Job is still running, so the finalizer is held. A failed prune is
+/// reported and the backups are left in place.
+pub async fn cleanup(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    pp: &PatchParams,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &ObjectReference,
+) -> Result<bool, kube::Error> {
+    let Some((storage, bucket, prefix)) = target(app) else {
+        return Ok(false);
+    };
+    let location = format!("s3://{bucket}/{prefix}");
+    let name = app.name_any();
+    if storage.deletion_policy == BackupDeletionPolicy::Retain {
+        info!(%name, %location, "keeping uploaded backups of deleted app");
+        publish(
+            recorder,
+            obj_ref,
+            EventType::Normal,
+            "BackupsRetained",
+            format!("Uploaded backups in {location} are kept (deletionPolicy: Retain)"),
+        )
+        .await;
+        return Ok(false);
+    }
+
+    let app_type = app.spec.app.as_str();
+    let job_api = Api::<Job>::namespaced(client.clone(), ns);
+    let job_name = object_storage::prune_job_name(app);
+    match job_outcome(job_api.get_opt(&job_name).await?.as_ref()) {
+        JobOutcome::Missing => {
+            info!(%name, %location, "deleting uploaded backups of deleted app");
+            let job = object_storage::build_prune_job(app, storage, bucket);
+            job_api.patch(&job_name, pp, &Patch::Apply(&job)).await?;
+            publish(
+                recorder,
+                obj_ref,
+                EventType::Normal,
+                "BackupsDeleting",
+                format!("Deleting uploaded backups in {location} (deletionPolicy: Delete)"),
+            )
+            .await;
+            Ok(true)
+        }
+        JobOutcome::Running => Ok(true),
+        JobOutcome::Succeeded => {
+            job_api
+                .delete(&job_name, &DeleteParams::background())
+                .await?;
+            increment_backup_operations(app_type, "prune", "success");
+            publish(
+                recorder,
+                obj_ref,
+                EventType::Normal,
+                "BackupsDeleted",
+                format!("Deleted uploaded backups in {location}"),
+            )
+            .await;
+            Ok(false)
+        }
+        JobOutcome::Failed(message) => {
+            job_api
+                .delete(&job_name, &DeleteParams::background())
+                .await?;
+            warn!(%name, %location, %message, "deleting uploaded backups failed");
+            increment_backup_operations(app_type, "prune", "error");
+            publish(
+                recorder,
+                obj_ref,
+                EventType::Warning,
+                "BackupDeleteFailed",
+                format!(
+                    "Deleting uploaded backups in {location} failed, so they are kept: {message}"
+                ),
+            )
+            .await;
+            Ok(false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(time: Option<&str>, result: &str) -> BackupStatus {
+        BackupStatus {
+            last_backup_time: time.map(String::from),
+            last_backup_result: Some(result.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn upload_follows_each_successful_backup() {
+        let backed_up = status(Some("2026-10-16T03:00:00Z"), "success");
+        assert!(upload_due(&backed_up, None));
+        assert!(upload_due(&backed_up, Some("2026-10-15T03:05:00Z")));
+        assert!(!upload_due(&backed_up, Some("2026-10-16T03:05:00Z")));
+
+        assert!(!upload_due(
+            &status(Some("2026-10-16T03:00:00Z"), "error: timeout"),
+            None
+        ));
+        assert!(!upload_due(&status(None, "success"), None));
+    }
+
+    #[test]
+    fn upload_prefix_is_scoped_to_the_app() {
+        let mut storage = ObjectStorageSpec::default();
+        assert_eq!(storage.upload_prefix("media", "sonarr"), "media/sonarr");
+        storage.prefix = Some("/".into());
+        assert_eq!(storage.upload_prefix("media", "sonarr"), "media/sonarr");
+        storage.prefix = Some("/backups/".into());
+        assert_eq!(
+            storage.upload_prefix("media", "sonarr"),
+            "backups/media/sonarr"
+        );
+    }
+}
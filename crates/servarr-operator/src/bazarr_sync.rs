@@ -0,0 +1,397 @@
+//! The settings form for `bazarrSync`: Bazarr's Sonarr and Radarr
+//! connections, and the providers, language profiles, and default profile
+//! of `appConfig.bazarr`.
+//!
+//! Bazarr takes all of these as `settings-{section}-{key}` fields posted to
+//! its settings endpoint, so each helper returns only the fields whose value
+//! differs from what Bazarr has, and the sync posts nothing when Bazarr is
+//! already up to date. Language profiles are the exception to field-wise
+//! updates: Bazarr deletes any profile left out of `languages-profiles`, so
+//! the full list is posted with the spec's profiles merged in by name.
+
+use serde_json::{Value, json};
+use servarr_api::bazarr::{
+    BazarrConnection, BazarrLanguageProfile, BazarrProfileItem, BazarrSettings,
+};
+use servarr_crds::BazarrConfig;
+
+type Form = Vec<(String, String)>;
+
+fn field(key: impl Into<String>, value: impl ToString) -> (String, String) {
+    (key.into(), value.to_string())
+}
+
+/// Fields that point Bazarr's `section` (`sonarr` or `radarr`) at the app
+/// serving `base_url`, and turn the connection on. Bazarr's API key for
+/// the app is compared too, so a rotated key is picked up.
+pub fn connect(
+    section: &str,
+    base_url: &str,
+    api_key: &str,
+    enabled: bool,
+    current: &BazarrConnection,
+) -> Result<Form, url::ParseError> {
+    let url = url::Url::parse(base_url)?;
+    let wanted = BazarrConnection {
+        ip: url.host_str().unwrap_or_default().to_string(),
+        port: url.port_or_known_default().unwrap_or(80),
+        base_url: url.path().to_string(),
+        ssl: url.scheme() == "https",
+        apikey: api_key.to_string(),
+    };
+    let in_sync = enabled
+        && current.ip == wanted.ip
+        && current.port == wanted.port
+        && current.base_url.trim_matches('/') == wanted.base_url.trim_matches('/')
+        && current.ssl == wanted.ssl
+        && current.apikey == wanted.apikey;
+    if in_sync {
+        return Ok(Vec::new());
+    }
+    let key = |name: &str| format!("settings-{section}-{name}");
+    Ok(vec![
+        field(format!("settings-general-use_{section}"), true),
+        field(key("ip"), &wanted.ip),
+        field(key("port"), wanted.port),
+        field(key("base_url"), &wanted.base_url),
+        field(key("ssl"), wanted.ssl),
+        field(key("apikey"), &wanted.apikey),
+    ])
+}
+
+/// The field that turns Bazarr's `section` connection off.
+pub fn disconnect(section: &str) -> Form {
+    vec![field(format!("settings-general-use_{section}"), false)]
+}
+
+/// Fields for `config` that differ from `settings` and `profiles`, Bazarr's
+/// current settings and language profiles.
+pub fn config_form(
+    config: &BazarrConfig,
+    settings: &BazarrSettings,
+    profiles: &[BazarrLanguageProfile],
+) -> Form {
+    let mut form = Vec::new();
+
+    let mut wanted_providers = config.providers.clone();
+    wanted_providers.sort();
+    let mut current_providers = settings.general.enabled_providers.clone();
+    current_providers.sort();
+    if !config.providers.is_empty() && wanted_providers != current_providers {
+        form.extend(
+            config
+                .providers
+                .iter()
+                .map(|p| field("settings-general-enabled_providers", p)),
+        );
+    }
+
+    let merged = merge_profiles(profiles, config);
+    if let Some(ref merged) = merged {
+        let mut languages: Vec<&str> = Vec::new();
+        for item in merged.iter().flat_map(|p| &p.items) {
+            if !languages.contains(&item.language.as_str()) {
+                languages.push(&item.language);
+            }
+        }
+        form.extend(languages.iter().map(|l| field("languages-enabled", l)));
+        form.push(field(
+            "languages-profiles",
+            serde_json::to_string(merged).unwrap_or_default(),
+        ));
+    }
+
+    if let Some(ref name) = config.default_profile {
+        let id = merged
+            .as_deref()
+            .unwrap_or(profiles)
+            .iter()
+            .find(|p| &p.name == name)
+            .map(|p| p.profile_id);
+        if let Some(id) = id {
+            let general = &settings.general;
+            for (kind, enabled, current) in [
+                (
+                    "serie",
+                    general.serie_default_enabled,
+                    general.serie_default_profile,
+                ),
+                (
+                    "movie",
+                    general.movie_default_enabled,
+                    general.movie_default_profile,
+                ),
+            ] {
+                if !enabled || current != Some(id) {
+                    form.push(field(
+                        format!("settings-general-{kind}_default_enabled"),
+                        true,
+                    ));
+                    form.push(field(
+                        format!("settings-general-{kind}_default_profile"),
+                        id,
+                    ));
+                }
+            }
+        }
+    }
+
+    form
+}
+
+/// Bazarr's profiles with the ones of `config` created or updated by name,
+/// or `None` when none of them changes. An updated profile keeps its ID,
+/// its settings, and the flags of the languages it already had; a new one
+/// gets the next free ID.
+pub fn merge_profiles(
+    existing: &[BazarrLanguageProfile],
+    config: &BazarrConfig,
+) -> Option<Vec<BazarrLanguageProfile>> {
+    let mut merged = existing.to_vec();
+    let mut changed = false;
+    let mut next_id = existing.iter().map(|p| p.profile_id).max().unwrap_or(0) + 1;
+    for wanted in &config.language_profiles {
+        let current = merged.iter_mut().find(|p| p.name == wanted.name);
+        let current_languages: Vec<&str> = current
+            .as_ref()
+            .map(|p| p.items.iter().map(|i| i.language.as_str()).collect())
+            .unwrap_or_default();
+        if current.is_some() && current_languages == wanted.languages {
+            continue;
+        }
+        changed = true;
+        let items = |old: &[BazarrProfileItem]| -> Vec<BazarrProfileItem> {
+            wanted
+                .languages
+                .iter()
+                .zip(1..)
+                .map(|(language, id)| {
+                    let extra = old
+                        .iter()
+                        .find(|i| &i.language == language)
+                        .map(|i| i.extra.clone())
+                        .unwrap_or_else(default_item_flags);
+                    BazarrProfileItem {
+                        id,
+                        language: language.clone(),
+                        extra,
+                    }
+                })
+                .collect()
+        };
+        match current {
+            Some(profile) => {
+                profile.items = items(&profile.items);
+                // The cutoff refers to an item ID, which may now be another
+                // language.
+                profile.cutoff = None;
+            }
+            None => {
+                merged.push(BazarrLanguageProfile {
+                    profile_id: next_id,
+                    name: wanted.name.clone(),
+                    cutoff: None,
+                    items: items(&[]),
+                    extra: default_profile_fields(),
+                });
+                next_id += 1;
+            }
+        }
+    }
+    changed.then_some(merged)
+}
+
+/// Flags of a language added to a profile: full subtitles, not hearing
+/// impaired or forced ones.
+fn default_item_flags() -> serde_json::Map<String, Value> {
+    let Value::Object(flags) = json!({"audio_exclude": "False", "hi": "False", "forced": "False"})
+    else {
+        unreachable!()
+    };
+    flags
+}
+
+/// Settings of a new profile besides its languages, as the web UI creates it.
+fn default_profile_fields() -> serde_json::Map<String, Value> {
+    let Value::Object(fields) =
+        json!({"mustContain": [], "mustNotContain": [], "originalFormat": false})
+    else {
+        unreachable!()
+    };
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use servarr_api::bazarr::BazarrGeneralSettings;
+
+    fn profile(id: i64, name: &str, languages: &[&str]) -> BazarrLanguageProfile {
+        BazarrLanguageProfile {
+            profile_id: id,
+            name: name.into(),
+            cutoff: Some(1),
+            items: languages
+                .iter()
+                .zip(1..)
+                .map(|(l, id)| BazarrProfileItem {
+                    id,
+                    language: l.to_string(),
+                    extra: [("hi".to_string(), json!("True"))].into_iter().collect(),
+                })
+                .collect(),
+            extra: [("mustContain".to_string(), json!(["x"]))]
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    fn config(profiles: &[(&str, &[&str])]) -> BazarrConfig {
+        BazarrConfig {
+            language_profiles: profiles
+                .iter()
+                .map(|(name, languages)| servarr_crds::BazarrLanguageProfile {
+                    name: name.to_string(),
+                    languages: languages.iter().map(|l| l.to_string()).collect(),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn keys(form: &Form) -> Vec<&str> {
+        form.iter().map(|(k, _)| k.as_str()).collect()
+    }
+
+    #[test]
+    fn connect_only_when_different() {
+        let current = BazarrConnection {
+            ip: "sonarr.media.svc".into(),
+            port: 8989,
+            base_url: "/".into(),
+            ssl: false,
+            apikey: "key".into(),
+        };
+        let url = "http://sonarr.media.svc:8989";
+        assert!(
+            connect("sonarr", url, "key", true, &current)
+                .unwrap()
+                .is_empty()
+        );
+
+        // Turned off in the web UI
+        let form = connect("sonarr", url, "key", false, &current).unwrap();
+        assert_eq!(form[0], field("settings-general-use_sonarr", true));
+
+        // Rotated key
+        let form = connect("sonarr", url, "new", true, &current).unwrap();
+        assert!(form.contains(&field("settings-sonarr-apikey", "new")));
+        assert!(form.contains(&field("settings-sonarr-port", 8989)));
+
+        let form = connect(
+            "radarr",
+            "http://radarr-4k.media.svc:7878",
+            "k",
+            false,
+            &BazarrConnection::default(),
+        )
+        .unwrap();
+        assert!(form.contains(&field("settings-radarr-ip", "radarr-4k.media.svc")));
+        assert!(form.contains(&field("settings-radarr-ssl", false)));
+    }
+
+    #[test]
+    fn profiles_merge_by_name() {
+        let existing = vec![
+            profile(1, "English", &["en"]),
+            profile(3, "Manual", &["fr"]),
+        ];
+        assert!(merge_profiles(&existing, &config(&[("English", &["en"])])).is_none());
+
+        let merged = merge_profiles(
+            &existing,
+            &config(&[("English", &["de", "en"]), ("Dutch", &["nl"])]),
+        )
+        .unwrap();
+        assert_eq!(merged.len(), 3);
+        let english = &merged[0];
+        assert_eq!(english.profile_id, 1);
+        assert_eq!(english.cutoff, None);
+        assert_eq!(english.extra["mustContain"], json!(["x"]));
+        let languages: Vec<_> = english
+            .items
+            .iter()
+            .map(|i| (i.id, i.language.as_str()))
+            .collect();
+        assert_eq!(languages, [(1, "de"), (2, "en")]);
+        // en keeps its hearing-impaired flag, de gets the defaults
+        assert_eq!(english.items[1].extra["hi"], "True");
+        assert_eq!(english.items[0].extra["hi"], "False");
+        // Profiles not in the spec stay
+        assert_eq!(merged[1].name, "Manual");
+        let dutch = &merged[2];
+        assert_eq!((dutch.profile_id, dutch.name.as_str()), (4, "Dutch"));
+        assert_eq!(dutch.extra["originalFormat"], false);
+    }
+
+    #[test]
+    fn config_form_enables_languages_and_defaults() {
+        let mut config = config(&[("English", &["en"]), ("Dutch", &["nl", "en"])]);
+        config.default_profile = Some("Dutch".into());
+        config.providers = vec!["podnapisi".into(), "opensubtitlescom".into()];
+        let settings = BazarrSettings {
+            general: BazarrGeneralSettings {
+                enabled_providers: vec!["opensubtitlescom".into()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let existing = vec![
+            profile(1, "English", &["en"]),
+            profile(2, "Manual", &["fr"]),
+        ];
+        let form = config_form(&config, &settings, &existing);
+        assert_eq!(
+            keys(&form),
+            [
+                "settings-general-enabled_providers",
+                "settings-general-enabled_providers",
+                "languages-enabled",
+                "languages-enabled",
+                "languages-enabled",
+                "languages-profiles",
+                "settings-general-serie_default_enabled",
+                "settings-general-serie_default_profile",
+                "settings-general-movie_default_enabled",
+                "settings-general-movie_default_profile",
+            ]
+        );
+        let enabled: Vec<_> = form
+            .iter()
+            .filter(|(k, _)| k == "languages-enabled")
+            .map(|(_, v)| v.as_str())
+            .collect();
+        assert_eq!(enabled, ["en", "fr", "nl"]);
+        assert!(form.contains(&field("settings-general-movie_default_profile", 3)));
+    }
+
+    #[test]
+    fn config_form_is_empty_when_in_sync() {
+        let mut config = config(&[("English", &["en"])]);
+        config.default_profile = Some("English".into());
+        config.providers = vec!["podnapisi".into()];
+        let settings = BazarrSettings {
+            general: BazarrGeneralSettings {
+                enabled_providers: vec!["podnapisi".into()],
+                serie_default_enabled: true,
+                serie_default_profile: Some(1),
+                movie_default_enabled: true,
+                movie_default_profile: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let form = config_form(&config, &settings, &[profile(1, "English", &["en"])]);
+        assert!(form.is_empty(), "{form:?}");
+    }
+}
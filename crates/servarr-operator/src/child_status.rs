@@ -0,0 +1,488 @@
+//! Conditions read back from an app's child objects, so that
+//! `kubectl describe servarrapp` shows whether its storage, route, and
+//! certificate came up and not only that they were applied:
+//!
+//! - `StorageBound`: every PVC the app uses is `Bound`.
+//! - `RouteAccepted`: each Gateway the HTTPRoute or TCPRoute attaches to
+//!   has accepted it.
+//! - `CertificateReady`: cert-manager has issued the Certificate.
+//! - `MountsVerified`: the `persistence.verifyMounts` init container of the
+//!   newest pod found every NFS mount usable.
+//!
+//! Each condition is only reported when the app has that kind of child.
+
+use k8s_openapi::api::core::v1::{PersistentVolumeClaim, Pod};
+use kube::Client;
+use kube::api::{Api, DynamicObject, ListParams};
+use kube::discovery::ApiResource;
+use servarr_crds::{Condition, GeneratedResources, ServarrApp, condition_types};
+use servarr_resources::mount_check;
+use tracing::debug;
+
+/// The `StorageBound`, `RouteAccepted`, `CertificateReady`, and
+/// `MountsVerified` conditions for `app`'s children in `generated`.
+pub async fn observe(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    generated: &GeneratedResources,
+    now: &str,
+) -> Vec<Condition> {
+    let mut conditions = Vec::new();
+
+    let verify_mounts = app
+        .spec
+        .persistence
+        .as_ref()
+        .is_some_and(|p| p.verify_mounts && !p.nfs_mounts.is_empty());
+    if verify_mounts {
+        let selector = servarr_resources::common::selector_labels(app)
+            .iter()
+            .map(|(k, v)| format!("{k}={v}"))
+            .collect::<Vec<_>>()
+            .join(",");
+        conditions.push(
+            match Api::<Pod>::namespaced(client.clone(), ns)
+                .list(&ListParams::default().labels(&selector))
+                .await
+            {
+                Ok(pods) => mounts_condition(&pods.items, now),
+                Err(e) => {
+                    debug!(error = %e, "failed to list pods");
+                    unknown(
+                        condition_types::MOUNTS_VERIFIED,
+                        &format!("Failed to list pods: {e}"),
+                        now,
+                    )
+                }
+            },
+        );
+    }
+
+    if !generated.persistent_volume_claims.is_empty() {
+        let api = Api::<PersistentVolumeClaim>::namespaced(client.clone(), ns);
+        let mut claims = Vec::new();
+        let mut failed = None;
+        for name in &generated.persistent_volume_claims {
+            match api.get_opt(name).await {
+                Ok(pvc) => claims.push((name.as_str(), pvc)),
+                Err(e) => {
+                    debug!(%name, error = %e, "failed to read PVC");
+                    failed = Some(format!("Failed to read PVC {name}: {e}"));
+                    break;
+                }
+            }
+        }
+        conditions.push(match failed {
+            Some(message) => unknown(condition_types::STORAGE_BOUND, &message, now),
+            None => storage_condition(&claims, now),
+        });
+    }
+
+    let route = match (&generated.http_route, &generated.tcp_route) {
+        (_, Some(name)) => Some((name, route_resource("TCPRoute", "v1alpha2"))),
+        (Some(name), None) => Some((name, route_resource("HTTPRoute", "v1"))),
+        (None, None) => None,
+    };
+    if let Some((name, resource)) = route {
+        conditions.push(match get_dynamic(client, ns, &resource, name).await {
+            Ok(route) => route_condition(&resource.kind, route.as_ref(), now),
+            Err(message) => unknown(condition_types::ROUTE_ACCEPTED, &message, now),
+        });
+    }
+
+    if let Some(name) = &generated.certificate {
+        let resource = ApiResource {
+            group: "cert-manager.io".into(),
+            version: "v1".into(),
+            api_version: "cert-manager.io/v1".into(),
+            kind: "Certificate".into(),
+            plural: "certificates".into(),
+        };
+        conditions.push(match get_dynamic(client, ns, &resource, name).await {
+            Ok(cert) => certificate_condition(cert.as_ref(), now),
+            Err(message) => unknown(condition_types::CERTIFICATE_READY, &message, now),
+        });
+    }
+
+    conditions
+}
+
+fn route_resource(kind: &str, version: &str) -> ApiResource {
+    ApiResource {
+        group: "gateway.networking.k8s.io".into(),
+        version: version.into(),
+        api_version: format!("gateway.networking.k8s.io/{version}"),
+        kind: kind.into(),
+        plural: format!("{}s", kind.to_lowercase()),
+    }
+}
+
+async fn get_dynamic(
+    client: &Client,
+    ns: &str,
+    resource: &ApiResource,
+    name: &str,
+) -> Result<Option<DynamicObject>, String> {
+    Api::<DynamicObject>::namespaced_with(client.clone(), ns, resource)
+        .get_opt(name)
+        .await
+        .map_err(|e| {
+            debug!(%name, kind = %resource.kind, error = %e, "failed to read child");
+            format!("Failed to read {} {name}: {e}", resource.kind)
+        })
+}
+
+fn unknown(condition_type: &str, message: &str, now: &str) -> Condition {
+    Condition {
+        condition_type: condition_type.to_string(),
+        status: "Unknown".to_string(),
+        reason: "ReadFailed".to_string(),
+        message: message.to_string(),
+        last_transition_time: now.to_string(),
+    }
+}
+
+/// `StorageBound` from the app's PVCs, `None` for one that doesn't exist.
+fn storage_condition(claims: &[(&str, Option<PersistentVolumeClaim>)], now: &str) -> Condition {
+    let mut waiting = Vec::new();
+    let mut lost = Vec::new();
+    for (name, pvc) in claims {
+        let phase = pvc
+            .as_ref()
+            .and_then(|p| p.status.as_ref())
+            .and_then(|s| s.phase.as_deref());
+        match phase {
+            Some("Bound") => {}
+            Some("Lost") => lost.push(format!("{name} (Lost)")),
+            Some(phase) => waiting.push(format!("{name} ({phase})")),
+            None if pvc.is_none() => waiting.push(format!("{name} (NotFound)")),
+            None => waiting.push(format!("{name} (Pending)")),
+        }
+    }
+    let bound = claims.len() - waiting.len() - lost.len();
+    let summary = format!("{bound}/{} PVC(s) bound", claims.len());
+    if !lost.is_empty() {
+        lost.extend(waiting);
+        return Condition::fail(
+            condition_types::STORAGE_BOUND,
+            "ClaimLost",
+            &format!("{summary}: {}", lost.join(", ")),
+            now,
+        );
+    }
+    if !waiting.is_empty() {
+        return Condition::fail(
+            condition_types::STORAGE_BOUND,
+            "ClaimPending",
+            &format!("{summary}: {}", waiting.join(", ")),
+            now,
+        );
+    }
+    Condition::ok(condition_types::STORAGE_BOUND, "AllBound", &summary, now)
+}
+
+/// `MountsVerified` from the mount check init container of the newest pod
+/// that ran it. A failed check shows as the container terminating with a
+/// non-zero exit code, or waiting to restart after one.
+fn mounts_condition(pods: &[Pod], now: &str) -> Condition {
+    let newest_first = {
+        let mut pods: Vec<&Pod> = pods.iter().collect();
+        pods.sort_by_key(|p| std::cmp::Reverse(p.metadata.creation_timestamp.clone()));
+        pods
+    };
+    let check = newest_first.iter().find_map(|pod| {
+        pod.status
+            .as_ref()?
+            .init_container_statuses
+            .as_ref()?
+            .iter()
+            .find(|c| c.name == mount_check::CONTAINER_NAME)
+    });
+    let pending = |message: &str| Condition {
+        condition_type: condition_types::MOUNTS_VERIFIED.to_string(),
+        status: "Unknown".to_string(),
+        reason: "Pending".to_string(),
+        message: message.to_string(),
+        last_transition_time: now.to_string(),
+    };
+    let Some(check) = check else {
+        return pending("No pod has run the mount check yet");
+    };
+    let terminated = check
+        .state
+        .as_ref()
+        .and_then(|s| s.terminated.as_ref())
+        .or_else(|| check.last_state.as_ref()?.terminated.as_ref());
+    match terminated {
+        Some(t) if t.exit_code == 0 => Condition::ok(
+            condition_types::MOUNTS_VERIFIED,
+            "MountsUsable",
+            "Every NFS mount is readable, and writable unless readOnly",
+            now,
+        ),
+        Some(t) => Condition::fail(
+            condition_types::MOUNTS_VERIFIED,
+            "MountCheckFailed",
+            t.message
+                .as_deref()
+                .map(str::trim)
+                .filter(|m| !m.is_empty())
+                .unwrap_or("mount check failed"),
+            now,
+        ),
+        None => pending("The mount check is running"),
+    }
+}
+
+/// `RouteAccepted` from the `Accepted` condition each parent Gateway writes
+/// into `status.parents`.
+fn route_condition(kind: &str, route: Option<&DynamicObject>, now: &str) -> Condition {
+    let Some(route) = route else {
+        return Condition::fail(
+            condition_types::ROUTE_ACCEPTED,
+            "NotFound",
+            &format!("{kind} not found"),
+            now,
+        );
+    };
+    let parents = route
+        .data
+        .pointer("/status/parents")
+        .and_then(|p| p.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let mut accepted = 0;
+    for parent in parents {
+        let gateway = parent
+            .pointer("/parentRef/name")
+            .and_then(|n| n.as_str())
+            .unwrap_or("gateway");
+        match find_condition(parent, "Accepted") {
+            Some(("True", _, _)) => accepted += 1,
+            Some((_, reason, message)) => {
+                return Condition::fail(
+                    condition_types::ROUTE_ACCEPTED,
+                    reason.unwrap_or("NotAccepted"),
+                    &format!(
+                        "{kind} not accepted by {gateway}: {}",
+                        message.unwrap_or("")
+                    ),
+                    now,
+                );
+            }
+            None => {}
+        }
+    }
+    if accepted == 0 {
+        return Condition::fail(
+            condition_types::ROUTE_ACCEPTED,
+            "Pending",
+            &format!("Waiting for a Gateway to accept the {kind}"),
+            now,
+        );
+    }
+    Condition::ok(
+        condition_types::ROUTE_ACCEPTED,
+        "Accepted",
+        &format!("{kind} accepted by {accepted} Gateway(s)"),
+        now,
+    )
+}
+
+/// `CertificateReady` from the Certificate's own `Ready` condition.
+fn certificate_condition(cert: Option<&DynamicObject>, now: &str) -> Condition {
+    let Some(cert) = cert else {
+        return Condition::fail(
+            condition_types::CERTIFICATE_READY,
+            "NotFound",
+            "Certificate not found",
+            now,
+        );
+    };
+    let status = cert.data.get("status").unwrap_or(&serde_json::Value::Null);
+    match find_condition(status, "Ready") {
+        Some(("True", reason, message)) => Condition::ok(
+            condition_types::CERTIFICATE_READY,
+            reason.unwrap_or("Ready"),
+            message.unwrap_or("Certificate is up to date"),
+            now,
+        ),
+        Some((_, reason, message)) => Condition::fail(
+            condition_types::CERTIFICATE_READY,
+            reason.unwrap_or("NotReady"),
+            message.unwrap_or_default(),
+            now,
+        ),
+        None => Condition::fail(
+            condition_types::CERTIFICATE_READY,
+            "Pending",
+            "Waiting for cert-manager to issue the Certificate",
+            now,
+        ),
+    }
+}
+
+/// Status, reason, and message of the `condition_type` entry in
+/// `object.conditions`.
+fn find_condition<'a>(
+    object: &'a serde_json::Value,
+    condition_type: &str,
+) -> Option<(&'a str, Option<&'a str>, Option<&'a str>)> {
+    let condition = object
+        .get("conditions")?
+        .as_array()?
+        .iter()
+        .find(|c| c.get("type").and_then(|t| t.as_str()) == Some(condition_type))?;
+    let field = |key: &str| condition.get(key).and_then(|v| v.as_str());
+    Some((field("status")?, field("reason"), field("message")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::PersistentVolumeClaimStatus;
+    use serde_json::json;
+
+    fn pvc(phase: &str) -> Option<PersistentVolumeClaim> {
+        Some(PersistentVolumeClaim {
+            status: Some(PersistentVolumeClaimStatus {
+                phase: Some(phase.into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+
+    fn dynamic(data: serde_json::Value) -> DynamicObject {
+        let mut object = DynamicObject::new("x", &route_resource("HTTPRoute", "v1"));
+        object.data = data;
+        object
+    }
+
+    #[test]
+    fn storage_condition_lists_unbound_claims() {
+        let cond = storage_condition(&[("a-config", pvc("Bound"))], "t");
+        assert_eq!(cond.status, "True");
+        assert_eq!(cond.message, "1/1 PVC(s) bound");
+
+        let cond = storage_condition(
+            &[
+                ("a-config", pvc("Bound")),
+                ("a-media", pvc("Pending")),
+                ("a-cache", None),
+            ],
+            "t",
+        );
+        assert_eq!(cond.status, "False");
+        assert_eq!(cond.reason, "ClaimPending");
+        assert_eq!(
+            cond.message,
+            "1/3 PVC(s) bound: a-media (Pending), a-cache (NotFound)"
+        );
+
+        let cond = storage_condition(&[("a-media", pvc("Pending")), ("b", pvc("Lost"))], "t");
+        assert_eq!(cond.reason, "ClaimLost");
+        assert_eq!(
+            cond.message,
+            "0/2 PVC(s) bound: b (Lost), a-media (Pending)"
+        );
+    }
+
+    #[test]
+    fn route_condition_reads_parent_status() {
+        let accepted = |status: &str| {
+            json!({"parentRef": {"name": "public"}, "conditions": [
+                {"type": "ResolvedRefs", "status": "True"},
+                {"type": "Accepted", "status": status, "reason": "NotAllowedByListeners",
+                 "message": "no listener matches"}
+            ]})
+        };
+        let route = dynamic(json!({"status": {"parents": [accepted("True")]}}));
+        let cond = route_condition("HTTPRoute", Some(&route), "t");
+        assert_eq!(cond.status, "True");
+        assert_eq!(cond.message, "HTTPRoute accepted by 1 Gateway(s)");
+
+        let route = dynamic(json!({"status": {"parents": [accepted("True"), accepted("False")]}}));
+        let cond = route_condition("HTTPRoute", Some(&route), "t");
+        assert_eq!(cond.status, "False");
+        assert_eq!(cond.reason, "NotAllowedByListeners");
+        assert_eq!(
+            cond.message,
+            "HTTPRoute not accepted by public: no listener matches"
+        );
+
+        let cond = route_condition("TCPRoute", Some(&dynamic(json!({}))), "t");
+        assert_eq!(cond.reason, "Pending");
+        assert_eq!(route_condition("TCPRoute", None, "t").reason, "NotFound");
+    }
+
+    #[test]
+    fn certificate_condition_mirrors_ready() {
+        let cert = dynamic(json!({"status": {"conditions": [
+            {"type": "Ready", "status": "True", "reason": "Ready",
+             "message": "Certificate is up to date and has not expired"}
+        ]}}));
+        let cond = certificate_condition(Some(&cert), "t");
+        assert_eq!(cond.status, "True");
+        assert_eq!(
+            cond.message,
+            "Certificate is up to date and has not expired"
+        );
+
+        let cert = dynamic(json!({"status": {"conditions": [
+            {"type": "Ready", "status": "False", "reason": "DoesNotExist",
+             "message": "Issuing certificate as Secret does not exist"}
+        ]}}));
+        let cond = certificate_condition(Some(&cert), "t");
+        assert_eq!(cond.status, "False");
+        assert_eq!(cond.reason, "DoesNotExist");
+
+        assert_eq!(
+            certificate_condition(Some(&dynamic(json!({}))), "t").reason,
+            "Pending"
+        );
+    }
+
+    #[test]
+    fn mounts_condition_reads_the_newest_check() {
+        let pod = |created: &str, state: serde_json::Value, last: serde_json::Value| -> Pod {
+            serde_json::from_value(json!({
+                "metadata": {"creationTimestamp": created},
+                "status": {"initContainerStatuses": [{
+                    "name": mount_check::CONTAINER_NAME,
+                    "image": "sonarr",
+                    "imageID": "",
+                    "ready": false,
+                    "restartCount": 1,
+                    "state": state,
+                    "lastState": last,
+                }]},
+            }))
+            .unwrap()
+        };
+        let passed = pod(
+            "2025-01-01T00:00:00Z",
+            json!({"terminated": {"exitCode": 0}}),
+            json!({}),
+        );
+        let failing = pod(
+            "2025-01-02T00:00:00Z",
+            json!({"waiting": {"reason": "CrashLoopBackOff"}}),
+            json!({"terminated": {
+                "exitCode": 1,
+                "message": "mount check failed: /media (not readable)\n",
+            }}),
+        );
+
+        let cond = mounts_condition(std::slice::from_ref(&passed), "t");
+        assert_eq!(cond.status, "True");
+
+        let cond = mounts_condition(&[passed, failing], "t");
+        assert_eq!(cond.status, "False");
+        assert_eq!(cond.reason, "MountCheckFailed");
+        assert_eq!(cond.message, "mount check failed: /media (not readable)");
+
+        assert_eq!(mounts_condition(&[], "t").status, "Unknown");
+    }
+}
@@ -1,3 +1,6 @@
+use crate::events::EventThrottle;
+use crate::network_policy_enforcement::EnforcementCheck;
+use crate::requeue::ErrorBackoff;
 use kube::Client;
 use kube::runtime::events::Reporter;
 use servarr_crds::ImageSpec;
@@ -19,6 +22,29 @@ pub struct Context {
     /// set via the downward API). Set `WATCH_ALL_NAMESPACES=true` to opt into
     /// cluster-scoped mode.
     pub watch_namespace: Option<String>,
+    /// Operator-wide pod template paths excluded from Deployment drift checks,
+    /// loaded from the comma-separated `DRIFT_IGNORE_FIELDS` env var. Apps add
+    /// their own via `spec.driftIgnoreFields`.
+    pub drift_ignore_fields: Vec<String>,
+    /// Whether `spec.unsupportedOverrides` is honoured, from the
+    /// `ALLOW_UNSUPPORTED_OVERRIDES` env var. Off by default.
+    pub allow_unsupported_overrides: bool,
+    /// Node architecture (`kubernetes.io/arch` value) assumed for apps that
+    /// do not pin one through scheduling, from the `DEFAULT_NODE_ARCH` env
+    /// var. Selects architecture-specific default images.
+    pub default_arch: Option<String>,
+    /// Suppresses repeats of Normal events, configured by
+    /// `EVENT_THROTTLE_SECONDS`.
+    pub event_throttle: EventThrottle,
+    /// Whether NetworkPolicies are enforced, detected from the CNI or set by
+    /// `NETWORK_POLICY_ENFORCEMENT`.
+    pub network_policy_enforcement: EnforcementCheck,
+    /// Report what reconciles would change in `status.wouldChange` instead of
+    /// applying it, from `--dry-run` or the `DRY_RUN` env var.
+    pub dry_run: bool,
+    /// Consecutive failed reconciles per object, for `error_policy`'s
+    /// backoff.
+    pub error_backoff: ErrorBackoff,
 }
 
 impl Context {
@@ -68,30 +94,45 @@ impl Context {
             image_overrides,
             reporter,
             watch_namespace,
+            drift_ignore_fields: load_drift_ignore_fields(),
+            allow_unsupported_overrides: allow_unsupported_overrides(),
+            default_arch: load_default_arch(),
+            event_throttle: EventThrottle::from_env(),
+            network_policy_enforcement: EnforcementCheck::from_env(),
+            dry_run: false,
+            error_backoff: ErrorBackoff::default(),
         }
     }
 }
 
 /// Read DEFAULT_IMAGE_<APP>_REPO and DEFAULT_IMAGE_<APP>_TAG env vars for each known app.
-fn load_image_overrides() -> HashMap<String, ImageSpec> {
+pub(crate) fn load_image_overrides() -> HashMap<String, ImageSpec> {
     let apps = [
         "sonarr",
         "radarr",
         "lidarr",
+        "readarr",
+        "whisparr",
         "prowlarr",
         "sabnzbd",
         "transmission",
+        "qbittorrent",
         "tautulli",
         "overseerr",
+        "jellyseerr",
         "maintainerr",
         "jackett",
+        "bazarr",
+        "tdarr",
+        "tdarr-node",
     ];
 
     let mut overrides = HashMap::new();
 
     for app in &apps {
-        let repo_key = format!("DEFAULT_IMAGE_{}_REPO", app.to_uppercase());
-        let tag_key = format!("DEFAULT_IMAGE_{}_TAG", app.to_uppercase());
+        let env_name = app.to_uppercase().replace('-', "_");
+        let repo_key = format!("DEFAULT_IMAGE_{env_name}_REPO");
+        let tag_key = format!("DEFAULT_IMAGE_{env_name}_TAG");
 
         if let Ok(repo) = std::env::var(&repo_key) {
             let tag = std::env::var(&tag_key).unwrap_or_default();
@@ -111,6 +152,45 @@ fn load_image_overrides() -> HashMap<String, ImageSpec> {
     overrides
 }
 
+/// Read the operator-wide drift-ignore paths from `DRIFT_IGNORE_FIELDS`.
+fn load_drift_ignore_fields() -> Vec<String> {
+    let fields: Vec<String> = std::env::var("DRIFT_IGNORE_FIELDS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect();
+    if !fields.is_empty() {
+        info!(?fields, "loaded operator-wide drift ignore fields");
+    }
+    fields
+}
+
+/// Read the cluster's node architecture from `DEFAULT_NODE_ARCH`.
+pub(crate) fn load_default_arch() -> Option<String> {
+    let arch = std::env::var("DEFAULT_NODE_ARCH")
+        .ok()
+        .map(|v| v.trim().to_ascii_lowercase())
+        .filter(|v| !v.is_empty())?;
+    info!(%arch, "default node architecture");
+    Some(arch)
+}
+
+/// Read the `ALLOW_UNSUPPORTED_OVERRIDES` operator flag. Shared with the
+/// webhook so admission and reconciliation agree on whether raw overrides
+/// are allowed.
+pub fn allow_unsupported_overrides() -> bool {
+    std::env::var("ALLOW_UNSUPPORTED_OVERRIDES")
+        .is_ok_and(|v| v.eq_ignore_ascii_case("true") || v == "1" || v.eq_ignore_ascii_case("yes"))
+}
+
+/// Read the `DRY_RUN` operator flag, the env equivalent of `--dry-run`.
+pub fn dry_run() -> bool {
+    std::env::var("DRY_RUN")
+        .is_ok_and(|v| v.eq_ignore_ascii_case("true") || v == "1" || v.eq_ignore_ascii_case("yes"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,6 +278,69 @@ mod tests {
         );
     }
 
+    // ── load_drift_ignore_fields ──
+
+    #[test]
+    fn load_drift_ignore_fields_splits_and_trims() {
+        temp_env::with_var(
+            "DRIFT_IGNORE_FIELDS",
+            Some(" spec.containers[*].env , ,metadata.annotations['a/b']"),
+            || {
+                assert_eq!(
+                    load_drift_ignore_fields(),
+                    vec!["spec.containers[*].env", "metadata.annotations['a/b']"]
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn load_drift_ignore_fields_unset_is_empty() {
+        temp_env::with_var("DRIFT_IGNORE_FIELDS", None::<&str>, || {
+            assert!(load_drift_ignore_fields().is_empty());
+        });
+    }
+
+    // ── allow_unsupported_overrides ──
+
+    #[test]
+    fn allow_unsupported_overrides_defaults_off() {
+        temp_env::with_var("ALLOW_UNSUPPORTED_OVERRIDES", None::<&str>, || {
+            assert!(!allow_unsupported_overrides());
+        });
+        temp_env::with_var("ALLOW_UNSUPPORTED_OVERRIDES", Some("maybe"), || {
+            assert!(!allow_unsupported_overrides());
+        });
+    }
+
+    #[test]
+    fn allow_unsupported_overrides_accepts_true() {
+        temp_env::with_var("ALLOW_UNSUPPORTED_OVERRIDES", Some("TRUE"), || {
+            assert!(allow_unsupported_overrides());
+        });
+    }
+
+    // ── dry_run ──
+
+    #[test]
+    fn dry_run_reads_env() {
+        temp_env::with_var("DRY_RUN", None::<&str>, || assert!(!dry_run()));
+        temp_env::with_var("DRY_RUN", Some("1"), || assert!(dry_run()));
+        temp_env::with_var("DRY_RUN", Some("false"), || assert!(!dry_run()));
+    }
+
+    // ── load_default_arch ──
+
+    #[test]
+    fn load_default_arch_normalizes_value() {
+        temp_env::with_var("DEFAULT_NODE_ARCH", Some(" ARM64 "), || {
+            assert_eq!(load_default_arch().as_deref(), Some("arm64"));
+        });
+        temp_env::with_var("DEFAULT_NODE_ARCH", Some(""), || {
+            assert_eq!(load_default_arch(), None);
+        });
+    }
+
     // ── WATCH_ALL_NAMESPACES parsing (tested via Context::new internals) ──
     //
     // Context::new requires a kube::Client, which needs a real cluster.
@@ -1,34 +1,52 @@
-use std::str::FromStr;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
 use anyhow::Result;
 use futures::StreamExt;
-use k8s_openapi::api::apps::v1::Deployment;
-use k8s_openapi::api::core::v1::{ConfigMap, PersistentVolumeClaim, Secret, Service};
-use k8s_openapi::api::networking::v1::NetworkPolicy;
-use kube::api::{Api, Patch, PatchParams, PostParams};
+use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
+use k8s_openapi::api::autoscaling::v2::HorizontalPodAutoscaler;
+use k8s_openapi::api::core::v1::{
+    ConfigMap, PersistentVolumeClaim, PodTemplateSpec, Secret, Service,
+};
+use k8s_openapi::api::networking::v1::{Ingress, NetworkPolicy};
+use kube::api::{Api, DeleteParams, Patch, PatchParams, PostParams};
 use kube::runtime::controller::{Action, Controller};
 use kube::runtime::events::{Event, EventType, Recorder};
 use kube::runtime::reflector::{self, ObjectRef};
 use kube::runtime::watcher;
 use kube::{Client, CustomResourceExt, Resource, ResourceExt};
 use servarr_api::AppKind;
-use servarr_crds::{AppType, Condition, ServarrApp, ServarrAppStatus, condition_types};
+use servarr_crds::{
+    AppType, Condition, DriftReport, ErrorCategory, GeneratedResources, MaintenanceMode,
+    MaintenanceWindow, ReconcileError, RequeueSpec, ResourceGroupHashes, ServarrApp,
+    ServarrAppStatus, ServarrCredential, SyncStatus, UpdateMode, condition_types,
+};
 use thiserror::Error;
 use tokio::time::Duration;
 use tracing::{error, info, warn};
 
 use crate::context::Context;
+use crate::credential_controller;
+use crate::events::ThrottledRecorder;
+use crate::gpu;
+use crate::maintenance::{self, ActiveWindow};
 use crate::metrics::{
-    increment_backup_operations, increment_drift_corrections, increment_reconcile_total,
-    observe_reconcile_duration, set_managed_apps,
+    increment_backup_operations, increment_drift_corrections, increment_drift_path,
+    increment_queue_remediations, increment_reconcile_errors, increment_reconcile_total,
+    increment_trash_syncs, observe_reconcile_duration, set_managed_apps,
 };
+use crate::requeue::object_key;
+use crate::resource_groups::{self, ResourceGroup};
+use crate::storage_migration::{self, MigrationState};
+use crate::workload::{self, Workload};
 
-fn app_type_to_kind(app_type: &AppType) -> AppKind {
+pub(crate) fn app_type_to_kind(app_type: &AppType) -> AppKind {
     match app_type {
         AppType::Sonarr => AppKind::Sonarr,
         AppType::Radarr => AppKind::Radarr,
         AppType::Lidarr => AppKind::Lidarr,
+        AppType::Readarr => AppKind::Readarr,
+        AppType::Whisparr => AppKind::Whisparr,
         AppType::Prowlarr => AppKind::Prowlarr,
         other => panic!("AppKind not supported for {other:?}"),
     }
@@ -36,12 +54,200 @@ fn app_type_to_kind(app_type: &AppType) -> AppKind {
 
 const FIELD_MANAGER: &str = "servarr-operator";
 
+/// Setting or changing this annotation (usually to a timestamp) requests an
+/// immediate reconcile; the handled value is echoed to `status.reconcileTrigger`.
+const RECONCILE_NOW_ANNOTATION: &str = "servarr.dev/reconcile-now";
+const SYNC_NOW_ANNOTATION: &str = "servarr.dev/sync-now";
+
+/// Finalizers holding a deleted app until its Prowlarr and Overseerr
+/// registrations are removed.
+const PROWLARR_FINALIZER: &str = "servarr.dev/prowlarr-sync";
+const OVERSEERR_FINALIZER: &str = "servarr.dev/overseerr-sync";
+
+/// `"true"` suspends the app like `spec.suspend`, for apps whose spec is
+/// owned by something else, such as a MediaStack.
+pub const SUSPEND_ANNOTATION: &str = "servarr.dev/suspend";
+
+/// Version of the operator that last applied a Deployment, next to the
+/// builder's image provenance annotations.
+const OPERATOR_VERSION_ANNOTATION: &str = "servarr.dev/operator-version";
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Kubernetes API error: {0}")]
     Kube(#[source] kube::Error),
     #[error("Serialization error: {0}")]
     Serialization(#[source] serde_json::Error),
+    #[error("Invalid resource patch: {0}")]
+    Patch(#[source] servarr_resources::patches::PatchError),
+    #[error("Render error: {0}")]
+    Render(#[source] crate::render::Error),
+    #[error("App API error: {0}")]
+    ExternalApi(#[source] servarr_api::ApiError),
+    #[error("{}", secret_missing_message(.secret, .key.as_deref()))]
+    SecretMissing { secret: String, key: Option<String> },
+    #[error("Invalid spec: {0}")]
+    InvalidSpec(String),
+    #[error("Conflicting update: {0}")]
+    Conflict(#[source] kube::Error),
+}
+
+fn secret_missing_message(secret: &str, key: Option<&str>) -> String {
+    match key {
+        Some(key) => format!("Secret {secret} has no key {key}"),
+        None => format!("Secret {secret} does not exist"),
+    }
+}
+
+impl Error {
+    /// Wrap a Kubernetes API error, telling write conflicts (409) and
+    /// objects the API server rejects as invalid (422) from other failures.
+    pub fn kube(e: kube::Error) -> Self {
+        match e {
+            kube::Error::Api(ref status) if status.code == 409 => Self::Conflict(e),
+            kube::Error::Api(ref status) if status.code == 422 => {
+                Self::InvalidSpec(status.message.clone())
+            }
+            e => Self::Kube(e),
+        }
+    }
+
+    /// The category reported in `status.lastError` and the
+    /// `servarr_operator_reconcile_errors_total` metric.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::Kube(_) => ErrorCategory::Kubernetes,
+            Self::Serialization(_) | Self::Render(_) => ErrorCategory::Internal,
+            Self::Patch(_) | Self::InvalidSpec(_) => ErrorCategory::InvalidSpec,
+            Self::ExternalApi(_) => ErrorCategory::ExternalApi,
+            Self::SecretMissing { .. } => ErrorCategory::SecretMissing,
+            Self::Conflict(_) => ErrorCategory::Conflict,
+        }
+    }
+}
+
+/// Run a generated resource through the app's post-render `patches`.
+fn patched<T>(app: &ServarrApp, obj: T) -> Result<T, Error>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    servarr_resources::patches::apply(obj, &app.spec.patches).map_err(Error::Patch)
+}
+
+/// Condition describing `spec.unsupportedOverrides`: applied, ignored
+/// because the operator flag is off, or not in use.
+fn unsupported_overrides_condition(app: &ServarrApp, allowed: bool, now: &str) -> Condition {
+    let pod_spec = app
+        .spec
+        .unsupported_overrides
+        .as_ref()
+        .and_then(|o| o.pod_spec.as_ref());
+    match pod_spec {
+        Some(pod_spec) if allowed => Condition::ok(
+            condition_types::UNSUPPORTED_OVERRIDES,
+            "Applied",
+            &format!(
+                "unsupportedOverrides.podSpec is merged into the pod template; drift detection ignores {}",
+                servarr_resources::patches::pod_spec_override_paths(pod_spec).join(", ")
+            ),
+            now,
+        ),
+        Some(_) => Condition::ok(
+            condition_types::UNSUPPORTED_OVERRIDES,
+            "Disabled",
+            "unsupportedOverrides.podSpec is ignored because the operator was started without ALLOW_UNSUPPORTED_OVERRIDES=true",
+            now,
+        ),
+        None => Condition::fail(
+            condition_types::UNSUPPORTED_OVERRIDES,
+            "NotSet",
+            "No unsupported overrides in use",
+            now,
+        ),
+    }
+}
+
+/// Condition recording whether the default image exists for the app's node
+/// architecture.
+fn image_architecture_condition(
+    unsupported: Option<&servarr_crds::UnsupportedArchitecture>,
+    now: &str,
+) -> Condition {
+    match unsupported {
+        Some(e) => Condition::fail(
+            condition_types::IMAGE_ARCHITECTURE_SUPPORTED,
+            "UnsupportedArchitecture",
+            &e.to_string(),
+            now,
+        ),
+        None => Condition::ok(
+            condition_types::IMAGE_ARCHITECTURE_SUPPORTED,
+            "ImageAvailable",
+            "The container image is available for the node architecture",
+            now,
+        ),
+    }
+}
+
+/// The condition of type `condition_type` from the app's last status.
+fn previous_condition(app: &ServarrApp, condition_type: &str) -> Option<Condition> {
+    app.status
+        .as_ref()?
+        .conditions
+        .iter()
+        .find(|c| c.condition_type == condition_type)
+        .cloned()
+}
+
+/// Whether the last reconcile recorded a generated resource matching `f`.
+fn previously_generated(app: &ServarrApp, f: impl Fn(&GeneratedResources) -> bool) -> bool {
+    app.status
+        .as_ref()
+        .and_then(|s| s.generated_resources.as_ref())
+        .is_some_and(f)
+}
+
+/// The `Autoscaling` condition from the HPA's own conditions: failing when
+/// it reports that it can't scale or can't compute its metrics (usually
+/// because the container sets no resource requests or metrics-server is
+/// missing), and pending until the HPA controller first observes it.
+fn autoscaling_condition(hpa: &HorizontalPodAutoscaler, now: &str) -> Condition {
+    let conditions = hpa
+        .status
+        .as_ref()
+        .and_then(|s| s.conditions.as_deref())
+        .unwrap_or_default();
+    let failing = ["AbleToScale", "ScalingActive"]
+        .iter()
+        .filter_map(|t| conditions.iter().find(|c| c.type_ == *t))
+        .find(|c| c.status == "False");
+    if let Some(c) = failing {
+        return Condition::fail(
+            condition_types::AUTOSCALING,
+            c.reason.as_deref().unwrap_or(&c.type_),
+            c.message.as_deref().unwrap_or_default(),
+            now,
+        );
+    }
+    if conditions.is_empty() {
+        return Condition::fail(
+            condition_types::AUTOSCALING,
+            "Pending",
+            "Waiting for the HorizontalPodAutoscaler to report its status",
+            now,
+        );
+    }
+    let status = hpa.status.as_ref();
+    Condition::ok(
+        condition_types::AUTOSCALING,
+        "ScalingActive",
+        &format!(
+            "{} current replica(s), {} desired",
+            status.and_then(|s| s.current_replicas).unwrap_or(0),
+            status.map(|s| s.desired_replicas).unwrap_or(0),
+        ),
+        now,
+    )
 }
 
 pub fn print_crd() -> Result<()> {
@@ -51,32 +257,65 @@ pub fn print_crd() -> Result<()> {
     Ok(())
 }
 
-pub async fn run(client: kube::Client, server_state: crate::server::ServerState) -> Result<()> {
-    let ctx = Arc::new(Context::new(client.clone()));
+pub async fn run(
+    client: kube::Client,
+    server_state: crate::server::ServerState,
+    dry_run: bool,
+) -> Result<()> {
+    let ctx = Arc::new(Context {
+        dry_run,
+        ..Context::new(client.clone())
+    });
 
-    let (apps, deployments, services, config_maps, secrets) =
-        if let Some(ref ns) = ctx.watch_namespace {
-            (
-                Api::<ServarrApp>::namespaced(client.clone(), ns),
-                Api::<Deployment>::namespaced(client.clone(), ns),
-                Api::<Service>::namespaced(client.clone(), ns),
-                Api::<ConfigMap>::namespaced(client.clone(), ns),
-                Api::<Secret>::namespaced(client.clone(), ns),
-            )
-        } else {
-            (
-                Api::<ServarrApp>::all(client.clone()),
-                Api::<Deployment>::all(client.clone()),
-                Api::<Service>::all(client.clone()),
-                Api::<ConfigMap>::all(client.clone()),
-                Api::<Secret>::all(client.clone()),
-            )
-        };
+    let (
+        apps,
+        deployments,
+        statefulsets,
+        services,
+        config_maps,
+        secrets,
+        maintenance_windows,
+        credentials,
+    ) = if let Some(ref ns) = ctx.watch_namespace {
+        (
+            Api::<ServarrApp>::namespaced(client.clone(), ns),
+            Api::<Deployment>::namespaced(client.clone(), ns),
+            Api::<StatefulSet>::namespaced(client.clone(), ns),
+            Api::<Service>::namespaced(client.clone(), ns),
+            Api::<ConfigMap>::namespaced(client.clone(), ns),
+            Api::<Secret>::namespaced(client.clone(), ns),
+            Api::<MaintenanceWindow>::namespaced(client.clone(), ns),
+            Api::<ServarrCredential>::namespaced(client.clone(), ns),
+        )
+    } else {
+        (
+            Api::<ServarrApp>::all(client.clone()),
+            Api::<Deployment>::all(client.clone()),
+            Api::<StatefulSet>::all(client.clone()),
+            Api::<Service>::all(client.clone()),
+            Api::<ConfigMap>::all(client.clone()),
+            Api::<Secret>::all(client.clone()),
+            Api::<MaintenanceWindow>::all(client.clone()),
+            Api::<ServarrCredential>::all(client.clone()),
+        )
+    };
 
     // Build a reflector store so the secret watcher mapper can look up which
     // ServarrApps reference a changed secret without an async API call.
     let (app_store, app_writer) = reflector::store::<ServarrApp>();
     let app_store_for_watcher = app_store.clone();
+    let app_store_for_windows = app_store.clone();
+    let app_store_for_credentials = app_store.clone();
+    tokio::spawn(crate::metrics::sample_cache(
+        "servarrapp",
+        app_store,
+        |app| {
+            crate::metrics::generation_pending(
+                app.metadata.generation,
+                app.status.as_ref().map(|s| s.observed_generation),
+            )
+        },
+    ));
 
     // Background task: keep the store up-to-date by watching ServarrApps.
     // This runs independently of the Controller's own internal watcher.
@@ -99,9 +338,10 @@ pub async fn run(client: kube::Client, server_state: crate::server::ServerState)
 
     Controller::new(apps, watcher::Config::default())
         .owns(deployments, watcher::Config::default())
+        .owns(statefulsets, watcher::Config::default())
         .owns(services, watcher::Config::default())
         .owns(config_maps, watcher::Config::default())
-        // Watch admin-credential secrets: when a secret changes, enqueue all
+        // Watch admin-credential and auth secrets: when a secret changes, enqueue all
         // ServarrApps that reference it so credential rotation propagates immediately.
         .watches(secrets, watcher::Config::default(), move |secret| {
             let secret_name = secret.name_any();
@@ -109,20 +349,68 @@ pub async fn run(client: kube::Client, server_state: crate::server::ServerState)
                 .state()
                 .into_iter()
                 .filter(move |app| {
+                    let auth_secret = app.spec.auth.as_ref().and_then(|a| a.secret_name.as_ref());
                     app.spec
                         .admin_credentials
                         .as_ref()
                         .is_some_and(|ac| ac.secret_name == secret_name)
+                        || auth_secret == Some(&secret_name)
+                })
+                .map(|app| ObjectRef::from_obj(&*app))
+                .collect::<Vec<_>>()
+        })
+        // Watch MaintenanceWindows: edits (including new windows) re-evaluate
+        // every ServarrApp the window selects.
+        .watches(
+            maintenance_windows,
+            watcher::Config::default(),
+            move |window| {
+                let window_ns = window.namespace();
+                app_store_for_windows
+                    .state()
+                    .into_iter()
+                    .filter(move |app| {
+                        app.namespace() == window_ns && window.spec.matches(app.labels())
+                    })
+                    .map(|app| ObjectRef::from_obj(&*app))
+                    .collect::<Vec<_>>()
+            },
+        )
+        // Watch ServarrCredentials: the credential controller records a new
+        // checksum when a source Secret rotates, which re-projects the copy
+        // into every consuming app.
+        .watches(credentials, watcher::Config::default(), move |cred| {
+            let cred_ns = cred.namespace();
+            let cred_name = cred.name_any();
+            app_store_for_credentials
+                .state()
+                .into_iter()
+                .filter(move |app| {
+                    app.namespace() == cred_ns
+                        && app.spec.credentials.iter().any(|c| c.name == cred_name)
                 })
                 .map(|app| ObjectRef::from_obj(&*app))
                 .collect::<Vec<_>>()
         })
         .shutdown_on_signal()
-        .run(reconcile, error_policy, ctx)
-        .for_each(|res| async move {
-            match res {
-                Ok(o) => info!(?o, "reconciled"),
-                Err(e) => error!(%e, "reconcile error"),
+        .run(reconcile, error_policy, ctx.clone())
+        .for_each(|res| {
+            if let Ok((ref obj, _)) = res {
+                ctx.error_backoff
+                    .succeeded(&object_key(obj.namespace.as_deref(), &obj.name));
+            }
+            async move {
+                match res {
+                    Ok(o) => info!(?o, "reconciled"),
+                    // Apps without finalizers are gone before a reconcile sees them deleted
+                    Err(kube::runtime::controller::Error::ObjectNotFound(obj)) => {
+                        crate::metrics::forget_app(
+                            obj.namespace.as_deref().unwrap_or_default(),
+                            &obj.name,
+                        );
+                    }
+                    Err(e) => error!(%e, "reconcile error"),
+                }
             }
         })
         .await;
@@ -131,26 +419,63 @@ pub async fn run(client: kube::Client, server_state: crate::server::ServerState)
 }
 
 pub async fn reconcile(app: Arc<ServarrApp>, ctx: Arc<Context>) -> Result<Action, Error> {
+    let _in_flight = crate::metrics::InFlight::start("servarrapp");
     let client = &ctx.client;
     let name = app.name_any();
     let ns = app.namespace().unwrap_or_else(|| "default".into());
     let pp = PatchParams::apply(FIELD_MANAGER).force();
 
-    let recorder = Recorder::new(client.clone(), ctx.reporter.clone());
+    let recorder = ThrottledRecorder::new(
+        Recorder::new(client.clone(), ctx.reporter.clone()),
+        &ctx.event_throttle,
+    );
     let obj_ref = app.object_ref(&());
 
     info!(%name, %ns, app_type = %app.spec.app, "reconciling");
 
+    if ctx.dry_run {
+        return reconcile_dry_run(&ctx, &app, &ns, &recorder, &obj_ref).await;
+    }
+
     let app_type = app.spec.app.as_str();
     let start_time = std::time::Instant::now();
 
+    if app.metadata.deletion_timestamp.is_some() {
+        crate::metrics::forget_app(&ns, &name);
+    }
+
+    // Uploaded backups are kept or deleted per deletionPolicy before the
+    // app goes away
+    if app.metadata.deletion_timestamp.is_some()
+        && app
+            .finalizers()
+            .iter()
+            .any(|f| f == crate::backup_upload::FINALIZER)
+    {
+        if crate::backup_upload::cleanup(client, &app, &ns, &pp, &recorder, &obj_ref)
+            .await
+            .map_err(Error::kube)?
+        {
+            return Ok(Action::requeue(Duration::from_secs(10)));
+        }
+        let finalizers: Vec<&String> = app
+            .finalizers()
+            .iter()
+            .filter(|f| *f != crate::backup_upload::FINALIZER)
+            .collect();
+        Api::<ServarrApp>::namespaced(client.clone(), &ns)
+            .patch(
+                &name,
+                &PatchParams::default(),
+                &Patch::Merge(serde_json::json!({ "metadata": { "finalizers": finalizers } })),
+            )
+            .await
+            .map_err(Error::kube)?;
+        return Ok(Action::await_change());
+    }
+
     // Prowlarr cleanup finalizer for Servarr v3 apps
-    const PROWLARR_FINALIZER: &str = "servarr.dev/prowlarr-sync";
-    const OVERSEERR_FINALIZER: &str = "servarr.dev/overseerr-sync";
-    if matches!(
-        app.spec.app,
-        AppType::Sonarr | AppType::Radarr | AppType::Lidarr
-    ) {
+    if app.spec.app.downloads_media() {
         if app.metadata.deletion_timestamp.is_some() {
             // App is being deleted — clean up Prowlarr registration
             if let Err(e) =
@@ -183,7 +508,7 @@ pub async fn reconcile(app: Arc<ServarrApp>, ctx: Arc<Context>) -> Result<Action
             sa_api
                 .patch(&name, &PatchParams::default(), &Patch::Merge(patch))
                 .await
-                .map_err(Error::Kube)?;
+                .map_err(Error::kube)?;
             return Ok(Action::await_change());
         }
 
@@ -203,7 +528,7 @@ pub async fn reconcile(app: Arc<ServarrApp>, ctx: Arc<Context>) -> Result<Action
             sa_api
                 .patch(&name, &PatchParams::default(), &Patch::Merge(patch))
                 .await
-                .map_err(Error::Kube)?;
+                .map_err(Error::kube)?;
         }
 
         // Ensure Overseerr finalizer is present if an Overseerr with sync enabled exists
@@ -222,10 +547,27 @@ pub async fn reconcile(app: Arc<ServarrApp>, ctx: Arc<Context>) -> Result<Action
             sa_api
                 .patch(&name, &PatchParams::default(), &Patch::Merge(patch))
                 .await
-                .map_err(Error::Kube)?;
+                .map_err(Error::kube)?;
         }
     }
 
+    if app.metadata.deletion_timestamp.is_none() {
+        crate::backup_upload::ensure_finalizer(client, &app, &ns)
+            .await
+            .map_err(Error::kube)?;
+    }
+
+    // Restores, automatic updates, and drift corrections restart the pod,
+    // so they wait for the app's maintenance window when it has one.
+    let mut disruptions = maintenance::DisruptionGate::new(&app, chrono::Utc::now());
+
+    // A restore from object storage stages the archive, then hands off to
+    // the restore-from annotation below
+    let restore_staging =
+        crate::restore_object::reconcile(client, &app, &ns, &pp, &recorder, &obj_ref)
+            .await
+            .map_err(Error::kube)?;
+
     // Check for restore-from-backup annotation
     if let Some(restore_id) = app
         .metadata
@@ -233,82 +575,434 @@ pub async fn reconcile(app: Arc<ServarrApp>, ctx: Arc<Context>) -> Result<Action
         .as_ref()
         .and_then(|a| a.get("servarr.dev/restore-from"))
         .cloned()
+        && disruptions.allow("Restore", format!("Restore from backup {restore_id}"))
     {
         maybe_restore_backup(client, &app, &ns, &name, &restore_id, &recorder, &obj_ref).await;
     }
 
-    // Build and apply Deployment
-    let deployment = servarr_resources::deployment::build(&app, &ctx.image_overrides);
-    let deploy_api = Api::<Deployment>::namespaced(client.clone(), &ns);
-    tracing::debug!(%name, "SSA: applying Deployment");
-    deploy_api
-        .patch(&name, &pp, &Patch::Apply(&deployment))
+    // The annotation edit itself is what wakes the controller; all that is
+    // left is to make the manual trigger visible before the full pass runs.
+    let triggered = pending_reconcile_trigger(&app);
+    if let Some(ref trigger) = triggered {
+        info!(%name, %trigger, "manual reconcile requested");
+        recorder
+            .publish(
+                &Event {
+                    type_: EventType::Normal,
+                    reason: "ReconcileTriggered".into(),
+                    note: Some(format!("{RECONCILE_NOW_ANNOTATION}={trigger}")),
+                    action: "Reconcile".into(),
+                    secondary: None,
+                },
+                &obj_ref,
+            )
+            .await
+            .map_err(Error::kube)?;
+    }
+
+    // Maintenance windows covering this app can scale it to zero or freeze
+    // its Deployment. A window's own edits wake us via the controller watch;
+    // its start and end are handled by the requeue at the end of reconcile.
+    let maintenance = maintenance::plan(
+        &maintenance::list_windows(client, &ns).await,
+        app.labels(),
+        chrono::Utc::now(),
+    );
+
+    // Storage migrations come first: a finished one changes which PVC the
+    // Deployment mounts, and one in flight needs the app scaled down.
+    let mut migration = storage_migration::reconcile(client, &app, &ns, &pp, &recorder, &obj_ref)
+        .await
+        .map_err(Error::kube)?;
+    // A changed workloadType keeps mounting the claims that hold the data.
+    for (volume, claim) in workload::adopt_claims(client, &app, &ns, &mut migration.volume_claims)
+        .await
+        .map_err(Error::kube)?
+    {
+        info!(%name, %volume, %claim, "keeping existing claim for new workload type");
+    }
+    let app = migration.with_volume_claims(app);
+    let migrating = migration.copying();
+
+    // Copy shared ServarrCredentials into the app's own Secrets first, so
+    // the Deployment below carries their checksum and rolls on rotation.
+    let credentials = credential_controller::project(client, &app, &ns, &pp)
         .await
-        .map_err(Error::Kube)?;
-
-    // Check for drift: read back the Deployment and compare only operator-managed fields.
-    // Kubernetes adds default fields (terminationGracePeriodSeconds, dnsPolicy, etc.)
-    // so we check that our desired fields are a subset of the actual state.
-    tracing::debug!(%name, "getting Deployment for drift check");
-    let applied_deploy = deploy_api.get(&name).await.map_err(Error::Kube)?;
-    if let (Some(desired_spec), Some(actual_spec)) =
-        (deployment.spec.as_ref(), applied_deploy.spec.as_ref())
+        .map_err(Error::kube)?;
+    for problem in &credentials.problems {
+        warn!(%name, %problem, "credential not projected");
+        recorder
+            .publish(
+                &Event {
+                    type_: EventType::Warning,
+                    reason: "CredentialUnavailable".into(),
+                    note: Some(problem.clone()),
+                    action: "Reconcile".into(),
+                    secondary: None,
+                },
+                &obj_ref,
+            )
+            .await
+            .map_err(Error::kube)?;
+    }
+
+    // GPUs no node can provide would leave the pod Pending; unless the app
+    // requires them, build the Deployment without them.
+    let gpu_check = gpu::check(client, &app).await;
+    let fell_back_before = app.status.as_ref().is_some_and(|s| {
+        s.conditions.iter().any(|c| {
+            c.condition_type == condition_types::GPU_AVAILABLE && c.reason == "CpuFallback"
+        })
+    });
+    if let gpu::GpuCheck::FellBack(ref missing) = gpu_check
+        && !fell_back_before
+    {
+        warn!(%name, ?missing, "GPU unavailable, falling back to CPU");
+        recorder
+            .publish(
+                &Event {
+                    type_: EventType::Warning,
+                    reason: "GpuFallback".into(),
+                    note: Some(format!(
+                        "No node advertises {}; running without the GPU",
+                        missing.join(", ")
+                    )),
+                    action: "Reconcile".into(),
+                    secondary: None,
+                },
+                &obj_ref,
+            )
+            .await
+            .map_err(Error::kube)?;
+    }
+
+    // Automatic image updates pick their tag before the workload is built,
+    // so a new one rolls out on this pass.
+    let image_update = maybe_update_image(
+        &ctx,
+        &app,
+        &maintenance,
+        &mut disruptions,
+        &recorder,
+        &obj_ref,
+    )
+    .await;
+
+    // Build and apply the Deployment or StatefulSet. A raw podSpec override
+    // is merged before the post-render patches so patches can still adjust
+    // its result.
+    let mut built = gpu_check.apply(app.clone());
+    if let Some(image) = crate::image_update::upgraded_image(
+        &app,
+        image_update.as_ref(),
+        &ctx.image_overrides,
+        ctx.default_arch.as_deref(),
+    ) {
+        Arc::make_mut(&mut built).spec.image = Some(image);
+    }
+    let mut deployment = servarr_resources::deployment::build_for_arch(
+        &built,
+        &ctx.image_overrides,
+        ctx.default_arch.as_deref(),
+    );
+    let mut override_ignore_paths = Vec::new();
+    if let Some(pod_spec) = app
+        .spec
+        .unsupported_overrides
+        .as_ref()
+        .and_then(|o| o.pod_spec.as_ref())
+    {
+        if ctx.allow_unsupported_overrides {
+            deployment = servarr_resources::patches::merge_pod_spec(deployment, pod_spec)
+                .map_err(Error::Patch)?;
+            override_ignore_paths = servarr_resources::patches::pod_spec_override_paths(pod_spec);
+        } else {
+            warn!(%name, "unsupportedOverrides.podSpec set but ALLOW_UNSUPPORTED_OVERRIDES is off, ignoring");
+        }
+    }
+    let mut workload = match Workload::for_app(&app, deployment) {
+        Workload::Deployment(d) => Workload::Deployment(patched(&app, d)?),
+        Workload::StatefulSet(s) => Workload::StatefulSet(patched(&app, s)?),
+    };
+    let kind = workload.kind();
+    workload
+        .meta_mut()
+        .annotations
+        .get_or_insert_default()
+        .insert(
+            OPERATOR_VERSION_ANNOTATION.into(),
+            env!("CARGO_PKG_VERSION").into(),
+        );
+    if let Some(ref checksum) = credentials.checksum
+        && let Some(template) = workload.template_mut()
     {
-        let desired_json = serde_json::to_value(&desired_spec.template).unwrap_or_default();
-        let actual_json = serde_json::to_value(&actual_spec.template).unwrap_or_default();
-        if !json_is_subset(&desired_json, &actual_json) {
-            let diff = json_diff_paths(&desired_json, &actual_json, "".to_string());
-            warn!(%name, "deployment drift detected, re-applying");
-            tracing::debug!(%name, ?diff, "drift details");
+        template
+            .metadata
+            .get_or_insert_default()
+            .annotations
+            .get_or_insert_default()
+            .insert(
+                servarr_resources::credential::CHECKSUM_ANNOTATION.into(),
+                checksum.clone(),
+            );
+    }
+    let mut generated = GeneratedResources {
+        secrets: credentials.secrets,
+        ..Default::default()
+    };
+    match workload {
+        Workload::Deployment(_) => generated.deployment = Some(name.clone()),
+        Workload::StatefulSet(_) => generated.stateful_set = Some(name.clone()),
+    }
+    let suspension = suspension(&app, &maintenance, &chrono_now());
+    if suspension.is_some() || migrating {
+        workload.set_replicas(0);
+    } else if let Some(autoscaling) = servarr_resources::hpa::autoscaling(&app) {
+        // The workload leaves its replica count to the HPA, which won't scale
+        // up from zero, so bring it back after a suspension or migration.
+        if crate::workload::spec_replicas(client, &app, &ns)
+            .await
+            .map_err(Error::kube)?
+            == Some(0)
+        {
+            workload.set_replicas(autoscaling.min_replicas);
+        }
+    }
+    // Extra Tdarr nodes run the same pod template as the app's own node
+    let workers = match workload {
+        Workload::Deployment(ref d) => servarr_resources::deployment::tdarr_workers(&app, d),
+        Workload::StatefulSet(_) => Vec::new(),
+    };
+    generated.worker_deployments = workers
+        .iter()
+        .filter_map(|w| w.metadata.name.clone())
+        .collect();
+    let pvcs = servarr_resources::pvc::build_all(&app)
+        .into_iter()
+        .map(|pvc| patched(&app, pvc))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Without an explicit spec.image, refuse to schedule pods whose default
+    // image has no variant for the node architecture; they would only sit
+    // in ImagePullBackOff.
+    let unsupported_arch = match app.spec.image {
+        Some(_) => None,
+        None => servarr_resources::deployment::default_image(
+            &app,
+            &ctx.image_overrides,
+            ctx.default_arch.as_deref(),
+        )
+        .err(),
+    };
+    // Groups of child objects built from the same inputs as last time are
+    // not applied again until the next full resync. A workload left
+    // untouched on this pass records no hash, so it is applied next time.
+    let groups = resource_groups::GroupPlan::new(
+        &app,
+        ResourceGroupHashes {
+            workload: (unsupported_arch.is_none() && !maintenance.frozen())
+                .then(|| resource_groups::hash(&(&workload, &workers, &pvcs))),
+            networking: Some(resource_groups::hash(&resource_groups::networking_inputs(
+                &app,
+            ))),
+            config: Some(resource_groups::hash(&resource_groups::config_inputs(&app))),
+            external_sync: Some(resource_groups::hash(
+                &resource_groups::external_sync_inputs(&app),
+            )),
+            full_sync_time: None,
+        },
+        chrono::Utc::now(),
+        crate::requeue::settings().full_resync,
+        triggered.is_some(),
+    );
+    let workload_unchanged = groups.unchanged(ResourceGroup::Workload);
+    let networking_unchanged = groups.unchanged(ResourceGroup::Networking);
+    let config_unchanged = groups.unchanged(ResourceGroup::Config);
+    let external_sync_unchanged = groups.unchanged(ResourceGroup::ExternalSync);
+    if !groups.full() {
+        tracing::debug!(
+            %name,
+            workload_unchanged,
+            networking_unchanged,
+            config_unchanged,
+            external_sync_unchanged,
+            "selective reconcile"
+        );
+    }
+
+    if let Some(window) = maintenance.active.as_ref().filter(|_| maintenance.frozen()) {
+        tracing::debug!(%name, window = %window.name, "maintenance freeze: leaving {kind} untouched");
+    } else if let Some(e) = &unsupported_arch {
+        warn!(%name, error = %e, "not applying {kind}");
+        generated.deployment = None;
+        generated.stateful_set = None;
+        generated.worker_deployments.clear();
+        recorder
+            .publish(
+                &Event {
+                    type_: EventType::Warning,
+                    reason: "UnsupportedArchitecture".into(),
+                    note: Some(e.to_string()),
+                    action: "Reconcile".into(),
+                    secondary: None,
+                },
+                &obj_ref,
+            )
+            .await
+            .map_err(Error::kube)?;
+    } else if !workload_unchanged {
+        // The old workload goes first so its pod lets go of the volumes.
+        if let Some(stale) = workload::remove_stale(client, &app, &ns)
+            .await
+            .map_err(Error::kube)?
+        {
+            info!(%name, %stale, "workload type changed, deleted old workload");
             recorder
                 .publish(
                     &Event {
-                        type_: EventType::Warning,
-                        reason: "DriftDetected".into(),
-                        note: Some("Deployment pod template differs from desired state".into()),
-                        action: "DriftCheck".into(),
+                        type_: EventType::Normal,
+                        reason: "WorkloadTypeChanged".into(),
+                        note: Some(format!("Replaced {stale} {name} with a {kind}")),
+                        action: "Reconcile".into(),
                         secondary: None,
                     },
                     &obj_ref,
                 )
                 .await
-                .map_err(Error::Kube)?;
-            increment_drift_corrections(app_type, &ns, "Deployment");
-            // Re-apply to correct drift
-            tracing::debug!(%name, "SSA: re-applying Deployment (drift correction)");
-            deploy_api
-                .patch(&name, &pp, &Patch::Apply(&deployment))
+                .map_err(Error::kube)?;
+        }
+        tracing::debug!(%name, "SSA: applying {kind}");
+        workload
+            .apply(client, &ns, &name, &pp)
+            .await
+            .map_err(Error::kube)?;
+        if app.spec.app == AppType::TdarrNode {
+            tracing::debug!(%name, workers = workers.len(), "SSA: applying Tdarr node Deployments");
+            workload::apply_workers(client, &app, &ns, &workers, &pp)
+                .await
+                .map_err(Error::kube)?;
+        }
+    }
+    let mut last_drift = app.status.as_ref().and_then(|s| s.last_drift.clone());
+    if unsupported_arch.is_none() && !maintenance.frozen() {
+        // Check for drift: read back the workload and compare only operator-managed fields.
+        // Kubernetes adds default fields (terminationGracePeriodSeconds, dnsPolicy, etc.)
+        // so we check that our desired fields are a subset of the actual state.
+        tracing::debug!(%name, "getting {kind} for drift check");
+        let applied_template = workload::pod_template(client, &app, &ns)
+            .await
+            .map_err(Error::kube)?;
+        if workload_unchanged && applied_template.is_none() {
+            info!(%name, "{kind} missing, re-applying");
+            workload
+                .apply(client, &ns, &name, &pp)
                 .await
-                .map_err(Error::Kube)?;
+                .map_err(Error::kube)?;
+        } else if let (Some(desired_template), Some(actual_template)) =
+            (workload.template(), applied_template.as_ref())
+        {
+            let mut desired_json = serde_json::to_value(desired_template).unwrap_or_default();
+            let mut actual_json = serde_json::to_value(actual_template).unwrap_or_default();
+            // Exclude fields that admission webhooks are known to rewrite, both
+            // operator-wide and per app, so they don't cause perpetual re-applies.
+            // Fields set through unsupportedOverrides are left alone as well.
+            for path in ctx
+                .drift_ignore_fields
+                .iter()
+                .chain(&app.spec.drift_ignore_fields)
+                .chain(&override_ignore_paths)
+            {
+                match parse_drift_path(path) {
+                    Some(segments) => {
+                        remove_json_path(&mut desired_json, &segments);
+                        remove_json_path(&mut actual_json, &segments);
+                    }
+                    None => warn!(%name, %path, "ignoring unparseable driftIgnoreFields entry"),
+                }
+            }
+            align_quantities(&mut desired_json, &actual_json);
+            let drifted = !json_is_subset(&desired_json, &actual_json);
+            let diff = if drifted {
+                json_diff_paths(&desired_json, &actual_json, "".to_string())
+            } else {
+                Vec::new()
+            };
+            let report = drift_report(kind, &diff, &chrono_now());
+            if drifted
+                && !disruptions.allow(
+                    "DriftCorrection",
+                    format!(
+                        "{kind} pod template differs at {} path(s): {}",
+                        report.total_paths,
+                        report.paths.join(", ")
+                    ),
+                )
+            {
+                tracing::debug!(%name, ?diff, "drift correction waits for the maintenance window");
+            } else if drifted {
+                warn!(%name, "{kind} drift detected, re-applying");
+                tracing::debug!(%name, ?diff, "drift details");
+                for path in &report.paths {
+                    increment_drift_path(app_type, &ns, kind, path);
+                }
+                last_drift = Some(report);
+                recorder
+                    .publish(
+                        &Event {
+                            type_: EventType::Warning,
+                            reason: "DriftDetected".into(),
+                            note: Some(format!("{kind} pod template differs from desired state")),
+                            action: "DriftCheck".into(),
+                            secondary: None,
+                        },
+                        &obj_ref,
+                    )
+                    .await
+                    .map_err(Error::kube)?;
+                increment_drift_corrections(app_type, &ns, kind);
+                // Re-apply to correct drift
+                tracing::debug!(%name, "SSA: re-applying {kind} (drift correction)");
+                workload
+                    .apply(client, &ns, &name, &pp)
+                    .await
+                    .map_err(Error::kube)?;
+            }
         }
     }
 
     // Build and apply Service
-    let service = servarr_resources::service::build(&app);
-    let svc_api = Api::<Service>::namespaced(client.clone(), &ns);
-    tracing::debug!(%name, "SSA: applying Service");
-    svc_api
-        .patch(&name, &pp, &Patch::Apply(&service))
-        .await
-        .map_err(Error::Kube)?;
+    if !networking_unchanged {
+        let service = patched(&app, servarr_resources::service::build(&app))?;
+        let svc_api = Api::<Service>::namespaced(client.clone(), &ns);
+        tracing::debug!(%name, "SSA: applying Service");
+        svc_api
+            .patch(&name, &pp, &Patch::Apply(&service))
+            .await
+            .map_err(Error::kube)?;
+    }
+    generated.service = Some(name.clone());
 
-    // Build and apply PVCs (get-or-create to avoid mutating immutable fields)
-    let pvcs = servarr_resources::pvc::build_all(&app);
+    // Apply PVCs (get-or-create to avoid mutating immutable fields)
     let pvc_api = Api::<PersistentVolumeClaim>::namespaced(client.clone(), &ns);
     for pvc in &pvcs {
         let pvc_name = pvc.metadata.name.as_deref().unwrap_or("unknown");
-        match pvc_api.get(pvc_name).await {
-            Ok(_) => {
-                // PVC exists, don't modify (immutable fields)
-            }
-            Err(kube::Error::Api(err)) if err.code == 404 => {
-                pvc_api
-                    .patch(pvc_name, &pp, &Patch::Apply(pvc))
-                    .await
-                    .map_err(Error::Kube)?;
+        if !workload_unchanged {
+            match pvc_api.get(pvc_name).await {
+                Ok(_) => {
+                    // PVC exists, don't modify (immutable fields)
+                }
+                Err(kube::Error::Api(err)) if err.code == 404 => {
+                    pvc_api
+                        .patch(pvc_name, &pp, &Patch::Apply(pvc))
+                        .await
+                        .map_err(Error::kube)?;
+                }
+                Err(e) => return Err(Error::kube(e)),
             }
-            Err(e) => return Err(Error::Kube(e)),
         }
+        generated
+            .persistent_volume_claims
+            .push(pvc_name.to_string());
     }
 
     // Build and apply NetworkPolicy.
@@ -323,90 +1017,184 @@ pub async fn reconcile(app: Arc<ServarrApp>, ctx: Arc<Context>) -> Result<Action
         );
     }
     if network_policy_enabled {
-        let np = servarr_resources::networkpolicy::build(&app);
+        let np = patched(&app, servarr_resources::networkpolicy::build(&app))?;
         let np_api = Api::<NetworkPolicy>::namespaced(client.clone(), &ns);
-        tracing::debug!(%name, "SSA: applying NetworkPolicy");
-        np_api
-            .patch(&name, &pp, &Patch::Apply(&np))
-            .await
-            .map_err(Error::Kube)?;
+        if !networking_unchanged {
+            tracing::debug!(%name, "SSA: applying NetworkPolicy");
+            np_api
+                .patch(&name, &pp, &Patch::Apply(&np))
+                .await
+                .map_err(Error::kube)?;
+        }
+        generated.network_policy = Some(name.clone());
     }
+    let network_policy_enforced = if network_policy_enabled {
+        Some(
+            ctx.network_policy_enforcement
+                .check(client)
+                .await
+                .condition(&chrono_now()),
+        )
+    } else {
+        None
+    };
 
     // Build and apply ConfigMap (Transmission settings, SABnzbd whitelist)
     if let Some(cm) = servarr_resources::configmap::build(&app) {
+        let cm = patched(&app, cm)?;
         let cm_name = cm.metadata.name.as_deref().unwrap_or(&name);
         let cm_api = Api::<ConfigMap>::namespaced(client.clone(), &ns);
-        tracing::debug!(%name, cm_name, "SSA: applying ConfigMap");
-        cm_api
-            .patch(cm_name, &pp, &Patch::Apply(&cm))
-            .await
-            .map_err(Error::Kube)?;
+        if !config_unchanged {
+            tracing::debug!(%name, cm_name, "SSA: applying ConfigMap");
+            cm_api
+                .patch(cm_name, &pp, &Patch::Apply(&cm))
+                .await
+                .map_err(Error::kube)?;
+        }
+        generated.config_maps.push(cm_name.to_string());
     }
 
     // Build and apply tar-unpack ConfigMap (SABnzbd)
     if let Some(cm) = servarr_resources::configmap::build_tar_unpack(&app) {
+        let cm = patched(&app, cm)?;
         let cm_name = cm.metadata.name.as_deref().unwrap_or(&name);
         let cm_api = Api::<ConfigMap>::namespaced(client.clone(), &ns);
-        tracing::debug!(%name, cm_name, "SSA: applying tar-unpack ConfigMap");
-        cm_api
-            .patch(cm_name, &pp, &Patch::Apply(&cm))
-            .await
-            .map_err(Error::Kube)?;
+        if !config_unchanged {
+            tracing::debug!(%name, cm_name, "SSA: applying tar-unpack ConfigMap");
+            cm_api
+                .patch(cm_name, &pp, &Patch::Apply(&cm))
+                .await
+                .map_err(Error::kube)?;
+        }
+        generated.config_maps.push(cm_name.to_string());
     }
 
     // Build and apply Prowlarr custom definitions ConfigMap
     if let Some(cm) = servarr_resources::configmap::build_prowlarr_definitions(&app) {
+        let cm = patched(&app, cm)?;
         let cm_name = cm.metadata.name.as_deref().unwrap_or(&name);
         let cm_api = Api::<ConfigMap>::namespaced(client.clone(), &ns);
-        tracing::debug!(%name, cm_name, "SSA: applying Prowlarr definitions ConfigMap");
-        cm_api
-            .patch(cm_name, &pp, &Patch::Apply(&cm))
-            .await
-            .map_err(Error::Kube)?;
-    }
-
-    // Auto-create API key Secret if apiKeySecret is set and the Secret is absent.
-    // Uses a get-then-create pattern so an existing key is never overwritten.
-    tracing::debug!(%name, "ensuring API key secret");
-    ensure_api_key_secret(client, &app, &ns).await?;
-
-    // For Servarr v3 apps (Sonarr/Radarr/Lidarr/Prowlarr) credentials are applied
-    // via PUT /api/v3/config/host after each pod start (sync_admin_credentials).
-    // Patch a checksum annotation on the pod template so Kubernetes rolls pods
-    // when the Secret rotates, giving sync_admin_credentials a fresh target.
-    //
-    // Transmission MUST NOT get a checksum annotation: the LSIO init script
-    // rewrites settings.json on every container start, so a rolling update would
+        if !config_unchanged {
+            let changed = cm_api
+                .get_opt(cm_name)
+                .await
+                .map_err(Error::kube)?
+                .map(|live| changed_definitions(&live, &cm))
+                .unwrap_or_default();
+            tracing::debug!(%name, cm_name, "SSA: applying Prowlarr definitions ConfigMap");
+            cm_api
+                .patch(cm_name, &pp, &Patch::Apply(&cm))
+                .await
+                .map_err(Error::kube)?;
+            if !changed.is_empty() {
+                // The definitions checksum on the pod template rolls the pod,
+                // which is the only way Prowlarr rereads its Custom directory.
+                info!(%name, ?changed, "Prowlarr custom definitions changed, restarting");
+                recorder
+                    .publish(
+                        &Event {
+                            type_: EventType::Normal,
+                            reason: "DefinitionsChanged".into(),
+                            note: Some(format!(
+                                "Custom indexer definitions changed ({}); restarting Prowlarr to load them",
+                                changed.join(", ")
+                            )),
+                            action: "DefinitionsReload".into(),
+                            secondary: None,
+                        },
+                        &obj_ref,
+                    )
+                    .await
+                    .map_err(Error::kube)?;
+            }
+        }
+        generated.config_maps.push(cm_name.to_string());
+    }
+
+    // Build and apply the config file settings ConfigMap (spec.configFile)
+    if let Some(cm) = servarr_resources::config_file::build(&app) {
+        let cm = patched(&app, cm)?;
+        let cm_name = cm.metadata.name.as_deref().unwrap_or(&name);
+        let cm_api = Api::<ConfigMap>::namespaced(client.clone(), &ns);
+        if !config_unchanged {
+            tracing::debug!(%name, cm_name, "SSA: applying config file ConfigMap");
+            cm_api
+                .patch(cm_name, &pp, &Patch::Apply(&cm))
+                .await
+                .map_err(Error::kube)?;
+        }
+        generated.config_maps.push(cm_name.to_string());
+    }
+
+    // Auto-create API key Secret if apiKeySecret is set and the Secret is absent.
+    // Uses a get-then-create pattern so an existing key is never overwritten.
+    if !config_unchanged {
+        tracing::debug!(%name, "ensuring API key secret");
+        ensure_api_key_secret(client, &app, &ns).await?;
+    }
+    generated.api_key_secret = app.spec.api_key_ref().map(|(secret, _)| secret.to_string());
+
+    // For Servarr apps (Sonarr/Radarr/Lidarr/Readarr/Whisparr/Prowlarr)
+    // credentials are applied via PUT /api/v3/config/host after each pod start
+    // (sync_admin_credentials).
+    // Patch a checksum annotation on the pod template so Kubernetes rolls pods
+    // when the Secret rotates, giving sync_admin_credentials a fresh target.
+    //
+    // Transmission MUST NOT get a checksum annotation: the LSIO init script
+    // rewrites settings.json on every container start, so a rolling update would
     // race and reset auth to false before the next reconcile can re-apply it.
-    let needs_rollout_on_secret_change = matches!(
-        app.spec.app,
-        AppType::Sonarr | AppType::Radarr | AppType::Lidarr | AppType::Prowlarr
-    );
-    if needs_rollout_on_secret_change && let Some(ref ac) = app.spec.admin_credentials {
+    let needs_rollout_on_secret_change = app.spec.app.is_servarr_v3();
+    if needs_rollout_on_secret_change
+        && !maintenance.frozen()
+        && let Some(ref ac) = app.spec.admin_credentials
+    {
         tracing::debug!(%name, secret_name = %ac.secret_name, "patching admin credentials checksum");
         patch_admin_credentials_checksum(client, &app, &ns, &ac.secret_name).await?;
     }
 
     // Build and apply SSH bastion authorized-keys Secret
     if let Some(secret) = servarr_resources::secret::build_authorized_keys(&app) {
+        let secret = patched(&app, secret)?;
         let secret_name = secret.metadata.name.as_deref().unwrap_or(&name);
         let secret_api = Api::<Secret>::namespaced(client.clone(), &ns);
-        tracing::debug!(%name, secret_name, "SSA: applying SSH bastion authorized-keys Secret");
-        secret_api
-            .patch(secret_name, &pp, &Patch::Apply(&secret))
-            .await
-            .map_err(Error::Kube)?;
+        if !config_unchanged {
+            tracing::debug!(%name, secret_name, "SSA: applying SSH bastion authorized-keys Secret");
+            secret_api
+                .patch(secret_name, &pp, &Patch::Apply(&secret))
+                .await
+                .map_err(Error::kube)?;
+        }
+        generated.secrets.push(secret_name.to_string());
     }
 
     // Build and apply SSH bastion restricted-rsync ConfigMap
     if let Some(cm) = servarr_resources::configmap::build_ssh_bastion_restricted_rsync(&app) {
+        let cm = patched(&app, cm)?;
         let cm_name = cm.metadata.name.as_deref().unwrap_or(&name);
         let cm_api = Api::<ConfigMap>::namespaced(client.clone(), &ns);
-        tracing::debug!(%name, cm_name, "SSA: applying SSH bastion restricted-rsync ConfigMap");
-        cm_api
-            .patch(cm_name, &pp, &Patch::Apply(&cm))
-            .await
-            .map_err(Error::Kube)?;
+        if !config_unchanged {
+            tracing::debug!(%name, cm_name, "SSA: applying SSH bastion restricted-rsync ConfigMap");
+            cm_api
+                .patch(cm_name, &pp, &Patch::Apply(&cm))
+                .await
+                .map_err(Error::kube)?;
+        }
+        generated.config_maps.push(cm_name.to_string());
+    }
+
+    // Build and apply SSH bastion SFTP chroot ConfigMap
+    if let Some(cm) = servarr_resources::configmap::build_ssh_bastion_sftp_chroot(&app) {
+        let cm = patched(&app, cm)?;
+        let cm_name = cm.metadata.name.as_deref().unwrap_or(&name);
+        let cm_api = Api::<ConfigMap>::namespaced(client.clone(), &ns);
+        if !config_unchanged {
+            tracing::debug!(%name, cm_name, "SSA: applying SSH bastion SFTP chroot ConfigMap");
+            cm_api
+                .patch(cm_name, &pp, &Patch::Apply(&cm))
+                .await
+                .map_err(Error::kube)?;
+        }
+        generated.config_maps.push(cm_name.to_string());
     }
 
     // Build and apply HTTPRoute or TCPRoute (if gateway enabled)
@@ -422,12 +1210,16 @@ pub async fn reconcile(app: Arc<ServarrApp>, ctx: Arc<Context>) -> Result<Action
         };
         let route_api =
             Api::<kube::api::DynamicObject>::namespaced_with(client.clone(), &ns, &api_resource);
-        let route_data = serde_json::to_value(&route).map_err(Error::Serialization)?;
-        tracing::debug!(%name, "SSA: applying TCPRoute");
-        route_api
-            .patch(&name, &pp, &Patch::Apply(route_data))
-            .await
-            .map_err(Error::Kube)?;
+        let route_data =
+            serde_json::to_value(patched(&app, route)?).map_err(Error::Serialization)?;
+        if !networking_unchanged {
+            tracing::debug!(%name, "SSA: applying TCPRoute");
+            route_api
+                .patch(&name, &pp, &Patch::Apply(route_data))
+                .await
+                .map_err(Error::kube)?;
+        }
+        generated.tcp_route = Some(name.clone());
     } else if let Some(route) = servarr_resources::httproute::build(&app) {
         let api_resource = kube::discovery::ApiResource {
             group: "gateway.networking.k8s.io".into(),
@@ -438,12 +1230,38 @@ pub async fn reconcile(app: Arc<ServarrApp>, ctx: Arc<Context>) -> Result<Action
         };
         let route_api =
             Api::<kube::api::DynamicObject>::namespaced_with(client.clone(), &ns, &api_resource);
-        let route_data = serde_json::to_value(&route).map_err(Error::Serialization)?;
-        tracing::debug!(%name, "SSA: applying HTTPRoute");
-        route_api
-            .patch(&name, &pp, &Patch::Apply(route_data))
-            .await
-            .map_err(Error::Kube)?;
+        let route_data =
+            serde_json::to_value(patched(&app, route)?).map_err(Error::Serialization)?;
+        if !networking_unchanged {
+            tracing::debug!(%name, "SSA: applying HTTPRoute");
+            route_api
+                .patch(&name, &pp, &Patch::Apply(route_data))
+                .await
+                .map_err(Error::kube)?;
+        }
+        generated.http_route = Some(name.clone());
+    }
+
+    // Build and apply the Ingress (if ingress is set), or delete the one a
+    // previous spec.ingress created.
+    let ingress_api = Api::<Ingress>::namespaced(client.clone(), &ns);
+    if let Some(ingress) = servarr_resources::ingress::build(&app) {
+        let ingress = patched(&app, ingress)?;
+        if !networking_unchanged {
+            tracing::debug!(%name, "SSA: applying Ingress");
+            ingress_api
+                .patch(&name, &pp, &Patch::Apply(&ingress))
+                .await
+                .map_err(Error::kube)?;
+        }
+        generated.ingress = Some(name.clone());
+    } else if previously_generated(&app, |g| g.ingress.is_some()) {
+        info!(%name, "ingress removed: deleting Ingress");
+        match ingress_api.delete(&name, &DeleteParams::default()).await {
+            Ok(_) => {}
+            Err(kube::Error::Api(e)) if e.code == 404 => {}
+            Err(e) => return Err(Error::kube(e)),
+        }
     }
 
     // Build and apply cert-manager Certificate (if TLS is enabled)
@@ -457,19 +1275,67 @@ pub async fn reconcile(app: Arc<ServarrApp>, ctx: Arc<Context>) -> Result<Action
         };
         let cert_api =
             Api::<kube::api::DynamicObject>::namespaced_with(client.clone(), &ns, &api_resource);
-        let cert_data = serde_json::to_value(&cert).map_err(Error::Serialization)?;
-        tracing::debug!(%name, "SSA: applying Certificate");
-        cert_api
-            .patch(&name, &pp, &Patch::Apply(cert_data))
+        let cert_data = serde_json::to_value(patched(&app, cert)?).map_err(Error::Serialization)?;
+        let cert_secret = cert_data["spec"]["secretName"].as_str().map(String::from);
+        if !networking_unchanged {
+            tracing::debug!(%name, "SSA: applying Certificate");
+            cert_api
+                .patch(&name, &pp, &Patch::Apply(cert_data))
+                .await
+                .map_err(Error::kube)?;
+        }
+        generated.certificate = Some(name.clone());
+        generated.certificate_secret = cert_secret;
+    }
+
+    // Build and apply the HorizontalPodAutoscaler (if autoscaling is set),
+    // or delete the one a previous spec.autoscaling created.
+    let hpa_api = Api::<HorizontalPodAutoscaler>::namespaced(client.clone(), &ns);
+    let autoscaling = if let Some(hpa) = servarr_resources::hpa::build(&app) {
+        let hpa = patched(&app, hpa)?;
+        tracing::debug!(%name, "SSA: applying HorizontalPodAutoscaler");
+        let applied = hpa_api
+            .patch(&name, &pp, &Patch::Apply(&hpa))
             .await
-            .map_err(Error::Kube)?;
+            .map_err(Error::kube)?;
+        generated.horizontal_pod_autoscaler = Some(name.clone());
+        Some(autoscaling_condition(&applied, &chrono_now()))
+    } else {
+        if previously_generated(&app, |g| g.horizontal_pod_autoscaler.is_some()) {
+            info!(%name, "autoscaling removed: deleting HorizontalPodAutoscaler");
+            match hpa_api.delete(&name, &DeleteParams::default()).await {
+                Ok(_) => {}
+                Err(kube::Error::Api(e)) if e.code == 404 => {}
+                Err(e) => return Err(Error::kube(e)),
+            }
+        }
+        None
+    };
+
+    // The workload is applied either way, but a pod missing a Secret only
+    // shows CreateContainerConfigError; fail here so lastError says which.
+    // Secrets applied above are known to exist.
+    if let Some(template) = workload.template() {
+        check_required_secrets(client, &ns, template, &generated.secrets).await?;
     }
 
+    // A suspended app has no pods to talk to, so skip everything that calls
+    // its API until the suspension or maintenance window ends.
+    let suspended = suspension.is_some();
+
     // API health check and update check (non-blocking)
-    let (health_condition, update_condition) = check_api_health(client, &app, &ns).await;
+    let (health_condition, update_condition) = if suspended {
+        (None, None)
+    } else {
+        check_api_health(client, &app, &ns).await
+    };
 
     // Admin credential sync via live API (SABnzbd, Transmission, Jellyfin, Tautulli, Overseerr)
-    let admin_creds_condition = sync_admin_credentials(client, &app, &ns).await;
+    let admin_creds_condition = if suspended {
+        None
+    } else {
+        sync_admin_credentials(client, &app, &ns).await
+    };
     // If sync failed (app not ready yet), requeue sooner than the default 300s so
     // credentials are applied once the app becomes healthy.
     let admin_creds_pending = admin_creds_condition
@@ -477,34 +1343,279 @@ pub async fn reconcile(app: Arc<ServarrApp>, ctx: Arc<Context>) -> Result<Action
         .map(|c| c.status != "True")
         .unwrap_or(false);
 
+    // Web UI authentication for the *arr apps (spec.auth)
+    let auth_condition = if suspended {
+        None
+    } else {
+        sync_auth(client, &app, &ns).await
+    };
+    let auth_pending = auth_condition.as_ref().is_some_and(|c| c.status != "True");
+
+    // The settings below are pushed through the app's API. While their
+    // inputs are unchanged they are only re-checked on full resyncs, and the
+    // conditions of the last sync carry over.
+
+    // Web UI preferences for the *arr apps (spec.uiSettings)
+    let ui_settings_condition = if suspended {
+        None
+    } else if external_sync_unchanged {
+        previous_condition(&app, condition_types::UI_SETTINGS_SYNCED)
+    } else {
+        sync_ui_settings(client, &app, &ns).await
+    };
+    let ui_settings_pending = ui_settings_condition
+        .as_ref()
+        .is_some_and(|c| c.status != "True");
+
+    // Declarative custom formats (Sonarr/Radarr), applied ahead of the
+    // quality profiles that score them
+    let custom_formats_condition = if suspended {
+        None
+    } else if external_sync_unchanged {
+        previous_condition(&app, condition_types::CUSTOM_FORMATS_SYNCED)
+    } else {
+        sync_custom_formats(client, &app, &ns, &recorder, &obj_ref).await
+    };
+    let custom_formats_pending = custom_formats_condition
+        .as_ref()
+        .is_some_and(|c| c.status != "True");
+
+    // Declarative quality profiles (Sonarr/Radarr), drift-corrected every pass
+    let quality_profiles_condition = if suspended {
+        None
+    } else if external_sync_unchanged {
+        previous_condition(&app, condition_types::QUALITY_PROFILES_SYNCED)
+    } else {
+        sync_quality_profiles(client, &app, &ns, &recorder, &obj_ref).await
+    };
+    let quality_profiles_pending = quality_profiles_condition
+        .as_ref()
+        .is_some_and(|c| c.status != "True");
+
+    // Declarative root folders (Sonarr/Radarr)
+    let root_folders_condition = if suspended {
+        None
+    } else if external_sync_unchanged {
+        previous_condition(&app, condition_types::ROOT_FOLDERS_SYNCED)
+    } else {
+        sync_root_folders(client, &app, &ns, &recorder, &obj_ref).await
+    };
+    let root_folders_pending = root_folders_condition
+        .as_ref()
+        .is_some_and(|c| c.status != "True");
+
+    // Media management settings (Sonarr/Radarr)
+    let media_management_condition = if suspended {
+        None
+    } else if external_sync_unchanged {
+        previous_condition(&app, condition_types::MEDIA_MANAGEMENT_SYNCED)
+    } else {
+        sync_media_management(client, &app, &ns).await
+    };
+    let media_management_pending = media_management_condition
+        .as_ref()
+        .is_some_and(|c| c.status != "True");
+
+    // Monitoring settings (Sonarr/Radarr)
+    let monitoring_condition = if suspended {
+        None
+    } else if external_sync_unchanged {
+        previous_condition(&app, condition_types::MONITORING_SYNCED)
+    } else {
+        sync_monitoring(client, &app, &ns).await
+    };
+    let monitoring_pending = monitoring_condition
+        .as_ref()
+        .is_some_and(|c| c.status != "True");
+
+    // Declarative tags (Sonarr/Radarr/Lidarr)
+    let tags_condition = if suspended {
+        None
+    } else if external_sync_unchanged {
+        previous_condition(&app, condition_types::TAGS_SYNCED)
+    } else {
+        sync_tags(client, &app, &ns, &recorder, &obj_ref).await
+    };
+    let tags_pending = tags_condition.as_ref().is_some_and(|c| c.status != "True");
+
+    // Declarative indexers (Prowlarr)
+    let indexers_condition = if suspended {
+        None
+    } else if external_sync_unchanged {
+        previous_condition(&app, condition_types::INDEXERS_SYNCED)
+    } else {
+        sync_prowlarr_indexers(client, &app, &ns, &recorder, &obj_ref).await
+    };
+    let indexers_pending = indexers_condition
+        .as_ref()
+        .is_some_and(|c| c.status != "True");
+
+    // Declarative users (Jellyfin/Plex)
+    let users_condition = if suspended {
+        None
+    } else if external_sync_unchanged {
+        previous_condition(&app, condition_types::USERS_SYNCED)
+    } else {
+        sync_media_server_users(client, &app, &ns, &recorder, &obj_ref).await
+    };
+    let users_pending = users_condition.as_ref().is_some_and(|c| c.status != "True");
+
+    // Scheduled SQLite integrity check (backup.integrityCheck)
+    let integrity_condition =
+        crate::integrity_check::reconcile(client, &app, &ns, &pp, &recorder, &obj_ref).await;
+
+    // Transmission/SABnzbd download clients (Sonarr/Radarr/Lidarr/Readarr/Whisparr)
+    let download_clients_condition = if suspended {
+        None
+    } else {
+        sync_download_clients(client, &app, &ns, &recorder, &obj_ref).await
+    };
+    let download_clients_pending = download_clients_condition
+        .as_ref()
+        .is_some_and(|c| c.status != "True");
+
     // Backup scheduling (non-blocking)
-    let backup_status = maybe_run_backup(client, &app, &ns, &recorder, &obj_ref).await;
+    let backup_status = if suspended {
+        app.status.as_ref().and_then(|s| s.backup_status.clone())
+    } else {
+        maybe_run_backup(
+            client,
+            &app,
+            &ns,
+            maintenance.backup_cutoff,
+            &recorder,
+            &obj_ref,
+        )
+        .await
+    };
+    // Upload finished backups to backup.objectStorage.bucket (non-blocking)
+    let (backup_status, backup_uploading) = match crate::backup_upload::reconcile(
+        client,
+        &app,
+        &ns,
+        &pp,
+        &recorder,
+        &obj_ref,
+        backup_status.clone(),
+    )
+    .await
+    {
+        Ok(upload) => upload,
+        Err(e) => {
+            warn!(%name, error = %e, "backup upload failed");
+            (backup_status, false)
+        }
+    };
+
+    // API key rotation (non-blocking)
+    let api_key_rotation = if suspended {
+        app.status.as_ref().and_then(|s| s.api_key_rotation.clone())
+    } else {
+        crate::api_key_rotation::maybe_rotate(client, &app, &ns, &recorder, &obj_ref).await
+    };
+
+    // Queue remediation (non-blocking)
+    let queue_remediation = if suspended {
+        app.status
+            .as_ref()
+            .and_then(|s| s.queue_remediation.clone())
+    } else {
+        maybe_remediate_queue(client, &app, &ns, &recorder, &obj_ref).await
+    };
+
+    // TRaSH-guide sync (non-blocking)
+    let trash_sync = if suspended {
+        app.status.as_ref().and_then(|s| s.last_trash_sync.clone())
+    } else {
+        maybe_run_trash_sync(client, &app, &ns, &recorder, &obj_ref).await
+    };
+
+    // A sync-now request is served by the sync pass below, which runs on
+    // every reconcile; the annotation edit is what woke us up early.
+    let sync_now = app.annotations().contains_key(SYNC_NOW_ANNOTATION);
+    if sync_now {
+        if sync_requested(&app) {
+            info!(%name, "manual sync requested");
+            if let Err(e) = recorder
+                .publish(
+                    &Event {
+                        type_: EventType::Normal,
+                        reason: "SyncTriggered".into(),
+                        note: Some(format!("{SYNC_NOW_ANNOTATION}=true")),
+                        action: "Sync".into(),
+                        secondary: None,
+                    },
+                    &obj_ref,
+                )
+                .await
+            {
+                warn!(%name, error = %e, "failed to publish sync trigger event");
+            }
+        } else {
+            warn!(%name, "sync-now annotation set on an app without an enabled prowlarrSync, overseerrSync, or bazarrSync, or not \"true\", ignoring");
+        }
+    }
 
     // Prowlarr cross-app sync (only for Prowlarr-type apps with sync enabled)
+    let mut sync_result = None;
     if app.spec.app == AppType::Prowlarr
         && let Some(ref sync_spec) = app.spec.prowlarr_sync
         && sync_spec.enabled
     {
-        let target_ns = sync_spec.namespace_scope.as_deref().unwrap_or(&ns);
-        if let Err(e) = sync_prowlarr_apps(client, &app, target_ns, &recorder, &obj_ref).await {
+        let result = sync_prowlarr_apps(client, &app, sync_spec, &recorder, &obj_ref).await;
+        if let Err(ref e) = result {
             warn!(%name, error = %e, "Prowlarr sync failed");
         }
+        sync_result = Some(result);
     }
 
-    // Overseerr cross-app sync (only for Overseerr-type apps with sync enabled)
-    if app.spec.app == AppType::Overseerr
+    // Overseerr cross-app sync (only for Overseerr and Jellyseerr apps with
+    // sync enabled)
+    if runs_overseerr_sync(&app)
         && let Some(ref sync_spec) = app.spec.overseerr_sync
-        && sync_spec.enabled
     {
         let target_ns = sync_spec.namespace_scope.as_deref().unwrap_or(&ns);
-        if let Err(e) = sync_overseerr_servers(client, &app, target_ns, &recorder, &obj_ref).await {
+        let result = sync_overseerr_servers(client, &app, target_ns, &recorder, &obj_ref).await;
+        if let Err(ref e) = result {
             warn!(%name, error = %e, "Overseerr sync failed");
         }
+        sync_result = Some(result);
+    }
+
+    // Bazarr cross-app sync (only for Bazarr apps with sync enabled)
+    if app.spec.app == AppType::Bazarr
+        && let Some(ref sync_spec) = app.spec.bazarr_sync
+        && sync_spec.enabled
+    {
+        let target_ns = sync_spec.namespace_scope.as_deref().unwrap_or(&ns);
+        let result = sync_bazarr_apps(client, &app, target_ns, &recorder, &obj_ref).await;
+        if let Err(ref e) = result {
+            warn!(%name, error = %e, "Bazarr sync failed");
+        }
+        sync_result = Some(result);
+    }
+    if sync_now {
+        clear_sync_now(client, &ns, &name).await;
     }
+    let synced_apps = sync_result
+        .as_ref()
+        .and_then(|r| r.as_ref().ok())
+        .map(|r| r.synced);
+    let sync = sync_result.map(|result| {
+        sync_status(
+            app.status.as_ref().and_then(|s| s.sync_status.as_ref()),
+            &result,
+            &chrono_now(),
+        )
+    });
+
+    let window_opens = disruptions.opens;
+    let pending_actions = disruptions.into_pending();
+    let deferred = !pending_actions.is_empty();
 
     // Update status
     tracing::debug!(%name, "updating status");
-    update_status(
+    let status = update_status(
         client,
         &app,
         &ns,
@@ -513,11 +1624,81 @@ pub async fn reconcile(app: Arc<ServarrApp>, ctx: Arc<Context>) -> Result<Action
             health: health_condition,
             update: update_condition,
             admin_creds: admin_creds_condition,
+            auth: auth_condition,
+            ui_settings: ui_settings_condition,
+            custom_formats: custom_formats_condition,
+            quality_profiles: quality_profiles_condition,
+            root_folders: root_folders_condition,
+            media_management: media_management_condition,
+            monitoring: monitoring_condition,
+            tags: tags_condition,
+            indexers: indexers_condition,
+            users: users_condition,
+            integrity: integrity_condition,
+            download_clients: download_clients_condition,
+            maintenance: maintenance.active.clone(),
+            suspended: suspension,
+            unsupported_overrides: Some(unsupported_overrides_condition(
+                &app,
+                ctx.allow_unsupported_overrides,
+                &chrono_now(),
+            )),
+            image_architecture: Some(image_architecture_condition(
+                unsupported_arch.as_ref(),
+                &chrono_now(),
+            )),
+            network_policy_enforced,
+            gpu: gpu_check.condition(&chrono_now()),
+            autoscaling,
+            storage_migration: migration,
         },
-        backup_status,
+        TaskStatus {
+            backup: backup_status,
+            api_key_rotation,
+            queue_remediation,
+            trash_sync,
+            image_update,
+            pending_actions,
+            synced_apps,
+            sync,
+            resource_groups: Some(groups.record(
+                !(suspended
+                    || ui_settings_pending
+                    || custom_formats_pending
+                    || quality_profiles_pending
+                    || root_folders_pending
+                    || media_management_pending
+                    || monitoring_pending
+                    || tags_pending
+                    || indexers_pending
+                    || users_pending),
+                &chrono_now(),
+            )),
+            drift: last_drift,
+        },
+        Some(generated),
     )
     .await?;
 
+    // The first time the app is ready, publish the guidance as an Event too,
+    // like the notes Helm prints after an install.
+    if let Some(ref message) = status.message
+        && app.status.as_ref().is_none_or(|s| s.message.is_none())
+    {
+        let _ = recorder
+            .publish(
+                &Event {
+                    type_: EventType::Normal,
+                    reason: "Provisioned".into(),
+                    note: Some(message.clone()),
+                    action: "Provision".into(),
+                    secondary: None,
+                },
+                &obj_ref,
+            )
+            .await;
+    }
+
     info!(%name, "reconciliation complete");
 
     let duration = start_time.elapsed().as_secs_f64();
@@ -536,7 +1717,7 @@ pub async fn reconcile(app: Arc<ServarrApp>, ctx: Arc<Context>) -> Result<Action
         for a in &app_list.items {
             let key = (
                 a.spec.app.as_str().to_owned(),
-                a.namespace().unwrap_or_default(),
+                crate::metrics::namespace_label(&a.namespace().unwrap_or_default()),
             );
             *counts.entry(key).or_default() += 1;
         }
@@ -557,23 +1738,215 @@ pub async fn reconcile(app: Arc<ServarrApp>, ctx: Arc<Context>) -> Result<Action
             &obj_ref,
         )
         .await
-        .map_err(Error::Kube)?;
+        .map_err(Error::kube)?;
+
+    if app.status.as_ref().is_some_and(|s| s.last_error.is_some()) {
+        let patch = serde_json::json!({ "status": { "lastError": null } });
+        Api::<ServarrApp>::namespaced(client.clone(), &ns)
+            .patch_status(&name, &PatchParams::default(), &Patch::Merge(patch))
+            .await
+            .map_err(Error::kube)?;
+    }
+
+    // Come back quickly while the app is still settling (rollout, failing
+    // health check, pending admin credentials or storage copy) so status
+    // catches up without waiting for the next long resync.
+    let mut requeue = requeue_interval(
+        app.spec.requeue.as_ref(),
+        &status,
+        admin_creds_pending
+            || auth_pending
+            || ui_settings_pending
+            || custom_formats_pending
+            || quality_profiles_pending
+            || root_folders_pending
+            || media_management_pending
+            || monitoring_pending
+            || tags_pending
+            || indexers_pending
+            || users_pending
+            || restore_staging
+            || backup_uploading
+            || download_clients_pending
+            || migrating,
+    );
+    // Wake up right after the next maintenance boundary so suspends and
+    // pre-window backups start on time, and deferred actions run as soon
+    // as the app's own window opens.
+    let next_window = window_opens.filter(|_| deferred);
+    if let Some(until) = [maintenance.next_transition, next_window]
+        .into_iter()
+        .flatten()
+        .min()
+        .and_then(|t| (t - chrono::Utc::now()).to_std().ok())
+    {
+        requeue = requeue.min(until + Duration::from_secs(1));
+    }
+    Ok(Action::requeue(requeue))
+}
+
+/// The `Suspended` condition when `spec.suspend`, the suspend annotation,
+/// or a Suspend maintenance window scales the app to zero.
+pub(crate) fn suspension(
+    app: &ServarrApp,
+    maintenance: &maintenance::MaintenancePlan,
+    now: &str,
+) -> Option<Condition> {
+    let (reason, message) = if app.spec.suspend {
+        ("SuspendedBySpec", "Suspended by spec.suspend".to_string())
+    } else if app
+        .annotations()
+        .get(SUSPEND_ANNOTATION)
+        .is_some_and(|v| v == "true")
+    {
+        (
+            "SuspendedByAnnotation",
+            format!("Suspended by the {SUSPEND_ANNOTATION} annotation"),
+        )
+    } else {
+        let window = maintenance
+            .active
+            .as_ref()
+            .filter(|_| maintenance.suspended())?;
+        (
+            "MaintenanceWindow",
+            format!("Suspended by MaintenanceWindow {}", window.name),
+        )
+    };
+    Some(Condition::ok(
+        condition_types::SUSPENDED,
+        reason,
+        &message,
+        now,
+    ))
+}
+
+/// Secrets the pods of `template` can't start without, each with the keys
+/// they read from it. References marked `optional` are left out.
+pub(crate) fn required_secrets(template: &PodTemplateSpec) -> BTreeMap<String, BTreeSet<String>> {
+    let mut required: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let Some(spec) = template.spec.as_ref() else {
+        return required;
+    };
+    for container in spec
+        .containers
+        .iter()
+        .chain(spec.init_containers.iter().flatten())
+    {
+        for env in container.env.iter().flatten() {
+            if let Some(r) = env
+                .value_from
+                .as_ref()
+                .and_then(|v| v.secret_key_ref.as_ref())
+                && r.optional != Some(true)
+            {
+                required
+                    .entry(r.name.clone())
+                    .or_default()
+                    .insert(r.key.clone());
+            }
+        }
+        for from in container.env_from.iter().flatten() {
+            if let Some(r) = from.secret_ref.as_ref()
+                && r.optional != Some(true)
+            {
+                required.entry(r.name.clone()).or_default();
+            }
+        }
+    }
+    for volume in spec.volumes.iter().flatten() {
+        if let Some(secret) = volume.secret.as_ref()
+            && secret.optional != Some(true)
+            && let Some(ref name) = secret.secret_name
+        {
+            required.entry(name.clone()).or_default();
+        }
+    }
+    required
+}
+
+/// Fail with [`Error::SecretMissing`] when a Secret the pods of `template`
+/// need, or one of the keys they read from it, doesn't exist. Secrets in
+/// `applied` were just written by the operator and aren't looked up.
+async fn check_required_secrets(
+    client: &Client,
+    ns: &str,
+    template: &PodTemplateSpec,
+    applied: &[String],
+) -> Result<(), Error> {
+    let secrets = Api::<Secret>::namespaced(client.clone(), ns);
+    for (secret, keys) in required_secrets(template) {
+        if applied.contains(&secret) {
+            continue;
+        }
+        let Some(found) = secrets.get_opt(&secret).await.map_err(Error::kube)? else {
+            return Err(Error::SecretMissing { secret, key: None });
+        };
+        let data = found.data.unwrap_or_default();
+        if let Some(key) = keys.into_iter().find(|k| !data.contains_key(k)) {
+            return Err(Error::SecretMissing {
+                secret,
+                key: Some(key),
+            });
+        }
+    }
+    Ok(())
+}
 
-    // Use a short requeue interval when admin credential sync is still pending so
-    // the operator retries quickly once the app finishes starting up.
-    let requeue_secs = if admin_creds_pending { 30 } else { 300 };
-    Ok(Action::requeue(Duration::from_secs(requeue_secs)))
+/// Delay before the next reconcile of an app whose status is `status`: the
+/// short interval while it is not Ready, still Progressing, or failing its
+/// health check (or when `pending` work is waiting on it), the long one
+/// once it has settled. Apps without `spec.requeue` use the operator's
+/// intervals.
+pub(crate) fn requeue_interval(
+    spec: Option<&RequeueSpec>,
+    status: &ServarrAppStatus,
+    pending: bool,
+) -> Duration {
+    let operator = crate::requeue::settings();
+    let secs = |d: Duration| u32::try_from(d.as_secs()).unwrap_or(u32::MAX);
+    let defaults = RequeueSpec {
+        unsettled_seconds: secs(operator.unsettled),
+        settled_seconds: secs(operator.settled),
+    };
+    let spec = spec.unwrap_or(&defaults);
+    let is = |condition_type: &str, value: &str| {
+        status
+            .conditions
+            .iter()
+            .any(|c| c.condition_type == condition_type && c.status == value)
+    };
+    let unsettled = pending
+        || (!status.ready && !is(condition_types::SUSPENDED, "True"))
+        || is(condition_types::PROGRESSING, "True")
+        || is(condition_types::APP_HEALTHY, "False");
+    let secs = if unsettled {
+        spec.unsettled_seconds.min(spec.settled_seconds)
+    } else {
+        spec.settled_seconds
+    };
+    Duration::from_secs(u64::from(secs.max(MIN_REQUEUE_SECONDS)))
 }
 
+/// Floor for `spec.requeue`, so a misconfigured app can't hammer the API
+/// server.
+pub(crate) const MIN_REQUEUE_SECONDS: u32 = 5;
+
 /// Create the API key Secret the first time `apiKeySecret` is reconciled.
 ///
-/// A random 32-byte (64-char hex) key is generated and stored as `api-key`
-/// in the Secret.  For .NET-based apps (Sonarr, Radarr, Lidarr, Prowlarr)
-/// the deployment builder injects the value as the `APP__AUTH__APIKEY` env
-/// var so the app uses the operator-managed key from first startup.
+/// A random key from [`generate_api_key`] is stored in the Secret
+/// under `apiKeySecretKey` (default `api-key`).  For .NET-based apps (Sonarr,
+/// Radarr, Lidarr, Prowlarr) the deployment builder injects the value as the
+/// `APP__AUTH__APIKEY` env var so the app uses the operator-managed key from
+/// first startup.
 ///
 /// The Secret is owned by the ServarrApp so it is garbage-collected when the
 /// ServarrApp is deleted.  An existing Secret is never touched.
+///
+/// Two reconciles (or two operator replicas) can both see the Secret
+/// missing and race to create it. The loser's create fails with
+/// AlreadyExists; it keeps the winner's key and only checks that the
+/// Secret is really there.
 async fn ensure_api_key_secret(client: &Client, app: &ServarrApp, ns: &str) -> Result<(), Error> {
     let secret_name = match app.spec.api_key_secret.as_deref() {
         Some(s) => s,
@@ -586,31 +1959,41 @@ async fn ensure_api_key_secret(client: &Client, app: &ServarrApp, ns: &str) -> R
     match secret_api.get(secret_name).await {
         Ok(_) => return Ok(()),
         Err(kube::Error::Api(err)) if err.code == 404 => {}
-        Err(e) => return Err(Error::Kube(e)),
+        Err(e) => return Err(Error::kube(e)),
+    }
+
+    let Some(secret) = servarr_resources::secret::build_api_key(app, &generate_api_key()) else {
+        return Ok(());
+    };
+    info!(name = %app.name_any(), secret = %secret_name, "creating api-key secret");
+    match secret_api.create(&PostParams::default(), &secret).await {
+        Ok(_) => Ok(()),
+        Err(kube::Error::Api(err)) if err.code == 409 && err.reason == "AlreadyExists" => {
+            tracing::debug!(name = %app.name_any(), secret = %secret_name, "api-key secret created concurrently");
+            secret_api
+                .get(secret_name)
+                .await
+                .map(|_| ())
+                .map_err(Error::kube)
+        }
+        Err(e) => Err(Error::kube(e)),
     }
+}
 
+/// A random 32-character alphanumeric API key.
+pub(crate) fn generate_api_key() -> String {
     use rand::Rng as _;
-    let key: String = rand::rng()
+    rand::rng()
         .sample_iter(rand::distr::Alphanumeric)
         .take(32)
         .map(char::from)
-        .collect();
-
-    if let Some(secret) = servarr_resources::secret::build_api_key(app, &key) {
-        info!(name = %app.name_any(), secret = %secret_name, "creating api-key secret");
-        secret_api
-            .create(&PostParams::default(), &secret)
-            .await
-            .map_err(Error::Kube)?;
-    }
-
-    Ok(())
+        .collect()
 }
 
 /// Patch a SHA-256 checksum of the admin credentials onto the pod template annotation.
 ///
 /// When the referenced Secret rotates, the annotation changes, which causes
-/// Kubernetes to perform a rolling update of the Deployment so pods restart
+/// Kubernetes to perform a rolling update of the workload so pods restart
 /// with the new `secretKeyRef` env var values.
 async fn patch_admin_credentials_checksum(
     client: &Client,
@@ -652,13 +2035,12 @@ async fn patch_admin_credentials_checksum(
     let checksum = format!("{:x}", hasher.finalize());
 
     let name = app.name_any();
-    let deploy_api = Api::<Deployment>::namespaced(client.clone(), ns);
     // Use a separate field manager so this annotation does not conflict with
     // the main SSA apply (FIELD_MANAGER), which would strip it on the next cycle.
     let pp = PatchParams::apply("servarr-operator/admin-credentials").force();
     let patch = serde_json::json!({
         "apiVersion": "apps/v1",
-        "kind": "Deployment",
+        "kind": workload::workload_type(app).as_str(),
         "metadata": { "name": name },
         "spec": {
             "template": {
@@ -670,10 +2052,9 @@ async fn patch_admin_credentials_checksum(
             }
         }
     });
-    deploy_api
-        .patch(&name, &pp, &Patch::Apply(patch))
+    workload::patch(client, app, ns, &pp, &Patch::Apply(patch))
         .await
-        .map_err(Error::Kube)?;
+        .map_err(Error::kube)?;
 
     Ok(())
 }
@@ -687,6 +2068,10 @@ async fn patch_admin_credentials_checksum(
 /// This is idempotent and safe to call on every reconcile cycle.
 async fn sync_admin_credentials(client: &Client, app: &ServarrApp, ns: &str) -> Option<Condition> {
     let ac = app.spec.admin_credentials.as_ref()?;
+    // spec.auth takes over the *arr login (sync_auth).
+    if app.spec.auth.is_some() && app.spec.app.auth_env_prefix().is_some() {
+        return None;
+    }
     let now = chrono_now();
 
     let username = match servarr_api::read_secret_key(client, ns, &ac.secret_name, "username").await
@@ -726,8 +2111,8 @@ async fn sync_admin_credentials(client: &Client, app: &ServarrApp, ns: &str) ->
 
     let result: Result<(), String> = match app.spec.app {
         AppType::Sabnzbd => {
-            let api_key = match app.spec.api_key_secret.as_deref() {
-                Some(s) => match servarr_api::read_secret_key(client, ns, s, "api-key").await {
+            let api_key = match app.spec.api_key_ref() {
+                Some((s, key)) => match servarr_api::read_secret_key(client, ns, s, key).await {
                     Ok(k) => k,
                     Err(e) => {
                         return Some(Condition {
@@ -806,9 +2191,9 @@ async fn sync_admin_credentials(client: &Client, app: &ServarrApp, ns: &str) ->
                 .map_err(|e| e.to_string()),
             Err(e) => Err(e.to_string()),
         },
-        AppType::Overseerr => {
-            let api_key = match app.spec.api_key_secret.as_deref() {
-                Some(s) => match servarr_api::read_secret_key(client, ns, s, "api-key").await {
+        AppType::Overseerr | AppType::Jellyseerr => {
+            let api_key = match app.spec.api_key_ref() {
+                Some((s, key)) => match servarr_api::read_secret_key(client, ns, s, key).await {
                     Ok(k) => k,
                     Err(e) => {
                         return Some(Condition {
@@ -825,7 +2210,10 @@ async fn sync_admin_credentials(client: &Client, app: &ServarrApp, ns: &str) ->
                     return Some(Condition::fail(
                         condition_types::ADMIN_CREDENTIALS_CONFIGURED,
                         "NoApiKey",
-                        "Overseerr credential sync requires apiKeySecret to be set",
+                        &format!(
+                            "{} credential sync requires apiKeySecret to be set",
+                            app.spec.app
+                        ),
                         &now,
                     ));
                 }
@@ -835,9 +2223,14 @@ async fn sync_admin_credentials(client: &Client, app: &ServarrApp, ns: &str) ->
                 .await
                 .map_err(|e| e.to_string())
         }
-        AppType::Sonarr | AppType::Radarr | AppType::Lidarr | AppType::Prowlarr => {
-            let api_key = match app.spec.api_key_secret.as_deref() {
-                Some(s) => match servarr_api::read_secret_key(client, ns, s, "api-key").await {
+        AppType::Sonarr
+        | AppType::Radarr
+        | AppType::Lidarr
+        | AppType::Readarr
+        | AppType::Whisparr
+        | AppType::Prowlarr => {
+            let api_key = match app.spec.api_key_ref() {
+                Some((s, key)) => match servarr_api::read_secret_key(client, ns, s, key).await {
                     Ok(k) => k,
                     Err(e) => {
                         return Some(Condition {
@@ -896,22 +2289,1025 @@ async fn sync_admin_credentials(client: &Client, app: &ServarrApp, ns: &str) ->
     })
 }
 
-pub(crate) async fn check_api_health(
-    client: &Client,
-    app: &ServarrApp,
-    ns: &str,
-) -> (Option<Condition>, Option<Condition>) {
-    let _health_check = match app.spec.api_health_check.as_ref() {
-        Some(hc) if hc.enabled => hc,
-        _ => return (None, None),
+/// Apply `spec.auth` to Sonarr/Radarr/Lidarr/Prowlarr through
+/// `/api/v3/config/host` and report it as the `AuthConfigured` condition.
+///
+/// The method itself reaches the app through env vars (see the deployment
+/// builder), so External needs no API call. For Forms and Basic the login
+/// from `secretName` is written on every pass, which also repairs it after a
+/// pod restart or a change made in the UI.
+async fn sync_auth(client: &Client, app: &ServarrApp, ns: &str) -> Option<Condition> {
+    let auth = app.spec.auth.as_ref()?;
+    app.spec.app.auth_env_prefix()?;
+    let now = chrono_now();
+    let unknown = |reason: &str, message: String| {
+        Some(Condition {
+            condition_type: condition_types::AUTH_CONFIGURED.to_string(),
+            status: "Unknown".to_string(),
+            reason: reason.to_string(),
+            message,
+            last_transition_time: now.clone(),
+        })
     };
-    let secret_name = match app.spec.api_key_secret.as_deref() {
-        Some(s) => s,
+
+    if !auth.method.needs_credentials() {
+        return Some(Condition::ok(
+            condition_types::AUTH_CONFIGURED,
+            "Configured",
+            "External authentication set through the app's environment",
+            &now,
+        ));
+    }
+    let Some(secret_name) = auth.secret_name.as_deref() else {
+        return Some(Condition::fail(
+            condition_types::AUTH_CONFIGURED,
+            "NoSecret",
+            &format!(
+                "{} authentication requires auth.secretName",
+                auth.method.as_str()
+            ),
+            &now,
+        ));
+    };
+    let username = match servarr_api::read_secret_key(client, ns, secret_name, "username").await {
+        Ok(v) => v,
+        Err(e) => return unknown("SecretReadError", e.to_string()),
+    };
+    let password = match servarr_api::read_secret_key(client, ns, secret_name, "password").await {
+        Ok(v) => v,
+        Err(e) => return unknown("SecretReadError", e.to_string()),
+    };
+    let api_key = match app.spec.api_key_ref() {
+        Some((s, key)) => match servarr_api::read_secret_key(client, ns, s, key).await {
+            Ok(k) => k,
+            Err(e) => return unknown("ApiKeyReadError", e.to_string()),
+        },
+        None => String::new(),
+    };
+
+    let app_name = servarr_resources::common::app_name(app);
+    let defaults = servarr_crds::AppDefaults::for_app(&app.spec.app);
+    let svc_spec = app.spec.service.as_ref().unwrap_or(&defaults.service);
+    let port = svc_spec.ports.first().map(|p| p.port).unwrap_or(80);
+    let base_url = format!("http://{app_name}.{ns}.svc:{port}");
+    let api =
+        match servarr_api::ServarrClient::new(&base_url, &api_key, app_type_to_kind(&app.spec.app))
+        {
+            Ok(api) => api,
+            Err(e) => return unknown("ClientError", e.to_string()),
+        };
+
+    let method = auth.method.as_str().to_lowercase();
+    let required = if auth.disabled_for_local_addresses {
+        "disabledForLocalAddresses"
+    } else {
+        "enabled"
+    };
+    match api
+        .configure_auth(&method, Some(required), Some((&username, &password)))
+        .await
+    {
+        Ok(()) => Some(Condition::ok(
+            condition_types::AUTH_CONFIGURED,
+            "Configured",
+            &format!("{} authentication configured", auth.method.as_str()),
+            &now,
+        )),
+        Err(e) => {
+            warn!(app = %app_name, error = %e, "auth: failed to configure web UI authentication");
+            unknown("ApiUnavailable", e.to_string())
+        }
+    }
+}
+
+/// Client for a Servarr v3 app's API, for the `appConfig` and `uiSettings`
+/// syncs. On failure returns the `condition_type` condition to report:
+/// False without `apiKeySecret` (naming `field` as what needs it), Unknown
+/// when the key can't be read.
+async fn servarr_api_for_sync(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    condition_type: &str,
+    field: &str,
+) -> Result<servarr_api::ServarrClient, Condition> {
+    let (base_url, api_key) = api_endpoint_for_sync(client, app, ns, condition_type, field).await?;
+    servarr_api::ServarrClient::new(&base_url, &api_key, app_type_to_kind(&app.spec.app)).map_err(
+        |e| Condition {
+            condition_type: condition_type.to_string(),
+            status: "Unknown".to_string(),
+            reason: "ClientError".to_string(),
+            message: e.to_string(),
+            last_transition_time: chrono_now(),
+        },
+    )
+}
+
+/// The in-cluster URL of the app's API and the key from `apiKeySecret`,
+/// or the condition to report when the key can't be read.
+async fn api_endpoint_for_sync(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    condition_type: &str,
+    field: &str,
+) -> Result<(String, String), Condition> {
+    let now = chrono_now();
+    let unknown = |reason: &str, message: String| Condition {
+        condition_type: condition_type.to_string(),
+        status: "Unknown".to_string(),
+        reason: reason.to_string(),
+        message,
+        last_transition_time: now.clone(),
+    };
+    let Some((secret_name, key)) = app.spec.api_key_ref() else {
+        return Err(Condition::fail(
+            condition_type,
+            "NoApiKey",
+            &format!("{field} requires apiKeySecret to be set"),
+            &now,
+        ));
+    };
+    let api_key = servarr_api::read_secret_key(client, ns, secret_name, key)
+        .await
+        .map_err(|e| unknown("ApiKeyReadError", e.to_string()))?;
+    let app_name = servarr_resources::common::app_name(app);
+    let defaults = servarr_crds::AppDefaults::for_app(&app.spec.app);
+    let svc_spec = app.spec.service.as_ref().unwrap_or(&defaults.service);
+    let port = svc_spec.ports.first().map(|p| p.port).unwrap_or(80);
+    Ok((format!("http://{app_name}.{ns}.svc:{port}"), api_key))
+}
+
+/// Apply `spec.uiSettings` to a Servarr v3 app and report it as the
+/// `UiSettingsSynced` condition. `None` when no settings are declared.
+async fn sync_ui_settings(client: &Client, app: &ServarrApp, ns: &str) -> Option<Condition> {
+    let spec = app.spec.ui_settings.as_ref()?;
+    if !app.spec.app.is_servarr_v3() {
+        return None;
+    }
+    let api = match servarr_api_for_sync(
+        client,
+        app,
+        ns,
+        condition_types::UI_SETTINGS_SYNCED,
+        "uiSettings",
+    )
+    .await
+    {
+        Ok(api) => api,
+        Err(condition) => return Some(condition),
+    };
+    let now = chrono_now();
+    let app_name = servarr_resources::common::app_name(app);
+
+    match crate::ui_settings::sync(&api, spec).await {
+        Ok(changed) => {
+            if changed {
+                info!(app = %app_name, "UI settings applied");
+            }
+            Some(Condition::ok(
+                condition_types::UI_SETTINGS_SYNCED,
+                "Synced",
+                "UI settings match uiSettings",
+                &now,
+            ))
+        }
+        Err(e) => {
+            warn!(app = %app_name, error = %e, "UI settings: app API unavailable");
+            Some(Condition {
+                condition_type: condition_types::UI_SETTINGS_SYNCED.to_string(),
+                status: "Unknown".to_string(),
+                reason: "ApiUnavailable".to_string(),
+                message: e.to_string(),
+                last_transition_time: now,
+            })
+        }
+    }
+}
+
+/// Push `appConfig.customFormats` to Sonarr/Radarr and report the result
+/// as the `CustomFormatsSynced` condition. `None` when none are declared.
+async fn sync_custom_formats(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
+) -> Option<Condition> {
+    let formats = match app.spec.app_config {
+        Some(servarr_crds::AppConfig::Sonarr(ref c) | servarr_crds::AppConfig::Radarr(ref c)) => {
+            &c.custom_formats
+        }
+        _ => return None,
+    };
+    if formats.is_empty() || !matches!(app.spec.app, AppType::Sonarr | AppType::Radarr) {
+        return None;
+    }
+    let api = match servarr_api_for_sync(
+        client,
+        app,
+        ns,
+        condition_types::CUSTOM_FORMATS_SYNCED,
+        "customFormats",
+    )
+    .await
+    {
+        Ok(api) => api,
+        Err(condition) => return Some(condition),
+    };
+    let now = chrono_now();
+    let app_name = servarr_resources::common::app_name(app);
+
+    let outcome = match crate::custom_formats::sync(&api, formats).await {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            warn!(app = %app_name, error = %e, "custom formats: app API unavailable");
+            return Some(Condition {
+                condition_type: condition_types::CUSTOM_FORMATS_SYNCED.to_string(),
+                status: "Unknown".to_string(),
+                reason: "ApiUnavailable".to_string(),
+                message: e.to_string(),
+                last_transition_time: now,
+            });
+        }
+    };
+    for (name, error) in &outcome.failed {
+        warn!(app = %app_name, format = %name, %error, "custom format sync failed");
+    }
+    if !outcome.created.is_empty() || !outcome.updated.is_empty() {
+        info!(app = %app_name, created = ?outcome.created, updated = ?outcome.updated, "custom formats applied");
+        let _ = recorder
+            .publish(
+                &Event {
+                    type_: EventType::Normal,
+                    reason: "CustomFormatsApplied".into(),
+                    note: Some(outcome.message()),
+                    action: "CustomFormatSync".into(),
+                    secondary: None,
+                },
+                obj_ref,
+            )
+            .await;
+    }
+
+    Some(if outcome.failed.is_empty() {
+        Condition::ok(
+            condition_types::CUSTOM_FORMATS_SYNCED,
+            "Synced",
+            &outcome.message(),
+            &now,
+        )
+    } else {
+        Condition::fail(
+            condition_types::CUSTOM_FORMATS_SYNCED,
+            "SyncFailed",
+            &outcome.message(),
+            &now,
+        )
+    })
+}
+
+/// Push `appConfig.qualityProfiles` to Sonarr/Radarr and report the result
+/// as the `QualityProfilesSynced` condition. `None` when none are declared.
+async fn sync_quality_profiles(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
+) -> Option<Condition> {
+    let profiles = match app.spec.app_config {
+        Some(servarr_crds::AppConfig::Sonarr(ref c) | servarr_crds::AppConfig::Radarr(ref c)) => {
+            &c.quality_profiles
+        }
+        _ => return None,
+    };
+    if profiles.is_empty() || !matches!(app.spec.app, AppType::Sonarr | AppType::Radarr) {
+        return None;
+    }
+    let api = match servarr_api_for_sync(
+        client,
+        app,
+        ns,
+        condition_types::QUALITY_PROFILES_SYNCED,
+        "qualityProfiles",
+    )
+    .await
+    {
+        Ok(api) => api,
+        Err(condition) => return Some(condition),
+    };
+    let now = chrono_now();
+    let app_name = servarr_resources::common::app_name(app);
+
+    let outcome = match crate::quality_profiles::sync(&api, profiles).await {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            warn!(app = %app_name, error = %e, "quality profiles: app API unavailable");
+            return Some(Condition {
+                condition_type: condition_types::QUALITY_PROFILES_SYNCED.to_string(),
+                status: "Unknown".to_string(),
+                reason: "ApiUnavailable".to_string(),
+                message: e.to_string(),
+                last_transition_time: now,
+            });
+        }
+    };
+    for (name, error) in &outcome.failed {
+        warn!(app = %app_name, profile = %name, %error, "quality profile sync failed");
+    }
+    if !outcome.created.is_empty() || !outcome.updated.is_empty() {
+        info!(app = %app_name, created = ?outcome.created, updated = ?outcome.updated, "quality profiles applied");
+        let _ = recorder
+            .publish(
+                &Event {
+                    type_: EventType::Normal,
+                    reason: "QualityProfilesApplied".into(),
+                    note: Some(outcome.message()),
+                    action: "QualityProfileSync".into(),
+                    secondary: None,
+                },
+                obj_ref,
+            )
+            .await;
+    }
+
+    Some(if outcome.failed.is_empty() {
+        Condition::ok(
+            condition_types::QUALITY_PROFILES_SYNCED,
+            "Synced",
+            &outcome.message(),
+            &now,
+        )
+    } else {
+        Condition::fail(
+            condition_types::QUALITY_PROFILES_SYNCED,
+            "SyncFailed",
+            &outcome.message(),
+            &now,
+        )
+    })
+}
+
+/// Add (and with `autoRemove`, remove) Sonarr/Radarr root folders per
+/// `appConfig.rootFolders`, reported as the `RootFoldersSynced` condition.
+async fn sync_root_folders(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
+) -> Option<Condition> {
+    let spec = match app.spec.app_config {
+        Some(servarr_crds::AppConfig::Sonarr(ref c) | servarr_crds::AppConfig::Radarr(ref c)) => {
+            c.root_folders.as_ref()?
+        }
+        _ => return None,
+    };
+    if !matches!(app.spec.app, AppType::Sonarr | AppType::Radarr) {
+        return None;
+    }
+    let api = match servarr_api_for_sync(
+        client,
+        app,
+        ns,
+        condition_types::ROOT_FOLDERS_SYNCED,
+        "rootFolders",
+    )
+    .await
+    {
+        Ok(api) => api,
+        Err(condition) => return Some(condition),
+    };
+    let now = chrono_now();
+    let app_name = servarr_resources::common::app_name(app);
+
+    let outcome = match crate::root_folders::sync(&api, spec).await {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            warn!(app = %app_name, error = %e, "root folders: app API unavailable");
+            return Some(Condition {
+                condition_type: condition_types::ROOT_FOLDERS_SYNCED.to_string(),
+                status: "Unknown".to_string(),
+                reason: "ApiUnavailable".to_string(),
+                message: e.to_string(),
+                last_transition_time: now,
+            });
+        }
+    };
+    for (path, error) in &outcome.failed {
+        warn!(app = %app_name, %path, %error, "root folder sync failed");
+    }
+    if !outcome.added.is_empty() || !outcome.removed.is_empty() {
+        info!(app = %app_name, added = ?outcome.added, removed = ?outcome.removed, "root folders applied");
+        let _ = recorder
+            .publish(
+                &Event {
+                    type_: EventType::Normal,
+                    reason: "RootFoldersApplied".into(),
+                    note: Some(outcome.message()),
+                    action: "RootFolderSync".into(),
+                    secondary: None,
+                },
+                obj_ref,
+            )
+            .await;
+    }
+
+    Some(if outcome.failed.is_empty() {
+        Condition::ok(
+            condition_types::ROOT_FOLDERS_SYNCED,
+            "Synced",
+            &outcome.message(),
+            &now,
+        )
+    } else {
+        Condition::fail(
+            condition_types::ROOT_FOLDERS_SYNCED,
+            "SyncFailed",
+            &outcome.message(),
+            &now,
+        )
+    })
+}
+
+/// Push `appConfig.mediaManagement` to Sonarr/Radarr and report the result
+/// as the `MediaManagementSynced` condition. `None` when it isn't declared.
+async fn sync_media_management(client: &Client, app: &ServarrApp, ns: &str) -> Option<Condition> {
+    let spec = match app.spec.app_config {
+        Some(servarr_crds::AppConfig::Sonarr(ref c) | servarr_crds::AppConfig::Radarr(ref c)) => {
+            c.media_management.as_ref()?
+        }
+        _ => return None,
+    };
+    if !matches!(app.spec.app, AppType::Sonarr | AppType::Radarr) {
+        return None;
+    }
+    let api = match servarr_api_for_sync(
+        client,
+        app,
+        ns,
+        condition_types::MEDIA_MANAGEMENT_SYNCED,
+        "mediaManagement",
+    )
+    .await
+    {
+        Ok(api) => api,
+        Err(condition) => return Some(condition),
+    };
+    let now = chrono_now();
+    let app_name = servarr_resources::common::app_name(app);
+
+    match crate::media_management::sync(&api, spec).await {
+        Ok(changed) => {
+            if changed {
+                info!(app = %app_name, "media management settings applied");
+            }
+            Some(Condition::ok(
+                condition_types::MEDIA_MANAGEMENT_SYNCED,
+                "Synced",
+                "Media management settings match appConfig.mediaManagement",
+                &now,
+            ))
+        }
+        Err(e) => {
+            warn!(app = %app_name, error = %e, "media management: app API unavailable");
+            Some(Condition {
+                condition_type: condition_types::MEDIA_MANAGEMENT_SYNCED.to_string(),
+                status: "Unknown".to_string(),
+                reason: "ApiUnavailable".to_string(),
+                message: e.to_string(),
+                last_transition_time: now,
+            })
+        }
+    }
+}
+
+/// Apply `appConfig.monitoring` to Sonarr or Radarr, reported as the
+/// `MonitoringSynced` condition.
+async fn sync_monitoring(client: &Client, app: &ServarrApp, ns: &str) -> Option<Condition> {
+    let spec = match app.spec.app_config {
+        Some(servarr_crds::AppConfig::Sonarr(ref c) | servarr_crds::AppConfig::Radarr(ref c)) => {
+            c.monitoring.as_ref()?
+        }
+        _ => return None,
+    };
+    if !matches!(app.spec.app, AppType::Sonarr | AppType::Radarr) {
+        return None;
+    }
+    let api = match servarr_api_for_sync(
+        client,
+        app,
+        ns,
+        condition_types::MONITORING_SYNCED,
+        "monitoring",
+    )
+    .await
+    {
+        Ok(api) => api,
+        Err(condition) => return Some(condition),
+    };
+    let now = chrono_now();
+    let app_name = servarr_resources::common::app_name(app);
+
+    match crate::monitoring::sync(&api, &app.spec.app, spec).await {
+        Ok(changed) => {
+            if changed {
+                info!(app = %app_name, "monitoring settings applied");
+            }
+            Some(Condition::ok(
+                condition_types::MONITORING_SYNCED,
+                "Synced",
+                "Monitoring settings match appConfig.monitoring",
+                &now,
+            ))
+        }
+        Err(e) => {
+            warn!(app = %app_name, error = %e, "monitoring: app API unavailable");
+            Some(Condition {
+                condition_type: condition_types::MONITORING_SYNCED.to_string(),
+                status: "Unknown".to_string(),
+                reason: "ApiUnavailable".to_string(),
+                message: e.to_string(),
+                last_transition_time: now,
+            })
+        }
+    }
+}
+
+/// Create the tags in `appConfig.tags` that Sonarr, Radarr, or Lidarr
+/// lacks, reported as the `TagsSynced` condition. `None` when none are
+/// declared.
+async fn sync_tags(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
+) -> Option<Condition> {
+    let labels = match app.spec.app_config {
+        Some(
+            servarr_crds::AppConfig::Sonarr(ref c)
+            | servarr_crds::AppConfig::Radarr(ref c)
+            | servarr_crds::AppConfig::Lidarr(ref c),
+        ) => &c.tags,
+        _ => return None,
+    };
+    if labels.is_empty()
+        || !matches!(
+            app.spec.app,
+            AppType::Sonarr | AppType::Radarr | AppType::Lidarr
+        )
+    {
+        return None;
+    }
+    let api =
+        match servarr_api_for_sync(client, app, ns, condition_types::TAGS_SYNCED, "tags").await {
+            Ok(api) => api,
+            Err(condition) => return Some(condition),
+        };
+    let now = chrono_now();
+    let app_name = servarr_resources::common::app_name(app);
+
+    let outcome = match crate::tags::sync(&api, labels).await {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            warn!(app = %app_name, error = %e, "tags: app API unavailable");
+            return Some(Condition {
+                condition_type: condition_types::TAGS_SYNCED.to_string(),
+                status: "Unknown".to_string(),
+                reason: "ApiUnavailable".to_string(),
+                message: e.to_string(),
+                last_transition_time: now,
+            });
+        }
+    };
+    for (label, error) in &outcome.failed {
+        warn!(app = %app_name, tag = %label, %error, "tag sync failed");
+    }
+    if !outcome.created.is_empty() {
+        info!(app = %app_name, created = ?outcome.created, "tags created");
+        let _ = recorder
+            .publish(
+                &Event {
+                    type_: EventType::Normal,
+                    reason: "TagsCreated".into(),
+                    note: Some(outcome.message()),
+                    action: "TagSync".into(),
+                    secondary: None,
+                },
+                obj_ref,
+            )
+            .await;
+    }
+
+    Some(if outcome.failed.is_empty() {
+        Condition::ok(
+            condition_types::TAGS_SYNCED,
+            "Synced",
+            &outcome.message(),
+            &now,
+        )
+    } else {
+        Condition::fail(
+            condition_types::TAGS_SYNCED,
+            "SyncFailed",
+            &outcome.message(),
+            &now,
+        )
+    })
+}
+
+/// Create, update, and (with `autoRemove`) remove Prowlarr indexers per
+/// `appConfig.indexers`, reported as the `IndexersSynced` condition.
+async fn sync_prowlarr_indexers(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
+) -> Option<Condition> {
+    let config = match app.spec.app_config {
+        Some(servarr_crds::AppConfig::Prowlarr(ref c))
+            if !c.indexers.is_empty() || c.auto_remove =>
+        {
+            c
+        }
+        _ => return None,
+    };
+    if app.spec.app != AppType::Prowlarr {
+        return None;
+    }
+    let (base_url, api_key) = match api_endpoint_for_sync(
+        client,
+        app,
+        ns,
+        condition_types::INDEXERS_SYNCED,
+        "indexers",
+    )
+    .await
+    {
+        Ok(endpoint) => endpoint,
+        Err(condition) => return Some(condition),
+    };
+    let now = chrono_now();
+    let app_name = servarr_resources::common::app_name(app);
+    let unknown = |reason: &str, message: String| {
+        Some(Condition {
+            condition_type: condition_types::INDEXERS_SYNCED.to_string(),
+            status: "Unknown".to_string(),
+            reason: reason.to_string(),
+            message,
+            last_transition_time: now.clone(),
+        })
+    };
+
+    let mut secrets = std::collections::BTreeMap::new();
+    for name in config
+        .indexers
+        .iter()
+        .filter_map(|i| i.fields_secret.as_deref())
+    {
+        if secrets.contains_key(name) {
+            continue;
+        }
+        match servarr_api::read_secret_data(client, ns, name).await {
+            Ok(data) => {
+                secrets.insert(name.to_string(), data);
+            }
+            Err(e) => return unknown("SecretReadError", e.to_string()),
+        }
+    }
+    let api = match servarr_api::ProwlarrClient::new(&base_url, &api_key) {
+        Ok(api) => api,
+        Err(e) => return unknown("ClientError", e.to_string()),
+    };
+    let outcome = match crate::prowlarr_indexers::sync(&api, config, &secrets).await {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            warn!(app = %app_name, error = %e, "indexers: Prowlarr API unavailable");
+            return unknown("ApiUnavailable", e.to_string());
+        }
+    };
+    for (indexer, error) in &outcome.failed {
+        warn!(app = %app_name, %indexer, %error, "indexer sync failed");
+    }
+    if !outcome.added.is_empty() || !outcome.updated.is_empty() || !outcome.removed.is_empty() {
+        info!(
+            app = %app_name,
+            added = ?outcome.added,
+            updated = ?outcome.updated,
+            removed = ?outcome.removed,
+            "indexers applied"
+        );
+        let _ = recorder
+            .publish(
+                &Event {
+                    type_: EventType::Normal,
+                    reason: "IndexersApplied".into(),
+                    note: Some(outcome.message()),
+                    action: "IndexerSync".into(),
+                    secondary: None,
+                },
+                obj_ref,
+            )
+            .await;
+    }
+
+    Some(if outcome.failed.is_empty() {
+        Condition::ok(
+            condition_types::INDEXERS_SYNCED,
+            "Synced",
+            &outcome.message(),
+            &now,
+        )
+    } else {
+        Condition::fail(
+            condition_types::INDEXERS_SYNCED,
+            "SyncFailed",
+            &outcome.message(),
+            &now,
+        )
+    })
+}
+
+/// Create, update, and (with `autoRemove`) remove the accounts of a
+/// Jellyfin or Plex server per `appConfig.users`, reported as the
+/// `UsersSynced` condition.
+async fn sync_media_server_users(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
+) -> Option<Condition> {
+    let config =
+        match app.spec.app_config {
+            Some(
+                servarr_crds::AppConfig::Jellyfin(ref c) | servarr_crds::AppConfig::Plex(ref c),
+            ) if !c.users.is_empty() || c.auto_remove => c,
+            _ => return None,
+        };
+    let now = chrono_now();
+    let app_name = servarr_resources::common::app_name(app);
+    let base_url = servarr_resources::common::internal_url(app);
+    let unknown = |reason: &str, message: String| {
+        Some(Condition {
+            condition_type: condition_types::USERS_SYNCED.to_string(),
+            status: "Unknown".to_string(),
+            reason: reason.to_string(),
+            message,
+            last_transition_time: now.clone(),
+        })
+    };
+
+    let result = match app.spec.app {
+        AppType::Jellyfin => {
+            let Some(ref ac) = app.spec.admin_credentials else {
+                return Some(Condition::fail(
+                    condition_types::USERS_SYNCED,
+                    "NoAdminCredentials",
+                    "Jellyfin users require adminCredentials to be set",
+                    &now,
+                ));
+            };
+            let admin = match servarr_api::read_secret_data(client, ns, &ac.secret_name).await {
+                Ok(data) => data,
+                Err(e) => return unknown("SecretReadError", e.to_string()),
+            };
+            let admin_user = admin.get("username").cloned().unwrap_or_default();
+            let admin_password = admin.get("password").cloned().unwrap_or_default();
+            let mut passwords = std::collections::BTreeMap::new();
+            for user in &config.users {
+                let Some(ref secret) = user.password_secret else {
+                    continue;
+                };
+                match servarr_api::read_secret_key(client, ns, secret, "password").await {
+                    Ok(password) => {
+                        passwords.insert(user.username.clone(), password);
+                    }
+                    Err(e) => return unknown("SecretReadError", e.to_string()),
+                }
+            }
+            let api = match servarr_api::JellyfinClient::new(&base_url) {
+                Ok(api) => api,
+                Err(e) => return unknown("ClientError", e.to_string()),
+            };
+            match api.authenticate(&admin_user, &admin_password).await {
+                Ok(token) => {
+                    crate::media_server_users::sync_jellyfin(
+                        &api,
+                        &token,
+                        &admin_user,
+                        config,
+                        &passwords,
+                    )
+                    .await
+                }
+                Err(e) => Err(e),
+            }
+        }
+        AppType::Plex => {
+            let Some(ref secret) = config.plex_token_secret else {
+                return Some(Condition::fail(
+                    condition_types::USERS_SYNCED,
+                    "NoPlexToken",
+                    "Plex users require plexTokenSecret to be set",
+                    &now,
+                ));
+            };
+            let token = match servarr_api::read_secret_key(client, ns, secret, "token").await {
+                Ok(token) => token,
+                Err(e) => return unknown("SecretReadError", e.to_string()),
+            };
+            let (server, tv) = match (
+                servarr_api::PlexClient::new(&base_url),
+                servarr_api::PlexTvClient::new(servarr_api::plex::PLEX_TV_URL, &token),
+            ) {
+                (Ok(server), Ok(tv)) => (server, tv),
+                (Err(e), _) | (_, Err(e)) => return unknown("ClientError", e.to_string()),
+            };
+            match server.machine_identifier().await {
+                Ok(machine_id) => {
+                    crate::media_server_users::sync_plex(&tv, &machine_id, config).await
+                }
+                Err(e) => Err(e),
+            }
+        }
+        _ => return None,
+    };
+    let outcome = match result {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            warn!(app = %app_name, error = %e, "users: media server API unavailable");
+            return unknown("ApiUnavailable", e.to_string());
+        }
+    };
+    for (user, error) in &outcome.failed {
+        warn!(app = %app_name, %user, %error, "user sync failed");
+    }
+    if !outcome.added.is_empty() || !outcome.updated.is_empty() || !outcome.removed.is_empty() {
+        info!(
+            app = %app_name,
+            added = ?outcome.added,
+            updated = ?outcome.updated,
+            removed = ?outcome.removed,
+            "users applied"
+        );
+        let _ = recorder
+            .publish(
+                &Event {
+                    type_: EventType::Normal,
+                    reason: "UsersApplied".into(),
+                    note: Some(outcome.message()),
+                    action: "UserSync".into(),
+                    secondary: None,
+                },
+                obj_ref,
+            )
+            .await;
+    }
+
+    Some(if outcome.failed.is_empty() {
+        Condition::ok(
+            condition_types::USERS_SYNCED,
+            "Synced",
+            &outcome.message(),
+            &now,
+        )
+    } else {
+        Condition::fail(
+            condition_types::USERS_SYNCED,
+            "SyncFailed",
+            &outcome.message(),
+            &now,
+        )
+    })
+}
+
+/// Register the namespace's Transmission and SABnzbd apps as download
+/// clients per `downloadClientSync`, reported as the `DownloadClientsSynced`
+/// condition. `None` unless sync is enabled.
+async fn sync_download_clients(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
+) -> Option<Condition> {
+    let spec = app
+        .spec
+        .download_client_sync
+        .as_ref()
+        .filter(|s| s.enabled)?;
+    if !app.spec.app.downloads_media() {
+        return None;
+    }
+    let api = match servarr_api_for_sync(
+        client,
+        app,
+        ns,
+        condition_types::DOWNLOAD_CLIENTS_SYNCED,
+        "downloadClientSync",
+    )
+    .await
+    {
+        Ok(api) => api,
+        Err(condition) => return Some(condition),
+    };
+    let now = chrono_now();
+    let app_name = servarr_resources::common::app_name(app);
+    let unknown = |reason: &str, message: String| {
+        Some(Condition {
+            condition_type: condition_types::DOWNLOAD_CLIENTS_SYNCED.to_string(),
+            status: "Unknown".to_string(),
+            reason: reason.to_string(),
+            message,
+            last_transition_time: now.clone(),
+        })
+    };
+
+    let targets = match discover_download_clients(client, app, ns).await {
+        Ok(targets) => targets,
+        Err(e) => return unknown("DiscoveryFailed", e.to_string()),
+    };
+    let outcome = match crate::download_clients::sync(
+        &api,
+        &targets,
+        spec.category.as_deref(),
+        &spec.tags,
+        spec.auto_remove,
+        ns,
+    )
+    .await
+    {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            warn!(app = %app_name, error = %e, "download clients: app API unavailable");
+            return unknown("ApiUnavailable", e.to_string());
+        }
+    };
+    for (name, error) in &outcome.failed {
+        warn!(app = %app_name, download_client = %name, %error, "download client sync failed");
+    }
+    if !outcome.added.is_empty() || !outcome.updated.is_empty() || !outcome.removed.is_empty() {
+        info!(
+            app = %app_name,
+            added = ?outcome.added,
+            updated = ?outcome.updated,
+            removed = ?outcome.removed,
+            "download clients applied"
+        );
+        let _ = recorder
+            .publish(
+                &Event {
+                    type_: EventType::Normal,
+                    reason: "DownloadClientsApplied".into(),
+                    note: Some(outcome.message()),
+                    action: "DownloadClientSync".into(),
+                    secondary: None,
+                },
+                obj_ref,
+            )
+            .await;
+    }
+
+    if !outcome.failed.is_empty() {
+        Some(Condition::fail(
+            condition_types::DOWNLOAD_CLIENTS_SYNCED,
+            "SyncFailed",
+            &outcome.message(),
+            &now,
+        ))
+    } else if !outcome.deferred.is_empty() {
+        // Unknown keeps the short requeue until the download client is up
+        unknown("Deferred", outcome.message())
+    } else {
+        Some(Condition::ok(
+            condition_types::DOWNLOAD_CLIENTS_SYNCED,
+            "Synced",
+            &outcome.message(),
+            &now,
+        ))
+    }
+}
+
+pub(crate) async fn check_api_health(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+) -> (Option<Condition>, Option<Condition>) {
+    let _health_check = match app.spec.api_health_check.as_ref() {
+        Some(hc) if hc.enabled => hc,
+        _ => return (None, None),
+    };
+    let (secret_name, key) = match app.spec.api_key_ref() {
+        Some(r) => r,
         None => return (None, None),
     };
 
     let now = chrono_now();
-    let api_key = match servarr_api::read_secret_key(client, ns, secret_name, "api-key").await {
+    let api_key = match servarr_api::read_secret_key(client, ns, secret_name, key).await {
         Ok(k) => k,
         Err(e) => {
             warn!(error = %e, "failed to read API key secret");
@@ -934,7 +3330,12 @@ pub(crate) async fn check_api_health(
 
     use servarr_api::HealthCheck;
     let (healthy, update_cond): (Result<bool, String>, Option<Condition>) = match app.spec.app {
-        AppType::Sonarr | AppType::Radarr | AppType::Lidarr | AppType::Prowlarr => {
+        AppType::Sonarr
+        | AppType::Radarr
+        | AppType::Lidarr
+        | AppType::Readarr
+        | AppType::Whisparr
+        | AppType::Prowlarr => {
             match servarr_api::ServarrClient::new(
                 &base_url,
                 &api_key,
@@ -942,7 +3343,16 @@ pub(crate) async fn check_api_health(
             ) {
                 Ok(c) => {
                     let h = c.is_healthy().await.map_err(|e| e.to_string());
-                    let uc = check_update_available(&c, &now).await;
+                    let manual = app
+                        .spec
+                        .update_policy
+                        .as_ref()
+                        .is_some_and(|p| p.mode == UpdateMode::Manual);
+                    let uc = if manual {
+                        None
+                    } else {
+                        check_update_available(&c, &now).await
+                    };
                     (h, uc)
                 }
                 Err(e) => (Err(e.to_string()), None),
@@ -981,6 +3391,13 @@ pub(crate) async fn check_api_health(
                 Err(e) => (Err(e.to_string()), None),
             }
         }
+        AppType::Qbittorrent => match servarr_api::QbittorrentClient::new(&base_url) {
+            Ok(c) => {
+                let h = c.is_healthy().await.map_err(|e| e.to_string());
+                (h, None)
+            }
+            Err(e) => (Err(e.to_string()), None),
+        },
         AppType::Jellyfin => match servarr_api::JellyfinClient::new(&base_url) {
             Ok(c) => {
                 let h = c.is_healthy().await.map_err(|e| e.to_string());
@@ -995,6 +3412,17 @@ pub(crate) async fn check_api_health(
             }
             Err(e) => (Err(e.to_string()), None),
         },
+        AppType::Overseerr | AppType::Jellyseerr => {
+            let c = servarr_api::OverseerrClient::new(&base_url, &api_key);
+            (c.is_healthy().await.map_err(|e| e.to_string()), None)
+        }
+        AppType::Bazarr => match servarr_api::BazarrClient::new(&base_url, &api_key) {
+            Ok(c) => {
+                let h = c.is_healthy().await.map_err(|e| e.to_string());
+                (h, None)
+            }
+            Err(e) => (Err(e.to_string()), None),
+        },
         _ => return (None, None),
     };
 
@@ -1049,10 +3477,293 @@ async fn check_update_available(
     })
 }
 
+/// Current value of the `servarr.dev/reconcile-now` annotation, if any.
+fn reconcile_trigger(app: &ServarrApp) -> Option<String> {
+    app.metadata
+        .annotations
+        .as_ref()
+        .and_then(|a| a.get(RECONCILE_NOW_ANNOTATION))
+        .cloned()
+}
+
+/// The reconcile-now value if it has not yet been recorded in status.
+fn pending_reconcile_trigger(app: &ServarrApp) -> Option<String> {
+    let trigger = reconcile_trigger(app)?;
+    let handled = app
+        .status
+        .as_ref()
+        .and_then(|s| s.reconcile_trigger.as_deref());
+    (handled != Some(trigger.as_str())).then_some(trigger)
+}
+
+/// Whether the `servarr.dev/sync-now` annotation asks for a sync pass on
+/// an app that runs one.
+fn sync_requested(app: &ServarrApp) -> bool {
+    let enabled = match app.spec.app {
+        AppType::Prowlarr => app.spec.prowlarr_sync.as_ref().is_some_and(|s| s.enabled),
+        AppType::Overseerr | AppType::Jellyseerr => runs_overseerr_sync(app),
+        AppType::Bazarr => app.spec.bazarr_sync.as_ref().is_some_and(|s| s.enabled),
+        _ => false,
+    };
+    enabled
+        && app
+            .annotations()
+            .get(SYNC_NOW_ANNOTATION)
+            .is_some_and(|v| v == "true")
+}
+
+/// Remove the `servarr.dev/sync-now` annotation once its sync pass ran.
+async fn clear_sync_now(client: &Client, ns: &str, name: &str) {
+    let patch = serde_json::json!({
+        "metadata": { "annotations": { SYNC_NOW_ANNOTATION: null } }
+    });
+    if let Err(e) = Api::<ServarrApp>::namespaced(client.clone(), ns)
+        .patch(name, &PatchParams::default(), &Patch::Merge(patch))
+        .await
+    {
+        warn!(%name, error = %e, "failed to remove sync-now annotation");
+    }
+}
+
+/// SHA-256 of the serialized spec, published as `status.appliedSpecHash` so
+/// external tooling can tell whether the operator has caught up with an edit.
+pub(crate) fn spec_hash(spec: &servarr_crds::ServarrAppSpec) -> String {
+    use sha2::{Digest, Sha256};
+
+    let bytes = serde_json::to_vec(spec).unwrap_or_default();
+    format!("{:x}", Sha256::digest(&bytes))
+}
+
+/// Where a Deployment rollout stands, following the kstatus rules for
+/// Deployments.
+#[derive(Debug, PartialEq)]
+pub(crate) enum RolloutState {
+    Complete,
+    InProgress(String),
+    Stalled(String),
+}
+
+pub(crate) fn rollout_state(deploy: &Deployment) -> RolloutState {
+    let Some(status) = deploy.status.as_ref() else {
+        return RolloutState::InProgress("Deployment status not yet reported".to_string());
+    };
+
+    let generation = deploy.metadata.generation.unwrap_or(0);
+    if status.observed_generation.unwrap_or(0) < generation {
+        return RolloutState::InProgress(
+            "Deployment controller has not observed the latest spec".to_string(),
+        );
+    }
+
+    if let Some(cond) = status.conditions.iter().flatten().find(|c| {
+        c.type_ == "Progressing" && c.reason.as_deref() == Some("ProgressDeadlineExceeded")
+    }) {
+        return RolloutState::Stalled(
+            cond.message
+                .clone()
+                .unwrap_or_else(|| "Deployment exceeded its progress deadline".to_string()),
+        );
+    }
+
+    let desired = deploy.spec.as_ref().and_then(|s| s.replicas).unwrap_or(1);
+    let updated = status.updated_replicas.unwrap_or(0);
+    let total = status.replicas.unwrap_or(0);
+    let available = status.available_replicas.unwrap_or(0);
+    if updated < desired {
+        RolloutState::InProgress(format!("{updated} of {desired} replica(s) updated"))
+    } else if total > updated {
+        RolloutState::InProgress(format!(
+            "{} old replica(s) pending termination",
+            total - updated
+        ))
+    } else if available < updated {
+        RolloutState::InProgress(format!(
+            "{available} of {updated} updated replica(s) available"
+        ))
+    } else {
+        RolloutState::Complete
+    }
+}
+
+/// Conditions reporting configuration the operator applies through the
+/// app's API once it is running.
+const CONFIGURATION_CONDITIONS: &[&str] = &[
+    condition_types::ADMIN_CREDENTIALS_CONFIGURED,
+    condition_types::AUTH_CONFIGURED,
+    condition_types::UI_SETTINGS_SYNCED,
+    condition_types::CUSTOM_FORMATS_SYNCED,
+    condition_types::QUALITY_PROFILES_SYNCED,
+    condition_types::ROOT_FOLDERS_SYNCED,
+    condition_types::MEDIA_MANAGEMENT_SYNCED,
+    condition_types::MONITORING_SYNCED,
+    condition_types::TAGS_SYNCED,
+    condition_types::INDEXERS_SYNCED,
+    condition_types::USERS_SYNCED,
+    condition_types::DOWNLOAD_CLIENTS_SYNCED,
+];
+
+/// `ConfigurationComplete` for `status`: True once the app is Ready and
+/// every configuration condition it reports is True.
+pub(crate) fn configuration_condition(status: &ServarrAppStatus, now: &str) -> Condition {
+    if !status.ready {
+        return Condition::fail(
+            condition_types::CONFIGURATION_COMPLETE,
+            "AppNotReady",
+            "Waiting for the app to become Ready",
+            now,
+        );
+    }
+    let pending: Vec<&str> = status
+        .conditions
+        .iter()
+        .filter(|c| CONFIGURATION_CONDITIONS.contains(&c.condition_type.as_str()))
+        .filter(|c| c.status != "True")
+        .map(|c| c.condition_type.as_str())
+        .collect();
+    if pending.is_empty() {
+        Condition::ok(
+            condition_types::CONFIGURATION_COMPLETE,
+            "Configured",
+            "All declared configuration has been applied",
+            now,
+        )
+    } else {
+        Condition::fail(
+            condition_types::CONFIGURATION_COMPLETE,
+            "ConfigurationPending",
+            &format!("Waiting for {}", pending.join(", ")),
+            now,
+        )
+    }
+}
+
 pub(crate) struct StatusConditions {
     pub health: Option<Condition>,
     pub update: Option<Condition>,
     pub admin_creds: Option<Condition>,
+    pub auth: Option<Condition>,
+    pub ui_settings: Option<Condition>,
+    pub custom_formats: Option<Condition>,
+    pub quality_profiles: Option<Condition>,
+    pub root_folders: Option<Condition>,
+    pub media_management: Option<Condition>,
+    pub monitoring: Option<Condition>,
+    pub tags: Option<Condition>,
+    pub indexers: Option<Condition>,
+    pub users: Option<Condition>,
+    pub integrity: Option<Condition>,
+    pub download_clients: Option<Condition>,
+    pub maintenance: Option<ActiveWindow>,
+    /// The `Suspended` condition while the app is scaled to zero.
+    pub suspended: Option<Condition>,
+    pub unsupported_overrides: Option<Condition>,
+    pub image_architecture: Option<Condition>,
+    pub network_policy_enforced: Option<Condition>,
+    pub gpu: Option<Condition>,
+    pub autoscaling: Option<Condition>,
+    pub storage_migration: MigrationState,
+}
+
+/// Results of the periodic tasks run against the app's API, carried over
+/// between reconciles in the status.
+#[derive(Default)]
+pub(crate) struct TaskStatus {
+    pub backup: Option<servarr_crds::BackupStatus>,
+    pub api_key_rotation: Option<servarr_crds::ApiKeyRotationStatus>,
+    pub queue_remediation: Option<servarr_crds::QueueRemediationStatus>,
+    pub trash_sync: Option<servarr_crds::TrashSyncStatus>,
+    pub image_update: Option<servarr_crds::ImageUpdateStatus>,
+    pub pending_actions: Vec<servarr_crds::PendingAction>,
+    /// Apps the Prowlarr or Overseerr sync registered on this pass.
+    pub synced_apps: Option<usize>,
+    pub sync: Option<SyncStatus>,
+    pub resource_groups: Option<ResourceGroupHashes>,
+    pub drift: Option<DriftReport>,
+}
+
+/// `--dry-run` pass: record what a real reconcile would change in
+/// `status.wouldChange` and leave everything else untouched.
+async fn reconcile_dry_run(
+    ctx: &Context,
+    app: &ServarrApp,
+    ns: &str,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
+) -> Result<Action, Error> {
+    let name = app.name_any();
+    if app.metadata.deletion_timestamp.is_some() {
+        // Nothing is cleaned up in a dry run, but the app must not hang in
+        // Terminating on finalizers a real reconcile added.
+        let released = |f: &String| {
+            [
+                PROWLARR_FINALIZER,
+                OVERSEERR_FINALIZER,
+                crate::backup_upload::FINALIZER,
+            ]
+            .contains(&f.as_str())
+        };
+        let finalizers = app.finalizers();
+        if finalizers.iter().any(released) {
+            warn!(%name, %ns, "dry run: releasing finalizers without removing Prowlarr and Overseerr registrations or applying the backup deletionPolicy");
+            let kept: Vec<&String> = finalizers.iter().filter(|f| !released(f)).collect();
+            Api::<ServarrApp>::namespaced(ctx.client.clone(), ns)
+                .patch(
+                    &name,
+                    &PatchParams::default(),
+                    &Patch::Merge(serde_json::json!({ "metadata": { "finalizers": kept } })),
+                )
+                .await
+                .map_err(Error::kube)?;
+        }
+        return Ok(Action::await_change());
+    }
+    let opts = crate::render::Options {
+        image_overrides: ctx.image_overrides.clone(),
+        default_arch: ctx.default_arch.clone(),
+        allow_unsupported_overrides: ctx.allow_unsupported_overrides,
+    };
+    let objects = crate::render::render(app, &opts).map_err(Error::Render)?;
+    let changes = crate::dry_run::plan(&ctx.client, ns, &objects)
+        .await
+        .map_err(Error::kube)?;
+
+    let previous = app.status.as_ref().and_then(|s| s.would_change.as_ref());
+    if previous != Some(&changes) {
+        for change in &changes {
+            info!(%name, %ns, %change, "dry run: would change");
+        }
+        if !changes.is_empty() {
+            recorder
+                .publish(
+                    &Event {
+                        type_: EventType::Normal,
+                        reason: "WouldChange".into(),
+                        note: Some(changes.join("; ")),
+                        action: "DryRun".into(),
+                        secondary: None,
+                    },
+                    obj_ref,
+                )
+                .await
+                .map_err(Error::kube)?;
+        }
+        let mut status = app.status.clone().unwrap_or_default();
+        status.would_change = Some(changes);
+        let status_patch = serde_json::json!({
+            "apiVersion": "servarr.dev/v1alpha1",
+            "kind": "ServarrApp",
+            "status": status,
+        });
+        Api::<ServarrApp>::namespaced(ctx.client.clone(), ns)
+            .patch_status(
+                &name,
+                &PatchParams::apply(FIELD_MANAGER).force(),
+                &Patch::Apply(status_patch),
+            )
+            .await
+            .map_err(Error::kube)?;
+    }
+    Ok(Action::requeue(Duration::from_secs(300)))
 }
 
 pub(crate) async fn update_status(
@@ -1061,25 +3772,61 @@ pub(crate) async fn update_status(
     ns: &str,
     name: &str,
     conditions: StatusConditions,
-    backup_status: Option<servarr_crds::BackupStatus>,
-) -> Result<(), Error> {
+    tasks: TaskStatus,
+    generated_resources: Option<GeneratedResources>,
+) -> Result<ServarrAppStatus, Error> {
     let StatusConditions {
         health: health_condition,
         update: update_condition,
         admin_creds: admin_creds_condition,
+        auth: auth_condition,
+        ui_settings: ui_settings_condition,
+        custom_formats: custom_formats_condition,
+        quality_profiles: quality_profiles_condition,
+        root_folders: root_folders_condition,
+        media_management: media_management_condition,
+        monitoring: monitoring_condition,
+        tags: tags_condition,
+        indexers: indexers_condition,
+        users: users_condition,
+        integrity: integrity_condition,
+        download_clients: download_clients_condition,
+        maintenance: maintenance_window,
+        suspended: suspension,
+        unsupported_overrides: overrides_condition,
+        image_architecture: arch_condition,
+        network_policy_enforced: enforcement_condition,
+        gpu: gpu_condition,
+        autoscaling: hpa_condition,
+        storage_migration: migration,
     } = conditions;
-    let deploy_api = Api::<Deployment>::namespaced(client.clone(), ns);
-    let (ready, ready_replicas) = match deploy_api.get(name).await {
-        Ok(deploy) => {
-            let replicas = deploy
-                .status
-                .as_ref()
-                .and_then(|s| s.ready_replicas)
-                .unwrap_or(0);
-            (replicas > 0, replicas)
-        }
-        Err(_) => (false, 0),
-    };
+    let TaskStatus {
+        backup: backup_status,
+        api_key_rotation,
+        queue_remediation,
+        trash_sync,
+        image_update,
+        pending_actions,
+        synced_apps,
+        sync: sync_status,
+        resource_groups,
+        drift: last_drift,
+    } = tasks;
+    let (ready_replicas, rollout) = workload::observe(client, app, ns).await;
+    let deployment_ready = ready_replicas > 0;
+
+    // Ready requires a finished rollout and, when an API health check is
+    // configured, a passing result; GitOps tools treat Ready as "healthy".
+    let unhealthy = health_condition
+        .as_ref()
+        .filter(|c| c.status != "True")
+        .map(|c| c.message.clone());
+    let ready = deployment_ready && rollout == RolloutState::Complete && unhealthy.is_none();
+    let suspended = suspension.is_some();
+    let unsupported_arch = arch_condition
+        .as_ref()
+        .filter(|c| c.status != "True")
+        .map(|c| c.message.clone());
 
     let generation = app.metadata.generation.unwrap_or(0);
     let now = chrono_now();
@@ -1089,10 +3836,31 @@ pub(crate) async fn update_status(
         observed_generation: generation,
         conditions: Vec::new(),
         backup_status,
+        api_key_rotation,
+        queue_remediation,
+        last_trash_sync: trash_sync,
+        image_update,
+        sync_status,
+        applied_spec_hash: Some(spec_hash(&app.spec)),
+        reconcile_trigger: reconcile_trigger(app),
+        message: if ready {
+            Some(crate::setup_notes::message(app, ns, synced_apps))
+        } else {
+            app.status.as_ref().and_then(|s| s.message.clone())
+        },
+        generated_resources,
+        volume_claims: migration.volume_claims.clone(),
+        storage_migrations: migration.migrations.clone(),
+        would_change: None,
+        last_error: None,
+        last_drift,
+        pending_actions,
+        resource_groups,
     };
+    let migration_condition = migration.condition(&now);
 
     // DeploymentReady
-    if ready {
+    if deployment_ready {
         status.set_condition(Condition::ok(
             condition_types::DEPLOYMENT_READY,
             "ReplicasAvailable",
@@ -1116,13 +3884,70 @@ pub(crate) async fn update_status(
         &now,
     ));
 
-    // Progressing is false now (reconcile completed)
-    status.set_condition(Condition::fail(
-        condition_types::PROGRESSING,
-        "ReconcileComplete",
-        "Reconciliation finished",
-        &now,
-    ));
+    // Progressing mirrors the Deployment rollout rather than this reconcile
+    // pass; Reconciling and Stalled are the kstatus equivalents Flux reads.
+    match &rollout {
+        RolloutState::InProgress(msg) => {
+            status.set_condition(Condition::ok(
+                condition_types::PROGRESSING,
+                "RolloutInProgress",
+                msg,
+                &now,
+            ));
+            status.set_condition(Condition::ok(
+                condition_types::RECONCILING,
+                "RolloutInProgress",
+                msg,
+                &now,
+            ));
+            status.set_condition(Condition::fail(
+                condition_types::STALLED,
+                "RolloutInProgress",
+                msg,
+                &now,
+            ));
+        }
+        RolloutState::Complete => {
+            status.set_condition(Condition::fail(
+                condition_types::PROGRESSING,
+                "RolloutComplete",
+                "Deployment rollout complete",
+                &now,
+            ));
+            status.set_condition(Condition::fail(
+                condition_types::RECONCILING,
+                "RolloutComplete",
+                "Deployment rollout complete",
+                &now,
+            ));
+            status.set_condition(Condition::fail(
+                condition_types::STALLED,
+                "RolloutComplete",
+                "Deployment rollout complete",
+                &now,
+            ));
+        }
+        RolloutState::Stalled(msg) => {
+            status.set_condition(Condition::fail(
+                condition_types::PROGRESSING,
+                "ProgressDeadlineExceeded",
+                msg,
+                &now,
+            ));
+            status.set_condition(Condition::fail(
+                condition_types::RECONCILING,
+                "ProgressDeadlineExceeded",
+                msg,
+                &now,
+            ));
+            status.set_condition(Condition::ok(
+                condition_types::STALLED,
+                "ProgressDeadlineExceeded",
+                msg,
+                &now,
+            ));
+        }
+    }
 
     // Overall Ready
     status.set_condition(if ready {
@@ -1132,32 +3957,95 @@ pub(crate) async fn update_status(
             &format!("{ready_replicas} replica(s) ready"),
             &now,
         )
-    } else {
+    } else if let Some(cond) = &suspension {
+        Condition::fail(condition_types::READY, "Suspended", &cond.message, &now)
+    } else if let Some(msg) = &unsupported_arch {
+        Condition::fail(condition_types::READY, "UnsupportedArchitecture", msg, &now)
+    } else if let Some(cond) = migration_condition.as_ref().filter(|_| migration.copying()) {
         Condition::fail(
             condition_types::READY,
-            "DeploymentNotReady",
-            &format!("{ready_replicas} replica(s) ready"),
+            "MigratingStorage",
+            &cond.message,
             &now,
         )
+    } else {
+        let (reason, message) = match (&rollout, &unhealthy) {
+            (RolloutState::Stalled(msg), _) => ("ProgressDeadlineExceeded", msg.clone()),
+            (RolloutState::InProgress(msg), _) => ("RolloutInProgress", msg.clone()),
+            (RolloutState::Complete, _) if !deployment_ready => (
+                "DeploymentNotReady",
+                format!("{ready_replicas} replica(s) ready"),
+            ),
+            (RolloutState::Complete, msg) => ("AppUnhealthy", msg.clone().unwrap_or_default()),
+        };
+        Condition::fail(condition_types::READY, reason, &message, &now)
     });
 
-    // Degraded
-    if !ready {
-        status.set_condition(Condition::ok(
-            condition_types::DEGRADED,
+    // Degraded — only genuine failures; an in-flight rollout is not degraded
+    let api_failed = health_condition
+        .as_ref()
+        .filter(|c| c.status == "False")
+        .map(|c| c.message.clone());
+    let degraded = match (&rollout, api_failed) {
+        _ if suspended || migration.copying() => None,
+        _ if unsupported_arch.is_some() => unsupported_arch
+            .clone()
+            .map(|msg| ("UnsupportedArchitecture", msg)),
+        (RolloutState::Stalled(msg), _) => Some(("ProgressDeadlineExceeded", msg.clone())),
+        (RolloutState::Complete, _) if !deployment_ready => Some((
             "DeploymentNotReady",
-            &format!("{ready_replicas} replica(s) ready"),
+            format!("{ready_replicas} replica(s) ready"),
+        )),
+        (_, Some(msg)) => Some(("AppUnhealthy", msg)),
+        _ => None,
+    };
+    match degraded {
+        Some((reason, message)) => status.set_condition(Condition::ok(
+            condition_types::DEGRADED,
+            reason,
+            &message,
             &now,
-        ));
-    } else {
-        status.set_condition(Condition::fail(
+        )),
+        None => status.set_condition(Condition::fail(
             condition_types::DEGRADED,
             "AllHealthy",
             "All resources healthy",
             &now,
-        ));
+        )),
     }
 
+    // Maintenance
+    status.set_condition(match &maintenance_window {
+        Some(window) => Condition::ok(
+            condition_types::MAINTENANCE,
+            match window.mode {
+                MaintenanceMode::Suspend => "Suspended",
+                MaintenanceMode::Freeze => "Frozen",
+            },
+            &format!(
+                "MaintenanceWindow {} active until {}",
+                window.name,
+                window.end.to_rfc3339()
+            ),
+            &now,
+        ),
+        None => Condition::fail(
+            condition_types::MAINTENANCE,
+            "NoActiveWindow",
+            "No maintenance window in effect",
+            &now,
+        ),
+    });
+
+    status.set_condition(suspension.unwrap_or_else(|| {
+        Condition::fail(
+            condition_types::SUSPENDED,
+            "NotSuspended",
+            "The app is running",
+            &now,
+        )
+    }));
+
     // API health condition
     if let Some(cond) = health_condition {
         status.set_condition(cond);
@@ -1166,10 +4054,91 @@ pub(crate) async fn update_status(
     if let Some(cond) = update_condition {
         status.set_condition(cond);
     }
-    // Admin credentials condition
-    if let Some(cond) = admin_creds_condition {
+    // Admin credentials condition
+    if let Some(cond) = admin_creds_condition {
+        status.set_condition(cond);
+    }
+    // Web UI authentication, only reported when spec.auth is set
+    if let Some(cond) = auth_condition {
+        status.set_condition(cond);
+    }
+    // UI preferences, only reported when spec.uiSettings is set
+    if let Some(cond) = ui_settings_condition {
+        status.set_condition(cond);
+    }
+    // Custom formats, only reported when the app declares some
+    if let Some(cond) = custom_formats_condition {
+        status.set_condition(cond);
+    }
+    // Quality profiles, only reported when the app declares some
+    if let Some(cond) = quality_profiles_condition {
+        status.set_condition(cond);
+    }
+    // Root folders, only reported when the app declares them
+    if let Some(cond) = root_folders_condition {
+        status.set_condition(cond);
+    }
+    // Media management settings, only reported when the app declares them
+    if let Some(cond) = media_management_condition {
+        status.set_condition(cond);
+    }
+    // Monitoring settings, only reported when the app declares them
+    if let Some(cond) = monitoring_condition {
+        status.set_condition(cond);
+    }
+    // Tags, only reported when the app declares them
+    if let Some(cond) = tags_condition {
+        status.set_condition(cond);
+    }
+    // Prowlarr indexers, only reported when the app declares them
+    if let Some(cond) = indexers_condition {
+        status.set_condition(cond);
+    }
+    // Media server users, only reported when the app declares them
+    if let Some(cond) = users_condition {
+        status.set_condition(cond);
+    }
+    // SQLite integrity, only reported once a configured check has finished
+    if let Some(cond) = integrity_condition {
+        status.set_condition(cond);
+    }
+    // Download clients, only reported when downloadClientSync is enabled
+    if let Some(cond) = download_clients_condition {
+        status.set_condition(cond);
+    }
+    // Rolled up from the API-driven configuration conditions above
+    let configured = configuration_condition(&status, &now);
+    status.set_condition(configured);
+    // Unsupported overrides condition
+    if let Some(cond) = overrides_condition {
+        status.set_condition(cond);
+    }
+    // Image architecture condition
+    if let Some(cond) = arch_condition {
+        status.set_condition(cond);
+    }
+    // NetworkPolicy enforcement, only reported when the app has a policy
+    if let Some(cond) = enforcement_condition {
+        status.set_condition(cond);
+    }
+    // GPU availability, only reported when the app requests a GPU
+    if let Some(cond) = gpu_condition {
+        status.set_condition(cond);
+    }
+    // HorizontalPodAutoscaler, only reported when spec.autoscaling is set
+    if let Some(cond) = hpa_condition {
+        status.set_condition(cond);
+    }
+    if let Some(cond) = migration_condition {
         status.set_condition(cond);
     }
+    // PVCs, route, certificate, and mount check, each only reported when
+    // the app has them
+    if let Some(generated) = &status.generated_resources {
+        for cond in crate::child_status::observe(client, app, ns, generated, &now).await {
+            status.set_condition(cond);
+        }
+    }
 
     let status_patch = serde_json::json!({
         "apiVersion": "servarr.dev/v1alpha1",
@@ -1184,51 +4153,88 @@ pub(crate) async fn update_status(
         &Patch::Apply(status_patch),
     )
     .await
-    .map_err(Error::Kube)?;
+    .map_err(Error::kube)?;
 
-    Ok(())
+    Ok(status)
 }
 
 pub fn error_policy(app: Arc<ServarrApp>, error: &Error, ctx: Arc<Context>) -> Action {
     let app_type = app.spec.app.as_str();
+    let category = error.category();
     increment_reconcile_total(app_type, "error");
-    warn!(%error, "reconciliation failed, requeuing");
+    increment_reconcile_errors(app_type, category.as_str());
+    warn!(%error, category = category.as_str(), "reconciliation failed, requeuing");
+
+    // Merge-patched rather than applied, so the status apply of the next
+    // successful reconcile neither owns nor drops it.
+    let last_error = ReconcileError {
+        category,
+        message: error.to_string(),
+        time: chrono_now(),
+    };
+    let apps = Api::<ServarrApp>::namespaced(
+        ctx.client.clone(),
+        &app.namespace().unwrap_or_else(|| "default".into()),
+    );
+    let name = app.name_any();
+    tokio::spawn(async move {
+        let patch = serde_json::json!({ "status": { "lastError": last_error } });
+        if let Err(e) = apps
+            .patch_status(&name, &PatchParams::default(), &Patch::Merge(patch))
+            .await
+        {
+            warn!(%name, error = %e, "failed to record lastError");
+        }
+    });
 
     let recorder = Recorder::new(ctx.client.clone(), ctx.reporter.clone());
     let obj_ref = app.object_ref(&());
-    let error_msg = error.to_string();
+    let event = Event {
+        type_: EventType::Warning,
+        reason: "ReconcileError".into(),
+        note: Some(error.to_string()),
+        action: "Reconcile".into(),
+        secondary: None,
+    };
+    // Warnings are never throttled, but the throttle still needs to see this
+    // one so the next success is published as a recovery.
+    ctx.event_throttle.admit(&obj_ref, &event);
     tokio::spawn(async move {
-        let _ = recorder
-            .publish(
-                &Event {
-                    type_: EventType::Warning,
-                    reason: "ReconcileError".into(),
-                    note: Some(error_msg),
-                    action: "Reconcile".into(),
-                    secondary: None,
-                },
-                &obj_ref,
-            )
-            .await;
+        let _ = recorder.publish(&event, &obj_ref).await;
     });
 
-    Action::requeue(Duration::from_secs(60))
+    Action::requeue(
+        ctx.error_backoff
+            .failed(&object_key(app.namespace().as_deref(), &app.name_any())),
+    )
 }
 
 async fn maybe_run_backup(
     client: &Client,
     app: &ServarrApp,
     ns: &str,
-    recorder: &Recorder,
+    maintenance_cutoff: Option<chrono::DateTime<chrono::Utc>>,
+    recorder: &ThrottledRecorder<'_>,
     obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
 ) -> Option<servarr_crds::BackupStatus> {
     let backup_spec = app.spec.backup.as_ref()?;
-    if !backup_spec.enabled || backup_spec.schedule.is_empty() {
+    if !backup_spec.enabled || (backup_spec.schedule.is_empty() && maintenance_cutoff.is_none()) {
         return None;
     }
+    if backup_spec.volume_snapshot.is_some() {
+        return crate::volume_snapshot::maybe_snapshot(
+            client,
+            app,
+            ns,
+            maintenance_cutoff,
+            recorder,
+            obj_ref,
+        )
+        .await;
+    }
 
-    let secret_name = app.spec.api_key_secret.as_deref()?;
-    let api_key = match servarr_api::read_secret_key(client, ns, secret_name, "api-key").await {
+    let (secret_name, key) = app.spec.api_key_ref()?;
+    let api_key = match servarr_api::read_secret_key(client, ns, secret_name, key).await {
         Ok(k) => k,
         Err(e) => {
             warn!(error = %e, "backup: failed to read API key");
@@ -1240,40 +4246,23 @@ async fn maybe_run_backup(
     };
 
     // Only Servarr v3 apps support backup API
-    if !matches!(
-        app.spec.app,
-        AppType::Sonarr | AppType::Radarr | AppType::Lidarr | AppType::Prowlarr
-    ) {
+    if !app.spec.app.is_servarr_v3() {
         return None;
     }
 
-    // Check if backup is due based on cron schedule
-    let schedule = match cron::Schedule::from_str(&backup_spec.schedule) {
-        Ok(s) => s,
-        Err(e) => {
-            warn!(error = %e, schedule = %backup_spec.schedule, "invalid cron schedule");
-            return Some(servarr_crds::BackupStatus {
-                last_backup_result: Some(format!("invalid schedule: {e}")),
-                ..Default::default()
-            });
-        }
-    };
-
     use chrono::Utc;
-    let now = Utc::now();
-
-    // Check last backup time from existing status
-    let last_backup = app
-        .status
-        .as_ref()
-        .and_then(|s| s.backup_status.as_ref())
-        .and_then(|bs| bs.last_backup_time.as_deref())
-        .and_then(|t| t.parse::<chrono::DateTime<Utc>>().ok());
-
-    let is_due = match last_backup {
-        Some(last) => schedule.after(&last).take(1).any(|next| next <= now),
-        None => true, // Never backed up, do it now
-    };
+    let last_backup = last_backup_time(app);
+    let (is_due, before_maintenance) =
+        match backup_due(backup_spec, last_backup, maintenance_cutoff, Utc::now()) {
+            Ok(due) => due,
+            Err(e) => {
+                warn!(error = %e, "invalid cron schedule");
+                return Some(servarr_crds::BackupStatus {
+                    last_backup_result: Some(e),
+                    ..Default::default()
+                });
+            }
+        };
 
     if !is_due {
         // Return existing status unchanged
@@ -1304,7 +4293,14 @@ async fn maybe_run_backup(
             &Event {
                 type_: EventType::Normal,
                 reason: "BackupStarted".into(),
-                note: Some("Scheduled backup started".into()),
+                note: Some(
+                    if before_maintenance {
+                        "Pre-maintenance backup started"
+                    } else {
+                        "Scheduled backup started"
+                    }
+                    .into(),
+                ),
                 action: "Backup".into(),
                 secondary: None,
             },
@@ -1355,6 +4351,7 @@ async fn maybe_run_backup(
                         .map(|b| b.len() as u32)
                         .unwrap_or(0),
                 ),
+                ..Default::default()
             })
         }
         Err(e) => {
@@ -1375,29 +4372,468 @@ async fn maybe_run_backup(
             Some(servarr_crds::BackupStatus {
                 last_backup_time: last_backup.map(|_| chrono_now()),
                 last_backup_result: Some(format!("error: {e}")),
-                backup_count: 0,
+                ..Default::default()
             })
         }
     }
 }
 
+/// When the app's last scheduled backup was taken, from its previous status.
+pub(crate) fn last_backup_time(app: &ServarrApp) -> Option<chrono::DateTime<chrono::Utc>> {
+    app.status
+        .as_ref()
+        .and_then(|s| s.backup_status.as_ref())
+        .and_then(|bs| bs.last_backup_time.as_deref())
+        .and_then(|t| t.parse().ok())
+}
+
+/// Whether a backup is due at `now`: `backup.schedule` fired since
+/// `last_backup` (or there was none yet), or an upcoming maintenance window
+/// needs one taken after `maintenance_cutoff`. The second value is true in
+/// the maintenance case. `Err` carries an invalid schedule's message.
+pub(crate) fn backup_due(
+    backup_spec: &servarr_crds::BackupSpec,
+    last_backup: Option<chrono::DateTime<chrono::Utc>>,
+    maintenance_cutoff: Option<chrono::DateTime<chrono::Utc>>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<(bool, bool), String> {
+    let schedule = if backup_spec.schedule.is_empty() {
+        None
+    } else {
+        Some(crate::schedule::parse(&backup_spec.schedule)?)
+    };
+
+    // An upcoming maintenance window needs a backup taken within its lead time
+    let before_maintenance =
+        maintenance_cutoff.is_some_and(|cutoff| last_backup.is_none_or(|last| last < cutoff));
+
+    let is_due = before_maintenance
+        || match (last_backup, schedule) {
+            (_, None) => false,
+            (Some(last), Some(schedule)) => schedule.after(&last).take(1).any(|next| next <= now),
+            (None, Some(_)) => true, // Never backed up, do it now
+        };
+    Ok((is_due, before_maintenance))
+}
+
 /// Handle restore-from-backup triggered by the `servarr.dev/restore-from` annotation.
-/// Scales the Deployment to 0, calls restore via the API, scales back up, and removes
+/// Scales the workload to 0, calls restore via the API, scales back up, and removes
 /// the annotation to prevent re-triggering.
+/// Blocklist-and-research stuck queue items per `spec.queueRemediation`.
+///
+/// Runs at most once per `intervalMinutes`, tracked through the previous
+/// status like scheduled backups. Returns the status to record, carrying
+/// the counters over from the last reconcile.
+async fn maybe_remediate_queue(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
+) -> Option<servarr_crds::QueueRemediationStatus> {
+    let spec = app.spec.queue_remediation.as_ref().filter(|s| s.enabled)?;
+    if !matches!(app.spec.app, AppType::Sonarr | AppType::Radarr) {
+        return None;
+    }
+
+    use chrono::Utc;
+    let now = Utc::now();
+    let mut status = app
+        .status
+        .as_ref()
+        .and_then(|s| s.queue_remediation.clone())
+        .unwrap_or_default();
+    let last_check = status
+        .last_check_time
+        .as_deref()
+        .and_then(|t| t.parse::<chrono::DateTime<Utc>>().ok());
+    if last_check
+        .is_some_and(|last| now - last < chrono::Duration::minutes(spec.interval_minutes.into()))
+    {
+        return Some(status);
+    }
+    let now_str = chrono_now();
+    status.last_check_time = Some(now_str.clone());
+
+    let Some((secret_name, key)) = app.spec.api_key_ref() else {
+        status.last_check_result = Some("apiKeySecret is required".into());
+        return Some(status);
+    };
+    let api_key = match servarr_api::read_secret_key(client, ns, secret_name, key).await {
+        Ok(k) => k,
+        Err(e) => {
+            status.last_check_result = Some(format!("secret read error: {e}"));
+            return Some(status);
+        }
+    };
+
+    let app_name = servarr_resources::common::app_name(app);
+    let defaults = servarr_crds::AppDefaults::for_app(&app.spec.app);
+    let svc_spec = app.spec.service.as_ref().unwrap_or(&defaults.service);
+    let port = svc_spec.ports.first().map(|p| p.port).unwrap_or(80);
+    let base_url = format!("http://{app_name}.{ns}.svc:{port}");
+    let api =
+        match servarr_api::ServarrClient::new(&base_url, &api_key, app_type_to_kind(&app.spec.app))
+        {
+            Ok(api) => api,
+            Err(e) => {
+                status.last_check_result = Some(format!("client error: {e}"));
+                return Some(status);
+            }
+        };
+    let queue = match api.queue().await {
+        Ok(queue) => queue,
+        Err(e) => {
+            status.last_check_result = Some(format!("queue read error: {e}"));
+            return Some(status);
+        }
+    };
+
+    let app_type = app.spec.app.as_str();
+    let (mut done, mut failed) = (0, 0);
+    for remediation in crate::queue_remediation::plan(spec, &queue, now) {
+        let reason = remediation.reason.as_str();
+        let action = remediation.rule.action;
+        let title = remediation.title();
+        if !spec.dry_run {
+            let result = api
+                .remove_queue_item(
+                    remediation.item.id,
+                    remediation.rule.remove_from_client,
+                    action != servarr_crds::RemediationAction::Remove,
+                    action != servarr_crds::RemediationAction::BlocklistAndSearch,
+                )
+                .await;
+            if let Err(e) = result {
+                warn!(app = %app_name, %title, error = %e, "queue remediation failed");
+                increment_queue_remediations(app_type, ns, reason, "error");
+                failed += 1;
+                continue;
+            }
+            *status.remediated.entry(reason.to_string()).or_default() += 1;
+        }
+        done += 1;
+        increment_queue_remediations(
+            app_type,
+            ns,
+            reason,
+            if spec.dry_run {
+                "dry_run"
+            } else {
+                "remediated"
+            },
+        );
+        info!(app = %app_name, %title, %reason, ?action, dry_run = spec.dry_run, "remediating stuck queue item");
+        let _ = recorder
+            .publish(
+                &Event {
+                    type_: EventType::Normal,
+                    reason: "QueueItemRemediated".into(),
+                    note: Some(format!(
+                        "{}{action:?} for {title} ({reason})",
+                        if spec.dry_run { "Dry run: " } else { "" }
+                    )),
+                    action: "QueueRemediation".into(),
+                    secondary: None,
+                },
+                obj_ref,
+            )
+            .await;
+        status.recent.insert(
+            0,
+            servarr_crds::RemediatedItem {
+                title,
+                reason: reason.to_string(),
+                action: format!("{action:?}"),
+                time: now_str.clone(),
+                dry_run: spec.dry_run,
+            },
+        );
+    }
+    status
+        .recent
+        .truncate(servarr_crds::QueueRemediationStatus::MAX_RECENT);
+    status.last_check_result = Some(match (done, failed, spec.dry_run) {
+        (0, 0, _) => "No stuck items".to_string(),
+        (n, 0, true) => format!("{n} item(s) would be remediated (dry run)"),
+        (n, 0, false) => format!("{n} item(s) remediated"),
+        (n, f, _) => format!("{n} item(s) remediated, {f} failed"),
+    });
+    Some(status)
+}
+
+/// Apply TRaSH-guide custom formats and quality definitions per
+/// `spec.trashSync` when its schedule is due.
+async fn maybe_run_trash_sync(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
+) -> Option<servarr_crds::TrashSyncStatus> {
+    let spec = app.spec.trash_sync.as_ref().filter(|s| s.enabled)?;
+    if !matches!(app.spec.app, AppType::Sonarr | AppType::Radarr) {
+        return None;
+    }
+    let previous = app
+        .status
+        .as_ref()
+        .and_then(|s| s.last_trash_sync.clone())
+        .unwrap_or_default();
+    let failed = |result: String| {
+        Some(servarr_crds::TrashSyncStatus {
+            result: Some(result),
+            succeeded: false,
+            ..previous.clone()
+        })
+    };
+
+    let schedule = match crate::schedule::parse(&spec.schedule) {
+        Ok(s) => s,
+        Err(e) => return failed(e),
+    };
+    use chrono::Utc;
+    let now = Utc::now();
+    let last_sync = previous
+        .time
+        .as_deref()
+        .and_then(|t| t.parse::<chrono::DateTime<Utc>>().ok());
+    let is_due = match last_sync {
+        Some(last) => schedule.after(&last).take(1).any(|next| next <= now),
+        None => true,
+    };
+    if !is_due {
+        return Some(previous);
+    }
+
+    let mut template = crate::trash_sync::Template::default();
+    for name in &spec.templates {
+        let Some(json) = crate::trash_sync::builtin(&app.spec.app, name) else {
+            return failed(format!("unknown template '{name}'"));
+        };
+        match crate::trash_sync::Template::parse(json) {
+            Ok(t) => template = template.merge(t),
+            Err(e) => return failed(format!("template '{name}': {e}")),
+        }
+    }
+    if let Some(ref cm_name) = spec.config_map {
+        let cm = match Api::<ConfigMap>::namespaced(client.clone(), ns)
+            .get(cm_name)
+            .await
+        {
+            Ok(cm) => cm,
+            Err(e) => return failed(format!("ConfigMap {cm_name}: {e}")),
+        };
+        for (key, json) in cm.data.unwrap_or_default() {
+            if !key.ends_with(".json") {
+                continue;
+            }
+            match crate::trash_sync::Template::parse(&json) {
+                Ok(t) => template = template.merge(t),
+                Err(e) => return failed(format!("ConfigMap {cm_name} key {key}: {e}")),
+            }
+        }
+    }
+
+    let Some((secret_name, key)) = app.spec.api_key_ref() else {
+        return failed("apiKeySecret is required".into());
+    };
+    let api_key = match servarr_api::read_secret_key(client, ns, secret_name, key).await {
+        Ok(k) => k,
+        Err(e) => return failed(format!("secret read error: {e}")),
+    };
+    let app_name = servarr_resources::common::app_name(app);
+    let defaults = servarr_crds::AppDefaults::for_app(&app.spec.app);
+    let svc_spec = app.spec.service.as_ref().unwrap_or(&defaults.service);
+    let port = svc_spec.ports.first().map(|p| p.port).unwrap_or(80);
+    let base_url = format!("http://{app_name}.{ns}.svc:{port}");
+    let api =
+        match servarr_api::ServarrClient::new(&base_url, &api_key, app_type_to_kind(&app.spec.app))
+        {
+            Ok(api) => api,
+            Err(e) => return failed(format!("client error: {e}")),
+        };
+
+    let app_type = app.spec.app.as_str();
+    let outcome = match crate::trash_sync::sync(&api, &template).await {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            warn!(app = %app_name, error = %e, "TRaSH sync failed");
+            increment_trash_syncs(app_type, ns, "error");
+            return failed(format!("sync error: {e}"));
+        }
+    };
+    let succeeded = outcome.failed.is_empty();
+    let message = outcome.message();
+    increment_trash_syncs(app_type, ns, if succeeded { "success" } else { "partial" });
+    info!(app = %app_name, %message, "TRaSH sync finished");
+    if !outcome.created.is_empty()
+        || !outcome.updated.is_empty()
+        || outcome.quality_definitions > 0
+        || !succeeded
+    {
+        let _ = recorder
+            .publish(
+                &Event {
+                    type_: if succeeded {
+                        EventType::Normal
+                    } else {
+                        EventType::Warning
+                    },
+                    reason: if succeeded {
+                        "TrashSynced".into()
+                    } else {
+                        "TrashSyncFailed".into()
+                    },
+                    note: Some(message.clone()),
+                    action: "TrashSync".into(),
+                    secondary: None,
+                },
+                obj_ref,
+            )
+            .await;
+    }
+    Some(servarr_crds::TrashSyncStatus {
+        time: Some(chrono_now()),
+        result: Some(message),
+        succeeded,
+        custom_formats_changed: (outcome.created.len() + outcome.updated.len()) as u32,
+        quality_definitions_changed: outcome.quality_definitions as u32,
+    })
+}
+
+/// Roll the app's image forward to the newest registry tag of the same
+/// form when `updatePolicy.mode` is `Auto` and its schedule is due. A
+/// suspended or frozen app waits for the next scheduled check.
+async fn maybe_update_image(
+    ctx: &Context,
+    app: &ServarrApp,
+    maintenance: &maintenance::MaintenancePlan,
+    disruptions: &mut maintenance::DisruptionGate,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
+) -> Option<servarr_crds::ImageUpdateStatus> {
+    let policy = app
+        .spec
+        .update_policy
+        .as_ref()
+        .filter(|p| p.mode == UpdateMode::Auto)?;
+    let previous = app
+        .status
+        .as_ref()
+        .and_then(|s| s.image_update.clone())
+        .unwrap_or_default();
+    let failed = |result: String| {
+        Some(servarr_crds::ImageUpdateStatus {
+            result: Some(result),
+            ..previous.clone()
+        })
+    };
+
+    let Some(base) =
+        crate::image_update::base_image(app, &ctx.image_overrides, ctx.default_arch.as_deref())
+    else {
+        return failed("no default image for the node architecture".into());
+    };
+    if !base.digest.is_empty() {
+        return failed("spec.image.digest pins the image".into());
+    }
+    let schedule = match crate::schedule::parse(&policy.schedule) {
+        Ok(s) => s,
+        Err(e) => return failed(e),
+    };
+    use chrono::Utc;
+    let now = Utc::now();
+    let last_check = previous
+        .last_check
+        .as_deref()
+        .and_then(|t| t.parse::<chrono::DateTime<Utc>>().ok());
+    let is_due = match last_check {
+        Some(last) => schedule.after(&last).take(1).any(|next| next <= now),
+        None => true,
+    };
+    if !is_due
+        || maintenance.frozen()
+        || suspension(app, maintenance, &chrono_now()).is_some()
+        || !disruptions.allow(
+            "ImageUpdate",
+            format!("Check {} for a newer tag", base.repository),
+        )
+    {
+        return Some(previous);
+    }
+
+    let repository = base.repository.clone();
+    let current = crate::image_update::upgraded_image(
+        app,
+        Some(&previous),
+        &ctx.image_overrides,
+        ctx.default_arch.as_deref(),
+    )
+    .unwrap_or(base)
+    .tag;
+    let tags = match crate::image_digest::DigestResolver::default()
+        .tags(&repository)
+        .await
+    {
+        Ok(tags) => tags,
+        Err(e) => return failed(e),
+    };
+    let newer = match crate::image_update::newer_tag(&current, &tags, policy.allow_major) {
+        Ok(newer) => newer,
+        Err(e) => return failed(e),
+    };
+    let Some(newer) = newer else {
+        return Some(servarr_crds::ImageUpdateStatus {
+            last_check: Some(chrono_now()),
+            result: Some(format!("{current} is the newest tag")),
+            ..previous
+        });
+    };
+
+    let message = format!("Upgraded from {current} to {newer}");
+    info!(app = %servarr_resources::common::app_name(app), %message, "image updated");
+    let _ = recorder
+        .publish(
+            &Event {
+                type_: EventType::Normal,
+                reason: "ImageUpgraded".into(),
+                note: Some(message.clone()),
+                action: "Update".into(),
+                secondary: None,
+            },
+            obj_ref,
+        )
+        .await;
+    let mut history = previous.history;
+    history.insert(
+        0,
+        servarr_crds::ImageUpgrade {
+            from: current,
+            to: newer.clone(),
+            time: chrono_now(),
+        },
+    );
+    history.truncate(servarr_crds::ImageUpdateStatus::MAX_HISTORY);
+    Some(servarr_crds::ImageUpdateStatus {
+        tag: Some(newer),
+        last_check: Some(chrono_now()),
+        result: Some(message),
+        history,
+    })
+}
+
 async fn maybe_restore_backup(
     client: &Client,
     app: &ServarrApp,
     ns: &str,
     name: &str,
     restore_id: &str,
-    recorder: &Recorder,
+    recorder: &ThrottledRecorder<'_>,
     obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
 ) {
     // Only Servarr v3 apps support backup/restore API
-    if !matches!(
-        app.spec.app,
-        AppType::Sonarr | AppType::Radarr | AppType::Lidarr | AppType::Prowlarr
-    ) {
+    if !app.spec.app.is_servarr_v3() {
         warn!(%name, app_type = ?app.spec.app, "restore-from annotation set on unsupported app type, ignoring");
         return;
     }
@@ -1412,9 +4848,7 @@ async fn maybe_restore_backup(
 
     info!(%name, backup_id, "restore-from-backup triggered");
 
-    let deploy_api = Api::<Deployment>::namespaced(client.clone(), ns);
-
-    // Step 1: Scale deployment to 0
+    // Step 1: Scale the workload to 0
     let _ = recorder
         .publish(
             &Event {
@@ -1431,9 +4865,14 @@ async fn maybe_restore_backup(
     let scale_down = serde_json::json!({
         "spec": { "replicas": 0 }
     });
-    if let Err(e) = deploy_api
-        .patch(name, &PatchParams::default(), &Patch::Merge(scale_down))
-        .await
+    if let Err(e) = workload::patch(
+        client,
+        app,
+        ns,
+        &PatchParams::default(),
+        &Patch::Merge(scale_down),
+    )
+    .await
     {
         warn!(%name, error = %e, "failed to scale down for restore");
         return;
@@ -1442,36 +4881,36 @@ async fn maybe_restore_backup(
     // Wait for pods to terminate (poll for up to 60 seconds)
     for _ in 0..12 {
         tokio::time::sleep(Duration::from_secs(5)).await;
-        match deploy_api.get(name).await {
-            Ok(d) => {
-                let ready = d
-                    .status
-                    .as_ref()
-                    .and_then(|s| s.ready_replicas)
-                    .unwrap_or(0);
+        match workload::ready_replicas(client, app, ns).await {
+            Ok(ready) => {
                 if ready == 0 {
                     break;
                 }
             }
             Err(e) => {
-                warn!(%name, error = %e, "failed to check deployment status during restore");
+                warn!(%name, error = %e, "failed to check workload status during restore");
                 break;
             }
         }
     }
 
     // Step 2: Build API client and call restore
-    let api_key = match app.spec.api_key_secret.as_deref() {
-        Some(secret_name) => {
-            match servarr_api::read_secret_key(client, ns, secret_name, "api-key").await {
+    let api_key = match app.spec.api_key_ref() {
+        Some((secret_name, key)) => {
+            match servarr_api::read_secret_key(client, ns, secret_name, key).await {
                 Ok(k) => k,
                 Err(e) => {
                     warn!(%name, error = %e, "failed to read API key for restore");
                     // Scale back up before returning
                     let scale_up = serde_json::json!({ "spec": { "replicas": 1 } });
-                    let _ = deploy_api
-                        .patch(name, &PatchParams::default(), &Patch::Merge(scale_up))
-                        .await;
+                    let _ = workload::patch(
+                        client,
+                        app,
+                        ns,
+                        &PatchParams::default(),
+                        &Patch::Merge(scale_up),
+                    )
+                    .await;
                     return;
                 }
             }
@@ -1479,9 +4918,14 @@ async fn maybe_restore_backup(
         None => {
             warn!(%name, "no api_key_secret configured, cannot restore");
             let scale_up = serde_json::json!({ "spec": { "replicas": 1 } });
-            let _ = deploy_api
-                .patch(name, &PatchParams::default(), &Patch::Merge(scale_up))
-                .await;
+            let _ = workload::patch(
+                client,
+                app,
+                ns,
+                &PatchParams::default(),
+                &Patch::Merge(scale_up),
+            )
+            .await;
             return;
         }
     };
@@ -1492,7 +4936,8 @@ async fn maybe_restore_backup(
     let port = svc_spec.ports.first().map(|p| p.port).unwrap_or(80);
     let base_url = format!("http://{app_name}.{ns}.svc:{port}");
 
-    // Only Servarr v3 apps (Sonarr/Radarr/Lidarr/Prowlarr) support backup/restore
+    // Only Servarr v3 apps (Sonarr/Radarr/Lidarr/Readarr/Whisparr/Prowlarr)
+    // support backup/restore
     let restore_result =
         match servarr_api::ServarrClient::new(&base_url, &api_key, app_type_to_kind(&app.spec.app))
         {
@@ -1500,9 +4945,14 @@ async fn maybe_restore_backup(
             Err(e) => {
                 warn!(%name, error = %e, "failed to create API client for restore");
                 let scale_up = serde_json::json!({ "spec": { "replicas": 1 } });
-                let _ = deploy_api
-                    .patch(name, &PatchParams::default(), &Patch::Merge(scale_up))
-                    .await;
+                let _ = workload::patch(
+                    client,
+                    app,
+                    ns,
+                    &PatchParams::default(),
+                    &Patch::Merge(scale_up),
+                )
+                .await;
                 return;
             }
         };
@@ -1544,9 +4994,14 @@ async fn maybe_restore_backup(
 
     // Step 3: Scale back up
     let scale_up = serde_json::json!({ "spec": { "replicas": 1 } });
-    if let Err(e) = deploy_api
-        .patch(name, &PatchParams::default(), &Patch::Merge(scale_up))
-        .await
+    if let Err(e) = workload::patch(
+        client,
+        app,
+        ns,
+        &PatchParams::default(),
+        &Patch::Merge(scale_up),
+    )
+    .await
     {
         warn!(%name, error = %e, "failed to scale back up after restore");
     }
@@ -1576,13 +5031,38 @@ async fn maybe_restore_backup(
 #[derive(Debug)]
 pub(crate) struct DiscoveredApp {
     pub(crate) name: String,
+    pub(crate) namespace: String,
     pub(crate) app_type: AppType,
     pub(crate) base_url: String,
     pub(crate) api_key: String,
     pub(crate) instance: Option<String>,
+    /// Whether the app's Service has a ready endpoint. Syncs skip apps that
+    /// don't, so Prowlarr and Overseerr never get a URL that refuses
+    /// connections, but keep their existing registrations.
+    pub(crate) ready: bool,
+}
+
+impl DiscoveredApp {
+    /// Name the app is registered under in Prowlarr and Overseerr. MediaStack
+    /// children already end in their instance; other instanced apps get it
+    /// appended so several instances of one app stay distinguishable.
+    pub(crate) fn registration_name(&self) -> String {
+        match self.instance.as_deref() {
+            Some(inst) if !self.name.ends_with(&format!("-{inst}")) => {
+                format!("{}-{inst}", self.name)
+            }
+            _ => self.name.clone(),
+        }
+    }
+
+    /// Overseerr allows one default server per app for regular and one for
+    /// 4K requests: the un-instanced app and the `4k` instance.
+    fn overseerr_default(&self) -> bool {
+        matches!(self.instance.as_deref(), None | Some("4k"))
+    }
 }
 
-/// Discover all Servarr v3 apps (Sonarr/Radarr/Lidarr) in a namespace
+/// Discover all Servarr v3 apps (Sonarr/Radarr/Lidarr/Readarr/Whisparr) in a namespace
 /// and resolve their service URLs and API keys.
 pub(crate) async fn discover_namespace_apps(
     client: &Client,
@@ -1595,24 +5075,29 @@ pub(crate) async fn discover_namespace_apps(
         .list(&ListParams::default())
         .await
         .map_err(|e| anyhow::anyhow!("failed to list ServarrApps: {e}"))?;
+    Ok(discover_listed_apps(client, namespace, &apps.items).await)
+}
 
+/// The apps among `apps`, listed from `namespace`, that syncs register,
+/// with their service URLs and API keys.
+async fn discover_listed_apps(
+    client: &Client,
+    namespace: &str,
+    apps: &[ServarrApp],
+) -> Vec<DiscoveredApp> {
     let mut discovered = Vec::new();
-    for app in &apps {
-        // Only sync Servarr v3 apps (they share the /api/v3 interface)
-        if !matches!(
-            app.spec.app,
-            AppType::Sonarr | AppType::Radarr | AppType::Lidarr
-        ) {
+    for app in apps {
+        // Only sync the Servarr apps that download media
+        if !app.spec.app.downloads_media() {
             continue;
         }
 
-        let secret_name = match app.spec.api_key_secret.as_deref() {
-            Some(s) => s,
+        let (secret_name, key) = match app.spec.api_key_ref() {
+            Some(r) => r,
             None => continue,
         };
 
-        let api_key = match servarr_api::read_secret_key(client, namespace, secret_name, "api-key")
-            .await
+        let api_key = match servarr_api::read_secret_key(client, namespace, secret_name, key).await
         {
             Ok(k) => k,
             Err(e) => {
@@ -1627,36 +5112,243 @@ pub(crate) async fn discover_namespace_apps(
         let port = svc_spec.ports.first().map(|p| p.port).unwrap_or(80);
         let base_url = format!("http://{app_name}.{namespace}.svc:{port}");
 
+        let ready = match service_has_ready_endpoints(client, namespace, &app_name).await {
+            Ok(ready) => ready,
+            Err(e) => {
+                // Fail open: without EndpointSlice access, sync as before
+                warn!(app = %app.name_any(), error = %e, "failed to check endpoint readiness");
+                true
+            }
+        };
+
         discovered.push(DiscoveredApp {
             name: app.name_any(),
+            namespace: namespace.to_string(),
             app_type: app.spec.app.clone(),
             base_url,
             api_key,
             instance: app.spec.instance.clone(),
+            ready,
+        });
+    }
+
+    discovered
+}
+
+/// Transmission and SABnzbd ServarrApps in `namespace` that `app` should
+/// register as download clients: those of the same MediaStack when `app`
+/// belongs to one, otherwise all of them.
+async fn discover_download_clients(
+    client: &Client,
+    app: &ServarrApp,
+    namespace: &str,
+) -> Result<Vec<crate::download_clients::Target>, anyhow::Error> {
+    use kube::api::ListParams;
+
+    let mut params = ListParams::default();
+    let labels = app.labels();
+    let stack_selector = labels.get("servarr.dev/stack").map(|stack| {
+        match labels.get("servarr.dev/stack-namespace") {
+            Some(stack_ns) => {
+                format!("servarr.dev/stack={stack},servarr.dev/stack-namespace={stack_ns}")
+            }
+            None => format!("servarr.dev/stack={stack}"),
+        }
+    });
+    if let Some(ref selector) = stack_selector {
+        params = params.labels(selector);
+    }
+    // Children of a stack that spans namespaces list every one of them.
+    let namespaces: Vec<&str> = match app
+        .annotations()
+        .get(crate::media_stack_controller::STACK_NAMESPACES_ANNOTATION)
+    {
+        Some(list) if stack_selector.is_some() => list.split(',').collect(),
+        _ => vec![namespace],
+    };
+    let mut apps = Vec::new();
+    for ns in namespaces {
+        let list = Api::<ServarrApp>::namespaced(client.clone(), ns)
+            .list(&params)
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to list ServarrApps in {ns}: {e}"))?;
+        apps.extend(list.items);
+    }
+
+    let read = |namespace: String, secret: String, key: String| async move {
+        servarr_api::read_secret_key(client, &namespace, &secret, &key)
+            .await
+            .map_err(|e| warn!(%secret, error = %e, "download client sync: failed to read secret"))
+            .ok()
+    };
+
+    let mut targets = Vec::new();
+    for dc in &apps {
+        let namespace = dc.namespace().unwrap_or_else(|| namespace.to_string());
+        let implementation = match dc.spec.app {
+            AppType::Transmission => "Transmission",
+            AppType::Sabnzbd => "Sabnzbd",
+            _ => continue,
+        };
+        // Transmission's RPC login comes from appConfig.transmission
+        // (credentialsSecretRef or auth), or from adminCredentials, which
+        // the operator applies over RPC.
+        let login = match dc.spec.app_config {
+            Some(servarr_crds::AppConfig::Transmission(ref tc)) if tc.rpc_login().is_some() => {
+                tc.rpc_login()
+            }
+            _ => dc
+                .spec
+                .admin_credentials
+                .as_ref()
+                .map(|ac| (ac.secret_name.as_str(), "username", "password")),
+        };
+        let (username, password) = match login {
+            Some((secret, user_key, pass_key)) if dc.spec.app == AppType::Transmission => (
+                read(namespace.clone(), secret.into(), user_key.into()).await,
+                read(namespace.clone(), secret.into(), pass_key.into()).await,
+            ),
+            _ => (None, None),
+        };
+        let api_key = match dc.spec.api_key_ref() {
+            Some((secret, key)) if dc.spec.app == AppType::Sabnzbd => {
+                read(namespace.clone(), secret.into(), key.into()).await
+            }
+            _ => None,
+        };
+
+        let service = servarr_resources::common::app_name(dc);
+        let defaults = servarr_crds::AppDefaults::for_app(&dc.spec.app);
+        let svc_spec = dc.spec.service.as_ref().unwrap_or(&defaults.service);
+        let port = svc_spec.ports.first().map(|p| p.port).unwrap_or(80);
+        let ready = match service_has_ready_endpoints(client, &namespace, &service).await {
+            Ok(ready) => ready,
+            Err(e) => {
+                warn!(app = %dc.name_any(), error = %e, "failed to check endpoint readiness");
+                true
+            }
+        };
+
+        targets.push(crate::download_clients::Target {
+            name: dc.name_any(),
+            implementation,
+            host: format!("{service}.{namespace}.svc"),
+            port,
+            username,
+            password,
+            api_key,
+            ready,
         });
     }
+    Ok(targets)
+}
+
+/// Whether any EndpointSlice of `service` has a ready endpoint.
+async fn service_has_ready_endpoints(
+    client: &Client,
+    namespace: &str,
+    service: &str,
+) -> Result<bool, kube::Error> {
+    use k8s_openapi::api::discovery::v1::EndpointSlice;
+    use kube::api::ListParams;
+
+    let slices = Api::<EndpointSlice>::namespaced(client.clone(), namespace)
+        .list(&ListParams::default().labels(&format!("kubernetes.io/service-name={service}")))
+        .await?;
+    Ok(endpoints_ready(&slices.items))
+}
+
+/// An endpoint with no `ready` condition counts as ready, as the
+/// EndpointSlice API asks consumers to do.
+fn endpoints_ready(slices: &[k8s_openapi::api::discovery::v1::EndpointSlice]) -> bool {
+    slices
+        .iter()
+        .flat_map(|s| &s.endpoints)
+        .any(|e| e.conditions.as_ref().and_then(|c| c.ready).unwrap_or(true))
+}
+
+/// Tell the user which discovered apps were left out of a sync because their
+/// pods aren't ready yet. They're picked up on a later reconcile.
+async fn publish_deferred(
+    discovered: &[DiscoveredApp],
+    target: &str,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
+) {
+    let deferred: Vec<&str> = discovered
+        .iter()
+        .filter(|a| !a.ready)
+        .map(|a| a.name.as_str())
+        .collect();
+    if deferred.is_empty() {
+        return;
+    }
+    info!(apps = ?deferred, %target, "deferring sync of apps without ready endpoints");
+    let _ = recorder
+        .publish(
+            &Event {
+                type_: EventType::Normal,
+                reason: "SyncDeferred".into(),
+                note: Some(format!(
+                    "Not syncing {} to {target} until their Services have ready endpoints",
+                    deferred.join(", ")
+                )),
+                action: format!("{target}Sync"),
+                secondary: None,
+            },
+            obj_ref,
+        )
+        .await;
+}
+
+/// What a Prowlarr, Overseerr, or Bazarr sync pass did. Failures for single apps
+/// don't abort the pass; they are collected in `errors`.
+#[derive(Debug, Default)]
+struct SyncReport {
+    synced: usize,
+    removed: usize,
+    errors: Vec<String>,
+}
 
-    Ok(discovered)
+/// The `status.syncStatus` for a sync pass that ended with `result`. A
+/// failed pass keeps the counts and time of the last completed one.
+fn sync_status(
+    previous: Option<&SyncStatus>,
+    result: &Result<SyncReport, anyhow::Error>,
+    now: &str,
+) -> SyncStatus {
+    match result {
+        Ok(report) => SyncStatus {
+            last_sync_time: Some(now.to_string()),
+            apps_synced: report.synced as u32,
+            apps_removed: report.removed as u32,
+            last_error: (!report.errors.is_empty()).then(|| report.errors.join("; ")),
+        },
+        Err(e) => SyncStatus {
+            last_error: Some(format!("{e:#}")),
+            ..previous.cloned().unwrap_or_default()
+        },
+    }
 }
 
-/// Sync discovered namespace apps into Prowlarr as registered applications.
+/// Sync the apps discovered in the `prowlarrSync` namespaces into Prowlarr
+/// as registered applications.
 async fn sync_prowlarr_apps(
     client: &Client,
     prowlarr: &ServarrApp,
-    target_ns: &str,
-    recorder: &Recorder,
+    sync_spec: &servarr_crds::ProwlarrSyncSpec,
+    recorder: &ThrottledRecorder<'_>,
     obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
-) -> Result<(), anyhow::Error> {
+) -> Result<SyncReport, anyhow::Error> {
     let prowlarr_name = prowlarr.name_any();
     let ns = prowlarr.namespace().unwrap_or_else(|| "default".into());
 
     // Build Prowlarr client
-    let secret_name = prowlarr
+    let (secret_name, key) = prowlarr
         .spec
-        .api_key_secret
-        .as_deref()
+        .api_key_ref()
         .ok_or_else(|| anyhow::anyhow!("Prowlarr sync requires api_key_secret"))?;
-    let prowlarr_key = servarr_api::read_secret_key(client, &ns, secret_name, "api-key").await?;
+    let prowlarr_key = servarr_api::read_secret_key(client, &ns, secret_name, key).await?;
 
     let prowlarr_app_name = servarr_resources::common::app_name(prowlarr);
     let defaults = servarr_crds::AppDefaults::for_app(&prowlarr.spec.app);
@@ -1666,8 +5358,45 @@ async fn sync_prowlarr_apps(
 
     let prowlarr_client = servarr_api::ProwlarrClient::new(&prowlarr_url, &prowlarr_key)?;
 
-    // Discover apps in target namespace
-    let discovered = discover_namespace_apps(client, target_ns).await?;
+    // Discover apps in the target namespaces. One the operator may not list
+    // is reported, and its registrations are left alone.
+    let selected = if sync_spec.namespace_selector.is_empty() {
+        Vec::new()
+    } else {
+        select_namespaces(client, &sync_spec.namespace_selector).await?
+    };
+    let namespaces = crate::prowlarr_sync::target_namespaces(sync_spec, &ns, &selected);
+    let mut discovered = Vec::new();
+    let mut unwatched = Vec::new();
+    let mut errors = Vec::new();
+    for target_ns in &namespaces {
+        match Api::<ServarrApp>::namespaced(client.clone(), target_ns)
+            .list(&kube::api::ListParams::default())
+            .await
+        {
+            Ok(apps) => {
+                discovered.extend(discover_listed_apps(client, target_ns, &apps.items).await)
+            }
+            Err(e) => {
+                let message = crate::prowlarr_sync::unwatchable(target_ns, &e);
+                warn!(prowlarr = %prowlarr_name, "{message}");
+                let _ = recorder
+                    .publish(
+                        &Event {
+                            type_: EventType::Warning,
+                            reason: "NamespaceNotWatchable".into(),
+                            note: Some(message.clone()),
+                            action: "ProwlarrSync".into(),
+                            secondary: None,
+                        },
+                        obj_ref,
+                    )
+                    .await;
+                unwatched.push(target_ns.as_str());
+                errors.push(message);
+            }
+        }
+    }
 
     // Get current Prowlarr applications
     let existing = prowlarr_client.list_applications().await?;
@@ -1685,22 +5414,33 @@ async fn sync_prowlarr_apps(
             })
             .collect();
 
-    let auto_remove = prowlarr
-        .spec
-        .prowlarr_sync
-        .as_ref()
-        .map(|s| s.auto_remove)
-        .unwrap_or(true);
+    let auto_remove = sync_spec.auto_remove;
+
+    // Create or update the app profiles indexers refer to
+    errors.extend(crate::prowlarr_sync::sync_app_profiles(&prowlarr_client, sync_spec).await?);
+    let mut report = SyncReport {
+        errors,
+        ..Default::default()
+    };
 
     // Add or update discovered apps
     let mut synced_urls = std::collections::HashSet::new();
     for app in &discovered {
         synced_urls.insert(app.base_url.clone());
+        if !app.ready {
+            continue;
+        }
+        if let Some(e) = servarr_api::faults::sync("prowlarr-apps", &app.name) {
+            report.errors.push(format!("sync {}: {e}", app.name));
+            continue;
+        }
 
         let implementation = match app.app_type {
             AppType::Sonarr => "Sonarr",
             AppType::Radarr => "Radarr",
             AppType::Lidarr => "Lidarr",
+            AppType::Readarr => "Readarr",
+            AppType::Whisparr => "Whisparr",
             _ => continue,
         };
 
@@ -1708,39 +5448,52 @@ async fn sync_prowlarr_apps(
             AppType::Sonarr => "SonarrSettings",
             AppType::Radarr => "RadarrSettings",
             AppType::Lidarr => "LidarrSettings",
+            AppType::Readarr => "ReadarrSettings",
+            AppType::Whisparr => "WhisparrSettings",
             _ => continue,
         };
 
+        let mut fields = vec![
+            servarr_api::prowlarr::ProwlarrAppField {
+                name: "baseUrl".into(),
+                value: serde_json::Value::String(app.base_url.clone()),
+            },
+            servarr_api::prowlarr::ProwlarrAppField {
+                name: "apiKey".into(),
+                value: serde_json::Value::String(app.api_key.clone()),
+            },
+        ];
+        fields.extend(crate::prowlarr_sync::category_fields(
+            sync_spec,
+            &app.app_type,
+            app.instance.as_deref(),
+        ));
         let new_app = servarr_api::prowlarr::ProwlarrApp {
             id: 0,
-            name: app.name.clone(),
-            sync_level: "fullSync".into(),
+            name: crate::prowlarr_sync::registration_name(
+                app.registration_name(),
+                &app.namespace,
+                &ns,
+                namespaces.len(),
+            ),
+            sync_level: sync_spec.sync_level.as_str().into(),
             implementation: implementation.into(),
             config_contract: config_contract.into(),
-            fields: vec![
-                servarr_api::prowlarr::ProwlarrAppField {
-                    name: "baseUrl".into(),
-                    value: serde_json::Value::String(app.base_url.clone()),
-                },
-                servarr_api::prowlarr::ProwlarrAppField {
-                    name: "apiKey".into(),
-                    value: serde_json::Value::String(app.api_key.clone()),
-                },
-            ],
+            fields,
             tags: Vec::new(),
         };
 
         if let Some(existing_app) = existing_by_url.get(&app.base_url) {
-            // Update if name changed
-            if existing_app.name != app.name {
+            // Update if the name, sync level, or categories changed
+            if crate::prowlarr_sync::needs_update(existing_app, &new_app) {
                 info!(prowlarr = %prowlarr_name, app = %app.name, "updating Prowlarr application");
-                let mut updated = new_app;
-                updated.id = existing_app.id;
+                let updated = crate::prowlarr_sync::merge(existing_app, &new_app);
                 if let Err(e) = prowlarr_client
                     .update_application(existing_app.id, &updated)
                     .await
                 {
                     warn!(app = %app.name, error = %e, "failed to update Prowlarr application");
+                    report.errors.push(format!("update {}: {e}", app.name));
                 }
             }
         } else {
@@ -1748,6 +5501,7 @@ async fn sync_prowlarr_apps(
             info!(prowlarr = %prowlarr_name, app = %app.name, "adding application to Prowlarr");
             if let Err(e) = prowlarr_client.add_application(&new_app).await {
                 warn!(app = %app.name, error = %e, "failed to add Prowlarr application");
+                report.errors.push(format!("add {}: {e}", app.name));
             }
         }
     }
@@ -1761,21 +5515,31 @@ async fn sync_prowlarr_apps(
                 .find(|f| f.name == "baseUrl")
                 .and_then(|f| f.value.as_str())
                 .unwrap_or("");
-            if !url.is_empty() && !synced_urls.contains(url) {
+            let unknown = unwatched
+                .iter()
+                .any(|n| crate::prowlarr_sync::in_namespace(url, n));
+            if !url.is_empty() && !synced_urls.contains(url) && !unknown {
                 info!(prowlarr = %prowlarr_name, app = %app.name, "removing stale application from Prowlarr");
-                if let Err(e) = prowlarr_client.delete_application(app.id).await {
-                    warn!(app = %app.name, error = %e, "failed to remove Prowlarr application");
+                match prowlarr_client.delete_application(app.id).await {
+                    Ok(()) => report.removed += 1,
+                    Err(e) => {
+                        warn!(app = %app.name, error = %e, "failed to remove Prowlarr application");
+                        report.errors.push(format!("remove {}: {e}", app.name));
+                    }
                 }
             }
         }
     }
 
+    publish_deferred(&discovered, "Prowlarr", recorder, obj_ref).await;
+    let synced = discovered.iter().filter(|a| a.ready).count();
+    report.synced = synced;
     let _ = recorder
         .publish(
             &Event {
                 type_: EventType::Normal,
                 reason: "ProwlarrSyncComplete".into(),
-                note: Some(format!("Synced {} apps to Prowlarr", discovered.len())),
+                note: Some(format!("Synced {synced} apps to Prowlarr")),
                 action: "ProwlarrSync".into(),
                 secondary: None,
             },
@@ -1783,7 +5547,159 @@ async fn sync_prowlarr_apps(
         )
         .await;
 
-    Ok(())
+    Ok(report)
+}
+
+/// Connect Bazarr to the discovered Sonarr and Radarr of the configured
+/// instance, then apply `appConfig.bazarr`. Bazarr takes one of each, so
+/// other instances are ignored; with `autoRemove` a connection whose app is
+/// gone is turned off.
+async fn sync_bazarr_apps(
+    client: &Client,
+    bazarr: &ServarrApp,
+    target_ns: &str,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
+) -> Result<SyncReport, anyhow::Error> {
+    let bazarr_name = bazarr.name_any();
+    let ns = bazarr.namespace().unwrap_or_else(|| "default".into());
+
+    // Build Bazarr client
+    let (secret_name, key) = bazarr
+        .spec
+        .api_key_ref()
+        .ok_or_else(|| anyhow::anyhow!("Bazarr sync requires api_key_secret"))?;
+    let bazarr_key = servarr_api::read_secret_key(client, &ns, secret_name, key).await?;
+
+    let bazarr_app_name = servarr_resources::common::app_name(bazarr);
+    let defaults = servarr_crds::AppDefaults::for_app(&bazarr.spec.app);
+    let svc_spec = bazarr.spec.service.as_ref().unwrap_or(&defaults.service);
+    let port = svc_spec.ports.first().map(|p| p.port).unwrap_or(80);
+    let bazarr_url = format!("http://{bazarr_app_name}.{ns}.svc:{port}");
+
+    let bazarr_client = servarr_api::BazarrClient::new(&bazarr_url, &bazarr_key)?;
+
+    let sync_spec = bazarr.spec.bazarr_sync.clone().unwrap_or_default();
+
+    // The Sonarr and Radarr of the configured instance
+    let discovered: Vec<DiscoveredApp> = discover_namespace_apps(client, target_ns)
+        .await?
+        .into_iter()
+        .filter(|a| {
+            matches!(a.app_type, AppType::Sonarr | AppType::Radarr)
+                && a.instance == sync_spec.instance
+        })
+        .collect();
+
+    let settings = bazarr_client.settings().await?;
+
+    let mut report = SyncReport::default();
+    let mut form = Vec::new();
+    for (app_type, section, enabled, current) in [
+        (
+            AppType::Sonarr,
+            "sonarr",
+            settings.general.use_sonarr,
+            &settings.sonarr,
+        ),
+        (
+            AppType::Radarr,
+            "radarr",
+            settings.general.use_radarr,
+            &settings.radarr,
+        ),
+    ] {
+        match discovered.iter().find(|a| a.app_type == app_type) {
+            Some(app) if !app.ready => {}
+            Some(app) => {
+                if let Some(e) = servarr_api::faults::sync("bazarr-apps", &app.name) {
+                    report.errors.push(format!("sync {}: {e}", app.name));
+                    continue;
+                }
+                match crate::bazarr_sync::connect(
+                    section,
+                    &app.base_url,
+                    &app.api_key,
+                    enabled,
+                    current,
+                ) {
+                    Ok(fields) => {
+                        if !fields.is_empty() {
+                            info!(bazarr = %bazarr_name, app = %app.name, "connecting Bazarr");
+                        }
+                        form.extend(fields);
+                        report.synced += 1;
+                    }
+                    Err(e) => report.errors.push(format!("sync {}: {e}", app.name)),
+                }
+            }
+            None if sync_spec.auto_remove && enabled => {
+                info!(bazarr = %bazarr_name, %section, "turning off Bazarr connection to a removed app");
+                form.extend(crate::bazarr_sync::disconnect(section));
+                report.removed += 1;
+            }
+            None => {}
+        }
+    }
+
+    // Providers and language profiles
+    if let Some(servarr_crds::AppConfig::Bazarr(ref config)) = bazarr.spec.app_config {
+        let profiles = if config.language_profiles.is_empty() && config.default_profile.is_none() {
+            Vec::new()
+        } else {
+            bazarr_client.language_profiles().await?
+        };
+        form.extend(crate::bazarr_sync::config_form(
+            config, &settings, &profiles,
+        ));
+    }
+
+    if !form.is_empty()
+        && let Err(e) = bazarr_client.update_settings(&form).await
+    {
+        warn!(bazarr = %bazarr_name, error = %e, "failed to update Bazarr settings");
+        report.errors.push(format!("update settings: {e}"));
+        report.synced = 0;
+        report.removed = 0;
+    }
+
+    publish_deferred(&discovered, "Bazarr", recorder, obj_ref).await;
+    let synced = report.synced;
+    let _ = recorder
+        .publish(
+            &Event {
+                type_: EventType::Normal,
+                reason: "BazarrSyncComplete".into(),
+                note: Some(format!("Synced {synced} apps to Bazarr")),
+                action: "BazarrSync".into(),
+                secondary: None,
+            },
+            obj_ref,
+        )
+        .await;
+
+    Ok(report)
+}
+
+/// The names of the namespaces matching `labels`, for
+/// `prowlarrSync.namespaceSelector`.
+async fn select_namespaces(
+    client: &Client,
+    labels: &std::collections::BTreeMap<String, String>,
+) -> Result<Vec<String>, anyhow::Error> {
+    use k8s_openapi::api::core::v1::Namespace;
+    use kube::api::ListParams;
+
+    let params = ListParams::default().labels(&crate::prowlarr_sync::label_selector(labels));
+    match Api::<Namespace>::all(client.clone()).list(&params).await {
+        Ok(list) => Ok(list.iter().map(|n| n.name_any()).collect()),
+        Err(kube::Error::Api(status)) if status.code == 403 => Err(anyhow::anyhow!(
+            "namespaceSelector: the operator may not list namespaces (install it with watchAllNamespaces)"
+        )),
+        Err(e) => Err(anyhow::anyhow!(
+            "namespaceSelector: failed to list namespaces: {e}"
+        )),
+    }
 }
 
 /// Check if any Prowlarr instance with prowlarr_sync.enabled exists in the namespace.
@@ -1804,7 +5720,7 @@ async fn cleanup_prowlarr_registration(
     client: &Client,
     app: &ServarrApp,
     namespace: &str,
-    recorder: &Recorder,
+    recorder: &ThrottledRecorder<'_>,
     obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
 ) -> Result<(), anyhow::Error> {
     use kube::api::ListParams;
@@ -1827,13 +5743,12 @@ async fn cleanup_prowlarr_registration(
         None => return Ok(()), // No Prowlarr with sync, nothing to clean up
     };
 
-    let secret_name = match prowlarr.spec.api_key_secret.as_deref() {
-        Some(s) => s,
+    let (secret_name, key) = match prowlarr.spec.api_key_ref() {
+        Some(r) => r,
         None => return Ok(()),
     };
 
-    let prowlarr_key =
-        servarr_api::read_secret_key(client, namespace, secret_name, "api-key").await?;
+    let prowlarr_key = servarr_api::read_secret_key(client, namespace, secret_name, key).await?;
 
     let prowlarr_app_name = servarr_resources::common::app_name(prowlarr);
     let prowlarr_defaults = servarr_crds::AppDefaults::for_app(&prowlarr.spec.app);
@@ -1883,19 +5798,18 @@ async fn sync_overseerr_servers(
     client: &Client,
     overseerr: &ServarrApp,
     target_ns: &str,
-    recorder: &Recorder,
+    recorder: &ThrottledRecorder<'_>,
     obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
-) -> Result<(), anyhow::Error> {
+) -> Result<SyncReport, anyhow::Error> {
     let overseerr_name = overseerr.name_any();
     let ns = overseerr.namespace().unwrap_or_else(|| "default".into());
 
     // Build Overseerr client
-    let secret_name = overseerr
+    let (secret_name, key) = overseerr
         .spec
-        .api_key_secret
-        .as_deref()
+        .api_key_ref()
         .ok_or_else(|| anyhow::anyhow!("Overseerr sync requires api_key_secret"))?;
-    let overseerr_key = servarr_api::read_secret_key(client, &ns, secret_name, "api-key").await?;
+    let overseerr_key = servarr_api::read_secret_key(client, &ns, secret_name, key).await?;
 
     let overseerr_app_name = servarr_resources::common::app_name(overseerr);
     let defaults = servarr_crds::AppDefaults::for_app(&overseerr.spec.app);
@@ -1914,7 +5828,9 @@ async fn sync_overseerr_servers(
 
     // Get Overseerr config for default profile/directory settings
     let overseerr_config = match &overseerr.spec.app_config {
-        Some(servarr_crds::AppConfig::Overseerr(c)) => Some(c.as_ref()),
+        Some(servarr_crds::AppConfig::Overseerr(c) | servarr_crds::AppConfig::Jellyseerr(c)) => {
+            Some(c.as_ref())
+        }
         _ => None,
     };
 
@@ -1926,6 +5842,7 @@ async fn sync_overseerr_servers(
         .unwrap_or(true);
 
     // Track which hostname:port combos we sync so we can detect stale entries
+    let mut report = SyncReport::default();
     let mut synced_sonarr_keys = std::collections::HashSet::new();
     let mut synced_radarr_keys = std::collections::HashSet::new();
 
@@ -1942,6 +5859,13 @@ async fn sync_overseerr_servers(
             AppType::Sonarr => {
                 let key = (hostname.clone(), port as u16);
                 synced_sonarr_keys.insert(key);
+                if !app.ready {
+                    continue;
+                }
+                if let Some(e) = servarr_api::faults::sync("overseerr-servers", &app.name) {
+                    report.errors.push(format!("sync {}: {e}", app.name));
+                    continue;
+                }
 
                 let sonarr_defaults = overseerr_config.and_then(|c| c.sonarr.as_ref());
                 let (profile_id, profile_name, root_folder, enable_season_folders) = if is4k {
@@ -1968,7 +5892,7 @@ async fn sync_overseerr_servers(
                 };
 
                 let settings = overseerr::models::SonarrSettings::new(
-                    app.name.clone(),
+                    app.registration_name(),
                     hostname.clone(),
                     port,
                     app.api_key.clone(),
@@ -1978,7 +5902,7 @@ async fn sync_overseerr_servers(
                     root_folder,
                     is4k,
                     enable_season_folders,
-                    !is4k,
+                    app.overseerr_default(),
                 );
 
                 // Match existing by hostname + port
@@ -1991,17 +5915,26 @@ async fn sync_overseerr_servers(
                     updated.id = existing.id;
                     if let Err(e) = overseerr_client.update_sonarr(id, updated).await {
                         warn!(app = %app.name, error = %e, "failed to update Sonarr in Overseerr");
+                        report.errors.push(format!("update {}: {e}", app.name));
                     }
                 } else {
                     info!(overseerr = %overseerr_name, app = %app.name, "adding Sonarr server to Overseerr");
                     if let Err(e) = overseerr_client.create_sonarr(settings).await {
                         warn!(app = %app.name, error = %e, "failed to add Sonarr to Overseerr");
+                        report.errors.push(format!("add {}: {e}", app.name));
                     }
                 }
             }
             AppType::Radarr => {
                 let key = (hostname.clone(), port as u16);
                 synced_radarr_keys.insert(key);
+                if !app.ready {
+                    continue;
+                }
+                if let Some(e) = servarr_api::faults::sync("overseerr-servers", &app.name) {
+                    report.errors.push(format!("sync {}: {e}", app.name));
+                    continue;
+                }
 
                 let radarr_defaults = overseerr_config.and_then(|c| c.radarr.as_ref());
                 let (profile_id, profile_name, root_folder, minimum_availability) = if is4k {
@@ -2030,7 +5963,7 @@ async fn sync_overseerr_servers(
                 };
 
                 let settings = overseerr::models::RadarrSettings::new(
-                    app.name.clone(),
+                    app.registration_name(),
                     hostname.clone(),
                     port,
                     app.api_key.clone(),
@@ -2040,7 +5973,7 @@ async fn sync_overseerr_servers(
                     root_folder,
                     is4k,
                     minimum_availability,
-                    !is4k,
+                    app.overseerr_default(),
                 );
 
                 // Match existing by hostname + port
@@ -2053,11 +5986,13 @@ async fn sync_overseerr_servers(
                     updated.id = existing.id;
                     if let Err(e) = overseerr_client.update_radarr(id, updated).await {
                         warn!(app = %app.name, error = %e, "failed to update Radarr in Overseerr");
+                        report.errors.push(format!("update {}: {e}", app.name));
                     }
                 } else {
                     info!(overseerr = %overseerr_name, app = %app.name, "adding Radarr server to Overseerr");
                     if let Err(e) = overseerr_client.create_radarr(settings).await {
                         warn!(app = %app.name, error = %e, "failed to add Radarr to Overseerr");
+                        report.errors.push(format!("add {}: {e}", app.name));
                     }
                 }
             }
@@ -2072,8 +6007,12 @@ async fn sync_overseerr_servers(
             if !synced_sonarr_keys.contains(&key) {
                 let id = existing.id.unwrap_or(0.0) as i32;
                 info!(overseerr = %overseerr_name, server = %existing.name, "removing stale Sonarr server from Overseerr");
-                if let Err(e) = overseerr_client.delete_sonarr(id).await {
-                    warn!(server = %existing.name, error = %e, "failed to remove stale Sonarr from Overseerr");
+                match overseerr_client.delete_sonarr(id).await {
+                    Ok(_) => report.removed += 1,
+                    Err(e) => {
+                        warn!(server = %existing.name, error = %e, "failed to remove stale Sonarr from Overseerr");
+                        report.errors.push(format!("remove {}: {e}", existing.name));
+                    }
                 }
             }
         }
@@ -2082,20 +6021,32 @@ async fn sync_overseerr_servers(
             if !synced_radarr_keys.contains(&key) {
                 let id = existing.id.unwrap_or(0.0) as i32;
                 info!(overseerr = %overseerr_name, server = %existing.name, "removing stale Radarr server from Overseerr");
-                if let Err(e) = overseerr_client.delete_radarr(id).await {
-                    warn!(server = %existing.name, error = %e, "failed to remove stale Radarr from Overseerr");
+                match overseerr_client.delete_radarr(id).await {
+                    Ok(_) => report.removed += 1,
+                    Err(e) => {
+                        warn!(server = %existing.name, error = %e, "failed to remove stale Radarr from Overseerr");
+                        report.errors.push(format!("remove {}: {e}", existing.name));
+                    }
                 }
             }
         }
     }
 
+    if overseerr.spec.app == AppType::Jellyseerr
+        && let Err(e) = sync_jellyseerr_jellyfin(client, &overseerr_client, target_ns).await
+    {
+        warn!(jellyseerr = %overseerr_name, error = %e, "failed to connect Jellyseerr to Jellyfin");
+        report.errors.push(format!("jellyfin: {e}"));
+    }
+
+    publish_deferred(&discovered, "Overseerr", recorder, obj_ref).await;
     let sonarr_count = discovered
         .iter()
-        .filter(|a| a.app_type == AppType::Sonarr)
+        .filter(|a| a.ready && a.app_type == AppType::Sonarr)
         .count();
     let radarr_count = discovered
         .iter()
-        .filter(|a| a.app_type == AppType::Radarr)
+        .filter(|a| a.ready && a.app_type == AppType::Radarr)
         .count();
     let _ = recorder
         .publish(
@@ -2112,18 +6063,94 @@ async fn sync_overseerr_servers(
         )
         .await;
 
+    report.synced = sonarr_count + radarr_count;
+    Ok(report)
+}
+
+/// Point Jellyseerr at the Jellyfin in `target_ns`, using an API key the
+/// operator creates in Jellyfin with the app's adminCredentials. Nothing
+/// is done without a Jellyfin that has adminCredentials.
+async fn sync_jellyseerr_jellyfin(
+    client: &Client,
+    jellyseerr: &servarr_api::OverseerrClient,
+    target_ns: &str,
+) -> Result<(), anyhow::Error> {
+    use kube::api::ListParams;
+
+    let api = Api::<ServarrApp>::namespaced(client.clone(), target_ns);
+    let apps = api.list(&ListParams::default()).await?;
+    let Some((jellyfin, creds)) = apps.iter().find_map(|a| {
+        let creds = a.spec.admin_credentials.as_ref()?;
+        (a.spec.app == AppType::Jellyfin).then_some((a, creds))
+    }) else {
+        return Ok(());
+    };
+
+    let app_name = servarr_resources::common::app_name(jellyfin);
+    let defaults = servarr_crds::AppDefaults::for_app(&AppType::Jellyfin);
+    let svc_spec = jellyfin.spec.service.as_ref().unwrap_or(&defaults.service);
+    let port = svc_spec.ports.first().map(|p| p.port).unwrap_or(8096);
+    let host = format!("{app_name}.{target_ns}.svc");
+
+    let username =
+        servarr_api::read_secret_key(client, target_ns, &creds.secret_name, "username").await?;
+    let password =
+        servarr_api::read_secret_key(client, target_ns, &creds.secret_name, "password").await?;
+    let jellyfin_client = servarr_api::JellyfinClient::new(&format!("http://{host}:{port}"))?;
+    let token = jellyfin_client.authenticate(&username, &password).await?;
+    let api_key = jellyfin_client.ensure_api_key(&token, "jellyseerr").await?;
+
+    let current = jellyseerr.jellyfin_settings().await?;
+    if let Some(updated) = jellyfin_connection(&current, &host, port, &api_key) {
+        info!(jellyfin = %jellyfin.name_any(), "connecting Jellyseerr to Jellyfin");
+        jellyseerr.update_jellyfin_settings(&updated).await?;
+    }
     Ok(())
 }
 
-/// Check if any Overseerr instance with overseerr_sync.enabled exists in the namespace.
+/// Jellyseerr's Jellyfin settings `current` with the connection fields set,
+/// or `None` when they already match. Other settings, such as the enabled
+/// libraries, are kept.
+fn jellyfin_connection(
+    current: &serde_json::Value,
+    host: &str,
+    port: i32,
+    api_key: &str,
+) -> Option<serde_json::Value> {
+    let wanted = serde_json::json!({
+        "ip": host,
+        "port": port,
+        "useSsl": false,
+        "urlBase": "",
+        "apiKey": api_key,
+    });
+    let mut updated = match current {
+        serde_json::Value::Object(_) => current.clone(),
+        _ => serde_json::json!({}),
+    };
+    let mut changed = false;
+    for (key, value) in wanted.as_object()? {
+        if updated.get(key) != Some(value) {
+            updated[key] = value.clone();
+            changed = true;
+        }
+    }
+    changed.then_some(updated)
+}
+
+/// Whether `app` is an Overseerr or Jellyseerr with overseerr_sync.enabled.
+pub(crate) fn runs_overseerr_sync(app: &ServarrApp) -> bool {
+    matches!(app.spec.app, AppType::Overseerr | AppType::Jellyseerr)
+        && app.spec.overseerr_sync.as_ref().is_some_and(|s| s.enabled)
+}
+
+/// Check if any Overseerr or Jellyseerr instance with overseerr_sync.enabled
+/// exists in the namespace.
 async fn overseerr_sync_exists(client: &Client, namespace: &str) -> bool {
     use kube::api::ListParams;
     let api = Api::<ServarrApp>::namespaced(client.clone(), namespace);
     match api.list(&ListParams::default()).await {
-        Ok(list) => list.iter().any(|a| {
-            a.spec.app == AppType::Overseerr
-                && a.spec.overseerr_sync.as_ref().is_some_and(|s| s.enabled)
-        }),
+        Ok(list) => list.iter().any(runs_overseerr_sync),
         Err(_) => false,
     }
 }
@@ -2133,7 +6160,7 @@ async fn cleanup_overseerr_registration(
     client: &Client,
     app: &ServarrApp,
     namespace: &str,
-    recorder: &Recorder,
+    recorder: &ThrottledRecorder<'_>,
     obj_ref: &k8s_openapi::api::core::v1::ObjectReference,
 ) -> Result<(), anyhow::Error> {
     use kube::api::ListParams;
@@ -2148,124 +6175,166 @@ async fn cleanup_overseerr_registration(
     let port = svc_spec.ports.first().map(|p| p.port).unwrap_or(80);
     let app_hostname = format!("{app_name_str}.{namespace}.svc");
 
-    // Find the Overseerr instance
+    // Find the Overseerr and Jellyseerr instances
     let sa_api = Api::<ServarrApp>::namespaced(client.clone(), namespace);
     let apps = sa_api.list(&ListParams::default()).await?;
-    let overseerr = apps.iter().find(|a| {
-        a.spec.app == AppType::Overseerr
-            && a.spec.overseerr_sync.as_ref().is_some_and(|s| s.enabled)
-    });
-
-    let overseerr = match overseerr {
-        Some(o) => o,
-        None => return Ok(()),
-    };
-
-    let secret_name = match overseerr.spec.api_key_secret.as_deref() {
-        Some(s) => s,
-        None => return Ok(()),
-    };
-
-    let overseerr_ns = overseerr.namespace().unwrap_or_else(|| namespace.into());
-    let overseerr_key =
-        servarr_api::read_secret_key(client, &overseerr_ns, secret_name, "api-key").await?;
-
-    let overseerr_app_name = servarr_resources::common::app_name(overseerr);
-    let overseerr_defaults = servarr_crds::AppDefaults::for_app(&overseerr.spec.app);
-    let overseerr_svc = overseerr
-        .spec
-        .service
-        .as_ref()
-        .unwrap_or(&overseerr_defaults.service);
-    let overseerr_port = overseerr_svc.ports.first().map(|p| p.port).unwrap_or(80);
-    let overseerr_url = format!("http://{overseerr_app_name}.{overseerr_ns}.svc:{overseerr_port}");
-
-    let overseerr_client = servarr_api::OverseerrClient::new(&overseerr_url, &overseerr_key);
+    for overseerr in apps.iter().filter(|a| runs_overseerr_sync(a)) {
+        let (secret_name, key) = match overseerr.spec.api_key_ref() {
+            Some(r) => r,
+            None => continue,
+        };
 
-    // Remove matching Sonarr or Radarr server by hostname + port
-    match app.spec.app {
-        AppType::Sonarr => {
-            let existing = overseerr_client.list_sonarr().await?;
-            if let Some(registered) = existing
-                .iter()
-                .find(|s| s.hostname == app_hostname && s.port == port as f64)
-            {
-                let id = registered.id.unwrap_or(0.0) as i32;
-                info!(
-                    app = %app.name_any(),
-                    overseerr_server_id = id,
-                    "removing Sonarr from Overseerr on deletion"
-                );
-                overseerr_client.delete_sonarr(id).await?;
+        let overseerr_ns = overseerr.namespace().unwrap_or_else(|| namespace.into());
+        let overseerr_key =
+            servarr_api::read_secret_key(client, &overseerr_ns, secret_name, key).await?;
 
-                let _ = recorder
-                    .publish(
-                        &Event {
-                            type_: EventType::Normal,
-                            reason: "OverseerrCleanup".into(),
-                            note: Some(format!("Removed {} from Overseerr", app.name_any())),
-                            action: "Finalize".into(),
-                            secondary: None,
-                        },
-                        obj_ref,
-                    )
-                    .await;
-            }
-        }
-        AppType::Radarr => {
-            let existing = overseerr_client.list_radarr().await?;
-            if let Some(registered) = existing
-                .iter()
-                .find(|s| s.hostname == app_hostname && s.port == port as f64)
-            {
-                let id = registered.id.unwrap_or(0.0) as i32;
-                info!(
-                    app = %app.name_any(),
-                    overseerr_server_id = id,
-                    "removing Radarr from Overseerr on deletion"
-                );
-                overseerr_client.delete_radarr(id).await?;
+        let overseerr_app_name = servarr_resources::common::app_name(overseerr);
+        let overseerr_defaults = servarr_crds::AppDefaults::for_app(&overseerr.spec.app);
+        let overseerr_svc = overseerr
+            .spec
+            .service
+            .as_ref()
+            .unwrap_or(&overseerr_defaults.service);
+        let overseerr_port = overseerr_svc.ports.first().map(|p| p.port).unwrap_or(80);
+        let overseerr_url =
+            format!("http://{overseerr_app_name}.{overseerr_ns}.svc:{overseerr_port}");
 
-                let _ = recorder
-                    .publish(
-                        &Event {
-                            type_: EventType::Normal,
-                            reason: "OverseerrCleanup".into(),
-                            note: Some(format!("Removed {} from Overseerr", app.name_any())),
-                            action: "Finalize".into(),
-                            secondary: None,
-                        },
-                        obj_ref,
-                    )
-                    .await;
+        let overseerr_client = servarr_api::OverseerrClient::new(&overseerr_url, &overseerr_key);
+
+        // Remove matching Sonarr or Radarr server by hostname + port
+        match app.spec.app {
+            AppType::Sonarr => {
+                let existing = overseerr_client.list_sonarr().await?;
+                if let Some(registered) = existing
+                    .iter()
+                    .find(|s| s.hostname == app_hostname && s.port == port as f64)
+                {
+                    let id = registered.id.unwrap_or(0.0) as i32;
+                    info!(
+                        app = %app.name_any(),
+                        overseerr_server_id = id,
+                        "removing Sonarr from Overseerr on deletion"
+                    );
+                    overseerr_client.delete_sonarr(id).await?;
+
+                    let _ = recorder
+                        .publish(
+                            &Event {
+                                type_: EventType::Normal,
+                                reason: "OverseerrCleanup".into(),
+                                note: Some(format!(
+                                    "Removed {} from {}",
+                                    app.name_any(),
+                                    overseerr.name_any()
+                                )),
+                                action: "Finalize".into(),
+                                secondary: None,
+                            },
+                            obj_ref,
+                        )
+                        .await;
+                }
+            }
+            AppType::Radarr => {
+                let existing = overseerr_client.list_radarr().await?;
+                if let Some(registered) = existing
+                    .iter()
+                    .find(|s| s.hostname == app_hostname && s.port == port as f64)
+                {
+                    let id = registered.id.unwrap_or(0.0) as i32;
+                    info!(
+                        app = %app.name_any(),
+                        overseerr_server_id = id,
+                        "removing Radarr from Overseerr on deletion"
+                    );
+                    overseerr_client.delete_radarr(id).await?;
+
+                    let _ = recorder
+                        .publish(
+                            &Event {
+                                type_: EventType::Normal,
+                                reason: "OverseerrCleanup".into(),
+                                note: Some(format!(
+                                    "Removed {} from {}",
+                                    app.name_any(),
+                                    overseerr.name_any()
+                                )),
+                                action: "Finalize".into(),
+                                secondary: None,
+                            },
+                            obj_ref,
+                        )
+                        .await;
+                }
             }
+            _ => {}
         }
-        _ => {}
     }
 
     Ok(())
 }
 
-fn chrono_now() -> String {
+pub(crate) fn chrono_now() -> String {
     // ISO 8601 timestamp with seconds precision
     use chrono::{SecondsFormat, Utc};
     Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
 }
 
 /// Return true if `v` is a Kubernetes zero/default value that the API server
-/// omits when serialising resources (false, 0, "", null).  A field absent from
-/// `actual` but present as a zero value in `desired` is not real drift.
-fn is_zero_value(v: &serde_json::Value) -> bool {
+/// omits when serialising resources (false, 0, "", null, and empty lists and
+/// maps).  A field absent from `actual` but present as a zero value in
+/// `desired` is not real drift.
+pub(crate) fn is_zero_value(v: &serde_json::Value) -> bool {
     match v {
         serde_json::Value::Bool(false) | serde_json::Value::Null => true,
         serde_json::Value::Number(n) => n.as_i64() == Some(0) || n.as_f64() == Some(0.0),
         serde_json::Value::String(s) => s.is_empty(),
+        serde_json::Value::Array(a) => a.is_empty(),
+        serde_json::Value::Object(o) => o.is_empty(),
         _ => false,
     }
 }
 
+/// Most drift paths listed in `status.lastDrift` and counted in
+/// `servarr_operator_drift_paths_total`.
+const MAX_DRIFT_PATHS: usize = 10;
+
+/// `status.lastDrift` for a `json_diff_paths` result: the paths only, as
+/// the values can be secrets.
+fn drift_report(kind: &str, diff: &[String], now: &str) -> DriftReport {
+    DriftReport {
+        kind: kind.to_string(),
+        paths: diff
+            .iter()
+            .take(MAX_DRIFT_PATHS)
+            .map(|d| {
+                d.split_once(": ")
+                    .map_or(d.as_str(), |(path, _)| path)
+                    .to_string()
+            })
+            .collect(),
+        total_paths: diff.len() as u32,
+        time: now.to_string(),
+    }
+}
+
+/// Definition files (`{name}.yml` keys) that were added, edited, or removed
+/// between the live Prowlarr definitions ConfigMap and `desired`.
+fn changed_definitions(live: &ConfigMap, desired: &ConfigMap) -> Vec<String> {
+    let empty = BTreeMap::new();
+    let live = live.data.as_ref().unwrap_or(&empty);
+    let desired = desired.data.as_ref().unwrap_or(&empty);
+    live.keys()
+        .chain(desired.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|k| live.get(*k) != desired.get(*k))
+        .cloned()
+        .collect()
+}
+
 /// Return paths where `desired` differs from `actual` for debugging drift.
-fn json_diff_paths(
+pub(crate) fn json_diff_paths(
     desired: &serde_json::Value,
     actual: &serde_json::Value,
     path: String,
@@ -2302,6 +6371,183 @@ fn json_diff_paths(
     }
 }
 
+/// One step of a `driftIgnoreFields` path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+    Wildcard,
+}
+
+/// Parse a drift-ignore path using a small JSONPath subset.
+///
+/// Supports an optional `$.` prefix, dotted keys, `[N]` indices, `[*]`
+/// wildcards, and quoted keys (`['a.b/c']` or `["a.b/c"]`) for annotation and
+/// label names that contain dots or slashes. Returns `None` on malformed input.
+fn parse_drift_path(path: &str) -> Option<Vec<PathSegment>> {
+    let path = path.trim();
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut segments = Vec::new();
+    let mut chars = path.chars().peekable();
+    let mut key = String::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                if !key.is_empty() {
+                    segments.push(PathSegment::Key(std::mem::take(&mut key)));
+                }
+            }
+            '[' => {
+                if !key.is_empty() {
+                    segments.push(PathSegment::Key(std::mem::take(&mut key)));
+                }
+                let segment = match chars.peek() {
+                    Some(&q) if q == '\'' || q == '"' => {
+                        chars.next();
+                        let quoted: String = chars.by_ref().take_while(|&ch| ch != q).collect();
+                        if chars.next()? != ']' {
+                            return None;
+                        }
+                        PathSegment::Key(quoted)
+                    }
+                    _ => {
+                        let inner: String = chars.by_ref().take_while(|&ch| ch != ']').collect();
+                        if inner == "*" {
+                            PathSegment::Wildcard
+                        } else {
+                            PathSegment::Index(inner.parse().ok()?)
+                        }
+                    }
+                };
+                segments.push(segment);
+            }
+            _ => key.push(c),
+        }
+    }
+    if !key.is_empty() {
+        segments.push(PathSegment::Key(key));
+    }
+
+    if segments.is_empty() {
+        None
+    } else {
+        Some(segments)
+    }
+}
+
+/// Remove every value addressed by `segments` from `value`.
+///
+/// A trailing index or wildcard removes array elements (all of them for a
+/// wildcard). Paths that don't exist are silently ignored.
+fn remove_json_path(value: &mut serde_json::Value, segments: &[PathSegment]) {
+    use serde_json::Value;
+    let Some((first, rest)) = segments.split_first() else {
+        return;
+    };
+    match (first, value) {
+        (PathSegment::Key(k), Value::Object(map)) => {
+            if rest.is_empty() {
+                map.remove(k);
+            } else if let Some(child) = map.get_mut(k) {
+                remove_json_path(child, rest);
+            }
+        }
+        (PathSegment::Index(i), Value::Array(items)) => {
+            if rest.is_empty() {
+                if *i < items.len() {
+                    items.remove(*i);
+                }
+            } else if let Some(child) = items.get_mut(*i) {
+                remove_json_path(child, rest);
+            }
+        }
+        (PathSegment::Wildcard, Value::Array(items)) => {
+            if rest.is_empty() {
+                items.clear();
+            } else {
+                for child in items.iter_mut() {
+                    remove_json_path(child, rest);
+                }
+            }
+        }
+        (PathSegment::Wildcard, Value::Object(map)) => {
+            if rest.is_empty() {
+                map.clear();
+            } else {
+                for child in map.values_mut() {
+                    remove_json_path(child, rest);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The value of a Kubernetes quantity such as `500m`, `1.5Gi`, or `1e3`.
+fn quantity_value(q: &str) -> Option<f64> {
+    let split = q
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '+' | '-')))
+        .unwrap_or(q.len());
+    let (number, suffix) = q.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier = match suffix {
+        "" => 1.0,
+        "n" => 1e-9,
+        "u" => 1e-6,
+        "m" => 1e-3,
+        "k" => 1e3,
+        "M" => 1e6,
+        "G" => 1e9,
+        "T" => 1e12,
+        "P" => 1e15,
+        "E" => 1e18,
+        "Ki" => 1024f64,
+        "Mi" => 1024f64.powi(2),
+        "Gi" => 1024f64.powi(3),
+        "Ti" => 1024f64.powi(4),
+        "Pi" => 1024f64.powi(5),
+        "Ei" => 1024f64.powi(6),
+        exp => 10f64.powi(exp.strip_prefix(['e', 'E'])?.parse().ok()?),
+    };
+    Some(number * multiplier)
+}
+
+/// Replace quantities under `limits` and `requests` in `desired` with their
+/// form in `actual` when the two are equal. The API server stores quantities
+/// in canonical form, so `1000m` is read back as `1` and `0.5Gi` as `512Mi`;
+/// user-written container specs would otherwise drift forever.
+fn align_quantities(desired: &mut serde_json::Value, actual: &serde_json::Value) {
+    use serde_json::Value;
+    match (desired, actual) {
+        (Value::Object(d), Value::Object(a)) => {
+            for (k, dv) in d.iter_mut() {
+                let Some(av) = a.get(k) else { continue };
+                if let ("limits" | "requests", Value::Object(dq), Value::Object(aq)) =
+                    (k.as_str(), &mut *dv, av)
+                {
+                    for (name, q) in dq.iter_mut() {
+                        if let (Value::String(dq), Some(Value::String(aq))) = (&*q, aq.get(name))
+                            && let (Some(dn), Some(an)) = (quantity_value(dq), quantity_value(aq))
+                            && (dn - an).abs() <= f64::EPSILON * dn.abs().max(an.abs())
+                        {
+                            *q = Value::String(aq.clone());
+                        }
+                    }
+                } else {
+                    align_quantities(dv, av);
+                }
+            }
+        }
+        (Value::Array(d), Value::Array(a)) => {
+            for (dv, av) in d.iter_mut().zip(a) {
+                align_quantities(dv, av);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Check that every field in `desired` exists with the same value in `actual`.
 /// Extra fields in `actual` (e.g. Kubernetes defaults) are ignored.
 /// Fields absent from `actual` but present as zero values in `desired` are
@@ -2329,6 +6575,168 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    // ---- error taxonomy ----
+
+    fn api_error(code: u16, message: &str) -> kube::Error {
+        kube::Error::Api(
+            kube::core::Status::failure(message, "")
+                .with_code(code)
+                .boxed(),
+        )
+    }
+
+    #[test]
+    fn kube_errors_are_classified_by_status_code() {
+        let conflict = Error::kube(api_error(409, "the object has been modified"));
+        assert_eq!(conflict.category(), ErrorCategory::Conflict);
+        let invalid = Error::kube(api_error(422, "spec.replicas: Invalid value: -1"));
+        assert_eq!(invalid.category(), ErrorCategory::InvalidSpec);
+        assert_eq!(
+            invalid.to_string(),
+            "Invalid spec: spec.replicas: Invalid value: -1"
+        );
+        assert_eq!(
+            Error::kube(api_error(500, "etcd timeout")).category(),
+            ErrorCategory::Kubernetes
+        );
+        let missing = Error::SecretMissing {
+            secret: "sonarr-api-key".into(),
+            key: Some("api-key".into()),
+        };
+        assert_eq!(missing.category(), ErrorCategory::SecretMissing);
+        assert_eq!(
+            missing.to_string(),
+            "Secret sonarr-api-key has no key api-key"
+        );
+    }
+
+    #[test]
+    fn configuration_complete_waits_for_ready_and_every_sync() {
+        let mut status = ServarrAppStatus::default();
+        let cond = configuration_condition(&status, "t");
+        assert_eq!(
+            (cond.status.as_str(), cond.reason.as_str()),
+            ("False", "AppNotReady")
+        );
+
+        status.ready = true;
+        status.set_condition(Condition::ok(
+            condition_types::DEPLOYMENT_READY,
+            "",
+            "",
+            "t",
+        ));
+        assert_eq!(configuration_condition(&status, "t").status, "True");
+
+        status.set_condition(Condition::ok(
+            condition_types::ROOT_FOLDERS_SYNCED,
+            "",
+            "",
+            "t",
+        ));
+        status.set_condition(Condition::fail(
+            condition_types::DOWNLOAD_CLIENTS_SYNCED,
+            "ApiUnavailable",
+            "",
+            "t",
+        ));
+        let cond = configuration_condition(&status, "t");
+        assert_eq!(cond.reason, "ConfigurationPending");
+        assert_eq!(cond.message, "Waiting for DownloadClientsSynced");
+    }
+
+    #[test]
+    fn required_secrets_skips_optional_references() {
+        let template: PodTemplateSpec = serde_json::from_value(json!({
+            "spec": {
+                "containers": [{
+                    "name": "app",
+                    "env": [
+                        {"name": "KEY", "valueFrom": {"secretKeyRef": {"name": "api", "key": "api-key"}}},
+                        {"name": "OPT", "valueFrom": {"secretKeyRef": {"name": "extra", "key": "x", "optional": true}}}
+                    ],
+                    "envFrom": [{"secretRef": {"name": "env"}}]
+                }],
+                "volumes": [
+                    {"name": "keys", "secret": {"secretName": "keys"}},
+                    {"name": "certs", "secret": {"secretName": "certs", "optional": true}}
+                ]
+            }
+        }))
+        .unwrap();
+        let required = required_secrets(&template);
+        assert_eq!(
+            required.keys().map(String::as_str).collect::<Vec<_>>(),
+            ["api", "env", "keys"]
+        );
+        assert!(required["api"].contains("api-key"));
+        assert!(required["keys"].is_empty());
+    }
+
+    // ---- drift ignore paths ----
+
+    #[test]
+    fn parse_drift_path_dotted_keys_and_indices() {
+        assert_eq!(
+            parse_drift_path("$.spec.containers[0].env").unwrap(),
+            vec![
+                PathSegment::Key("spec".into()),
+                PathSegment::Key("containers".into()),
+                PathSegment::Index(0),
+                PathSegment::Key("env".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_drift_path_quoted_key_and_wildcard() {
+        assert_eq!(
+            parse_drift_path("metadata.annotations['sidecar.istio.io/status']").unwrap(),
+            vec![
+                PathSegment::Key("metadata".into()),
+                PathSegment::Key("annotations".into()),
+                PathSegment::Key("sidecar.istio.io/status".into()),
+            ]
+        );
+        assert_eq!(
+            parse_drift_path("spec.initContainers[*]").unwrap()[2],
+            PathSegment::Wildcard
+        );
+    }
+
+    #[test]
+    fn parse_drift_path_rejects_malformed() {
+        assert!(parse_drift_path("").is_none());
+        assert!(parse_drift_path("spec.containers[x]").is_none());
+        assert!(parse_drift_path("metadata.annotations['open").is_none());
+    }
+
+    #[test]
+    fn remove_json_path_makes_mutated_template_a_subset() {
+        let desired = json!({
+            "metadata": {"annotations": {"a": "1"}},
+            "spec": {"containers": [{"name": "app", "env": [{"name": "TZ"}]}]}
+        });
+        let mut actual = json!({
+            "metadata": {"annotations": {"a": "1", "vault.hashicorp.com/status": "injected"}},
+            "spec": {"containers": [{"name": "app", "env": [{"name": "TZ"}, {"name": "VAULT"}]}]}
+        });
+        let mut desired_stripped = desired.clone();
+        assert!(!json_is_subset(&desired, &actual));
+
+        let segments = parse_drift_path("spec.containers[*].env").unwrap();
+        remove_json_path(&mut desired_stripped, &segments);
+        remove_json_path(&mut actual, &segments);
+        assert!(json_is_subset(&desired_stripped, &actual));
+    }
+
+    #[test]
+    fn remove_json_path_missing_path_is_noop() {
+        let mut v = json!({"spec": {"containers": []}});
+        remove_json_path(&mut v, &parse_drift_path("spec.volumes[0].name").unwrap());
+        assert_eq!(v, json!({"spec": {"containers": []}}));
+    }
+
     // ---- json_is_subset ----
 
     #[test]
@@ -2436,6 +6844,99 @@ mod tests {
 
     // ---- json_diff_paths ----
 
+    #[test]
+    fn drift_report_lists_paths_without_values() {
+        let desired = json!({
+            "spec": {"containers": [{"env": [{"name": "TOKEN", "value": "s3cret"}], "image": "a"}]}
+        });
+        let actual = json!({
+            "spec": {"containers": [{"env": [{"name": "TOKEN", "value": "other"}], "image": "b"}]}
+        });
+        let diff = json_diff_paths(&desired, &actual, String::new());
+        let report = drift_report("Deployment", &diff, "2026-01-01T00:00:00Z");
+        assert_eq!(
+            report.paths,
+            [
+                "spec.containers[0].env[0].value",
+                "spec.containers[0].image"
+            ]
+        );
+        assert_eq!(report.total_paths, 2);
+        assert!(!serde_json::to_string(&report).unwrap().contains("s3cret"));
+
+        let many: Vec<String> = (0..12).map(|i| format!("p{i}: 1 vs 2")).collect();
+        let report = drift_report("StatefulSet", &many, "2026-01-01T00:00:00Z");
+        assert_eq!(report.paths.len(), MAX_DRIFT_PATHS);
+        assert_eq!(report.total_paths, 12);
+    }
+
+    #[test]
+    fn changed_definitions_lists_edited_added_and_removed_files() {
+        let cm = |entries: &[(&str, &str)]| ConfigMap {
+            data: Some(
+                entries
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        let live = cm(&[("a.yml", "id: a"), ("b.yml", "id: b"), ("c.yml", "id: c")]);
+        let desired = cm(&[("a.yml", "id: a"), ("b.yml", "id: b2"), ("d.yml", "id: d")]);
+        assert_eq!(
+            changed_definitions(&live, &desired),
+            vec!["b.yml", "c.yml", "d.yml"]
+        );
+        assert!(changed_definitions(&live, &live).is_empty());
+    }
+
+    #[test]
+    fn json_is_subset_missing_empty_collections_not_drift() {
+        assert!(json_is_subset(
+            &json!({"args": [], "labels": {}}),
+            &json!({})
+        ));
+    }
+
+    // ---- align_quantities ----
+
+    #[test]
+    fn quantity_value_suffixes() {
+        assert_eq!(quantity_value("500m"), Some(0.5));
+        assert_eq!(quantity_value("1.5Gi"), Some(1.5 * 1024f64.powi(3)));
+        assert_eq!(quantity_value("2k"), Some(2000.0));
+        assert_eq!(quantity_value("1e3"), Some(1000.0));
+        assert_eq!(quantity_value("1Zi"), None);
+    }
+
+    #[test]
+    fn align_quantities_accepts_canonical_forms() {
+        let actual = json!({"containers": [{
+            "name": "vector",
+            "resources": {
+                "limits": {"cpu": "1", "memory": "512Mi"},
+                "requests": {"cpu": "100m"}
+            }
+        }]});
+        let mut desired = json!({"containers": [{
+            "name": "vector",
+            "resources": {
+                "limits": {"cpu": "1000m", "memory": "0.5Gi"},
+                "requests": {"cpu": "200m"}
+            }
+        }]});
+        align_quantities(&mut desired, &actual);
+        assert_eq!(
+            desired["containers"][0]["resources"]["limits"],
+            actual["containers"][0]["resources"]["limits"]
+        );
+        // A real change is still drift.
+        assert_eq!(
+            json_diff_paths(&desired, &actual, String::new()),
+            vec!["containers[0].resources.requests.cpu: \"200m\" vs \"100m\""]
+        );
+    }
+
     #[test]
     fn json_diff_paths_both_empty_objects() {
         let result = json_diff_paths(&json!({}), &json!({}), String::new());
@@ -2521,6 +7022,18 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn app_type_to_kind_readarr_and_whisparr() {
+        assert!(matches!(
+            app_type_to_kind(&AppType::Readarr),
+            AppKind::Readarr
+        ));
+        assert!(matches!(
+            app_type_to_kind(&AppType::Whisparr),
+            AppKind::Whisparr
+        ));
+    }
+
     #[test]
     fn app_type_to_kind_prowlarr() {
         assert!(matches!(
@@ -2529,26 +7042,189 @@ mod tests {
         ));
     }
 
-    #[test]
-    #[should_panic(expected = "AppKind not supported")]
-    fn app_type_to_kind_unsupported_panics() {
-        app_type_to_kind(&AppType::Sabnzbd);
+    #[test]
+    #[should_panic(expected = "AppKind not supported")]
+    fn app_type_to_kind_unsupported_panics() {
+        app_type_to_kind(&AppType::Sabnzbd);
+    }
+
+    // ---- chrono_now ----
+
+    #[test]
+    fn chrono_now_returns_valid_iso8601() {
+        let now = chrono_now();
+        assert!(now.contains('T'), "should contain T separator: {now}");
+        assert!(now.ends_with('Z'), "should end with Z: {now}");
+    }
+
+    // ---- backup_due ----
+
+    #[test]
+    fn backup_due_on_schedule_and_before_maintenance() {
+        let at = |t: &str| t.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+        let spec = servarr_crds::BackupSpec {
+            enabled: true,
+            schedule: "0 3 * * *".into(),
+            ..Default::default()
+        };
+        let now = at("2025-06-02T04:00:00Z");
+        assert_eq!(backup_due(&spec, None, None, now), Ok((true, false)));
+        let last = Some(at("2025-06-02T03:00:05Z"));
+        assert_eq!(backup_due(&spec, last, None, now), Ok((false, false)));
+        let stale = Some(at("2025-06-01T03:00:05Z"));
+        assert_eq!(backup_due(&spec, stale, None, now), Ok((true, false)));
+        let cutoff = Some(at("2025-06-02T03:30:00Z"));
+        assert_eq!(backup_due(&spec, last, cutoff, now), Ok((true, true)));
+
+        let unscheduled = servarr_crds::BackupSpec {
+            schedule: String::new(),
+            ..spec.clone()
+        };
+        assert_eq!(
+            backup_due(&unscheduled, None, None, now),
+            Ok((false, false))
+        );
+        let invalid = servarr_crds::BackupSpec {
+            schedule: "nope".into(),
+            ..spec
+        };
+        assert!(backup_due(&invalid, None, None, now).is_err());
+    }
+
+    // ---- print_crd ----
+
+    #[test]
+    fn print_crd_returns_ok() {
+        assert!(print_crd().is_ok());
+    }
+
+    // ---- ensure_api_key_secret ----
+
+    fn api_key_app() -> ServarrApp {
+        let mut app = ServarrApp::new(
+            "sonarr",
+            servarr_crds::ServarrAppSpec {
+                app: AppType::Sonarr,
+                api_key_secret: Some("sonarr-api-key".into()),
+                ..Default::default()
+            },
+        );
+        app.metadata.namespace = Some("test".into());
+        app.metadata.uid = Some("sonarr-uid".into());
+        app
+    }
+
+    fn status_body(code: u16, reason: &str) -> serde_json::Value {
+        json!({
+            "kind": "Status",
+            "apiVersion": "v1",
+            "metadata": {},
+            "status": "Failure",
+            "message": format!("secrets \"sonarr-api-key\" {reason}"),
+            "reason": reason,
+            "code": code
+        })
+    }
+
+    fn api_key_secret_body() -> serde_json::Value {
+        json!({
+            "apiVersion": "v1",
+            "kind": "Secret",
+            "metadata": { "name": "sonarr-api-key", "namespace": "test" },
+            "data": { "api-key": "a2V5" }
+        })
+    }
+
+    const SECRET_PATH: &str = "/api/v1/namespaces/test/secrets/sonarr-api-key";
+
+    #[tokio::test]
+    async fn ensure_api_key_secret_tolerates_a_concurrent_create() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let client = build_mock_client(&mock_server.uri()).await;
+
+        // Missing on the first read, there once the other reconcile won.
+        Mock::given(method("GET"))
+            .and(path(SECRET_PATH))
+            .respond_with(ResponseTemplate::new(404).set_body_json(status_body(404, "NotFound")))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(SECRET_PATH))
+            .respond_with(ResponseTemplate::new(200).set_body_json(api_key_secret_body()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/namespaces/test/secrets"))
+            .respond_with(
+                ResponseTemplate::new(409).set_body_json(status_body(409, "AlreadyExists")),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        ensure_api_key_secret(&client, &api_key_app(), "test")
+            .await
+            .expect("losing the create race is not an error");
     }
 
-    // ---- chrono_now ----
+    #[tokio::test]
+    async fn ensure_api_key_secret_fails_when_the_winner_is_gone() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
 
-    #[test]
-    fn chrono_now_returns_valid_iso8601() {
-        let now = chrono_now();
-        assert!(now.contains('T'), "should contain T separator: {now}");
-        assert!(now.ends_with('Z'), "should end with Z: {now}");
+        let mock_server = MockServer::start().await;
+        let client = build_mock_client(&mock_server.uri()).await;
+
+        Mock::given(method("GET"))
+            .and(path(SECRET_PATH))
+            .respond_with(ResponseTemplate::new(404).set_body_json(status_body(404, "NotFound")))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/namespaces/test/secrets"))
+            .respond_with(
+                ResponseTemplate::new(409).set_body_json(status_body(409, "AlreadyExists")),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let err = ensure_api_key_secret(&client, &api_key_app(), "test")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Kube(_)), "{err:?}");
     }
 
-    // ---- print_crd ----
+    #[tokio::test]
+    async fn ensure_api_key_secret_creates_a_missing_secret_once() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
 
-    #[test]
-    fn print_crd_returns_ok() {
-        assert!(print_crd().is_ok());
+        let mock_server = MockServer::start().await;
+        let client = build_mock_client(&mock_server.uri()).await;
+
+        Mock::given(method("GET"))
+            .and(path(SECRET_PATH))
+            .respond_with(ResponseTemplate::new(404).set_body_json(status_body(404, "NotFound")))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/namespaces/test/secrets"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(api_key_secret_body()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        ensure_api_key_secret(&client, &api_key_app(), "test")
+            .await
+            .unwrap();
     }
 
     // ---- prowlarr_sync_exists ----
@@ -2801,11 +7477,433 @@ mod tests {
         app
     }
 
+    // ---- reconcile-now trigger and spec hash ----
+
+    #[test]
+    fn spec_hash_tracks_spec_changes() {
+        let app = make_test_app("my-sonarr", "test", AppType::Sonarr);
+        let mut changed = app.spec.clone();
+        changed.uid = Some(1000);
+
+        assert_eq!(spec_hash(&app.spec), spec_hash(&app.spec.clone()));
+        assert_ne!(spec_hash(&app.spec), spec_hash(&changed));
+        assert_eq!(spec_hash(&app.spec).len(), 64);
+    }
+
+    #[test]
+    fn registration_name_includes_instance_once() {
+        let discovered = |name: &str, instance: Option<&str>| DiscoveredApp {
+            name: name.into(),
+            namespace: "media".into(),
+            app_type: AppType::Sonarr,
+            base_url: String::new(),
+            api_key: String::new(),
+            instance: instance.map(String::from),
+            ready: true,
+        };
+        assert_eq!(discovered("sonarr", None).registration_name(), "sonarr");
+        assert_eq!(
+            discovered("media-sonarr-anime", Some("anime")).registration_name(),
+            "media-sonarr-anime"
+        );
+        assert_eq!(
+            discovered("sonarr", Some("anime")).registration_name(),
+            "sonarr-anime"
+        );
+        assert!(discovered("sonarr", None).overseerr_default());
+        assert!(discovered("sonarr-4k", Some("4k")).overseerr_default());
+        assert!(!discovered("sonarr", Some("anime")).overseerr_default());
+    }
+
+    #[test]
+    fn unsupported_overrides_condition_reflects_flag() {
+        let mut app = make_test_app("my-sonarr", "test", AppType::Sonarr);
+        let cond = unsupported_overrides_condition(&app, true, "t");
+        assert_eq!(cond.status, "False");
+        assert_eq!(cond.reason, "NotSet");
+
+        app.spec.unsupported_overrides = Some(servarr_crds::UnsupportedOverrides {
+            pod_spec: Some(serde_json::json!({"hostIPC": true})),
+        });
+        let cond = unsupported_overrides_condition(&app, true, "t");
+        assert_eq!(cond.status, "True");
+        assert_eq!(cond.reason, "Applied");
+        assert!(cond.message.contains("spec['hostIPC']"));
+
+        let cond = unsupported_overrides_condition(&app, false, "t");
+        assert_eq!(cond.reason, "Disabled");
+    }
+
+    #[test]
+    fn image_architecture_condition_reports_refusal() {
+        let cond = image_architecture_condition(None, "t");
+        assert_eq!(cond.status, "True");
+
+        let err = servarr_crds::AppDefaults::image_for_arch(&AppType::Plex, "arm").unwrap_err();
+        let cond = image_architecture_condition(Some(&err), "t");
+        assert_eq!(cond.status, "False");
+        assert_eq!(cond.reason, "UnsupportedArchitecture");
+        assert!(cond.message.contains("arm"));
+    }
+
+    #[test]
+    fn autoscaling_condition_reflects_hpa_status() {
+        use k8s_openapi::api::autoscaling::v2::{
+            HorizontalPodAutoscalerCondition, HorizontalPodAutoscalerStatus,
+        };
+        let hpa_condition =
+            |type_: &str, status: &str, reason: &str| HorizontalPodAutoscalerCondition {
+                type_: type_.into(),
+                status: status.into(),
+                reason: Some(reason.into()),
+                message: Some(format!("{type_} is {status}")),
+                ..Default::default()
+            };
+        let mut hpa = HorizontalPodAutoscaler::default();
+        let cond = autoscaling_condition(&hpa, "t");
+        assert_eq!(cond.status, "False");
+        assert_eq!(cond.reason, "Pending");
+
+        hpa.status = Some(HorizontalPodAutoscalerStatus {
+            current_replicas: Some(2),
+            desired_replicas: 3,
+            conditions: Some(vec![
+                hpa_condition("AbleToScale", "True", "ReadyForNewScale"),
+                hpa_condition("ScalingActive", "True", "ValidMetricFound"),
+            ]),
+            ..Default::default()
+        });
+        let cond = autoscaling_condition(&hpa, "t");
+        assert_eq!(cond.status, "True");
+        assert_eq!(cond.message, "2 current replica(s), 3 desired");
+
+        hpa.status.as_mut().unwrap().conditions = Some(vec![
+            hpa_condition("AbleToScale", "True", "ReadyForNewScale"),
+            hpa_condition("ScalingActive", "False", "FailedGetResourceMetric"),
+        ]);
+        let cond = autoscaling_condition(&hpa, "t");
+        assert_eq!(cond.status, "False");
+        assert_eq!(cond.reason, "FailedGetResourceMetric");
+        assert_eq!(cond.message, "ScalingActive is False");
+    }
+
+    #[test]
+    fn pending_reconcile_trigger_only_for_unhandled_values() {
+        let mut app = make_test_app("my-sonarr", "test", AppType::Sonarr);
+        assert_eq!(pending_reconcile_trigger(&app), None);
+
+        app.metadata.annotations = Some(
+            [(RECONCILE_NOW_ANNOTATION.to_string(), "t1".to_string())]
+                .into_iter()
+                .collect(),
+        );
+        assert_eq!(pending_reconcile_trigger(&app).as_deref(), Some("t1"));
+
+        app.status = Some(ServarrAppStatus {
+            reconcile_trigger: Some("t1".into()),
+            ..Default::default()
+        });
+        assert_eq!(pending_reconcile_trigger(&app), None);
+        assert_eq!(reconcile_trigger(&app).as_deref(), Some("t1"));
+    }
+
+    #[test]
+    fn sync_requested_only_on_apps_that_sync() {
+        let annotated = |app_type, value: &str| {
+            let mut app = make_test_app("app", "test", app_type);
+            app.metadata.annotations = Some(
+                [(SYNC_NOW_ANNOTATION.to_string(), value.to_string())]
+                    .into_iter()
+                    .collect(),
+            );
+            app
+        };
+        let mut prowlarr = annotated(AppType::Prowlarr, "true");
+        assert!(!sync_requested(&prowlarr));
+        prowlarr.spec.prowlarr_sync = Some(servarr_crds::ProwlarrSyncSpec {
+            enabled: true,
+            ..Default::default()
+        });
+        assert!(sync_requested(&prowlarr));
+
+        let mut overseerr = annotated(AppType::Overseerr, "yes");
+        overseerr.spec.overseerr_sync = Some(servarr_crds::OverseerrSyncSpec {
+            enabled: true,
+            ..Default::default()
+        });
+        assert!(!sync_requested(&overseerr));
+        assert!(!sync_requested(&annotated(AppType::Sonarr, "true")));
+    }
+
+    #[test]
+    fn jellyfin_connection_updates_only_changed_fields() {
+        let current = json!({
+            "ip": "",
+            "port": 8096,
+            "useSsl": false,
+            "libraries": [{"id": "1", "enabled": true}],
+        });
+        let updated = jellyfin_connection(&current, "jellyfin.media.svc", 8096, "k3y").unwrap();
+        assert_eq!(updated["ip"], "jellyfin.media.svc");
+        assert_eq!(updated["apiKey"], "k3y");
+        assert_eq!(updated["urlBase"], "");
+        assert_eq!(updated["libraries"], current["libraries"]);
+
+        assert!(jellyfin_connection(&updated, "jellyfin.media.svc", 8096, "k3y").is_none());
+        assert!(jellyfin_connection(&updated, "jellyfin.media.svc", 8920, "k3y").is_some());
+    }
+
+    // ---- rollout_state ----
+
+    fn deployment_with_status(generation: i64, status: serde_json::Value) -> Deployment {
+        serde_json::from_value(json!({
+            "metadata": { "name": "my-sonarr", "generation": generation },
+            "spec": {
+                "replicas": 1,
+                "selector": { "matchLabels": { "app": "my-sonarr" } },
+                "template": { "spec": { "containers": [] } }
+            },
+            "status": status
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn rollout_state_complete_when_all_replicas_updated_and_available() {
+        let deploy = deployment_with_status(
+            2,
+            json!({
+                "observedGeneration": 2,
+                "replicas": 1,
+                "updatedReplicas": 1,
+                "availableReplicas": 1,
+                "readyReplicas": 1
+            }),
+        );
+        assert_eq!(rollout_state(&deploy), RolloutState::Complete);
+    }
+
+    #[test]
+    fn rollout_state_in_progress_until_generation_observed() {
+        let deploy = deployment_with_status(
+            3,
+            json!({
+                "observedGeneration": 2,
+                "replicas": 1,
+                "updatedReplicas": 1,
+                "availableReplicas": 1
+            }),
+        );
+        assert!(matches!(
+            rollout_state(&deploy),
+            RolloutState::InProgress(_)
+        ));
+    }
+
+    #[test]
+    fn rollout_state_in_progress_while_old_pod_terminates() {
+        let deploy = deployment_with_status(
+            2,
+            json!({
+                "observedGeneration": 2,
+                "replicas": 2,
+                "updatedReplicas": 1,
+                "availableReplicas": 1
+            }),
+        );
+        assert_eq!(
+            rollout_state(&deploy),
+            RolloutState::InProgress("1 old replica(s) pending termination".into())
+        );
+    }
+
+    #[test]
+    fn rollout_state_in_progress_while_new_pod_unavailable() {
+        let deploy = deployment_with_status(
+            2,
+            json!({
+                "observedGeneration": 2,
+                "replicas": 1,
+                "updatedReplicas": 1,
+                "availableReplicas": 0
+            }),
+        );
+        assert_eq!(
+            rollout_state(&deploy),
+            RolloutState::InProgress("0 of 1 updated replica(s) available".into())
+        );
+    }
+
+    #[test]
+    fn rollout_state_stalled_on_progress_deadline() {
+        let deploy = deployment_with_status(
+            2,
+            json!({
+                "observedGeneration": 2,
+                "replicas": 1,
+                "updatedReplicas": 1,
+                "availableReplicas": 0,
+                "conditions": [{
+                    "type": "Progressing",
+                    "status": "False",
+                    "reason": "ProgressDeadlineExceeded",
+                    "message": "ReplicaSet \"my-sonarr-abc\" has timed out progressing."
+                }]
+            }),
+        );
+        assert!(matches!(rollout_state(&deploy), RolloutState::Stalled(_)));
+    }
+
+    // ---- requeue interval ----
+
+    #[test]
+    fn requeue_interval_short_until_settled() {
+        let now = "2026-01-01T00:00:00Z";
+        let mut status = ServarrAppStatus {
+            ready: true,
+            ..Default::default()
+        };
+        status.set_condition(Condition::fail(
+            condition_types::PROGRESSING,
+            "RolloutComplete",
+            "",
+            now,
+        ));
+        status.set_condition(Condition::ok(
+            condition_types::APP_HEALTHY,
+            "Healthy",
+            "",
+            now,
+        ));
+        assert_eq!(requeue_interval(None, &status, false).as_secs(), 300);
+        assert_eq!(requeue_interval(None, &status, true).as_secs(), 20);
+
+        let mut rolling = status.clone();
+        rolling.set_condition(Condition::ok(
+            condition_types::PROGRESSING,
+            "RolloutInProgress",
+            "",
+            now,
+        ));
+        assert_eq!(requeue_interval(None, &rolling, false).as_secs(), 20);
+
+        let mut unhealthy = status.clone();
+        unhealthy.set_condition(Condition::fail(
+            condition_types::APP_HEALTHY,
+            "HealthCheckFailed",
+            "",
+            now,
+        ));
+        assert_eq!(requeue_interval(None, &unhealthy, false).as_secs(), 20);
+
+        let not_ready = ServarrAppStatus::default();
+        assert_eq!(requeue_interval(None, &not_ready, false).as_secs(), 20);
+
+        let mut suspended = not_ready.clone();
+        suspended.set_condition(Condition::ok(
+            condition_types::SUSPENDED,
+            "SuspendedBySpec",
+            "",
+            now,
+        ));
+        assert_eq!(requeue_interval(None, &suspended, false).as_secs(), 300);
+    }
+
+    #[test]
+    fn suspension_from_spec_annotation_or_window() {
+        let mut app = ServarrApp::new("sonarr", servarr_crds::ServarrAppSpec::default());
+        let none = maintenance::MaintenancePlan::default();
+        assert!(suspension(&app, &none, "t").is_none());
+
+        app.metadata.annotations = Some([(SUSPEND_ANNOTATION.into(), "true".into())].into());
+        let cond = suspension(&app, &none, "t").unwrap();
+        assert_eq!(cond.status, "True");
+        assert_eq!(cond.reason, "SuspendedByAnnotation");
+
+        app.spec.suspend = true;
+        assert_eq!(
+            suspension(&app, &none, "t").unwrap().reason,
+            "SuspendedBySpec"
+        );
+
+        app.spec.suspend = false;
+        app.metadata.annotations = Some([(SUSPEND_ANNOTATION.into(), "false".into())].into());
+        let window = maintenance::MaintenancePlan {
+            active: Some(ActiveWindow {
+                name: "upgrade".into(),
+                mode: MaintenanceMode::Suspend,
+                end: chrono::Utc::now(),
+            }),
+            ..Default::default()
+        };
+        let cond = suspension(&app, &window, "t").unwrap();
+        assert_eq!(cond.reason, "MaintenanceWindow");
+        assert_eq!(cond.message, "Suspended by MaintenanceWindow upgrade");
+    }
+
+    #[test]
+    fn requeue_interval_honours_spec_bounds() {
+        let status = ServarrAppStatus {
+            ready: true,
+            ..Default::default()
+        };
+        let spec = RequeueSpec {
+            unsettled_seconds: 45,
+            settled_seconds: 900,
+        };
+        assert_eq!(requeue_interval(Some(&spec), &status, false).as_secs(), 900);
+        assert_eq!(requeue_interval(Some(&spec), &status, true).as_secs(), 45);
+
+        // The short interval never exceeds the long one, and both are floored.
+        let inverted = RequeueSpec {
+            unsettled_seconds: 600,
+            settled_seconds: 1,
+        };
+        assert_eq!(
+            requeue_interval(Some(&inverted), &status, true).as_secs(),
+            u64::from(MIN_REQUEUE_SECONDS)
+        );
+    }
+
+    // ---- sync status ----
+
+    #[test]
+    fn sync_status_records_pass_and_keeps_counts_on_failure() {
+        let report = SyncReport {
+            synced: 3,
+            removed: 1,
+            errors: vec!["add lidarr: connection refused".into()],
+        };
+        let partial = sync_status(None, &Ok(report), "t1");
+        assert_eq!(partial.last_sync_time.as_deref(), Some("t1"));
+        assert_eq!((partial.apps_synced, partial.apps_removed), (3, 1));
+        assert_eq!(
+            partial.last_error.as_deref(),
+            Some("add lidarr: connection refused")
+        );
+
+        let clean = sync_status(Some(&partial), &Ok(SyncReport::default()), "t2");
+        assert_eq!(clean.last_error, None);
+        assert_eq!(clean.apps_synced, 0);
+
+        let failed = sync_status(
+            Some(&partial),
+            &Err(anyhow::anyhow!("Prowlarr sync requires api_key_secret")),
+            "t3",
+        );
+        assert_eq!(failed.last_sync_time.as_deref(), Some("t1"));
+        assert_eq!((failed.apps_synced, failed.apps_removed), (3, 1));
+        assert_eq!(
+            failed.last_error.as_deref(),
+            Some("Prowlarr sync requires api_key_secret")
+        );
+    }
+
     // ---- update_status tests ----
 
     #[tokio::test]
     async fn update_status_ready_deployment() {
-        use wiremock::matchers::{method, path};
+        use wiremock::matchers::{body_partial_json, method, path};
         use wiremock::{Mock, MockServer, ResponseTemplate};
 
         let mock_server = MockServer::start().await;
@@ -2835,6 +7933,7 @@ mod tests {
                 "status": {
                     "readyReplicas": 1,
                     "replicas": 1,
+                    "updatedReplicas": 1,
                     "availableReplicas": 1
                 }
             })))
@@ -2846,6 +7945,12 @@ mod tests {
             .and(path(
                 "/apis/servarr.dev/v1alpha1/namespaces/test/servarrapps/my-sonarr/status",
             ))
+            .and(body_partial_json(json!({
+                "status": {
+                    "appliedSpecHash": spec_hash(&app.spec),
+                    "generatedResources": { "deployment": "my-sonarr", "service": "my-sonarr" }
+                }
+            })))
             .respond_with(ResponseTemplate::new(200).set_body_json(json!({
                 "apiVersion": "servarr.dev/v1alpha1",
                 "kind": "ServarrApp",
@@ -2870,8 +7975,33 @@ mod tests {
                 health: None,
                 update: None,
                 admin_creds: None,
+                auth: None,
+                ui_settings: None,
+                custom_formats: None,
+                quality_profiles: None,
+                root_folders: None,
+                media_management: None,
+                monitoring: None,
+                tags: None,
+                indexers: None,
+                users: None,
+                integrity: None,
+                download_clients: None,
+                maintenance: None,
+                suspended: None,
+                unsupported_overrides: None,
+                image_architecture: None,
+                network_policy_enforced: None,
+                gpu: None,
+                autoscaling: None,
+                storage_migration: Default::default(),
             },
-            None,
+            TaskStatus::default(),
+            Some(GeneratedResources {
+                deployment: Some("my-sonarr".into()),
+                service: Some("my-sonarr".into()),
+                ..Default::default()
+            }),
         )
         .await;
         assert!(
@@ -2947,7 +8077,28 @@ mod tests {
                 health: None,
                 update: None,
                 admin_creds: None,
+                auth: None,
+                ui_settings: None,
+                custom_formats: None,
+                quality_profiles: None,
+                root_folders: None,
+                media_management: None,
+                monitoring: None,
+                tags: None,
+                indexers: None,
+                users: None,
+                integrity: None,
+                download_clients: None,
+                maintenance: None,
+                suspended: None,
+                unsupported_overrides: None,
+                image_architecture: None,
+                network_policy_enforced: None,
+                gpu: None,
+                autoscaling: None,
+                storage_migration: Default::default(),
             },
+            TaskStatus::default(),
             None,
         )
         .await;
@@ -3134,4 +8285,127 @@ mod tests {
             "Transmission should not be in discovered results"
         );
     }
+
+    #[test]
+    fn endpoints_ready_needs_one_ready_endpoint() {
+        use k8s_openapi::api::discovery::v1::{Endpoint, EndpointConditions, EndpointSlice};
+
+        let slice = |ready: Option<bool>| EndpointSlice {
+            endpoints: vec![Endpoint {
+                addresses: vec!["10.0.0.1".into()],
+                conditions: Some(EndpointConditions {
+                    ready,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert!(!endpoints_ready(&[]));
+        assert!(!endpoints_ready(&[EndpointSlice::default()]));
+        assert!(!endpoints_ready(&[slice(Some(false))]));
+        assert!(endpoints_ready(&[slice(Some(false)), slice(Some(true))]));
+        assert!(endpoints_ready(&[slice(None)]));
+    }
+
+    #[tokio::test]
+    async fn discover_apps_marks_apps_without_ready_endpoints() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let client = build_mock_client(&mock_server.uri()).await;
+
+        Mock::given(method("GET"))
+            .and(path(
+                "/apis/servarr.dev/v1alpha1/namespaces/test/servarrapps",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "apiVersion": "servarr.dev/v1alpha1",
+                "kind": "ServarrAppList",
+                "metadata": {},
+                "items": [
+                    {
+                        "apiVersion": "servarr.dev/v1alpha1",
+                        "kind": "ServarrApp",
+                        "metadata": {
+                            "name": "my-sonarr",
+                            "namespace": "test",
+                            "uid": "sonarr-uid",
+                            "resourceVersion": "1"
+                        },
+                        "spec": {
+                            "app": "Sonarr",
+                            "apiKeySecret": "sonarr-secret"
+                        }
+                    },
+                    {
+                        "apiVersion": "servarr.dev/v1alpha1",
+                        "kind": "ServarrApp",
+                        "metadata": {
+                            "name": "my-radarr",
+                            "namespace": "test",
+                            "uid": "radarr-uid",
+                            "resourceVersion": "1"
+                        },
+                        "spec": {
+                            "app": "Radarr",
+                            "apiKeySecret": "radarr-secret"
+                        }
+                    }
+                ]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        for (secret, key) in [
+            ("sonarr-secret", "c29uYXJyLWtleQ=="),
+            ("radarr-secret", "cmFkYXJyLWtleQ=="),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(format!("/api/v1/namespaces/test/secrets/{secret}")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "apiVersion": "v1",
+                    "kind": "Secret",
+                    "metadata": { "name": secret, "namespace": "test" },
+                    "data": { "api-key": key }
+                })))
+                .mount(&mock_server)
+                .await;
+        }
+
+        // Sonarr's pod is still starting; Radarr's is serving
+        for (service, ready) in [("my-sonarr", false), ("my-radarr", true)] {
+            Mock::given(method("GET"))
+                .and(path(
+                    "/apis/discovery.k8s.io/v1/namespaces/test/endpointslices",
+                ))
+                .and(query_param(
+                    "labelSelector",
+                    format!("kubernetes.io/service-name={service}"),
+                ))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "apiVersion": "discovery.k8s.io/v1",
+                    "kind": "EndpointSliceList",
+                    "metadata": {},
+                    "items": [{
+                        "apiVersion": "discovery.k8s.io/v1",
+                        "kind": "EndpointSlice",
+                        "metadata": { "name": format!("{service}-abcde"), "namespace": "test" },
+                        "addressType": "IPv4",
+                        "endpoints": [{
+                            "addresses": ["10.0.0.1"],
+                            "conditions": { "ready": ready }
+                        }]
+                    }]
+                })))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let apps = discover_namespace_apps(&client, "test").await.unwrap();
+        assert_eq!(apps.len(), 2);
+        assert!(!apps.iter().find(|a| a.name == "my-sonarr").unwrap().ready);
+        assert!(apps.iter().find(|a| a.name == "my-radarr").unwrap().ready);
+    }
 }
@@ -0,0 +1,390 @@
+//! ServarrCredential controller and the projection of credentials into the
+//! apps that use them.
+//!
+//! The controller only reports: which apps consume a credential, a checksum
+//! of its shared keys, and whether its Secret is usable. Copying the keys
+//! into each app is done by the ServarrApp reconcile through [`project`],
+//! which the app controller re-runs whenever a credential or its Secret
+//! changes.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use anyhow::Result;
+use futures::StreamExt;
+use k8s_openapi::api::core::v1::Secret;
+use kube::api::{Api, DeleteParams, ListParams, Patch, PatchParams};
+use kube::runtime::controller::{Action, Controller};
+use kube::runtime::reflector::{self, ObjectRef};
+use kube::runtime::watcher;
+use kube::{Client, CustomResourceExt, ResourceExt};
+use servarr_crds::{Condition, ServarrApp, ServarrCredential, ServarrCredentialStatus};
+use thiserror::Error;
+use tokio::time::Duration;
+use tracing::{error, info, warn};
+
+use crate::context::Context;
+use crate::requeue::{self, object_key};
+
+const FIELD_MANAGER: &str = "servarr-operator-credential";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Kubernetes API error: {0}")]
+    Kube(#[source] kube::Error),
+}
+
+pub fn print_crd() -> Result<()> {
+    let crd = ServarrCredential::crd();
+    let yaml = serde_yaml::to_string(&crd)?;
+    println!("{yaml}");
+    Ok(())
+}
+
+pub async fn run(client: kube::Client, server_state: crate::server::ServerState) -> Result<()> {
+    let ctx = Arc::new(Context::new(client.clone()));
+
+    let (credentials, secrets, apps) = if let Some(ref ns) = ctx.watch_namespace {
+        (
+            Api::<ServarrCredential>::namespaced(client.clone(), ns),
+            Api::<Secret>::namespaced(client.clone(), ns),
+            Api::<ServarrApp>::namespaced(client.clone(), ns),
+        )
+    } else {
+        (
+            Api::<ServarrCredential>::all(client.clone()),
+            Api::<Secret>::all(client.clone()),
+            Api::<ServarrApp>::all(client.clone()),
+        )
+    };
+
+    // Credentials by source Secret, for the Secret watch below.
+    let (store, writer) = reflector::store::<ServarrCredential>();
+    tokio::spawn(crate::metrics::sample_cache(
+        "servarrcredential",
+        store.clone(),
+        |cred| {
+            crate::metrics::generation_pending(
+                cred.metadata.generation,
+                cred.status.as_ref().map(|s| s.observed_generation),
+            )
+        },
+    ));
+    let credentials_for_reflector = credentials.clone();
+    tokio::spawn(async move {
+        reflector::reflector(
+            writer,
+            watcher::watcher(credentials_for_reflector, watcher::Config::default()),
+        )
+        .for_each(|_| std::future::ready(()))
+        .await;
+    });
+
+    info!("Starting credential controller");
+    server_state.set_ready();
+
+    Controller::new(credentials, watcher::Config::default())
+        .watches(secrets, watcher::Config::default(), move |secret| {
+            let secret_ns = secret.namespace();
+            let secret_name = secret.name_any();
+            store
+                .state()
+                .into_iter()
+                .filter(move |cred| {
+                    cred.namespace() == secret_ns && cred.spec.secret_name == secret_name
+                })
+                .map(|cred| ObjectRef::from_obj(&*cred))
+                .collect::<Vec<_>>()
+        })
+        // Apps that start using a credential show up as consumers right
+        // away; ones that stop are dropped on the next periodic reconcile.
+        .watches(apps, watcher::Config::default(), |app| {
+            let ns = app.namespace();
+            app.spec
+                .credentials
+                .iter()
+                .map(|c| {
+                    let r = ObjectRef::<ServarrCredential>::new(&c.name);
+                    match ns {
+                        Some(ref ns) => r.within(ns),
+                        None => r,
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .shutdown_on_signal()
+        .run(reconcile, error_policy, ctx.clone())
+        .for_each(|res| {
+            if let Ok((ref obj, _)) = res {
+                ctx.error_backoff
+                    .succeeded(&object_key(obj.namespace.as_deref(), &obj.name));
+            }
+            async move {
+                match res {
+                    Ok(o) => info!(?o, "credential reconciled"),
+                    Err(e) => error!(%e, "credential reconcile error"),
+                }
+            }
+        })
+        .await;
+
+    Ok(())
+}
+
+/// Keys listed in `spec.keys` that the Secret doesn't have.
+fn missing_keys(cred: &ServarrCredential, secret: &Secret) -> Vec<String> {
+    let data = secret.data.as_ref();
+    cred.spec
+        .keys
+        .iter()
+        .filter(|k| !data.is_some_and(|d| d.contains_key(*k)))
+        .cloned()
+        .collect()
+}
+
+pub async fn reconcile(cred: Arc<ServarrCredential>, ctx: Arc<Context>) -> Result<Action, Error> {
+    let _in_flight = crate::metrics::InFlight::start("servarrcredential");
+    let client = &ctx.client;
+    let name = cred.name_any();
+    let ns = cred.namespace().unwrap_or_else(|| "default".into());
+    let now = chrono_now();
+
+    info!(%name, %ns, "reconciling ServarrCredential");
+
+    // Start from the current status so unchanged conditions keep their
+    // transition time and the status patch is a no-op.
+    let mut status = cred.status.clone().unwrap_or_default();
+    status.observed_generation = cred.metadata.generation.unwrap_or(0);
+
+    let apps = Api::<ServarrApp>::namespaced(client.clone(), &ns)
+        .list(&ListParams::default())
+        .await
+        .map_err(Error::Kube)?;
+    status.consumers = apps
+        .iter()
+        .filter(|app| app.spec.credentials.iter().any(|c| c.name == name))
+        .map(|app| app.name_any())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    status.consumer_count = status.consumers.len() as i32;
+
+    let secret = Api::<Secret>::namespaced(client.clone(), &ns)
+        .get_opt(&cred.spec.secret_name)
+        .await
+        .map_err(Error::Kube)?;
+    let ready = match secret {
+        None => {
+            status.secret_checksum = None;
+            Condition::fail(
+                "Ready",
+                "SecretNotFound",
+                &format!("Secret {} not found", cred.spec.secret_name),
+                &now,
+            )
+        }
+        Some(secret) => {
+            let data = servarr_resources::credential::shared_data(&cred, &secret);
+            status.secret_checksum = Some(servarr_resources::credential::checksum([(
+                name.as_str(),
+                &data,
+            )]));
+            let missing = missing_keys(&cred, &secret);
+            if !missing.is_empty() {
+                Condition::fail(
+                    "Ready",
+                    "MissingKeys",
+                    &format!(
+                        "Secret {} has no key(s) {}",
+                        cred.spec.secret_name,
+                        missing.join(", ")
+                    ),
+                    &now,
+                )
+            } else {
+                Condition::ok(
+                    "Ready",
+                    "SecretFound",
+                    &format!("{} key(s) shared", data.len()),
+                    &now,
+                )
+            }
+        }
+    };
+    let is_ready = ready.status == "True";
+    status.set_condition(ready);
+
+    patch_status(client, &ns, &name, &status).await?;
+
+    Ok(Action::requeue(if is_ready {
+        requeue::settings().settled
+    } else {
+        Duration::from_secs(60)
+    }))
+}
+
+async fn patch_status(
+    client: &Client,
+    ns: &str,
+    name: &str,
+    status: &ServarrCredentialStatus,
+) -> Result<(), Error> {
+    let api = Api::<ServarrCredential>::namespaced(client.clone(), ns);
+    let status_patch = serde_json::json!({
+        "apiVersion": "servarr.dev/v1alpha1",
+        "kind": "ServarrCredential",
+        "status": status,
+    });
+    api.patch_status(
+        name,
+        &PatchParams::apply(FIELD_MANAGER).force(),
+        &Patch::Apply(status_patch),
+    )
+    .await
+    .map_err(Error::Kube)?;
+    Ok(())
+}
+
+pub fn error_policy(cred: Arc<ServarrCredential>, error: &Error, ctx: Arc<Context>) -> Action {
+    warn!(%error, "credential reconciliation failed, requeuing");
+    Action::requeue(
+        ctx.error_backoff
+            .failed(&object_key(cred.namespace().as_deref(), &cred.name_any())),
+    )
+}
+
+fn chrono_now() -> String {
+    use chrono::{SecondsFormat, Utc};
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Result of projecting an app's credentials.
+#[derive(Debug, Default)]
+pub struct Projection {
+    /// Names of the app's credential copies that were applied.
+    pub secrets: Vec<String>,
+    /// Checksum of the applied copies, for the pod template; `None` when
+    /// the app uses no credentials.
+    pub checksum: Option<String>,
+    /// Why a referenced credential couldn't be projected, one per
+    /// credential. Pods referencing its copy stay pending until it can.
+    pub problems: Vec<String>,
+}
+
+/// Copy each credential in `app.spec.credentials` into the app's own
+/// Secret, and delete copies of credentials the app no longer references.
+pub async fn project(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    pp: &PatchParams,
+) -> Result<Projection, kube::Error> {
+    let credentials = Api::<ServarrCredential>::namespaced(client.clone(), ns);
+    let secrets = Api::<Secret>::namespaced(client.clone(), ns);
+    let mut projection = Projection::default();
+    let mut applied = BTreeMap::new();
+
+    for cref in &app.spec.credentials {
+        let Some(cred) = credentials.get_opt(&cref.name).await? else {
+            projection
+                .problems
+                .push(format!("ServarrCredential {} not found", cref.name));
+            continue;
+        };
+        let Some(source) = secrets.get_opt(&cred.spec.secret_name).await? else {
+            projection.problems.push(format!(
+                "Secret {} of ServarrCredential {} not found",
+                cred.spec.secret_name, cref.name
+            ));
+            continue;
+        };
+        let shared = servarr_resources::credential::shared_data(&cred, &source);
+        let unknown: Vec<&str> = cref
+            .env
+            .iter()
+            .map(|v| v.key.as_str())
+            .filter(|k| !shared.contains_key(*k))
+            .collect();
+        if !unknown.is_empty() {
+            projection.problems.push(format!(
+                "ServarrCredential {} does not share key(s) {}",
+                cref.name,
+                unknown.join(", ")
+            ));
+        }
+        let copy = servarr_resources::credential::build(app, &cred, &source);
+        let copy_name = servarr_resources::credential::secret_name(app, &cref.name);
+        secrets.patch(&copy_name, pp, &Patch::Apply(&copy)).await?;
+        projection.secrets.push(copy_name);
+        applied.insert(cref.name.as_str(), shared);
+    }
+
+    if !app.spec.credentials.is_empty() {
+        projection.checksum = Some(servarr_resources::credential::checksum(
+            applied.iter().map(|(name, data)| (*name, data)),
+        ));
+    }
+
+    // Copies of credentials dropped from the spec would otherwise linger
+    // until the app is deleted. The last status lists what was projected.
+    let prefix = servarr_resources::credential::secret_name(app, "");
+    let previous = app
+        .status
+        .as_ref()
+        .and_then(|s| s.generated_resources.as_ref())
+        .map(|g| g.secrets.as_slice())
+        .unwrap_or_default();
+    for secret_name in previous {
+        if secret_name.starts_with(&prefix) && !projection.secrets.contains(secret_name) {
+            info!(app = %app.name_any(), secret = %secret_name, "deleting copy of unused credential");
+            match secrets.delete(secret_name, &DeleteParams::default()).await {
+                Ok(_) => {}
+                Err(kube::Error::Api(e)) if e.code == 404 => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    Ok(projection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::ByteString;
+    use servarr_crds::ServarrCredentialSpec;
+
+    #[test]
+    fn print_crd_returns_ok() {
+        assert!(print_crd().is_ok());
+    }
+
+    #[test]
+    fn missing_keys_lists_only_absent_keys() {
+        let cred = ServarrCredential::new(
+            "usenet",
+            ServarrCredentialSpec {
+                secret_name: "usenet".into(),
+                keys: vec!["username".into(), "password".into()],
+                ..Default::default()
+            },
+        );
+        let secret = Secret {
+            data: Some(BTreeMap::from([(
+                "username".to_string(),
+                ByteString(b"alice".to_vec()),
+            )])),
+            ..Default::default()
+        };
+        assert_eq!(missing_keys(&cred, &secret), vec!["password".to_string()]);
+
+        let shares_all = ServarrCredential::new(
+            "usenet",
+            ServarrCredentialSpec {
+                secret_name: "usenet".into(),
+                ..Default::default()
+            },
+        );
+        assert!(missing_keys(&shares_all, &secret).is_empty());
+    }
+}
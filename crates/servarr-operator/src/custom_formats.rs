@@ -0,0 +1,194 @@
+//! Sonarr/Radarr custom formats declared in `appConfig.customFormats`.
+//!
+//! Formats are matched to the app's by name. A format is written back only
+//! when its specifications differ from the app's copy; fields the app
+//! returns beyond those declared, such as labels and help text, are not
+//! compared.
+
+use std::collections::BTreeMap;
+
+use serde_json::{Value, json};
+use servarr_api::{ApiError, ServarrClient};
+use servarr_crds::CustomFormat;
+
+/// What a sync pass did with each declared format.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SyncOutcome {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub unchanged: Vec<String>,
+    /// `(format, error)` for formats that could not be applied.
+    pub failed: Vec<(String, String)>,
+}
+
+impl SyncOutcome {
+    /// One-line summary for the `CustomFormatsSynced` condition.
+    pub fn message(&self) -> String {
+        let mut parts = Vec::new();
+        for (label, names) in [("created", &self.created), ("updated", &self.updated)] {
+            if !names.is_empty() {
+                parts.push(format!("{label}: {}", names.join(", ")));
+            }
+        }
+        if !self.unchanged.is_empty() {
+            parts.push(format!("{} unchanged", self.unchanged.len()));
+        }
+        for (name, error) in &self.failed {
+            parts.push(format!("{name} failed: {error}"));
+        }
+        parts.join("; ")
+    }
+}
+
+/// `spec` in the shape the app's API takes, with each specification's
+/// fields as the `[{name, value}]` list the apps use.
+pub fn desired(spec: &CustomFormat) -> Value {
+    let specifications: Vec<Value> = spec
+        .specifications
+        .iter()
+        .map(|s| {
+            json!({
+                "name": s.name,
+                "implementation": s.implementation,
+                "negate": s.negate,
+                "required": s.required,
+                "fields": s
+                    .fields
+                    .iter()
+                    .map(|(name, value)| json!({ "name": name, "value": value }))
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    json!({
+        "name": spec.name,
+        "includeCustomFormatWhenRenaming": spec.include_custom_format_when_renaming,
+        "specifications": specifications,
+    })
+}
+
+/// Field values by name, for comparing specifications whose field lists
+/// differ in order or in fields the app fills in itself.
+fn field_values(spec: &Value) -> BTreeMap<&str, &Value> {
+    spec.get("fields")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|f| Some((f.get("name")?.as_str()?, f.get("value")?)))
+        .collect()
+}
+
+/// Whether the app's `current` format already matches `desired`. Fields
+/// the app returns beyond those in `desired` are ignored.
+pub fn matches(current: &Value, desired: &Value) -> bool {
+    let specs = |v: &Value| v["specifications"].as_array().cloned().unwrap_or_default();
+    let (current_specs, desired_specs) = (specs(current), specs(desired));
+    current["includeCustomFormatWhenRenaming"] == desired["includeCustomFormatWhenRenaming"]
+        && current_specs.len() == desired_specs.len()
+        && current_specs.iter().zip(&desired_specs).all(|(c, d)| {
+            ["name", "implementation", "negate", "required"]
+                .iter()
+                .all(|key| c[key] == d[key])
+                && {
+                    let have = field_values(c);
+                    field_values(d)
+                        .iter()
+                        .all(|(name, value)| have.get(name) == Some(value))
+                }
+        })
+}
+
+/// Create or update every format in `specs` that differs from the app.
+pub async fn sync(api: &ServarrClient, specs: &[CustomFormat]) -> Result<SyncOutcome, ApiError> {
+    let existing = api.custom_formats().await?;
+    let mut outcome = SyncOutcome::default();
+
+    for spec in specs {
+        let mut format = desired(spec);
+        let current = existing
+            .iter()
+            .find(|f| f.get("name").and_then(Value::as_str) == Some(spec.name.as_str()));
+        let result = match current {
+            Some(current) if matches(current, &format) => {
+                outcome.unchanged.push(spec.name.clone());
+                continue;
+            }
+            Some(current) => {
+                let id = current.get("id").and_then(Value::as_i64).unwrap_or(0);
+                format["id"] = id.into();
+                api.update_custom_format(id, &format)
+                    .await
+                    .map(|_| &mut outcome.updated)
+            }
+            None => api
+                .create_custom_format(&format)
+                .await
+                .map(|_| &mut outcome.created),
+        };
+        match result {
+            Ok(list) => list.push(spec.name.clone()),
+            Err(e) => outcome.failed.push((spec.name.clone(), e.to_string())),
+        }
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use servarr_crds::CustomFormatSpecification;
+
+    fn format() -> CustomFormat {
+        CustomFormat {
+            name: "x265 (HD)".into(),
+            include_custom_format_when_renaming: false,
+            specifications: vec![
+                CustomFormatSpecification {
+                    name: "x265".into(),
+                    implementation: "ReleaseTitleSpecification".into(),
+                    negate: false,
+                    required: true,
+                    fields: [("value".to_string(), json!("[xh][ ._-]?265"))].into(),
+                },
+                CustomFormatSpecification {
+                    name: "Not 2160p".into(),
+                    implementation: "ResolutionSpecification".into(),
+                    negate: true,
+                    required: true,
+                    fields: [("value".to_string(), json!(2160))].into(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn desired_uses_field_lists() {
+        let format = desired(&format());
+        assert_eq!(format["name"], "x265 (HD)");
+        assert_eq!(
+            format["specifications"][1]["fields"],
+            json!([{ "name": "value", "value": 2160 }])
+        );
+        assert_eq!(format["specifications"][1]["negate"], true);
+    }
+
+    #[test]
+    fn matching_ignores_extra_app_fields() {
+        let desired = desired(&format());
+        let mut current = desired.clone();
+        current["id"] = 7.into();
+        current["specifications"][0]["implementationName"] = "Release Title".into();
+        current["specifications"][0]["fields"] = json!([
+            { "order": 0, "name": "value", "label": "Regular Expression", "value": "[xh][ ._-]?265" }
+        ]);
+        assert!(matches(&current, &desired));
+
+        current["specifications"][1]["negate"] = false.into();
+        assert!(!matches(&current, &desired));
+
+        let mut renamed = desired.clone();
+        renamed["includeCustomFormatWhenRenaming"] = true.into();
+        assert!(!matches(&renamed, &desired));
+    }
+}
@@ -0,0 +1,272 @@
+//! `servarr-operator debug`: a throwaway toolbox pod next to a ServarrApp,
+//! with the app's config volume mounted read-only and `sqlite3`, `curl`,
+//! and `jq` at hand, so nobody has to hand-write one to inspect a database
+//! or poke the API from inside the cluster.
+
+use std::time::Duration;
+
+use anyhow::{Context as _, bail};
+use k8s_openapi::api::core::v1::{
+    Container, ExecAction, PersistentVolumeClaimVolumeSource, Pod, PodSpec, Probe, SecurityContext,
+    Volume, VolumeMount,
+};
+use kube::api::{Api, DeleteParams, ListParams, ObjectMeta, PostParams};
+use kube::runtime::wait::await_condition;
+use kube::{Client, ResourceExt};
+use servarr_crds::ServarrApp;
+use servarr_resources::{common, pvc};
+
+use crate::port_forward;
+
+/// Label naming the app a toolbox pod belongs to. Kept off the app's own
+/// selector labels so its Service never routes to the toolbox.
+pub const DEBUG_OF_LABEL: &str = "servarr.dev/debug-of";
+
+/// When the toolbox pod is deleted, RFC 3339.
+pub const EXPIRES_ANNOTATION: &str = "servarr.dev/debug-expires";
+
+pub const DEFAULT_IMAGE: &str = "alpine:3.20";
+
+/// File the container creates once the tools are installed; the readiness
+/// probe waits for it.
+const READY_FILE: &str = "/tmp/toolbox-ready";
+
+/// How long to wait for the pod to become ready.
+const START_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// The toolbox pod for `app`. It installs the tools when the image has
+/// `apk`, then sleeps until `ttl` runs out; `activeDeadlineSeconds` stops
+/// it even if the CLI that created it is gone. `node` pins it next to the
+/// app's running pod so a ReadWriteOnce claim can be shared.
+pub fn build_pod(
+    app: &ServarrApp,
+    image: &str,
+    ttl: Duration,
+    node: Option<String>,
+    expires: &str,
+) -> Pod {
+    let app_name = common::app_name(app);
+    let ttl_secs = ttl.as_secs().max(1);
+    let script = format!(
+        "command -v apk >/dev/null && apk add --no-cache sqlite curl jq >/dev/null; \
+         touch {READY_FILE}; exec sleep {ttl_secs}"
+    );
+
+    let (volumes, mounts) = match pvc::config_volume(app) {
+        Some(vol) => (
+            vec![Volume {
+                name: "config".into(),
+                persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
+                    claim_name: pvc::claim_for(app, &vol),
+                    read_only: Some(true),
+                }),
+                ..Default::default()
+            }],
+            vec![VolumeMount {
+                name: "config".into(),
+                mount_path: vol.mount_path,
+                read_only: Some(true),
+                ..Default::default()
+            }],
+        ),
+        None => (Vec::new(), Vec::new()),
+    };
+
+    Pod {
+        metadata: ObjectMeta {
+            generate_name: Some(format!("{app_name}-debug-")),
+            namespace: Some(common::app_namespace(app)),
+            labels: Some([(DEBUG_OF_LABEL.to_string(), app_name)].into()),
+            annotations: Some([(EXPIRES_ANNOTATION.to_string(), expires.to_string())].into()),
+            owner_references: Some(vec![common::owner_reference(app)]),
+            ..Default::default()
+        },
+        spec: Some(PodSpec {
+            restart_policy: Some("Never".into()),
+            active_deadline_seconds: Some(ttl_secs as i64),
+            termination_grace_period_seconds: Some(1),
+            node_name: node,
+            containers: vec![Container {
+                name: "toolbox".into(),
+                image: Some(image.to_string()),
+                command: Some(vec!["sh".into(), "-c".into(), script]),
+                working_dir: mounts.first().map(|m| m.mount_path.clone()),
+                volume_mounts: Some(mounts).filter(|m| !m.is_empty()),
+                readiness_probe: Some(Probe {
+                    exec: Some(ExecAction {
+                        command: Some(vec!["test".into(), "-e".into(), READY_FILE.into()]),
+                    }),
+                    period_seconds: Some(2),
+                    ..Default::default()
+                }),
+                security_context: Some(SecurityContext {
+                    allow_privilege_escalation: Some(false),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            volumes: Some(volumes).filter(|v| !v.is_empty()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Node of a running pod of `app`, if any.
+async fn app_node(client: &Client, ns: &str, app: &ServarrApp) -> anyhow::Result<Option<String>> {
+    let selector = common::selector_labels(app)
+        .iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join(",");
+    let pods = Api::<Pod>::namespaced(client.clone(), ns)
+        .list(&ListParams::default().labels(&selector))
+        .await?;
+    Ok(pods.items.into_iter().find_map(|p| {
+        let running = p.status.as_ref()?.phase.as_deref() == Some("Running");
+        running.then(|| p.spec?.node_name).flatten()
+    }))
+}
+
+fn is_ready(pod: Option<&Pod>) -> bool {
+    pod.and_then(|p| p.status.as_ref())
+        .and_then(|s| s.conditions.as_ref())
+        .is_some_and(|c| c.iter().any(|c| c.type_ == "Ready" && c.status == "True"))
+}
+
+/// Start a toolbox pod for ServarrApp `ns/name`, print how to exec into
+/// it, and delete it after `ttl` or on Ctrl-C.
+pub async fn run(
+    client: Client,
+    ns: &str,
+    name: &str,
+    image: &str,
+    ttl: Duration,
+) -> anyhow::Result<()> {
+    let app = port_forward::get_app(&client, ns, name).await?;
+    let node = app_node(&client, ns, &app).await?;
+    let expires =
+        chrono::Utc::now() + chrono::Duration::from_std(ttl).context("TTL out of range")?;
+    let expires = expires.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let pods = Api::<Pod>::namespaced(client.clone(), ns);
+    let pod = pods
+        .create(
+            &PostParams::default(),
+            &build_pod(&app, image, ttl, node, &expires),
+        )
+        .await
+        .context("failed to create the toolbox pod")?;
+    let pod_name = pod.name_any();
+
+    let delete = || async {
+        match pods.delete(&pod_name, &DeleteParams::default()).await {
+            Ok(_) => println!("Deleted {ns}/{pod_name}"),
+            Err(e) => eprintln!("failed to delete {ns}/{pod_name}: {e}"),
+        }
+    };
+
+    let ready = tokio::time::timeout(
+        START_TIMEOUT,
+        await_condition(pods.clone(), &pod_name, is_ready),
+    )
+    .await;
+    if !matches!(ready, Ok(Ok(_))) {
+        delete().await;
+        bail!(
+            "{ns}/{pod_name} did not become ready within {}s; check `kubectl describe pod`",
+            START_TIMEOUT.as_secs()
+        );
+    }
+
+    println!("Toolbox pod {ns}/{pod_name} is ready until {expires}");
+    if let Some(vol) = pvc::config_volume(&app) {
+        println!(
+            "{} is mounted read-only at {}",
+            pvc::claim_for(&app, &vol),
+            vol.mount_path
+        );
+    }
+    println!("  kubectl exec -it -n {ns} {pod_name} -- sh");
+    println!("Press Ctrl-C to delete it now.");
+
+    tokio::select! {
+        _ = tokio::time::sleep(ttl) => {}
+        _ = tokio::signal::ctrl_c() => {}
+    }
+    delete().await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use servarr_crds::{AppType, ServarrAppSpec, WorkloadType};
+
+    fn app(app_type: AppType) -> ServarrApp {
+        let mut app = ServarrApp::new(
+            "sonarr",
+            ServarrAppSpec {
+                app: app_type,
+                ..Default::default()
+            },
+        );
+        app.metadata.namespace = Some("media".into());
+        app.metadata.uid = Some("uid-1".into());
+        app
+    }
+
+    #[test]
+    fn pod_mounts_config_read_only_and_expires() {
+        let pod = build_pod(
+            &app(AppType::Sonarr),
+            DEFAULT_IMAGE,
+            Duration::from_secs(1800),
+            Some("node-a".into()),
+            "2026-01-01T00:00:00Z",
+        );
+        assert_eq!(
+            pod.metadata.labels.unwrap()[DEBUG_OF_LABEL],
+            "sonarr".to_string()
+        );
+        let spec = pod.spec.unwrap();
+        assert_eq!(spec.active_deadline_seconds, Some(1800));
+        assert_eq!(spec.node_name.as_deref(), Some("node-a"));
+        let claim = spec.volumes.unwrap()[0]
+            .persistent_volume_claim
+            .clone()
+            .unwrap();
+        assert_eq!(claim.claim_name, "sonarr-config");
+        assert_eq!(claim.read_only, Some(true));
+        let container = &spec.containers[0];
+        let mount = &container.volume_mounts.as_ref().unwrap()[0];
+        assert_eq!(mount.mount_path, "/config");
+        assert_eq!(mount.read_only, Some(true));
+        assert!(container.command.as_ref().unwrap()[2].contains("sleep 1800"));
+    }
+
+    #[test]
+    fn statefulset_apps_use_the_template_claim() {
+        let mut app = app(AppType::Sonarr);
+        app.spec.workload_type = Some(WorkloadType::StatefulSet);
+        let pod = build_pod(&app, DEFAULT_IMAGE, Duration::from_secs(60), None, "");
+        let claim = pod.spec.unwrap().volumes.unwrap()[0]
+            .persistent_volume_claim
+            .clone()
+            .unwrap();
+        assert_eq!(claim.claim_name, "config-sonarr-0");
+    }
+
+    #[test]
+    fn apps_without_volumes_get_no_mount() {
+        let pod = build_pod(
+            &app(AppType::TdarrNode),
+            DEFAULT_IMAGE,
+            Duration::from_secs(60),
+            None,
+            "",
+        );
+        let spec = pod.spec.unwrap();
+        assert!(spec.volumes.is_none());
+        assert!(spec.containers[0].volume_mounts.is_none());
+    }
+}
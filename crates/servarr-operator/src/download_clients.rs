@@ -0,0 +1,391 @@
+//! Transmission and SABnzbd registered as download clients in Sonarr,
+//! Radarr, Lidarr, Readarr, and Whisparr (`downloadClientSync`).
+//!
+//! A download client is built on the app's current copy when one already
+//! points at the same Service, or on the app's blank schema entry for the
+//! implementation otherwise, so settings the operator doesn't manage
+//! (priority, remove-completed) keep the app's value. The apps never return
+//! passwords or API keys, so those are left out of the comparison: they are
+//! sent whenever a client is added or updated, but a rotated password alone
+//! doesn't trigger an update.
+
+use serde_json::Value;
+use servarr_api::{ApiError, ServarrClient};
+
+/// Fields whose values the apps mask in responses.
+const SECRET_FIELDS: &[&str] = &["password", "apiKey"];
+
+/// Per-app field holding the download category.
+const CATEGORY_FIELDS: &[&str] = &[
+    "tvCategory",
+    "movieCategory",
+    "musicCategory",
+    "bookCategory",
+];
+
+/// A Transmission or SABnzbd ServarrApp to register.
+#[derive(Debug, Clone)]
+pub struct Target {
+    /// Name the download client is registered under.
+    pub name: String,
+    /// `Transmission` or `Sabnzbd`, the apps' implementation name.
+    pub implementation: &'static str,
+    /// The app's Service DNS name, `{service}.{namespace}.svc`.
+    pub host: String,
+    pub port: i32,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub api_key: Option<String>,
+    /// Whether the Service has a ready endpoint. The apps test the
+    /// connection before saving, so unready targets are deferred.
+    pub ready: bool,
+}
+
+fn field<'a>(client: &'a Value, name: &str) -> Option<&'a Value> {
+    client
+        .get("fields")?
+        .as_array()?
+        .iter()
+        .find(|f| f.get("name").and_then(Value::as_str) == Some(name))
+        .and_then(|f| f.get("value"))
+}
+
+/// Set `name`'s value if the client has such a field. Returns whether it did.
+fn set_field(client: &mut Value, name: &str, value: Value) -> bool {
+    let Some(fields) = client.get_mut("fields").and_then(Value::as_array_mut) else {
+        return false;
+    };
+    match fields
+        .iter_mut()
+        .find(|f| f.get("name").and_then(Value::as_str) == Some(name))
+    {
+        Some(f) => {
+            f["value"] = value;
+            true
+        }
+        None => false,
+    }
+}
+
+/// The client's host field, which identifies the operator's registrations.
+pub fn host(client: &Value) -> Option<&str> {
+    field(client, "host").and_then(Value::as_str)
+}
+
+/// Whether the operator registered `client`: a Transmission or SABnzbd
+/// client pointing at a Service in `namespace`.
+pub fn is_managed(client: &Value, namespace: &str) -> bool {
+    let implementation = client.get("implementation").and_then(Value::as_str);
+    matches!(implementation, Some("Transmission" | "Sabnzbd"))
+        && host(client).is_some_and(|h| h.ends_with(&format!(".{namespace}.svc")))
+}
+
+/// `base` (the app's copy, or its schema entry) with `target` applied.
+/// Empty `tags` leave the client's tags as they are.
+pub fn desired(
+    target: &Target,
+    base: &Value,
+    category: Option<&str>,
+    tags: &[i64],
+) -> Result<Value, String> {
+    let mut client = base.clone();
+    client["name"] = target.name.clone().into();
+    client["enable"] = true.into();
+    for (name, value) in [
+        ("host", Value::from(target.host.clone())),
+        ("port", target.port.into()),
+        ("useSsl", false.into()),
+    ] {
+        if !set_field(&mut client, name, value) {
+            return Err(format!("{} has no '{name}' field", target.implementation));
+        }
+    }
+    if target.implementation == "Sabnzbd" {
+        let api_key = target
+            .api_key
+            .as_deref()
+            .ok_or("SABnzbd has no apiKeySecret")?;
+        set_field(&mut client, "apiKey", api_key.into());
+    }
+    if let (Some(username), Some(password)) = (&target.username, &target.password) {
+        set_field(&mut client, "username", username.clone().into());
+        set_field(&mut client, "password", password.clone().into());
+    }
+    if let Some(category) = category {
+        for name in CATEGORY_FIELDS {
+            set_field(&mut client, name, category.into());
+        }
+    }
+    if !tags.is_empty() {
+        let mut tags = tags.to_vec();
+        tags.sort_unstable();
+        client["tags"] = tags.into();
+    }
+    Ok(client)
+}
+
+/// `client` without the values the apps mask, for comparison.
+fn comparable(client: &Value) -> Value {
+    let mut client = client.clone();
+    if let Some(fields) = client.get_mut("fields").and_then(Value::as_array_mut) {
+        fields.retain(|f| {
+            !f.get("name")
+                .and_then(Value::as_str)
+                .is_some_and(|n| SECRET_FIELDS.contains(&n))
+        });
+    }
+    client
+}
+
+/// Whether `current` must be replaced by `desired`.
+pub fn needs_update(current: &Value, desired: &Value) -> bool {
+    comparable(current) != comparable(desired)
+}
+
+/// What a sync pass did.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SyncOutcome {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub unchanged: Vec<String>,
+    pub removed: Vec<String>,
+    /// Targets skipped because their Service has no ready endpoint.
+    pub deferred: Vec<String>,
+    /// `(download client, error)` for those that could not be applied.
+    pub failed: Vec<(String, String)>,
+}
+
+impl SyncOutcome {
+    /// One-line summary for the `DownloadClientsSynced` condition.
+    pub fn message(&self) -> String {
+        let mut parts = Vec::new();
+        for (label, names) in [
+            ("added", &self.added),
+            ("updated", &self.updated),
+            ("removed", &self.removed),
+            ("waiting for", &self.deferred),
+        ] {
+            if !names.is_empty() {
+                parts.push(format!("{label}: {}", names.join(", ")));
+            }
+        }
+        if !self.unchanged.is_empty() {
+            parts.push(format!("{} unchanged", self.unchanged.len()));
+        }
+        for (name, error) in &self.failed {
+            parts.push(format!("{name} failed: {error}"));
+        }
+        if parts.is_empty() {
+            "No download clients found".to_string()
+        } else {
+            parts.join("; ")
+        }
+    }
+}
+
+/// Register every ready target, tagged with `tags` (labels), and, with
+/// `auto_remove`, remove the operator's registrations whose target is gone.
+pub async fn sync(
+    api: &ServarrClient,
+    targets: &[Target],
+    category: Option<&str>,
+    tags: &[String],
+    auto_remove: bool,
+    namespace: &str,
+) -> Result<SyncOutcome, ApiError> {
+    let existing = api.download_clients().await?;
+    let tags = if tags.is_empty() {
+        Vec::new()
+    } else {
+        crate::tags::ids(api, &mut api.tags().await?, tags).await?
+    };
+    let mut schema = None;
+    let mut outcome = SyncOutcome::default();
+
+    for target in targets {
+        if !target.ready {
+            outcome.deferred.push(target.name.clone());
+            continue;
+        }
+        if let Some(e) = servarr_api::faults::sync("download-clients", &target.name) {
+            outcome.failed.push((target.name.clone(), e.to_string()));
+            continue;
+        }
+        let current = existing
+            .iter()
+            .find(|c| host(c) == Some(target.host.as_str()));
+        let base = match current {
+            Some(current) => current.clone(),
+            None => {
+                if schema.is_none() {
+                    schema = Some(api.download_client_schema().await?);
+                }
+                let entry = schema.iter().flatten().find(|s| {
+                    s.get("implementation").and_then(Value::as_str) == Some(target.implementation)
+                });
+                let Some(entry) = entry else {
+                    outcome.failed.push((
+                        target.name.clone(),
+                        format!("app does not support {}", target.implementation),
+                    ));
+                    continue;
+                };
+                let mut blank = entry.clone();
+                if let Some(obj) = blank.as_object_mut() {
+                    obj.remove("id");
+                }
+                blank
+            }
+        };
+        let client = match desired(target, &base, category, &tags) {
+            Ok(client) => client,
+            Err(e) => {
+                outcome.failed.push((target.name.clone(), e));
+                continue;
+            }
+        };
+
+        let result = match current {
+            Some(current) if !needs_update(current, &client) => {
+                outcome.unchanged.push(target.name.clone());
+                continue;
+            }
+            Some(current) => {
+                let id = current.get("id").and_then(Value::as_i64).unwrap_or(0);
+                api.update_download_client(id, &client)
+                    .await
+                    .map(|_| &mut outcome.updated)
+            }
+            None => api
+                .create_download_client(&client)
+                .await
+                .map(|_| &mut outcome.added),
+        };
+        match result {
+            Ok(list) => list.push(target.name.clone()),
+            Err(e) => outcome.failed.push((target.name.clone(), e.to_string())),
+        }
+    }
+
+    if auto_remove {
+        for client in existing.iter().filter(|c| is_managed(c, namespace)) {
+            if targets
+                .iter()
+                .any(|t| host(client) == Some(t.host.as_str()))
+            {
+                continue;
+            }
+            let name = client
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            let id = client.get("id").and_then(Value::as_i64).unwrap_or(0);
+            match api.delete_download_client(id).await {
+                Ok(()) => outcome.removed.push(name),
+                Err(e) => outcome.failed.push((name, e.to_string())),
+            }
+        }
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema_entry() -> Value {
+        json!({
+            "enable": false,
+            "protocol": "usenet",
+            "priority": 1,
+            "name": "",
+            "implementation": "Sabnzbd",
+            "fields": [
+                { "name": "host", "value": "localhost" },
+                { "name": "port", "value": 8080 },
+                { "name": "useSsl", "value": false },
+                { "name": "apiKey" },
+                { "name": "username" },
+                { "name": "password" },
+                { "name": "tvCategory", "value": "tv" }
+            ]
+        })
+    }
+
+    fn target() -> Target {
+        Target {
+            name: "media-sabnzbd".into(),
+            implementation: "Sabnzbd",
+            host: "media-sabnzbd.media.svc".into(),
+            port: 8080,
+            username: None,
+            password: None,
+            api_key: Some("sab-key".into()),
+            ready: true,
+        }
+    }
+
+    #[test]
+    fn desired_fills_connection_and_category() {
+        let client = desired(&target(), &schema_entry(), Some("sonarr"), &[]).unwrap();
+        assert_eq!(client["name"], "media-sabnzbd");
+        assert_eq!(client["enable"], true);
+        assert_eq!(client["priority"], 1);
+        assert_eq!(host(&client), Some("media-sabnzbd.media.svc"));
+        assert_eq!(field(&client, "apiKey"), Some(&json!("sab-key")));
+        assert_eq!(field(&client, "tvCategory"), Some(&json!("sonarr")));
+        assert!(is_managed(&client, "media"));
+        assert!(!is_managed(&client, "other"));
+    }
+
+    #[test]
+    fn sabnzbd_requires_api_key() {
+        let mut target = target();
+        target.api_key = None;
+        assert!(desired(&target, &schema_entry(), None, &[]).is_err());
+    }
+
+    #[test]
+    fn masked_secrets_do_not_force_an_update() {
+        let client = desired(&target(), &schema_entry(), None, &[]).unwrap();
+        let mut current = client.clone();
+        set_field(&mut current, "apiKey", "********".into());
+        assert!(!needs_update(&current, &client));
+
+        set_field(&mut current, "port", 8081.into());
+        assert!(needs_update(&current, &client));
+    }
+
+    #[test]
+    fn declared_tags_replace_the_clients_tags() {
+        let mut current = desired(&target(), &schema_entry(), None, &[]).unwrap();
+        current["tags"] = json!([7]);
+        let client = desired(&target(), &current, None, &[]).unwrap();
+        assert_eq!(client["tags"], json!([7]));
+        assert!(!needs_update(&current, &client));
+
+        let client = desired(&target(), &current, None, &[3, 1]).unwrap();
+        assert_eq!(client["tags"], json!([1, 3]));
+        assert!(needs_update(&current, &client));
+    }
+
+    #[test]
+    fn outcome_message() {
+        let outcome = SyncOutcome {
+            added: vec!["media-transmission".into()],
+            unchanged: vec!["media-sabnzbd".into()],
+            deferred: vec!["media-sabnzbd-2".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            outcome.message(),
+            "added: media-transmission; waiting for: media-sabnzbd-2; 1 unchanged"
+        );
+        assert_eq!(
+            SyncOutcome::default().message(),
+            "No download clients found"
+        );
+    }
+}
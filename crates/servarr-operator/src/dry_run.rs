@@ -0,0 +1,188 @@
+//! `--dry-run` mode: a ServarrApp, MediaStack or ServarrAppSet reconcile
+//! renders its child objects, compares them with what is in the cluster, and
+//! reports the difference in `status.wouldChange` instead of applying it.
+//!
+//! The comparison mirrors server-side apply: only fields the operator sets
+//! are compared, so defaults the API server fills in and fields owned by
+//! other tools don't show up as changes. As in drift detection, a zero value
+//! the API server omits is not a change. Existing PVCs are never modified,
+//! so they are only reported when missing.
+
+use kube::api::{Api, DynamicObject, GroupVersionKind};
+use kube::discovery::ApiResource;
+use serde_json::Value;
+
+/// Changed paths listed per object before the rest are summarized.
+const MAX_PATHS: usize = 5;
+
+/// Paths of the fields in `desired` whose value differs in `live`. Lists of
+/// a different length are reported as a whole.
+pub fn changed_paths(desired: &Value, live: &Value) -> Vec<String> {
+    let mut paths = Vec::new();
+    walk(desired, live, String::new(), &mut paths);
+    paths
+}
+
+fn walk(desired: &Value, live: &Value, path: String, paths: &mut Vec<String>) {
+    match (desired, live) {
+        (Value::Object(want), Value::Object(have)) => {
+            for (key, value) in want {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match have.get(key) {
+                    Some(current) => walk(value, current, child, paths),
+                    None if crate::controller::is_zero_value(value) => {}
+                    None => paths.push(child),
+                }
+            }
+        }
+        (Value::Array(want), Value::Array(have)) if want.len() == have.len() => {
+            for (i, (value, current)) in want.iter().zip(have).enumerate() {
+                walk(value, current, format!("{path}[{i}]"), paths);
+            }
+        }
+        _ if desired != live => paths.push(path),
+        _ => {}
+    }
+}
+
+/// One `wouldChange` entry for a rendered object, or `None` when the live
+/// object already matches.
+pub fn describe(desired: &Value, live: Option<&Value>) -> Option<String> {
+    let kind = desired["kind"].as_str().unwrap_or_default();
+    let name = desired["metadata"]["name"].as_str().unwrap_or_default();
+    let Some(live) = live else {
+        return Some(format!("create {kind}/{name}"));
+    };
+    if kind == "PersistentVolumeClaim" {
+        return None;
+    }
+    let paths = changed_paths(desired, live);
+    if paths.is_empty() {
+        return None;
+    }
+    let mut shown = paths[..paths.len().min(MAX_PATHS)].join(", ");
+    if paths.len() > MAX_PATHS {
+        shown.push_str(&format!(" and {} more", paths.len() - MAX_PATHS));
+    }
+    Some(format!("update {kind}/{name}: {shown}"))
+}
+
+fn api_resource(object: &Value) -> ApiResource {
+    let api_version = object["apiVersion"].as_str().unwrap_or_default();
+    let (group, version) = api_version.split_once('/').unwrap_or(("", api_version));
+    let kind = object["kind"].as_str().unwrap_or_default();
+    ApiResource::from_gvk(&GroupVersionKind::gvk(group, version, kind))
+}
+
+/// Compare each of `objects` (as rendered by [`crate::render::render`]) with
+/// the cluster and describe what applying them would change. Objects without
+/// a namespace of their own are looked up in `ns`.
+pub async fn plan(
+    client: &kube::Client,
+    ns: &str,
+    objects: &[Value],
+) -> Result<Vec<String>, kube::Error> {
+    let mut changes = Vec::new();
+    for desired in objects {
+        let object_ns = desired["metadata"]["namespace"].as_str().unwrap_or(ns);
+        let api = Api::<DynamicObject>::namespaced_with(
+            client.clone(),
+            object_ns,
+            &api_resource(desired),
+        );
+        let name = desired["metadata"]["name"].as_str().unwrap_or_default();
+        let live = match api.get_opt(name).await? {
+            Some(obj) => Some(serde_json::to_value(obj).map_err(kube::Error::SerdeError)?),
+            None => None,
+        };
+        changes.extend(describe(desired, live.as_ref()));
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn deployment(image: &str) -> Value {
+        json!({
+            "apiVersion": "apps/v1",
+            "kind": "Deployment",
+            "metadata": { "name": "sonarr", "labels": { "app": "sonarr" } },
+            "spec": {
+                "template": {
+                    "spec": { "containers": [{ "name": "sonarr", "image": image }] }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn server_defaults_are_not_changes() {
+        let mut live = deployment("sonarr:4");
+        live["metadata"]["uid"] = "abc".into();
+        live["spec"]["template"]["spec"]["containers"][0]["terminationMessagePath"] =
+            "/dev/termination-log".into();
+        live["status"] = json!({ "readyReplicas": 1 });
+        assert!(changed_paths(&deployment("sonarr:4"), &live).is_empty());
+
+        let mut desired = deployment("sonarr:4");
+        desired["spec"]["template"]["spec"]["hostNetwork"] = false.into();
+        assert!(changed_paths(&desired, &live).is_empty());
+        assert_eq!(describe(&deployment("sonarr:4"), Some(&live)), None);
+    }
+
+    #[test]
+    fn reports_changed_and_missing_fields() {
+        let mut live = deployment("sonarr:3");
+        live["metadata"]["labels"] = json!({});
+        assert_eq!(
+            changed_paths(&deployment("sonarr:4"), &live),
+            vec![
+                "metadata.labels.app",
+                "spec.template.spec.containers[0].image"
+            ]
+        );
+
+        live["spec"]["template"]["spec"]["containers"] = json!([]);
+        assert_eq!(
+            describe(&deployment("sonarr:4"), Some(&live)).as_deref(),
+            Some("update Deployment/sonarr: metadata.labels.app, spec.template.spec.containers")
+        );
+    }
+
+    #[test]
+    fn describes_creates_and_skips_existing_pvcs() {
+        assert_eq!(
+            describe(&deployment("sonarr:4"), None).as_deref(),
+            Some("create Deployment/sonarr")
+        );
+        let pvc = json!({
+            "apiVersion": "v1",
+            "kind": "PersistentVolumeClaim",
+            "metadata": { "name": "sonarr-config" },
+            "spec": { "storageClassName": "fast" }
+        });
+        let live = json!({ "metadata": { "name": "sonarr-config" }, "spec": {} });
+        assert_eq!(describe(&pvc, Some(&live)), None);
+    }
+
+    #[test]
+    fn long_diffs_are_summarized() {
+        let desired = json!({
+            "kind": "ConfigMap",
+            "metadata": { "name": "cm" },
+            "data": { "a": "1", "b": "1", "c": "1", "d": "1", "e": "1", "f": "1", "g": "1" }
+        });
+        let live = json!({ "kind": "ConfigMap", "metadata": { "name": "cm" }, "data": {} });
+        assert_eq!(
+            describe(&desired, Some(&live)).as_deref(),
+            Some("update ConfigMap/cm: data.a, data.b, data.c, data.d, data.e and 2 more")
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use k8s_openapi::api::core::v1::ObjectReference;
+use kube::runtime::events::{Event, EventType, Recorder};
+use tracing::{debug, info};
+
+/// Default minimum gap between repeats of the same Normal event on one
+/// object.
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Reasons published on every reconcile whose note carries a measurement,
+/// so repeats are recognised by reason alone.
+const PERIODIC_REASONS: &[&str] = &["ReconcileSuccess"];
+
+#[derive(Default)]
+struct ObjectEvents {
+    last_was_warning: bool,
+    sent: HashMap<String, Instant>,
+}
+
+/// Decides which events reach the API server. Warning events always do.
+/// A Normal event is published the first time its reason and note are seen
+/// for an object, when the object's previous event was a Warning (it
+/// recovered), or once `interval` has passed since the same reason and
+/// note were last published for it. A new note is a new event, so one-shot
+/// actions such as a remediated queue item or a triggered reconcile are
+/// never dropped. A zero interval publishes everything.
+pub struct EventThrottle {
+    interval: Duration,
+    objects: Mutex<HashMap<String, ObjectEvents>>,
+}
+
+impl EventThrottle {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            objects: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Read the interval from `EVENT_THROTTLE_SECONDS` (default one hour).
+    pub fn from_env() -> Self {
+        let interval = std::env::var("EVENT_THROTTLE_SECONDS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map_or(DEFAULT_INTERVAL, Duration::from_secs);
+        info!(interval_secs = interval.as_secs(), "event throttle");
+        Self::new(interval)
+    }
+
+    /// Whether `event` for the object identified by `key` should be
+    /// published at `now`. A `true` answer is recorded as a publication.
+    pub fn should_publish(&self, key: &str, event: &Event, now: Instant) -> bool {
+        let mut objects = self.objects.lock().unwrap_or_else(|e| e.into_inner());
+        if !objects.contains_key(key) {
+            // Forget objects that have gone quiet (usually deleted ones).
+            let horizon = self.interval.max(DEFAULT_INTERVAL) * 2;
+            objects.retain(|_, o| o.sent.values().any(|t| now.duration_since(*t) < horizon));
+        }
+        let entry = objects.entry(key.to_string()).or_default();
+        entry
+            .sent
+            .retain(|_, t| now.duration_since(*t) < self.interval);
+
+        let repeat_key = if PERIODIC_REASONS.contains(&event.reason.as_str()) {
+            event.reason.clone()
+        } else {
+            format!(
+                "{}\n{}",
+                event.reason,
+                event.note.as_deref().unwrap_or_default()
+            )
+        };
+        let publish = event.type_ == EventType::Warning
+            || self.interval.is_zero()
+            || entry.last_was_warning
+            || !entry.sent.contains_key(&repeat_key);
+        if publish {
+            entry.last_was_warning = event.type_ == EventType::Warning;
+            entry.sent.insert(repeat_key, now);
+        }
+        publish
+    }
+
+    /// [`should_publish`](Self::should_publish) for the object `reference`
+    /// points at, now.
+    pub fn admit(&self, reference: &ObjectReference, event: &Event) -> bool {
+        let key = format!(
+            "{}/{}/{}",
+            reference.kind.as_deref().unwrap_or_default(),
+            reference.namespace.as_deref().unwrap_or_default(),
+            reference.name.as_deref().unwrap_or_default()
+        );
+        let publish = self.should_publish(&key, event, Instant::now());
+        if !publish {
+            debug!(object = %key, reason = %event.reason, "event throttled");
+        }
+        publish
+    }
+}
+
+impl Default for EventThrottle {
+    fn default() -> Self {
+        Self::new(DEFAULT_INTERVAL)
+    }
+}
+
+/// A [`Recorder`] that consults an [`EventThrottle`] before publishing.
+pub struct ThrottledRecorder<'a> {
+    recorder: Recorder,
+    throttle: &'a EventThrottle,
+}
+
+impl<'a> ThrottledRecorder<'a> {
+    pub fn new(recorder: Recorder, throttle: &'a EventThrottle) -> Self {
+        Self { recorder, throttle }
+    }
+
+    pub async fn publish(
+        &self,
+        event: &Event,
+        reference: &ObjectReference,
+    ) -> Result<(), kube::Error> {
+        if !self.throttle.admit(reference, event) {
+            return Ok(());
+        }
+        self.recorder.publish(event, reference).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(type_: EventType, reason: &str) -> Event {
+        Event {
+            type_,
+            reason: reason.into(),
+            note: None,
+            action: "Reconcile".into(),
+            secondary: None,
+        }
+    }
+
+    #[test]
+    fn repeated_normal_event_is_throttled_until_interval() {
+        let throttle = EventThrottle::new(Duration::from_secs(60));
+        let ok = event(EventType::Normal, "ReconcileSuccess");
+        let t0 = Instant::now();
+        assert!(throttle.should_publish("a", &ok, t0));
+        assert!(!throttle.should_publish("a", &ok, t0 + Duration::from_secs(30)));
+        assert!(throttle.should_publish("a", &ok, t0 + Duration::from_secs(60)));
+        // Other objects are tracked separately
+        assert!(throttle.should_publish("b", &ok, t0 + Duration::from_secs(61)));
+    }
+
+    #[test]
+    fn state_change_and_warnings_publish_immediately() {
+        let throttle = EventThrottle::new(Duration::from_secs(60));
+        let ok = event(EventType::Normal, "ReconcileSuccess");
+        let err = event(EventType::Warning, "ReconcileError");
+        let t0 = Instant::now();
+        assert!(throttle.should_publish("a", &ok, t0));
+        assert!(throttle.should_publish("a", &err, t0));
+        assert!(throttle.should_publish("a", &err, t0));
+        // Recovering is a state change
+        assert!(throttle.should_publish("a", &ok, t0));
+        assert!(!throttle.should_publish("a", &ok, t0));
+        // Reasons are throttled independently
+        let synced = event(EventType::Normal, "ProwlarrSyncComplete");
+        assert!(throttle.should_publish("a", &synced, t0));
+        assert!(!throttle.should_publish("a", &synced, t0));
+        assert!(!throttle.should_publish("a", &ok, t0));
+    }
+
+    #[test]
+    fn changed_note_is_a_new_event() {
+        let throttle = EventThrottle::new(Duration::from_secs(60));
+        let t0 = Instant::now();
+        let remediated = |item: &str| Event {
+            note: Some(format!("Blocklisted and re-searched {item}")),
+            ..event(EventType::Normal, "QueueItemRemediated")
+        };
+        assert!(throttle.should_publish("a", &remediated("Show S01E01"), t0));
+        assert!(throttle.should_publish("a", &remediated("Show S01E02"), t0));
+        assert!(!throttle.should_publish("a", &remediated("Show S01E02"), t0));
+
+        // Periodic reasons are throttled whatever their note says.
+        let success = |secs: &str| Event {
+            note: Some(format!("All resources reconciled in {secs}s")),
+            ..event(EventType::Normal, "ReconcileSuccess")
+        };
+        assert!(throttle.should_publish("a", &success("0.41"), t0));
+        assert!(!throttle.should_publish("a", &success("0.38"), t0));
+    }
+
+    #[test]
+    fn zero_interval_disables_throttling() {
+        let throttle = EventThrottle::new(Duration::ZERO);
+        let ok = event(EventType::Normal, "ReconcileSuccess");
+        let t0 = Instant::now();
+        assert!(throttle.should_publish("a", &ok, t0));
+        assert!(throttle.should_publish("a", &ok, t0));
+    }
+
+    #[test]
+    fn from_env_reads_interval() {
+        temp_env::with_var("EVENT_THROTTLE_SECONDS", Some("0"), || {
+            assert!(EventThrottle::from_env().interval.is_zero());
+        });
+        temp_env::with_var("EVENT_THROTTLE_SECONDS", None::<&str>, || {
+            assert_eq!(EventThrottle::from_env().interval, DEFAULT_INTERVAL);
+        });
+    }
+}
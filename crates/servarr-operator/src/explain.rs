@@ -0,0 +1,441 @@
+//! `servarr-operator explain`: print a ServarrApp's effective spec — the CR
+//! merged with its MediaStack's settings, the compiled app defaults and the
+//! operator's image overrides — noting where every field came from.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use anyhow::Context as _;
+use kube::{Api, Client, ResourceExt};
+use serde_yaml::{Mapping, Value};
+use servarr_crds::{AppDefaults, EnvVar, ImageSpec, MediaStack, ServarrApp};
+
+use crate::port_forward;
+
+/// Where an effective field value came from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    /// Set on the ServarrApp.
+    App,
+    /// Generated by MediaStack `stack` from the listed parts of its spec
+    /// (`apps[1]`, `defaults`, ...). No parts means the stack injected it
+    /// (NFS mounts, shared Postgres).
+    Stack { stack: String, layers: Vec<String> },
+    /// The operator's compiled default for the app type.
+    OperatorDefault,
+    /// The compiled default image for a node architecture.
+    ArchDefault(String),
+    /// The operator's image override env var.
+    ImageOverride(String),
+    /// The given source merged over the operator default (env, persistence).
+    Merged(Box<Source>),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::App => write!(f, "ServarrApp spec"),
+            Self::Stack { stack, layers } if layers.is_empty() => {
+                write!(f, "MediaStack {stack} (generated)")
+            }
+            Self::Stack { stack, layers } => {
+                write!(f, "MediaStack {stack} {}", layers.join(" + "))
+            }
+            Self::OperatorDefault => write!(f, "operator default"),
+            Self::ArchDefault(arch) => write!(f, "operator default for {arch}"),
+            Self::ImageOverride(var) => write!(f, "operator image override ({var})"),
+            Self::Merged(source) => write!(f, "{source} + operator default"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExplainedField {
+    /// Spec key, as written in YAML.
+    pub name: String,
+    pub value: Value,
+    pub source: Source,
+}
+
+/// Spec fields a MediaStack merges from its defaults and the app entry
+/// rather than taking one or the other.
+const STACK_MERGED_FIELDS: &[&str] = &["env", "persistence", "podAnnotations", "driftIgnoreFields"];
+
+fn is_set(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::Sequence(s)) => !s.is_empty(),
+        Some(Value::Mapping(m)) => !m.is_empty(),
+        Some(_) => true,
+    }
+}
+
+fn to_mapping<T: serde::Serialize>(value: &T) -> Mapping {
+    match serde_yaml::to_value(value) {
+        Ok(Value::Mapping(m)) => m,
+        _ => Mapping::new(),
+    }
+}
+
+/// Where a field set in the app's spec was set: the CR itself, or the
+/// parts of the owning MediaStack it was generated from.
+fn spec_source(app: &ServarrApp, stack: Option<&MediaStack>, key: &str) -> Source {
+    let Some(stack) = stack else {
+        return Source::App;
+    };
+    let stack_name = stack.name_any();
+    let name = app.name_any();
+    let entry = stack.spec.apps.iter().enumerate().find(|(_, a)| {
+        a.child_name(&stack_name) == name
+            || (a.split4k == Some(true) && format!("{stack_name}-{}-4k", a.app.as_str()) == name)
+    });
+    let Some((i, entry)) = entry else {
+        return Source::App;
+    };
+    let is_4k = entry.child_name(&stack_name) != name;
+
+    let mut layers = Vec::new();
+    if is_4k
+        && is_set(
+            entry
+                .split4k_overrides
+                .as_ref()
+                .map(to_mapping)
+                .unwrap_or_default()
+                .get(key),
+        )
+    {
+        layers.push(format!("apps[{i}].split4kOverrides"));
+    }
+    if is_set(to_mapping(entry).get(key)) {
+        layers.push(format!("apps[{i}]"));
+    }
+    let merged = STACK_MERGED_FIELDS.contains(&key);
+    if (layers.is_empty() || merged)
+        && is_set(
+            stack
+                .spec
+                .defaults
+                .as_ref()
+                .map(to_mapping)
+                .unwrap_or_default()
+                .get(key),
+        )
+    {
+        layers.push("defaults".into());
+    }
+    if !merged {
+        layers.truncate(1);
+    }
+    Source::Stack {
+        stack: stack_name,
+        layers,
+    }
+}
+
+/// Resolve the effective spec of `app` the way the resource builders do and
+/// attribute each set field. `stack` is the MediaStack that owns the app,
+/// if any; `image_overrides` and `default_arch` are the operator settings
+/// used to pick the default image.
+pub fn explain(
+    app: &ServarrApp,
+    stack: Option<&MediaStack>,
+    image_overrides: &HashMap<String, ImageSpec>,
+    default_arch: Option<&str>,
+) -> Vec<ExplainedField> {
+    let defaults = AppDefaults::for_app(&app.spec.app);
+    let mut effective = app.spec.clone();
+    let mut filled: HashMap<&str, Source> = HashMap::new();
+
+    if effective.image.is_none() {
+        let app_key = app.spec.app.to_string();
+        let arch = servarr_resources::common::pinned_arch(app)
+            .or_else(|| default_arch.map(str::to_string));
+        let (image, source) = match servarr_resources::deployment::default_image(
+            app,
+            image_overrides,
+            default_arch,
+        ) {
+            Ok(image) if image_overrides.contains_key(&app_key) => (
+                image,
+                Source::ImageOverride(format!("DEFAULT_IMAGE_{}_REPO", app_key.to_uppercase())),
+            ),
+            Ok(image) => match arch {
+                Some(arch) => (image, Source::ArchDefault(arch)),
+                None => (image, Source::OperatorDefault),
+            },
+            // Reconciliation refuses this; show what the image would be
+            Err(_) => (defaults.image.clone(), Source::OperatorDefault),
+        };
+        effective.image = Some(image);
+        filled.insert("image", source);
+    }
+    if effective.uid.is_none() {
+        effective.uid = Some(defaults.uid);
+        filled.insert("uid", Source::OperatorDefault);
+    }
+    if effective.gid.is_none() {
+        effective.gid = Some(defaults.gid);
+        filled.insert("gid", Source::OperatorDefault);
+    }
+    if effective.security.is_none() {
+        effective.security = Some(defaults.security.clone());
+        filled.insert("security", Source::OperatorDefault);
+    }
+    if effective.service.is_none() {
+        effective.service = Some(defaults.service.clone());
+        filled.insert("service", Source::OperatorDefault);
+    }
+    if effective.resources.is_none() {
+        effective.resources = Some(defaults.resources.clone());
+        filled.insert("resources", Source::OperatorDefault);
+    }
+    if effective.probes.is_none() {
+        effective.probes = Some(defaults.probes.clone());
+        filled.insert("probes", Source::OperatorDefault);
+    }
+    if effective.app_config.is_none() && defaults.app_config.is_some() {
+        effective.app_config = defaults.app_config.clone();
+        filled.insert("appConfig", Source::OperatorDefault);
+    }
+
+    // The builders always layer the spec's persistence and env over the
+    // app-type defaults
+    match &app.spec.persistence {
+        None => {
+            effective.persistence = Some(defaults.persistence.clone());
+            filled.insert("persistence", Source::OperatorDefault);
+        }
+        Some(p) => {
+            effective.persistence = Some(defaults.persistence.merge_with(p));
+            filled.insert(
+                "persistence",
+                Source::Merged(Box::new(spec_source(app, stack, "persistence"))),
+            );
+        }
+    }
+    if !defaults.env.is_empty() {
+        let mut env: Vec<EnvVar> = defaults
+            .env
+            .iter()
+            .filter(|d| !app.spec.env.iter().any(|e| e.name == d.name))
+            .cloned()
+            .collect();
+        env.extend(app.spec.env.iter().cloned());
+        let source = if app.spec.env.is_empty() {
+            Source::OperatorDefault
+        } else {
+            Source::Merged(Box::new(spec_source(app, stack, "env")))
+        };
+        effective.env = env;
+        filled.insert("env", source);
+    }
+
+    to_mapping(&effective)
+        .into_iter()
+        .filter(|(_, value)| is_set(Some(value)))
+        .filter_map(|(key, value)| {
+            let name = key.as_str()?.to_string();
+            let source = filled
+                .remove(name.as_str())
+                .unwrap_or_else(|| spec_source(app, stack, &name));
+            Some(ExplainedField {
+                name,
+                value,
+                source,
+            })
+        })
+        .collect()
+}
+
+/// Render explained fields as YAML, each top-level field followed by a
+/// comment naming its source.
+pub fn render(fields: &[ExplainedField]) -> anyhow::Result<String> {
+    let mut out = String::new();
+    for field in fields {
+        let mut mapping = Mapping::new();
+        mapping.insert(Value::String(field.name.clone()), field.value.clone());
+        let yaml = serde_yaml::to_string(&mapping)?;
+        let mut lines = yaml.lines();
+        if let Some(first) = lines.next() {
+            out.push_str(&format!("{first}  # {}\n", field.source));
+        }
+        for line in lines {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+/// Print the effective spec of ServarrApp `ns/name`. Operator-level
+/// settings (image overrides, default architecture) are read from this
+/// process's environment, so run it with the operator's values exported to
+/// see exactly what the operator would use.
+pub async fn run(client: Client, ns: &str, name: &str) -> anyhow::Result<()> {
+    let app = port_forward::get_app(&client, ns, name).await?;
+    let stack = match app.labels().get("servarr.dev/stack") {
+        Some(stack_name) => Api::<MediaStack>::namespaced(client.clone(), ns)
+            .get_opt(stack_name)
+            .await
+            .with_context(|| format!("failed to get MediaStack {ns}/{stack_name}"))?,
+        None => None,
+    };
+
+    let image_overrides = crate::context::load_image_overrides();
+    let default_arch = crate::context::load_default_arch();
+    let fields = explain(
+        &app,
+        stack.as_ref(),
+        &image_overrides,
+        default_arch.as_deref(),
+    );
+
+    println!("# ServarrApp {ns}/{name}");
+    if let Some(stack) = &stack {
+        println!("# generated by MediaStack {ns}/{}", stack.name_any());
+    }
+    println!("# operator settings read from DEFAULT_IMAGE_* / DEFAULT_NODE_ARCH in this shell");
+    print!("{}", render(&fields)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use servarr_crds::{AppType, MediaStackSpec, ServarrAppSpec, StackApp, StackDefaults};
+
+    fn field<'a>(fields: &'a [ExplainedField], name: &str) -> &'a ExplainedField {
+        fields
+            .iter()
+            .find(|f| f.name == name)
+            .unwrap_or_else(|| panic!("no field {name}"))
+    }
+
+    fn stack_app(app: AppType) -> StackApp {
+        serde_json::from_value(serde_json::json!({ "app": app })).unwrap()
+    }
+
+    #[test]
+    fn standalone_app_fields_come_from_cr_or_defaults() {
+        let mut app = ServarrApp::new(
+            "sonarr",
+            ServarrAppSpec {
+                uid: Some(1000),
+                ..ServarrAppSpec::default()
+            },
+        );
+        app.spec.app = AppType::Sonarr;
+        let fields = explain(&app, None, &HashMap::new(), None);
+
+        assert_eq!(field(&fields, "app").source, Source::App);
+        assert_eq!(field(&fields, "uid").source, Source::App);
+        assert_eq!(field(&fields, "uid").value, Value::from(1000));
+        assert_eq!(field(&fields, "gid").source, Source::OperatorDefault);
+        assert_eq!(field(&fields, "image").source, Source::OperatorDefault);
+        assert_eq!(field(&fields, "env").source, Source::OperatorDefault);
+        // Unset fields without a default are left out
+        assert!(fields.iter().all(|f| f.name != "gateway"));
+    }
+
+    #[test]
+    fn image_override_and_architecture_are_attributed() {
+        let app = ServarrApp::new(
+            "radarr",
+            ServarrAppSpec {
+                app: AppType::Radarr,
+                ..ServarrAppSpec::default()
+            },
+        );
+        let overrides = HashMap::from([(
+            "radarr".to_string(),
+            ImageSpec {
+                repository: "example/radarr".into(),
+                tag: "1".into(),
+                ..ImageSpec::default()
+            },
+        )]);
+        let fields = explain(&app, None, &overrides, None);
+        assert_eq!(
+            field(&fields, "image").source,
+            Source::ImageOverride("DEFAULT_IMAGE_RADARR_REPO".into())
+        );
+
+        let fields = explain(&app, None, &HashMap::new(), Some("arm64"));
+        assert_eq!(
+            field(&fields, "image").source,
+            Source::ArchDefault("arm64".into())
+        );
+    }
+
+    #[test]
+    fn stack_child_fields_name_the_stack_layer() {
+        let mut sonarr = stack_app(AppType::Sonarr);
+        sonarr.gid = Some(2000);
+        sonarr.env = vec![EnvVar {
+            name: "A".into(),
+            value: "1".into(),
+        }];
+        let mut stack = MediaStack::new(
+            "media",
+            MediaStackSpec {
+                nfs: None,
+                postgres: None,
+                defaults: Some(StackDefaults {
+                    uid: Some(1000),
+                    gid: Some(1000),
+                    env: vec![EnvVar {
+                        name: "B".into(),
+                        value: "2".into(),
+                    }],
+                    ..StackDefaults::default()
+                }),
+                apps: vec![stack_app(AppType::Radarr), sonarr],
+                apply_parallelism: None,
+                spread_policy: None,
+            },
+        );
+        stack.metadata.namespace = Some("media".into());
+        let defaults = stack.spec.defaults.clone();
+        let spec = stack.spec.apps[1].to_servarr_spec(defaults.as_ref());
+        let app = ServarrApp::new("media-sonarr", spec);
+
+        let fields = explain(&app, Some(&stack), &HashMap::new(), None);
+        let layer = |layers: &[&str]| Source::Stack {
+            stack: "media".into(),
+            layers: layers.iter().map(|l| l.to_string()).collect(),
+        };
+        assert_eq!(field(&fields, "uid").source, layer(&["defaults"]));
+        assert_eq!(field(&fields, "gid").source, layer(&["apps[1]"]));
+        assert_eq!(
+            field(&fields, "env").source,
+            Source::Merged(Box::new(layer(&["apps[1]", "defaults"])))
+        );
+        assert_eq!(field(&fields, "image").source, Source::OperatorDefault);
+    }
+
+    #[test]
+    fn render_annotates_first_line_of_each_field() {
+        let fields = vec![
+            ExplainedField {
+                name: "uid".into(),
+                value: Value::from(1000),
+                source: Source::App,
+            },
+            ExplainedField {
+                name: "env".into(),
+                value: serde_yaml::to_value(vec![EnvVar {
+                    name: "TZ".into(),
+                    value: "UTC".into(),
+                }])
+                .unwrap(),
+                source: Source::OperatorDefault,
+            },
+        ];
+        let out = render(&fields).unwrap();
+        assert_eq!(
+            out,
+            "uid: 1000  # ServarrApp spec\nenv:  # operator default\n- name: TZ\n  value: UTC\n"
+        );
+    }
+}
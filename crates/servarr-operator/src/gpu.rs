@@ -0,0 +1,257 @@
+//! GPU availability for apps that set `spec.gpu`. A pod requesting
+//! `nvidia.com/gpu` on a cluster without the NVIDIA device plugin never
+//! schedules, so before the Deployment is applied the requested resources
+//! are checked against what the nodes advertise as allocatable.
+//!
+//! Missing GPUs are dropped from the app (software transcoding) unless
+//! `gpu.required` is set, in which case the request stands and the
+//! `GpuAvailable` condition explains why the pod is Pending. Listing nodes
+//! needs cluster-wide `list` on Nodes; without it the request is applied
+//! unchanged and the condition is Unknown.
+
+use std::sync::Arc;
+
+use k8s_openapi::api::core::v1::Node;
+use kube::Client;
+use kube::api::{Api, ListParams};
+use servarr_crds::{Condition, GpuSpec, ServarrApp, condition_types};
+use tracing::warn;
+
+/// Device plugin resources requested by `gpu`, with their counts.
+pub fn requested(gpu: &GpuSpec) -> Vec<(&'static str, i32)> {
+    [
+        ("nvidia.com/gpu", gpu.nvidia),
+        ("gpu.intel.com/i915", gpu.intel),
+        ("amd.com/gpu", gpu.amd),
+    ]
+    .into_iter()
+    .filter_map(|(resource, n)| n.filter(|&n| n > 0).map(|n| (resource, n)))
+    .collect()
+}
+
+/// Requested resources that no single node has enough of.
+pub fn missing(requested: &[(&'static str, i32)], nodes: &[Node]) -> Vec<&'static str> {
+    requested
+        .iter()
+        .filter(|(resource, n)| {
+            !nodes.iter().any(|node| {
+                node.status
+                    .as_ref()
+                    .and_then(|s| s.allocatable.as_ref())
+                    .and_then(|a| a.get(*resource))
+                    .and_then(|q| q.0.parse::<i64>().ok())
+                    .is_some_and(|allocatable| allocatable >= i64::from(*n))
+            })
+        })
+        .map(|(resource, _)| *resource)
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GpuCheck {
+    /// The app requests no GPU.
+    NotRequested,
+    Available,
+    /// The listed resources were dropped and the app runs on CPU.
+    FellBack(Vec<&'static str>),
+    /// The listed resources are missing but `gpu.required` keeps them.
+    Unavailable(Vec<&'static str>),
+    Unknown(String),
+}
+
+impl GpuCheck {
+    pub fn condition(&self, now: &str) -> Option<Condition> {
+        let cond = match self {
+            Self::NotRequested => return None,
+            Self::Available => Condition::ok(
+                condition_types::GPU_AVAILABLE,
+                "DevicePluginReady",
+                "A node advertises every requested GPU resource",
+                now,
+            ),
+            Self::FellBack(missing) => Condition::fail(
+                condition_types::GPU_AVAILABLE,
+                "CpuFallback",
+                &format!(
+                    "No node advertises {}; running without the GPU. Set gpu.required to wait for it instead",
+                    missing.join(", ")
+                ),
+                now,
+            ),
+            Self::Unavailable(missing) => Condition::fail(
+                condition_types::GPU_AVAILABLE,
+                "GpuUnavailable",
+                &format!(
+                    "No node advertises {}; pods stay Pending until a device plugin provides it",
+                    missing.join(", ")
+                ),
+                now,
+            ),
+            Self::Unknown(msg) => Condition {
+                condition_type: condition_types::GPU_AVAILABLE.to_string(),
+                status: "Unknown".to_string(),
+                reason: "NodesUnreadable".to_string(),
+                message: msg.clone(),
+                last_transition_time: now.to_string(),
+            },
+        };
+        Some(cond)
+    }
+
+    /// `app` as its Deployment should be built: without the missing GPUs
+    /// when the check fell back to CPU, unchanged otherwise.
+    pub fn apply(&self, app: Arc<ServarrApp>) -> Arc<ServarrApp> {
+        let Self::FellBack(missing) = self else {
+            return app;
+        };
+        let mut app = (*app).clone();
+        if let Some(gpu) = app.spec.gpu.as_mut() {
+            for resource in missing {
+                match *resource {
+                    "nvidia.com/gpu" => gpu.nvidia = None,
+                    "gpu.intel.com/i915" => gpu.intel = None,
+                    "amd.com/gpu" => gpu.amd = None,
+                    _ => {}
+                }
+            }
+        }
+        Arc::new(app)
+    }
+}
+
+/// Decide from `nodes` what happens to the GPUs `app` requests.
+pub fn evaluate(app: &ServarrApp, nodes: &[Node]) -> GpuCheck {
+    let Some(ref gpu) = app.spec.gpu else {
+        return GpuCheck::NotRequested;
+    };
+    let requested = requested(gpu);
+    if requested.is_empty() {
+        return GpuCheck::NotRequested;
+    }
+    let missing = missing(&requested, nodes);
+    if missing.is_empty() {
+        GpuCheck::Available
+    } else if gpu.required {
+        GpuCheck::Unavailable(missing)
+    } else {
+        GpuCheck::FellBack(missing)
+    }
+}
+
+/// List the cluster's nodes and evaluate `app` against them.
+pub async fn check(client: &Client, app: &ServarrApp) -> GpuCheck {
+    if app
+        .spec
+        .gpu
+        .as_ref()
+        .is_none_or(|g| requested(g).is_empty())
+    {
+        return GpuCheck::NotRequested;
+    }
+    match Api::<Node>::all(client.clone())
+        .list(&ListParams::default())
+        .await
+    {
+        Ok(list) => evaluate(app, &list.items),
+        Err(e) => {
+            warn!(error = %e, "could not list nodes to check GPU resources");
+            GpuCheck::Unknown(format!(
+                "Could not list nodes to check GPU resources ({e}); the GPU request is applied unchanged"
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::NodeStatus;
+    use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+    use servarr_crds::{AppType, ServarrAppSpec};
+
+    fn node(resources: &[(&str, &str)]) -> Node {
+        Node {
+            status: Some(NodeStatus {
+                allocatable: Some(
+                    resources
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), Quantity(v.to_string())))
+                        .collect(),
+                ),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn jellyfin(gpu: GpuSpec) -> Arc<ServarrApp> {
+        Arc::new(ServarrApp::new(
+            "jellyfin",
+            ServarrAppSpec {
+                app: AppType::Jellyfin,
+                gpu: Some(gpu),
+                ..Default::default()
+            },
+        ))
+    }
+
+    #[test]
+    fn available_when_one_node_has_enough() {
+        let app = jellyfin(GpuSpec {
+            nvidia: Some(2),
+            ..Default::default()
+        });
+        let nodes = [
+            node(&[("cpu", "8"), ("nvidia.com/gpu", "1")]),
+            node(&[("nvidia.com/gpu", "2")]),
+        ];
+        assert_eq!(evaluate(&app, &nodes), GpuCheck::Available);
+        assert_eq!(
+            evaluate(&app, &nodes[..1]),
+            GpuCheck::FellBack(vec!["nvidia.com/gpu"])
+        );
+    }
+
+    #[test]
+    fn falls_back_to_cpu_unless_required() {
+        let gpu = GpuSpec {
+            nvidia: Some(1),
+            intel: Some(1),
+            ..Default::default()
+        };
+        let nodes = [node(&[("gpu.intel.com/i915", "1")])];
+
+        let check = evaluate(&jellyfin(gpu.clone()), &nodes);
+        assert_eq!(check, GpuCheck::FellBack(vec!["nvidia.com/gpu"]));
+        let cond = check.condition("t").unwrap();
+        assert_eq!(
+            (cond.status.as_str(), cond.reason.as_str()),
+            ("False", "CpuFallback")
+        );
+        let deployed = check.apply(jellyfin(gpu.clone())).spec.gpu.clone().unwrap();
+        assert_eq!((deployed.nvidia, deployed.intel), (None, Some(1)));
+
+        let required = jellyfin(GpuSpec {
+            required: true,
+            ..gpu
+        });
+        let check = evaluate(&required, &nodes);
+        assert_eq!(check, GpuCheck::Unavailable(vec!["nvidia.com/gpu"]));
+        assert_eq!(check.condition("t").unwrap().reason, "GpuUnavailable");
+        assert_eq!(
+            check.apply(required).spec.gpu.as_ref().unwrap().nvidia,
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn no_condition_without_gpu_request() {
+        let app = jellyfin(GpuSpec {
+            nvidia: Some(0),
+            ..Default::default()
+        });
+        let check = evaluate(&app, &[]);
+        assert_eq!(check, GpuCheck::NotRequested);
+        assert!(check.condition("t").is_none());
+    }
+}
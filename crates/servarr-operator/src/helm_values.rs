@@ -0,0 +1,516 @@
+//! Convert k8s-at-home / bjw-s Helm chart values into a ServarrApp manifest.
+//!
+//! Two layouts are recognised: the k8s-at-home `common` library chart
+//! (top-level `image`, `env`, `persistence`, `ingress`) and the bjw-s
+//! `app-template` chart (`controllers.<name>.containers.<name>`). Anything
+//! that has no ServarrApp equivalent is reported as a warning instead of
+//! being dropped silently.
+
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+use servarr_crds::{
+    AppDefaults, AppType, EnvVar, GatewaySpec, ImageSpec, NfsMount, NodeScheduling,
+    PersistenceSpec, PvcVolume, ResourceList, ResourceRequirements, ServarrApp, ServarrAppSpec,
+    ServiceSpec,
+};
+
+/// Result of a conversion: the manifest plus notes about values that could
+/// not be carried over.
+pub struct Conversion {
+    pub app: ServarrApp,
+    pub warnings: Vec<String>,
+}
+
+/// Render a scalar YAML value (tags are often written as bare numbers) as a
+/// string.
+fn scalar(v: &Value) -> Option<String> {
+    match v {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn str_field(v: &Value, key: &str) -> Option<String> {
+    v.get(key).and_then(scalar).filter(|s| !s.is_empty())
+}
+
+fn enabled(v: &Value) -> bool {
+    v.get("enabled").and_then(Value::as_bool).unwrap_or(true)
+}
+
+fn entries(v: Option<&Value>) -> impl Iterator<Item = (&String, &Value)> {
+    v.and_then(Value::as_object).into_iter().flatten()
+}
+
+/// The bjw-s main container: `controllers.main.containers.main`, falling
+/// back to the first controller and container.
+fn bjw_s_container(values: &Value) -> Option<&Value> {
+    let controllers = values.get("controllers")?.as_object()?;
+    let controller = controllers
+        .get("main")
+        .or_else(|| controllers.values().next())?;
+    let containers = controller.get("containers")?.as_object()?;
+    containers
+        .get("main")
+        .or_else(|| containers.values().next())
+}
+
+fn convert_image(image: &Value) -> Option<ImageSpec> {
+    Some(ImageSpec {
+        repository: str_field(image, "repository")?,
+        tag: str_field(image, "tag").unwrap_or_default(),
+        digest: String::new(),
+        pull_policy: str_field(image, "pullPolicy").unwrap_or_else(|| "IfNotPresent".into()),
+    })
+}
+
+/// Env may be a `NAME: value` map or a list of `{name, value}` entries.
+/// `PUID` / `PGID` become `uid` / `gid` since the operator sets them itself.
+fn convert_env(env: &Value, spec: &mut ServarrAppSpec, warnings: &mut Vec<String>) -> Vec<EnvVar> {
+    let pairs: Vec<(String, &Value)> = match env {
+        Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|i| Some((str_field(i, "name")?, i.get("value").unwrap_or(i))))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let mut out = Vec::new();
+    for (name, value) in pairs {
+        let Some(value) = scalar(value) else {
+            warnings.push(format!(
+                "env {name}: only literal values are supported; valueFrom references were skipped"
+            ));
+            continue;
+        };
+        match name.as_str() {
+            "PUID" => spec.uid = value.parse().ok().or(spec.uid),
+            "PGID" => spec.gid = value.parse().ok().or(spec.gid),
+            _ => out.push(EnvVar { name, value }),
+        }
+    }
+    out
+}
+
+fn convert_resources(resources: &Value) -> Option<ResourceRequirements> {
+    let list = |key: &str| {
+        let v = resources.get(key);
+        ResourceList {
+            cpu: v.and_then(|v| str_field(v, "cpu")).unwrap_or_default(),
+            memory: v.and_then(|v| str_field(v, "memory")).unwrap_or_default(),
+        }
+    };
+    let (limits, requests) = (list("limits"), list("requests"));
+    let empty = |l: &ResourceList| l.cpu.is_empty() && l.memory.is_empty();
+    (!empty(&limits) || !empty(&requests)).then_some(ResourceRequirements { limits, requests })
+}
+
+fn convert_persistence(
+    persistence: &Value,
+    bjw_s: bool,
+    warnings: &mut Vec<String>,
+) -> Option<PersistenceSpec> {
+    let mut spec = PersistenceSpec::default();
+
+    for (name, p) in entries(Some(persistence)) {
+        if !enabled(p) {
+            continue;
+        }
+        let mount_path = if bjw_s {
+            p.get("globalMounts")
+                .and_then(Value::as_array)
+                .and_then(|m| m.first())
+                .and_then(|m| str_field(m, "path"))
+        } else {
+            str_field(p, "mountPath")
+        }
+        .unwrap_or_else(|| format!("/{name}"));
+
+        let kind = str_field(p, "type").unwrap_or_else(|| "pvc".into());
+        match kind.as_str() {
+            "pvc" | "persistentVolumeClaim" => {
+                if let Some(claim) = str_field(p, "existingClaim") {
+                    warnings.push(format!(
+                        "persistence.{name}: existingClaim '{claim}' is not supported; the operator creates its own PVC, so copy the data over before switching"
+                    ));
+                }
+                spec.volumes.push(PvcVolume {
+                    name: name.clone(),
+                    mount_path,
+                    access_mode: str_field(p, "accessMode")
+                        .unwrap_or_else(|| "ReadWriteOnce".into()),
+                    size: str_field(p, "size").unwrap_or_else(|| "1Gi".into()),
+                    storage_class: str_field(p, "storageClass").unwrap_or_default(),
+                    ..Default::default()
+                });
+            }
+            "nfs" => match (str_field(p, "server"), str_field(p, "path")) {
+                (Some(server), Some(path)) => spec.nfs_mounts.push(NfsMount {
+                    name: name.clone(),
+                    server,
+                    path,
+                    mount_path,
+                    read_only: p.get("readOnly").and_then(Value::as_bool).unwrap_or(false),
+                }),
+                _ => warnings.push(format!(
+                    "persistence.{name}: nfs volume is missing server or path, skipped"
+                )),
+            },
+            other => warnings.push(format!(
+                "persistence.{name}: volume type '{other}' has no ServarrApp equivalent, skipped"
+            )),
+        }
+    }
+
+    (!spec.volumes.is_empty() || !spec.nfs_mounts.is_empty()).then_some(spec)
+}
+
+/// Ingress hosts become Gateway API route hosts. ServarrApp only speaks
+/// Gateway API, so the parent Gateway has to be filled in by hand.
+fn convert_ingress(ingress: &Value, warnings: &mut Vec<String>) -> Option<GatewaySpec> {
+    let mut hosts = Vec::new();
+    let mut tls = false;
+    for (_, ing) in entries(Some(ingress)) {
+        if !enabled(ing) {
+            continue;
+        }
+        for h in ing
+            .get("hosts")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            if let Some(host) = str_field(h, "host").or_else(|| scalar(h))
+                && !hosts.contains(&host)
+            {
+                hosts.push(host);
+            }
+        }
+        tls |= ing
+            .get("tls")
+            .and_then(Value::as_array)
+            .is_some_and(|t| !t.is_empty());
+    }
+    if hosts.is_empty() {
+        return None;
+    }
+    warnings.push(
+        "ingress was converted to a Gateway API route; set gateway.parentRefs to your Gateway"
+            .into(),
+    );
+    if tls {
+        warnings.push(
+            "ingress TLS was not converted; configure gateway.tls or terminate TLS at the Gateway"
+                .into(),
+        );
+    }
+    Some(GatewaySpec {
+        enabled: true,
+        hosts,
+        ..Default::default()
+    })
+}
+
+/// Only emit a service override when the chart used a non-default port.
+fn convert_service(service: &Value, app_type: &AppType) -> Option<ServiceSpec> {
+    let (_, main) = entries(Some(service)).find(|(k, _)| *k == "main")?;
+    let port =
+        entries(main.get("ports")).find_map(|(_, p)| p.get("port").and_then(Value::as_i64))? as i32;
+    let mut defaults = AppDefaults::for_app(app_type).service;
+    let first = defaults.ports.first_mut()?;
+    if first.port == port {
+        return None;
+    }
+    first.port = port;
+    Some(defaults)
+}
+
+fn convert_scheduling(pod: &Value) -> Option<NodeScheduling> {
+    let node_selector: BTreeMap<String, String> = entries(pod.get("nodeSelector"))
+        .filter_map(|(k, v)| Some((k.clone(), scalar(v)?)))
+        .collect();
+    let tolerations = pod
+        .get("tolerations")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let affinity = pod
+        .get("affinity")
+        .filter(|a| a.as_object().is_some_and(|o| !o.is_empty()))
+        .cloned();
+    let topology_spread_constraints = pod
+        .get("topologySpreadConstraints")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    (!node_selector.is_empty()
+        || !tolerations.is_empty()
+        || affinity.is_some()
+        || !topology_spread_constraints.is_empty())
+    .then_some(NodeScheduling {
+        node_selector,
+        tolerations,
+        affinity,
+        topology_spread_constraints,
+    })
+}
+
+/// Convert chart `values` for an app of type `app_type`.
+pub fn convert(
+    values: &Value,
+    app_type: AppType,
+    name: &str,
+    namespace: Option<&str>,
+) -> Conversion {
+    let mut warnings = Vec::new();
+    let mut spec = ServarrAppSpec {
+        app: app_type.clone(),
+        ..Default::default()
+    };
+
+    let container = bjw_s_container(values);
+    let bjw_s = container.is_some();
+    // Container-level settings live on the container for bjw-s and at the
+    // top level for k8s-at-home; pod-level settings move under
+    // defaultPodOptions in bjw-s.
+    let workload = container.unwrap_or(values);
+    let pod = if bjw_s {
+        values.get("defaultPodOptions").unwrap_or(&Value::Null)
+    } else {
+        values
+    };
+
+    spec.image = workload.get("image").and_then(convert_image);
+    if let Some(env) = workload.get("env") {
+        spec.env = convert_env(env, &mut spec, &mut warnings);
+    }
+    spec.resources = workload.get("resources").and_then(convert_resources);
+    spec.persistence = values
+        .get("persistence")
+        .and_then(|p| convert_persistence(p, bjw_s, &mut warnings));
+    spec.gateway = values
+        .get("ingress")
+        .and_then(|i| convert_ingress(i, &mut warnings));
+    spec.service = values
+        .get("service")
+        .and_then(|s| convert_service(s, &app_type));
+    spec.scheduling = convert_scheduling(pod);
+
+    let security_context = if bjw_s {
+        pod.get("securityContext")
+    } else {
+        values.get("podSecurityContext")
+    };
+    if let Some(sc) = security_context {
+        spec.uid = sc.get("runAsUser").and_then(Value::as_i64).or(spec.uid);
+        spec.gid = sc.get("runAsGroup").and_then(Value::as_i64).or(spec.gid);
+    }
+
+    let annotations = if bjw_s {
+        pod.get("annotations")
+    } else {
+        values.get("podAnnotations")
+    };
+    let annotations: BTreeMap<String, String> = entries(annotations)
+        .filter_map(|(k, v)| Some((k.clone(), scalar(v)?)))
+        .collect();
+    spec.pod_annotations = (!annotations.is_empty()).then_some(annotations);
+
+    if values.get("addons").is_some() {
+        warnings.push(
+            "addons (VPN, code-server, ...) are not supported; use extraContainers to add sidecars"
+                .into(),
+        );
+    }
+
+    let mut app = ServarrApp::new(name, spec);
+    app.metadata.namespace = namespace.map(str::to_string);
+    Conversion { app, warnings }
+}
+
+/// Drop nulls, empty strings, and empty lists/maps so the emitted manifest
+/// only shows what the conversion actually set.
+fn prune(v: &mut Value) -> bool {
+    match v {
+        Value::Null => false,
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => {
+            items.retain_mut(prune);
+            !items.is_empty()
+        }
+        Value::Object(map) => {
+            let keep: Map<String, Value> = std::mem::take(map)
+                .into_iter()
+                .filter_map(|(k, mut v)| prune(&mut v).then_some((k, v)))
+                .collect();
+            *map = keep;
+            !map.is_empty()
+        }
+        _ => true,
+    }
+}
+
+/// Render a converted ServarrApp as a YAML manifest.
+pub fn to_yaml(app: &ServarrApp) -> anyhow::Result<String> {
+    let mut value = serde_json::to_value(app)?;
+    prune(&mut value);
+    Ok(serde_yaml::to_string(&value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yaml(s: &str) -> Value {
+        serde_yaml::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn converts_k8s_at_home_values() {
+        let values = yaml(
+            r#"
+image:
+  repository: ghcr.io/k8s-at-home/sonarr
+  tag: v3.0.9
+env:
+  TZ: Europe/Berlin
+  PUID: "1000"
+  PGID: 1000
+persistence:
+  config:
+    enabled: true
+    mountPath: /config
+    size: 5Gi
+    storageClass: longhorn
+  media:
+    enabled: true
+    type: nfs
+    server: nas.lan
+    path: /volume1/media
+    mountPath: /media
+  cache:
+    enabled: true
+    type: emptyDir
+  backup:
+    enabled: false
+ingress:
+  main:
+    enabled: true
+    hosts:
+      - host: sonarr.example.com
+        paths:
+          - path: /
+    tls:
+      - hosts: [sonarr.example.com]
+nodeSelector:
+  kubernetes.io/arch: amd64
+podAnnotations:
+  backup.velero.io/backup-volumes: config
+"#,
+        );
+        let Conversion { app, warnings } =
+            convert(&values, AppType::Sonarr, "sonarr", Some("media"));
+        let spec = &app.spec;
+
+        let image = spec.image.as_ref().unwrap();
+        assert_eq!(image.repository, "ghcr.io/k8s-at-home/sonarr");
+        assert_eq!(image.tag, "v3.0.9");
+        assert_eq!(spec.uid, Some(1000));
+        assert_eq!(spec.gid, Some(1000));
+        assert_eq!(spec.env.len(), 1);
+        assert_eq!(spec.env[0].name, "TZ");
+
+        let persistence = spec.persistence.as_ref().unwrap();
+        assert_eq!(persistence.volumes.len(), 1);
+        assert_eq!(persistence.volumes[0].size, "5Gi");
+        assert_eq!(persistence.volumes[0].storage_class, "longhorn");
+        assert_eq!(persistence.nfs_mounts[0].server, "nas.lan");
+
+        let gateway = spec.gateway.as_ref().unwrap();
+        assert!(gateway.enabled);
+        assert_eq!(gateway.hosts, vec!["sonarr.example.com"]);
+
+        assert_eq!(
+            spec.scheduling.as_ref().unwrap().node_selector["kubernetes.io/arch"],
+            "amd64"
+        );
+        assert!(spec.pod_annotations.is_some());
+        assert_eq!(app.metadata.namespace.as_deref(), Some("media"));
+
+        // emptyDir skipped, ingress parentRefs and TLS flagged
+        assert_eq!(warnings.len(), 3, "{warnings:?}");
+    }
+
+    #[test]
+    fn converts_bjw_s_app_template_values() {
+        let values = yaml(
+            r#"
+controllers:
+  main:
+    containers:
+      main:
+        image:
+          repository: ghcr.io/onedr0p/radarr
+          tag: 5.2.6
+        env:
+          - name: TZ
+            value: UTC
+          - name: RADARR__API_KEY
+            valueFrom:
+              secretKeyRef: {name: radarr, key: api-key}
+        resources:
+          limits:
+            memory: 2Gi
+defaultPodOptions:
+  securityContext:
+    runAsUser: 568
+    runAsGroup: 568
+service:
+  main:
+    ports:
+      http:
+        port: 8080
+persistence:
+  config:
+    existingClaim: radarr-config
+    globalMounts:
+      - path: /config
+"#,
+        );
+        let Conversion { app, warnings } = convert(&values, AppType::Radarr, "radarr", None);
+        let spec = &app.spec;
+
+        assert_eq!(spec.image.as_ref().unwrap().tag, "5.2.6");
+        assert_eq!(spec.env.len(), 1);
+        assert_eq!(spec.uid, Some(568));
+        assert_eq!(spec.resources.as_ref().unwrap().limits.memory, "2Gi");
+        assert_eq!(spec.service.as_ref().unwrap().ports[0].port, 8080);
+        assert_eq!(
+            spec.persistence.as_ref().unwrap().volumes[0].mount_path,
+            "/config"
+        );
+        // valueFrom env and existingClaim
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+    }
+
+    #[test]
+    fn default_service_port_is_not_emitted() {
+        let values = yaml("service: {main: {ports: {http: {port: 8989}}}}");
+        let conversion = convert(&values, AppType::Sonarr, "sonarr", None);
+        assert!(conversion.app.spec.service.is_none());
+    }
+
+    #[test]
+    fn to_yaml_prunes_unset_fields() {
+        let values = yaml("image: {repository: lscr.io/linuxserver/sonarr, tag: latest}");
+        let out = to_yaml(&convert(&values, AppType::Sonarr, "sonarr", None).app).unwrap();
+        assert!(out.contains("kind: ServarrApp"));
+        assert!(out.contains("repository: lscr.io/linuxserver/sonarr"));
+        assert!(!out.contains("null"));
+        assert!(!out.contains("gateway"));
+    }
+}
@@ -0,0 +1,373 @@
+//! Resolve image tags to digests through a registry's v2 API, for the
+//! mutating webhook's image pinning, and list a repository's tags for
+//! automatic updates. Only anonymous pulls are supported, which covers the
+//! public default images on lscr.io, ghcr.io, and Docker Hub; a registry
+//! that wants credentials fails the lookup.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+use reqwest::header::{ACCEPT, AUTHORIZATION, LINK, WWW_AUTHENTICATE};
+use servarr_crds::ImageSpec;
+
+/// Manifest types the lookup accepts, multi-arch indexes first so the
+/// digest pins every architecture rather than one.
+const MANIFEST_TYPES: &str = "application/vnd.oci.image.index.v1+json, \
+     application/vnd.docker.distribution.manifest.list.v2+json, \
+     application/vnd.oci.image.manifest.v1+json, \
+     application/vnd.docker.distribution.manifest.v2+json";
+
+/// How long a resolved digest is reused, so creating a stack of apps
+/// doesn't query the registry once per app.
+const CACHE_TTL: Duration = Duration::from_secs(600);
+
+/// Tags asked for per page of a tag listing, and the most pages followed.
+/// Docker Hub caps pages at 100 whatever is asked for.
+const TAGS_PAGE_SIZE: usize = 1000;
+const MAX_TAG_PAGES: usize = 50;
+
+/// Registry host and repository path of `repository`, following the
+/// Docker conventions: no registry means Docker Hub, and a single-segment
+/// Docker Hub name lives under `library/`.
+pub fn split_repository(repository: &str) -> (String, String) {
+    let (registry, path) = match repository.split_once('/') {
+        Some((first, rest))
+            if first.contains('.') || first.contains(':') || first == "localhost" =>
+        {
+            (first.to_string(), rest.to_string())
+        }
+        _ => ("docker.io".to_string(), repository.to_string()),
+    };
+    if registry == "docker.io" {
+        let path = if path.contains('/') {
+            path
+        } else {
+            format!("library/{path}")
+        };
+        ("registry-1.docker.io".to_string(), path)
+    } else {
+        (registry, path)
+    }
+}
+
+/// The `key="value"` parameters of a `WWW-Authenticate: Bearer ...`
+/// challenge.
+fn bearer_challenge(header: &str) -> Option<HashMap<String, String>> {
+    let params = header.strip_prefix("Bearer ")?;
+    Some(
+        params
+            .split(',')
+            .filter_map(|p| {
+                let (key, value) = p.trim().split_once('=')?;
+                Some((key.to_string(), value.trim_matches('"').to_string()))
+            })
+            .collect(),
+    )
+}
+
+/// The `rel="next"` target of a `Link` header, which registries send
+/// while a tag listing has more pages.
+fn next_page(header: &str) -> Option<&str> {
+    header.split(',').find_map(|link| {
+        let (target, params) = link.trim().split_once(';')?;
+        params
+            .contains(r#"rel="next""#)
+            .then(|| target.trim().trim_start_matches('<').trim_end_matches('>'))
+    })
+}
+
+pub struct DigestResolver {
+    http: reqwest::Client,
+    cache: Mutex<HashMap<String, (Instant, String)>>,
+}
+
+impl Default for DigestResolver {
+    fn default() -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .timeout(Duration::from_secs(5))
+                .build()
+                .unwrap_or_default(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl DigestResolver {
+    /// The digest `image`'s tag currently points to.
+    pub async fn resolve(&self, image: &ImageSpec) -> Result<String, String> {
+        let (registry, path) = split_repository(&image.repository);
+        self.resolve_at(&format!("https://{registry}"), &path, &image.tag)
+            .await
+            .map_err(|e| format!("could not resolve {}:{}: {e}", image.repository, image.tag))
+    }
+
+    async fn resolve_at(&self, base: &str, path: &str, tag: &str) -> Result<String, String> {
+        let tag = if tag.is_empty() { "latest" } else { tag };
+        let url = format!("{base}/v2/{path}/manifests/{tag}");
+        if let Some(digest) = self.cached(&url) {
+            return Ok(digest);
+        }
+
+        let mut response = self.head(&url, None).await?;
+        if response.status() == StatusCode::UNAUTHORIZED {
+            let token = self.authorize(&response, path).await?;
+            response = self.head(&url, Some(&token)).await?;
+        }
+        if !response.status().is_success() {
+            return Err(format!("registry returned {}", response.status()));
+        }
+        let digest = response
+            .headers()
+            .get("docker-content-digest")
+            .and_then(|h| h.to_str().ok())
+            .ok_or("registry returned no Docker-Content-Digest")?
+            .to_string();
+        self.cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(url, (Instant::now(), digest.clone()));
+        Ok(digest)
+    }
+
+    /// Every tag in `repository`, in the registry's order.
+    pub async fn tags(&self, repository: &str) -> Result<Vec<String>, String> {
+        let (registry, path) = split_repository(repository);
+        self.tags_at(&format!("https://{registry}"), &path)
+            .await
+            .map_err(|e| format!("could not list tags of {repository}: {e}"))
+    }
+
+    async fn tags_at(&self, base: &str, path: &str) -> Result<Vec<String>, String> {
+        #[derive(serde::Deserialize)]
+        struct TagList {
+            tags: Option<Vec<String>>,
+        }
+
+        let mut tags = Vec::new();
+        let mut url = format!("{base}/v2/{path}/tags/list?n={TAGS_PAGE_SIZE}");
+        let mut token = None;
+        for _ in 0..MAX_TAG_PAGES {
+            let mut response = self.get(&url, token.as_deref()).await?;
+            if response.status() == StatusCode::UNAUTHORIZED && token.is_none() {
+                token = Some(self.authorize(&response, path).await?);
+                response = self.get(&url, token.as_deref()).await?;
+            }
+            if !response.status().is_success() {
+                return Err(format!("registry returned {}", response.status()));
+            }
+            let next = response
+                .headers()
+                .get(LINK)
+                .and_then(|h| h.to_str().ok())
+                .and_then(next_page)
+                .map(|next| {
+                    if next.starts_with("http") {
+                        next.to_string()
+                    } else {
+                        format!("{base}{next}")
+                    }
+                });
+            let page: TagList = response
+                .json()
+                .await
+                .map_err(|e| format!("invalid tag list: {e}"))?;
+            tags.extend(page.tags.unwrap_or_default());
+            match next {
+                Some(next) => url = next,
+                None => break,
+            }
+        }
+        Ok(tags)
+    }
+
+    fn cached(&self, url: &str) -> Option<String> {
+        self.cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(url)
+            .filter(|(at, _)| at.elapsed() < CACHE_TTL)
+            .map(|(_, digest)| digest.clone())
+    }
+
+    async fn head(&self, url: &str, token: Option<&str>) -> Result<reqwest::Response, String> {
+        let mut request = self.http.head(url).header(ACCEPT, MANIFEST_TYPES);
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+        request.send().await.map_err(|e| e.to_string())
+    }
+
+    async fn get(&self, url: &str, token: Option<&str>) -> Result<reqwest::Response, String> {
+        let mut request = self.http.get(url);
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+        request.send().await.map_err(|e| e.to_string())
+    }
+
+    /// A token answering the challenge of an unauthorized `response`.
+    async fn authorize(&self, response: &reqwest::Response, path: &str) -> Result<String, String> {
+        let challenge = response
+            .headers()
+            .get(WWW_AUTHENTICATE)
+            .and_then(|h| h.to_str().ok())
+            .and_then(bearer_challenge)
+            .ok_or("registry requires credentials")?;
+        self.token(&challenge, path).await
+    }
+
+    /// An anonymous pull token from the realm named in `challenge`.
+    async fn token(
+        &self,
+        challenge: &HashMap<String, String>,
+        path: &str,
+    ) -> Result<String, String> {
+        let realm = challenge
+            .get("realm")
+            .ok_or("registry challenge has no realm")?;
+        let scope = challenge
+            .get("scope")
+            .cloned()
+            .unwrap_or_else(|| format!("repository:{path}:pull"));
+        let mut query = vec![("scope", scope)];
+        if let Some(service) = challenge.get("service") {
+            query.push(("service", service.clone()));
+        }
+        let url = url::Url::parse_with_params(realm, &query)
+            .map_err(|e| format!("invalid token realm {realm}: {e}"))?;
+        let body: serde_json::Value = self
+            .http
+            .get(url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("token request failed: {e}"))?
+            .json()
+            .await
+            .map_err(|e| format!("invalid token response: {e}"))?;
+        body.get("token")
+            .or_else(|| body.get("access_token"))
+            .and_then(|t| t.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| "token response has no token".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn repositories_split_into_registry_and_path() {
+        let split = |r| split_repository(r);
+        assert_eq!(
+            split("lscr.io/linuxserver/sonarr"),
+            ("lscr.io".into(), "linuxserver/sonarr".into())
+        );
+        assert_eq!(
+            split("registry:5000/sonarr"),
+            ("registry:5000".into(), "sonarr".into())
+        );
+        assert_eq!(
+            split("linuxserver/sonarr"),
+            ("registry-1.docker.io".into(), "linuxserver/sonarr".into())
+        );
+        assert_eq!(
+            split("nginx"),
+            ("registry-1.docker.io".into(), "library/nginx".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_through_an_anonymous_token() {
+        let server = MockServer::start().await;
+        let digest = "sha256:0123456789abcdef";
+        Mock::given(method("HEAD"))
+            .and(path("/v2/linuxserver/sonarr/manifests/4.0.0"))
+            .and(header("authorization", "Bearer t0k3n"))
+            .respond_with(ResponseTemplate::new(200).insert_header("docker-content-digest", digest))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/v2/linuxserver/sonarr/manifests/4.0.0"))
+            .respond_with(ResponseTemplate::new(401).insert_header(
+                "www-authenticate",
+                format!(
+                    r#"Bearer realm="{}/token",service="registry",scope="repository:linuxserver/sonarr:pull""#,
+                    server.uri()
+                ),
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/token"))
+            .and(query_param("service", "registry"))
+            .and(query_param("scope", "repository:linuxserver/sonarr:pull"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"token": "t0k3n"})),
+            )
+            .mount(&server)
+            .await;
+
+        let resolver = DigestResolver::default();
+        for _ in 0..2 {
+            let resolved = resolver
+                .resolve_at(&server.uri(), "linuxserver/sonarr", "4.0.0")
+                .await
+                .unwrap();
+            assert_eq!(resolved, digest);
+        }
+    }
+
+    #[tokio::test]
+    async fn tag_listing_follows_pages() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/linuxserver/sonarr/tags/list"))
+            .and(query_param("last", "4.0.1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"tags": ["4.0.2", "latest"]})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/linuxserver/sonarr/tags/list"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "link",
+                        r#"</v2/linuxserver/sonarr/tags/list?last=4.0.1&n=2>; rel="next""#,
+                    )
+                    .set_body_json(serde_json::json!({"tags": ["4.0.0", "4.0.1"]})),
+            )
+            .mount(&server)
+            .await;
+
+        let tags = DigestResolver::default()
+            .tags_at(&server.uri(), "linuxserver/sonarr")
+            .await
+            .unwrap();
+        assert_eq!(tags, ["4.0.0", "4.0.1", "4.0.2", "latest"]);
+    }
+
+    #[tokio::test]
+    async fn missing_tag_is_an_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        let err = DigestResolver::default()
+            .resolve_at(&server.uri(), "linuxserver/sonarr", "nope")
+            .await
+            .unwrap_err();
+        assert!(err.contains("404"), "{err}");
+    }
+}
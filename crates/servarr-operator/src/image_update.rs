@@ -0,0 +1,169 @@
+//! Automatic image updates for `updatePolicy.mode: Auto`. The newest tag
+//! of the same form as the running one is picked from the registry's tag
+//! list, and the operator runs it in place of the spec's tag for as long
+//! as it is the newer of the two.
+
+use std::collections::HashMap;
+
+use servarr_crds::{ImageSpec, ImageUpdateStatus, ServarrApp, UpdateMode};
+
+/// A tag's numbers, and its text with each number replaced by `#`. Tags
+/// compare only when the text matches, so `4.0.16.2944-ls292` moves to
+/// `4.0.17.2952-ls293` but never to `4.0.17-develop` or `latest`.
+#[derive(Debug, PartialEq)]
+struct Version {
+    shape: String,
+    numbers: Vec<u64>,
+}
+
+fn version(tag: &str) -> Option<Version> {
+    let mut shape = String::new();
+    let mut numbers = Vec::new();
+    let mut digits = String::new();
+    for c in tag.chars().chain(std::iter::once('\0')) {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        if !digits.is_empty() {
+            numbers.push(digits.parse().ok()?);
+            digits.clear();
+            shape.push('#');
+        }
+        if c != '\0' {
+            shape.push(c);
+        }
+    }
+    (!numbers.is_empty()).then_some(Version { shape, numbers })
+}
+
+/// The newest tag in `tags` of the same form as `current` and newer than
+/// it. Without `allow_major` the first number has to stay the same. Fails
+/// when `current` has no version to compare, as with `latest`.
+pub fn newer_tag(
+    current: &str,
+    tags: &[String],
+    allow_major: bool,
+) -> Result<Option<String>, String> {
+    let current_version =
+        version(current).ok_or_else(|| format!("tag '{current}' has no version to compare"))?;
+    Ok(tags
+        .iter()
+        .filter_map(|tag| Some((version(tag)?, tag)))
+        .filter(|(v, _)| {
+            v.shape == current_version.shape
+                && v.numbers > current_version.numbers
+                && (allow_major || v.numbers[0] == current_version.numbers[0])
+        })
+        .max_by(|(a, _), (b, _)| a.numbers.cmp(&b.numbers))
+        .map(|(_, tag)| tag.clone()))
+}
+
+fn is_newer(tag: &str, than: &str) -> bool {
+    match (version(tag), version(than)) {
+        (Some(tag), Some(than)) => tag.shape == than.shape && tag.numbers > than.numbers,
+        _ => false,
+    }
+}
+
+/// The image `app` runs without an automatic update: its spec's, or the
+/// operator default for its architecture.
+pub fn base_image(
+    app: &ServarrApp,
+    image_overrides: &HashMap<String, ImageSpec>,
+    default_arch: Option<&str>,
+) -> Option<ImageSpec> {
+    app.spec.image.clone().or_else(|| {
+        servarr_resources::deployment::default_image(app, image_overrides, default_arch).ok()
+    })
+}
+
+/// The image to run in place of `app`'s own, while `mode` is `Auto` and
+/// `update` records a tag newer than the one the app asks for.
+pub fn upgraded_image(
+    app: &ServarrApp,
+    update: Option<&ImageUpdateStatus>,
+    image_overrides: &HashMap<String, ImageSpec>,
+    default_arch: Option<&str>,
+) -> Option<ImageSpec> {
+    app.spec
+        .update_policy
+        .as_ref()
+        .filter(|p| p.mode == UpdateMode::Auto)?;
+    let tag = update?.tag.as_deref()?;
+    let mut image = base_image(app, image_overrides, default_arch)?;
+    if !image.digest.is_empty() || !is_newer(tag, &image.tag) {
+        return None;
+    }
+    image.tag = tag.to_string();
+    Some(image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use servarr_crds::{AppType, ServarrAppSpec, UpdatePolicySpec};
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn newer_tag_keeps_the_form_of_the_current_one() {
+        let available = tags(&[
+            "latest",
+            "develop",
+            "4.0.16.2944-ls292",
+            "4.0.17.2952-ls293",
+            "4.0.17.2952-ls294",
+            "4.1.0-develop",
+            "5.0.0.100-ls1",
+            "amd64-4.0.18.3000-ls300",
+        ]);
+        assert_eq!(
+            newer_tag("4.0.16.2944-ls292", &available, false).unwrap(),
+            Some("4.0.17.2952-ls294".into())
+        );
+        assert_eq!(
+            newer_tag("4.0.16.2944-ls292", &available, true).unwrap(),
+            Some("5.0.0.100-ls1".into())
+        );
+        assert_eq!(newer_tag("5.0.0.100-ls1", &available, true).unwrap(), None);
+        assert!(newer_tag("latest", &available, true).is_err());
+    }
+
+    #[test]
+    fn upgraded_tag_applies_while_newer_than_the_spec() {
+        let mut app = ServarrApp::new(
+            "sonarr",
+            ServarrAppSpec {
+                app: AppType::Sonarr,
+                image: Some(ImageSpec {
+                    repository: "linuxserver/sonarr".into(),
+                    tag: "4.0.16".into(),
+                    ..Default::default()
+                }),
+                update_policy: Some(UpdatePolicySpec {
+                    mode: UpdateMode::Auto,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        );
+        let update = ImageUpdateStatus {
+            tag: Some("4.0.17".into()),
+            ..Default::default()
+        };
+        let upgraded = |app: &ServarrApp| {
+            upgraded_image(app, Some(&update), &HashMap::new(), None).map(|i| i.tag)
+        };
+        assert_eq!(upgraded(&app).as_deref(), Some("4.0.17"));
+
+        app.spec.image.as_mut().unwrap().tag = "4.0.18".into();
+        assert_eq!(upgraded(&app), None);
+
+        app.spec.image.as_mut().unwrap().tag = "4.0.16".into();
+        app.spec.update_policy.as_mut().unwrap().mode = UpdateMode::Notify;
+        assert_eq!(upgraded(&app), None);
+    }
+}
@@ -0,0 +1,239 @@
+//! Scheduled `PRAGMA integrity_check` runs against a Servarr app's SQLite
+//! database, configured with `backup.integrityCheck`. A CronJob checks a
+//! consistent copy of the database; the latest finished run is reported as
+//! the `ConfigDatabaseHealthy` condition, so corruption shows up before the
+//! app refuses to start.
+
+use k8s_openapi::api::batch::v1::{CronJob, Job};
+use k8s_openapi::api::core::v1::{ObjectReference, Pod};
+use kube::api::{Api, DeleteParams, ListParams, Patch, PatchParams};
+use kube::runtime::events::{Event, EventType};
+use kube::{Client, ResourceExt};
+use servarr_crds::{Condition, ServarrApp, condition_types};
+use servarr_resources::integrity_check;
+use tracing::warn;
+
+use crate::events::ThrottledRecorder;
+use crate::storage_migration::{JobOutcome, job_outcome};
+
+fn is_not_found(e: &kube::Error) -> bool {
+    matches!(e, kube::Error::Api(e) if e.code == 404)
+}
+
+/// The `ConfigDatabaseHealthy` condition from the app's last status, kept
+/// while a check is running or none has finished yet.
+fn previous(app: &ServarrApp) -> Option<Condition> {
+    app.status.as_ref().and_then(|s| {
+        s.conditions
+            .iter()
+            .find(|c| c.condition_type == condition_types::CONFIG_DATABASE_HEALTHY)
+            .cloned()
+    })
+}
+
+/// The output of a failed check, which its pod reports as the container's
+/// termination message.
+async fn failure_output(client: &Client, ns: &str, job: &str) -> Option<String> {
+    let pods = Api::<Pod>::namespaced(client.clone(), ns)
+        .list(&ListParams::default().labels(&format!("job-name={job}")))
+        .await
+        .ok()?;
+    pods.items
+        .iter()
+        .filter_map(|p| p.status.as_ref()?.container_statuses.as_ref())
+        .flatten()
+        .filter_map(|c| c.state.as_ref()?.terminated.as_ref()?.message.as_deref())
+        .map(str::trim)
+        .find(|m| !m.is_empty())
+        .map(str::to_string)
+}
+
+/// `ConfigDatabaseHealthy` for the finished Job `job`, timestamped with when
+/// it finished. `None` while it is still running.
+pub(crate) fn job_condition(job: &Job, output: Option<&str>, now: &str) -> Option<Condition> {
+    let name = job.name_any();
+    let finished = job.status.as_ref().and_then(|s| {
+        s.completion_time
+            .as_ref()
+            .map(|t| t.0.to_string())
+            .or_else(|| {
+                s.conditions
+                    .as_ref()?
+                    .iter()
+                    .find(|c| c.type_ == "Failed" && c.status == "True")?
+                    .last_transition_time
+                    .as_ref()
+                    .map(|t| t.0.to_string())
+            })
+    });
+    let at = finished.as_deref().unwrap_or(now);
+    match job_outcome(Some(job)) {
+        JobOutcome::Missing | JobOutcome::Running => None,
+        JobOutcome::Succeeded => Some(Condition::ok(
+            condition_types::CONFIG_DATABASE_HEALTHY,
+            "IntegrityCheckPassed",
+            &format!("PRAGMA integrity_check returned ok (Job {name})"),
+            at,
+        )),
+        JobOutcome::Failed(reason) => Some(Condition::fail(
+            condition_types::CONFIG_DATABASE_HEALTHY,
+            "IntegrityCheckFailed",
+            &format!(
+                "Integrity check Job {name} failed: {}",
+                output.unwrap_or(&reason)
+            ),
+            at,
+        )),
+    }
+}
+
+/// Apply or remove the app's integrity check CronJob and report the latest
+/// finished check. Returns `None` when no check is configured or none has
+/// finished yet.
+pub async fn reconcile(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    pp: &PatchParams,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &ObjectReference,
+) -> Option<Condition> {
+    let name = app.name_any();
+    let cronjob_api = Api::<CronJob>::namespaced(client.clone(), ns);
+    let cronjob_name = integrity_check::cronjob_name(app);
+    // Apps on Postgres have no SQLite database to check.
+    let cronjob = app
+        .spec
+        .backup
+        .as_ref()
+        .and_then(|b| b.integrity_check.as_ref())
+        .filter(|_| app.spec.database.is_none())
+        .and_then(|check| integrity_check::build_cronjob(app, check));
+    let app_type = app.spec.app.as_str();
+    let Some(cronjob) = cronjob else {
+        crate::metrics::set_database_healthy(app_type, ns, &name, None);
+        match cronjob_api
+            .delete(&cronjob_name, &DeleteParams::background())
+            .await
+        {
+            Err(e) if !is_not_found(&e) => {
+                warn!(%name, error = %e, "failed to delete integrity check CronJob");
+            }
+            _ => {}
+        }
+        return None;
+    };
+
+    let now = crate::controller::chrono_now();
+    if let Err(e) = cronjob_api
+        .patch(&cronjob_name, pp, &Patch::Apply(&cronjob))
+        .await
+    {
+        warn!(%name, error = %e, "failed to apply integrity check CronJob");
+        return Some(Condition::fail(
+            condition_types::CONFIG_DATABASE_HEALTHY,
+            "IntegrityCheckNotScheduled",
+            &format!("Could not apply CronJob {cronjob_name}: {e}"),
+            &now,
+        ));
+    }
+
+    let jobs = match Api::<Job>::namespaced(client.clone(), ns)
+        .list(&ListParams::default().labels(&integrity_check::job_selector(app)))
+        .await
+    {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            warn!(%name, error = %e, "failed to list integrity check Jobs");
+            return previous(app);
+        }
+    };
+    let Some(latest) = jobs
+        .items
+        .iter()
+        .filter(|j| job_outcome(Some(j)) != JobOutcome::Running)
+        .max_by_key(|j| j.creation_timestamp())
+    else {
+        return previous(app);
+    };
+    let output = match job_outcome(Some(latest)) {
+        JobOutcome::Failed(_) => failure_output(client, ns, &latest.name_any()).await,
+        _ => None,
+    };
+    let condition = job_condition(latest, output.as_deref(), &now)?;
+    crate::metrics::set_database_healthy(app_type, ns, &name, Some(condition.status == "True"));
+
+    let was_ok = previous(app).is_none_or(|c| c.status == "True");
+    if was_ok && condition.status != "True" {
+        warn!(%name, message = %condition.message, "database integrity check failed");
+        if let Err(e) = recorder
+            .publish(
+                &Event {
+                    type_: EventType::Warning,
+                    reason: "ConfigDatabaseUnhealthy".into(),
+                    note: Some(condition.message.clone()),
+                    action: "IntegrityCheck".into(),
+                    secondary: None,
+                },
+                obj_ref,
+            )
+            .await
+        {
+            warn!(%name, error = %e, "failed to publish integrity check event");
+        }
+    }
+    Some(condition)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::batch::v1::{JobCondition, JobStatus};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+
+    fn job(status: JobStatus) -> Job {
+        let mut job = Job {
+            status: Some(status),
+            ..Default::default()
+        };
+        job.metadata.name = Some("sonarr-integrity-check-29000000".into());
+        job
+    }
+
+    #[test]
+    fn job_condition_reports_finished_runs() {
+        let finished: Time = serde_json::from_str("\"2026-10-11T05:00:42Z\"").unwrap();
+        let passed = job(JobStatus {
+            succeeded: Some(1),
+            completion_time: Some(finished.clone()),
+            ..Default::default()
+        });
+        let cond = job_condition(&passed, None, "now").unwrap();
+        assert_eq!(cond.status, "True");
+        assert_eq!(cond.reason, "IntegrityCheckPassed");
+        assert_eq!(cond.last_transition_time, "2026-10-11T05:00:42Z");
+
+        let failed = job(JobStatus {
+            failed: Some(1),
+            conditions: Some(vec![JobCondition {
+                type_: "Failed".into(),
+                status: "True".into(),
+                reason: Some("BackoffLimitExceeded".into()),
+                message: Some("Job has reached the specified backoff limit".into()),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        });
+        let output = "*** in database main ***\nPage 42: btreeInitPage() returns error code 11";
+        let cond = job_condition(&failed, Some(output), "now").unwrap();
+        assert_eq!(cond.status, "False");
+        assert_eq!(cond.reason, "IntegrityCheckFailed");
+        assert!(cond.message.ends_with(output));
+        assert_eq!(cond.last_transition_time, "now");
+
+        let cond = job_condition(&failed, None, "now").unwrap();
+        assert!(cond.message.ends_with("backoff limit"));
+
+        assert!(job_condition(&job(JobStatus::default()), None, "now").is_none());
+    }
+}
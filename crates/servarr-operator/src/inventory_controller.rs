@@ -0,0 +1,449 @@
+//! ServarrInventory controller: every `intervalMinutes` it lists the
+//! managed apps, reads the version each one runs from its workload's
+//! image tag, and compares it with the latest upstream release.
+//!
+//! Upstream versions come from the inventory's JSON feed when it lists the
+//! app type, otherwise from the latest GitHub release of the app's
+//! repository. Unauthenticated GitHub requests are limited to 60 an hour,
+//! so each collection asks once per app type, and a collection is skipped
+//! when the previous one is younger than the interval.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use anyhow::Result;
+use futures::StreamExt;
+use kube::api::{Api, ListParams, Patch, PatchParams};
+use kube::runtime::controller::{Action, Controller};
+use kube::runtime::watcher;
+use kube::{Client, CustomResourceExt, ResourceExt};
+use servarr_api::ReleaseClient;
+use servarr_crds::{
+    AppType, AppVersion, Condition, ServarrApp, ServarrInventory, ServarrInventoryStatus,
+    condition_types,
+};
+use thiserror::Error;
+use tokio::time::Duration;
+use tracing::{error, info, warn};
+
+use crate::context::Context;
+
+const FIELD_MANAGER: &str = "servarr-operator-inventory";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Kubernetes API error: {0}")]
+    Kube(#[source] kube::Error),
+}
+
+pub fn print_crd() -> Result<()> {
+    let crd = ServarrInventory::crd();
+    let yaml = serde_yaml::to_string(&crd)?;
+    println!("{yaml}");
+    Ok(())
+}
+
+pub async fn run(client: kube::Client, server_state: crate::server::ServerState) -> Result<()> {
+    let ctx = Arc::new(Context::new(client.clone()));
+
+    let inventories = match ctx.watch_namespace {
+        Some(ref ns) => Api::<ServarrInventory>::namespaced(client.clone(), ns),
+        None => Api::<ServarrInventory>::all(client.clone()),
+    };
+
+    info!("Starting inventory controller");
+    server_state.set_ready();
+
+    let controller = Controller::new(inventories, watcher::Config::default());
+    tokio::spawn(crate::metrics::sample_cache(
+        "servarrinventory",
+        controller.store(),
+        |inv| {
+            crate::metrics::generation_pending(
+                inv.metadata.generation,
+                inv.status.as_ref().map(|s| s.observed_generation),
+            )
+        },
+    ));
+    controller
+        .shutdown_on_signal()
+        .run(reconcile, error_policy, ctx)
+        .for_each(|res| async move {
+            match res {
+                Ok(o) => info!(?o, "inventory reconciled"),
+                Err(e) => error!(%e, "inventory reconcile error"),
+            }
+        })
+        .await;
+
+    Ok(())
+}
+
+/// Built-in GitHub repositories of the app types that publish releases
+/// there. Plex and the SSH bastion have none; list them in a feed instead.
+pub fn default_repo(app: &AppType) -> Option<&'static str> {
+    Some(match app {
+        AppType::Sonarr => "Sonarr/Sonarr",
+        AppType::Radarr => "Radarr/Radarr",
+        AppType::Lidarr => "Lidarr/Lidarr",
+        AppType::Readarr => "Readarr/Readarr",
+        AppType::Whisparr => "Whisparr/Whisparr",
+        AppType::Prowlarr => "Prowlarr/Prowlarr",
+        AppType::Sabnzbd => "sabnzbd/sabnzbd",
+        AppType::Transmission => "transmission/transmission",
+        AppType::Qbittorrent => "qbittorrent/qBittorrent",
+        AppType::Tautulli => "Tautulli/Tautulli",
+        AppType::Overseerr => "sct/overseerr",
+        AppType::Jellyseerr => "fallenbagel/jellyseerr",
+        AppType::Maintainerr => "jorenn92/Maintainerr",
+        AppType::Jackett => "Jackett/Jackett",
+        AppType::Bazarr => "morpheus65535/bazarr",
+        AppType::Jellyfin => "jellyfin/jellyfin",
+        _ => return None,
+    })
+}
+
+/// The tag of an image reference, `latest` when it has none.
+pub fn image_tag(image: &str) -> &str {
+    let image = image.split('@').next().unwrap_or(image);
+    match image.rsplit_once(':') {
+        Some((_, tag)) if !tag.contains('/') => tag,
+        _ => "latest",
+    }
+}
+
+/// The dotted version at the start of an image or release tag, ignoring a
+/// `v`, `version-`, or `release-` prefix and build suffixes:
+/// `4.0.16.2944-ls292` and `v4.0.16.2944` both give `4.0.16.2944`. `None`
+/// for tags like `latest`.
+pub fn parse_version(tag: &str) -> Option<String> {
+    let tag = tag
+        .strip_prefix("version-")
+        .or_else(|| tag.strip_prefix("release-"))
+        .or_else(|| tag.strip_prefix('v'))
+        .unwrap_or(tag);
+    let version: String = tag
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    let version = version.trim_end_matches('.');
+    (version.contains('.') && version.split('.').all(|p| !p.is_empty()))
+        .then(|| version.to_string())
+}
+
+/// Whether `installed` is older than `latest`. Only the components both
+/// versions have are compared, so a tag pinned to `4.0.16` is current for
+/// every `4.0.16.x` build.
+pub fn is_outdated(installed: &str, latest: &str) -> bool {
+    let parts = |v: &str| -> Vec<u64> { v.split('.').map(|p| p.parse().unwrap_or(0)).collect() };
+    let (installed, latest) = (parts(installed), parts(latest));
+    installed
+        .iter()
+        .zip(&latest)
+        .find(|(i, l)| i != l)
+        .is_some_and(|(i, l)| i < l)
+}
+
+/// An app's report row, given the image it runs and the latest version.
+pub fn app_version(app: &ServarrApp, image: String, latest: Option<String>) -> AppVersion {
+    let installed_version = parse_version(image_tag(&image));
+    let outdated = installed_version
+        .as_deref()
+        .zip(latest.as_deref())
+        .map(|(i, l)| is_outdated(i, l));
+    AppVersion {
+        namespace: app.namespace().unwrap_or_default(),
+        name: app.name_any(),
+        app: app.spec.app.clone(),
+        image,
+        installed_version,
+        latest_version: latest,
+        outdated,
+    }
+}
+
+pub async fn reconcile(inv: Arc<ServarrInventory>, ctx: Arc<Context>) -> Result<Action, Error> {
+    let _in_flight = crate::metrics::InFlight::start("servarrinventory");
+    let client = &ctx.client;
+    let ns = inv.namespace().unwrap_or_default();
+    let name = inv.name_any();
+    let interval = Duration::from_secs(u64::from(inv.spec.interval_minutes.max(1)) * 60);
+
+    let metrics_key = format!("{ns}/{name}");
+
+    // Status patches wake the controller too; only collect when the spec
+    // changed or the previous report is due. The stored report is still
+    // exported, so a restarted operator has the metric right away.
+    let generation = inv.metadata.generation.unwrap_or(0);
+    if let Some(status) = inv.status.as_ref()
+        && status.observed_generation == generation
+        && let Some(age) = status.last_collected.as_deref().and_then(age_of)
+        && age < interval
+    {
+        crate::metrics::set_app_versions(&metrics_key, &status.apps);
+        return Ok(Action::requeue(interval - age));
+    }
+
+    let apps = list_apps(client, &inv, ctx.watch_namespace.as_deref()).await?;
+    let mut problems = Vec::new();
+    let latest = latest_versions(&inv, &apps, &mut problems).await;
+
+    let mut rows = Vec::with_capacity(apps.len());
+    for app in &apps {
+        let image = running_image(client, app, &ctx).await;
+        let latest = latest.get(app.spec.app.as_str()).cloned().flatten();
+        rows.push(app_version(app, image, latest));
+    }
+    rows.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+    crate::metrics::set_app_versions(&metrics_key, &rows);
+
+    let now = chrono_now();
+    let mut status = inv.status.clone().unwrap_or_default();
+    status.total = rows.len() as i32;
+    status.outdated = rows.iter().filter(|r| r.outdated == Some(true)).count() as i32;
+    status.unknown = rows.iter().filter(|r| r.outdated.is_none()).count() as i32;
+    status.apps = rows;
+    status.last_collected = Some(now.clone());
+    status.observed_generation = generation;
+    status.set_condition(if problems.is_empty() {
+        Condition::ok(
+            condition_types::READY,
+            "Collected",
+            &format!("{} app(s), {} outdated", status.total, status.outdated),
+            &now,
+        )
+    } else {
+        warn!(%name, ?problems, "inventory incomplete");
+        Condition::fail(
+            condition_types::READY,
+            "VersionLookupFailed",
+            &problems.join("; "),
+            &now,
+        )
+    });
+
+    patch_status(client, &ns, &name, &status).await?;
+    Ok(Action::requeue(interval))
+}
+
+/// The ServarrApps the inventory covers.
+async fn list_apps(
+    client: &Client,
+    inv: &ServarrInventory,
+    watch_namespace: Option<&str>,
+) -> Result<Vec<ServarrApp>, Error> {
+    let api = match watch_namespace {
+        Some(ns) => Api::<ServarrApp>::namespaced(client.clone(), ns),
+        None => Api::<ServarrApp>::all(client.clone()),
+    };
+    let apps = api
+        .list(&ListParams::default())
+        .await
+        .map_err(Error::Kube)?;
+    Ok(apps
+        .items
+        .into_iter()
+        .filter(|a| {
+            inv.spec
+                .includes_namespace(&a.namespace().unwrap_or_default())
+        })
+        .collect())
+}
+
+/// Latest version per app type present in `apps`, keyed by the type's
+/// name. Lookups that fail are recorded in `problems` and map to `None`.
+async fn latest_versions(
+    inv: &ServarrInventory,
+    apps: &[ServarrApp],
+    problems: &mut Vec<String>,
+) -> HashMap<&'static str, Option<String>> {
+    let feed = &inv.spec.feed;
+    let github_api = feed
+        .github_api_url
+        .as_deref()
+        .unwrap_or(servarr_api::releases::GITHUB_API_URL);
+    let releases = match ReleaseClient::new(github_api) {
+        Ok(c) => c,
+        Err(e) => {
+            problems.push(format!("GitHub API URL {github_api}: {e}"));
+            return HashMap::new();
+        }
+    };
+    let listed: BTreeMap<String, String> = match feed.url {
+        Some(ref url) => releases.version_feed(url).await.unwrap_or_else(|e| {
+            problems.push(format!("feed {url}: {e}"));
+            BTreeMap::new()
+        }),
+        None => BTreeMap::new(),
+    };
+
+    let mut latest = HashMap::new();
+    for app in apps {
+        let app_type = app.spec.app.as_str();
+        if latest.contains_key(app_type) {
+            continue;
+        }
+        let version = if let Some(v) = listed.get(app_type) {
+            parse_version(v)
+        } else if let Some(repo) = feed
+            .github_repos
+            .get(app_type)
+            .map(String::as_str)
+            .or_else(|| default_repo(&app.spec.app))
+        {
+            match releases.latest_github_release(repo).await {
+                Ok(tag) => parse_version(&tag),
+                Err(e) => {
+                    problems.push(format!("{app_type} ({repo}): {e}"));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        latest.insert(app_type, version);
+    }
+    latest
+}
+
+/// Image of the app's main container as deployed, or the image the
+/// operator would deploy when its workload can't be read.
+async fn running_image(client: &Client, app: &ServarrApp, ctx: &Context) -> String {
+    let ns = app.namespace().unwrap_or_default();
+    let deployed = crate::workload::pod_template(client, app, &ns)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|t| t.spec)
+        .and_then(|s| s.containers.into_iter().next())
+        .and_then(|c| c.image);
+    deployed.unwrap_or_else(|| {
+        let image = app.spec.image.clone().unwrap_or_else(|| {
+            servarr_resources::deployment::default_image(
+                app,
+                &ctx.image_overrides,
+                ctx.default_arch.as_deref(),
+            )
+            .unwrap_or_else(|_| servarr_crds::AppDefaults::for_app(&app.spec.app).image)
+        });
+        format!("{}:{}", image.repository, image.tag)
+    })
+}
+
+async fn patch_status(
+    client: &Client,
+    ns: &str,
+    name: &str,
+    status: &ServarrInventoryStatus,
+) -> Result<(), Error> {
+    let api = Api::<ServarrInventory>::namespaced(client.clone(), ns);
+    let status_patch = serde_json::json!({
+        "apiVersion": "servarr.dev/v1alpha1",
+        "kind": "ServarrInventory",
+        "status": status,
+    });
+    api.patch_status(
+        name,
+        &PatchParams::apply(FIELD_MANAGER).force(),
+        &Patch::Apply(status_patch),
+    )
+    .await
+    .map_err(Error::Kube)?;
+    Ok(())
+}
+
+pub fn error_policy(_inv: Arc<ServarrInventory>, error: &Error, _ctx: Arc<Context>) -> Action {
+    warn!(%error, "inventory collection failed, requeuing");
+    Action::requeue(Duration::from_secs(300))
+}
+
+/// Time since the RFC 3339 timestamp `ts`.
+fn age_of(ts: &str) -> Option<Duration> {
+    let at = chrono::DateTime::parse_from_rfc3339(ts).ok()?;
+    (chrono::Utc::now() - at.with_timezone(&chrono::Utc))
+        .to_std()
+        .ok()
+}
+
+fn chrono_now() -> String {
+    use chrono::{SecondsFormat, Utc};
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use servarr_crds::ServarrAppSpec;
+
+    #[test]
+    fn print_crd_returns_ok() {
+        assert!(print_crd().is_ok());
+    }
+
+    #[test]
+    fn parses_image_and_release_tags() {
+        assert_eq!(image_tag("linuxserver/sonarr:4.0.16"), "4.0.16");
+        assert_eq!(image_tag("registry:5000/sonarr"), "latest");
+        assert_eq!(image_tag("ghcr.io/a/b:1.2@sha256:abc"), "1.2");
+
+        assert_eq!(
+            parse_version("4.0.16.2944-ls292").as_deref(),
+            Some("4.0.16.2944")
+        );
+        assert_eq!(
+            parse_version("v4.0.16.2944").as_deref(),
+            Some("4.0.16.2944")
+        );
+        assert_eq!(parse_version("version-2.9.6").as_deref(), Some("2.9.6"));
+        assert_eq!(parse_version("release-5.1.2").as_deref(), Some("5.1.2"));
+        assert_eq!(parse_version("latest"), None);
+        assert_eq!(parse_version("develop"), None);
+        assert_eq!(parse_version("4"), None);
+    }
+
+    #[test]
+    fn compares_shared_components() {
+        assert!(is_outdated("4.0.15", "4.0.16.2944"));
+        assert!(is_outdated("4.9.0", "4.10.0"));
+        assert!(!is_outdated("4.0.16", "4.0.16.2944"));
+        assert!(!is_outdated("4.0.17", "4.0.16.2944"));
+        assert!(!is_outdated("6.0.4", "6.0.4"));
+    }
+
+    #[test]
+    fn app_version_rows() {
+        let mut app = ServarrApp::new(
+            "sonarr",
+            ServarrAppSpec {
+                app: AppType::Sonarr,
+                ..Default::default()
+            },
+        );
+        app.metadata.namespace = Some("media".into());
+
+        let row = app_version(
+            &app,
+            "linuxserver/sonarr:4.0.15".into(),
+            Some("4.0.16.2944".into()),
+        );
+        assert_eq!(row.installed_version.as_deref(), Some("4.0.15"));
+        assert_eq!(row.outdated, Some(true));
+        assert_eq!(
+            (row.namespace.as_str(), row.name.as_str()),
+            ("media", "sonarr")
+        );
+
+        let row = app_version(
+            &app,
+            "linuxserver/sonarr:latest".into(),
+            Some("4.0.16".into()),
+        );
+        assert_eq!(row.outdated, None);
+    }
+
+    #[test]
+    fn plex_has_no_default_repo() {
+        assert_eq!(default_repo(&AppType::Plex), None);
+        assert_eq!(default_repo(&AppType::Sonarr), Some("Sonarr/Sonarr"));
+    }
+}
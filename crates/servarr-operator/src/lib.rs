@@ -1,7 +1,54 @@
+pub mod api_client;
+pub mod api_key_rotation;
+pub mod app_set_controller;
+pub mod backup_download;
+pub mod backup_upload;
+pub mod bazarr_sync;
+pub mod child_status;
 pub mod context;
 pub mod controller;
+pub mod credential_controller;
+pub mod custom_formats;
+pub mod debug;
+pub mod download_clients;
+pub mod dry_run;
+pub mod events;
+pub mod explain;
+pub mod gpu;
+pub mod helm_values;
+pub mod image_digest;
+pub mod image_update;
+pub mod integrity_check;
+pub mod inventory_controller;
+pub mod maintenance;
+pub mod media_management;
+pub mod media_server_users;
 pub mod media_stack_controller;
 pub mod metrics;
+pub mod monitoring;
+pub mod mutate;
+pub mod network_policy_enforcement;
+pub mod nfs_backup;
+pub mod policy;
+pub mod port_forward;
+pub mod prowlarr_indexers;
+pub mod prowlarr_sync;
+pub mod quality_profiles;
+pub mod queue_remediation;
+pub mod render;
+pub mod requeue;
+pub mod resource_groups;
+pub mod restore_object;
+pub mod root_folders;
+pub mod schedule;
 pub mod server;
+pub mod setup_notes;
+pub mod storage_migration;
+pub mod support_bundle;
+pub mod tags;
 pub mod telemetry;
+pub mod trash_sync;
+pub mod ui_settings;
+pub mod volume_snapshot;
 pub mod webhook;
+pub mod workload;
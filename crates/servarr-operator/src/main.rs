@@ -1,7 +1,12 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use servarr_operator::{controller, media_stack_controller, server, telemetry, webhook};
-use tracing::{error, info};
+use servarr_crds::AppType;
+use servarr_operator::{
+    api_client, app_set_controller, backup_download, context, controller, credential_controller,
+    debug, explain, helm_values, inventory_controller, maintenance, media_stack_controller,
+    metrics, policy, port_forward, render, requeue, server, support_bundle, telemetry, webhook,
+};
+use tracing::{error, info, warn};
 
 const METRICS_PORT: u16 = 8080;
 
@@ -21,6 +26,37 @@ struct Cli {
     #[arg(long, value_name = "NAME")]
     context: Option<String>,
 
+    /// Report what each ServarrApp, MediaStack and ServarrAppSet reconcile
+    /// would change in status.wouldChange without writing anything else.
+    /// Also enabled by DRY_RUN=true.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Seconds between reconciles of a Ready object. Overrides
+    /// REQUEUE_SETTLED_SECONDS; a ServarrApp's spec.requeue overrides both.
+    #[arg(long, value_name = "SECONDS")]
+    requeue_settled_seconds: Option<u64>,
+
+    /// Seconds between reconciles of a ServarrApp that is still settling.
+    /// Overrides REQUEUE_UNSETTLED_SECONDS.
+    #[arg(long, value_name = "SECONDS")]
+    requeue_unsettled_seconds: Option<u64>,
+
+    /// Delay before retrying an object's first failed reconcile, doubled on
+    /// each further failure. Overrides ERROR_BACKOFF_BASE_SECONDS.
+    #[arg(long, value_name = "SECONDS")]
+    error_backoff_base_seconds: Option<u64>,
+
+    /// Longest delay before retrying a failed reconcile. Overrides
+    /// ERROR_BACKOFF_MAX_SECONDS.
+    #[arg(long, value_name = "SECONDS")]
+    error_backoff_max_seconds: Option<u64>,
+
+    /// Seconds between ServarrApp reconciles that apply every resource
+    /// group, changed or not. Overrides FULL_RESYNC_SECONDS.
+    #[arg(long, value_name = "SECONDS")]
+    full_resync_seconds: Option<u64>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -29,28 +65,143 @@ struct Cli {
 enum Commands {
     /// Print the ServarrApp CRD YAML to stdout.
     Crd,
+    /// Convert k8s-at-home or bjw-s app-template Helm values into a
+    /// ServarrApp manifest on stdout. Unconvertible settings are listed on
+    /// stderr.
+    ConvertHelmValues {
+        /// Path to the chart's values.yaml.
+        values: std::path::PathBuf,
+        /// App type the chart deployed (e.g. sonarr, radarr).
+        #[arg(long)]
+        app: AppType,
+        /// Name for the ServarrApp. Defaults to the app type.
+        #[arg(long)]
+        name: Option<String>,
+        /// Namespace to set on the ServarrApp.
+        #[arg(long)]
+        namespace: Option<String>,
+    },
+    /// Forward a local port to a ServarrApp's web UI.
+    PortForward {
+        /// Name of the ServarrApp.
+        app: String,
+        /// Namespace of the ServarrApp. Defaults to the kubeconfig namespace.
+        #[arg(short, long)]
+        namespace: Option<String>,
+        /// Local port to listen on. Defaults to the app's Service port.
+        #[arg(long)]
+        port: Option<u16>,
+        /// Print a URL carrying the app's API key (where the app accepts
+        /// one) and open it in a browser.
+        #[arg(long)]
+        open: bool,
+    },
+    /// Print a ServarrApp's effective spec, noting for each field whether it
+    /// comes from the app, its MediaStack, or operator defaults.
+    Explain {
+        /// Name of the ServarrApp.
+        app: String,
+        /// Namespace of the ServarrApp. Defaults to the kubeconfig namespace.
+        #[arg(short, long)]
+        namespace: Option<String>,
+    },
+    /// Print the child manifests the operator would create for a ServarrApp
+    /// manifest, without contacting a cluster. Operator settings such as
+    /// DEFAULT_IMAGE_* and DEFAULT_NODE_ARCH are read from the environment.
+    Render {
+        /// Path to the ServarrApp manifest, or - for stdin.
+        manifest: std::path::PathBuf,
+    },
+    /// Start a toolbox pod with a ServarrApp's config volume mounted
+    /// read-only and sqlite3, curl, and jq installed. It is deleted after
+    /// the TTL or on Ctrl-C.
+    Debug {
+        /// Name of the ServarrApp.
+        app: String,
+        /// Namespace of the ServarrApp. Defaults to the kubeconfig namespace.
+        #[arg(short, long)]
+        namespace: Option<String>,
+        /// Minutes before the pod is deleted.
+        #[arg(long, value_name = "MINUTES", default_value_t = 60)]
+        ttl_minutes: u64,
+        /// Toolbox image. The tools are only installed on images with apk.
+        #[arg(long, default_value = debug::DEFAULT_IMAGE)]
+        image: String,
+    },
+    /// Collect the namespace's custom resources, child objects, drift,
+    /// events, operator logs, and app health checks into a .tar.gz for a
+    /// bug report. Secret values are redacted.
+    SupportBundle {
+        /// Namespace to collect. Defaults to the kubeconfig namespace.
+        #[arg(short, long)]
+        namespace: Option<String>,
+        /// Namespace the operator runs in, for its logs.
+        #[arg(long, default_value = "servarr")]
+        operator_namespace: String,
+        /// Minutes of operator logs to include.
+        #[arg(long, value_name = "MINUTES", default_value_t = 60)]
+        since_minutes: u64,
+        /// Skip port-forwarding to each app for its health checks.
+        #[arg(long)]
+        no_health: bool,
+        /// Path of the tarball. Defaults to
+        /// support-bundle-<namespace>-<time>.tar.gz in the working directory.
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Work with a ServarrApp's API-managed backups.
+    Backup {
+        #[command(subcommand)]
+        command: BackupCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum BackupCommands {
+    /// Download a backup archive through a temporary port-forward. Without
+    /// --latest or --id, lists the available backups instead.
+    Download {
+        /// Name of the ServarrApp.
+        app: String,
+        /// Namespace of the ServarrApp. Defaults to the kubeconfig namespace.
+        #[arg(short, long)]
+        namespace: Option<String>,
+        /// Download the most recent backup.
+        #[arg(long, conflicts_with = "id")]
+        latest: bool,
+        /// Download the backup with this id.
+        #[arg(long)]
+        id: Option<i64>,
+        /// Directory to write the archive to.
+        #[arg(short, long, default_value = ".")]
+        output: std::path::PathBuf,
+    },
 }
 
 async fn build_client(
     kubeconfig: Option<std::path::PathBuf>,
     context: Option<String>,
 ) -> anyhow::Result<kube::Client> {
-    if kubeconfig.is_none() && context.is_none() {
-        return Ok(kube::Client::try_default().await?);
-    }
-    let options = kube::config::KubeConfigOptions {
-        context,
-        cluster: None,
-        user: None,
-    };
-    let config = match kubeconfig {
-        Some(path) => {
-            let kb = kube::config::Kubeconfig::read_from(path)?;
-            kube::Config::from_custom_kubeconfig(kb, &options).await?
+    let config = if kubeconfig.is_none() && context.is_none() {
+        kube::Config::infer().await?
+    } else {
+        let options = kube::config::KubeConfigOptions {
+            context,
+            cluster: None,
+            user: None,
+        };
+        match kubeconfig {
+            Some(path) => {
+                let kb = kube::config::Kubeconfig::read_from(path)?;
+                kube::Config::from_custom_kubeconfig(kb, &options).await?
+            }
+            None => kube::Config::from_kubeconfig(&options).await?,
         }
-        None => kube::Config::from_kubeconfig(&options).await?,
     };
-    Ok(kube::Client::try_from(config)?)
+    Ok(api_client::build(
+        config,
+        &api_client::ApiClientSettings::from_env(),
+    )?)
 }
 
 #[tokio::main]
@@ -63,14 +214,146 @@ async fn main() -> Result<()> {
         Some(Commands::Crd) => {
             controller::print_crd()?;
             media_stack_controller::print_crd()?;
+            maintenance::print_crd()?;
+            app_set_controller::print_crd()?;
+            credential_controller::print_crd()?;
+            policy::print_crd()?;
+            inventory_controller::print_crd()?;
+            return Ok(());
+        }
+        Some(Commands::ConvertHelmValues {
+            values,
+            app,
+            name,
+            namespace,
+        }) => {
+            let values: serde_json::Value = serde_yaml::from_reader(std::fs::File::open(&values)?)?;
+            let name = name.unwrap_or_else(|| app.as_str().to_string());
+            let conversion = helm_values::convert(&values, app, &name, namespace.as_deref());
+            for warning in &conversion.warnings {
+                eprintln!("warning: {warning}");
+            }
+            print!("{}", helm_values::to_yaml(&conversion.app)?);
+            return Ok(());
+        }
+        Some(Commands::PortForward {
+            app,
+            namespace,
+            port,
+            open,
+        }) => {
+            let client = build_client(cli.kubeconfig, cli.context).await?;
+            let ns = namespace.unwrap_or_else(|| client.default_namespace().to_string());
+            return port_forward::run(client, &ns, &app, port, open).await;
+        }
+        Some(Commands::Explain { app, namespace }) => {
+            let client = build_client(cli.kubeconfig, cli.context).await?;
+            let ns = namespace.unwrap_or_else(|| client.default_namespace().to_string());
+            return explain::run(client, &ns, &app).await;
+        }
+        Some(Commands::Debug {
+            app,
+            namespace,
+            ttl_minutes,
+            image,
+        }) => {
+            let client = build_client(cli.kubeconfig, cli.context).await?;
+            let ns = namespace.unwrap_or_else(|| client.default_namespace().to_string());
+            let ttl = std::time::Duration::from_secs(ttl_minutes.max(1) * 60);
+            return debug::run(client, &ns, &app, &image, ttl).await;
+        }
+        Some(Commands::SupportBundle {
+            namespace,
+            operator_namespace,
+            since_minutes,
+            no_health,
+            output,
+        }) => {
+            let client = build_client(cli.kubeconfig, cli.context).await?;
+            let opts = support_bundle::Options {
+                namespace: namespace.unwrap_or_else(|| client.default_namespace().to_string()),
+                operator_namespace,
+                log_window: std::time::Duration::from_secs(since_minutes.max(1) * 60),
+                health: !no_health,
+            };
+            return support_bundle::run(client, &opts, output.as_deref()).await;
+        }
+        Some(Commands::Render { manifest }) => {
+            let manifest = if manifest.as_os_str() == "-" {
+                std::io::read_to_string(std::io::stdin())?
+            } else {
+                std::fs::read_to_string(&manifest)?
+            };
+            let app = render::parse(&manifest)?;
+            let objects = render::render(&app, &render::Options::from_env())?;
+            print!("{}", render::to_yaml(&objects)?);
             return Ok(());
         }
+        Some(Commands::Backup {
+            command:
+                BackupCommands::Download {
+                    app,
+                    namespace,
+                    latest,
+                    id,
+                    output,
+                },
+        }) => {
+            let client = build_client(cli.kubeconfig, cli.context).await?;
+            let ns = namespace.unwrap_or_else(|| client.default_namespace().to_string());
+            let selection = match (latest, id) {
+                (true, _) => Some(backup_download::Selection::Latest),
+                (false, Some(id)) => Some(backup_download::Selection::Id(id)),
+                (false, None) => None,
+            };
+            return backup_download::run(client, &ns, &app, selection, &output).await;
+        }
         None => {}
     }
 
+    let dry_run = cli.dry_run || context::dry_run();
+    metrics::configure(metrics::MetricsSettings::from_env());
+    let mut requeue_settings = requeue::RequeueSettings::from_env();
+    for (arg, setting) in [
+        (cli.requeue_settled_seconds, &mut requeue_settings.settled),
+        (
+            cli.requeue_unsettled_seconds,
+            &mut requeue_settings.unsettled,
+        ),
+        (
+            cli.error_backoff_base_seconds,
+            &mut requeue_settings.error_backoff_base,
+        ),
+        (
+            cli.error_backoff_max_seconds,
+            &mut requeue_settings.error_backoff_max,
+        ),
+        (cli.full_resync_seconds, &mut requeue_settings.full_resync),
+    ] {
+        if let Some(secs) = arg {
+            *setting = std::time::Duration::from_secs(secs.max(1));
+        }
+    }
+    requeue::configure(requeue_settings);
     let client = build_client(cli.kubeconfig, cli.context).await?;
 
-    let state = server::ServerState::new();
+    let mut state = server::ServerState::new();
+    if let Some(token) = std::env::var("RENDER_TOKEN").ok().filter(|t| !t.is_empty()) {
+        info!("render endpoint enabled");
+        state = state.with_render_token(&token, render::Options::from_env());
+    }
+    metrics::set_build_info();
+    info!(
+        version = metrics::VERSION,
+        commit = metrics::COMMIT,
+        "starting servarr-operator"
+    );
+    if dry_run {
+        warn!(
+            "dry-run mode: ServarrApp, MediaStack and ServarrAppSet changes are reported in \
+             status.wouldChange and not applied; finalizers are released on deletion without cleanup"
+        );
+    }
 
     // Optionally start the webhook server if WEBHOOK_ENABLED=true
     let webhook_enabled = std::env::var("WEBHOOK_ENABLED")
@@ -88,18 +371,30 @@ async fn main() -> Result<()> {
         });
     }
 
-    // Run the metrics/health server and both controllers concurrently.
+    // Run the metrics/health server and the controllers concurrently.
     // If any exits, shut down.
     let state2 = state.clone();
+    let state3 = state.clone();
+    let state4 = state.clone();
+    let state5 = state.clone();
     tokio::select! {
         res = server::run(METRICS_PORT, state.clone()) => {
             error!("metrics server exited: {res:?}");
             res
         }
-        res = controller::run(client.clone(), state) => {
+        res = controller::run(client.clone(), state, dry_run) => {
+            res
+        }
+        res = media_stack_controller::run(client.clone(), state2, dry_run) => {
+            res
+        }
+        res = app_set_controller::run(client.clone(), state3, dry_run) => {
+            res
+        }
+        res = credential_controller::run(client.clone(), state4) => {
             res
         }
-        res = media_stack_controller::run(client, state2) => {
+        res = inventory_controller::run(client, state5) => {
             res
         }
     }
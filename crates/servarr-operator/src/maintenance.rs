@@ -0,0 +1,367 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+use kube::api::{Api, ListParams};
+use kube::{Client, CustomResourceExt, ResourceExt};
+use servarr_crds::{MaintenanceMode, MaintenanceWindow, PendingAction, ServarrApp};
+use tracing::warn;
+
+pub fn print_crd() -> anyhow::Result<()> {
+    let crd = MaintenanceWindow::crd();
+    let yaml = serde_yaml::to_string(&crd)?;
+    println!("{yaml}");
+    Ok(())
+}
+
+/// A maintenance window currently covering an app.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveWindow {
+    pub name: String,
+    pub mode: MaintenanceMode,
+    pub end: DateTime<Utc>,
+}
+
+/// What the maintenance windows in an app's namespace mean for it right now.
+#[derive(Debug, Default, PartialEq)]
+pub struct MaintenancePlan {
+    /// The window in effect, if any. `Suspend` wins over `Freeze` when
+    /// windows overlap.
+    pub active: Option<ActiveWindow>,
+    /// Set while inside a window's backup lead time: a backup older than
+    /// this instant must be refreshed before the window starts.
+    pub backup_cutoff: Option<DateTime<Utc>>,
+    /// The next start, end, or backup-lead boundary, so the controller can
+    /// requeue in time to act on it.
+    pub next_transition: Option<DateTime<Utc>>,
+}
+
+impl MaintenancePlan {
+    pub fn suspended(&self) -> bool {
+        self.active
+            .as_ref()
+            .is_some_and(|w| w.mode == MaintenanceMode::Suspend)
+    }
+
+    pub fn frozen(&self) -> bool {
+        self.active
+            .as_ref()
+            .is_some_and(|w| w.mode == MaintenanceMode::Freeze)
+    }
+}
+
+fn parse_time(window: &MaintenanceWindow, field: &str, value: &str) -> Option<DateTime<Utc>> {
+    match DateTime::parse_from_rfc3339(value) {
+        Ok(t) => Some(t.with_timezone(&Utc)),
+        Err(e) => {
+            warn!(window = %window.name_any(), field, value, error = %e, "invalid maintenance window timestamp, ignoring window");
+            None
+        }
+    }
+}
+
+/// Evaluate `windows` for an app carrying `labels` at time `now`.
+pub fn plan(
+    windows: &[MaintenanceWindow],
+    labels: &BTreeMap<String, String>,
+    now: DateTime<Utc>,
+) -> MaintenancePlan {
+    let mut plan = MaintenancePlan::default();
+
+    for window in windows.iter().filter(|w| w.spec.matches(labels)) {
+        let (Some(start), Some(end)) = (
+            parse_time(window, "start", &window.spec.start),
+            parse_time(window, "end", &window.spec.end),
+        ) else {
+            continue;
+        };
+        if end <= start {
+            warn!(window = %window.name_any(), "maintenance window ends before it starts, ignoring");
+            continue;
+        }
+        let backup_from = start - Duration::minutes(i64::from(window.spec.backup_lead_minutes));
+
+        if start <= now && now < end {
+            let candidate = ActiveWindow {
+                name: window.name_any(),
+                mode: window.spec.mode,
+                end,
+            };
+            let replace = match &plan.active {
+                None => true,
+                Some(current) => {
+                    (candidate.mode == MaintenanceMode::Suspend
+                        && current.mode == MaintenanceMode::Freeze)
+                        || (candidate.mode == current.mode && candidate.end > current.end)
+                }
+            };
+            if replace {
+                plan.active = Some(candidate);
+            }
+        } else if backup_from <= now && now < start {
+            plan.backup_cutoff = plan.backup_cutoff.max(Some(backup_from));
+        }
+
+        for boundary in [backup_from, start, end] {
+            if boundary > now {
+                plan.next_transition = Some(match plan.next_transition {
+                    Some(t) => t.min(boundary),
+                    None => boundary,
+                });
+            }
+        }
+    }
+
+    plan
+}
+
+/// The disruptive actions of one reconcile, gated by the app's own
+/// `spec.maintenanceWindow`. Each action asks [`DisruptionGate::allow`]
+/// before it runs; while the window is closed the answer is no and the
+/// action is kept for `status.pendingActions`.
+#[derive(Debug)]
+pub struct DisruptionGate {
+    open: bool,
+    /// When a closed window next opens.
+    pub opens: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    previous: Vec<PendingAction>,
+    pending: Vec<PendingAction>,
+}
+
+impl DisruptionGate {
+    /// The gate for `app` at `now`. Apps without a window, or with a
+    /// schedule that doesn't parse, are always open.
+    pub fn new(app: &ServarrApp, now: DateTime<Utc>) -> Self {
+        let previous = app
+            .status
+            .as_ref()
+            .map(|s| s.pending_actions.clone())
+            .unwrap_or_default();
+        let mut gate = Self {
+            open: true,
+            opens: None,
+            now,
+            previous,
+            pending: Vec::new(),
+        };
+        let Some(window) = app.spec.maintenance_window.as_ref() else {
+            return gate;
+        };
+        let schedule = match crate::schedule::parse(&window.schedule) {
+            Ok(s) => s,
+            Err(e) => {
+                warn!(app = %app.name_any(), error = %e, "invalid maintenanceWindow schedule, not deferring");
+                return gate;
+            }
+        };
+        let length = Duration::minutes(i64::from(window.duration_minutes));
+        gate.open = schedule
+            .after(&(now - length))
+            .next()
+            .is_some_and(|start| start <= now);
+        if !gate.open {
+            gate.opens = schedule.after(&now).next();
+        }
+        gate
+    }
+
+    /// Whether `action` may run now. If not, it is recorded as pending
+    /// with `message`, keeping the time it was first held back.
+    pub fn allow(&mut self, action: &str, message: String) -> bool {
+        if self.open {
+            return true;
+        }
+        let since = self
+            .previous
+            .iter()
+            .find(|p| p.action == action)
+            .map(|p| p.since.clone())
+            .unwrap_or_else(|| self.now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+        self.pending.retain(|p| p.action != action);
+        self.pending.push(PendingAction {
+            action: action.to_string(),
+            message,
+            since,
+        });
+        false
+    }
+
+    /// The actions held back on this pass.
+    pub fn into_pending(self) -> Vec<PendingAction> {
+        self.pending
+    }
+}
+
+/// List the MaintenanceWindows in `ns`. Failures (including the CRD not
+/// being installed) are logged and treated as "no windows" so maintenance
+/// support never blocks a reconcile.
+pub async fn list_windows(client: &Client, ns: &str) -> Vec<MaintenanceWindow> {
+    let api = Api::<MaintenanceWindow>::namespaced(client.clone(), ns);
+    match api.list(&ListParams::default()).await {
+        Ok(list) => list.items,
+        Err(e) => {
+            warn!(%ns, error = %e, "failed to list maintenance windows");
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use servarr_crds::{AppMaintenanceWindow, MaintenanceWindowSpec, ServarrAppSpec};
+
+    fn window(name: &str, start: &str, end: &str, mode: MaintenanceMode) -> MaintenanceWindow {
+        MaintenanceWindow::new(
+            name,
+            MaintenanceWindowSpec {
+                selector: BTreeMap::from([("servarr.dev/stack".into(), "media".into())]),
+                start: start.into(),
+                end: end.into(),
+                mode,
+                backup_lead_minutes: 30,
+            },
+        )
+    }
+
+    fn stack_labels() -> BTreeMap<String, String> {
+        BTreeMap::from([("servarr.dev/stack".into(), "media".into())])
+    }
+
+    fn at(ts: &str) -> DateTime<Utc> {
+        ts.parse().unwrap()
+    }
+
+    #[test]
+    fn print_crd_returns_ok() {
+        assert!(print_crd().is_ok());
+    }
+
+    #[test]
+    fn active_window_suspends_matching_app() {
+        let windows = [window(
+            "nas",
+            "2025-06-01T02:00:00Z",
+            "2025-06-01T04:00:00Z",
+            MaintenanceMode::Suspend,
+        )];
+        let plan = plan(&windows, &stack_labels(), at("2025-06-01T03:00:00Z"));
+        assert!(plan.suspended());
+        assert_eq!(plan.active.unwrap().name, "nas");
+        assert_eq!(plan.next_transition, Some(at("2025-06-01T04:00:00Z")));
+    }
+
+    #[test]
+    fn non_matching_app_is_unaffected() {
+        let windows = [window(
+            "nas",
+            "2025-06-01T02:00:00Z",
+            "2025-06-01T04:00:00Z",
+            MaintenanceMode::Suspend,
+        )];
+        let labels = BTreeMap::from([("servarr.dev/stack".into(), "other".into())]);
+        assert_eq!(
+            plan(&windows, &labels, at("2025-06-01T03:00:00Z")),
+            MaintenancePlan::default()
+        );
+    }
+
+    #[test]
+    fn backup_cutoff_set_during_lead_time() {
+        let windows = [window(
+            "nas",
+            "2025-06-01T02:00:00Z",
+            "2025-06-01T04:00:00Z",
+            MaintenanceMode::Freeze,
+        )];
+        let plan = plan(&windows, &stack_labels(), at("2025-06-01T01:45:00Z"));
+        assert!(plan.active.is_none());
+        assert_eq!(plan.backup_cutoff, Some(at("2025-06-01T01:30:00Z")));
+        assert_eq!(plan.next_transition, Some(at("2025-06-01T02:00:00Z")));
+
+        let earlier = super::plan(&windows, &stack_labels(), at("2025-06-01T01:00:00Z"));
+        assert!(earlier.backup_cutoff.is_none());
+        assert_eq!(earlier.next_transition, Some(at("2025-06-01T01:30:00Z")));
+    }
+
+    #[test]
+    fn suspend_takes_precedence_over_freeze() {
+        let windows = [
+            window(
+                "freeze",
+                "2025-06-01T01:00:00Z",
+                "2025-06-01T06:00:00Z",
+                MaintenanceMode::Freeze,
+            ),
+            window(
+                "suspend",
+                "2025-06-01T02:00:00Z",
+                "2025-06-01T03:00:00Z",
+                MaintenanceMode::Suspend,
+            ),
+        ];
+        let plan = plan(&windows, &stack_labels(), at("2025-06-01T02:30:00Z"));
+        assert!(plan.suspended());
+        assert!(!plan.frozen());
+    }
+
+    #[test]
+    fn invalid_or_inverted_windows_are_ignored() {
+        let windows = [
+            window(
+                "bad",
+                "tomorrow",
+                "2025-06-01T04:00:00Z",
+                MaintenanceMode::Suspend,
+            ),
+            window(
+                "inverted",
+                "2025-06-01T04:00:00Z",
+                "2025-06-01T02:00:00Z",
+                MaintenanceMode::Suspend,
+            ),
+        ];
+        assert_eq!(
+            plan(&windows, &stack_labels(), at("2025-06-01T03:00:00Z")),
+            MaintenancePlan::default()
+        );
+    }
+
+    #[test]
+    fn disruptions_wait_for_the_app_window() {
+        let mut app = ServarrApp::new(
+            "sonarr",
+            ServarrAppSpec {
+                maintenance_window: Some(AppMaintenanceWindow {
+                    schedule: "0 2 * * *".into(),
+                    duration_minutes: 120,
+                }),
+                ..Default::default()
+            },
+        );
+
+        let mut inside = DisruptionGate::new(&app, at("2025-06-01T03:30:00Z"));
+        assert!(inside.allow("Restore", "Restore from backup 3".into()));
+        assert!(inside.into_pending().is_empty());
+
+        let mut outside = DisruptionGate::new(&app, at("2025-06-01T12:00:00Z"));
+        assert_eq!(outside.opens, Some(at("2025-06-02T02:00:00Z")));
+        assert!(!outside.allow("Restore", "Restore from backup 3".into()));
+        let pending = outside.into_pending();
+        assert_eq!(pending[0].since, "2025-06-01T12:00:00Z");
+
+        // Still pending an hour later, since the first deferral
+        app.status = Some(servarr_crds::ServarrAppStatus {
+            pending_actions: pending,
+            ..Default::default()
+        });
+        let mut later = DisruptionGate::new(&app, at("2025-06-01T13:00:00Z"));
+        assert!(!later.allow("Restore", "Restore from backup 3".into()));
+        assert_eq!(later.into_pending()[0].since, "2025-06-01T12:00:00Z");
+
+        app.spec.maintenance_window = None;
+        assert!(
+            DisruptionGate::new(&app, at("2025-06-01T12:00:00Z")).allow("Restore", String::new())
+        );
+    }
+}
@@ -0,0 +1,74 @@
+//! Sonarr/Radarr media management settings declared in
+//! `appConfig.mediaManagement`.
+//!
+//! Like `uiSettings`, the declared fields are set on the app's current copy
+//! of `config/mediamanagement`, which also holds file date, permission, and
+//! import options the operator doesn't manage, and it is only written back
+//! when one differs.
+
+use serde_json::Value;
+use servarr_api::{ApiError, ServarrClient};
+use servarr_crds::MediaManagementSpec;
+
+/// `current` with `spec` applied, or `None` when it already matches.
+pub fn desired(spec: &MediaManagementSpec, current: &Value) -> Option<Value> {
+    let mut config = current.clone();
+    for (field, value) in spec.api_fields() {
+        config[field] = value;
+    }
+    (config != *current).then_some(config)
+}
+
+/// Apply `spec` to the app. Returns whether anything was changed.
+pub async fn sync(api: &ServarrClient, spec: &MediaManagementSpec) -> Result<bool, ApiError> {
+    let current = api.media_management_config().await?;
+    let Some(config) = desired(spec, &current) else {
+        return Ok(false);
+    };
+    let id = current.get("id").and_then(Value::as_i64).unwrap_or(1);
+    api.update_media_management_config(id, &config).await?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn current() -> Value {
+        json!({
+            "id": 1,
+            "copyUsingHardlinks": true,
+            "deleteEmptyFolders": false,
+            "minimumFreeSpaceWhenImporting": 100,
+            "recycleBin": "",
+            "fileDate": "none"
+        })
+    }
+
+    #[test]
+    fn sets_only_declared_fields() {
+        let spec = MediaManagementSpec {
+            minimum_free_space_mb: Some(1024),
+            recycle_bin: Some("/data/.recycle".into()),
+            ..Default::default()
+        };
+        let config = desired(&spec, &current()).unwrap();
+        assert_eq!(config["minimumFreeSpaceWhenImporting"], 1024);
+        assert_eq!(config["recycleBin"], "/data/.recycle");
+        assert_eq!(config["copyUsingHardlinks"], true);
+        assert_eq!(config["fileDate"], "none");
+    }
+
+    #[test]
+    fn matching_config_needs_no_update() {
+        let spec = MediaManagementSpec {
+            use_hardlinks: Some(true),
+            delete_empty_folders: Some(false),
+            recycle_bin: Some(String::new()),
+            ..Default::default()
+        };
+        assert_eq!(desired(&spec, &current()), None);
+        assert_eq!(desired(&MediaManagementSpec::default(), &current()), None);
+    }
+}
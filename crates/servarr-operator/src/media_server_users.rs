@@ -0,0 +1,393 @@
+//! Media server accounts declared in `appConfig.users`.
+//!
+//! Jellyfin users are created and updated through the server's API while
+//! signed in as the `adminCredentials` account; access is limited through
+//! the `EnableAllFolders` / `EnabledFolders` keys of the user's policy, and
+//! the rest of the policy is left as the server has it. Plex accounts belong
+//! to plex.tv, so a Plex user is a share of the server's libraries with that
+//! account, managed with the owner's plex.tv token.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+use servarr_api::jellyfin::JellyfinUser;
+use servarr_api::{ApiError, JellyfinClient, PlexTvClient};
+use servarr_crds::{MediaServerConfig, MediaServerUser};
+
+#[derive(Debug, Default)]
+pub struct SyncOutcome {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    /// `(user, error)` for users that could not be applied.
+    pub failed: Vec<(String, String)>,
+}
+
+impl SyncOutcome {
+    /// One-line summary for the `UsersSynced` condition.
+    pub fn message(&self) -> String {
+        let mut parts = Vec::new();
+        for (label, names) in [
+            ("added", &self.added),
+            ("updated", &self.updated),
+            ("removed", &self.removed),
+        ] {
+            if !names.is_empty() {
+                parts.push(format!("{label}: {}", names.join(", ")));
+            }
+        }
+        for (name, error) in &self.failed {
+            parts.push(format!("{name} failed: {error}"));
+        }
+        if parts.is_empty() {
+            "Users match appConfig.users".to_string()
+        } else {
+            parts.join("; ")
+        }
+    }
+}
+
+/// The plex.tv account `user` is shared with.
+pub fn plex_account(user: &MediaServerUser) -> &str {
+    user.sso_subject.as_deref().unwrap_or(&user.username)
+}
+
+/// The IDs of the `wanted` libraries among `known` `(name, id)` pairs,
+/// matched case-insensitively, or an error naming the first unknown one.
+pub fn library_ids<T: Clone>(wanted: &[String], known: &[(&str, T)]) -> Result<Vec<T>, String> {
+    wanted
+        .iter()
+        .map(|name| {
+            known
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, id)| id.clone())
+                .ok_or_else(|| format!("no library named {name}"))
+        })
+        .collect()
+}
+
+fn same_ids<T: Ord + Clone>(a: &[T], b: &[T]) -> bool {
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    a.sort();
+    b.sort();
+    a == b
+}
+
+/// Jellyfin writes GUIDs both with and without dashes.
+fn normalize_id(id: &str) -> String {
+    id.replace('-', "").to_ascii_lowercase()
+}
+
+/// `policy` with access limited to the `folders` IDs, or to every library
+/// when `None`. `None` when the policy already grants exactly that.
+pub fn policy_update(policy: &Value, folders: Option<&[String]>) -> Option<Value> {
+    let all_now = policy
+        .get("EnableAllFolders")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let folders_now: Vec<String> = policy
+        .get("EnabledFolders")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(normalize_id)
+        .collect();
+    let wanted: Vec<String> = folders
+        .unwrap_or_default()
+        .iter()
+        .map(|f| normalize_id(f))
+        .collect();
+    let matches = match folders {
+        None => all_now,
+        Some(_) => !all_now && same_ids(&folders_now, &wanted),
+    };
+    if matches {
+        return None;
+    }
+    let mut updated = if policy.is_object() {
+        policy.clone()
+    } else {
+        Value::Object(Default::default())
+    };
+    updated["EnableAllFolders"] = folders.is_none().into();
+    updated["EnabledFolders"] = wanted.into();
+    Some(updated)
+}
+
+fn random_password() -> String {
+    use rand::Rng as _;
+    rand::rng()
+        .sample_iter(rand::distr::Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect()
+}
+
+/// Bring an existing Jellyfin user in line with its spec. Returns whether
+/// anything changed.
+async fn update_jellyfin_user(
+    api: &JellyfinClient,
+    token: &str,
+    current: &JellyfinUser,
+    password: Option<&str>,
+    folders: Option<&[String]>,
+) -> Result<bool, ApiError> {
+    let mut changed = false;
+    if let Some(password) = password {
+        match api.authenticate(&current.name, password).await {
+            Ok(_) => {}
+            Err(ApiError::ApiResponse { status: 401, .. }) => {
+                api.set_user_password(token, &current.id, password).await?;
+                changed = true;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    if let Some(policy) = policy_update(&current.policy, folders) {
+        api.set_user_policy(token, &current.id, &policy).await?;
+        changed = true;
+    }
+    Ok(changed)
+}
+
+async fn add_jellyfin_user(
+    api: &JellyfinClient,
+    token: &str,
+    username: &str,
+    password: Option<&str>,
+    folders: Option<&[String]>,
+) -> Result<bool, ApiError> {
+    let password = password.map_or_else(random_password, str::to_string);
+    let user = api.create_user(token, username, &password).await?;
+    if let Some(policy) = policy_update(&user.policy, folders) {
+        api.set_user_policy(token, &user.id, &policy).await?;
+    }
+    Ok(true)
+}
+
+/// Create or update the Jellyfin users in `config` and, with `autoRemove`,
+/// delete the other non-administrator users. `token` belongs to the `admin`
+/// account; `passwords` holds each `passwordSecret` user's password by
+/// username.
+pub async fn sync_jellyfin(
+    api: &JellyfinClient,
+    token: &str,
+    admin: &str,
+    config: &MediaServerConfig,
+    passwords: &BTreeMap<String, String>,
+) -> Result<SyncOutcome, ApiError> {
+    let existing = api.list_users(token).await?;
+    let libraries = if config.users.iter().any(|u| !u.libraries.is_empty()) {
+        api.list_libraries(token).await?
+    } else {
+        Vec::new()
+    };
+    let known: Vec<(&str, String)> = libraries
+        .iter()
+        .map(|l| (l.name.as_str(), l.item_id.clone()))
+        .collect();
+    let mut outcome = SyncOutcome::default();
+
+    for user in &config.users {
+        if let Some(e) = servarr_api::faults::sync("media-server-users", &user.username) {
+            outcome.failed.push((user.username.clone(), e.to_string()));
+            continue;
+        }
+        let folders = if user.libraries.is_empty() {
+            None
+        } else {
+            match library_ids(&user.libraries, &known) {
+                Ok(ids) => Some(ids),
+                Err(e) => {
+                    outcome.failed.push((user.username.clone(), e));
+                    continue;
+                }
+            }
+        };
+        let password = passwords.get(&user.username).map(String::as_str);
+        let (result, list) = match existing
+            .iter()
+            .find(|u| u.name.eq_ignore_ascii_case(&user.username))
+        {
+            Some(current) => (
+                update_jellyfin_user(api, token, current, password, folders.as_deref()).await,
+                &mut outcome.updated,
+            ),
+            None => (
+                add_jellyfin_user(api, token, &user.username, password, folders.as_deref()).await,
+                &mut outcome.added,
+            ),
+        };
+        match result {
+            Ok(true) => list.push(user.username.clone()),
+            Ok(false) => {}
+            Err(e) => outcome.failed.push((user.username.clone(), e.to_string())),
+        }
+    }
+
+    if config.auto_remove {
+        for user in &existing {
+            let is_admin = user
+                .policy
+                .get("IsAdministrator")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            if is_admin
+                || user.name.eq_ignore_ascii_case(admin)
+                || config
+                    .users
+                    .iter()
+                    .any(|u| u.username.eq_ignore_ascii_case(&user.name))
+            {
+                continue;
+            }
+            match api.delete_user(token, &user.id).await {
+                Ok(()) => outcome.removed.push(user.name.clone()),
+                Err(e) => outcome.failed.push((user.name.clone(), e.to_string())),
+            }
+        }
+    }
+    Ok(outcome)
+}
+
+/// Share the Plex server `machine_id` with each user's plex.tv account and
+/// the user's libraries, and with `autoRemove`, stop sharing it with other
+/// accounts.
+pub async fn sync_plex(
+    api: &PlexTvClient,
+    machine_id: &str,
+    config: &MediaServerConfig,
+) -> Result<SyncOutcome, ApiError> {
+    let sections = api.sections(machine_id).await?;
+    let shares = api.list_shares(machine_id).await?;
+    let known: Vec<(&str, u64)> = sections.iter().map(|s| (s.title.as_str(), s.id)).collect();
+    let mut outcome = SyncOutcome::default();
+
+    for user in &config.users {
+        if let Some(e) = servarr_api::faults::sync("media-server-users", &user.username) {
+            outcome.failed.push((user.username.clone(), e.to_string()));
+            continue;
+        }
+        let ids = if user.libraries.is_empty() {
+            sections.iter().map(|s| s.id).collect()
+        } else {
+            match library_ids(&user.libraries, &known) {
+                Ok(ids) => ids,
+                Err(e) => {
+                    outcome.failed.push((user.username.clone(), e));
+                    continue;
+                }
+            }
+        };
+        let account = plex_account(user);
+        let (result, list) = match shares.iter().find(|s| s.is_for(account)) {
+            Some(share) => {
+                if same_ids(&share.section_ids, &ids) {
+                    continue;
+                }
+                (
+                    api.update_share(machine_id, share.id, &ids).await,
+                    &mut outcome.updated,
+                )
+            }
+            None => (
+                api.share(machine_id, account, &ids).await,
+                &mut outcome.added,
+            ),
+        };
+        match result {
+            Ok(()) => list.push(user.username.clone()),
+            Err(e) => outcome.failed.push((user.username.clone(), e.to_string())),
+        }
+    }
+
+    if config.auto_remove {
+        for share in &shares {
+            if config.users.iter().any(|u| share.is_for(plex_account(u))) {
+                continue;
+            }
+            let name = if share.username.is_empty() {
+                share.email.clone()
+            } else {
+                share.username.clone()
+            };
+            match api.remove_share(machine_id, share.id).await {
+                Ok(()) => outcome.removed.push(name),
+                Err(e) => outcome.failed.push((name, e.to_string())),
+            }
+        }
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn library_ids_match_names_case_insensitively() {
+        let known = [("Movies", 101u64), ("TV Shows", 102)];
+        assert_eq!(
+            library_ids(&["tv shows".into(), "Movies".into()], &known),
+            Ok(vec![102, 101])
+        );
+        assert_eq!(
+            library_ids(&["Music".into()], &known),
+            Err("no library named Music".to_string())
+        );
+    }
+
+    #[test]
+    fn plex_account_defaults_to_username() {
+        let mut user = MediaServerUser {
+            username: "kid".into(),
+            ..Default::default()
+        };
+        assert_eq!(plex_account(&user), "kid");
+        user.sso_subject = Some("kid@example.com".into());
+        assert_eq!(plex_account(&user), "kid@example.com");
+    }
+
+    #[test]
+    fn policy_update_only_when_access_differs() {
+        let policy = json!({
+            "IsAdministrator": false,
+            "EnableAllFolders": false,
+            "EnabledFolders": ["4b1f9c2e-0000-0000-0000-000000000001"],
+            "AuthenticationProviderId": "Default",
+        });
+        let folders = vec!["4B1F9C2E000000000000000000000001".to_string()];
+        assert!(policy_update(&policy, Some(&folders)).is_none());
+
+        let all = policy_update(&policy, None).unwrap();
+        assert_eq!(all["EnableAllFolders"], json!(true));
+        assert_eq!(all["EnabledFolders"], json!([]));
+        assert_eq!(all["AuthenticationProviderId"], json!("Default"));
+        assert!(policy_update(&all, None).is_none());
+
+        let other = vec!["ffff".to_string()];
+        let limited = policy_update(&policy, Some(&other)).unwrap();
+        assert_eq!(limited["EnableAllFolders"], json!(false));
+        assert_eq!(limited["EnabledFolders"], json!(["ffff"]));
+    }
+
+    #[test]
+    fn message_lists_changes() {
+        let outcome = SyncOutcome {
+            added: vec!["kid".into()],
+            failed: vec![("gran".into(), "no library named Music".into())],
+            ..Default::default()
+        };
+        assert_eq!(
+            outcome.message(),
+            "added: kid; gran failed: no library named Music"
+        );
+        assert_eq!(
+            SyncOutcome::default().message(),
+            "Users match appConfig.users"
+        );
+    }
+}
@@ -1,18 +1,20 @@
 use chrono;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::sync::Arc;
 
 use anyhow::Result;
 use futures::StreamExt;
 use k8s_openapi::api::apps::v1::StatefulSet;
-use k8s_openapi::api::core::v1::{Pod, Service};
+use k8s_openapi::api::core::v1::{ConfigMap, PersistentVolumeClaim, Pod, Secret, Service};
 use kube::api::{Api, DeleteParams, ListParams, Patch, PatchParams};
 use kube::runtime::controller::{Action, Controller};
+use kube::runtime::reflector::ObjectRef;
 use kube::runtime::watcher;
 use kube::{Client, CustomResourceExt, Resource, ResourceExt};
 use servarr_crds::{
-    AppType, Condition, MediaStack, MediaStackStatus, ServarrApp, ServarrAppSpec, StackAppStatus,
-    StackPhase,
+    AppType, Condition, MediaStack, MediaStackStatus, NfsServerSpec, PostgresSpec, ServarrApp,
+    ServarrAppSpec, ServarrAppStatus, StackAppStatus, StackPhase, TeardownPhase,
+    TeardownPhaseStatus, TeardownState, condition_types,
 };
 use thiserror::Error;
 use tokio::time::Duration;
@@ -22,9 +24,19 @@ use crate::context::Context;
 use crate::metrics::{
     increment_stack_reconcile_total, observe_stack_reconcile_duration, set_managed_stacks,
 };
+use crate::requeue::{self, object_key};
 
 const FIELD_MANAGER: &str = "servarr-operator-stack";
 const TIER_TIMEOUT_SECS: i64 = 300; // 5 minutes
+const TEARDOWN_FINALIZER: &str = "servarr.dev/ordered-teardown";
+const TEARDOWN_PHASE_TIMEOUT_SECS: i64 = 300;
+const STACK_LABEL: &str = "servarr.dev/stack";
+/// Namespace of the stack a child belongs to, which differs from the
+/// child's own for apps with `namespace` set.
+const STACK_NAMESPACE_LABEL: &str = "servarr.dev/stack-namespace";
+/// Every namespace of a stack that spans several, comma-separated, so
+/// children can discover their peers.
+pub const STACK_NAMESPACES_ANNOTATION: &str = "servarr.dev/stack-namespaces";
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -43,8 +55,15 @@ pub fn print_crd() -> Result<()> {
     Ok(())
 }
 
-pub async fn run(client: kube::Client, server_state: crate::server::ServerState) -> Result<()> {
-    let ctx = Arc::new(Context::new(client.clone()));
+pub async fn run(
+    client: kube::Client,
+    server_state: crate::server::ServerState,
+    dry_run: bool,
+) -> Result<()> {
+    let ctx = Arc::new(Context {
+        dry_run,
+        ..Context::new(client.clone())
+    });
 
     let (stacks, apps) = if let Some(ref ns) = ctx.watch_namespace {
         (
@@ -61,14 +80,48 @@ pub async fn run(client: kube::Client, server_state: crate::server::ServerState)
     info!("Starting media-stack controller");
     server_state.set_ready();
 
-    Controller::new(stacks, watcher::Config::default())
-        .owns(apps, watcher::Config::default())
+    let controller = Controller::new(stacks, watcher::Config::default());
+    tokio::spawn(crate::metrics::sample_cache(
+        "mediastack",
+        controller.store(),
+        |stack| {
+            crate::metrics::generation_pending(
+                stack.metadata.generation,
+                stack.status.as_ref().map(|s| s.observed_generation),
+            )
+        },
+    ));
+
+    // Any change to a child (including a status flip to or from Ready)
+    // queues its stack right away instead of waiting for its requeue timer.
+    // Children in another namespace can't carry an ownerReference to their
+    // stack, so children are mapped back to it by label.
+    controller
+        .watches(
+            apps,
+            watcher::Config::default().labels(STACK_LABEL),
+            |app| {
+                let labels = app.labels();
+                let stack = labels.get(STACK_LABEL)?;
+                let ns = labels
+                    .get(STACK_NAMESPACE_LABEL)
+                    .cloned()
+                    .or_else(|| app.namespace())?;
+                Some(ObjectRef::<MediaStack>::new(stack).within(&ns))
+            },
+        )
         .shutdown_on_signal()
-        .run(reconcile, error_policy, ctx)
-        .for_each(|res| async move {
-            match res {
-                Ok(o) => info!(?o, "media-stack reconciled"),
-                Err(e) => error!(%e, "media-stack reconcile error"),
+        .run(reconcile, error_policy, ctx.clone())
+        .for_each(|res| {
+            if let Ok((ref obj, _)) = res {
+                ctx.error_backoff
+                    .succeeded(&object_key(obj.namespace.as_deref(), &obj.name));
+            }
+            async move {
+                match res {
+                    Ok(o) => info!(?o, "media-stack reconciled"),
+                    Err(e) => error!(%e, "media-stack reconcile error"),
+                }
             }
         })
         .await;
@@ -77,6 +130,7 @@ pub async fn run(client: kube::Client, server_state: crate::server::ServerState)
 }
 
 pub async fn reconcile(stack: Arc<MediaStack>, ctx: Arc<Context>) -> Result<Action, Error> {
+    let _in_flight = crate::metrics::InFlight::start("mediastack");
     let client = &ctx.client;
     let name = stack.name_any();
     let ns = stack.namespace().unwrap_or_else(|| "default".into());
@@ -85,91 +139,75 @@ pub async fn reconcile(stack: Arc<MediaStack>, ctx: Arc<Context>) -> Result<Acti
     info!(%name, %ns, "reconciling MediaStack");
     let start_time = std::time::Instant::now();
 
-    let defaults = stack.spec.defaults.as_ref();
+    if ctx.dry_run {
+        return reconcile_dry_run(&stack, client, &name, &ns).await;
+    }
+
+    if stack.metadata.deletion_timestamp.is_some() {
+        return teardown(&stack, client, &name, &ns).await;
+    }
+
+    // Hold deletion until the children have been torn down in order
+    let has_finalizer = stack
+        .metadata
+        .finalizers
+        .as_ref()
+        .is_some_and(|f| f.iter().any(|x| x == TEARDOWN_FINALIZER));
+    if !has_finalizer {
+        let mut finalizers = stack.metadata.finalizers.clone().unwrap_or_default();
+        finalizers.push(TEARDOWN_FINALIZER.to_string());
+        let patch = serde_json::json!({
+            "metadata": { "finalizers": finalizers }
+        });
+        Api::<MediaStack>::namespaced(client.clone(), &ns)
+            .patch(&name, &PatchParams::default(), &Patch::Merge(patch))
+            .await
+            .map_err(Error::Kube)?;
+    }
 
     // Reconcile in-cluster NFS server StatefulSet and Service.
     // Returns the pod IP if the server is running (used below to bypass cluster DNS).
     let nfs_pod_ip = reconcile_nfs_server(&stack, client, &name, &ns, &pp).await?;
-
-    // Build an effective NfsServerSpec: for in-cluster servers override the server
-    // address with the pod IP so the kubelet can resolve it without cluster DNS.
-    let nfs_override: Option<servarr_crds::NfsServerSpec>;
-    let effective_nfs = match (stack.spec.nfs.as_ref(), nfs_pod_ip) {
-        (Some(nfs), Some(ip)) if nfs.external_server.is_none() => {
-            nfs_override = Some(servarr_crds::NfsServerSpec {
-                external_server: Some(ip),
-                external_path: String::new(),
-                ..nfs.clone()
-            });
-            nfs_override.as_ref()
+    let nfs_backup = crate::nfs_backup::reconcile(client, &stack, &name, &ns, &pp).await;
+    let effective_nfs = effective_nfs(&stack, nfs_pod_ip);
+
+    let Expansion {
+        children: expanded,
+        postgres_roles,
+    } = match expand_children(&stack, &name, &ns, effective_nfs.as_ref()) {
+        Ok(expansion) => expansion,
+        Err((reason, msg)) => {
+            let now = chrono_now();
+            let mut status = MediaStackStatus::default();
+            status.set_condition(Condition::fail("Valid", reason, &msg, &now));
+            status.observed_generation = stack.metadata.generation.unwrap_or(0);
+            patch_status(client, &ns, &name, &status).await?;
+            increment_stack_reconcile_total("error");
+            return Ok(Action::requeue(Duration::from_secs(60)));
         }
-        _ => stack.spec.nfs.as_ref(),
     };
 
-    // Collect enabled apps and expand split4k entries
-    let mut expanded: Vec<(String, ServarrAppSpec, AppType, u8)> = Vec::new();
-    for app in stack.spec.apps.iter().filter(|a| a.enabled) {
-        match app.expand(&name, &ns, defaults, effective_nfs) {
-            Ok(pairs) => {
-                for (child_name, spec) in pairs {
-                    let tier = app.app.tier();
-                    let app_type = spec.app.clone();
-                    expanded.push((child_name, spec, app_type, tier));
-                }
-            }
-            Err(msg) => {
-                warn!(%name, error = %msg, "split4k validation failed");
-                let now = chrono_now();
-                let mut status = MediaStackStatus::default();
-                status.set_condition(Condition::fail("Valid", "InvalidSplit4k", &msg, &now));
-                status.observed_generation = stack.metadata.generation.unwrap_or(0);
-                patch_status(client, &ns, &name, &status).await?;
-                increment_stack_reconcile_total("error");
-                return Ok(Action::requeue(Duration::from_secs(60)));
-            }
-        }
-    }
+    // Reconcile the shared Postgres instance before any child is applied so
+    // the credentials Secret exists when the app pods first start.
+    reconcile_postgres(&stack, client, &name, &ns, &pp, &postgres_roles).await?;
 
-    // Check for duplicate child names
-    {
-        let mut seen = HashSet::new();
-        for (child_name, _, _, _) in &expanded {
-            if !seen.insert(child_name.clone()) {
-                warn!(%name, child = %child_name, "duplicate app+instance in MediaStack");
-                let now = chrono_now();
-                let mut status = MediaStackStatus::default();
-                status.set_condition(Condition::fail(
-                    "Valid",
-                    "DuplicateApp",
-                    &format!("Duplicate app+instance: {child_name}"),
-                    &now,
-                ));
-                status.observed_generation = stack.metadata.generation.unwrap_or(0);
-                patch_status(client, &ns, &name, &status).await?;
-                increment_stack_reconcile_total("error");
-                return Ok(Action::requeue(Duration::from_secs(60)));
-            }
-        }
-    }
+    let tiers = group_tiers(expanded);
 
-    // Group by tier
-    let mut tiers: BTreeMap<u8, Vec<(String, ServarrAppSpec, AppType)>> = BTreeMap::new();
-    for (child_name, spec, app_type, tier) in expanded {
-        tiers
-            .entry(tier)
-            .or_default()
-            .push((child_name, spec, app_type));
-    }
-
-    // Desired child names for orphan cleanup
-    let desired_children: HashSet<String> = tiers
+    // Desired `(namespace, name)` of each child for orphan cleanup
+    let desired_children: HashSet<(String, String)> = tiers
         .values()
-        .flat_map(|apps| apps.iter().map(|(n, _, _)| n.clone()))
+        .flat_map(|apps| {
+            apps.iter()
+                .map(|(n, child_ns, ..)| (child_ns.clone(), n.clone()))
+        })
         .collect();
 
-    let sa_api = Api::<ServarrApp>::namespaced(client.clone(), &ns);
+    // Publish every app's URLs and API key Secret for tools in the stack
+    reconcile_connections(&stack, client, &name, &ns, &pp, &tiers).await?;
+
     let mut app_statuses: Vec<StackAppStatus> = Vec::new();
     let mut ready_count: i32 = 0;
+    let mut applied_count: i32 = 0;
     let mut current_tier: Option<u8> = None;
     let mut all_previous_ready = true;
 
@@ -205,14 +243,18 @@ pub async fn reconcile(stack: Arc<MediaStack>, ctx: Arc<Context>) -> Result<Acti
 
             if !timed_out {
                 // Previous tier not ready and not timed out — skip.
-                for (child_name, _, app_type) in apps {
+                for (child_name, child_ns, _, app_type) in apps {
                     app_statuses.push(StackAppStatus {
                         name: child_name.clone(),
                         app_type: app_type.as_str().to_string(),
                         tier,
                         ready: false,
                         enabled: true,
+                        applied: false,
                         bypassed: false,
+                        namespace: foreign_namespace(child_ns, &ns),
+                        reason: Some("WaitingForTier".into()),
+                        message: Some("Waiting for earlier tiers to become ready".into()),
                     });
                 }
                 continue;
@@ -235,69 +277,51 @@ pub async fn reconcile(stack: Arc<MediaStack>, ctx: Arc<Context>) -> Result<Acti
 
         current_tier = Some(tier);
 
-        for (child_name, spec, app_type) in apps {
-            // Build child ServarrApp with ownerReferences and labels
-            let owner_ref = stack
-                .controller_owner_ref(&())
-                .expect("stack should have UID");
-
-            let child = ServarrApp::new(child_name, spec.clone());
-            let mut child_value = serde_json::to_value(&child).map_err(Error::Serialization)?;
-
-            // Inject metadata. serde_json::to_value on a struct always produces
-            // an object, so these casts are guaranteed by the type system.
-            let child_obj = child_value.as_object_mut().ok_or(Error::Internal(
-                "serialized ServarrApp is not a JSON object",
-            ))?;
-            let meta = child_obj
-                .entry("metadata")
-                .or_insert_with(|| serde_json::json!({}));
-            let meta_obj = meta
-                .as_object_mut()
-                .ok_or(Error::Internal("metadata field is not a JSON object"))?;
-            meta_obj.insert("namespace".to_string(), serde_json::json!(ns));
-            meta_obj.insert(
-                "ownerReferences".to_string(),
-                serde_json::to_value(vec![&owner_ref]).map_err(Error::Serialization)?,
-            );
-            meta_obj.insert(
-                "labels".to_string(),
-                serde_json::json!({
-                    "servarr.dev/stack": name,
-                    "servarr.dev/tier": tier.to_string(),
-                    "app.kubernetes.io/managed-by": FIELD_MANAGER
-                }),
-            );
-
-            sa_api
-                .patch(child_name, &pp, &Patch::Apply(child_value))
-                .await
-                .map_err(Error::Kube)?;
+        let mut children = Vec::with_capacity(apps.len());
+        for (child_name, child_ns, spec, _) in apps {
+            let value = child_value(&stack, &name, &ns, child_ns, child_name, spec, tier)?;
+            let sa_api = Api::<ServarrApp>::namespaced(client.clone(), child_ns);
+            children.push((child_name.clone(), sa_api, value));
+        }
 
-            // Read back child status.  Bypassed apps count as "ready" for tier
-            // advancement but the actual ready flag is preserved in status.
+        // Apply the tier's children concurrently, at most `applyParallelism`
+        // at a time. `buffered` keeps the results in spec order.
+        let results: Vec<Result<_, kube::Error>> = futures::stream::iter(children)
+            .map(|(child_name, sa_api, value)| {
+                let pp = pp.clone();
+                async move {
+                    sa_api.patch(&child_name, &pp, &Patch::Apply(value)).await?;
+                    Ok(sa_api.get(&child_name).await.ok().and_then(|sa| sa.status))
+                }
+            })
+            .buffered(stack.spec.apply_parallelism())
+            .collect()
+            .await;
+
+        for ((child_name, child_ns, _, app_type), result) in apps.iter().zip(results) {
+            // Bypassed apps count as "ready" for tier advancement but the
+            // actual ready flag is preserved in status.
             let was_bypassed = prev_bypassed.contains(child_name.as_str());
-            let actual_ready = match sa_api.get(child_name).await {
-                Ok(sa) => sa.status.as_ref().is_some_and(|s| s.ready),
-                Err(_) => false,
-            };
-
-            if actual_ready {
-                ready_count += 1;
+            if let Err(ref e) = result {
+                warn!(%name, child = %child_name, error = %e, "failed to apply child ServarrApp");
             }
-            if !actual_ready && !was_bypassed {
+            let mut app_status = applied_child_status(
+                child_name,
+                app_type,
+                tier,
+                was_bypassed,
+                result.as_ref().map(Option::as_ref),
+            );
+            app_status.namespace = foreign_namespace(child_ns, &ns);
+            if app_status.applied {
+                applied_count += 1;
+            }
+            if app_status.ready {
+                ready_count += 1;
+            } else if !was_bypassed {
                 all_previous_ready = false;
             }
-
-            app_statuses.push(StackAppStatus {
-                name: child_name.clone(),
-                app_type: app_type.as_str().to_string(),
-                tier,
-                ready: actual_ready,
-                enabled: true,
-                // Clear bypass once the app is actually ready.
-                bypassed: was_bypassed && !actual_ready,
-            });
+            app_statuses.push(app_status);
         }
     }
 
@@ -310,22 +334,24 @@ pub async fn reconcile(stack: Arc<MediaStack>, ctx: Arc<Context>) -> Result<Acti
                 tier: app.app.tier(),
                 ready: false,
                 enabled: false,
+                applied: false,
                 bypassed: false,
+                namespace: foreign_namespace(app.namespace_or(&ns), &ns),
+                reason: None,
+                message: None,
             });
         }
     }
 
-    // Cleanup orphaned children
-    let label_selector = format!("servarr.dev/stack={name}");
-    let existing = sa_api
-        .list(&ListParams::default().labels(&label_selector))
-        .await
-        .map_err(Error::Kube)?;
-
+    // Cleanup orphaned children, including those left behind in a namespace
+    // an app moved out of
+    let existing = list_children(client, &name, &ns, &child_namespaces(&stack, &ns)).await?;
     for child in &existing {
         let child_name = child.name_any();
-        if !desired_children.contains(&child_name) {
-            info!(%name, child = %child_name, "deleting orphaned child ServarrApp");
+        let child_ns = child.namespace().unwrap_or_default();
+        if !desired_children.contains(&(child_ns.clone(), child_name.clone())) {
+            info!(%name, child = %child_name, namespace = %child_ns, "deleting orphaned child ServarrApp");
+            let sa_api = Api::<ServarrApp>::namespaced(client.clone(), &child_ns);
             if let Err(e) = sa_api.delete(&child_name, &Default::default()).await {
                 warn!(%name, child = %child_name, error = %e, "failed to delete orphaned child");
             }
@@ -367,16 +393,21 @@ pub async fn reconcile(stack: Arc<MediaStack>, ctx: Arc<Context>) -> Result<Acti
         None
     };
 
+    let not_ready = not_ready_summary(&app_statuses);
     let mut status = MediaStackStatus {
         ready: phase == StackPhase::Ready,
         phase: phase.clone(),
         current_tier,
         total_apps,
         ready_apps: ready_count,
+        applied_apps: applied_count,
         app_statuses,
         conditions: Vec::new(),
         observed_generation: stack.metadata.generation.unwrap_or(0),
         tier_blocked_since,
+        teardown: Vec::new(),
+        nfs_backup,
+        would_change: None,
     };
 
     status.set_condition(Condition::ok("Valid", "Valid", "Spec is valid", &now));
@@ -395,7 +426,7 @@ pub async fn reconcile(stack: Arc<MediaStack>, ctx: Arc<Context>) -> Result<Acti
                 "Ready",
                 "RollingOut",
                 &format!(
-                    "{ready_count}/{total_apps} apps ready, rolling out tier {}",
+                    "{ready_count}/{total_apps} apps ready, rolling out tier {}{not_ready}",
                     current_tier.unwrap_or(0)
                 ),
                 &now,
@@ -405,11 +436,11 @@ pub async fn reconcile(stack: Arc<MediaStack>, ctx: Arc<Context>) -> Result<Acti
             status.set_condition(Condition::fail(
                 "Ready",
                 "Degraded",
-                &format!("{ready_count}/{total_apps} apps ready (was fully ready)"),
+                &format!("{ready_count}/{total_apps} apps ready (was fully ready){not_ready}"),
                 &now,
             ));
         }
-        StackPhase::Pending => {
+        StackPhase::Pending | StackPhase::Terminating => {
             status.set_condition(Condition::fail(
                 "Ready",
                 "Pending",
@@ -430,7 +461,7 @@ pub async fn reconcile(stack: Arc<MediaStack>, ctx: Arc<Context>) -> Result<Acti
     if let Ok(stack_list) = gauge_api.list(&ListParams::default()).await {
         let mut counts: std::collections::HashMap<String, i64> = std::collections::HashMap::new();
         for s in &stack_list.items {
-            let key = s.namespace().unwrap_or_default();
+            let key = crate::metrics::namespace_label(&s.namespace().unwrap_or_default());
             *counts.entry(key).or_default() += 1;
         }
         for (ns_key, count) in &counts {
@@ -446,13 +477,454 @@ pub async fn reconcile(stack: Arc<MediaStack>, ctx: Arc<Context>) -> Result<Acti
 
     // Requeue interval based on phase
     let requeue = match phase {
-        StackPhase::Ready => Duration::from_secs(300),
+        StackPhase::Ready => requeue::settings().settled,
         _ => Duration::from_secs(30),
     };
 
     Ok(Action::requeue(requeue))
 }
 
+/// `--dry-run` pass: record what a real reconcile would change in
+/// `status.wouldChange` and leave everything else untouched. Children of
+/// every tier are compared, whether or not the rollout has reached their
+/// tier, and the Postgres Secret is left out because its passwords are
+/// only generated on apply.
+async fn reconcile_dry_run(
+    stack: &MediaStack,
+    client: &Client,
+    name: &str,
+    ns: &str,
+) -> Result<Action, Error> {
+    if stack.metadata.deletion_timestamp.is_some() {
+        // Nothing is torn down in a dry run, but the stack must not hang in
+        // Terminating on the finalizer a real reconcile added.
+        if stack.finalizers().iter().any(|f| f == TEARDOWN_FINALIZER) {
+            warn!(%name, %ns, "dry run: releasing the teardown finalizer without an ordered teardown");
+            let kept: Vec<&String> = stack
+                .finalizers()
+                .iter()
+                .filter(|f| *f != TEARDOWN_FINALIZER)
+                .collect();
+            Api::<MediaStack>::namespaced(client.clone(), ns)
+                .patch(
+                    name,
+                    &PatchParams::default(),
+                    &Patch::Merge(serde_json::json!({ "metadata": { "finalizers": kept } })),
+                )
+                .await
+                .map_err(Error::Kube)?;
+        }
+        return Ok(Action::await_change());
+    }
+
+    let deployed_nfs = stack.spec.nfs.as_ref().filter(|n| n.deploy_in_cluster());
+    let pod_ip = match deployed_nfs {
+        Some(_) => nfs_pod_ip(client, name, ns).await?,
+        None => None,
+    };
+    let effective_nfs = effective_nfs(stack, pod_ip);
+    let children = match expand_children(stack, name, ns, effective_nfs.as_ref()) {
+        Ok(expansion) => expansion.children,
+        Err(_) => return Ok(Action::requeue(Duration::from_secs(60))),
+    };
+    let tiers = group_tiers(children);
+
+    let owner_ref = stack
+        .controller_owner_ref(&())
+        .expect("stack should have UID");
+    let mut objects = Vec::new();
+    let mut removals = Vec::new();
+    match deployed_nfs {
+        Some(nfs) => {
+            for pvc in servarr_resources::nfs_server::build_export_pvcs(name, ns, nfs) {
+                objects.push(serde_json::to_value(&pvc).map_err(Error::Serialization)?);
+            }
+            let statefulset = servarr_resources::nfs_server::build_statefulset(
+                name,
+                ns,
+                nfs,
+                stack.spec.spread_policy.as_ref(),
+                owner_ref.clone(),
+            );
+            let service = servarr_resources::nfs_server::build_service(name, ns, owner_ref.clone());
+            objects.push(serde_json::to_value(&statefulset).map_err(Error::Serialization)?);
+            objects.push(serde_json::to_value(&service).map_err(Error::Serialization)?);
+        }
+        None => removals.extend(planned_removals(client, ns, &format!("{name}-nfs-server")).await?),
+    }
+    match stack.spec.postgres.as_ref().filter(|p| p.enabled) {
+        Some(pg) => {
+            let statefulset =
+                servarr_resources::postgres::build_statefulset(name, ns, pg, owner_ref.clone());
+            let service = servarr_resources::postgres::build_service(name, ns, owner_ref);
+            objects.push(serde_json::to_value(&statefulset).map_err(Error::Serialization)?);
+            objects.push(serde_json::to_value(&service).map_err(Error::Serialization)?);
+        }
+        None => {
+            removals.extend(planned_removals(client, ns, &PostgresSpec::resource_name(name)).await?)
+        }
+    }
+    let cm = connections_config_map(stack, name, ns, &tiers);
+    objects.push(serde_json::to_value(&cm).map_err(Error::Serialization)?);
+
+    let mut desired_children = HashSet::new();
+    for (&tier, apps) in &tiers {
+        for (child_name, child_ns, spec, _) in apps {
+            let mut value = child_value(stack, name, ns, child_ns, child_name, spec, tier)?;
+            // The serialized status is always empty; it isn't the stack's to set
+            if let Some(obj) = value.as_object_mut() {
+                obj.remove("status");
+            }
+            objects.push(value);
+            desired_children.insert((child_ns.clone(), child_name.clone()));
+        }
+    }
+    for child in list_children(client, name, ns, &child_namespaces(stack, ns)).await? {
+        let child_name = child.name_any();
+        let child_ns = child.namespace().unwrap_or_default();
+        if !desired_children.contains(&(child_ns, child_name.clone())) {
+            removals.push(format!("delete ServarrApp/{child_name}"));
+        }
+    }
+
+    let mut changes = crate::dry_run::plan(client, ns, &objects)
+        .await
+        .map_err(Error::Kube)?;
+    changes.extend(removals);
+
+    let previous = stack.status.as_ref().and_then(|s| s.would_change.as_ref());
+    if previous != Some(&changes) {
+        for change in &changes {
+            info!(%name, %ns, %change, "dry run: would change");
+        }
+        let mut status = stack.status.clone().unwrap_or_default();
+        status.would_change = Some(changes);
+        patch_status(client, ns, name, &status).await?;
+    }
+    Ok(Action::requeue(Duration::from_secs(300)))
+}
+
+/// `delete` entries for the StatefulSet and Service `resource_name` of a
+/// disabled stack service that still exist.
+async fn planned_removals(
+    client: &Client,
+    ns: &str,
+    resource_name: &str,
+) -> Result<Vec<String>, Error> {
+    let mut removals = Vec::new();
+    if Api::<StatefulSet>::namespaced(client.clone(), ns)
+        .get_opt(resource_name)
+        .await
+        .map_err(Error::Kube)?
+        .is_some()
+    {
+        removals.push(format!("delete StatefulSet/{resource_name}"));
+    }
+    if Api::<Service>::namespaced(client.clone(), ns)
+        .get_opt(resource_name)
+        .await
+        .map_err(Error::Kube)?
+        .is_some()
+    {
+        removals.push(format!("delete Service/{resource_name}"));
+    }
+    Ok(removals)
+}
+
+/// Why a child is not ready, from its `Ready` condition. Children that
+/// have not reported a status yet are still being created.
+/// Whether a child is Ready and done with its post-start configuration.
+/// Children whose status predates `ConfigurationComplete` only need Ready.
+fn child_ready(status: &ServarrAppStatus) -> bool {
+    status.ready
+        && status.conditions.iter().all(|c| {
+            c.condition_type != condition_types::CONFIGURATION_COMPLETE || c.status == "True"
+        })
+}
+
+fn not_ready_reason(status: Option<&ServarrAppStatus>) -> (Option<String>, Option<String>) {
+    // A Ready child is waiting on its configuration, so report that.
+    let condition_type = if status.is_some_and(|s| s.ready) {
+        condition_types::CONFIGURATION_COMPLETE
+    } else {
+        condition_types::READY
+    };
+    let ready = status.and_then(|s| {
+        s.conditions
+            .iter()
+            .find(|c| c.condition_type == condition_type)
+    });
+    match ready {
+        Some(c) => (
+            Some(c.reason.clone()).filter(|r| !r.is_empty()),
+            Some(c.message.clone()).filter(|m| !m.is_empty()),
+        ),
+        None => (Some("Pending".into()), None),
+    }
+}
+
+/// `"; not ready: sonarr (AppUnhealthy), radarr"` for the enabled children
+/// that are not ready, or an empty string when there are none.
+/// The child ServarrApp `child_name` in `child_ns` as a server-side apply
+/// body, labelled for the stack. Only children in the stack's own namespace
+/// get an ownerReference; the API server rejects cross-namespace owners.
+fn child_value(
+    stack: &MediaStack,
+    name: &str,
+    ns: &str,
+    child_ns: &str,
+    child_name: &str,
+    spec: &ServarrAppSpec,
+    tier: u8,
+) -> Result<serde_json::Value, Error> {
+    let child = ServarrApp::new(child_name, spec.clone());
+    let mut child_value = serde_json::to_value(&child).map_err(Error::Serialization)?;
+
+    // Inject metadata. serde_json::to_value on a struct always produces
+    // an object, so these casts are guaranteed by the type system.
+    let child_obj = child_value.as_object_mut().ok_or(Error::Internal(
+        "serialized ServarrApp is not a JSON object",
+    ))?;
+    let meta = child_obj
+        .entry("metadata")
+        .or_insert_with(|| serde_json::json!({}));
+    let meta_obj = meta
+        .as_object_mut()
+        .ok_or(Error::Internal("metadata field is not a JSON object"))?;
+    meta_obj.insert("namespace".to_string(), serde_json::json!(child_ns));
+    if child_ns == ns {
+        let owner_ref = stack
+            .controller_owner_ref(&())
+            .expect("stack should have UID");
+        meta_obj.insert(
+            "ownerReferences".to_string(),
+            serde_json::to_value(vec![&owner_ref]).map_err(Error::Serialization)?,
+        );
+    }
+    meta_obj.insert(
+        "labels".to_string(),
+        serde_json::json!({
+            STACK_LABEL: name,
+            STACK_NAMESPACE_LABEL: ns,
+            "servarr.dev/tier": tier.to_string(),
+            "app.kubernetes.io/managed-by": FIELD_MANAGER
+        }),
+    );
+    let namespaces = stack.spec.namespaces(ns);
+    if namespaces.len() > 1 {
+        let joined: Vec<&str> = namespaces.iter().map(String::as_str).collect();
+        meta_obj.insert(
+            "annotations".to_string(),
+            serde_json::json!({ STACK_NAMESPACES_ANNOTATION: joined.join(",") }),
+        );
+    }
+    Ok(child_value)
+}
+
+/// A stack's children after split4k expansion and injection of the shared
+/// services: `(name, namespace, spec, app type, tier)` in spec order, and the
+/// Postgres roles they use.
+struct Expansion {
+    children: Vec<(String, String, ServarrAppSpec, AppType, u8)>,
+    postgres_roles: Vec<String>,
+}
+
+/// Expand the stack's enabled apps into their children. Fails with the
+/// reason and message for the `Valid` condition when an app can't be split
+/// or two children would share a name.
+fn expand_children(
+    stack: &MediaStack,
+    name: &str,
+    ns: &str,
+    nfs: Option<&NfsServerSpec>,
+) -> Result<Expansion, (&'static str, String)> {
+    let defaults = stack.spec.defaults.as_ref();
+    let postgres = stack.spec.postgres.as_ref();
+    let pg_secret = PostgresSpec::resource_name(name);
+    let stack_namespaces = stack.spec.namespaces(ns);
+    let mut expansion = Expansion {
+        children: Vec::new(),
+        postgres_roles: Vec::new(),
+    };
+    for app in stack.spec.apps.iter().filter(|a| a.enabled) {
+        let child_ns = app.namespace_or(ns);
+        let pairs = app.expand(name, ns, defaults, nfs).map_err(|msg| {
+            warn!(%name, error = %msg, "split4k validation failed");
+            ("InvalidSplit4k", msg)
+        })?;
+        for (child_name, mut spec) in pairs {
+            // The shared Postgres Secret only exists in the stack's namespace.
+            if child_ns == ns {
+                servarr_crds::inject_postgres(&mut spec, &child_name, postgres, name, ns);
+            }
+            servarr_crds::inject_spread(&mut spec, stack.spec.spread_policy.as_ref(), name);
+            servarr_crds::inject_namespace_peers(&mut spec, child_ns, &stack_namespaces);
+            if let Some(db) = spec.database.as_ref()
+                && db.password_secret == pg_secret
+            {
+                expansion.postgres_roles.push(db.user.clone());
+            }
+            let tier = app.app.tier();
+            let app_type = spec.app.clone();
+            expansion
+                .children
+                .push((child_name, child_ns.to_string(), spec, app_type, tier));
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for (child_name, ..) in &expansion.children {
+        if !seen.insert(child_name.clone()) {
+            warn!(%name, child = %child_name, "duplicate app+instance in MediaStack");
+            return Err((
+                "DuplicateApp",
+                format!("Duplicate app+instance: {child_name}"),
+            ));
+        }
+    }
+    Ok(expansion)
+}
+
+/// The stack's NFS settings with an in-cluster server addressed by its pod
+/// IP, so the kubelet can resolve it without cluster DNS.
+fn effective_nfs(stack: &MediaStack, pod_ip: Option<String>) -> Option<NfsServerSpec> {
+    let nfs = stack.spec.nfs.as_ref()?;
+    match pod_ip {
+        Some(ip) if nfs.external_server.is_none() => Some(NfsServerSpec {
+            external_server: Some(ip),
+            external_path: String::new(),
+            ..nfs.clone()
+        }),
+        _ => Some(nfs.clone()),
+    }
+}
+
+/// Group expanded children by tier.
+fn group_tiers(children: Vec<(String, String, ServarrAppSpec, AppType, u8)>) -> StackTiers {
+    let mut tiers: StackTiers = BTreeMap::new();
+    for (child_name, child_ns, spec, app_type, tier) in children {
+        tiers
+            .entry(tier)
+            .or_default()
+            .push((child_name, child_ns, spec, app_type));
+    }
+    tiers
+}
+
+/// `(child name, namespace, spec, app type)` of the stack's children, by tier.
+type StackTiers = BTreeMap<u8, Vec<(String, String, ServarrAppSpec, AppType)>>;
+
+/// `child_ns` when it is not the stack's namespace `ns`.
+fn foreign_namespace(child_ns: &str, ns: &str) -> Option<String> {
+    (child_ns != ns).then(|| child_ns.to_string())
+}
+
+/// Namespaces that may hold children of the stack: its own, every app's,
+/// and those recorded in its status, so children stay reachable for cleanup
+/// after an app moves or is removed.
+fn child_namespaces(stack: &MediaStack, ns: &str) -> BTreeSet<String> {
+    let mut namespaces = BTreeSet::from([ns.to_string()]);
+    for app in &stack.spec.apps {
+        namespaces.insert(app.namespace_or(ns).to_string());
+    }
+    for app in stack.status.iter().flat_map(|s| &s.app_statuses) {
+        if let Some(child_ns) = &app.namespace {
+            namespaces.insert(child_ns.clone());
+        }
+    }
+    namespaces
+}
+
+/// Whether `child`, listed by its stack label, belongs to the stack in `ns`
+/// rather than a same-named stack in another namespace. Children applied
+/// before the stack-namespace label existed are matched by their own
+/// namespace.
+fn belongs_to_stack(child: &ServarrApp, ns: &str) -> bool {
+    match child.labels().get(STACK_NAMESPACE_LABEL) {
+        Some(stack_ns) => stack_ns == ns,
+        None => child.namespace().as_deref() == Some(ns),
+    }
+}
+
+/// The stack's child ServarrApps across `namespaces`. A namespace other than
+/// the stack's that the operator can't list is logged and skipped, so it
+/// can't hold up the rest of the stack.
+async fn list_children(
+    client: &Client,
+    name: &str,
+    ns: &str,
+    namespaces: &BTreeSet<String>,
+) -> Result<Vec<ServarrApp>, Error> {
+    let params = ListParams::default().labels(&format!("{STACK_LABEL}={name}"));
+    let mut children = Vec::new();
+    for child_ns in namespaces {
+        match Api::<ServarrApp>::namespaced(client.clone(), child_ns)
+            .list(&params)
+            .await
+        {
+            Ok(list) => children.extend(list.items.into_iter().filter(|c| belongs_to_stack(c, ns))),
+            Err(e) if child_ns != ns => {
+                warn!(%name, namespace = %child_ns, error = %e, "failed to list stack children");
+            }
+            Err(e) => return Err(Error::Kube(e)),
+        }
+    }
+    Ok(children)
+}
+
+/// Status of a child of an active tier, from the result of applying it:
+/// the status read back after a successful apply, or the apply error.
+fn applied_child_status(
+    child_name: &str,
+    app_type: &AppType,
+    tier: u8,
+    was_bypassed: bool,
+    result: Result<Option<&ServarrAppStatus>, &kube::Error>,
+) -> StackAppStatus {
+    let (applied, ready, reason, message) = match result {
+        Err(e) => (
+            false,
+            false,
+            Some("ApplyFailed".into()),
+            Some(e.to_string()),
+        ),
+        Ok(status) if status.is_some_and(child_ready) => (true, true, None, None),
+        Ok(status) => {
+            let (reason, message) = not_ready_reason(status);
+            (true, false, reason, message)
+        }
+    };
+    StackAppStatus {
+        name: child_name.to_string(),
+        app_type: app_type.as_str().to_string(),
+        tier,
+        ready,
+        enabled: true,
+        applied,
+        // Clear bypass once the app is actually ready.
+        bypassed: was_bypassed && !ready,
+        namespace: None,
+        reason,
+        message,
+    }
+}
+
+fn not_ready_summary(apps: &[StackAppStatus]) -> String {
+    let names: Vec<String> = apps
+        .iter()
+        .filter(|a| a.enabled && !a.ready)
+        .map(|a| match a.reason.as_deref() {
+            Some(reason) => format!("{} ({reason})", a.name),
+            None => a.name.clone(),
+        })
+        .collect();
+    if names.is_empty() {
+        String::new()
+    } else {
+        format!("; not ready: {}", names.join(", "))
+    }
+}
+
 /// Apply (or clean up) the in-cluster NFS server StatefulSet and Service.
 ///
 /// When `nfs.deploy_in_cluster()` is true, both resources are created/updated
@@ -486,10 +958,28 @@ async fn reconcile_nfs_server(
             .controller_owner_ref(&())
             .expect("stack should have UID");
 
-        let statefulset =
-            servarr_resources::nfs_server::build_statefulset(name, ns, nfs, owner_ref.clone());
+        let statefulset = servarr_resources::nfs_server::build_statefulset(
+            name,
+            ns,
+            nfs,
+            stack.spec.spread_policy.as_ref(),
+            owner_ref.clone(),
+        );
         let service = servarr_resources::nfs_server::build_service(name, ns, owner_ref);
 
+        // The server pod can't start until the PVCs of its sized exports exist
+        let pvc_api = Api::<PersistentVolumeClaim>::namespaced(client.clone(), ns);
+        for pvc in servarr_resources::nfs_server::build_export_pvcs(name, ns, nfs) {
+            pvc_api
+                .patch(
+                    &pvc.name_any(),
+                    pp,
+                    &Patch::Apply(serde_json::to_value(&pvc).map_err(Error::Serialization)?),
+                )
+                .await
+                .map_err(Error::Kube)?;
+        }
+
         ss_api
             .patch(
                 &nfs_name,
@@ -510,16 +1000,7 @@ async fn reconcile_nfs_server(
 
         info!(%name, %ns, "applied NFS server StatefulSet and Service");
 
-        // Look up the pod IP of the running NFS server pod.  The kubelet mounts
-        // volumes from the host network namespace where cluster-internal DNS may
-        // not resolve.  Using the pod IP directly bypasses that limitation.
-        let pod_api = Api::<Pod>::namespaced(client.clone(), ns);
-        let pod_name = format!("{nfs_name}-0");
-        let pod_ip = pod_api
-            .get_opt(&pod_name)
-            .await
-            .map_err(Error::Kube)?
-            .and_then(|p| p.status?.pod_ip);
+        let pod_ip = nfs_pod_ip(client, name, ns).await?;
 
         if let Some(ref ip) = pod_ip {
             info!(%name, %ns, pod_ip = %ip, "NFS server pod IP resolved");
@@ -552,6 +1033,380 @@ async fn reconcile_nfs_server(
     Ok(None)
 }
 
+/// IP of the running in-cluster NFS server pod. The kubelet mounts volumes
+/// from the host network namespace where cluster-internal DNS may not
+/// resolve; using the pod IP directly bypasses that limitation.
+async fn nfs_pod_ip(client: &Client, name: &str, ns: &str) -> Result<Option<String>, Error> {
+    Ok(Api::<Pod>::namespaced(client.clone(), ns)
+        .get_opt(&format!("{name}-nfs-server-0"))
+        .await
+        .map_err(Error::Kube)?
+        .and_then(|p| p.status?.pod_ip))
+}
+
+/// Apply (or clean up) the stack's shared Postgres StatefulSet, Service, and
+/// credentials Secret.
+///
+/// Passwords already present in the Secret are preserved; only roles without
+/// an entry get a freshly generated one. Keys for roles no longer in the
+/// stack are dropped, but their databases are left in place so re-adding an
+/// app does not lose data. Disabling Postgres or deleting the stack keeps the
+/// Secret along with the retained PVC: Postgres only reads the passwords at
+/// initdb, so new ones would lock the provisioner and the apps out of the
+/// existing data.
+async fn reconcile_postgres(
+    stack: &MediaStack,
+    client: &Client,
+    name: &str,
+    ns: &str,
+    pp: &PatchParams,
+    roles: &[String],
+) -> Result<(), Error> {
+    let pg_name = PostgresSpec::resource_name(name);
+    let ss_api = Api::<StatefulSet>::namespaced(client.clone(), ns);
+    let svc_api = Api::<Service>::namespaced(client.clone(), ns);
+    let secret_api = Api::<Secret>::namespaced(client.clone(), ns);
+
+    let Some(pg) = stack.spec.postgres.as_ref().filter(|p| p.enabled) else {
+        for result in [
+            ss_api
+                .delete(&pg_name, &DeleteParams::default())
+                .await
+                .map(|_| ()),
+            svc_api
+                .delete(&pg_name, &DeleteParams::default())
+                .await
+                .map(|_| ()),
+        ] {
+            match result {
+                Err(e) if !is_not_found(&e) => {
+                    warn!(%name, error = %e, "failed to delete Postgres resource");
+                }
+                _ => {}
+            }
+        }
+        return Ok(());
+    };
+
+    let existing: BTreeMap<String, String> = secret_api
+        .get_opt(&pg_name)
+        .await
+        .map_err(Error::Kube)?
+        .and_then(|s| s.data)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(k, v)| String::from_utf8(v.0).ok().map(|v| (k, v)))
+        .collect();
+
+    let wanted = std::iter::once(PostgresSpec::SUPERUSER_PASSWORD_KEY.to_string())
+        .chain(roles.iter().map(|r| PostgresSpec::password_key(r)));
+    let passwords: BTreeMap<String, String> = wanted
+        .map(|key| {
+            let value = existing
+                .get(&key)
+                .cloned()
+                .unwrap_or_else(generate_password);
+            (key, value)
+        })
+        .collect();
+
+    let owner_ref = stack
+        .controller_owner_ref(&())
+        .expect("stack should have UID");
+    let secret = servarr_resources::postgres::build_secret(name, ns, &passwords);
+    let statefulset =
+        servarr_resources::postgres::build_statefulset(name, ns, pg, owner_ref.clone());
+    let service = servarr_resources::postgres::build_service(name, ns, owner_ref);
+
+    secret_api
+        .patch(
+            &pg_name,
+            pp,
+            &Patch::Apply(serde_json::to_value(&secret).map_err(Error::Serialization)?),
+        )
+        .await
+        .map_err(Error::Kube)?;
+
+    ss_api
+        .patch(
+            &pg_name,
+            pp,
+            &Patch::Apply(serde_json::to_value(&statefulset).map_err(Error::Serialization)?),
+        )
+        .await
+        .map_err(Error::Kube)?;
+
+    svc_api
+        .patch(
+            &pg_name,
+            pp,
+            &Patch::Apply(serde_json::to_value(&service).map_err(Error::Serialization)?),
+        )
+        .await
+        .map_err(Error::Kube)?;
+
+    info!(%name, %ns, roles = roles.len(), "applied Postgres StatefulSet, Service, and Secret");
+    Ok(())
+}
+
+/// Apply the `{stack}-connections` ConfigMap listing each app's in-cluster
+/// URL, external URL, and API key Secret.
+async fn reconcile_connections(
+    stack: &MediaStack,
+    client: &Client,
+    name: &str,
+    ns: &str,
+    pp: &PatchParams,
+    tiers: &StackTiers,
+) -> Result<(), Error> {
+    let cm = connections_config_map(stack, name, ns, tiers);
+    Api::<ConfigMap>::namespaced(client.clone(), ns)
+        .patch(
+            &servarr_resources::connections::resource_name(name),
+            pp,
+            &Patch::Apply(serde_json::to_value(&cm).map_err(Error::Serialization)?),
+        )
+        .await
+        .map_err(Error::Kube)?;
+    Ok(())
+}
+
+/// The `{stack}-connections` ConfigMap for the children in `tiers`.
+fn connections_config_map(
+    stack: &MediaStack,
+    name: &str,
+    ns: &str,
+    tiers: &StackTiers,
+) -> ConfigMap {
+    let apps: Vec<ServarrApp> = tiers
+        .values()
+        .flatten()
+        .map(|(child_name, child_ns, spec, _)| {
+            let mut app = ServarrApp::new(child_name, spec.clone());
+            app.metadata.namespace = Some(child_ns.clone());
+            app
+        })
+        .collect();
+    let owner_ref = stack
+        .controller_owner_ref(&())
+        .expect("stack should have UID");
+    servarr_resources::connections::build(name, ns, &apps, owner_ref)
+}
+
+/// Work out the state of every teardown phase from the resources that still
+/// exist. `children` are the stack's remaining child ServarrApps and
+/// `storage` its remaining NFS/Postgres StatefulSets.
+///
+/// A phase starts once every earlier phase is complete or has timed out, and
+/// times out `TEARDOWN_PHASE_TIMEOUT_SECS` after it started, so a child stuck
+/// on its own finalizer delays the rest of the teardown but cannot block it.
+pub fn plan_teardown(
+    children: &[(String, AppType)],
+    storage: &[String],
+    previous: &[TeardownPhaseStatus],
+    now: chrono::DateTime<chrono::Utc>,
+) -> Vec<TeardownPhaseStatus> {
+    let mut blocked = false;
+    TeardownPhase::ALL
+        .iter()
+        .map(|&phase| {
+            let mut remaining: Vec<String> = if phase == TeardownPhase::Storage {
+                storage.to_vec()
+            } else {
+                children
+                    .iter()
+                    .filter(|(_, app)| TeardownPhase::for_app(app) == phase)
+                    .map(|(n, _)| n.clone())
+                    .collect()
+            };
+            remaining.sort();
+            let started_at = previous
+                .iter()
+                .find(|p| p.phase == phase)
+                .and_then(|p| p.started_at.clone());
+
+            let (state, started_at) = if remaining.is_empty() {
+                (TeardownState::Complete, started_at)
+            } else if blocked {
+                (TeardownState::Pending, None)
+            } else {
+                let started_at = started_at
+                    .unwrap_or_else(|| now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+                let timed_out =
+                    chrono::DateTime::parse_from_rfc3339(&started_at).is_ok_and(|since| {
+                        (now - since.with_timezone(&chrono::Utc)).num_seconds()
+                            >= TEARDOWN_PHASE_TIMEOUT_SECS
+                    });
+                if timed_out {
+                    (TeardownState::TimedOut, Some(started_at))
+                } else {
+                    blocked = true;
+                    (TeardownState::InProgress, Some(started_at))
+                }
+            };
+
+            TeardownPhaseStatus {
+                phase,
+                state,
+                remaining,
+                started_at,
+            }
+        })
+        .collect()
+}
+
+/// Delete the stack's children one phase at a time, then release the
+/// finalizer so the stack (and anything left, via ownerReferences) goes away.
+async fn teardown(
+    stack: &MediaStack,
+    client: &Client,
+    name: &str,
+    ns: &str,
+) -> Result<Action, Error> {
+    let has_finalizer = stack
+        .metadata
+        .finalizers
+        .as_ref()
+        .is_some_and(|f| f.iter().any(|x| x == TEARDOWN_FINALIZER));
+    if !has_finalizer {
+        return Ok(Action::await_change());
+    }
+
+    let ss_api = Api::<StatefulSet>::namespaced(client.clone(), ns);
+
+    let existing = list_children(client, name, ns, &child_namespaces(stack, ns)).await?;
+    let children: Vec<(String, AppType)> = existing
+        .iter()
+        .map(|sa| (sa.name_any(), sa.spec.app.clone()))
+        .collect();
+    let child_apis: HashMap<String, Api<ServarrApp>> = existing
+        .iter()
+        .map(|sa| {
+            let child_ns = sa.namespace().unwrap_or_else(|| ns.to_string());
+            (sa.name_any(), Api::namespaced(client.clone(), &child_ns))
+        })
+        .collect();
+
+    let mut storage = Vec::new();
+    for ss_name in [
+        format!("{name}-nfs-server"),
+        PostgresSpec::resource_name(name),
+    ] {
+        if ss_api
+            .get_opt(&ss_name)
+            .await
+            .map_err(Error::Kube)?
+            .is_some()
+        {
+            storage.push(ss_name);
+        }
+    }
+
+    let previous = stack
+        .status
+        .as_ref()
+        .map(|s| s.teardown.as_slice())
+        .unwrap_or_default();
+    let phases = plan_teardown(&children, &storage, previous, chrono::Utc::now());
+
+    let dp = DeleteParams::foreground();
+    for phase in phases
+        .iter()
+        .filter(|p| matches!(p.state, TeardownState::InProgress | TeardownState::TimedOut))
+    {
+        if phase.phase == TeardownPhase::Storage {
+            delete_storage(client, name, ns).await;
+            continue;
+        }
+        for child in &phase.remaining {
+            let Some(sa_api) = child_apis.get(child) else {
+                continue;
+            };
+            info!(%name, child = %child, phase = ?phase.phase, "deleting child ServarrApp");
+            match sa_api.delete(child, &dp).await {
+                Ok(_) => {}
+                Err(e) if is_not_found(&e) => {}
+                Err(e) => warn!(%name, child = %child, error = %e, "failed to delete child"),
+            }
+        }
+    }
+
+    let done = phases
+        .iter()
+        .all(|p| matches!(p.state, TeardownState::Complete | TeardownState::TimedOut));
+    if done {
+        info!(%name, %ns, "MediaStack teardown complete, removing finalizer");
+        let finalizers: Vec<String> = stack
+            .metadata
+            .finalizers
+            .iter()
+            .flatten()
+            .filter(|x| *x != TEARDOWN_FINALIZER)
+            .cloned()
+            .collect();
+        let patch = serde_json::json!({
+            "metadata": { "finalizers": finalizers }
+        });
+        Api::<MediaStack>::namespaced(client.clone(), ns)
+            .patch(name, &PatchParams::default(), &Patch::Merge(patch))
+            .await
+            .map_err(Error::Kube)?;
+        return Ok(Action::await_change());
+    }
+
+    let now = chrono_now();
+    let mut status = stack.status.clone().unwrap_or_default();
+    status.ready = false;
+    status.phase = StackPhase::Terminating;
+    let active: Vec<String> = phases
+        .iter()
+        .filter(|p| p.state == TeardownState::InProgress)
+        .map(|p| format!("{:?} ({})", p.phase, p.remaining.join(", ")))
+        .collect();
+    status.set_condition(Condition::fail(
+        "Ready",
+        "Terminating",
+        &format!("Tearing down {}", active.join("; ")),
+        &now,
+    ));
+    status.teardown = phases;
+    patch_status(client, ns, name, &status).await?;
+
+    Ok(Action::requeue(Duration::from_secs(10)))
+}
+
+/// Delete the in-cluster NFS server and Postgres resources. The Postgres
+/// Secret is kept with its PVC, as in [`reconcile_postgres`]. Failures are
+/// logged; the next teardown pass retries while the StatefulSets remain.
+async fn delete_storage(client: &Client, name: &str, ns: &str) {
+    let ss_api = Api::<StatefulSet>::namespaced(client.clone(), ns);
+    let svc_api = Api::<Service>::namespaced(client.clone(), ns);
+    let dp = DeleteParams::foreground();
+
+    let nfs_name = format!("{name}-nfs-server");
+    let pg_name = PostgresSpec::resource_name(name);
+    let results = [
+        ss_api.delete(&nfs_name, &dp).await.map(|_| ()),
+        svc_api.delete(&nfs_name, &dp).await.map(|_| ()),
+        ss_api.delete(&pg_name, &dp).await.map(|_| ()),
+        svc_api.delete(&pg_name, &dp).await.map(|_| ()),
+    ];
+    for e in results.into_iter().filter_map(Result::err) {
+        if !is_not_found(&e) {
+            warn!(%name, error = %e, "failed to delete stack storage resource");
+        }
+    }
+}
+
+fn generate_password() -> String {
+    use rand::Rng as _;
+    rand::rng()
+        .sample_iter(rand::distr::Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect()
+}
+
 fn is_not_found(e: &kube::Error) -> bool {
     matches!(e, kube::Error::Api(e) if e.code == 404)
 }
@@ -579,10 +1434,13 @@ async fn patch_status(
     Ok(())
 }
 
-pub fn error_policy(_stack: Arc<MediaStack>, error: &Error, _ctx: Arc<Context>) -> Action {
+pub fn error_policy(stack: Arc<MediaStack>, error: &Error, ctx: Arc<Context>) -> Action {
     increment_stack_reconcile_total("error");
     warn!(%error, "media-stack reconciliation failed, requeuing");
-    Action::requeue(Duration::from_secs(60))
+    Action::requeue(
+        ctx.error_backoff
+            .failed(&object_key(stack.namespace().as_deref(), &stack.name_any())),
+    )
 }
 
 fn chrono_now() -> String {
@@ -599,10 +1457,255 @@ mod tests {
         assert!(print_crd().is_ok());
     }
 
+    #[test]
+    fn not_ready_reason_comes_from_child_ready_condition() {
+        assert_eq!(not_ready_reason(None), (Some("Pending".into()), None));
+
+        let mut status = ServarrAppStatus::default();
+        status.set_condition(Condition::fail(
+            condition_types::READY,
+            "AppUnhealthy",
+            "health check returned 503",
+            "t",
+        ));
+        assert_eq!(
+            not_ready_reason(Some(&status)),
+            (
+                Some("AppUnhealthy".into()),
+                Some("health check returned 503".into())
+            )
+        );
+    }
+
+    #[test]
+    fn children_wait_for_configuration() {
+        let mut status = ServarrAppStatus {
+            ready: true,
+            ..Default::default()
+        };
+        status.set_condition(Condition::fail(
+            condition_types::CONFIGURATION_COMPLETE,
+            "ConfigurationPending",
+            "Waiting for RootFoldersSynced",
+            "t",
+        ));
+        let app = applied_child_status("sonarr", &AppType::Sonarr, 1, false, Ok(Some(&status)));
+        assert!(app.applied && !app.ready);
+        assert_eq!(app.reason.as_deref(), Some("ConfigurationPending"));
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Waiting for RootFoldersSynced")
+        );
+
+        status.set_condition(Condition::ok(
+            condition_types::CONFIGURATION_COMPLETE,
+            "Configured",
+            "",
+            "t",
+        ));
+        assert!(child_ready(&status));
+    }
+
+    #[test]
+    fn applied_child_status_records_apply_failures() {
+        let ready = ServarrAppStatus {
+            ready: true,
+            ..Default::default()
+        };
+        let status = applied_child_status("sonarr", &AppType::Sonarr, 1, true, Ok(Some(&ready)));
+        assert!(status.applied && status.ready && !status.bypassed);
+        assert_eq!(status.reason, None);
+
+        let status = applied_child_status("sonarr", &AppType::Sonarr, 1, true, Ok(None));
+        assert!(status.applied && !status.ready && status.bypassed);
+        assert_eq!(status.reason.as_deref(), Some("Pending"));
+
+        let err = kube::Error::Service("connection reset".into());
+        let status = applied_child_status("sonarr", &AppType::Sonarr, 1, false, Err(&err));
+        assert!(!status.applied && !status.ready);
+        assert_eq!(status.reason.as_deref(), Some("ApplyFailed"));
+        assert!(status.message.unwrap().contains("connection reset"));
+    }
+
+    #[test]
+    fn not_ready_summary_lists_enabled_children() {
+        let app = |name: &str, ready: bool, enabled: bool, reason: Option<&str>| StackAppStatus {
+            name: name.into(),
+            app_type: "Sonarr".into(),
+            tier: 1,
+            bypassed: false,
+            ready,
+            enabled,
+            applied: enabled,
+            namespace: None,
+            reason: reason.map(Into::into),
+            message: None,
+        };
+        assert_eq!(not_ready_summary(&[app("sonarr", true, true, None)]), "");
+        assert_eq!(
+            not_ready_summary(&[
+                app("sonarr", false, true, Some("AppUnhealthy")),
+                app("radarr", true, true, None),
+                app("lidarr", false, false, None),
+                app("bazarr", false, true, None),
+            ]),
+            "; not ready: sonarr (AppUnhealthy), bazarr"
+        );
+    }
+
     #[test]
     fn chrono_now_returns_valid_iso8601() {
         let now = chrono_now();
         assert!(now.contains('T'), "should contain T separator: {now}");
         assert!(now.ends_with('Z'), "should end with Z: {now}");
     }
+
+    fn state_of(phases: &[TeardownPhaseStatus], phase: TeardownPhase) -> TeardownState {
+        phases.iter().find(|p| p.phase == phase).unwrap().state
+    }
+
+    #[test]
+    fn teardown_starts_with_request_apps_only() {
+        let children = vec![
+            ("s-overseerr".to_string(), AppType::Overseerr),
+            ("s-sonarr".to_string(), AppType::Sonarr),
+            ("s-prowlarr".to_string(), AppType::Prowlarr),
+            ("s-transmission".to_string(), AppType::Transmission),
+        ];
+        let storage = vec!["s-nfs-server".to_string()];
+        let phases = plan_teardown(&children, &storage, &[], chrono::Utc::now());
+
+        assert_eq!(phases.len(), TeardownPhase::ALL.len());
+        assert_eq!(
+            state_of(&phases, TeardownPhase::RequestApps),
+            TeardownState::InProgress
+        );
+        assert_eq!(phases[0].remaining, vec!["s-overseerr"]);
+        assert!(phases[0].started_at.is_some());
+        for phase in [
+            TeardownPhase::MediaManagers,
+            TeardownPhase::Indexers,
+            TeardownPhase::DownloadClients,
+            TeardownPhase::Storage,
+        ] {
+            assert_eq!(state_of(&phases, phase), TeardownState::Pending);
+        }
+        // No media servers in this stack
+        assert_eq!(
+            state_of(&phases, TeardownPhase::MediaServers),
+            TeardownState::Complete
+        );
+    }
+
+    #[test]
+    fn teardown_advances_when_earlier_phases_are_gone() {
+        let children = vec![
+            ("s-prowlarr".to_string(), AppType::Prowlarr),
+            ("s-sabnzbd".to_string(), AppType::Sabnzbd),
+        ];
+        let phases = plan_teardown(&children, &[], &[], chrono::Utc::now());
+        assert_eq!(
+            state_of(&phases, TeardownPhase::Indexers),
+            TeardownState::InProgress
+        );
+        assert_eq!(
+            state_of(&phases, TeardownPhase::DownloadClients),
+            TeardownState::Pending
+        );
+
+        let phases = plan_teardown(&[], &[], &phases, chrono::Utc::now());
+        assert!(phases.iter().all(|p| p.state == TeardownState::Complete));
+    }
+
+    #[test]
+    fn teardown_phase_times_out_and_unblocks_next() {
+        let children = vec![
+            ("s-sonarr".to_string(), AppType::Sonarr),
+            ("s-transmission".to_string(), AppType::Transmission),
+        ];
+        let start = chrono::Utc::now();
+        let phases = plan_teardown(&children, &[], &[], start);
+        assert_eq!(
+            state_of(&phases, TeardownPhase::MediaManagers),
+            TeardownState::InProgress
+        );
+
+        // The started_at timestamp is kept across passes
+        let later = start + chrono::Duration::seconds(TEARDOWN_PHASE_TIMEOUT_SECS + 1);
+        let phases = plan_teardown(&children, &[], &phases, later);
+        assert_eq!(
+            state_of(&phases, TeardownPhase::MediaManagers),
+            TeardownState::TimedOut
+        );
+        assert_eq!(
+            state_of(&phases, TeardownPhase::DownloadClients),
+            TeardownState::InProgress
+        );
+    }
+
+    #[test]
+    fn children_outside_the_stack_namespace() {
+        let stack_app = |app: AppType, namespace: Option<&str>| {
+            let mut value = serde_json::json!({ "app": app });
+            if let Some(ns) = namespace {
+                value["namespace"] = ns.into();
+            }
+            serde_json::from_value::<servarr_crds::StackApp>(value).unwrap()
+        };
+        let spec = servarr_crds::MediaStackSpec {
+            apps: vec![
+                stack_app(AppType::Sonarr, None),
+                stack_app(AppType::Sabnzbd, Some("downloads")),
+            ],
+            ..Default::default()
+        };
+        let mut stack = MediaStack::new("media", spec);
+        stack.metadata.namespace = Some("media".into());
+        stack.metadata.uid = Some("stack-uid".into());
+
+        let spec = ServarrAppSpec {
+            app: AppType::Sabnzbd,
+            ..Default::default()
+        };
+        let value = child_value(
+            &stack,
+            "media",
+            "media",
+            "downloads",
+            "media-sabnzbd",
+            &spec,
+            1,
+        )
+        .unwrap();
+        let meta = &value["metadata"];
+        assert_eq!(meta["namespace"], "downloads");
+        assert!(meta.get("ownerReferences").is_none());
+        assert_eq!(meta["labels"][STACK_NAMESPACE_LABEL], "media");
+        assert_eq!(
+            meta["annotations"][STACK_NAMESPACES_ANNOTATION],
+            "downloads,media"
+        );
+        let value =
+            child_value(&stack, "media", "media", "media", "media-sonarr", &spec, 2).unwrap();
+        assert_eq!(value["metadata"]["ownerReferences"][0]["uid"], "stack-uid");
+
+        let child = |namespace: &str, labels: &[(&str, &str)]| {
+            let mut app = ServarrApp::new("media-sabnzbd", spec.clone());
+            app.metadata.namespace = Some(namespace.into());
+            app.metadata.labels = Some(
+                labels
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            );
+            app
+        };
+        let ours = [(STACK_LABEL, "media"), (STACK_NAMESPACE_LABEL, "media")];
+        let theirs = [(STACK_LABEL, "media"), (STACK_NAMESPACE_LABEL, "other")];
+        assert!(belongs_to_stack(&child("downloads", &ours), "media"));
+        assert!(!belongs_to_stack(&child("downloads", &theirs), "media"));
+        // Children from before the stack-namespace label existed
+        assert!(belongs_to_stack(&child("media", &ours[..1]), "media"));
+        assert!(!belongs_to_stack(&child("downloads", &ours[..1]), "media"));
+    }
 }
@@ -1,4 +1,133 @@
-use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use kube::Resource;
+use kube::runtime::reflector::Store;
+use prometheus::{HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGaugeVec, Opts};
+use servarr_crds::{AppType, AppVersion};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+/// Operator version, from the crate version.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Git commit the binary was built from, if the build set
+/// `SERVARR_OPERATOR_GIT_COMMIT`.
+pub const COMMIT: &str = match option_env!("SERVARR_OPERATOR_GIT_COMMIT") {
+    Some(commit) => commit,
+    None => "unknown",
+};
+
+/// How often [`sample_cache`] refreshes the cache and queue gauges.
+const CACHE_SAMPLE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// How the `namespace` label is exported, from `METRICS_NAMESPACE_LABEL`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NamespaceLabel {
+    /// The namespace name.
+    #[default]
+    Keep,
+    /// A short hash of the name: one series per namespace without exposing
+    /// tenant names.
+    Hash,
+    /// Empty, which Prometheus treats as no label; series are summed across
+    /// namespaces.
+    Drop,
+}
+
+impl NamespaceLabel {
+    /// The label value for `namespace`.
+    pub fn value(self, namespace: &str) -> String {
+        match self {
+            Self::Keep => namespace.to_string(),
+            Self::Hash => format!("{:x}", Sha256::digest(namespace.as_bytes()))[..12].to_string(),
+            Self::Drop => String::new(),
+        }
+    }
+}
+
+/// Metric cardinality settings, fixed at startup.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetricsSettings {
+    pub namespace_label: NamespaceLabel,
+    /// Buckets of the reconcile duration histograms, in seconds, from the
+    /// comma-separated `METRICS_DURATION_BUCKETS`.
+    pub duration_buckets: Vec<f64>,
+    /// Whether the per-app-type `servarr_operator_reconcile_duration_seconds`
+    /// histogram is recorded, from `METRICS_RECONCILE_HISTOGRAM`.
+    pub reconcile_histogram: bool,
+}
+
+impl Default for MetricsSettings {
+    fn default() -> Self {
+        Self {
+            namespace_label: NamespaceLabel::Keep,
+            duration_buckets: prometheus::DEFAULT_BUCKETS.to_vec(),
+            reconcile_histogram: true,
+        }
+    }
+}
+
+impl MetricsSettings {
+    /// Read the settings from the environment. Unrecognized values are
+    /// logged and replaced by the default.
+    pub fn from_env() -> Self {
+        let mut settings = Self::default();
+        match std::env::var("METRICS_NAMESPACE_LABEL")
+            .as_deref()
+            .map(str::trim)
+        {
+            Err(_) | Ok("") | Ok("keep") => {}
+            Ok("hash") => settings.namespace_label = NamespaceLabel::Hash,
+            Ok("drop") => settings.namespace_label = NamespaceLabel::Drop,
+            Ok(v) => {
+                warn!(value = %v, "unrecognized METRICS_NAMESPACE_LABEL, expected keep/hash/drop")
+            }
+        }
+        if let Some(v) = std::env::var("METRICS_DURATION_BUCKETS")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+        {
+            let buckets: Option<Vec<f64>> =
+                v.split(',').map(|b| b.trim().parse::<f64>().ok()).collect();
+            match buckets {
+                Some(b) if !b.is_empty() && b.windows(2).all(|w| w[0] < w[1]) => {
+                    settings.duration_buckets = b;
+                }
+                _ => {
+                    warn!(value = %v, "METRICS_DURATION_BUCKETS must be increasing numbers, using defaults")
+                }
+            }
+        }
+        if let Ok(v) = std::env::var("METRICS_RECONCILE_HISTOGRAM") {
+            settings.reconcile_histogram =
+                !(v.eq_ignore_ascii_case("false") || v == "0" || v.eq_ignore_ascii_case("no"));
+        }
+        info!(?settings, "metrics settings");
+        settings
+    }
+}
+
+static SETTINGS: OnceLock<MetricsSettings> = OnceLock::new();
+
+/// Install `settings`. Must run before any metric is first used; later
+/// calls are ignored.
+pub fn configure(settings: MetricsSettings) {
+    let _ = SETTINGS.set(settings);
+}
+
+fn settings() -> &'static MetricsSettings {
+    SETTINGS.get_or_init(MetricsSettings::default)
+}
+
+/// The `namespace` label value for `namespace` under the configured
+/// [`NamespaceLabel`] mode. Gauges that are set per namespace should be
+/// aggregated by this value.
+pub fn namespace_label(namespace: &str) -> String {
+    settings().namespace_label.value(namespace)
+}
 
 lazy_static::lazy_static! {
     pub static ref RECONCILE_TOTAL: IntCounterVec = prometheus::register_int_counter_vec!(
@@ -10,11 +139,21 @@ lazy_static::lazy_static! {
     )
     .unwrap();
 
+    pub static ref RECONCILE_ERRORS_TOTAL: IntCounterVec = prometheus::register_int_counter_vec!(
+        Opts::new(
+            "servarr_operator_reconcile_errors_total",
+            "Failed reconciliations by error category"
+        ),
+        &["app_type", "category"]
+    )
+    .unwrap();
+
     pub static ref RECONCILE_DURATION: HistogramVec = prometheus::register_histogram_vec!(
         HistogramOpts::new(
             "servarr_operator_reconcile_duration_seconds",
             "Duration of reconciliations in seconds"
-        ),
+        )
+        .buckets(settings().duration_buckets.clone()),
         &["app_type"]
     )
     .unwrap();
@@ -28,6 +167,15 @@ lazy_static::lazy_static! {
     )
     .unwrap();
 
+    pub static ref DRIFT_PATHS_TOTAL: IntCounterVec = prometheus::register_int_counter_vec!(
+        Opts::new(
+            "servarr_operator_drift_paths_total",
+            "Number of drift corrections that included each pod template path"
+        ),
+        &["app_type", "namespace", "resource_type", "path"]
+    )
+    .unwrap();
+
     pub static ref BACKUP_OPERATIONS_TOTAL: IntCounterVec = prometheus::register_int_counter_vec!(
         Opts::new(
             "servarr_operator_backup_operations_total",
@@ -37,6 +185,33 @@ lazy_static::lazy_static! {
     )
     .unwrap();
 
+    pub static ref QUEUE_REMEDIATIONS_TOTAL: IntCounterVec = prometheus::register_int_counter_vec!(
+        Opts::new(
+            "servarr_operator_queue_remediations_total",
+            "Stuck Sonarr/Radarr queue items handled by queue remediation"
+        ),
+        &["app_type", "namespace", "reason", "result"]
+    )
+    .unwrap();
+
+    pub static ref TRASH_SYNCS_TOTAL: IntCounterVec = prometheus::register_int_counter_vec!(
+        Opts::new(
+            "servarr_operator_trash_syncs_total",
+            "TRaSH-guide syncs run against Sonarr/Radarr"
+        ),
+        &["app_type", "namespace", "result"]
+    )
+    .unwrap();
+
+    pub static ref DATABASE_HEALTHY: IntGaugeVec = prometheus::register_int_gauge_vec!(
+        Opts::new(
+            "servarr_app_database_healthy",
+            "1 when the app's last SQLite integrity check passed, 0 when it failed"
+        ),
+        &["app_type", "namespace", "name"]
+    )
+    .unwrap();
+
     pub static ref MANAGED_APPS: IntGaugeVec = prometheus::register_int_gauge_vec!(
         Opts::new(
             "servarr_operator_managed_apps",
@@ -59,7 +234,8 @@ lazy_static::lazy_static! {
         HistogramOpts::new(
             "servarr_operator_stack_reconcile_duration_seconds",
             "Duration of MediaStack reconciliations in seconds"
-        ),
+        )
+        .buckets(settings().duration_buckets.clone()),
         &[]
     )
     .unwrap();
@@ -72,13 +248,96 @@ lazy_static::lazy_static! {
         &["namespace"]
     )
     .unwrap();
+
+    pub static ref KUBE_API_ERRORS_TOTAL: IntCounterVec = prometheus::register_int_counter_vec!(
+        Opts::new(
+            "servarr_operator_kube_api_errors_total",
+            "Kubernetes API requests that failed after retries, by kind of failure"
+        ),
+        &["kind"]
+    )
+    .unwrap();
+
+    pub static ref KUBE_API_RETRIES_TOTAL: IntCounterVec = prometheus::register_int_counter_vec!(
+        Opts::new(
+            "servarr_operator_kube_api_retries_total",
+            "Kubernetes API requests retried, by reason"
+        ),
+        &["reason"]
+    )
+    .unwrap();
+
+    pub static ref BUILD_INFO: IntGaugeVec = prometheus::register_int_gauge_vec!(
+        Opts::new(
+            "servarr_operator_build_info",
+            "Always 1; labels identify the running operator build"
+        ),
+        &["version", "commit"]
+    )
+    .unwrap();
+
+    pub static ref RECONCILES_IN_FLIGHT: IntGaugeVec = prometheus::register_int_gauge_vec!(
+        Opts::new(
+            "servarr_operator_reconciles_in_flight",
+            "Reconciliations currently running, per controller"
+        ),
+        &["controller"]
+    )
+    .unwrap();
+
+    pub static ref CACHE_OBJECTS: IntGaugeVec = prometheus::register_int_gauge_vec!(
+        Opts::new(
+            "servarr_operator_cache_objects",
+            "Objects in a controller's reflector cache"
+        ),
+        &["controller", "kind"]
+    )
+    .unwrap();
+
+    pub static ref WORK_QUEUE_DEPTH: IntGaugeVec = prometheus::register_int_gauge_vec!(
+        Opts::new(
+            "servarr_operator_work_queue_depth",
+            "Cached objects whose latest generation the controller has not reconciled yet"
+        ),
+        &["controller"]
+    )
+    .unwrap();
+
+    pub static ref APP_VERSION_OUTDATED: IntGaugeVec = prometheus::register_int_gauge_vec!(
+        Opts::new(
+            "servarr_app_version_outdated",
+            "1 when an app runs an older version than the latest upstream release, per ServarrInventory report"
+        ),
+        &["namespace", "name", "app", "installed", "latest"]
+    )
+    .unwrap();
+
+    /// Label sets each ServarrInventory last exported, so series for
+    /// removed apps or superseded versions can be dropped.
+    static ref APP_VERSION_SERIES: Mutex<HashMap<String, Vec<[String; 5]>>> =
+        Mutex::new(HashMap::new());
+
+    pub static ref KUBE_API_RECONNECTS_TOTAL: IntCounter = prometheus::register_int_counter!(
+        "servarr_operator_kube_api_reconnects_total",
+        "Times the Kubernetes API became reachable again after timeouts or connection errors"
+    )
+    .unwrap();
 }
 
 pub fn increment_reconcile_total(app_type: &str, result: &str) {
     RECONCILE_TOTAL.with_label_values(&[app_type, result]).inc();
 }
 
+pub fn increment_reconcile_errors(app_type: &str, category: &str) {
+    RECONCILE_ERRORS_TOTAL
+        .with_label_values(&[app_type, category])
+        .inc();
+}
+
 pub fn observe_reconcile_duration(app_type: &str, duration_secs: f64) {
+    if !settings().reconcile_histogram {
+        return;
+    }
     RECONCILE_DURATION
         .with_label_values(&[app_type])
         .observe(duration_secs);
@@ -86,7 +345,13 @@ pub fn observe_reconcile_duration(app_type: &str, duration_secs: f64) {
 
 pub fn increment_drift_corrections(app_type: &str, namespace: &str, resource_type: &str) {
     DRIFT_CORRECTIONS_TOTAL
-        .with_label_values(&[app_type, namespace, resource_type])
+        .with_label_values(&[app_type, &namespace_label(namespace), resource_type])
+        .inc();
+}
+
+pub fn increment_drift_path(app_type: &str, namespace: &str, resource_type: &str, path: &str) {
+    DRIFT_PATHS_TOTAL
+        .with_label_values(&[app_type, &namespace_label(namespace), resource_type, path])
         .inc();
 }
 
@@ -96,6 +361,42 @@ pub fn increment_backup_operations(app_type: &str, operation: &str, result: &str
         .inc();
 }
 
+pub fn increment_queue_remediations(app_type: &str, namespace: &str, reason: &str, result: &str) {
+    QUEUE_REMEDIATIONS_TOTAL
+        .with_label_values(&[app_type, &namespace_label(namespace), reason, result])
+        .inc();
+}
+
+pub fn increment_trash_syncs(app_type: &str, namespace: &str, result: &str) {
+    TRASH_SYNCS_TOTAL
+        .with_label_values(&[app_type, &namespace_label(namespace), result])
+        .inc();
+}
+
+/// Record the result of `name`'s latest integrity check; `None` drops the
+/// series once the app no longer has a check.
+pub fn set_database_healthy(app_type: &str, namespace: &str, name: &str, healthy: Option<bool>) {
+    let labels = [app_type, &namespace_label(namespace), name];
+    match healthy {
+        Some(healthy) => DATABASE_HEALTHY
+            .with_label_values(&labels)
+            .set(i64::from(healthy)),
+        None => {
+            let _ = DATABASE_HEALTHY.remove_label_values(&labels);
+        }
+    }
+}
+
+/// Drop the per-app series of the deleted app `name`, whatever its type.
+pub fn forget_app(namespace: &str, name: &str) {
+    let namespace = namespace_label(namespace);
+    for app_type in AppType::ALL {
+        let _ = DATABASE_HEALTHY.remove_label_values(&[app_type.as_str(), &namespace, name]);
+    }
+}
+
+/// `namespace` is a [`namespace_label`] value, since apps are counted per
+/// label rather than per namespace.
 pub fn set_managed_apps(app_type: &str, namespace: &str, count: i64) {
     MANAGED_APPS
         .with_label_values(&[app_type, namespace])
@@ -112,10 +413,117 @@ pub fn observe_stack_reconcile_duration(duration_secs: f64) {
         .observe(duration_secs);
 }
 
+/// `namespace` is a [`namespace_label`] value, as for [`set_managed_apps`].
 pub fn set_managed_stacks(namespace: &str, count: i64) {
     MANAGED_STACKS.with_label_values(&[namespace]).set(count);
 }
 
+pub fn increment_kube_api_errors(kind: &str) {
+    KUBE_API_ERRORS_TOTAL.with_label_values(&[kind]).inc();
+}
+
+pub fn increment_kube_api_retries(reason: &str) {
+    KUBE_API_RETRIES_TOTAL.with_label_values(&[reason]).inc();
+}
+
+pub fn increment_kube_api_reconnects() {
+    KUBE_API_RECONNECTS_TOTAL.inc();
+}
+
+pub fn set_build_info() {
+    BUILD_INFO.with_label_values(&[VERSION, COMMIT]).set(1);
+}
+
+/// Replace the `servarr_app_version_outdated` series exported for the
+/// inventory `inventory` with one per app in `apps` whose versions are both
+/// known.
+pub fn set_app_versions(inventory: &str, apps: &[AppVersion]) {
+    let series: Vec<([String; 5], bool)> = apps
+        .iter()
+        .filter_map(|a| {
+            let labels = [
+                namespace_label(&a.namespace),
+                a.name.clone(),
+                a.app.to_string(),
+                a.installed_version.clone()?,
+                a.latest_version.clone()?,
+            ];
+            Some((labels, a.outdated?))
+        })
+        .collect();
+    let mut exported = APP_VERSION_SERIES.lock().unwrap_or_else(|e| e.into_inner());
+    for old in exported.remove(inventory).unwrap_or_default() {
+        if !series.iter().any(|(labels, _)| *labels == old) {
+            let values: Vec<&str> = old.iter().map(String::as_str).collect();
+            let _ = APP_VERSION_OUTDATED.remove_label_values(&values);
+        }
+    }
+    for (labels, outdated) in &series {
+        let values: Vec<&str> = labels.iter().map(String::as_str).collect();
+        APP_VERSION_OUTDATED
+            .with_label_values(&values)
+            .set(i64::from(*outdated));
+    }
+    exported.insert(
+        inventory.to_string(),
+        series.into_iter().map(|(labels, _)| labels).collect(),
+    );
+}
+
+/// Counts a reconcile as in flight until dropped, so early returns and
+/// errors are covered too.
+pub struct InFlight(&'static str);
+
+impl InFlight {
+    pub fn start(controller: &'static str) -> Self {
+        RECONCILES_IN_FLIGHT.with_label_values(&[controller]).inc();
+        Self(controller)
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        RECONCILES_IN_FLIGHT.with_label_values(&[self.0]).dec();
+    }
+}
+
+/// Set the cache and queue gauges of `controller` from its cached objects.
+/// `pending` tells whether an object has changes the controller has not
+/// reconciled yet.
+pub fn observe_cache<K>(
+    controller: &str,
+    kind: &str,
+    objects: &[impl AsRef<K>],
+    pending: fn(&K) -> bool,
+) {
+    CACHE_OBJECTS
+        .with_label_values(&[controller, kind])
+        .set(objects.len() as i64);
+    WORK_QUEUE_DEPTH
+        .with_label_values(&[controller])
+        .set(objects.iter().filter(|o| pending((*o).as_ref())).count() as i64);
+}
+
+/// Refresh the cache and queue gauges of `controller` from `store` until
+/// the task is dropped.
+pub async fn sample_cache<K>(controller: &'static str, store: Store<K>, pending: fn(&K) -> bool)
+where
+    K: Resource + Clone + 'static,
+    K::DynamicType: Eq + Hash + Clone + Default,
+{
+    let kind = K::kind(&K::DynamicType::default()).into_owned();
+    let mut interval = tokio::time::interval(CACHE_SAMPLE_INTERVAL);
+    loop {
+        interval.tick().await;
+        observe_cache(controller, &kind, &store.state(), pending);
+    }
+}
+
+/// Whether `generation` is ahead of the generation last recorded in status.
+pub fn generation_pending(generation: Option<i64>, observed: Option<i64>) -> bool {
+    generation.unwrap_or(0) > observed.unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,6 +561,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn increment_drift_path_counts_each_path() {
+        let labels = [
+            "test_drift_path",
+            "testns",
+            "Deployment",
+            "spec.containers[0].image",
+        ];
+        let before = DRIFT_PATHS_TOTAL.with_label_values(&labels).get();
+        increment_drift_path(labels[0], labels[1], labels[2], labels[3]);
+        assert_eq!(
+            DRIFT_PATHS_TOTAL.with_label_values(&labels).get(),
+            before + 1
+        );
+    }
+
     #[test]
     fn increment_drift_corrections_increments_counter() {
         let before = DRIFT_CORRECTIONS_TOTAL
@@ -177,6 +601,43 @@ mod tests {
         assert_eq!(after, before + 1);
     }
 
+    #[test]
+    fn increment_queue_remediations_increments_counter() {
+        let labels = ["test_remediation", "testns", "Stalled", "remediated"];
+        let before = QUEUE_REMEDIATIONS_TOTAL.with_label_values(&labels).get();
+        increment_queue_remediations("test_remediation", "testns", "Stalled", "remediated");
+        let after = QUEUE_REMEDIATIONS_TOTAL.with_label_values(&labels).get();
+        assert_eq!(after, before + 1);
+    }
+
+    #[test]
+    fn set_database_healthy_sets_and_drops_series() {
+        let labels = ["test_integrity", "testns", "sonarr"];
+        set_database_healthy("test_integrity", "testns", "sonarr", Some(false));
+        assert_eq!(DATABASE_HEALTHY.with_label_values(&labels).get(), 0);
+        set_database_healthy("test_integrity", "testns", "sonarr", Some(true));
+        assert_eq!(DATABASE_HEALTHY.with_label_values(&labels).get(), 1);
+        set_database_healthy("test_integrity", "testns", "sonarr", None);
+        assert!(DATABASE_HEALTHY.remove_label_values(&labels).is_err());
+    }
+
+    #[test]
+    fn forget_app_drops_database_healthy() {
+        let labels = ["radarr", "testns", "deleted-radarr"];
+        set_database_healthy("radarr", "testns", "deleted-radarr", Some(true));
+        forget_app("testns", "deleted-radarr");
+        assert!(DATABASE_HEALTHY.remove_label_values(&labels).is_err());
+    }
+
+    #[test]
+    fn increment_trash_syncs_increments_counter() {
+        let labels = ["test_trash", "testns", "success"];
+        let before = TRASH_SYNCS_TOTAL.with_label_values(&labels).get();
+        increment_trash_syncs("test_trash", "testns", "success");
+        let after = TRASH_SYNCS_TOTAL.with_label_values(&labels).get();
+        assert_eq!(after, before + 1);
+    }
+
     #[test]
     fn set_managed_apps_sets_gauge() {
         set_managed_apps("test_gauge_app", "test_ns", 3);
@@ -229,6 +690,133 @@ mod tests {
         assert_eq!(val, 5);
     }
 
+    #[test]
+    fn increment_reconcile_errors_counts_per_category() {
+        let count = |category| {
+            RECONCILE_ERRORS_TOTAL
+                .with_label_values(&["test_errors", category])
+                .get()
+        };
+        let before = count("SecretMissing");
+        increment_reconcile_errors("test_errors", "SecretMissing");
+        assert_eq!(count("SecretMissing"), before + 1);
+        assert_eq!(count("Conflict"), 0);
+    }
+
+    #[test]
+    fn increment_kube_api_errors_increments_counter() {
+        let before = KUBE_API_ERRORS_TOTAL
+            .with_label_values(&["test_kind"])
+            .get();
+        increment_kube_api_errors("test_kind");
+        let after = KUBE_API_ERRORS_TOTAL
+            .with_label_values(&["test_kind"])
+            .get();
+        assert_eq!(after, before + 1);
+    }
+
+    #[test]
+    fn set_build_info_reports_version() {
+        set_build_info();
+        assert_eq!(BUILD_INFO.with_label_values(&[VERSION, COMMIT]).get(), 1);
+    }
+
+    #[test]
+    fn in_flight_guard_decrements_on_drop() {
+        let gauge = RECONCILES_IN_FLIGHT.with_label_values(&["test_in_flight"]);
+        let guard = InFlight::start("test_in_flight");
+        assert_eq!(gauge.get(), 1);
+        drop(guard);
+        assert_eq!(gauge.get(), 0);
+    }
+
+    #[test]
+    fn observe_cache_counts_pending_objects() {
+        let objects: Vec<std::sync::Arc<(i64, i64)>> = vec![
+            std::sync::Arc::new((2, 2)),
+            std::sync::Arc::new((3, 2)),
+            std::sync::Arc::new((1, 0)),
+        ];
+        observe_cache("test_cache", "Test", &objects, |(generation, observed)| {
+            generation_pending(Some(*generation), Some(*observed))
+        });
+        assert_eq!(
+            CACHE_OBJECTS
+                .with_label_values(&["test_cache", "Test"])
+                .get(),
+            3
+        );
+        assert_eq!(WORK_QUEUE_DEPTH.with_label_values(&["test_cache"]).get(), 2);
+    }
+
+    #[test]
+    fn set_app_versions_replaces_stale_series() {
+        let mut app = AppVersion {
+            namespace: "metrics-test".into(),
+            name: "sonarr".into(),
+            app: servarr_crds::AppType::Sonarr,
+            image: "linuxserver/sonarr:4.0.15".into(),
+            installed_version: Some("4.0.15".into()),
+            latest_version: Some("4.0.16".into()),
+            outdated: Some(true),
+        };
+        let labels = |a: &AppVersion| {
+            [
+                a.namespace.clone(),
+                a.name.clone(),
+                "sonarr".to_string(),
+                a.installed_version.clone().unwrap(),
+                a.latest_version.clone().unwrap(),
+            ]
+        };
+        let old = labels(&app);
+        let old: Vec<&str> = old.iter().map(String::as_str).collect();
+        set_app_versions("metrics-test/fleet", std::slice::from_ref(&app));
+        assert_eq!(APP_VERSION_OUTDATED.with_label_values(&old).get(), 1);
+
+        app.installed_version = Some("4.0.16".into());
+        app.outdated = Some(false);
+        set_app_versions("metrics-test/fleet", std::slice::from_ref(&app));
+        let new = labels(&app);
+        let new: Vec<&str> = new.iter().map(String::as_str).collect();
+        assert_eq!(APP_VERSION_OUTDATED.with_label_values(&new).get(), 0);
+        assert!(APP_VERSION_OUTDATED.remove_label_values(&old).is_err());
+    }
+
+    #[test]
+    fn namespace_label_modes() {
+        assert_eq!(NamespaceLabel::Keep.value("media"), "media");
+        assert_eq!(NamespaceLabel::Drop.value("media"), "");
+        let hashed = NamespaceLabel::Hash.value("media");
+        assert_eq!(hashed.len(), 12);
+        assert_ne!(hashed, NamespaceLabel::Hash.value("media-2"));
+    }
+
+    #[test]
+    fn settings_from_env() {
+        temp_env::with_vars(
+            [
+                ("METRICS_NAMESPACE_LABEL", Some("hash")),
+                ("METRICS_DURATION_BUCKETS", Some("0.5, 2,10")),
+                ("METRICS_RECONCILE_HISTOGRAM", Some("false")),
+            ],
+            || {
+                let settings = MetricsSettings::from_env();
+                assert_eq!(settings.namespace_label, NamespaceLabel::Hash);
+                assert_eq!(settings.duration_buckets, vec![0.5, 2.0, 10.0]);
+                assert!(!settings.reconcile_histogram);
+            },
+        );
+        temp_env::with_vars(
+            [
+                ("METRICS_NAMESPACE_LABEL", Some("tenant")),
+                ("METRICS_DURATION_BUCKETS", Some("5,1")),
+                ("METRICS_RECONCILE_HISTOGRAM", None),
+            ],
+            || assert_eq!(MetricsSettings::from_env(), MetricsSettings::default()),
+        );
+    }
+
     #[test]
     fn metrics_appear_in_prometheus_gather() {
         // Trigger at least one metric so the family is populated.
@@ -0,0 +1,97 @@
+//! Sonarr/Radarr monitoring settings declared in `appConfig.monitoring`.
+//!
+//! The settings live in two of the app's resources: unmonitoring deleted
+//! items and creating empty folders in `config/mediamanagement`, and
+//! Radarr's availability delay in `config/indexer`. As with
+//! `mediaManagement`, the declared fields are set on the app's current
+//! copy of each and it is only written back when one differs.
+
+use serde_json::Value;
+use servarr_api::{ApiError, ServarrClient};
+use servarr_crds::{AppType, MonitoringSpec};
+
+/// `current` with `fields` applied, or `None` when it already matches.
+pub fn desired(fields: Vec<(&'static str, Value)>, current: &Value) -> Option<Value> {
+    let mut config = current.clone();
+    for (field, value) in fields {
+        config[field] = value;
+    }
+    (config != *current).then_some(config)
+}
+
+/// Apply `spec` to the `app` behind `api`. Returns whether anything was
+/// changed.
+pub async fn sync(
+    api: &ServarrClient,
+    app: &AppType,
+    spec: &MonitoringSpec,
+) -> Result<bool, ApiError> {
+    let mut changed = false;
+
+    let fields = spec.media_management_fields(app);
+    if !fields.is_empty() {
+        let current = api.media_management_config().await?;
+        if let Some(config) = desired(fields, &current) {
+            let id = current.get("id").and_then(Value::as_i64).unwrap_or(1);
+            api.update_media_management_config(id, &config).await?;
+            changed = true;
+        }
+    }
+
+    let fields = spec.indexer_fields(app);
+    if !fields.is_empty() {
+        let current = api.indexer_config().await?;
+        if let Some(config) = desired(fields, &current) {
+            let id = current.get("id").and_then(Value::as_i64).unwrap_or(1);
+            api.update_indexer_config(id, &config).await?;
+            changed = true;
+        }
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn field_names_follow_the_app() {
+        let spec = MonitoringSpec {
+            unmonitor_deleted: Some(true),
+            create_empty_folders: Some(false),
+            availability_delay_days: Some(-3),
+        };
+        let current = json!({
+            "id": 1,
+            "autoUnmonitorPreviouslyDownloadedMovies": false,
+            "createEmptyMovieFolders": false,
+            "recycleBin": ""
+        });
+        let config = desired(spec.media_management_fields(&AppType::Radarr), &current).unwrap();
+        assert_eq!(config["autoUnmonitorPreviouslyDownloadedMovies"], true);
+        assert_eq!(config["recycleBin"], "");
+        assert_eq!(
+            spec.indexer_fields(&AppType::Radarr),
+            vec![("availabilityDelay", json!(-3))]
+        );
+
+        let sonarr = spec.media_management_fields(&AppType::Sonarr);
+        assert_eq!(sonarr[0].0, "autoUnmonitorPreviouslyDownloadedEpisodes");
+        assert_eq!(sonarr[1].0, "createEmptySeriesFolders");
+        assert!(spec.indexer_fields(&AppType::Sonarr).is_empty());
+    }
+
+    #[test]
+    fn matching_config_needs_no_update() {
+        let spec = MonitoringSpec {
+            unmonitor_deleted: Some(false),
+            ..Default::default()
+        };
+        let current = json!({ "id": 1, "autoUnmonitorPreviouslyDownloadedEpisodes": false });
+        assert_eq!(
+            desired(spec.media_management_fields(&AppType::Sonarr), &current),
+            None
+        );
+    }
+}
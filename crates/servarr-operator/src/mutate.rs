@@ -0,0 +1,282 @@
+//! Defaulting for the mutating admission webhook: fill the ServarrApp spec
+//! fields the resource builders would otherwise default at reconcile time,
+//! so the stored object shows the resolved spec, and optionally pin the
+//! image to the digest its tag points to.
+
+use std::collections::HashMap;
+
+use serde_json::{Value, json};
+use servarr_crds::{AppDefaults, ImageSpec, ServarrApp, UpdateMode};
+
+use crate::image_digest::DigestResolver;
+
+/// Operator settings the defaults depend on.
+#[derive(Clone, Default)]
+pub struct MutateSettings {
+    pub image_overrides: HashMap<String, ImageSpec>,
+    pub default_arch: Option<String>,
+    /// Resolve and set `image.digest` (`WEBHOOK_PIN_IMAGE_DIGESTS`).
+    pub pin_digests: bool,
+}
+
+/// A JSON Patch for the admitted object, and warnings for the client.
+#[derive(Debug, Default)]
+pub struct Mutation {
+    pub patch: Vec<Value>,
+    pub warnings: Vec<String>,
+}
+
+/// Unset spec fields of `app` and the values the builders would use for
+/// them. `env` and `persistence` are left out: the builders merge those
+/// with the defaults rather than replacing them. No image is returned when
+/// the app type has none for the target architecture, which reconciliation
+/// reports instead.
+pub fn defaults(app: &ServarrApp, settings: &MutateSettings) -> Vec<(&'static str, Value)> {
+    let spec = &app.spec;
+    let defaults = AppDefaults::for_app(&spec.app);
+    let mut fields = Vec::new();
+    if spec.image.is_none()
+        && let Ok(image) = servarr_resources::deployment::default_image(
+            app,
+            &settings.image_overrides,
+            settings.default_arch.as_deref(),
+        )
+    {
+        fields.push(("image", json!(image)));
+    }
+    if spec.uid.is_none() {
+        fields.push(("uid", json!(defaults.uid)));
+    }
+    if spec.gid.is_none() {
+        fields.push(("gid", json!(defaults.gid)));
+    }
+    if spec.security.is_none() {
+        fields.push(("security", json!(defaults.security)));
+    }
+    if spec.service.is_none() {
+        fields.push(("service", json!(defaults.service)));
+    }
+    if spec.resources.is_none() {
+        fields.push(("resources", json!(defaults.resources)));
+    }
+    if spec.probes.is_none() {
+        fields.push(("probes", json!(defaults.probes)));
+    }
+    if spec.app_config.is_none()
+        && let Some(ref config) = defaults.app_config
+    {
+        fields.push(("appConfig", json!(config)));
+    }
+    fields
+}
+
+/// Whether `image` should get a digest looked up: it has none, or it kept
+/// the digest pinned for the previous repository or tag, as happens when a
+/// client-side apply changes only the tag.
+pub fn needs_digest(image: &ImageSpec, old: Option<&ImageSpec>) -> bool {
+    if image.digest.is_empty() {
+        return true;
+    }
+    old.is_some_and(|old| {
+        old.digest == image.digest && (old.repository != image.repository || old.tag != image.tag)
+    })
+}
+
+fn spec_image(object: &Value) -> Option<ImageSpec> {
+    serde_json::from_value(object.pointer("/spec/image")?.clone()).ok()
+}
+
+/// The mutation for an admitted ServarrApp. Apps owned by a controller (a
+/// MediaStack or ServarrAppSet) are left alone, as their owner sets their
+/// spec, and so are objects that don't parse, which the validating
+/// webhook rejects.
+pub async fn mutate(
+    object: &Value,
+    old_object: Option<&Value>,
+    settings: &MutateSettings,
+    resolver: &DigestResolver,
+) -> Mutation {
+    let mut mutation = Mutation::default();
+    let Ok(app) = serde_json::from_value::<ServarrApp>(object.clone()) else {
+        return mutation;
+    };
+    let owned = app
+        .metadata
+        .owner_references
+        .iter()
+        .flatten()
+        .any(|o| o.controller == Some(true));
+    if owned {
+        return mutation;
+    }
+
+    let mut fields = defaults(&app, settings);
+    // A digest would hold back automatic updates, which move the tag.
+    let auto_update = app
+        .spec
+        .update_policy
+        .as_ref()
+        .is_some_and(|p| p.mode == UpdateMode::Auto);
+    if settings.pin_digests && !auto_update {
+        let defaulted = fields.iter_mut().find(|(name, _)| *name == "image");
+        let image = match defaulted {
+            Some((_, value)) => serde_json::from_value(value.clone()).ok(),
+            None => app.spec.image.clone(),
+        };
+        if let Some(mut image) = image.filter(|i| {
+            needs_digest(i, old_object.and_then(spec_image).as_ref()) && !i.tag.is_empty()
+        }) {
+            match resolver.resolve(&image).await {
+                Ok(digest) => {
+                    image.digest = digest;
+                    match fields.iter_mut().find(|(name, _)| *name == "image") {
+                        Some((_, value)) => *value = json!(image),
+                        None => fields.push(("image", json!(image))),
+                    }
+                }
+                Err(e) => mutation
+                    .warnings
+                    .push(format!("spec.image was not pinned to a digest: {e}")),
+            }
+        }
+    }
+    mutation.patch = fields
+        .into_iter()
+        .map(|(name, value)| json!({"op": "add", "path": format!("/spec/{name}"), "value": value}))
+        .collect();
+    mutation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(spec: Value) -> Value {
+        json!({
+            "apiVersion": "servarr.dev/v1alpha1",
+            "kind": "ServarrApp",
+            "metadata": {"name": "sonarr", "namespace": "media"},
+            "spec": spec,
+        })
+    }
+
+    fn paths(mutation: &Mutation) -> Vec<&str> {
+        mutation
+            .patch
+            .iter()
+            .filter_map(|op| op["path"].as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn unset_fields_get_the_operator_defaults() {
+        let settings = MutateSettings::default();
+        let resolver = DigestResolver::default();
+        let mutation = mutate(&app(json!({"app": "Sonarr"})), None, &settings, &resolver).await;
+        assert_eq!(
+            paths(&mutation),
+            [
+                "/spec/image",
+                "/spec/uid",
+                "/spec/gid",
+                "/spec/security",
+                "/spec/service",
+                "/spec/resources",
+                "/spec/probes"
+            ]
+        );
+        let image = &mutation.patch[0]["value"];
+        let expected = AppDefaults::for_app(&servarr_crds::AppType::Sonarr).image;
+        assert_eq!(image["repository"], expected.repository.as_str());
+        assert_eq!(image["tag"], expected.tag.as_str());
+        assert!(mutation.warnings.is_empty());
+
+        let set = app(json!({
+            "app": "Sonarr",
+            "uid": 1001,
+            "image": {"repository": "example/sonarr", "tag": "4"}
+        }));
+        let mutation = mutate(&set, None, &settings, &resolver).await;
+        assert!(!paths(&mutation).contains(&"/spec/image"));
+        assert!(!paths(&mutation).contains(&"/spec/uid"));
+    }
+
+    #[tokio::test]
+    async fn image_override_is_the_default() {
+        let settings = MutateSettings {
+            image_overrides: [(
+                "sonarr".to_string(),
+                ImageSpec {
+                    repository: "mirror.example/sonarr".into(),
+                    tag: "4.0.0".into(),
+                    ..Default::default()
+                },
+            )]
+            .into(),
+            ..Default::default()
+        };
+        let mutation = mutate(
+            &app(json!({"app": "Sonarr"})),
+            None,
+            &settings,
+            &DigestResolver::default(),
+        )
+        .await;
+        assert_eq!(
+            mutation.patch[0]["value"]["repository"],
+            "mirror.example/sonarr"
+        );
+    }
+
+    #[tokio::test]
+    async fn auto_updated_images_are_not_pinned() {
+        let settings = MutateSettings {
+            pin_digests: true,
+            ..Default::default()
+        };
+        let object = app(json!({
+            "app": "Sonarr",
+            "image": {"repository": "registry.invalid/sonarr", "tag": "4.0.0"},
+            "updatePolicy": {"mode": "Auto"}
+        }));
+        let mutation = mutate(&object, None, &settings, &DigestResolver::default()).await;
+        assert!(!paths(&mutation).contains(&"/spec/image"));
+        assert!(mutation.warnings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn stack_children_are_left_alone() {
+        let mut object = app(json!({"app": "Sonarr"}));
+        object["metadata"]["ownerReferences"] = json!([{
+            "apiVersion": "servarr.dev/v1alpha1",
+            "kind": "MediaStack",
+            "name": "media",
+            "uid": "1",
+            "controller": true
+        }]);
+        let mutation = mutate(
+            &object,
+            None,
+            &MutateSettings::default(),
+            &DigestResolver::default(),
+        )
+        .await;
+        assert!(mutation.patch.is_empty());
+    }
+
+    #[test]
+    fn stale_digests_are_looked_up_again() {
+        let image = |tag: &str, digest: &str| ImageSpec {
+            repository: "lscr.io/linuxserver/sonarr".into(),
+            tag: tag.into(),
+            digest: digest.into(),
+            ..Default::default()
+        };
+        assert!(needs_digest(&image("4.0.0", ""), None));
+        assert!(!needs_digest(&image("4.0.0", "sha256:a"), None));
+        let old = image("4.0.0", "sha256:a");
+        assert!(!needs_digest(&image("4.0.0", "sha256:a"), Some(&old)));
+        assert!(needs_digest(&image("4.0.1", "sha256:a"), Some(&old)));
+        assert!(!needs_digest(&image("4.0.1", "sha256:b"), Some(&old)));
+    }
+}
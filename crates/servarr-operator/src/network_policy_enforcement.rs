@@ -0,0 +1,236 @@
+//! Whether the cluster's CNI actually enforces NetworkPolicies. A policy on
+//! a cluster running plain flannel is accepted by the API server and then
+//! ignored, so apps report the `NetworkPolicyEnforced` condition next to the
+//! policy they get.
+//!
+//! Detection looks for the DaemonSets of known CNIs, which needs cluster-wide
+//! `list` on DaemonSets. `NETWORK_POLICY_ENFORCEMENT` overrides it for
+//! clusters where that isn't possible or conclusive (k3s, for example, runs
+//! its policy controller inside the k3s binary).
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use k8s_openapi::api::apps::v1::DaemonSet;
+use kube::Client;
+use kube::api::{Api, ListParams};
+use servarr_crds::{Condition, condition_types};
+use tracing::{info, warn};
+
+/// How long a detection result is reused before the DaemonSets are listed
+/// again.
+const CACHE_TTL: Duration = Duration::from_secs(600);
+
+/// DaemonSet name prefixes of CNIs (or policy engines) that enforce
+/// NetworkPolicies, with the name shown in the condition message.
+const ENFORCING: &[(&str, &str)] = &[
+    ("calico-node", "Calico"),
+    ("canal", "Canal"),
+    ("cilium", "Cilium"),
+    ("antrea-agent", "Antrea"),
+    ("kube-router", "kube-router"),
+    ("weave-net", "Weave Net"),
+    ("kube-ovn-cni", "Kube-OVN"),
+    ("ovnkube-node", "OVN-Kubernetes"),
+];
+
+/// DaemonSet name prefixes of CNIs that ignore NetworkPolicies.
+const NON_ENFORCING: &[(&str, &str)] = &[("kube-flannel", "flannel"), ("flannel", "flannel")];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Enforcement {
+    Enforced(String),
+    NotEnforced(String),
+    Unknown(String),
+}
+
+impl Enforcement {
+    pub fn condition(&self, now: &str) -> Condition {
+        match self {
+            Self::Enforced(msg) => Condition::ok(
+                condition_types::NETWORK_POLICY_ENFORCED,
+                "Enforced",
+                msg,
+                now,
+            ),
+            Self::NotEnforced(msg) => Condition::fail(
+                condition_types::NETWORK_POLICY_ENFORCED,
+                "NotEnforced",
+                msg,
+                now,
+            ),
+            Self::Unknown(msg) => Condition {
+                condition_type: condition_types::NETWORK_POLICY_ENFORCED.to_string(),
+                status: "Unknown".to_string(),
+                reason: "CniUnknown".to_string(),
+                message: msg.clone(),
+                last_transition_time: now.to_string(),
+            },
+        }
+    }
+}
+
+fn matches_prefix(name: &str, prefix: &str) -> bool {
+    name == prefix
+        || name
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('-'))
+}
+
+/// Classify a cluster from the names of its DaemonSets.
+pub fn classify<'a>(daemonsets: impl IntoIterator<Item = &'a str> + Clone) -> Enforcement {
+    let find = |known: &[(&str, &'static str)]| {
+        daemonsets.clone().into_iter().find_map(|name| {
+            known
+                .iter()
+                .find(|(prefix, _)| matches_prefix(name, prefix))
+                .map(|(_, cni)| *cni)
+        })
+    };
+    if let Some(cni) = find(ENFORCING) {
+        Enforcement::Enforced(format!("{cni} enforces NetworkPolicies"))
+    } else if let Some(cni) = find(NON_ENFORCING) {
+        Enforcement::NotEnforced(format!(
+            "The cluster runs {cni} without a policy engine; the NetworkPolicy is created but not enforced"
+        ))
+    } else {
+        Enforcement::Unknown(
+            "No known CNI DaemonSet found; set NETWORK_POLICY_ENFORCEMENT if policies are enforced"
+                .into(),
+        )
+    }
+}
+
+/// Detected (or configured) enforcement, shared by all reconciles.
+pub struct EnforcementCheck {
+    configured: Option<Enforcement>,
+    cached: Mutex<Option<(Instant, Enforcement)>>,
+}
+
+impl Default for EnforcementCheck {
+    fn default() -> Self {
+        Self {
+            configured: None,
+            cached: Mutex::new(None),
+        }
+    }
+}
+
+impl EnforcementCheck {
+    /// Read `NETWORK_POLICY_ENFORCEMENT`: `true` or `false` skip detection,
+    /// anything else (default `auto`) detects.
+    pub fn from_env() -> Self {
+        let value = std::env::var("NETWORK_POLICY_ENFORCEMENT").unwrap_or_default();
+        let configured = match value.trim().to_ascii_lowercase().as_str() {
+            "true" => Some(Enforcement::Enforced(
+                "NetworkPolicy enforcement set by NETWORK_POLICY_ENFORCEMENT".into(),
+            )),
+            "false" => Some(Enforcement::NotEnforced(
+                "The operator is configured with NETWORK_POLICY_ENFORCEMENT=false; the NetworkPolicy is created but not enforced".into(),
+            )),
+            "" | "auto" => None,
+            other => {
+                warn!(value = %other, "unrecognized NETWORK_POLICY_ENFORCEMENT value, expected auto/true/false; detecting");
+                None
+            }
+        };
+        if let Some(ref c) = configured {
+            info!(enforcement = ?c, "NetworkPolicy enforcement configured");
+        }
+        Self {
+            configured,
+            cached: Mutex::new(None),
+        }
+    }
+
+    pub async fn check(&self, client: &Client) -> Enforcement {
+        if let Some(ref c) = self.configured {
+            return c.clone();
+        }
+        if let Some((at, ref e)) = *self.cached.lock().unwrap_or_else(|e| e.into_inner())
+            && at.elapsed() < CACHE_TTL
+        {
+            return e.clone();
+        }
+        let enforcement = detect(client).await;
+        *self.cached.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some((Instant::now(), enforcement.clone()));
+        enforcement
+    }
+}
+
+async fn detect(client: &Client) -> Enforcement {
+    match Api::<DaemonSet>::all(client.clone())
+        .list_metadata(&ListParams::default())
+        .await
+    {
+        Ok(list) => {
+            let names: Vec<String> = list
+                .items
+                .into_iter()
+                .filter_map(|d| d.metadata.name)
+                .collect();
+            classify(names.iter().map(String::as_str))
+        }
+        Err(e) => {
+            warn!(error = %e, "could not list DaemonSets to detect the CNI");
+            Enforcement::Unknown(format!(
+                "Could not list DaemonSets to detect the CNI ({e}); set NETWORK_POLICY_ENFORCEMENT to report enforcement"
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_recognises_cnis() {
+        assert!(matches!(
+            classify(["coredns", "calico-node"]),
+            Enforcement::Enforced(m) if m.starts_with("Calico")
+        ));
+        // Canal is flannel plus Calico's policy engine.
+        assert!(matches!(
+            classify(["kube-flannel-ds", "canal"]),
+            Enforcement::Enforced(m) if m.starts_with("Canal")
+        ));
+        assert!(matches!(
+            classify(["kube-proxy", "kube-flannel-ds"]),
+            Enforcement::NotEnforced(m) if m.contains("flannel")
+        ));
+        assert!(matches!(classify(["kube-proxy"]), Enforcement::Unknown(_)));
+        // Only whole name segments count.
+        assert!(matches!(classify(["ciliumx"]), Enforcement::Unknown(_)));
+    }
+
+    #[test]
+    fn condition_status_follows_enforcement() {
+        let now = "2025-01-01T00:00:00Z";
+        assert_eq!(
+            Enforcement::Enforced("x".into()).condition(now).status,
+            "True"
+        );
+        assert_eq!(
+            Enforcement::NotEnforced("x".into()).condition(now).status,
+            "False"
+        );
+        let unknown = Enforcement::Unknown("x".into()).condition(now);
+        assert_eq!(unknown.status, "Unknown");
+        assert_eq!(unknown.condition_type, "NetworkPolicyEnforced");
+    }
+
+    #[test]
+    fn from_env_reads_override() {
+        temp_env::with_var("NETWORK_POLICY_ENFORCEMENT", Some("false"), || {
+            assert!(matches!(
+                EnforcementCheck::from_env().configured,
+                Some(Enforcement::NotEnforced(_))
+            ));
+        });
+        temp_env::with_var("NETWORK_POLICY_ENFORCEMENT", Some("auto"), || {
+            assert!(EnforcementCheck::from_env().configured.is_none());
+        });
+    }
+}
@@ -0,0 +1,250 @@
+//! Backups of a MediaStack's in-cluster NFS server, which holds the media
+//! itself. Snapshots are taken by the operator on its reconcile cadence;
+//! the remote rsync runs as a CronJob. Failures are reported in
+//! `status.nfsBackup` rather than failing the stack reconcile.
+
+use chrono::{DateTime, Utc};
+use k8s_openapi::api::batch::v1::{CronJob, Job};
+use kube::api::{Api, DeleteParams, DynamicObject, ListParams, Patch, PatchParams};
+use kube::discovery::ApiResource;
+use kube::{Client, Resource, ResourceExt};
+use servarr_crds::{MediaStack, NfsBackupStatus, NfsRsyncSpec, NfsSnapshotSpec};
+use servarr_resources::nfs_server;
+use tracing::{info, warn};
+
+fn snapshot_resource() -> ApiResource {
+    ApiResource {
+        group: "snapshot.storage.k8s.io".into(),
+        version: "v1".into(),
+        api_version: "snapshot.storage.k8s.io/v1".into(),
+        kind: "VolumeSnapshot".into(),
+        plural: "volumesnapshots".into(),
+    }
+}
+
+/// Whether a run is due at `now` given the previous one at `last`.
+fn is_due(schedule: &cron::Schedule, last: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    match last {
+        None => true,
+        Some(last) => schedule.after(&last).next().is_some_and(|next| next <= now),
+    }
+}
+
+fn is_not_found(e: &kube::Error) -> bool {
+    matches!(e, kube::Error::Api(e) if e.code == 404)
+}
+
+/// Apply or remove the stack's backup resources and report their state.
+/// Returns `None` when the stack has no NFS backups configured.
+pub async fn reconcile(
+    client: &Client,
+    stack: &MediaStack,
+    name: &str,
+    ns: &str,
+    pp: &PatchParams,
+) -> Option<NfsBackupStatus> {
+    let backup = stack
+        .spec
+        .nfs
+        .as_ref()
+        .filter(|n| n.deploy_in_cluster())
+        .and_then(|n| n.backup.as_ref());
+    let rsync = backup.and_then(|b| b.rsync.as_ref());
+    let snapshot = backup.and_then(|b| b.snapshot.as_ref());
+
+    let mut status = NfsBackupStatus::default();
+    let mut messages = Vec::new();
+
+    match rsync {
+        Some(rsync) => {
+            if let Err(e) = reconcile_rsync(client, stack, name, ns, pp, rsync, &mut status).await {
+                warn!(%name, error = %e, "NFS rsync backup failed to reconcile");
+                messages.push(format!("rsync: {e}"));
+            }
+        }
+        None => {
+            let cronjobs = Api::<CronJob>::namespaced(client.clone(), ns);
+            match cronjobs
+                .delete(&nfs_server::backup_name(name), &DeleteParams::background())
+                .await
+            {
+                Err(e) if !is_not_found(&e) => {
+                    warn!(%name, error = %e, "failed to delete NFS backup CronJob");
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if let Some(snapshot) = snapshot
+        && let Err(e) = reconcile_snapshots(client, name, ns, pp, snapshot, &mut status).await
+    {
+        warn!(%name, error = %e, "NFS snapshot failed");
+        messages.push(format!("snapshot: {e}"));
+    }
+
+    if !messages.is_empty() {
+        status.message = Some(messages.join("; "));
+    }
+    backup.map(|_| status)
+}
+
+async fn reconcile_rsync(
+    client: &Client,
+    stack: &MediaStack,
+    name: &str,
+    ns: &str,
+    pp: &PatchParams,
+    rsync: &NfsRsyncSpec,
+    status: &mut NfsBackupStatus,
+) -> Result<(), String> {
+    crate::schedule::parse(&rsync.schedule)?;
+
+    let owner_ref = stack
+        .controller_owner_ref(&())
+        .expect("stack should have UID");
+    let cronjob = nfs_server::build_backup_cronjob(name, ns, rsync, owner_ref);
+    let applied = Api::<CronJob>::namespaced(client.clone(), ns)
+        .patch(&nfs_server::backup_name(name), pp, &Patch::Apply(&cronjob))
+        .await
+        .map_err(|e| e.to_string())?;
+    status.last_sync_time = applied
+        .status
+        .and_then(|s| s.last_successful_time)
+        .map(|t| t.0.to_string());
+
+    let jobs = Api::<Job>::namespaced(client.clone(), ns)
+        .list(&ListParams::default().labels(&nfs_server::backup_selector(name)))
+        .await
+        .map_err(|e| e.to_string())?;
+    status.last_sync_result = jobs
+        .items
+        .iter()
+        .max_by_key(|j| j.creation_timestamp())
+        .map(job_result);
+    Ok(())
+}
+
+/// `Running`, `Succeeded`, or `Failed: <reason>` for an rsync Job.
+fn job_result(job: &Job) -> String {
+    let status = job.status.as_ref();
+    if status.and_then(|s| s.succeeded).unwrap_or(0) > 0 {
+        return "Succeeded".into();
+    }
+    let failed = status
+        .and_then(|s| s.conditions.as_ref())
+        .into_iter()
+        .flatten()
+        .find(|c| c.type_ == "Failed" && c.status == "True");
+    match failed {
+        Some(c) => format!(
+            "Failed: {}",
+            c.message
+                .as_deref()
+                .or(c.reason.as_deref())
+                .unwrap_or("job failed")
+        ),
+        None => "Running".into(),
+    }
+}
+
+async fn reconcile_snapshots(
+    client: &Client,
+    name: &str,
+    ns: &str,
+    pp: &PatchParams,
+    snapshot: &NfsSnapshotSpec,
+    status: &mut NfsBackupStatus,
+) -> Result<(), String> {
+    let schedule = crate::schedule::parse(&snapshot.schedule)?;
+    let api = Api::<DynamicObject>::namespaced_with(client.clone(), ns, &snapshot_resource());
+
+    let mut existing: Vec<(DateTime<Utc>, String)> = api
+        .list(&ListParams::default().labels(&nfs_server::snapshot_selector(name)))
+        .await
+        .map_err(|e| format!("listing VolumeSnapshots: {e}"))?
+        .items
+        .iter()
+        .filter_map(|s| {
+            let created = DateTime::from_timestamp(s.creation_timestamp()?.0.as_second(), 0)?;
+            Some((created, s.name_any()))
+        })
+        .collect();
+    existing.sort();
+
+    let now = Utc::now();
+    if is_due(&schedule, existing.last().map(|(t, _)| *t), now) {
+        let snapshot_name = format!("{name}-nfs-{}", now.format("%Y%m%d%H%M%S"));
+        let object = nfs_server::build_snapshot(name, ns, snapshot, &snapshot_name)
+            .ok_or("building VolumeSnapshot")?;
+        api.patch(&snapshot_name, pp, &Patch::Apply(&object))
+            .await
+            .map_err(|e| format!("creating VolumeSnapshot: {e}"))?;
+        info!(%name, snapshot = %snapshot_name, "created NFS VolumeSnapshot");
+        existing.push((now, snapshot_name));
+    }
+
+    let keep = snapshot.retention_count.max(1) as usize;
+    let excess = existing.len().saturating_sub(keep);
+    for (_, old) in existing.drain(..excess) {
+        info!(%name, snapshot = %old, "deleting expired NFS VolumeSnapshot");
+        match api.delete(&old, &DeleteParams::default()).await {
+            Err(e) if !is_not_found(&e) => {
+                return Err(format!("deleting VolumeSnapshot {old}: {e}"));
+            }
+            _ => {}
+        }
+    }
+
+    if let Some((time, newest)) = existing.last() {
+        status.last_snapshot = Some(newest.clone());
+        status.last_snapshot_time = Some(time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+    }
+    status.snapshots = existing.into_iter().map(|(_, n)| n).collect();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::batch::v1::{JobCondition, JobStatus};
+
+    #[test]
+    fn snapshot_due_after_next_scheduled_time() {
+        let schedule = crate::schedule::parse("0 4 * * *").unwrap();
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+
+        assert!(is_due(&schedule, None, at("2025-06-01T12:00:00Z")));
+        let last = Some(at("2025-06-01T04:00:00Z"));
+        assert!(!is_due(&schedule, last, at("2025-06-02T03:59:00Z")));
+        assert!(is_due(&schedule, last, at("2025-06-02T04:01:00Z")));
+    }
+
+    #[test]
+    fn job_result_reports_outcome() {
+        let job = |status: JobStatus| Job {
+            status: Some(status),
+            ..Default::default()
+        };
+        assert_eq!(job_result(&Job::default()), "Running");
+        assert_eq!(
+            job_result(&job(JobStatus {
+                succeeded: Some(1),
+                ..Default::default()
+            })),
+            "Succeeded"
+        );
+        assert_eq!(
+            job_result(&job(JobStatus {
+                conditions: Some(vec![JobCondition {
+                    type_: "Failed".into(),
+                    status: "True".into(),
+                    reason: Some("BackoffLimitExceeded".into()),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            })),
+            "Failed: BackoffLimitExceeded"
+        );
+    }
+}
@@ -0,0 +1,331 @@
+//! ServarrPolicy checks, run by the admission webhook against every
+//! ServarrApp and every app a MediaStack expands to.
+//!
+//! Policies only gate admission: objects created before a policy are
+//! checked on their next update, and the controllers never act on them.
+
+use kube::api::{Api, ListParams};
+use kube::{Client, CustomResourceExt, ResourceExt};
+use servarr_crds::{AppDefaults, ServarrAppSpec, ServarrPolicy};
+
+use crate::webhook::{parse_cpu, parse_memory};
+
+pub fn print_crd() -> anyhow::Result<()> {
+    let crd = ServarrPolicy::crd();
+    let yaml = serde_yaml::to_string(&crd)?;
+    println!("{yaml}");
+    Ok(())
+}
+
+/// The policies in `namespace`.
+pub async fn load(client: &Client, namespace: &str) -> Result<Vec<ServarrPolicy>, kube::Error> {
+    let api = Api::<ServarrPolicy>::namespaced(client.clone(), namespace);
+    Ok(api.list(&ListParams::default()).await?.items)
+}
+
+/// Ways `spec` violates `policy`, one message per violation.
+pub fn check(policy: &ServarrPolicy, spec: &ServarrAppSpec) -> Vec<String> {
+    let rules = &policy.spec;
+    let mut violations = Vec::new();
+
+    if !rules.allows_app(&spec.app) {
+        violations.push(format!("app type {} is not allowed", spec.app));
+    }
+
+    if let Some(ref np) = rules.network_policy {
+        let config = spec.network_policy_config.as_ref();
+        if np.required && config.is_none() && spec.network_policy == Some(false) {
+            violations.push("networkPolicy must not be disabled".into());
+        }
+        if np.deny_internet_egress && config.is_some_and(|c| c.allow_internet_egress) {
+            violations.push("networkPolicyConfig.allowInternetEgress is not allowed".into());
+        }
+        let denied = config
+            .map(|c| c.denied_cidr_blocks.as_slice())
+            .unwrap_or_default();
+        for cidr in &np.denied_cidr_blocks {
+            if !denied.contains(cidr) {
+                violations.push(format!(
+                    "networkPolicyConfig.deniedCidrBlocks must include {cidr}"
+                ));
+            }
+        }
+    }
+
+    if let Some(ref rp) = rules.resources {
+        let defaults = AppDefaults::for_app(&spec.app);
+        let limits = &spec
+            .resources
+            .as_ref()
+            .unwrap_or(&defaults.resources)
+            .limits;
+        // The app container, then each extra container, by field path.
+        let mut checked = vec![(
+            "resources.limits".to_string(),
+            limits.cpu.clone(),
+            limits.memory.clone(),
+        )];
+        for (field, containers) in [
+            ("extraContainers", &spec.extra_containers),
+            ("extraInitContainers", &spec.extra_init_containers),
+        ] {
+            for c in containers {
+                let limit = |resource: &str| {
+                    c.resources
+                        .as_ref()
+                        .and_then(|r| r.limits.as_ref())
+                        .and_then(|l| l.get(resource))
+                        .map(|q| q.0.clone())
+                        .unwrap_or_default()
+                };
+                checked.push((
+                    format!("{field}[{}].resources.limits", c.name),
+                    limit("cpu"),
+                    limit("memory"),
+                ));
+            }
+        }
+        for (path, cpu, memory) in &checked {
+            if rp.require_limits {
+                if cpu.is_empty() {
+                    violations.push(format!("{path}.cpu is required"));
+                }
+                if memory.is_empty() {
+                    violations.push(format!("{path}.memory is required"));
+                }
+            }
+            if let Some(max) = rp.max_cpu.as_deref()
+                && let (Some(limit), Some(max_m)) = (parse_cpu(cpu), parse_cpu(max))
+                && limit > max_m
+            {
+                violations.push(format!("{path}.cpu {cpu} exceeds the maximum of {max}"));
+            }
+            if let Some(max) = rp.max_memory.as_deref()
+                && let (Some(limit), Some(max_b)) = (parse_memory(memory), parse_memory(max))
+                && limit > max_b
+            {
+                violations.push(format!(
+                    "{path}.memory {memory} exceeds the maximum of {max}"
+                ));
+            }
+        }
+    }
+
+    let nfs_mounts = spec.persistence.iter().flat_map(|p| &p.nfs_mounts);
+    for mount in nfs_mounts {
+        if rules.denied_nfs_servers.contains(&mount.server) {
+            violations.push(format!(
+                "nfsMounts[{}]: NFS server {} is not allowed",
+                mount.name, mount.server
+            ));
+        }
+    }
+
+    if rules.deny_host_path {
+        for patch in &spec.patches {
+            if patch.patch.contains("hostPath") {
+                violations.push(format!(
+                    "patches for {}: hostPath volumes are not allowed",
+                    patch.target.kind
+                ));
+            }
+        }
+        if spec
+            .unsupported_overrides
+            .as_ref()
+            .and_then(|o| o.pod_spec.as_ref())
+            .is_some_and(has_host_path)
+        {
+            violations
+                .push("unsupportedOverrides.podSpec: hostPath volumes are not allowed".into());
+        }
+    }
+
+    let name = policy.name_any();
+    violations
+        .into_iter()
+        .map(|v| format!("ServarrPolicy {name}: {v}"))
+        .collect()
+}
+
+/// Whether any object in `value` has a `hostPath` key.
+fn has_host_path(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.contains_key("hostPath") || map.values().any(has_host_path)
+        }
+        serde_json::Value::Array(items) => items.iter().any(has_host_path),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use servarr_crds::*;
+
+    fn policy(spec: ServarrPolicySpec) -> ServarrPolicy {
+        ServarrPolicy::new("tenant", spec)
+    }
+
+    fn app(app: AppType) -> ServarrAppSpec {
+        ServarrAppSpec {
+            app,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn print_crd_returns_ok() {
+        assert!(print_crd().is_ok());
+    }
+
+    #[test]
+    fn empty_policy_allows_everything() {
+        let p = policy(ServarrPolicySpec::default());
+        assert!(check(&p, &app(AppType::Sonarr)).is_empty());
+    }
+
+    #[test]
+    fn allowed_apps_and_network_policy() {
+        let p = policy(ServarrPolicySpec {
+            allowed_apps: vec![AppType::Sonarr, AppType::Radarr],
+            network_policy: Some(NetworkPolicyRequirements {
+                required: true,
+                deny_internet_egress: true,
+                denied_cidr_blocks: vec!["10.0.0.0/8".into()],
+            }),
+            ..Default::default()
+        });
+
+        let mut spec = app(AppType::Transmission);
+        spec.network_policy = Some(false);
+        let violations = check(&p, &spec);
+        assert_eq!(violations.len(), 3, "{violations:?}");
+        assert!(violations[0].starts_with("ServarrPolicy tenant: app type"));
+        assert!(violations[1].contains("networkPolicy must not be disabled"));
+        assert!(violations[2].contains("must include 10.0.0.0/8"));
+
+        let mut spec = app(AppType::Sonarr);
+        spec.network_policy_config = Some(NetworkPolicyConfig {
+            allow_internet_egress: true,
+            denied_cidr_blocks: vec!["10.0.0.0/8".into()],
+            ..Default::default()
+        });
+        let violations = check(&p, &spec);
+        assert_eq!(violations.len(), 1, "{violations:?}");
+        assert!(violations[0].contains("allowInternetEgress is not allowed"));
+    }
+
+    #[test]
+    fn resource_limits() {
+        let p = policy(ServarrPolicySpec {
+            resources: Some(ResourceRequirementsPolicy {
+                require_limits: true,
+                max_cpu: Some("2".into()),
+                max_memory: Some("2Gi".into()),
+            }),
+            ..Default::default()
+        });
+
+        // The app type's default limits satisfy the policy.
+        assert!(check(&p, &app(AppType::Sonarr)).is_empty());
+
+        let mut spec = app(AppType::Plex);
+        spec.resources = Some(ResourceRequirements {
+            limits: ResourceList {
+                cpu: "4".into(),
+                memory: String::new(),
+            },
+            ..Default::default()
+        });
+        let violations = check(&p, &spec);
+        assert_eq!(violations.len(), 2, "{violations:?}");
+        assert!(violations[0].contains("resources.limits.memory is required"));
+        assert!(violations[1].contains("cpu 4 exceeds the maximum of 2"));
+    }
+
+    #[test]
+    fn resource_limits_cover_extra_containers() {
+        use k8s_openapi::api::core::v1::{Container, ResourceRequirements as Resources};
+        use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+
+        let p = policy(ServarrPolicySpec {
+            resources: Some(ResourceRequirementsPolicy {
+                require_limits: true,
+                max_cpu: Some("2".into()),
+                max_memory: Some("2Gi".into()),
+            }),
+            ..Default::default()
+        });
+
+        let mut spec = app(AppType::Sonarr);
+        spec.extra_containers = vec![Container {
+            name: "vector".into(),
+            ..Default::default()
+        }];
+        spec.extra_init_containers = vec![Container {
+            name: "warmup".into(),
+            resources: Some(Resources {
+                limits: Some(
+                    [
+                        ("cpu".to_string(), Quantity("500m".into())),
+                        ("memory".to_string(), Quantity("4Gi".into())),
+                    ]
+                    .into(),
+                ),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }];
+        let violations = check(&p, &spec);
+        assert_eq!(violations.len(), 3, "{violations:?}");
+        assert!(violations[0].contains("extraContainers[vector].resources.limits.cpu is required"));
+        assert!(
+            violations[1].contains("extraContainers[vector].resources.limits.memory is required")
+        );
+        assert!(violations[2].contains(
+            "extraInitContainers[warmup].resources.limits.memory 4Gi exceeds the maximum of 2Gi"
+        ));
+    }
+
+    #[test]
+    fn nfs_servers_and_host_paths() {
+        let p = policy(ServarrPolicySpec {
+            denied_nfs_servers: vec!["nas.example.com".into()],
+            deny_host_path: true,
+            ..Default::default()
+        });
+
+        let mut spec = app(AppType::Sonarr);
+        spec.persistence = Some(PersistenceSpec {
+            volumes: vec![],
+            nfs_mounts: vec![NfsMount {
+                name: "media".into(),
+                server: "nas.example.com".into(),
+                path: "/export/media".into(),
+                mount_path: "/media".into(),
+                read_only: false,
+            }],
+            verify_mounts: false,
+        });
+        spec.patches = vec![ResourcePatch {
+            target: PatchTarget {
+                kind: "Deployment".into(),
+                name: None,
+            },
+            patch_type: PatchType::StrategicMerge,
+            patch: "spec:\n  template:\n    spec:\n      volumes:\n        - name: host\n          hostPath:\n            path: /\n".into(),
+        }];
+        spec.unsupported_overrides = Some(UnsupportedOverrides {
+            pod_spec: Some(serde_json::json!({
+                "volumes": [{"name": "dev", "hostPath": {"path": "/dev"}}]
+            })),
+        });
+        let violations = check(&p, &spec);
+        assert_eq!(violations.len(), 3, "{violations:?}");
+        assert!(violations[0].contains("NFS server nas.example.com is not allowed"));
+        assert!(violations[1].contains("patches for Deployment"));
+        assert!(violations[2].contains("unsupportedOverrides.podSpec"));
+    }
+}
@@ -0,0 +1,286 @@
+//! `servarr-operator port-forward`: reach a ServarrApp's web UI from the
+//! local machine without looking up its Service, pod, or port by hand.
+
+use anyhow::{Context as _, bail};
+use k8s_openapi::api::core::v1::Pod;
+use kube::api::{Api, ListParams};
+use kube::{Client, ResourceExt};
+use servarr_crds::{AppDefaults, AppType, ServarrApp};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// The port to forward: the app's first Service port, and the container port
+/// behind it.
+#[derive(Debug, PartialEq)]
+pub struct ForwardTarget {
+    pub service_port: u16,
+    pub container_port: u16,
+}
+
+pub fn target(app: &ServarrApp) -> anyhow::Result<ForwardTarget> {
+    let defaults = AppDefaults::for_app(&app.spec.app);
+    let svc = app.spec.service.as_ref().unwrap_or(&defaults.service);
+    let Some(port) = svc.ports.first() else {
+        bail!("{} has no service ports", app.name_any());
+    };
+    let as_u16 = |p: i32| u16::try_from(p).with_context(|| format!("invalid port {p}"));
+    Ok(ForwardTarget {
+        service_port: as_u16(port.port)?,
+        container_port: as_u16(port.container_port.unwrap_or(port.port))?,
+    })
+}
+
+/// Whether the app's web UI accepts its API key as an `apikey` query
+/// parameter.
+fn accepts_apikey_param(app_type: &AppType) -> bool {
+    matches!(
+        app_type,
+        AppType::Sonarr
+            | AppType::Radarr
+            | AppType::Lidarr
+            | AppType::Readarr
+            | AppType::Whisparr
+            | AppType::Prowlarr
+            | AppType::Sabnzbd
+    )
+}
+
+/// Local URL for the forwarded app, with the API key appended when given
+/// and the app supports it.
+pub fn local_url(app_type: &AppType, port: u16, api_key: Option<&str>) -> String {
+    match api_key {
+        Some(key) if accepts_apikey_param(app_type) => {
+            format!("http://127.0.0.1:{port}/?apikey={key}")
+        }
+        _ => format!("http://127.0.0.1:{port}/"),
+    }
+}
+
+/// Pick a running, ready pod belonging to `app`.
+async fn ready_pod(client: &Client, ns: &str, app: &ServarrApp) -> anyhow::Result<String> {
+    let selector = servarr_resources::common::selector_labels(app)
+        .iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join(",");
+    let pods = Api::<Pod>::namespaced(client.clone(), ns)
+        .list(&ListParams::default().labels(&selector))
+        .await?;
+    pods.items
+        .iter()
+        .find(|p| {
+            p.status
+                .as_ref()
+                .and_then(|s| s.conditions.as_ref())
+                .is_some_and(|c| c.iter().any(|c| c.type_ == "Ready" && c.status == "True"))
+        })
+        .map(|p| p.name_any())
+        .with_context(|| format!("no ready pod found for {} ({selector})", app.name_any()))
+}
+
+/// A running port-forward to one of an app's pods. Connections are accepted
+/// in the background until the value is dropped.
+pub struct Forward {
+    pub local_port: u16,
+    pub pod: String,
+    pub container_port: u16,
+    task: JoinHandle<anyhow::Result<()>>,
+}
+
+impl Forward {
+    /// Start forwarding `127.0.0.1:local_port` (`0` picks a free port) to a
+    /// ready pod of `app`.
+    pub async fn start(
+        client: &Client,
+        ns: &str,
+        app: &ServarrApp,
+        local_port: u16,
+    ) -> anyhow::Result<Self> {
+        let target = target(app)?;
+        let pod = ready_pod(client, ns, app).await?;
+        let listener = TcpListener::bind(("127.0.0.1", local_port))
+            .await
+            .context("failed to bind local port")?;
+        let local_port = listener.local_addr()?.port();
+        let pods = Api::<Pod>::namespaced(client.clone(), ns);
+        let task = tokio::spawn(accept_loop(
+            listener,
+            pods,
+            pod.clone(),
+            target.container_port,
+        ));
+        Ok(Self {
+            local_port,
+            pod,
+            container_port: target.container_port,
+            task,
+        })
+    }
+
+    /// Block until the accept loop stops, which only happens on error.
+    pub async fn wait(&mut self) -> anyhow::Result<()> {
+        (&mut self.task).await?
+    }
+}
+
+impl Drop for Forward {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn accept_loop(
+    listener: TcpListener,
+    pods: Api<Pod>,
+    pod: String,
+    container_port: u16,
+) -> anyhow::Result<()> {
+    loop {
+        let (mut conn, peer) = listener.accept().await?;
+        let pods = pods.clone();
+        let pod = pod.clone();
+        tokio::spawn(async move {
+            let result = async {
+                let mut forwarder = pods.portforward(&pod, &[container_port]).await?;
+                let mut upstream = forwarder
+                    .take_stream(container_port)
+                    .context("port-forward stream unavailable")?;
+                tokio::io::copy_bidirectional(&mut conn, &mut upstream).await?;
+                drop(upstream);
+                forwarder.join().await?;
+                anyhow::Ok(())
+            }
+            .await;
+            if let Err(e) = result {
+                warn!(%peer, error = %e, "port-forward connection failed");
+            }
+        });
+    }
+}
+
+/// Fetch the ServarrApp `ns/name`.
+pub async fn get_app(client: &Client, ns: &str, name: &str) -> anyhow::Result<ServarrApp> {
+    Api::<ServarrApp>::namespaced(client.clone(), ns)
+        .get(name)
+        .await
+        .with_context(|| format!("ServarrApp {ns}/{name} not found"))
+}
+
+/// Forward `local_port` (default: the Service port) to the app's pod until
+/// interrupted. With `open`, print a URL carrying the API key and try to
+/// open it in a browser.
+pub async fn run(
+    client: Client,
+    ns: &str,
+    name: &str,
+    local_port: Option<u16>,
+    open: bool,
+) -> anyhow::Result<()> {
+    let app = get_app(&client, ns, name).await?;
+    let local_port = match local_port {
+        Some(port) => port,
+        None => target(&app)?.service_port,
+    };
+    let mut forward = Forward::start(&client, ns, &app, local_port).await?;
+
+    let api_key = match (open, app.spec.api_key_ref()) {
+        (true, Some((secret, key))) => {
+            match servarr_api::read_secret_key(&client, ns, secret, key).await {
+                Ok(key) => Some(key),
+                Err(e) => {
+                    warn!(%secret, error = %e, "could not read API key, printing plain URL");
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+    let url = local_url(&app.spec.app, forward.local_port, api_key.as_deref());
+    println!(
+        "Forwarding 127.0.0.1:{} -> {ns}/{}:{}",
+        forward.local_port, forward.pod, forward.container_port
+    );
+    println!("{url}");
+    if open {
+        open_browser(&url);
+    }
+
+    forward.wait().await
+}
+
+fn open_browser(url: &str) {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    if let Err(e) = std::process::Command::new(opener).arg(url).spawn() {
+        info!(%opener, error = %e, "could not launch a browser; open the URL above manually");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use servarr_crds::{ServarrAppSpec, ServicePort, ServiceSpec};
+
+    fn app(app_type: AppType) -> ServarrApp {
+        ServarrApp::new(
+            "test",
+            ServarrAppSpec {
+                app: app_type,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn target_uses_default_service_port() {
+        assert_eq!(
+            target(&app(AppType::Sonarr)).unwrap(),
+            ForwardTarget {
+                service_port: 8989,
+                container_port: 8989,
+            }
+        );
+    }
+
+    #[test]
+    fn target_prefers_container_port_override() {
+        let mut app = app(AppType::Radarr);
+        app.spec.service = Some(ServiceSpec {
+            ports: vec![ServicePort {
+                name: "http".into(),
+                port: 80,
+                protocol: "TCP".into(),
+                container_port: Some(7878),
+                host_port: None,
+            }],
+            ..Default::default()
+        });
+        assert_eq!(
+            target(&app).unwrap(),
+            ForwardTarget {
+                service_port: 80,
+                container_port: 7878,
+            }
+        );
+    }
+
+    #[test]
+    fn local_url_appends_api_key_where_supported() {
+        assert_eq!(
+            local_url(&AppType::Sonarr, 8989, Some("abc")),
+            "http://127.0.0.1:8989/?apikey=abc"
+        );
+        assert_eq!(
+            local_url(&AppType::Jellyfin, 8096, Some("abc")),
+            "http://127.0.0.1:8096/"
+        );
+        assert_eq!(
+            local_url(&AppType::Radarr, 7878, None),
+            "http://127.0.0.1:7878/"
+        );
+    }
+}
@@ -0,0 +1,402 @@
+//! Prowlarr indexers declared in `appConfig.indexers`.
+//!
+//! A new indexer starts from the matching entry of Prowlarr's indexer schema,
+//! so fields that aren't declared keep their defaults; an existing one (by
+//! name) is updated in place. Prowlarr masks secret fields in responses, so
+//! a field whose current value is the mask is sent on every update but never
+//! compared.
+
+use std::collections::BTreeMap;
+
+use servarr_api::ApiError;
+use servarr_api::ProwlarrClient;
+use servarr_api::prowlarr::{ProwlarrAppField, ProwlarrIndexer as Indexer, ProwlarrTag};
+use servarr_crds::{ProwlarrConfig, ProwlarrIndexer};
+
+/// What Prowlarr returns in place of a secret field's value.
+const MASK: &str = "********";
+
+/// The settings of an indexer the operator manages, with tags and the app
+/// profile resolved to Prowlarr IDs.
+#[derive(Debug, Clone)]
+pub struct Desired {
+    pub name: String,
+    pub enable: bool,
+    pub priority: Option<i32>,
+    pub app_profile_id: Option<i64>,
+    pub tags: Vec<i64>,
+    pub fields: Vec<ProwlarrAppField>,
+}
+
+/// The declared fields of `spec`, with the keys of its `fieldsSecret`
+/// (given as `secret`) set after them.
+pub fn fields(
+    spec: &ProwlarrIndexer,
+    secret: Option<&BTreeMap<String, String>>,
+) -> Vec<ProwlarrAppField> {
+    let mut fields: Vec<ProwlarrAppField> = spec
+        .fields
+        .iter()
+        .map(|(name, value)| ProwlarrAppField {
+            name: name.clone(),
+            value: value.clone(),
+        })
+        .collect();
+    for (name, value) in secret.into_iter().flatten() {
+        fields.retain(|f| &f.name != name);
+        fields.push(ProwlarrAppField {
+            name: name.clone(),
+            value: value.clone().into(),
+        });
+    }
+    fields
+}
+
+/// The schema entry a new indexer is created from: the one for its
+/// implementation and, when given, definition.
+pub fn schema_entry<'a>(schema: &'a [Indexer], spec: &ProwlarrIndexer) -> Option<&'a Indexer> {
+    schema.iter().find(|entry| {
+        entry
+            .implementation
+            .eq_ignore_ascii_case(&spec.implementation)
+            && spec
+                .definition_name
+                .as_deref()
+                .is_none_or(|d| entry.definition_name.eq_ignore_ascii_case(d))
+    })
+}
+
+fn field<'a>(indexer: &'a Indexer, name: &str) -> Option<&'a serde_json::Value> {
+    indexer
+        .fields
+        .iter()
+        .find(|f| f.name == name)
+        .map(|f| &f.value)
+}
+
+fn sorted(tags: &[i64]) -> Vec<i64> {
+    let mut tags = tags.to_vec();
+    tags.sort_unstable();
+    tags
+}
+
+/// Whether `existing` differs from `desired` in anything `desired` sets.
+pub fn needs_update(existing: &Indexer, desired: &Desired) -> bool {
+    existing.enable != desired.enable
+        || desired.priority.is_some_and(|p| p != existing.priority)
+        || desired
+            .app_profile_id
+            .is_some_and(|id| id != existing.app_profile_id)
+        || sorted(&existing.tags) != sorted(&desired.tags)
+        || desired
+            .fields
+            .iter()
+            .any(|f| match field(existing, &f.name) {
+                Some(serde_json::Value::String(current)) if current == MASK => false,
+                current => current != Some(&f.value),
+            })
+}
+
+/// `base` (an existing indexer or a schema entry) with `desired` applied.
+pub fn merge(base: &Indexer, desired: &Desired) -> Indexer {
+    let mut indexer = base.clone();
+    indexer.name = desired.name.clone();
+    indexer.enable = desired.enable;
+    if let Some(priority) = desired.priority {
+        indexer.priority = priority;
+    }
+    if let Some(id) = desired.app_profile_id {
+        indexer.app_profile_id = id;
+    }
+    indexer.tags = desired.tags.clone();
+    for f in &desired.fields {
+        match indexer.fields.iter_mut().find(|e| e.name == f.name) {
+            Some(e) => e.value = f.value.clone(),
+            None => indexer.fields.push(f.clone()),
+        }
+    }
+    indexer
+}
+
+/// What a sync pass did.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SyncOutcome {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    /// `(indexer, error)` for indexers that could not be applied.
+    pub failed: Vec<(String, String)>,
+}
+
+impl SyncOutcome {
+    /// One-line summary for the `IndexersSynced` condition.
+    pub fn message(&self) -> String {
+        let mut parts = Vec::new();
+        for (label, names) in [
+            ("added", &self.added),
+            ("updated", &self.updated),
+            ("removed", &self.removed),
+        ] {
+            if !names.is_empty() {
+                parts.push(format!("{label}: {}", names.join(", ")));
+            }
+        }
+        for (name, error) in &self.failed {
+            parts.push(format!("{name} failed: {error}"));
+        }
+        if parts.is_empty() {
+            "Indexers match appConfig.indexers".to_string()
+        } else {
+            parts.join("; ")
+        }
+    }
+}
+
+/// The IDs of `labels`, creating the tags Prowlarr lacks.
+async fn tag_ids(
+    api: &ProwlarrClient,
+    known: &mut Vec<ProwlarrTag>,
+    labels: &[String],
+) -> Result<Vec<i64>, ApiError> {
+    let mut ids = Vec::new();
+    for label in labels {
+        let id = match known.iter().find(|t| t.label.eq_ignore_ascii_case(label)) {
+            Some(tag) => tag.id,
+            None => {
+                let tag = api.create_tag(label).await?;
+                known.push(tag.clone());
+                tag.id
+            }
+        };
+        ids.push(id);
+    }
+    Ok(ids)
+}
+
+/// Create or update the indexers in `config` and, with `autoRemove`,
+/// remove the rest. `secrets` holds the data of each `fieldsSecret` by name.
+pub async fn sync(
+    api: &ProwlarrClient,
+    config: &ProwlarrConfig,
+    secrets: &BTreeMap<String, BTreeMap<String, String>>,
+) -> Result<SyncOutcome, ApiError> {
+    let existing = api.list_indexers().await?;
+    let profiles = api.list_app_profiles().await?;
+    let mut tags = if config.indexers.iter().any(|i| !i.tags.is_empty()) {
+        api.list_tags().await?
+    } else {
+        Vec::new()
+    };
+    let mut schema: Option<Vec<Indexer>> = None;
+    let mut outcome = SyncOutcome::default();
+
+    for spec in &config.indexers {
+        if let Some(e) = servarr_api::faults::sync("indexers", &spec.name) {
+            outcome.failed.push((spec.name.clone(), e.to_string()));
+            continue;
+        }
+        let app_profile_id = match spec.app_profile.as_deref() {
+            Some(name) => match profiles.iter().find(|p| p.name == name) {
+                Some(profile) => Some(profile.id),
+                None => {
+                    outcome
+                        .failed
+                        .push((spec.name.clone(), format!("no app profile named {name}")));
+                    continue;
+                }
+            },
+            None => None,
+        };
+        let tag_ids = match tag_ids(api, &mut tags, &spec.tags).await {
+            Ok(ids) => ids,
+            Err(e) => {
+                outcome.failed.push((spec.name.clone(), e.to_string()));
+                continue;
+            }
+        };
+        let desired = Desired {
+            name: spec.name.clone(),
+            enable: spec.enable,
+            priority: spec.priority,
+            app_profile_id,
+            tags: tag_ids,
+            fields: fields(
+                spec,
+                spec.fields_secret.as_deref().and_then(|s| secrets.get(s)),
+            ),
+        };
+
+        let result = match existing.iter().find(|i| i.name == spec.name) {
+            Some(current) => {
+                if !needs_update(current, &desired) {
+                    continue;
+                }
+                api.update_indexer(current.id, &merge(current, &desired))
+                    .await
+                    .map(|_| &mut outcome.updated)
+            }
+            None => {
+                if schema.is_none() {
+                    schema = Some(api.indexer_schema().await?);
+                }
+                let Some(entry) = schema_entry(schema.as_deref().unwrap_or_default(), spec) else {
+                    outcome.failed.push((
+                        spec.name.clone(),
+                        format!("Prowlarr has no {} indexer", spec.implementation),
+                    ));
+                    continue;
+                };
+                let mut indexer = merge(entry, &desired);
+                indexer.id = 0;
+                if desired.app_profile_id.is_none()
+                    && let Some(profile) = profiles.first()
+                {
+                    indexer.app_profile_id = profile.id;
+                }
+                api.add_indexer(&indexer).await.map(|_| &mut outcome.added)
+            }
+        };
+        match result {
+            Ok(list) => list.push(spec.name.clone()),
+            Err(e) => outcome.failed.push((spec.name.clone(), e.to_string())),
+        }
+    }
+
+    if config.auto_remove {
+        for indexer in &existing {
+            if config.indexers.iter().any(|i| i.name == indexer.name) {
+                continue;
+            }
+            match api.delete_indexer(indexer.id).await {
+                Ok(()) => outcome.removed.push(indexer.name.clone()),
+                Err(e) => outcome.failed.push((indexer.name.clone(), e.to_string())),
+            }
+        }
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec() -> ProwlarrIndexer {
+        ProwlarrIndexer {
+            name: "NZBgeek".into(),
+            implementation: "Newznab".into(),
+            definition_name: Some("nzbgeek".into()),
+            fields: BTreeMap::from([("baseUrl".into(), json!("https://api.nzbgeek.info"))]),
+            fields_secret: Some("nzbgeek".into()),
+            tags: Vec::new(),
+            enable: true,
+            priority: Some(10),
+            app_profile: None,
+        }
+    }
+
+    fn indexer(definition: &str, fields: Vec<(&str, serde_json::Value)>) -> Indexer {
+        Indexer {
+            id: 7,
+            name: "NZBgeek".into(),
+            implementation: "Newznab".into(),
+            config_contract: "NewznabSettings".into(),
+            definition_name: definition.into(),
+            enable: true,
+            priority: 25,
+            app_profile_id: 1,
+            fields: fields
+                .into_iter()
+                .map(|(name, value)| ProwlarrAppField {
+                    name: name.into(),
+                    value,
+                })
+                .collect(),
+            tags: Vec::new(),
+        }
+    }
+
+    fn desired() -> Desired {
+        let secret = BTreeMap::from([("apiKey".to_string(), "abc123".to_string())]);
+        Desired {
+            name: "NZBgeek".into(),
+            enable: true,
+            priority: Some(10),
+            app_profile_id: None,
+            tags: vec![3],
+            fields: fields(&spec(), Some(&secret)),
+        }
+    }
+
+    #[test]
+    fn secret_keys_override_declared_fields() {
+        let mut spec = spec();
+        spec.fields.insert("apiKey".into(), json!("in-the-spec"));
+        let secret = BTreeMap::from([("apiKey".to_string(), "abc123".to_string())]);
+        let fields = fields(&spec, Some(&secret));
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[1].name, "apiKey");
+        assert_eq!(fields[1].value, json!("abc123"));
+    }
+
+    #[test]
+    fn schema_entry_matches_definition() {
+        let schema = [
+            indexer("generic", vec![]),
+            indexer("NZBgeek", vec![("apiPath", json!("/api"))]),
+        ];
+        assert_eq!(
+            schema_entry(&schema, &spec()).map(|e| e.definition_name.as_str()),
+            Some("NZBgeek")
+        );
+        let mut generic = spec();
+        generic.definition_name = None;
+        assert_eq!(
+            schema_entry(&schema, &generic).map(|e| e.definition_name.as_str()),
+            Some("generic")
+        );
+        generic.implementation = "Torznab".into();
+        assert!(schema_entry(&schema, &generic).is_none());
+    }
+
+    #[test]
+    fn masked_fields_are_not_compared() {
+        let mut existing = indexer(
+            "nzbgeek",
+            vec![
+                ("baseUrl", json!("https://api.nzbgeek.info")),
+                ("apiPath", json!("/api")),
+                ("apiKey", json!(MASK)),
+            ],
+        );
+        existing.priority = 10;
+        existing.tags = vec![3];
+        assert!(!needs_update(&existing, &desired()));
+
+        existing.priority = 25;
+        assert!(needs_update(&existing, &desired()));
+        let merged = merge(&existing, &desired());
+        assert_eq!(merged.priority, 10);
+        assert_eq!(merged.app_profile_id, 1);
+        assert_eq!(field(&merged, "apiKey"), Some(&json!("abc123")));
+        assert_eq!(field(&merged, "apiPath"), Some(&json!("/api")));
+    }
+
+    #[test]
+    fn message_summarizes_changes() {
+        let outcome = SyncOutcome {
+            added: vec!["NZBgeek".into()],
+            removed: vec!["1337x".into()],
+            failed: vec![("EZTV".into(), "no app profile named Slow".into())],
+            ..Default::default()
+        };
+        assert_eq!(
+            outcome.message(),
+            "added: NZBgeek; removed: 1337x; EZTV failed: no app profile named Slow"
+        );
+        assert_eq!(
+            SyncOutcome::default().message(),
+            "Indexers match appConfig.indexers"
+        );
+    }
+}
@@ -0,0 +1,325 @@
+//! The parts of `prowlarrSync` beyond registering apps: the namespaces apps
+//! are discovered in, the sync level, per-app indexer categories
+//! (`syncCategories` / `animeSyncCategories` application fields), and app
+//! profiles.
+//!
+//! An existing registration is updated in place, so fields the operator
+//! doesn't manage keep Prowlarr's value. Prowlarr may mask the app's API key
+//! in responses, so it is sent on every update but never compared.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+use servarr_api::ApiError;
+use servarr_api::ProwlarrClient;
+use servarr_api::prowlarr::{ProwlarrApp, ProwlarrAppField, ProwlarrAppProfile};
+use servarr_crds::{AppType, ProwlarrSyncSpec};
+
+/// Fields whose values are left out of the comparison.
+const SECRET_FIELDS: &[&str] = &["apiKey"];
+
+/// The namespaces to discover apps in: `namespaceScope`, `namespaces`, and
+/// `selected` (those matching `namespaceSelector`), sorted and each once.
+/// `own`, the Prowlarr CR's namespace, when none of them is set.
+pub fn target_namespaces(spec: &ProwlarrSyncSpec, own: &str, selected: &[String]) -> Vec<String> {
+    let mut namespaces: Vec<String> = spec
+        .namespace_scope
+        .iter()
+        .chain(&spec.namespaces)
+        .chain(selected)
+        .cloned()
+        .collect();
+    if namespaces.is_empty() && spec.namespace_selector.is_empty() {
+        namespaces.push(own.to_string());
+    }
+    namespaces.sort();
+    namespaces.dedup();
+    namespaces
+}
+
+/// `labels` as a label selector, `key=value` pairs joined by commas.
+pub fn label_selector(labels: &BTreeMap<String, String>) -> String {
+    labels
+        .iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Why apps in `namespace` could not be discovered, naming the missing
+/// permission when the API server refused the list.
+pub fn unwatchable(namespace: &str, e: &kube::Error) -> String {
+    match e {
+        kube::Error::Api(status) if status.code == 403 => format!(
+            "namespace {namespace} is not watchable: the operator may not list ServarrApps there \
+             (install it with watchAllNamespaces, or grant its ServiceAccount a Role in {namespace})"
+        ),
+        e => format!("namespace {namespace}: failed to list ServarrApps: {e}"),
+    }
+}
+
+/// Whether `url`, a registration's base URL, points at a Service in
+/// `namespace`.
+pub fn in_namespace(url: &str, namespace: &str) -> bool {
+    url.contains(&format!(".{namespace}.svc:")) || url.ends_with(&format!(".{namespace}.svc"))
+}
+
+/// The name to register an app as. When apps come from several namespaces,
+/// those outside Prowlarr's own get theirs appended, since two namespaces
+/// may each have a `sonarr`.
+pub fn registration_name(name: String, namespace: &str, own: &str, namespaces: usize) -> String {
+    if namespaces > 1 && namespace != own {
+        format!("{name}-{namespace}")
+    } else {
+        name
+    }
+}
+
+/// The category fields for an app of `app_type` and `instance`, from the
+/// mapping for that instance or else the one for the app type. Empty when
+/// neither exists, leaving Prowlarr's defaults.
+pub fn category_fields(
+    spec: &ProwlarrSyncSpec,
+    app_type: &AppType,
+    instance: Option<&str>,
+) -> Vec<ProwlarrAppField> {
+    let for_type = || spec.categories.iter().filter(|m| &m.app == app_type);
+    let mapping = for_type()
+        .find(|m| instance.is_some() && m.instance.as_deref() == instance)
+        .or_else(|| for_type().find(|m| m.instance.is_none()));
+    let Some(mapping) = mapping else {
+        return Vec::new();
+    };
+    let mut fields = vec![ProwlarrAppField {
+        name: "syncCategories".into(),
+        value: mapping.sync_categories.clone().into(),
+    }];
+    if *app_type == AppType::Sonarr {
+        fields.push(ProwlarrAppField {
+            name: "animeSyncCategories".into(),
+            value: mapping.anime_sync_categories.clone().into(),
+        });
+    }
+    fields
+}
+
+fn field<'a>(app: &'a ProwlarrApp, name: &str) -> Option<&'a Value> {
+    app.fields.iter().find(|f| f.name == name).map(|f| &f.value)
+}
+
+/// Whether `existing` differs from `desired` in its name, sync level, or a
+/// field `desired` sets.
+pub fn needs_update(existing: &ProwlarrApp, desired: &ProwlarrApp) -> bool {
+    existing.name != desired.name
+        || existing.sync_level != desired.sync_level
+        || desired
+            .fields
+            .iter()
+            .filter(|f| !SECRET_FIELDS.contains(&f.name.as_str()))
+            .any(|f| field(existing, &f.name) != Some(&f.value))
+}
+
+/// `existing` with `desired`'s name, sync level, and fields applied.
+pub fn merge(existing: &ProwlarrApp, desired: &ProwlarrApp) -> ProwlarrApp {
+    let mut app = existing.clone();
+    app.name = desired.name.clone();
+    app.sync_level = desired.sync_level.clone();
+    for f in &desired.fields {
+        match app.fields.iter_mut().find(|e| e.name == f.name) {
+            Some(e) => e.value = f.value.clone(),
+            None => app.fields.push(f.clone()),
+        }
+    }
+    app
+}
+
+/// Create or update each of the spec's app profiles, matched by name.
+/// Profiles not in the spec are left alone, since indexers may use them.
+/// Returns an error message per profile that could not be applied.
+pub async fn sync_app_profiles(
+    api: &ProwlarrClient,
+    spec: &ProwlarrSyncSpec,
+) -> Result<Vec<String>, ApiError> {
+    if spec.app_profiles.is_empty() {
+        return Ok(Vec::new());
+    }
+    let existing = api.list_app_profiles().await?;
+    let mut errors = Vec::new();
+    for profile in &spec.app_profiles {
+        let mut desired = ProwlarrAppProfile {
+            id: 0,
+            name: profile.name.clone(),
+            enable_rss: profile.enable_rss,
+            enable_automatic_search: profile.enable_automatic_search,
+            enable_interactive_search: profile.enable_interactive_search,
+            minimum_seeders: profile.minimum_seeders,
+        };
+        let result = match existing.iter().find(|p| p.name == profile.name) {
+            Some(current) => {
+                desired.id = current.id;
+                if *current == desired {
+                    continue;
+                }
+                api.update_app_profile(current.id, &desired).await
+            }
+            None => api.add_app_profile(&desired).await,
+        };
+        if let Err(e) = result {
+            errors.push(format!("app profile {}: {e}", profile.name));
+        }
+    }
+    Ok(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use servarr_crds::ProwlarrCategoryMapping;
+
+    #[test]
+    fn target_namespaces_default_to_prowlarrs_own() {
+        let mut spec = ProwlarrSyncSpec::default();
+        assert_eq!(target_namespaces(&spec, "media", &[]), vec!["media"]);
+
+        spec.namespace_scope = Some("tv".into());
+        spec.namespaces = vec!["movies".into(), "tv".into()];
+        assert_eq!(
+            target_namespaces(&spec, "media", &["anime".into()]),
+            vec!["anime", "movies", "tv"]
+        );
+
+        // A selector matching nothing means no namespaces, not the own one
+        let spec = ProwlarrSyncSpec {
+            namespace_selector: [("servarr.dev/prowlarr".into(), "shared".into())].into(),
+            ..Default::default()
+        };
+        assert!(target_namespaces(&spec, "media", &[]).is_empty());
+        assert_eq!(
+            label_selector(&spec.namespace_selector),
+            "servarr.dev/prowlarr=shared"
+        );
+    }
+
+    #[test]
+    fn forbidden_namespaces_name_the_missing_access() {
+        let forbidden = kube::Error::Api(
+            kube::core::Status::failure("servarrapps.servarr.dev is forbidden", "Forbidden")
+                .with_code(403)
+                .boxed(),
+        );
+        let message = unwatchable("tv", &forbidden);
+        assert!(
+            message.starts_with("namespace tv is not watchable"),
+            "{message}"
+        );
+        assert!(message.contains("watchAllNamespaces"), "{message}");
+    }
+
+    #[test]
+    fn registrations_from_other_namespaces_are_qualified() {
+        assert!(in_namespace("http://sonarr.tv.svc:8989", "tv"));
+        assert!(!in_namespace("http://sonarr.tv.svc:8989", "v"));
+        assert_eq!(
+            registration_name("sonarr".into(), "media", "media", 2),
+            "sonarr"
+        );
+        assert_eq!(
+            registration_name("sonarr".into(), "tv", "media", 2),
+            "sonarr-tv"
+        );
+        assert_eq!(
+            registration_name("sonarr".into(), "tv", "media", 1),
+            "sonarr"
+        );
+    }
+
+    fn spec() -> ProwlarrSyncSpec {
+        ProwlarrSyncSpec {
+            enabled: true,
+            categories: vec![
+                ProwlarrCategoryMapping {
+                    app: AppType::Sonarr,
+                    sync_categories: vec![5000, 5040],
+                    anime_sync_categories: vec![5070],
+                    ..Default::default()
+                },
+                ProwlarrCategoryMapping {
+                    app: AppType::Radarr,
+                    instance: Some("4k".into()),
+                    sync_categories: vec![2045],
+                    ..Default::default()
+                },
+                ProwlarrCategoryMapping {
+                    app: AppType::Radarr,
+                    sync_categories: vec![2000, 2040],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn app(fields: Vec<(&str, Value)>) -> ProwlarrApp {
+        ProwlarrApp {
+            id: 3,
+            name: "sonarr".into(),
+            sync_level: "fullSync".into(),
+            implementation: "Sonarr".into(),
+            config_contract: "SonarrSettings".into(),
+            fields: fields
+                .into_iter()
+                .map(|(name, value)| ProwlarrAppField {
+                    name: name.into(),
+                    value,
+                })
+                .collect(),
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn category_fields_prefer_instance_mapping() {
+        let spec = spec();
+        let sonarr = category_fields(&spec, &AppType::Sonarr, None);
+        assert_eq!(sonarr.len(), 2);
+        assert_eq!(sonarr[0].value, json!([5000, 5040]));
+        assert_eq!(sonarr[1].name, "animeSyncCategories");
+
+        let radarr_4k = category_fields(&spec, &AppType::Radarr, Some("4k"));
+        assert_eq!(radarr_4k.len(), 1);
+        assert_eq!(radarr_4k[0].value, json!([2045]));
+        let radarr_hd = category_fields(&spec, &AppType::Radarr, Some("hd"));
+        assert_eq!(radarr_hd[0].value, json!([2000, 2040]));
+
+        assert!(category_fields(&spec, &AppType::Lidarr, None).is_empty());
+    }
+
+    #[test]
+    fn update_ignores_masked_key_and_unmanaged_fields() {
+        let existing = app(vec![
+            ("baseUrl", json!("http://sonarr.media.svc:8989")),
+            ("apiKey", json!("********")),
+            ("prowlarrUrl", json!("http://prowlarr:9696")),
+            ("syncCategories", json!([5000, 5040])),
+        ]);
+        let mut desired = app(vec![
+            ("baseUrl", json!("http://sonarr.media.svc:8989")),
+            ("apiKey", json!("secret")),
+            ("syncCategories", json!([5000, 5040])),
+        ]);
+        assert!(!needs_update(&existing, &desired));
+
+        desired.fields[2].value = json!([5000]);
+        desired.sync_level = "addOnly".into();
+        assert!(needs_update(&existing, &desired));
+        let merged = merge(&existing, &desired);
+        assert_eq!(merged.sync_level, "addOnly");
+        assert_eq!(field(&merged, "apiKey"), Some(&json!("secret")));
+        assert_eq!(
+            field(&merged, "prowlarrUrl"),
+            Some(&json!("http://prowlarr:9696"))
+        );
+        assert_eq!(field(&merged, "syncCategories"), Some(&json!([5000])));
+    }
+}
@@ -0,0 +1,347 @@
+//! Sonarr/Radarr quality profiles declared in `appConfig.qualityProfiles`.
+//!
+//! A declared profile is built on top of the app's own copy when one with
+//! the same name exists, or on the blank `qualityprofile/schema` profile
+//! otherwise, so every field the operator doesn't manage (unlisted custom
+//! format scores, Radarr's language) keeps the app's value. Only when the result
+//! differs from what the app has is it written back.
+
+use serde_json::Value;
+use servarr_api::{ApiError, ServarrClient};
+use servarr_crds::QualityProfile;
+
+/// What a sync pass did with each declared profile.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SyncOutcome {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub unchanged: Vec<String>,
+    /// `(profile, error)` for profiles that could not be applied.
+    pub failed: Vec<(String, String)>,
+}
+
+impl SyncOutcome {
+    /// One-line summary for the `QualityProfilesSynced` condition.
+    pub fn message(&self) -> String {
+        let mut parts = Vec::new();
+        for (label, names) in [("created", &self.created), ("updated", &self.updated)] {
+            if !names.is_empty() {
+                parts.push(format!("{label}: {}", names.join(", ")));
+            }
+        }
+        if !self.unchanged.is_empty() {
+            parts.push(format!("{} unchanged", self.unchanged.len()));
+        }
+        for (name, error) in &self.failed {
+            parts.push(format!("{name} failed: {error}"));
+        }
+        parts.join("; ")
+    }
+}
+
+/// Display name of a profile item: the group name, or the quality's.
+fn item_name(item: &Value) -> Option<&str> {
+    item.get("name")
+        .and_then(Value::as_str)
+        .filter(|n| !n.is_empty())
+        .or_else(|| item.pointer("/quality/name").and_then(Value::as_str))
+}
+
+/// Id the profile's `cutoff` refers to: the quality's, or the group id.
+fn item_id(item: &Value) -> Option<i64> {
+    item.pointer("/quality/id")
+        .and_then(Value::as_i64)
+        .or_else(|| item.get("id").and_then(Value::as_i64))
+}
+
+fn children(item: &Value) -> &[Value] {
+    item.get("items")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// Index of the top-level item named `name`, or of the group holding a
+/// quality of that name, and whether the match was that inner quality.
+fn find(items: &[Value], name: &str) -> Option<(usize, bool)> {
+    items.iter().enumerate().find_map(|(i, item)| {
+        if item_name(item) == Some(name) {
+            Some((i, false))
+        } else if children(item).iter().any(|c| item_name(c) == Some(name)) {
+            Some((i, true))
+        } else {
+            None
+        }
+    })
+}
+
+/// `base` with `spec` applied: the listed qualities allowed and moved to
+/// the end of `items` in reverse (the apps rank the last item highest),
+/// everything else disallowed and left in place ahead of them.
+pub fn desired(spec: &QualityProfile, base: &Value) -> Result<Value, String> {
+    let mut profile = base.clone();
+    let mut items: Vec<Value> = base
+        .get("items")
+        .and_then(Value::as_array)
+        .cloned()
+        .ok_or("profile has no items")?;
+
+    for item in &mut items {
+        item["allowed"] = false.into();
+        if let Some(inner) = item.get_mut("items").and_then(Value::as_array_mut) {
+            for child in inner {
+                child["allowed"] = false.into();
+            }
+        }
+    }
+
+    // Top-level positions in preference order, most preferred first.
+    let mut ranked: Vec<usize> = Vec::new();
+    for name in &spec.qualities {
+        let (i, inner) = find(&items, name).ok_or_else(|| format!("unknown quality '{name}'"))?;
+        let item = &mut items[i];
+        item["allowed"] = true.into();
+        if let Some(group) = item.get_mut("items").and_then(Value::as_array_mut) {
+            for child in group {
+                if !inner || item_name(child) == Some(name) {
+                    child["allowed"] = true.into();
+                }
+            }
+        }
+        if !ranked.contains(&i) {
+            ranked.push(i);
+        }
+    }
+
+    let cutoff_name = spec.cutoff.as_deref().unwrap_or(&spec.qualities[0]);
+    let cutoff = find(&items, cutoff_name)
+        .and_then(|(i, _)| item_id(&items[i]))
+        .ok_or_else(|| format!("unknown cutoff quality '{cutoff_name}'"))?;
+
+    let mut ordered: Vec<Value> = items
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !ranked.contains(i))
+        .map(|(_, item)| item.clone())
+        .collect();
+    ordered.extend(ranked.iter().rev().map(|&i| items[i].clone()));
+
+    if !spec.format_scores.is_empty() {
+        let mut format_items: Vec<Value> = base
+            .get("formatItems")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        for (name, score) in &spec.format_scores {
+            let item = format_items
+                .iter_mut()
+                .find(|f| f.get("name").and_then(Value::as_str) == Some(name))
+                .ok_or_else(|| format!("unknown custom format '{name}'"))?;
+            item["score"] = (*score).into();
+        }
+        profile["formatItems"] = format_items.into();
+    }
+
+    profile["name"] = spec.name.clone().into();
+    profile["items"] = ordered.into();
+    profile["cutoff"] = cutoff.into();
+    profile["upgradeAllowed"] = spec.upgrade_allowed.into();
+    profile["minFormatScore"] = spec.min_format_score.into();
+    profile["cutoffFormatScore"] = spec.cutoff_format_score.into();
+    Ok(profile)
+}
+
+/// Create or update every profile in `specs` that differs from the app.
+pub async fn sync(api: &ServarrClient, specs: &[QualityProfile]) -> Result<SyncOutcome, ApiError> {
+    let existing = api.quality_profiles().await?;
+    let mut schema = None;
+    let mut outcome = SyncOutcome::default();
+
+    for spec in specs {
+        let current = existing
+            .iter()
+            .find(|p| p.get("name").and_then(Value::as_str) == Some(spec.name.as_str()));
+        let base = match current {
+            Some(profile) => profile.clone(),
+            None => {
+                if schema.is_none() {
+                    schema = Some(api.quality_profile_schema().await?);
+                }
+                let mut blank = schema.clone().unwrap_or_default();
+                if let Some(obj) = blank.as_object_mut() {
+                    obj.remove("id");
+                }
+                blank
+            }
+        };
+        let profile = match desired(spec, &base) {
+            Ok(profile) => profile,
+            Err(e) => {
+                outcome.failed.push((spec.name.clone(), e));
+                continue;
+            }
+        };
+
+        let result = match current {
+            Some(current) if *current == profile => {
+                outcome.unchanged.push(spec.name.clone());
+                continue;
+            }
+            Some(current) => {
+                let id = current.get("id").and_then(Value::as_i64).unwrap_or(0);
+                api.update_quality_profile(id, &profile)
+                    .await
+                    .map(|_| &mut outcome.updated)
+            }
+            None => api
+                .create_quality_profile(&profile)
+                .await
+                .map(|_| &mut outcome.created),
+        };
+        match result {
+            Ok(list) => list.push(spec.name.clone()),
+            Err(e) => outcome.failed.push((spec.name.clone(), e.to_string())),
+        }
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn quality(id: i64, name: &str) -> Value {
+        json!({ "quality": { "id": id, "name": name }, "items": [], "allowed": false })
+    }
+
+    fn schema() -> Value {
+        json!({
+            "name": "",
+            "upgradeAllowed": false,
+            "cutoff": 0,
+            "minFormatScore": 0,
+            "cutoffFormatScore": 0,
+            "formatItems": [],
+            "items": [
+                quality(1, "SDTV"),
+                {
+                    "name": "WEB 1080p",
+                    "id": 1003,
+                    "allowed": false,
+                    "items": [quality(3, "WEBDL-1080p"), quality(15, "WEBRip-1080p")]
+                },
+                quality(7, "Bluray-1080p"),
+                quality(19, "Bluray-2160p")
+            ]
+        })
+    }
+
+    fn spec(qualities: &[&str], cutoff: Option<&str>) -> QualityProfile {
+        QualityProfile {
+            name: "HD".into(),
+            qualities: qualities.iter().map(|q| q.to_string()).collect(),
+            cutoff: cutoff.map(String::from),
+            upgrade_allowed: true,
+            min_format_score: 0,
+            cutoff_format_score: 0,
+            format_scores: Default::default(),
+        }
+    }
+
+    fn names(profile: &Value) -> Vec<(&str, bool)> {
+        profile["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| (item_name(i).unwrap(), i["allowed"].as_bool().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn allows_listed_qualities_in_preference_order() {
+        let profile = desired(&spec(&["Bluray-1080p", "WEB 1080p"], None), &schema()).unwrap();
+        assert_eq!(profile["name"], "HD");
+        assert_eq!(
+            names(&profile),
+            vec![
+                ("SDTV", false),
+                ("Bluray-2160p", false),
+                ("WEB 1080p", true),
+                ("Bluray-1080p", true),
+            ]
+        );
+        // The first listed quality is the default cutoff.
+        assert_eq!(profile["cutoff"], 7);
+        assert_eq!(profile["upgradeAllowed"], true);
+        let group = &profile["items"][2]["items"];
+        assert!(
+            group
+                .as_array()
+                .unwrap()
+                .iter()
+                .all(|c| c["allowed"] == true)
+        );
+    }
+
+    #[test]
+    fn inner_quality_allows_only_itself_and_group_cutoff() {
+        let profile = desired(&spec(&["WEBDL-1080p"], None), &schema()).unwrap();
+        let group = profile["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|i| item_name(i) == Some("WEB 1080p"))
+            .unwrap();
+        assert_eq!(group["allowed"], true);
+        assert_eq!(group["items"][0]["allowed"], true);
+        assert_eq!(group["items"][1]["allowed"], false);
+        assert_eq!(profile["cutoff"], 1003);
+    }
+
+    #[test]
+    fn desired_is_stable_on_its_own_output() {
+        let spec = spec(&["Bluray-1080p", "WEB 1080p"], Some("WEB 1080p"));
+        let once = desired(&spec, &schema()).unwrap();
+        assert_eq!(desired(&spec, &once).unwrap(), once);
+        assert_eq!(once["cutoff"], 1003);
+    }
+
+    #[test]
+    fn unknown_quality_is_an_error() {
+        let err = desired(&spec(&["Bluray-480p"], None), &schema()).unwrap_err();
+        assert!(err.contains("Bluray-480p"));
+    }
+
+    #[test]
+    fn format_scores_set_listed_formats_only() {
+        let mut base = schema();
+        base["formatItems"] = json!([
+            { "format": 1, "name": "x265 (HD)", "score": 0 },
+            { "format": 2, "name": "3D", "score": -50 }
+        ]);
+        let mut spec = spec(&["Bluray-1080p"], None);
+        spec.format_scores = [("x265 (HD)".to_string(), -10000)].into();
+        let profile = desired(&spec, &base).unwrap();
+        assert_eq!(profile["formatItems"][0]["score"], -10000);
+        assert_eq!(profile["formatItems"][1]["score"], -50);
+
+        spec.format_scores = [("Upscaled".to_string(), -100)].into();
+        let err = desired(&spec, &base).unwrap_err();
+        assert!(err.contains("unknown custom format 'Upscaled'"));
+    }
+
+    #[test]
+    fn outcome_message() {
+        let outcome = SyncOutcome {
+            created: vec!["HD".into()],
+            updated: vec!["UHD".into(), "Any".into()],
+            unchanged: vec!["SD".into()],
+            failed: vec![("Anime".into(), "unknown quality 'x'".into())],
+        };
+        assert_eq!(
+            outcome.message(),
+            "created: HD; updated: UHD, Any; 1 unchanged; Anime failed: unknown quality 'x'"
+        );
+    }
+}
@@ -0,0 +1,218 @@
+//! Which Sonarr/Radarr queue items `spec.queueRemediation` acts on.
+//!
+//! The apps already retry failed downloads, but an import that fails or a
+//! torrent that stalls stays in the queue until someone removes it by hand.
+//! Items are sorted into a [`RemediationReason`] from their tracked state
+//! and status messages; the first rule for that reason decides what
+//! happens once the item is old enough.
+
+use chrono::{DateTime, Utc};
+use servarr_api::QueueItem;
+use servarr_crds::{QueueRemediationSpec, RemediationReason, RemediationRule};
+
+/// Status message fragments (lowercase) of downloads without media files.
+const MISSING_FILES: &[&str] = &[
+    "no files found",
+    "no video files",
+    "no audio files",
+    "no eligible files",
+];
+
+/// Status message fragments (lowercase) of releases that can't be imported
+/// as they are.
+const WRONG_FORMAT: &[&str] = &[
+    "not an upgrade",
+    "not a custom format upgrade",
+    "unable to parse",
+    "unsupported extension",
+    "sample",
+    "invalid season or episode",
+    "quality profile does not allow",
+];
+
+fn mentions(item: &QueueItem, fragments: &[&str]) -> bool {
+    item.messages().any(|m| {
+        let m = m.to_lowercase();
+        fragments.iter().any(|f| m.contains(f))
+    })
+}
+
+/// Why `item` is stuck, or `None` when it is progressing normally.
+pub fn classify(item: &QueueItem) -> Option<RemediationReason> {
+    let state = item.tracked_download_state.as_deref().unwrap_or_default();
+    let status = item
+        .tracked_download_status
+        .as_deref()
+        .unwrap_or_default()
+        .to_lowercase();
+    let import_stuck = matches!(state, "importFailed" | "importBlocked")
+        || (state == "importPending" && matches!(status.as_str(), "warning" | "error"));
+
+    if import_stuck {
+        Some(if mentions(item, MISSING_FILES) {
+            RemediationReason::MissingFiles
+        } else if mentions(item, WRONG_FORMAT) {
+            RemediationReason::WrongFormat
+        } else {
+            RemediationReason::ImportFailed
+        })
+    } else if state == "downloading"
+        && (item
+            .status
+            .as_deref()
+            .is_some_and(|s| s.eq_ignore_ascii_case("stalled"))
+            || mentions(item, &["stalled"]))
+    {
+        Some(RemediationReason::Stalled)
+    } else {
+        None
+    }
+}
+
+/// Whether `item` was grabbed at least `min_age_minutes` ago. Items
+/// without a usable `added` time only qualify for rules without a minimum.
+fn old_enough(item: &QueueItem, min_age_minutes: u32, now: DateTime<Utc>) -> bool {
+    if min_age_minutes == 0 {
+        return true;
+    }
+    item.added
+        .as_deref()
+        .and_then(|a| a.parse::<DateTime<Utc>>().ok())
+        .is_some_and(|added| now - added >= chrono::Duration::minutes(min_age_minutes.into()))
+}
+
+/// A queue item to act on.
+#[derive(Debug)]
+pub struct Remediation<'a> {
+    pub item: &'a QueueItem,
+    pub reason: RemediationReason,
+    pub rule: &'a RemediationRule,
+}
+
+impl Remediation<'_> {
+    pub fn title(&self) -> String {
+        self.item
+            .title
+            .clone()
+            .unwrap_or_else(|| format!("queue item {}", self.item.id))
+    }
+}
+
+/// The queue items `spec` acts on at `now`.
+pub fn plan<'a>(
+    spec: &'a QueueRemediationSpec,
+    queue: &'a [QueueItem],
+    now: DateTime<Utc>,
+) -> Vec<Remediation<'a>> {
+    queue
+        .iter()
+        .filter_map(|item| {
+            let reason = classify(item)?;
+            let rule = spec.rule_for(reason)?;
+            old_enough(item, rule.min_age_minutes, now).then_some(Remediation {
+                item,
+                reason,
+                rule,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(state: &str, status: &str, messages: &[&str], added: &str) -> QueueItem {
+        serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "title": "Show.S01E01.1080p.WEB",
+            "status": "completed",
+            "trackedDownloadStatus": status,
+            "trackedDownloadState": state,
+            "statusMessages": [{"title": "Show.S01E01.1080p.WEB", "messages": messages}],
+            "added": added,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn classify_sorts_stuck_items() {
+        let added = "2025-01-01T00:00:00Z";
+        assert_eq!(
+            classify(&item(
+                "importPending",
+                "warning",
+                &["No files found are eligible for import"],
+                added
+            )),
+            Some(RemediationReason::MissingFiles)
+        );
+        assert_eq!(
+            classify(&item(
+                "importBlocked",
+                "warning",
+                &["Not an upgrade for existing episode file(s)"],
+                added
+            )),
+            Some(RemediationReason::WrongFormat)
+        );
+        assert_eq!(
+            classify(&item(
+                "importFailed",
+                "error",
+                &["Episode file already imported"],
+                added
+            )),
+            Some(RemediationReason::ImportFailed)
+        );
+        let mut stalled = item("downloading", "warning", &[], added);
+        stalled.error_message = Some("The download is stalled with no connections".into());
+        assert_eq!(classify(&stalled), Some(RemediationReason::Stalled));
+
+        assert_eq!(classify(&item("importPending", "ok", &[], added)), None);
+        assert_eq!(classify(&item("downloading", "ok", &[], added)), None);
+    }
+
+    #[test]
+    fn plan_honours_rules_and_age() {
+        let now: DateTime<Utc> = "2025-01-01T02:00:00Z".parse().unwrap();
+        let queue = vec![
+            // Failed import grabbed two hours ago: acted on.
+            item("importFailed", "error", &[], "2025-01-01T00:00:00Z"),
+            // Failed import grabbed ten minutes ago: too young.
+            item("importFailed", "error", &[], "2025-01-01T01:50:00Z"),
+            // Stalled for two hours: the default rule waits six.
+            {
+                let mut i = item(
+                    "downloading",
+                    "warning",
+                    &["stalled"],
+                    "2025-01-01T00:00:00Z",
+                );
+                i.status = Some("stalled".into());
+                i
+            },
+        ];
+        let spec = QueueRemediationSpec {
+            enabled: true,
+            ..Default::default()
+        };
+        let planned = plan(&spec, &queue, now);
+        assert_eq!(planned.len(), 1);
+        assert_eq!(planned[0].reason, RemediationReason::ImportFailed);
+
+        // Without a rule for the reason, nothing happens.
+        let only_stalled = QueueRemediationSpec {
+            rules: vec![RemediationRule {
+                reason: RemediationReason::Stalled,
+                min_age_minutes: 60,
+                action: Default::default(),
+                remove_from_client: true,
+            }],
+            ..spec
+        };
+        let planned = plan(&only_stalled, &queue, now);
+        assert_eq!(planned.len(), 1);
+        assert_eq!(planned[0].reason, RemediationReason::Stalled);
+    }
+}
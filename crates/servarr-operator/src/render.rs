@@ -0,0 +1,287 @@
+//! Offline rendering of a ServarrApp's child manifests, shared by the
+//! `render` subcommand and the metrics server's `POST /render` endpoint.
+//!
+//! Mirrors what the reconciler applies, minus anything that needs the
+//! cluster: the generated API key Secret, projected credentials, and
+//! MediaStack defaults are left out.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use servarr_crds::{ImageSpec, ServarrApp};
+use servarr_resources::patches::PatchError;
+use thiserror::Error;
+
+use crate::context;
+use crate::workload::Workload;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("invalid ServarrApp manifest: {0}")]
+    Parse(#[source] serde_yaml::Error),
+    #[error("expected kind ServarrApp, got {0}")]
+    Kind(String),
+    #[error("invalid resource patch: {0}")]
+    Patch(#[source] PatchError),
+    #[error("serialization error: {0}")]
+    Serialization(#[source] serde_json::Error),
+}
+
+/// Operator settings that change the rendered output.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    pub image_overrides: HashMap<String, ImageSpec>,
+    pub default_arch: Option<String>,
+    pub allow_unsupported_overrides: bool,
+}
+
+impl Options {
+    /// The settings the operator itself would use, read from the same
+    /// environment variables.
+    pub fn from_env() -> Self {
+        Self {
+            image_overrides: context::load_image_overrides(),
+            default_arch: context::load_default_arch(),
+            allow_unsupported_overrides: context::allow_unsupported_overrides(),
+        }
+    }
+}
+
+/// Placeholder UID for owner references when the manifest has none, as is
+/// usual for an object that was never applied.
+const PLACEHOLDER_UID: &str = "00000000-0000-0000-0000-000000000000";
+
+/// Parse a ServarrApp from YAML (or JSON). Apps without a namespace are
+/// rendered into `default`.
+pub fn parse(manifest: &str) -> Result<ServarrApp, Error> {
+    let value: serde_yaml::Value = serde_yaml::from_str(manifest).map_err(Error::Parse)?;
+    if let Some(kind) = value.get("kind").and_then(|k| k.as_str())
+        && kind != "ServarrApp"
+    {
+        return Err(Error::Kind(kind.to_string()));
+    }
+    let mut app: ServarrApp = serde_yaml::from_value(value).map_err(Error::Parse)?;
+    if app.metadata.namespace.is_none() {
+        app.metadata.namespace = Some("default".into());
+    }
+    app.metadata
+        .uid
+        .get_or_insert_with(|| PLACEHOLDER_UID.into());
+    Ok(app)
+}
+
+/// The child objects the operator would apply for `app`, in apply order,
+/// with the app's post-render `patches` applied.
+pub fn render(app: &ServarrApp, opts: &Options) -> Result<Vec<serde_json::Value>, Error> {
+    let mut objects = Vec::new();
+
+    let upgraded = crate::image_update::upgraded_image(
+        app,
+        app.status.as_ref().and_then(|s| s.image_update.as_ref()),
+        &opts.image_overrides,
+        opts.default_arch.as_deref(),
+    )
+    .map(|image| {
+        let mut app = app.clone();
+        app.spec.image = Some(image);
+        app
+    });
+    let mut deployment = servarr_resources::deployment::build_for_arch(
+        upgraded.as_ref().unwrap_or(app),
+        &opts.image_overrides,
+        opts.default_arch.as_deref(),
+    );
+    if opts.allow_unsupported_overrides
+        && let Some(pod_spec) = app
+            .spec
+            .unsupported_overrides
+            .as_ref()
+            .and_then(|o| o.pod_spec.as_ref())
+    {
+        deployment = servarr_resources::patches::merge_pod_spec(deployment, pod_spec)
+            .map_err(Error::Patch)?;
+    }
+    let mut workload = match Workload::for_app(app, deployment) {
+        Workload::Deployment(d) => Workload::Deployment(patched(app, d)?),
+        Workload::StatefulSet(s) => Workload::StatefulSet(patched(app, s)?),
+    };
+    workload
+        .meta_mut()
+        .annotations
+        .get_or_insert_default()
+        .insert(
+            "servarr.dev/operator-version".into(),
+            env!("CARGO_PKG_VERSION").into(),
+        );
+    match &workload {
+        Workload::Deployment(d) => push(&mut objects, d)?,
+        Workload::StatefulSet(s) => push(&mut objects, s)?,
+    }
+
+    push(
+        &mut objects,
+        &patched(app, servarr_resources::service::build(app))?,
+    )?;
+    for pvc in servarr_resources::pvc::build_all(app) {
+        push(&mut objects, &patched(app, pvc)?)?;
+    }
+    let network_policy_enabled =
+        app.spec.network_policy_config.is_some() || app.spec.network_policy.unwrap_or(true);
+    if network_policy_enabled {
+        push(
+            &mut objects,
+            &patched(app, servarr_resources::networkpolicy::build(app))?,
+        )?;
+    }
+
+    let config_maps = [
+        servarr_resources::configmap::build(app),
+        servarr_resources::configmap::build_tar_unpack(app),
+        servarr_resources::configmap::build_prowlarr_definitions(app),
+        servarr_resources::config_file::build(app),
+    ];
+    for cm in config_maps.into_iter().flatten() {
+        push(&mut objects, &patched(app, cm)?)?;
+    }
+    if let Some(secret) = servarr_resources::secret::build_authorized_keys(app) {
+        push(&mut objects, &patched(app, secret)?)?;
+    }
+    if let Some(cm) = servarr_resources::configmap::build_ssh_bastion_restricted_rsync(app) {
+        push(&mut objects, &patched(app, cm)?)?;
+    }
+    if let Some(cm) = servarr_resources::configmap::build_ssh_bastion_sftp_chroot(app) {
+        push(&mut objects, &patched(app, cm)?)?;
+    }
+
+    let route = servarr_resources::tcproute::build(app)
+        .or_else(|| servarr_resources::httproute::build(app));
+    if let Some(route) = route {
+        push(&mut objects, &patched(app, route)?)?;
+    }
+    if let Some(ingress) = servarr_resources::ingress::build(app) {
+        push(&mut objects, &patched(app, ingress)?)?;
+    }
+    if let Some(cert) = servarr_resources::certificate::build(app) {
+        push(&mut objects, &patched(app, cert)?)?;
+    }
+    if let Some(hpa) = servarr_resources::hpa::build(app) {
+        push(&mut objects, &patched(app, hpa)?)?;
+    }
+
+    Ok(objects)
+}
+
+/// Render `objects` as a multi-document YAML stream.
+pub fn to_yaml(objects: &[serde_json::Value]) -> Result<String, serde_yaml::Error> {
+    let docs = objects
+        .iter()
+        .map(serde_yaml::to_string)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(docs.join("---\n"))
+}
+
+fn patched<T>(app: &ServarrApp, obj: T) -> Result<T, Error>
+where
+    T: Serialize + serde::de::DeserializeOwned,
+{
+    servarr_resources::patches::apply(obj, &app.spec.patches).map_err(Error::Patch)
+}
+
+fn push<T: Serialize>(objects: &mut Vec<serde_json::Value>, obj: &T) -> Result<(), Error> {
+    objects.push(serde_json::to_value(obj).map_err(Error::Serialization)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kube::ResourceExt;
+
+    fn kinds(objects: &[serde_json::Value]) -> Vec<&str> {
+        objects
+            .iter()
+            .map(|o| o["kind"].as_str().unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn renders_default_children() {
+        let app = parse(
+            "apiVersion: servarr.dev/v1alpha1\nkind: ServarrApp\nmetadata:\n  name: sonarr\nspec:\n  app: Sonarr\n",
+        )
+        .unwrap();
+        assert_eq!(app.namespace().as_deref(), Some("default"));
+
+        let objects = render(&app, &Options::default()).unwrap();
+        let kinds = kinds(&objects);
+        assert_eq!(kinds[..2], ["Deployment", "Service"]);
+        assert!(kinds.contains(&"PersistentVolumeClaim"), "{kinds:?}");
+        assert!(kinds.contains(&"NetworkPolicy"), "{kinds:?}");
+        assert_eq!(objects[0]["metadata"]["namespace"], "default");
+
+        let yaml = to_yaml(&objects).unwrap();
+        assert_eq!(yaml.matches("---\n").count(), objects.len() - 1);
+    }
+
+    #[test]
+    fn renders_statefulset_workload() {
+        let app = parse(
+            "apiVersion: servarr.dev/v1alpha1\nkind: ServarrApp\nmetadata:\n  name: plex\nspec:\n  app: Plex\n  workloadType: StatefulSet\n",
+        )
+        .unwrap();
+        let objects = render(&app, &Options::default()).unwrap();
+        assert_eq!(objects[0]["kind"], "StatefulSet");
+        assert_eq!(objects[0]["spec"]["serviceName"], "plex");
+        assert!(
+            objects
+                .iter()
+                .all(|o| o["metadata"]["name"] != "plex-config"),
+            "config claim comes from the volumeClaimTemplate"
+        );
+    }
+
+    #[test]
+    fn applies_patches_and_gateway() {
+        let app = parse(
+            r#"
+apiVersion: servarr.dev/v1alpha1
+kind: ServarrApp
+metadata:
+  name: radarr
+  namespace: media
+spec:
+  app: Radarr
+  networkPolicy: false
+  gateway:
+    enabled: true
+    hosts: [radarr.example.com]
+  patches:
+    - target:
+        kind: Service
+      patch: |
+        metadata:
+          labels:
+            team: media
+"#,
+        )
+        .unwrap();
+        let objects = render(&app, &Options::default()).unwrap();
+        let kinds = kinds(&objects);
+        assert!(!kinds.contains(&"NetworkPolicy"), "{kinds:?}");
+        assert!(kinds.contains(&"HTTPRoute"), "{kinds:?}");
+        let service = objects.iter().find(|o| o["kind"] == "Service").unwrap();
+        assert_eq!(service["metadata"]["labels"]["team"], "media");
+    }
+
+    #[test]
+    fn rejects_other_kinds() {
+        let err =
+            parse("apiVersion: v1\nkind: ConfigMap\nmetadata:\n  name: x\nspec:\n  app: Sonarr\n")
+                .unwrap_err();
+        assert!(
+            matches!(err, Error::Kind(ref k) if k == "ConfigMap"),
+            "{err}"
+        );
+        assert!(matches!(parse("spec: 3"), Err(Error::Parse(_))));
+    }
+}
@@ -0,0 +1,196 @@
+//! Operator-wide reconcile timing: how long settled and unsettled objects
+//! wait before their next reconcile, and how failed reconciles back off.
+//!
+//! A ServarrApp's own `spec.requeue` replaces the requeue intervals; the
+//! error backoff and full resync interval always come from here.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use tracing::{info, warn};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequeueSettings {
+    /// Wait before the next reconcile while an object is still converging.
+    pub unsettled: Duration,
+    /// Wait before the next reconcile once an object is Ready.
+    pub settled: Duration,
+    /// Delay after the first failed reconcile of an object. Each further
+    /// consecutive failure doubles it.
+    pub error_backoff_base: Duration,
+    /// Longest delay after a failed reconcile.
+    pub error_backoff_max: Duration,
+    /// How often a ServarrApp reconcile applies every resource group, even
+    /// those whose inputs are unchanged.
+    pub full_resync: Duration,
+}
+
+impl Default for RequeueSettings {
+    fn default() -> Self {
+        Self {
+            unsettled: Duration::from_secs(20),
+            settled: Duration::from_secs(300),
+            error_backoff_base: Duration::from_secs(5),
+            error_backoff_max: Duration::from_secs(300),
+            full_resync: Duration::from_secs(3600),
+        }
+    }
+}
+
+/// Floor for every interval, so a misconfigured operator can't hammer the
+/// API server.
+const MIN_SECONDS: u64 = 1;
+
+fn seconds_var(var: &str, default: Duration) -> Duration {
+    match std::env::var(var).as_deref().map(str::trim) {
+        Err(_) | Ok("") => default,
+        Ok(v) => match v.parse::<u64>() {
+            Ok(secs) => Duration::from_secs(secs.max(MIN_SECONDS)),
+            Err(_) => {
+                warn!(%var, value = %v, "expected a number of seconds, using the default");
+                default
+            }
+        },
+    }
+}
+
+impl RequeueSettings {
+    /// Read `REQUEUE_UNSETTLED_SECONDS` (default 20),
+    /// `REQUEUE_SETTLED_SECONDS` (default 300),
+    /// `ERROR_BACKOFF_BASE_SECONDS` (default 5),
+    /// `ERROR_BACKOFF_MAX_SECONDS` (default 300), and
+    /// `FULL_RESYNC_SECONDS` (default 3600).
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            unsettled: seconds_var("REQUEUE_UNSETTLED_SECONDS", defaults.unsettled),
+            settled: seconds_var("REQUEUE_SETTLED_SECONDS", defaults.settled),
+            error_backoff_base: seconds_var(
+                "ERROR_BACKOFF_BASE_SECONDS",
+                defaults.error_backoff_base,
+            ),
+            error_backoff_max: seconds_var("ERROR_BACKOFF_MAX_SECONDS", defaults.error_backoff_max),
+            full_resync: seconds_var("FULL_RESYNC_SECONDS", defaults.full_resync),
+        }
+    }
+
+    /// Delay before retrying an object after its `failures`-th consecutive
+    /// failed reconcile: doubling from the base, capped at the maximum, with
+    /// up to half of it removed at random (`jitter` in `0.0..=1.0`) so that
+    /// objects failing together don't retry in lockstep.
+    pub fn error_backoff(&self, failures: u32, jitter: f64) -> Duration {
+        let delay = self
+            .error_backoff_base
+            .saturating_mul(2u32.saturating_pow(failures.saturating_sub(1)))
+            .min(self.error_backoff_max.max(self.error_backoff_base));
+        delay.mul_f64(1.0 - jitter.clamp(0.0, 1.0) / 2.0)
+    }
+}
+
+static SETTINGS: OnceLock<RequeueSettings> = OnceLock::new();
+
+/// Install `settings`. Must run before the controllers start; later calls
+/// are ignored.
+pub fn configure(settings: RequeueSettings) {
+    info!(?settings, "requeue settings");
+    let _ = SETTINGS.set(settings);
+}
+
+pub fn settings() -> &'static RequeueSettings {
+    SETTINGS.get_or_init(RequeueSettings::default)
+}
+
+/// Consecutive failed reconciles per object, which set how long
+/// `error_policy` backs off.
+#[derive(Default)]
+pub struct ErrorBackoff {
+    failures: Mutex<HashMap<String, u32>>,
+}
+
+impl ErrorBackoff {
+    /// Record a failed reconcile of the object `key` and return how long to
+    /// wait before retrying it.
+    pub fn failed(&self, key: &str) -> Duration {
+        let mut failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        let count = failures.entry(key.to_string()).or_default();
+        *count = count.saturating_add(1);
+        settings().error_backoff(*count, rand::random_range(0.0..=1.0))
+    }
+
+    /// Consecutive failed reconciles recorded for `key`.
+    pub fn failures(&self, key: &str) -> u32 {
+        self.failures
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Forget the failures of `key` after it reconciled successfully.
+    pub fn succeeded(&self, key: &str) {
+        self.failures
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(key);
+    }
+}
+
+/// Key for an object in [`ErrorBackoff`].
+pub fn object_key(namespace: Option<&str>, name: &str) -> String {
+    format!("{}/{name}", namespace.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_backoff_doubles_up_to_the_cap() {
+        let settings = RequeueSettings::default();
+        let secs = |failures| settings.error_backoff(failures, 0.0).as_secs();
+        assert_eq!(secs(1), 5);
+        assert_eq!(secs(2), 10);
+        assert_eq!(secs(4), 40);
+        assert_eq!(secs(7), 300);
+        assert_eq!(secs(u32::MAX), 300);
+        // Jitter removes at most half of the delay.
+        assert_eq!(settings.error_backoff(3, 1.0).as_secs(), 10);
+        assert_eq!(settings.error_backoff(3, 2.0).as_secs(), 10);
+    }
+
+    #[test]
+    fn error_backoff_resets_after_success() {
+        let backoff = ErrorBackoff::default();
+        let key = object_key(Some("media"), "sonarr");
+        let first = backoff.failed(&key);
+        backoff.failed(&key);
+        let third = backoff.failed(&key);
+        assert!(third > first);
+        assert_eq!(backoff.failures(&key), 3);
+        backoff.succeeded(&key);
+        assert_eq!(backoff.failures(&key), 0);
+        assert!(backoff.failed(&key) <= Duration::from_secs(5));
+    }
+
+    #[test]
+    fn from_env_reads_seconds() {
+        temp_env::with_vars(
+            [
+                ("REQUEUE_SETTLED_SECONDS", Some("900")),
+                ("REQUEUE_UNSETTLED_SECONDS", Some("0")),
+                ("ERROR_BACKOFF_MAX_SECONDS", Some("ten")),
+                ("FULL_RESYNC_SECONDS", Some("7200")),
+            ],
+            || {
+                let settings = RequeueSettings::from_env();
+                assert_eq!(settings.settled.as_secs(), 900);
+                assert_eq!(settings.unsettled.as_secs(), 1);
+                assert_eq!(settings.error_backoff_max.as_secs(), 300);
+                assert_eq!(settings.error_backoff_base.as_secs(), 5);
+                assert_eq!(settings.full_resync.as_secs(), 7200);
+            },
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! Selective ServarrApp reconciles. Each pass hashes what every group of
+//! child objects is built from and skips applying a group whose hash
+//! matches the one recorded in `status.resourceGroups`, so the periodic
+//! resync of a settled app mostly reads. Once per full resync interval
+//! (and on a `servarr.dev/reconcile-now` trigger) every group is applied
+//! regardless, which also undoes edits made to the objects directly.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use servarr_crds::{ResourceGroupHashes, ServarrApp};
+use servarr_resources as resources;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourceGroup {
+    Workload,
+    Networking,
+    Config,
+    ExternalSync,
+}
+
+impl ResourceGroup {
+    fn hash(self, hashes: &ResourceGroupHashes) -> Option<&str> {
+        match self {
+            Self::Workload => hashes.workload.as_deref(),
+            Self::Networking => hashes.networking.as_deref(),
+            Self::Config => hashes.config.as_deref(),
+            Self::ExternalSync => hashes.external_sync.as_deref(),
+        }
+    }
+}
+
+/// SHA-256 of `inputs` and the operator version, so an upgrade that
+/// renders objects differently applies every group again.
+pub fn hash(inputs: &impl Serialize) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(env!("CARGO_PKG_VERSION"));
+    hasher.update(serde_json::to_vec(inputs).unwrap_or_default());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Inputs of the networking group: the Service, NetworkPolicy, Gateway API
+/// route, Ingress, and Certificate as built from the spec.
+pub fn networking_inputs(app: &ServarrApp) -> impl Serialize {
+    (
+        resources::service::build(app),
+        app.spec.network_policy,
+        resources::networkpolicy::build(app),
+        resources::tcproute::build(app),
+        resources::httproute::build(app),
+        resources::ingress::build(app),
+        resources::certificate::build(app),
+        &app.spec.patches,
+    )
+}
+
+/// Inputs of the config group: the generated ConfigMaps and Secrets.
+pub fn config_inputs(app: &ServarrApp) -> impl Serialize {
+    (
+        resources::configmap::build(app),
+        resources::configmap::build_tar_unpack(app),
+        resources::configmap::build_prowlarr_definitions(app),
+        resources::config_file::build(app),
+        resources::secret::build_authorized_keys(app),
+        resources::configmap::build_ssh_bastion_restricted_rsync(app),
+        resources::configmap::build_ssh_bastion_sftp_chroot(app),
+        app.spec.api_key_ref(),
+        &app.spec.patches,
+    )
+}
+
+/// Inputs of the external sync group: the settings pushed through the
+/// app's API.
+pub fn external_sync_inputs(app: &ServarrApp) -> impl Serialize {
+    (&app.spec.app, &app.spec.ui_settings, &app.spec.app_config)
+}
+
+/// Which groups this pass applies.
+#[derive(Debug)]
+pub struct GroupPlan {
+    previous: ResourceGroupHashes,
+    current: ResourceGroupHashes,
+    full: bool,
+}
+
+impl GroupPlan {
+    /// Compare `current` with the hashes of the app's last reconcile. A
+    /// `forced` pass, or one more than `full_resync` after the last full
+    /// pass, applies every group.
+    pub fn new(
+        app: &ServarrApp,
+        current: ResourceGroupHashes,
+        now: DateTime<Utc>,
+        full_resync: Duration,
+        forced: bool,
+    ) -> Self {
+        let previous = app
+            .status
+            .as_ref()
+            .and_then(|s| s.resource_groups.clone())
+            .unwrap_or_default();
+        let last_full = previous
+            .full_sync_time
+            .as_deref()
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok());
+        let full = forced
+            || last_full.is_none_or(|t| {
+                (now - t.with_timezone(&Utc))
+                    .to_std()
+                    .is_ok_and(|elapsed| elapsed >= full_resync)
+            });
+        Self {
+            previous,
+            current,
+            full,
+        }
+    }
+
+    pub fn full(&self) -> bool {
+        self.full
+    }
+
+    /// Whether `group` can be skipped: its inputs hash the same as when it
+    /// was last applied, and no full pass is due.
+    pub fn unchanged(&self, group: ResourceGroup) -> bool {
+        !self.full
+            && group
+                .hash(&self.previous)
+                .is_some_and(|h| group.hash(&self.current) == Some(h))
+    }
+
+    /// The hashes to record for the next pass. The external sync hash is
+    /// left out unless every sync succeeded, so a failed one is retried.
+    pub fn record(self, external_synced: bool, now: &str) -> ResourceGroupHashes {
+        ResourceGroupHashes {
+            external_sync: self.current.external_sync.filter(|_| external_synced),
+            full_sync_time: if self.full {
+                Some(now.to_string())
+            } else {
+                self.previous.full_sync_time
+            },
+            ..self.current
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use servarr_crds::{ServarrAppSpec, ServarrAppStatus};
+
+    fn hashes(value: &str) -> ResourceGroupHashes {
+        ResourceGroupHashes {
+            workload: Some(format!("w-{value}")),
+            networking: Some(format!("n-{value}")),
+            config: Some(format!("c-{value}")),
+            external_sync: Some(format!("e-{value}")),
+            full_sync_time: None,
+        }
+    }
+
+    fn app_with(previous: Option<ResourceGroupHashes>) -> ServarrApp {
+        let mut app = ServarrApp::new("sonarr", ServarrAppSpec::default());
+        app.status = Some(ServarrAppStatus {
+            resource_groups: previous,
+            ..Default::default()
+        });
+        app
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2026-10-15T12:00:00Z".parse().unwrap()
+    }
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    #[test]
+    fn unchanged_groups_are_skipped_until_a_full_resync_is_due() {
+        let previous = ResourceGroupHashes {
+            full_sync_time: Some("2026-10-15T11:30:00Z".into()),
+            ..hashes("1")
+        };
+        let current = ResourceGroupHashes {
+            networking: Some("n-2".into()),
+            ..hashes("1")
+        };
+        let plan = GroupPlan::new(
+            &app_with(Some(previous.clone())),
+            current.clone(),
+            now(),
+            HOUR,
+            false,
+        );
+        assert!(!plan.full());
+        assert!(plan.unchanged(ResourceGroup::Workload));
+        assert!(!plan.unchanged(ResourceGroup::Networking));
+        assert!(plan.unchanged(ResourceGroup::ExternalSync));
+
+        let later = now() + chrono::Duration::minutes(30);
+        let plan = GroupPlan::new(&app_with(Some(previous)), current, later, HOUR, false);
+        assert!(plan.full());
+        assert!(!plan.unchanged(ResourceGroup::Workload));
+    }
+
+    #[test]
+    fn first_and_forced_passes_apply_everything() {
+        let plan = GroupPlan::new(&app_with(None), hashes("1"), now(), HOUR, false);
+        assert!(plan.full());
+        assert!(!plan.unchanged(ResourceGroup::Config));
+
+        let previous = ResourceGroupHashes {
+            full_sync_time: Some("2026-10-15T11:59:00Z".into()),
+            ..hashes("1")
+        };
+        let plan = GroupPlan::new(&app_with(Some(previous)), hashes("1"), now(), HOUR, true);
+        assert!(plan.full());
+    }
+
+    #[test]
+    fn record_keeps_the_external_sync_hash_only_after_a_successful_sync() {
+        let plan = GroupPlan::new(&app_with(None), hashes("1"), now(), HOUR, false);
+        let recorded = plan.record(false, "2026-10-15T12:00:00Z");
+        assert_eq!(recorded.external_sync, None);
+        assert_eq!(recorded.workload.as_deref(), Some("w-1"));
+        assert_eq!(
+            recorded.full_sync_time.as_deref(),
+            Some("2026-10-15T12:00:00Z")
+        );
+
+        let previous = ResourceGroupHashes {
+            full_sync_time: Some("2026-10-15T11:30:00Z".into()),
+            ..hashes("1")
+        };
+        let plan = GroupPlan::new(&app_with(Some(previous)), hashes("1"), now(), HOUR, false);
+        let recorded = plan.record(true, "2026-10-15T12:00:00Z");
+        assert_eq!(recorded.external_sync.as_deref(), Some("e-1"));
+        assert_eq!(
+            recorded.full_sync_time.as_deref(),
+            Some("2026-10-15T11:30:00Z")
+        );
+    }
+
+    #[test]
+    fn hash_changes_with_the_inputs() {
+        assert_eq!(hash(&("a", 1)), hash(&("a", 1)));
+        assert_ne!(hash(&("a", 1)), hash(&("a", 2)));
+    }
+}
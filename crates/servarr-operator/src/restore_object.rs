@@ -0,0 +1,283 @@
+//! Restoring a Servarr v3 app from a backup archive in object storage,
+//! requested with `servarr.dev/restore-from-object: s3://bucket/key`.
+//!
+//! A Job copies the archive into the app's manual backup folder on the
+//! config volume, where the app lists it like one of its own backups. Once
+//! the Job succeeds the annotation is swapped for `servarr.dev/restore-from`
+//! with the staged backup's ID, which runs the usual scale-down, restore,
+//! and scale-up on the next reconcile.
+
+use k8s_openapi::api::batch::v1::Job;
+use k8s_openapi::api::core::v1::ObjectReference;
+use kube::api::{Api, DeleteParams, Patch, PatchParams};
+use kube::runtime::events::{Event, EventType};
+use kube::{Client, ResourceExt};
+use servarr_crds::ServarrApp;
+use servarr_resources::{common, restore};
+use tracing::{info, warn};
+
+use crate::events::ThrottledRecorder;
+use crate::metrics::increment_backup_operations;
+use crate::storage_migration::{JobOutcome, job_outcome};
+
+/// Annotation requesting a restore from an `s3://bucket/key` archive.
+pub const ANNOTATION: &str = "servarr.dev/restore-from-object";
+
+/// Annotation the existing in-app restore is triggered by.
+const RESTORE_FROM: &str = "servarr.dev/restore-from";
+
+/// A backup archive in an S3 bucket.
+#[derive(Debug, PartialEq)]
+pub struct ObjectUrl {
+    pub bucket: String,
+    pub key: String,
+}
+
+impl ObjectUrl {
+    /// Parse `s3://bucket/key`, where the key names a `.zip` archive.
+    pub fn parse(url: &str) -> Result<Self, String> {
+        let (bucket, key) = url
+            .strip_prefix("s3://")
+            .and_then(|rest| rest.split_once('/'))
+            .filter(|(bucket, key)| !bucket.is_empty() && !key.is_empty())
+            .ok_or_else(|| format!("'{url}' is not an s3://bucket/key URL"))?;
+        if !key.ends_with(".zip") {
+            return Err(format!("'{url}' does not name a .zip backup archive"));
+        }
+        Ok(Self {
+            bucket: bucket.to_string(),
+            key: key.to_string(),
+        })
+    }
+
+    /// The archive's file name, which the staged backup is listed under.
+    pub fn file_name(&self) -> &str {
+        self.key.rsplit('/').next().unwrap_or(&self.key)
+    }
+}
+
+async fn publish(
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &ObjectReference,
+    type_: EventType,
+    reason: &str,
+    note: String,
+) -> Result<(), kube::Error> {
+    recorder
+        .publish(
+            &Event {
+                type_,
+                reason: reason.into(),
+                note: Some(note),
+                action: "Restore".into(),
+                secondary: None,
+            },
+            obj_ref,
+        )
+        .await
+}
+
+/// Report a failed restore and drop the annotation so it isn't retried.
+async fn fail(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &ObjectReference,
+    message: String,
+) -> Result<(), kube::Error> {
+    warn!(name = %app.name_any(), %message, "restore from object storage failed");
+    increment_backup_operations(app.spec.app.as_str(), "restore", "error");
+    publish(
+        recorder,
+        obj_ref,
+        EventType::Warning,
+        "RestoreFailed",
+        message,
+    )
+    .await?;
+    let patch = serde_json::json!({
+        "metadata": { "annotations": { ANNOTATION: null } }
+    });
+    Api::<ServarrApp>::namespaced(client.clone(), ns)
+        .patch(
+            &app.name_any(),
+            &PatchParams::default(),
+            &Patch::Merge(patch),
+        )
+        .await?;
+    Ok(())
+}
+
+/// The ID under which the app lists the staged archive `file_name`.
+async fn staged_backup_id(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    file_name: &str,
+) -> Result<i64, String> {
+    let (secret, key) = app
+        .spec
+        .api_key_ref()
+        .ok_or("apiKeySecret is required to restore")?;
+    let api_key = servarr_api::read_secret_key(client, ns, secret, key)
+        .await
+        .map_err(|e| e.to_string())?;
+    let api = servarr_api::ServarrClient::new(
+        &common::internal_url(app),
+        &api_key,
+        crate::controller::app_type_to_kind(&app.spec.app),
+    )
+    .map_err(|e| e.to_string())?;
+    let backups = api.list_backups().await.map_err(|e| e.to_string())?;
+    backups
+        .iter()
+        .find(|b| b.name == file_name)
+        .map(|b| b.id)
+        .ok_or_else(|| format!("the app does not list the staged archive {file_name}"))
+}
+
+/// Advance a restore requested with [`ANNOTATION`] by one step. Returns
+/// whether the archive is still being staged, so the app is checked again
+/// soon.
+pub async fn reconcile(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    pp: &PatchParams,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &ObjectReference,
+) -> Result<bool, kube::Error> {
+    let Some(url) = app.annotations().get(ANNOTATION).cloned() else {
+        return Ok(false);
+    };
+    let name = app.name_any();
+    let storage = app
+        .spec
+        .backup
+        .as_ref()
+        .and_then(|b| b.object_storage.as_ref());
+    let parsed = match (app.spec.app.is_servarr_v3(), storage) {
+        (false, _) => Err(format!(
+            "{} has no backup API to restore through",
+            app.spec.app
+        )),
+        (true, None) => Err("backup.objectStorage is not set".to_string()),
+        (true, Some(storage)) => ObjectUrl::parse(&url).map(|object| (storage, object)),
+    };
+    let (storage, object) = match parsed {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            fail(client, app, ns, recorder, obj_ref, message).await?;
+            return Ok(false);
+        }
+    };
+
+    let job_api = Api::<Job>::namespaced(client.clone(), ns);
+    let job_name = restore::job_name(app);
+    let job = job_api.get_opt(&job_name).await?;
+    // A Job left from an earlier annotation value is replaced.
+    if let Some(ref job) = job
+        && job.annotations().get(restore::SOURCE_ANNOTATION) != Some(&url)
+    {
+        job_api
+            .delete(&job_name, &DeleteParams::background())
+            .await?;
+        return Ok(true);
+    }
+
+    match job_outcome(job.as_ref()) {
+        JobOutcome::Missing => {
+            info!(%name, %url, "staging backup archive from object storage");
+            let job = restore::build_stage_job(
+                app,
+                storage,
+                &object.bucket,
+                &object.key,
+                object.file_name(),
+            );
+            job_api.patch(&job_name, pp, &Patch::Apply(&job)).await?;
+            publish(
+                recorder,
+                obj_ref,
+                EventType::Normal,
+                "RestoreStaging",
+                format!("Copying {url} to {}", restore::STAGE_DIR),
+            )
+            .await?;
+            Ok(true)
+        }
+        JobOutcome::Running => Ok(true),
+        JobOutcome::Failed(message) => {
+            job_api
+                .delete(&job_name, &DeleteParams::background())
+                .await?;
+            fail(
+                client,
+                app,
+                ns,
+                recorder,
+                obj_ref,
+                format!("Copying {url} failed: {message}"),
+            )
+            .await?;
+            Ok(false)
+        }
+        JobOutcome::Succeeded => {
+            job_api
+                .delete(&job_name, &DeleteParams::background())
+                .await?;
+            let backup_id = match staged_backup_id(client, app, ns, object.file_name()).await {
+                Ok(id) => id,
+                Err(message) => {
+                    fail(client, app, ns, recorder, obj_ref, message).await?;
+                    return Ok(false);
+                }
+            };
+            info!(%name, %url, backup_id, "backup archive staged, restoring");
+            let patch = serde_json::json!({
+                "metadata": { "annotations": {
+                    ANNOTATION: null,
+                    RESTORE_FROM: backup_id.to_string(),
+                } }
+            });
+            Api::<ServarrApp>::namespaced(client.clone(), ns)
+                .patch(&name, &PatchParams::default(), &Patch::Merge(patch))
+                .await?;
+            publish(
+                recorder,
+                obj_ref,
+                EventType::Normal,
+                "RestoreStaged",
+                format!("Staged {url} as backup {backup_id}; restoring"),
+            )
+            .await?;
+            Ok(false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_object_urls() {
+        let url =
+            ObjectUrl::parse("s3://backups/sonarr/sonarr_backup_v4.0.0_2026.10.01.zip").unwrap();
+        assert_eq!(url.bucket, "backups");
+        assert_eq!(url.key, "sonarr/sonarr_backup_v4.0.0_2026.10.01.zip");
+        assert_eq!(url.file_name(), "sonarr_backup_v4.0.0_2026.10.01.zip");
+        assert_eq!(
+            ObjectUrl::parse("s3://backups/top.zip")
+                .unwrap()
+                .file_name(),
+            "top.zip"
+        );
+
+        assert!(ObjectUrl::parse("https://backups/sonarr.zip").is_err());
+        assert!(ObjectUrl::parse("s3://backups").is_err());
+        assert!(ObjectUrl::parse("s3:///sonarr.zip").is_err());
+        assert!(ObjectUrl::parse("s3://backups/sonarr.tar.gz").is_err());
+    }
+}
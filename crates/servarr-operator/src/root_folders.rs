@@ -0,0 +1,158 @@
+//! Sonarr/Radarr root folders declared in `appConfig.rootFolders`.
+//!
+//! Paths are compared without their trailing slash, since the apps store
+//! root folders with one whether or not it was given.
+
+use servarr_api::servarr_v3::RootFolder;
+use servarr_api::{ApiError, ServarrClient};
+use servarr_crds::RootFoldersSpec;
+
+fn normalize(path: &str) -> &str {
+    match path.trim_end_matches('/') {
+        "" => "/",
+        trimmed => trimmed,
+    }
+}
+
+/// Declared paths the app lacks, and (with `autoRemove`) the app's folders
+/// that aren't declared.
+pub fn plan<'s, 'e>(
+    spec: &'s RootFoldersSpec,
+    existing: &'e [RootFolder],
+) -> (Vec<&'s str>, Vec<&'e RootFolder>) {
+    let mut missing: Vec<&str> = Vec::new();
+    for path in &spec.paths {
+        let wanted = normalize(path);
+        if !existing.iter().any(|f| normalize(&f.path) == wanted)
+            && !missing.iter().any(|p| normalize(p) == wanted)
+        {
+            missing.push(path);
+        }
+    }
+    let extra = if spec.auto_remove {
+        existing
+            .iter()
+            .filter(|f| {
+                !spec
+                    .paths
+                    .iter()
+                    .any(|p| normalize(p) == normalize(&f.path))
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    (missing, extra)
+}
+
+/// What a sync pass did.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SyncOutcome {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// `(path, error)` for folders that could not be added or removed.
+    pub failed: Vec<(String, String)>,
+}
+
+impl SyncOutcome {
+    /// One-line summary for the `RootFoldersSynced` condition.
+    pub fn message(&self) -> String {
+        let mut parts = Vec::new();
+        for (label, paths) in [("added", &self.added), ("removed", &self.removed)] {
+            if !paths.is_empty() {
+                parts.push(format!("{label}: {}", paths.join(", ")));
+            }
+        }
+        for (path, error) in &self.failed {
+            parts.push(format!("{path} failed: {error}"));
+        }
+        if parts.is_empty() {
+            "Root folders match appConfig.rootFolders".to_string()
+        } else {
+            parts.join("; ")
+        }
+    }
+}
+
+/// Add the missing root folders and, with `autoRemove`, remove the rest.
+pub async fn sync(api: &ServarrClient, spec: &RootFoldersSpec) -> Result<SyncOutcome, ApiError> {
+    let existing = api.root_folder().await?;
+    let (missing, extra) = plan(spec, &existing);
+    let mut outcome = SyncOutcome::default();
+
+    for path in missing {
+        if let Some(e) = servarr_api::faults::sync("root-folders", path) {
+            outcome.failed.push((path.to_string(), e.to_string()));
+            continue;
+        }
+        match api.create_root_folder(path).await {
+            Ok(_) => outcome.added.push(path.to_string()),
+            Err(e) => outcome.failed.push((path.to_string(), e.to_string())),
+        }
+    }
+    for folder in extra {
+        if let Some(e) = servarr_api::faults::sync("root-folders", &folder.path) {
+            outcome.failed.push((folder.path.clone(), e.to_string()));
+            continue;
+        }
+        match api.delete_root_folder(folder.id).await {
+            Ok(()) => outcome.removed.push(folder.path.clone()),
+            Err(e) => outcome.failed.push((folder.path.clone(), e.to_string())),
+        }
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn folder(id: i64, path: &str) -> RootFolder {
+        RootFolder {
+            id,
+            path: path.into(),
+            accessible: true,
+            free_space: 0,
+        }
+    }
+
+    fn spec(paths: &[&str], auto_remove: bool) -> RootFoldersSpec {
+        RootFoldersSpec {
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+            auto_remove,
+        }
+    }
+
+    #[test]
+    fn plan_ignores_trailing_slashes() {
+        let existing = [folder(1, "/tv/"), folder(2, "/anime/")];
+        let spec = spec(&["/tv", "/tv-4k/", "/tv-4k"], false);
+        let (missing, extra) = plan(&spec, &existing);
+        assert_eq!(missing, vec!["/tv-4k/"]);
+        assert!(extra.is_empty());
+    }
+
+    #[test]
+    fn plan_removes_undeclared_only_with_auto_remove() {
+        let existing = [folder(1, "/tv/"), folder(2, "/anime/")];
+        let (_, extra) = plan(&spec(&["/tv"], true), &existing);
+        assert_eq!(extra.iter().map(|f| f.id).collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn outcome_message() {
+        assert_eq!(
+            SyncOutcome::default().message(),
+            "Root folders match appConfig.rootFolders"
+        );
+        let outcome = SyncOutcome {
+            added: vec!["/tv".into()],
+            removed: vec!["/old/".into()],
+            failed: vec![("/nope".into(), "folder does not exist".into())],
+        };
+        assert_eq!(
+            outcome.message(),
+            "added: /tv; removed: /old/; /nope failed: folder does not exist"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! Cron schedules as users write them for CronJobs: five fields or a
+//! shorthand such as `@daily`. Shared by the operator-run schedules
+//! (`backup.schedule`, NFS snapshots) and the admission webhook, so a
+//! schedule the webhook admits is one the operator can run.
+
+use std::str::FromStr;
+
+/// Shift a CronJob weekday field (`0`-`7`, Sunday is `0` or `7`) to the
+/// `cron` crate's numbering (`1`-`7`, Sunday is `1`). Names and steps are
+/// kept as written.
+fn weekdays(field: &str) -> String {
+    let shift = |day: &str| match day.parse::<u8>() {
+        Ok(7) => "1".to_string(),
+        Ok(n) => (n + 1).to_string(),
+        Err(_) => day.to_string(),
+    };
+    field
+        .split(',')
+        .map(|item| {
+            let (range, step) = match item.split_once('/') {
+                Some((range, step)) => (range, Some(step)),
+                None => (item, None),
+            };
+            let range = range.split('-').map(shift).collect::<Vec<_>>().join("-");
+            match step {
+                Some(step) => format!("{range}/{step}"),
+                None => range,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Parse a CronJob-style expression. The `cron` crate also wants a
+/// seconds field and counts weekdays from one.
+pub fn parse(schedule: &str) -> Result<cron::Schedule, String> {
+    let fields: Vec<&str> = schedule.split_whitespace().collect();
+    let expression = if schedule.starts_with('@') {
+        schedule.to_string()
+    } else if let [minute, hour, day, month, weekday] = fields[..] {
+        format!("0 {minute} {hour} {day} {month} {}", weekdays(weekday))
+    } else {
+        return Err(format!(
+            "invalid schedule '{schedule}': expected five fields (minute hour day month weekday)"
+        ));
+    };
+    cron::Schedule::from_str(&expression).map_err(|e| format!("invalid schedule '{schedule}': {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedule_takes_five_fields() {
+        assert!(parse("0 4 * * *").is_ok());
+        assert!(parse("@daily").is_ok());
+        assert!(parse("0 0 4 * * *").is_err());
+        assert!(parse("nightly").is_err());
+        assert!(parse("61 4 * * *").is_err());
+    }
+
+    #[test]
+    fn weekdays_count_from_sunday_zero() {
+        use chrono::{Datelike, TimeZone, Utc, Weekday};
+
+        let next = |schedule| {
+            let from = Utc.with_ymd_and_hms(2026, 10, 14, 0, 0, 0).unwrap();
+            parse(schedule)
+                .unwrap()
+                .after(&from)
+                .next()
+                .unwrap()
+                .weekday()
+        };
+        assert_eq!(next("0 5 * * 0"), Weekday::Sun);
+        assert_eq!(next("0 5 * * 7"), Weekday::Sun);
+        assert_eq!(next("0 5 * * 1"), Weekday::Mon);
+        assert_eq!(next("0 5 * * 6"), Weekday::Sat);
+        assert_eq!(next("0 5 * * 5-6"), Weekday::Fri);
+        assert_eq!(next("0 5 * * SUN"), Weekday::Sun);
+        assert!(parse("0 5 * * 1-5/2").is_ok());
+    }
+}
@@ -4,23 +4,31 @@ use std::sync::atomic::{AtomicBool, Ordering};
 
 use axum::Router;
 use axum::extract::State;
-use axum::http::StatusCode;
+use axum::http::{HeaderMap, StatusCode, header};
 use axum::response::IntoResponse;
-use axum::routing::get;
+use axum::routing::{get, post};
 use prometheus::Encoder;
+use sha2::{Digest, Sha256};
 use tracing::info;
 
+use crate::render;
+
 /// Shared state for the HTTP health/metrics server.
 #[derive(Clone)]
 pub struct ServerState {
     ready: Arc<AtomicBool>,
+    render: Option<Arc<RenderEndpoint>>,
+}
+
+/// Settings for `POST /render`, which is off unless a token is configured.
+struct RenderEndpoint {
+    token_digest: [u8; 32],
+    options: render::Options,
 }
 
 impl Default for ServerState {
     fn default() -> Self {
-        Self {
-            ready: Arc::new(AtomicBool::new(false)),
-        }
+        Self::new()
     }
 }
 
@@ -28,9 +36,20 @@ impl ServerState {
     pub fn new() -> Self {
         Self {
             ready: Arc::new(AtomicBool::new(false)),
+            render: None,
         }
     }
 
+    /// Enable `POST /render` for callers presenting `token` as a bearer
+    /// token.
+    pub fn with_render_token(mut self, token: &str, options: render::Options) -> Self {
+        self.render = Some(Arc::new(RenderEndpoint {
+            token_digest: Sha256::digest(token.as_bytes()).into(),
+            options,
+        }));
+        self
+    }
+
     /// Mark the operator as ready (call after CRD registration).
     pub fn set_ready(&self) {
         self.ready.store(true, Ordering::Relaxed);
@@ -43,12 +62,10 @@ impl ServerState {
 /// - `GET /metrics` — Prometheus text format
 /// - `GET /healthz` — liveness probe (always 200)
 /// - `GET /readyz`  — readiness probe (200 after initial sync)
+/// - `POST /render` — child manifests for a ServarrApp (bearer token, off
+///   unless configured)
 pub async fn run(port: u16, state: ServerState) -> anyhow::Result<()> {
-    let app = Router::new()
-        .route("/metrics", get(metrics_handler))
-        .route("/healthz", get(healthz_handler))
-        .route("/readyz", get(readyz_handler))
-        .with_state(state);
+    let app = router(state);
 
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
     info!(%addr, "starting metrics/health server");
@@ -58,6 +75,15 @@ pub async fn run(port: u16, state: ServerState) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn router(state: ServerState) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/healthz", get(healthz_handler))
+        .route("/readyz", get(readyz_handler))
+        .route("/render", post(render_handler))
+        .with_state(state)
+}
+
 async fn metrics_handler() -> impl IntoResponse {
     let encoder = prometheus::TextEncoder::new();
     let metric_families = prometheus::gather();
@@ -86,6 +112,66 @@ async fn readyz_handler(State(state): State<ServerState>) -> impl IntoResponse {
     }
 }
 
+/// Render the POSTed ServarrApp manifest into its child manifests, as a
+/// multi-document YAML stream. Never touches the cluster.
+async fn render_handler(
+    State(state): State<ServerState>,
+    headers: HeaderMap,
+    body: String,
+) -> impl IntoResponse {
+    let Some(ref endpoint) = state.render else {
+        return plain(StatusCode::NOT_FOUND, "render endpoint is disabled".into());
+    };
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    // Comparing digests keeps the comparison time independent of how much
+    // of the token matched.
+    let authorized = token
+        .is_some_and(|t| <[u8; 32]>::from(Sha256::digest(t.as_bytes())) == endpoint.token_digest);
+    if !authorized {
+        return plain(
+            StatusCode::UNAUTHORIZED,
+            "invalid or missing bearer token".into(),
+        );
+    }
+
+    let rendered = render::parse(&body)
+        .and_then(|app| render::render(&app, &endpoint.options))
+        .map_err(|e| {
+            let status = match e {
+                render::Error::Parse(_) | render::Error::Kind(_) => StatusCode::BAD_REQUEST,
+                render::Error::Patch(_) => StatusCode::UNPROCESSABLE_ENTITY,
+                render::Error::Serialization(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (status, e.to_string())
+        })
+        .and_then(|objects| {
+            render::to_yaml(&objects)
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+        });
+    match rendered {
+        Ok(yaml) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "application/yaml")],
+            yaml,
+        ),
+        Err((status, msg)) => plain(status, msg),
+    }
+}
+
+fn plain(
+    status: StatusCode,
+    msg: String,
+) -> (StatusCode, [(header::HeaderName, &'static str); 1], String) {
+    (
+        status,
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        msg,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,11 +180,17 @@ mod tests {
     use tower::ServiceExt; // for oneshot
 
     fn build_app(state: ServerState) -> Router {
-        Router::new()
-            .route("/metrics", get(metrics_handler))
-            .route("/healthz", get(healthz_handler))
-            .route("/readyz", get(readyz_handler))
-            .with_state(state)
+        router(state)
+    }
+
+    const SONARR: &str = "apiVersion: servarr.dev/v1alpha1\nkind: ServarrApp\nmetadata:\n  name: sonarr\n  namespace: media\nspec:\n  app: Sonarr\n";
+
+    fn render_request(token: Option<&str>, body: &str) -> Request<Body> {
+        let mut req = Request::post("/render");
+        if let Some(token) = token {
+            req = req.header("authorization", format!("Bearer {token}"));
+        }
+        req.body(Body::from(body.to_string())).unwrap()
     }
 
     #[tokio::test]
@@ -169,4 +261,52 @@ mod tests {
         state.set_ready();
         assert!(state.ready.load(Ordering::Relaxed));
     }
+
+    #[tokio::test]
+    async fn render_is_disabled_without_token() {
+        let app = build_app(ServerState::new());
+        let response = app
+            .oneshot(render_request(Some("secret"), SONARR))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn render_requires_bearer_token() {
+        let state = ServerState::new().with_render_token("secret", render::Options::default());
+        for token in [None, Some("wrong")] {
+            let response = build_app(state.clone())
+                .oneshot(render_request(token, SONARR))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{token:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn render_returns_child_manifests() {
+        let state = ServerState::new().with_render_token("secret", render::Options::default());
+        let response = build_app(state.clone())
+            .oneshot(render_request(Some("secret"), SONARR))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/yaml");
+        let body = axum::body::to_bytes(response.into_body(), 1_048_576)
+            .await
+            .unwrap();
+        let yaml = String::from_utf8(body.to_vec()).unwrap();
+        assert!(
+            yaml.starts_with("apiVersion: apps/v1\nkind: Deployment\n"),
+            "{yaml}"
+        );
+        assert!(yaml.contains("namespace: media"));
+
+        let response = build_app(state)
+            .oneshot(render_request(Some("secret"), "kind: ConfigMap\n"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
 }
@@ -0,0 +1,204 @@
+//! Post-install guidance for `status.message`, the CR equivalent of a Helm
+//! chart's NOTES.txt: where the app is reachable, which Secrets hold its
+//! credentials, and what is left to do by hand.
+
+use kube::ResourceExt;
+use servarr_crds::{AppDefaults, AppType, ServarrApp};
+
+/// Guidance for a ready `app` in `ns`. `synced` is how many apps the
+/// Prowlarr or Overseerr sync registered on this pass, if it ran.
+pub fn message(app: &ServarrApp, ns: &str, synced: Option<usize>) -> String {
+    let name = app.name_any();
+    let mut lines = vec![access(app, ns, &name)];
+
+    if let Some((secret, key)) = app.spec.api_key_ref() {
+        lines.push(format!("API key: Secret {secret}, key {key}."));
+    }
+    if let Some(ref creds) = app.spec.admin_credentials {
+        lines.push(format!(
+            "Admin login: Secret {}, keys username and password.",
+            creds.secret_name
+        ));
+    }
+    if let Some(step) = next_step(app, synced) {
+        lines.push(format!("Next: {step}"));
+    }
+    lines.join(" ")
+}
+
+fn access(app: &ServarrApp, ns: &str, name: &str) -> String {
+    if app.spec.app == AppType::SshBastion {
+        let defaults = AppDefaults::for_app(&app.spec.app);
+        let svc = app.spec.service.as_ref().unwrap_or(&defaults.service);
+        let port = svc.ports.first().map(|p| p.port).unwrap_or(80);
+        let host = format!("{}.{ns}.svc", servarr_resources::common::app_name(app));
+        return format!("{name} is ready; SSH to {host} port {port} from inside the cluster.");
+    }
+    let internal = servarr_resources::common::internal_url(app);
+    let urls = servarr_resources::common::external_urls(app);
+    if urls.is_empty() {
+        format!(
+            "{name} is ready at {internal} (in-cluster only; set gateway or ingress to expose it)."
+        )
+    } else {
+        format!(
+            "{name} is ready at {} (in-cluster: {internal}).",
+            urls.join(", ")
+        )
+    }
+}
+
+fn next_step(app: &ServarrApp, synced: Option<usize>) -> Option<String> {
+    let spec = &app.spec;
+    Some(match spec.app {
+        AppType::Prowlarr if spec.prowlarr_sync.as_ref().is_some_and(|s| s.enabled) => {
+            match synced {
+                Some(n) => format!("Prowlarr sync registered {n} app(s); add indexers in Prowlarr."),
+                None => "add indexers in Prowlarr; ready Sonarr, Radarr, Lidarr, Readarr, and Whisparr apps are registered automatically.".into(),
+            }
+        }
+        AppType::Prowlarr => {
+            "add indexers, then enable prowlarrSync to register Sonarr, Radarr, Lidarr, Readarr, and Whisparr automatically.".into()
+        }
+        AppType::Overseerr if spec.overseerr_sync.as_ref().is_some_and(|s| s.enabled) => {
+            match synced {
+                Some(n) => format!(
+                    "Overseerr sync registered {n} Sonarr/Radarr server(s); sign in with your media server account."
+                ),
+                None => "sign in with your media server account; Sonarr and Radarr are registered automatically.".into(),
+            }
+        }
+        AppType::Overseerr => {
+            "sign in with your media server account and add Sonarr and Radarr, or enable overseerrSync.".into()
+        }
+        AppType::Jellyseerr if spec.overseerr_sync.as_ref().is_some_and(|s| s.enabled) => {
+            match synced {
+                Some(n) => format!(
+                    "Jellyseerr sync registered {n} Sonarr/Radarr server(s); sign in with your Jellyfin account."
+                ),
+                None => "sign in with your Jellyfin account; Jellyfin, Sonarr, and Radarr are connected automatically.".into(),
+            }
+        }
+        AppType::Jellyseerr => {
+            "sign in with your Jellyfin account and add Sonarr and Radarr, or enable overseerrSync.".into()
+        }
+        AppType::Bazarr if spec.bazarr_sync.as_ref().is_some_and(|s| s.enabled) => {
+            match synced {
+                Some(n) => format!(
+                    "Bazarr sync connected {n} Sonarr/Radarr app(s); log in to subtitle providers under Settings > Providers."
+                ),
+                None => "log in to subtitle providers under Settings > Providers; Sonarr and Radarr are connected automatically.".into(),
+            }
+        }
+        AppType::Bazarr => {
+            "connect Sonarr and Radarr under Settings, or enable bazarrSync.".into()
+        }
+        AppType::Sonarr
+        | AppType::Radarr
+        | AppType::Lidarr
+        | AppType::Readarr
+        | AppType::Whisparr => {
+            "add a root folder and a download client in Settings.".into()
+        }
+        AppType::Jellyfin | AppType::Plex => "finish the setup wizard in the web UI.".into(),
+        AppType::Tdarr => {
+            "add libraries under Libraries; transcoding runs on the nodes of a TdarrNode app.".into()
+        }
+        AppType::Qbittorrent => {
+            "sign in with the temporary password from the container log, then set a password under Options > Web UI.".into()
+        }
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use servarr_crds::*;
+
+    fn app(name: &str, spec: ServarrAppSpec) -> ServarrApp {
+        let mut app = ServarrApp::new(name, spec);
+        app.metadata.namespace = Some("media".into());
+        app
+    }
+
+    #[test]
+    fn cluster_internal_app_with_secrets() {
+        let app = app(
+            "sonarr",
+            ServarrAppSpec {
+                app: AppType::Sonarr,
+                api_key_secret: Some("sonarr-api-key".into()),
+                admin_credentials: Some(AdminCredentialsSpec {
+                    secret_name: "sonarr-admin".into(),
+                }),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            message(&app, "media", None),
+            "sonarr is ready at http://sonarr.media.svc:8989 (in-cluster only; set gateway or ingress to expose it). \
+             API key: Secret sonarr-api-key, key api-key. \
+             Admin login: Secret sonarr-admin, keys username and password. \
+             Next: add a root folder and a download client in Settings."
+        );
+    }
+
+    #[test]
+    fn gateway_hosts_and_prowlarr_sync() {
+        let app = app(
+            "prowlarr",
+            ServarrAppSpec {
+                app: AppType::Prowlarr,
+                gateway: Some(GatewaySpec {
+                    enabled: true,
+                    hosts: vec!["prowlarr.example.com".into()],
+                    tls: Some(TlsSpec {
+                        enabled: true,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                prowlarr_sync: Some(ProwlarrSyncSpec {
+                    enabled: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        );
+        let msg = message(&app, "media", Some(3));
+        assert!(
+            msg.starts_with(
+                "prowlarr is ready at https://prowlarr.example.com (in-cluster: http://prowlarr.media.svc:9696)."
+            ),
+            "{msg}"
+        );
+        assert!(
+            msg.ends_with("Next: Prowlarr sync registered 3 app(s); add indexers in Prowlarr.")
+        );
+    }
+
+    #[test]
+    fn ingress_host_and_path() {
+        let app = app(
+            "sonarr",
+            ServarrAppSpec {
+                app: AppType::Sonarr,
+                ingress: Some(IngressSpec {
+                    host: "media.example.com".into(),
+                    path: Some("/sonarr".into()),
+                    tls_secret_name: Some("media-tls".into()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        );
+        let msg = message(&app, "media", None);
+        assert!(
+            msg.starts_with(
+                "sonarr is ready at https://media.example.com/sonarr (in-cluster: http://sonarr.media.svc:8989)."
+            ),
+            "{msg}"
+        );
+    }
+}
@@ -0,0 +1,464 @@
+//! Moving a volume to another storage class. A PVC's class cannot change
+//! in place, so for a volume with `migrateStorage` whose class no longer
+//! matches its claim, the app is scaled to zero, a Job copies the data into
+//! a new PVC, and the Deployment is switched to that claim. The mapping is
+//! kept in `status.volumeClaims` so later reconciles keep using it.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use k8s_openapi::api::batch::v1::Job;
+use k8s_openapi::api::core::v1::{ObjectReference, PersistentVolumeClaim};
+use kube::api::{Api, DeleteParams, Patch, PatchParams};
+use kube::runtime::events::{Event, EventType};
+use kube::{Client, ResourceExt};
+use servarr_crds::{
+    Condition, PvcVolume, ServarrApp, StorageMigrationPhase, StorageMigrationStatus,
+    VolumeDeletionPolicy, condition_types,
+};
+use servarr_resources::{common, pvc, storage_migration};
+use tracing::{info, warn};
+
+use crate::events::ThrottledRecorder;
+
+/// Storage migration state after a reconcile pass.
+#[derive(Debug, Default)]
+pub struct MigrationState {
+    /// Volumes now served by a migrated PVC.
+    pub volume_claims: BTreeMap<String, String>,
+    /// Migrations still copying or failed.
+    pub migrations: Vec<StorageMigrationStatus>,
+    /// Migrations that could not start, e.g. because the target PVC name is
+    /// taken. Re-evaluated every reconcile, so not kept in status.
+    pub blocked: Vec<String>,
+}
+
+impl MigrationState {
+    /// Whether a copy Job is running, so the app must stay scaled down.
+    pub fn copying(&self) -> bool {
+        self.migrations
+            .iter()
+            .any(|m| m.phase == StorageMigrationPhase::Copying)
+    }
+
+    /// `app` with this state's volume claims, for the resource builders.
+    pub fn with_volume_claims(&self, app: Arc<ServarrApp>) -> Arc<ServarrApp> {
+        let current = app.status.as_ref().map(|s| &s.volume_claims);
+        if current.is_some_and(|c| *c == self.volume_claims)
+            || (current.is_none() && self.volume_claims.is_empty())
+        {
+            return app;
+        }
+        let mut app = (*app).clone();
+        app.status.get_or_insert_default().volume_claims = self.volume_claims.clone();
+        Arc::new(app)
+    }
+
+    /// The `StorageMigration` condition, or `None` for an app that has
+    /// never migrated a volume.
+    pub fn condition(&self, now: &str) -> Option<Condition> {
+        let failures: Vec<String> = self
+            .blocked
+            .iter()
+            .cloned()
+            .chain(
+                self.migrations
+                    .iter()
+                    .filter(|m| m.phase == StorageMigrationPhase::Failed)
+                    .map(|m| {
+                        format!(
+                            "{}: {}",
+                            m.volume,
+                            m.message.as_deref().unwrap_or("copy Job failed")
+                        )
+                    }),
+            )
+            .collect();
+        if !failures.is_empty() {
+            return Some(Condition::fail(
+                condition_types::STORAGE_MIGRATION,
+                "MigrationFailed",
+                &failures.join("; "),
+                now,
+            ));
+        }
+        let copying: Vec<String> = self
+            .migrations
+            .iter()
+            .map(|m| {
+                format!(
+                    "{} -> {} ({})",
+                    m.source_claim, m.target_claim, m.storage_class
+                )
+            })
+            .collect();
+        if !copying.is_empty() {
+            return Some(Condition::fail(
+                condition_types::STORAGE_MIGRATION,
+                "Copying",
+                &format!("Copying {}", copying.join(", ")),
+                now,
+            ));
+        }
+        if self.volume_claims.is_empty() {
+            return None;
+        }
+        let moved: Vec<String> = self
+            .volume_claims
+            .iter()
+            .map(|(volume, claim)| format!("{volume} -> {claim}"))
+            .collect();
+        Some(Condition::ok(
+            condition_types::STORAGE_MIGRATION,
+            "Migrated",
+            &format!("Volumes migrated: {}", moved.join(", ")),
+            now,
+        ))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum JobOutcome {
+    Missing,
+    Running,
+    Succeeded,
+    Failed(String),
+}
+
+pub(crate) fn job_outcome(job: Option<&Job>) -> JobOutcome {
+    let Some(job) = job else {
+        return JobOutcome::Missing;
+    };
+    let status = job.status.as_ref();
+    if status.and_then(|s| s.succeeded).unwrap_or(0) > 0 {
+        return JobOutcome::Succeeded;
+    }
+    let failed = status
+        .and_then(|s| s.conditions.as_ref())
+        .and_then(|c| c.iter().find(|c| c.type_ == "Failed" && c.status == "True"));
+    match failed {
+        Some(c) => JobOutcome::Failed(
+            c.message
+                .clone()
+                .or_else(|| c.reason.clone())
+                .unwrap_or_else(|| "copy Job failed".into()),
+        ),
+        None => JobOutcome::Running,
+    }
+}
+
+async fn publish(
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &ObjectReference,
+    type_: EventType,
+    reason: &str,
+    note: String,
+) -> Result<(), kube::Error> {
+    recorder
+        .publish(
+            &Event {
+                type_,
+                reason: reason.into(),
+                note: Some(note),
+                action: "StorageMigration".into(),
+                secondary: None,
+            },
+            obj_ref,
+        )
+        .await
+}
+
+/// Advance the storage migrations of `app` by one step: start a copy for
+/// every `migrateStorage` volume whose PVC has the wrong class, and
+/// finish the ones whose Job has succeeded.
+pub async fn reconcile(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    pp: &PatchParams,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &ObjectReference,
+) -> Result<MigrationState, kube::Error> {
+    let status = app.status.as_ref();
+    let mut state = MigrationState {
+        volume_claims: status.map(|s| s.volume_claims.clone()).unwrap_or_default(),
+        ..Default::default()
+    };
+    let volumes: Vec<&PvcVolume> = app
+        .spec
+        .persistence
+        .iter()
+        .flat_map(|p| &p.volumes)
+        .filter(|v| v.migrate_storage && !v.storage_class.is_empty())
+        .collect();
+    if volumes.is_empty() {
+        return Ok(state);
+    }
+
+    let name = app.name_any();
+    let pvc_api = Api::<PersistentVolumeClaim>::namespaced(client.clone(), ns);
+    let job_api = Api::<Job>::namespaced(client.clone(), ns);
+
+    for vol in volumes {
+        let job_name = storage_migration::job_name(app, &vol.name);
+        let previous = status
+            .into_iter()
+            .flat_map(|s| &s.storage_migrations)
+            .find(|m| m.volume == vol.name && m.storage_class == vol.storage_class);
+
+        if let Some(previous) = previous {
+            match job_outcome(job_api.get_opt(&job_name).await?.as_ref()) {
+                JobOutcome::Running => state.migrations.push(StorageMigrationStatus {
+                    phase: StorageMigrationPhase::Copying,
+                    message: None,
+                    ..previous.clone()
+                }),
+                JobOutcome::Failed(message) => {
+                    if previous.phase == StorageMigrationPhase::Copying {
+                        warn!(%name, volume = %vol.name, %message, "storage migration failed");
+                        publish(
+                            recorder,
+                            obj_ref,
+                            EventType::Warning,
+                            "StorageMigrationFailed",
+                            format!(
+                                "Copying {} to {} failed: {message}",
+                                previous.source_claim, previous.target_claim
+                            ),
+                        )
+                        .await?;
+                    }
+                    state.migrations.push(StorageMigrationStatus {
+                        phase: StorageMigrationPhase::Failed,
+                        message: Some(message),
+                        ..previous.clone()
+                    });
+                }
+                JobOutcome::Missing => {
+                    // First pass after a superseded Job was removed, or the
+                    // user deleted a failed Job to retry
+                    let job = storage_migration::build_copy_job(
+                        app,
+                        &vol.name,
+                        &previous.source_claim,
+                        &previous.target_claim,
+                    );
+                    job_api.patch(&job_name, pp, &Patch::Apply(&job)).await?;
+                    state.migrations.push(StorageMigrationStatus {
+                        phase: StorageMigrationPhase::Copying,
+                        message: None,
+                        ..previous.clone()
+                    });
+                }
+                JobOutcome::Succeeded => {
+                    info!(%name, volume = %vol.name, target = %previous.target_claim, "storage migration complete");
+                    state
+                        .volume_claims
+                        .insert(vol.name.clone(), previous.target_claim.clone());
+                    job_api
+                        .delete(&job_name, &DeleteParams::background())
+                        .await?;
+                    if vol.deletion_policy == VolumeDeletionPolicy::Delete {
+                        match pvc_api
+                            .delete(&previous.source_claim, &DeleteParams::default())
+                            .await
+                        {
+                            Ok(_) => {}
+                            Err(kube::Error::Api(e)) if e.code == 404 => {}
+                            Err(e) => return Err(e),
+                        }
+                    }
+                    publish(
+                        recorder,
+                        obj_ref,
+                        EventType::Normal,
+                        "StorageMigrationComplete",
+                        format!(
+                            "Volume {} now uses PVC {} ({}); old PVC {} {}",
+                            vol.name,
+                            previous.target_claim,
+                            vol.storage_class,
+                            previous.source_claim,
+                            match vol.deletion_policy {
+                                VolumeDeletionPolicy::Retain => "retained",
+                                VolumeDeletionPolicy::Delete => "deleted",
+                            }
+                        ),
+                    )
+                    .await?;
+                }
+            }
+            continue;
+        }
+
+        // No migration to this class yet: start one if the claim's class
+        // differs. A missing claim is created with the right class later.
+        let source = state
+            .volume_claims
+            .get(&vol.name)
+            .cloned()
+            .unwrap_or_else(|| common::child_name(app, &vol.name));
+        let Some(existing) = pvc_api.get_opt(&source).await? else {
+            continue;
+        };
+        let class = existing
+            .spec
+            .as_ref()
+            .and_then(|s| s.storage_class_name.as_deref());
+        if class == Some(vol.storage_class.as_str()) {
+            continue;
+        }
+
+        let target = storage_migration::target_claim_name(app, vol);
+        if pvc_api.get_opt(&target).await?.is_some() {
+            state.blocked.push(format!(
+                "{}: PVC {target} already exists; delete it to migrate to {}",
+                vol.name, vol.storage_class
+            ));
+            continue;
+        }
+
+        info!(%name, volume = %vol.name, %source, %target, "starting storage migration");
+        pvc_api
+            .patch(
+                &target,
+                pp,
+                &Patch::Apply(&pvc::build_claim(app, vol, &target)),
+            )
+            .await?;
+        if job_api.get_opt(&job_name).await?.is_some() {
+            // Left over from a migration to another class; the Job is
+            // recreated for this one on the next pass
+            job_api
+                .delete(&job_name, &DeleteParams::background())
+                .await?;
+        } else {
+            let job = storage_migration::build_copy_job(app, &vol.name, &source, &target);
+            job_api.patch(&job_name, pp, &Patch::Apply(&job)).await?;
+        }
+        publish(
+            recorder,
+            obj_ref,
+            EventType::Normal,
+            "StorageMigrationStarted",
+            format!(
+                "Scaling down to copy PVC {source} ({}) to {target} ({})",
+                class.unwrap_or("default class"),
+                vol.storage_class
+            ),
+        )
+        .await?;
+        state.migrations.push(StorageMigrationStatus {
+            volume: vol.name.clone(),
+            source_claim: source,
+            target_claim: target,
+            storage_class: vol.storage_class.clone(),
+            phase: StorageMigrationPhase::Copying,
+            message: None,
+        });
+    }
+
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::batch::v1::{JobCondition, JobStatus};
+
+    fn migration(phase: StorageMigrationPhase) -> StorageMigrationStatus {
+        StorageMigrationStatus {
+            volume: "config".into(),
+            source_claim: "sonarr-config".into(),
+            target_claim: "sonarr-config-fast".into(),
+            storage_class: "fast".into(),
+            phase,
+            message: None,
+        }
+    }
+
+    #[test]
+    fn job_outcome_reads_job_status() {
+        assert_eq!(job_outcome(None), JobOutcome::Missing);
+        assert_eq!(job_outcome(Some(&Job::default())), JobOutcome::Running);
+
+        let succeeded = Job {
+            status: Some(JobStatus {
+                succeeded: Some(1),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(job_outcome(Some(&succeeded)), JobOutcome::Succeeded);
+
+        let failed = Job {
+            status: Some(JobStatus {
+                failed: Some(4),
+                conditions: Some(vec![JobCondition {
+                    type_: "Failed".into(),
+                    status: "True".into(),
+                    reason: Some("BackoffLimitExceeded".into()),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            job_outcome(Some(&failed)),
+            JobOutcome::Failed("BackoffLimitExceeded".into())
+        );
+    }
+
+    #[test]
+    fn condition_reflects_migration_progress() {
+        let now = "2024-01-01T00:00:00Z";
+        assert!(MigrationState::default().condition(now).is_none());
+
+        let copying = MigrationState {
+            migrations: vec![migration(StorageMigrationPhase::Copying)],
+            ..Default::default()
+        };
+        assert!(copying.copying());
+        let cond = copying.condition(now).unwrap();
+        assert_eq!(cond.status, "False");
+        assert_eq!(cond.reason, "Copying");
+        assert!(cond.message.contains("sonarr-config -> sonarr-config-fast"));
+
+        let failed = MigrationState {
+            migrations: vec![migration(StorageMigrationPhase::Failed)],
+            ..Default::default()
+        };
+        assert!(!failed.copying());
+        assert_eq!(failed.condition(now).unwrap().reason, "MigrationFailed");
+
+        let blocked = MigrationState {
+            blocked: vec!["config: PVC sonarr-config-fast already exists".into()],
+            ..Default::default()
+        };
+        assert_eq!(blocked.condition(now).unwrap().reason, "MigrationFailed");
+
+        let done = MigrationState {
+            volume_claims: BTreeMap::from([("config".into(), "sonarr-config-fast".into())]),
+            ..Default::default()
+        };
+        let cond = done.condition(now).unwrap();
+        assert_eq!(cond.status, "True");
+        assert_eq!(cond.reason, "Migrated");
+    }
+
+    #[test]
+    fn with_volume_claims_only_clones_on_change() {
+        let app = Arc::new(ServarrApp::new("sonarr", Default::default()));
+        let unchanged = MigrationState::default().with_volume_claims(app.clone());
+        assert!(Arc::ptr_eq(&app, &unchanged));
+
+        let state = MigrationState {
+            volume_claims: BTreeMap::from([("config".into(), "sonarr-config-fast".into())]),
+            ..Default::default()
+        };
+        let updated = state.with_volume_claims(app.clone());
+        assert_eq!(common::claim_name(&updated, "config"), "sonarr-config-fast");
+        assert_eq!(common::claim_name(&app, "config"), "sonarr-config");
+    }
+}
@@ -0,0 +1,686 @@
+//! `servarr-operator support-bundle`: what a bug report against the operator
+//! needs, gathered from one namespace into a `.tar.gz`. The bundle holds the
+//! namespace's custom resources with their status, the live child objects of
+//! every ServarrApp, drift between those and what the operator would apply,
+//! recent events, the operator's log lines about the namespace, and the apps'
+//! own health checks. Secret values are redacted before anything is written.
+
+use std::collections::BTreeSet;
+use std::fmt::Debug;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Context as _;
+use k8s_openapi::NamespaceResourceScope;
+use k8s_openapi::api::core::v1::{Event, Pod};
+use kube::api::{Api, ApiResource, DynamicObject, GroupVersionKind, ListParams, LogParams};
+use kube::{Client, Resource, ResourceExt};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use servarr_crds::{
+    MaintenanceWindow, MediaStack, ServarrApp, ServarrAppSet, ServarrCredential, ServarrInventory,
+    ServarrPolicy,
+};
+
+use crate::port_forward::Forward;
+use crate::{controller, metrics, render};
+
+/// Label selecting the operator's own pods, as set by the Helm chart.
+pub const OPERATOR_SELECTOR: &str = "app.kubernetes.io/name=servarr-operator";
+
+/// Replacement for redacted values.
+pub const REDACTED: &str = "REDACTED";
+
+/// Words that mark a field or environment variable as holding a secret.
+const SENSITIVE_WORDS: &[&str] = &[
+    "password",
+    "passwd",
+    "apikey",
+    "token",
+    "secret",
+    "credential",
+    "privatekey",
+];
+
+/// Field-name suffixes for references to a Secret rather than its value,
+/// such as `apiKeySecret` or `credentialsSecretRef`.
+const SECRET_REFERENCE_SUFFIXES: &[&str] = &["secret", "secretkey", "ref", "name"];
+
+/// Markers in free text (log lines, event and health messages) that are
+/// followed by a secret, matched case-insensitively.
+const TEXT_MARKERS: &[&str] = &[
+    "apikey=",
+    "api_key=",
+    "token=",
+    "password=",
+    "x-api-key: ",
+    "<apikey>",
+];
+
+/// Characters ending a secret that follows one of [`TEXT_MARKERS`].
+const TEXT_DELIMITERS: [char; 9] = ['&', '"', '\'', ' ', ',', ';', '}', '<', '\\'];
+
+/// What to collect.
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Namespace the bundle covers.
+    pub namespace: String,
+    /// Namespace the operator runs in, for its logs.
+    pub operator_namespace: String,
+    /// How far back to read operator logs.
+    pub log_window: Duration,
+    /// Port-forward to each *arr app and record its health checks.
+    pub health: bool,
+}
+
+/// A file in the bundle, relative to the bundle's top directory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub path: String,
+    pub contents: Vec<u8>,
+}
+
+/// The collected files, plus whatever could not be collected.
+#[derive(Debug, Default)]
+pub struct Bundle {
+    pub entries: Vec<Entry>,
+    pub errors: Vec<String>,
+}
+
+impl Bundle {
+    pub fn add(&mut self, path: impl Into<String>, contents: impl Into<Vec<u8>>) {
+        self.entries.push(Entry {
+            path: path.into(),
+            contents: contents.into(),
+        });
+    }
+
+    /// Add `value` as YAML, redacted.
+    pub fn add_yaml(&mut self, path: impl Into<String>, mut value: Value) {
+        redact(&mut value);
+        match serde_yaml::to_string(&value) {
+            Ok(yaml) => self.add(path, yaml),
+            Err(e) => self.errors.push(format!("{}: {e}", path.into())),
+        }
+    }
+
+    fn error(&mut self, what: &str, err: impl std::fmt::Display) {
+        self.errors.push(redact_text(&format!("{what}: {err}")));
+    }
+
+    /// Write the bundle as a gzipped tarball with every file under `root/`.
+    pub fn write_tar_gz(&self, root: &str, out: impl Write) -> std::io::Result<()> {
+        let gz = flate2::write::GzEncoder::new(out, flate2::Compression::default());
+        let mut tar = tar::Builder::new(gz);
+        let mtime = chrono::Utc::now().timestamp().max(0) as u64;
+        for entry in &self.entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(entry.contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(mtime);
+            header.set_cksum();
+            tar.append_data(
+                &mut header,
+                format!("{root}/{}", entry.path),
+                entry.contents.as_slice(),
+            )?;
+        }
+        tar.into_inner()?.finish()?.flush()
+    }
+}
+
+fn normalized(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Whether an environment variable or field called `name` holds a secret.
+pub fn is_sensitive(name: &str) -> bool {
+    let name = normalized(name);
+    SENSITIVE_WORDS.iter().any(|w| name.contains(w))
+}
+
+fn is_secret_reference(key: &str) -> bool {
+    let key = normalized(key);
+    SECRET_REFERENCE_SUFFIXES.iter().any(|s| key.ends_with(s))
+}
+
+/// Redact secret values in a Kubernetes object: the data of a Secret, the
+/// values of sensitive environment variables and string fields, and the
+/// `last-applied-configuration` annotation, which repeats the whole object.
+/// `managedFields` are dropped as noise.
+pub fn redact(value: &mut Value) {
+    if value.get("kind").and_then(Value::as_str) == Some("Secret") {
+        for field in ["data", "stringData"] {
+            if let Some(Value::Object(data)) = value.get_mut(field) {
+                for v in data.values_mut() {
+                    *v = Value::String(REDACTED.into());
+                }
+            }
+        }
+    }
+    if let Some(Value::Object(meta)) = value.get_mut("metadata") {
+        meta.remove("managedFields");
+        if let Some(Value::Object(annotations)) = meta.get_mut("annotations") {
+            annotations.remove("kubectl.kubernetes.io/last-applied-configuration");
+        }
+    }
+    redact_fields(value);
+}
+
+fn redact_fields(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            let env_secret = map
+                .get("name")
+                .and_then(Value::as_str)
+                .is_some_and(is_sensitive);
+            for (k, v) in map.iter_mut() {
+                match v {
+                    Value::String(s) if !s.is_empty() => {
+                        let secret = (k == "value" && env_secret)
+                            || (is_sensitive(k) && !is_secret_reference(k));
+                        *s = if secret {
+                            REDACTED.into()
+                        } else {
+                            redact_text(s)
+                        };
+                    }
+                    _ => redact_fields(v),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_fields),
+        _ => {}
+    }
+}
+
+/// Redact whatever follows a [`TEXT_MARKERS`] entry in `text`, up to the
+/// next delimiter.
+pub fn redact_text(text: &str) -> String {
+    let lower = text.to_ascii_lowercase();
+    let mut spans = Vec::new();
+    for marker in TEXT_MARKERS {
+        let mut from = 0;
+        while let Some(i) = lower[from..].find(marker) {
+            let start = from + i + marker.len();
+            let end = text[start..]
+                .find(TEXT_DELIMITERS)
+                .map_or(text.len(), |j| start + j);
+            if end > start {
+                spans.push((start, end));
+            }
+            from = end;
+        }
+    }
+    if spans.is_empty() {
+        return text.to_string();
+    }
+    spans.sort();
+    let mut out = String::with_capacity(text.len());
+    let mut pos = 0;
+    for (start, end) in spans {
+        if start < pos {
+            pos = pos.max(end);
+            continue;
+        }
+        out.push_str(&text[pos..start]);
+        out.push_str(REDACTED);
+        pos = end;
+    }
+    out.push_str(&text[pos..]);
+    out
+}
+
+/// Whether an operator log line (JSON, one per line) concerns namespace
+/// `ns`: a `namespace` field or a controller `object.ref` ending in it.
+pub fn mentions_namespace(line: &str, ns: &str) -> bool {
+    line.contains(&format!("\"{ns}\"")) || line.contains(&format!(".{ns}\""))
+}
+
+/// Drift between a rendered child and its live object: the paths where the
+/// live object lacks or differs from what the operator would apply. Values
+/// are left out, as they can be secrets.
+pub fn drift_paths(desired: &Value, live: &Value) -> Vec<String> {
+    let (desired, live) = match (desired.get("spec"), live.get("spec")) {
+        (Some(d), Some(l)) => (d, l),
+        _ => (desired, live),
+    };
+    controller::json_diff_paths(desired, live, String::new())
+        .into_iter()
+        .map(|d| {
+            d.split_once(": ")
+                .map_or(d.clone(), |(path, _)| path.to_string())
+        })
+        .collect()
+}
+
+/// Name of the bundle's top directory, and of the tarball without
+/// `.tar.gz`.
+pub fn bundle_name(ns: &str, now: chrono::DateTime<chrono::Utc>) -> String {
+    format!("support-bundle-{ns}-{}", now.format("%Y%m%dT%H%M%SZ"))
+}
+
+/// List every `K` in `ns` into `crs/{plural}/{name}.yaml`.
+async fn collect_crs<K>(bundle: &mut Bundle, client: &Client, ns: &str) -> Vec<K>
+where
+    K: Resource<Scope = NamespaceResourceScope, DynamicType = ()>
+        + Clone
+        + Debug
+        + DeserializeOwned
+        + Serialize,
+{
+    let plural = K::plural(&());
+    match Api::<K>::namespaced(client.clone(), ns)
+        .list(&ListParams::default())
+        .await
+    {
+        Ok(list) => {
+            for obj in &list.items {
+                match serde_json::to_value(obj) {
+                    Ok(mut value) => {
+                        value["apiVersion"] = K::api_version(&()).into();
+                        value["kind"] = K::kind(&()).into();
+                        bundle.add_yaml(format!("crs/{plural}/{}.yaml", obj.name_any()), value);
+                    }
+                    Err(e) => bundle.error(&format!("{plural}/{}", obj.name_any()), e),
+                }
+            }
+            list.items
+        }
+        Err(e) => {
+            bundle.error(&format!("list {plural}"), e);
+            Vec::new()
+        }
+    }
+}
+
+/// Fetch the live counterpart of a rendered child object.
+async fn live_object(client: &Client, desired: &Value) -> anyhow::Result<Option<Value>> {
+    let api_version = desired["apiVersion"].as_str().unwrap_or_default();
+    let kind = desired["kind"].as_str().unwrap_or_default();
+    let name = desired["metadata"]["name"].as_str().unwrap_or_default();
+    let ns = desired["metadata"]["namespace"]
+        .as_str()
+        .unwrap_or_default();
+    let (group, version) = api_version.split_once('/').unwrap_or(("", api_version));
+    let resource = ApiResource::from_gvk(&GroupVersionKind::gvk(group, version, kind));
+    let api = Api::<DynamicObject>::namespaced_with(client.clone(), ns, &resource);
+    Ok(match api.get_opt(name).await? {
+        Some(obj) => Some(serde_json::to_value(obj)?),
+        None => None,
+    })
+}
+
+/// Live children of `app` under `children/{app}/`, and their drift from the
+/// rendered objects in `drift/{app}.txt`. Returns the children's names.
+async fn collect_children(
+    bundle: &mut Bundle,
+    client: &Client,
+    app: &ServarrApp,
+    opts: &render::Options,
+) -> Vec<String> {
+    let app_name = app.name_any();
+    let desired = match render::render(app, opts) {
+        Ok(objects) => objects,
+        Err(e) => {
+            bundle.error(&format!("render {app_name}"), e);
+            return Vec::new();
+        }
+    };
+    let mut names = Vec::new();
+    let mut drift = String::new();
+    for obj in &desired {
+        let kind = obj["kind"].as_str().unwrap_or_default().to_string();
+        let name = obj["metadata"]["name"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        names.push(name.clone());
+        match live_object(client, obj).await {
+            Ok(Some(live)) => {
+                for path in drift_paths(obj, &live) {
+                    drift.push_str(&format!("{kind}/{name}: {path}\n"));
+                }
+                bundle.add_yaml(
+                    format!("children/{app_name}/{}-{name}.yaml", kind.to_lowercase()),
+                    live,
+                );
+            }
+            Ok(None) => drift.push_str(&format!("{kind}/{name}: not found\n")),
+            Err(e) => bundle.error(&format!("get {kind}/{name}"), e),
+        }
+    }
+    if drift.is_empty() {
+        drift.push_str("no drift\n");
+    }
+    bundle.add(format!("drift/{app_name}.txt"), drift);
+    names
+}
+
+/// Events in `ns` about any object in `names`, oldest first.
+async fn collect_events(bundle: &mut Bundle, client: &Client, ns: &str, names: &BTreeSet<String>) {
+    let events = match Api::<Event>::namespaced(client.clone(), ns)
+        .list(&ListParams::default())
+        .await
+    {
+        Ok(list) => list.items,
+        Err(e) => return bundle.error("list events", e),
+    };
+    let mut events: Vec<(String, Value)> = events
+        .into_iter()
+        .filter(|e| {
+            e.involved_object
+                .name
+                .as_ref()
+                .is_some_and(|n| names.contains(n))
+        })
+        .map(|e| {
+            let time = e
+                .last_timestamp
+                .as_ref()
+                .map(|t| t.0.to_string())
+                .or_else(|| e.event_time.as_ref().map(|t| t.0.to_string()))
+                .unwrap_or_default();
+            let record = json!({
+                "time": time,
+                "type": e.type_,
+                "reason": e.reason,
+                "object": format!(
+                    "{}/{}",
+                    e.involved_object.kind.unwrap_or_default(),
+                    e.involved_object.name.unwrap_or_default()
+                ),
+                "count": e.count,
+                "message": e.message.as_deref().map(redact_text),
+            });
+            (time, record)
+        })
+        .collect();
+    events.sort_by(|a, b| a.0.cmp(&b.0));
+    bundle.add_yaml(
+        "events.yaml",
+        Value::Array(events.into_iter().map(|(_, e)| e).collect()),
+    );
+}
+
+/// The operator's log lines about `opts.namespace`, one file per pod.
+async fn collect_logs(bundle: &mut Bundle, client: &Client, opts: &Options) {
+    let pods = Api::<Pod>::namespaced(client.clone(), &opts.operator_namespace);
+    let list = match pods
+        .list(&ListParams::default().labels(OPERATOR_SELECTOR))
+        .await
+    {
+        Ok(list) => list.items,
+        Err(e) => return bundle.error("list operator pods", e),
+    };
+    if list.is_empty() {
+        bundle.errors.push(format!(
+            "no operator pods matching {OPERATOR_SELECTOR} in namespace {}",
+            opts.operator_namespace
+        ));
+    }
+    let params = LogParams {
+        since_seconds: Some(opts.log_window.as_secs().max(1) as i64),
+        ..Default::default()
+    };
+    for pod in list {
+        let pod_name = pod.name_any();
+        match pods.logs(&pod_name, &params).await {
+            Ok(logs) => {
+                let lines: String = logs
+                    .lines()
+                    .filter(|l| mentions_namespace(l, &opts.namespace))
+                    .map(|l| redact_text(l) + "\n")
+                    .collect();
+                bundle.add(format!("logs/{pod_name}.log"), lines);
+            }
+            Err(e) => bundle.error(&format!("logs of {pod_name}"), e),
+        }
+    }
+}
+
+/// The `/health` checks of an *arr app, read through a temporary
+/// port-forward.
+async fn app_health(client: &Client, ns: &str, app: &ServarrApp) -> anyhow::Result<Value> {
+    let (secret, key) = app.spec.api_key_ref().context("no apiKeySecret")?;
+    let api_key = servarr_api::read_secret_key(client, ns, secret, key).await?;
+    let forward = Forward::start(client, ns, app, 0).await?;
+    let api = servarr_api::ServarrClient::new(
+        &format!("http://127.0.0.1:{}", forward.local_port),
+        &api_key,
+        controller::app_type_to_kind(&app.spec.app),
+    )?;
+    let checks = api.health().await?;
+    Ok(Value::Array(
+        checks
+            .into_iter()
+            .map(|c| {
+                json!({
+                    "source": c.source,
+                    "type": c.check_type,
+                    "message": redact_text(&c.message),
+                    "wikiUrl": c.wiki_url,
+                })
+            })
+            .collect(),
+    ))
+}
+
+/// Collect the bundle for `opts.namespace`. Anything that cannot be read is
+/// noted in `Bundle::errors` rather than failing the whole bundle.
+pub async fn collect(client: &Client, opts: &Options) -> Bundle {
+    let ns = opts.namespace.as_str();
+    let mut bundle = Bundle::default();
+
+    let apps = collect_crs::<ServarrApp>(&mut bundle, client, ns).await;
+    let stacks = collect_crs::<MediaStack>(&mut bundle, client, ns).await;
+    let sets = collect_crs::<ServarrAppSet>(&mut bundle, client, ns).await;
+    let credentials = collect_crs::<ServarrCredential>(&mut bundle, client, ns).await;
+    let inventories = collect_crs::<ServarrInventory>(&mut bundle, client, ns).await;
+    let windows = collect_crs::<MaintenanceWindow>(&mut bundle, client, ns).await;
+    let policies = collect_crs::<ServarrPolicy>(&mut bundle, client, ns).await;
+
+    let mut names: BTreeSet<String> = apps.iter().map(|a| a.name_any()).collect();
+    names.extend(stacks.iter().map(|s| s.name_any()));
+    names.extend(sets.iter().map(|s| s.name_any()));
+    names.extend(credentials.iter().map(|c| c.name_any()));
+    names.extend(inventories.iter().map(|i| i.name_any()));
+    names.extend(windows.iter().map(|w| w.name_any()));
+    names.extend(policies.iter().map(|p| p.name_any()));
+
+    let render_opts = render::Options::from_env();
+    for app in &apps {
+        let children = collect_children(&mut bundle, client, app, &render_opts).await;
+        names.extend(children);
+    }
+    collect_events(&mut bundle, client, ns, &names).await;
+    collect_logs(&mut bundle, client, opts).await;
+
+    if opts.health {
+        for app in apps.iter().filter(|a| a.spec.app.is_servarr_v3()) {
+            match app_health(client, ns, app).await {
+                Ok(checks) => bundle.add_yaml(format!("health/{}.yaml", app.name_any()), checks),
+                Err(e) => bundle.error(&format!("health of {}", app.name_any()), e),
+            }
+        }
+    }
+
+    let summary = json!({
+        "namespace": ns,
+        "generated": controller::chrono_now(),
+        "cliVersion": metrics::VERSION,
+        "cliCommit": metrics::COMMIT,
+        "counts": {
+            "servarrApps": apps.len(),
+            "mediaStacks": stacks.len(),
+            "servarrAppSets": sets.len(),
+            "servarrCredentials": credentials.len(),
+            "servarrInventories": inventories.len(),
+            "maintenanceWindows": windows.len(),
+            "servarrPolicies": policies.len(),
+        },
+        "errors": bundle.errors,
+    });
+    bundle.add_yaml("summary.yaml", summary);
+    bundle
+}
+
+/// Collect the bundle for `opts.namespace` and write it to `output`, or to
+/// `support-bundle-{ns}-{time}.tar.gz` in the working directory.
+pub async fn run(client: Client, opts: &Options, output: Option<&Path>) -> anyhow::Result<()> {
+    let name = bundle_name(&opts.namespace, chrono::Utc::now());
+    let bundle = collect(&client, opts).await;
+    let dest = output
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| format!("{name}.tar.gz").into());
+    let file = std::fs::File::create(&dest)
+        .with_context(|| format!("failed to create {}", dest.display()))?;
+    bundle
+        .write_tar_gz(&name, std::io::BufWriter::new(file))
+        .with_context(|| format!("failed to write {}", dest.display()))?;
+    for error in &bundle.errors {
+        eprintln!("warning: {error}");
+    }
+    println!("{} files -> {}", bundle.entries.len(), dest.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn redact_secret_data_and_sensitive_env() {
+        let mut secret = json!({
+            "kind": "Secret",
+            "metadata": {
+                "name": "sonarr-api-key",
+                "managedFields": [{"manager": "servarr-operator"}],
+                "annotations": {
+                    "kubectl.kubernetes.io/last-applied-configuration": "{\"data\":{}}",
+                    "keep": "me",
+                },
+            },
+            "data": {"api-key": "c2VjcmV0"},
+        });
+        redact(&mut secret);
+        assert_eq!(secret["data"]["api-key"], REDACTED);
+        assert!(secret["metadata"].get("managedFields").is_none());
+        assert_eq!(secret["metadata"]["annotations"], json!({"keep": "me"}));
+
+        let mut deploy = json!({
+            "kind": "Deployment",
+            "spec": {"template": {"spec": {"containers": [{
+                "name": "transmission",
+                "env": [
+                    {"name": "TZ", "value": "UTC"},
+                    {"name": "TRANSMISSION_RPC_PASSWORD", "value": "hunter2"},
+                    {"name": "API_TOKEN", "valueFrom": {"secretKeyRef": {"name": "s", "key": "k"}}},
+                ],
+            }]}}},
+        });
+        redact(&mut deploy);
+        let env = &deploy["spec"]["template"]["spec"]["containers"][0]["env"];
+        assert_eq!(env[0]["value"], "UTC");
+        assert_eq!(env[1]["value"], REDACTED);
+        assert_eq!(env[2]["valueFrom"]["secretKeyRef"]["name"], "s");
+    }
+
+    #[test]
+    fn redact_keeps_secret_references() {
+        let mut app = json!({
+            "spec": {
+                "apiKeySecret": "sonarr-api-key",
+                "auth": {"password": "hunter2", "passwordSecret": "admin"},
+                "webhookUrl": "http://x/hook?apikey=abc123&x=1",
+            },
+        });
+        redact(&mut app);
+        assert_eq!(app["spec"]["apiKeySecret"], "sonarr-api-key");
+        assert_eq!(app["spec"]["auth"]["password"], REDACTED);
+        assert_eq!(app["spec"]["auth"]["passwordSecret"], "admin");
+        assert_eq!(
+            app["spec"]["webhookUrl"],
+            "http://x/hook?apikey=REDACTED&x=1"
+        );
+    }
+
+    #[test]
+    fn redact_text_masks_markers() {
+        assert_eq!(
+            redact_text("GET /api/v3/health?apiKey=abc&x=1 X-Api-Key: def"),
+            "GET /api/v3/health?apiKey=REDACTED&x=1 X-Api-Key: REDACTED"
+        );
+        assert_eq!(redact_text("nothing to hide"), "nothing to hide");
+        assert_eq!(redact_text("token="), "token=");
+    }
+
+    #[test]
+    fn log_lines_are_matched_by_namespace() {
+        let field = r#"{"fields":{"message":"synced","namespace":"media"}}"#;
+        let span = r#"{"span":{"object.ref":"ServarrApp.v1alpha1.servarr.dev/sonarr.media"}}"#;
+        let other = r#"{"fields":{"message":"synced","namespace":"media-test"}}"#;
+        assert!(mentions_namespace(field, "media"));
+        assert!(mentions_namespace(span, "media"));
+        assert!(!mentions_namespace(other, "media"));
+    }
+
+    #[test]
+    fn drift_paths_omit_values() {
+        let desired = json!({"spec": {"replicas": 1, "template": {"spec": {"env": "secret"}}}});
+        let live = json!({"spec": {"replicas": 2, "template": {"spec": {}}, "extra": true}});
+        assert_eq!(
+            drift_paths(&desired, &live),
+            vec!["replicas", "template.spec.env"]
+        );
+    }
+
+    #[test]
+    fn tarball_holds_every_entry_under_root() {
+        let mut bundle = Bundle::default();
+        bundle.add("summary.yaml", "namespace: media\n");
+        bundle.add_yaml(
+            "crs/servarrapps/sonarr.yaml",
+            json!({"spec": {"password": "x"}}),
+        );
+        let mut buf = Vec::new();
+        bundle
+            .write_tar_gz("support-bundle-media", &mut buf)
+            .unwrap();
+
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(buf.as_slice()));
+        let mut files = Vec::new();
+        for entry in archive.entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let path = entry.path().unwrap().display().to_string();
+            let mut contents = String::new();
+            entry.read_to_string(&mut contents).unwrap();
+            files.push((path, contents));
+        }
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].0, "support-bundle-media/summary.yaml");
+        assert_eq!(
+            files[1].0,
+            "support-bundle-media/crs/servarrapps/sonarr.yaml"
+        );
+        assert!(files[1].1.contains(REDACTED));
+        assert!(!files[1].1.contains(": x"));
+    }
+
+    #[test]
+    fn bundle_name_includes_namespace_and_time() {
+        let now = chrono::DateTime::parse_from_rfc3339("2024-05-01T12:30:05Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(
+            bundle_name("media", now),
+            "support-bundle-media-20240501T123005Z"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Sonarr/Radarr/Lidarr tags declared in `appConfig.tags`, and the tag IDs
+//! of labels other syncs refer to.
+//!
+//! The apps lowercase labels when they save a tag, so labels are compared
+//! without regard to case.
+
+use servarr_api::{ApiError, ServarrClient, Tag};
+
+/// Labels in `labels` that `existing` lacks, each once.
+pub fn missing<'a>(existing: &[Tag], labels: &'a [String]) -> Vec<&'a str> {
+    let mut missing: Vec<&str> = Vec::new();
+    for label in labels {
+        if !existing.iter().any(|t| t.label.eq_ignore_ascii_case(label))
+            && !missing.iter().any(|l| l.eq_ignore_ascii_case(label))
+        {
+            missing.push(label);
+        }
+    }
+    missing
+}
+
+/// The IDs of `labels`, creating the tags the app lacks and adding them to
+/// `known`.
+pub async fn ids(
+    api: &ServarrClient,
+    known: &mut Vec<Tag>,
+    labels: &[String],
+) -> Result<Vec<i64>, ApiError> {
+    let mut ids = Vec::new();
+    for label in labels {
+        let id = match known.iter().find(|t| t.label.eq_ignore_ascii_case(label)) {
+            Some(tag) => tag.id,
+            None => {
+                let tag = api.create_tag(label).await?;
+                known.push(tag.clone());
+                tag.id
+            }
+        };
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    Ok(ids)
+}
+
+/// What a sync pass did.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SyncOutcome {
+    pub created: Vec<String>,
+    /// `(label, error)` for tags that could not be created.
+    pub failed: Vec<(String, String)>,
+}
+
+impl SyncOutcome {
+    /// One-line summary for the `TagsSynced` condition.
+    pub fn message(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.created.is_empty() {
+            parts.push(format!("created: {}", self.created.join(", ")));
+        }
+        for (label, error) in &self.failed {
+            parts.push(format!("{label} failed: {error}"));
+        }
+        if parts.is_empty() {
+            "Tags match appConfig.tags".to_string()
+        } else {
+            parts.join("; ")
+        }
+    }
+}
+
+/// Create the tags in `labels` the app lacks.
+pub async fn sync(api: &ServarrClient, labels: &[String]) -> Result<SyncOutcome, ApiError> {
+    let existing = api.tags().await?;
+    let mut outcome = SyncOutcome::default();
+    for label in missing(&existing, labels) {
+        if let Some(e) = servarr_api::faults::sync("tags", label) {
+            outcome.failed.push((label.to_string(), e.to_string()));
+            continue;
+        }
+        match api.create_tag(label).await {
+            Ok(tag) => outcome.created.push(tag.label),
+            Err(e) => outcome.failed.push((label.to_string(), e.to_string())),
+        }
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_ignores_case_and_repeats() {
+        let existing = [Tag {
+            id: 1,
+            label: "4k".into(),
+        }];
+        let labels: Vec<String> = ["4K", "anime", "Anime", "kids"]
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        assert_eq!(missing(&existing, &labels), vec!["anime", "kids"]);
+    }
+
+    #[test]
+    fn outcome_message() {
+        assert_eq!(
+            SyncOutcome::default().message(),
+            "Tags match appConfig.tags"
+        );
+        let outcome = SyncOutcome {
+            created: vec!["anime".into()],
+            failed: vec![("kids".into(), "HTTP 500".into())],
+        };
+        assert_eq!(outcome.message(), "created: anime; kids failed: HTTP 500");
+    }
+}
@@ -0,0 +1,349 @@
+//! TRaSH-guide custom formats and quality definitions for `spec.trashSync`.
+//!
+//! Templates are JSON documents with a `customFormats` list, holding custom
+//! formats as the guide publishes them (`trash_id`, and `fields` as an
+//! object), and a `qualityDefinitions` list shaped like the `qualities` of
+//! the guide's quality-size files. Custom formats are matched to the app's
+//! by name and quality definitions by quality name; only those that differ
+//! from what the app has are written back.
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use servarr_api::{ApiError, ServarrClient};
+use servarr_crds::AppType;
+
+use crate::custom_formats::matches;
+
+/// Names of the templates built into the operator.
+pub const BUILTIN_TEMPLATES: &[&str] = &["quality-definitions", "unwanted"];
+
+/// The built-in template `name` for `app`, if there is one.
+pub fn builtin(app: &AppType, name: &str) -> Option<&'static str> {
+    Some(match (app, name) {
+        (AppType::Sonarr, "quality-definitions") => {
+            include_str!("trash_templates/sonarr/quality-definitions.json")
+        }
+        (AppType::Sonarr, "unwanted") => include_str!("trash_templates/sonarr/unwanted.json"),
+        (AppType::Radarr, "quality-definitions") => {
+            include_str!("trash_templates/radarr/quality-definitions.json")
+        }
+        (AppType::Radarr, "unwanted") => include_str!("trash_templates/radarr/unwanted.json"),
+        _ => return None,
+    })
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Template {
+    #[serde(default)]
+    pub custom_formats: Vec<Value>,
+    #[serde(default)]
+    pub quality_definitions: Vec<QualitySize>,
+}
+
+/// Size limits for one quality, in MB per minute.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct QualitySize {
+    pub quality: String,
+    pub min: f64,
+    #[serde(default)]
+    pub preferred: Option<f64>,
+    /// No upper limit when unset.
+    #[serde(default)]
+    pub max: Option<f64>,
+}
+
+impl Template {
+    pub fn parse(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| e.to_string())
+    }
+
+    /// `self` with `other` applied on top: custom formats and qualities
+    /// named in both take `other`'s version.
+    pub fn merge(mut self, other: Template) -> Self {
+        for format in other.custom_formats {
+            let name = format.get("name").cloned();
+            self.custom_formats
+                .retain(|f| f.get("name").cloned() != name);
+            self.custom_formats.push(format);
+        }
+        for size in other.quality_definitions {
+            self.quality_definitions
+                .retain(|q| q.quality != size.quality);
+            self.quality_definitions.push(size);
+        }
+        self
+    }
+}
+
+/// A guide custom format in the shape the app's API takes: guide-only keys
+/// dropped and each specification's `fields` object turned into the
+/// `[{name, value}]` list the apps use.
+pub fn custom_format(guide: &Value) -> Result<Value, String> {
+    let name = guide
+        .get("name")
+        .and_then(Value::as_str)
+        .filter(|n| !n.is_empty())
+        .ok_or("custom format has no name")?;
+    let specifications = guide
+        .get("specifications")
+        .and_then(Value::as_array)
+        .ok_or_else(|| format!("custom format '{name}' has no specifications"))?
+        .iter()
+        .map(|spec| {
+            let fields = match spec.get("fields") {
+                Some(Value::Object(fields)) => fields
+                    .iter()
+                    .map(|(name, value)| json!({ "name": name, "value": value }))
+                    .collect(),
+                Some(Value::Array(fields)) => fields.clone(),
+                _ => Vec::new(),
+            };
+            json!({
+                "name": spec.get("name").cloned().unwrap_or_default(),
+                "implementation": spec.get("implementation").cloned().unwrap_or_default(),
+                "negate": spec.get("negate").and_then(Value::as_bool).unwrap_or(false),
+                "required": spec.get("required").and_then(Value::as_bool).unwrap_or(false),
+                "fields": fields,
+            })
+        })
+        .collect::<Vec<_>>();
+    Ok(json!({
+        "name": name,
+        "includeCustomFormatWhenRenaming": guide
+            .get("includeCustomFormatWhenRenaming")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        "specifications": specifications,
+    }))
+}
+
+/// The app's quality definitions that `sizes` changes, updated, and the
+/// qualities in `sizes` the app doesn't have.
+pub fn quality_definitions(current: &[Value], sizes: &[QualitySize]) -> (Vec<Value>, Vec<String>) {
+    let mut changed = Vec::new();
+    let mut unknown = Vec::new();
+    for size in sizes {
+        let Some(definition) = current
+            .iter()
+            .find(|d| d.pointer("/quality/name").and_then(Value::as_str) == Some(&size.quality))
+        else {
+            unknown.push(size.quality.clone());
+            continue;
+        };
+        let mut updated = definition.clone();
+        updated["minSize"] = size.min.into();
+        updated["preferredSize"] = size.preferred.into();
+        updated["maxSize"] = size.max.into();
+        let same = |key: &str| definition.get(key).and_then(Value::as_f64) == updated[key].as_f64();
+        if !(same("minSize") && same("preferredSize") && same("maxSize")) {
+            changed.push(updated);
+        }
+    }
+    (changed, unknown)
+}
+
+/// What a sync pass did.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SyncOutcome {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub unchanged: usize,
+    /// Quality definitions updated.
+    pub quality_definitions: usize,
+    /// `(item, error)` for custom formats and qualities that could not be
+    /// applied.
+    pub failed: Vec<(String, String)>,
+}
+
+impl SyncOutcome {
+    /// One-line summary for `status.lastTrashSync.result`.
+    pub fn message(&self) -> String {
+        let mut parts = Vec::new();
+        for (label, names) in [("created", &self.created), ("updated", &self.updated)] {
+            if !names.is_empty() {
+                parts.push(format!("{label}: {}", names.join(", ")));
+            }
+        }
+        if self.unchanged > 0 {
+            parts.push(format!("{} custom format(s) unchanged", self.unchanged));
+        }
+        if self.quality_definitions > 0 {
+            parts.push(format!(
+                "{} quality definition(s) updated",
+                self.quality_definitions
+            ));
+        }
+        for (name, error) in &self.failed {
+            parts.push(format!("{name} failed: {error}"));
+        }
+        if parts.is_empty() {
+            "Nothing to sync".to_string()
+        } else {
+            parts.join("; ")
+        }
+    }
+}
+
+/// Apply `template` to the app behind `api`.
+pub async fn sync(api: &ServarrClient, template: &Template) -> Result<SyncOutcome, ApiError> {
+    let mut outcome = SyncOutcome::default();
+
+    if !template.custom_formats.is_empty() {
+        let existing = api.custom_formats().await?;
+        for guide in &template.custom_formats {
+            let label = guide
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or("<unnamed>")
+                .to_string();
+            let mut desired = match custom_format(guide) {
+                Ok(format) => format,
+                Err(e) => {
+                    outcome.failed.push((label, e));
+                    continue;
+                }
+            };
+            let current = existing
+                .iter()
+                .find(|f| f.get("name") == desired.get("name"));
+            let result = match current {
+                Some(current) if matches(current, &desired) => {
+                    outcome.unchanged += 1;
+                    continue;
+                }
+                Some(current) => {
+                    let id = current.get("id").and_then(Value::as_i64).unwrap_or(0);
+                    desired["id"] = id.into();
+                    api.update_custom_format(id, &desired)
+                        .await
+                        .map(|_| &mut outcome.updated)
+                }
+                None => api
+                    .create_custom_format(&desired)
+                    .await
+                    .map(|_| &mut outcome.created),
+            };
+            match result {
+                Ok(list) => list.push(label),
+                Err(e) => outcome.failed.push((label, e.to_string())),
+            }
+        }
+    }
+
+    if !template.quality_definitions.is_empty() {
+        let current = api.quality_definitions().await?;
+        let (changed, unknown) = quality_definitions(&current, &template.quality_definitions);
+        for quality in unknown {
+            outcome
+                .failed
+                .push((quality, "unknown quality".to_string()));
+        }
+        if !changed.is_empty() {
+            match api.update_quality_definitions(&changed).await {
+                Ok(_) => outcome.quality_definitions = changed.len(),
+                Err(e) => outcome
+                    .failed
+                    .push(("quality definitions".to_string(), e.to_string())),
+            }
+        }
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_templates_parse() {
+        for app in [AppType::Sonarr, AppType::Radarr] {
+            for name in BUILTIN_TEMPLATES {
+                let json = builtin(&app, name).unwrap();
+                let template = Template::parse(json).unwrap();
+                for format in &template.custom_formats {
+                    custom_format(format).unwrap();
+                }
+            }
+        }
+        assert!(builtin(&AppType::Lidarr, "unwanted").is_none());
+    }
+
+    #[test]
+    fn guide_fields_become_name_value_lists() {
+        let guide = json!({
+            "trash_id": "abc",
+            "trash_scores": { "default": -10000 },
+            "name": "x265 (HD)",
+            "specifications": [{
+                "name": "Not 2160p",
+                "implementation": "ResolutionSpecification",
+                "negate": true,
+                "required": true,
+                "fields": { "value": 2160 }
+            }]
+        });
+        let format = custom_format(&guide).unwrap();
+        assert!(format.get("trash_id").is_none());
+        assert_eq!(format["includeCustomFormatWhenRenaming"], false);
+        assert_eq!(
+            format["specifications"][0]["fields"],
+            json!([{ "name": "value", "value": 2160 }])
+        );
+        assert!(custom_format(&json!({ "name": "x" })).is_err());
+    }
+
+    #[test]
+    fn quality_definitions_only_returns_changes() {
+        let current = vec![
+            json!({ "id": 1, "quality": { "name": "HDTV-720p" }, "minSize": 10.0, "preferredSize": 995.0, "maxSize": 1000.0 }),
+            json!({ "id": 2, "quality": { "name": "WEBDL-1080p" }, "minSize": 0.0, "preferredSize": null, "maxSize": null }),
+        ];
+        let sizes = [
+            QualitySize {
+                quality: "HDTV-720p".into(),
+                min: 10.0,
+                preferred: Some(995.0),
+                max: Some(1000.0),
+            },
+            QualitySize {
+                quality: "WEBDL-1080p".into(),
+                min: 15.0,
+                preferred: Some(995.0),
+                max: None,
+            },
+            QualitySize {
+                quality: "Bluray-8K".into(),
+                min: 1.0,
+                preferred: None,
+                max: None,
+            },
+        ];
+        let (changed, unknown) = quality_definitions(&current, &sizes);
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0]["id"], 2);
+        assert_eq!(changed[0]["minSize"], 15.0);
+        assert_eq!(changed[0]["maxSize"], Value::Null);
+        assert_eq!(unknown, vec!["Bluray-8K".to_string()]);
+    }
+
+    #[test]
+    fn merge_replaces_by_name() {
+        let base = Template::parse(builtin(&AppType::Radarr, "unwanted").unwrap()).unwrap();
+        let overlay = Template::parse(
+            r#"{"customFormats": [{"name": "3D", "specifications": []}],
+                "qualityDefinitions": [{"quality": "Remux-2160p", "min": 200}]}"#,
+        )
+        .unwrap();
+        let count = base.custom_formats.len();
+        let merged = base.merge(overlay);
+        assert_eq!(merged.custom_formats.len(), count);
+        let three_d = merged
+            .custom_formats
+            .iter()
+            .find(|f| f["name"] == "3D")
+            .unwrap();
+        assert_eq!(three_d["specifications"], json!([]));
+        assert_eq!(merged.quality_definitions[0].max, None);
+    }
+}
@@ -0,0 +1,71 @@
+//! `spec.uiSettings` for the Servarr v3 apps.
+//!
+//! The app's `config/ui` resource also carries date formats and display
+//! toggles the operator doesn't manage, so the declared fields are set on
+//! the app's current copy and it is only written back when one differs.
+
+use serde_json::Value;
+use servarr_api::{ApiError, ServarrClient};
+use servarr_crds::UiSettingsSpec;
+
+/// `current` with `spec` applied, or `None` when it already matches.
+pub fn desired(spec: &UiSettingsSpec, current: &Value) -> Option<Value> {
+    let mut config = current.clone();
+    for (field, value) in spec.api_fields() {
+        config[field] = value;
+    }
+    (config != *current).then_some(config)
+}
+
+/// Apply `spec` to the app. Returns whether anything was changed.
+pub async fn sync(api: &ServarrClient, spec: &UiSettingsSpec) -> Result<bool, ApiError> {
+    let current = api.ui_config().await?;
+    let Some(config) = desired(spec, &current) else {
+        return Ok(false);
+    };
+    let id = current.get("id").and_then(Value::as_i64).unwrap_or(1);
+    api.update_ui_config(id, &config).await?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use servarr_crds::{FirstDayOfWeek, UiTheme};
+
+    fn current() -> Value {
+        json!({
+            "id": 1,
+            "firstDayOfWeek": 0,
+            "shortDateFormat": "MMM D YYYY",
+            "theme": "auto",
+            "uiLanguage": 1
+        })
+    }
+
+    #[test]
+    fn sets_only_declared_fields() {
+        let spec = UiSettingsSpec {
+            theme: Some(UiTheme::Dark),
+            first_day_of_week: Some(FirstDayOfWeek::Monday),
+            ui_language: None,
+        };
+        let config = desired(&spec, &current()).unwrap();
+        assert_eq!(config["theme"], "dark");
+        assert_eq!(config["firstDayOfWeek"], 1);
+        assert_eq!(config["uiLanguage"], 1);
+        assert_eq!(config["shortDateFormat"], "MMM D YYYY");
+    }
+
+    #[test]
+    fn matching_config_needs_no_update() {
+        let spec = UiSettingsSpec {
+            theme: Some(UiTheme::Auto),
+            ui_language: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(desired(&spec, &current()), None);
+        assert_eq!(desired(&UiSettingsSpec::default(), &current()), None);
+    }
+}
@@ -0,0 +1,209 @@
+//! Scheduled CSI VolumeSnapshots of an app's config volume, configured with
+//! `backup.volumeSnapshot`. This is the backup path for apps without a
+//! backup API (Plex, Jellyfin, Transmission), and an alternative to the API
+//! for those with one. Snapshots follow `backup.schedule` and maintenance
+//! windows like API backups, and the oldest beyond `retentionCount` are
+//! deleted after each new one.
+
+use chrono::Utc;
+use k8s_openapi::api::core::v1::ObjectReference;
+use kube::api::{Api, DeleteParams, DynamicObject, ListParams, PostParams};
+use kube::discovery::ApiResource;
+use kube::runtime::events::{Event, EventType};
+use kube::{Client, ResourceExt};
+use servarr_crds::{BackupStatus, ServarrApp};
+use servarr_resources::volume_snapshot;
+use tracing::{info, warn};
+
+use crate::controller::{backup_due, chrono_now, last_backup_time};
+use crate::events::ThrottledRecorder;
+use crate::metrics::increment_backup_operations;
+
+fn snapshot_resource() -> ApiResource {
+    ApiResource {
+        group: "snapshot.storage.k8s.io".into(),
+        version: "v1".into(),
+        api_version: "snapshot.storage.k8s.io/v1".into(),
+        kind: "VolumeSnapshot".into(),
+        plural: "volumesnapshots".into(),
+    }
+}
+
+/// Names of `snapshots`, oldest first. Snapshots without a creation time
+/// (not yet persisted) count as newest; names break ties.
+pub(crate) fn by_age(snapshots: &[DynamicObject]) -> Vec<String> {
+    let mut aged: Vec<_> = snapshots
+        .iter()
+        .map(|s| {
+            let created = s.metadata.creation_timestamp.as_ref().map(|t| t.0);
+            (created.is_none(), created, s.name_any())
+        })
+        .collect();
+    aged.sort();
+    aged.into_iter().map(|(_, _, name)| name).collect()
+}
+
+/// The oldest of `names` (oldest first) beyond the newest `retention`.
+pub(crate) fn to_prune(names: &[String], retention: u32) -> &[String] {
+    &names[..names.len().saturating_sub(retention.max(1) as usize)]
+}
+
+/// Take a VolumeSnapshot of the app's config volume when one is due and
+/// prune the oldest beyond `retentionCount`. Returns the status to record;
+/// between snapshots that is the previous status.
+pub async fn maybe_snapshot(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    maintenance_cutoff: Option<chrono::DateTime<Utc>>,
+    recorder: &ThrottledRecorder<'_>,
+    obj_ref: &ObjectReference,
+) -> Option<BackupStatus> {
+    let backup_spec = app.spec.backup.as_ref()?;
+    let spec = backup_spec.volume_snapshot.as_ref()?;
+    let previous = app
+        .status
+        .as_ref()
+        .and_then(|s| s.backup_status.clone())
+        .unwrap_or_default();
+
+    let now = Utc::now();
+    let (is_due, before_maintenance) =
+        match backup_due(backup_spec, last_backup_time(app), maintenance_cutoff, now) {
+            Ok(due) => due,
+            Err(e) => {
+                warn!(error = %e, "invalid cron schedule");
+                return Some(BackupStatus {
+                    last_backup_result: Some(e),
+                    ..previous
+                });
+            }
+        };
+    if !is_due {
+        return app.status.as_ref().and_then(|s| s.backup_status.clone());
+    }
+
+    let name = volume_snapshot::snapshot_name(app, &now.format("%Y%m%d%H%M%S").to_string());
+    let Some(snapshot) = volume_snapshot::build(app, spec, &name) else {
+        return Some(BackupStatus {
+            last_backup_result: Some("error: the app has no volume to snapshot".into()),
+            ..previous
+        });
+    };
+
+    let app_name = app.name_any();
+    let app_type = app.spec.app.as_str();
+    let _ = recorder
+        .publish(
+            &Event {
+                type_: EventType::Normal,
+                reason: "BackupStarted".into(),
+                note: Some(
+                    if before_maintenance {
+                        "Pre-maintenance volume snapshot started"
+                    } else {
+                        "Scheduled volume snapshot started"
+                    }
+                    .into(),
+                ),
+                action: "Backup".into(),
+                secondary: None,
+            },
+            obj_ref,
+        )
+        .await;
+
+    let api = Api::<DynamicObject>::namespaced_with(client.clone(), ns, &snapshot_resource());
+    info!(app = %app_name, snapshot = %name, "creating VolumeSnapshot");
+    if let Err(e) = api.create(&PostParams::default(), &snapshot).await {
+        warn!(app = %app_name, error = %e, "volume snapshot failed");
+        increment_backup_operations(app_type, "backup", "error");
+        let _ = recorder
+            .publish(
+                &Event {
+                    type_: EventType::Warning,
+                    reason: "BackupFailed".into(),
+                    note: Some(format!("VolumeSnapshot {name} failed: {e}")),
+                    action: "Backup".into(),
+                    secondary: None,
+                },
+                obj_ref,
+            )
+            .await;
+        return Some(BackupStatus {
+            last_backup_result: Some(format!("error: {e}")),
+            ..previous
+        });
+    }
+    increment_backup_operations(app_type, "backup", "success");
+    let _ = recorder
+        .publish(
+            &Event {
+                type_: EventType::Normal,
+                reason: "BackupCompleted".into(),
+                note: Some(format!("VolumeSnapshot {name} created")),
+                action: "Backup".into(),
+                secondary: None,
+            },
+            obj_ref,
+        )
+        .await;
+
+    let mut snapshots = match api
+        .list(&ListParams::default().labels(&volume_snapshot::selector(app)))
+        .await
+    {
+        Ok(list) => by_age(&list.items),
+        Err(e) => {
+            warn!(app = %app_name, error = %e, "failed to list VolumeSnapshots");
+            previous.snapshots.into_iter().chain([name]).collect()
+        }
+    };
+    let pruned = to_prune(&snapshots, backup_spec.retention_count).len();
+    for old in snapshots.drain(..pruned) {
+        match api.delete(&old, &DeleteParams::default()).await {
+            Ok(_) => info!(app = %app_name, snapshot = %old, "pruned VolumeSnapshot"),
+            Err(kube::Error::Api(e)) if e.code == 404 => {}
+            Err(e) => warn!(snapshot = %old, error = %e, "failed to prune old VolumeSnapshot"),
+        }
+    }
+
+    Some(BackupStatus {
+        last_backup_time: Some(chrono_now()),
+        last_backup_result: Some("success".into()),
+        backup_count: snapshots.len() as u32,
+        snapshots,
+        object_storage: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+
+    fn snapshot(name: &str, created: Option<&str>) -> DynamicObject {
+        let mut obj = DynamicObject::new(name, &snapshot_resource());
+        obj.metadata.creation_timestamp = created.map(|t| Time(t.parse().unwrap()));
+        obj
+    }
+
+    #[test]
+    fn by_age_sorts_oldest_first_pending_last() {
+        let snapshots = [
+            snapshot("b", Some("2024-01-02T00:00:00Z")),
+            snapshot("pending", None),
+            snapshot("a", Some("2024-01-01T00:00:00Z")),
+        ];
+        assert_eq!(by_age(&snapshots), ["a", "b", "pending"]);
+    }
+
+    #[test]
+    fn to_prune_keeps_the_newest_retention() {
+        let names: Vec<String> = ["a", "b", "c", "d"].map(String::from).into();
+        assert_eq!(to_prune(&names, 2), ["a", "b"]);
+        assert!(to_prune(&names, 5).is_empty());
+        // A retention of zero still keeps the snapshot just taken.
+        assert_eq!(to_prune(&names, 0), ["a", "b", "c"]);
+    }
+}
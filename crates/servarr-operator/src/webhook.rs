@@ -1,7 +1,7 @@
-use std::collections::HashSet;
-use std::net::SocketAddr;
+use std::collections::{BTreeSet, HashSet};
+use std::net::{IpAddr, SocketAddr};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use axum::extract::State;
 use axum::http::StatusCode;
@@ -9,22 +9,44 @@ use axum::response::IntoResponse;
 use axum::routing::post;
 use axum::{Json, Router};
 use axum_server::tls_rustls::RustlsConfig;
-use kube::Client;
 use kube::api::{Api, ListParams};
+use kube::{Client, CustomResourceExt};
 use serde::{Deserialize, Serialize};
-use servarr_crds::{AppConfig, AppType, ServarrApp, ServarrAppSpec, SshMode};
+use servarr_crds::{
+    AppConfig, AppDefaults, AppType, MediaStack, MediaStackSpec, ServarrApp, ServarrAppSpec,
+    SshMode, TransmissionConfig, TransmissionDirectories, UpdateMode, WorkloadType,
+    is_valid_instance,
+};
 use tracing::{debug, info, warn};
 
+use crate::image_digest::DigestResolver;
+use crate::mutate::MutateSettings;
+
 const DEFAULT_WEBHOOK_PORT: u16 = 9443;
 
 const DEFAULT_TLS_DIR: &str = "/etc/webhook/tls";
 
+/// Annotation that guards a ServarrApp or MediaStack against deletion when
+/// the webhook receives DELETE requests.
+const PROTECTED_ANNOTATION: &str = "servarr.dev/protected";
+
 /// Configuration for the webhook server.
 #[derive(Clone)]
 pub struct WebhookConfig {
     pub port: u16,
     pub tls_cert: PathBuf,
     pub tls_key: PathBuf,
+    /// Admit `spec.unsupportedOverrides` (`ALLOW_UNSUPPORTED_OVERRIDES`).
+    pub allow_unsupported_overrides: bool,
+    /// Defaults the mutating webhook fills in.
+    pub mutate: MutateSettings,
+    /// Refuse deletes of objects without `servarr.dev/protected: "false"`,
+    /// not only those annotated `"true"` (`WEBHOOK_PROTECT_BY_DEFAULT`).
+    pub protect_by_default: bool,
+    /// The operator's own username, whose deletes of stack children,
+    /// AppSet members, and canaries are never refused
+    /// (`WEBHOOK_OPERATOR_USERNAME`).
+    pub operator_username: Option<String>,
 }
 
 impl Default for WebhookConfig {
@@ -56,6 +78,20 @@ impl Default for WebhookConfig {
             port,
             tls_cert,
             tls_key,
+            allow_unsupported_overrides: crate::context::allow_unsupported_overrides(),
+            mutate: MutateSettings {
+                image_overrides: crate::context::load_image_overrides(),
+                default_arch: crate::context::load_default_arch(),
+                pin_digests: std::env::var("WEBHOOK_PIN_IMAGE_DIGESTS").is_ok_and(|v| {
+                    v.eq_ignore_ascii_case("true") || v == "1" || v.eq_ignore_ascii_case("yes")
+                }),
+            },
+            protect_by_default: std::env::var("WEBHOOK_PROTECT_BY_DEFAULT").is_ok_and(|v| {
+                v.eq_ignore_ascii_case("true") || v == "1" || v.eq_ignore_ascii_case("yes")
+            }),
+            operator_username: std::env::var("WEBHOOK_OPERATOR_USERNAME")
+                .ok()
+                .filter(|u| !u.is_empty()),
         }
     }
 }
@@ -63,6 +99,11 @@ impl Default for WebhookConfig {
 #[derive(Clone)]
 struct WebhookState {
     client: Client,
+    allow_unsupported_overrides: bool,
+    protect_by_default: bool,
+    operator_username: Option<String>,
+    mutate: Arc<MutateSettings>,
+    digests: Arc<DigestResolver>,
 }
 
 // --- Admission API types ---
@@ -86,6 +127,14 @@ struct AdmissionRequest {
     object: serde_json::Value,
     #[serde(default)]
     old_object: Option<serde_json::Value>,
+    #[serde(default)]
+    user_info: UserInfo,
+}
+
+#[derive(Deserialize, Default)]
+struct UserInfo {
+    #[serde(default)]
+    username: String,
 }
 
 #[derive(Serialize)]
@@ -103,24 +152,91 @@ struct AdmissionResponse {
     allowed: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     status: Option<AdmissionStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    patch_type: Option<&'static str>,
+    /// Base64-encoded JSON Patch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    patch: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<String>,
 }
 
+/// Why an object was rejected, in the shape of a Kubernetes `Status` so
+/// clients report it as invalid and list each problem under its field.
 #[derive(Serialize)]
 struct AdmissionStatus {
     message: String,
+    code: u16,
+    reason: &'static str,
+    details: AdmissionStatusDetails,
+}
+
+#[derive(Serialize)]
+struct AdmissionStatusDetails {
+    causes: Vec<StatusCause>,
+}
+
+#[derive(Serialize)]
+struct StatusCause {
+    reason: &'static str,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    field: Option<String>,
+}
+
+/// Top-level `spec` properties of the CRD `K`.
+fn spec_fields<K: CustomResourceExt>() -> BTreeSet<String> {
+    K::crd()
+        .spec
+        .versions
+        .first()
+        .and_then(|v| v.schema.as_ref()?.open_api_v3_schema.as_ref())
+        .and_then(|s| s.properties.as_ref()?.get("spec")?.properties.as_ref())
+        .map(|p| p.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn servarr_app_fields() -> &'static BTreeSet<String> {
+    static FIELDS: OnceLock<BTreeSet<String>> = OnceLock::new();
+    FIELDS.get_or_init(spec_fields::<ServarrApp>)
+}
+
+fn media_stack_fields() -> &'static BTreeSet<String> {
+    static FIELDS: OnceLock<BTreeSet<String>> = OnceLock::new();
+    FIELDS.get_or_init(spec_fields::<MediaStack>)
 }
 
-/// Start the validating webhook server.
+/// The field a rule message is about. Messages start with the offending
+/// path relative to `spec` (`gateway.hosts must ...`, `apps[2]: ...`);
+/// `None` when the first word is not a path into one of `spec_fields`.
+fn cause_field(message: &str, spec_fields: &BTreeSet<String>) -> Option<String> {
+    let path = message.split_whitespace().next()?.trim_end_matches(':');
+    let path = path.strip_prefix("spec.").unwrap_or(path);
+    let top = path.split(['.', '[']).next()?;
+    spec_fields.contains(top).then(|| format!("spec.{path}"))
+}
+
+/// Start the admission webhook server.
 ///
-/// Listens for `POST /validate-servarrapp` with AdmissionReview payloads.
+/// Listens for `POST /validate-servarrapp`, `POST /validate-mediastack`,
+/// and `POST /mutate-servarrapp` with AdmissionReview payloads.
 /// Serves TLS using the cert/key at `config.tls_cert` / `config.tls_key`
 /// (defaults: `/etc/webhook/tls/tls.crt` and `/etc/webhook/tls/tls.key`).
 /// Override paths via `WEBHOOK_TLS_CERT`, `WEBHOOK_TLS_KEY`, or `WEBHOOK_TLS_DIR`.
 /// Set `WEBHOOK_PORT` to override the default port 9443.
 pub async fn run(client: kube::Client, config: WebhookConfig) -> anyhow::Result<()> {
-    let state = Arc::new(WebhookState { client });
+    let state = Arc::new(WebhookState {
+        client,
+        allow_unsupported_overrides: config.allow_unsupported_overrides,
+        protect_by_default: config.protect_by_default,
+        operator_username: config.operator_username,
+        mutate: Arc::new(config.mutate),
+        digests: Arc::new(DigestResolver::default()),
+    });
     let app = Router::new()
         .route("/validate-servarrapp", post(validate_handler))
+        .route("/validate-mediastack", post(validate_media_stack_handler))
+        .route("/mutate-servarrapp", post(mutate_handler))
         .with_state(state);
 
     let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
@@ -157,30 +273,120 @@ async fn validate_handler(
     };
 
     let uid = request.uid.clone();
+    if request.operation == "DELETE" {
+        return review_response(
+            review.api_version,
+            review.kind,
+            uid,
+            validate_deletion(
+                request.old_object.as_ref(),
+                &request.user_info.username,
+                _state.protect_by_default,
+                _state.operator_username.as_deref(),
+            ),
+            servarr_app_fields(),
+        );
+    }
     let validation_result = validate_spec(
         &request.object,
         request.old_object.as_ref(),
         &request.operation,
         &request.namespace,
         &_state.client,
+        _state.allow_unsupported_overrides,
     )
     .await;
 
+    review_response(
+        review.api_version,
+        review.kind,
+        uid,
+        validation_result,
+        servarr_app_fields(),
+    )
+}
+
+async fn validate_media_stack_handler(
+    State(state): State<Arc<WebhookState>>,
+    Json(review): Json<AdmissionReview>,
+) -> impl IntoResponse {
+    let Some(request) = review.request else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "missing request"})),
+        );
+    };
+    if request.operation == "DELETE" {
+        return review_response(
+            review.api_version,
+            review.kind,
+            request.uid,
+            validate_deletion(
+                request.old_object.as_ref(),
+                &request.user_info.username,
+                state.protect_by_default,
+                state.operator_username.as_deref(),
+            ),
+            media_stack_fields(),
+        );
+    }
+    let mut result = validate_media_stack(&request.object, &request.namespace);
+    if result.is_ok() && !request.namespace.is_empty() {
+        result =
+            validate_media_stack_policies(&request.object, &request.namespace, &state.client).await;
+    }
+    review_response(
+        review.api_version,
+        review.kind,
+        request.uid,
+        result,
+        media_stack_fields(),
+    )
+}
+
+fn review_response(
+    api_version: String,
+    kind: String,
+    uid: String,
+    validation_result: Result<(), Vec<String>>,
+    spec_fields: &BTreeSet<String>,
+) -> (StatusCode, Json<serde_json::Value>) {
     let response = AdmissionReviewResponse {
-        api_version: review.api_version,
-        kind: review.kind,
+        api_version,
+        kind,
         response: match validation_result {
             Ok(()) => AdmissionResponse {
                 uid,
                 allowed: true,
                 status: None,
+                patch_type: None,
+                patch: None,
+                warnings: Vec::new(),
             },
-            Err(msg) => {
-                warn!(%msg, "admission rejected");
+            Err(errors) => {
+                let message = errors.join("; ");
+                warn!(%message, "admission rejected");
                 AdmissionResponse {
                     uid,
                     allowed: false,
-                    status: Some(AdmissionStatus { message: msg }),
+                    status: Some(AdmissionStatus {
+                        message,
+                        code: 422,
+                        reason: "Invalid",
+                        details: AdmissionStatusDetails {
+                            causes: errors
+                                .into_iter()
+                                .map(|message| StatusCause {
+                                    reason: "FieldValueInvalid",
+                                    field: cause_field(&message, spec_fields),
+                                    message,
+                                })
+                                .collect(),
+                        },
+                    }),
+                    patch_type: None,
+                    patch: None,
+                    warnings: Vec::new(),
                 }
             }
         },
@@ -192,20 +398,259 @@ async fn validate_handler(
     )
 }
 
-/// Validate a ServarrApp spec. Returns `Ok(())` on success or `Err(message)`.
+/// Refuse to delete a protected object. The API server only sends DELETE
+/// requests when the chart's `webhook.deletionProtection.enabled` adds the
+/// operation to the webhook rules; the object being deleted arrives as
+/// `oldObject`. Deletes by the operator itself (`username` matching
+/// `operator_username`) always pass: they follow a spec change, such as an
+/// app removed from a MediaStack or a canary being promoted.
+fn validate_deletion(
+    old_object: Option<&serde_json::Value>,
+    username: &str,
+    protect_by_default: bool,
+    operator_username: Option<&str>,
+) -> Result<(), Vec<String>> {
+    let Some(object) = old_object else {
+        return Ok(());
+    };
+    if operator_username == Some(username) {
+        return Ok(());
+    }
+    let protected = match object
+        .pointer("/metadata/annotations")
+        .and_then(|a| a.get(PROTECTED_ANNOTATION))
+        .and_then(|v| v.as_str())
+    {
+        Some(v) => v.eq_ignore_ascii_case("true"),
+        None => protect_by_default,
+    };
+    if !protected {
+        return Ok(());
+    }
+    let kind = object
+        .get("kind")
+        .and_then(|k| k.as_str())
+        .unwrap_or("object");
+    let name = object
+        .pointer("/metadata/name")
+        .and_then(|n| n.as_str())
+        .unwrap_or_default();
+    Err(vec![format!(
+        "{kind} {name} is protected from deletion; set the {PROTECTED_ANNOTATION} annotation to \"false\" to delete it"
+    )])
+}
+
+/// Fill in the operator defaults of an admitted ServarrApp, see
+/// [`crate::mutate`]. Never rejects: problems are left to the validating
+/// webhook.
+async fn mutate_handler(
+    State(state): State<Arc<WebhookState>>,
+    Json(review): Json<AdmissionReview>,
+) -> impl IntoResponse {
+    use base64::Engine as _;
+
+    let Some(request) = review.request else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "missing request"})),
+        );
+    };
+    let mutation = crate::mutate::mutate(
+        &request.object,
+        request.old_object.as_ref(),
+        &state.mutate,
+        &state.digests,
+    )
+    .await;
+    for warning in &mutation.warnings {
+        warn!(%warning, "admission warning");
+    }
+    let patch = (!mutation.patch.is_empty()).then(|| {
+        base64::engine::general_purpose::STANDARD
+            .encode(serde_json::to_vec(&mutation.patch).unwrap_or_default())
+    });
+    let response = AdmissionReviewResponse {
+        api_version: review.api_version,
+        kind: review.kind,
+        response: AdmissionResponse {
+            uid: request.uid,
+            allowed: true,
+            status: None,
+            patch_type: patch.as_ref().map(|_| "JSONPatch"),
+            patch,
+            warnings: mutation.warnings,
+        },
+    };
+    (
+        StatusCode::OK,
+        Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+/// Validate a MediaStack spec: every app needs a distinct, well-formed
+/// `(app, instance)` pair so the child ServarrApps get unique names, and
+/// the ServarrApps it expands to must pass the per-app spec rules.
+fn validate_media_stack(object: &serde_json::Value, namespace: &str) -> Result<(), Vec<String>> {
+    let spec = object
+        .get("spec")
+        .ok_or_else(|| vec!["missing spec field".to_string()])?;
+    let parsed: MediaStackSpec =
+        serde_json::from_value(spec.clone()).map_err(|e| vec![format!("invalid spec: {e}")])?;
+    let stack_name = object
+        .pointer("/metadata/name")
+        .and_then(|n| n.as_str())
+        .unwrap_or_default();
+
+    let mut errors = parsed.validate_instances();
+    errors.extend(parsed.validate_namespaces(namespace));
+    if let Some(keys) = parsed.defaults.as_ref().and_then(|d| d.api_keys.as_ref())
+        && !keys.secret_name.contains("{name}")
+    {
+        errors.push(format!(
+            "defaults.apiKeys.secretName: '{}' must contain {{name}} so each app gets its own Secret",
+            keys.secret_name
+        ));
+    }
+    for (i, app) in parsed.apps.iter().enumerate() {
+        match app.expand(
+            stack_name,
+            namespace,
+            parsed.defaults.as_ref(),
+            parsed.nfs.as_ref(),
+        ) {
+            Ok(children) => {
+                for (child, spec) in children {
+                    let mut child_errors = Vec::new();
+                    validate_app_config_match(&spec, &mut child_errors);
+                    validate_tdarr_node(&spec, &mut child_errors);
+                    validate_backup_schedule(&spec, &mut child_errors);
+                    validate_exposure(&spec, &mut child_errors);
+                    validate_network_policy_cidrs(&spec, &mut child_errors);
+                    validate_gateway_backends(&spec, &mut child_errors);
+                    validate_lifecycle(&spec, &mut child_errors);
+                    validate_credential_secret_refs(&spec, &mut child_errors);
+                    validate_api_key_rotation(&spec, &mut child_errors);
+                    validate_extra_containers(&spec, &mut child_errors);
+                    errors.extend(
+                        child_errors
+                            .into_iter()
+                            .map(|e| format!("apps[{i}] ({child}): {e}")),
+                    );
+                }
+            }
+            Err(e) => errors.push(format!("apps[{i}]: {e}")),
+        }
+    }
+    if let Some(nfs) = parsed.nfs.as_ref() {
+        let mut names = std::collections::HashSet::new();
+        for export in &nfs.exports {
+            errors.extend(export.validate());
+            if !names.insert(export.name.as_str()) {
+                errors.push(format!("nfs.exports[{}]: duplicate name", export.name));
+            }
+            if export.size.is_some() && nfs.external_server.is_some() {
+                errors.push(format!(
+                    "nfs.exports[{}]: size needs the in-cluster NFS server, not externalServer",
+                    export.name
+                ));
+            }
+        }
+    }
+    if let Some(backup) = parsed.nfs.as_ref().and_then(|n| n.backup.as_ref()) {
+        for (field, schedule) in [
+            ("snapshot", backup.snapshot.as_ref().map(|s| &s.schedule)),
+            ("rsync", backup.rsync.as_ref().map(|r| &r.schedule)),
+        ] {
+            if let Some(Err(e)) = schedule.map(|s| crate::schedule::parse(s)) {
+                errors.push(format!("nfs.backup.{field}.schedule: {e}"));
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Check every app a MediaStack expands to against the namespace's
+/// ServarrPolicies, so violations are reported on the stack rather than
+/// when the stack controller creates its ServarrApps.
+async fn validate_media_stack_policies(
+    object: &serde_json::Value,
+    namespace: &str,
+    client: &Client,
+) -> Result<(), Vec<String>> {
+    let spec = object
+        .get("spec")
+        .ok_or_else(|| vec!["missing spec field".to_string()])?;
+    let parsed: MediaStackSpec =
+        serde_json::from_value(spec.clone()).map_err(|e| vec![format!("invalid spec: {e}")])?;
+    let stack_name = object
+        .pointer("/metadata/name")
+        .and_then(|n| n.as_str())
+        .unwrap_or_default();
+    // Each app answers to the policies of the namespace it is deployed into.
+    let mut policies = std::collections::BTreeMap::new();
+    for app in &parsed.apps {
+        let app_ns = app.namespace_or(namespace);
+        if policies.contains_key(app_ns) {
+            continue;
+        }
+        let loaded = crate::policy::load(client, app_ns).await.map_err(|e| {
+            warn!(error = %e, "failed to list ServarrPolicies");
+            vec![format!("failed to check ServarrPolicies: {e}")]
+        })?;
+        policies.insert(app_ns, loaded);
+    }
+    if policies.values().all(Vec::is_empty) {
+        return Ok(());
+    }
+
+    let mut errors = Vec::new();
+    for (i, app) in parsed.apps.iter().enumerate() {
+        let policies = &policies[app.namespace_or(namespace)];
+        let Ok(children) = app.expand(
+            stack_name,
+            namespace,
+            parsed.defaults.as_ref(),
+            parsed.nfs.as_ref(),
+        ) else {
+            continue;
+        };
+        for (child, spec) in children {
+            for policy in policies {
+                errors.extend(
+                    crate::policy::check(policy, &spec)
+                        .into_iter()
+                        .map(|v| format!("apps[{i}] ({child}): {v}")),
+                );
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Validate a ServarrApp spec. Returns `Ok(())` on success or every rule
+/// the spec breaks.
 async fn validate_spec(
     object: &serde_json::Value,
     old_object: Option<&serde_json::Value>,
     operation: &str,
     namespace: &str,
     client: &Client,
-) -> Result<(), String> {
+    allow_unsupported_overrides: bool,
+) -> Result<(), Vec<String>> {
     let spec = object
         .get("spec")
-        .ok_or_else(|| "missing spec field".to_string())?;
+        .ok_or_else(|| vec!["missing spec field".to_string()])?;
 
     let parsed: ServarrAppSpec =
-        serde_json::from_value(spec.clone()).map_err(|e| format!("invalid spec: {e}"))?;
+        serde_json::from_value(spec.clone()).map_err(|e| vec![format!("invalid spec: {e}")])?;
 
     debug!(
         operation,
@@ -257,10 +702,152 @@ async fn validate_spec(
     // Rule 11: adminCredentials.secretName must be non-empty when set
     validate_admin_credentials(&parsed, &mut errors);
 
+    // Rule 12: unsupportedOverrides require the operator opt-in flag
+    validate_unsupported_overrides(&parsed, allow_unsupported_overrides, &mut errors);
+
+    // Rule 13: instance must be usable in resource names
+    if let Some(inst) = parsed.instance.as_deref()
+        && !is_valid_instance(inst)
+    {
+        errors.push(format!(
+            "instance '{inst}' must be a lowercase DNS label (a-z, 0-9, '-')"
+        ));
+    }
+
+    // Rule 14: configFile keys must fit the app's config file format
+    errors.extend(servarr_resources::config_file::validate(&parsed));
+
+    // Rule 15: Transmission directories and qBittorrent categories must lie
+    // on writable volume mounts
+    validate_torrent_directories(&parsed, &mut errors);
+
+    // Rule 16: rclone mounts must be well-formed and not collide
+    validate_rclone_mounts(&parsed, &mut errors);
+
+    // Rule 17: credential references must be well-formed and not collide
+    validate_credentials(&parsed, &mut errors);
+
+    // Rule 18: queueRemediation needs Sonarr/Radarr and an API key
+    validate_queue_remediation(&parsed, &mut errors);
+
+    // Rule 19: the namespace's ServarrPolicies must allow the app
+    if !namespace.is_empty() {
+        validate_policies(&parsed, namespace, client, &mut errors).await;
+    }
+
+    // Rule 20: requeue intervals must be ordered and not too short
+    validate_requeue(&parsed, &mut errors);
+
+    // Rule 21: quality profiles need an API key and a consistent cutoff
+    validate_quality_profiles(&parsed, &mut errors);
+
+    // Rule 22: auth only applies to the *arr apps; Forms/Basic need a login
+    validate_auth(&parsed, &mut errors);
+
+    // Rule 23: uiSettings only apply to the *arr apps and need an API key
+    validate_ui_settings(&parsed, &mut errors);
+
+    // Rule 24: root folders need an API key and distinct absolute paths
+    validate_root_folders(&parsed, &mut errors);
+
+    // Rule 25: download client sync only applies to Sonarr/Radarr/Lidarr/Readarr/Whisparr
+    validate_download_client_sync(&parsed, &mut errors);
+
+    // Rule 26: autoscaling only for apps that can run several replicas,
+    // with a sane replica range and positive targets
+    validate_autoscaling(&parsed, &mut errors);
+
+    // Rule 27: ingress needs a bare hostname and an absolute path
+    validate_ingress(&parsed, &mut errors);
+
+    // Rule 28: SFTP chroots need sftp mode and a declared persistence volume
+    validate_sftp_chroot(&parsed, &mut errors);
+
+    // Rule 29: prowlarrSync categories only for apps Prowlarr syncs to, one
+    // mapping per app and instance; app profile names unique
+    validate_prowlarr_sync(&parsed, &mut errors);
+
+    // Rule 30: Prowlarr indexers need apiKeySecret, unique names, and a
+    // priority in Prowlarr's range
+    validate_prowlarr_indexers(&parsed, &mut errors);
+
+    // Rule 31: media server users need unique names and the credentials
+    // their server is managed with
+    validate_media_server_users(&parsed, &mut errors);
+
+    // Rule 32: integrity checks need an app with a SQLite database and a
+    // five-field schedule
+    validate_integrity_check(&parsed, &mut errors);
+
+    // Rule 33: media management needs an API key, and a recycle bin on a
+    // mounted volume
+    validate_media_management(&parsed, &mut errors);
+
+    // Rule 34: backup.schedule must be a five-field cron expression
+    validate_backup_schedule(&parsed, &mut errors);
+
+    // Rule 35: ingress and an enabled gateway can't route the same host
+    validate_exposure(&parsed, &mut errors);
+
+    // Rule 36: networkPolicyConfig CIDR blocks must parse
+    validate_network_policy_cidrs(&parsed, &mut errors);
+
+    // Rule 37: bazarrSync only on Bazarr; it and appConfig.bazarr need
+    // apiKeySecret, unique language profiles, and ISO 639-1 languages
+    validate_bazarr(&parsed, &mut errors);
+
+    // Rule 38: Tdarr nodes need a sane node count and a server name that
+    // resolves as a Service
+    validate_tdarr_node(&parsed, &mut errors);
+
+    // Rule 39: trashSync needs Sonarr/Radarr, an API key, a valid
+    // schedule, and templates the operator has built in
+    validate_trash_sync(&parsed, &mut errors);
+
+    // Rule 40: custom formats need an API key, unique names, and at least
+    // one condition each
+    validate_custom_formats(&parsed, &mut errors);
+
+    // Rule 41: monitoring settings need an API key; the availability delay
+    // is Radarr's
+    validate_monitoring(&parsed, &mut errors);
+
+    // Rule 42: automatic updates need a schedule that parses and an image
+    // that isn't pinned to a digest
+    validate_update_policy(&parsed, &mut errors);
+
+    // Rule 43: the maintenance window needs a schedule that parses and a
+    // non-zero duration
+    validate_maintenance_window(&parsed, &mut errors);
+
+    // Rule 44: tags need an API key and labels the apps accept; Lidarr's
+    // appConfig takes nothing else
+    validate_tags(&parsed, &mut errors);
+
+    // Rule 45: weighted gateway backends need a name and weights that leave
+    // the app's own Service a share between 0 and 100
+    validate_gateway_backends(&parsed, &mut errors);
+
+    // Rule 46: lifecycle hooks need a command in each set entry and a
+    // non-negative grace period
+    validate_lifecycle(&parsed, &mut errors);
+
+    // Rule 47: credential Secret references need a name and non-empty keys,
+    // and replace the operator-keyed fields rather than joining them
+    validate_credential_secret_refs(&parsed, &mut errors);
+
+    // Rule 48: API key rotation needs a supported app, an apiKeySecret, and
+    // a valid schedule
+    validate_api_key_rotation(&parsed, &mut errors);
+
+    // Rule 49: extra containers need unique names that don't take the app
+    // container's
+    validate_extra_containers(&parsed, &mut errors);
+
     if errors.is_empty() {
         Ok(())
     } else {
-        Err(errors.join("; "))
+        Err(errors)
     }
 }
 
@@ -310,6 +897,24 @@ fn validate_admin_credentials(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
     }
 }
 
+fn validate_unsupported_overrides(spec: &ServarrAppSpec, allowed: bool, errors: &mut Vec<String>) {
+    let Some(pod_spec) = spec
+        .unsupported_overrides
+        .as_ref()
+        .and_then(|o| o.pod_spec.as_ref())
+    else {
+        return;
+    };
+    if !allowed {
+        errors.push(
+            "unsupportedOverrides.podSpec is disabled; start the operator with ALLOW_UNSUPPORTED_OVERRIDES=true to use it"
+                .into(),
+        );
+    } else if !pod_spec.is_object() {
+        errors.push("unsupportedOverrides.podSpec must be a mapping".into());
+    }
+}
+
 fn validate_ssh_shell_override(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
     if let Some(AppConfig::SshBastion(ref sc)) = spec.app_config {
         for user in &sc.users {
@@ -328,15 +933,62 @@ fn validate_ssh_shell_override(spec: &ServarrAppSpec, errors: &mut Vec<String>)
     }
 }
 
+fn validate_sftp_chroot(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(AppConfig::SshBastion(ref sc)) = spec.app_config else {
+        return;
+    };
+    let defaults = AppDefaults::for_app(&spec.app).persistence;
+    let persistence = match spec.persistence {
+        Some(ref p) => defaults.merge_with(p),
+        None => defaults,
+    };
+    for user in &sc.users {
+        let Some(ref chroot) = user.sftp_chroot else {
+            continue;
+        };
+        let field = format!("appConfig.sshBastion.users[{}].sftpChroot", user.name);
+        if user.mode != SshMode::Sftp {
+            errors.push(format!("{field} requires mode sftp"));
+        }
+        let declared = persistence.volumes.iter().any(|v| v.name == chroot.volume)
+            || persistence
+                .nfs_mounts
+                .iter()
+                .any(|n| n.name == chroot.volume);
+        if !declared {
+            errors.push(format!(
+                "{field}.volume '{}' is not a persistence volume or NFS mount",
+                chroot.volume
+            ));
+        }
+        if let Some(ref sub_path) = chroot.sub_path
+            && (sub_path.starts_with('/') || sub_path.split('/').any(|c| c == ".."))
+        {
+            errors.push(format!(
+                "{field}.subPath '{sub_path}' must be a relative path without '..'"
+            ));
+        }
+    }
+}
+
 fn validate_app_config_match(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
     if let Some(ref config) = spec.app_config {
         let valid = matches!(
             (&spec.app, config),
             (AppType::Transmission, AppConfig::Transmission(_))
+                | (AppType::Qbittorrent, AppConfig::Qbittorrent(_))
                 | (AppType::Sabnzbd, AppConfig::Sabnzbd(_))
                 | (AppType::Prowlarr, AppConfig::Prowlarr(_))
                 | (AppType::SshBastion, AppConfig::SshBastion(_))
                 | (AppType::Overseerr, AppConfig::Overseerr(_))
+                | (AppType::Jellyseerr, AppConfig::Jellyseerr(_))
+                | (AppType::Bazarr, AppConfig::Bazarr(_))
+                | (AppType::TdarrNode, AppConfig::TdarrNode(_))
+                | (AppType::Sonarr, AppConfig::Sonarr(_))
+                | (AppType::Radarr, AppConfig::Radarr(_))
+                | (AppType::Lidarr, AppConfig::Lidarr(_))
+                | (AppType::Jellyfin, AppConfig::Jellyfin(_))
+                | (AppType::Plex, AppConfig::Plex(_))
         );
         if !valid {
             errors.push(format!(
@@ -370,10 +1022,12 @@ fn validate_port_ranges(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
         }
     }
 
-    if let Some(AppConfig::Transmission(ref tc)) = spec.app_config
-        && let Some(ref peer) = tc.peer_port
-    {
-        check_port(peer.port, "appConfig.transmission.peerPort.port", errors);
+    if let Some(peer) = spec.app_config.as_ref().and_then(AppConfig::peer_port) {
+        check_port(
+            peer.port,
+            &format!("appConfig.{}.peerPort.port", spec.app),
+            errors,
+        );
     }
 }
 
@@ -410,6 +1064,92 @@ fn validate_gateway_hosts(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
     }
 }
 
+fn validate_gateway_backends(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(ref gw) = spec.gateway else {
+        return;
+    };
+    for (i, backend) in gw.backends.iter().enumerate() {
+        if backend.name.is_empty() {
+            errors.push(format!("gateway.backends[{i}].name must be set"));
+        }
+        if !(0..=100).contains(&backend.weight) {
+            errors.push(format!(
+                "gateway.backends[{i}].weight {} must be between 0 and 100",
+                backend.weight
+            ));
+        }
+    }
+    let total: i32 = gw.backends.iter().map(|b| b.weight).sum();
+    if total > 100 {
+        errors.push(format!(
+            "gateway.backends weights add up to {total}, more than 100"
+        ));
+    }
+}
+
+fn validate_lifecycle(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(ref lifecycle) = spec.lifecycle else {
+        return;
+    };
+    for (field, command) in [
+        ("postStart", &lifecycle.post_start),
+        ("preStop", &lifecycle.pre_stop),
+    ] {
+        if command.first().is_some_and(|c| c.trim().is_empty()) {
+            errors.push(format!(
+                "lifecycle.{field} must start with the program to run"
+            ));
+        }
+    }
+    if lifecycle
+        .termination_grace_period_seconds
+        .is_some_and(|s| s < 0)
+    {
+        errors.push("lifecycle.terminationGracePeriodSeconds must be >= 0".into());
+    }
+}
+
+fn validate_credential_secret_refs(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    if let Some(ref key) = spec.api_key_secret_key {
+        if spec.api_key_secret.is_none() {
+            errors.push("apiKeySecretKey requires apiKeySecret".into());
+        } else if key.trim().is_empty() {
+            errors.push("apiKeySecretKey must not be empty".into());
+        }
+    }
+    let (field, creds, replaces) = match spec.app_config {
+        Some(AppConfig::Transmission(ref tc)) => (
+            "appConfig.transmission.credentialsSecretRef",
+            &tc.credentials_secret_ref,
+            tc.auth.is_some().then_some("appConfig.transmission.auth"),
+        ),
+        Some(AppConfig::Sabnzbd(ref sc)) => (
+            "appConfig.sabnzbd.credentialsSecretRef",
+            &sc.credentials_secret_ref,
+            spec.api_key_secret.is_some().then_some("apiKeySecret"),
+        ),
+        _ => return,
+    };
+    let Some(creds) = creds else {
+        return;
+    };
+    if let Some(other) = replaces {
+        errors.push(format!("{field} and {other} are mutually exclusive"));
+    }
+    if creds.name.trim().is_empty() {
+        errors.push(format!("{field}.name must not be empty"));
+    }
+    for (key, value) in [
+        ("usernameKey", &creds.username_key),
+        ("passwordKey", &creds.password_key),
+        ("apiKeyKey", &creds.api_key_key),
+    ] {
+        if value.trim().is_empty() {
+            errors.push(format!("{field}.{key} must not be empty"));
+        }
+    }
+}
+
 async fn validate_no_duplicate_instance(
     spec: &ServarrAppSpec,
     namespace: &str,
@@ -447,6 +1187,25 @@ async fn validate_no_duplicate_instance(
     }
 }
 
+async fn validate_policies(
+    spec: &ServarrAppSpec,
+    namespace: &str,
+    client: &Client,
+    errors: &mut Vec<String>,
+) {
+    match crate::policy::load(client, namespace).await {
+        Ok(policies) => {
+            for policy in &policies {
+                errors.extend(crate::policy::check(policy, spec));
+            }
+        }
+        Err(e) => {
+            warn!(error = %e, "failed to list ServarrPolicies");
+            errors.push(format!("failed to check ServarrPolicies: {e}"));
+        }
+    }
+}
+
 fn validate_unique_volume_names(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
     if let Some(ref persistence) = spec.persistence {
         let mut seen = HashSet::new();
@@ -491,578 +1250,2696 @@ fn validate_transmission_settings(spec: &ServarrAppSpec, errors: &mut Vec<String
                 ));
             }
         }
+        if tc.directories.is_some() {
+            for key in TransmissionDirectories::SETTINGS_KEYS {
+                if !TRANSMISSION_MANAGED_KEYS.contains(key) && map.contains_key(*key) {
+                    errors.push(format!(
+                        "appConfig.transmission.settings must not contain '{key}' when directories is set"
+                    ));
+                }
+            }
+        }
     }
 }
 
-fn validate_backup_retention(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
-    if let Some(ref backup) = spec.backup
-        && backup.enabled
-        && backup.retention_count == 0
-    {
-        errors.push("backup.retentionCount must be >= 1 when backups are enabled".into());
+/// Transmission directories and qBittorrent categories must be on the app's
+/// writable mounts, checked against the persistence the Deployment will
+/// actually get.
+fn validate_torrent_directories(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let persistence = || {
+        let defaults = AppDefaults::for_app(&spec.app).persistence;
+        match spec.persistence {
+            Some(ref p) => defaults.merge_with(p),
+            None => defaults,
+        }
+    };
+    match spec.app_config {
+        Some(AppConfig::Transmission(TransmissionConfig {
+            directories: Some(ref dirs),
+            ..
+        })) => errors.extend(dirs.validate(&persistence())),
+        Some(AppConfig::Qbittorrent(ref qc)) => errors.extend(qc.validate(&persistence())),
+        _ => {}
     }
 }
 
-fn validate_indexer_definition_names(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
-    if let Some(AppConfig::Prowlarr(ref pc)) = spec.app_config {
-        for def in &pc.custom_definitions {
-            if !def
-                .name
-                .chars()
-                .all(|c| c.is_ascii_alphanumeric() || c == '-')
-                || def.name.is_empty()
-            {
-                errors.push(format!(
-                    "appConfig.prowlarr.customDefinitions[].name '{}' must be non-empty and contain only alphanumeric characters or hyphens",
-                    def.name
-                ));
-            }
+fn validate_rclone_mounts(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let mut names = HashSet::new();
+    let mut paths = HashSet::new();
+    let persistence = spec.persistence.as_ref();
+    let mount_paths: Vec<&str> = persistence
+        .map(|p| {
+            p.volumes
+                .iter()
+                .map(|v| v.mount_path.as_str())
+                .chain(p.nfs_mounts.iter().map(|m| m.mount_path.as_str()))
+                .collect()
+        })
+        .unwrap_or_default();
+    for mount in &spec.rclone_mounts {
+        errors.extend(mount.validate());
+        if !names.insert(&mount.name) {
+            errors.push(format!("duplicate rcloneMount name: '{}'", mount.name));
+        }
+        if !paths.insert(&mount.mount_path) || mount_paths.contains(&mount.mount_path.as_str()) {
+            errors.push(format!(
+                "rcloneMounts[{}]: mountPath '{}' is already used by another mount",
+                mount.name, mount.mount_path
+            ));
         }
     }
 }
 
-/// Parse CPU quantity to millicores for comparison.
-fn parse_cpu(s: &str) -> Option<u64> {
-    if s.is_empty() {
-        return None;
+fn validate_credentials(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let mut names = HashSet::new();
+    let mut env_names: HashSet<&str> = spec.env.iter().map(|e| e.name.as_str()).collect();
+    let mut paths: HashSet<&str> = spec
+        .rclone_mounts
+        .iter()
+        .map(|m| m.mount_path.as_str())
+        .collect();
+    if let Some(ref p) = spec.persistence {
+        paths.extend(p.volumes.iter().map(|v| v.mount_path.as_str()));
+        paths.extend(p.nfs_mounts.iter().map(|m| m.mount_path.as_str()));
     }
-    if let Some(m) = s.strip_suffix('m') {
-        m.parse().ok()
-    } else {
-        s.parse::<f64>().ok().map(|v| (v * 1000.0) as u64)
+    for cred in &spec.credentials {
+        errors.extend(cred.validate());
+        if !names.insert(&cred.name) {
+            errors.push(format!("duplicate credential: '{}'", cred.name));
+        }
+        for var in &cred.env {
+            if !var.name.is_empty() && !env_names.insert(&var.name) {
+                errors.push(format!(
+                    "credentials[{}]: env var '{}' is already set",
+                    cred.name, var.name
+                ));
+            }
+        }
+        if let Some(ref path) = cred.mount_path
+            && !paths.insert(path)
+        {
+            errors.push(format!(
+                "credentials[{}]: mountPath '{path}' is already used by another mount",
+                cred.name
+            ));
+        }
     }
 }
 
-/// Parse memory quantity to bytes for comparison.
-fn parse_memory(s: &str) -> Option<u64> {
-    if s.is_empty() {
-        return None;
+fn validate_queue_remediation(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(ref qr) = spec.queue_remediation else {
+        return;
+    };
+    if !qr.enabled {
+        return;
     }
-    for (suffix, multiplier) in [
-        ("Ti", 1024u64 * 1024 * 1024 * 1024),
-        ("Gi", 1024 * 1024 * 1024),
-        ("Mi", 1024 * 1024),
-        ("Ki", 1024),
-        ("T", 1000 * 1000 * 1000 * 1000),
-        ("G", 1000 * 1000 * 1000),
-        ("M", 1000 * 1000),
-        ("K", 1000),
+    if !matches!(spec.app, AppType::Sonarr | AppType::Radarr) {
+        errors.push(format!(
+            "queueRemediation is only supported for Sonarr and Radarr, not {}",
+            spec.app
+        ));
+    }
+    if spec.api_key_ref().is_none() {
+        errors.push("queueRemediation requires apiKeySecret".into());
+    }
+    if qr.interval_minutes == 0 {
+        errors.push("queueRemediation.intervalMinutes must be >= 1".into());
+    }
+    let mut reasons = HashSet::new();
+    for rule in &qr.rules {
+        if !reasons.insert(rule.reason) {
+            errors.push(format!(
+                "queueRemediation.rules: duplicate rule for {}",
+                rule.reason.as_str()
+            ));
+        }
+    }
+}
+
+fn validate_trash_sync(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(ref trash) = spec.trash_sync else {
+        return;
+    };
+    if !trash.enabled {
+        return;
+    }
+    if !matches!(spec.app, AppType::Sonarr | AppType::Radarr) {
+        errors.push(format!(
+            "trashSync is only supported for Sonarr and Radarr, not {}",
+            spec.app
+        ));
+    }
+    if spec.api_key_ref().is_none() {
+        errors.push("trashSync requires apiKeySecret".into());
+    }
+    if let Err(e) = crate::schedule::parse(&trash.schedule) {
+        errors.push(format!("trashSync.schedule: {e}"));
+    }
+    for name in &trash.templates {
+        if !crate::trash_sync::BUILTIN_TEMPLATES.contains(&name.as_str()) {
+            errors.push(format!(
+                "trashSync.templates: unknown template '{name}' (valid: {})",
+                crate::trash_sync::BUILTIN_TEMPLATES.join(", ")
+            ));
+        }
+    }
+    if trash.templates.is_empty() && trash.config_map.is_none() {
+        errors.push("trashSync needs templates or a configMap".into());
+    }
+}
+
+fn validate_requeue(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(ref requeue) = spec.requeue else {
+        return;
+    };
+    let min = crate::controller::MIN_REQUEUE_SECONDS;
+    for (field, secs) in [
+        ("unsettledSeconds", requeue.unsettled_seconds),
+        ("settledSeconds", requeue.settled_seconds),
     ] {
-        if let Some(num) = s.strip_suffix(suffix) {
-            return num.parse::<u64>().ok().map(|v| v * multiplier);
+        if secs < min {
+            errors.push(format!("requeue.{field} must be >= {min}"));
         }
     }
-    s.parse().ok()
+    if requeue.unsettled_seconds > requeue.settled_seconds {
+        errors.push("requeue.unsettledSeconds must not exceed requeue.settledSeconds".into());
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use servarr_crds::*;
+fn validate_auth(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(ref auth) = spec.auth else {
+        return;
+    };
+    if spec.app.auth_env_prefix().is_none() {
+        errors.push(format!(
+            "auth is only supported for Sonarr, Radarr, Lidarr, Readarr, Whisparr, and Prowlarr, not {:?}",
+            spec.app
+        ));
+    }
+    if auth.method.needs_credentials() && auth.secret_name.as_deref().is_none_or(str::is_empty) {
+        errors.push(format!(
+            "auth.secretName is required for {} authentication",
+            auth.method.as_str()
+        ));
+    }
+}
+
+fn validate_ui_settings(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    if spec.ui_settings.is_none() {
+        return;
+    }
+    if !spec.app.is_servarr_v3() {
+        errors.push(format!(
+            "uiSettings is only supported for Sonarr, Radarr, Lidarr, Readarr, Whisparr, and Prowlarr, not {:?}",
+            spec.app
+        ));
+    } else if spec.api_key_ref().is_none() {
+        errors.push("uiSettings requires apiKeySecret to be set".to_string());
+    }
+}
+
+fn validate_quality_profiles(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let config = match spec.app_config {
+        Some(AppConfig::Sonarr(ref c)) => c,
+        Some(AppConfig::Radarr(ref c)) => c,
+        _ => return,
+    };
+    if config.quality_profiles.is_empty() {
+        return;
+    }
+    if spec.api_key_ref().is_none() {
+        errors.push("appConfig qualityProfiles require apiKeySecret".into());
+    }
+    let mut names = HashSet::new();
+    for profile in &config.quality_profiles {
+        let field = format!("qualityProfiles[{}]", profile.name);
+        if profile.name.trim().is_empty() {
+            errors.push("qualityProfiles[].name must not be empty".into());
+        } else if !names.insert(profile.name.as_str()) {
+            errors.push(format!("{field}: duplicate profile name"));
+        }
+        if profile.qualities.is_empty() {
+            errors.push(format!("{field}.qualities must list at least one quality"));
+        }
+        if let Some(ref cutoff) = profile.cutoff
+            && !profile.qualities.contains(cutoff)
+        {
+            errors.push(format!(
+                "{field}.cutoff '{cutoff}' must be one of its qualities"
+            ));
+        }
+    }
+}
+
+fn validate_custom_formats(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let config = match spec.app_config {
+        Some(AppConfig::Sonarr(ref c) | AppConfig::Radarr(ref c)) => c,
+        _ => return,
+    };
+    if config.custom_formats.is_empty() {
+        return;
+    }
+    if spec.api_key_ref().is_none() {
+        errors.push("appConfig customFormats require apiKeySecret".into());
+    }
+    let mut names = HashSet::new();
+    for format in &config.custom_formats {
+        let field = format!("customFormats[{}]", format.name);
+        if format.name.trim().is_empty() {
+            errors.push("customFormats[].name must not be empty".into());
+        } else if !names.insert(format.name.as_str()) {
+            errors.push(format!("{field}: duplicate format name"));
+        }
+        if format.specifications.is_empty() {
+            errors.push(format!(
+                "{field}.specifications must list at least one condition"
+            ));
+        }
+        for condition in &format.specifications {
+            if condition.implementation.trim().is_empty() {
+                errors.push(format!(
+                    "{field}.specifications[{}].implementation must not be empty",
+                    condition.name
+                ));
+            }
+        }
+    }
+}
+
+fn validate_monitoring(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let monitoring = match spec.app_config {
+        Some(AppConfig::Sonarr(ref c) | AppConfig::Radarr(ref c)) => c.monitoring.as_ref(),
+        _ => None,
+    };
+    let Some(monitoring) = monitoring else {
+        return;
+    };
+    if spec.api_key_ref().is_none() {
+        errors.push("appConfig monitoring requires apiKeySecret".into());
+    }
+    if monitoring.availability_delay_days.is_some() && spec.app != AppType::Radarr {
+        errors.push("monitoring.availabilityDelayDays is only supported by Radarr".into());
+    }
+}
+
+fn validate_update_policy(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(policy) = spec
+        .update_policy
+        .as_ref()
+        .filter(|p| p.mode == UpdateMode::Auto)
+    else {
+        return;
+    };
+    if let Err(e) = crate::schedule::parse(&policy.schedule) {
+        errors.push(format!("updatePolicy.schedule: {e}"));
+    }
+    if spec.image.as_ref().is_some_and(|i| !i.digest.is_empty()) {
+        errors.push("updatePolicy.mode Auto cannot update an image pinned by image.digest".into());
+    }
+}
+
+fn validate_maintenance_window(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(window) = spec.maintenance_window.as_ref() else {
+        return;
+    };
+    if let Err(e) = crate::schedule::parse(&window.schedule) {
+        errors.push(format!("maintenanceWindow.schedule: {e}"));
+    }
+    if window.duration_minutes == 0 {
+        errors.push("maintenanceWindow.durationMinutes must be greater than 0".into());
+    }
+}
+
+/// Whether the apps accept `label` as a tag: letters, digits, and hyphens.
+fn valid_tag(label: &str) -> bool {
+    !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn validate_tags(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    if let Some(AppConfig::Lidarr(ref c)) = spec.app_config
+        && (!c.custom_formats.is_empty()
+            || !c.quality_profiles.is_empty()
+            || c.root_folders.is_some()
+            || c.media_management.is_some()
+            || c.monitoring.is_some())
+    {
+        errors.push("appConfig.lidarr only supports tags".into());
+    }
+    let declared = match spec.app_config {
+        Some(AppConfig::Sonarr(ref c) | AppConfig::Radarr(ref c) | AppConfig::Lidarr(ref c)) => {
+            c.tags.as_slice()
+        }
+        _ => &[],
+    };
+    if !declared.is_empty() && spec.api_key_ref().is_none() {
+        errors.push("appConfig tags require apiKeySecret".into());
+    }
+    let download_client_tags = spec
+        .download_client_sync
+        .as_ref()
+        .map_or(&[][..], |s| s.tags.as_slice());
+    for (field, labels) in [
+        ("appConfig tags", declared),
+        ("downloadClientSync.tags", download_client_tags),
+    ] {
+        for label in labels.iter().filter(|l| !valid_tag(l)) {
+            errors.push(format!(
+                "{field}: '{label}' may only contain letters, digits, and hyphens"
+            ));
+        }
+    }
+}
+
+fn validate_root_folders(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let folders = match spec.app_config {
+        Some(AppConfig::Sonarr(ref c) | AppConfig::Radarr(ref c)) => c.root_folders.as_ref(),
+        _ => None,
+    };
+    let Some(folders) = folders else {
+        return;
+    };
+    if spec.api_key_ref().is_none() {
+        errors.push("appConfig rootFolders require apiKeySecret".into());
+    }
+    let mut paths = HashSet::new();
+    for path in &folders.paths {
+        if !path.starts_with('/') {
+            errors.push(format!("rootFolders path '{path}' must be absolute"));
+        } else if !paths.insert(path.trim_end_matches('/')) {
+            errors.push(format!("rootFolders path '{path}' is listed twice"));
+        }
+    }
+}
+
+fn validate_media_management(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let management = match spec.app_config {
+        Some(AppConfig::Sonarr(ref c) | AppConfig::Radarr(ref c)) => c.media_management.as_ref(),
+        _ => None,
+    };
+    let Some(management) = management else {
+        return;
+    };
+    if spec.api_key_ref().is_none() {
+        errors.push("appConfig mediaManagement requires apiKeySecret".into());
+    }
+    let Some(bin) = management.recycle_bin.as_deref().filter(|b| !b.is_empty()) else {
+        return;
+    };
+    if !bin.starts_with('/') {
+        errors.push(format!(
+            "mediaManagement.recycleBin '{bin}' must be an absolute path"
+        ));
+        return;
+    }
+    let defaults = AppDefaults::for_app(&spec.app).persistence;
+    let persistence = match spec.persistence {
+        Some(ref p) => defaults.merge_with(p),
+        None => defaults,
+    };
+    let mounted = persistence
+        .volumes
+        .iter()
+        .map(|v| v.mount_path.as_str())
+        .chain(persistence.nfs_mounts.iter().map(|m| m.mount_path.as_str()))
+        .chain(spec.rclone_mounts.iter().map(|m| m.mount_path.as_str()))
+        .any(|mount| {
+            let mount = mount.trim_end_matches('/');
+            bin == mount
+                || bin
+                    .strip_prefix(mount)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        });
+    if !mounted {
+        errors.push(format!(
+            "mediaManagement.recycleBin '{bin}' is not on a mounted volume"
+        ));
+    }
+}
+
+fn validate_download_client_sync(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    if !spec
+        .download_client_sync
+        .as_ref()
+        .is_some_and(|s| s.enabled)
+    {
+        return;
+    }
+    if !spec.app.downloads_media() {
+        errors.push(format!(
+            "downloadClientSync is only supported for Sonarr, Radarr, Lidarr, Readarr, and Whisparr, not {:?}",
+            spec.app
+        ));
+    } else if spec.api_key_ref().is_none() {
+        errors.push("downloadClientSync requires apiKeySecret to be set".to_string());
+    }
+}
+
+fn validate_bazarr(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let sync_enabled = spec.bazarr_sync.as_ref().is_some_and(|s| s.enabled);
+    if sync_enabled && spec.app != AppType::Bazarr {
+        errors.push(format!(
+            "bazarrSync is only supported for Bazarr, not {:?}",
+            spec.app
+        ));
+        return;
+    }
+    let config = match spec.app_config {
+        Some(AppConfig::Bazarr(ref config)) => Some(config),
+        _ => None,
+    };
+    if (sync_enabled || config.is_some()) && spec.api_key_ref().is_none() {
+        errors.push("bazarrSync and appConfig.bazarr require apiKeySecret to be set".into());
+    }
+    if let Some(config) = config {
+        errors.extend(config.validate());
+    }
+}
+
+/// Most nodes one Tdarr node app may run.
+const MAX_TDARR_NODES: i32 = 32;
+
+fn validate_tdarr_node(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(AppConfig::TdarrNode(ref config)) = spec.app_config else {
+        return;
+    };
+    let count = config.node_count();
+    if !(1..=MAX_TDARR_NODES).contains(&count) {
+        errors.push(format!(
+            "appConfig.tdarrNode.nodeCount: {count} must be between 1 and {MAX_TDARR_NODES}"
+        ));
+    } else if count > 1 && spec.workload_type == Some(WorkloadType::StatefulSet) {
+        errors.push(
+            "appConfig.tdarrNode.nodeCount above 1 needs workloadType Deployment".to_string(),
+        );
+    }
+    if !is_valid_instance(config.server()) {
+        errors.push(format!(
+            "appConfig.tdarrNode.server '{}' must be the name of a ServarrApp",
+            config.server()
+        ));
+    }
+}
+
+fn validate_autoscaling(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(ref autoscaling) = spec.autoscaling else {
+        return;
+    };
+    if !spec.app.supports_autoscaling() {
+        errors.push(format!(
+            "autoscaling is only supported for Overseerr and SshBastion, not {:?}",
+            spec.app
+        ));
+        return;
+    }
+    if autoscaling.min_replicas < 1 {
+        errors.push("autoscaling.minReplicas must be >= 1".into());
+    }
+    if autoscaling.max_replicas < autoscaling.min_replicas {
+        errors.push(format!(
+            "autoscaling.maxReplicas ({}) must be >= minReplicas ({})",
+            autoscaling.max_replicas, autoscaling.min_replicas
+        ));
+    }
+    for (field, target) in [
+        (
+            "targetCpuUtilizationPercentage",
+            autoscaling.target_cpu_utilization_percentage,
+        ),
+        (
+            "targetMemoryUtilizationPercentage",
+            autoscaling.target_memory_utilization_percentage,
+        ),
+    ] {
+        if target.is_some_and(|t| t <= 0) {
+            errors.push(format!("autoscaling.{field} must be > 0"));
+        }
+    }
+}
+
+fn validate_ingress(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(ref ingress) = spec.ingress else {
+        return;
+    };
+    if ingress.host.is_empty() {
+        errors.push("ingress.host must be set".into());
+    } else if ingress.host.contains("://") || ingress.host.contains('/') {
+        errors.push(format!(
+            "ingress.host '{}' must be a hostname without a scheme or path",
+            ingress.host
+        ));
+    }
+    if !ingress.path().starts_with('/') {
+        errors.push(format!(
+            "ingress.path '{}' must start with '/'",
+            ingress.path()
+        ));
+    }
+}
+
+fn validate_prowlarr_sync(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(ref sync) = spec.prowlarr_sync else {
+        return;
+    };
+    let mut mappings = HashSet::new();
+    for mapping in &sync.categories {
+        if !mapping.app.downloads_media() {
+            errors.push(format!(
+                "prowlarrSync.categories: {:?} is not an app Prowlarr syncs to (Sonarr, Radarr, Lidarr, Readarr, Whisparr)",
+                mapping.app
+            ));
+        }
+        if !mapping.anime_sync_categories.is_empty() && mapping.app != AppType::Sonarr {
+            errors.push(format!(
+                "prowlarrSync.categories: animeSyncCategories only applies to Sonarr, not {:?}",
+                mapping.app
+            ));
+        }
+        if !mappings.insert((format!("{:?}", mapping.app), mapping.instance.clone())) {
+            errors.push(format!(
+                "prowlarrSync.categories: duplicate mapping for {:?}{}",
+                mapping.app,
+                mapping
+                    .instance
+                    .as_deref()
+                    .map(|i| format!(" instance {i}"))
+                    .unwrap_or_default()
+            ));
+        }
+    }
+    let mut names = HashSet::new();
+    for profile in &sync.app_profiles {
+        if profile.name.is_empty() {
+            errors.push("prowlarrSync.appProfiles[].name must be non-empty".into());
+        } else if !names.insert(profile.name.as_str()) {
+            errors.push(format!(
+                "prowlarrSync.appProfiles: duplicate profile name '{}'",
+                profile.name
+            ));
+        }
+        if profile.minimum_seeders < 0 {
+            errors.push(format!(
+                "prowlarrSync.appProfiles[{}].minimumSeeders must be >= 0",
+                profile.name
+            ));
+        }
+    }
+}
+
+fn validate_prowlarr_indexers(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(AppConfig::Prowlarr(ref config)) = spec.app_config else {
+        return;
+    };
+    if config.indexers.is_empty() && !config.auto_remove {
+        return;
+    }
+    if spec.api_key_ref().is_none() {
+        errors.push("appConfig indexers require apiKeySecret".into());
+    }
+    let mut names = HashSet::new();
+    for indexer in &config.indexers {
+        if indexer.name.is_empty() {
+            errors.push("indexers[].name must be non-empty".into());
+        } else if !names.insert(indexer.name.as_str()) {
+            errors.push(format!("indexers: duplicate name '{}'", indexer.name));
+        }
+        if indexer.implementation.is_empty() {
+            errors.push(format!(
+                "indexers[{}].implementation must be non-empty",
+                indexer.name
+            ));
+        }
+        if indexer.priority.is_some_and(|p| !(1..=50).contains(&p)) {
+            errors.push(format!(
+                "indexers[{}].priority must be between 1 and 50",
+                indexer.name
+            ));
+        }
+    }
+}
+
+fn validate_media_server_users(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let (plex, config) = match spec.app_config {
+        Some(AppConfig::Jellyfin(ref c)) => (false, c),
+        Some(AppConfig::Plex(ref c)) => (true, c),
+        _ => return,
+    };
+    if !plex && config.plex_token_secret.is_some() {
+        errors.push("appConfig.plexTokenSecret is only supported for Plex".into());
+    }
+    if config.users.is_empty() && !config.auto_remove {
+        return;
+    }
+    if plex && config.plex_token_secret.is_none() {
+        errors.push("Plex users require appConfig.plexTokenSecret".into());
+    }
+    if !plex && spec.admin_credentials.is_none() {
+        errors.push("Jellyfin users require adminCredentials".into());
+    }
+    let mut names = HashSet::new();
+    for user in &config.users {
+        if user.username.is_empty() {
+            errors.push("users[].username must be non-empty".into());
+            continue;
+        }
+        if !names.insert(user.username.to_ascii_lowercase()) {
+            errors.push(format!("users: duplicate username '{}'", user.username));
+        }
+        if plex && user.password_secret.is_some() {
+            errors.push(format!(
+                "users[{}].passwordSecret is not supported for Plex; passwords belong to plex.tv",
+                user.username
+            ));
+        }
+        if !plex && user.password_secret.is_some() == user.sso_subject.is_some() {
+            errors.push(format!(
+                "users[{}] must set exactly one of passwordSecret or ssoSubject",
+                user.username
+            ));
+        }
+    }
+}
+
+fn validate_integrity_check(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(check) = spec
+        .backup
+        .as_ref()
+        .and_then(|b| b.integrity_check.as_ref())
+    else {
+        return;
+    };
+    if servarr_resources::integrity_check::database_file(&spec.app).is_none() {
+        errors.push(format!(
+            "backup.integrityCheck is not supported for {}; it has no SQLite database",
+            spec.app
+        ));
+    }
+    if spec.database.is_some() {
+        errors.push("backup.integrityCheck cannot be used with a Postgres database".into());
+    }
+    if crate::schedule::parse(&check.schedule).is_err() {
+        errors.push(format!(
+            "backup.integrityCheck.schedule '{}' must be a five-field cron expression",
+            check.schedule
+        ));
+    }
+}
+
+fn validate_backup_schedule(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    if let Some(ref backup) = spec.backup
+        && !backup.schedule.is_empty()
+        && let Err(e) = crate::schedule::parse(&backup.schedule)
+    {
+        errors.push(format!("backup.schedule: {e}"));
+    }
+}
+
+/// Names of the containers and init containers the deployment builder
+/// produces for `spec` on its own, without the user's extra containers.
+fn operator_container_names(spec: &ServarrAppSpec) -> BTreeSet<String> {
+    let mut app = ServarrApp::new(
+        "validate",
+        ServarrAppSpec {
+            extra_containers: Vec::new(),
+            extra_init_containers: Vec::new(),
+            ..spec.clone()
+        },
+    );
+    app.metadata.namespace = Some("default".into());
+    app.metadata.uid = Some("validate".into());
+    let deployment = servarr_resources::deployment::build(&app, &Default::default());
+    deployment
+        .spec
+        .and_then(|s| s.template.spec)
+        .map(|pod| {
+            pod.containers
+                .into_iter()
+                .chain(pod.init_containers.into_iter().flatten())
+                .map(|c| c.name)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn validate_extra_containers(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    if spec.extra_containers.is_empty() && spec.extra_init_containers.is_empty() {
+        return;
+    }
+    let reserved = operator_container_names(spec);
+    let mut seen = HashSet::new();
+    for (field, containers) in [
+        ("extraContainers", &spec.extra_containers),
+        ("extraInitContainers", &spec.extra_init_containers),
+    ] {
+        for c in containers {
+            if c.name.is_empty() {
+                errors.push(format!("{field}: container name must not be empty"));
+            } else if reserved.contains(&c.name) {
+                errors.push(format!(
+                    "{field}: container name '{}' is taken by an operator-managed container",
+                    c.name
+                ));
+            } else if !seen.insert(c.name.as_str()) {
+                errors.push(format!("{field}: duplicate container name '{}'", c.name));
+            }
+        }
+    }
+}
+
+fn validate_api_key_rotation(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(ref rotation) = spec.api_key_rotation else {
+        return;
+    };
+    if !crate::api_key_rotation::supported(&spec.app) {
+        errors.push(format!(
+            "apiKeyRotation is not supported for {}; only Sonarr, Radarr, Lidarr, Readarr, Whisparr, Prowlarr, and Bazarr",
+            spec.app
+        ));
+    }
+    if spec.api_key_secret.is_none() {
+        errors.push("apiKeyRotation requires apiKeySecret".into());
+    }
+    if let Err(e) = crate::schedule::parse(&rotation.schedule) {
+        errors.push(format!("apiKeyRotation.schedule: {e}"));
+    }
+}
+
+fn validate_exposure(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    if let Some(ref ingress) = spec.ingress
+        && let Some(ref gateway) = spec.gateway
+        && gateway.enabled
+        && gateway.hosts.contains(&ingress.host)
+    {
+        errors.push(format!(
+            "ingress.host '{}' is also in gateway.hosts; route each host through either the Ingress or the gateway",
+            ingress.host
+        ));
+    }
+}
+
+/// Whether `s` is an IPv4 or IPv6 CIDR block such as `10.0.0.0/8`.
+fn is_valid_cidr(s: &str) -> bool {
+    let Some((addr, prefix)) = s.split_once('/') else {
+        return false;
+    };
+    let Ok(prefix) = prefix.parse::<u8>() else {
+        return false;
+    };
+    match addr.parse::<IpAddr>() {
+        Ok(IpAddr::V4(_)) => prefix <= 32,
+        Ok(IpAddr::V6(_)) => prefix <= 128,
+        Err(_) => false,
+    }
+}
+
+fn validate_network_policy_cidrs(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    let Some(ref config) = spec.network_policy_config else {
+        return;
+    };
+    for (i, cidr) in config.denied_cidr_blocks.iter().enumerate() {
+        if !is_valid_cidr(cidr) {
+            errors.push(format!(
+                "networkPolicyConfig.deniedCidrBlocks[{i}]: '{cidr}' is not a valid CIDR block"
+            ));
+        }
+    }
+    for (i, rule) in config.custom_egress_rules.iter().enumerate() {
+        let peers = rule.get("to").and_then(|t| t.as_array());
+        for (j, peer) in peers.into_iter().flatten().enumerate() {
+            let Some(block) = peer.get("ipBlock") else {
+                continue;
+            };
+            let path = format!("networkPolicyConfig.customEgressRules[{i}].to[{j}].ipBlock");
+            let cidr = block
+                .get("cidr")
+                .and_then(|c| c.as_str())
+                .unwrap_or_default();
+            if !is_valid_cidr(cidr) {
+                errors.push(format!("{path}.cidr: '{cidr}' is not a valid CIDR block"));
+            }
+            let excepts = block.get("except").and_then(|e| e.as_array());
+            for (k, except) in excepts.into_iter().flatten().enumerate() {
+                let except = except.as_str().unwrap_or_default();
+                if !is_valid_cidr(except) {
+                    errors.push(format!(
+                        "{path}.except[{k}]: '{except}' is not a valid CIDR block"
+                    ));
+                }
+            }
+        }
+    }
+}
+
+fn validate_backup_retention(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    if let Some(ref backup) = spec.backup
+        && backup.enabled
+        && backup.retention_count == 0
+    {
+        errors.push("backup.retentionCount must be >= 1 when backups are enabled".into());
+    }
+}
+
+fn validate_indexer_definition_names(spec: &ServarrAppSpec, errors: &mut Vec<String>) {
+    if let Some(AppConfig::Prowlarr(ref pc)) = spec.app_config {
+        for def in &pc.custom_definitions {
+            if !def
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-')
+                || def.name.is_empty()
+            {
+                errors.push(format!(
+                    "appConfig.prowlarr.customDefinitions[].name '{}' must be non-empty and contain only alphanumeric characters or hyphens",
+                    def.name
+                ));
+            }
+        }
+    }
+}
+
+/// Parse CPU quantity to millicores for comparison.
+pub(crate) fn parse_cpu(s: &str) -> Option<u64> {
+    if s.is_empty() {
+        return None;
+    }
+    if let Some(m) = s.strip_suffix('m') {
+        m.parse().ok()
+    } else {
+        s.parse::<f64>().ok().map(|v| (v * 1000.0) as u64)
+    }
+}
+
+/// Parse memory quantity to bytes for comparison.
+pub(crate) fn parse_memory(s: &str) -> Option<u64> {
+    if s.is_empty() {
+        return None;
+    }
+    for (suffix, multiplier) in [
+        ("Ti", 1024u64 * 1024 * 1024 * 1024),
+        ("Gi", 1024 * 1024 * 1024),
+        ("Mi", 1024 * 1024),
+        ("Ki", 1024),
+        ("T", 1000 * 1000 * 1000 * 1000),
+        ("G", 1000 * 1000 * 1000),
+        ("M", 1000 * 1000),
+        ("K", 1000),
+    ] {
+        if let Some(num) = s.strip_suffix(suffix) {
+            return num.parse::<u64>().ok().map(|v| v * multiplier);
+        }
+    }
+    s.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::Container;
+    use servarr_crds::*;
+
+    // ── Helper to build a minimal ServarrAppSpec ──
+
+    fn minimal_spec(app: AppType) -> ServarrAppSpec {
+        ServarrAppSpec {
+            app,
+            ..Default::default()
+        }
+    }
+
+    // ── parse_cpu ──
+
+    #[test]
+    fn parse_cpu_empty_string() {
+        assert_eq!(parse_cpu(""), None);
+    }
+
+    #[test]
+    fn parse_cpu_millicores() {
+        assert_eq!(parse_cpu("500m"), Some(500));
+    }
+
+    #[test]
+    fn parse_cpu_whole_cores() {
+        assert_eq!(parse_cpu("1"), Some(1000));
+    }
+
+    #[test]
+    fn parse_cpu_fractional_cores() {
+        assert_eq!(parse_cpu("2.5"), Some(2500));
+    }
+
+    #[test]
+    fn parse_cpu_quarter_core() {
+        assert_eq!(parse_cpu("0.25"), Some(250));
+    }
+
+    #[test]
+    fn parse_cpu_100m() {
+        assert_eq!(parse_cpu("100m"), Some(100));
+    }
+
+    // ── parse_memory ──
+
+    #[test]
+    fn parse_memory_empty_string() {
+        assert_eq!(parse_memory(""), None);
+    }
+
+    #[test]
+    fn parse_memory_raw_bytes() {
+        assert_eq!(parse_memory("1024"), Some(1024));
+    }
+
+    #[test]
+    fn parse_memory_ki() {
+        assert_eq!(parse_memory("1Ki"), Some(1024));
+    }
+
+    #[test]
+    fn parse_memory_mi() {
+        assert_eq!(parse_memory("1Mi"), Some(1_048_576));
+    }
+
+    #[test]
+    fn parse_memory_gi() {
+        assert_eq!(parse_memory("1Gi"), Some(1_073_741_824));
+    }
+
+    #[test]
+    fn parse_memory_ti() {
+        assert_eq!(parse_memory("1Ti"), Some(1_099_511_627_776));
+    }
+
+    #[test]
+    fn parse_memory_k_decimal() {
+        assert_eq!(parse_memory("1K"), Some(1_000));
+    }
+
+    #[test]
+    fn parse_memory_m_decimal() {
+        assert_eq!(parse_memory("1M"), Some(1_000_000));
+    }
+
+    #[test]
+    fn parse_memory_g_decimal() {
+        assert_eq!(parse_memory("1G"), Some(1_000_000_000));
+    }
+
+    #[test]
+    fn parse_memory_t_decimal() {
+        assert_eq!(parse_memory("1T"), Some(1_000_000_000_000));
+    }
+
+    #[test]
+    fn parse_memory_512mi() {
+        assert_eq!(parse_memory("512Mi"), Some(536_870_912));
+    }
+
+    // ── validate_app_config_match ──
+
+    #[test]
+    fn app_config_match_no_config() {
+        let spec = minimal_spec(AppType::Sonarr);
+        let mut errors = Vec::new();
+        validate_app_config_match(&spec, &mut errors);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn app_config_match_transmission_ok() {
+        let mut spec = minimal_spec(AppType::Transmission);
+        spec.app_config = Some(AppConfig::Transmission(TransmissionConfig::default()));
+        let mut errors = Vec::new();
+        validate_app_config_match(&spec, &mut errors);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn app_config_match_sabnzbd_ok() {
+        let mut spec = minimal_spec(AppType::Sabnzbd);
+        spec.app_config = Some(AppConfig::Sabnzbd(SabnzbdConfig::default()));
+        let mut errors = Vec::new();
+        validate_app_config_match(&spec, &mut errors);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn app_config_match_prowlarr_ok() {
+        let mut spec = minimal_spec(AppType::Prowlarr);
+        spec.app_config = Some(AppConfig::Prowlarr(ProwlarrConfig::default()));
+        let mut errors = Vec::new();
+        validate_app_config_match(&spec, &mut errors);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn app_config_match_overseerr_ok() {
+        let mut spec = minimal_spec(AppType::Overseerr);
+        spec.app_config = Some(AppConfig::Overseerr(Box::default()));
+        let mut errors = Vec::new();
+        validate_app_config_match(&spec, &mut errors);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn app_config_match_jellyseerr() {
+        let mut spec = minimal_spec(AppType::Jellyseerr);
+        spec.app_config = Some(AppConfig::Jellyseerr(Box::default()));
+        let mut errors = Vec::new();
+        validate_app_config_match(&spec, &mut errors);
+        assert!(errors.is_empty());
+
+        spec.app_config = Some(AppConfig::Overseerr(Box::default()));
+        validate_app_config_match(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn app_config_match_qbittorrent() {
+        let mut spec = minimal_spec(AppType::Qbittorrent);
+        spec.app_config = Some(AppConfig::Qbittorrent(QbittorrentConfig::default()));
+        let mut errors = Vec::new();
+        validate_app_config_match(&spec, &mut errors);
+        assert!(errors.is_empty());
+
+        spec.app_config = Some(AppConfig::Transmission(TransmissionConfig::default()));
+        validate_app_config_match(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn app_config_match_ssh_bastion_ok() {
+        let mut spec = minimal_spec(AppType::SshBastion);
+        spec.app_config = Some(AppConfig::SshBastion(SshBastionConfig::default()));
+        let mut errors = Vec::new();
+        validate_app_config_match(&spec, &mut errors);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn app_config_match_mismatch() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.app_config = Some(AppConfig::Transmission(TransmissionConfig::default()));
+        let mut errors = Vec::new();
+        validate_app_config_match(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("does not match app type"));
+    }
+
+    // ── validate_port_ranges ──
+
+    #[test]
+    fn port_ranges_valid_port() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.service = Some(ServiceSpec {
+            ports: vec![ServicePort {
+                name: "http".into(),
+                port: 8080,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        let mut errors = Vec::new();
+        validate_port_ranges(&spec, &mut errors);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn port_ranges_port_zero() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.service = Some(ServiceSpec {
+            ports: vec![ServicePort {
+                name: "http".into(),
+                port: 0,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        let mut errors = Vec::new();
+        validate_port_ranges(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("out of range"));
+    }
+
+    #[test]
+    fn port_ranges_port_65536() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.service = Some(ServiceSpec {
+            ports: vec![ServicePort {
+                name: "http".into(),
+                port: 65536,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        let mut errors = Vec::new();
+        validate_port_ranges(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("out of range"));
+    }
+
+    #[test]
+    fn port_ranges_container_port_out_of_range() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.service = Some(ServiceSpec {
+            ports: vec![ServicePort {
+                name: "http".into(),
+                port: 80,
+                container_port: Some(70000),
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        let mut errors = Vec::new();
+        validate_port_ranges(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("containerPort"));
+    }
+
+    #[test]
+    fn port_ranges_host_port_out_of_range() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.service = Some(ServiceSpec {
+            ports: vec![ServicePort {
+                name: "http".into(),
+                port: 80,
+                host_port: Some(-1),
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        let mut errors = Vec::new();
+        validate_port_ranges(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("hostPort"));
+    }
+
+    #[test]
+    fn port_ranges_transmission_peer_port_out_of_range() {
+        let mut spec = minimal_spec(AppType::Transmission);
+        spec.app_config = Some(AppConfig::Transmission(TransmissionConfig {
+            peer_port: Some(PeerPortConfig {
+                port: 0,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }));
+        let mut errors = Vec::new();
+        validate_port_ranges(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("peerPort"));
+    }
+
+    // ── validate_resource_bounds ──
+
+    #[test]
+    fn resource_bounds_no_resources() {
+        let spec = minimal_spec(AppType::Sonarr);
+        let mut errors = Vec::new();
+        validate_resource_bounds(&spec, &mut errors);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn resource_bounds_cpu_limit_gte_request() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.resources = Some(ResourceRequirements {
+            limits: ResourceList {
+                cpu: "1".into(),
+                memory: "".into(),
+            },
+            requests: ResourceList {
+                cpu: "500m".into(),
+                memory: "".into(),
+            },
+        });
+        let mut errors = Vec::new();
+        validate_resource_bounds(&spec, &mut errors);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn resource_bounds_cpu_limit_lt_request() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.resources = Some(ResourceRequirements {
+            limits: ResourceList {
+                cpu: "250m".into(),
+                memory: "".into(),
+            },
+            requests: ResourceList {
+                cpu: "500m".into(),
+                memory: "".into(),
+            },
+        });
+        let mut errors = Vec::new();
+        validate_resource_bounds(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("limits.cpu"));
+    }
+
+    #[test]
+    fn resource_bounds_memory_limit_lt_request() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.resources = Some(ResourceRequirements {
+            limits: ResourceList {
+                cpu: "".into(),
+                memory: "256Mi".into(),
+            },
+            requests: ResourceList {
+                cpu: "".into(),
+                memory: "512Mi".into(),
+            },
+        });
+        let mut errors = Vec::new();
+        validate_resource_bounds(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("limits.memory"));
+    }
+
+    #[test]
+    fn resource_bounds_empty_cpu_no_error() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.resources = Some(ResourceRequirements {
+            limits: ResourceList {
+                cpu: "".into(),
+                memory: "".into(),
+            },
+            requests: ResourceList {
+                cpu: "".into(),
+                memory: "".into(),
+            },
+        });
+        let mut errors = Vec::new();
+        validate_resource_bounds(&spec, &mut errors);
+        assert!(errors.is_empty());
+    }
+
+    // ── validate_gateway_hosts ──
+
+    #[test]
+    fn gateway_hosts_disabled() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.gateway = Some(GatewaySpec {
+            enabled: false,
+            hosts: vec![],
+            ..Default::default()
+        });
+        let mut errors = Vec::new();
+        validate_gateway_hosts(&spec, &mut errors);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn gateway_hosts_enabled_with_hosts() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.gateway = Some(GatewaySpec {
+            enabled: true,
+            hosts: vec!["sonarr.example.com".into()],
+            ..Default::default()
+        });
+        let mut errors = Vec::new();
+        validate_gateway_hosts(&spec, &mut errors);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn gateway_hosts_enabled_empty_hosts() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.gateway = Some(GatewaySpec {
+            enabled: true,
+            hosts: vec![],
+            ..Default::default()
+        });
+        let mut errors = Vec::new();
+        validate_gateway_hosts(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("non-empty"));
+    }
+
+    #[test]
+    fn gateway_backend_weights() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.gateway = Some(GatewaySpec {
+            enabled: true,
+            hosts: vec!["sonarr.example.com".into()],
+            backends: vec![
+                GatewayBackend {
+                    name: "sonarr-canary".into(),
+                    port: None,
+                    weight: 20,
+                },
+                GatewayBackend {
+                    name: String::new(),
+                    port: None,
+                    weight: 90,
+                },
+            ],
+            ..Default::default()
+        });
+        let mut errors = Vec::new();
+        validate_gateway_backends(&spec, &mut errors);
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors[0].contains("backends[1].name must be set"));
+        assert!(errors[1].contains("add up to 110"));
 
-    // ── Helper to build a minimal ServarrAppSpec ──
+        spec.gateway.as_mut().unwrap().backends.truncate(1);
+        let mut errors = Vec::new();
+        validate_gateway_backends(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+    }
 
-    fn minimal_spec(app: AppType) -> ServarrAppSpec {
-        ServarrAppSpec {
-            app,
+    #[test]
+    fn lifecycle_commands_and_grace_period() {
+        let mut spec = minimal_spec(AppType::Transmission);
+        spec.lifecycle = Some(LifecycleSpec {
+            pre_stop: vec![String::new(), "--exit".into()],
+            termination_grace_period_seconds: Some(-1),
             ..Default::default()
-        }
+        });
+        let mut errors = Vec::new();
+        validate_lifecycle(&spec, &mut errors);
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors[0].contains("lifecycle.preStop"));
+        assert!(errors[1].contains("terminationGracePeriodSeconds"));
+
+        spec.lifecycle = Some(LifecycleSpec {
+            pre_stop: vec!["transmission-remote".into(), "--exit".into()],
+            termination_grace_period_seconds: Some(60),
+            ..Default::default()
+        });
+        let mut errors = Vec::new();
+        validate_lifecycle(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
     }
 
-    // ── parse_cpu ──
-
     #[test]
-    fn parse_cpu_empty_string() {
-        assert_eq!(parse_cpu(""), None);
+    fn credential_secret_refs() {
+        let mut spec = minimal_spec(AppType::Sabnzbd);
+        spec.api_key_secret = Some("sabnzbd-api-key".into());
+        spec.app_config = Some(AppConfig::Sabnzbd(SabnzbdConfig {
+            credentials_secret_ref: Some(CredentialsSecretRef {
+                name: "media-credentials".into(),
+                api_key_key: String::new(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }));
+        let mut errors = Vec::new();
+        validate_credential_secret_refs(&spec, &mut errors);
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors[0].contains("mutually exclusive"));
+        assert!(errors[1].contains("apiKeyKey"));
+
+        spec.api_key_secret = None;
+        spec.app_config = Some(AppConfig::Sabnzbd(SabnzbdConfig {
+            credentials_secret_ref: Some(CredentialsSecretRef {
+                name: "media-credentials".into(),
+                api_key_key: "SABNZBD_API_KEY".into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }));
+        let mut errors = Vec::new();
+        validate_credential_secret_refs(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(
+            spec.api_key_ref(),
+            Some(("media-credentials", "SABNZBD_API_KEY"))
+        );
+
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.api_key_secret_key = Some("SONARR_API_KEY".into());
+        let mut errors = Vec::new();
+        validate_credential_secret_refs(&spec, &mut errors);
+        assert_eq!(errors, ["apiKeySecretKey requires apiKeySecret"]);
     }
 
     #[test]
-    fn parse_cpu_millicores() {
-        assert_eq!(parse_cpu("500m"), Some(500));
+    fn extra_container_names() {
+        let container = |name: &str| Container {
+            name: name.into(),
+            image: Some("timberio/vector:0.39.0-alpine".into()),
+            ..Default::default()
+        };
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.extra_containers = vec![container("vector"), container("sonarr")];
+        spec.extra_init_containers = vec![
+            container("vector"),
+            container(""),
+            container("apply-config-file"),
+            container("rclone-gdrive"),
+        ];
+        spec.config_file = [("UrlBase".to_string(), "/sonarr".to_string())].into();
+        spec.rclone_mounts = vec![
+            serde_json::from_value(serde_json::json!({
+                "name": "gdrive",
+                "remote": "gdrive:media",
+                "mountPath": "/media",
+                "configSecret": "rclone-config",
+            }))
+            .unwrap(),
+        ];
+        let mut errors = Vec::new();
+        validate_extra_containers(&spec, &mut errors);
+        assert_eq!(
+            errors,
+            [
+                "extraContainers: container name 'sonarr' is taken by an operator-managed container",
+                "extraInitContainers: duplicate container name 'vector'",
+                "extraInitContainers: container name must not be empty",
+                "extraInitContainers: container name 'apply-config-file' is taken by an operator-managed container",
+                "extraInitContainers: container name 'rclone-gdrive' is taken by an operator-managed container",
+            ]
+        );
+
+        spec.extra_containers.pop();
+        spec.extra_init_containers = vec![container("wait-for-db")];
+        let mut errors = Vec::new();
+        validate_extra_containers(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
     }
 
     #[test]
-    fn parse_cpu_whole_cores() {
-        assert_eq!(parse_cpu("1"), Some(1000));
+    fn api_key_rotation_needs_supported_app_secret_and_schedule() {
+        let mut spec = minimal_spec(AppType::Sabnzbd);
+        spec.api_key_rotation = Some(ApiKeyRotationSpec {
+            schedule: "sometimes".into(),
+        });
+        let mut errors = Vec::new();
+        validate_api_key_rotation(&spec, &mut errors);
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors[0].starts_with("apiKeyRotation is not supported"));
+        assert!(errors[1].contains("requires apiKeySecret"));
+        assert!(errors[2].starts_with("apiKeyRotation.schedule"));
+
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.api_key_secret = Some("sonarr-api-key".into());
+        spec.api_key_rotation = Some(ApiKeyRotationSpec {
+            schedule: "0 4 1 * *".into(),
+        });
+        let mut errors = Vec::new();
+        validate_api_key_rotation(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
     }
 
+    // ── validate_unique_volume_names ──
+
     #[test]
-    fn parse_cpu_fractional_cores() {
-        assert_eq!(parse_cpu("2.5"), Some(2500));
+    fn unique_volume_names_ok() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.persistence = Some(PersistenceSpec {
+            volumes: vec![
+                PvcVolume {
+                    name: "config".into(),
+                    mount_path: "/config".into(),
+                    ..Default::default()
+                },
+                PvcVolume {
+                    name: "data".into(),
+                    mount_path: "/data".into(),
+                    ..Default::default()
+                },
+            ],
+            nfs_mounts: vec![],
+            verify_mounts: false,
+        });
+        let mut errors = Vec::new();
+        validate_unique_volume_names(&spec, &mut errors);
+        assert!(errors.is_empty());
     }
 
     #[test]
-    fn parse_cpu_quarter_core() {
-        assert_eq!(parse_cpu("0.25"), Some(250));
+    fn unique_volume_names_duplicate() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.persistence = Some(PersistenceSpec {
+            volumes: vec![
+                PvcVolume {
+                    name: "config".into(),
+                    mount_path: "/config".into(),
+                    ..Default::default()
+                },
+                PvcVolume {
+                    name: "config".into(),
+                    mount_path: "/config2".into(),
+                    ..Default::default()
+                },
+            ],
+            nfs_mounts: vec![],
+            verify_mounts: false,
+        });
+        let mut errors = Vec::new();
+        validate_unique_volume_names(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("duplicate volume name"));
     }
 
     #[test]
-    fn parse_cpu_100m() {
-        assert_eq!(parse_cpu("100m"), Some(100));
+    fn unique_volume_names_duplicate_nfs() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.persistence = Some(PersistenceSpec {
+            volumes: vec![],
+            nfs_mounts: vec![
+                NfsMount {
+                    name: "media".into(),
+                    server: "nas".into(),
+                    path: "/media".into(),
+                    mount_path: "/media".into(),
+                    ..Default::default()
+                },
+                NfsMount {
+                    name: "media".into(),
+                    server: "nas".into(),
+                    path: "/media2".into(),
+                    mount_path: "/media2".into(),
+                    ..Default::default()
+                },
+            ],
+            verify_mounts: false,
+        });
+        let mut errors = Vec::new();
+        validate_unique_volume_names(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("duplicate nfsMount name"));
     }
 
-    // ── parse_memory ──
+    // ── validate_transmission_settings ──
 
     #[test]
-    fn parse_memory_empty_string() {
-        assert_eq!(parse_memory(""), None);
+    fn transmission_settings_no_config() {
+        let spec = minimal_spec(AppType::Sonarr);
+        let mut errors = Vec::new();
+        validate_transmission_settings(&spec, &mut errors);
+        assert!(errors.is_empty());
     }
 
     #[test]
-    fn parse_memory_raw_bytes() {
-        assert_eq!(parse_memory("1024"), Some(1024));
+    fn transmission_settings_no_managed_keys() {
+        let mut spec = minimal_spec(AppType::Transmission);
+        spec.app_config = Some(AppConfig::Transmission(TransmissionConfig {
+            settings: serde_json::json!({"speed-limit-down": 100}),
+            ..Default::default()
+        }));
+        let mut errors = Vec::new();
+        validate_transmission_settings(&spec, &mut errors);
+        assert!(errors.is_empty());
     }
 
     #[test]
-    fn parse_memory_ki() {
-        assert_eq!(parse_memory("1Ki"), Some(1024));
+    fn transmission_settings_with_managed_key() {
+        let mut spec = minimal_spec(AppType::Transmission);
+        spec.app_config = Some(AppConfig::Transmission(TransmissionConfig {
+            settings: serde_json::json!({"rpc-password": "hunter2"}),
+            ..Default::default()
+        }));
+        let mut errors = Vec::new();
+        validate_transmission_settings(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("rpc-password"));
     }
 
     #[test]
-    fn parse_memory_mi() {
-        assert_eq!(parse_memory("1Mi"), Some(1_048_576));
+    fn transmission_settings_conflict_with_directories() {
+        let mut spec = minimal_spec(AppType::Transmission);
+        spec.app_config = Some(AppConfig::Transmission(TransmissionConfig {
+            settings: serde_json::json!({"download-dir": "/downloads/done"}),
+            directories: Some(TransmissionDirectories::default()),
+            ..Default::default()
+        }));
+        let mut errors = Vec::new();
+        validate_transmission_settings(&spec, &mut errors);
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert!(errors[0].contains("'download-dir' when directories is set"));
+    }
+
+    // ── validate_torrent_directories ──
+
+    fn transmission_with_dirs(dirs: TransmissionDirectories) -> ServarrAppSpec {
+        let mut spec = minimal_spec(AppType::Transmission);
+        spec.app_config = Some(AppConfig::Transmission(TransmissionConfig {
+            directories: Some(dirs),
+            ..Default::default()
+        }));
+        spec
     }
 
     #[test]
-    fn parse_memory_gi() {
-        assert_eq!(parse_memory("1Gi"), Some(1_073_741_824));
+    fn transmission_directories_on_default_mounts() {
+        let spec = transmission_with_dirs(TransmissionDirectories {
+            download: Some("/downloads/complete".into()),
+            incomplete: Some("/downloads/incomplete".into()),
+            watch: Some("/watch/tv".into()),
+            categories: std::collections::BTreeMap::from([(
+                "tv-sonarr".into(),
+                "/downloads/complete/tv".into(),
+            )]),
+            ..Default::default()
+        });
+        let mut errors = Vec::new();
+        validate_torrent_directories(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
     }
 
     #[test]
-    fn parse_memory_ti() {
-        assert_eq!(parse_memory("1Ti"), Some(1_099_511_627_776));
+    fn transmission_directories_must_match_mounts() {
+        let mut spec = transmission_with_dirs(TransmissionDirectories {
+            download: Some("/data/complete".into()),
+            incomplete: Some("downloads/incomplete".into()),
+            categories: std::collections::BTreeMap::from([(
+                "movies".into(),
+                "/media/movies".into(),
+            )]),
+            ..Default::default()
+        });
+        spec.persistence = Some(PersistenceSpec {
+            volumes: vec![],
+            nfs_mounts: vec![NfsMount {
+                name: "media".into(),
+                server: "nas".into(),
+                path: "/media".into(),
+                mount_path: "/media".into(),
+                read_only: true,
+            }],
+            verify_mounts: false,
+        });
+        let mut errors = Vec::new();
+        validate_torrent_directories(&spec, &mut errors);
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors[0].contains("directories.download: '/data/complete' is not under"));
+        assert!(
+            errors[1].contains(
+                "directories.incomplete: 'downloads/incomplete' must be an absolute path"
+            )
+        );
+        assert!(errors[2].contains("read-only mount '/media'"));
     }
 
     #[test]
-    fn parse_memory_k_decimal() {
-        assert_eq!(parse_memory("1K"), Some(1_000));
+    fn qbittorrent_categories_must_match_mounts() {
+        let mut spec = minimal_spec(AppType::Qbittorrent);
+        spec.app_config = Some(AppConfig::Qbittorrent(QbittorrentConfig {
+            categories: std::collections::BTreeMap::from([
+                ("tv-sonarr".into(), "/downloads/tv".into()),
+                ("radarr".into(), "/data/movies".into()),
+                ("4k movies".into(), "/downloads/4k".into()),
+            ]),
+            peer_port: Some(PeerPortConfig {
+                port: 6881,
+                random_on_start: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }));
+        let mut errors = Vec::new();
+        validate_torrent_directories(&spec, &mut errors);
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors[0].contains("randomOnStart is not supported"));
+        assert!(errors[1].contains("'4k movies' must be non-empty without whitespace"));
+        assert!(errors[2].contains("categories.radarr: '/data/movies' is not under"));
+    }
+
+    // ── validate_rclone_mounts ──
+
+    fn rclone_mount(name: &str, mount_path: &str) -> RcloneMount {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "remote": "gdrive:media",
+            "mountPath": mount_path,
+            "configSecret": "rclone-config",
+        }))
+        .unwrap()
     }
 
     #[test]
-    fn parse_memory_m_decimal() {
-        assert_eq!(parse_memory("1M"), Some(1_000_000));
+    fn rclone_mounts_valid() {
+        let mut spec = minimal_spec(AppType::Jellyfin);
+        spec.rclone_mounts = vec![rclone_mount("movies", "/movies"), rclone_mount("tv", "/tv")];
+        let mut errors = Vec::new();
+        validate_rclone_mounts(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
     }
 
     #[test]
-    fn parse_memory_g_decimal() {
-        assert_eq!(parse_memory("1G"), Some(1_000_000_000));
+    fn rclone_mounts_rejects_bad_and_colliding_mounts() {
+        let mut spec = minimal_spec(AppType::Jellyfin);
+        let mut bad = rclone_mount("Movies", "/movies");
+        bad.remote = "gdrive".into();
+        bad.vfs.cache_mode = Some("everything".into());
+        spec.rclone_mounts = vec![
+            bad,
+            rclone_mount("tv", "/movies"),
+            rclone_mount("tv", "/config"),
+        ];
+        spec.persistence = Some(PersistenceSpec {
+            volumes: vec![PvcVolume {
+                name: "config".into(),
+                mount_path: "/config".into(),
+                ..Default::default()
+            }],
+            nfs_mounts: vec![],
+            verify_mounts: false,
+        });
+        let mut errors = Vec::new();
+        validate_rclone_mounts(&spec, &mut errors);
+        assert_eq!(errors.len(), 6, "{errors:?}");
+        assert!(errors[0].contains("lowercase DNS label"));
+        assert!(errors[1].contains("must be 'remote:path'"));
+        assert!(errors[2].contains("vfs.cacheMode 'everything'"));
+        assert!(errors[3].contains("mountPath '/movies' is already used"));
+        assert!(errors[4].contains("duplicate rcloneMount name: 'tv'"));
+        assert!(errors[5].contains("mountPath '/config' is already used"));
+    }
+
+    // ── validate_credentials ──
+
+    fn credential_ref(name: &str, env: &[(&str, &str)], mount_path: Option<&str>) -> CredentialRef {
+        CredentialRef {
+            name: name.into(),
+            env: env
+                .iter()
+                .map(|(name, key)| CredentialEnvVar {
+                    name: (*name).into(),
+                    key: (*key).into(),
+                })
+                .collect(),
+            mount_path: mount_path.map(Into::into),
+        }
     }
 
     #[test]
-    fn parse_memory_t_decimal() {
-        assert_eq!(parse_memory("1T"), Some(1_000_000_000_000));
+    fn credentials_valid() {
+        let mut spec = minimal_spec(AppType::Transmission);
+        spec.credentials = vec![
+            credential_ref("vpn", &[("OPENVPN_USER", "username")], None),
+            credential_ref("usenet", &[], Some("/run/secrets/usenet")),
+        ];
+        let mut errors = Vec::new();
+        validate_credentials(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
     }
 
     #[test]
-    fn parse_memory_512mi() {
-        assert_eq!(parse_memory("512Mi"), Some(536_870_912));
+    fn credentials_rejects_bad_and_colliding_refs() {
+        let mut spec = minimal_spec(AppType::Transmission);
+        spec.env = vec![EnvVar {
+            name: "TZ".into(),
+            value: "UTC".into(),
+        }];
+        spec.credentials = vec![
+            credential_ref("VPN", &[], None),
+            credential_ref("usenet", &[("TZ", "username")], Some("/config")),
+            credential_ref("usenet", &[], Some("relative")),
+        ];
+        spec.persistence = Some(PersistenceSpec {
+            volumes: vec![PvcVolume {
+                name: "config".into(),
+                mount_path: "/config".into(),
+                ..Default::default()
+            }],
+            nfs_mounts: vec![],
+            verify_mounts: false,
+        });
+        let mut errors = Vec::new();
+        validate_credentials(&spec, &mut errors);
+        assert_eq!(errors.len(), 6, "{errors:?}");
+        assert!(errors[0].contains("lowercase DNS label"));
+        assert!(errors[1].contains("set env, mountPath, or both"));
+        assert!(errors[2].contains("env var 'TZ' is already set"));
+        assert!(errors[3].contains("mountPath '/config' is already used"));
+        assert!(errors[4].contains("mountPath 'relative' must be an absolute path"));
+        assert!(errors[5].contains("duplicate credential: 'usenet'"));
     }
 
-    // ── validate_app_config_match ──
+    // ── validate_quality_profiles ──
+
+    fn arr_config(profiles: serde_json::Value) -> AppConfig {
+        AppConfig::Sonarr(
+            serde_json::from_value(serde_json::json!({ "qualityProfiles": profiles })).unwrap(),
+        )
+    }
 
     #[test]
-    fn app_config_match_no_config() {
-        let spec = minimal_spec(AppType::Sonarr);
+    fn quality_profiles_valid() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.api_key_secret = Some("sonarr-api-key".into());
+        spec.app_config = Some(arr_config(serde_json::json!([
+            { "name": "HD", "qualities": ["WEB 1080p", "Bluray-1080p"], "cutoff": "Bluray-1080p" }
+        ])));
         let mut errors = Vec::new();
         validate_app_config_match(&spec, &mut errors);
-        assert!(errors.is_empty());
+        validate_quality_profiles(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
     }
 
     #[test]
-    fn app_config_match_transmission_ok() {
-        let mut spec = minimal_spec(AppType::Transmission);
-        spec.app_config = Some(AppConfig::Transmission(TransmissionConfig::default()));
+    fn quality_profiles_rejects_bad_profiles() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.app_config = Some(arr_config(serde_json::json!([
+            { "name": "HD", "qualities": ["WEB 1080p"], "cutoff": "Bluray-2160p" },
+            { "name": "HD", "qualities": [] }
+        ])));
         let mut errors = Vec::new();
-        validate_app_config_match(&spec, &mut errors);
-        assert!(errors.is_empty());
+        validate_quality_profiles(&spec, &mut errors);
+        assert_eq!(errors.len(), 4, "{errors:?}");
+        assert!(errors.iter().any(|e| e.contains("apiKeySecret")));
+        assert!(errors.iter().any(|e| e.contains("duplicate")));
+        assert!(errors.iter().any(|e| e.contains("cutoff 'Bluray-2160p'")));
     }
 
     #[test]
-    fn app_config_match_sabnzbd_ok() {
-        let mut spec = minimal_spec(AppType::Sabnzbd);
-        spec.app_config = Some(AppConfig::Sabnzbd(SabnzbdConfig::default()));
+    fn quality_profiles_config_must_match_app() {
+        let mut spec = minimal_spec(AppType::Radarr);
+        spec.app_config = Some(arr_config(serde_json::json!([])));
         let mut errors = Vec::new();
         validate_app_config_match(&spec, &mut errors);
-        assert!(errors.is_empty());
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn custom_formats_rejects_bad_formats() {
+        let mut spec = minimal_spec(AppType::Radarr);
+        spec.api_key_secret = Some("radarr-api-key".into());
+        spec.app_config = Some(AppConfig::Radarr(
+            serde_json::from_value(serde_json::json!({
+                "customFormats": [
+                    { "name": "3D", "specifications": [
+                        { "name": "3D", "implementation": "ReleaseTitleSpecification",
+                          "fields": { "value": "\\b3D\\b" } }
+                    ] }
+                ]
+            }))
+            .unwrap(),
+        ));
+        let mut errors = Vec::new();
+        validate_custom_formats(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        spec.api_key_secret = None;
+        spec.app_config = Some(AppConfig::Radarr(
+            serde_json::from_value(serde_json::json!({
+                "customFormats": [
+                    { "name": "3D", "specifications": [] },
+                    { "name": "3D", "specifications": [
+                        { "name": "3D", "implementation": "" }
+                    ] }
+                ]
+            }))
+            .unwrap(),
+        ));
+        validate_custom_formats(&spec, &mut errors);
+        assert_eq!(errors.len(), 4, "{errors:?}");
+        assert!(errors[0].contains("apiKeySecret"));
+        assert!(errors[1].contains("at least one condition"));
+        assert!(errors[2].contains("duplicate format name"));
+        assert!(errors[3].contains("implementation must not be empty"));
     }
 
     #[test]
-    fn app_config_match_prowlarr_ok() {
-        let mut spec = minimal_spec(AppType::Prowlarr);
-        spec.app_config = Some(AppConfig::Prowlarr(ProwlarrConfig::default()));
+    fn root_folders_rejects_relative_and_duplicate_paths() {
+        let mut spec = minimal_spec(AppType::Radarr);
+        spec.api_key_secret = Some("radarr-api-key".into());
+        spec.app_config = Some(AppConfig::Radarr(
+            serde_json::from_value(serde_json::json!({
+                "rootFolders": { "paths": ["/movies", "/movies/", "movies-4k"] }
+            }))
+            .unwrap(),
+        ));
         let mut errors = Vec::new();
-        validate_app_config_match(&spec, &mut errors);
-        assert!(errors.is_empty());
+        validate_root_folders(&spec, &mut errors);
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors[0].contains("'/movies/' is listed twice"));
+        assert!(errors[1].contains("'movies-4k' must be absolute"));
+
+        spec.api_key_secret = None;
+        let mut errors = Vec::new();
+        validate_root_folders(&spec, &mut errors);
+        assert!(errors.iter().any(|e| e.contains("apiKeySecret")));
     }
 
+    // ── validate_download_client_sync ──
+
     #[test]
-    fn app_config_match_overseerr_ok() {
-        let mut spec = minimal_spec(AppType::Overseerr);
-        spec.app_config = Some(AppConfig::Overseerr(Box::default()));
+    fn download_client_sync_checks_app_and_api_key() {
+        let mut spec = minimal_spec(AppType::Radarr);
+        spec.download_client_sync = Some(DownloadClientSyncSpec {
+            enabled: true,
+            ..Default::default()
+        });
         let mut errors = Vec::new();
-        validate_app_config_match(&spec, &mut errors);
-        assert!(errors.is_empty());
+        validate_download_client_sync(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("apiKeySecret"));
+
+        spec.api_key_secret = Some("radarr-api-key".into());
+        let mut errors = Vec::new();
+        validate_download_client_sync(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        spec.app = AppType::Prowlarr;
+        validate_download_client_sync(&spec, &mut errors);
+        assert!(errors[0].contains("Prowlarr"));
     }
 
     #[test]
-    fn app_config_match_ssh_bastion_ok() {
-        let mut spec = minimal_spec(AppType::SshBastion);
-        spec.app_config = Some(AppConfig::SshBastion(SshBastionConfig::default()));
+    fn tdarr_node_count_and_server() {
+        let mut spec = minimal_spec(AppType::TdarrNode);
+        spec.app_config = Some(AppConfig::TdarrNode(TdarrNodeConfig {
+            server: Some("media-tdarr".into()),
+            node_count: Some(4),
+        }));
         let mut errors = Vec::new();
-        validate_app_config_match(&spec, &mut errors);
-        assert!(errors.is_empty());
+        validate_tdarr_node(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        spec.workload_type = Some(WorkloadType::StatefulSet);
+        let mut errors = Vec::new();
+        validate_tdarr_node(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("workloadType Deployment"));
+
+        spec.app_config = Some(AppConfig::TdarrNode(TdarrNodeConfig {
+            server: Some("Media_Tdarr".into()),
+            node_count: Some(0),
+        }));
+        let mut errors = Vec::new();
+        validate_tdarr_node(&spec, &mut errors);
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors[0].contains("nodeCount: 0"));
+        assert!(errors[1].contains("'Media_Tdarr'"));
     }
 
     #[test]
-    fn app_config_match_mismatch() {
+    fn bazarr_checks_app_api_key_and_profiles() {
         let mut spec = minimal_spec(AppType::Sonarr);
-        spec.app_config = Some(AppConfig::Transmission(TransmissionConfig::default()));
+        spec.bazarr_sync = Some(BazarrSyncSpec {
+            enabled: true,
+            ..Default::default()
+        });
         let mut errors = Vec::new();
-        validate_app_config_match(&spec, &mut errors);
+        validate_bazarr(&spec, &mut errors);
         assert_eq!(errors.len(), 1);
-        assert!(errors[0].contains("does not match app type"));
+        assert!(errors[0].contains("Sonarr"));
+
+        spec.app = AppType::Bazarr;
+        spec.app_config = Some(AppConfig::Bazarr(BazarrConfig {
+            language_profiles: vec![
+                BazarrLanguageProfile {
+                    name: "English".into(),
+                    languages: vec!["en".into()],
+                },
+                BazarrLanguageProfile {
+                    name: "English".into(),
+                    languages: vec!["eng".into()],
+                },
+            ],
+            default_profile: Some("Dutch".into()),
+            ..Default::default()
+        }));
+        let mut errors = Vec::new();
+        validate_bazarr(&spec, &mut errors);
+        assert_eq!(errors.len(), 4, "{errors:?}");
+        assert!(errors[0].contains("apiKeySecret"));
+        assert!(errors[1].contains("duplicate profile 'English'"));
+        assert!(errors[2].contains("'eng' is not an ISO 639-1 code"));
+        assert!(errors[3].contains("defaultProfile"));
+
+        spec.api_key_secret = Some("bazarr-api-key".into());
+        if let Some(AppConfig::Bazarr(ref mut config)) = spec.app_config {
+            config.language_profiles.pop();
+            config.default_profile = Some("English".into());
+        }
+        let mut errors = Vec::new();
+        validate_bazarr(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
     }
 
-    // ── validate_port_ranges ──
+    #[test]
+    fn autoscaling_checks_app_and_bounds() {
+        let mut spec = minimal_spec(AppType::Overseerr);
+        spec.autoscaling = Some(AutoscalingSpec {
+            min_replicas: 1,
+            max_replicas: 3,
+            target_cpu_utilization_percentage: Some(70),
+            target_memory_utilization_percentage: None,
+        });
+        let mut errors = Vec::new();
+        validate_autoscaling(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let autoscaling = spec.autoscaling.as_mut().unwrap();
+        autoscaling.min_replicas = 0;
+        autoscaling.max_replicas = -1;
+        autoscaling.target_memory_utilization_percentage = Some(0);
+        validate_autoscaling(&spec, &mut errors);
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors[2].contains("targetMemoryUtilizationPercentage"));
+
+        spec.app = AppType::Sonarr;
+        let mut errors = Vec::new();
+        validate_autoscaling(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("Sonarr"));
+    }
 
     #[test]
-    fn port_ranges_valid_port() {
+    fn ingress_needs_hostname_and_absolute_path() {
         let mut spec = minimal_spec(AppType::Sonarr);
-        spec.service = Some(ServiceSpec {
-            ports: vec![ServicePort {
-                name: "http".into(),
-                port: 8080,
-                ..Default::default()
-            }],
+        spec.ingress = Some(IngressSpec {
+            host: "sonarr.example.com".into(),
             ..Default::default()
         });
         let mut errors = Vec::new();
-        validate_port_ranges(&spec, &mut errors);
-        assert!(errors.is_empty());
+        validate_ingress(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let ingress = spec.ingress.as_mut().unwrap();
+        ingress.host = "https://sonarr.example.com".into();
+        ingress.path = Some("sonarr".into());
+        validate_ingress(&spec, &mut errors);
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors[0].contains("scheme"));
+        assert!(errors[1].contains("start with '/'"));
     }
 
     #[test]
-    fn port_ranges_port_zero() {
-        let mut spec = minimal_spec(AppType::Sonarr);
-        spec.service = Some(ServiceSpec {
-            ports: vec![ServicePort {
-                name: "http".into(),
-                port: 0,
+    fn sftp_chroot_needs_sftp_mode_and_declared_volume() {
+        let mut spec = minimal_spec(AppType::SshBastion);
+        spec.persistence = Some(PersistenceSpec {
+            volumes: vec![PvcVolume {
+                name: "photos".into(),
+                mount_path: "/media/photos".into(),
                 ..Default::default()
             }],
             ..Default::default()
         });
+        spec.app_config = Some(AppConfig::SshBastion(SshBastionConfig {
+            users: vec![SshUser {
+                name: "alice".into(),
+                uid: 1000,
+                gid: 1000,
+                mode: SshMode::Sftp,
+                sftp_chroot: Some(SftpChroot {
+                    volume: "photos".into(),
+                    sub_path: Some("alice".into()),
+                    read_only: false,
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }));
         let mut errors = Vec::new();
-        validate_port_ranges(&spec, &mut errors);
-        assert_eq!(errors.len(), 1);
-        assert!(errors[0].contains("out of range"));
+        validate_sftp_chroot(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let Some(AppConfig::SshBastion(ref mut sc)) = spec.app_config else {
+            unreachable!()
+        };
+        sc.users[0].mode = SshMode::Shell;
+        sc.users[0].sftp_chroot = Some(SftpChroot {
+            volume: "videos".into(),
+            sub_path: Some("../etc".into()),
+            read_only: false,
+        });
+        validate_sftp_chroot(&spec, &mut errors);
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors[0].contains("requires mode sftp"));
+        assert!(errors[1].contains("'videos'"));
+        assert!(errors[2].contains("subPath"));
     }
 
     #[test]
-    fn port_ranges_port_65536() {
-        let mut spec = minimal_spec(AppType::Sonarr);
-        spec.service = Some(ServiceSpec {
-            ports: vec![ServicePort {
-                name: "http".into(),
-                port: 65536,
-                ..Default::default()
-            }],
+    fn prowlarr_sync_categories_and_profiles() {
+        let mut spec = minimal_spec(AppType::Prowlarr);
+        let mapping = |app: AppType, instance: Option<&str>| ProwlarrCategoryMapping {
+            app,
+            instance: instance.map(String::from),
+            sync_categories: vec![5000],
+            anime_sync_categories: Vec::new(),
+        };
+        let profile = |name: &str| ProwlarrAppProfile {
+            name: name.into(),
+            enable_rss: true,
+            enable_automatic_search: false,
+            enable_interactive_search: true,
+            minimum_seeders: 1,
+        };
+        spec.prowlarr_sync = Some(ProwlarrSyncSpec {
+            enabled: true,
+            categories: vec![
+                mapping(AppType::Sonarr, None),
+                mapping(AppType::Sonarr, Some("anime")),
+            ],
+            app_profiles: vec![profile("Usenet"), profile("Torrents")],
             ..Default::default()
         });
         let mut errors = Vec::new();
-        validate_port_ranges(&spec, &mut errors);
-        assert_eq!(errors.len(), 1);
-        assert!(errors[0].contains("out of range"));
+        validate_prowlarr_sync(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let sync = spec.prowlarr_sync.as_mut().unwrap();
+        sync.categories.push(mapping(AppType::Sonarr, None));
+        let mut plex = mapping(AppType::Plex, None);
+        plex.anime_sync_categories = vec![5070];
+        sync.categories.push(plex);
+        sync.app_profiles.push(profile("Usenet"));
+        validate_prowlarr_sync(&spec, &mut errors);
+        assert_eq!(errors.len(), 4, "{errors:?}");
+        assert!(errors[0].contains("duplicate mapping for Sonarr"));
+        assert!(errors[1].contains("Plex"));
+        assert!(errors[2].contains("animeSyncCategories"));
+        assert!(errors[3].contains("'Usenet'"));
     }
 
     #[test]
-    fn port_ranges_container_port_out_of_range() {
-        let mut spec = minimal_spec(AppType::Sonarr);
-        spec.service = Some(ServiceSpec {
-            ports: vec![ServicePort {
-                name: "http".into(),
-                port: 80,
-                container_port: Some(70000),
-                ..Default::default()
-            }],
+    fn prowlarr_indexers_need_api_key_and_unique_names() {
+        let indexer = |name: &str, priority| servarr_crds::ProwlarrIndexer {
+            name: name.into(),
+            implementation: "Cardigann".into(),
+            definition_name: Some("1337x".into()),
+            fields: Default::default(),
+            fields_secret: None,
+            tags: vec![],
+            enable: true,
+            priority,
+            app_profile: None,
+        };
+        let mut spec = minimal_spec(AppType::Prowlarr);
+        spec.app_config = Some(AppConfig::Prowlarr(ProwlarrConfig {
+            indexers: vec![indexer("1337x", Some(25)), indexer("1337x", Some(60))],
             ..Default::default()
-        });
+        }));
         let mut errors = Vec::new();
-        validate_port_ranges(&spec, &mut errors);
-        assert_eq!(errors.len(), 1);
-        assert!(errors[0].contains("containerPort"));
+        validate_prowlarr_indexers(&spec, &mut errors);
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors[0].contains("apiKeySecret"));
+        assert!(errors[1].contains("duplicate name"));
+        assert!(errors[2].contains("priority"));
+
+        spec.api_key_secret = Some("prowlarr-api-key".into());
+        spec.app_config = Some(AppConfig::Prowlarr(ProwlarrConfig {
+            indexers: vec![indexer("1337x", None), indexer("EZTV", Some(1))],
+            auto_remove: true,
+            ..Default::default()
+        }));
+        let mut errors = Vec::new();
+        validate_prowlarr_indexers(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
     }
 
     #[test]
-    fn port_ranges_host_port_out_of_range() {
-        let mut spec = minimal_spec(AppType::Sonarr);
-        spec.service = Some(ServiceSpec {
-            ports: vec![ServicePort {
-                name: "http".into(),
-                port: 80,
-                host_port: Some(-1),
-                ..Default::default()
-            }],
+    fn media_server_users_need_credentials_and_one_sign_in() {
+        use servarr_crds::{AdminCredentialsSpec, MediaServerConfig, MediaServerUser};
+        let user = |name: &str, password: Option<&str>, sso: Option<&str>| MediaServerUser {
+            username: name.into(),
+            password_secret: password.map(Into::into),
+            sso_subject: sso.map(Into::into),
+            libraries: vec![],
+        };
+        let mut spec = minimal_spec(AppType::Jellyfin);
+        spec.app_config = Some(AppConfig::Jellyfin(MediaServerConfig {
+            users: vec![
+                user("kid", Some("kid-password"), None),
+                user("Kid", None, None),
+            ],
             ..Default::default()
+        }));
+        let mut errors = Vec::new();
+        validate_media_server_users(&spec, &mut errors);
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors[0].contains("adminCredentials"));
+        assert!(errors[1].contains("duplicate username"));
+        assert!(errors[2].contains("exactly one"));
+
+        spec.admin_credentials = Some(AdminCredentialsSpec {
+            secret_name: "jellyfin-admin".into(),
         });
+        spec.app_config = Some(AppConfig::Jellyfin(MediaServerConfig {
+            users: vec![
+                user("kid", Some("kid-password"), None),
+                user("gran", None, Some("gran@example.com")),
+            ],
+            auto_remove: true,
+            ..Default::default()
+        }));
         let mut errors = Vec::new();
-        validate_port_ranges(&spec, &mut errors);
-        assert_eq!(errors.len(), 1);
-        assert!(errors[0].contains("hostPort"));
+        validate_media_server_users(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let mut spec = minimal_spec(AppType::Plex);
+        spec.app_config = Some(AppConfig::Plex(MediaServerConfig {
+            users: vec![user("kid", Some("kid-password"), None)],
+            ..Default::default()
+        }));
+        let mut errors = Vec::new();
+        validate_media_server_users(&spec, &mut errors);
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors[0].contains("plexTokenSecret"));
+        assert!(errors[1].contains("plex.tv"));
     }
 
+    // ── validate_integrity_check ──
+
     #[test]
-    fn port_ranges_transmission_peer_port_out_of_range() {
-        let mut spec = minimal_spec(AppType::Transmission);
-        spec.app_config = Some(AppConfig::Transmission(TransmissionConfig {
-            peer_port: Some(PeerPortConfig {
-                port: 0,
-                ..Default::default()
+    fn integrity_check_needs_sqlite_app_and_schedule() {
+        let check = |schedule: &str| BackupSpec {
+            integrity_check: Some(IntegrityCheckSpec {
+                schedule: schedule.into(),
+                image: None,
             }),
             ..Default::default()
-        }));
+        };
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.backup = Some(check("0 5 * * 0"));
         let mut errors = Vec::new();
-        validate_port_ranges(&spec, &mut errors);
+        validate_integrity_check(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        spec.backup = Some(check("@weekly"));
+        validate_integrity_check(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        spec.backup = Some(check("0 0 5 * * 0"));
+        validate_integrity_check(&spec, &mut errors);
         assert_eq!(errors.len(), 1);
-        assert!(errors[0].contains("peerPort"));
+        assert!(errors[0].contains("five-field"));
+
+        let mut spec = minimal_spec(AppType::Jellyfin);
+        spec.backup = Some(check("0 5 * * 0"));
+        let mut errors = Vec::new();
+        validate_integrity_check(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("no SQLite database"));
     }
 
-    // ── validate_resource_bounds ──
+    // ── validate_backup_schedule ──
 
     #[test]
-    fn resource_bounds_no_resources() {
-        let spec = minimal_spec(AppType::Sonarr);
+    fn backup_schedule_must_be_five_fields() {
+        let mut spec = minimal_spec(AppType::Sonarr);
         let mut errors = Vec::new();
-        validate_resource_bounds(&spec, &mut errors);
-        assert!(errors.is_empty());
+        for schedule in ["0 3 * * *", "@daily", ""] {
+            spec.backup = Some(BackupSpec {
+                schedule: schedule.into(),
+                ..Default::default()
+            });
+            validate_backup_schedule(&spec, &mut errors);
+        }
+        assert!(errors.is_empty(), "{errors:?}");
+
+        for schedule in ["0 0 3 * * *", "nightly", "61 3 * * *"] {
+            spec.backup = Some(BackupSpec {
+                schedule: schedule.into(),
+                ..Default::default()
+            });
+            validate_backup_schedule(&spec, &mut errors);
+        }
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors.iter().all(|e| e.starts_with("backup.schedule: ")));
     }
 
+    // ── validate_exposure ──
+
     #[test]
-    fn resource_bounds_cpu_limit_gte_request() {
+    fn ingress_and_gateway_cannot_share_a_host() {
         let mut spec = minimal_spec(AppType::Sonarr);
-        spec.resources = Some(ResourceRequirements {
-            limits: ResourceList {
-                cpu: "1".into(),
-                memory: "".into(),
-            },
-            requests: ResourceList {
-                cpu: "500m".into(),
-                memory: "".into(),
-            },
+        spec.ingress = Some(IngressSpec {
+            host: "sonarr.example.com".into(),
+            ..Default::default()
+        });
+        spec.gateway = Some(GatewaySpec {
+            enabled: false,
+            ..Default::default()
         });
         let mut errors = Vec::new();
-        validate_resource_bounds(&spec, &mut errors);
+        validate_exposure(&spec, &mut errors);
+        assert!(errors.is_empty());
+
+        spec.gateway = Some(GatewaySpec {
+            enabled: true,
+            hosts: vec!["tv.example.com".into()],
+            ..Default::default()
+        });
+        validate_exposure(&spec, &mut errors);
         assert!(errors.is_empty());
+
+        spec.gateway = Some(GatewaySpec {
+            enabled: true,
+            hosts: vec!["tv.example.com".into(), "sonarr.example.com".into()],
+            ..Default::default()
+        });
+        validate_exposure(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("ingress.host 'sonarr.example.com'"));
+    }
+
+    // ── validate_network_policy_cidrs ──
+
+    #[test]
+    fn cidr_parsing() {
+        assert!(is_valid_cidr("10.0.0.0/8"));
+        assert!(is_valid_cidr("0.0.0.0/0"));
+        assert!(is_valid_cidr("fd00::/8"));
+        assert!(!is_valid_cidr("10.0.0.0"));
+        assert!(!is_valid_cidr("10.0.0/8"));
+        assert!(!is_valid_cidr("10.0.0.0/33"));
+        assert!(!is_valid_cidr("fd00::/129"));
     }
 
     #[test]
-    fn resource_bounds_cpu_limit_lt_request() {
+    fn network_policy_cidrs_must_parse() {
         let mut spec = minimal_spec(AppType::Sonarr);
-        spec.resources = Some(ResourceRequirements {
-            limits: ResourceList {
-                cpu: "250m".into(),
-                memory: "".into(),
-            },
-            requests: ResourceList {
-                cpu: "500m".into(),
-                memory: "".into(),
-            },
-        });
+        spec.network_policy_config = Some(
+            serde_json::from_value(serde_json::json!({
+                "deniedCidrBlocks": ["10.0.0.0/8", "192.168.0.0/33"],
+                "customEgressRules": [{
+                    "to": [
+                        {"ipBlock": {"cidr": "0.0.0.0/0", "except": ["10.0.0.0/8", "bogus"]}},
+                        {"namespaceSelector": {}},
+                        {"ipBlock": {"cidr": "172.16.0.0"}}
+                    ]
+                }]
+            }))
+            .unwrap(),
+        );
         let mut errors = Vec::new();
-        validate_resource_bounds(&spec, &mut errors);
+        validate_network_policy_cidrs(&spec, &mut errors);
+        assert_eq!(
+            errors,
+            vec![
+                "networkPolicyConfig.deniedCidrBlocks[1]: '192.168.0.0/33' is not a valid CIDR block",
+                "networkPolicyConfig.customEgressRules[0].to[0].ipBlock.except[1]: 'bogus' is not a valid CIDR block",
+                "networkPolicyConfig.customEgressRules[0].to[2].ipBlock.cidr: '172.16.0.0' is not a valid CIDR block",
+            ]
+        );
+    }
+
+    // ── validate_media_management ──
+
+    #[test]
+    fn media_management_recycle_bin_must_be_mounted() {
+        let with_bin = |bin: &str| {
+            let mut spec = minimal_spec(AppType::Sonarr);
+            spec.api_key_secret = Some("sonarr-api-key".into());
+            spec.persistence = Some(PersistenceSpec {
+                nfs_mounts: vec![NfsMount {
+                    name: "media".into(),
+                    server: "nas".into(),
+                    path: "/export/media".into(),
+                    mount_path: "/data".into(),
+                    read_only: false,
+                }],
+                ..Default::default()
+            });
+            spec.app_config = Some(AppConfig::Sonarr(ArrConfig {
+                media_management: Some(MediaManagementSpec {
+                    use_hardlinks: Some(true),
+                    recycle_bin: Some(bin.into()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }));
+            let mut errors = Vec::new();
+            validate_media_management(&spec, &mut errors);
+            errors
+        };
+        assert!(with_bin("/data/.recycle").is_empty());
+        assert!(with_bin("/data").is_empty());
+        assert!(with_bin("").is_empty());
+
+        let errors = with_bin("/database/.recycle");
         assert_eq!(errors.len(), 1);
-        assert!(errors[0].contains("limits.cpu"));
+        assert!(errors[0].contains("not on a mounted volume"));
+        assert!(with_bin("recycle")[0].contains("absolute"));
+
+        let mut spec = minimal_spec(AppType::Radarr);
+        spec.app_config = Some(AppConfig::Radarr(ArrConfig {
+            media_management: Some(MediaManagementSpec::default()),
+            ..Default::default()
+        }));
+        let mut errors = Vec::new();
+        validate_media_management(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("apiKeySecret"));
     }
 
     #[test]
-    fn resource_bounds_memory_limit_lt_request() {
+    fn auto_update_needs_a_schedule_and_an_unpinned_image() {
         let mut spec = minimal_spec(AppType::Sonarr);
-        spec.resources = Some(ResourceRequirements {
-            limits: ResourceList {
-                cpu: "".into(),
-                memory: "256Mi".into(),
-            },
-            requests: ResourceList {
-                cpu: "".into(),
-                memory: "512Mi".into(),
-            },
+        spec.update_policy = Some(UpdatePolicySpec {
+            mode: UpdateMode::Auto,
+            schedule: "weekly".into(),
+            ..Default::default()
+        });
+        spec.image = Some(ImageSpec {
+            repository: "linuxserver/sonarr".into(),
+            tag: "4.0.16".into(),
+            digest: "sha256:abc".into(),
+            ..Default::default()
         });
         let mut errors = Vec::new();
-        validate_resource_bounds(&spec, &mut errors);
-        assert_eq!(errors.len(), 1);
-        assert!(errors[0].contains("limits.memory"));
+        validate_update_policy(&spec, &mut errors);
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors[0].contains("updatePolicy.schedule"));
+        assert!(errors[1].contains("image.digest"));
+
+        spec.update_policy.as_mut().unwrap().mode = UpdateMode::Notify;
+        let mut errors = Vec::new();
+        validate_update_policy(&spec, &mut errors);
+        assert!(errors.is_empty());
     }
 
     #[test]
-    fn resource_bounds_empty_cpu_no_error() {
+    fn maintenance_window_needs_a_schedule_and_a_duration() {
         let mut spec = minimal_spec(AppType::Sonarr);
-        spec.resources = Some(ResourceRequirements {
-            limits: ResourceList {
-                cpu: "".into(),
-                memory: "".into(),
-            },
-            requests: ResourceList {
-                cpu: "".into(),
-                memory: "".into(),
-            },
+        spec.maintenance_window = Some(AppMaintenanceWindow {
+            schedule: "0 2 * * 6".into(),
+            duration_minutes: 120,
         });
         let mut errors = Vec::new();
-        validate_resource_bounds(&spec, &mut errors);
-        assert!(errors.is_empty());
+        validate_maintenance_window(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        spec.maintenance_window = Some(AppMaintenanceWindow {
+            schedule: "saturdays".into(),
+            duration_minutes: 0,
+        });
+        validate_maintenance_window(&spec, &mut errors);
+        assert_eq!(errors.len(), 2, "{errors:?}");
     }
 
-    // ── validate_gateway_hosts ──
+    #[test]
+    fn tags_need_labels_the_apps_accept() {
+        let mut spec = minimal_spec(AppType::Lidarr);
+        spec.api_key_secret = Some("api-key".into());
+        spec.app_config = Some(AppConfig::Lidarr(ArrConfig {
+            tags: vec!["lossless".into(), "kids-music".into()],
+            ..Default::default()
+        }));
+        let mut errors = Vec::new();
+        validate_tags(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        spec.app_config = Some(AppConfig::Lidarr(ArrConfig {
+            tags: vec!["hi res".into()],
+            root_folders: Some(Default::default()),
+            ..Default::default()
+        }));
+        spec.download_client_sync = Some(DownloadClientSyncSpec {
+            enabled: true,
+            tags: vec!["".into()],
+            ..Default::default()
+        });
+        validate_tags(&spec, &mut errors);
+        assert_eq!(errors.len(), 3, "{errors:?}");
+    }
 
     #[test]
-    fn gateway_hosts_disabled() {
+    fn monitoring_availability_delay_is_radarr_only() {
+        let with = |app: AppType, config: AppConfig| {
+            let mut spec = minimal_spec(app);
+            spec.api_key_secret = Some("api-key".into());
+            spec.app_config = Some(config);
+            let mut errors = Vec::new();
+            validate_monitoring(&spec, &mut errors);
+            errors
+        };
+        let monitoring = MonitoringSpec {
+            unmonitor_deleted: Some(true),
+            availability_delay_days: Some(2),
+            ..Default::default()
+        };
+        let arr = ArrConfig {
+            monitoring: Some(monitoring),
+            ..Default::default()
+        };
+        assert!(with(AppType::Radarr, AppConfig::Radarr(arr.clone())).is_empty());
+
+        let errors = with(AppType::Sonarr, AppConfig::Sonarr(arr));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("only supported by Radarr"));
+
         let mut spec = minimal_spec(AppType::Sonarr);
-        spec.gateway = Some(GatewaySpec {
-            enabled: false,
-            hosts: vec![],
+        spec.app_config = Some(AppConfig::Sonarr(ArrConfig {
+            monitoring: Some(MonitoringSpec::default()),
             ..Default::default()
-        });
+        }));
         let mut errors = Vec::new();
-        validate_gateway_hosts(&spec, &mut errors);
-        assert!(errors.is_empty());
+        validate_monitoring(&spec, &mut errors);
+        assert!(errors[0].contains("apiKeySecret"));
     }
 
+    // ── validate_auth ──
+
     #[test]
-    fn gateway_hosts_enabled_with_hosts() {
-        let mut spec = minimal_spec(AppType::Sonarr);
-        spec.gateway = Some(GatewaySpec {
-            enabled: true,
-            hosts: vec!["sonarr.example.com".into()],
+    fn auth_external_needs_no_secret() {
+        let mut spec = minimal_spec(AppType::Prowlarr);
+        spec.auth = Some(AuthSpec {
+            method: AuthMethod::External,
             ..Default::default()
         });
         let mut errors = Vec::new();
-        validate_gateway_hosts(&spec, &mut errors);
-        assert!(errors.is_empty());
+        validate_auth(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
     }
 
     #[test]
-    fn gateway_hosts_enabled_empty_hosts() {
+    fn auth_forms_requires_secret() {
         let mut spec = minimal_spec(AppType::Sonarr);
-        spec.gateway = Some(GatewaySpec {
-            enabled: true,
-            hosts: vec![],
+        spec.auth = Some(AuthSpec::default());
+        let mut errors = Vec::new();
+        validate_auth(&spec, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("auth.secretName"));
+    }
+
+    #[test]
+    fn auth_rejects_unsupported_app() {
+        let mut spec = minimal_spec(AppType::Jellyfin);
+        spec.auth = Some(AuthSpec {
+            secret_name: Some("media-admin".into()),
             ..Default::default()
         });
         let mut errors = Vec::new();
-        validate_gateway_hosts(&spec, &mut errors);
+        validate_auth(&spec, &mut errors);
         assert_eq!(errors.len(), 1);
-        assert!(errors[0].contains("non-empty"));
+        assert!(errors[0].contains("Jellyfin"));
     }
 
-    // ── validate_unique_volume_names ──
+    // ── validate_ui_settings ──
 
     #[test]
-    fn unique_volume_names_ok() {
-        let mut spec = minimal_spec(AppType::Sonarr);
-        spec.persistence = Some(PersistenceSpec {
-            volumes: vec![
-                PvcVolume {
-                    name: "config".into(),
-                    mount_path: "/config".into(),
-                    ..Default::default()
-                },
-                PvcVolume {
-                    name: "data".into(),
-                    mount_path: "/data".into(),
-                    ..Default::default()
-                },
-            ],
-            nfs_mounts: vec![],
+    fn ui_settings_valid() {
+        let mut spec = minimal_spec(AppType::Lidarr);
+        spec.api_key_secret = Some("lidarr-api-key".into());
+        spec.ui_settings = Some(UiSettingsSpec {
+            theme: Some(UiTheme::Dark),
+            ..Default::default()
         });
         let mut errors = Vec::new();
-        validate_unique_volume_names(&spec, &mut errors);
-        assert!(errors.is_empty());
+        validate_ui_settings(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
     }
 
     #[test]
-    fn unique_volume_names_duplicate() {
+    fn ui_settings_rejects_missing_api_key_and_other_apps() {
         let mut spec = minimal_spec(AppType::Sonarr);
-        spec.persistence = Some(PersistenceSpec {
-            volumes: vec![
-                PvcVolume {
-                    name: "config".into(),
-                    mount_path: "/config".into(),
-                    ..Default::default()
-                },
-                PvcVolume {
-                    name: "config".into(),
-                    mount_path: "/config2".into(),
-                    ..Default::default()
-                },
-            ],
-            nfs_mounts: vec![],
-        });
+        spec.ui_settings = Some(UiSettingsSpec::default());
         let mut errors = Vec::new();
-        validate_unique_volume_names(&spec, &mut errors);
-        assert_eq!(errors.len(), 1);
-        assert!(errors[0].contains("duplicate volume name"));
+        validate_ui_settings(&spec, &mut errors);
+        spec.app = AppType::Plex;
+        validate_ui_settings(&spec, &mut errors);
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors[0].contains("apiKeySecret"));
+        assert!(errors[1].contains("Plex"));
     }
 
+    // ── validate_queue_remediation ──
+
     #[test]
-    fn unique_volume_names_duplicate_nfs() {
-        let mut spec = minimal_spec(AppType::Sonarr);
-        spec.persistence = Some(PersistenceSpec {
-            volumes: vec![],
-            nfs_mounts: vec![
-                NfsMount {
-                    name: "media".into(),
-                    server: "nas".into(),
-                    path: "/media".into(),
-                    mount_path: "/media".into(),
-                    ..Default::default()
-                },
-                NfsMount {
-                    name: "media".into(),
-                    server: "nas".into(),
-                    path: "/media2".into(),
-                    mount_path: "/media2".into(),
-                    ..Default::default()
-                },
-            ],
+    fn queue_remediation_valid() {
+        let mut spec = minimal_spec(AppType::Radarr);
+        spec.api_key_secret = Some("radarr-api-key".into());
+        spec.queue_remediation = Some(QueueRemediationSpec {
+            enabled: true,
+            ..Default::default()
         });
         let mut errors = Vec::new();
-        validate_unique_volume_names(&spec, &mut errors);
-        assert_eq!(errors.len(), 1);
-        assert!(errors[0].contains("duplicate nfsMount name"));
+        validate_queue_remediation(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        // Disabled settings are not checked.
+        let mut spec = minimal_spec(AppType::Jellyfin);
+        spec.queue_remediation = Some(QueueRemediationSpec::default());
+        validate_queue_remediation(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
     }
 
-    // ── validate_transmission_settings ──
-
     #[test]
-    fn transmission_settings_no_config() {
-        let spec = minimal_spec(AppType::Sonarr);
+    fn queue_remediation_rejects_bad_settings() {
+        let mut spec = minimal_spec(AppType::Lidarr);
+        let mut rules = QueueRemediationSpec::default_rules();
+        rules.push(rules[0].clone());
+        spec.queue_remediation = Some(QueueRemediationSpec {
+            enabled: true,
+            interval_minutes: 0,
+            rules,
+            ..Default::default()
+        });
         let mut errors = Vec::new();
-        validate_transmission_settings(&spec, &mut errors);
-        assert!(errors.is_empty());
+        validate_queue_remediation(&spec, &mut errors);
+        assert_eq!(errors.len(), 4, "{errors:?}");
+        assert!(errors[0].contains("only supported for Sonarr and Radarr"));
+        assert!(errors[1].contains("requires apiKeySecret"));
+        assert!(errors[2].contains("intervalMinutes must be >= 1"));
+        assert!(errors[3].contains("duplicate rule for ImportFailed"));
     }
 
+    // ── validate_trash_sync ──
+
     #[test]
-    fn transmission_settings_no_managed_keys() {
-        let mut spec = minimal_spec(AppType::Transmission);
-        spec.app_config = Some(AppConfig::Transmission(TransmissionConfig {
-            settings: serde_json::json!({"speed-limit-down": 100}),
+    fn trash_sync_valid_and_invalid() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.api_key_secret = Some("sonarr-api-key".into());
+        spec.trash_sync = Some(TrashSyncSpec {
+            enabled: true,
+            templates: vec!["quality-definitions".into(), "unwanted".into()],
             ..Default::default()
-        }));
+        });
         let mut errors = Vec::new();
-        validate_transmission_settings(&spec, &mut errors);
-        assert!(errors.is_empty());
+        validate_trash_sync(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let mut spec = minimal_spec(AppType::Lidarr);
+        spec.trash_sync = Some(TrashSyncSpec {
+            enabled: true,
+            schedule: "daily".into(),
+            templates: vec!["anime".into()],
+            config_map: None,
+        });
+        validate_trash_sync(&spec, &mut errors);
+        assert_eq!(errors.len(), 4, "{errors:?}");
+        assert!(errors[0].contains("only supported for Sonarr and Radarr"));
+        assert!(errors[1].contains("requires apiKeySecret"));
+        assert!(errors[2].starts_with("trashSync.schedule"));
+        assert!(errors[3].contains("unknown template 'anime'"));
     }
 
+    // ── validate_requeue ──
+
     #[test]
-    fn transmission_settings_with_managed_key() {
-        let mut spec = minimal_spec(AppType::Transmission);
-        spec.app_config = Some(AppConfig::Transmission(TransmissionConfig {
-            settings: serde_json::json!({"rpc-password": "hunter2"}),
-            ..Default::default()
-        }));
+    fn requeue_bounds() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.requeue = Some(RequeueSpec::default());
         let mut errors = Vec::new();
-        validate_transmission_settings(&spec, &mut errors);
-        assert_eq!(errors.len(), 1);
-        assert!(errors[0].contains("rpc-password"));
+        validate_requeue(&spec, &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        spec.requeue = Some(RequeueSpec {
+            unsettled_seconds: 600,
+            settled_seconds: 2,
+        });
+        validate_requeue(&spec, &mut errors);
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors[0].contains("requeue.settledSeconds must be >= 5"));
+        assert!(errors[1].contains("must not exceed"));
     }
 
     // ── validate_backup_retention ──
@@ -1120,6 +3997,7 @@ mod tests {
                 name: "my-indexer".into(),
                 content: "yaml: here".into(),
             }],
+            ..Default::default()
         }));
         let mut errors = Vec::new();
         validate_indexer_definition_names(&spec, &mut errors);
@@ -1134,6 +4012,7 @@ mod tests {
                 name: "".into(),
                 content: "yaml: here".into(),
             }],
+            ..Default::default()
         }));
         let mut errors = Vec::new();
         validate_indexer_definition_names(&spec, &mut errors);
@@ -1149,6 +4028,7 @@ mod tests {
                 name: "my indexer!".into(),
                 content: "yaml: here".into(),
             }],
+            ..Default::default()
         }));
         let mut errors = Vec::new();
         validate_indexer_definition_names(&spec, &mut errors);
@@ -1205,6 +4085,50 @@ mod tests {
         assert!(errors[0].contains("restricted-rsync"));
     }
 
+    // ── validate_deletion ──
+
+    const USER: &str = "kubernetes-admin";
+    const OPERATOR: Option<&str> = Some("system:serviceaccount:servarr-system:servarr-operator");
+
+    fn deleted(annotations: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "kind": "ServarrApp",
+            "metadata": {"name": "sonarr", "annotations": annotations}
+        })
+    }
+
+    #[test]
+    fn deletion_blocked_by_protected_annotation() {
+        let object = deleted(serde_json::json!({"servarr.dev/protected": "true"}));
+        let errors = validate_deletion(Some(&object), USER, false, OPERATOR).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("ServarrApp sonarr is protected"));
+        assert!(
+            validate_deletion(Some(&deleted(serde_json::json!({}))), USER, false, OPERATOR).is_ok()
+        );
+    }
+
+    #[test]
+    fn deletion_protect_by_default_honours_opt_out() {
+        assert!(
+            validate_deletion(Some(&deleted(serde_json::json!({}))), USER, true, OPERATOR).is_err()
+        );
+        let object = deleted(serde_json::json!({"servarr.dev/protected": "false"}));
+        assert!(validate_deletion(Some(&object), USER, true, OPERATOR).is_ok());
+        assert!(validate_deletion(None, USER, true, OPERATOR).is_ok());
+    }
+
+    #[test]
+    fn deletion_by_operator_is_never_refused() {
+        let operator = OPERATOR.unwrap();
+        let child = deleted(serde_json::json!({}));
+        assert!(validate_deletion(Some(&child), operator, true, OPERATOR).is_ok());
+        let object = deleted(serde_json::json!({"servarr.dev/protected": "true"}));
+        assert!(validate_deletion(Some(&object), operator, false, OPERATOR).is_ok());
+        // Without a configured operator username nobody is exempt.
+        assert!(validate_deletion(Some(&child), operator, true, None).is_err());
+    }
+
     // ── validate_identity_immutable ──
 
     fn wrap_spec_as_object(spec: &ServarrAppSpec) -> serde_json::Value {
@@ -1257,4 +4181,197 @@ mod tests {
         assert!(errors[0].contains("immutable"));
         assert!(errors[0].contains("instance"));
     }
+
+    // --- unsupported overrides ---
+
+    #[test]
+    fn unsupported_overrides_rejected_when_disabled() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.unsupported_overrides = Some(servarr_crds::UnsupportedOverrides {
+            pod_spec: Some(serde_json::json!({"hostIPC": true})),
+        });
+        let mut errors = Vec::new();
+        validate_unsupported_overrides(&spec, false, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("ALLOW_UNSUPPORTED_OVERRIDES"));
+
+        let mut errors = Vec::new();
+        validate_unsupported_overrides(&spec, true, &mut errors);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn unsupported_overrides_pod_spec_must_be_mapping() {
+        let mut spec = minimal_spec(AppType::Sonarr);
+        spec.unsupported_overrides = Some(servarr_crds::UnsupportedOverrides {
+            pod_spec: Some(serde_json::json!(["nope"])),
+        });
+        let mut errors = Vec::new();
+        validate_unsupported_overrides(&spec, true, &mut errors);
+        assert_eq!(errors.len(), 1);
+    }
+
+    // --- MediaStack validation ---
+
+    #[test]
+    fn media_stack_duplicate_instances_rejected() {
+        let object = serde_json::json!({
+            "spec": {
+                "apps": [
+                    {"app": "Sonarr"},
+                    {"app": "Sonarr", "instance": "anime"},
+                    {"app": "Sonarr", "instance": "anime"}
+                ]
+            }
+        });
+        let errors = validate_media_stack(&object, "media").unwrap_err();
+        assert!(errors.contains(&"apps[2]: duplicate app=sonarr instance='anime'".to_string()));
+
+        let object = serde_json::json!({
+            "spec": {"apps": [{"app": "Sonarr"}, {"app": "Sonarr", "instance": "anime"}]}
+        });
+        assert!(validate_media_stack(&object, "media").is_ok());
+    }
+
+    #[test]
+    fn media_stack_nfs_exports() {
+        let object = serde_json::json!({
+            "spec": {
+                "nfs": {
+                    "externalServer": "nas.local",
+                    "exports": [
+                        {"name": "downloads", "size": "500Gi"},
+                        {"name": "tv"},
+                        {"name": "backups", "squash": "NoRootSquash", "anonUid": 1000},
+                        {"name": "backups", "mountPath": "backups"}
+                    ]
+                },
+                "apps": [{"app": "Sonarr"}]
+            }
+        });
+        let errors = validate_media_stack(&object, "media").unwrap_err();
+        assert_eq!(errors.len(), 5, "{errors:?}");
+        assert!(errors[0].contains("size needs the in-cluster NFS server"));
+        assert!(errors[1].contains("nfs.exports[tv]: name is used by the media mounts"));
+        assert!(errors[2].contains("anonUid and anonGid need squash"));
+        assert!(errors[3].contains("mountPath 'backups' must be an absolute path"));
+        assert!(errors[4].contains("nfs.exports[backups]: duplicate name"));
+    }
+
+    #[test]
+    fn media_stack_apps_in_other_namespaces() {
+        let object = serde_json::json!({
+            "spec": {
+                "postgres": {},
+                "apps": [
+                    {"app": "Sabnzbd", "namespace": "downloads"},
+                    {"app": "Sonarr", "namespace": "downloads"},
+                    {"app": "Radarr", "namespace": "media"},
+                    {"app": "Lidarr", "namespace": "Bad_NS", "database": {
+                        "host": "pg", "user": "lidarr", "passwordSecret": "pg",
+                        "passwordKey": "password", "mainDatabase": "lidarr"
+                    }}
+                ]
+            }
+        });
+        let errors = validate_media_stack(&object, "media").unwrap_err();
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(
+            errors[0].starts_with(
+                "apps[1]: namespace 'downloads' cannot use the stack's shared Postgres"
+            )
+        );
+        assert!(errors[1].contains("'Bad_NS' must be a lowercase DNS label"));
+    }
+
+    #[test]
+    fn media_stack_api_key_template_needs_name() {
+        let object = serde_json::json!({
+            "spec": {
+                "defaults": {"apiKeys": {"secretName": "shared-api-key"}},
+                "apps": [{"app": "Sonarr"}]
+            }
+        });
+        let errors = validate_media_stack(&object, "media").unwrap_err();
+        assert!(
+            errors[0].starts_with("defaults.apiKeys.secretName:"),
+            "{errors:?}"
+        );
+
+        let object = serde_json::json!({
+            "spec": {"defaults": {"apiKeys": {}}, "apps": [{"app": "Sonarr"}]}
+        });
+        assert!(validate_media_stack(&object, "media").is_ok());
+    }
+
+    #[test]
+    fn media_stack_apps_checked_as_servarr_apps() {
+        let object = serde_json::json!({
+            "metadata": {"name": "media"},
+            "spec": {
+                "apps": [
+                    {"app": "Sonarr", "backup": {"enabled": true, "schedule": "daily"}},
+                    {"app": "Radarr"}
+                ],
+                "nfs": {"backup": {"snapshot": {"schedule": "0 0 3 * * *"}}}
+            }
+        });
+        let errors = validate_media_stack(&object, "media").unwrap_err();
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors[0].starts_with("apps[0] (media-sonarr): backup.schedule:"));
+        assert!(errors[1].starts_with("nfs.backup.snapshot.schedule:"));
+    }
+
+    // --- Structured admission errors ---
+
+    #[test]
+    fn cause_field_maps_messages_to_spec_paths() {
+        let fields = servarr_app_fields();
+        assert_eq!(
+            cause_field("backup.schedule: invalid schedule 'daily'", fields).as_deref(),
+            Some("spec.backup.schedule")
+        );
+        assert_eq!(
+            cause_field(
+                "networkPolicyConfig.deniedCidrBlocks[1]: 'x' is not",
+                fields
+            )
+            .as_deref(),
+            Some("spec.networkPolicyConfig.deniedCidrBlocks[1]")
+        );
+        assert_eq!(
+            cause_field("ingress.host 'a' is also in gateway.hosts", fields).as_deref(),
+            Some("spec.ingress.host")
+        );
+        assert_eq!(cause_field("duplicate instance", fields), None);
+        assert_eq!(
+            cause_field("apps[2]: duplicate app=sonarr", media_stack_fields()).as_deref(),
+            Some("spec.apps[2]")
+        );
+    }
+
+    #[test]
+    fn rejection_lists_each_error_as_a_cause() {
+        let (_, Json(body)) = review_response(
+            "admission.k8s.io/v1".into(),
+            "AdmissionReview".into(),
+            "uid-1".into(),
+            Err(vec![
+                "backup.schedule: invalid schedule 'daily'".into(),
+                "duplicate instance".into(),
+            ]),
+            servarr_app_fields(),
+        );
+        let status = &body["response"]["status"];
+        assert_eq!(status["code"], 422);
+        assert_eq!(status["reason"], "Invalid");
+        assert_eq!(
+            status["message"],
+            "backup.schedule: invalid schedule 'daily'; duplicate instance"
+        );
+        let causes = status["details"]["causes"].as_array().unwrap();
+        assert_eq!(causes[0]["field"], "spec.backup.schedule");
+        assert_eq!(causes[0]["reason"], "FieldValueInvalid");
+        assert!(causes[1].get("field").is_none());
+    }
 }
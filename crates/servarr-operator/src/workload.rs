@@ -0,0 +1,473 @@
+//! The workload an app's pod runs under: a Deployment, or a StatefulSet
+//! with `workloadType: StatefulSet`.
+//!
+//! Switching the type deletes the old workload before the new one is
+//! applied, so the two pods never compete for a ReadWriteOnce volume. Data
+//! stays where it is: a volume whose claim already exists under the other
+//! type's naming is recorded in `status.volumeClaims` and mounted as is.
+
+use std::collections::BTreeMap;
+
+use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
+use k8s_openapi::api::core::v1::{PersistentVolumeClaim, PodTemplateSpec};
+use kube::api::{Api, DeleteParams, ListParams, ObjectMeta, Patch, PatchParams};
+use kube::{Client, Resource};
+use serde::Serialize;
+use servarr_crds::{ServarrApp, WorkloadType};
+use servarr_resources::{common, pvc, statefulset};
+
+use crate::controller::{RolloutState, rollout_state};
+
+pub fn workload_type(app: &ServarrApp) -> WorkloadType {
+    app.spec.workload_type.unwrap_or_default()
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum Workload {
+    Deployment(Deployment),
+    StatefulSet(StatefulSet),
+}
+
+impl Workload {
+    /// The workload `app` runs under, built from its `deployment`.
+    pub fn for_app(app: &ServarrApp, deployment: Deployment) -> Self {
+        match workload_type(app) {
+            WorkloadType::Deployment => Self::Deployment(deployment),
+            WorkloadType::StatefulSet => {
+                Self::StatefulSet(statefulset::from_deployment(app, deployment))
+            }
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Deployment(_) => "Deployment",
+            Self::StatefulSet(_) => "StatefulSet",
+        }
+    }
+
+    pub fn meta_mut(&mut self) -> &mut ObjectMeta {
+        match self {
+            Self::Deployment(d) => d.meta_mut(),
+            Self::StatefulSet(s) => s.meta_mut(),
+        }
+    }
+
+    pub fn template(&self) -> Option<&PodTemplateSpec> {
+        match self {
+            Self::Deployment(d) => d.spec.as_ref().map(|s| &s.template),
+            Self::StatefulSet(s) => s.spec.as_ref().map(|s| &s.template),
+        }
+    }
+
+    pub fn template_mut(&mut self) -> Option<&mut PodTemplateSpec> {
+        match self {
+            Self::Deployment(d) => d.spec.as_mut().map(|s| &mut s.template),
+            Self::StatefulSet(s) => s.spec.as_mut().map(|s| &mut s.template),
+        }
+    }
+
+    pub fn set_replicas(&mut self, replicas: i32) {
+        match self {
+            Self::Deployment(d) => {
+                if let Some(spec) = d.spec.as_mut() {
+                    spec.replicas = Some(replicas);
+                }
+            }
+            Self::StatefulSet(s) => {
+                if let Some(spec) = s.spec.as_mut() {
+                    spec.replicas = Some(replicas);
+                }
+            }
+        }
+    }
+
+    /// Server-side apply the workload.
+    pub async fn apply(
+        &self,
+        client: &Client,
+        ns: &str,
+        name: &str,
+        pp: &PatchParams,
+    ) -> Result<(), kube::Error> {
+        match self {
+            Self::Deployment(d) => {
+                Api::<Deployment>::namespaced(client.clone(), ns)
+                    .patch(name, pp, &Patch::Apply(d))
+                    .await?;
+            }
+            Self::StatefulSet(s) => {
+                Api::<StatefulSet>::namespaced(client.clone(), ns)
+                    .patch(name, pp, &Patch::Apply(s))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Server-side apply the Deployments of a Tdarr node app's extra nodes and
+/// delete the ones a lower `nodeCount` no longer asks for.
+pub async fn apply_workers(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    workers: &[Deployment],
+    pp: &PatchParams,
+) -> Result<(), kube::Error> {
+    let api = Api::<Deployment>::namespaced(client.clone(), ns);
+    let mut names = Vec::new();
+    for worker in workers {
+        let name = worker.metadata.name.clone().unwrap_or_default();
+        api.patch(&name, pp, &Patch::Apply(worker)).await?;
+        names.push(name);
+    }
+    let selector = format!(
+        "{}={}",
+        servarr_resources::deployment::WORKER_OF_LABEL,
+        common::app_name(app)
+    );
+    for stale in api
+        .list_metadata(&ListParams::default().labels(&selector))
+        .await?
+    {
+        if let Some(name) = stale.metadata.name
+            && !names.contains(&name)
+        {
+            api.delete(&name, &DeleteParams::default()).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether the workload last applied for `app` was of the other type,
+/// going by `status.generatedResources`.
+pub fn type_changed(app: &ServarrApp) -> bool {
+    let Some(generated) = app
+        .status
+        .as_ref()
+        .and_then(|s| s.generated_resources.as_ref())
+    else {
+        return false;
+    };
+    match workload_type(app) {
+        WorkloadType::Deployment => generated.stateful_set.is_some(),
+        WorkloadType::StatefulSet => generated.deployment.is_some(),
+    }
+}
+
+/// Delete the workload of the type `app` no longer uses. Returns its kind
+/// when there was one.
+pub async fn remove_stale(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+) -> Result<Option<&'static str>, kube::Error> {
+    if !type_changed(app) {
+        return Ok(None);
+    }
+    let name = common::app_name(app);
+    let deleted = match workload_type(app) {
+        WorkloadType::Deployment => Api::<StatefulSet>::namespaced(client.clone(), ns)
+            .delete(&name, &DeleteParams::default())
+            .await
+            .map(|_| "StatefulSet"),
+        WorkloadType::StatefulSet => Api::<Deployment>::namespaced(client.clone(), ns)
+            .delete(&name, &DeleteParams::default())
+            .await
+            .map(|_| "Deployment"),
+    };
+    match deleted {
+        Ok(kind) => Ok(Some(kind)),
+        Err(kube::Error::Api(e)) if e.code == 404 => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// After a workload type change, record in `claims` the existing PVC of
+/// every volume that would otherwise get a new, empty one: `{app}-{volume}`
+/// for a StatefulSet, `{volume}-{app}-0` for a Deployment. Returns the
+/// volumes adopted this way with their claims.
+pub async fn adopt_claims(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    claims: &mut BTreeMap<String, String>,
+) -> Result<Vec<(String, String)>, kube::Error> {
+    let mut adopted = Vec::new();
+    if !type_changed(app) {
+        return Ok(adopted);
+    }
+    let pvc_api = Api::<PersistentVolumeClaim>::namespaced(client.clone(), ns);
+    for vol in pvc::volumes(app) {
+        if claims.contains_key(&vol.name) {
+            continue;
+        }
+        let deployment_claim = common::child_name(app, &vol.name);
+        let existing = match workload_type(app) {
+            WorkloadType::StatefulSet => pvc_api
+                .get_opt(&deployment_claim)
+                .await?
+                .map(|_| deployment_claim),
+            WorkloadType::Deployment => {
+                let template_claim = statefulset::template_claim_name(app, &vol.name);
+                if pvc_api.get_opt(&deployment_claim).await?.is_some() {
+                    None
+                } else {
+                    pvc_api
+                        .get_opt(&template_claim)
+                        .await?
+                        .map(|_| template_claim)
+                }
+            }
+        };
+        if let Some(claim) = existing {
+            claims.insert(vol.name.clone(), claim.clone());
+            adopted.push((vol.name, claim));
+        }
+    }
+    Ok(adopted)
+}
+
+/// Ready replicas and rollout state of the app's workload.
+pub(crate) async fn observe(client: &Client, app: &ServarrApp, ns: &str) -> (i32, RolloutState) {
+    let name = common::app_name(app);
+    match workload_type(app) {
+        WorkloadType::Deployment => {
+            match Api::<Deployment>::namespaced(client.clone(), ns)
+                .get(&name)
+                .await
+            {
+                Ok(deploy) => (
+                    deploy
+                        .status
+                        .as_ref()
+                        .and_then(|s| s.ready_replicas)
+                        .unwrap_or(0),
+                    rollout_state(&deploy),
+                ),
+                Err(_) => (
+                    0,
+                    RolloutState::InProgress("Deployment not found".to_string()),
+                ),
+            }
+        }
+        WorkloadType::StatefulSet => {
+            match Api::<StatefulSet>::namespaced(client.clone(), ns)
+                .get(&name)
+                .await
+            {
+                Ok(sts) => (
+                    sts.status
+                        .as_ref()
+                        .and_then(|s| s.ready_replicas)
+                        .unwrap_or(0),
+                    statefulset_rollout_state(&sts),
+                ),
+                Err(_) => (
+                    0,
+                    RolloutState::InProgress("StatefulSet not found".to_string()),
+                ),
+            }
+        }
+    }
+}
+
+/// Where a StatefulSet rollout stands, following the kstatus rules for
+/// StatefulSets. Unlike Deployments they have no progress deadline, so a
+/// rollout never reports as stalled.
+pub(crate) fn statefulset_rollout_state(sts: &StatefulSet) -> RolloutState {
+    let Some(status) = sts.status.as_ref() else {
+        return RolloutState::InProgress("StatefulSet status not yet reported".to_string());
+    };
+    let generation = sts.metadata.generation.unwrap_or(0);
+    if status.observed_generation.unwrap_or(0) < generation {
+        return RolloutState::InProgress(
+            "StatefulSet controller has not observed the latest spec".to_string(),
+        );
+    }
+
+    let spec = sts.spec.as_ref();
+    let desired = spec.and_then(|s| s.replicas).unwrap_or(1);
+    let on_delete = spec
+        .and_then(|s| s.update_strategy.as_ref())
+        .and_then(|u| u.type_.as_deref())
+        == Some("OnDelete");
+    let updated = status.updated_replicas.unwrap_or(0);
+    let ready = status.ready_replicas.unwrap_or(0);
+    if !on_delete && updated < desired {
+        RolloutState::InProgress(format!("{updated} of {desired} replica(s) updated"))
+    } else if !on_delete && status.current_revision != status.update_revision {
+        RolloutState::InProgress("Waiting for the update revision to become current".to_string())
+    } else if ready < desired {
+        RolloutState::InProgress(format!("{ready} of {desired} replica(s) ready"))
+    } else {
+        RolloutState::Complete
+    }
+}
+
+/// Apply `patch` to the app's workload, whichever kind it is.
+pub async fn patch(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+    pp: &PatchParams,
+    patch: &Patch<serde_json::Value>,
+) -> Result<(), kube::Error> {
+    let name = common::app_name(app);
+    match workload_type(app) {
+        WorkloadType::Deployment => {
+            Api::<Deployment>::namespaced(client.clone(), ns)
+                .patch(&name, pp, patch)
+                .await?;
+        }
+        WorkloadType::StatefulSet => {
+            Api::<StatefulSet>::namespaced(client.clone(), ns)
+                .patch(&name, pp, patch)
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Ready replicas of the app's workload.
+pub async fn ready_replicas(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+) -> Result<i32, kube::Error> {
+    let name = common::app_name(app);
+    Ok(match workload_type(app) {
+        WorkloadType::Deployment => Api::<Deployment>::namespaced(client.clone(), ns)
+            .get(&name)
+            .await?
+            .status
+            .and_then(|s| s.ready_replicas),
+        WorkloadType::StatefulSet => Api::<StatefulSet>::namespaced(client.clone(), ns)
+            .get(&name)
+            .await?
+            .status
+            .and_then(|s| s.ready_replicas),
+    }
+    .unwrap_or(0))
+}
+
+/// `spec.replicas` of the app's workload, if it exists.
+pub async fn spec_replicas(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+) -> Result<Option<i32>, kube::Error> {
+    let name = common::app_name(app);
+    Ok(match workload_type(app) {
+        WorkloadType::Deployment => Api::<Deployment>::namespaced(client.clone(), ns)
+            .get_opt(&name)
+            .await?
+            .and_then(|d| d.spec)
+            .and_then(|s| s.replicas),
+        WorkloadType::StatefulSet => Api::<StatefulSet>::namespaced(client.clone(), ns)
+            .get_opt(&name)
+            .await?
+            .and_then(|s| s.spec)
+            .and_then(|s| s.replicas),
+    })
+}
+
+/// The pod template of the app's workload, if it exists.
+pub async fn pod_template(
+    client: &Client,
+    app: &ServarrApp,
+    ns: &str,
+) -> Result<Option<PodTemplateSpec>, kube::Error> {
+    let name = common::app_name(app);
+    Ok(match workload_type(app) {
+        WorkloadType::Deployment => Api::<Deployment>::namespaced(client.clone(), ns)
+            .get_opt(&name)
+            .await?
+            .and_then(|d| d.spec)
+            .map(|s| s.template),
+        WorkloadType::StatefulSet => Api::<StatefulSet>::namespaced(client.clone(), ns)
+            .get_opt(&name)
+            .await?
+            .and_then(|s| s.spec)
+            .map(|s| s.template),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn statefulset(generation: i64, status: serde_json::Value) -> StatefulSet {
+        serde_json::from_value(json!({
+            "metadata": { "name": "plex", "generation": generation },
+            "spec": {
+                "replicas": 1,
+                "serviceName": "plex",
+                "selector": {},
+                "template": {}
+            },
+            "status": status
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn statefulset_rollout_complete_when_revision_current_and_ready() {
+        let sts = statefulset(
+            2,
+            json!({
+                "observedGeneration": 2,
+                "replicas": 1,
+                "updatedReplicas": 1,
+                "readyReplicas": 1,
+                "currentRevision": "plex-abc",
+                "updateRevision": "plex-abc"
+            }),
+        );
+        assert_eq!(statefulset_rollout_state(&sts), RolloutState::Complete);
+    }
+
+    #[test]
+    fn statefulset_rollout_in_progress_until_updated_and_ready() {
+        let unobserved = statefulset(3, json!({ "observedGeneration": 2, "replicas": 1 }));
+        assert!(matches!(
+            statefulset_rollout_state(&unobserved),
+            RolloutState::InProgress(_)
+        ));
+
+        let old_revision = statefulset(
+            2,
+            json!({
+                "observedGeneration": 2,
+                "replicas": 1,
+                "updatedReplicas": 1,
+                "readyReplicas": 1,
+                "currentRevision": "plex-abc",
+                "updateRevision": "plex-def"
+            }),
+        );
+        assert!(matches!(
+            statefulset_rollout_state(&old_revision),
+            RolloutState::InProgress(_)
+        ));
+
+        let not_ready = statefulset(
+            2,
+            json!({
+                "observedGeneration": 2,
+                "replicas": 1,
+                "updatedReplicas": 1,
+                "readyReplicas": 0,
+                "currentRevision": "plex-def",
+                "updateRevision": "plex-def"
+            }),
+        );
+        assert_eq!(
+            statefulset_rollout_state(&not_ready),
+            RolloutState::InProgress("0 of 1 replica(s) ready".into())
+        );
+    }
+}
@@ -1,5 +1,6 @@
-//! Integration tests for the controller `reconcile` and `media_stack_controller` reconcile
-//! functions, using wiremock to mock the Kubernetes API server.
+//! Integration tests for the controller, `media_stack_controller`, and
+//! `app_set_controller` reconcile functions, using wiremock to mock the
+//! Kubernetes API server.
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -12,11 +13,12 @@ use kube::runtime::controller::Action;
 use kube::runtime::events::Reporter;
 use serde_json::json;
 use servarr_crds::{
-    AppType, MediaStack, MediaStackSpec, NfsServerSpec, ServarrApp, ServarrAppSpec, StackApp,
+    AppSetInstance, AppType, MediaStack, MediaStackSpec, NfsServerSpec, ServarrApp, ServarrAppSet,
+    ServarrAppSetSpec, ServarrAppSpec, ServarrAppTemplate, StackApp,
 };
 use servarr_operator::context::Context;
 use tokio::time::Duration;
-use wiremock::matchers::{method, path, path_regex};
+use wiremock::matchers::{body_partial_json, method, path, path_regex};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 // ---------------------------------------------------------------------------
@@ -65,6 +67,13 @@ fn test_context(client: kube::Client) -> Arc<Context> {
             instance: None,
         },
         watch_namespace: Some("test".into()),
+        drift_ignore_fields: Vec::new(),
+        allow_unsupported_overrides: false,
+        default_arch: None,
+        event_throttle: Default::default(),
+        network_policy_enforcement: Default::default(),
+        dry_run: false,
+        error_backoff: Default::default(),
     })
 }
 
@@ -110,25 +119,55 @@ fn make_media_stack(name: &str, ns: &str) -> MediaStack {
             network_policy: None,
             network_policy_config: None,
             app_config: None,
+            config_file: Default::default(),
+            rclone_mounts: Default::default(),
+            credentials: Default::default(),
+            queue_remediation: None,
+            trash_sync: None,
+            update_policy: None,
+            maintenance_window: None,
+            requeue: None,
+            workload_type: None,
+            auth: None,
+            ui_settings: None,
             api_key_secret: None,
+            api_key_secret_key: None,
+            api_key_rotation: None,
             api_health_check: None,
             backup: None,
             image_pull_secrets: None,
             pod_annotations: None,
             gpu: None,
+            autoscaling: None,
+            ingress: None,
             prowlarr_sync: None,
             overseerr_sync: None,
+            bazarr_sync: None,
+            download_client_sync: None,
             admin_credentials: None,
+            database: None,
+            drift_ignore_fields: Vec::new(),
+            patches: Vec::new(),
+            unsupported_overrides: None,
             split4k: None,
             split4k_overrides: None,
+            namespace: None,
+            canary: None,
+            lifecycle: None,
+            extra_containers: vec![],
+            extra_init_containers: vec![],
         }],
         nfs: None,
+        postgres: None,
+        apply_parallelism: None,
+        spread_policy: None,
     };
     let mut stack = MediaStack::new(name, spec);
     stack.metadata.namespace = Some(ns.into());
     stack.metadata.uid = Some("stack-uid-12345".into());
     stack.metadata.resource_version = Some("1".into());
     stack.metadata.generation = Some(1);
+    stack.metadata.finalizers = Some(vec!["servarr.dev/ordered-teardown".into()]);
     stack
 }
 
@@ -162,6 +201,7 @@ fn deployment_response(name: &str, ns: &str) -> serde_json::Value {
         "status": {
             "readyReplicas": 1,
             "replicas": 1,
+            "updatedReplicas": 1,
             "availableReplicas": 1
         }
     })
@@ -267,6 +307,22 @@ fn mediastack_response(name: &str, ns: &str) -> serde_json::Value {
     })
 }
 
+/// Accept the stack's `{stack}-connections` ConfigMap apply.
+async fn mount_connections_mock(mock_server: &MockServer, ns: &str) {
+    Mock::given(method("PATCH"))
+        .and(path_regex(format!(
+            "/api/v1/namespaces/{ns}/configmaps/.*-connections"
+        )))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "metadata": { "name": "connections", "namespace": ns }
+        })))
+        .named("patch-connections-cm")
+        .mount(mock_server)
+        .await;
+}
+
 // ---------------------------------------------------------------------------
 // Test 1: Basic Sonarr reconcile succeeds
 // ---------------------------------------------------------------------------
@@ -542,11 +598,11 @@ async fn test_sonarr_reconcile_network_policy_disabled() {
 }
 
 // ---------------------------------------------------------------------------
-// Test 3: error_policy returns requeue(60s)
+// Test 3: error_policy backs off on repeated failures
 // ---------------------------------------------------------------------------
 
 #[tokio::test]
-async fn test_error_policy_returns_requeue_60s() {
+async fn test_error_policy_backs_off() {
     let mock_server = MockServer::start().await;
     let client = mock_client(&mock_server.uri()).await;
     let ctx = test_context(client);
@@ -564,13 +620,16 @@ async fn test_error_policy_returns_requeue_60s() {
         serde_json::from_str::<serde_json::Value>("invalid").unwrap_err(),
     );
 
-    let action = servarr_operator::controller::error_policy(app, &error, ctx);
+    let first = servarr_operator::controller::error_policy(app.clone(), &error, ctx.clone());
+    let second = servarr_operator::controller::error_policy(app, &error, ctx.clone());
 
-    assert_eq!(
-        action,
-        Action::requeue(Duration::from_secs(60)),
-        "error_policy should requeue after 60 seconds"
-    );
+    // The first retry comes within the 5s base backoff; the second waits
+    // at least the jittered double of it.
+    assert_ne!(first, Action::requeue(Duration::from_secs(60)));
+    assert_ne!(first, second);
+    assert_eq!(ctx.error_backoff.failures("test/test-sonarr"), 2);
+    ctx.error_backoff.succeeded("test/test-sonarr");
+    assert_eq!(ctx.error_backoff.failures("test/test-sonarr"), 0);
 }
 
 // ---------------------------------------------------------------------------
@@ -655,6 +714,27 @@ async fn test_media_stack_reconcile_basic() {
         .mount(&mock_server)
         .await;
 
+    // PATCH connection info ConfigMap (SSA)
+    Mock::given(method("PATCH"))
+        .and(path(
+            "/api/v1/namespaces/test/configmaps/my-stack-connections",
+        ))
+        .and(body_partial_json(json!({
+            "data": {
+                "my-stack-sonarr.type": "sonarr",
+                "my-stack-sonarr.url": "http://my-stack-sonarr.test.svc:8989"
+            }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "metadata": { "name": "my-stack-connections", "namespace": "test" }
+        })))
+        .expect(1)
+        .named("patch-connections-cm")
+        .mount(&mock_server)
+        .await;
+
     let result = servarr_operator::media_stack_controller::reconcile(stack, ctx).await;
 
     assert!(
@@ -671,11 +751,11 @@ async fn test_media_stack_reconcile_basic() {
 }
 
 // ---------------------------------------------------------------------------
-// Test 5: MediaStack error_policy returns requeue(60s)
+// Test 5: MediaStack error_policy backs off on repeated failures
 // ---------------------------------------------------------------------------
 
 #[tokio::test]
-async fn test_media_stack_error_policy_returns_requeue_60s() {
+async fn test_media_stack_error_policy_backs_off() {
     let mock_server = MockServer::start().await;
     let client = mock_client(&mock_server.uri()).await;
     let ctx = test_context(client);
@@ -686,13 +766,10 @@ async fn test_media_stack_error_policy_returns_requeue_60s() {
         serde_json::from_str::<serde_json::Value>("invalid").unwrap_err(),
     );
 
-    let action = servarr_operator::media_stack_controller::error_policy(stack, &error, ctx);
+    servarr_operator::media_stack_controller::error_policy(stack.clone(), &error, ctx.clone());
+    servarr_operator::media_stack_controller::error_policy(stack, &error, ctx.clone());
 
-    assert_eq!(
-        action,
-        Action::requeue(Duration::from_secs(60)),
-        "media_stack error_policy should requeue after 60 seconds"
-    );
+    assert_eq!(ctx.error_backoff.failures("test/my-stack"), 2);
 }
 
 // ---------------------------------------------------------------------------
@@ -772,6 +849,8 @@ async fn test_media_stack_reconcile_child_not_ready() {
         .mount(&mock_server)
         .await;
 
+    mount_connections_mock(&mock_server, "test").await;
+
     let result = servarr_operator::media_stack_controller::reconcile(stack, ctx).await;
 
     assert!(
@@ -812,17 +891,43 @@ fn make_multi_app_stack(name: &str, ns: &str) -> MediaStack {
                 network_policy: None,
                 network_policy_config: None,
                 app_config: None,
+                config_file: Default::default(),
+                rclone_mounts: Default::default(),
+                credentials: Default::default(),
+                queue_remediation: None,
+                trash_sync: None,
+                update_policy: None,
+                maintenance_window: None,
+                requeue: None,
+                workload_type: None,
+                auth: None,
+                ui_settings: None,
                 api_key_secret: None,
+                api_key_secret_key: None,
+                api_key_rotation: None,
                 api_health_check: None,
                 backup: None,
                 image_pull_secrets: None,
                 pod_annotations: None,
                 gpu: None,
+                autoscaling: None,
+                ingress: None,
                 prowlarr_sync: None,
                 overseerr_sync: None,
+                bazarr_sync: None,
+                download_client_sync: None,
                 admin_credentials: None,
+                database: None,
+                drift_ignore_fields: Vec::new(),
+                patches: Vec::new(),
+                unsupported_overrides: None,
                 split4k: None,
                 split4k_overrides: None,
+                namespace: None,
+                canary: None,
+                lifecycle: None,
+                extra_containers: vec![],
+                extra_init_containers: vec![],
             },
             StackApp {
                 app: AppType::Radarr,
@@ -842,17 +947,43 @@ fn make_multi_app_stack(name: &str, ns: &str) -> MediaStack {
                 network_policy: None,
                 network_policy_config: None,
                 app_config: None,
+                config_file: Default::default(),
+                rclone_mounts: Default::default(),
+                credentials: Default::default(),
+                queue_remediation: None,
+                trash_sync: None,
+                update_policy: None,
+                maintenance_window: None,
+                requeue: None,
+                workload_type: None,
+                auth: None,
+                ui_settings: None,
                 api_key_secret: None,
+                api_key_secret_key: None,
+                api_key_rotation: None,
                 api_health_check: None,
                 backup: None,
                 image_pull_secrets: None,
                 pod_annotations: None,
                 gpu: None,
+                autoscaling: None,
+                ingress: None,
                 prowlarr_sync: None,
                 overseerr_sync: None,
+                bazarr_sync: None,
+                download_client_sync: None,
                 admin_credentials: None,
+                database: None,
+                drift_ignore_fields: Vec::new(),
+                patches: Vec::new(),
+                unsupported_overrides: None,
                 split4k: None,
                 split4k_overrides: None,
+                namespace: None,
+                canary: None,
+                lifecycle: None,
+                extra_containers: vec![],
+                extra_init_containers: vec![],
             },
             StackApp {
                 app: AppType::Transmission,
@@ -872,26 +1003,56 @@ fn make_multi_app_stack(name: &str, ns: &str) -> MediaStack {
                 network_policy: None,
                 network_policy_config: None,
                 app_config: None,
+                config_file: Default::default(),
+                rclone_mounts: Default::default(),
+                credentials: Default::default(),
+                queue_remediation: None,
+                trash_sync: None,
+                update_policy: None,
+                maintenance_window: None,
+                requeue: None,
+                workload_type: None,
+                auth: None,
+                ui_settings: None,
                 api_key_secret: None,
+                api_key_secret_key: None,
+                api_key_rotation: None,
                 api_health_check: None,
                 backup: None,
                 image_pull_secrets: None,
                 pod_annotations: None,
                 gpu: None,
+                autoscaling: None,
+                ingress: None,
                 prowlarr_sync: None,
                 overseerr_sync: None,
+                bazarr_sync: None,
+                download_client_sync: None,
                 admin_credentials: None,
+                database: None,
+                drift_ignore_fields: Vec::new(),
+                patches: Vec::new(),
+                unsupported_overrides: None,
                 split4k: None,
                 split4k_overrides: None,
+                namespace: None,
+                canary: None,
+                lifecycle: None,
+                extra_containers: vec![],
+                extra_init_containers: vec![],
             },
         ],
         nfs: None,
+        postgres: None,
+        apply_parallelism: None,
+        spread_policy: None,
     };
     let mut stack = MediaStack::new(name, spec);
     stack.metadata.namespace = Some(ns.into());
     stack.metadata.uid = Some("stack-uid-multi".into());
     stack.metadata.resource_version = Some("1".into());
     stack.metadata.generation = Some(1);
+    stack.metadata.finalizers = Some(vec!["servarr.dev/ordered-teardown".into()]);
     stack
 }
 
@@ -979,6 +1140,8 @@ async fn test_media_stack_reconcile_multi_app() {
         .mount(&mock_server)
         .await;
 
+    mount_connections_mock(&mock_server, "test").await;
+
     let result = servarr_operator::media_stack_controller::reconcile(stack, ctx).await;
 
     assert!(
@@ -1026,17 +1189,43 @@ async fn test_media_stack_reconcile_disabled_app() {
                 network_policy: None,
                 network_policy_config: None,
                 app_config: None,
+                config_file: Default::default(),
+                rclone_mounts: Default::default(),
+                credentials: Default::default(),
+                queue_remediation: None,
+                trash_sync: None,
+                update_policy: None,
+                maintenance_window: None,
+                requeue: None,
+                workload_type: None,
+                auth: None,
+                ui_settings: None,
                 api_key_secret: None,
+                api_key_secret_key: None,
+                api_key_rotation: None,
                 api_health_check: None,
                 backup: None,
                 image_pull_secrets: None,
                 pod_annotations: None,
                 gpu: None,
+                autoscaling: None,
+                ingress: None,
                 prowlarr_sync: None,
                 overseerr_sync: None,
+                bazarr_sync: None,
+                download_client_sync: None,
                 admin_credentials: None,
+                database: None,
+                drift_ignore_fields: Vec::new(),
+                patches: Vec::new(),
+                unsupported_overrides: None,
                 split4k: None,
                 split4k_overrides: None,
+                namespace: None,
+                canary: None,
+                lifecycle: None,
+                extra_containers: vec![],
+                extra_init_containers: vec![],
             },
             StackApp {
                 app: AppType::Radarr,
@@ -1056,26 +1245,56 @@ async fn test_media_stack_reconcile_disabled_app() {
                 network_policy: None,
                 network_policy_config: None,
                 app_config: None,
+                config_file: Default::default(),
+                rclone_mounts: Default::default(),
+                credentials: Default::default(),
+                queue_remediation: None,
+                trash_sync: None,
+                update_policy: None,
+                maintenance_window: None,
+                requeue: None,
+                workload_type: None,
+                auth: None,
+                ui_settings: None,
                 api_key_secret: None,
+                api_key_secret_key: None,
+                api_key_rotation: None,
                 api_health_check: None,
                 backup: None,
                 image_pull_secrets: None,
                 pod_annotations: None,
                 gpu: None,
+                autoscaling: None,
+                ingress: None,
                 prowlarr_sync: None,
                 overseerr_sync: None,
+                bazarr_sync: None,
+                download_client_sync: None,
                 admin_credentials: None,
+                database: None,
+                drift_ignore_fields: Vec::new(),
+                patches: Vec::new(),
+                unsupported_overrides: None,
                 split4k: None,
                 split4k_overrides: None,
+                namespace: None,
+                canary: None,
+                lifecycle: None,
+                extra_containers: vec![],
+                extra_init_containers: vec![],
             },
         ],
         nfs: None,
+        postgres: None,
+        apply_parallelism: None,
+        spread_policy: None,
     };
     let mut stack = MediaStack::new("disabled-test", spec);
     stack.metadata.namespace = Some("test".into());
     stack.metadata.uid = Some("stack-uid-disabled".into());
     stack.metadata.resource_version = Some("1".into());
     stack.metadata.generation = Some(1);
+    stack.metadata.finalizers = Some(vec!["servarr.dev/ordered-teardown".into()]);
     let stack = Arc::new(stack);
 
     // Only the Sonarr child should be patched. We mock the Sonarr child endpoints.
@@ -1158,6 +1377,8 @@ async fn test_media_stack_reconcile_disabled_app() {
         .mount(&mock_server)
         .await;
 
+    mount_connections_mock(&mock_server, "test").await;
+
     let result = servarr_operator::media_stack_controller::reconcile(stack, ctx).await;
 
     assert!(
@@ -1298,6 +1519,8 @@ async fn test_media_stack_reconcile_orphan_cleanup() {
         .mount(&mock_server)
         .await;
 
+    mount_connections_mock(&mock_server, "test").await;
+
     let result = servarr_operator::media_stack_controller::reconcile(stack, ctx).await;
 
     assert!(
@@ -1313,6 +1536,127 @@ async fn test_media_stack_reconcile_orphan_cleanup() {
     // The expect(1) on the DELETE mock will verify the orphan was deleted
 }
 
+// ---------------------------------------------------------------------------
+// Test: deleted MediaStack tears down children in dependency order
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_media_stack_deletion_tears_down_media_managers_before_indexers() {
+    let mock_server = MockServer::start().await;
+    let client = mock_client(&mock_server.uri()).await;
+    let ctx = test_context(client);
+
+    let mut stack = make_media_stack("gone-stack", "test");
+    stack.metadata.deletion_timestamp =
+        serde_json::from_value(json!("2024-01-01T00:00:00Z")).unwrap();
+    let stack = Arc::new(stack);
+
+    // Remaining children: Sonarr (media manager) and Prowlarr (indexer)
+    Mock::given(method("GET"))
+        .and(path(
+            "/apis/servarr.dev/v1alpha1/namespaces/test/servarrapps",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "apiVersion": "servarr.dev/v1alpha1",
+            "kind": "ServarrAppList",
+            "metadata": {},
+            "items": [
+                {
+                    "apiVersion": "servarr.dev/v1alpha1",
+                    "kind": "ServarrApp",
+                    "metadata": {
+                        "name": "gone-stack-sonarr",
+                        "namespace": "test",
+                        "uid": "sa-uid-sonarr",
+                        "resourceVersion": "200"
+                    },
+                    "spec": { "app": "Sonarr" }
+                },
+                {
+                    "apiVersion": "servarr.dev/v1alpha1",
+                    "kind": "ServarrApp",
+                    "metadata": {
+                        "name": "gone-stack-prowlarr",
+                        "namespace": "test",
+                        "uid": "sa-uid-prowlarr",
+                        "resourceVersion": "201"
+                    },
+                    "spec": { "app": "Prowlarr" }
+                }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    // No NFS/Postgres StatefulSets
+    Mock::given(method("GET"))
+        .and(path_regex(
+            r"^/apis/apps/v1/namespaces/test/statefulsets/.*$",
+        ))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "kind": "Status",
+            "apiVersion": "v1",
+            "status": "Failure",
+            "reason": "NotFound",
+            "code": 404
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path(
+            "/apis/servarr.dev/v1alpha1/namespaces/test/servarrapps/gone-stack-sonarr",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(servarrapp_response("gone-stack-sonarr", "test")),
+        )
+        .expect(1)
+        .named("delete-sonarr")
+        .mount(&mock_server)
+        .await;
+
+    // Prowlarr must wait until Sonarr is gone
+    Mock::given(method("DELETE"))
+        .and(path(
+            "/apis/servarr.dev/v1alpha1/namespaces/test/servarrapps/gone-stack-prowlarr",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(servarrapp_response("gone-stack-prowlarr", "test")),
+        )
+        .expect(0)
+        .named("delete-prowlarr")
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path(
+            "/apis/servarr.dev/v1alpha1/namespaces/test/mediastacks/gone-stack/status",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(mediastack_response("gone-stack", "test")),
+        )
+        .expect(1)
+        .named("patch-stack-status")
+        .mount(&mock_server)
+        .await;
+
+    mount_connections_mock(&mock_server, "test").await;
+
+    let result = servarr_operator::media_stack_controller::reconcile(stack, ctx).await;
+
+    assert!(
+        result.is_ok(),
+        "teardown reconcile should succeed, got: {result:?}"
+    );
+    assert_eq!(
+        result.unwrap(),
+        Action::requeue(Duration::from_secs(10)),
+        "teardown in progress should requeue after 10 seconds"
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Helper: DynamicObject response for Gateway API resources
 // ---------------------------------------------------------------------------
@@ -1679,6 +2023,7 @@ async fn test_ssh_bastion_reconcile() {
                 }),
                 shell: None,
                 public_keys: "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5 test@example".into(),
+                sftp_chroot: None,
             }],
             ..Default::default()
         })),
@@ -1746,6 +2091,7 @@ async fn test_sabnzbd_whitelist_and_tar_unpack() {
         app_config: Some(AppConfig::Sabnzbd(SabnzbdConfig {
             host_whitelist: vec!["sabnzbd.example.com".into(), "sab.local".into()],
             tar_unpack: true,
+            credentials_secret_ref: None,
         })),
         ..Default::default()
     };
@@ -1813,6 +2159,7 @@ async fn test_prowlarr_custom_definitions() {
                 name: "my-tracker".into(),
                 content: "id: my-tracker\nname: My Tracker\n".into(),
             }],
+            ..Default::default()
         })),
         ..Default::default()
     };
@@ -1839,6 +2186,33 @@ async fn test_prowlarr_custom_definitions() {
         .mount(&mock_server)
         .await;
 
+    // GET the live definitions ConfigMap, which still has the old content
+    let mut live = configmap_response("test-prowlarr-prowlarr-definitions", "test");
+    live["data"] = json!({"my-tracker.yml": "id: my-tracker\nname: Old Name\n"});
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v1/namespaces/test/configmaps/test-prowlarr-prowlarr-definitions",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(live))
+        .named("get-prowlarr-defs-cm")
+        .expect(1..)
+        .mount(&mock_server)
+        .await;
+
+    // The changed definition is announced before the pod restarts
+    Mock::given(method("POST"))
+        .and(path("/apis/events.k8s.io/v1/namespaces/test/events"))
+        .and(body_partial_json(json!({
+            "reason": "DefinitionsChanged",
+            "note": "Custom indexer definitions changed (my-tracker.yml); restarting Prowlarr to load them"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(event_response()))
+        .with_priority(1)
+        .named("definitions-changed-event")
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
     let result = servarr_operator::controller::reconcile(app, ctx).await;
     assert!(
         result.is_ok(),
@@ -2070,6 +2444,7 @@ async fn test_deployment_drift_detection() {
         "status": {
             "readyReplicas": 1,
             "replicas": 1,
+            "updatedReplicas": 1,
             "availableReplicas": 1
         }
     });
@@ -2257,25 +2632,55 @@ fn make_nfs_stack(name: &str, ns: &str, nfs: Option<NfsServerSpec>) -> MediaStac
             network_policy: None,
             network_policy_config: None,
             app_config: None,
+            config_file: Default::default(),
+            rclone_mounts: Default::default(),
+            credentials: Default::default(),
+            queue_remediation: None,
+            trash_sync: None,
+            update_policy: None,
+            maintenance_window: None,
+            requeue: None,
+            workload_type: None,
+            auth: None,
+            ui_settings: None,
             api_key_secret: None,
+            api_key_secret_key: None,
+            api_key_rotation: None,
             api_health_check: None,
             backup: None,
             image_pull_secrets: None,
             pod_annotations: None,
             gpu: None,
+            autoscaling: None,
+            ingress: None,
             prowlarr_sync: None,
             overseerr_sync: None,
+            bazarr_sync: None,
+            download_client_sync: None,
             admin_credentials: None,
+            database: None,
+            drift_ignore_fields: Vec::new(),
+            patches: Vec::new(),
+            unsupported_overrides: None,
             split4k: None,
             split4k_overrides: None,
+            namespace: None,
+            canary: None,
+            lifecycle: None,
+            extra_containers: vec![],
+            extra_init_containers: vec![],
         }],
         nfs,
+        postgres: None,
+        apply_parallelism: None,
+        spread_policy: None,
     };
     let mut stack = MediaStack::new(name, spec);
     stack.metadata.namespace = Some(ns.into());
     stack.metadata.uid = Some("nfs-stack-uid".into());
     stack.metadata.resource_version = Some("1".into());
     stack.metadata.generation = Some(1);
+    stack.metadata.finalizers = Some(vec!["servarr.dev/ordered-teardown".into()]);
     stack
 }
 
@@ -2299,6 +2704,7 @@ fn statefulset_response(name: &str, ns: &str) -> serde_json::Value {
 
 /// Mount common MediaStack child-app mocks (ServarrApp PATCH/GET, list, status).
 async fn mount_child_app_mocks(mock_server: &MockServer, stack_name: &str, ns: &str) {
+    mount_connections_mock(mock_server, ns).await;
     let pattern = format!("/apis/servarr.dev/v1alpha1/namespaces/{ns}/servarrapps/{stack_name}-.*");
     Mock::given(method("PATCH"))
         .and(path_regex(pattern.as_str()))
@@ -2500,3 +2906,422 @@ async fn test_media_stack_nfs_external_does_not_create_in_cluster_resources() {
     );
     // _ss_mock drop verifies expect(0)
 }
+
+// ---------------------------------------------------------------------------
+// ServarrAppSet tests
+// ---------------------------------------------------------------------------
+
+fn make_app_set(name: &str, ns: &str, instances: &[&str]) -> ServarrAppSet {
+    let spec = ServarrAppSetSpec {
+        template: ServarrAppTemplate {
+            labels: Default::default(),
+            spec: ServarrAppSpec {
+                app: AppType::Overseerr,
+                ..Default::default()
+            },
+        },
+        instances: instances
+            .iter()
+            .map(|i| AppSetInstance {
+                name: i.to_string(),
+                parameters: Default::default(),
+            })
+            .collect(),
+    };
+    let mut set = ServarrAppSet::new(name, spec);
+    set.metadata.namespace = Some(ns.into());
+    set.metadata.uid = Some("set-uid-1".into());
+    set.metadata.resource_version = Some("1".into());
+    set.metadata.generation = Some(1);
+    set
+}
+
+/// ServarrApp JSON generated by the set `family`, optionally owned by it.
+fn app_set_child(name: &str, owned: bool) -> serde_json::Value {
+    let mut app = json!({
+        "apiVersion": "servarr.dev/v1alpha1",
+        "kind": "ServarrApp",
+        "metadata": {
+            "name": name,
+            "namespace": "test",
+            "uid": format!("{name}-uid"),
+            "resourceVersion": "200",
+            "labels": { "servarr.dev/app-set": "family" }
+        },
+        "spec": { "app": "Overseerr" }
+    });
+    if owned {
+        app["metadata"]["ownerReferences"] = json!([{
+            "apiVersion": "servarr.dev/v1alpha1",
+            "kind": "ServarrAppSet",
+            "name": "family",
+            "uid": "set-uid-1",
+            "controller": true
+        }]);
+    }
+    app
+}
+
+#[tokio::test]
+async fn test_app_set_reconcile_creates_instances_and_removes_orphans() {
+    let mock_server = MockServer::start().await;
+    let client = mock_client(&mock_server.uri()).await;
+    let ctx = test_context(client);
+
+    let set = Arc::new(make_app_set("family", "test", &["alice", "bob"]));
+
+    // alice doesn't exist yet; bob was created by hand
+    Mock::given(method("GET"))
+        .and(path(
+            "/apis/servarr.dev/v1alpha1/namespaces/test/servarrapps/family-alice",
+        ))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "apiVersion": "v1",
+            "kind": "Status",
+            "metadata": {},
+            "status": "Failure",
+            "message": "not found",
+            "reason": "NotFound",
+            "code": 404
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/apis/servarr.dev/v1alpha1/namespaces/test/servarrapps/family-bob",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(app_set_child("family-bob", false)))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path(
+            "/apis/servarr.dev/v1alpha1/namespaces/test/servarrapps/family-alice",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json({
+            let mut resp = app_set_child("family-alice", true);
+            resp["status"] = json!({ "ready": true });
+            resp
+        }))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(
+            "/apis/servarr.dev/v1alpha1/namespaces/test/servarrapps/family-bob",
+        ))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .named("no-takeover-of-unowned-app")
+        .mount(&mock_server)
+        .await;
+
+    // Listing by label finds a removed instance and a look-alike not owned
+    // by the set
+    Mock::given(method("GET"))
+        .and(path(
+            "/apis/servarr.dev/v1alpha1/namespaces/test/servarrapps",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "apiVersion": "servarr.dev/v1alpha1",
+            "kind": "ServarrAppList",
+            "metadata": {},
+            "items": [
+                app_set_child("family-alice", true),
+                app_set_child("family-carol", true),
+                app_set_child("family-bob", false),
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path(
+            "/apis/servarr.dev/v1alpha1/namespaces/test/servarrapps/family-carol",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(app_set_child("family-carol", true)))
+        .expect(1)
+        .named("delete-removed-instance")
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path(
+            "/apis/servarr.dev/v1alpha1/namespaces/test/servarrapps/family-bob",
+        ))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .named("keep-unowned-app")
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path(
+            "/apis/servarr.dev/v1alpha1/namespaces/test/servarrappsets/family/status",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "apiVersion": "servarr.dev/v1alpha1",
+            "kind": "ServarrAppSet",
+            "metadata": {
+                "name": "family",
+                "namespace": "test",
+                "uid": "set-uid-1",
+                "resourceVersion": "400"
+            },
+            "spec": { "template": { "spec": { "app": "Overseerr" } } }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = servarr_operator::app_set_controller::reconcile(set, ctx).await;
+    assert_eq!(
+        result.unwrap(),
+        Action::requeue(Duration::from_secs(30)),
+        "a blocked instance keeps the set on the short requeue"
+    );
+
+    let status_patch = mock_server
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|r| r.url.path().ends_with("/servarrappsets/family/status"))
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&status_patch.body).unwrap();
+    let status = &body["status"];
+    assert_eq!(status["replicas"], 2);
+    assert_eq!(status["readyReplicas"], 1);
+    assert_eq!(status["selector"], "servarr.dev/app-set=family");
+    assert!(
+        status["instances"][1]["message"]
+            .as_str()
+            .unwrap()
+            .contains("not managed by this set")
+    );
+    let ready = status["conditions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["type"] == "Ready")
+        .unwrap();
+    assert_eq!(ready["reason"], "NameConflict");
+}
+
+// ---------------------------------------------------------------------------
+// Dry run: only status.wouldChange is written
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_dry_run_reports_would_change_without_applying() {
+    let mock_server = MockServer::start().await;
+    let client = mock_client(&mock_server.uri()).await;
+    let mut ctx = Arc::into_inner(test_context(client)).unwrap();
+    ctx.dry_run = true;
+    let ctx = Arc::new(ctx);
+
+    let app = Arc::new(make_sonarr_app("test-sonarr", "test"));
+
+    // The Deployment already matches; everything else is missing.
+    let mut deployment = deployment_response("test-sonarr", "test");
+    let objects =
+        servarr_operator::render::render(&app, &servarr_operator::render::Options::default())
+            .unwrap();
+    deployment["metadata"] = objects[0]["metadata"].clone();
+    deployment["spec"] = objects[0]["spec"].clone();
+    Mock::given(method("GET"))
+        .and(path(
+            "/apis/apps/v1/namespaces/test/deployments/test-sonarr",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(deployment))
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"/namespaces/test/"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "apiVersion": "v1",
+            "kind": "Status",
+            "metadata": {},
+            "status": "Failure",
+            "message": "not found",
+            "reason": "NotFound",
+            "code": 404
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path(
+            "/apis/servarr.dev/v1alpha1/namespaces/test/servarrapps/test-sonarr/status",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(servarrapp_response("test-sonarr", "test")),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/apis/events.k8s.io/v1/namespaces/test/events"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(event_response()))
+        .mount(&mock_server)
+        .await;
+
+    let result = servarr_operator::controller::reconcile(app, ctx).await;
+    assert_eq!(result.unwrap(), Action::requeue(Duration::from_secs(300)));
+
+    let requests = mock_server.received_requests().await.unwrap();
+    let writes: Vec<_> = requests
+        .iter()
+        .filter(|r| r.method.as_str() != "GET" && !r.url.path().ends_with("/events"))
+        .collect();
+    assert_eq!(writes.len(), 1, "only the status is patched: {writes:?}");
+    let body: serde_json::Value = serde_json::from_slice(&writes[0].body).unwrap();
+    let would_change: Vec<&str> = body["status"]["wouldChange"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c.as_str().unwrap())
+        .collect();
+    assert!(
+        would_change.contains(&"create Service/test-sonarr"),
+        "{would_change:?}"
+    );
+    assert!(
+        !would_change.iter().any(|c| c.contains("Deployment")),
+        "{would_change:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_media_stack_dry_run_reports_would_change_without_applying() {
+    let mock_server = MockServer::start().await;
+    let client = mock_client(&mock_server.uri()).await;
+    let mut ctx = Arc::into_inner(test_context(client)).unwrap();
+    ctx.dry_run = true;
+    let ctx = Arc::new(ctx);
+
+    let stack = Arc::new(make_nfs_stack("dry", "test", None));
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/apis/servarr.dev/v1alpha1/namespaces/test/servarrapps",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(empty_list("servarr.dev/v1alpha1", "ServarrAppList")),
+        )
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"/namespaces/test/"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "apiVersion": "v1",
+            "kind": "Status",
+            "metadata": {},
+            "status": "Failure",
+            "message": "not found",
+            "reason": "NotFound",
+            "code": 404
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(
+            "/apis/servarr.dev/v1alpha1/namespaces/test/mediastacks/dry/status",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&*stack))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = servarr_operator::media_stack_controller::reconcile(stack, ctx).await;
+    assert_eq!(result.unwrap(), Action::requeue(Duration::from_secs(300)));
+
+    let requests = mock_server.received_requests().await.unwrap();
+    let writes: Vec<_> = requests
+        .iter()
+        .filter(|r| r.method.as_str() != "GET")
+        .collect();
+    assert_eq!(writes.len(), 1, "only the status is patched: {writes:?}");
+    let body: serde_json::Value = serde_json::from_slice(&writes[0].body).unwrap();
+    let would_change: Vec<&str> = body["status"]["wouldChange"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c.as_str().unwrap())
+        .collect();
+    assert!(
+        would_change.contains(&"create ServarrApp/dry-sonarr"),
+        "{would_change:?}"
+    );
+    assert!(
+        would_change.contains(&"create ConfigMap/dry-connections"),
+        "{would_change:?}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Selective reconcile: unchanged resource groups are not applied again
+// ---------------------------------------------------------------------------
+
+async fn status_patch_body(mock_server: &MockServer, name: &str) -> serde_json::Value {
+    let request = mock_server
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|r| {
+            r.url
+                .path()
+                .ends_with(&format!("/servarrapps/{name}/status"))
+        })
+        .unwrap();
+    serde_json::from_slice(&request.body).unwrap()
+}
+
+#[tokio::test]
+async fn test_unchanged_resource_groups_are_skipped() {
+    let mock_server = MockServer::start().await;
+    let ctx = test_context(mock_client(&mock_server.uri()).await);
+    mount_common_mocks(&mock_server, "test-sonarr", "test").await;
+    let app = make_sonarr_app("test-sonarr", "test");
+    servarr_operator::controller::reconcile(Arc::new(app.clone()), ctx)
+        .await
+        .unwrap();
+    let first = status_patch_body(&mock_server, "test-sonarr").await;
+    let groups = first["status"]["resourceGroups"].clone();
+    assert!(groups["networking"].is_string(), "{groups}");
+    assert!(groups["fullSyncTime"].is_string(), "{groups}");
+
+    // Second pass over the recorded hashes.
+    let mock_server = MockServer::start().await;
+    let ctx = test_context(mock_client(&mock_server.uri()).await);
+    mount_common_mocks(&mock_server, "test-sonarr", "test").await;
+    let mut app = app;
+    app.status = Some(serde_json::from_value(json!({ "resourceGroups": groups })).unwrap());
+    let result = servarr_operator::controller::reconcile(Arc::new(app), ctx).await;
+    assert_eq!(result.unwrap(), Action::requeue(Duration::from_secs(300)));
+
+    let requests = mock_server.received_requests().await.unwrap();
+    // PVCs are still read for the StorageBound condition, but not applied.
+    let skipped: Vec<_> = requests
+        .iter()
+        .filter(|r| {
+            let p = r.url.path();
+            p.contains("/services/")
+                || p.contains("/networkpolicies/")
+                || (p.contains("/persistentvolumeclaims/") && r.method.as_str() != "GET")
+        })
+        .map(|r| r.url.path())
+        .collect();
+    assert!(
+        skipped.is_empty(),
+        "unchanged groups were applied: {skipped:?}"
+    );
+    let second = status_patch_body(&mock_server, "test-sonarr").await;
+    assert_eq!(second["status"]["resourceGroups"], groups);
+    assert_eq!(
+        second["status"]["generatedResources"]["service"],
+        "test-sonarr"
+    );
+}
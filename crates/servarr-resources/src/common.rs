@@ -1,7 +1,7 @@
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
 use kube::Resource;
 use kube::api::ObjectMeta;
-use servarr_crds::ServarrApp;
+use servarr_crds::{AppDefaults, AppType, ServarrApp};
 use std::collections::BTreeMap;
 
 pub const MANAGER: &str = "servarr-operator";
@@ -69,6 +69,16 @@ pub fn child_name(app: &ServarrApp, suffix: &str) -> String {
     }
 }
 
+/// Name of the PVC backing `volume`: `{app}-{volume}`, unless a storage
+/// migration has moved the volume to another claim.
+pub fn claim_name(app: &ServarrApp, volume: &str) -> String {
+    app.status
+        .as_ref()
+        .and_then(|s| s.volume_claims.get(volume))
+        .cloned()
+        .unwrap_or_else(|| child_name(app, volume))
+}
+
 pub fn metadata(app: &ServarrApp, suffix: &str) -> ObjectMeta {
     ObjectMeta {
         name: Some(child_name(app, suffix)),
@@ -78,3 +88,87 @@ pub fn metadata(app: &ServarrApp, suffix: &str) -> ObjectMeta {
         ..Default::default()
     }
 }
+
+/// In-cluster URL of the app's Service, e.g. `http://sonarr.media.svc:8989`
+/// (`ssh://` for the SSH bastion).
+pub fn internal_url(app: &ServarrApp) -> String {
+    let defaults = AppDefaults::for_app(&app.spec.app);
+    let svc = app.spec.service.as_ref().unwrap_or(&defaults.service);
+    let port = svc.ports.first().map(|p| p.port).unwrap_or(80);
+    let scheme = if app.spec.app == AppType::SshBastion {
+        "ssh"
+    } else {
+        "http"
+    };
+    format!(
+        "{scheme}://{}.{}.svc:{port}",
+        app_name(app),
+        app_namespace(app)
+    )
+}
+
+/// URLs the app is exposed at through `spec.gateway` and `spec.ingress`,
+/// without duplicates.
+pub fn external_urls(app: &ServarrApp) -> Vec<String> {
+    let mut urls = Vec::new();
+    if let Some(gateway) = app.spec.gateway.as_ref().filter(|g| g.enabled) {
+        let scheme = if gateway.tls.as_ref().is_some_and(|t| t.enabled) {
+            "https"
+        } else {
+            "http"
+        };
+        urls.extend(gateway.hosts.iter().map(|h| format!("{scheme}://{h}")));
+    }
+    if let Some(ingress) = &app.spec.ingress {
+        let scheme = if ingress.tls_secret_name.is_some() {
+            "https"
+        } else {
+            "http"
+        };
+        let path = ingress.path().trim_end_matches('/');
+        let url = format!("{scheme}://{}{path}", ingress.host);
+        if !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+    urls
+}
+
+const ARCH_LABEL: &str = "kubernetes.io/arch";
+
+/// The single node architecture the app's scheduling constraints pin it to,
+/// from a `kubernetes.io/arch` nodeSelector or a required node affinity
+/// whose terms all allow exactly that one value.
+pub fn pinned_arch(app: &ServarrApp) -> Option<String> {
+    let scheduling = app.spec.scheduling.as_ref()?;
+    if let Some(arch) = scheduling.node_selector.get(ARCH_LABEL) {
+        return Some(arch.clone());
+    }
+    let terms = scheduling
+        .affinity
+        .as_ref()?
+        .pointer("/nodeAffinity/requiredDuringSchedulingIgnoredDuringExecution/nodeSelectorTerms")?
+        .as_array()?;
+    let mut pinned: Option<&str> = None;
+    for term in terms {
+        let values = term
+            .get("matchExpressions")?
+            .as_array()?
+            .iter()
+            .find(|e| {
+                e.get("key").and_then(|k| k.as_str()) == Some(ARCH_LABEL)
+                    && e.get("operator").and_then(|o| o.as_str()) == Some("In")
+            })?
+            .get("values")?
+            .as_array()?;
+        let [value] = values.as_slice() else {
+            return None;
+        };
+        let value = value.as_str()?;
+        if pinned.is_some_and(|p| p != value) {
+            return None;
+        }
+        pinned = Some(value);
+    }
+    pinned.map(String::from)
+}
@@ -0,0 +1,402 @@
+//! Settings merged into an app's own config file (`config.xml`,
+//! `sabnzbd.ini`, `qBittorrent.conf`) by an init container before each
+//! start.
+//!
+//! Values come from `spec.configFile` and from [`managed_settings`], which
+//! derives entries from typed spec fields. They are rendered into a
+//! ConfigMap as one tab-separated line per setting together with a merge
+//! script for the file's format; the script replaces matching entries, adds
+//! missing ones, and leaves everything else as the app wrote it. New
+//! app-specific settings only need an entry in [`managed_settings`].
+
+use std::collections::BTreeMap;
+
+use k8s_openapi::api::core::v1::{
+    ConfigMap, ConfigMapVolumeSource, Container, SecurityContext, Volume, VolumeMount,
+};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use servarr_crds::{AppConfig, AppDefaults, AppType, ServarrApp, ServarrAppSpec};
+
+use crate::common;
+
+const VOLUME: &str = "config-file";
+const MOUNT_PATH: &str = "/config-file";
+
+/// How a config file is structured, which decides the key syntax and the
+/// merge script.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// Flat `<Config><Key>value</Key></Config>` as written by the *arr apps.
+    /// Keys are element names.
+    Xml,
+    /// configobj- or QSettings-style INI. Keys are `section.key` for
+    /// top-level sections.
+    Ini,
+}
+
+/// The config file an app type reads, if it has one the operator can merge
+/// settings into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConfigFileTemplate {
+    pub path: &'static str,
+    pub format: ConfigFormat,
+    /// Key of the port the app listens on.
+    pub port_key: &'static str,
+    /// File the image ships to start from when `path` doesn't exist yet.
+    pub seed: Option<&'static str>,
+}
+
+pub fn template(app: &AppType) -> Option<ConfigFileTemplate> {
+    match app {
+        AppType::Sonarr
+        | AppType::Radarr
+        | AppType::Lidarr
+        | AppType::Readarr
+        | AppType::Whisparr
+        | AppType::Prowlarr => Some(ConfigFileTemplate {
+            path: "/config/config.xml",
+            format: ConfigFormat::Xml,
+            port_key: "Port",
+            seed: None,
+        }),
+        AppType::Sabnzbd => Some(ConfigFileTemplate {
+            path: "/config/sabnzbd.ini",
+            format: ConfigFormat::Ini,
+            port_key: "misc.port",
+            seed: None,
+        }),
+        AppType::Qbittorrent => Some(ConfigFileTemplate {
+            path: "/config/qBittorrent/qBittorrent.conf",
+            format: ConfigFormat::Ini,
+            port_key: "Preferences.WebUI\\Port",
+            seed: Some("/defaults/qBittorrent.conf"),
+        }),
+        _ => None,
+    }
+}
+
+/// Settings the operator derives from typed spec fields. These take
+/// precedence over `spec.configFile`, which may not set the same keys.
+pub fn managed_settings(spec: &ServarrAppSpec) -> BTreeMap<String, String> {
+    let mut settings = BTreeMap::new();
+    let Some(template) = template(&spec.app) else {
+        return settings;
+    };
+
+    // The app must listen on the container port the Service targets.
+    let default_port = AppDefaults::for_app(&spec.app)
+        .service
+        .ports
+        .first()
+        .map(|p| p.container_port.unwrap_or(p.port));
+    let port = spec
+        .service
+        .as_ref()
+        .and_then(|s| s.ports.first())
+        .map(|p| p.container_port.unwrap_or(p.port));
+    if let Some(port) = port
+        && Some(port) != default_port
+    {
+        settings.insert(template.port_key.into(), port.to_string());
+    }
+
+    if let Some(AppConfig::Qbittorrent(ref qc)) = spec.app_config {
+        if let Some(ref peer) = qc.peer_port {
+            settings.insert("BitTorrent.Session\\Port".into(), peer.port.to_string());
+        }
+        if let Some(ref limits) = qc.speed_limits {
+            for (key, limit) in limits.settings() {
+                settings.insert(key.into(), limit.to_string());
+            }
+        }
+    }
+
+    settings
+}
+
+/// All settings for the app's config file: `spec.configFile` overlaid with
+/// [`managed_settings`].
+pub fn settings(spec: &ServarrAppSpec) -> BTreeMap<String, String> {
+    let mut settings = spec.config_file.clone();
+    settings.extend(managed_settings(spec));
+    settings
+}
+
+/// Check `spec.configFile` against the app's config file format. Returns one
+/// message per problem.
+pub fn validate(spec: &ServarrAppSpec) -> Vec<String> {
+    let mut errors = Vec::new();
+    if spec.config_file.is_empty() {
+        return errors;
+    }
+    let Some(template) = template(&spec.app) else {
+        errors.push(format!(
+            "configFile is not supported for app type '{}'",
+            spec.app
+        ));
+        return errors;
+    };
+    let managed = managed_settings(spec);
+    for (key, value) in &spec.config_file {
+        let key_ok = match template.format {
+            ConfigFormat::Xml => is_xml_name(key) && key != "Config",
+            ConfigFormat::Ini => key
+                .split_once('.')
+                .is_some_and(|(section, name)| is_ini_section(section) && is_ini_key(name)),
+        };
+        if !key_ok {
+            let expected = match template.format {
+                ConfigFormat::Xml => "an XML element name such as 'UrlBase'",
+                ConfigFormat::Ini => "'section.key', such as 'misc.cache_limit'",
+            };
+            errors.push(format!("configFile key '{key}' must be {expected}"));
+        }
+        if value.contains(['\n', '\r', '\t']) {
+            errors.push(format!(
+                "configFile.{key}: value must not contain newlines or tabs"
+            ));
+        }
+        if managed.contains_key(key) {
+            errors.push(format!(
+                "configFile.{key} is managed by the operator from other spec fields"
+            ));
+        }
+    }
+    errors
+}
+
+fn is_xml_name(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_ini_section(s: &str) -> bool {
+    !s.is_empty() && !s.contains(['[', ']', '.']) && s.trim() == s
+}
+
+fn is_ini_key(s: &str) -> bool {
+    !s.is_empty() && !s.contains(['=', '[', ']']) && s.trim() == s
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// The settings file read by the merge script: one line per setting,
+/// `key<TAB>value` for XML and `section<TAB>key<TAB>value` for INI.
+fn render(format: ConfigFormat, settings: &BTreeMap<String, String>) -> String {
+    settings
+        .iter()
+        .filter_map(|(key, value)| match format {
+            ConfigFormat::Xml => Some(format!("{key}\t{}\n", escape_xml(value))),
+            ConfigFormat::Ini => {
+                let (section, name) = key.split_once('.')?;
+                Some(format!("{section}\t{name}\t{value}\n"))
+            }
+        })
+        .collect()
+}
+
+const XML_MERGE_SCRIPT: &str = r#"#!/bin/sh
+# Merge /config-file/settings ("Element<TAB>value" lines) into an XML config
+# file: matching elements are replaced, missing ones are added before
+# </Config>, and everything else is left alone.
+set -e
+FILE="$1"
+SETTINGS=/config-file/settings
+
+if [ ! -f "$FILE" ]; then
+  echo "Creating $FILE"
+  printf '<Config>\n</Config>\n' > "$FILE"
+fi
+
+TMP=$(mktemp)
+awk -F '\t' '
+  NR == FNR { value[$1] = $2; order[++n] = $1; next }
+  match($0, /<[A-Za-z_][A-Za-z0-9_]*>/) {
+    key = substr($0, RSTART + 1, RLENGTH - 2)
+    if ((key in value) && index($0, "</" key ">")) {
+      print substr($0, 1, RSTART - 1) "<" key ">" value[key] "</" key ">"
+      done[key] = 1
+      next
+    }
+  }
+  /<\/Config>/ {
+    for (i = 1; i <= n; i++)
+      if (!(order[i] in done)) print "  <" order[i] ">" value[order[i]] "</" order[i] ">"
+  }
+  { print }
+' "$SETTINGS" "$FILE" > "$TMP"
+cat "$TMP" > "$FILE"
+rm -f "$TMP"
+echo "Applied $(wc -l < "$SETTINGS") setting(s) to $FILE"
+"#;
+
+const INI_MERGE_SCRIPT: &str = r#"#!/bin/sh
+# Merge /config-file/settings ("section<TAB>key<TAB>value" lines) into an INI
+# config file: matching keys of top-level [sections] are replaced, missing
+# keys are added to their section (appended if the section is new), and
+# everything else, including [[nested]] sections, is left alone.
+set -e
+FILE="$1"
+SETTINGS=/config-file/settings
+
+if [ ! -f "$FILE" ]; then
+  mkdir -p "$(dirname "$FILE")"
+  if [ -n "$2" ] && [ -f "$2" ]; then
+    echo "Creating $FILE from $2"
+    cp "$2" "$FILE"
+  else
+    echo "Creating $FILE"
+    : > "$FILE"
+  fi
+fi
+
+TMP=$(mktemp)
+awk -F '\t' '
+  function add_missing(s,   i) {
+    for (i = 1; i <= n; i++)
+      if (sect[i] == s && !(i in done)) { print name[i] " = " value[i]; done[i] = 1 }
+  }
+  NR == FNR { n++; sect[n] = $1; name[n] = $2; value[n] = $3; idx[$1 SUBSEP $2] = n; next }
+  /^[ \t]*\[\[/ {
+    if (!nested) add_missing(section)
+    nested = 1
+    print
+    next
+  }
+  /^[ \t]*\[/ {
+    if (!nested) add_missing(section)
+    section = $0
+    sub(/^[ \t]*\[/, "", section)
+    sub(/\][ \t]*$/, "", section)
+    seen[section] = 1
+    nested = 0
+    print
+    next
+  }
+  !nested && /=/ {
+    key = substr($0, 1, index($0, "=") - 1)
+    gsub(/^[ \t]+|[ \t]+$/, "", key)
+    if ((section SUBSEP key) in idx) {
+      i = idx[section SUBSEP key]
+      print key " = " value[i]
+      done[i] = 1
+      next
+    }
+  }
+  { print }
+  END {
+    if (!nested) add_missing(section)
+    for (i = 1; i <= n; i++)
+      if (!(i in done)) {
+        if (!(sect[i] in seen)) { print ""; print "[" sect[i] "]"; seen[sect[i]] = 1 }
+        add_missing(sect[i])
+      }
+  }
+' "$SETTINGS" "$FILE" > "$TMP"
+cat "$TMP" > "$FILE"
+rm -f "$TMP"
+echo "Applied $(wc -l < "$SETTINGS") setting(s) to $FILE"
+"#;
+
+/// Settings and merge script for the app's config file. `None` when the app
+/// type has no supported config file or there is nothing to set.
+pub fn build(app: &ServarrApp) -> Option<ConfigMap> {
+    let template = template(&app.spec.app)?;
+    let settings = settings(&app.spec);
+    if settings.is_empty() {
+        return None;
+    }
+    let script = match template.format {
+        ConfigFormat::Xml => XML_MERGE_SCRIPT,
+        ConfigFormat::Ini => INI_MERGE_SCRIPT,
+    };
+    let data = BTreeMap::from([
+        ("settings".to_string(), render(template.format, &settings)),
+        ("merge.sh".to_string(), script.to_string()),
+    ]);
+
+    Some(ConfigMap {
+        metadata: ObjectMeta {
+            name: Some(common::child_name(app, VOLUME)),
+            namespace: Some(common::app_namespace(app)),
+            labels: Some(common::labels(app)),
+            owner_references: Some(vec![common::owner_reference(app)]),
+            ..Default::default()
+        },
+        data: Some(data),
+        ..Default::default()
+    })
+}
+
+/// Volume for the ConfigMap from [`build`].
+pub fn volume(app: &ServarrApp) -> Option<Volume> {
+    template(&app.spec.app)?;
+    if settings(&app.spec).is_empty() {
+        return None;
+    }
+    Some(Volume {
+        name: VOLUME.into(),
+        config_map: Some(ConfigMapVolumeSource {
+            name: common::child_name(app, VOLUME),
+            default_mode: Some(0o755),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
+
+/// Init container running the merge script against the app's config file.
+/// Runs as the app's uid/gid so a file it creates stays writable by the app.
+pub fn init_container(
+    app: &ServarrApp,
+    image: &str,
+    security_context: &SecurityContext,
+    uid: i64,
+    gid: i64,
+) -> Option<Container> {
+    let template = template(&app.spec.app)?;
+    if settings(&app.spec).is_empty() {
+        return None;
+    }
+    Some(Container {
+        name: "apply-config-file".into(),
+        image: Some(image.to_string()),
+        command: Some(
+            [
+                "/bin/sh".into(),
+                format!("{MOUNT_PATH}/merge.sh"),
+                template.path.into(),
+            ]
+            .into_iter()
+            .chain(template.seed.map(String::from))
+            .collect(),
+        ),
+        security_context: Some(SecurityContext {
+            run_as_user: Some(uid),
+            run_as_group: Some(gid),
+            ..security_context.clone()
+        }),
+        volume_mounts: Some(vec![
+            VolumeMount {
+                name: "config".into(),
+                mount_path: "/config".into(),
+                ..Default::default()
+            },
+            VolumeMount {
+                name: VOLUME.into(),
+                mount_path: MOUNT_PATH.into(),
+                read_only: Some(true),
+                ..Default::default()
+            },
+        ]),
+        ..Default::default()
+    })
+}
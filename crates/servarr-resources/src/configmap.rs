@@ -1,6 +1,6 @@
 use k8s_openapi::api::core::v1::ConfigMap;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
-use servarr_crds::{AppConfig, AppType, ServarrApp, SshMode};
+use servarr_crds::{AppConfig, AppType, ServarrApp, SftpChroot, SshMode, SshUser};
 use std::collections::BTreeMap;
 
 use crate::common;
@@ -8,7 +8,9 @@ use crate::common;
 pub fn build(app: &ServarrApp) -> Option<ConfigMap> {
     match app.spec.app {
         AppType::Transmission => build_transmission(app),
+        AppType::Qbittorrent => build_qbittorrent(app),
         AppType::Sabnzbd => build_sabnzbd(app),
+        AppType::Bazarr => build_bazarr(app),
         _ => None,
     }
 }
@@ -180,6 +182,69 @@ exec "${{ARGS[@]}}"
     })
 }
 
+/// Key of the SFTP chroot entrypoint script in its ConfigMap.
+pub const SFTP_CHROOT_SCRIPT: &str = "sftp-chroot.sh";
+
+/// Users of an SSH bastion with an SFTP chroot.
+pub fn sftp_chroot_users(app: &ServarrApp) -> Vec<&SshUser> {
+    match app.spec.app_config {
+        Some(AppConfig::SshBastion(ref sc)) => sc
+            .users
+            .iter()
+            .filter(|u| u.mode == SshMode::Sftp && u.sftp_chroot.is_some())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Build a ConfigMap holding an `/etc/entrypoint.d` script that appends an
+/// sshd `Match User` block per chrooted SFTP user: `ChrootDirectory
+/// /sftp/<user>` with `ForceCommand internal-sftp`.
+///
+/// Returns `None` when no users have an SFTP chroot.
+pub fn build_ssh_bastion_sftp_chroot(app: &ServarrApp) -> Option<ConfigMap> {
+    let users = sftp_chroot_users(app);
+    if users.is_empty() {
+        return None;
+    }
+
+    let blocks: String = users
+        .iter()
+        .map(|u| {
+            format!(
+                "Match User {name}\n    ChrootDirectory {root}/{name}\n    ForceCommand internal-sftp\n    AllowTcpForwarding no\n    X11Forwarding no\n",
+                name = u.name,
+                root = SftpChroot::ROOT,
+            )
+        })
+        .collect();
+    // Match blocks must come last in sshd_config; the marker keeps the
+    // script idempotent if entry.sh runs it more than once.
+    let marker = "# servarr-operator: sftp chroot";
+    let script = format!(
+        r#"#!/bin/sh
+set -e
+CONFIG=/etc/ssh/sshd_config
+grep -qxF '{marker}' "$CONFIG" && exit 0
+cat >> "$CONFIG" <<'EOF'
+{marker}
+{blocks}EOF
+"#
+    );
+
+    Some(ConfigMap {
+        metadata: ObjectMeta {
+            name: Some(common::child_name(app, "sftp-chroot")),
+            namespace: Some(common::app_namespace(app)),
+            labels: Some(common::labels(app)),
+            owner_references: Some(vec![common::owner_reference(app)]),
+            ..Default::default()
+        },
+        data: Some(BTreeMap::from([(SFTP_CHROOT_SCRIPT.to_string(), script)])),
+        ..Default::default()
+    })
+}
+
 /// Build a ConfigMap containing custom Prowlarr indexer definitions.
 ///
 /// Each definition entry becomes a `{name}.yml` key in the ConfigMap data,
@@ -336,15 +401,33 @@ fn build_transmission(app: &ServarrApp) -> Option<ConfigMap> {
     let uid = app.spec.uid.unwrap_or(65534);
     let gid = app.spec.gid.unwrap_or(65534);
 
-    let settings_json = if let Some(AppConfig::Transmission(ref tc)) = app.spec.app_config {
-        if tc.settings.is_null() {
-            default_transmission_settings()
-        } else {
-            serde_json::to_string_pretty(&tc.settings).unwrap_or_default()
-        }
-    } else {
-        default_transmission_settings()
+    let tc = match app.spec.app_config {
+        Some(AppConfig::Transmission(ref tc)) => Some(tc),
+        _ => None,
+    };
+    let mut settings = match tc {
+        Some(tc) if !tc.settings.is_null() => tc.settings.clone(),
+        _ => default_transmission_settings(),
     };
+    let directories = tc.and_then(|tc| tc.directories.as_ref());
+    if let (Some(dirs), serde_json::Value::Object(map)) = (directories, &mut settings) {
+        map.extend(dirs.settings());
+    }
+    let settings_json = serde_json::to_string_pretty(&settings).unwrap_or_default();
+
+    // Directories that must exist before Transmission starts; the init
+    // container mounts the app's volumes when there are any.
+    let make_dirs: String = directories
+        .map(|d| d.paths())
+        .unwrap_or_default()
+        .iter()
+        .map(|(_, path)| {
+            format!(
+                "mkdir -p '{path}' || echo 'Warning: could not create {path}'\n",
+                path = path.replace('\'', "'\\''")
+            )
+        })
+        .collect();
 
     let apply_script = format!(
         r#"#!/bin/sh
@@ -394,7 +477,7 @@ rm -f "$TMP" "$AUTH_FILE"
 # Fix ownership
 chown {uid}:{gid} "$SETTINGS_FILE"
 chmod 600 "$SETTINGS_FILE"
-
+{make_dirs}
 echo "Settings applied successfully."
 "#
     );
@@ -454,8 +537,126 @@ echo "[transmission-auth] Admin credentials set successfully."
     })
 }
 
-fn default_transmission_settings() -> String {
-    serde_json::to_string_pretty(&serde_json::json!({
+/// Categories for qBittorrent's `categories.json` and the script the
+/// apply-settings init container runs to install them. The rest of
+/// qBittorrent's settings go through [`crate::config_file`].
+fn build_qbittorrent(app: &ServarrApp) -> Option<ConfigMap> {
+    let qc = match app.spec.app_config {
+        Some(AppConfig::Qbittorrent(ref qc)) if !qc.categories.is_empty() => qc,
+        _ => return None,
+    };
+
+    let categories: serde_json::Map<String, serde_json::Value> = qc
+        .categories
+        .iter()
+        .map(|(name, path)| (name.clone(), serde_json::json!({ "save_path": path })))
+        .collect();
+    let categories_json = serde_json::to_string_pretty(&categories).unwrap_or_default();
+
+    let make_dirs: String = qc
+        .categories
+        .values()
+        .map(|path| {
+            format!(
+                "mkdir -p '{path}' || echo 'Warning: could not create {path}'\n",
+                path = path.replace('\'', "'\\''")
+            )
+        })
+        .collect();
+
+    let apply_script = format!(
+        r#"#!/bin/sh
+set -e
+CATEGORIES_FILE="/config/qBittorrent/categories.json"
+
+# qBittorrent reads categories.json at startup; the spec's categories
+# replace whatever the web UI saved.
+mkdir -p "$(dirname "$CATEGORIES_FILE")"
+cp /scripts/categories.json "$CATEGORIES_FILE"
+chmod 600 "$CATEGORIES_FILE"
+{make_dirs}
+echo "Categories applied successfully."
+"#
+    );
+
+    let mut data = BTreeMap::new();
+    data.insert("categories.json".into(), categories_json);
+    data.insert("apply-settings.sh".into(), apply_script);
+
+    Some(ConfigMap {
+        metadata: ObjectMeta {
+            name: Some(common::app_name(app)),
+            namespace: Some(common::app_namespace(app)),
+            labels: Some(common::labels(app)),
+            owner_references: Some(vec![common::owner_reference(app)]),
+            ..Default::default()
+        },
+        data: Some(data),
+        ..Default::default()
+    })
+}
+
+/// Script for Bazarr's apply-api-key init container. Bazarr has no
+/// environment override for its API key, so the key from `apiKeySecret` is
+/// written into the `auth` section of `config.yaml` before each start,
+/// creating the file on first start.
+fn build_bazarr(app: &ServarrApp) -> Option<ConfigMap> {
+    app.spec.api_key_ref()?;
+
+    let apply_script = r#"#!/bin/sh
+set -e
+CONFIG="/config/config/config.yaml"
+KEY="$(cat /run/secrets/api-key/api-key)"
+
+mkdir -p "$(dirname "$CONFIG")"
+if [ ! -f "$CONFIG" ]; then
+    printf "auth:\n  apikey: '%s'\n" "$KEY" > "$CONFIG"
+    echo "Created $CONFIG with the API key."
+    exit 0
+fi
+
+TMP="$(mktemp)"
+awk -v key="$KEY" '
+    /^[^ #]/ {
+        if (in_auth && !done) { print "  apikey: \047" key "\047"; done = 1 }
+        in_auth = ($0 ~ /^auth:/)
+    }
+    in_auth && /^  apikey:/ {
+        if (!done) print "  apikey: \047" key "\047"
+        done = 1
+        next
+    }
+    { print }
+    END {
+        if (!done) {
+            if (!in_auth) print "auth:"
+            print "  apikey: \047" key "\047"
+        }
+    }
+' "$CONFIG" > "$TMP"
+cat "$TMP" > "$CONFIG"
+rm -f "$TMP"
+echo "API key applied to $CONFIG"
+"#;
+
+    let mut data = BTreeMap::new();
+    data.insert("apply-api-key.sh".into(), apply_script.to_string());
+
+    Some(ConfigMap {
+        metadata: ObjectMeta {
+            name: Some(common::app_name(app)),
+            namespace: Some(common::app_namespace(app)),
+            labels: Some(common::labels(app)),
+            owner_references: Some(vec![common::owner_reference(app)]),
+            ..Default::default()
+        },
+        data: Some(data),
+        ..Default::default()
+    })
+}
+
+fn default_transmission_settings() -> serde_json::Value {
+    serde_json::json!({
         "download-dir": "/downloads/complete",
         "incomplete-dir": "/downloads/incomplete",
         "incomplete-dir-enabled": true,
@@ -477,6 +678,5 @@ fn default_transmission_settings() -> String {
         "umask": "002",
         "rename-partial-files": true,
         "start-added-torrents": true,
-    }))
-    .unwrap()
+    })
 }
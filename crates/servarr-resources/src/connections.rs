@@ -0,0 +1,86 @@
+use k8s_openapi::api::core::v1::ConfigMap;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, OwnerReference};
+use servarr_crds::ServarrApp;
+use std::collections::BTreeMap;
+
+use crate::common;
+
+const COMPONENT: &str = "connections";
+
+/// Key holding every app's entry as one JSON array.
+pub const JSON_KEY: &str = "connections.json";
+
+/// Name of the stack's connection info ConfigMap: `{stack}-connections`.
+pub fn resource_name(stack_name: &str) -> String {
+    format!("{stack_name}-{COMPONENT}")
+}
+
+/// Build the `{stack}-connections` ConfigMap describing the stack's apps
+/// (as expanded into child ServarrApps) for tools that run alongside it.
+///
+/// Each app gets `{app}.type`, `{app}.url`, and, when set,
+/// `{app}.externalUrl`, `{app}.apiKeySecret`, and `{app}.apiKeySecretKey`
+/// keys, so a mounted file can be read from a shell script.
+/// `connections.json` holds the same entries as an array of objects.
+pub fn build(
+    stack_name: &str,
+    ns: &str,
+    apps: &[ServarrApp],
+    owner_ref: OwnerReference,
+) -> ConfigMap {
+    let mut data = BTreeMap::new();
+    let mut entries = Vec::new();
+    for app in apps {
+        let name = common::app_name(app);
+        let url = common::internal_url(app);
+        let external_url = common::external_urls(app).into_iter().next();
+        let api_key_ref = app.spec.api_key_ref();
+
+        data.insert(format!("{name}.type"), app.spec.app.as_str().to_string());
+        data.insert(format!("{name}.url"), url.clone());
+        if let Some(ref external) = external_url {
+            data.insert(format!("{name}.externalUrl"), external.clone());
+        }
+        if let Some((secret, key)) = api_key_ref {
+            data.insert(format!("{name}.apiKeySecret"), secret.to_string());
+            data.insert(format!("{name}.apiKeySecretKey"), key.to_string());
+        }
+
+        let mut entry = serde_json::json!({
+            "name": name,
+            "type": app.spec.app.as_str(),
+            "url": url,
+        });
+        if let Some(external) = external_url {
+            entry["externalUrl"] = external.into();
+        }
+        if let Some((secret, key)) = api_key_ref {
+            entry["apiKeySecret"] = secret.into();
+            entry["apiKeySecretKey"] = key.into();
+        }
+        entries.push(entry);
+    }
+    data.insert(
+        JSON_KEY.to_string(),
+        serde_json::to_string_pretty(&entries).unwrap_or_default(),
+    );
+
+    ConfigMap {
+        metadata: ObjectMeta {
+            name: Some(resource_name(stack_name)),
+            namespace: Some(ns.to_string()),
+            labels: Some(BTreeMap::from([
+                ("servarr.dev/stack".into(), stack_name.to_string()),
+                ("servarr.dev/component".into(), COMPONENT.to_string()),
+                (
+                    "app.kubernetes.io/managed-by".into(),
+                    common::MANAGER.to_string(),
+                ),
+            ])),
+            owner_references: Some(vec![owner_ref]),
+            ..Default::default()
+        },
+        data: Some(data),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,138 @@
+//! Per-app copies of shared ServarrCredentials (`spec.credentials`).
+//!
+//! The source Secret of a ServarrCredential is never mounted directly: each
+//! consuming app gets a Secret `{app}-credential-{name}` with just the
+//! shared keys, owned by the app so it goes away with it. Env vars and
+//! volumes reference that copy, and a checksum of all copies on the pod
+//! template rolls the app when the source Secret is rotated.
+
+use std::collections::BTreeMap;
+
+use k8s_openapi::ByteString;
+use k8s_openapi::api::core::v1::{
+    EnvVar, EnvVarSource, Secret, SecretKeySelector, SecretVolumeSource, Volume, VolumeMount,
+};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use servarr_crds::{ServarrApp, ServarrCredential};
+
+use crate::common;
+
+/// Pod template annotation holding the checksum of the app's credential
+/// copies.
+pub const CHECKSUM_ANNOTATION: &str = "servarr.dev/credentials-checksum";
+
+/// Name of the app's copy of the credential `credential`.
+pub fn secret_name(app: &ServarrApp, credential: &str) -> String {
+    common::child_name(app, &format!("credential-{credential}"))
+}
+
+fn volume_name(credential: &str) -> String {
+    format!("credential-{credential}")
+}
+
+/// The keys of `source` that `credential` shares.
+pub fn shared_data(
+    credential: &ServarrCredential,
+    source: &Secret,
+) -> BTreeMap<String, ByteString> {
+    source
+        .data
+        .iter()
+        .flatten()
+        .filter(|(key, _)| credential.spec.shares(key))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect()
+}
+
+/// The app's copy of `credential`, holding the keys of `source` that the
+/// credential shares.
+pub fn build(app: &ServarrApp, credential: &ServarrCredential, source: &Secret) -> Secret {
+    let name = credential.metadata.name.as_deref().unwrap_or_default();
+    Secret {
+        metadata: ObjectMeta {
+            name: Some(secret_name(app, name)),
+            namespace: Some(common::app_namespace(app)),
+            labels: Some(common::labels(app)),
+            owner_references: Some(vec![common::owner_reference(app)]),
+            ..Default::default()
+        },
+        data: Some(shared_data(credential, source)),
+        type_: Some("Opaque".into()),
+        ..Default::default()
+    }
+}
+
+/// SHA-256 over named sets of credential data, e.g. the app's copies keyed
+/// by credential name.
+pub fn checksum<'a>(
+    sets: impl IntoIterator<Item = (&'a str, &'a BTreeMap<String, ByteString>)>,
+) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for (name, data) in sets {
+        hasher.update(name.as_bytes());
+        hasher.update(b"\0");
+        for (key, value) in data {
+            hasher.update(key.as_bytes());
+            hasher.update(b"=");
+            hasher.update(&value.0);
+            hasher.update(b"\0");
+        }
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Env vars set from the app's credential copies.
+pub fn env_vars(app: &ServarrApp) -> Vec<EnvVar> {
+    app.spec
+        .credentials
+        .iter()
+        .flat_map(|cred| {
+            cred.env.iter().map(|var| EnvVar {
+                name: var.name.clone(),
+                value_from: Some(EnvVarSource {
+                    secret_key_ref: Some(SecretKeySelector {
+                        name: secret_name(app, &cred.name),
+                        key: var.key.clone(),
+                        optional: Some(false),
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Read-only mounts for credentials with a `mountPath`.
+pub fn volume_mounts(app: &ServarrApp) -> Vec<VolumeMount> {
+    app.spec
+        .credentials
+        .iter()
+        .filter_map(|cred| {
+            Some(VolumeMount {
+                name: volume_name(&cred.name),
+                mount_path: cred.mount_path.clone()?,
+                read_only: Some(true),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+pub fn volumes(app: &ServarrApp) -> Vec<Volume> {
+    app.spec
+        .credentials
+        .iter()
+        .filter(|cred| cred.mount_path.is_some())
+        .map(|cred| Volume {
+            name: volume_name(&cred.name),
+            secret: Some(SecretVolumeSource {
+                secret_name: Some(secret_name(app, &cred.name)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .collect()
+}
@@ -1,10 +1,10 @@
 use k8s_openapi::api::apps::v1::{Deployment, DeploymentSpec, DeploymentStrategy};
 use k8s_openapi::api::core::v1::{
-    Capabilities, ConfigMapVolumeSource, Container, ContainerPort, EnvVar, EnvVarSource,
-    ExecAction, HTTPGetAction, LocalObjectReference, NFSVolumeSource,
+    Capabilities, ConfigMap, ConfigMapVolumeSource, Container, ContainerPort, EnvVar, EnvVarSource,
+    ExecAction, HTTPGetAction, Lifecycle, LifecycleHandler, LocalObjectReference, NFSVolumeSource,
     PersistentVolumeClaimVolumeSource, PodSecurityContext, PodSpec, PodTemplateSpec, Probe,
     ResourceRequirements as K8sResources, SeccompProfile, SecretKeySelector, SecurityContext,
-    TCPSocketAction, Volume, VolumeMount,
+    TCPSocketAction, Toleration, TopologySpreadConstraint, Volume, VolumeMount,
 };
 use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, ObjectMeta};
@@ -14,15 +14,39 @@ use std::collections::{BTreeMap, HashMap};
 
 use crate::common;
 
+/// Annotation on generated Deployments recording where the container image
+/// came from: `spec` (the ServarrApp's `spec.image`), `operator-override`
+/// (the operator's `DEFAULT_IMAGE_<APP>_*` settings), or `default` (the
+/// compiled default for the app and node architecture).
+pub const IMAGE_SOURCE_ANNOTATION: &str = "servarr.dev/image-source";
+
+/// Label the MediaStack controller puts on its child apps, valued with the
+/// stack name.
+pub const STACK_LABEL: &str = "servarr.dev/stack";
+
+/// Annotation on generated Deployments: `digest` when the image is pinned by
+/// digest, `tag` otherwise.
+pub const IMAGE_PINNED_BY_ANNOTATION: &str = "servarr.dev/image-pinned-by";
+
+/// Pod template annotation holding [`definitions_checksum`]. Prowlarr only
+/// reads `/config/Definitions/Custom` at startup, so a change to it rolls
+/// the pod.
+pub const DEFINITIONS_CHECKSUM_ANNOTATION: &str = "servarr.dev/definitions-checksum";
+
 /// Compute a SHA-256 checksum of any config data that should trigger a pod restart.
 pub fn config_checksum(app: &ServarrApp) -> Option<String> {
-    use sha2::{Digest, Sha256};
+    checksum(&[crate::configmap::build(app), crate::config_file::build(app)])
+}
 
-    // Collect all ConfigMaps that should trigger a rollout
-    let config_maps = [
-        crate::configmap::build(app),
-        crate::configmap::build_prowlarr_definitions(app),
-    ];
+/// SHA-256 checksum of the Prowlarr custom definitions ConfigMap, kept apart
+/// from [`config_checksum`] so the operator can tell a definitions change
+/// from other config changes.
+pub fn definitions_checksum(app: &ServarrApp) -> Option<String> {
+    checksum(&[crate::configmap::build_prowlarr_definitions(app)])
+}
+
+fn checksum(config_maps: &[Option<ConfigMap>]) -> Option<String> {
+    use sha2::{Digest, Sha256};
 
     let mut hasher = Sha256::new();
     let mut has_data = false;
@@ -42,13 +66,57 @@ pub fn config_checksum(app: &ServarrApp) -> Option<String> {
     has_data.then(|| format!("{:x}", hasher.finalize()))
 }
 
+/// The image used when `spec.image` is unset: the operator's env/Helm
+/// override if one is configured, otherwise the compiled default for the
+/// node architecture the app is pinned to (falling back to `default_arch`,
+/// the operator's `DEFAULT_NODE_ARCH`). With no known architecture the
+/// compiled default is returned as is.
+pub fn default_image(
+    app: &ServarrApp,
+    image_overrides: &HashMap<String, ImageSpec>,
+    default_arch: Option<&str>,
+) -> Result<ImageSpec, UnsupportedArchitecture> {
+    if let Some(override_image) = image_overrides.get(&app.spec.app.to_string()) {
+        return Ok(override_image.clone());
+    }
+    match common::pinned_arch(app).as_deref().or(default_arch) {
+        Some(arch) => AppDefaults::image_for_arch(&app.spec.app, arch),
+        None => Ok(AppDefaults::for_app(&app.spec.app).image),
+    }
+}
+
+/// Which of [`IMAGE_SOURCE_ANNOTATION`]'s values the app's image comes from.
+pub fn image_source(
+    app: &ServarrApp,
+    image_overrides: &HashMap<String, ImageSpec>,
+) -> &'static str {
+    if app.spec.image.is_some() {
+        "spec"
+    } else if image_overrides.contains_key(&app.spec.app.to_string()) {
+        "operator-override"
+    } else {
+        "default"
+    }
+}
+
 pub fn build(app: &ServarrApp, image_overrides: &HashMap<String, ImageSpec>) -> Deployment {
+    build_for_arch(app, image_overrides, None)
+}
+
+/// [`build`], resolving the default image for `default_arch` when the app
+/// does not pin an architecture itself. An unsupported architecture keeps
+/// the compiled default; callers check [`default_image`] to refuse instead.
+pub fn build_for_arch(
+    app: &ServarrApp,
+    image_overrides: &HashMap<String, ImageSpec>,
+    default_arch: Option<&str>,
+) -> Deployment {
     let mut defaults = AppDefaults::for_app(&app.spec.app);
 
     // Apply image override from operator config (env vars / Helm values)
-    let app_key = app.spec.app.to_string();
-    if let Some(override_image) = image_overrides.get(&app_key) {
-        defaults.image = override_image.clone();
+    // or the architecture-specific default
+    if let Ok(image) = default_image(app, image_overrides, default_arch) {
+        defaults.image = image;
     }
 
     let name = common::app_name(app);
@@ -84,9 +152,14 @@ pub fn build(app: &ServarrApp, image_overrides: &HashMap<String, ImageSpec>) ->
 
     let container_ports = build_container_ports(svc_spec, app);
     let has_host_port = container_ports.iter().any(|p| p.host_port.is_some());
-    let volume_mounts = build_volume_mounts(persistence, app);
-    let volumes = build_volumes(app, persistence);
-    let env_vars = build_env_vars(app, &defaults, uid, gid);
+    let mut volume_mounts = build_volume_mounts(persistence, app);
+    volume_mounts.extend(crate::rclone::app_volume_mounts(app));
+    let mut volumes = build_volumes(app, persistence);
+    volumes.extend(crate::rclone::volumes(app));
+    volume_mounts.extend(crate::credential::volume_mounts(app));
+    volumes.extend(crate::credential::volumes(app));
+    let mut env_vars = build_env_vars(app, &defaults, uid, gid);
+    env_vars.extend(crate::credential::env_vars(app));
     let (container_security, pod_security) = build_security_contexts(security, uid, gid);
 
     // Auto-select exec probes for Transmission with auth enabled
@@ -129,10 +202,16 @@ pub fn build(app: &ServarrApp, image_overrides: &HashMap<String, ImageSpec>) ->
         ..Default::default()
     };
 
-    let mut init_containers = build_init_containers(app, &image, &container_security, uid, gid);
+    let mut init_containers =
+        build_init_containers(app, persistence, &image, &container_security, uid, gid);
     if matches!(app.spec.app, AppType::SshBastion) {
         build_ssh_bastion_init_containers(&mut init_containers, app, &image, &container_security);
     }
+    // rclone sidecars follow the operator's init containers so that those
+    // never wait on a remote being reachable. The user's extra init
+    // containers come last and see everything already in place.
+    init_containers.extend(crate::rclone::sidecars(app, uid, gid));
+    init_containers.extend(app.spec.extra_init_containers.iter().cloned());
 
     // SshBastion: override the image CMD to tell sshd which port to listen on.
     // panubo/sshd defaults to port 22 but operators configure port 2222 via
@@ -166,13 +245,21 @@ pub fn build(app: &ServarrApp, image_overrides: &HashMap<String, ImageSpec>) ->
         liveness_probe: Some(liveness),
         readiness_probe: Some(readiness),
         startup_probe: Some(startup),
+        lifecycle: build_lifecycle(app),
         ..Default::default()
     };
 
     let mut pod_spec = PodSpec {
         automount_service_account_token: Some(false),
+        termination_grace_period_seconds: app
+            .spec
+            .lifecycle
+            .as_ref()
+            .and_then(|l| l.termination_grace_period_seconds),
         security_context: Some(pod_security),
-        containers: vec![container],
+        containers: std::iter::once(container)
+            .chain(app.spec.extra_containers.iter().cloned())
+            .collect(),
         volumes: Some(volumes),
         ..Default::default()
     };
@@ -225,6 +312,46 @@ pub fn build(app: &ServarrApp, image_overrides: &HashMap<String, ImageSpec>) ->
         pod_spec.node_selector = Some(node_selector);
     }
 
+    // Tolerations, affinity, and spread constraints are passed through as
+    // written; entries that aren't valid Kubernetes objects are dropped.
+    let scheduling = app.spec.scheduling.as_ref();
+    if let Some(s) = scheduling {
+        let tolerations: Vec<Toleration> = s
+            .tolerations
+            .iter()
+            .filter_map(|t| serde_json::from_value(t.clone()).ok())
+            .collect();
+        if !tolerations.is_empty() {
+            pod_spec.tolerations = Some(tolerations);
+        }
+        pod_spec.affinity = s
+            .affinity
+            .as_ref()
+            .and_then(|a| serde_json::from_value(a.clone()).ok());
+        let constraints: Vec<TopologySpreadConstraint> = s
+            .topology_spread_constraints
+            .iter()
+            .filter_map(|c| serde_json::from_value(c.clone()).ok())
+            .collect();
+        if !constraints.is_empty() {
+            pod_spec.topology_spread_constraints = Some(constraints);
+        }
+    }
+
+    // A MediaStack spreads its pods by their stack label. Only pods with
+    // spread constraints get it, so stacks without a spreadPolicy aren't
+    // rolled out for a label nothing selects on.
+    let mut pod_labels = selector_labels.clone();
+    if pod_spec.topology_spread_constraints.is_some()
+        && let Some(stack) = app
+            .metadata
+            .labels
+            .as_ref()
+            .and_then(|l| l.get(STACK_LABEL))
+    {
+        pod_labels.insert(STACK_LABEL.to_string(), stack.clone());
+    }
+
     let strategy = if has_host_port {
         Some(DeploymentStrategy {
             type_: Some("Recreate".to_string()),
@@ -234,16 +361,36 @@ pub fn build(app: &ServarrApp, image_overrides: &HashMap<String, ImageSpec>) ->
         None
     };
 
+    // Provenance goes on the Deployment rather than the pod template so that
+    // it never triggers a rollout by itself.
+    let provenance = BTreeMap::from([
+        (
+            IMAGE_SOURCE_ANNOTATION.to_string(),
+            image_source(app, image_overrides).to_string(),
+        ),
+        (
+            IMAGE_PINNED_BY_ANNOTATION.to_string(),
+            if image_spec.digest.is_empty() {
+                "tag"
+            } else {
+                "digest"
+            }
+            .to_string(),
+        ),
+    ]);
+
     Deployment {
         metadata: ObjectMeta {
             name: Some(name),
             namespace: Some(ns),
             labels: Some(labels.clone()),
+            annotations: Some(provenance),
             owner_references: Some(vec![common::owner_reference(app)]),
             ..Default::default()
         },
         spec: Some(DeploymentSpec {
-            replicas: Some(1),
+            // An autoscaled app's replica count belongs to its HPA.
+            replicas: crate::hpa::autoscaling(app).is_none().then_some(1),
             strategy,
             selector: LabelSelector {
                 match_labels: Some(selector_labels.clone()),
@@ -252,13 +399,16 @@ pub fn build(app: &ServarrApp, image_overrides: &HashMap<String, ImageSpec>) ->
             template: PodTemplateSpec {
                 metadata: Some({
                     let mut pod_meta = ObjectMeta {
-                        labels: Some(selector_labels),
+                        labels: Some(pod_labels),
                         ..Default::default()
                     };
                     let mut annotations = BTreeMap::new();
                     if let Some(checksum) = config_checksum(app) {
                         annotations.insert("servarr.dev/config-checksum".to_string(), checksum);
                     }
+                    if let Some(checksum) = definitions_checksum(app) {
+                        annotations.insert(DEFINITIONS_CHECKSUM_ANNOTATION.to_string(), checksum);
+                    }
                     // Exclude NFS volumes from Velero fs-backup
                     let nfs_volume_names: Vec<String> = persistence
                         .nfs_mounts
@@ -296,6 +446,65 @@ pub fn build(app: &ServarrApp, image_overrides: &HashMap<String, ImageSpec>) ->
     }
 }
 
+/// Label on the extra Deployments of a Tdarr node app, valued with the app
+/// name, so the ones left over after `nodeCount` goes down can be found.
+pub const WORKER_OF_LABEL: &str = "servarr.dev/worker-of";
+
+/// The Deployments of a Tdarr node app's extra nodes, `{app}-1` up to
+/// `nodeCount - 1`, copied from the app's own `deployment`. Each gets its own
+/// `app.kubernetes.io/instance` so its selector doesn't overlap the app's,
+/// and registers with Tdarr under its Deployment name. Empty for other apps.
+pub fn tdarr_workers(app: &ServarrApp, deployment: &Deployment) -> Vec<Deployment> {
+    let node_count = match app.spec.app_config {
+        Some(AppConfig::TdarrNode(ref c)) if app.spec.app == AppType::TdarrNode => c.node_count(),
+        _ => return Vec::new(),
+    };
+    let name = common::app_name(app);
+    (1..node_count)
+        .map(|i| {
+            let worker_name = format!("{name}-{i}");
+            let mut worker = deployment.clone();
+            worker.metadata.name = Some(worker_name.clone());
+            worker.metadata.resource_version = None;
+            worker
+                .metadata
+                .labels
+                .get_or_insert_default()
+                .insert(WORKER_OF_LABEL.into(), name.clone());
+            if let Some(labels) = worker.metadata.labels.as_mut() {
+                labels.insert("app.kubernetes.io/instance".into(), worker_name.clone());
+            }
+            if let Some(spec) = worker.spec.as_mut() {
+                for labels in [
+                    spec.selector.match_labels.as_mut(),
+                    spec.template
+                        .metadata
+                        .as_mut()
+                        .and_then(|m| m.labels.as_mut()),
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    labels.insert("app.kubernetes.io/instance".into(), worker_name.clone());
+                }
+                for container in spec
+                    .template
+                    .spec
+                    .iter_mut()
+                    .flat_map(|s| s.containers.iter_mut())
+                {
+                    for env in container.env.iter_mut().flatten() {
+                        if env.name == "nodeName" {
+                            env.value = Some(worker_name.clone());
+                        }
+                    }
+                }
+            }
+            worker
+        })
+        .collect()
+}
+
 fn build_container_ports(svc_spec: &ServiceSpec, app: &ServarrApp) -> Vec<ContainerPort> {
     let mut ports: Vec<ContainerPort> = svc_spec
         .ports
@@ -309,10 +518,8 @@ fn build_container_ports(svc_spec: &ServiceSpec, app: &ServarrApp) -> Vec<Contai
         })
         .collect();
 
-    // Transmission peer port
-    if let Some(AppConfig::Transmission(ref tc)) = app.spec.app_config
-        && let Some(ref peer) = tc.peer_port
-    {
+    // Torrent client peer port
+    if let Some(peer) = app.spec.app_config.as_ref().and_then(AppConfig::peer_port) {
         ports.push(ContainerPort {
             name: Some("peer-tcp".into()),
             container_port: peer.port,
@@ -360,6 +567,15 @@ fn build_volume_mounts(persistence: &PersistenceSpec, app: &ServarrApp) -> Vec<V
         });
     }
 
+    // Tdarr node transcode cache
+    if matches!(app.spec.app, AppType::TdarrNode) {
+        mounts.push(VolumeMount {
+            name: "transcode-cache".into(),
+            mount_path: "/temp".into(),
+            ..Default::default()
+        });
+    }
+
     // Transmission watch dir + scripts volume
     if matches!(app.spec.app, AppType::Transmission) {
         mounts.push(VolumeMount {
@@ -437,11 +653,52 @@ fn build_volume_mounts(persistence: &PersistenceSpec, app: &ServarrApp) -> Vec<V
                 });
             }
         }
+        // SFTP chroot: bind each user's volume under their root-owned chroot
+        // directory, which sshd requires, and add the Match blocks via
+        // panubo/sshd's /etc/entrypoint.d hook.
+        let chroot_users = crate::configmap::sftp_chroot_users(app);
+        for user in &chroot_users {
+            let Some(chroot) = user.sftp_chroot.as_ref() else {
+                continue;
+            };
+            let Some((volume, read_only)) = chroot_volume(persistence, &chroot.volume) else {
+                continue;
+            };
+            mounts.push(VolumeMount {
+                name: volume,
+                mount_path: format!("{}/{}/{}", SftpChroot::ROOT, user.name, chroot.volume),
+                sub_path: chroot.sub_path.clone(),
+                read_only: (read_only || chroot.read_only).then_some(true),
+                ..Default::default()
+            });
+        }
+        if !chroot_users.is_empty() {
+            mounts.push(VolumeMount {
+                name: "sftp-chroot".into(),
+                mount_path: format!("/etc/entrypoint.d/{}", crate::configmap::SFTP_CHROOT_SCRIPT),
+                sub_path: Some(crate::configmap::SFTP_CHROOT_SCRIPT.into()),
+                read_only: Some(true),
+                ..Default::default()
+            });
+        }
     }
 
     mounts
 }
 
+/// The pod volume backing persistence entry `name` (a volume or an NFS
+/// mount), and whether it is mounted read-only.
+fn chroot_volume(persistence: &PersistenceSpec, name: &str) -> Option<(String, bool)> {
+    if persistence.volumes.iter().any(|v| v.name == name) {
+        return Some((name.to_string(), false));
+    }
+    persistence
+        .nfs_mounts
+        .iter()
+        .find(|n| n.name == name)
+        .map(|n| (format!("nfs-{}", n.name), n.read_only))
+}
+
 fn build_volumes(app: &ServarrApp, persistence: &PersistenceSpec) -> Vec<Volume> {
     let mut volumes: Vec<Volume> = persistence
         .volumes
@@ -449,7 +706,7 @@ fn build_volumes(app: &ServarrApp, persistence: &PersistenceSpec) -> Vec<Volume>
         .map(|v| Volume {
             name: v.name.clone(),
             persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
-                claim_name: common::child_name(app, &v.name),
+                claim_name: common::claim_name(app, &v.name),
                 read_only: None,
             }),
             ..Default::default()
@@ -468,6 +725,15 @@ fn build_volumes(app: &ServarrApp, persistence: &PersistenceSpec) -> Vec<Volume>
         });
     }
 
+    // Tdarr node transcode cache, local to each node's pod
+    if matches!(app.spec.app, AppType::TdarrNode) {
+        volumes.push(Volume {
+            name: "transcode-cache".into(),
+            empty_dir: Some(Default::default()),
+            ..Default::default()
+        });
+    }
+
     // Transmission ConfigMap + watch dir
     if matches!(app.spec.app, AppType::Transmission) {
         volumes.push(Volume {
@@ -515,6 +781,55 @@ fn build_volumes(app: &ServarrApp, persistence: &PersistenceSpec) -> Vec<Volume>
         }
     }
 
+    // qBittorrent categories ConfigMap
+    if let Some(AppConfig::Qbittorrent(ref qc)) = app.spec.app_config
+        && !qc.categories.is_empty()
+    {
+        volumes.push(Volume {
+            name: "scripts".into(),
+            config_map: Some(ConfigMapVolumeSource {
+                name: common::app_name(app),
+                default_mode: Some(0o755),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+    }
+
+    // Bazarr API key script ConfigMap and the key Secret
+    if app.spec.app == AppType::Bazarr
+        && let Some((secret_name, key)) = app.spec.api_key_ref()
+    {
+        use k8s_openapi::api::core::v1::{KeyToPath, SecretVolumeSource};
+        volumes.push(Volume {
+            name: "scripts".into(),
+            config_map: Some(ConfigMapVolumeSource {
+                name: common::app_name(app),
+                default_mode: Some(0o755),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        volumes.push(Volume {
+            name: "api-key".into(),
+            secret: Some(SecretVolumeSource {
+                secret_name: Some(secret_name.into()),
+                items: Some(vec![KeyToPath {
+                    key: key.into(),
+                    path: "api-key".into(),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+    }
+
+    // Settings and merge script for the app's config file
+    if let Some(volume) = crate::config_file::volume(app) {
+        volumes.push(volume);
+    }
+
     // SABnzbd tar-unpack scripts ConfigMap
     if let Some(AppConfig::Sabnzbd(ref sc)) = app.spec.app_config
         && sc.tar_unpack
@@ -597,6 +912,17 @@ fn build_volumes(app: &ServarrApp, persistence: &PersistenceSpec) -> Vec<Volume>
                 ..Default::default()
             });
         }
+        if !crate::configmap::sftp_chroot_users(app).is_empty() {
+            volumes.push(Volume {
+                name: "sftp-chroot".into(),
+                config_map: Some(ConfigMapVolumeSource {
+                    name: common::child_name(app, "sftp-chroot"),
+                    default_mode: Some(0o755),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            });
+        }
         // Shell mode: per-user ~/.ssh PVC volumes
         for user in &sc.users {
             if user.mode == SshMode::Shell {
@@ -746,17 +1072,40 @@ fn build_env_vars(app: &ServarrApp, defaults: &AppDefaults, uid: i64, gid: i64)
         }
     }
 
+    // Tdarr node: the server to register with and the name to register as.
+    // Extra nodes from `nodeCount` get their own name in `tdarr_workers`.
+    if matches!(app.spec.app, AppType::TdarrNode) {
+        let config = match app.spec.app_config {
+            Some(AppConfig::TdarrNode(ref c)) => c.clone(),
+            _ => TdarrNodeConfig::default(),
+        };
+        for (name, value) in [
+            ("serverIP", config.server().to_string()),
+            ("serverPort", TDARR_SERVER_PORT.to_string()),
+            ("nodeName", common::app_name(app)),
+        ] {
+            env.retain(|e| e.name != name);
+            env.push(EnvVar {
+                name: name.into(),
+                value: Some(value),
+                ..Default::default()
+            });
+        }
+    }
+
     // .NET *arr app API key from Secret.
-    // Sonarr, Radarr, Lidarr, and Prowlarr all support setting their API key
-    // via the double-underscore ASP.NET Core env var override pattern.  When
-    // apiKeySecret is set the operator creates the Secret (if absent) and
-    // injects the value here so the app uses the operator-managed key from
-    // the moment it first starts.
-    if let Some(ref secret_name) = app.spec.api_key_secret {
+    // Sonarr, Radarr, Lidarr, Readarr, Whisparr, and Prowlarr all support
+    // setting their API key via the double-underscore ASP.NET Core env var
+    // override pattern.  When apiKeySecret is set the operator creates the
+    // Secret (if absent) and injects the value here so the app uses the
+    // operator-managed key from the moment it first starts.
+    if let Some((secret_name, key)) = app.spec.api_key_ref() {
         let apikey_env = match app.spec.app {
             AppType::Sonarr => Some("SONARR__AUTH__APIKEY"),
             AppType::Radarr => Some("RADARR__AUTH__APIKEY"),
             AppType::Lidarr => Some("LIDARR__AUTH__APIKEY"),
+            AppType::Readarr => Some("READARR__AUTH__APIKEY"),
+            AppType::Whisparr => Some("WHISPARR__AUTH__APIKEY"),
             AppType::Prowlarr => Some("PROWLARR__AUTH__APIKEY"),
             _ => None,
         };
@@ -765,8 +1114,8 @@ fn build_env_vars(app: &ServarrApp, defaults: &AppDefaults, uid: i64, gid: i64)
                 name: env_name.into(),
                 value_from: Some(EnvVarSource {
                     secret_key_ref: Some(SecretKeySelector {
-                        name: secret_name.clone(),
-                        key: "api-key".into(),
+                        name: secret_name.into(),
+                        key: key.into(),
                         optional: Some(false),
                     }),
                     ..Default::default()
@@ -776,6 +1125,59 @@ fn build_env_vars(app: &ServarrApp, defaults: &AppDefaults, uid: i64, gid: i64)
         }
     }
 
+    // Web UI authentication for the .NET *arr apps. With a method set the
+    // app skips its first-run authentication setup screen; the login itself
+    // is applied through the API (sync_auth in the controller).
+    if let Some(ref auth) = app.spec.auth
+        && let Some(prefix) = app.spec.app.auth_env_prefix()
+    {
+        for (suffix, value) in [
+            ("METHOD", auth.method.as_str()),
+            ("REQUIRED", auth.required()),
+        ] {
+            env.push(EnvVar {
+                name: format!("{prefix}__AUTH__{suffix}"),
+                value: Some(value.into()),
+                ..Default::default()
+            });
+        }
+    }
+
+    // Postgres connection for the .NET *arr apps, using the same
+    // double-underscore override pattern as the API key.
+    if let Some(ref db) = app.spec.database
+        && let Some(prefix) = app.spec.app.postgres_env_prefix()
+    {
+        let mut plain = vec![
+            ("HOST", db.host.clone()),
+            ("PORT", db.port.to_string()),
+            ("USER", db.user.clone()),
+            ("MAINDB", db.main_database.clone()),
+        ];
+        if let Some(ref log_db) = db.log_database {
+            plain.push(("LOGDB", log_db.clone()));
+        }
+        for (suffix, value) in plain {
+            env.push(EnvVar {
+                name: format!("{prefix}__POSTGRES__{suffix}"),
+                value: Some(value),
+                ..Default::default()
+            });
+        }
+        env.push(EnvVar {
+            name: format!("{prefix}__POSTGRES__PASSWORD"),
+            value_from: Some(EnvVarSource {
+                secret_key_ref: Some(SecretKeySelector {
+                    name: db.password_secret.clone(),
+                    key: db.password_key.clone(),
+                    optional: Some(false),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+    }
+
     if let Some(ref ac) = app.spec.admin_credentials {
         // Transmission: enable RPC authentication via the LSIO FILE__ mechanism.
         //
@@ -831,16 +1233,36 @@ fn build_env_vars(app: &ServarrApp, defaults: &AppDefaults, uid: i64, gid: i64)
         }
     }
 
+    // qBittorrent: the LSIO image passes WEBUI_PORT and TORRENTING_PORT to
+    // qbittorrent-nox as flags, which win over qBittorrent.conf.
+    if matches!(app.spec.app, AppType::Qbittorrent) {
+        let svc_spec = app.spec.service.as_ref().unwrap_or(&defaults.service);
+        if let Some(p) = svc_spec.ports.first() {
+            env.push(EnvVar {
+                name: "WEBUI_PORT".into(),
+                value: Some(p.container_port.unwrap_or(p.port).to_string()),
+                ..Default::default()
+            });
+        }
+        if let Some(peer) = app.spec.app_config.as_ref().and_then(AppConfig::peer_port) {
+            env.push(EnvVar {
+                name: "TORRENTING_PORT".into(),
+                value: Some(peer.port.to_string()),
+                ..Default::default()
+            });
+        }
+    }
+
     // Transmission auth from secret
     if let Some(AppConfig::Transmission(ref tc)) = app.spec.app_config
-        && let Some(ref auth) = tc.auth
+        && let Some((secret_name, user_key, pass_key)) = tc.rpc_login()
     {
         env.push(EnvVar {
             name: "USER".into(),
             value_from: Some(EnvVarSource {
                 secret_key_ref: Some(SecretKeySelector {
-                    name: auth.secret_name.clone(),
-                    key: "USER".into(),
+                    name: secret_name.into(),
+                    key: user_key.into(),
                     optional: Some(false),
                 }),
                 ..Default::default()
@@ -851,8 +1273,8 @@ fn build_env_vars(app: &ServarrApp, defaults: &AppDefaults, uid: i64, gid: i64)
             name: "PASS".into(),
             value_from: Some(EnvVarSource {
                 secret_key_ref: Some(SecretKeySelector {
-                    name: auth.secret_name.clone(),
-                    key: "PASS".into(),
+                    name: secret_name.into(),
+                    key: pass_key.into(),
                     optional: Some(false),
                 }),
                 ..Default::default()
@@ -1030,6 +1452,7 @@ fn build_startup_probe(liveness_config: &ProbeConfig, svc_spec: &ServiceSpec) ->
 
 fn build_init_containers(
     app: &ServarrApp,
+    persistence: &PersistenceSpec,
     image: &str,
     security_context: &SecurityContext,
     uid: i64,
@@ -1037,6 +1460,15 @@ fn build_init_containers(
 ) -> Vec<Container> {
     let mut init = Vec::new();
 
+    // NFS mount check, first so a bad mount fails before anything uses it
+    init.extend(crate::mount_check::init_container(
+        persistence,
+        image,
+        security_context,
+        uid,
+        gid,
+    ));
+
     // Transmission settings apply init container
     if matches!(app.spec.app, AppType::Transmission) {
         // Run as the app uid/gid explicitly.  The LinuxServer security profile drops
@@ -1061,6 +1493,19 @@ fn build_init_containers(
                 ..Default::default()
             },
         ];
+        // With directories configured the script also creates them, so it
+        // needs the data volumes they live on.
+        if let Some(AppConfig::Transmission(TransmissionConfig {
+            directories: Some(_),
+            ..
+        })) = app.spec.app_config
+        {
+            apply_settings_mounts.extend(
+                build_volume_mounts(persistence, app)
+                    .into_iter()
+                    .filter(|m| m.mount_path != "/config" && m.read_only != Some(true)),
+            );
+        }
         if app.spec.admin_credentials.is_some() {
             apply_settings_mounts.push(VolumeMount {
                 name: "admin-credentials".into(),
@@ -1079,6 +1524,85 @@ fn build_init_containers(
         });
     }
 
+    // qBittorrent categories init container; like Transmission's it runs
+    // as the app uid/gid and mounts the data volumes to create save paths.
+    if let Some(AppConfig::Qbittorrent(ref qc)) = app.spec.app_config
+        && !qc.categories.is_empty()
+    {
+        let mut apply_settings_mounts = vec![
+            VolumeMount {
+                name: "config".into(),
+                mount_path: "/config".into(),
+                ..Default::default()
+            },
+            VolumeMount {
+                name: "scripts".into(),
+                mount_path: "/scripts".into(),
+                read_only: Some(true),
+                ..Default::default()
+            },
+        ];
+        apply_settings_mounts.extend(
+            build_volume_mounts(persistence, app)
+                .into_iter()
+                .filter(|m| m.mount_path != "/config" && m.read_only != Some(true)),
+        );
+        init.push(Container {
+            name: "apply-settings".into(),
+            image: Some(image.to_string()),
+            command: Some(vec!["/bin/sh".into(), "/scripts/apply-settings.sh".into()]),
+            security_context: Some(SecurityContext {
+                run_as_user: Some(uid),
+                run_as_group: Some(gid),
+                ..security_context.clone()
+            }),
+            volume_mounts: Some(apply_settings_mounts),
+            ..Default::default()
+        });
+    }
+
+    // Bazarr API key init container, run as the app uid/gid so config.yaml
+    // stays writable by Bazarr when the script creates it.
+    if app.spec.app == AppType::Bazarr && app.spec.api_key_ref().is_some() {
+        init.push(Container {
+            name: "apply-api-key".into(),
+            image: Some(image.to_string()),
+            command: Some(vec!["/bin/sh".into(), "/scripts/apply-api-key.sh".into()]),
+            security_context: Some(SecurityContext {
+                run_as_user: Some(uid),
+                run_as_group: Some(gid),
+                ..security_context.clone()
+            }),
+            volume_mounts: Some(vec![
+                VolumeMount {
+                    name: "config".into(),
+                    mount_path: "/config".into(),
+                    ..Default::default()
+                },
+                VolumeMount {
+                    name: "scripts".into(),
+                    mount_path: "/scripts".into(),
+                    read_only: Some(true),
+                    ..Default::default()
+                },
+                VolumeMount {
+                    name: "api-key".into(),
+                    mount_path: "/run/secrets/api-key".into(),
+                    read_only: Some(true),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        });
+    }
+
+    // Settings merged into the app's config file
+    if let Some(container) =
+        crate::config_file::init_container(app, image, security_context, uid, gid)
+    {
+        init.push(container);
+    }
+
     // SABnzbd tar-unpack init container (installs tools)
     if let Some(AppConfig::Sabnzbd(ref sc)) = app.spec.app_config
         && sc.tar_unpack
@@ -1270,6 +1794,61 @@ apk add --no-cache rsync >/dev/null 2>&1 || true
     }
 }
 
+/// Stops every torrent so Transmission writes its resume files before it
+/// gets SIGTERM. Credentials come from the USER/PASS env vars set when
+/// auth is enabled.
+const TRANSMISSION_PRE_STOP: &str = r#"transmission-remote 127.0.0.1:9091 ${USER:+--auth "$USER:$PASS"} --torrent all --stop >/dev/null 2>&1; sleep 2"#;
+
+/// The preStop command an app type gets unless `spec.lifecycle` says
+/// otherwise. Transmission stops its torrents; other linuxserver.io images
+/// wait a few seconds, so the Service stops routing to the pod before the
+/// app shuts down. Images without a shell get none.
+pub fn default_pre_stop(app_type: &AppType) -> Option<Vec<String>> {
+    match app_type {
+        AppType::Transmission => Some(vec![
+            "/bin/sh".into(),
+            "-c".into(),
+            TRANSMISSION_PRE_STOP.into(),
+        ]),
+        _ if matches!(
+            AppDefaults::for_app(app_type).security.profile_type,
+            SecurityProfileType::LinuxServer
+        ) =>
+        {
+            Some(vec!["sleep".into(), "5".into()])
+        }
+        _ => None,
+    }
+}
+
+/// The app container's hooks: `spec.lifecycle`'s commands, with the app
+/// type's default preStop hook when none is given.
+fn build_lifecycle(app: &ServarrApp) -> Option<Lifecycle> {
+    let spec = app.spec.lifecycle.clone().unwrap_or_default();
+    let exec = |command: Vec<String>| LifecycleHandler {
+        exec: Some(ExecAction {
+            command: Some(command),
+        }),
+        ..Default::default()
+    };
+    let pre_stop = if !spec.pre_stop.is_empty() {
+        Some(spec.pre_stop)
+    } else if spec.default_hooks {
+        default_pre_stop(&app.spec.app)
+    } else {
+        None
+    };
+    let post_start = Some(spec.post_start).filter(|c| !c.is_empty());
+    if pre_stop.is_none() && post_start.is_none() {
+        return None;
+    }
+    Some(Lifecycle {
+        post_start: post_start.map(exec),
+        pre_stop: pre_stop.map(exec),
+        ..Default::default()
+    })
+}
+
 /// For Transmission with auth enabled, automatically switch to exec probes
 /// that use curl with credentials, matching the legacy Helm chart behavior.
 fn maybe_override_probes_for_auth(app: &ServarrApp, probes: &ProbeSpec) -> ProbeSpec {
@@ -1279,7 +1858,7 @@ fn maybe_override_probes_for_auth(app: &ServarrApp, probes: &ProbeSpec) -> Probe
             .spec
             .app_config
             .as_ref()
-            .is_some_and(|c| matches!(c, AppConfig::Transmission(tc) if tc.auth.is_some()))
+            .is_some_and(|c| matches!(c, AppConfig::Transmission(tc) if tc.rpc_login().is_some()))
             || app.spec.admin_credentials.is_some())
         && !matches!(probes.liveness.probe_type, ProbeType::Exec)
     {
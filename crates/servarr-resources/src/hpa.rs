@@ -0,0 +1,69 @@
+use k8s_openapi::api::autoscaling::v2::{
+    CrossVersionObjectReference, HorizontalPodAutoscaler, HorizontalPodAutoscalerSpec, MetricSpec,
+    MetricTarget, ResourceMetricSource,
+};
+use servarr_crds::{AutoscalingSpec, ServarrApp, WorkloadType};
+
+use crate::common;
+
+/// The app's `spec.autoscaling`, if its app type supports more than one
+/// replica. The workload leaves its replica count to the autoscaler then.
+pub fn autoscaling(app: &ServarrApp) -> Option<&AutoscalingSpec> {
+    app.spec
+        .autoscaling
+        .as_ref()
+        .filter(|_| app.spec.app.supports_autoscaling())
+}
+
+fn utilization(resource: &str, percent: i32) -> MetricSpec {
+    MetricSpec {
+        type_: "Resource".into(),
+        resource: Some(ResourceMetricSource {
+            name: resource.into(),
+            target: MetricTarget {
+                type_: "Utilization".into(),
+                average_utilization: Some(percent),
+                ..Default::default()
+            },
+        }),
+        ..Default::default()
+    }
+}
+
+/// Build a HorizontalPodAutoscaler (autoscaling/v2) scaling the app's
+/// Deployment or StatefulSet. Returns `None` without `spec.autoscaling`.
+pub fn build(app: &ServarrApp) -> Option<HorizontalPodAutoscaler> {
+    let spec = autoscaling(app)?;
+
+    let mut metrics = Vec::new();
+    if let Some(cpu) = spec.target_cpu_utilization_percentage {
+        metrics.push(utilization("cpu", cpu));
+    }
+    if let Some(memory) = spec.target_memory_utilization_percentage {
+        metrics.push(utilization("memory", memory));
+    }
+    if metrics.is_empty() {
+        metrics.push(utilization("cpu", AutoscalingSpec::DEFAULT_CPU_UTILIZATION));
+    }
+
+    let kind = match app.spec.workload_type.unwrap_or_default() {
+        WorkloadType::Deployment => "Deployment",
+        WorkloadType::StatefulSet => "StatefulSet",
+    };
+
+    Some(HorizontalPodAutoscaler {
+        metadata: common::metadata(app, ""),
+        spec: Some(HorizontalPodAutoscalerSpec {
+            scale_target_ref: CrossVersionObjectReference {
+                api_version: Some("apps/v1".into()),
+                kind: kind.into(),
+                name: common::app_name(app),
+            },
+            min_replicas: Some(spec.min_replicas),
+            max_replicas: spec.max_replicas,
+            metrics: Some(metrics),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
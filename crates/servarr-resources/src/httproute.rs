@@ -36,6 +36,23 @@ pub fn build(app: &ServarrApp) -> Option<DynamicObject> {
 
     let hostnames: Vec<serde_json::Value> = gateway.hosts.iter().map(|h| json!(h)).collect();
 
+    // The app's own Service gets whatever share the extra backends leave.
+    let mut backend_refs = vec![json!({
+        "name": name,
+        "port": first_port,
+    })];
+    if !gateway.backends.is_empty() {
+        let others: i32 = gateway.backends.iter().map(|b| b.weight).sum();
+        backend_refs[0]["weight"] = json!((100 - others).max(0));
+        backend_refs.extend(gateway.backends.iter().map(|b| {
+            json!({
+                "name": b.name,
+                "port": b.port.unwrap_or(first_port),
+                "weight": b.weight,
+            })
+        }));
+    }
+
     let route = json!({
         "apiVersion": "gateway.networking.k8s.io/v1",
         "kind": "HTTPRoute",
@@ -49,10 +66,7 @@ pub fn build(app: &ServarrApp) -> Option<DynamicObject> {
             "parentRefs": parent_refs,
             "hostnames": hostnames,
             "rules": [{
-                "backendRefs": [{
-                    "name": name,
-                    "port": first_port,
-                }],
+                "backendRefs": backend_refs,
             }],
         },
     });
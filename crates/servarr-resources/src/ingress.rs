@@ -0,0 +1,57 @@
+use k8s_openapi::api::networking::v1::{
+    HTTPIngressPath, HTTPIngressRuleValue, Ingress, IngressBackend, IngressRule,
+    IngressServiceBackend, IngressSpec as K8sIngressSpec, IngressTLS, ServiceBackendPort,
+};
+use servarr_crds::{AppDefaults, ServarrApp};
+
+use crate::common;
+
+/// Build an Ingress (networking.k8s.io/v1) routing `spec.ingress.host` to
+/// the app's first Service port. Returns `None` without `spec.ingress`.
+pub fn build(app: &ServarrApp) -> Option<Ingress> {
+    let ingress = app.spec.ingress.as_ref()?;
+
+    let defaults = AppDefaults::for_app(&app.spec.app);
+    let svc_spec = app.spec.service.as_ref().unwrap_or(&defaults.service);
+    let first_port = svc_spec.ports.first().map(|p| p.port).unwrap_or(80);
+    let name = common::app_name(app);
+
+    let mut metadata = common::metadata(app, "");
+    if !ingress.annotations.is_empty() {
+        metadata.annotations = Some(ingress.annotations.clone());
+    }
+
+    Some(Ingress {
+        metadata,
+        spec: Some(K8sIngressSpec {
+            ingress_class_name: ingress.ingress_class_name.clone(),
+            rules: Some(vec![IngressRule {
+                host: Some(ingress.host.clone()),
+                http: Some(HTTPIngressRuleValue {
+                    paths: vec![HTTPIngressPath {
+                        path: Some(ingress.path().to_string()),
+                        path_type: "Prefix".into(),
+                        backend: IngressBackend {
+                            service: Some(IngressServiceBackend {
+                                name: name.clone(),
+                                port: Some(ServiceBackendPort {
+                                    number: Some(first_port),
+                                    ..Default::default()
+                                }),
+                            }),
+                            ..Default::default()
+                        },
+                    }],
+                }),
+            }]),
+            tls: ingress.tls_secret_name.as_ref().map(|secret| {
+                vec![IngressTLS {
+                    hosts: Some(vec![ingress.host.clone()]),
+                    secret_name: Some(secret.clone()),
+                }]
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
@@ -0,0 +1,165 @@
+use k8s_openapi::api::batch::v1::{CronJob, CronJobSpec, JobSpec, JobTemplateSpec};
+use k8s_openapi::api::core::v1::{
+    Affinity, Container, EmptyDirVolumeSource, EnvVar, PersistentVolumeClaimVolumeSource,
+    PodAffinity, PodAffinityTerm, PodSpec, PodTemplateSpec, SecurityContext, Volume, VolumeMount,
+};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, ObjectMeta};
+use servarr_crds::{AppDefaults, AppType, IntegrityCheckSpec, ServarrApp};
+use std::collections::BTreeMap;
+
+use crate::common;
+
+const SUFFIX: &str = "integrity-check";
+const DEFAULT_IMAGE: &str = "keinos/sqlite3";
+const DEFAULT_TAG: &str = "3.46.1";
+
+/// Label carried by the check's Jobs and pods, valued with the app name.
+/// The pods must not match the app's selector labels, or its Service would
+/// route to them.
+pub const JOB_LABEL: &str = "servarr.dev/integrity-check";
+
+/// Copies the database with SQLite's online backup API, which gives a
+/// consistent snapshot while the app keeps writing, and checks the copy.
+/// A failing run's output becomes its termination message
+/// (`FallbackToLogsOnError`), so the operator can report it without
+/// reading logs.
+const SCRIPT: &str = r#"set -u
+sqlite3 "$DB" ".backup /tmp/check.db" || exit 1
+result=$(sqlite3 /tmp/check.db "PRAGMA integrity_check" 2>&1)
+echo "$result"
+[ "$result" = ok ]
+"#;
+
+pub fn cronjob_name(app: &ServarrApp) -> String {
+    common::child_name(app, SUFFIX)
+}
+
+/// Label selector for the Jobs the CronJob of `app` creates.
+pub fn job_selector(app: &ServarrApp) -> String {
+    format!("{JOB_LABEL}={}", common::app_name(app))
+}
+
+/// The SQLite database under `/config` of an app type that keeps one.
+pub fn database_file(app_type: &AppType) -> Option<&'static str> {
+    match app_type {
+        AppType::Sonarr => Some("sonarr.db"),
+        AppType::Radarr => Some("radarr.db"),
+        AppType::Lidarr => Some("lidarr.db"),
+        AppType::Readarr => Some("readarr.db"),
+        AppType::Whisparr => Some("whisparr2.db"),
+        AppType::Prowlarr => Some("prowlarr.db"),
+        _ => None,
+    }
+}
+
+/// A CronJob that runs `PRAGMA integrity_check` against a copy of the app's
+/// database on `check.schedule`, failing its Job when the result is not
+/// `ok`. Like the restore staging Job it runs as the app's uid/gid on the
+/// app pod's node, so a ReadWriteOnce config volume can be mounted. `None`
+/// for app types without a SQLite database.
+pub fn build_cronjob(app: &ServarrApp, check: &IntegrityCheckSpec) -> Option<CronJob> {
+    let db = database_file(&app.spec.app)?;
+    let defaults = AppDefaults::for_app(&app.spec.app);
+    let uid = app.spec.uid.unwrap_or(defaults.uid);
+    let gid = app.spec.gid.unwrap_or(defaults.gid);
+    let image = match check.image {
+        Some(ref img) if !img.tag.is_empty() => format!("{}:{}", img.repository, img.tag),
+        Some(ref img) => format!("{}:latest", img.repository),
+        None => format!("{DEFAULT_IMAGE}:{DEFAULT_TAG}"),
+    };
+    let job_labels = BTreeMap::from([(JOB_LABEL.to_string(), common::app_name(app))]);
+
+    let pod_spec = PodSpec {
+        restart_policy: Some("Never".into()),
+        affinity: Some(Affinity {
+            pod_affinity: Some(PodAffinity {
+                required_during_scheduling_ignored_during_execution: Some(vec![PodAffinityTerm {
+                    label_selector: Some(LabelSelector {
+                        match_labels: Some(common::selector_labels(app)),
+                        ..Default::default()
+                    }),
+                    topology_key: "kubernetes.io/hostname".into(),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        containers: vec![Container {
+            name: "sqlite".into(),
+            image: Some(image),
+            image_pull_policy: check.image.as_ref().map(|img| img.pull_policy.clone()),
+            command: Some(vec!["sh".into(), "-c".into(), SCRIPT.into()]),
+            termination_message_policy: Some("FallbackToLogsOnError".into()),
+            env: Some(vec![EnvVar {
+                name: "DB".into(),
+                value: Some(format!("/config/{db}")),
+                ..Default::default()
+            }]),
+            security_context: Some(SecurityContext {
+                run_as_user: Some(uid),
+                run_as_group: Some(gid),
+                ..Default::default()
+            }),
+            volume_mounts: Some(vec![
+                VolumeMount {
+                    name: "config".into(),
+                    mount_path: "/config".into(),
+                    ..Default::default()
+                },
+                VolumeMount {
+                    name: "tmp".into(),
+                    mount_path: "/tmp".into(),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        }],
+        volumes: Some(vec![
+            Volume {
+                name: "config".into(),
+                persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
+                    claim_name: common::claim_name(app, "config"),
+                    read_only: Some(false),
+                }),
+                ..Default::default()
+            },
+            Volume {
+                name: "tmp".into(),
+                empty_dir: Some(EmptyDirVolumeSource::default()),
+                ..Default::default()
+            },
+        ]),
+        ..Default::default()
+    };
+
+    Some(CronJob {
+        metadata: common::metadata(app, SUFFIX),
+        spec: Some(CronJobSpec {
+            schedule: check.schedule.clone(),
+            concurrency_policy: Some("Forbid".into()),
+            successful_jobs_history_limit: Some(1),
+            failed_jobs_history_limit: Some(1),
+            job_template: JobTemplateSpec {
+                metadata: Some(ObjectMeta {
+                    labels: Some(job_labels.clone()),
+                    ..Default::default()
+                }),
+                spec: Some(JobSpec {
+                    // A corrupt database fails the same way every time.
+                    backoff_limit: Some(0),
+                    template: PodTemplateSpec {
+                        metadata: Some(ObjectMeta {
+                            labels: Some(job_labels),
+                            ..Default::default()
+                        }),
+                        spec: Some(pod_spec),
+                    },
+                    ..Default::default()
+                }),
+            },
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
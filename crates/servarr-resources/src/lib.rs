@@ -1,11 +1,26 @@
 pub mod certificate;
 pub mod common;
+pub mod config_file;
 pub mod configmap;
+pub mod connections;
+pub mod credential;
 pub mod deployment;
+pub mod hpa;
 pub mod httproute;
+pub mod ingress;
+pub mod integrity_check;
+pub mod mount_check;
 pub mod networkpolicy;
 pub mod nfs_server;
+pub mod object_storage;
+pub mod patches;
+pub mod postgres;
 pub mod pvc;
+pub mod rclone;
+pub mod restore;
 pub mod secret;
 pub mod service;
+pub mod statefulset;
+pub mod storage_migration;
 pub mod tcproute;
+pub mod volume_snapshot;
@@ -0,0 +1,79 @@
+//! The `persistence.verifyMounts` init container, which checks the app's
+//! NFS mounts before it starts. An export that mounts but can't be read,
+//! or a share the app's uid can't write to, otherwise only shows up as an
+//! empty library or failing imports.
+
+use k8s_openapi::api::core::v1::{Container, SecurityContext, VolumeMount};
+use servarr_crds::PersistenceSpec;
+
+/// Name of the init container, which the operator looks for in pod status
+/// to report the `MountsVerified` condition.
+pub const CONTAINER_NAME: &str = "verify-mounts";
+
+/// Takes `rw:<path>` and `ro:<path>` arguments. A failing run's output
+/// becomes its termination message (`FallbackToLogsOnError`), so the
+/// operator can report which mounts failed without reading logs.
+const SCRIPT: &str = r#"set -u
+failed=""
+for mount in "$@"; do
+  mode=${mount%%:*}
+  path=${mount#*:}
+  probe="$path/.servarr-verify-${HOSTNAME:-pod}"
+  if ! ls "$path" >/dev/null 2>&1; then
+    failed="$failed $path (not readable)"
+  elif [ "$mode" = rw ] && ! { touch "$probe" && rm -f "$probe"; } 2>/dev/null; then
+    failed="$failed $path (not writable)"
+  fi
+done
+[ -z "$failed" ] && exit 0
+echo "mount check failed:$failed"
+exit 1
+"#;
+
+/// The init container checking `persistence`'s NFS mounts, run from the
+/// app image as the app's uid/gid so it sees the permissions the app will.
+/// `None` unless `verifyMounts` is set and there are NFS mounts.
+pub fn init_container(
+    persistence: &PersistenceSpec,
+    image: &str,
+    security_context: &SecurityContext,
+    uid: i64,
+    gid: i64,
+) -> Option<Container> {
+    if !persistence.verify_mounts || persistence.nfs_mounts.is_empty() {
+        return None;
+    }
+    let mut command = vec![
+        "/bin/sh".to_string(),
+        "-c".into(),
+        SCRIPT.into(),
+        CONTAINER_NAME.into(),
+    ];
+    command.extend(persistence.nfs_mounts.iter().map(|m| {
+        let mode = if m.read_only { "ro" } else { "rw" };
+        format!("{mode}:{}", m.mount_path)
+    }));
+    let volume_mounts = persistence
+        .nfs_mounts
+        .iter()
+        .map(|m| VolumeMount {
+            name: format!("nfs-{}", m.name),
+            mount_path: m.mount_path.clone(),
+            read_only: m.read_only.then_some(true),
+            ..Default::default()
+        })
+        .collect();
+    Some(Container {
+        name: CONTAINER_NAME.into(),
+        image: Some(image.to_string()),
+        command: Some(command),
+        security_context: Some(SecurityContext {
+            run_as_user: Some(uid),
+            run_as_group: Some(gid),
+            ..security_context.clone()
+        }),
+        termination_message_policy: Some("FallbackToLogsOnError".into()),
+        volume_mounts: Some(volume_mounts),
+        ..Default::default()
+    })
+}
@@ -51,6 +51,32 @@ pub fn build(app: &ServarrApp) -> NetworkPolicy {
         }
     }
 
+    // rclone sidecars: allow HTTPS to the internet so they can reach their
+    // cloud remotes, without opening private ranges.
+    if !app.spec.rclone_mounts.is_empty() {
+        let except: Vec<&str> = if config.denied_cidr_blocks.is_empty() {
+            DEFAULT_DENIED_CIDRS.to_vec()
+        } else {
+            config
+                .denied_cidr_blocks
+                .iter()
+                .map(String::as_str)
+                .collect()
+        };
+        config.custom_egress_rules.push(serde_json::json!({
+            "to": [{
+                "ipBlock": {
+                    "cidr": "0.0.0.0/0",
+                    "except": except
+                }
+            }],
+            "ports": [{
+                "protocol": "TCP",
+                "port": 443
+            }]
+        }));
+    }
+
     let app_ports: Vec<NetworkPolicyPort> = svc_spec
         .ports
         .iter()
@@ -132,6 +158,40 @@ fn build_ingress_rules(
         }
     }
 
+    // Allow from the other namespaces of the app's stack on app ports
+    if !config.allowed_namespaces.is_empty() {
+        rules.push(NetworkPolicyIngressRule {
+            from: Some(namespace_peers(&config.allowed_namespaces)),
+            ports: Some(app_ports.to_vec()),
+        });
+    }
+
+    // Allow from the ingress controller's namespace when ingress names it
+    if let Some(controller_ns) = app
+        .spec
+        .ingress
+        .as_ref()
+        .and_then(|i| i.controller_namespace.as_ref())
+    {
+        rules.push(NetworkPolicyIngressRule {
+            from: Some(vec![NetworkPolicyPeer {
+                namespace_selector: Some(LabelSelector {
+                    match_labels: Some(
+                        [(
+                            "kubernetes.io/metadata.name".to_string(),
+                            controller_ns.clone(),
+                        )]
+                        .into_iter()
+                        .collect(),
+                    ),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }]),
+            ports: Some(app_ports.to_vec()),
+        });
+    }
+
     // SSH bastion: allow SSH ingress from anywhere
     if matches!(app.spec.app, AppType::SshBastion) {
         rules.push(NetworkPolicyIngressRule {
@@ -146,10 +206,8 @@ fn build_ingress_rules(
         });
     }
 
-    // Allow peer port ingress from anywhere (Transmission torrent peers)
-    if let Some(AppConfig::Transmission(ref tc)) = app.spec.app_config
-        && let Some(ref peer) = tc.peer_port
-    {
+    // Allow peer port ingress from anywhere (torrent client peers)
+    if let Some(peer) = app.spec.app_config.as_ref().and_then(AppConfig::peer_port) {
         rules.push(NetworkPolicyIngressRule {
             from: Some(vec![NetworkPolicyPeer {
                 ip_block: Some(IPBlock {
@@ -188,6 +246,14 @@ fn build_egress_rules(config: &NetworkPolicyConfig) -> Vec<NetworkPolicyEgressRu
         ports: None,
     });
 
+    // Allow egress to the other namespaces of the app's stack
+    if !config.allowed_namespaces.is_empty() {
+        rules.push(NetworkPolicyEgressRule {
+            to: Some(namespace_peers(&config.allowed_namespaces)),
+            ports: None,
+        });
+    }
+
     // Allow DNS egress to kube-dns
     if config.allow_dns {
         rules.push(NetworkPolicyEgressRule {
@@ -250,3 +316,21 @@ fn build_egress_rules(config: &NetworkPolicyConfig) -> Vec<NetworkPolicyEgressRu
 
     rules
 }
+
+/// One peer per namespace, selected by its `kubernetes.io/metadata.name` label.
+fn namespace_peers(namespaces: &[String]) -> Vec<NetworkPolicyPeer> {
+    namespaces
+        .iter()
+        .map(|ns| NetworkPolicyPeer {
+            namespace_selector: Some(LabelSelector {
+                match_labels: Some(
+                    [("kubernetes.io/metadata.name".to_string(), ns.clone())]
+                        .into_iter()
+                        .collect(),
+                ),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .collect()
+}
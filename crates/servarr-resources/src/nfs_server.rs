@@ -1,7 +1,10 @@
 use k8s_openapi::api::apps::v1::{StatefulSet, StatefulSetSpec};
+use k8s_openapi::api::batch::v1::{CronJob, CronJobSpec, JobSpec, JobTemplateSpec};
 use k8s_openapi::api::core::v1::{
-    Container, ContainerPort, EnvVar, PersistentVolumeClaim, PersistentVolumeClaimSpec, PodSpec,
-    PodTemplateSpec, SecurityContext, VolumeMount, VolumeResourceRequirements,
+    Affinity, Container, ContainerPort, EnvVar, PersistentVolumeClaim, PersistentVolumeClaimSpec,
+    PersistentVolumeClaimVolumeSource, PodAffinity, PodAffinityTerm, PodSpec, PodTemplateSpec,
+    SecretVolumeSource, SecurityContext, TopologySpreadConstraint, Volume, VolumeMount,
+    VolumeResourceRequirements,
 };
 use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, ObjectMeta, OwnerReference};
@@ -9,9 +12,14 @@ use k8s_openapi::{
     api::core::v1::{Service, ServicePort, ServiceSpec},
     apimachinery::pkg::util::intstr::IntOrString,
 };
-use servarr_crds::NfsServerSpec;
+use kube::api::DynamicObject;
+use servarr_crds::{
+    ImageSpec, NfsExport, NfsRsyncSpec, NfsServerSpec, NfsSnapshotSpec, SpreadPolicy,
+};
 use std::collections::BTreeMap;
 
+use crate::storage_migration::COPY_IMAGE;
+
 const MANAGED_BY: &str = "servarr-operator";
 const NFS_PORT: i32 = 2049;
 const PORTMAPPER_PORT: i32 = 111;
@@ -26,19 +34,73 @@ const EXPORT_DIR: &str = "/nfsshare";
 // it relative to /nfsshare.
 const EXPORT_OPTS: &str = "*(rw,async,no_subtree_check,no_auth_nlm,insecure,no_root_squash,fsid=0)";
 const DATA_VOLUME: &str = "data";
+const BACKUP_COMPONENT: &str = "nfs-backup";
+const SNAPSHOT_COMPONENT: &str = "nfs-snapshot";
+// rsync runs from a shell so the host key options can depend on whether the
+// Secret carries known_hosts. The destination comes in through the
+// environment rather than being spliced into the script.
+const RSYNC_SCRIPT: &str = r#"if [ -f /ssh/known_hosts ]; then
+  HOSTS="-o UserKnownHostsFile=/ssh/known_hosts -o StrictHostKeyChecking=yes"
+else
+  HOSTS="-o UserKnownHostsFile=/tmp/known_hosts -o StrictHostKeyChecking=accept-new"
+fi
+exec rsync -aH --numeric-ids $RSYNC_DELETE \
+  -e "ssh -p $SSH_PORT -i /ssh/ssh-privatekey $HOSTS" /data/ "$RSYNC_DEST"
+"#;
 
 fn resource_name(stack_name: &str) -> String {
     format!("{stack_name}-nfs-server")
 }
 
-fn labels(stack_name: &str) -> BTreeMap<String, String> {
+/// Name of the PVC the StatefulSet's volume claim template creates.
+pub fn data_claim_name(stack_name: &str) -> String {
+    format!("{DATA_VOLUME}-{}-0", resource_name(stack_name))
+}
+
+/// Name of the PVC backing the export `export` when it has a size of its own.
+pub fn export_claim_name(stack_name: &str, export: &str) -> String {
+    format!("{stack_name}-nfs-{export}")
+}
+
+pub fn backup_name(stack_name: &str) -> String {
+    format!("{stack_name}-nfs-backup")
+}
+
+fn component_labels(stack_name: &str, component: &str) -> BTreeMap<String, String> {
     BTreeMap::from([
         ("servarr.dev/stack".into(), stack_name.to_string()),
-        ("servarr.dev/component".into(), COMPONENT.to_string()),
+        ("servarr.dev/component".into(), component.to_string()),
         ("app.kubernetes.io/managed-by".into(), MANAGED_BY.into()),
     ])
 }
 
+fn labels(stack_name: &str) -> BTreeMap<String, String> {
+    component_labels(stack_name, COMPONENT)
+}
+
+/// Label selector for the rsync backup Jobs of a stack.
+pub fn backup_selector(stack_name: &str) -> String {
+    format!("servarr.dev/stack={stack_name},servarr.dev/component={BACKUP_COMPONENT}")
+}
+
+/// Label selector for the VolumeSnapshots of a stack's NFS server.
+pub fn snapshot_selector(stack_name: &str) -> String {
+    format!("servarr.dev/stack={stack_name},servarr.dev/component={SNAPSHOT_COMPONENT}")
+}
+
+fn image_ref(image: Option<&ImageSpec>, default: &str) -> String {
+    image
+        .map(|img| {
+            let tag = if img.tag.is_empty() {
+                "latest".to_string()
+            } else {
+                img.tag.clone()
+            };
+            format!("{}:{tag}", img.repository)
+        })
+        .unwrap_or_else(|| default.to_string())
+}
+
 fn selector_labels(stack_name: &str) -> BTreeMap<String, String> {
     BTreeMap::from([
         ("servarr.dev/stack".into(), stack_name.to_string()),
@@ -50,29 +112,75 @@ fn selector_labels(stack_name: &str) -> BTreeMap<String, String> {
 ///
 /// The StatefulSet runs a single NFS server pod backed by a PVC whose size
 /// and storage class are taken from `nfs`. The pod exports `EXPORT_DIR` via
-/// NFS on port 2049.
+/// NFS on port 2049, plus each of `nfs.exports` with its own options. With a
+/// `spread` policy the pod is placed by the same topologySpreadConstraint as
+/// the stack's apps.
 pub fn build_statefulset(
     stack_name: &str,
     namespace: &str,
     nfs: &NfsServerSpec,
+    spread: Option<&SpreadPolicy>,
     owner_ref: OwnerReference,
 ) -> StatefulSet {
     let name = resource_name(stack_name);
     let labels = labels(stack_name);
     let selector = selector_labels(stack_name);
 
-    let image = nfs
-        .image
-        .as_ref()
-        .map(|img| {
-            let tag = if img.tag.is_empty() {
-                "latest".to_string()
-            } else {
-                img.tag.clone()
-            };
-            format!("{}:{tag}", img.repository)
-        })
-        .unwrap_or_else(|| DEFAULT_IMAGE.to_string());
+    let image = image_ref(nfs.image.as_ref(), DEFAULT_IMAGE);
+
+    // Exports with a size of their own are separate PVCs mounted over their
+    // directory in the share; the rest are directories of the main PVC.
+    let mut volume_mounts = vec![VolumeMount {
+        name: DATA_VOLUME.to_string(),
+        mount_path: EXPORT_DIR.to_string(),
+        ..Default::default()
+    }];
+    let mut volumes = Vec::new();
+    for export in nfs.exports.iter().filter(|e| e.size.is_some()) {
+        let volume = format!("export-{}", export.name);
+        volume_mounts.push(VolumeMount {
+            name: volume.clone(),
+            mount_path: format!("{EXPORT_DIR}{}", export.path()),
+            ..Default::default()
+        });
+        volumes.push(Volume {
+            name: volume,
+            persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
+                claim_name: export_claim_name(stack_name, &export.name),
+                read_only: None,
+            }),
+            ..Default::default()
+        });
+    }
+
+    let mut env = vec![EnvVar {
+        name: "NFS_EXPORT_0".to_string(),
+        value: Some(format!("{EXPORT_DIR} {EXPORT_OPTS}")),
+        ..Default::default()
+    }];
+    env.extend(nfs.exports.iter().enumerate().map(|(i, export)| EnvVar {
+        name: format!("NFS_EXPORT_{}", i + 1),
+        value: Some(format!(
+            "{EXPORT_DIR}{} {}",
+            export.path(),
+            export_opts(export, i + 1)
+        )),
+        ..Default::default()
+    }));
+
+    let dirs = [
+        nfs.movies_path.as_str(),
+        nfs.tv_path.as_str(),
+        nfs.music_path.as_str(),
+        nfs.movies_4k_path.as_str(),
+        nfs.tv_4k_path.as_str(),
+    ]
+    .iter()
+    .map(|p| nfs.media_path(p))
+    .chain(nfs.exports.iter().map(|e| e.path()))
+    .map(|p| format!("{EXPORT_DIR}{p}"))
+    .collect::<Vec<_>>()
+    .join(" ");
 
     let storage_class = nfs.storage_class.clone().filter(|s| !s.is_empty());
 
@@ -117,40 +225,31 @@ pub fn build_statefulset(
                     ..Default::default()
                 }),
                 spec: Some(PodSpec {
+                    topology_spread_constraints: spread
+                        .and_then(|s| {
+                            serde_json::from_value::<TopologySpreadConstraint>(
+                                s.constraint(stack_name),
+                            )
+                            .ok()
+                        })
+                        .map(|c| vec![c]),
                     init_containers: Some(vec![Container {
                         name: "mkdir".to_string(),
                         image: Some("busybox:latest".to_string()),
                         image_pull_policy: Some("IfNotPresent".to_string()),
-                        command: Some(vec!["sh".to_string(), "-c".to_string(), {
-                            let dirs = [
-                                nfs.movies_path.as_str(),
-                                nfs.tv_path.as_str(),
-                                nfs.music_path.as_str(),
-                                nfs.movies_4k_path.as_str(),
-                                nfs.tv_4k_path.as_str(),
-                            ]
-                            .iter()
-                            .map(|p| format!("{EXPORT_DIR}{p}"))
-                            .collect::<Vec<_>>()
-                            .join(" ");
-                            format!("mkdir -p {dirs}")
-                        }]),
-                        volume_mounts: Some(vec![VolumeMount {
-                            name: DATA_VOLUME.to_string(),
-                            mount_path: EXPORT_DIR.to_string(),
-                            ..Default::default()
-                        }]),
+                        command: Some(vec![
+                            "sh".to_string(),
+                            "-c".to_string(),
+                            format!("mkdir -p {dirs}"),
+                        ]),
+                        volume_mounts: Some(volume_mounts.clone()),
                         ..Default::default()
                     }]),
                     containers: vec![Container {
                         name: COMPONENT.to_string(),
                         image: Some(image),
                         image_pull_policy: Some("IfNotPresent".to_string()),
-                        env: Some(vec![EnvVar {
-                            name: "NFS_EXPORT_0".to_string(),
-                            value: Some(format!("{EXPORT_DIR} {EXPORT_OPTS}")),
-                            ..Default::default()
-                        }]),
+                        env: Some(env),
                         ports: Some(vec![
                             ContainerPort {
                                 name: Some("nfs".to_string()),
@@ -187,13 +286,10 @@ pub fn build_statefulset(
                             privileged: Some(true),
                             ..Default::default()
                         }),
-                        volume_mounts: Some(vec![VolumeMount {
-                            name: DATA_VOLUME.to_string(),
-                            mount_path: EXPORT_DIR.to_string(),
-                            ..Default::default()
-                        }]),
+                        volume_mounts: Some(volume_mounts),
                         ..Default::default()
                     }],
+                    volumes: (!volumes.is_empty()).then_some(volumes),
                     ..Default::default()
                 }),
             },
@@ -204,6 +300,65 @@ pub fn build_statefulset(
     }
 }
 
+/// `/etc/exports` options of a named export. Every export needs an `fsid`
+/// of its own, since those with a PVC of their own are separate filesystems.
+fn export_opts(export: &NfsExport, fsid: usize) -> String {
+    let mut opts = vec![
+        if export.read_only { "ro" } else { "rw" }.to_string(),
+        "async".to_string(),
+        "no_subtree_check".to_string(),
+        "no_auth_nlm".to_string(),
+        "insecure".to_string(),
+        export.squash.export_option().to_string(),
+    ];
+    if let Some(uid) = export.anon_uid {
+        opts.push(format!("anonuid={uid}"));
+    }
+    if let Some(gid) = export.anon_gid {
+        opts.push(format!("anongid={gid}"));
+    }
+    opts.push(format!("fsid={fsid}"));
+    format!("*({})", opts.join(","))
+}
+
+/// Build the PVCs of the exports that have a size of their own.
+///
+/// Like the server's main PVC they carry no owner reference, so the data
+/// outlives the MediaStack.
+pub fn build_export_pvcs(
+    stack_name: &str,
+    namespace: &str,
+    nfs: &NfsServerSpec,
+) -> Vec<PersistentVolumeClaim> {
+    nfs.exports
+        .iter()
+        .filter_map(|export| {
+            let size = export.size.as_ref()?;
+            Some(PersistentVolumeClaim {
+                metadata: ObjectMeta {
+                    name: Some(export_claim_name(stack_name, &export.name)),
+                    namespace: Some(namespace.to_string()),
+                    labels: Some(labels(stack_name)),
+                    ..Default::default()
+                },
+                spec: Some(PersistentVolumeClaimSpec {
+                    access_modes: Some(vec!["ReadWriteOnce".to_string()]),
+                    storage_class_name: nfs.storage_class.clone().filter(|s| !s.is_empty()),
+                    resources: Some(VolumeResourceRequirements {
+                        requests: Some(BTreeMap::from([(
+                            "storage".to_string(),
+                            Quantity(size.clone()),
+                        )])),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
 /// Build the headless Service for the in-cluster NFS server.
 ///
 /// Other pods reach the NFS server via the cluster-local DNS name
@@ -268,3 +423,157 @@ pub fn build_service(stack_name: &str, namespace: &str, owner_ref: OwnerReferenc
         ..Default::default()
     }
 }
+
+/// Build the CronJob that rsyncs the NFS export to a remote host over SSH.
+///
+/// The NFS server's PVC is ReadWriteOnce, so the Job pod is pinned to the
+/// server pod's node and mounts the claim read-only alongside it.
+pub fn build_backup_cronjob(
+    stack_name: &str,
+    namespace: &str,
+    rsync: &NfsRsyncSpec,
+    owner_ref: OwnerReference,
+) -> CronJob {
+    let labels = component_labels(stack_name, BACKUP_COMPONENT);
+    let env = |name: &str, value: String| EnvVar {
+        name: name.to_string(),
+        value: Some(value),
+        ..Default::default()
+    };
+
+    let pod_spec = PodSpec {
+        restart_policy: Some("Never".to_string()),
+        affinity: Some(Affinity {
+            pod_affinity: Some(PodAffinity {
+                required_during_scheduling_ignored_during_execution: Some(vec![PodAffinityTerm {
+                    label_selector: Some(LabelSelector {
+                        match_labels: Some(selector_labels(stack_name)),
+                        ..Default::default()
+                    }),
+                    topology_key: "kubernetes.io/hostname".to_string(),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        containers: vec![Container {
+            name: "rsync".to_string(),
+            image: Some(image_ref(rsync.image.as_ref(), COPY_IMAGE)),
+            image_pull_policy: Some("IfNotPresent".to_string()),
+            command: Some(vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                RSYNC_SCRIPT.to_string(),
+            ]),
+            env: Some(vec![
+                env("RSYNC_DEST", rsync.destination.clone()),
+                env("SSH_PORT", rsync.port.to_string()),
+                env(
+                    "RSYNC_DELETE",
+                    if rsync.delete { "--delete" } else { "" }.to_string(),
+                ),
+            ]),
+            volume_mounts: Some(vec![
+                VolumeMount {
+                    name: DATA_VOLUME.to_string(),
+                    mount_path: "/data".to_string(),
+                    read_only: Some(true),
+                    ..Default::default()
+                },
+                VolumeMount {
+                    name: "ssh".to_string(),
+                    mount_path: "/ssh".to_string(),
+                    read_only: Some(true),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        }],
+        volumes: Some(vec![
+            Volume {
+                name: DATA_VOLUME.to_string(),
+                persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
+                    claim_name: data_claim_name(stack_name),
+                    read_only: Some(true),
+                }),
+                ..Default::default()
+            },
+            Volume {
+                name: "ssh".to_string(),
+                secret: Some(SecretVolumeSource {
+                    secret_name: Some(rsync.ssh_secret.clone()),
+                    // ssh refuses private keys readable by others
+                    default_mode: Some(0o400),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        ]),
+        ..Default::default()
+    };
+
+    CronJob {
+        metadata: ObjectMeta {
+            name: Some(backup_name(stack_name)),
+            namespace: Some(namespace.to_string()),
+            labels: Some(labels.clone()),
+            owner_references: Some(vec![owner_ref]),
+            ..Default::default()
+        },
+        spec: Some(CronJobSpec {
+            schedule: rsync.schedule.clone(),
+            concurrency_policy: Some("Forbid".to_string()),
+            successful_jobs_history_limit: Some(3),
+            failed_jobs_history_limit: Some(3),
+            job_template: JobTemplateSpec {
+                metadata: Some(ObjectMeta {
+                    labels: Some(labels.clone()),
+                    ..Default::default()
+                }),
+                spec: Some(JobSpec {
+                    backoff_limit: Some(2),
+                    template: PodTemplateSpec {
+                        metadata: Some(ObjectMeta {
+                            labels: Some(labels),
+                            ..Default::default()
+                        }),
+                        spec: Some(pod_spec),
+                    },
+                    ..Default::default()
+                }),
+            },
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Build a VolumeSnapshot of the NFS server's PVC named `name`.
+///
+/// Snapshots deliberately carry no owner reference: they are backups and
+/// must outlive the MediaStack.
+pub fn build_snapshot(
+    stack_name: &str,
+    namespace: &str,
+    snapshot: &NfsSnapshotSpec,
+    name: &str,
+) -> Option<DynamicObject> {
+    let mut spec = serde_json::json!({
+        "source": { "persistentVolumeClaimName": data_claim_name(stack_name) },
+    });
+    if let Some(class) = snapshot.volume_snapshot_class.as_ref() {
+        spec["volumeSnapshotClassName"] = serde_json::json!(class);
+    }
+    serde_json::from_value(serde_json::json!({
+        "apiVersion": "snapshot.storage.k8s.io/v1",
+        "kind": "VolumeSnapshot",
+        "metadata": {
+            "name": name,
+            "namespace": namespace,
+            "labels": component_labels(stack_name, SNAPSHOT_COMPONENT),
+        },
+        "spec": spec,
+    }))
+    .ok()
+}
@@ -0,0 +1,180 @@
+//! rclone Jobs that move the app's backups to and from S3-compatible object
+//! storage (`backup.objectStorage`).
+
+use k8s_openapi::api::batch::v1::{Job, JobSpec};
+use k8s_openapi::api::core::v1::{
+    Affinity, Container, EnvFromSource, EnvVar, PersistentVolumeClaimVolumeSource, PodAffinity,
+    PodAffinityTerm, PodSpec, PodTemplateSpec, SecretEnvSource, SecurityContext, Volume,
+    VolumeMount,
+};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, ObjectMeta};
+use servarr_crds::{AppDefaults, ObjectStorageSpec, ServarrApp};
+use std::collections::BTreeMap;
+
+use crate::{common, rclone};
+
+const UPLOAD_SUFFIX: &str = "backup-upload";
+const PRUNE_SUFFIX: &str = "backup-prune";
+
+/// Label carried by upload and prune Job pods, valued with the app name.
+/// Like the restore Job's, it must not match the app's selector labels.
+pub const JOB_LABEL: &str = "servarr.dev/backup-object-storage";
+
+/// Folder of the app's backups on its config volume.
+pub const BACKUP_DIR: &str = "/config/Backups";
+
+pub fn upload_job_name(app: &ServarrApp) -> String {
+    common::child_name(app, UPLOAD_SUFFIX)
+}
+
+pub fn prune_job_name(app: &ServarrApp) -> String {
+    common::child_name(app, PRUNE_SUFFIX)
+}
+
+/// Key prefix of the app's uploaded backups in `storage`; see
+/// [`ObjectStorageSpec::upload_prefix`].
+pub fn upload_prefix(app: &ServarrApp, storage: &ObjectStorageSpec) -> String {
+    storage.upload_prefix(&common::app_namespace(app), &common::app_name(app))
+}
+
+/// A Job that copies every backup in [`BACKUP_DIR`] to the app's
+/// [`upload_prefix`] in `bucket`. Backups already uploaded are skipped, and
+/// ones the app pruned locally are kept in the bucket.
+pub fn build_upload_job(app: &ServarrApp, storage: &ObjectStorageSpec, bucket: &str) -> Job {
+    let prefix = upload_prefix(app, storage);
+    build_job(
+        app,
+        storage,
+        UPLOAD_SUFFIX,
+        JOB_LABEL,
+        vec![
+            "copy".into(),
+            BACKUP_DIR.into(),
+            format!("s3:{bucket}/{prefix}"),
+        ],
+        true,
+    )
+}
+
+/// A Job that deletes every object under the app's [`upload_prefix`] in
+/// `bucket`.
+pub fn build_prune_job(app: &ServarrApp, storage: &ObjectStorageSpec, bucket: &str) -> Job {
+    let prefix = upload_prefix(app, storage);
+    build_job(
+        app,
+        storage,
+        PRUNE_SUFFIX,
+        JOB_LABEL,
+        vec!["purge".into(), format!("s3:{bucket}/{prefix}")],
+        false,
+    )
+}
+
+/// A Job named `{app}-{suffix}` running rclone with `args` against an `s3`
+/// remote configured from `storage`. With `config_volume` it mounts the
+/// app's config volume at `/config` and runs as the app's uid/gid, on the
+/// app pod's node so a ReadWriteOnce volume can be mounted while the app
+/// keeps running.
+pub(crate) fn build_job(
+    app: &ServarrApp,
+    storage: &ObjectStorageSpec,
+    suffix: &str,
+    pod_label: &str,
+    args: Vec<String>,
+    config_volume: bool,
+) -> Job {
+    let env_var = |name: &str, value: &str| EnvVar {
+        name: name.into(),
+        value: Some(value.into()),
+        ..Default::default()
+    };
+    let mut env = vec![
+        env_var("RCLONE_CONFIG_S3_TYPE", "s3"),
+        env_var(
+            "RCLONE_CONFIG_S3_PROVIDER",
+            if storage.endpoint.is_some() {
+                "Other"
+            } else {
+                "AWS"
+            },
+        ),
+        env_var("RCLONE_CONFIG_S3_ENV_AUTH", "true"),
+    ];
+    if let Some(ref endpoint) = storage.endpoint {
+        env.push(env_var("RCLONE_CONFIG_S3_ENDPOINT", endpoint));
+    }
+    if let Some(ref region) = storage.region {
+        env.push(env_var("RCLONE_CONFIG_S3_REGION", region));
+    }
+
+    let mut container = Container {
+        name: "rclone".into(),
+        image: Some(format!("{}:{}", rclone::DEFAULT_IMAGE, rclone::DEFAULT_TAG)),
+        args: Some(args),
+        env: Some(env),
+        env_from: Some(vec![EnvFromSource {
+            secret_ref: Some(SecretEnvSource {
+                name: storage.credentials_secret.clone(),
+                optional: Some(false),
+            }),
+            ..Default::default()
+        }]),
+        ..Default::default()
+    };
+    let mut pod = PodSpec {
+        restart_policy: Some("Never".into()),
+        ..Default::default()
+    };
+    if config_volume {
+        let defaults = AppDefaults::for_app(&app.spec.app);
+        container.security_context = Some(SecurityContext {
+            run_as_user: Some(app.spec.uid.unwrap_or(defaults.uid)),
+            run_as_group: Some(app.spec.gid.unwrap_or(defaults.gid)),
+            ..Default::default()
+        });
+        container.volume_mounts = Some(vec![VolumeMount {
+            name: "config".into(),
+            mount_path: "/config".into(),
+            ..Default::default()
+        }]);
+        pod.affinity = Some(Affinity {
+            pod_affinity: Some(PodAffinity {
+                required_during_scheduling_ignored_during_execution: Some(vec![PodAffinityTerm {
+                    label_selector: Some(LabelSelector {
+                        match_labels: Some(common::selector_labels(app)),
+                        ..Default::default()
+                    }),
+                    topology_key: "kubernetes.io/hostname".into(),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        pod.volumes = Some(vec![Volume {
+            name: "config".into(),
+            persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
+                claim_name: common::claim_name(app, "config"),
+                read_only: Some(false),
+            }),
+            ..Default::default()
+        }]);
+    }
+    pod.containers = vec![container];
+
+    Job {
+        metadata: common::metadata(app, suffix),
+        spec: Some(JobSpec {
+            backoff_limit: Some(2),
+            template: PodTemplateSpec {
+                metadata: Some(ObjectMeta {
+                    labels: Some(BTreeMap::from([(pod_label.into(), common::app_name(app))])),
+                    ..Default::default()
+                }),
+                spec: Some(pod),
+            },
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,181 @@
+use k8s_openapi::api::apps::v1::Deployment;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use servarr_crds::{PatchType, ResourcePatch};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PatchError {
+    #[error("patches[{index}]: invalid patch body: {source}")]
+    Parse {
+        index: usize,
+        #[source]
+        source: serde_yaml::Error,
+    },
+    #[error("patches[{index}]: strategic merge patch must be a mapping")]
+    NotAMapping { index: usize },
+    #[error("patches[{index}]: {source}")]
+    Json6902 {
+        index: usize,
+        #[source]
+        source: json_patch::PatchError,
+    },
+    #[error("patches[{index}]: must not change apiVersion, kind, name, or namespace")]
+    Identity { index: usize },
+    #[error("unsupportedOverrides.podSpec must be a mapping")]
+    PodSpecNotAMapping,
+    #[error("patched {kind} {name} is invalid: {source}")]
+    Invalid {
+        kind: String,
+        name: String,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+fn identity(value: &Value) -> [Value; 4] {
+    [
+        value["apiVersion"].clone(),
+        value["kind"].clone(),
+        value["metadata"]["name"].clone(),
+        value["metadata"]["namespace"].clone(),
+    ]
+}
+
+/// Apply every patch in `patches` whose target matches `obj` (by kind and
+/// optional name), in list order. Objects no patch targets are returned
+/// unchanged without a serialization round trip.
+pub fn apply<T>(obj: T, patches: &[ResourcePatch]) -> Result<T, PatchError>
+where
+    T: Serialize + DeserializeOwned,
+{
+    if patches.is_empty() {
+        return Ok(obj);
+    }
+    let Ok(mut value) = serde_json::to_value(&obj) else {
+        return Ok(obj);
+    };
+    let kind = value["kind"].as_str().unwrap_or_default().to_string();
+    let name = value["metadata"]["name"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    let before = identity(&value);
+
+    let mut patched = false;
+    for (index, p) in patches.iter().enumerate() {
+        if !p.target.matches(&kind, &name) {
+            continue;
+        }
+        apply_one(&mut value, index, p)?;
+        if identity(&value) != before {
+            return Err(PatchError::Identity { index });
+        }
+        patched = true;
+    }
+
+    if !patched {
+        return Ok(obj);
+    }
+    serde_json::from_value(value).map_err(|source| PatchError::Invalid { kind, name, source })
+}
+
+/// Deep-merge a raw `unsupportedOverrides.podSpec` into the Deployment's
+/// pod template spec, using the strategic merge rules of [`apply`].
+pub fn merge_pod_spec(deployment: Deployment, pod_spec: &Value) -> Result<Deployment, PatchError> {
+    if !pod_spec.is_object() {
+        return Err(PatchError::PodSpecNotAMapping);
+    }
+    let name = deployment.metadata.name.clone().unwrap_or_default();
+    let Ok(mut value) = serde_json::to_value(&deployment) else {
+        return Ok(deployment);
+    };
+    strategic_merge(&mut value["spec"]["template"]["spec"], pod_spec);
+    serde_json::from_value(value).map_err(|source| PatchError::Invalid {
+        kind: "Deployment".into(),
+        name,
+        source,
+    })
+}
+
+/// Drift-ignore paths (relative to the pod template) for the top-level
+/// fields a `podSpec` override touches.
+pub fn pod_spec_override_paths(pod_spec: &Value) -> Vec<String> {
+    pod_spec
+        .as_object()
+        .into_iter()
+        .flat_map(|m| m.keys())
+        .map(|key| format!("spec['{key}']"))
+        .collect()
+}
+
+fn apply_one(value: &mut Value, index: usize, p: &ResourcePatch) -> Result<(), PatchError> {
+    match p.patch_type {
+        PatchType::StrategicMerge => {
+            let body: Value = serde_yaml::from_str(&p.patch)
+                .map_err(|source| PatchError::Parse { index, source })?;
+            if !body.is_object() {
+                return Err(PatchError::NotAMapping { index });
+            }
+            strategic_merge(value, &body);
+        }
+        PatchType::Json6902 => {
+            let ops: json_patch::Patch = serde_yaml::from_str(&p.patch)
+                .map_err(|source| PatchError::Parse { index, source })?;
+            json_patch::patch(value, &ops)
+                .map_err(|source| PatchError::Json6902 { index, source })?;
+        }
+    }
+    Ok(())
+}
+
+fn item_name(v: &Value) -> Option<&str> {
+    v.get("name").and_then(Value::as_str)
+}
+
+/// Merge `patch` into `target`. Maps merge key by key (`null` removes a
+/// key); lists whose items are all objects with a `name` merge item by item
+/// on that name, and an item carrying `$patch: delete` removes its match.
+/// Anything else is replaced.
+fn strategic_merge(target: &mut Value, patch: &Value) {
+    match (target, patch) {
+        (Value::Object(t), Value::Object(p)) => {
+            for (key, pv) in p {
+                if pv.is_null() {
+                    t.remove(key);
+                } else if let Some(tv) = t.get_mut(key) {
+                    strategic_merge(tv, pv);
+                } else {
+                    let mut fresh = Value::Null;
+                    strategic_merge(&mut fresh, pv);
+                    t.insert(key.clone(), fresh);
+                }
+            }
+        }
+        (Value::Array(t), Value::Array(p)) if t.iter().chain(p).all(|v| item_name(v).is_some()) => {
+            for item in p {
+                let name = item_name(item);
+                let delete = item.get("$patch").and_then(Value::as_str) == Some("delete");
+                let pos = t.iter().position(|v| item_name(v) == name);
+                match (pos, delete) {
+                    (Some(i), true) => {
+                        t.remove(i);
+                    }
+                    (None, true) => {}
+                    (Some(i), false) => strategic_merge(&mut t[i], item),
+                    (None, false) => {
+                        let mut fresh = Value::Null;
+                        strategic_merge(&mut fresh, item);
+                        t.push(fresh);
+                    }
+                }
+            }
+        }
+        (t, Value::Object(p)) => {
+            *t = Value::Object(Default::default());
+            strategic_merge(t, &Value::Object(p.clone()));
+        }
+        (t, p) => *t = p.clone(),
+    }
+}
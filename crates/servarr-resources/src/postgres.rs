@@ -0,0 +1,291 @@
+use k8s_openapi::ByteString;
+use k8s_openapi::api::apps::v1::{StatefulSet, StatefulSetSpec};
+use k8s_openapi::api::core::v1::{
+    Container, ContainerPort, EnvVar, EnvVarSource, ExecAction, PersistentVolumeClaim,
+    PersistentVolumeClaimSpec, PodSpec, PodTemplateSpec, Probe, Secret, SecretKeySelector,
+    SecretVolumeSource, Volume, VolumeMount, VolumeResourceRequirements,
+};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, ObjectMeta, OwnerReference};
+use k8s_openapi::{
+    api::core::v1::{Service, ServicePort, ServiceSpec},
+    apimachinery::pkg::util::intstr::IntOrString,
+};
+use servarr_crds::PostgresSpec;
+use std::collections::BTreeMap;
+
+const MANAGED_BY: &str = "servarr-operator";
+const POSTGRES_PORT: i32 = 5432;
+const COMPONENT: &str = "postgres";
+const DEFAULT_IMAGE: &str = "postgres:16-alpine";
+const DATA_VOLUME: &str = "data";
+const DATA_DIR: &str = "/var/lib/postgresql/data";
+const CREDENTIALS_VOLUME: &str = "credentials";
+const CREDENTIALS_DIR: &str = "/etc/servarr-postgres";
+
+// Runs in the provisioner sidecar. Every `app-{role}` key in the mounted
+// credentials Secret becomes a login role with `{role}_main` and `{role}_log`
+// databases. The loop re-reads the Secret volume, which the kubelet refreshes
+// in place, so apps added to the stack are provisioned without restarting
+// Postgres. The role password is re-applied each pass so a regenerated
+// Secret key is picked up as well.
+const PROVISION_SCRIPT: &str = r#"until pg_isready -q -h 127.0.0.1 -U postgres; do sleep 2; done
+while true; do
+  for f in /etc/servarr-postgres/app-*; do
+    [ -e "$f" ] || continue
+    role="${f##*/app-}"
+    psql -q -v ON_ERROR_STOP=1 -h 127.0.0.1 -U postgres -d postgres \
+      -v role="$role" -v pw="$(cat "$f")" <<'SQL'
+SELECT format('CREATE ROLE %I LOGIN', :'role') WHERE NOT EXISTS (SELECT FROM pg_roles WHERE rolname = :'role')\gexec
+SELECT format('ALTER ROLE %I PASSWORD %L', :'role', :'pw')\gexec
+SELECT format('CREATE DATABASE %I OWNER %I', :'role' || '_main', :'role') WHERE NOT EXISTS (SELECT FROM pg_database WHERE datname = :'role' || '_main')\gexec
+SELECT format('CREATE DATABASE %I OWNER %I', :'role' || '_log', :'role') WHERE NOT EXISTS (SELECT FROM pg_database WHERE datname = :'role' || '_log')\gexec
+SQL
+  done
+  sleep 60
+done
+"#;
+
+fn labels(stack_name: &str) -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("servarr.dev/stack".into(), stack_name.to_string()),
+        ("servarr.dev/component".into(), COMPONENT.to_string()),
+        ("app.kubernetes.io/managed-by".into(), MANAGED_BY.into()),
+    ])
+}
+
+fn selector_labels(stack_name: &str) -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("servarr.dev/stack".into(), stack_name.to_string()),
+        ("servarr.dev/component".into(), COMPONENT.to_string()),
+    ])
+}
+
+fn superuser_password_env(secret_name: &str, var: &str) -> EnvVar {
+    EnvVar {
+        name: var.to_string(),
+        value_from: Some(EnvVarSource {
+            secret_key_ref: Some(SecretKeySelector {
+                name: secret_name.to_string(),
+                key: PostgresSpec::SUPERUSER_PASSWORD_KEY.to_string(),
+                optional: Some(false),
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Build the StatefulSet for the stack's shared Postgres instance.
+///
+/// The pod runs Postgres itself plus a `provisioner` sidecar that keeps one
+/// role and database pair per app in sync with the credentials Secret
+/// produced by [`build_secret`].
+pub fn build_statefulset(
+    stack_name: &str,
+    namespace: &str,
+    pg: &PostgresSpec,
+    owner_ref: OwnerReference,
+) -> StatefulSet {
+    let name = PostgresSpec::resource_name(stack_name);
+    let labels = labels(stack_name);
+    let selector = selector_labels(stack_name);
+
+    let image = pg
+        .image
+        .as_ref()
+        .map(|img| {
+            let tag = if img.tag.is_empty() {
+                "latest".to_string()
+            } else {
+                img.tag.clone()
+            };
+            format!("{}:{tag}", img.repository)
+        })
+        .unwrap_or_else(|| DEFAULT_IMAGE.to_string());
+
+    let storage_class = pg.storage_class.clone().filter(|s| !s.is_empty());
+
+    let volume_claim_template = PersistentVolumeClaim {
+        metadata: ObjectMeta {
+            name: Some(DATA_VOLUME.to_string()),
+            ..Default::default()
+        },
+        spec: Some(PersistentVolumeClaimSpec {
+            access_modes: Some(vec!["ReadWriteOnce".to_string()]),
+            storage_class_name: storage_class,
+            resources: Some(VolumeResourceRequirements {
+                requests: Some(BTreeMap::from([(
+                    "storage".to_string(),
+                    Quantity(pg.storage_size.clone()),
+                )])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let ready_probe = Probe {
+        exec: Some(ExecAction {
+            command: Some(vec![
+                "pg_isready".to_string(),
+                "-U".to_string(),
+                "postgres".to_string(),
+            ]),
+        }),
+        period_seconds: Some(10),
+        ..Default::default()
+    };
+
+    StatefulSet {
+        metadata: ObjectMeta {
+            name: Some(name.clone()),
+            namespace: Some(namespace.to_string()),
+            labels: Some(labels.clone()),
+            owner_references: Some(vec![owner_ref]),
+            ..Default::default()
+        },
+        spec: Some(StatefulSetSpec {
+            replicas: Some(1),
+            service_name: Some(name.clone()),
+            selector: LabelSelector {
+                match_labels: Some(selector.clone()),
+                ..Default::default()
+            },
+            template: PodTemplateSpec {
+                metadata: Some(ObjectMeta {
+                    labels: Some(selector),
+                    ..Default::default()
+                }),
+                spec: Some(PodSpec {
+                    containers: vec![
+                        Container {
+                            name: COMPONENT.to_string(),
+                            image: Some(image.clone()),
+                            image_pull_policy: Some("IfNotPresent".to_string()),
+                            env: Some(vec![
+                                superuser_password_env(&name, "POSTGRES_PASSWORD"),
+                                // A subdirectory keeps initdb away from the
+                                // `lost+found` directory many volume types create.
+                                EnvVar {
+                                    name: "PGDATA".to_string(),
+                                    value: Some(format!("{DATA_DIR}/pgdata")),
+                                    ..Default::default()
+                                },
+                            ]),
+                            ports: Some(vec![ContainerPort {
+                                name: Some("postgres".to_string()),
+                                container_port: POSTGRES_PORT,
+                                protocol: Some("TCP".to_string()),
+                                ..Default::default()
+                            }]),
+                            readiness_probe: Some(ready_probe.clone()),
+                            liveness_probe: Some(Probe {
+                                initial_delay_seconds: Some(30),
+                                ..ready_probe
+                            }),
+                            volume_mounts: Some(vec![VolumeMount {
+                                name: DATA_VOLUME.to_string(),
+                                mount_path: DATA_DIR.to_string(),
+                                ..Default::default()
+                            }]),
+                            ..Default::default()
+                        },
+                        Container {
+                            name: "provisioner".to_string(),
+                            image: Some(image),
+                            image_pull_policy: Some("IfNotPresent".to_string()),
+                            command: Some(vec![
+                                "sh".to_string(),
+                                "-c".to_string(),
+                                PROVISION_SCRIPT.to_string(),
+                            ]),
+                            env: Some(vec![superuser_password_env(&name, "PGPASSWORD")]),
+                            volume_mounts: Some(vec![VolumeMount {
+                                name: CREDENTIALS_VOLUME.to_string(),
+                                mount_path: CREDENTIALS_DIR.to_string(),
+                                read_only: Some(true),
+                                ..Default::default()
+                            }]),
+                            ..Default::default()
+                        },
+                    ],
+                    volumes: Some(vec![Volume {
+                        name: CREDENTIALS_VOLUME.to_string(),
+                        secret: Some(SecretVolumeSource {
+                            secret_name: Some(name),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                }),
+            },
+            volume_claim_templates: Some(vec![volume_claim_template]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Build the ClusterIP Service for the shared Postgres instance.
+///
+/// Apps connect via `{stack-name}-postgres.{namespace}.svc.cluster.local:5432`.
+pub fn build_service(stack_name: &str, namespace: &str, owner_ref: OwnerReference) -> Service {
+    Service {
+        metadata: ObjectMeta {
+            name: Some(PostgresSpec::resource_name(stack_name)),
+            namespace: Some(namespace.to_string()),
+            labels: Some(labels(stack_name)),
+            owner_references: Some(vec![owner_ref]),
+            ..Default::default()
+        },
+        spec: Some(ServiceSpec {
+            selector: Some(selector_labels(stack_name)),
+            ports: Some(vec![ServicePort {
+                name: Some("postgres".to_string()),
+                port: POSTGRES_PORT,
+                target_port: Some(IntOrString::Int(POSTGRES_PORT)),
+                protocol: Some("TCP".to_string()),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Build the credentials Secret for the shared Postgres instance.
+///
+/// `passwords` maps Secret keys to values and must contain
+/// [`PostgresSpec::SUPERUSER_PASSWORD_KEY`] plus one
+/// [`PostgresSpec::password_key`] entry per app. Generating (and preserving)
+/// the passwords is the controller's job; this only assembles the object.
+///
+/// Unlike the StatefulSet and Service, the Secret has no owner reference.
+/// It lives as long as the data PVC, which outlives the stack: Postgres only
+/// reads the passwords at initdb, so a recreated stack must find the ones
+/// the retained data was initialised with.
+pub fn build_secret(
+    stack_name: &str,
+    namespace: &str,
+    passwords: &BTreeMap<String, String>,
+) -> Secret {
+    Secret {
+        metadata: ObjectMeta {
+            name: Some(PostgresSpec::resource_name(stack_name)),
+            namespace: Some(namespace.to_string()),
+            labels: Some(labels(stack_name)),
+            ..Default::default()
+        },
+        data: Some(
+            passwords
+                .iter()
+                .map(|(k, v)| (k.clone(), ByteString(v.as_bytes().to_vec())))
+                .collect(),
+        ),
+        type_: Some("Opaque".into()),
+        ..Default::default()
+    }
+}
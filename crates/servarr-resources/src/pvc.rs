@@ -2,25 +2,50 @@ use k8s_openapi::api::core::v1::{
     PersistentVolumeClaim, PersistentVolumeClaimSpec, VolumeResourceRequirements,
 };
 use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
-use servarr_crds::{AppConfig, AppDefaults, PersistenceSpec, PvcVolume, ServarrApp, SshMode};
+use servarr_crds::{AppConfig, AppDefaults, PvcVolume, ServarrApp, SshMode};
 use std::collections::BTreeMap;
 
 use crate::common;
 
-pub fn build_all(app: &ServarrApp) -> Vec<PersistentVolumeClaim> {
+/// The app's PVC volumes: its own merged over the app type's defaults.
+pub fn volumes(app: &ServarrApp) -> Vec<PvcVolume> {
     let defaults = AppDefaults::for_app(&app.spec.app);
-    let merged: PersistenceSpec;
-    let persistence = match &app.spec.persistence {
-        None => &defaults.persistence,
-        Some(spec) => {
-            merged = defaults.persistence.merge_with(spec);
-            &merged
-        }
-    };
+    match &app.spec.persistence {
+        None => defaults.persistence.volumes,
+        Some(spec) => defaults.persistence.merge_with(spec).volumes,
+    }
+}
 
-    let mut pvcs: Vec<PersistentVolumeClaim> = persistence
-        .volumes
+/// The app's config volume: the one named `config`, or its first PVC
+/// volume for apps without one. `None` for apps without volumes.
+pub fn config_volume(app: &ServarrApp) -> Option<PvcVolume> {
+    let volumes = volumes(app);
+    volumes
         .iter()
+        .find(|v| v.name == "config")
+        .or(volumes.first())
+        .cloned()
+}
+
+/// Name of the PVC backing `volume`, wherever the workload keeps it.
+pub fn claim_for(app: &ServarrApp, volume: &PvcVolume) -> String {
+    if crate::statefulset::template_volumes(app)
+        .iter()
+        .any(|v| v.name == volume.name)
+    {
+        crate::statefulset::template_claim_name(app, &volume.name)
+    } else {
+        common::claim_name(app, &volume.name)
+    }
+}
+
+/// The PVCs the operator creates ahead of the workload. Volumes a
+/// StatefulSet gets from its volumeClaimTemplates are left out.
+pub fn build_all(app: &ServarrApp) -> Vec<PersistentVolumeClaim> {
+    let templated = crate::statefulset::template_volumes(app);
+    let mut pvcs: Vec<PersistentVolumeClaim> = volumes(app)
+        .iter()
+        .filter(|v| !templated.iter().any(|t| t.name == v.name))
         .map(|v| build_one(app, v))
         .collect();
 
@@ -56,14 +81,22 @@ fn build_ssh_home_pvc(app: &ServarrApp, username: &str) -> PersistentVolumeClaim
 }
 
 fn build_one(app: &ServarrApp, vol: &PvcVolume) -> PersistentVolumeClaim {
+    build_claim(app, vol, &common::claim_name(app, &vol.name))
+}
+
+/// The PVC for `vol` under the name `claim_name`.
+pub fn build_claim(app: &ServarrApp, vol: &PvcVolume, claim_name: &str) -> PersistentVolumeClaim {
     let storage_class = if vol.storage_class.is_empty() {
         None
     } else {
         Some(vol.storage_class.clone())
     };
 
+    let mut metadata = common::metadata(app, &vol.name);
+    metadata.name = Some(claim_name.to_string());
+
     PersistentVolumeClaim {
-        metadata: common::metadata(app, &vol.name),
+        metadata,
         spec: Some(PersistentVolumeClaimSpec {
             access_modes: Some(vec![vol.access_mode.clone()]),
             resources: Some(VolumeResourceRequirements {
@@ -0,0 +1,206 @@
+//! rclone sidecars for `spec.rcloneMounts`.
+//!
+//! Each remote is mounted by its own native sidecar (an init container with
+//! `restartPolicy: Always`) onto an emptyDir with `Bidirectional` mount
+//! propagation. The app container mounts the same emptyDir with
+//! `HostToContainer`, so it sees the FUSE mount. The sidecar's startup probe
+//! waits for the mount, which keeps the app from starting against an empty
+//! directory.
+
+use std::collections::BTreeMap;
+
+use k8s_openapi::api::core::v1::{
+    Capabilities, Container, EmptyDirVolumeSource, ExecAction, KeyToPath, Probe,
+    ResourceRequirements as K8sResources, SecretVolumeSource, SecurityContext, Volume, VolumeMount,
+};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use servarr_crds::{RcloneMount, ResourceList, ServarrApp};
+
+pub(crate) const DEFAULT_IMAGE: &str = "rclone/rclone";
+pub(crate) const DEFAULT_TAG: &str = "1.68.2";
+const CONFIG_DIR: &str = "/config/rclone";
+const CACHE_DIR: &str = "/cache";
+
+fn mount_volume(mount: &RcloneMount) -> String {
+    format!("rclone-{}", mount.name)
+}
+
+fn cache_volume(mount: &RcloneMount) -> String {
+    format!("rclone-{}-cache", mount.name)
+}
+
+fn config_volume(mount: &RcloneMount) -> String {
+    format!("rclone-{}-config", mount.name)
+}
+
+/// Arguments for `rclone mount`. The remote is mounted at the same path as
+/// in the app container so paths in rclone's logs match what the app sees.
+fn args(mount: &RcloneMount, uid: i64, gid: i64) -> Vec<String> {
+    let mut args = vec![
+        "mount".to_string(),
+        mount.remote.clone(),
+        mount.mount_path.clone(),
+        format!("--config={CONFIG_DIR}/rclone.conf"),
+        format!("--cache-dir={CACHE_DIR}"),
+        "--allow-other".into(),
+        format!("--uid={uid}"),
+        format!("--gid={gid}"),
+        "--umask=002".into(),
+        format!(
+            "--vfs-cache-mode={}",
+            mount.vfs.cache_mode.as_deref().unwrap_or("full")
+        ),
+    ];
+    let vfs = [
+        ("--vfs-cache-max-size", &mount.vfs.cache_max_size),
+        ("--vfs-cache-max-age", &mount.vfs.cache_max_age),
+        ("--dir-cache-time", &mount.vfs.dir_cache_time),
+        ("--vfs-read-ahead", &mount.vfs.read_ahead),
+    ];
+    for (flag, value) in vfs {
+        if let Some(value) = value {
+            args.push(format!("{flag}={value}"));
+        }
+    }
+    if mount.read_only {
+        args.push("--read-only".into());
+    }
+    args.extend(mount.extra_args.iter().cloned());
+    args
+}
+
+fn resource_list(list: &ResourceList) -> Option<BTreeMap<String, Quantity>> {
+    let map: BTreeMap<String, Quantity> = [("cpu", &list.cpu), ("memory", &list.memory)]
+        .into_iter()
+        .filter(|(_, v)| !v.is_empty())
+        .map(|(k, v)| (k.to_string(), Quantity(v.clone())))
+        .collect();
+    (!map.is_empty()).then_some(map)
+}
+
+/// Sidecar containers, one per mount, to append to the pod's init
+/// containers. They run as root with `privileged` for FUSE and mount
+/// propagation; files are presented to the app as `uid`/`gid`.
+pub fn sidecars(app: &ServarrApp, uid: i64, gid: i64) -> Vec<Container> {
+    app.spec
+        .rclone_mounts
+        .iter()
+        .map(|mount| {
+            let image = match mount.image {
+                Some(ref i) if !i.digest.is_empty() => format!("{}@{}", i.repository, i.digest),
+                Some(ref i) => format!("{}:{}", i.repository, i.tag),
+                None => format!("{DEFAULT_IMAGE}:{DEFAULT_TAG}"),
+            };
+            let pull_policy = mount
+                .image
+                .as_ref()
+                .map_or("IfNotPresent".to_string(), |i| i.pull_policy.clone());
+            Container {
+                name: mount_volume(mount),
+                image: Some(image),
+                image_pull_policy: Some(pull_policy),
+                args: Some(args(mount, uid, gid)),
+                restart_policy: Some("Always".into()),
+                security_context: Some(SecurityContext {
+                    privileged: Some(true),
+                    allow_privilege_escalation: Some(true),
+                    run_as_user: Some(0),
+                    run_as_group: Some(0),
+                    run_as_non_root: Some(false),
+                    capabilities: Some(Capabilities {
+                        add: Some(vec!["SYS_ADMIN".into()]),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                startup_probe: Some(Probe {
+                    exec: Some(ExecAction {
+                        command: Some(vec![
+                            "/bin/sh".into(),
+                            "-c".into(),
+                            format!("grep -q ' {} fuse' /proc/mounts", mount.mount_path),
+                        ]),
+                    }),
+                    period_seconds: Some(2),
+                    failure_threshold: Some(150),
+                    ..Default::default()
+                }),
+                resources: mount.resources.as_ref().map(|r| K8sResources {
+                    limits: resource_list(&r.limits),
+                    requests: resource_list(&r.requests),
+                    ..Default::default()
+                }),
+                volume_mounts: Some(vec![
+                    VolumeMount {
+                        name: mount_volume(mount),
+                        mount_path: mount.mount_path.clone(),
+                        mount_propagation: Some("Bidirectional".into()),
+                        ..Default::default()
+                    },
+                    VolumeMount {
+                        name: cache_volume(mount),
+                        mount_path: CACHE_DIR.into(),
+                        ..Default::default()
+                    },
+                    VolumeMount {
+                        name: config_volume(mount),
+                        mount_path: CONFIG_DIR.into(),
+                        read_only: Some(true),
+                        ..Default::default()
+                    },
+                ]),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+/// The app container's view of each mount.
+pub fn app_volume_mounts(app: &ServarrApp) -> Vec<VolumeMount> {
+    app.spec
+        .rclone_mounts
+        .iter()
+        .map(|mount| VolumeMount {
+            name: mount_volume(mount),
+            mount_path: mount.mount_path.clone(),
+            mount_propagation: Some("HostToContainer".into()),
+            read_only: mount.read_only.then_some(true),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Shared mount, VFS cache, and config volumes for each mount.
+pub fn volumes(app: &ServarrApp) -> Vec<Volume> {
+    app.spec
+        .rclone_mounts
+        .iter()
+        .flat_map(|mount| {
+            [
+                Volume {
+                    name: mount_volume(mount),
+                    empty_dir: Some(EmptyDirVolumeSource::default()),
+                    ..Default::default()
+                },
+                Volume {
+                    name: cache_volume(mount),
+                    empty_dir: Some(EmptyDirVolumeSource::default()),
+                    ..Default::default()
+                },
+                Volume {
+                    name: config_volume(mount),
+                    secret: Some(SecretVolumeSource {
+                        secret_name: Some(mount.config_secret.clone()),
+                        items: Some(vec![KeyToPath {
+                            key: mount.config_key.clone(),
+                            path: "rclone.conf".into(),
+                            mode: None,
+                        }]),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            ]
+        })
+        .collect()
+}
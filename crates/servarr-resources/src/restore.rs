@@ -0,0 +1,54 @@
+use k8s_openapi::api::batch::v1::Job;
+use servarr_crds::{ObjectStorageSpec, ServarrApp};
+use std::collections::BTreeMap;
+
+use crate::{common, object_storage};
+
+const SUFFIX: &str = "restore-object";
+
+/// Label carried by staging Job pods, valued with the app name. The pods
+/// must not match the app's selector labels, or its Service would route to
+/// them.
+pub const JOB_LABEL: &str = "servarr.dev/restore-object";
+
+/// Annotation on the Job recording the object it stages.
+pub const SOURCE_ANNOTATION: &str = "servarr.dev/restore-source";
+
+/// Folder of the app's manual backups, where a staged archive shows up in
+/// its backup list.
+pub const STAGE_DIR: &str = "/config/Backups/manual";
+
+pub fn job_name(app: &ServarrApp) -> String {
+    common::child_name(app, SUFFIX)
+}
+
+/// A Job that copies `s3://{bucket}/{key}` into [`STAGE_DIR`] on the app's
+/// config volume as `file_name`, using rclone with the credentials of
+/// `storage`. It runs as the app's uid/gid so the app can read the archive,
+/// and on the app pod's node so a ReadWriteOnce volume can be mounted while
+/// the app keeps running.
+pub fn build_stage_job(
+    app: &ServarrApp,
+    storage: &ObjectStorageSpec,
+    bucket: &str,
+    key: &str,
+    file_name: &str,
+) -> Job {
+    let mut job = object_storage::build_job(
+        app,
+        storage,
+        SUFFIX,
+        JOB_LABEL,
+        vec![
+            "copyto".into(),
+            format!("s3:{bucket}/{key}"),
+            format!("{STAGE_DIR}/{file_name}"),
+        ],
+        true,
+    );
+    job.metadata.annotations = Some(BTreeMap::from([(
+        SOURCE_ANNOTATION.into(),
+        format!("s3://{bucket}/{key}"),
+    )]));
+    job
+}
@@ -1,6 +1,6 @@
 use k8s_openapi::api::core::v1::Secret;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
-use servarr_crds::{AppConfig, AppType, ServarrApp};
+use servarr_crds::{AppConfig, AppType, DEFAULT_API_KEY_KEY, ServarrApp};
 use std::collections::BTreeMap;
 
 use crate::common;
@@ -12,6 +12,11 @@ use crate::common;
 /// whether the Secret already exists; that gate belongs in the controller.
 pub fn build_api_key(app: &ServarrApp, key: &str) -> Option<Secret> {
     let secret_name = app.spec.api_key_secret.as_deref()?;
+    let data_key = app
+        .spec
+        .api_key_secret_key
+        .as_deref()
+        .unwrap_or(DEFAULT_API_KEY_KEY);
     Some(Secret {
         metadata: ObjectMeta {
             name: Some(secret_name.to_owned()),
@@ -20,7 +25,7 @@ pub fn build_api_key(app: &ServarrApp, key: &str) -> Option<Secret> {
             owner_references: Some(vec![common::owner_reference(app)]),
             ..Default::default()
         },
-        string_data: Some(BTreeMap::from([("api-key".into(), key.to_owned())])),
+        string_data: Some(BTreeMap::from([(data_key.to_owned(), key.to_owned())])),
         type_: Some("Opaque".into()),
         ..Default::default()
     })
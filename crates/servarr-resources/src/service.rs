@@ -1,8 +1,11 @@
 use k8s_openapi::api::core::v1::{Service, ServicePort, ServiceSpec};
-use servarr_crds::{AppConfig, AppDefaults, AppType, ServarrApp};
+use servarr_crds::{AppConfig, AppDefaults, ServarrApp};
 
 use crate::common;
 
+/// Turns on topology aware routing for a Service.
+pub const TOPOLOGY_MODE_ANNOTATION: &str = "service.kubernetes.io/topology-mode";
+
 pub fn build(app: &ServarrApp) -> Service {
     let defaults = AppDefaults::for_app(&app.spec.app);
     let svc = app.spec.service.as_ref().unwrap_or(&defaults.service);
@@ -23,10 +26,8 @@ pub fn build(app: &ServarrApp) -> Service {
         })
         .collect();
 
-    // Transmission peer port
-    if let (AppType::Transmission, Some(AppConfig::Transmission(tc))) = (&app.spec.app, app_config)
-        && let Some(pp) = &tc.peer_port
-    {
+    // Torrent client peer port
+    if let Some(pp) = app_config.and_then(AppConfig::peer_port) {
         ports.push(ServicePort {
             name: Some("peer-tcp".into()),
             port: pp.port,
@@ -41,12 +42,21 @@ pub fn build(app: &ServarrApp) -> Service {
         });
     }
 
+    let mut metadata = common::metadata(app, "");
+    if svc.topology_aware_routing {
+        metadata.annotations = Some([(TOPOLOGY_MODE_ANNOTATION.into(), "Auto".into())].into());
+    }
+
     Service {
-        metadata: common::metadata(app, ""),
+        metadata,
         spec: Some(ServiceSpec {
             type_: Some(svc.service_type.clone()),
             selector: Some(common::selector_labels(app)),
             ports: Some(ports),
+            internal_traffic_policy: svc
+                .internal_traffic_policy
+                .as_ref()
+                .map(|p| p.as_str().to_string()),
             ..Default::default()
         }),
         ..Default::default()
@@ -0,0 +1,90 @@
+//! The StatefulSet an app runs under with `workloadType: StatefulSet`.
+//!
+//! Its pod template is the one [`crate::deployment`] builds. PVC volumes
+//! become volumeClaimTemplates, except those already backed by a claim
+//! recorded in `status.volumeClaims` (a migrated volume, or one kept from
+//! when the app ran as a Deployment), which stay mounted as they are.
+
+use std::collections::HashMap;
+
+use k8s_openapi::api::apps::v1::{Deployment, StatefulSet, StatefulSetSpec};
+use k8s_openapi::api::core::v1::PersistentVolumeClaim;
+use kube::api::ObjectMeta;
+use servarr_crds::{ImageSpec, PvcVolume, ServarrApp, WorkloadType};
+
+use crate::{common, pvc};
+
+/// Volumes served by the StatefulSet's volumeClaimTemplates; empty for
+/// apps that run as a Deployment.
+pub fn template_volumes(app: &ServarrApp) -> Vec<PvcVolume> {
+    if app.spec.workload_type != Some(WorkloadType::StatefulSet) {
+        return Vec::new();
+    }
+    let claims = app.status.as_ref().map(|s| &s.volume_claims);
+    pvc::volumes(app)
+        .into_iter()
+        .filter(|v| claims.is_none_or(|c| !c.contains_key(&v.name)))
+        .collect()
+}
+
+/// Name of the PVC the StatefulSet creates for `volume`:
+/// `{volume}-{app}-0`.
+pub fn template_claim_name(app: &ServarrApp, volume: &str) -> String {
+    format!("{volume}-{}-0", common::app_name(app))
+}
+
+pub fn build(app: &ServarrApp, image_overrides: &HashMap<String, ImageSpec>) -> StatefulSet {
+    build_for_arch(app, image_overrides, None)
+}
+
+/// [`crate::deployment::build_for_arch`] as a StatefulSet.
+pub fn build_for_arch(
+    app: &ServarrApp,
+    image_overrides: &HashMap<String, ImageSpec>,
+    default_arch: Option<&str>,
+) -> StatefulSet {
+    from_deployment(
+        app,
+        crate::deployment::build_for_arch(app, image_overrides, default_arch),
+    )
+}
+
+/// Turn the app's Deployment into its StatefulSet, moving the templated
+/// volumes from pod volumes to volumeClaimTemplates. Anything merged into
+/// the Deployment beforehand, such as a podSpec override, is kept.
+pub fn from_deployment(app: &ServarrApp, deployment: Deployment) -> StatefulSet {
+    let templated = template_volumes(app);
+    let spec = deployment.spec.unwrap_or_default();
+    let mut template = spec.template;
+    if let Some(volumes) = template.spec.as_mut().and_then(|s| s.volumes.as_mut()) {
+        volumes.retain(|v| !templated.iter().any(|t| t.name == v.name));
+    }
+
+    let claim_templates: Vec<PersistentVolumeClaim> = templated
+        .iter()
+        .map(|vol| {
+            let claim = pvc::build_claim(app, vol, &vol.name);
+            PersistentVolumeClaim {
+                metadata: ObjectMeta {
+                    name: Some(vol.name.clone()),
+                    labels: Some(common::labels(app)),
+                    ..Default::default()
+                },
+                ..claim
+            }
+        })
+        .collect();
+
+    StatefulSet {
+        metadata: deployment.metadata,
+        spec: Some(StatefulSetSpec {
+            replicas: spec.replicas,
+            selector: spec.selector,
+            service_name: Some(common::app_name(app)),
+            template,
+            volume_claim_templates: (!claim_templates.is_empty()).then_some(claim_templates),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
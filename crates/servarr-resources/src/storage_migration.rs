@@ -0,0 +1,82 @@
+use k8s_openapi::api::batch::v1::{Job, JobSpec};
+use k8s_openapi::api::core::v1::{
+    Container, PersistentVolumeClaimVolumeSource, PodSpec, PodTemplateSpec, Volume, VolumeMount,
+};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use servarr_crds::{PvcVolume, ServarrApp};
+use std::collections::BTreeMap;
+
+use crate::common;
+
+/// Image providing `rsync` for the copy Job.
+pub const COPY_IMAGE: &str = "instrumentisto/rsync-ssh:alpine";
+
+/// Label carried by copy Job pods, valued with the app name. The pods must
+/// not match the app's selector labels, or its Service would route to them.
+pub const JOB_LABEL: &str = "servarr.dev/storage-migration";
+
+/// Name of the PVC a volume is migrated into: `{app}-{volume}-{storageClass}`.
+pub fn target_claim_name(app: &ServarrApp, vol: &PvcVolume) -> String {
+    common::child_name(app, &format!("{}-{}", vol.name, vol.storage_class))
+}
+
+pub fn job_name(app: &ServarrApp, volume: &str) -> String {
+    common::child_name(app, &format!("migrate-{volume}"))
+}
+
+/// A Job that copies the contents of PVC `source` into PVC `target`,
+/// preserving ownership and permissions. It runs as root so it can read
+/// and chown files of any app user; the app must be scaled down first so
+/// a ReadWriteOnce source can be mounted.
+pub fn build_copy_job(app: &ServarrApp, volume: &str, source: &str, target: &str) -> Job {
+    let claim = |name: &str, claim_name: &str, read_only: bool| Volume {
+        name: name.into(),
+        persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
+            claim_name: claim_name.into(),
+            read_only: Some(read_only),
+        }),
+        ..Default::default()
+    };
+    let mount = |name: &str, read_only: bool| VolumeMount {
+        name: name.into(),
+        mount_path: format!("/{name}"),
+        read_only: Some(read_only),
+        ..Default::default()
+    };
+
+    Job {
+        metadata: common::metadata(app, &format!("migrate-{volume}")),
+        spec: Some(JobSpec {
+            backoff_limit: Some(3),
+            template: PodTemplateSpec {
+                metadata: Some(ObjectMeta {
+                    labels: Some(BTreeMap::from([(JOB_LABEL.into(), common::app_name(app))])),
+                    ..Default::default()
+                }),
+                spec: Some(PodSpec {
+                    restart_policy: Some("Never".into()),
+                    containers: vec![Container {
+                        name: "rsync".into(),
+                        image: Some(COPY_IMAGE.into()),
+                        command: Some(vec![
+                            "rsync".into(),
+                            "-aH".into(),
+                            "--numeric-ids".into(),
+                            "/from/".into(),
+                            "/to/".into(),
+                        ]),
+                        volume_mounts: Some(vec![mount("from", true), mount("to", false)]),
+                        ..Default::default()
+                    }],
+                    volumes: Some(vec![
+                        claim("from", source, true),
+                        claim("to", target, false),
+                    ]),
+                    ..Default::default()
+                }),
+            },
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,57 @@
+use kube::api::DynamicObject;
+use serde_json::json;
+use servarr_crds::{ServarrApp, VolumeSnapshotBackupSpec};
+
+use crate::{common, pvc};
+
+/// Label carried by the app's backup snapshots, valued with the app name.
+pub const SNAPSHOT_LABEL: &str = "servarr.dev/snapshot-of";
+
+/// Label selector for the backup snapshots of `app`.
+pub fn selector(app: &ServarrApp) -> String {
+    format!("{SNAPSHOT_LABEL}={}", common::app_name(app))
+}
+
+/// `{app}-config-{stamp}`, where `stamp` is the UTC time the snapshot was
+/// taken as `YYYYmmddHHMMSS`, so names sort by age.
+pub fn snapshot_name(app: &ServarrApp, stamp: &str) -> String {
+    format!("{}-{stamp}", common::child_name(app, "config"))
+}
+
+/// A VolumeSnapshot (snapshot.storage.k8s.io/v1) of the app's config PVC.
+///
+/// Unlike the app's other children it has no owner reference, so backups
+/// outlive the ServarrApp they were taken from. `None` for apps without
+/// volumes. Uses DynamicObject since the snapshot types are not in
+/// k8s-openapi.
+pub fn build(
+    app: &ServarrApp,
+    spec: &VolumeSnapshotBackupSpec,
+    name: &str,
+) -> Option<DynamicObject> {
+    let volume = pvc::config_volume(app)?;
+    let mut labels = common::labels(app);
+    labels.insert(SNAPSHOT_LABEL.into(), common::app_name(app));
+
+    let mut snapshot_spec = json!({
+        "source": {
+            "persistentVolumeClaimName": pvc::claim_for(app, &volume),
+        },
+    });
+    if let Some(class) = &spec.volume_snapshot_class_name {
+        snapshot_spec["volumeSnapshotClassName"] = json!(class);
+    }
+
+    let snapshot = json!({
+        "apiVersion": "snapshot.storage.k8s.io/v1",
+        "kind": "VolumeSnapshot",
+        "metadata": {
+            "name": name,
+            "namespace": common::app_namespace(app),
+            "labels": labels,
+        },
+        "spec": snapshot_spec,
+    });
+
+    serde_json::from_value(snapshot).ok()
+}
@@ -165,6 +165,66 @@ fn test_deployment_builder_transmission() {
     assert!(volumes.iter().any(|v| v.name == "watch"));
 }
 
+#[test]
+fn test_deployment_lifecycle_hooks() {
+    use servarr_resources::deployment;
+
+    let container = |app: &ServarrApp| {
+        deployment::build(app, &std::collections::HashMap::new())
+            .spec
+            .unwrap()
+            .template
+            .spec
+            .unwrap()
+            .containers
+            .remove(0)
+    };
+    let pre_stop = |c: &k8s_openapi::api::core::v1::Container| {
+        c.lifecycle
+            .as_ref()
+            .and_then(|l| l.pre_stop.as_ref())
+            .and_then(|h| h.exec.as_ref())
+            .and_then(|e| e.command.clone())
+    };
+
+    // Transmission stops its torrents before SIGTERM by default.
+    let mut app = make_app(AppType::Transmission);
+    let command = pre_stop(&container(&app)).unwrap();
+    assert!(command[2].contains("--torrent all --stop"));
+    assert_eq!(
+        deployment::default_pre_stop(&AppType::Sonarr),
+        Some(vec!["sleep".to_string(), "5".to_string()])
+    );
+
+    app.spec.lifecycle = Some(LifecycleSpec {
+        post_start: vec!["touch".into(), "/tmp/started".into()],
+        pre_stop: vec!["transmission-remote".into(), "--exit".into()],
+        termination_grace_period_seconds: Some(90),
+        ..Default::default()
+    });
+    let c = container(&app);
+    assert_eq!(
+        pre_stop(&c).unwrap(),
+        vec!["transmission-remote".to_string(), "--exit".to_string()]
+    );
+    let post_start = c.lifecycle.unwrap().post_start.unwrap().exec.unwrap();
+    assert_eq!(post_start.command.unwrap()[0], "touch");
+    let pod = deployment::build(&app, &std::collections::HashMap::new())
+        .spec
+        .unwrap()
+        .template
+        .spec
+        .unwrap();
+    assert_eq!(pod.termination_grace_period_seconds, Some(90));
+
+    // Defaults can be turned off, leaving no hooks at all.
+    app.spec.lifecycle = Some(LifecycleSpec {
+        default_hooks: false,
+        ..Default::default()
+    });
+    assert!(container(&app).lifecycle.is_none());
+}
+
 #[test]
 fn test_service_builder() {
     let app = make_app(AppType::Radarr);
@@ -207,6 +267,390 @@ fn test_pvc_builder_config_only() {
     assert_eq!(pvcs[0].metadata.name.as_deref(), Some("test-app-config"));
 }
 
+#[test]
+fn test_migrated_volume_uses_mapped_claim() {
+    let mut app = make_app(AppType::Prowlarr);
+    app.status = Some(ServarrAppStatus {
+        volume_claims: std::collections::BTreeMap::from([(
+            "config".into(),
+            "test-app-config-fast".into(),
+        )]),
+        ..Default::default()
+    });
+
+    let pvcs = servarr_resources::pvc::build_all(&app);
+    assert_eq!(
+        pvcs[0].metadata.name.as_deref(),
+        Some("test-app-config-fast")
+    );
+
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let volumes = deploy.spec.unwrap().template.spec.unwrap().volumes.unwrap();
+    let config = volumes.iter().find(|v| v.name == "config").unwrap();
+    assert_eq!(
+        config.persistent_volume_claim.as_ref().unwrap().claim_name,
+        "test-app-config-fast"
+    );
+}
+
+#[test]
+fn test_statefulset_builder_templates_volumes() {
+    let mut app = make_app(AppType::Jellyfin);
+    app.spec.workload_type = Some(WorkloadType::StatefulSet);
+
+    // The StatefulSet creates the config claim itself.
+    let pvcs = servarr_resources::pvc::build_all(&app);
+    assert!(
+        pvcs.iter()
+            .all(|p| p.metadata.name.as_deref() != Some("test-app-config"))
+    );
+
+    let sts = servarr_resources::statefulset::build(&app, &std::collections::HashMap::new());
+    assert_eq!(sts.metadata.name.as_deref(), Some("test-app"));
+    let spec = sts.spec.unwrap();
+    assert_eq!(spec.service_name.as_deref(), Some("test-app"));
+    let templates = spec.volume_claim_templates.unwrap();
+    let config = templates
+        .iter()
+        .find(|t| t.metadata.name.as_deref() == Some("config"))
+        .expect("config volumeClaimTemplate");
+    assert!(config.metadata.owner_references.is_none());
+    let volumes = spec.template.spec.unwrap().volumes.unwrap_or_default();
+    assert!(volumes.iter().all(|v| v.name != "config"));
+    assert_eq!(
+        servarr_resources::statefulset::template_claim_name(&app, "config"),
+        "config-test-app-0"
+    );
+}
+
+#[test]
+fn test_statefulset_keeps_recorded_claims_mounted() {
+    let mut app = make_app(AppType::Jellyfin);
+    app.spec.workload_type = Some(WorkloadType::StatefulSet);
+    app.status = Some(ServarrAppStatus {
+        volume_claims: std::collections::BTreeMap::from([(
+            "config".into(),
+            "test-app-config".into(),
+        )]),
+        ..Default::default()
+    });
+
+    let sts = servarr_resources::statefulset::build(&app, &std::collections::HashMap::new());
+    let spec = sts.spec.unwrap();
+    assert!(
+        spec.volume_claim_templates
+            .unwrap_or_default()
+            .iter()
+            .all(|t| t.metadata.name.as_deref() != Some("config"))
+    );
+    let volumes = spec.template.spec.unwrap().volumes.unwrap();
+    let config = volumes.iter().find(|v| v.name == "config").unwrap();
+    assert_eq!(
+        config.persistent_volume_claim.as_ref().unwrap().claim_name,
+        "test-app-config"
+    );
+}
+
+#[test]
+fn test_backup_upload_and_prune_jobs() {
+    use servarr_crds::ObjectStorageSpec;
+    use servarr_resources::object_storage;
+
+    let app = make_app(AppType::Sonarr);
+    let storage = ObjectStorageSpec {
+        credentials_secret: "s3-credentials".into(),
+        region: Some("eu-west-1".into()),
+        ..Default::default()
+    };
+
+    let upload = object_storage::build_upload_job(&app, &storage, "backups");
+    assert_eq!(
+        upload.metadata.name.as_deref(),
+        Some("test-app-backup-upload")
+    );
+    let pod = upload.spec.unwrap().template.spec.unwrap();
+    assert!(pod.affinity.is_some());
+    let container = &pod.containers[0];
+    assert_eq!(
+        container.args.as_ref().unwrap(),
+        &vec![
+            "copy".to_string(),
+            "/config/Backups".into(),
+            "s3:backups/media/test-app".into(),
+        ]
+    );
+    let env = container.env.as_ref().unwrap();
+    assert!(
+        env.iter()
+            .any(|e| e.name == "RCLONE_CONFIG_S3_PROVIDER" && e.value.as_deref() == Some("AWS"))
+    );
+
+    // Pruning doesn't touch the app's volume, so it can run anywhere
+    let prune = object_storage::build_prune_job(&app, &storage, "backups");
+    assert_eq!(
+        prune.metadata.name.as_deref(),
+        Some("test-app-backup-prune")
+    );
+    let pod = prune.spec.unwrap().template.spec.unwrap();
+    assert!(pod.affinity.is_none());
+    assert!(pod.volumes.is_none());
+    assert_eq!(
+        pod.containers[0].args.as_ref().unwrap(),
+        &vec!["purge".to_string(), "s3:backups/media/test-app".into()]
+    );
+}
+
+#[test]
+fn test_backup_upload_paths_are_per_app_under_a_shared_prefix() {
+    use servarr_crds::ObjectStorageSpec;
+    use servarr_resources::object_storage;
+
+    // An app set or split4k pair shares one objectStorage block
+    let storage = ObjectStorageSpec {
+        credentials_secret: "s3-credentials".into(),
+        bucket: Some("backups".into()),
+        prefix: Some("shared".into()),
+        ..Default::default()
+    };
+    let sonarr = make_app(AppType::Sonarr);
+    let mut sonarr_4k = make_app(AppType::Sonarr);
+    sonarr_4k.metadata.name = Some("test-app-4k".into());
+
+    let args = |job: k8s_openapi::api::batch::v1::Job| {
+        job.spec.unwrap().template.spec.unwrap().containers[0]
+            .args
+            .clone()
+            .unwrap()
+    };
+    let upload = args(object_storage::build_upload_job(
+        &sonarr, &storage, "backups",
+    ));
+    let upload_4k = args(object_storage::build_upload_job(
+        &sonarr_4k, &storage, "backups",
+    ));
+    assert_eq!(upload[2], "s3:backups/shared/media/test-app");
+    assert_eq!(upload_4k[2], "s3:backups/shared/media/test-app-4k");
+
+    let prune = args(object_storage::build_prune_job(
+        &sonarr, &storage, "backups",
+    ));
+    let prune_4k = args(object_storage::build_prune_job(
+        &sonarr_4k, &storage, "backups",
+    ));
+    assert_eq!(prune[1], "s3:backups/shared/media/test-app");
+    assert_eq!(prune_4k[1], "s3:backups/shared/media/test-app-4k");
+    assert_ne!(prune[1], prune_4k[1]);
+}
+
+#[test]
+fn test_restore_stage_job() {
+    use servarr_crds::ObjectStorageSpec;
+    use servarr_resources::restore;
+
+    let app = make_app(AppType::Radarr);
+    let storage = ObjectStorageSpec {
+        credentials_secret: "b2-credentials".into(),
+        endpoint: Some("https://s3.us-west-000.backblazeb2.com".into()),
+        ..Default::default()
+    };
+    let job = restore::build_stage_job(
+        &app,
+        &storage,
+        "backups",
+        "radarr/radarr_backup.zip",
+        "radarr_backup.zip",
+    );
+    assert_eq!(
+        job.metadata.name.as_deref(),
+        Some("test-app-restore-object")
+    );
+    assert_eq!(
+        job.metadata.annotations.unwrap()[restore::SOURCE_ANNOTATION],
+        "s3://backups/radarr/radarr_backup.zip"
+    );
+    let pod = job.spec.unwrap().template.spec.unwrap();
+    let affinity = pod.affinity.unwrap().pod_affinity.unwrap();
+    let term = &affinity
+        .required_during_scheduling_ignored_during_execution
+        .unwrap()[0];
+    assert_eq!(term.topology_key, "kubernetes.io/hostname");
+
+    let container = &pod.containers[0];
+    assert_eq!(
+        container.args.as_ref().unwrap(),
+        &vec![
+            "copyto".to_string(),
+            "s3:backups/radarr/radarr_backup.zip".into(),
+            "/config/Backups/manual/radarr_backup.zip".into(),
+        ]
+    );
+    let env = container.env.as_ref().unwrap();
+    let var = |name: &str| {
+        env.iter()
+            .find(|e| e.name == name)
+            .and_then(|e| e.value.clone())
+    };
+    assert_eq!(var("RCLONE_CONFIG_S3_PROVIDER").as_deref(), Some("Other"));
+    assert!(var("RCLONE_CONFIG_S3_REGION").is_none());
+    assert_eq!(
+        container.env_from.as_ref().unwrap()[0]
+            .secret_ref
+            .as_ref()
+            .unwrap()
+            .name,
+        "b2-credentials"
+    );
+    assert_eq!(
+        pod.volumes.unwrap()[0]
+            .persistent_volume_claim
+            .as_ref()
+            .unwrap()
+            .claim_name,
+        "test-app-config"
+    );
+}
+
+#[test]
+fn test_integrity_check_cronjob() {
+    use servarr_crds::IntegrityCheckSpec;
+    use servarr_resources::integrity_check;
+
+    let check = IntegrityCheckSpec {
+        schedule: "0 5 * * 0".into(),
+        image: None,
+    };
+    assert!(integrity_check::build_cronjob(&make_app(AppType::Jellyfin), &check).is_none());
+
+    let app = make_app(AppType::Sonarr);
+    let cronjob = integrity_check::build_cronjob(&app, &check).unwrap();
+    assert_eq!(
+        cronjob.metadata.name.as_deref(),
+        Some("test-app-integrity-check")
+    );
+    let spec = cronjob.spec.unwrap();
+    assert_eq!(spec.schedule, "0 5 * * 0");
+    assert_eq!(spec.concurrency_policy.as_deref(), Some("Forbid"));
+    let job = spec.job_template.spec.unwrap();
+    assert_eq!(job.backoff_limit, Some(0));
+    let template = job.template;
+    assert_eq!(
+        template.metadata.unwrap().labels.unwrap()[integrity_check::JOB_LABEL],
+        "test-app"
+    );
+    let pod = template.spec.unwrap();
+    assert!(pod.affinity.unwrap().pod_affinity.is_some());
+
+    let container = &pod.containers[0];
+    assert_eq!(container.image.as_deref(), Some("keinos/sqlite3:3.46.1"));
+    assert_eq!(
+        container.env.as_ref().unwrap()[0].value.as_deref(),
+        Some("/config/sonarr.db")
+    );
+    assert_eq!(
+        container.termination_message_policy.as_deref(),
+        Some("FallbackToLogsOnError")
+    );
+    let security = container.security_context.as_ref().unwrap();
+    assert_eq!(security.run_as_user, Some(65534));
+    assert_eq!(
+        pod.volumes.unwrap()[0]
+            .persistent_volume_claim
+            .as_ref()
+            .unwrap()
+            .claim_name,
+        "test-app-config"
+    );
+}
+
+#[test]
+fn test_volume_snapshot_of_config_claim() {
+    use servarr_resources::volume_snapshot;
+
+    let mut app = make_app(AppType::Jellyfin);
+    let name = volume_snapshot::snapshot_name(&app, "20240501030000");
+    assert_eq!(name, "test-app-config-20240501030000");
+
+    let spec = VolumeSnapshotBackupSpec {
+        volume_snapshot_class_name: Some("csi-snapclass".into()),
+    };
+    let snapshot = volume_snapshot::build(&app, &spec, &name).unwrap();
+    assert_eq!(snapshot.metadata.name.as_deref(), Some(name.as_str()));
+    assert!(snapshot.metadata.owner_references.is_none());
+    assert_eq!(
+        snapshot.metadata.labels.as_ref().unwrap()[volume_snapshot::SNAPSHOT_LABEL],
+        "test-app"
+    );
+    assert_eq!(
+        snapshot.data["spec"]["source"]["persistentVolumeClaimName"],
+        "test-app-config"
+    );
+    assert_eq!(
+        snapshot.data["spec"]["volumeSnapshotClassName"],
+        "csi-snapclass"
+    );
+
+    // A StatefulSet's config claim comes from its volumeClaimTemplate.
+    app.spec.workload_type = Some(WorkloadType::StatefulSet);
+    let snapshot = volume_snapshot::build(&app, &Default::default(), &name).unwrap();
+    assert_eq!(
+        snapshot.data["spec"]["source"]["persistentVolumeClaimName"],
+        "config-test-app-0"
+    );
+    assert!(
+        snapshot.data["spec"]
+            .get("volumeSnapshotClassName")
+            .is_none()
+    );
+}
+
+#[test]
+fn test_storage_migration_copy_job() {
+    use servarr_resources::storage_migration;
+
+    let app = make_app(AppType::Sonarr);
+    let vol = PvcVolume {
+        name: "config".into(),
+        storage_class: "fast".into(),
+        migrate_storage: true,
+        ..Default::default()
+    };
+    let target = storage_migration::target_claim_name(&app, &vol);
+    assert_eq!(target, "test-app-config-fast");
+    assert_eq!(
+        storage_migration::job_name(&app, "config"),
+        "test-app-migrate-config"
+    );
+
+    let job = storage_migration::build_copy_job(&app, "config", "test-app-config", &target);
+    assert_eq!(
+        job.metadata.name.as_deref(),
+        Some("test-app-migrate-config")
+    );
+    let template = job.spec.unwrap().template;
+    let labels = template.metadata.unwrap().labels.unwrap();
+    assert_eq!(labels[storage_migration::JOB_LABEL], "test-app");
+    assert!(!labels.contains_key("app.kubernetes.io/name"));
+
+    let pod = template.spec.unwrap();
+    assert_eq!(pod.restart_policy.as_deref(), Some("Never"));
+    let claims: Vec<_> = pod
+        .volumes
+        .unwrap()
+        .into_iter()
+        .map(|v| {
+            let pvc = v.persistent_volume_claim.unwrap();
+            (v.name, pvc.claim_name, pvc.read_only)
+        })
+        .collect();
+    assert_eq!(
+        claims,
+        vec![
+            ("from".into(), "test-app-config".into(), Some(true)),
+            ("to".into(), "test-app-config-fast".into(), Some(false)),
+        ]
+    );
+}
+
 #[test]
 fn test_pvc_ssh_bastion_shell_mode_creates_home_pvcs() {
     let app = ServarrApp {
@@ -228,6 +672,7 @@ fn test_pvc_ssh_bastion_shell_mode_creates_home_pvcs() {
                         restricted_rsync: None,
                         shell: None,
                         public_keys: String::new(),
+                        sftp_chroot: None,
                     },
                     SshUser {
                         name: "bob".into(),
@@ -237,6 +682,7 @@ fn test_pvc_ssh_bastion_shell_mode_creates_home_pvcs() {
                         restricted_rsync: None,
                         shell: None,
                         public_keys: String::new(),
+                        sftp_chroot: None,
                     },
                 ],
                 ..Default::default()
@@ -293,6 +739,7 @@ fn test_pvc_ssh_bastion_non_shell_mode_no_home_pvcs() {
                     restricted_rsync: None,
                     shell: None,
                     public_keys: String::new(),
+                    sftp_chroot: None,
                 }],
                 ..Default::default()
             })),
@@ -333,6 +780,7 @@ fn test_deployment_ssh_bastion_shell_mode_home_mounts() {
                     restricted_rsync: None,
                     shell: None,
                     public_keys: "ssh-ed25519 AAAA".into(),
+                    sftp_chroot: None,
                 }],
                 ..Default::default()
             })),
@@ -435,6 +883,7 @@ fn test_httproute_builder_enabled() {
                 }],
                 hosts: vec!["sonarr.example.com".into()],
                 tls: None,
+                backends: vec![],
             }),
             ..Default::default()
         },
@@ -445,6 +894,35 @@ fn test_httproute_builder_enabled() {
     assert!(route.is_some());
 }
 
+#[test]
+fn test_httproute_weighted_backends() {
+    let mut app = make_app(AppType::Sonarr);
+    app.spec.gateway = Some(GatewaySpec {
+        enabled: true,
+        hosts: vec!["sonarr.example.com".into()],
+        backends: vec![GatewayBackend {
+            name: "test-sonarr-canary".into(),
+            port: None,
+            weight: 10,
+        }],
+        ..Default::default()
+    });
+
+    let route = servarr_resources::httproute::build(&app).expect("should build HTTPRoute");
+    let refs = &route.data["spec"]["rules"][0]["backendRefs"];
+    assert_eq!(refs[0]["weight"], 90);
+    assert_eq!(refs[1]["name"], "test-sonarr-canary");
+    assert_eq!(refs[1]["port"], 8989);
+    assert_eq!(refs[1]["weight"], 10);
+
+    // Without extra backends the app's Service takes all traffic unweighted
+    app.spec.gateway.as_mut().unwrap().backends.clear();
+    let route = servarr_resources::httproute::build(&app).unwrap();
+    let refs = &route.data["spec"]["rules"][0]["backendRefs"];
+    assert_eq!(refs.as_array().unwrap().len(), 1);
+    assert!(refs[0].get("weight").is_none());
+}
+
 #[test]
 fn test_custom_env_override() {
     let app = ServarrApp {
@@ -566,6 +1044,7 @@ fn test_nfs_mounts() {
                     access_mode: "ReadWriteOnce".into(),
                     size: "1Gi".into(),
                     storage_class: String::new(),
+                    ..Default::default()
                 }],
                 nfs_mounts: vec![NfsMount {
                     name: "media".into(),
@@ -574,6 +1053,7 @@ fn test_nfs_mounts() {
                     mount_path: "/media".into(),
                     read_only: true,
                 }],
+                verify_mounts: false,
             }),
             ..Default::default()
         },
@@ -617,6 +1097,42 @@ fn test_image_override_from_env() {
         container.image.as_deref(),
         Some("custom-registry/sonarr:99.0.0")
     );
+    let annotations = deploy.metadata.annotations.unwrap();
+    assert_eq!(
+        annotations[servarr_resources::deployment::IMAGE_SOURCE_ANNOTATION],
+        "operator-override"
+    );
+}
+
+#[test]
+fn test_deployment_records_image_provenance() {
+    use servarr_resources::deployment::{IMAGE_PINNED_BY_ANNOTATION, IMAGE_SOURCE_ANNOTATION};
+
+    let mut app = make_app(AppType::Sonarr);
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let annotations = deploy.metadata.annotations.unwrap();
+    assert_eq!(annotations[IMAGE_SOURCE_ANNOTATION], "default");
+    assert_eq!(annotations[IMAGE_PINNED_BY_ANNOTATION], "tag");
+
+    app.spec.image = Some(ImageSpec {
+        repository: "linuxserver/sonarr".into(),
+        tag: String::new(),
+        digest: "sha256:abc123".into(),
+        pull_policy: "IfNotPresent".into(),
+    });
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let annotations = deploy.metadata.annotations.unwrap();
+    assert_eq!(annotations[IMAGE_SOURCE_ANNOTATION], "spec");
+    assert_eq!(annotations[IMAGE_PINNED_BY_ANNOTATION], "digest");
+
+    // Provenance stays off the pod template so it can't cause a rollout
+    let template_annotations = deploy.spec.unwrap().template.metadata.unwrap().annotations;
+    assert!(
+        template_annotations
+            .unwrap_or_default()
+            .keys()
+            .all(|k| k != IMAGE_SOURCE_ANNOTATION)
+    );
 }
 
 #[test]
@@ -737,6 +1253,89 @@ fn test_cr_image_overrides_env_override() {
     assert_eq!(container.image.as_deref(), Some("cr-level/sonarr:cr-tag"));
 }
 
+#[test]
+fn test_pinned_arch_from_node_selector_and_affinity() {
+    let mut app = make_app(AppType::Sonarr);
+    assert_eq!(servarr_resources::common::pinned_arch(&app), None);
+
+    app.spec.scheduling = Some(NodeScheduling {
+        node_selector: [("kubernetes.io/arch".to_string(), "arm64".to_string())].into(),
+        ..Default::default()
+    });
+    assert_eq!(
+        servarr_resources::common::pinned_arch(&app).as_deref(),
+        Some("arm64")
+    );
+
+    let affinity = |values: serde_json::Value| {
+        serde_json::json!({
+            "nodeAffinity": {
+                "requiredDuringSchedulingIgnoredDuringExecution": {
+                    "nodeSelectorTerms": [{
+                        "matchExpressions": [{
+                            "key": "kubernetes.io/arch",
+                            "operator": "In",
+                            "values": values,
+                        }]
+                    }]
+                }
+            }
+        })
+    };
+    app.spec.scheduling = Some(NodeScheduling {
+        affinity: Some(affinity(serde_json::json!(["arm"]))),
+        ..Default::default()
+    });
+    assert_eq!(
+        servarr_resources::common::pinned_arch(&app).as_deref(),
+        Some("arm")
+    );
+
+    // Several allowed architectures do not pin one
+    app.spec.scheduling = Some(NodeScheduling {
+        affinity: Some(affinity(serde_json::json!(["amd64", "arm64"]))),
+        ..Default::default()
+    });
+    assert_eq!(servarr_resources::common::pinned_arch(&app), None);
+}
+
+#[test]
+fn test_default_image_refuses_unsupported_arch() {
+    let overrides = std::collections::HashMap::new();
+    let mut app = make_app(AppType::Plex);
+    assert!(servarr_resources::deployment::default_image(&app, &overrides, Some("arm64")).is_ok());
+    let err =
+        servarr_resources::deployment::default_image(&app, &overrides, Some("arm")).unwrap_err();
+    assert_eq!(err.arch, "arm");
+
+    // The app's own pin wins over the operator default
+    app.spec.scheduling = Some(NodeScheduling {
+        node_selector: [("kubernetes.io/arch".to_string(), "amd64".to_string())].into(),
+        ..Default::default()
+    });
+    assert!(servarr_resources::deployment::default_image(&app, &overrides, Some("arm")).is_ok());
+}
+
+#[test]
+fn test_default_image_trusts_env_override_on_any_arch() {
+    let app = make_app(AppType::Plex);
+    let overrides = std::collections::HashMap::from([(
+        "plex".to_string(),
+        ImageSpec {
+            repository: "example/plex-armhf".into(),
+            tag: "1.0".into(),
+            digest: String::new(),
+            pull_policy: "IfNotPresent".into(),
+        },
+    )]);
+    let img = servarr_resources::deployment::default_image(&app, &overrides, Some("arm")).unwrap();
+    assert_eq!(img.repository, "example/plex-armhf");
+
+    let deploy = servarr_resources::deployment::build_for_arch(&app, &overrides, Some("arm"));
+    let container = &deploy.spec.unwrap().template.spec.unwrap().containers[0];
+    assert_eq!(container.image.as_deref(), Some("example/plex-armhf:1.0"));
+}
+
 // ---------------------------------------------------------------------------
 // secret::build_authorized_keys tests
 // ---------------------------------------------------------------------------
@@ -798,6 +1397,7 @@ fn test_secret_ssh_app_users_with_empty_keys_returns_none() {
                     restricted_rsync: None,
                     shell: None,
                     public_keys: String::new(),
+                    sftp_chroot: None,
                 }],
                 ..Default::default()
             })),
@@ -830,6 +1430,7 @@ fn test_secret_ssh_app_valid_users_returns_secret() {
                         restricted_rsync: None,
                         shell: None,
                         public_keys: "ssh-ed25519 AAAA alice@host".into(),
+                        sftp_chroot: None,
                     },
                     SshUser {
                         name: "bob".into(),
@@ -839,6 +1440,7 @@ fn test_secret_ssh_app_valid_users_returns_secret() {
                         restricted_rsync: None,
                         shell: None,
                         public_keys: "ssh-rsa BBBB bob@host".into(),
+                        sftp_chroot: None,
                     },
                 ],
                 ..Default::default()
@@ -1109,6 +1711,7 @@ fn test_tcproute_http_route_no_tls_returns_none() {
                 }],
                 hosts: vec!["sonarr.example.com".into()],
                 tls: None,
+                backends: vec![],
             }),
             ..Default::default()
         },
@@ -1139,6 +1742,7 @@ fn test_tcproute_tcp_route_type_returns_some() {
                 }],
                 hosts: vec![],
                 tls: None,
+                backends: vec![],
             }),
             ..Default::default()
         },
@@ -1190,6 +1794,7 @@ fn test_tcproute_http_route_with_tls_enabled_returns_some() {
                     cert_issuer: "letsencrypt".into(),
                     secret_name: None,
                 }),
+                backends: vec![],
             }),
             ..Default::default()
         },
@@ -1226,6 +1831,7 @@ fn test_tcproute_parent_refs_with_namespace_and_section_name() {
                 }],
                 hosts: vec![],
                 tls: None,
+                backends: vec![],
             }),
             ..Default::default()
         },
@@ -1258,6 +1864,7 @@ fn test_configmap_sabnzbd_with_host_whitelist() {
             app_config: Some(AppConfig::Sabnzbd(SabnzbdConfig {
                 host_whitelist: vec!["sabnzbd.example.com".into(), "sab.local".into()],
                 tar_unpack: false,
+                credentials_secret_ref: None,
             })),
             ..Default::default()
         },
@@ -1290,6 +1897,7 @@ fn test_configmap_sabnzbd_empty_host_whitelist_returns_none() {
             app_config: Some(AppConfig::Sabnzbd(SabnzbdConfig {
                 host_whitelist: vec![],
                 tar_unpack: false,
+                credentials_secret_ref: None,
             })),
             ..Default::default()
         },
@@ -1325,6 +1933,7 @@ fn test_configmap_ssh_bastion_restricted_rsync() {
                     }),
                     shell: None,
                     public_keys: "ssh-ed25519 AAAA".into(),
+                    sftp_chroot: None,
                 }],
                 ..Default::default()
             })),
@@ -1375,6 +1984,7 @@ fn test_configmap_ssh_bastion_rsync_mode_produces_configmap() {
                     restricted_rsync: None,
                     shell: None,
                     public_keys: "ssh-ed25519 AAAA".into(),
+                    sftp_chroot: None,
                 }],
                 ..Default::default()
             })),
@@ -1459,6 +2069,7 @@ fn test_configmap_prowlarr_definitions() {
                         content: "id: another\nname: Another".into(),
                     },
                 ],
+                ..Default::default()
             })),
             ..Default::default()
         },
@@ -1490,6 +2101,7 @@ fn test_configmap_prowlarr_empty_definitions_returns_none() {
             app: AppType::Prowlarr,
             app_config: Some(AppConfig::Prowlarr(ProwlarrConfig {
                 custom_definitions: vec![],
+                ..Default::default()
             })),
             ..Default::default()
         },
@@ -1517,6 +2129,7 @@ fn test_configmap_tar_unpack_enabled() {
             app_config: Some(AppConfig::Sabnzbd(SabnzbdConfig {
                 host_whitelist: vec![],
                 tar_unpack: true,
+                credentials_secret_ref: None,
             })),
             ..Default::default()
         },
@@ -1550,6 +2163,7 @@ fn test_configmap_tar_unpack_disabled_returns_none() {
             app_config: Some(AppConfig::Sabnzbd(SabnzbdConfig {
                 host_whitelist: vec![],
                 tar_unpack: false,
+                credentials_secret_ref: None,
             })),
             ..Default::default()
         },
@@ -1596,32 +2210,105 @@ fn test_configmap_transmission_custom_settings() {
     assert!(settings.contains("speed-limit-up"));
 }
 
-// ============================================================
-// Deployment coverage tests
-// ============================================================
-
 #[test]
-fn test_deployment_ssh_bastion_init_containers() {
-    let app = ServarrApp {
-        metadata: ObjectMeta {
-            name: Some("bastion".into()),
-            namespace: Some("infra".into()),
-            uid: Some("uid-ssh-deploy".into()),
+fn test_transmission_directories_rendered_and_created() {
+    let mut app = make_app(AppType::Transmission);
+    app.spec.app_config = Some(AppConfig::Transmission(TransmissionConfig {
+        directories: Some(TransmissionDirectories {
+            download: Some("/downloads/complete".into()),
+            incomplete: Some("/downloads/incomplete".into()),
+            watch_enabled: false,
+            categories: std::collections::BTreeMap::from([(
+                "tv-sonarr".into(),
+                "/downloads/complete/tv".into(),
+            )]),
             ..Default::default()
-        },
-        spec: ServarrAppSpec {
-            app: AppType::SshBastion,
-            app_config: Some(AppConfig::SshBastion(SshBastionConfig {
-                users: vec![SshUser {
-                    name: "backup".into(),
-                    uid: 1000,
-                    gid: 1000,
-                    mode: SshMode::RestrictedRsync,
-                    restricted_rsync: Some(RestrictedRsyncConfig {
-                        allowed_paths: vec!["/data".into()],
-                    }),
-                    shell: None,
-                    public_keys: "ssh-ed25519 AAAA".into(),
+        }),
+        ..Default::default()
+    }));
+
+    let data = servarr_resources::configmap::build(&app)
+        .unwrap()
+        .data
+        .unwrap();
+    let settings: serde_json::Value =
+        serde_json::from_str(&data["settings-override.json"]).unwrap();
+    assert_eq!(settings["download-dir"], "/downloads/complete");
+    assert_eq!(settings["incomplete-dir"], "/downloads/incomplete");
+    assert_eq!(settings["incomplete-dir-enabled"], true);
+    assert_eq!(settings["watch-dir"], "/watch");
+    assert_eq!(settings["watch-dir-enabled"], false);
+    // Defaults not covered by directories are kept.
+    assert_eq!(settings["dht-enabled"], true);
+    let script = &data["apply-settings.sh"];
+    assert!(script.contains("mkdir -p '/downloads/complete/tv'"));
+    assert!(script.contains("mkdir -p '/downloads/incomplete'"));
+
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let init = deploy
+        .spec
+        .unwrap()
+        .template
+        .spec
+        .unwrap()
+        .init_containers
+        .unwrap();
+    let apply = init.iter().find(|c| c.name == "apply-settings").unwrap();
+    let mounts: Vec<&str> = apply
+        .volume_mounts
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|m| m.mount_path.as_str())
+        .collect();
+    assert!(mounts.contains(&"/downloads"), "{mounts:?}");
+    assert!(mounts.contains(&"/watch"), "{mounts:?}");
+    assert_eq!(mounts.iter().filter(|m| **m == "/config").count(), 1);
+}
+
+#[test]
+fn test_transmission_without_directories_keeps_init_mounts() {
+    let app = make_app(AppType::Transmission);
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let init = deploy
+        .spec
+        .unwrap()
+        .template
+        .spec
+        .unwrap()
+        .init_containers
+        .unwrap();
+    let apply = init.iter().find(|c| c.name == "apply-settings").unwrap();
+    assert_eq!(apply.volume_mounts.as_ref().unwrap().len(), 2);
+}
+
+// ============================================================
+// Deployment coverage tests
+// ============================================================
+
+#[test]
+fn test_deployment_ssh_bastion_init_containers() {
+    let app = ServarrApp {
+        metadata: ObjectMeta {
+            name: Some("bastion".into()),
+            namespace: Some("infra".into()),
+            uid: Some("uid-ssh-deploy".into()),
+            ..Default::default()
+        },
+        spec: ServarrAppSpec {
+            app: AppType::SshBastion,
+            app_config: Some(AppConfig::SshBastion(SshBastionConfig {
+                users: vec![SshUser {
+                    name: "backup".into(),
+                    uid: 1000,
+                    gid: 1000,
+                    mode: SshMode::RestrictedRsync,
+                    restricted_rsync: Some(RestrictedRsyncConfig {
+                        allowed_paths: vec!["/data".into()],
+                    }),
+                    shell: None,
+                    public_keys: "ssh-ed25519 AAAA".into(),
+                    sftp_chroot: None,
                 }],
                 ..Default::default()
             })),
@@ -1723,6 +2410,7 @@ fn test_deployment_ssh_bastion_rsync_mode_uses_restricted_rsync() {
                     restricted_rsync: None,
                     shell: None,
                     public_keys: "ssh-ed25519 AAAA".into(),
+                    sftp_chroot: None,
                 }],
                 ..Default::default()
             })),
@@ -1781,6 +2469,7 @@ fn test_deployment_ssh_bastion_shell_package_installed() {
                     restricted_rsync: None,
                     shell: Some("/bin/bash".into()),
                     public_keys: String::new(),
+                    sftp_chroot: None,
                 }],
                 ..Default::default()
             })),
@@ -1825,6 +2514,7 @@ fn test_deployment_ssh_bastion_default_shell_no_install() {
                     restricted_rsync: None,
                     shell: None,
                     public_keys: String::new(),
+                    sftp_chroot: None,
                 }],
                 ..Default::default()
             })),
@@ -1860,6 +2550,7 @@ fn test_deployment_sabnzbd_host_whitelist_init_container() {
             app_config: Some(AppConfig::Sabnzbd(SabnzbdConfig {
                 host_whitelist: vec!["sabnzbd.example.com".into()],
                 tar_unpack: false,
+                credentials_secret_ref: None,
             })),
             ..Default::default()
         },
@@ -1898,6 +2589,7 @@ fn test_deployment_sabnzbd_tar_unpack_init_containers() {
             app_config: Some(AppConfig::Sabnzbd(SabnzbdConfig {
                 host_whitelist: vec![],
                 tar_unpack: true,
+                credentials_secret_ref: None,
             })),
             ..Default::default()
         },
@@ -1937,6 +2629,7 @@ fn test_deployment_prowlarr_definitions_volume() {
                     name: "my-tracker".into(),
                     content: "id: my-tracker".into(),
                 }],
+                ..Default::default()
             })),
             ..Default::default()
         },
@@ -1964,6 +2657,44 @@ fn test_deployment_prowlarr_definitions_volume() {
     );
 }
 
+#[test]
+fn test_deployment_definitions_checksum_changes_with_content() {
+    let prowlarr = |content: &str| {
+        let mut app = make_app(AppType::Prowlarr);
+        app.spec.app_config = Some(AppConfig::Prowlarr(ProwlarrConfig {
+            custom_definitions: vec![IndexerDefinition {
+                name: "my-tracker".into(),
+                content: content.into(),
+            }],
+            ..Default::default()
+        }));
+        app
+    };
+    let pod_annotations = |app: &ServarrApp| {
+        servarr_resources::deployment::build(app, &std::collections::HashMap::new())
+            .spec
+            .unwrap()
+            .template
+            .metadata
+            .unwrap()
+            .annotations
+            .unwrap_or_default()
+    };
+
+    let before = pod_annotations(&prowlarr("id: my-tracker"));
+    let after = pod_annotations(&prowlarr("id: my-tracker\nname: Renamed"));
+    let key = servarr_resources::deployment::DEFINITIONS_CHECKSUM_ANNOTATION;
+    assert!(before.contains_key(key));
+    assert_ne!(before.get(key), after.get(key));
+    assert_eq!(
+        before.get("servarr.dev/config-checksum"),
+        after.get("servarr.dev/config-checksum"),
+        "definitions should not change the general config checksum"
+    );
+
+    assert!(!pod_annotations(&make_app(AppType::Sonarr)).contains_key(key));
+}
+
 #[test]
 fn test_deployment_custom_resources() {
     let app = ServarrApp {
@@ -2071,6 +2802,7 @@ fn test_deployment_gpu_resources() {
                 nvidia: Some(1),
                 intel: Some(1),
                 amd: None,
+                required: false,
             }),
             ..Default::default()
         },
@@ -2119,6 +2851,7 @@ fn test_networkpolicy_gateway_namespace_ingress() {
                 }],
                 hosts: vec!["sonarr.example.com".into()],
                 tls: None,
+                backends: vec![],
             }),
             ..Default::default()
         },
@@ -2244,6 +2977,7 @@ fn test_networkpolicy_internet_egress_default_denied_cidrs() {
                 allow_internet_egress: true,
                 denied_cidr_blocks: vec![],
                 custom_egress_rules: vec![],
+                allowed_namespaces: vec![],
             }),
             ..Default::default()
         },
@@ -2293,6 +3027,7 @@ fn test_networkpolicy_internet_egress_custom_denied_cidrs() {
                 allow_internet_egress: true,
                 denied_cidr_blocks: vec!["10.0.0.0/8".into(), "192.168.0.0/16".into()],
                 custom_egress_rules: vec![],
+                allowed_namespaces: vec![],
             }),
             ..Default::default()
         },
@@ -2352,6 +3087,7 @@ fn test_networkpolicy_custom_egress_rules() {
                 allow_internet_egress: false,
                 denied_cidr_blocks: vec![],
                 custom_egress_rules: vec![custom_rule],
+                allowed_namespaces: vec![],
             }),
             ..Default::default()
         },
@@ -2379,6 +3115,41 @@ fn test_networkpolicy_custom_egress_rules() {
     );
 }
 
+#[test]
+fn test_networkpolicy_allowed_namespaces() {
+    let app = ServarrApp {
+        metadata: ObjectMeta {
+            name: Some("sabnzbd".into()),
+            namespace: Some("downloads".into()),
+            uid: Some("uid-np-allowed-ns".into()),
+            ..Default::default()
+        },
+        spec: ServarrAppSpec {
+            app: AppType::Sabnzbd,
+            network_policy_config: Some(NetworkPolicyConfig {
+                allowed_namespaces: vec!["media".into()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        status: None,
+    };
+
+    let np = servarr_resources::networkpolicy::build(&app);
+    let spec = np.spec.unwrap();
+    let selects_media =
+        |peers: &Option<Vec<k8s_openapi::api::networking::v1::NetworkPolicyPeer>>| {
+            peers.iter().flatten().any(|p| {
+                p.namespace_selector
+                    .as_ref()
+                    .and_then(|s| s.match_labels.as_ref())
+                    .is_some_and(|l| l.get("kubernetes.io/metadata.name") == Some(&"media".into()))
+            })
+        };
+    assert!(spec.ingress.unwrap().iter().any(|r| selects_media(&r.from)));
+    assert!(spec.egress.unwrap().iter().any(|r| selects_media(&r.to)));
+}
+
 #[test]
 fn test_networkpolicy_ssh_bastion_nfs_egress() {
     let app = ServarrApp {
@@ -2401,6 +3172,7 @@ fn test_networkpolicy_ssh_bastion_nfs_egress() {
                     access_mode: "ReadWriteOnce".into(),
                     size: "10Mi".into(),
                     storage_class: String::new(),
+                    ..Default::default()
                 }],
                 nfs_mounts: vec![NfsMount {
                     name: "media".into(),
@@ -2409,6 +3181,7 @@ fn test_networkpolicy_ssh_bastion_nfs_egress() {
                     mount_path: "/media".into(),
                     read_only: true,
                 }],
+                verify_mounts: false,
             }),
             ..Default::default()
         },
@@ -2450,6 +3223,7 @@ fn test_networkpolicy_allow_dns_false() {
                 allow_internet_egress: false,
                 denied_cidr_blocks: vec![],
                 custom_egress_rules: vec![],
+                allowed_namespaces: vec![],
             }),
             ..Default::default()
         },
@@ -2684,6 +3458,34 @@ fn test_service_builder_prowlarr_default_port() {
     assert_eq!(ports[0].port, 9696);
 }
 
+#[test]
+fn test_service_builder_traffic_policy_and_topology() {
+    let mut app = make_app(AppType::Radarr);
+    let svc = servarr_resources::service::build(&app);
+    assert!(svc.metadata.annotations.is_none());
+    assert!(svc.spec.unwrap().internal_traffic_policy.is_none());
+
+    app.spec.service = Some(ServiceSpec {
+        service_type: "ClusterIP".into(),
+        ports: vec![ServicePort {
+            name: "http".into(),
+            port: 7878,
+            ..Default::default()
+        }],
+        internal_traffic_policy: Some(TrafficPolicy::Local),
+        topology_aware_routing: true,
+    });
+    let svc = servarr_resources::service::build(&app);
+    assert_eq!(
+        svc.metadata.annotations.unwrap()[servarr_resources::service::TOPOLOGY_MODE_ANNOTATION],
+        "Auto"
+    );
+    assert_eq!(
+        svc.spec.unwrap().internal_traffic_policy.as_deref(),
+        Some("Local")
+    );
+}
+
 #[test]
 fn test_service_builder_transmission_with_peer_port() {
     let app = ServarrApp {
@@ -2773,6 +3575,7 @@ fn test_networkpolicy_allow_same_namespace_false() {
                 allow_internet_egress: false,
                 denied_cidr_blocks: vec![],
                 custom_egress_rules: vec![],
+                allowed_namespaces: vec![],
             }),
             ..Default::default()
         },
@@ -2815,6 +3618,7 @@ fn test_deployment_ssh_bastion_advanced_env_vars() {
                     restricted_rsync: None,
                     shell: None,
                     public_keys: "ssh-ed25519 AAAA".into(),
+                    sftp_chroot: None,
                 }],
                 tcp_forwarding: true,
                 gateway_ports: true,
@@ -2866,6 +3670,7 @@ fn test_deployment_ssh_bastion_managed_env_ignored() {
                     restricted_rsync: None,
                     shell: None,
                     public_keys: "ssh-ed25519 AAAA".into(),
+                    sftp_chroot: None,
                 }],
                 ..Default::default()
             })),
@@ -2939,6 +3744,7 @@ fn test_deployment_ssh_bastion_host_keys_preserved_with_nfs_mounts() {
                         read_only: false,
                     },
                 ],
+                verify_mounts: false,
             }),
             app_config: Some(AppConfig::SshBastion(SshBastionConfig {
                 users: vec![SshUser {
@@ -2949,6 +3755,7 @@ fn test_deployment_ssh_bastion_host_keys_preserved_with_nfs_mounts() {
                     restricted_rsync: None,
                     shell: None,
                     public_keys: "ssh-ed25519 AAAA".into(),
+                    sftp_chroot: None,
                 }],
                 ..Default::default()
             })),
@@ -3013,6 +3820,7 @@ fn test_nfs_server_statefulset_name_and_namespace() {
         "mystack",
         "media",
         &nfs,
+        None,
         make_owner_ref(),
     );
     assert_eq!(ss.metadata.name.as_deref(), Some("mystack-nfs-server"));
@@ -3026,6 +3834,7 @@ fn test_nfs_server_statefulset_replicas_and_service_name() {
         "mystack",
         "media",
         &nfs,
+        None,
         make_owner_ref(),
     );
     let spec = ss.spec.unwrap();
@@ -3040,6 +3849,7 @@ fn test_nfs_server_statefulset_port_2049() {
         "mystack",
         "media",
         &nfs,
+        None,
         make_owner_ref(),
     );
     let spec = ss.spec.unwrap();
@@ -3059,6 +3869,7 @@ fn test_nfs_server_statefulset_export_dir_mount() {
         "mystack",
         "media",
         &nfs,
+        None,
         make_owner_ref(),
     );
     let spec = ss.spec.unwrap();
@@ -3082,6 +3893,7 @@ fn test_nfs_server_statefulset_volume_claim_template_storage_size() {
         "mystack",
         "media",
         &nfs,
+        None,
         make_owner_ref(),
     );
     let spec = ss.spec.unwrap();
@@ -3100,6 +3912,96 @@ fn test_nfs_server_statefulset_volume_claim_template_storage_size() {
     assert_eq!(storage.0, "500Gi");
 }
 
+#[test]
+fn test_nfs_server_statefulset_named_exports() {
+    let nfs = NfsServerSpec {
+        exports: vec![
+            NfsExport {
+                name: "downloads".into(),
+                size: Some("200Gi".into()),
+                squash: NfsSquash::AllSquash,
+                anon_uid: Some(1000),
+                anon_gid: Some(1000),
+                ..Default::default()
+            },
+            NfsExport {
+                name: "media".into(),
+                read_only: true,
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let ss = servarr_resources::nfs_server::build_statefulset(
+        "mystack",
+        "media",
+        &nfs,
+        None,
+        make_owner_ref(),
+    );
+    let pod = ss.spec.unwrap().template.spec.unwrap();
+    let container = &pod.containers[0];
+    let env: Vec<_> = container
+        .env
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|e| (e.name.as_str(), e.value.as_deref().unwrap()))
+        .collect();
+    assert_eq!(env.len(), 3);
+    assert_eq!(
+        env[1],
+        (
+            "NFS_EXPORT_1",
+            "/nfsshare/downloads *(rw,async,no_subtree_check,no_auth_nlm,insecure,all_squash,anonuid=1000,anongid=1000,fsid=1)"
+        )
+    );
+    assert!(env[2].1.starts_with("/nfsshare/media *(ro,"));
+    assert!(env[2].1.ends_with("no_root_squash,fsid=2)"));
+
+    // Only the sized export gets a PVC of its own, mounted over its directory
+    let volumes = pod.volumes.unwrap();
+    assert_eq!(volumes.len(), 1);
+    assert_eq!(
+        volumes[0]
+            .persistent_volume_claim
+            .as_ref()
+            .unwrap()
+            .claim_name,
+        "mystack-nfs-downloads"
+    );
+    let mounts = container.volume_mounts.as_ref().unwrap();
+    assert!(
+        mounts
+            .iter()
+            .any(|m| m.name == "export-downloads" && m.mount_path == "/nfsshare/downloads")
+    );
+    let mkdir = &pod.init_containers.unwrap()[0];
+    let script = &mkdir.command.as_ref().unwrap()[2];
+    assert!(script.contains("/nfsshare/media/movies"), "{script}");
+    assert!(script.contains("/nfsshare/downloads"), "{script}");
+    assert_eq!(mkdir.volume_mounts.as_ref().unwrap().len(), 2);
+
+    let pvcs = servarr_resources::nfs_server::build_export_pvcs("mystack", "media", &nfs);
+    assert_eq!(pvcs.len(), 1);
+    assert_eq!(
+        pvcs[0].metadata.name.as_deref(),
+        Some("mystack-nfs-downloads")
+    );
+    assert!(pvcs[0].metadata.owner_references.is_none());
+    let storage = &pvcs[0]
+        .spec
+        .as_ref()
+        .unwrap()
+        .resources
+        .as_ref()
+        .unwrap()
+        .requests
+        .as_ref()
+        .unwrap()["storage"];
+    assert_eq!(storage.0, "200Gi");
+}
+
 #[test]
 fn test_nfs_server_statefulset_custom_image() {
     let nfs = NfsServerSpec {
@@ -3115,6 +4017,7 @@ fn test_nfs_server_statefulset_custom_image() {
         "mystack",
         "media",
         &nfs,
+        None,
         make_owner_ref(),
     );
     let spec = ss.spec.unwrap();
@@ -3125,6 +4028,38 @@ fn test_nfs_server_statefulset_custom_image() {
     );
 }
 
+#[test]
+fn test_nfs_server_statefulset_spread() {
+    let nfs = NfsServerSpec::default();
+    let ss = servarr_resources::nfs_server::build_statefulset(
+        "mystack",
+        "media",
+        &nfs,
+        None,
+        make_owner_ref(),
+    );
+    let pod = ss.spec.unwrap().template.spec.unwrap();
+    assert!(pod.topology_spread_constraints.is_none());
+
+    let spread = SpreadPolicy {
+        topology: SpreadTopology::Zone,
+        required: true,
+    };
+    let ss = servarr_resources::nfs_server::build_statefulset(
+        "mystack",
+        "media",
+        &nfs,
+        Some(&spread),
+        make_owner_ref(),
+    );
+    let template = ss.spec.unwrap().template;
+    let labels = template.metadata.unwrap().labels.unwrap();
+    assert_eq!(labels["servarr.dev/stack"], "mystack");
+    let constraints = template.spec.unwrap().topology_spread_constraints.unwrap();
+    assert_eq!(constraints[0].topology_key, "topology.kubernetes.io/zone");
+    assert_eq!(constraints[0].when_unsatisfiable, "DoNotSchedule");
+}
+
 #[test]
 fn test_nfs_server_service_name_and_namespace() {
     let svc = servarr_resources::nfs_server::build_service("mystack", "media", make_owner_ref());
@@ -3161,78 +4096,345 @@ fn test_nfs_server_service_selector_labels() {
 }
 
 #[test]
-fn test_deployment_no_gpu_no_node_selector() {
-    let app = make_app(AppType::Sonarr);
-    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
-    let pod_spec = deploy.spec.unwrap().template.spec.unwrap();
-    assert!(
-        pod_spec.node_selector.is_none(),
-        "no GPU spec should produce no nodeSelector"
+fn test_nfs_backup_cronjob_mounts_server_claim_next_to_server() {
+    let rsync = NfsRsyncSpec {
+        schedule: "0 5 * * 0".into(),
+        destination: "backup@nas:/backups/media".into(),
+        port: 2222,
+        ssh_secret: "nfs-backup-ssh".into(),
+        delete: false,
+        image: None,
+    };
+    let cronjob = servarr_resources::nfs_server::build_backup_cronjob(
+        "mystack",
+        "media",
+        &rsync,
+        make_owner_ref(),
     );
+    assert_eq!(cronjob.metadata.name.as_deref(), Some("mystack-nfs-backup"));
+    let spec = cronjob.spec.unwrap();
+    assert_eq!(spec.schedule, "0 5 * * 0");
+    assert_eq!(spec.concurrency_policy.as_deref(), Some("Forbid"));
+
+    let pod = spec.job_template.spec.unwrap().template.spec.unwrap();
+    let affinity = pod.affinity.unwrap().pod_affinity.unwrap();
+    let term = &affinity
+        .required_during_scheduling_ignored_during_execution
+        .unwrap()[0];
+    assert_eq!(term.topology_key, "kubernetes.io/hostname");
+    assert_eq!(
+        term.label_selector
+            .as_ref()
+            .unwrap()
+            .match_labels
+            .as_ref()
+            .unwrap()["servarr.dev/component"],
+        "nfs-server"
+    );
+
+    let volumes = pod.volumes.unwrap();
+    let data = volumes[0].persistent_volume_claim.as_ref().unwrap();
+    assert_eq!(data.claim_name, "data-mystack-nfs-server-0");
+    assert_eq!(data.read_only, Some(true));
+    let ssh = volumes[1].secret.as_ref().unwrap();
+    assert_eq!(ssh.secret_name.as_deref(), Some("nfs-backup-ssh"));
+
+    let env: std::collections::BTreeMap<_, _> = pod.containers[0]
+        .env
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|e| (e.name.as_str(), e.value.as_deref().unwrap_or("")))
+        .collect();
+    assert_eq!(env["RSYNC_DEST"], "backup@nas:/backups/media");
+    assert_eq!(env["SSH_PORT"], "2222");
+    assert_eq!(env["RSYNC_DELETE"], "");
 }
 
 #[test]
-fn test_deployment_intel_gpu_adds_nfd_node_selector() {
-    let mut app = make_app(AppType::Jellyfin);
-    app.spec.gpu = Some(servarr_crds::GpuSpec {
-        intel: Some(1),
-        ..Default::default()
-    });
-    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
-    let pod_spec = deploy.spec.unwrap().template.spec.unwrap();
-    let sel = pod_spec
-        .node_selector
-        .expect("nodeSelector must be set for Intel GPU");
+fn test_nfs_snapshot_targets_server_claim_without_owner() {
+    let snapshot = NfsSnapshotSpec {
+        schedule: "0 4 * * *".into(),
+        volume_snapshot_class: Some("csi-snapclass".into()),
+        retention_count: 7,
+    };
+    let obj = servarr_resources::nfs_server::build_snapshot(
+        "mystack",
+        "media",
+        &snapshot,
+        "mystack-nfs-20250601040000",
+    )
+    .unwrap();
     assert_eq!(
-        sel.get("gpu.intel.com/i915").map(|s| s.as_str()),
-        Some("true")
+        obj.metadata.name.as_deref(),
+        Some("mystack-nfs-20250601040000")
     );
-    assert!(!sel.contains_key("gpu.nvidia.com/present"));
-    assert!(!sel.contains_key("gpu.amd.com/present"));
+    assert!(obj.metadata.owner_references.is_none());
+    assert_eq!(
+        obj.metadata.labels.as_ref().unwrap()["servarr.dev/component"],
+        "nfs-snapshot"
+    );
+    assert_eq!(
+        obj.data["spec"]["source"]["persistentVolumeClaimName"],
+        "data-mystack-nfs-server-0"
+    );
+    assert_eq!(obj.data["spec"]["volumeSnapshotClassName"], "csi-snapclass");
 }
 
+// ---------------------------------------------------------------------------
+// Shared Postgres resource builders
+// ---------------------------------------------------------------------------
+
 #[test]
-fn test_deployment_nvidia_gpu_adds_nfd_node_selector() {
-    let mut app = make_app(AppType::Jellyfin);
-    app.spec.gpu = Some(servarr_crds::GpuSpec {
-        nvidia: Some(1),
-        ..Default::default()
-    });
-    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
-    let pod_spec = deploy.spec.unwrap().template.spec.unwrap();
-    let sel = pod_spec
-        .node_selector
-        .expect("nodeSelector must be set for NVIDIA GPU");
+fn test_postgres_statefulset_containers_and_secret_refs() {
+    let pg = PostgresSpec::default();
+    let ss =
+        servarr_resources::postgres::build_statefulset("mystack", "media", &pg, make_owner_ref());
+    assert_eq!(ss.metadata.name.as_deref(), Some("mystack-postgres"));
+    let spec = ss.spec.unwrap();
+    let pod_spec = spec.template.spec.unwrap();
+    let names: Vec<&str> = pod_spec
+        .containers
+        .iter()
+        .map(|c| c.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["postgres", "provisioner"]);
+
+    let pg_env = pod_spec.containers[0].env.as_ref().unwrap();
+    let pw = pg_env
+        .iter()
+        .find(|e| e.name == "POSTGRES_PASSWORD")
+        .unwrap();
+    let key_ref = pw
+        .value_from
+        .as_ref()
+        .unwrap()
+        .secret_key_ref
+        .as_ref()
+        .unwrap();
+    assert_eq!(key_ref.name, "mystack-postgres");
+    assert_eq!(key_ref.key, "superuser-password");
+
+    let creds = pod_spec.volumes.unwrap();
     assert_eq!(
-        sel.get("gpu.nvidia.com/present").map(|s| s.as_str()),
-        Some("true")
+        creds[0].secret.as_ref().unwrap().secret_name.as_deref(),
+        Some("mystack-postgres")
     );
-    assert!(!sel.contains_key("gpu.intel.com/i915"));
-    assert!(!sel.contains_key("gpu.amd.com/present"));
-}
 
-#[test]
-fn test_deployment_amd_gpu_adds_nfd_node_selector() {
-    let mut app = make_app(AppType::Jellyfin);
-    app.spec.gpu = Some(servarr_crds::GpuSpec {
-        amd: Some(1),
-        ..Default::default()
-    });
-    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
-    let pod_spec = deploy.spec.unwrap().template.spec.unwrap();
-    let sel = pod_spec
-        .node_selector
-        .expect("nodeSelector must be set for AMD GPU");
+    let claim = &spec.volume_claim_templates.unwrap()[0];
+    let storage = claim.spec.as_ref().unwrap().resources.as_ref().unwrap();
     assert_eq!(
-        sel.get("gpu.amd.com/present").map(|s| s.as_str()),
-        Some("true")
+        storage.requests.as_ref().unwrap().get("storage").unwrap().0,
+        "10Gi"
     );
-    assert!(!sel.contains_key("gpu.intel.com/i915"));
-    assert!(!sel.contains_key("gpu.nvidia.com/present"));
 }
 
 #[test]
-fn test_deployment_user_node_selector_preserved_with_gpu() {
+fn test_postgres_service_port_5432() {
+    let svc = servarr_resources::postgres::build_service("mystack", "media", make_owner_ref());
+    assert_eq!(svc.metadata.name.as_deref(), Some("mystack-postgres"));
+    let ports = svc.spec.unwrap().ports.unwrap();
+    assert_eq!(ports[0].port, 5432);
+}
+
+#[test]
+fn test_postgres_secret_data() {
+    let passwords = std::collections::BTreeMap::from([
+        ("superuser-password".to_string(), "root".to_string()),
+        ("app-mystack_sonarr".to_string(), "s3cret".to_string()),
+    ]);
+    let secret = servarr_resources::postgres::build_secret("mystack", "media", &passwords);
+    // Kept with the PVC when the stack is deleted
+    assert!(secret.metadata.owner_references.is_none());
+    let data = secret.data.unwrap();
+    assert_eq!(data.len(), 2);
+    assert_eq!(data.get("app-mystack_sonarr").unwrap().0, b"s3cret");
+}
+
+#[test]
+fn test_connections_configmap_lists_apps() {
+    let mut sonarr = make_app(AppType::Sonarr);
+    sonarr.metadata.name = Some("mystack-sonarr".into());
+    sonarr.spec.api_key_secret = Some("sonarr-api-key".into());
+    sonarr.spec.ingress = Some(IngressSpec {
+        host: "sonarr.example.com".into(),
+        tls_secret_name: Some("sonarr-tls".into()),
+        ..Default::default()
+    });
+    let mut bastion = make_app(AppType::SshBastion);
+    bastion.metadata.name = Some("mystack-ssh-bastion".into());
+
+    let cm = servarr_resources::connections::build(
+        "mystack",
+        "media",
+        &[sonarr, bastion],
+        make_owner_ref(),
+    );
+    assert_eq!(cm.metadata.name.as_deref(), Some("mystack-connections"));
+    let data = cm.data.unwrap();
+    assert_eq!(
+        data["mystack-sonarr.url"],
+        "http://mystack-sonarr.media.svc:8989"
+    );
+    assert_eq!(
+        data["mystack-sonarr.externalUrl"],
+        "https://sonarr.example.com"
+    );
+    assert_eq!(data["mystack-sonarr.apiKeySecret"], "sonarr-api-key");
+    assert!(data["mystack-ssh-bastion.url"].starts_with("ssh://"));
+    assert!(!data.contains_key("mystack-ssh-bastion.apiKeySecret"));
+
+    let entries: serde_json::Value = serde_json::from_str(&data["connections.json"]).unwrap();
+    assert_eq!(entries.as_array().unwrap().len(), 2);
+    assert_eq!(entries[0]["type"], "sonarr");
+    assert_eq!(entries[0]["externalUrl"], "https://sonarr.example.com");
+    assert!(entries[1].get("externalUrl").is_none());
+}
+
+#[test]
+fn test_deployment_database_env_vars() {
+    let mut app = make_app(AppType::Radarr);
+    app.spec.database = Some(DatabaseSpec {
+        host: "pg.media.svc".into(),
+        port: 5432,
+        user: "radarr".into(),
+        password_secret: "pg-creds".into(),
+        password_key: "app-radarr".into(),
+        main_database: "radarr_main".into(),
+        log_database: Some("radarr_log".into()),
+    });
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let env = deploy.spec.unwrap().template.spec.unwrap().containers[0]
+        .env
+        .clone()
+        .unwrap();
+    let value = |name: &str| {
+        env.iter()
+            .find(|e| e.name == name)
+            .and_then(|e| e.value.clone())
+    };
+    assert_eq!(
+        value("RADARR__POSTGRES__HOST").as_deref(),
+        Some("pg.media.svc")
+    );
+    assert_eq!(value("RADARR__POSTGRES__PORT").as_deref(), Some("5432"));
+    assert_eq!(value("RADARR__POSTGRES__USER").as_deref(), Some("radarr"));
+    assert_eq!(
+        value("RADARR__POSTGRES__MAINDB").as_deref(),
+        Some("radarr_main")
+    );
+    assert_eq!(
+        value("RADARR__POSTGRES__LOGDB").as_deref(),
+        Some("radarr_log")
+    );
+    let pw = env
+        .iter()
+        .find(|e| e.name == "RADARR__POSTGRES__PASSWORD")
+        .unwrap();
+    let key_ref = pw
+        .value_from
+        .as_ref()
+        .unwrap()
+        .secret_key_ref
+        .as_ref()
+        .unwrap();
+    assert_eq!(key_ref.name, "pg-creds");
+    assert_eq!(key_ref.key, "app-radarr");
+}
+
+#[test]
+fn test_deployment_database_ignored_for_unsupported_app() {
+    let mut app = make_app(AppType::Sabnzbd);
+    app.spec.database = Some(DatabaseSpec {
+        host: "pg".into(),
+        port: 5432,
+        user: "sab".into(),
+        password_secret: "pg-creds".into(),
+        password_key: "password".into(),
+        main_database: "sab".into(),
+        log_database: None,
+    });
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let env = deploy.spec.unwrap().template.spec.unwrap().containers[0]
+        .env
+        .clone()
+        .unwrap();
+    assert!(!env.iter().any(|e| e.name.contains("POSTGRES")));
+}
+
+#[test]
+fn test_deployment_no_gpu_no_node_selector() {
+    let app = make_app(AppType::Sonarr);
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let pod_spec = deploy.spec.unwrap().template.spec.unwrap();
+    assert!(
+        pod_spec.node_selector.is_none(),
+        "no GPU spec should produce no nodeSelector"
+    );
+}
+
+#[test]
+fn test_deployment_intel_gpu_adds_nfd_node_selector() {
+    let mut app = make_app(AppType::Jellyfin);
+    app.spec.gpu = Some(servarr_crds::GpuSpec {
+        intel: Some(1),
+        ..Default::default()
+    });
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let pod_spec = deploy.spec.unwrap().template.spec.unwrap();
+    let sel = pod_spec
+        .node_selector
+        .expect("nodeSelector must be set for Intel GPU");
+    assert_eq!(
+        sel.get("gpu.intel.com/i915").map(|s| s.as_str()),
+        Some("true")
+    );
+    assert!(!sel.contains_key("gpu.nvidia.com/present"));
+    assert!(!sel.contains_key("gpu.amd.com/present"));
+}
+
+#[test]
+fn test_deployment_nvidia_gpu_adds_nfd_node_selector() {
+    let mut app = make_app(AppType::Jellyfin);
+    app.spec.gpu = Some(servarr_crds::GpuSpec {
+        nvidia: Some(1),
+        ..Default::default()
+    });
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let pod_spec = deploy.spec.unwrap().template.spec.unwrap();
+    let sel = pod_spec
+        .node_selector
+        .expect("nodeSelector must be set for NVIDIA GPU");
+    assert_eq!(
+        sel.get("gpu.nvidia.com/present").map(|s| s.as_str()),
+        Some("true")
+    );
+    assert!(!sel.contains_key("gpu.intel.com/i915"));
+    assert!(!sel.contains_key("gpu.amd.com/present"));
+}
+
+#[test]
+fn test_deployment_amd_gpu_adds_nfd_node_selector() {
+    let mut app = make_app(AppType::Jellyfin);
+    app.spec.gpu = Some(servarr_crds::GpuSpec {
+        amd: Some(1),
+        ..Default::default()
+    });
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let pod_spec = deploy.spec.unwrap().template.spec.unwrap();
+    let sel = pod_spec
+        .node_selector
+        .expect("nodeSelector must be set for AMD GPU");
+    assert_eq!(
+        sel.get("gpu.amd.com/present").map(|s| s.as_str()),
+        Some("true")
+    );
+    assert!(!sel.contains_key("gpu.intel.com/i915"));
+    assert!(!sel.contains_key("gpu.nvidia.com/present"));
+}
+
+#[test]
+fn test_deployment_user_node_selector_preserved_with_gpu() {
     let mut app = make_app(AppType::Jellyfin);
     app.spec.gpu = Some(servarr_crds::GpuSpec {
         intel: Some(1),
@@ -3258,6 +4460,57 @@ fn test_deployment_user_node_selector_preserved_with_gpu() {
     );
 }
 
+#[test]
+fn test_deployment_renders_tolerations_affinity_and_spread() {
+    let mut app = make_app(AppType::Sonarr);
+    app.spec.scheduling = Some(NodeScheduling {
+        tolerations: vec![serde_json::json!({
+            "key": "dedicated",
+            "operator": "Equal",
+            "value": "media",
+            "effect": "NoSchedule"
+        })],
+        affinity: Some(serde_json::json!({
+            "nodeAffinity": {
+                "preferredDuringSchedulingIgnoredDuringExecution": [{
+                    "weight": 10,
+                    "preference": {
+                        "matchExpressions": [{ "key": "disk", "operator": "In", "values": ["ssd"] }]
+                    }
+                }]
+            }
+        })),
+        topology_spread_constraints: vec![SpreadPolicy::default().constraint("media")],
+        ..Default::default()
+    });
+    app.metadata.labels = Some([("servarr.dev/stack".to_string(), "media".to_string())].into());
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let template = deploy.spec.unwrap().template;
+    let pod_spec = template.spec.unwrap();
+    assert_eq!(
+        pod_spec.tolerations.unwrap()[0].key.as_deref(),
+        Some("dedicated")
+    );
+    assert!(pod_spec.affinity.unwrap().node_affinity.is_some());
+    let constraints = pod_spec.topology_spread_constraints.unwrap();
+    assert_eq!(constraints[0].topology_key, "kubernetes.io/hostname");
+    assert_eq!(constraints[0].when_unsatisfiable, "ScheduleAnyway");
+    // The spread selects on the stack label, so the pod must carry it
+    let labels = template.metadata.unwrap().labels.unwrap();
+    assert_eq!(labels["servarr.dev/stack"], "media");
+}
+
+#[test]
+fn test_deployment_stack_label_only_on_spread_pods() {
+    let mut app = make_app(AppType::Sonarr);
+    app.metadata.labels = Some([("servarr.dev/stack".to_string(), "media".to_string())].into());
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let template = deploy.spec.unwrap().template;
+    assert!(template.spec.unwrap().topology_spread_constraints.is_none());
+    let labels = template.metadata.unwrap().labels.unwrap();
+    assert!(!labels.contains_key("servarr.dev/stack"));
+}
+
 // ---------------------------------------------------------------------------
 // adminCredentials env var injection
 // ---------------------------------------------------------------------------
@@ -3301,6 +4554,30 @@ fn test_admin_credentials_sonarr_no_auth_env_vars() {
     assert!(find_env(&env, "SONARR__AUTH__METHOD").is_none());
 }
 
+#[test]
+fn test_auth_sets_method_env_vars() {
+    let mut app = make_app(AppType::Sonarr);
+    app.spec.auth = Some(AuthSpec {
+        method: AuthMethod::External,
+        secret_name: None,
+        disabled_for_local_addresses: true,
+    });
+    let env = get_env(&app);
+
+    let value = |name| find_env(&env, name).and_then(|e| e.value.as_deref());
+    assert_eq!(value("SONARR__AUTH__METHOD"), Some("External"));
+    assert_eq!(
+        value("SONARR__AUTH__REQUIRED"),
+        Some("DisabledForLocalAddresses")
+    );
+    // The login is applied through the API, never as env vars.
+    assert!(find_env(&env, "SONARR__AUTH__USERNAME").is_none());
+
+    let mut app = make_app(AppType::Jellyfin);
+    app.spec.auth = Some(AuthSpec::default());
+    assert!(get_env(&app).iter().all(|e| !e.name.contains("__AUTH__")));
+}
+
 #[test]
 fn test_admin_credentials_radarr_no_auth_env_vars() {
     let mut app = make_app(AppType::Radarr);
@@ -3590,3 +4867,1179 @@ fn test_httproute_ssa_body_has_type_meta() {
         "SSA body must contain kind"
     );
 }
+
+// ---------------------------------------------------------------------------
+// Post-render patches
+// ---------------------------------------------------------------------------
+
+fn resource_patch(kind: &str, patch_type: PatchType, patch: &str) -> ResourcePatch {
+    ResourcePatch {
+        target: PatchTarget {
+            kind: kind.into(),
+            name: None,
+        },
+        patch_type,
+        patch: patch.into(),
+    }
+}
+
+#[test]
+fn test_patches_strategic_merge_by_container_name() {
+    let app = make_app(AppType::Sonarr);
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let patches = vec![resource_patch(
+        "Deployment",
+        PatchType::StrategicMerge,
+        r#"
+metadata:
+  labels:
+    team: media
+spec:
+  template:
+    spec:
+      containers:
+        - name: sonarr
+          readinessProbe:
+            periodSeconds: 42
+        - name: sidecar
+          image: busybox
+"#,
+    )];
+
+    let patched = servarr_resources::patches::apply(deploy, &patches).unwrap();
+    assert_eq!(
+        patched.metadata.labels.as_ref().unwrap().get("team"),
+        Some(&"media".to_string())
+    );
+    let containers = patched.spec.unwrap().template.spec.unwrap().containers;
+    assert_eq!(containers.len(), 2);
+    let sonarr = &containers[0];
+    assert_eq!(sonarr.name, "sonarr");
+    // Merged into the existing container, other fields kept
+    assert!(sonarr.image.is_some());
+    assert_eq!(
+        sonarr.readiness_probe.as_ref().unwrap().period_seconds,
+        Some(42)
+    );
+    assert_eq!(containers[1].image.as_deref(), Some("busybox"));
+}
+
+#[test]
+fn test_patches_strategic_merge_delete_directive() {
+    let app = make_app(AppType::Sonarr);
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let patches = vec![resource_patch(
+        "Deployment",
+        PatchType::StrategicMerge,
+        r#"{"spec":{"template":{"spec":{"containers":[{"name":"sonarr","env":[{"name":"TZ","$patch":"delete"}]}]}}}}"#,
+    )];
+
+    let patched = servarr_resources::patches::apply(deploy, &patches).unwrap();
+    let env = patched.spec.unwrap().template.spec.unwrap().containers[0]
+        .env
+        .clone()
+        .unwrap();
+    assert!(!env.iter().any(|e| e.name == "TZ"));
+    assert!(env.iter().any(|e| e.name == "PUID"));
+}
+
+#[test]
+fn test_patches_json6902_and_target_filtering() {
+    let app = make_app(AppType::Sonarr);
+    let svc = servarr_resources::service::build(&app);
+    let patches = vec![
+        resource_patch(
+            "Service",
+            PatchType::Json6902,
+            r#"
+- op: add
+  path: /metadata/annotations
+  value:
+    metallb.universe.tf/address-pool: media
+"#,
+        ),
+        ResourcePatch {
+            target: PatchTarget {
+                kind: "Service".into(),
+                name: Some("other".into()),
+            },
+            patch_type: PatchType::StrategicMerge,
+            patch: "metadata: {labels: {ignored: 'true'}}".into(),
+        },
+    ];
+
+    let patched = servarr_resources::patches::apply(svc, &patches).unwrap();
+    assert_eq!(
+        patched
+            .metadata
+            .annotations
+            .as_ref()
+            .unwrap()
+            .get("metallb.universe.tf/address-pool"),
+        Some(&"media".to_string())
+    );
+    assert!(
+        !patched
+            .metadata
+            .labels
+            .as_ref()
+            .unwrap()
+            .contains_key("ignored")
+    );
+}
+
+#[test]
+fn test_patches_reject_identity_changes_and_bad_bodies() {
+    use servarr_resources::patches::PatchError;
+    let app = make_app(AppType::Sonarr);
+
+    let rename = vec![resource_patch(
+        "Service",
+        PatchType::StrategicMerge,
+        "metadata: {name: renamed}",
+    )];
+    assert!(matches!(
+        servarr_resources::patches::apply(servarr_resources::service::build(&app), &rename),
+        Err(PatchError::Identity { index: 0 })
+    ));
+
+    let list = vec![resource_patch(
+        "Service",
+        PatchType::StrategicMerge,
+        "- not a mapping",
+    )];
+    assert!(matches!(
+        servarr_resources::patches::apply(servarr_resources::service::build(&app), &list),
+        Err(PatchError::NotAMapping { index: 0 })
+    ));
+
+    let bad_op = vec![resource_patch(
+        "Service",
+        PatchType::Json6902,
+        "[{op: remove, path: /spec/doesNotExist}]",
+    )];
+    assert!(matches!(
+        servarr_resources::patches::apply(servarr_resources::service::build(&app), &bad_op),
+        Err(PatchError::Json6902 { index: 0, .. })
+    ));
+}
+
+#[test]
+fn test_merge_pod_spec_override() {
+    let app = make_app(AppType::Sonarr);
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let pod_spec = serde_json::json!({
+        "shareProcessNamespace": true,
+        "containers": [{"name": "sonarr", "stdin": true}]
+    });
+
+    let merged = servarr_resources::patches::merge_pod_spec(deploy, &pod_spec).unwrap();
+    let spec = merged.spec.unwrap().template.spec.unwrap();
+    assert_eq!(spec.share_process_namespace, Some(true));
+    assert_eq!(spec.containers.len(), 1);
+    assert_eq!(spec.containers[0].stdin, Some(true));
+    assert!(spec.containers[0].image.is_some());
+
+    assert_eq!(
+        servarr_resources::patches::pod_spec_override_paths(&pod_spec),
+        vec!["spec['containers']", "spec['shareProcessNamespace']"]
+    );
+    assert!(matches!(
+        servarr_resources::patches::merge_pod_spec(
+            servarr_resources::deployment::build(&app, &std::collections::HashMap::new()),
+            &serde_json::json!(["nope"])
+        ),
+        Err(servarr_resources::patches::PatchError::PodSpecNotAMapping)
+    ));
+}
+
+#[test]
+fn test_config_file_xml_settings_and_init_container() {
+    let mut app = make_app(AppType::Sonarr);
+    app.spec.config_file = std::collections::BTreeMap::from([
+        ("UrlBase".into(), "/sonarr".into()),
+        ("InstanceName".into(), "Sonarr <TV>".into()),
+    ]);
+
+    let cm = servarr_resources::config_file::build(&app).expect("ConfigMap");
+    assert_eq!(cm.metadata.name.as_deref(), Some("test-app-config-file"));
+    let data = cm.data.unwrap();
+    assert_eq!(
+        data["settings"],
+        "InstanceName\tSonarr &lt;TV&gt;\nUrlBase\t/sonarr\n"
+    );
+    assert!(data["merge.sh"].contains("</Config>"));
+
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let pod = deploy.spec.unwrap().template.spec.unwrap();
+    let init = pod.init_containers.unwrap();
+    let apply = init.iter().find(|c| c.name == "apply-config-file").unwrap();
+    assert_eq!(
+        apply.command.as_deref().unwrap(),
+        ["/bin/sh", "/config-file/merge.sh", "/config/config.xml"]
+    );
+    let sec = apply.security_context.as_ref().unwrap();
+    assert_eq!(sec.run_as_user, Some(65534));
+    assert!(pod.volumes.unwrap().iter().any(|v| v.name == "config-file"));
+}
+
+#[test]
+fn test_config_file_ini_includes_managed_port() {
+    let mut app = make_app(AppType::Sabnzbd);
+    app.spec.config_file =
+        std::collections::BTreeMap::from([("misc.cache_limit".into(), "1G".into())]);
+    app.spec.service = Some(ServiceSpec {
+        service_type: "ClusterIP".into(),
+        ports: vec![ServicePort {
+            name: "http".into(),
+            port: 80,
+            protocol: "TCP".into(),
+            container_port: Some(9090),
+            host_port: None,
+        }],
+        ..Default::default()
+    });
+
+    let cm = servarr_resources::config_file::build(&app).expect("ConfigMap");
+    let data = cm.data.unwrap();
+    assert_eq!(
+        data["settings"],
+        "misc\tcache_limit\t1G\nmisc\tport\t9090\n"
+    );
+    assert!(data["merge.sh"].contains("[[nested]]"));
+}
+
+#[test]
+fn test_config_file_absent_without_settings() {
+    let app = make_app(AppType::Radarr);
+    assert!(servarr_resources::config_file::build(&app).is_none());
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let pod = deploy.spec.unwrap().template.spec.unwrap();
+    assert!(
+        !pod.init_containers
+            .unwrap_or_default()
+            .iter()
+            .any(|c| c.name == "apply-config-file")
+    );
+}
+
+#[test]
+fn test_config_file_validation() {
+    use servarr_resources::config_file::validate;
+
+    let mut spec = ServarrAppSpec {
+        app: AppType::Sonarr,
+        config_file: std::collections::BTreeMap::from([
+            ("UrlBase".into(), "/sonarr".into()),
+            ("misc.port".into(), "1".into()),
+            ("LogLevel".into(), "debug\ntrace".into()),
+        ]),
+        ..Default::default()
+    };
+    let errors = validate(&spec);
+    assert_eq!(errors.len(), 2, "{errors:?}");
+    assert!(errors[0].contains("configFile.LogLevel"));
+    assert!(errors[1].contains("'misc.port'"));
+
+    spec.app = AppType::Sabnzbd;
+    spec.config_file = std::collections::BTreeMap::from([
+        ("misc.cache_limit".into(), "1G".into()),
+        ("cache_limit".into(), "1G".into()),
+    ]);
+    let errors = validate(&spec);
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].contains("'section.key'"));
+
+    // Keys the operator derives from other fields can't be set directly
+    spec.service = Some(ServiceSpec {
+        service_type: "ClusterIP".into(),
+        ports: vec![ServicePort {
+            name: "http".into(),
+            port: 9090,
+            protocol: "TCP".into(),
+            container_port: None,
+            host_port: None,
+        }],
+        ..Default::default()
+    });
+    spec.config_file = std::collections::BTreeMap::from([("misc.port".into(), "8080".into())]);
+    let errors = validate(&spec);
+    assert!(errors[0].contains("managed by the operator"), "{errors:?}");
+
+    spec.app = AppType::Jellyfin;
+    let errors = validate(&spec);
+    assert!(
+        errors[0].contains("not supported for app type 'jellyfin'"),
+        "{errors:?}"
+    );
+}
+
+fn qbittorrent_app() -> ServarrApp {
+    let mut app = make_app(AppType::Qbittorrent);
+    app.spec.app_config = Some(AppConfig::Qbittorrent(QbittorrentConfig {
+        peer_port: Some(PeerPortConfig {
+            port: 6881,
+            ..Default::default()
+        }),
+        categories: std::collections::BTreeMap::from([(
+            "tv-sonarr".into(),
+            "/downloads/tv".into(),
+        )]),
+        speed_limits: Some(QbittorrentSpeedLimits {
+            download: Some(10240),
+            alt_upload: Some(0),
+            ..Default::default()
+        }),
+    }));
+    app
+}
+
+#[test]
+fn test_qbittorrent_config_file_settings() {
+    let app = qbittorrent_app();
+    let cm = servarr_resources::config_file::build(&app).expect("ConfigMap");
+    assert_eq!(
+        cm.data.unwrap()["settings"],
+        "BitTorrent\tSession\\AlternativeGlobalUPSpeedLimit\t0\n\
+         BitTorrent\tSession\\GlobalDLSpeedLimit\t10240\n\
+         BitTorrent\tSession\\Port\t6881\n"
+    );
+
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let pod = deploy.spec.unwrap().template.spec.unwrap();
+    let init = pod.init_containers.unwrap();
+    let apply = init.iter().find(|c| c.name == "apply-config-file").unwrap();
+    assert_eq!(
+        apply.command.as_deref().unwrap(),
+        [
+            "/bin/sh",
+            "/config-file/merge.sh",
+            "/config/qBittorrent/qBittorrent.conf",
+            "/defaults/qBittorrent.conf"
+        ]
+    );
+
+    // The LSIO image passes both ports to qbittorrent-nox as flags
+    let env = pod.containers[0].env.clone().unwrap();
+    let value = |name: &str| {
+        env.iter()
+            .find(|e| e.name == name)
+            .and_then(|e| e.value.clone())
+    };
+    assert_eq!(value("WEBUI_PORT").as_deref(), Some("8080"));
+    assert_eq!(value("TORRENTING_PORT").as_deref(), Some("6881"));
+}
+
+#[test]
+fn test_qbittorrent_categories_init_container() {
+    let app = qbittorrent_app();
+    let cm = servarr_resources::configmap::build(&app).expect("ConfigMap");
+    let data = cm.data.unwrap();
+    let categories: serde_json::Value = serde_json::from_str(&data["categories.json"]).unwrap();
+    assert_eq!(
+        categories,
+        serde_json::json!({"tv-sonarr": {"save_path": "/downloads/tv"}})
+    );
+    assert!(data["apply-settings.sh"].contains("mkdir -p '/downloads/tv'"));
+
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let pod = deploy.spec.unwrap().template.spec.unwrap();
+    let init = pod.init_containers.unwrap();
+    let apply = init.iter().find(|c| c.name == "apply-settings").unwrap();
+    let mounts = apply.volume_mounts.as_ref().unwrap();
+    assert!(mounts.iter().any(|m| m.mount_path == "/downloads"));
+    assert!(pod.volumes.unwrap().iter().any(|v| v.name == "scripts"));
+
+    // Without categories there is nothing for the init container to do
+    let mut app = make_app(AppType::Qbittorrent);
+    app.spec.app_config = Some(AppConfig::Qbittorrent(QbittorrentConfig::default()));
+    assert!(servarr_resources::configmap::build(&app).is_none());
+}
+
+#[test]
+fn test_qbittorrent_peer_port_service_and_network_policy() {
+    let app = qbittorrent_app();
+    let ports = servarr_resources::service::build(&app)
+        .spec
+        .unwrap()
+        .ports
+        .unwrap();
+    assert!(
+        ports
+            .iter()
+            .any(|p| p.name.as_deref() == Some("peer-udp") && p.port == 6881)
+    );
+
+    let ingress = servarr_resources::networkpolicy::build(&app)
+        .spec
+        .unwrap()
+        .ingress
+        .unwrap();
+    assert!(ingress.iter().any(|r| {
+        r.ports.as_ref().is_some_and(|ports| {
+            ports.len() == 2
+                && ports.iter().all(|p| {
+                    p.port
+                        == Some(
+                            k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(6881),
+                        )
+                })
+        })
+    }));
+}
+
+fn jellyfin_with_rclone() -> ServarrApp {
+    let mut app = make_app(AppType::Jellyfin);
+    app.spec.rclone_mounts = vec![RcloneMount {
+        name: "gdrive".into(),
+        remote: "gdrive:media/movies".into(),
+        mount_path: "/movies".into(),
+        config_secret: "rclone-config".into(),
+        config_key: "rclone.conf".into(),
+        read_only: true,
+        vfs: RcloneVfsSpec {
+            cache_max_size: Some("50G".into()),
+            ..Default::default()
+        },
+        image: None,
+        extra_args: vec!["--buffer-size=64M".into()],
+        resources: None,
+    }];
+    app
+}
+
+#[test]
+fn test_rclone_mount_sidecar_and_propagation() {
+    let app = jellyfin_with_rclone();
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let pod = deploy.spec.unwrap().template.spec.unwrap();
+
+    let init = pod.init_containers.unwrap();
+    let sidecar = init.last().unwrap();
+    assert_eq!(sidecar.name, "rclone-gdrive");
+    assert_eq!(sidecar.restart_policy.as_deref(), Some("Always"));
+    assert!(sidecar.startup_probe.is_some());
+    let sec = sidecar.security_context.as_ref().unwrap();
+    assert_eq!(sec.privileged, Some(true));
+    let args = sidecar.args.as_ref().unwrap();
+    assert_eq!(&args[..3], ["mount", "gdrive:media/movies", "/movies"]);
+    assert!(args.contains(&"--vfs-cache-mode=full".to_string()));
+    assert!(args.contains(&"--vfs-cache-max-size=50G".to_string()));
+    assert!(args.contains(&"--read-only".to_string()));
+    assert_eq!(args.last().unwrap(), "--buffer-size=64M");
+    let shared = sidecar
+        .volume_mounts
+        .as_ref()
+        .unwrap()
+        .iter()
+        .find(|m| m.name == "rclone-gdrive")
+        .unwrap();
+    assert_eq!(shared.mount_propagation.as_deref(), Some("Bidirectional"));
+
+    let app_mount = pod.containers[0]
+        .volume_mounts
+        .as_ref()
+        .unwrap()
+        .iter()
+        .find(|m| m.name == "rclone-gdrive")
+        .unwrap();
+    assert_eq!(app_mount.mount_path, "/movies");
+    assert_eq!(
+        app_mount.mount_propagation.as_deref(),
+        Some("HostToContainer")
+    );
+
+    let volumes = pod.volumes.unwrap();
+    let config = volumes
+        .iter()
+        .find(|v| v.name == "rclone-gdrive-config")
+        .unwrap();
+    assert_eq!(
+        config.secret.as_ref().unwrap().secret_name.as_deref(),
+        Some("rclone-config")
+    );
+    assert!(
+        volumes
+            .iter()
+            .any(|v| v.name == "rclone-gdrive" && v.empty_dir.is_some())
+    );
+    assert!(volumes.iter().any(|v| v.name == "rclone-gdrive-cache"));
+}
+
+#[test]
+fn test_rclone_mount_allows_https_egress() {
+    let app = jellyfin_with_rclone();
+    let np = servarr_resources::networkpolicy::build(&app);
+    let egress = np.spec.unwrap().egress.unwrap();
+    let https = egress.iter().find(|r| {
+        r.ports.as_ref().is_some_and(|ports| {
+            ports.iter().any(|p| {
+                p.port == Some(k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(443))
+            })
+        })
+    });
+    let block = https.unwrap().to.as_ref().unwrap()[0]
+        .ip_block
+        .as_ref()
+        .unwrap();
+    assert_eq!(block.cidr, "0.0.0.0/0");
+    assert!(
+        block
+            .except
+            .as_ref()
+            .unwrap()
+            .contains(&"10.0.0.0/8".into())
+    );
+
+    let plain = servarr_resources::networkpolicy::build(&make_app(AppType::Jellyfin));
+    assert!(!plain.spec.unwrap().egress.unwrap().iter().any(|r| {
+        r.ports.as_ref().is_some_and(|ports| {
+            ports.iter().any(|p| {
+                p.port == Some(k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(443))
+            })
+        })
+    }));
+}
+
+#[test]
+fn test_credentials_projected_as_env_and_files() {
+    let mut app = make_app(AppType::Transmission);
+    app.spec.credentials = vec![
+        CredentialRef {
+            name: "vpn".into(),
+            env: vec![CredentialEnvVar {
+                name: "OPENVPN_USER".into(),
+                key: "username".into(),
+            }],
+            mount_path: None,
+        },
+        CredentialRef {
+            name: "usenet".into(),
+            env: vec![],
+            mount_path: Some("/run/secrets/usenet".into()),
+        },
+    ];
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let pod = deploy.spec.unwrap().template.spec.unwrap();
+    let container = &pod.containers[0];
+
+    let env = container
+        .env
+        .as_ref()
+        .unwrap()
+        .iter()
+        .find(|e| e.name == "OPENVPN_USER")
+        .unwrap();
+    let key_ref = env
+        .value_from
+        .as_ref()
+        .unwrap()
+        .secret_key_ref
+        .as_ref()
+        .unwrap();
+    assert_eq!(key_ref.name, "test-app-credential-vpn");
+    assert_eq!(key_ref.key, "username");
+
+    let mount = container
+        .volume_mounts
+        .as_ref()
+        .unwrap()
+        .iter()
+        .find(|m| m.name == "credential-usenet")
+        .unwrap();
+    assert_eq!(mount.mount_path, "/run/secrets/usenet");
+    assert_eq!(mount.read_only, Some(true));
+    let volume = pod
+        .volumes
+        .unwrap()
+        .into_iter()
+        .find(|v| v.name == "credential-usenet")
+        .unwrap();
+    assert_eq!(
+        volume.secret.unwrap().secret_name.as_deref(),
+        Some("test-app-credential-usenet")
+    );
+    // Only credentials with a mountPath get a volume.
+    assert!(
+        !container
+            .volume_mounts
+            .as_ref()
+            .unwrap()
+            .iter()
+            .any(|m| m.name == "credential-vpn")
+    );
+}
+
+#[test]
+fn test_credential_copy_keeps_only_shared_keys() {
+    use k8s_openapi::ByteString;
+    use k8s_openapi::api::core::v1::Secret;
+
+    let app = make_app(AppType::Sabnzbd);
+    let mut cred = ServarrCredential::new(
+        "usenet",
+        ServarrCredentialSpec {
+            secret_name: "usenet-provider".into(),
+            keys: vec!["username".into(), "password".into()],
+            ..Default::default()
+        },
+    );
+    cred.metadata.namespace = Some("media".into());
+    let source = Secret {
+        data: Some(std::collections::BTreeMap::from([
+            ("username".to_string(), ByteString(b"alice".to_vec())),
+            ("password".to_string(), ByteString(b"hunter2".to_vec())),
+            ("notes".to_string(), ByteString(b"unrelated".to_vec())),
+        ])),
+        ..Default::default()
+    };
+
+    let copy = servarr_resources::credential::build(&app, &cred, &source);
+    assert_eq!(
+        copy.metadata.name.as_deref(),
+        Some("test-app-credential-usenet")
+    );
+    assert_eq!(
+        copy.metadata.owner_references.unwrap()[0].uid,
+        "test-uid-123"
+    );
+    let data = copy.data.unwrap();
+    assert_eq!(data.keys().collect::<Vec<_>>(), ["password", "username"]);
+
+    // The checksum follows the shared values only.
+    let shared = servarr_resources::credential::shared_data(&cred, &source);
+    let before = servarr_resources::credential::checksum([("usenet", &shared)]);
+    let mut rotated = source.clone();
+    rotated
+        .data
+        .as_mut()
+        .unwrap()
+        .insert("notes".into(), ByteString(b"changed".to_vec()));
+    let unchanged = servarr_resources::credential::shared_data(&cred, &rotated);
+    assert_eq!(
+        before,
+        servarr_resources::credential::checksum([("usenet", &unchanged)])
+    );
+    rotated
+        .data
+        .as_mut()
+        .unwrap()
+        .insert("password".into(), ByteString(b"correct-horse".to_vec()));
+    let changed = servarr_resources::credential::shared_data(&cred, &rotated);
+    assert_ne!(
+        before,
+        servarr_resources::credential::checksum([("usenet", &changed)])
+    );
+}
+
+#[test]
+fn test_hpa_scales_workload_and_omits_replicas() {
+    let mut app = make_app(AppType::Overseerr);
+    assert!(servarr_resources::hpa::build(&app).is_none());
+
+    app.spec.autoscaling = Some(AutoscalingSpec {
+        min_replicas: 2,
+        max_replicas: 4,
+        target_cpu_utilization_percentage: None,
+        target_memory_utilization_percentage: Some(75),
+    });
+    let hpa = servarr_resources::hpa::build(&app).unwrap();
+    assert_eq!(hpa.metadata.name.as_deref(), Some("test-app"));
+    let spec = hpa.spec.unwrap();
+    assert_eq!(spec.scale_target_ref.kind, "Deployment");
+    assert_eq!(spec.scale_target_ref.name, "test-app");
+    assert_eq!(spec.min_replicas, Some(2));
+    assert_eq!(spec.max_replicas, 4);
+    let metrics = spec.metrics.unwrap();
+    assert_eq!(metrics.len(), 1);
+    let resource = metrics[0].resource.as_ref().unwrap();
+    assert_eq!(resource.name, "memory");
+    assert_eq!(resource.target.average_utilization, Some(75));
+
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    assert_eq!(deploy.spec.unwrap().replicas, None);
+
+    app.spec.workload_type = Some(WorkloadType::StatefulSet);
+    app.spec
+        .autoscaling
+        .as_mut()
+        .unwrap()
+        .target_memory_utilization_percentage = None;
+    let spec = servarr_resources::hpa::build(&app).unwrap().spec.unwrap();
+    assert_eq!(spec.scale_target_ref.kind, "StatefulSet");
+    let metrics = spec.metrics.unwrap();
+    let resource = metrics[0].resource.as_ref().unwrap();
+    assert_eq!(resource.name, "cpu");
+    assert_eq!(resource.target.average_utilization, Some(80));
+}
+
+#[test]
+fn test_hpa_ignored_for_single_replica_apps() {
+    let mut app = make_app(AppType::Sonarr);
+    app.spec.autoscaling = Some(AutoscalingSpec {
+        min_replicas: 1,
+        max_replicas: 3,
+        target_cpu_utilization_percentage: None,
+        target_memory_utilization_percentage: None,
+    });
+    assert!(servarr_resources::hpa::build(&app).is_none());
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    assert_eq!(deploy.spec.unwrap().replicas, Some(1));
+}
+
+#[test]
+fn test_ingress_builder() {
+    let mut app = make_app(AppType::Sonarr);
+    assert!(servarr_resources::ingress::build(&app).is_none());
+
+    app.spec.ingress = Some(IngressSpec {
+        host: "sonarr.example.com".into(),
+        ingress_class_name: Some("nginx".into()),
+        tls_secret_name: Some("sonarr-tls".into()),
+        annotations: [(
+            "nginx.ingress.kubernetes.io/proxy-body-size".to_string(),
+            "0".to_string(),
+        )]
+        .into_iter()
+        .collect(),
+        ..Default::default()
+    });
+    let ingress = servarr_resources::ingress::build(&app).unwrap();
+    assert_eq!(ingress.metadata.name.as_deref(), Some("test-app"));
+    assert_eq!(
+        ingress.metadata.annotations.unwrap()["nginx.ingress.kubernetes.io/proxy-body-size"],
+        "0"
+    );
+    let spec = ingress.spec.unwrap();
+    assert_eq!(spec.ingress_class_name.as_deref(), Some("nginx"));
+
+    let rules = spec.rules.unwrap();
+    assert_eq!(rules[0].host.as_deref(), Some("sonarr.example.com"));
+    let path = &rules[0].http.as_ref().unwrap().paths[0];
+    assert_eq!(path.path.as_deref(), Some("/"));
+    assert_eq!(path.path_type, "Prefix");
+    let backend = path.backend.service.as_ref().unwrap();
+    assert_eq!(backend.name, "test-app");
+    assert_eq!(backend.port.as_ref().unwrap().number, Some(8989));
+
+    let tls = spec.tls.unwrap();
+    assert_eq!(tls[0].secret_name.as_deref(), Some("sonarr-tls"));
+    assert_eq!(
+        tls[0].hosts.as_deref(),
+        Some(&["sonarr.example.com".to_string()][..])
+    );
+}
+
+#[test]
+fn test_networkpolicy_ingress_controller_namespace() {
+    let mut app = make_app(AppType::Sonarr);
+    app.spec.ingress = Some(IngressSpec {
+        host: "sonarr.example.com".into(),
+        controller_namespace: Some("ingress-nginx".into()),
+        ..Default::default()
+    });
+
+    let np = servarr_resources::networkpolicy::build(&app);
+    let rules = np.spec.unwrap().ingress.unwrap();
+    assert!(rules.iter().any(|r| {
+        r.from.iter().flatten().any(|p| {
+            p.namespace_selector
+                .as_ref()
+                .and_then(|ns| ns.match_labels.as_ref())
+                .and_then(|labels| labels.get("kubernetes.io/metadata.name"))
+                .is_some_and(|ns| ns == "ingress-nginx")
+        })
+    }));
+}
+
+fn bastion_with_sftp_chroot() -> ServarrApp {
+    let mut app = make_app(AppType::SshBastion);
+    app.spec.persistence = Some(PersistenceSpec {
+        volumes: vec![PvcVolume {
+            name: "photos".into(),
+            mount_path: "/media/photos".into(),
+            ..Default::default()
+        }],
+        nfs_mounts: vec![NfsMount {
+            name: "archive".into(),
+            server: "nas.local".into(),
+            path: "/export/archive".into(),
+            mount_path: "/media/archive".into(),
+            read_only: true,
+        }],
+        verify_mounts: false,
+    });
+    app.spec.app_config = Some(AppConfig::SshBastion(SshBastionConfig {
+        users: vec![
+            SshUser {
+                name: "alice".into(),
+                uid: 1000,
+                gid: 1000,
+                mode: SshMode::Sftp,
+                sftp_chroot: Some(SftpChroot {
+                    volume: "photos".into(),
+                    sub_path: Some("alice".into()),
+                    read_only: false,
+                }),
+                ..Default::default()
+            },
+            SshUser {
+                name: "bob".into(),
+                uid: 1001,
+                gid: 1001,
+                mode: SshMode::Sftp,
+                sftp_chroot: Some(SftpChroot {
+                    volume: "archive".into(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            SshUser {
+                name: "carol".into(),
+                uid: 1002,
+                gid: 1002,
+                mode: SshMode::Shell,
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }));
+    app
+}
+
+#[test]
+fn test_configmap_ssh_bastion_sftp_chroot() {
+    assert!(
+        servarr_resources::configmap::build_ssh_bastion_sftp_chroot(&make_app(AppType::SshBastion))
+            .is_none()
+    );
+
+    let cm =
+        servarr_resources::configmap::build_ssh_bastion_sftp_chroot(&bastion_with_sftp_chroot())
+            .unwrap();
+    assert_eq!(cm.metadata.name.as_deref(), Some("test-app-sftp-chroot"));
+    let script = &cm.data.unwrap()["sftp-chroot.sh"];
+    assert!(script.contains("Match User alice\n    ChrootDirectory /sftp/alice\n"));
+    assert!(script.contains("Match User bob\n    ChrootDirectory /sftp/bob\n"));
+    assert!(script.contains("ForceCommand internal-sftp"));
+    assert!(!script.contains("carol"));
+}
+
+#[test]
+fn test_deployment_ssh_bastion_sftp_chroot_mounts() {
+    let deploy = servarr_resources::deployment::build(
+        &bastion_with_sftp_chroot(),
+        &std::collections::HashMap::new(),
+    );
+    let pod_spec = deploy.spec.unwrap().template.spec.unwrap();
+    let mounts = pod_spec.containers[0].volume_mounts.as_ref().unwrap();
+
+    let alice = mounts
+        .iter()
+        .find(|m| m.mount_path == "/sftp/alice/photos")
+        .unwrap();
+    assert_eq!(alice.name, "photos");
+    assert_eq!(alice.sub_path.as_deref(), Some("alice"));
+    assert_eq!(alice.read_only, None);
+
+    let bob = mounts
+        .iter()
+        .find(|m| m.mount_path == "/sftp/bob/archive")
+        .unwrap();
+    assert_eq!(bob.name, "nfs-archive");
+    assert_eq!(bob.read_only, Some(true));
+
+    let hook = mounts.iter().find(|m| m.name == "sftp-chroot").unwrap();
+    assert_eq!(hook.mount_path, "/etc/entrypoint.d/sftp-chroot.sh");
+    let volumes = pod_spec.volumes.unwrap();
+    let volume = volumes.iter().find(|v| v.name == "sftp-chroot").unwrap();
+    assert_eq!(
+        volume.config_map.as_ref().unwrap().name,
+        "test-app-sftp-chroot"
+    );
+}
+
+#[test]
+fn test_bazarr_api_key_init_container() {
+    let mut app = make_app(AppType::Bazarr);
+    // Without an API key Secret there is nothing to write
+    assert!(servarr_resources::configmap::build(&app).is_none());
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let pod = deploy.spec.unwrap().template.spec.unwrap();
+    assert!(
+        pod.init_containers
+            .unwrap_or_default()
+            .iter()
+            .all(|c| c.name != "apply-api-key")
+    );
+
+    app.spec.api_key_secret = Some("bazarr-api-key".into());
+    let cm = servarr_resources::configmap::build(&app).expect("ConfigMap");
+    assert!(cm.data.unwrap()["apply-api-key.sh"].contains("/config/config/config.yaml"));
+
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let pod = deploy.spec.unwrap().template.spec.unwrap();
+    let init = pod.init_containers.unwrap();
+    let apply = init.iter().find(|c| c.name == "apply-api-key").unwrap();
+    let sec = apply.security_context.as_ref().unwrap();
+    assert_eq!(sec.run_as_user, Some(65534));
+    let mounts = apply.volume_mounts.as_ref().unwrap();
+    assert!(mounts.iter().any(|m| m.name == "api-key"));
+    let volumes = pod.volumes.unwrap();
+    let secret = volumes.iter().find(|v| v.name == "api-key").unwrap();
+    assert_eq!(
+        secret.secret.as_ref().unwrap().secret_name.as_deref(),
+        Some("bazarr-api-key")
+    );
+    // Bazarr reads the key from config.yaml only, not the environment
+    let container = &pod.containers[0];
+    assert!(
+        container
+            .env
+            .iter()
+            .flatten()
+            .all(|e| !e.name.contains("APIKEY"))
+    );
+}
+
+#[test]
+fn test_api_key_secret_key_mapping() {
+    let mut app = make_app(AppType::Sonarr);
+    app.spec.api_key_secret = Some("media-credentials".into());
+    app.spec.api_key_secret_key = Some("SONARR_API_KEY".into());
+
+    let secret = servarr_resources::secret::build_api_key(&app, "generated").unwrap();
+    assert!(secret.string_data.unwrap().contains_key("SONARR_API_KEY"));
+
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let container = &deploy.spec.unwrap().template.spec.unwrap().containers[0];
+    let env = container.env.as_ref().unwrap();
+    let apikey = env
+        .iter()
+        .find(|e| e.name == "SONARR__AUTH__APIKEY")
+        .unwrap();
+    let selector = apikey
+        .value_from
+        .as_ref()
+        .unwrap()
+        .secret_key_ref
+        .as_ref()
+        .unwrap();
+    assert_eq!(selector.name, "media-credentials");
+    assert_eq!(selector.key, "SONARR_API_KEY");
+
+    // Bazarr's script reads the key from a fixed file name.
+    let mut app = make_app(AppType::Bazarr);
+    app.spec.api_key_secret = Some("media-credentials".into());
+    app.spec.api_key_secret_key = Some("BAZARR_API_KEY".into());
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let volumes = deploy.spec.unwrap().template.spec.unwrap().volumes.unwrap();
+    let secret = volumes.iter().find(|v| v.name == "api-key").unwrap();
+    let items = secret.secret.as_ref().unwrap().items.as_ref().unwrap();
+    assert_eq!(items[0].key, "BAZARR_API_KEY");
+    assert_eq!(items[0].path, "api-key");
+}
+
+#[test]
+fn test_transmission_credentials_secret_ref() {
+    let mut app = make_app(AppType::Transmission);
+    app.spec.app_config = Some(AppConfig::Transmission(TransmissionConfig {
+        credentials_secret_ref: Some(CredentialsSecretRef {
+            name: "torrent-credentials".into(),
+            username_key: "rpc-user".into(),
+            password_key: "rpc-password".into(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }));
+
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let container = &deploy.spec.unwrap().template.spec.unwrap().containers[0];
+    let env = container.env.as_ref().unwrap();
+    let key_of = |name: &str| {
+        let selector = env
+            .iter()
+            .find(|e| e.name == name)
+            .and_then(|e| e.value_from.as_ref())
+            .and_then(|v| v.secret_key_ref.as_ref())
+            .unwrap();
+        (selector.name.clone(), selector.key.clone())
+    };
+    assert_eq!(
+        key_of("USER"),
+        ("torrent-credentials".into(), "rpc-user".into())
+    );
+    assert_eq!(
+        key_of("PASS"),
+        ("torrent-credentials".into(), "rpc-password".into())
+    );
+    // The probes log in with the same credentials.
+    let liveness = container.liveness_probe.as_ref().unwrap();
+    assert!(liveness.exec.is_some());
+}
+
+#[test]
+fn test_tdarr_node_workers() {
+    let mut app = make_app(AppType::TdarrNode);
+    app.spec.app_config = Some(AppConfig::TdarrNode(TdarrNodeConfig {
+        server: Some("media-tdarr".into()),
+        node_count: Some(3),
+    }));
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let env = |d: &k8s_openapi::api::apps::v1::Deployment, name: &str| {
+        d.spec
+            .as_ref()
+            .unwrap()
+            .template
+            .spec
+            .as_ref()
+            .unwrap()
+            .containers[0]
+            .env
+            .as_ref()
+            .unwrap()
+            .iter()
+            .find(|e| e.name == name)
+            .and_then(|e| e.value.clone())
+    };
+    assert_eq!(env(&deploy, "serverIP").as_deref(), Some("media-tdarr"));
+    assert_eq!(env(&deploy, "serverPort").as_deref(), Some("8266"));
+    assert_eq!(env(&deploy, "nodeName").as_deref(), Some("test-app"));
+    let pod_spec = deploy
+        .spec
+        .as_ref()
+        .unwrap()
+        .template
+        .spec
+        .as_ref()
+        .unwrap();
+    assert!(
+        pod_spec
+            .volumes
+            .as_ref()
+            .unwrap()
+            .iter()
+            .any(|v| v.name == "transcode-cache" && v.empty_dir.is_some())
+    );
+
+    let workers = servarr_resources::deployment::tdarr_workers(&app, &deploy);
+    let names: Vec<_> = workers
+        .iter()
+        .map(|w| w.metadata.name.as_deref().unwrap())
+        .collect();
+    assert_eq!(names, vec!["test-app-1", "test-app-2"]);
+    let worker = &workers[0];
+    assert_eq!(env(worker, "nodeName").as_deref(), Some("test-app-1"));
+    assert_eq!(
+        worker.metadata.labels.as_ref().unwrap()[servarr_resources::deployment::WORKER_OF_LABEL],
+        "test-app"
+    );
+    let spec = worker.spec.as_ref().unwrap();
+    let selector = spec.selector.match_labels.as_ref().unwrap();
+    assert_eq!(selector["app.kubernetes.io/instance"], "test-app-1");
+    assert_eq!(
+        spec.template
+            .metadata
+            .as_ref()
+            .unwrap()
+            .labels
+            .as_ref()
+            .unwrap()["app.kubernetes.io/instance"],
+        "test-app-1"
+    );
+
+    // Other apps, and a single node, have no extra Deployments
+    app.spec.app_config = None;
+    assert!(servarr_resources::deployment::tdarr_workers(&app, &deploy).is_empty());
+    let sonarr = make_app(AppType::Sonarr);
+    let deploy = servarr_resources::deployment::build(&sonarr, &std::collections::HashMap::new());
+    assert!(servarr_resources::deployment::tdarr_workers(&sonarr, &deploy).is_empty());
+}
+
+#[test]
+fn verify_mounts_checks_nfs_mounts_first() {
+    let mut app = make_app(AppType::Transmission);
+    app.spec.persistence = Some(PersistenceSpec {
+        nfs_mounts: vec![
+            NfsMount {
+                name: "media".into(),
+                server: "nas.local".into(),
+                path: "/export/media".into(),
+                mount_path: "/media".into(),
+                read_only: true,
+            },
+            NfsMount {
+                name: "downloads".into(),
+                server: "nas.local".into(),
+                path: "/export/downloads".into(),
+                mount_path: "/downloads".into(),
+                read_only: false,
+            },
+        ],
+        verify_mounts: true,
+        ..Default::default()
+    });
+    let deployment = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let pod_spec = deployment.spec.unwrap().template.spec.unwrap();
+    let init = pod_spec.init_containers.unwrap();
+    assert_eq!(init[0].name, servarr_resources::mount_check::CONTAINER_NAME);
+    assert_eq!(init[1].name, "apply-settings");
+
+    let command = init[0].command.as_ref().unwrap();
+    assert_eq!(command[command.len() - 2..], ["ro:/media", "rw:/downloads"]);
+    let mounts = init[0].volume_mounts.as_ref().unwrap();
+    assert_eq!(mounts.len(), 2);
+    assert_eq!(mounts[0].read_only, Some(true));
+    assert_eq!(
+        init[0].termination_message_policy.as_deref(),
+        Some("FallbackToLogsOnError")
+    );
+
+    app.spec.persistence.as_mut().unwrap().verify_mounts = false;
+    let deployment = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let init = deployment
+        .spec
+        .unwrap()
+        .template
+        .spec
+        .unwrap()
+        .init_containers;
+    assert!(
+        init.iter()
+            .flatten()
+            .all(|c| c.name != servarr_resources::mount_check::CONTAINER_NAME)
+    );
+}
+
+#[test]
+fn test_extra_containers_follow_operator_containers() {
+    use k8s_openapi::api::core::v1::{Container, VolumeMount};
+
+    let mut app = jellyfin_with_rclone();
+    app.spec.extra_containers = vec![Container {
+        name: "vector".into(),
+        image: Some("timberio/vector:0.39.0-alpine".into()),
+        volume_mounts: Some(vec![VolumeMount {
+            name: "config".into(),
+            mount_path: "/logs".into(),
+            read_only: Some(true),
+            ..Default::default()
+        }]),
+        ..Default::default()
+    }];
+    app.spec.extra_init_containers = vec![Container {
+        name: "wait-for-nas".into(),
+        image: Some("busybox:1.36".into()),
+        ..Default::default()
+    }];
+    let deploy = servarr_resources::deployment::build(&app, &std::collections::HashMap::new());
+    let pod = deploy.spec.unwrap().template.spec.unwrap();
+
+    let names: Vec<_> = pod.containers.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["jellyfin", "vector"]);
+    assert_eq!(pod.containers[1], app.spec.extra_containers[0]);
+
+    let init = pod.init_containers.unwrap();
+    let names: Vec<_> = init.iter().rev().take(2).map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["wait-for-nas", "rclone-gdrive"]);
+}
@@ -18,16 +18,20 @@ trap 'rm -rf "$TMPFILE" "$TMPDIR_SPLIT"' EXIT
 # Generate all CRDs
 cargo run -p servarr-operator -- crd 2>/dev/null > "$TMPFILE"
 
-# The output contains two CRDs concatenated without --- separators.
+# The output contains the CRDs concatenated without --- separators.
 # Split on each "apiVersion:" line that starts a new document.
 awk '
 /^apiVersion:/ { n++ }
-n == 1 { print > (ENVIRON["TMPDIR_SPLIT"] "/crd-1.yaml") }
-n == 2 { print > (ENVIRON["TMPDIR_SPLIT"] "/crd-2.yaml") }
+n > 0 { print > (ENVIRON["TMPDIR_SPLIT"] "/crd-" n ".yaml") }
 ' "$TMPFILE"
 
 SERVARRAPP_CRD="$CRD_CHART_DIR/servarrapp-crd.yaml"
 MEDIASTACK_CRD="$CRD_CHART_DIR/mediastack-crd.yaml"
+MAINTENANCEWINDOW_CRD="$CRD_CHART_DIR/maintenancewindow-crd.yaml"
+SERVARRAPPSET_CRD="$CRD_CHART_DIR/servarrappset-crd.yaml"
+SERVARRCREDENTIAL_CRD="$CRD_CHART_DIR/servarrcredential-crd.yaml"
+SERVARRPOLICY_CRD="$CRD_CHART_DIR/servarrpolicy-crd.yaml"
+SERVARRINVENTORY_CRD="$CRD_CHART_DIR/servarrinventory-crd.yaml"
 
 for f in "$TMPDIR_SPLIT"/crd-*.yaml; do
     [ -s "$f" ] || continue
@@ -41,6 +45,26 @@ for f in "$TMPDIR_SPLIT"/crd-*.yaml; do
             cp -f "$f" "$MEDIASTACK_CRD"
             echo "Generated mediastack-crd.yaml"
             ;;
+        maintenancewindows.servarr.dev)
+            cp -f "$f" "$MAINTENANCEWINDOW_CRD"
+            echo "Generated maintenancewindow-crd.yaml"
+            ;;
+        servarrappsets.servarr.dev)
+            cp -f "$f" "$SERVARRAPPSET_CRD"
+            echo "Generated servarrappset-crd.yaml"
+            ;;
+        servarrcredentials.servarr.dev)
+            cp -f "$f" "$SERVARRCREDENTIAL_CRD"
+            echo "Generated servarrcredential-crd.yaml"
+            ;;
+        servarrpolicies.servarr.dev)
+            cp -f "$f" "$SERVARRPOLICY_CRD"
+            echo "Generated servarrpolicy-crd.yaml"
+            ;;
+        servarrinventories.servarr.dev)
+            cp -f "$f" "$SERVARRINVENTORY_CRD"
+            echo "Generated servarrinventory-crd.yaml"
+            ;;
         *)
             echo "Warning: unknown CRD '$name'" >&2
             ;;